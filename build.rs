@@ -1,6 +1,24 @@
+use std::process::Command;
 use std::time::SystemTime;
 
+fn git_commit() -> String {
+    if let Ok(commit) = std::env::var("GIT_COMMIT") {
+        return commit;
+    }
+
+    Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_string())
+        .filter(|commit| !commit.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
 fn main() {
     let now = httpdate::fmt_http_date(SystemTime::now());
     println!("cargo:rustc-env=BUILD_DATE={now}");
+    println!("cargo:rustc-env=GIT_COMMIT={}", git_commit());
 }
@@ -0,0 +1,14 @@
+-- migrations/0006_create_admin_bootstrap.sql
+-- Singleton marker claimed by the first registration. Claiming the row and
+-- inserting the admin happen in one statement, so concurrent sign-ups on a
+-- fresh instance cannot both be promoted.
+CREATE TABLE IF NOT EXISTS admin_bootstrap (
+    singleton BOOLEAN PRIMARY KEY DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT admin_bootstrap_singleton_chk CHECK (singleton)
+);
+
+-- Instances that already have users are considered bootstrapped.
+INSERT INTO admin_bootstrap (singleton, created_at)
+SELECT TRUE, MIN(created_at) FROM users HAVING COUNT(1) > 0
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,25 @@
+-- migrations/0007_username_case_insensitive.sql
+-- Usernames must be unique regardless of case. Refuse to migrate while
+-- case-only duplicates exist so operators can resolve them explicitly.
+DO $$
+DECLARE
+    conflicts TEXT;
+BEGIN
+    SELECT string_agg(names, '; ')
+      INTO conflicts
+      FROM (
+            SELECT string_agg(username::text, ', ' ORDER BY id) AS names
+              FROM users
+             GROUP BY lower(username::text)
+            HAVING COUNT(1) > 1
+      ) AS dup;
+
+    IF conflicts IS NOT NULL THEN
+        RAISE EXCEPTION 'case-insensitive username conflicts must be resolved before migrating: %', conflicts;
+    END IF;
+END;
+$$;
+
+-- No-op on schemas created by 0001; repairs deployments where the column
+-- was changed to plain TEXT so the existing unique constraint folds case.
+ALTER TABLE users ALTER COLUMN username TYPE CITEXT;
@@ -0,0 +1,4 @@
+-- migrations/0008_index_audit_logs_action.sql
+-- Security incident listings (e.g. refresh-token reuse) filter audit logs by
+-- action and page through them newest first.
+CREATE INDEX idx_audit_logs_action ON audit_logs(action, created_at DESC, id DESC);
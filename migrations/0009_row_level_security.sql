@@ -0,0 +1,58 @@
+-- migrations/0009_row_level_security.sql
+-- Row-level security for article data. The application passes the acting
+-- user through the transaction-local `app.current_user_id` setting (and the
+-- tenant through `app.current_site_id`). Policies are enabled but not forced,
+-- so the table owner bypasses them; run the application as a separate,
+-- non-owner role to have them enforced.
+
+CREATE OR REPLACE FUNCTION app_current_user_id()
+RETURNS BIGINT AS $$
+    SELECT NULLIF(current_setting('app.current_user_id', TRUE), '')::BIGINT;
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION app_current_site_id()
+RETURNS TEXT AS $$
+    SELECT NULLIF(current_setting('app.current_site_id', TRUE), '');
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION app_current_user_is_admin()
+RETURNS BOOLEAN AS $$
+    SELECT EXISTS (
+        SELECT 1 FROM users
+        WHERE id = app_current_user_id() AND role = 'admin' AND is_active
+    );
+$$ LANGUAGE sql STABLE;
+
+ALTER TABLE articles ENABLE ROW LEVEL SECURITY;
+
+-- Anonymous requests only see published articles; authenticated users may
+-- see drafts, matching the `articles:view:drafts` capability of every role.
+CREATE POLICY articles_select ON articles
+    FOR SELECT
+    USING (published OR app_current_user_id() IS NOT NULL);
+
+CREATE POLICY articles_insert ON articles
+    FOR INSERT
+    WITH CHECK (author_id = app_current_user_id() OR app_current_user_is_admin());
+
+CREATE POLICY articles_update ON articles
+    FOR UPDATE
+    USING (author_id = app_current_user_id() OR app_current_user_is_admin());
+
+CREATE POLICY articles_delete ON articles
+    FOR DELETE
+    USING (author_id = app_current_user_id() OR app_current_user_is_admin());
+
+ALTER TABLE article_revisions ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY article_revisions_select ON article_revisions
+    FOR SELECT
+    USING (author_id = app_current_user_id() OR app_current_user_is_admin());
+
+CREATE POLICY article_revisions_insert ON article_revisions
+    FOR INSERT
+    WITH CHECK (
+        edited_by = app_current_user_id()
+        OR author_id = app_current_user_id()
+        OR app_current_user_is_admin()
+    );
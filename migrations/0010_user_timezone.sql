@@ -0,0 +1,5 @@
+-- migrations/0010_user_timezone.sql
+-- Per-user display time zone preference (IANA name, e.g. 'Asia/Tokyo').
+-- NULL means the client should fall back to UTC.
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS timezone TEXT;
@@ -0,0 +1,4 @@
+-- migrations/0011_user_password_reset.sql
+-- Imported accounts may be required to choose a new password.
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS password_reset_required BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,12 @@
+-- migrations/0012_federated_identities.sql
+-- Accounts at upstream OIDC providers linked to local users. `subject` is
+-- the provider's stable `sub` claim; a user links at most one account per
+-- provider.
+CREATE TABLE IF NOT EXISTS federated_identities (
+    provider TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT federated_identities_pkey PRIMARY KEY (provider, subject),
+    CONSTRAINT federated_identities_user_provider_key UNIQUE (user_id, provider)
+);
@@ -0,0 +1,8 @@
+-- migrations/0013_scheduler_locks.sql
+-- Locks that keep instances from running the same scheduled job at once.
+-- A row whose `expires_at` has passed is free to take over.
+CREATE TABLE IF NOT EXISTS scheduler_locks (
+    name TEXT PRIMARY KEY,
+    token TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,15 @@
+-- migrations/0014_job_runs.sql
+-- One row per run of a scheduled job, scheduled or started by an admin.
+-- `finished_at` and `outcome` stay NULL while the run is in progress.
+CREATE TABLE IF NOT EXISTS job_runs (
+    id BIGSERIAL PRIMARY KEY,
+    job TEXT NOT NULL,
+    trigger TEXT NOT NULL,
+    triggered_by BIGINT REFERENCES users(id) ON DELETE SET NULL,
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ,
+    outcome TEXT,
+    error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS job_runs_job_started_idx ON job_runs (job, started_at DESC, id DESC);
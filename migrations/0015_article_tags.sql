@@ -0,0 +1,7 @@
+-- migrations/0015_article_tags.sql
+-- Normalized tags per article; the GIN index serves `tags @> ARRAY[...]`
+-- filters on the article listing.
+ALTER TABLE articles
+ADD COLUMN IF NOT EXISTS tags TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX IF NOT EXISTS idx_articles_tags ON articles USING GIN (tags);
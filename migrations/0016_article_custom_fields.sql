@@ -0,0 +1,14 @@
+-- migrations/0016_article_custom_fields.sql
+-- Site-defined article metadata. Each tenant schema has its own
+-- definitions; values live on the article as a JSON object keyed by field
+-- name and are validated by the application on save.
+ALTER TABLE articles
+ADD COLUMN IF NOT EXISTS custom_fields JSONB NOT NULL DEFAULT '{}';
+
+CREATE TABLE IF NOT EXISTS custom_field_definitions (
+    name TEXT PRIMARY KEY,
+    field_type TEXT NOT NULL CHECK (field_type IN ('text', 'number', 'boolean', 'date')),
+    required BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
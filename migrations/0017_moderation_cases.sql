@@ -0,0 +1,31 @@
+-- Reader reports about articles, grouped into one open case per article
+-- until a moderator resolves it.
+CREATE TABLE IF NOT EXISTS moderation_cases (
+    id BIGSERIAL PRIMARY KEY,
+    article_id BIGINT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'resolved')),
+    resolution TEXT CHECK (resolution IN ('dismiss', 'unpublish', 'ban_author')),
+    resolved_by BIGINT REFERENCES users(id),
+    resolution_note TEXT,
+    opened_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ,
+    CHECK ((status = 'open') = (resolution IS NULL))
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS moderation_cases_open_article_idx
+    ON moderation_cases (article_id)
+    WHERE status = 'open';
+
+CREATE INDEX IF NOT EXISTS moderation_cases_status_idx
+    ON moderation_cases (status, id);
+
+CREATE TABLE IF NOT EXISTS moderation_reports (
+    id BIGSERIAL PRIMARY KEY,
+    case_id BIGINT NOT NULL REFERENCES moderation_cases(id) ON DELETE CASCADE,
+    reason TEXT NOT NULL CHECK (reason IN ('spam', 'harassment', 'misinformation', 'illegal', 'other')),
+    details TEXT,
+    reported_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS moderation_reports_case_idx
+    ON moderation_reports (case_id, id);
@@ -0,0 +1,18 @@
+-- Allow/deny rules for sensitive route groups, matched against the client
+-- address (cidr) or its autonomous system (asn). Lower priorities are tried
+-- first and the first match decides.
+CREATE TABLE IF NOT EXISTS access_rules (
+    id BIGSERIAL PRIMARY KEY,
+    route_group TEXT NOT NULL CHECK (route_group IN ('admin', 'registration')),
+    action TEXT NOT NULL CHECK (action IN ('allow', 'deny')),
+    cidr TEXT,
+    asn BIGINT CHECK (asn BETWEEN 0 AND 4294967295),
+    priority INTEGER NOT NULL DEFAULT 100,
+    note TEXT,
+    created_by BIGINT REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((cidr IS NULL) <> (asn IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS access_rules_group_priority_idx
+    ON access_rules (route_group, priority, id);
@@ -0,0 +1,14 @@
+-- Temporary rules, such as the blocks the honeypot places on scanners, and
+-- a site-wide route group checked on every request.
+ALTER TABLE access_rules
+    ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ;
+
+ALTER TABLE access_rules
+    DROP CONSTRAINT IF EXISTS access_rules_route_group_check;
+ALTER TABLE access_rules
+    ADD CONSTRAINT access_rules_route_group_check
+    CHECK (route_group IN ('site', 'admin', 'registration'));
+
+CREATE INDEX IF NOT EXISTS access_rules_expires_at_idx
+    ON access_rules (expires_at)
+    WHERE expires_at IS NOT NULL;
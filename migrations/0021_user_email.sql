@@ -0,0 +1,22 @@
+-- migrations/0021_user_email.sql
+-- Optional email address per user, proven by a mailed verification token.
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS email CITEXT,
+    ADD COLUMN IF NOT EXISTS email_verified BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE UNIQUE INDEX IF NOT EXISTS users_email_key
+    ON users (email)
+    WHERE email IS NOT NULL;
+
+-- Single-use tokens mailed for password resets and email verification.
+-- Only a SHA-256 hash of each token is stored.
+CREATE TABLE IF NOT EXISTS user_tokens (
+    token_hash TEXT PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    purpose TEXT NOT NULL CHECK (purpose IN ('password_reset', 'email_verification')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS user_tokens_user_idx
+    ON user_tokens (user_id, purpose);
@@ -0,0 +1,6 @@
+-- Articles whose authors asked not to announce them on the syndication
+-- targets (Mastodon, Bluesky, Slack, ...) when they are published.
+CREATE TABLE IF NOT EXISTS article_syndication_opt_outs (
+    article_id BIGINT PRIMARY KEY REFERENCES articles(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
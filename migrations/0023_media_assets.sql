@@ -0,0 +1,14 @@
+-- Files uploaded for use in articles. The bytes live in blob storage (a
+-- local directory or an S3-compatible bucket) under storage_key.
+CREATE TABLE IF NOT EXISTS media_assets (
+    id BIGSERIAL PRIMARY KEY,
+    uploaded_by BIGINT NOT NULL REFERENCES users(id),
+    file_name TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes > 0),
+    storage_key TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS media_assets_uploaded_by_idx
+    ON media_assets (uploaded_by, id);
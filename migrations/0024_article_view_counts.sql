@@ -0,0 +1,7 @@
+-- migrations/0024_article_view_counts.sql
+-- Read counts flushed in batches from the view counter; the index serves
+-- `?sort=popular` listings ordered by `(view_count, id)`.
+ALTER TABLE articles
+ADD COLUMN IF NOT EXISTS view_count BIGINT NOT NULL DEFAULT 0 CHECK (view_count >= 0);
+
+CREATE INDEX IF NOT EXISTS idx_articles_popularity ON articles (view_count DESC, id DESC);
@@ -0,0 +1,17 @@
+-- migrations/0025_article_search_language.sql
+-- Index each article with the text search configuration it was written
+-- under (`SEARCH_LANGUAGE`), keeping titles weighted above bodies. Startup
+-- re-indexes rows written under another configuration.
+ALTER TABLE articles
+ADD COLUMN IF NOT EXISTS search_language REGCONFIG NOT NULL DEFAULT 'simple';
+
+DROP INDEX IF EXISTS idx_articles_search;
+ALTER TABLE articles DROP COLUMN IF EXISTS search;
+ALTER TABLE articles
+ADD COLUMN search tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector(search_language, coalesce(title, '')), 'A') ||
+        setweight(to_tsvector(search_language, coalesce(body,  '')), 'B')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_articles_search ON articles USING GIN (search);
@@ -0,0 +1,18 @@
+-- Operator-defined roles. A user given one is issued its capabilities,
+-- written as `resource:action`, instead of those of their built-in role.
+CREATE TABLE IF NOT EXISTS custom_roles (
+    name TEXT PRIMARY KEY,
+    description TEXT,
+    capabilities TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- At most one custom role per user; deleting the role takes it away.
+CREATE TABLE IF NOT EXISTS user_custom_roles (
+    user_id BIGINT PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    role_name TEXT NOT NULL REFERENCES custom_roles(name) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS user_custom_roles_role_idx
+    ON user_custom_roles (role_name);
@@ -0,0 +1,6 @@
+-- migrations/0028_article_visibility.sql
+-- Who may find and read an article. Listings without drafts only show public
+-- ones; listings with drafts are for staff and show every article.
+ALTER TABLE articles
+ADD COLUMN IF NOT EXISTS visibility TEXT NOT NULL DEFAULT 'public'
+    CONSTRAINT articles_visibility_chk CHECK (visibility IN ('public', 'unlisted', 'private'));
@@ -0,0 +1,40 @@
+-- migrations/0029_article_contributors.sql
+-- Users other than the author who may edit an article.
+CREATE TABLE IF NOT EXISTS article_contributors (
+    article_id BIGINT NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL
+        CONSTRAINT article_contributors_user_id_fkey REFERENCES users(id) ON DELETE CASCADE,
+    added_by BIGINT REFERENCES users(id) ON DELETE SET NULL,
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT article_contributors_pkey PRIMARY KEY (article_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS article_contributors_user_idx
+    ON article_contributors (user_id);
+
+CREATE OR REPLACE FUNCTION app_current_user_contributes_to(target BIGINT)
+RETURNS BOOLEAN AS $$
+    SELECT EXISTS (
+        SELECT 1 FROM article_contributors
+        WHERE article_id = target AND user_id = app_current_user_id()
+    );
+$$ LANGUAGE sql STABLE;
+
+-- Contributors edit articles and read their history like authors do.
+DROP POLICY IF EXISTS articles_update ON articles;
+CREATE POLICY articles_update ON articles
+    FOR UPDATE
+    USING (
+        author_id = app_current_user_id()
+        OR app_current_user_is_admin()
+        OR app_current_user_contributes_to(id)
+    );
+
+DROP POLICY IF EXISTS article_revisions_select ON article_revisions;
+CREATE POLICY article_revisions_select ON article_revisions
+    FOR SELECT
+    USING (
+        author_id = app_current_user_id()
+        OR app_current_user_is_admin()
+        OR app_current_user_contributes_to(article_id)
+    );
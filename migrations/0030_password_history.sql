@@ -0,0 +1,12 @@
+-- migrations/0030_password_history.sql
+-- Hashes of passwords users have replaced, so a password change can refuse
+-- going back to a recent one. Only the most recent few per user are kept.
+CREATE TABLE IF NOT EXISTS password_history (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    password_hash TEXT NOT NULL,
+    replaced_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS password_history_user_idx
+    ON password_history (user_id, id DESC);
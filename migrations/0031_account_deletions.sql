@@ -0,0 +1,14 @@
+-- migrations/0031_account_deletions.sql
+-- Accounts their owners asked to delete. The user stays disabled until
+-- `purge_after`, when the account is anonymized and its articles move to
+-- `transfer_to` (or stay with the anonymized account). Admins may cancel
+-- before then.
+CREATE TABLE IF NOT EXISTS account_deletions (
+    user_id BIGINT PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    requested_at TIMESTAMPTZ NOT NULL,
+    purge_after TIMESTAMPTZ NOT NULL,
+    transfer_to BIGINT REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS account_deletions_purge_after_idx
+    ON account_deletions (purge_after);
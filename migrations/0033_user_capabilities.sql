@@ -0,0 +1,7 @@
+-- Capabilities given to individual users, written as `resource:action`.
+-- Their tokens carry them on top of those of their role or custom role.
+CREATE TABLE IF NOT EXISTS user_capabilities (
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    capability TEXT NOT NULL,
+    PRIMARY KEY (user_id, capability)
+);
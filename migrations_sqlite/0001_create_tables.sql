@@ -0,0 +1,181 @@
+-- SQLite schema equivalent to the Postgres migrations up to
+-- 0025_article_search_language. Timestamps are RFC 3339 text written by the
+-- application, so they sort chronologically; tags are JSON arrays and custom
+-- fields JSON objects. There is no row-level security, text search index or
+-- scheduler state: embedded instances run one process and search by substring.
+
+CREATE TABLE users (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    username TEXT NOT NULL COLLATE NOCASE,
+    password_hash TEXT NOT NULL,
+    role TEXT NOT NULL CHECK (role IN ('admin', 'author')),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TEXT NOT NULL,
+    timezone TEXT,
+    password_reset_required BOOLEAN NOT NULL DEFAULT FALSE,
+    email TEXT COLLATE NOCASE,
+    email_verified BOOLEAN NOT NULL DEFAULT FALSE,
+    CONSTRAINT users_username_key UNIQUE (username)
+);
+
+CREATE INDEX idx_users_created ON users (created_at DESC, id DESC);
+
+CREATE UNIQUE INDEX users_email_key
+    ON users (email)
+    WHERE email IS NOT NULL;
+
+CREATE TABLE admin_bootstrap (
+    singleton BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (singleton),
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE federated_identities (
+    provider TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (provider, subject),
+    UNIQUE (user_id, provider)
+);
+
+CREATE TABLE articles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    title TEXT NOT NULL,
+    slug TEXT NOT NULL COLLATE NOCASE,
+    body TEXT NOT NULL,
+    tags TEXT NOT NULL DEFAULT '[]',
+    custom_fields TEXT NOT NULL DEFAULT '{}',
+    published BOOLEAN NOT NULL DEFAULT FALSE,
+    published_at TEXT,
+    author_id INTEGER NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    view_count INTEGER NOT NULL DEFAULT 0 CHECK (view_count >= 0),
+    CONSTRAINT articles_slug_key UNIQUE (slug),
+    CONSTRAINT articles_published_requires_timestamp_chk CHECK (
+        published = FALSE OR published_at IS NOT NULL
+    )
+);
+
+CREATE INDEX idx_articles_author_id ON articles (author_id);
+CREATE INDEX idx_articles_pub_created ON articles (published, created_at DESC, id DESC);
+CREATE INDEX idx_articles_created ON articles (created_at DESC, id DESC);
+CREATE INDEX idx_articles_popularity ON articles (view_count DESC, id DESC);
+
+CREATE TABLE article_revisions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    version INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    body TEXT NOT NULL,
+    published BOOLEAN NOT NULL,
+    published_at TEXT,
+    author_id INTEGER NOT NULL REFERENCES users(id),
+    edited_by INTEGER REFERENCES users(id),
+    recorded_at TEXT NOT NULL,
+    UNIQUE (article_id, version)
+);
+
+CREATE TABLE custom_field_definitions (
+    name TEXT PRIMARY KEY,
+    field_type TEXT NOT NULL CHECK (field_type IN ('text', 'number', 'boolean', 'date')),
+    required BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE TABLE article_syndication_opt_outs (
+    article_id INTEGER PRIMARY KEY REFERENCES articles(id) ON DELETE CASCADE
+);
+
+CREATE TABLE audit_logs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER REFERENCES users(id),
+    action TEXT NOT NULL,
+    resource_type TEXT NOT NULL,
+    resource_id INTEGER,
+    details TEXT,
+    ip_address TEXT,
+    user_agent TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_audit_logs_user_id ON audit_logs (user_id);
+CREATE INDEX idx_audit_logs_resource ON audit_logs (resource_type, resource_id);
+CREATE INDEX idx_audit_logs_created_at ON audit_logs (created_at DESC, id DESC);
+CREATE INDEX idx_audit_logs_action ON audit_logs (action, created_at DESC, id DESC);
+
+CREATE TABLE moderation_cases (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'resolved')),
+    resolution TEXT CHECK (resolution IN ('dismiss', 'unpublish', 'ban_author')),
+    resolved_by INTEGER REFERENCES users(id),
+    resolution_note TEXT,
+    opened_at TEXT NOT NULL,
+    resolved_at TEXT,
+    CHECK ((status = 'open') = (resolution IS NULL))
+);
+
+CREATE UNIQUE INDEX moderation_cases_open_article_idx
+    ON moderation_cases (article_id)
+    WHERE status = 'open';
+
+CREATE INDEX moderation_cases_status_idx ON moderation_cases (status, id);
+
+CREATE TABLE moderation_reports (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    case_id INTEGER NOT NULL REFERENCES moderation_cases(id) ON DELETE CASCADE,
+    reason TEXT NOT NULL CHECK (reason IN ('spam', 'harassment', 'misinformation', 'illegal', 'other')),
+    details TEXT,
+    reported_at TEXT NOT NULL
+);
+
+CREATE INDEX moderation_reports_case_idx ON moderation_reports (case_id, id);
+
+CREATE TABLE user_blocks (
+    blocker_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    blocked_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (blocker_id, blocked_id),
+    CHECK (blocker_id <> blocked_id)
+);
+
+CREATE INDEX user_blocks_blocked_idx ON user_blocks (blocked_id);
+
+CREATE TABLE access_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    route_group TEXT NOT NULL CHECK (route_group IN ('site', 'admin', 'registration')),
+    action TEXT NOT NULL CHECK (action IN ('allow', 'deny')),
+    cidr TEXT,
+    asn INTEGER CHECK (asn BETWEEN 0 AND 4294967295),
+    priority INTEGER NOT NULL DEFAULT 100,
+    note TEXT,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT,
+    CHECK ((cidr IS NULL) <> (asn IS NULL))
+);
+
+CREATE INDEX access_rules_group_priority_idx ON access_rules (route_group, priority, id);
+
+CREATE TABLE user_tokens (
+    token_hash TEXT PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    purpose TEXT NOT NULL CHECK (purpose IN ('password_reset', 'email_verification')),
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
+
+CREATE INDEX user_tokens_user_idx ON user_tokens (user_id, purpose);
+
+CREATE TABLE media_assets (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uploaded_by INTEGER NOT NULL REFERENCES users(id),
+    file_name TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL CHECK (size_bytes > 0),
+    storage_key TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX media_assets_uploaded_by_idx ON media_assets (uploaded_by, id);
@@ -0,0 +1,13 @@
+-- Operator-defined roles; capabilities is a JSON array of `resource:action`.
+CREATE TABLE custom_roles (
+    name TEXT PRIMARY KEY,
+    description TEXT,
+    capabilities TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE user_custom_roles (
+    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    role_name TEXT NOT NULL REFERENCES custom_roles(name) ON DELETE CASCADE
+);
@@ -0,0 +1,5 @@
+-- Who may find and read an article. Listings without drafts only show public
+-- ones; listings with drafts are for staff and show every article.
+ALTER TABLE articles
+ADD COLUMN visibility TEXT NOT NULL DEFAULT 'public'
+    CHECK (visibility IN ('public', 'unlisted', 'private'));
@@ -0,0 +1,10 @@
+-- Users other than the author who may edit an article.
+CREATE TABLE article_contributors (
+    article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    added_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    added_at TEXT NOT NULL,
+    PRIMARY KEY (article_id, user_id)
+);
+
+CREATE INDEX article_contributors_user_idx ON article_contributors (user_id);
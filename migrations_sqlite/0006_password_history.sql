@@ -0,0 +1,10 @@
+-- Hashes of passwords users have replaced, so a password change can refuse
+-- going back to a recent one. Only the most recent few per user are kept.
+CREATE TABLE password_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    password_hash TEXT NOT NULL,
+    replaced_at TEXT NOT NULL
+);
+
+CREATE INDEX password_history_user_idx ON password_history (user_id, id DESC);
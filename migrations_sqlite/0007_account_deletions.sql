@@ -0,0 +1,12 @@
+-- Accounts their owners asked to delete. The user stays disabled until
+-- `purge_after`, when the account is anonymized and its articles move to
+-- `transfer_to` (or stay with the anonymized account). Admins may cancel
+-- before then.
+CREATE TABLE account_deletions (
+    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    requested_at TEXT NOT NULL,
+    purge_after TEXT NOT NULL,
+    transfer_to INTEGER REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX account_deletions_purge_after_idx ON account_deletions (purge_after);
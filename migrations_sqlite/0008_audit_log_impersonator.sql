@@ -0,0 +1,2 @@
+-- Admin who acted as `user_id` through an impersonation token.
+ALTER TABLE audit_logs ADD COLUMN impersonator_id INTEGER REFERENCES users(id);
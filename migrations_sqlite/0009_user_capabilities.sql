@@ -0,0 +1,6 @@
+-- Capabilities given to individual users, written as `resource:action`.
+CREATE TABLE user_capabilities (
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    capability TEXT NOT NULL,
+    PRIMARY KEY (user_id, capability)
+);
@@ -0,0 +1,277 @@
+// src/application/commands/articles/bulk.rs
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{ArticleChange, ArticleCommandService, capability::ensure_capability};
+use crate::{
+    application::{
+        AuthenticatedUser, BulkItemResult, BulkItemStatus, BulkResult,
+        error::{AppError, AppResult},
+        ports::command_journal::JournaledCommand,
+        queries::batch::normalize_ids,
+        services::AuditEvent,
+    },
+    domain::{
+        Article, ArticleId, ArticleUpdate, Tag,
+        article::specifications::{ArticleSpecification, CanDeleteArticleSpec},
+    },
+};
+
+/// What a bulk request does to each listed article.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum BulkArticleOperation {
+    /// Needs `articles:publish`.
+    Publish,
+    /// Needs `articles:publish`.
+    Unpublish,
+    /// Needs the right to delete each article, as for a single delete.
+    Delete,
+    /// Add `tags` to each article, keeping the ones it already carries.
+    /// Needs the right to update each article.
+    Tag { tags: Vec<String> },
+}
+
+impl BulkArticleOperation {
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Publish => "publish",
+            Self::Unpublish => "unpublish",
+            Self::Delete => "delete",
+            Self::Tag { .. } => "tag",
+        }
+    }
+}
+
+pub struct BulkArticleCommand {
+    pub operation: BulkArticleOperation,
+    pub ids: Vec<i64>,
+}
+
+/// Results so far, by article id. Ids without one were not found.
+type Outcomes = HashMap<i64, BulkItemResult>;
+
+fn outcome(outcomes: &mut Outcomes, id: ArticleId, status: BulkItemStatus, error: Option<String>) {
+    let id = i64::from(id);
+    outcomes.insert(id, BulkItemResult { id, status, error });
+}
+
+impl ArticleCommandService {
+    /// Apply one operation to up to [`MAX_BATCH_IDS`](crate::application::MAX_BATCH_IDS)
+    /// articles, reporting what happened to each.
+    ///
+    /// Articles are loaded, written, deleted and given revisions a batch at a
+    /// time rather than one by one; the revisions are written after the
+    /// batch, outside any unit of work. Missing articles, and those the actor
+    /// may not change, are reported per item and do not stop the rest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if too many ids are given, a publish state change
+    /// lacks `articles:publish`, the tags are invalid, or persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn bulk_articles(
+        &self,
+        actor: &AuthenticatedUser,
+        command: BulkArticleCommand,
+    ) -> AppResult<BulkResult> {
+        self.read_only.ensure_writable()?;
+        let journaled = self.journal.capture(|| JournaledCommand::BulkArticles {
+            operation: command.operation.clone(),
+            ids: command.ids.clone(),
+        });
+        let result = self.bulk_articles_inner(actor, command).await;
+        self.journal
+            .record(self.clock.now(), Some(actor), journaled, &result, |_| None)
+            .await;
+        result
+    }
+
+    async fn bulk_articles_inner(
+        &self,
+        actor: &AuthenticatedUser,
+        command: BulkArticleCommand,
+    ) -> AppResult<BulkResult> {
+        let requested = normalize_ids(&command.ids)?;
+        let tags = match &command.operation {
+            BulkArticleOperation::Publish | BulkArticleOperation::Unpublish => {
+                ensure_capability(actor, "articles", "publish")?;
+                Vec::new()
+            }
+            BulkArticleOperation::Delete => Vec::new(),
+            BulkArticleOperation::Tag { tags } => {
+                let tags = Tag::parse_list(tags)?;
+                if tags.is_empty() {
+                    return Err(AppError::validation("at least one tag is required"));
+                }
+                tags
+            }
+        };
+
+        let ids: Vec<ArticleId> = requested
+            .iter()
+            .filter_map(|id| ArticleId::new(*id).ok())
+            .collect();
+        let found = self.read_repo.find_by_ids(&ids).await?;
+
+        let mut outcomes = Outcomes::with_capacity(requested.len());
+        let changed = if command.operation == BulkArticleOperation::Delete {
+            self.bulk_delete(actor, found, &mut outcomes).await?
+        } else {
+            self.bulk_update(actor, &command.operation, &tags, found, &mut outcomes)
+                .await?
+        };
+
+        if !changed.is_empty() {
+            self.audit
+                .record(
+                    Some(actor.id),
+                    AuditEvent::new("article.bulk", "article", None).with_details(json!({
+                        "operation": command.operation.as_str(),
+                        "ids": changed,
+                    })),
+                )
+                .await;
+        }
+
+        let results = requested
+            .into_iter()
+            .map(|id| {
+                outcomes.remove(&id).unwrap_or(BulkItemResult {
+                    id,
+                    status: BulkItemStatus::NotFound,
+                    error: None,
+                })
+            })
+            .collect();
+        Ok(BulkResult::new(results))
+    }
+
+    /// Delete the `found` articles the actor may delete, returning their ids.
+    async fn bulk_delete(
+        &self,
+        actor: &AuthenticatedUser,
+        found: Vec<Article>,
+        outcomes: &mut Outcomes,
+    ) -> AppResult<Vec<i64>> {
+        let mut targets = Vec::with_capacity(found.len());
+        for article in found {
+            if CanDeleteArticleSpec::new(&actor.capabilities, &article, actor.id).is_satisfied() {
+                targets.push(article);
+            } else {
+                outcome(
+                    outcomes,
+                    article.id,
+                    BulkItemStatus::Forbidden,
+                    Some("insufficient privileges to delete article".into()),
+                );
+            }
+        }
+        if targets.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.revision_repo
+            .append_many(&targets, Some(actor.id))
+            .await?;
+        let ids: Vec<ArticleId> = targets.iter().map(|article| article.id).collect();
+        let deleted = self.write_repo.delete_many(&ids).await?;
+
+        let mut changed = Vec::with_capacity(deleted.len());
+        for article in targets.iter().filter(|a| deleted.contains(&a.id)) {
+            outcome(outcomes, article.id, BulkItemStatus::Deleted, None);
+            self.notify(article, ArticleChange::Deleted).await;
+            changed.push(i64::from(article.id));
+        }
+        Ok(changed)
+    }
+
+    /// Publish, unpublish or tag the `found` articles, returning the ids of
+    /// the ones written.
+    async fn bulk_update(
+        &self,
+        actor: &AuthenticatedUser,
+        operation: &BulkArticleOperation,
+        tags: &[Tag],
+        found: Vec<Article>,
+        outcomes: &mut Outcomes,
+    ) -> AppResult<Vec<i64>> {
+        let now = self.clock.now();
+        let mut updates = Vec::with_capacity(found.len());
+        for mut article in found {
+            let mut update = ArticleUpdate::new(article.id, article.updated_at);
+            match operation {
+                BulkArticleOperation::Publish | BulkArticleOperation::Unpublish => {
+                    let publish = *operation == BulkArticleOperation::Publish;
+                    if article.published == publish {
+                        outcome(outcomes, article.id, BulkItemStatus::Unchanged, None);
+                        continue;
+                    }
+                    if publish {
+                        article.publish(now);
+                    } else {
+                        article.unpublish(now);
+                    }
+                    update = update.with_publish_state(article.published, article.published_at);
+                }
+                BulkArticleOperation::Tag { .. } => {
+                    if !self.can_update(actor, &article).await? {
+                        outcome(
+                            outcomes,
+                            article.id,
+                            BulkItemStatus::Forbidden,
+                            Some("insufficient privileges to update article".into()),
+                        );
+                        continue;
+                    }
+                    let merged = match Tag::parse_list(article.tags.iter().chain(tags)) {
+                        Ok(merged) => merged,
+                        Err(err) => {
+                            let error = Some(AppError::from(err).to_string());
+                            outcome(outcomes, article.id, BulkItemStatus::Invalid, error);
+                            continue;
+                        }
+                    };
+                    if merged == article.tags {
+                        outcome(outcomes, article.id, BulkItemStatus::Unchanged, None);
+                        continue;
+                    }
+                    article.set_tags(merged.clone(), now);
+                    update = update.with_tags(merged);
+                }
+                BulkArticleOperation::Delete => unreachable!("deletions are not updates"),
+            }
+            update.set_updated_at(article.updated_at);
+            outcome(
+                outcomes,
+                article.id,
+                BulkItemStatus::Conflict,
+                Some("article changed during the operation, please retry".into()),
+            );
+            updates.push(update);
+        }
+        if updates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let stored = self.write_repo.update_many(updates).await?;
+        self.revision_repo
+            .append_many(&stored, Some(actor.id))
+            .await?;
+
+        let change = match operation {
+            BulkArticleOperation::Publish => ArticleChange::Published,
+            BulkArticleOperation::Unpublish => ArticleChange::Unpublished,
+            _ => ArticleChange::Updated,
+        };
+        let mut changed = Vec::with_capacity(stored.len());
+        for article in &stored {
+            outcome(outcomes, article.id, BulkItemStatus::Updated, None);
+            self.notify(article, change).await;
+            changed.push(i64::from(article.id));
+        }
+        Ok(changed)
+    }
+}
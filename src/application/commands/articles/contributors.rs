@@ -0,0 +1,170 @@
+// src/application/commands/articles/contributors.rs
+use serde_json::json;
+
+use super::ArticleCommandService;
+use crate::{
+    application::{
+        ArticleContributorDto, AuthenticatedUser,
+        error::{AppError, AppResult},
+        ports::command_journal::JournaledCommand,
+        services::AuditEvent,
+    },
+    domain::{
+        Article, ArticleContributor, ArticleId, UserId,
+        article::specifications::{ArticleSpecification, CanUpdateArticleSpec},
+    },
+};
+
+pub struct AddContributorCommand {
+    pub article_id: i64,
+    pub user_id: i64,
+}
+
+pub struct RemoveContributorCommand {
+    pub article_id: i64,
+    pub user_id: i64,
+}
+
+impl ArticleCommandService {
+    /// Let another user edit an article alongside its author. Adding someone
+    /// who already contributes returns their existing entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an id is invalid, the article or user is missing,
+    /// the user is the article's author or has blocked the actor, the actor
+    /// may not manage the article's contributors, contributors are not
+    /// enabled, or persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn add_contributor(
+        &self,
+        actor: &AuthenticatedUser,
+        command: AddContributorCommand,
+    ) -> AppResult<ArticleContributorDto> {
+        self.read_only.ensure_writable()?;
+        let journaled = self.journal.capture(|| JournaledCommand::AddContributor {
+            article_id: command.article_id,
+            user_id: command.user_id,
+        });
+        let result = self.add_contributor_inner(actor, command).await;
+        self.journal
+            .record(self.clock.now(), Some(actor), journaled, &result, |added| {
+                Some(added.article_id)
+            })
+            .await;
+        result
+    }
+
+    async fn add_contributor_inner(
+        &self,
+        actor: &AuthenticatedUser,
+        command: AddContributorCommand,
+    ) -> AppResult<ArticleContributorDto> {
+        let article = self.find_managed_article(actor, command.article_id).await?;
+        let user_id = UserId::new(command.user_id)?;
+        if user_id == article.author_id {
+            return Err(AppError::validation(
+                "the author cannot be added as a contributor",
+            ));
+        }
+        if let Some(blocks) = &self.blocks {
+            blocks.ensure_can_interact(actor.id, user_id).await?;
+        }
+
+        let added = self
+            .contributor_repo()?
+            .add(ArticleContributor {
+                article_id: article.id,
+                user_id,
+                added_by: Some(actor.id),
+                added_at: self.clock.now(),
+            })
+            .await?;
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new(
+                    "article.contributor_add",
+                    "article",
+                    Some(command.article_id),
+                )
+                .with_details(json!({ "user_id": command.user_id })),
+            )
+            .await;
+        Ok(added.into())
+    }
+
+    /// Stop a user from editing an article they contributed to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an id is invalid, the article is missing, the user
+    /// is not one of its contributors, the actor may not manage the article's
+    /// contributors, contributors are not enabled, or persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn remove_contributor(
+        &self,
+        actor: &AuthenticatedUser,
+        command: RemoveContributorCommand,
+    ) -> AppResult<()> {
+        self.read_only.ensure_writable()?;
+        let journaled = self
+            .journal
+            .capture(|| JournaledCommand::RemoveContributor {
+                article_id: command.article_id,
+                user_id: command.user_id,
+            });
+        let article_id = command.article_id;
+        let result = self.remove_contributor_inner(actor, command).await;
+        self.journal
+            .record(self.clock.now(), Some(actor), journaled, &result, |()| {
+                Some(article_id)
+            })
+            .await;
+        result
+    }
+
+    async fn remove_contributor_inner(
+        &self,
+        actor: &AuthenticatedUser,
+        command: RemoveContributorCommand,
+    ) -> AppResult<()> {
+        let article = self.find_managed_article(actor, command.article_id).await?;
+        let user_id = UserId::new(command.user_id)?;
+        if !self.contributor_repo()?.remove(article.id, user_id).await? {
+            return Err(AppError::not_found("contributor not found"));
+        }
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new(
+                    "article.contributor_remove",
+                    "article",
+                    Some(command.article_id),
+                )
+                .with_details(json!({ "user_id": command.user_id })),
+            )
+            .await;
+        Ok(())
+    }
+
+    /// The article, if `actor` may choose who contributes to it: its author
+    /// or a holder of `articles:update:any`, but not other contributors.
+    async fn find_managed_article(
+        &self,
+        actor: &AuthenticatedUser,
+        article_id: i64,
+    ) -> AppResult<Article> {
+        let article = self
+            .read_repo
+            .find_by_id(ArticleId::new(article_id)?)
+            .await?
+            .ok_or_else(|| AppError::not_found("article not found"))?;
+        if !CanUpdateArticleSpec::new(&actor.capabilities, &article, actor.id).is_satisfied() {
+            return Err(AppError::forbidden(
+                "insufficient privileges to manage contributors",
+            ));
+        }
+        Ok(article)
+    }
+}
@@ -1,14 +1,21 @@
 // src/application/commands/articles/create.rs
-use super::{ArticleCommandService, capability::ensure_capability};
+use super::{ArticleChange, ArticleCommandService, RevisedWrite, capability::ensure_capability};
 use crate::{
-    application::{ArticleDto, AuthenticatedUser, error::AppResult},
-    domain::{ArticleBody, ArticleTitle, NewArticle},
+    application::{
+        ArticleDto, AuthenticatedUser, error::AppResult, ports::command_journal::JournaledCommand,
+        services::AuditEvent,
+    },
+    domain::{ArticleBody, ArticleTitle, ArticleVisibility, CustomFields, NewArticle, Tag},
 };
 
 pub struct CreateArticleCommand {
     pub title: String,
     pub body: String,
     pub publish: bool,
+    pub tags: Vec<String>,
+    /// Values for the site's custom fields.
+    pub custom_fields: CustomFields,
+    pub visibility: ArticleVisibility,
 }
 
 impl CreateArticleCommand {
@@ -23,6 +30,9 @@ pub struct CreateArticleCommandBuilder {
     title: Option<String>,
     body: Option<String>,
     publish: bool,
+    tags: Vec<String>,
+    custom_fields: CustomFields,
+    visibility: ArticleVisibility,
 }
 
 impl CreateArticleCommandBuilder {
@@ -41,6 +51,25 @@ impl CreateArticleCommandBuilder {
         self
     }
 
+    pub fn tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn custom_fields(mut self, custom_fields: CustomFields) -> Self {
+        self.custom_fields = custom_fields;
+        self
+    }
+
+    pub const fn visibility(mut self, visibility: ArticleVisibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
     /// Finalize the command builder.
     ///
     /// # Errors
@@ -51,6 +80,9 @@ impl CreateArticleCommandBuilder {
             title: self.title.ok_or("title is required")?,
             body: self.body.ok_or("body is required")?,
             publish: self.publish,
+            tags: self.tags,
+            custom_fields: self.custom_fields,
+            visibility: self.visibility,
         })
     }
 }
@@ -60,17 +92,48 @@ impl ArticleCommandService {
     ///
     /// # Errors
     ///
-    /// Returns an error if the actor lacks `articles:create`, the title or
-    /// body is invalid, slug generation fails, or persistence fails.
+    /// Returns an error if the actor lacks `articles:create`, the title, body,
+    /// tags or custom fields are invalid, slug generation fails, or
+    /// persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn create_article(
         &self,
         actor: &AuthenticatedUser,
         command: CreateArticleCommand,
+    ) -> AppResult<ArticleDto> {
+        self.read_only.ensure_writable()?;
+        let journaled = self.journal.capture(|| JournaledCommand::CreateArticle {
+            title: command.title.clone(),
+            body: command.body.clone(),
+            publish: command.publish,
+            tags: command.tags.clone(),
+            custom_fields: command.custom_fields.clone(),
+            visibility: command.visibility,
+        });
+        let result = self.create_article_inner(actor, command).await;
+        self.journal
+            .record(
+                self.clock.now(),
+                Some(actor),
+                journaled,
+                &result,
+                |article| Some(article.id),
+            )
+            .await;
+        result
+    }
+
+    async fn create_article_inner(
+        &self,
+        actor: &AuthenticatedUser,
+        command: CreateArticleCommand,
     ) -> AppResult<ArticleDto> {
         ensure_capability(actor, "articles", "create")?;
 
         let title = ArticleTitle::new(command.title)?;
         let body = ArticleBody::new(command.body)?;
+        let tags = Tag::parse_list(command.tags)?;
+        self.validate_custom_fields(&command.custom_fields).await?;
         let now = self.clock.now();
 
         let slug = self.slug_service.generate_unique_slug(&title, None).await?;
@@ -79,15 +142,29 @@ impl ArticleCommandService {
             title,
             slug,
             body,
+            tags,
+            custom_fields: command.custom_fields,
             published: command.publish,
             published_at: if command.publish { Some(now) } else { None },
+            visibility: command.visibility,
             author_id: actor.id,
             created_at: now,
             updated_at: now,
         };
 
-        let created = self.write_repo.insert(new_article).await?;
-        self.revision_repo.append(&created, Some(actor.id)).await?;
+        let created = self
+            .write_revised(RevisedWrite::Insert(new_article), actor.id)
+            .await?;
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("article.create", "article", Some(i64::from(created.id))),
+            )
+            .await;
+        self.notify(&created, ArticleChange::Created).await;
+        if created.published {
+            self.notify(&created, ArticleChange::Published).await;
+        }
         Ok(created.into())
     }
 }
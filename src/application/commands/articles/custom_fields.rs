@@ -0,0 +1,131 @@
+// src/application/commands/articles/custom_fields.rs
+use std::sync::Arc;
+
+use serde_json::json;
+
+use super::{ArticleCommandService, capability::ensure_capability};
+use crate::{
+    application::{
+        AuthenticatedUser, CustomFieldDefinitionDto,
+        error::{AppError, AppResult},
+        ports::command_journal::JournaledCommand,
+        services::AuditEvent,
+    },
+    domain::{CustomFieldDefinition, CustomFieldRepository},
+};
+
+pub struct PutCustomFieldCommand {
+    pub name: String,
+    /// One of `text`, `number`, `boolean` or `date`.
+    pub field_type: String,
+    pub required: bool,
+}
+
+pub struct DeleteCustomFieldCommand {
+    pub name: String,
+}
+
+impl ArticleCommandService {
+    /// Define a custom field, or redefine the one with the same name.
+    /// Values already stored on articles are not revalidated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `custom_fields:manage`, the name or
+    /// type is invalid, custom fields are not enabled, or persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn put_custom_field(
+        &self,
+        actor: &AuthenticatedUser,
+        command: PutCustomFieldCommand,
+    ) -> AppResult<CustomFieldDefinitionDto> {
+        self.read_only.ensure_writable()?;
+        let journaled = self.journal.capture(|| JournaledCommand::PutCustomField {
+            name: command.name.clone(),
+            field_type: command.field_type.clone(),
+            required: command.required,
+        });
+        let result = self.put_custom_field_inner(actor, command).await;
+        self.journal
+            .record(self.clock.now(), Some(actor), journaled, &result, |_| None)
+            .await;
+        result
+    }
+
+    async fn put_custom_field_inner(
+        &self,
+        actor: &AuthenticatedUser,
+        command: PutCustomFieldCommand,
+    ) -> AppResult<CustomFieldDefinitionDto> {
+        ensure_capability(actor, "custom_fields", "manage")?;
+        let definition = CustomFieldDefinition::new(
+            command.name,
+            command.field_type.parse()?,
+            command.required,
+        )?;
+        let saved = self.custom_field_repo()?.upsert(definition).await?;
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("custom_field.put", "custom_field", None)
+                    .with_details(json!({ "name": saved.name })),
+            )
+            .await;
+        Ok(saved.into())
+    }
+
+    /// Remove a custom field definition. Articles keep their values for it
+    /// until their custom fields are next replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `custom_fields:manage`, the field
+    /// does not exist, custom fields are not enabled, or persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn delete_custom_field(
+        &self,
+        actor: &AuthenticatedUser,
+        command: DeleteCustomFieldCommand,
+    ) -> AppResult<()> {
+        self.read_only.ensure_writable()?;
+        let journaled = self
+            .journal
+            .capture(|| JournaledCommand::DeleteCustomField {
+                name: command.name.clone(),
+            });
+        let result = self.delete_custom_field_inner(actor, command).await;
+        self.journal
+            .record(self.clock.now(), Some(actor), journaled, &result, |()| None)
+            .await;
+        result
+    }
+
+    async fn delete_custom_field_inner(
+        &self,
+        actor: &AuthenticatedUser,
+        command: DeleteCustomFieldCommand,
+    ) -> AppResult<()> {
+        ensure_capability(actor, "custom_fields", "manage")?;
+        if self.custom_field_repo()?.delete(&command.name).await? {
+            self.audit
+                .record(
+                    Some(actor.id),
+                    AuditEvent::new("custom_field.delete", "custom_field", None)
+                        .with_details(json!({ "name": command.name })),
+                )
+                .await;
+            Ok(())
+        } else {
+            Err(AppError::not_found(format!(
+                "custom field '{}' not found",
+                command.name
+            )))
+        }
+    }
+
+    fn custom_field_repo(&self) -> AppResult<&Arc<dyn CustomFieldRepository>> {
+        self.custom_fields
+            .as_ref()
+            .ok_or_else(|| AppError::not_found("custom fields are not enabled"))
+    }
+}
@@ -1,9 +1,13 @@
 // src/application/commands/articles/delete.rs
-use super::ArticleCommandService;
+use serde_json::json;
+
+use super::{ArticleChange, ArticleCommandService};
 use crate::{
     application::{
         AuthenticatedUser,
         error::{AppError, AppResult},
+        ports::command_journal::JournaledCommand,
+        services::AuditEvent,
     },
     domain::{
         ArticleId,
@@ -22,10 +26,30 @@ impl ArticleCommandService {
     ///
     /// Returns an error if the id is invalid, the article is missing, the
     /// actor is not allowed to delete it, or repository operations fail.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn delete_article(
         &self,
         actor: &AuthenticatedUser,
         command: DeleteArticleCommand,
+    ) -> AppResult<()> {
+        self.read_only.ensure_writable()?;
+        let journaled = self
+            .journal
+            .capture(|| JournaledCommand::DeleteArticle { id: command.id });
+        let id = command.id;
+        let result = self.delete_article_inner(actor, command).await;
+        self.journal
+            .record(self.clock.now(), Some(actor), journaled, &result, |()| {
+                Some(id)
+            })
+            .await;
+        result
+    }
+
+    async fn delete_article_inner(
+        &self,
+        actor: &AuthenticatedUser,
+        command: DeleteArticleCommand,
     ) -> AppResult<()> {
         let id = ArticleId::new(command.id)?;
         let article = self
@@ -45,6 +69,14 @@ impl ArticleCommandService {
         self.revision_repo.append(&article, Some(actor.id)).await?;
 
         self.write_repo.delete(id).await?;
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("article.delete", "article", Some(command.id))
+                    .with_details(json!({ "slug": article.slug.as_str() })),
+            )
+            .await;
+        self.notify(&article, ArticleChange::Deleted).await;
         Ok(())
     }
 }
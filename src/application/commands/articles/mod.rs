@@ -1,13 +1,22 @@
 // src/application/commands/articles/mod.rs
+mod bulk;
 mod capability;
+mod contributors;
 mod create;
+mod custom_fields;
 mod delete;
 mod publish;
+mod revert;
 mod service;
 mod update;
 
+pub use bulk::{BulkArticleCommand, BulkArticleOperation};
+pub use contributors::{AddContributorCommand, RemoveContributorCommand};
 pub use create::{CreateArticleCommand, CreateArticleCommandBuilder};
+pub use custom_fields::{DeleteCustomFieldCommand, PutCustomFieldCommand};
 pub use delete::DeleteArticleCommand;
 pub use publish::SetPublishStateCommand;
+pub use revert::RevertArticleToRevisionCommand;
 pub use service::ArticleCommandService;
+use service::{ArticleChange, RevisedWrite};
 pub use update::UpdateArticleCommand;
@@ -1,9 +1,11 @@
 // src/application/commands/articles/publish.rs
-use super::{ArticleCommandService, capability::ensure_capability};
+use super::{ArticleChange, ArticleCommandService, RevisedWrite, capability::ensure_capability};
 use crate::{
     application::{
         ArticleDto, AuthenticatedUser,
         error::{AppError, AppResult},
+        ports::command_journal::JournaledCommand,
+        services::AuditEvent,
     },
     domain::{ArticleId, ArticleUpdate},
 };
@@ -20,10 +22,34 @@ impl ArticleCommandService {
     ///
     /// Returns an error if the actor lacks `articles:publish`, the id is
     /// invalid, the article is missing, or persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn set_publish_state(
         &self,
         actor: &AuthenticatedUser,
         command: SetPublishStateCommand,
+    ) -> AppResult<ArticleDto> {
+        self.read_only.ensure_writable()?;
+        let journaled = self.journal.capture(|| JournaledCommand::SetPublishState {
+            id: command.id,
+            publish: command.publish,
+        });
+        let result = self.set_publish_state_inner(actor, command).await;
+        self.journal
+            .record(
+                self.clock.now(),
+                Some(actor),
+                journaled,
+                &result,
+                |article| Some(article.id),
+            )
+            .await;
+        result
+    }
+
+    async fn set_publish_state_inner(
+        &self,
+        actor: &AuthenticatedUser,
+        command: SetPublishStateCommand,
     ) -> AppResult<ArticleDto> {
         ensure_capability(actor, "articles", "publish")?;
         let id = ArticleId::new(command.id)?;
@@ -58,8 +84,26 @@ impl ArticleCommandService {
         let mut update = ArticleUpdate::new(id, original_updated_at)
             .with_publish_state(article.published, article.published_at);
         update.set_updated_at(article.updated_at);
-        let updated = self.write_repo.update(update).await?;
-        self.revision_repo.append(&updated, Some(actor.id)).await?;
+        let updated = self
+            .write_revised(RevisedWrite::Update(update), actor.id)
+            .await?;
+        let action = if updated.published {
+            "article.publish"
+        } else {
+            "article.unpublish"
+        };
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new(action, "article", Some(i64::from(updated.id))),
+            )
+            .await;
+        let change = if updated.published {
+            ArticleChange::Published
+        } else {
+            ArticleChange::Unpublished
+        };
+        self.notify(&updated, change).await;
         Ok(updated.into())
     }
 }
@@ -0,0 +1,116 @@
+// src/application/commands/articles/revert.rs
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+use super::{ArticleCommandService, RevisedWrite};
+use crate::{
+    application::{
+        ArticleDto, AuthenticatedUser,
+        error::{AppError, AppResult},
+        ports::command_journal::JournaledCommand,
+        services::AuditEvent,
+    },
+    domain::{ArticleId, ArticleUpdate, errors::DomainError},
+};
+
+pub struct RevertArticleToRevisionCommand {
+    pub id: i64,
+    pub version: i32,
+    /// The `updated_at` the caller last saw; the revert is refused if the
+    /// article has changed since.
+    pub expected_updated_at: Option<DateTime<Utc>>,
+}
+
+impl ArticleCommandService {
+    /// Restore the title and body an article had at `version`, recording the
+    /// result as a new revision. Tags, custom fields and publication state
+    /// are left as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the article or revision is missing, the actor may
+    /// not update the article, the article changed since
+    /// `expected_updated_at` or during the revert, or persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn revert_article_to_revision(
+        &self,
+        actor: &AuthenticatedUser,
+        command: RevertArticleToRevisionCommand,
+    ) -> AppResult<ArticleDto> {
+        self.read_only.ensure_writable()?;
+        let journaled = self.journal.capture(|| JournaledCommand::RevertArticle {
+            id: command.id,
+            version: command.version,
+        });
+        let result = self.revert_article_inner(actor, command).await;
+        self.journal
+            .record(
+                self.clock.now(),
+                Some(actor),
+                journaled,
+                &result,
+                |article| Some(article.id),
+            )
+            .await;
+        result
+    }
+
+    async fn revert_article_inner(
+        &self,
+        actor: &AuthenticatedUser,
+        command: RevertArticleToRevisionCommand,
+    ) -> AppResult<ArticleDto> {
+        let id = ArticleId::new(command.id)?;
+        let mut article = self
+            .read_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::not_found("article not found"))?;
+
+        if !self.can_update(actor, &article).await? {
+            return Err(AppError::forbidden(
+                "insufficient privileges to update article",
+            ));
+        }
+        if command
+            .expected_updated_at
+            .is_some_and(|expected| expected != article.updated_at)
+        {
+            return Err(AppError::conflict(
+                "article has changed since it was last read",
+            ));
+        }
+
+        let revision = self
+            .revision_repo
+            .find_revision(id, command.version)
+            .await?
+            .ok_or_else(|| {
+                AppError::not_found(format!("revision {} not found", command.version))
+            })?;
+
+        let original_slug = article.slug.clone();
+        let title = (revision.title != article.title).then_some(revision.title);
+        let update = ArticleUpdate::new(id, article.updated_at);
+        let update = self
+            .apply_content_updates(&mut article, title, Some(revision.body), update)
+            .await?;
+
+        let updated = self
+            .write_revised(RevisedWrite::Update(update), actor.id)
+            .await
+            .map_err(|err| match err {
+                AppError::Domain(DomainError::Conflict(message)) => AppError::conflict(message),
+                other => other,
+            })?;
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("article.revert", "article", Some(i64::from(updated.id)))
+                    .with_details(json!({ "version": command.version })),
+            )
+            .await;
+        self.notify_renamed(&updated, &original_slug).await;
+        Ok(updated.into())
+    }
+}
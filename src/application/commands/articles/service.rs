@@ -2,10 +2,23 @@
 use std::sync::Arc;
 
 use crate::{
-    application::ports::time::Clock,
+    application::{
+        AppError, AppResult, AuthenticatedUser, ReadOnlySwitch,
+        commands::Recorder,
+        ports::{
+            command_journal::CommandJournal, domain_events::EventPublisher, time::Clock,
+            unit_of_work::UnitOfWork,
+        },
+        services::{AuditRecorder, BlockListService},
+    },
     domain::{
-        ArticleReadRepository, ArticleRevisionRepository, ArticleWriteRepository,
+        Article, ArticleId, ArticleReadRepository, ArticleRevisionRepository, ArticleSlug,
+        ArticleSnapshot, ArticleUpdate, ArticleWriteRepository, ContributorRepository,
+        CustomFieldRepository, CustomFields, DomainEvent, EventKind, NewArticle, UserId,
+        article::custom_fields,
         article::services::ArticleSlugService,
+        article::specifications::{ArticleSpecification, CanUpdateArticleSpec},
+        audit::repository::AuditLogRepository,
     },
 };
 
@@ -16,6 +29,31 @@ pub struct ArticleCommandService {
     pub(super) revision_repo: Arc<dyn ArticleRevisionRepository>,
     pub(super) slug_service: Arc<ArticleSlugService>,
     pub(super) clock: Arc<dyn Clock>,
+    pub(super) journal: Recorder,
+    pub(super) audit: AuditRecorder,
+    pub(super) custom_fields: Option<Arc<dyn CustomFieldRepository>>,
+    pub(super) contributors: Option<Arc<dyn ContributorRepository>>,
+    pub(super) blocks: Option<Arc<BlockListService>>,
+    pub(super) read_only: ReadOnlySwitch,
+    pub(super) publisher: Option<Arc<dyn EventPublisher>>,
+    pub(super) unit_of_work: Option<Arc<dyn UnitOfWork>>,
+}
+
+/// What happened to an article.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ArticleChange {
+    Created,
+    /// Content or metadata changed, including reverts to a revision.
+    Updated,
+    Published,
+    Unpublished,
+    Deleted,
+}
+
+/// An article write that is recorded as a new revision.
+pub(super) enum RevisedWrite {
+    Insert(NewArticle),
+    Update(ArticleUpdate),
 }
 
 impl ArticleCommandService {
@@ -32,6 +70,173 @@ impl ArticleCommandService {
             revision_repo,
             slug_service,
             clock,
+            journal: Recorder::default(),
+            audit: AuditRecorder::default(),
+            custom_fields: None,
+            contributors: None,
+            blocks: None,
+            read_only: ReadOnlySwitch::default(),
+            publisher: None,
+            unit_of_work: None,
         }
     }
+
+    /// Append every command's outcome to `journal`.
+    pub fn with_journal(mut self, journal: Arc<dyn CommandJournal>) -> Self {
+        self.journal = Recorder::new(journal);
+        self
+    }
+
+    /// Record every successful write in `audit_log_repo`.
+    pub fn with_audit(mut self, audit_log_repo: Arc<dyn AuditLogRepository>) -> Self {
+        self.audit = AuditRecorder::new(audit_log_repo);
+        self
+    }
+
+    /// Validate custom field values against the definitions in `repo`.
+    /// Without one, articles may not carry custom fields.
+    pub fn with_custom_fields(mut self, repo: Arc<dyn CustomFieldRepository>) -> Self {
+        self.custom_fields = Some(repo);
+        self
+    }
+
+    /// Let the users in `repo` edit articles alongside their authors.
+    /// Without one, only authors and `articles:update:any` may edit.
+    pub fn with_contributors(mut self, repo: Arc<dyn ContributorRepository>) -> Self {
+        self.contributors = Some(repo);
+        self
+    }
+
+    /// Refuse to add a contributor who has blocked the actor.
+    pub fn with_blocks(mut self, blocks: Arc<BlockListService>) -> Self {
+        self.blocks = Some(blocks);
+        self
+    }
+
+    /// Refuse every write while `switch` is on.
+    pub fn with_read_only(mut self, switch: ReadOnlySwitch) -> Self {
+        self.read_only = switch;
+        self
+    }
+
+    /// Publish a domain event to `publisher` for every stored change; the
+    /// slug cache, webhooks, syndication and the live event stream follow
+    /// articles through it.
+    pub fn with_event_publisher(mut self, publisher: Arc<dyn EventPublisher>) -> Self {
+        self.publisher = Some(publisher);
+        self
+    }
+
+    /// Write each article together with its revision through `unit_of_work`.
+    /// Without one, a failure between the two leaves the article unrecorded.
+    pub fn with_unit_of_work(mut self, unit_of_work: Arc<dyn UnitOfWork>) -> Self {
+        self.unit_of_work = Some(unit_of_work);
+        self
+    }
+
+    /// Store `write` and record the result as a revision edited by `editor`.
+    pub(super) async fn write_revised(
+        &self,
+        write: RevisedWrite,
+        editor: UserId,
+    ) -> AppResult<Article> {
+        let Some(unit_of_work) = &self.unit_of_work else {
+            let article = match write {
+                RevisedWrite::Insert(article) => self.write_repo.insert(article).await?,
+                RevisedWrite::Update(update) => self.write_repo.update(update).await?,
+            };
+            self.revision_repo.append(&article, Some(editor)).await?;
+            return Ok(article);
+        };
+
+        let mut work = unit_of_work.begin().await?;
+        let article = match write {
+            RevisedWrite::Insert(article) => work.insert_article(article).await?,
+            RevisedWrite::Update(update) => work.update_article(update).await?,
+        };
+        work.append_revision(&article, Some(editor)).await?;
+        work.commit().await?;
+        Ok(article)
+    }
+
+    /// Publish `change` to `article` as a domain event. The change is
+    /// already stored, so a failure is logged rather than returned.
+    pub(super) async fn notify(&self, article: &Article, change: ArticleChange) {
+        self.publish(article, change, ArticleSnapshot::from(article))
+            .await;
+    }
+
+    /// Publish an update of `article` that may have replaced
+    /// `previous_slug`, so both slugs are dropped from caches.
+    pub(super) async fn notify_renamed(&self, article: &Article, previous_slug: &ArticleSlug) {
+        let mut snapshot = ArticleSnapshot::from(article);
+        if previous_slug != &article.slug {
+            snapshot.previous_slug = Some(previous_slug.as_str().to_owned());
+        }
+        self.publish(article, ArticleChange::Updated, snapshot)
+            .await;
+    }
+
+    async fn publish(&self, article: &Article, change: ArticleChange, snapshot: ArticleSnapshot) {
+        let Some(publisher) = &self.publisher else {
+            return;
+        };
+        let kind = match change {
+            ArticleChange::Created => EventKind::ArticleCreated(article.id),
+            ArticleChange::Updated => EventKind::ArticleUpdated(article.id),
+            ArticleChange::Published => EventKind::ArticlePublished(article.id),
+            ArticleChange::Unpublished => EventKind::ArticleUnpublished(article.id),
+            ArticleChange::Deleted => EventKind::ArticleDeleted(article.id),
+        };
+        let event = DomainEvent::new(kind, self.clock.now()).with_article(snapshot);
+        if let Err(err) = publisher.publish(&event).await {
+            tracing::warn!(
+                error = %err,
+                article_id = i64::from(article.id),
+                event = kind.name(),
+                "domain event was not published"
+            );
+        }
+    }
+
+    /// Whether `actor` may edit `article` as its author, one of its
+    /// contributors or a holder of `articles:update:any`.
+    pub(super) async fn can_update(
+        &self,
+        actor: &AuthenticatedUser,
+        article: &Article,
+    ) -> AppResult<bool> {
+        let spec = CanUpdateArticleSpec::new(&actor.capabilities, article, actor.id);
+        if spec.is_satisfied() {
+            return Ok(true);
+        }
+        let contributors = self.contributor_ids(article.id).await?;
+        Ok(spec.with_contributors(&contributors).is_satisfied())
+    }
+
+    async fn contributor_ids(&self, article_id: ArticleId) -> AppResult<Vec<UserId>> {
+        let Some(repo) = &self.contributors else {
+            return Ok(Vec::new());
+        };
+        Ok(repo
+            .list(article_id)
+            .await?
+            .into_iter()
+            .map(|contributor| contributor.user_id)
+            .collect())
+    }
+
+    pub(super) fn contributor_repo(&self) -> AppResult<&Arc<dyn ContributorRepository>> {
+        self.contributors
+            .as_ref()
+            .ok_or_else(|| AppError::infrastructure("article contributors are not configured"))
+    }
+
+    pub(super) async fn validate_custom_fields(&self, fields: &CustomFields) -> AppResult<()> {
+        let definitions = match &self.custom_fields {
+            Some(repo) => repo.list().await?,
+            None => Vec::new(),
+        };
+        Ok(custom_fields::validate(&definitions, fields)?)
+    }
 }
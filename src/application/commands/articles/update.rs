@@ -1,12 +1,14 @@
-use super::{ArticleCommandService, capability::ensure_capability};
+use super::{ArticleChange, ArticleCommandService, RevisedWrite, capability::ensure_capability};
 use crate::{
     application::{
         ArticleDto, AuthenticatedUser,
         error::{AppError, AppResult},
+        ports::command_journal::JournaledCommand,
+        services::AuditEvent,
     },
     domain::{
-        Article, ArticleBody, ArticleId, ArticleTitle, ArticleUpdate,
-        article::specifications::{ArticleSpecification, CanUpdateArticleSpec},
+        Article, ArticleBody, ArticleId, ArticleTitle, ArticleUpdate, ArticleVisibility,
+        CustomFields, Tag,
     },
 };
 
@@ -15,6 +17,11 @@ pub struct UpdateArticleCommand {
     pub title: Option<String>,
     pub body: Option<String>,
     pub publish: Option<bool>,
+    /// Replaces the whole tag set when present.
+    pub tags: Option<Vec<String>>,
+    /// Replaces all custom field values when present.
+    pub custom_fields: Option<CustomFields>,
+    pub visibility: Option<ArticleVisibility>,
 }
 
 impl ArticleCommandService {
@@ -25,10 +32,39 @@ impl ArticleCommandService {
     /// Returns an error if the id is invalid, the article is missing, the
     /// actor lacks the required capability, validation fails, or persistence
     /// fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn update_article(
         &self,
         actor: &AuthenticatedUser,
         command: UpdateArticleCommand,
+    ) -> AppResult<ArticleDto> {
+        self.read_only.ensure_writable()?;
+        let journaled = self.journal.capture(|| JournaledCommand::UpdateArticle {
+            id: command.id,
+            title: command.title.clone(),
+            body: command.body.clone(),
+            publish: command.publish,
+            tags: command.tags.clone(),
+            custom_fields: command.custom_fields.clone(),
+            visibility: command.visibility,
+        });
+        let result = self.update_article_inner(actor, command).await;
+        self.journal
+            .record(
+                self.clock.now(),
+                Some(actor),
+                journaled,
+                &result,
+                |article| Some(article.id),
+            )
+            .await;
+        result
+    }
+
+    async fn update_article_inner(
+        &self,
+        actor: &AuthenticatedUser,
+        command: UpdateArticleCommand,
     ) -> AppResult<ArticleDto> {
         let id = ArticleId::new(command.id)?;
         let mut article = self
@@ -37,9 +73,7 @@ impl ArticleCommandService {
             .await?
             .ok_or_else(|| AppError::not_found("article not found"))?;
 
-        let update_spec = CanUpdateArticleSpec::new(&actor.capabilities, &article, actor.id);
-
-        if !update_spec.is_satisfied() {
+        if !self.can_update(actor, &article).await? {
             return Err(AppError::forbidden(
                 "insufficient privileges to update article",
             ));
@@ -50,27 +84,70 @@ impl ArticleCommandService {
             title,
             body,
             publish,
+            tags,
+            custom_fields,
+            visibility,
         } = command;
         let original_updated_at = article.updated_at;
+        let original_slug = article.slug.clone();
+        let was_published = article.published;
         let mut update = ArticleUpdate::new(id, original_updated_at);
 
         let title_opt = title.map(ArticleTitle::new).transpose()?;
         let body_opt = body.map(ArticleBody::new).transpose()?;
+        let tags_opt = tags.map(Tag::parse_list).transpose()?;
+        if let Some(custom_fields) = &custom_fields {
+            self.validate_custom_fields(custom_fields).await?;
+        }
 
         update = self
             .apply_content_updates(&mut article, title_opt, body_opt, update)
             .await?;
 
+        if let Some(tags) = tags_opt {
+            article.set_tags(tags.clone(), self.clock.now());
+            update = update.with_tags(tags);
+            update.set_updated_at(article.updated_at);
+        }
+
+        if let Some(custom_fields) = custom_fields {
+            article.set_custom_fields(custom_fields.clone(), self.clock.now());
+            update = update.with_custom_fields(custom_fields);
+            update.set_updated_at(article.updated_at);
+        }
+
+        if let Some(visibility) = visibility {
+            article.set_visibility(visibility, self.clock.now());
+            update = update.with_visibility(visibility);
+            update.set_updated_at(article.updated_at);
+        }
+
         if let Some(publish_flag) = publish {
             update = self.apply_publish_update(actor, &mut article, publish_flag, update)?;
         }
 
-        let updated = self.write_repo.update(update).await?;
-        self.revision_repo.append(&updated, Some(actor.id)).await?;
+        let updated = self
+            .write_revised(RevisedWrite::Update(update), actor.id)
+            .await?;
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("article.update", "article", Some(i64::from(updated.id))),
+            )
+            .await;
+        self.notify_renamed(&updated, &original_slug).await;
+        if updated.published != was_published {
+            let change = if updated.published {
+                ArticleChange::Published
+            } else {
+                ArticleChange::Unpublished
+            };
+            self.notify(&updated, change).await;
+        }
         Ok(updated.into())
     }
 
-    async fn apply_content_updates(
+    pub(super) async fn apply_content_updates(
         &self,
         article: &mut Article,
         title_opt: Option<ArticleTitle>,
@@ -0,0 +1,58 @@
+// src/application/commands/journal.rs
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::application::{
+    AppResult, AuthenticatedUser,
+    ports::command_journal::{
+        CommandJournal, JournalActor, JournalEntry, JournalOutcome, JournaledCommand,
+    },
+};
+
+/// Writes command outcomes to the configured journal, if any.
+#[derive(Clone, Default)]
+pub struct Recorder(Option<Arc<dyn CommandJournal>>);
+
+impl Recorder {
+    pub fn new(journal: Arc<dyn CommandJournal>) -> Self {
+        Self(Some(journal))
+    }
+
+    /// Build the journaled form of a command only when it will be written.
+    pub fn capture(&self, command: impl FnOnce() -> JournaledCommand) -> Option<JournaledCommand> {
+        self.0.as_ref().map(|_| command())
+    }
+
+    /// Append the outcome of `command`. The command has already taken effect,
+    /// so a journal failure is logged rather than returned.
+    pub async fn record<T: Sync>(
+        &self,
+        recorded_at: DateTime<Utc>,
+        actor: Option<&AuthenticatedUser>,
+        command: Option<JournaledCommand>,
+        result: &AppResult<T>,
+        resource_id: impl FnOnce(&T) -> Option<i64> + Send,
+    ) {
+        let (Some(journal), Some(command)) = (&self.0, command) else {
+            return;
+        };
+        let outcome = match result {
+            Ok(value) => JournalOutcome::Succeeded {
+                resource_id: resource_id(value),
+            },
+            Err(err) => JournalOutcome::Failed {
+                error: err.to_string(),
+            },
+        };
+        let entry = JournalEntry {
+            recorded_at,
+            actor: actor.map(JournalActor::from_user),
+            command,
+            outcome,
+        };
+        if let Err(err) = journal.append(&entry).await {
+            tracing::warn!(error = %err, "failed to write command journal entry");
+        }
+    }
+}
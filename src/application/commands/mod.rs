@@ -1,3 +1,6 @@
 // src/application/commands/mod.rs
 pub mod articles;
+mod journal;
 pub mod users;
+
+pub(crate) use journal::Recorder;
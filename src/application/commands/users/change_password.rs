@@ -3,8 +3,9 @@ use crate::{
     application::{
         AuthenticatedUser,
         error::{AppError, AppResult},
+        services::AuditEvent,
     },
-    domain::{PasswordHash, UserId, UserUpdate},
+    domain::{EventKind, PasswordHash, User, UserId, UserUpdate},
 };
 
 pub struct ChangePasswordCommand {
@@ -25,6 +26,7 @@ impl UserCommandService {
         actor: &AuthenticatedUser,
         command: ChangePasswordCommand,
     ) -> AppResult<()> {
+        self.read_only.ensure_writable()?;
         let target_id = UserId::new(command.user_id)?;
 
         let user = self
@@ -36,8 +38,16 @@ impl UserCommandService {
         self.verify_change_password_self(actor, &user, command.current_password.as_deref())
             .await?;
 
-        self.validate_and_set_new_password(target_id, &command.new_password)
+        self.validate_and_set_new_password(&user, &command.new_password)
             .await?;
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("user.password_change", "user", Some(command.user_id)),
+            )
+            .await;
+        self.publish(EventKind::UserPasswordChanged(target_id))
+            .await;
 
         Ok(())
     }
@@ -45,7 +55,7 @@ impl UserCommandService {
     async fn verify_change_password_self(
         &self,
         actor: &AuthenticatedUser,
-        user: &crate::domain::User,
+        user: &User,
         current_password: Option<&str>,
     ) -> AppResult<()> {
         let is_self = actor.id == user.id;
@@ -65,19 +75,69 @@ impl UserCommandService {
         Ok(())
     }
 
-    async fn validate_and_set_new_password(
+    pub(super) async fn validate_and_set_new_password(
         &self,
-        target_id: UserId,
+        user: &User,
         new_password: &str,
     ) -> AppResult<()> {
         validate_password(new_password)?;
+        self.ensure_password_not_breached(new_password).await?;
+        self.ensure_password_not_reused(user, new_password).await?;
 
         let hashed = self.password_hasher.hash(new_password).await?;
         let password_hash = PasswordHash::new(hashed)?;
 
-        let update = UserUpdate::new(target_id).with_password_hash(password_hash);
+        let update = UserUpdate::new(user.id)
+            .with_password_hash(password_hash)
+            .with_password_reset_required(false);
         self.user_repo.update(update).await?;
 
+        self.remember_replaced_password(user).await;
         Ok(())
     }
+
+    /// Refuse `new_password` if it is `user`'s current password or one of
+    /// the former ones kept in the password history.
+    async fn ensure_password_not_reused(&self, user: &User, new_password: &str) -> AppResult<()> {
+        let Some(reuse) = &self.password_reuse else {
+            return Ok(());
+        };
+        let former = reuse.history.recent(user.id, reuse.depth - 1).await?;
+        for hash in std::iter::once(&user.password_hash).chain(&former) {
+            // Hashes that cannot be verified against, such as the
+            // placeholders of directory users, never match.
+            if self
+                .password_hasher
+                .verify(new_password, hash.as_str())
+                .await
+                .is_ok()
+            {
+                return Err(AppError::validation(format!(
+                    "new password must differ from the last {} passwords",
+                    reuse.depth
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Keep `user`'s replaced password hash for later reuse checks. The new
+    /// password is already set, so a failure here is only logged.
+    async fn remember_replaced_password(&self, user: &User) {
+        let Some(reuse) = &self.password_reuse else {
+            return;
+        };
+        let recorded = reuse
+            .history
+            .record(
+                user.id,
+                user.password_hash.clone(),
+                self.clock.now(),
+                reuse.depth - 1,
+            )
+            .await;
+        if let Err(err) = recorded {
+            tracing::warn!(error = %err, user_id = i64::from(user.id), "failed to record replaced password");
+        }
+    }
 }
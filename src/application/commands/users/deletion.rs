@@ -0,0 +1,210 @@
+use super::service::AccountDeletion;
+use super::{UserCommandService, capability::ensure_capability, password::unusable_password_hash};
+use serde_json::json;
+
+use crate::{
+    application::{
+        AccountDeletionDto, AuthenticatedUser,
+        error::{AppError, AppResult},
+        services::AuditEvent,
+    },
+    domain::{EventKind, PendingDeletion, User, UserId, UserUpdate, Username},
+};
+
+/// How many due deletions one purge run works through.
+const PURGE_BATCH: u32 = 100;
+
+pub struct DeleteAccountCommand {
+    /// Re-entered to confirm the deletion.
+    pub current_password: String,
+    /// User who takes over the deleted user's articles; `None` leaves them
+    /// with the anonymized account.
+    pub transfer_articles_to: Option<i64>,
+}
+
+impl UserCommandService {
+    /// Schedule deletion of the authenticated user's own account. The
+    /// account is disabled and signed out everywhere at once, and
+    /// anonymized once the grace period ends unless an admin cancels the
+    /// deletion first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if account deletion is not configured, the password
+    /// is wrong, the transfer target is missing, inactive or the user
+    /// themselves, or persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn delete_account(
+        &self,
+        actor: &AuthenticatedUser,
+        command: DeleteAccountCommand,
+    ) -> AppResult<AccountDeletionDto> {
+        self.read_only.ensure_writable()?;
+        let deletion = self.account_deletion()?;
+        let user = self
+            .user_repo
+            .find_by_id(actor.id)
+            .await?
+            .ok_or_else(|| AppError::not_found("user not found"))?;
+        self.password_hasher
+            .verify(&command.current_password, user.password_hash.as_str())
+            .await?;
+        let transfer_to = match command.transfer_articles_to {
+            Some(id) => Some(self.transfer_target(&user, UserId::new(id)?).await?),
+            None => None,
+        };
+
+        let now = self.clock.now();
+        let pending = PendingDeletion {
+            user_id: user.id,
+            requested_at: now,
+            purge_after: now + deletion.grace,
+            transfer_to,
+        };
+        deletion.store.schedule(pending.clone()).await?;
+        self.user_repo
+            .update(UserUpdate::new(user.id).with_is_active(false))
+            .await?;
+        self.revoke_all_sessions(user.id).await?;
+        self.audit
+            .record(
+                Some(user.id),
+                AuditEvent::new("user.deletion_scheduled", "user", Some(i64::from(user.id)))
+                    .with_details(json!({
+                        "purge_after": pending.purge_after,
+                        "transfer_to": transfer_to.map(i64::from),
+                    })),
+            )
+            .await;
+        Ok(pending.into())
+    }
+
+    /// Cancel a user's pending account deletion and reactivate the account.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `users:update`, the user has no
+    /// pending deletion, or persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn cancel_account_deletion(
+        &self,
+        actor: &AuthenticatedUser,
+        user_id: i64,
+    ) -> AppResult<()> {
+        self.read_only.ensure_writable()?;
+        ensure_capability(actor, "users", "update")?;
+        let deletion = self.account_deletion()?;
+        let user_id = UserId::new(user_id)?;
+        if !deletion.store.remove(user_id).await? {
+            return Err(AppError::not_found("no pending deletion for this user"));
+        }
+        self.user_repo
+            .update(UserUpdate::new(user_id).with_is_active(true))
+            .await?;
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("user.deletion_cancelled", "user", Some(i64::from(user_id))),
+            )
+            .await;
+        Ok(())
+    }
+
+    /// Anonymize accounts whose deletion grace period has ended, handing
+    /// their articles to the chosen user first. Returns how many accounts
+    /// were purged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if account deletion is not configured or
+    /// persistence fails; accounts purged before the failure stay purged.
+    pub async fn purge_due_account_deletions(&self) -> AppResult<u64> {
+        self.read_only.ensure_writable()?;
+        let deletion = self.account_deletion()?;
+        let due = deletion.store.due(self.clock.now(), PURGE_BATCH).await?;
+        let mut purged = 0;
+        for pending in due {
+            let articles = match pending.transfer_to {
+                Some(to) => {
+                    deletion
+                        .articles
+                        .reassign_author(pending.user_id, to)
+                        .await?
+                }
+                None => 0,
+            };
+            let update = UserUpdate::new(pending.user_id)
+                .with_username(Username::new(format!(
+                    "deleted-user-{}",
+                    i64::from(pending.user_id)
+                ))?)
+                .with_email(None)
+                .with_email_verified(false)
+                .with_timezone(None)
+                .with_password_hash(unusable_password_hash(self.password_hasher.as_ref()).await?)
+                .with_is_active(false);
+            self.user_repo.update(update).await?;
+            deletion.store.remove(pending.user_id).await?;
+            self.audit
+                .record(
+                    None,
+                    AuditEvent::new(
+                        "user.deletion_purged",
+                        "user",
+                        Some(i64::from(pending.user_id)),
+                    )
+                    .with_details(json!({
+                        "transfer_to": pending.transfer_to.map(i64::from),
+                        "articles_transferred": articles,
+                    })),
+                )
+                .await;
+            self.publish(EventKind::UserDeleted(pending.user_id)).await;
+            purged += 1;
+        }
+        Ok(purged)
+    }
+
+    fn account_deletion(&self) -> AppResult<&AccountDeletion> {
+        self.account_deletion
+            .as_ref()
+            .ok_or_else(|| AppError::infrastructure("account deletion is not configured"))
+    }
+
+    /// Check `id` can take over `user`'s articles.
+    async fn transfer_target(&self, user: &User, id: UserId) -> AppResult<UserId> {
+        if id == user.id {
+            return Err(AppError::validation(
+                "articles cannot be transferred to the deleted account",
+            ));
+        }
+        match self.user_repo.find_by_id(id).await? {
+            Some(target) if target.is_active => Ok(target.id),
+            _ => Err(AppError::validation(
+                "articles can only be transferred to an active user",
+            )),
+        }
+    }
+
+    /// Revoke every session of `user_id` and invalidate access tokens
+    /// already issued to them.
+    async fn revoke_all_sessions(&self, user_id: UserId) -> AppResult<()> {
+        let user_id = i64::from(user_id);
+        self.session_stores
+            .revocation
+            .revoke_sessions_for_user(user_id)
+            .await?;
+        // Tokens issued before any minimum was set carry version 1.
+        let min_version = self
+            .session_stores
+            .token_versions
+            .get_min_token_version(user_id)
+            .await?
+            .unwrap_or(1)
+            .saturating_add(1);
+        self.session_stores
+            .token_versions
+            .set_min_token_version(user_id, min_version)
+            .await
+    }
+}
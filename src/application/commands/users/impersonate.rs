@@ -0,0 +1,99 @@
+use super::{UserCommandService, capability::ensure_capability};
+
+use crate::{
+    application::{
+        AuthTokenDto, AuthenticatedUser, TokenSubject,
+        error::{AppError, AppResult},
+        ports::security_events::ClientInfo,
+        random_id,
+        services::AuditEvent,
+    },
+    domain::UserId,
+};
+
+impl UserCommandService {
+    /// Issue a short-lived token that lets `actor` act as another user.
+    /// The token names `actor` as its impersonator, so every audit entry
+    /// written with it records both identities. No refresh token is issued.
+    /// The token gets a session of its own, started from `client`, which
+    /// shows up among the user's sessions and can be revoked like a login.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `users:impersonate` or is already
+    /// impersonating someone, the user is the actor, may impersonate others,
+    /// holds a capability the actor lacks, is missing or disabled, or token
+    /// or session persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn impersonate(
+        &self,
+        actor: &AuthenticatedUser,
+        user_id: i64,
+        client: &ClientInfo,
+    ) -> AppResult<AuthTokenDto> {
+        ensure_capability(actor, "users", "impersonate")?;
+        if actor.impersonator.is_some() {
+            return Err(AppError::forbidden(
+                "impersonation tokens cannot impersonate other users",
+            ));
+        }
+        let user_id = UserId::new(user_id)?;
+        if user_id == actor.id {
+            return Err(AppError::validation("users cannot impersonate themselves"));
+        }
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("user not found"))?;
+        if !user.is_active {
+            return Err(AppError::forbidden("account is disabled"));
+        }
+
+        // Judge by what the token would grant, custom roles and individual
+        // grants included, so impersonation never widens the actor's reach.
+        let (capabilities, custom_role) = self.token_grants(&user).await?;
+        if capabilities
+            .iter()
+            .any(|capability| capability.matches("users", "impersonate"))
+        {
+            return Err(AppError::forbidden(
+                "users who can impersonate cannot be impersonated",
+            ));
+        }
+        if let Some(missing) = capabilities.iter().find(|capability| {
+            !actor
+                .capabilities
+                .iter()
+                .any(|held| held.covers(capability))
+        }) {
+            return Err(AppError::forbidden(format!(
+                "the user holds {missing}, which the impersonator lacks"
+            )));
+        }
+
+        // A session of its own lets the token be revoked by logging out, and
+        // the token version ends it when the user is signed out everywhere.
+        let session_id = random_id::v4_string()?;
+        let subject = TokenSubject {
+            user_id: user.id,
+            username: user.username.to_string(),
+            role: user.role,
+            capabilities,
+            session_id: Some(session_id.clone()),
+            token_version: self.current_token_version(&user).await?,
+            custom_role,
+            impersonator: Some(actor.id),
+        };
+        let token = self.token_manager.issue(subject).await?;
+        self.register_session(user.id, &session_id, client).await?;
+        self.audit
+            .record_from(
+                client,
+                Some(actor.id),
+                AuditEvent::new("user.impersonate", "user", Some(i64::from(user.id))),
+            )
+            .await;
+        Ok(token)
+    }
+}
@@ -1,11 +1,16 @@
-use super::UserCommandService;
+use super::{UserCommandService, unusable_password_hash};
 use crate::{
     application::{
         AuthTokenDto, TokenSubject, UserDto,
         error::{AppError, AppResult},
+        ports::{
+            external_auth::{ExternalIdentity, GroupRoleMapping},
+            security_events::ClientInfo,
+        },
         random_id,
+        services::AuditEvent,
     },
-    domain::Username,
+    domain::{Email, LoginIdentifier, NewUser, PasswordHash, User, UserId, UserUpdate, Username},
 };
 
 pub struct LoginUserCommand {
@@ -19,21 +24,81 @@ pub struct LoginResult {
 }
 
 impl UserCommandService {
-    /// Authenticate a user and issue a new session token pair.
+    /// Authenticate a user by username or verified email address and issue a
+    /// new session token pair.
     ///
     /// # Errors
     ///
-    /// Returns an error if the username is invalid, credentials do not match,
+    /// Returns an error if the identifier is invalid, credentials do not match,
     /// the account is disabled, or token/session persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn login(&self, command: LoginUserCommand) -> AppResult<LoginResult> {
-        let username = Username::new(command.username)?;
-        let user = self
-            .find_and_authenticate_user(username, &command.password)
-            .await?;
+        self.login_from_client(command, &ClientInfo::default())
+            .await
+    }
+
+    /// Authenticate a user and record the requesting client on the new session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the identifier is invalid, credentials do not match,
+    /// the account is disabled or locked out after failed logins, or
+    /// token/session persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn login_from_client(
+        &self,
+        command: LoginUserCommand,
+        client: &ClientInfo,
+    ) -> AppResult<LoginResult> {
+        let username = match LoginIdentifier::parse(&command.username)? {
+            LoginIdentifier::Username(username) => username,
+            LoginIdentifier::Email(email) => self.username_for_email(&email).await?,
+        };
+        self.ensure_login_allowed(&username, client).await?;
+        let user = match self
+            .find_and_authenticate_user(username.clone(), &command.password)
+            .await
+        {
+            Ok(user) => user,
+            Err(err @ AppError::Unauthorized(_)) => {
+                self.record_login_failure(&username, client).await?;
+                return Err(err);
+            }
+            Err(err) => return Err(err),
+        };
+        self.clear_login_failures(&username).await?;
+
+        self.start_session(user, client).await
+    }
+
+    /// Issue a session for a user who authenticated some other way, such as
+    /// at an upstream OIDC provider.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the account is disabled or token/session
+    /// persistence fails.
+    pub(crate) async fn start_session(
+        &self,
+        user: User,
+        client: &ClientInfo,
+    ) -> AppResult<LoginResult> {
+        if !user.is_active {
+            return Err(AppError::forbidden("account is disabled"));
+        }
 
         let session_id = random_id::v4_string()?;
 
-        let token = self.issue_session_tokens(&user, &session_id).await?;
+        let token = self
+            .issue_session_tokens(&user, &session_id, client)
+            .await?;
+        self.audit
+            .record_from(
+                client,
+                Some(user.id),
+                AuditEvent::new("user.login", "user", Some(i64::from(user.id))),
+            )
+            .await;
         let user_dto: UserDto = user.into();
 
         Ok(LoginResult {
@@ -46,8 +111,9 @@ impl UserCommandService {
         &self,
         user: &crate::domain::User,
         session_id: &str,
+        client: &ClientInfo,
     ) -> AppResult<AuthTokenDto> {
-        let capabilities = user.role.default_capabilities();
+        let (capabilities, custom_role) = self.token_grants(user).await?;
 
         let refresh_nonce = self.create_session_refresh_nonce(session_id).await?;
 
@@ -55,9 +121,11 @@ impl UserCommandService {
             user_id: user.id,
             username: user.username.to_string(),
             role: user.role,
-            capabilities: capabilities.clone(),
+            capabilities,
             session_id: Some(session_id.to_string()),
-            token_version: None,
+            token_version: self.current_token_version(user).await?,
+            custom_role,
+            impersonator: None,
         };
 
         let mut token = self.token_manager.issue(subject).await?;
@@ -66,23 +134,50 @@ impl UserCommandService {
             .build_refresh_token_for_user(user, session_id, &refresh_nonce)
             .await?;
         token.refresh_token = Some(refresh_token);
+        self.register_session(user.id, session_id, client).await?;
 
+        // Each login starts a new refresh-token family so reuse detection can
+        // revoke this lineage without touching the user's other devices.
+        let family_id = random_id::v4_string()?;
         self.session_stores
-            .session_metadata
-            .add_session_for_user(i64::from(user.id), session_id)
+            .refresh_families
+            .add_session_to_family(&family_id, session_id)
             .await?;
-        self.session_stores
-            .session_metadata
+
+        Ok(token)
+    }
+
+    /// List `session_id` among the user's sessions, with the client that
+    /// started it, so it can be seen, expired and revoked like any other.
+    pub(super) async fn register_session(
+        &self,
+        user_id: UserId,
+        session_id: &str,
+        client: &ClientInfo,
+    ) -> AppResult<()> {
+        let metadata = &self.session_stores.session_metadata;
+        metadata
+            .add_session_for_user(i64::from(user_id), session_id)
+            .await?;
+        let now = self.clock.now().timestamp();
+        metadata
             .set_session_metadata(
-                i64::from(user.id),
+                i64::from(user_id),
                 session_id,
-                None,
-                None,
-                self.clock.now().timestamp(),
+                client.user_agent.as_deref(),
+                client.ip_address.as_deref(),
+                now,
             )
             .await?;
-
-        Ok(token)
+        metadata
+            .set_session_lifetime(session_id, now, self.session_policy.expires_at_unix(now))
+            .await?;
+        if let Some(fingerprint) = self.session_policy.binding.fingerprint(client) {
+            metadata
+                .set_session_fingerprint(session_id, &fingerprint)
+                .await?;
+        }
+        Ok(())
     }
 
     async fn create_session_refresh_nonce(&self, session_id: &str) -> AppResult<String> {
@@ -94,11 +189,25 @@ impl UserCommandService {
         Ok(refresh_nonce)
     }
 
+    /// The username of the account that verified `email`. An address no
+    /// account has verified stands in for the username, so the attempt fails
+    /// and is throttled like an unknown username.
+    async fn username_for_email(&self, email: &Email) -> AppResult<Username> {
+        match self.user_repo.find_by_email(email).await? {
+            Some(user) if user.email_verified => Ok(user.username),
+            _ => Ok(Username::new(email.as_str())?),
+        }
+    }
+
     async fn find_and_authenticate_user(
         &self,
         username: Username,
         password: &str,
     ) -> AppResult<crate::domain::User> {
+        if let Some(user) = self.authenticate_externally(&username, password).await? {
+            return Ok(user);
+        }
+
         let user = self
             .user_repo
             .find_by_username(&username)
@@ -112,7 +221,95 @@ impl UserCommandService {
         self.password_hasher
             .verify(password, user.password_hash.as_str())
             .await?;
+        self.upgrade_password_hash(&user, password).await;
 
         Ok(user)
     }
+
+    /// Rehash a just-verified password whose hash predates the current
+    /// hashing parameters. Best effort: the login succeeds regardless, and
+    /// read-only mode leaves the old hash in place.
+    async fn upgrade_password_hash(&self, user: &User, password: &str) {
+        if self.read_only.is_enabled()
+            || !self
+                .password_hasher
+                .needs_rehash(user.password_hash.as_str())
+        {
+            return;
+        }
+
+        let upgraded = async {
+            let hashed = self.password_hasher.hash(password).await?;
+            let update = UserUpdate::new(user.id).with_password_hash(PasswordHash::new(hashed)?);
+            self.user_repo.update(update).await?;
+            AppResult::Ok(())
+        };
+        if let Err(err) = upgraded.await {
+            tracing::warn!(error = %err, user_id = i64::from(user.id), "failed to upgrade password hash");
+        }
+    }
+
+    /// Try the external directory, if configured.
+    ///
+    /// Returns `Ok(None)` to fall through to the local password check: when
+    /// no directory is configured, it rejects the credentials, or it cannot
+    /// be reached (so local accounts keep working during an outage).
+    async fn authenticate_externally(
+        &self,
+        username: &Username,
+        password: &str,
+    ) -> AppResult<Option<User>> {
+        let Some(external) = &self.external_auth else {
+            return Ok(None);
+        };
+        let identity = match external
+            .authenticator
+            .authenticate(username.as_str(), password)
+            .await
+        {
+            Ok(Some(identity)) => identity,
+            Ok(None) => return Ok(None),
+            Err(err) => {
+                tracing::warn!(error = %err, "external authentication failed, trying local password");
+                return Ok(None);
+            }
+        };
+
+        let user = match self.user_repo.find_by_username(username).await? {
+            Some(user) => user,
+            None => {
+                self.provision_external_user(username, &identity, &external.roles)
+                    .await?
+            }
+        };
+        if !user.is_active {
+            return Err(AppError::forbidden("account is disabled"));
+        }
+        Ok(Some(user))
+    }
+
+    /// Create the local account for a directory user's first login.
+    ///
+    /// The stored hash is of a random secret, so the account can only be
+    /// entered through the directory (or after a local password reset).
+    async fn provision_external_user(
+        &self,
+        username: &Username,
+        identity: &ExternalIdentity,
+        roles: &GroupRoleMapping,
+    ) -> AppResult<User> {
+        let role = roles.role_for(&identity.groups).ok_or_else(|| {
+            AppError::forbidden("no role is mapped to the user's directory groups")
+        })?;
+        let password_hash = unusable_password_hash(self.password_hasher.as_ref()).await?;
+        let new_user = NewUser::new(username.clone(), password_hash, role, self.clock.now())?;
+        let user = self.user_repo.insert(new_user).await?;
+        tracing::info!(
+            user_id = i64::from(user.id),
+            role = role.as_str(),
+            subject = %identity.subject,
+            "provisioned user from external directory"
+        );
+        Ok(user)
+    }
 }
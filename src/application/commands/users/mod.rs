@@ -1,15 +1,27 @@
 mod capability;
 mod change_password;
+mod deletion;
+mod impersonate;
 mod login;
 mod password;
+mod preferences;
+mod recovery;
 mod refresh;
 mod register;
 mod role;
 mod service;
+mod throttle;
 mod update;
 
 pub use change_password::ChangePasswordCommand;
+pub use deletion::DeleteAccountCommand;
 pub use login::{LoginResult, LoginUserCommand};
+pub use password::MIN_PASSWORD_LENGTH;
+pub(crate) use password::{unusable_password_hash, validate_password};
+pub use preferences::UpdatePreferencesCommand;
+pub use recovery::{
+    ChangeEmailCommand, RequestPasswordResetCommand, ResetPasswordCommand, VerifyEmailCommand,
+};
 pub use refresh::RefreshTokenCommand;
 pub use register::RegisterUserCommand;
 pub use role::{GrantRoleCommand, RevokeRoleCommand};
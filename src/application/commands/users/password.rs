@@ -1,8 +1,12 @@
+use super::UserCommandService;
 use crate::application::error::{AppError, AppResult};
+use crate::application::{ports::security::PasswordHasher, random_id};
+use crate::domain::PasswordHash;
 
-pub(super) const MIN_PASSWORD_LENGTH: usize = 12;
+/// Shortest accepted password, in bytes.
+pub const MIN_PASSWORD_LENGTH: usize = 12;
 
-pub(super) fn validate_password(password: &str) -> AppResult<()> {
+pub fn validate_password(password: &str) -> AppResult<()> {
     if password.len() < MIN_PASSWORD_LENGTH {
         return Err(AppError::validation(format!(
             "password must be at least {MIN_PASSWORD_LENGTH} characters"
@@ -22,3 +26,31 @@ pub(super) fn validate_password(password: &str) -> AppResult<()> {
 
     Ok(())
 }
+
+impl UserCommandService {
+    /// Refuse `password` if it appears in a known data breach. Failed
+    /// lookups are logged and let through, so an unreachable breach service
+    /// does not stop registrations and password changes.
+    pub(super) async fn ensure_password_not_breached(&self, password: &str) -> AppResult<()> {
+        let Some(checker) = &self.breach_checker else {
+            return Ok(());
+        };
+        match checker.breach_count(password).await {
+            Ok(0) => Ok(()),
+            Ok(_) => Err(AppError::validation(
+                "password has appeared in a data breach; choose a different one",
+            )),
+            Err(err) => {
+                tracing::warn!(error = %err, "password breach check failed");
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Hash of a random secret nobody knows, for accounts created without a
+/// local password (imports, directory and upstream OIDC logins).
+pub async fn unusable_password_hash(hasher: &dyn PasswordHasher) -> AppResult<PasswordHash> {
+    let secret = random_id::v4_string()?;
+    Ok(PasswordHash::new(hasher.hash(&secret).await?)?)
+}
@@ -0,0 +1,70 @@
+use super::UserCommandService;
+use crate::{
+    application::{
+        AuthenticatedUser, UserProfileDto, error::AppResult,
+        ports::command_journal::JournaledCommand, services::AuditEvent,
+    },
+    domain::{Timezone, UserUpdate},
+};
+
+pub struct UpdatePreferencesCommand {
+    /// IANA timezone name; `None` clears the stored preference.
+    pub timezone: Option<String>,
+}
+
+impl UserCommandService {
+    /// Update the authenticated user's own display preferences.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the timezone is not a known IANA name, the user
+    /// record is missing, or persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn update_preferences(
+        &self,
+        actor: &AuthenticatedUser,
+        command: UpdatePreferencesCommand,
+    ) -> AppResult<UserProfileDto> {
+        self.read_only.ensure_writable()?;
+        let journaled = self
+            .journal
+            .capture(|| JournaledCommand::UpdatePreferences {
+                timezone: command.timezone.clone(),
+            });
+        let result = self.update_preferences_inner(actor, command).await;
+        self.journal
+            .record(
+                self.clock.now(),
+                Some(actor),
+                journaled,
+                &result,
+                |profile| Some(profile.user.id),
+            )
+            .await;
+        result
+    }
+
+    async fn update_preferences_inner(
+        &self,
+        actor: &AuthenticatedUser,
+        command: UpdatePreferencesCommand,
+    ) -> AppResult<UserProfileDto> {
+        let timezone = command
+            .timezone
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(Timezone::new)
+            .transpose()?;
+
+        let update = UserUpdate::new(actor.id).with_timezone(timezone);
+        let user = self.user_repo.update(update).await?;
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("user.preferences_update", "user", Some(i64::from(actor.id))),
+            )
+            .await;
+        Ok(UserProfileDto::from_parts(user, actor, self.clock.now()))
+    }
+}
@@ -0,0 +1,293 @@
+use super::{UserCommandService, password::validate_password, service::AccountRecovery};
+use crate::{
+    application::{
+        AuthenticatedUser, UserProfileDto,
+        error::{AppError, AppResult},
+        ports::{
+            notification::EmailMessage,
+            user_tokens::{TokenPurpose, UserToken},
+        },
+        random_id,
+        services::AuditEvent,
+    },
+    domain::{Email, PasswordHash, User, UserId, UserUpdate},
+};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::Duration;
+use sha2::{Digest, Sha256};
+
+/// How long a mailed password reset token stays usable.
+const PASSWORD_RESET_TTL: Duration = Duration::minutes(30);
+/// How long a mailed email verification token stays usable.
+const EMAIL_VERIFICATION_TTL: Duration = Duration::hours(24);
+
+pub struct RequestPasswordResetCommand {
+    pub email: String,
+}
+
+pub struct ResetPasswordCommand {
+    pub token: String,
+    pub new_password: String,
+}
+
+pub struct ChangeEmailCommand {
+    /// `None` or a blank value removes the address.
+    pub email: Option<String>,
+}
+
+pub struct VerifyEmailCommand {
+    pub token: String,
+}
+
+impl UserCommandService {
+    /// Mail a password reset token to `command.email`.
+    ///
+    /// Nothing is sent unless an active user has verified that address, and
+    /// the result is the same either way so callers cannot probe which
+    /// addresses are registered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address is malformed, account recovery is not
+    /// configured, or storing or mailing the token fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn request_password_reset(
+        &self,
+        command: RequestPasswordResetCommand,
+    ) -> AppResult<()> {
+        self.read_only.ensure_writable()?;
+        let recovery = self.recovery()?;
+        let email = Email::new(&command.email)?;
+        let Some(user) = self
+            .user_repo
+            .find_by_email(&email)
+            .await?
+            .filter(|user| user.is_active && user.email_verified)
+        else {
+            return Ok(());
+        };
+
+        let token = self
+            .issue_user_token(recovery, &user, TokenPurpose::PasswordReset)
+            .await?;
+        recovery
+            .mailer
+            .send(EmailMessage {
+                to: email.to_string(),
+                subject: "Reset your password".into(),
+                body: format!(
+                    "Hello {},\n\nUse this token to choose a new password. It expires in {} minutes.\n\n{token}\n\nIf you did not ask for a reset, ignore this message.",
+                    user.username,
+                    PASSWORD_RESET_TTL.num_minutes()
+                ),
+            })
+            .await
+    }
+
+    /// Set a new password with a mailed reset token and sign the user out
+    /// everywhere.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the password is too weak, the token is unknown,
+    /// used or expired, or persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn reset_password(&self, command: ResetPasswordCommand) -> AppResult<()> {
+        self.read_only.ensure_writable()?;
+        // Reject weak passwords before the token is spent, and hash outside
+        // the unit of work so it is not held open meanwhile.
+        validate_password(&command.new_password)?;
+        self.ensure_password_not_breached(&command.new_password)
+            .await?;
+        let password_hash =
+            PasswordHash::new(self.password_hasher.hash(&command.new_password).await?)?;
+        let user_id = self
+            .redeem_user_token(&command.token, TokenPurpose::PasswordReset, |user| {
+                Ok(UserUpdate::new(user.id)
+                    .with_password_hash(password_hash)
+                    .with_password_reset_required(false))
+            })
+            .await?;
+        self.session_stores
+            .revocation
+            .revoke_sessions_for_user(i64::from(user_id))
+            .await?;
+        self.audit
+            .record(
+                Some(user_id),
+                AuditEvent::new("user.password_reset", "user", Some(i64::from(user_id))),
+            )
+            .await;
+        Ok(())
+    }
+
+    /// Change the authenticated user's own email address and mail a
+    /// verification token to the new one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address is malformed or used by another
+    /// user, account recovery is not configured, or persistence or mailing
+    /// fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn change_email(
+        &self,
+        actor: &AuthenticatedUser,
+        command: ChangeEmailCommand,
+    ) -> AppResult<UserProfileDto> {
+        self.read_only.ensure_writable()?;
+        let recovery = self.recovery()?;
+        let email = command
+            .email
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(Email::new)
+            .transpose()?;
+        if let Some(email) = &email
+            && let Some(owner) = self.user_repo.find_by_email(email).await?
+            && owner.id != actor.id
+        {
+            return Err(AppError::conflict("email address is already in use"));
+        }
+
+        let update = UserUpdate::new(actor.id)
+            .with_email(email.clone())
+            .with_email_verified(false);
+        let user = self.user_repo.update(update).await?;
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("user.email_change", "user", Some(i64::from(actor.id))),
+            )
+            .await;
+
+        if let Some(email) = email {
+            let token = self
+                .issue_user_token(recovery, &user, TokenPurpose::EmailVerification)
+                .await?;
+            recovery
+                .mailer
+                .send(EmailMessage {
+                    to: email.to_string(),
+                    subject: "Verify your email address".into(),
+                    body: format!(
+                        "Hello {},\n\nUse this token to verify your email address. It expires in {} hours.\n\n{token}",
+                        user.username,
+                        EMAIL_VERIFICATION_TTL.num_hours()
+                    ),
+                })
+                .await?;
+        }
+
+        Ok(UserProfileDto::from_parts(user, actor, self.clock.now()))
+    }
+
+    /// Mark the address a mailed verification token was sent to as verified.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token is unknown, used or expired, the user
+    /// has since removed their address, or persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn verify_email(&self, command: VerifyEmailCommand) -> AppResult<()> {
+        self.read_only.ensure_writable()?;
+        let user_id = self
+            .redeem_user_token(&command.token, TokenPurpose::EmailVerification, |user| {
+                if user.email.is_none() {
+                    return Err(AppError::validation("no email address to verify"));
+                }
+                Ok(UserUpdate::new(user.id).with_email_verified(true))
+            })
+            .await?;
+        self.audit
+            .record(
+                Some(user_id),
+                AuditEvent::new("user.email_verify", "user", Some(i64::from(user_id))),
+            )
+            .await;
+        Ok(())
+    }
+
+    fn recovery(&self) -> AppResult<&AccountRecovery> {
+        self.recovery
+            .as_ref()
+            .ok_or_else(|| AppError::infrastructure("account recovery is not configured"))
+    }
+
+    async fn issue_user_token(
+        &self,
+        recovery: &AccountRecovery,
+        user: &User,
+        purpose: TokenPurpose,
+    ) -> AppResult<String> {
+        let token = random_id::secret_token()?;
+        let now = self.clock.now();
+        let ttl = match purpose {
+            TokenPurpose::PasswordReset => PASSWORD_RESET_TTL,
+            TokenPurpose::EmailVerification => EMAIL_VERIFICATION_TTL,
+        };
+        recovery
+            .tokens
+            .create(UserToken {
+                token_hash: hash_token(&token),
+                user_id: user.id,
+                purpose,
+                created_at: now,
+                expires_at: now + ttl,
+            })
+            .await?;
+        Ok(token)
+    }
+
+    /// Spend a mailed `token` and apply the update `change` makes for its
+    /// user. With a unit of work both happen together, so a refused or
+    /// failed update leaves the token usable; without one the token is
+    /// spent first.
+    async fn redeem_user_token(
+        &self,
+        token: &str,
+        purpose: TokenPurpose,
+        change: impl FnOnce(&User) -> AppResult<UserUpdate> + Send,
+    ) -> AppResult<UserId> {
+        let recovery = self.recovery()?;
+        let token_hash = hash_token(token.trim());
+        let now = self.clock.now();
+
+        let Some(unit_of_work) = &self.unit_of_work else {
+            let user_id = recovery
+                .tokens
+                .consume(&token_hash, purpose, now)
+                .await?
+                .ok_or_else(invalid_token)?;
+            let user = self
+                .user_repo
+                .find_by_id(user_id)
+                .await?
+                .ok_or_else(|| AppError::not_found("user not found"))?;
+            self.user_repo.update(change(&user)?).await?;
+            return Ok(user_id);
+        };
+
+        let mut work = unit_of_work.begin().await?;
+        let user_id = work
+            .consume_user_token(&token_hash, purpose, now)
+            .await?
+            .ok_or_else(invalid_token)?;
+        let user = work
+            .find_user(user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("user not found"))?;
+        work.update_user(change(&user)?).await?;
+        work.commit().await?;
+        Ok(user_id)
+    }
+}
+
+fn invalid_token() -> AppError {
+    AppError::validation("token is invalid or has expired")
+}
+
+fn hash_token(token: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(token.as_bytes()))
+}
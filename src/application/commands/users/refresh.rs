@@ -3,8 +3,12 @@ use crate::{
     application::{
         AuthTokenDto, TokenSubject,
         error::{AppError, AppResult},
-        ports::session_revocation::RefreshTokenRecord,
+        ports::{
+            security_events::{ClientInfo, TokenReuseIncident},
+            session_revocation::{RefreshBindingMode, RefreshTokenRecord, SessionInfo},
+        },
         random_id,
+        services::AuditEvent,
     },
     domain::UserId,
 };
@@ -19,52 +23,87 @@ pub struct RefreshTokenCommand {
     pub token: String,
 }
 
+struct RefreshContext<'a> {
+    user: crate::domain::User,
+    session: SessionInfo,
+    nonce: String,
+    client: &'a ClientInfo,
+}
+
 impl UserCommandService {
     /// Rotate a refresh token and issue a new access token pair.
     ///
     /// # Errors
     ///
-    /// Returns an error if the refresh token is invalid, reused, revoked, or
-    /// if the backing session or user can no longer be loaded.
+    /// Returns an error if the refresh token is invalid, reused or revoked, if
+    /// its session has expired, or if the backing session or user can no
+    /// longer be loaded.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn refresh_token(&self, command: RefreshTokenCommand) -> AppResult<AuthTokenDto> {
-        let (user, session_id, nonce, _token_ver) = self
-            .validate_and_load_user_from_refresh_token(&command.token)
-            .await?;
+        self.refresh_token_from_client(command, &ClientInfo::default())
+            .await
+    }
 
-        let new_access = self
-            .perform_refresh_for_user(&user, &session_id, &nonce)
+    /// Rotate a refresh token on behalf of `client`.
+    ///
+    /// The client details are recorded on the session and, if the token turns
+    /// out to be reused, reported as the suspect side of the incident. When
+    /// the session is bound to the client that logged in, a different
+    /// client is audited and, if binding is enforced, refused.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the refresh token is invalid, reused or revoked, if
+    /// its session has expired or is bound to another client, or if the
+    /// backing session or user can no longer be loaded.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn refresh_token_from_client(
+        &self,
+        command: RefreshTokenCommand,
+        client: &ClientInfo,
+    ) -> AppResult<AuthTokenDto> {
+        let (user, session, nonce) = self
+            .validate_and_load_user_from_refresh_token(&command.token)
             .await?;
+        self.check_refresh_client(&session, client).await?;
 
-        Ok(new_access)
+        self.perform_refresh_for_user(RefreshContext {
+            user,
+            session,
+            nonce,
+            client,
+        })
+        .await
     }
 
     async fn validate_and_load_user_from_refresh_token(
         &self,
         token: &str,
-    ) -> AppResult<(crate::domain::User, String, String, u32)> {
+    ) -> AppResult<(crate::domain::User, SessionInfo, String)> {
         let ParsedRefreshToken {
             session_id,
             nonce,
             token_version,
         } = self.parse_refresh_token(token).await?;
-        let user_id = self.user_id_for_session(&session_id).await?;
+        let session = self.session_for_refresh(&session_id).await?;
         self.ensure_session_not_revoked(&session_id).await?;
-        let user = self.load_user_for_refresh(user_id).await?;
+        self.ensure_session_not_expired(&session)?;
+        let user = self
+            .load_user_for_refresh(UserId::new(session.user_id)?)
+            .await?;
 
         self.ensure_token_version_not_revoked(&user, token_version)
             .await?;
 
-        Ok((user, session_id, nonce, token_version))
+        Ok((user, session, nonce))
     }
 
-    async fn user_id_for_session(&self, session_id: &str) -> AppResult<UserId> {
-        let meta = self
-            .session_stores
+    async fn session_for_refresh(&self, session_id: &str) -> AppResult<SessionInfo> {
+        self.session_stores
             .session_metadata
             .get_session_metadata(session_id)
             .await?
-            .ok_or_else(|| AppError::validation("invalid refresh token"))?;
-        UserId::new(meta.user_id).map_err(Into::into)
+            .ok_or_else(|| AppError::validation("invalid refresh token"))
     }
 
     async fn ensure_session_not_revoked(&self, session_id: &str) -> AppResult<()> {
@@ -80,6 +119,57 @@ impl UserCommandService {
         Ok(())
     }
 
+    fn ensure_session_not_expired(&self, session: &SessionInfo) -> AppResult<()> {
+        if session.is_expired(self.session_policy, self.clock.now().timestamp()) {
+            return Err(AppError::unauthorized("session expired"));
+        }
+
+        Ok(())
+    }
+
+    /// Compare `client` with the fingerprint the session was bound to at
+    /// login. Sessions started while binding was off carry none and are not
+    /// checked.
+    async fn check_refresh_client(
+        &self,
+        session: &SessionInfo,
+        client: &ClientInfo,
+    ) -> AppResult<()> {
+        let binding = self.session_policy.binding;
+        let (Some(bound), Some(presented)) =
+            (session.fingerprint.as_deref(), binding.fingerprint(client))
+        else {
+            return Ok(());
+        };
+        if bound == presented {
+            return Ok(());
+        }
+
+        let enforced = binding.mode == RefreshBindingMode::Enforce;
+        self.audit
+            .record_from(
+                client,
+                Some(UserId::new(session.user_id)?),
+                AuditEvent::new(
+                    "auth.refresh_client_mismatch",
+                    "user",
+                    Some(session.user_id),
+                )
+                .with_details(serde_json::json!({
+                    "session_id": session.session_id,
+                    "refused": enforced,
+                })),
+            )
+            .await;
+        if enforced {
+            return Err(AppError::forbidden(
+                "refresh token is bound to another client",
+            ));
+        }
+
+        Ok(())
+    }
+
     async fn load_user_for_refresh(&self, user_id: UserId) -> AppResult<crate::domain::User> {
         self.user_repo
             .find_by_id(user_id)
@@ -105,42 +195,45 @@ impl UserCommandService {
         Ok(())
     }
 
-    async fn perform_refresh_for_user(
-        &self,
-        user: &crate::domain::User,
-        session_id: &str,
-        expected_nonce: &str,
-    ) -> AppResult<AuthTokenDto> {
+    async fn perform_refresh_for_user(&self, ctx: RefreshContext<'_>) -> AppResult<AuthTokenDto> {
+        let RefreshContext {
+            user,
+            session,
+            nonce: expected_nonce,
+            client,
+        } = ctx;
+        let session_id = session.session_id.as_str();
         let new_nonce = random_id::v4_string()?;
         let swapped = self
             .session_stores
             .refresh_nonces
-            .compare_and_swap_session_refresh_nonce(session_id, expected_nonce, &new_nonce)
+            .compare_and_swap_session_refresh_nonce(session_id, &expected_nonce, &new_nonce)
             .await?;
 
         if !swapped {
             let nonce_already_used = self
                 .session_stores
                 .refresh_nonces
-                .is_session_refresh_nonce_used(session_id, expected_nonce)
+                .is_session_refresh_nonce_used(session_id, &expected_nonce)
                 .await?;
 
             if nonce_already_used {
-                self.session_stores
-                    .revocation
-                    .revoke_sessions_for_user(i64::from(user.id))
-                    .await?;
+                let family_id = self.revoke_refresh_family(session_id).await?;
+                self.report_token_reuse(&session, family_id, client).await;
                 return Err(AppError::forbidden("refresh token reused"));
             }
 
             return Err(AppError::forbidden("refresh token invalid or rotated"));
         }
 
-        let subject = Self::make_token_subject(user, session_id);
+        self.record_refresh_client(&session, client).await?;
+        self.extend_session(&session).await?;
+
+        let subject = self.token_subject(&user, session_id).await?;
         let mut new_access = self.token_manager.issue(subject).await?;
 
         let new_refresh_token = self
-            .build_refresh_token_for_user(user, session_id, &new_nonce)
+            .build_refresh_token_for_user(&user, session_id, &new_nonce)
             .await?;
 
         new_access.refresh_token = Some(new_refresh_token);
@@ -148,15 +241,130 @@ impl UserCommandService {
         Ok(new_access)
     }
 
-    fn make_token_subject(user: &crate::domain::User, session_id: &str) -> TokenSubject {
-        TokenSubject {
+    /// Remember the client behind the latest successful refresh so a later
+    /// reuse incident can name the legitimate side.
+    async fn record_refresh_client(
+        &self,
+        session: &SessionInfo,
+        client: &ClientInfo,
+    ) -> AppResult<()> {
+        if client.is_empty() {
+            return Ok(());
+        }
+
+        self.session_stores
+            .session_metadata
+            .set_session_metadata(
+                session.user_id,
+                &session.session_id,
+                client.user_agent.as_deref(),
+                client.ip_address.as_deref(),
+                session.created_at_unix,
+            )
+            .await
+    }
+
+    /// Mark the session active now. Its absolute deadline stays put;
+    /// sessions from before deadlines were stored get one from their
+    /// creation time.
+    async fn extend_session(&self, session: &SessionInfo) -> AppResult<()> {
+        let expires_at = if session.expires_at_unix > 0 || session.created_at_unix <= 0 {
+            session.expires_at_unix
+        } else {
+            self.session_policy.expires_at_unix(session.created_at_unix)
+        };
+
+        self.session_stores
+            .session_metadata
+            .set_session_lifetime(
+                &session.session_id,
+                self.clock.now().timestamp(),
+                expires_at,
+            )
+            .await
+    }
+
+    async fn report_token_reuse(
+        &self,
+        session: &SessionInfo,
+        family_id: Option<String>,
+        client: &ClientInfo,
+    ) {
+        let Some(sink) = &self.security_events else {
+            return;
+        };
+
+        let incident = TokenReuseIncident {
+            user_id: session.user_id,
+            session_id: session.session_id.clone(),
+            family_id,
+            legitimate: ClientInfo {
+                ip_address: session.ip_address.clone(),
+                user_agent: session.user_agent.clone(),
+            },
+            suspect: client.clone(),
+            detected_at: self.clock.now(),
+        };
+
+        // Reporting is best effort: the reuse has already been contained by
+        // revoking the family, so a reporting failure must not mask the 403.
+        if let Err(err) = sink.token_reuse_detected(&incident).await {
+            tracing::warn!(error = %err, session_id = %incident.session_id, "failed to report refresh token reuse");
+        }
+    }
+
+    /// Revoke the refresh-token family the reused session belongs to, leaving
+    /// the user's unrelated sessions (other devices) intact.
+    async fn revoke_refresh_family(&self, session_id: &str) -> AppResult<Option<String>> {
+        let family_id = self
+            .session_stores
+            .refresh_families
+            .family_for_session(session_id)
+            .await?;
+
+        match &family_id {
+            Some(family_id) => {
+                self.session_stores
+                    .refresh_families
+                    .revoke_family(family_id)
+                    .await?;
+            }
+            // Sessions issued before families were tracked only revoke themselves.
+            None => self.session_stores.revocation.revoke(session_id).await?,
+        }
+
+        Ok(family_id)
+    }
+
+    async fn token_subject(
+        &self,
+        user: &crate::domain::User,
+        session_id: &str,
+    ) -> AppResult<TokenSubject> {
+        let (capabilities, custom_role) = self.token_grants(user).await?;
+        Ok(TokenSubject {
             user_id: user.id,
             username: user.username.to_string(),
             role: user.role,
-            capabilities: user.role.default_capabilities(),
+            capabilities,
             session_id: Some(session_id.to_string()),
-            token_version: None,
-        }
+            token_version: self.current_token_version(user).await?,
+            custom_role,
+            impersonator: None,
+        })
+    }
+
+    /// The version new access tokens for `user` carry: their minimum token
+    /// version once one has been set, so logging them out everywhere does
+    /// not also reject the tokens issued afterwards.
+    pub(super) async fn current_token_version(
+        &self,
+        user: &crate::domain::User,
+    ) -> AppResult<Option<u32>> {
+        self.session_stores
+            .token_versions
+            .get_min_token_version(i64::from(user.id))
+            .await
     }
 
     pub(super) async fn build_refresh_token_for_user(
@@ -1,10 +1,14 @@
 use super::{UserCommandService, password::validate_password};
+use serde_json::json;
+
 use crate::{
     application::{
         AuthenticatedUser, UserDto,
         error::{AppError, AppResult},
+        ports::command_journal::JournaledCommand,
+        services::AuditEvent,
     },
-    domain::{NewUser, PasswordHash, Role, Username},
+    domain::{EventKind, LoginIdentifier, NewUser, PasswordHash, Role, UserId, Username},
 };
 
 pub struct RegisterUserCommand {
@@ -21,44 +25,84 @@ impl UserCommandService {
     /// Returns an error if the username or password is invalid, the caller is
     /// not allowed to choose the requested role, the username is taken, or
     /// persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn register(
         &self,
         actor: Option<&AuthenticatedUser>,
         command: RegisterUserCommand,
     ) -> AppResult<UserDto> {
-        let username = Username::new(command.username)?;
+        self.read_only.ensure_writable()?;
+        let journaled = self.journal.capture(|| JournaledCommand::RegisterUser {
+            username: command.username.clone(),
+            role: command.role,
+        });
+        let result = self.register_inner(actor, command).await;
+        self.journal
+            .record(self.clock.now(), actor, journaled, &result, |user| {
+                Some(user.id)
+            })
+            .await;
+        if let Ok(user) = &result {
+            self.audit
+                .record(
+                    actor.map(|actor| actor.id),
+                    AuditEvent::new("user.create", "user", Some(user.id))
+                        .with_details(json!({ "username": user.username, "role": user.role })),
+                )
+                .await;
+            if let Ok(user_id) = UserId::new(user.id) {
+                self.publish(EventKind::UserRegistered(user_id)).await;
+            }
+        }
+        result
+    }
+
+    async fn register_inner(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        command: RegisterUserCommand,
+    ) -> AppResult<UserDto> {
+        let username = LoginIdentifier::parse_username(&command.username)?;
         validate_password(&command.password)?;
-        let existing = self.user_repo.count().await?;
-        let role = Self::determine_role(existing, actor, command.role)?;
+        self.ensure_password_not_breached(&command.password).await?;
 
-        self.ensure_username_available(existing, &username).await?;
+        if self.user_repo.count().await? == 0
+            && let Some(user) = self
+                .try_bootstrap_admin(username.clone(), &command.password)
+                .await?
+        {
+            return Ok(user.into());
+        }
+
+        let role = Self::determine_role(actor, command.role)?;
+        self.ensure_username_available(&username).await?;
 
         let user = self
-            .create_and_insert_user(username.clone(), &command.password, role)
+            .create_and_insert_user(username, &command.password, role)
             .await?;
 
         Ok(user.into())
     }
 
-    fn determine_role(
-        existing: u64,
-        actor: Option<&AuthenticatedUser>,
-        role: Option<Role>,
-    ) -> AppResult<Role> {
-        if existing == 0 {
-            return Ok(Role::Admin);
-        }
+    /// Attempt to create the first administrator. Returns `None` when another
+    /// registration has already bootstrapped the instance.
+    async fn try_bootstrap_admin(
+        &self,
+        username: Username,
+        password: &str,
+    ) -> AppResult<Option<crate::domain::User>> {
+        let new_user = self.build_new_user(username, password, Role::Admin).await?;
+        Ok(self.user_repo.insert_bootstrap_admin(new_user).await?)
+    }
+
+    fn determine_role(actor: Option<&AuthenticatedUser>, role: Option<Role>) -> AppResult<Role> {
         let requester =
             actor.ok_or_else(|| AppError::forbidden("administrative privileges are required"))?;
         super::capability::ensure_capability(requester, "users", "create")?;
         Ok(role.unwrap_or(Role::Author))
     }
 
-    async fn ensure_username_available(&self, existing: u64, username: &Username) -> AppResult<()> {
-        if existing == 0 {
-            return Ok(());
-        }
-
+    async fn ensure_username_available(&self, username: &Username) -> AppResult<()> {
         if self.user_repo.find_by_username(username).await?.is_some() {
             return Err(AppError::conflict("username already exists"));
         }
@@ -72,13 +116,22 @@ impl UserCommandService {
         password: &str,
         role: Role,
     ) -> AppResult<crate::domain::User> {
+        let new_user = self.build_new_user(username, password, role).await?;
+        let user = self.user_repo.insert(new_user).await?;
+
+        Ok(user)
+    }
+
+    async fn build_new_user(
+        &self,
+        username: Username,
+        password: &str,
+        role: Role,
+    ) -> AppResult<NewUser> {
         let hashed = self.password_hasher.hash(password).await?;
         let password_hash = PasswordHash::new(hashed)?;
 
         let created_at = self.clock.now();
-        let new_user = NewUser::new(username, password_hash, role, created_at)?;
-        let user = self.user_repo.insert(new_user).await?;
-
-        Ok(user)
+        Ok(NewUser::new(username, password_hash, role, created_at)?)
     }
 }
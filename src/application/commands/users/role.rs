@@ -1,7 +1,12 @@
 use super::{UserCommandService, capability::ensure_capability};
+use serde_json::json;
+
 use crate::{
-    application::{AuthenticatedUser, UserDto, error::AppResult},
-    domain::{Role, UserId, UserUpdate},
+    application::{
+        AuthenticatedUser, UserDto, error::AppResult, ports::command_journal::JournaledCommand,
+        services::AuditEvent,
+    },
+    domain::{EventKind, Role, UserId, UserUpdate},
 };
 
 pub struct GrantRoleCommand {
@@ -20,18 +25,24 @@ impl UserCommandService {
     ///
     /// Returns an error if the actor lacks `users:update`, the user id is
     /// invalid, or the repository update fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn grant_role(
         &self,
         actor: &AuthenticatedUser,
         command: GrantRoleCommand,
     ) -> AppResult<UserDto> {
-        ensure_capability(actor, "users", "update")?;
-
-        let user_id = UserId::new(command.user_id)?;
-        let update = UserUpdate::new(user_id).with_role(command.role);
-
-        let user = self.user_repo.update(update).await?;
-        Ok(user.into())
+        self.read_only.ensure_writable()?;
+        let journaled = self.journal.capture(|| JournaledCommand::GrantRole {
+            user_id: command.user_id,
+            role: command.role,
+        });
+        let result = self.set_role(actor, command.user_id, command.role).await;
+        self.journal
+            .record(self.clock.now(), Some(actor), journaled, &result, |user| {
+                Some(user.id)
+            })
+            .await;
+        result
     }
 
     /// Revoke an elevated role from a user.
@@ -40,17 +51,45 @@ impl UserCommandService {
     ///
     /// Returns an error if the actor lacks `users:update`, the user id is
     /// invalid, or the repository update fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn revoke_role(
         &self,
         actor: &AuthenticatedUser,
         command: RevokeRoleCommand,
+    ) -> AppResult<UserDto> {
+        self.read_only.ensure_writable()?;
+        let journaled = self.journal.capture(|| JournaledCommand::RevokeRole {
+            user_id: command.user_id,
+        });
+        let result = self.set_role(actor, command.user_id, Role::Author).await;
+        self.journal
+            .record(self.clock.now(), Some(actor), journaled, &result, |user| {
+                Some(user.id)
+            })
+            .await;
+        result
+    }
+
+    async fn set_role(
+        &self,
+        actor: &AuthenticatedUser,
+        user_id: i64,
+        role: Role,
     ) -> AppResult<UserDto> {
         ensure_capability(actor, "users", "update")?;
 
-        let user_id = UserId::new(command.user_id)?;
-        let update = UserUpdate::new(user_id).with_role(Role::Author);
+        let user_id = UserId::new(user_id)?;
+        let update = UserUpdate::new(user_id).with_role(role);
 
         let user = self.user_repo.update(update).await?;
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("user.role_change", "user", Some(i64::from(user_id)))
+                    .with_details(json!({ "role": role })),
+            )
+            .await;
+        self.publish(EventKind::UserUpdated(user_id)).await;
         Ok(user.into())
     }
 }
@@ -1,12 +1,32 @@
 use std::sync::Arc;
 
+use crate::application::AppResult;
+use crate::application::ReadOnlySwitch;
+use crate::application::commands::Recorder;
 use crate::application::ports::{
+    command_journal::CommandJournal,
+    domain_events::EventPublisher,
+    external_auth::{ExternalAuthenticator, GroupRoleMapping},
+    notification::EmailSender,
+    rate_limit::{LoginThrottlePolicy, RateLimiter},
     refresh_token::Codec,
-    security::{PasswordHasher, TokenManager},
-    session_revocation::{Ports, Store},
+    security::{PasswordBreachChecker, PasswordHasher, TokenManager},
+    security_events::SecurityEventSink,
+    session_revocation::{Ports, SessionPolicy, Store},
     time::Clock,
+    unit_of_work::UnitOfWork,
+    user_tokens::UserTokenStore,
 };
-use crate::domain::UserRepository;
+use crate::application::services::AuditRecorder;
+use crate::domain::audit::repository::AuditLogRepository;
+use crate::domain::{
+    AccountDeletions, ArticleWriteRepository, Capability, DomainEvent, EventKind, PasswordHistory,
+    RoleStore, User, UserRepository,
+};
+use chrono::Duration;
+use std::collections::HashSet;
+
+use super::throttle::LoginThrottle;
 
 #[must_use]
 pub struct UserCommandService {
@@ -16,6 +36,48 @@ pub struct UserCommandService {
     pub(super) refresh_token_codec: Arc<dyn Codec>,
     pub(super) session_stores: Ports,
     pub(super) clock: Arc<dyn Clock>,
+    pub(super) security_events: Option<Arc<dyn SecurityEventSink>>,
+    pub(super) external_auth: Option<ExternalAuth>,
+    pub(super) recovery: Option<AccountRecovery>,
+    pub(super) login_throttle: Option<LoginThrottle>,
+    pub(super) session_policy: SessionPolicy,
+    pub(super) password_reuse: Option<PasswordReuse>,
+    pub(super) breach_checker: Option<Arc<dyn PasswordBreachChecker>>,
+    pub(super) account_deletion: Option<AccountDeletion>,
+    pub(super) journal: Recorder,
+    pub(super) audit: AuditRecorder,
+    pub(super) read_only: ReadOnlySwitch,
+    pub(super) unit_of_work: Option<Arc<dyn UnitOfWork>>,
+    pub(super) custom_roles: Option<Arc<dyn RoleStore>>,
+    pub(super) publisher: Option<Arc<dyn EventPublisher>>,
+}
+
+/// Directory login tried before local passwords, with the roles new users get.
+pub(super) struct ExternalAuth {
+    pub(super) authenticator: Arc<dyn ExternalAuthenticator>,
+    pub(super) roles: GroupRoleMapping,
+}
+
+/// Where mailed password reset and verification tokens are kept, and how
+/// they are mailed.
+pub(super) struct AccountRecovery {
+    pub(super) tokens: Arc<dyn UserTokenStore>,
+    pub(super) mailer: Arc<dyn EmailSender>,
+}
+
+/// How many of a user's passwords a new one may not repeat, and where the
+/// replaced ones are kept.
+pub(super) struct PasswordReuse {
+    pub(super) history: Arc<dyn PasswordHistory>,
+    pub(super) depth: usize,
+}
+
+/// Where scheduled account deletions are kept, where deleted users'
+/// articles are reassigned, and how long deleted accounts stay restorable.
+pub(super) struct AccountDeletion {
+    pub(super) store: Arc<dyn AccountDeletions>,
+    pub(super) articles: Arc<dyn ArticleWriteRepository>,
+    pub(super) grace: Duration,
 }
 
 impl UserCommandService {
@@ -34,6 +96,184 @@ impl UserCommandService {
             refresh_token_codec,
             session_stores: Ports::from_store(session_revocation_store),
             clock,
+            security_events: None,
+            external_auth: None,
+            recovery: None,
+            login_throttle: None,
+            session_policy: SessionPolicy::default(),
+            password_reuse: None,
+            breach_checker: None,
+            account_deletion: None,
+            journal: Recorder::default(),
+            audit: AuditRecorder::default(),
+            read_only: ReadOnlySwitch::default(),
+            unit_of_work: None,
+            custom_roles: None,
+            publisher: None,
+        }
+    }
+
+    /// Append the outcome of every user write to `journal`. Logins, token
+    /// refreshes, password changes and resets, and email changes (which
+    /// depend on mailed tokens) are not journaled.
+    pub fn with_journal(mut self, journal: Arc<dyn CommandJournal>) -> Self {
+        self.journal = Recorder::new(journal);
+        self
+    }
+
+    /// Record account changes and logins in `audit_log_repo`.
+    pub fn with_audit(mut self, audit_log_repo: Arc<dyn AuditLogRepository>) -> Self {
+        self.audit = AuditRecorder::new(audit_log_repo);
+        self
+    }
+
+    /// Report security incidents (e.g. refresh-token reuse) to `sink`.
+    pub fn with_security_events(mut self, sink: Arc<dyn SecurityEventSink>) -> Self {
+        self.security_events = Some(sink);
+        self
+    }
+
+    /// Authenticate logins against an external directory first, provisioning
+    /// a local user with a role from `roles` on their first login.
+    pub fn with_external_authenticator(
+        mut self,
+        authenticator: Arc<dyn ExternalAuthenticator>,
+        roles: GroupRoleMapping,
+    ) -> Self {
+        self.external_auth = Some(ExternalAuth {
+            authenticator,
+            roles,
+        });
+        self
+    }
+
+    /// Enable email verification and password resets, keeping their tokens
+    /// in `tokens` and mailing them with `mailer`.
+    pub fn with_account_recovery(
+        mut self,
+        tokens: Arc<dyn UserTokenStore>,
+        mailer: Arc<dyn EmailSender>,
+    ) -> Self {
+        self.recovery = Some(AccountRecovery { tokens, mailer });
+        self
+    }
+
+    /// Lock accounts and clients out after repeated failed logins, counting
+    /// failures in `limiter` and recording lockouts in `audit_log_repo`.
+    pub fn with_login_throttle(
+        mut self,
+        limiter: Arc<dyn RateLimiter>,
+        policy: LoginThrottlePolicy,
+        audit_log_repo: Arc<dyn AuditLogRepository>,
+    ) -> Self {
+        self.login_throttle = Some(LoginThrottle {
+            limiter,
+            policy,
+            audit_log_repo,
+        });
+        self
+    }
+
+    /// Expire sessions per `policy`: refreshing is refused once a session
+    /// outlives its maximum age or sits idle past the idle timeout.
+    pub const fn with_session_policy(mut self, policy: SessionPolicy) -> Self {
+        self.session_policy = policy;
+        self
+    }
+
+    /// Refuse password changes to any of the user's last `depth` passwords,
+    /// the current one included, keeping replaced hashes in `history`. A
+    /// `depth` of zero turns the check off.
+    pub fn with_password_history(
+        mut self,
+        history: Arc<dyn PasswordHistory>,
+        depth: usize,
+    ) -> Self {
+        self.password_reuse = (depth > 0).then_some(PasswordReuse { history, depth });
+        self
+    }
+
+    /// Refuse new passwords that `checker` finds in known data breaches, at
+    /// registration, password change and password reset.
+    pub fn with_breach_checker(mut self, checker: Arc<dyn PasswordBreachChecker>) -> Self {
+        self.breach_checker = Some(checker);
+        self
+    }
+
+    /// Let users delete their own accounts, keeping them disabled for
+    /// `grace` in `store` before they are anonymized and their articles
+    /// reassigned in `articles`.
+    pub fn with_account_deletion(
+        mut self,
+        store: Arc<dyn AccountDeletions>,
+        articles: Arc<dyn ArticleWriteRepository>,
+        grace: Duration,
+    ) -> Self {
+        self.account_deletion = Some(AccountDeletion {
+            store,
+            articles,
+            grace,
+        });
+        self
+    }
+
+    /// Refuse writes while `switch` is on. Logins and token refreshes are
+    /// still served.
+    pub fn with_read_only(mut self, switch: ReadOnlySwitch) -> Self {
+        self.read_only = switch;
+        self
+    }
+
+    /// Spend mailed tokens and apply what they were mailed for through
+    /// `unit_of_work`, so a failed update does not use the token up.
+    pub fn with_unit_of_work(mut self, unit_of_work: Arc<dyn UnitOfWork>) -> Self {
+        self.unit_of_work = Some(unit_of_work);
+        self
+    }
+
+    /// Issue tokens with the capabilities of the custom role a user has in
+    /// `store`, instead of those of their built-in role, plus any granted to
+    /// them individually there.
+    pub fn with_custom_roles(mut self, store: Arc<dyn RoleStore>) -> Self {
+        self.custom_roles = Some(store);
+        self
+    }
+
+    /// Publish a domain event to `publisher` for every stored account change.
+    pub fn with_event_publisher(mut self, publisher: Arc<dyn EventPublisher>) -> Self {
+        self.publisher = Some(publisher);
+        self
+    }
+
+    /// Publish `kind` as happening now. The change is already stored, so a
+    /// failing publisher is logged rather than returned.
+    pub(super) async fn publish(&self, kind: EventKind) {
+        let Some(publisher) = &self.publisher else {
+            return;
+        };
+        let event = DomainEvent::new(kind, self.clock.now());
+        if let Err(err) = publisher.publish(&event).await {
+            tracing::warn!(error = %err, event = kind.name(), "domain event was not published");
         }
     }
+
+    /// What `user`'s tokens grant, and the custom role the role-level part
+    /// comes from.
+    pub(super) async fn token_grants(
+        &self,
+        user: &User,
+    ) -> AppResult<(HashSet<Capability>, Option<String>)> {
+        let Some(store) = &self.custom_roles else {
+            return Ok((user.role.default_capabilities(), None));
+        };
+        let (mut capabilities, custom_role) = match store.role_of(user.id).await? {
+            Some(role) => (
+                role.capabilities.into_iter().collect::<HashSet<_>>(),
+                Some(role.name.to_string()),
+            ),
+            None => (user.role.default_capabilities(), None),
+        };
+        capabilities.extend(store.capabilities_of(user.id).await?);
+        Ok((capabilities, custom_role))
+    }
 }
@@ -0,0 +1,141 @@
+//! Brute-force protection for password logins.
+//!
+//! Failures are counted per username and per client address. Reaching the
+//! per-username limit locks the account; reaching the per-client limit shuts
+//! out the address, whichever accounts it tried. Both locks are audited and
+//! refuse logins with 429 until they end.
+use std::sync::Arc;
+
+use serde_json::json;
+
+use super::UserCommandService;
+use crate::application::{
+    error::{AppError, AppResult},
+    ports::{
+        rate_limit::{LoginThrottlePolicy, RateLimiter, retry_after_secs},
+        security_events::ClientInfo,
+    },
+};
+use crate::domain::{
+    Username,
+    audit::{entity::NewAuditLog, repository::AuditLogRepository},
+};
+
+/// Failed-login counters, the limits applied to them, and where lockouts
+/// are recorded.
+pub(super) struct LoginThrottle {
+    pub(super) limiter: Arc<dyn RateLimiter>,
+    pub(super) policy: LoginThrottlePolicy,
+    pub(super) audit_log_repo: Arc<dyn AuditLogRepository>,
+}
+
+fn user_key(username: &Username) -> String {
+    format!("user:{}", username.as_str().to_lowercase())
+}
+
+fn client_key(client: &ClientInfo) -> Option<String> {
+    client.ip_address.as_ref().map(|ip| format!("ip:{ip}"))
+}
+
+impl UserCommandService {
+    /// Refuse the attempt while the account or the client is locked out.
+    pub(super) async fn ensure_login_allowed(
+        &self,
+        username: &Username,
+        client: &ClientInfo,
+    ) -> AppResult<()> {
+        let Some(throttle) = &self.login_throttle else {
+            return Ok(());
+        };
+        let now = self.clock.now();
+        for key in std::iter::once(user_key(username)).chain(client_key(client)) {
+            if let Some(until) = throttle.limiter.locked_until(&key, now).await? {
+                return Err(AppError::too_many_requests(
+                    "too many failed logins, try again later",
+                    retry_after_secs(until, now),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Count a failed attempt, locking the account or the client once it
+    /// reaches its limit.
+    pub(super) async fn record_login_failure(
+        &self,
+        username: &Username,
+        client: &ClientInfo,
+    ) -> AppResult<()> {
+        let Some(throttle) = &self.login_throttle else {
+            return Ok(());
+        };
+        let now = self.clock.now();
+        let policy = throttle.policy;
+        let until = now + policy.lockout;
+
+        let key = user_key(username);
+        let failures = throttle.limiter.increment(&key, policy.window, now).await?;
+        if failures >= policy.max_failures_per_user {
+            throttle.limiter.lock(&key, until, now).await?;
+            let user_id = self
+                .user_repo
+                .find_by_username(username)
+                .await?
+                .map(|user| i64::from(user.id));
+            tracing::warn!(username = %username.as_str(), failures, "account locked after failed logins");
+            throttle
+                .audit_log_repo
+                .insert(NewAuditLog {
+                    user_id: None,
+                    impersonator_id: None,
+                    action: "user.locked".into(),
+                    resource_type: "user".into(),
+                    resource_id: user_id,
+                    details: Some(json!({
+                        "username": username.as_str(),
+                        "failures": failures,
+                        "locked_until": until,
+                    })),
+                    ip_address: client.ip_address.clone(),
+                    user_agent: client.user_agent.clone(),
+                })
+                .await?;
+        }
+
+        let Some(key) = client_key(client) else {
+            return Ok(());
+        };
+        let failures = throttle.limiter.increment(&key, policy.window, now).await?;
+        if failures >= policy.max_failures_per_client {
+            throttle.limiter.lock(&key, until, now).await?;
+            tracing::warn!(ip = ?client.ip_address, failures, "client locked out after failed logins");
+            throttle
+                .audit_log_repo
+                .insert(NewAuditLog {
+                    user_id: None,
+                    impersonator_id: None,
+                    action: "login.client_locked".into(),
+                    resource_type: "client".into(),
+                    resource_id: None,
+                    details: Some(json!({
+                        "failures": failures,
+                        "locked_until": until,
+                    })),
+                    ip_address: client.ip_address.clone(),
+                    user_agent: client.user_agent.clone(),
+                })
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Forget the account's failures after a successful login. The client's
+    /// count is kept, so one good password does not reset a spraying
+    /// attacker.
+    pub(super) async fn clear_login_failures(&self, username: &Username) -> AppResult<()> {
+        match &self.login_throttle {
+            Some(throttle) => throttle.limiter.reset(&user_key(username)).await,
+            None => Ok(()),
+        }
+    }
+}
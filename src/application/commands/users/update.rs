@@ -1,10 +1,14 @@
 use super::{UserCommandService, capability::ensure_capability};
+use serde_json::json;
+
 use crate::{
     application::{
         AuthenticatedUser, UserDto,
         error::{AppError, AppResult},
+        ports::command_journal::JournaledCommand,
+        services::AuditEvent,
     },
-    domain::{Role, UserId, UserUpdate},
+    domain::{EventKind, Role, UserId, UserUpdate},
 };
 
 pub struct UpdateUserCommand {
@@ -20,10 +24,31 @@ impl UserCommandService {
     ///
     /// Returns an error if the actor lacks `users:update`, the user id is
     /// invalid, no update fields are provided, or persistence fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn update_user(
         &self,
         actor: &AuthenticatedUser,
         command: UpdateUserCommand,
+    ) -> AppResult<UserDto> {
+        self.read_only.ensure_writable()?;
+        let journaled = self.journal.capture(|| JournaledCommand::UpdateUser {
+            user_id: command.user_id,
+            is_active: command.is_active,
+            role: command.role,
+        });
+        let result = self.update_user_inner(actor, command).await;
+        self.journal
+            .record(self.clock.now(), Some(actor), journaled, &result, |user| {
+                Some(user.id)
+            })
+            .await;
+        result
+    }
+
+    async fn update_user_inner(
+        &self,
+        actor: &AuthenticatedUser,
+        command: UpdateUserCommand,
     ) -> AppResult<UserDto> {
         ensure_capability(actor, "users", "update")?;
 
@@ -44,6 +69,14 @@ impl UserCommandService {
         }
 
         let user = self.user_repo.update(update).await?;
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("user.update", "user", Some(i64::from(user_id)))
+                    .with_details(json!({ "is_active": command.is_active, "role": command.role })),
+            )
+            .await;
+        self.publish(EventKind::UserUpdated(user_id)).await;
         Ok(user.into())
     }
 }
@@ -0,0 +1,49 @@
+use super::serde_time;
+use crate::domain::{AccessRule, RouteGroup, RuleAction, RuleTarget};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// An allow or deny rule for a route group. Exactly one of `cidr` and `asn`
+/// is set.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AccessRuleDto {
+    pub id: i64,
+    pub group: RouteGroup,
+    pub action: RuleAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cidr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asn: Option<u32>,
+    pub priority: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<i64>,
+    #[serde(with = "serde_time")]
+    pub created_at: DateTime<Utc>,
+    /// When the rule stops applying; absent for permanent rules.
+    #[serde(with = "serde_time::option", skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<AccessRule> for AccessRuleDto {
+    fn from(rule: AccessRule) -> Self {
+        let (cidr, asn) = match rule.target {
+            RuleTarget::Cidr(range) => (Some(range.to_string()), None),
+            RuleTarget::Asn(asn) => (None, Some(asn)),
+        };
+        Self {
+            id: rule.id,
+            group: rule.group,
+            action: rule.action,
+            cidr,
+            asn,
+            priority: rule.priority,
+            note: rule.note,
+            created_by: rule.created_by.map(i64::from),
+            created_at: rule.created_at,
+            expires_at: rule.expires_at,
+        }
+    }
+}
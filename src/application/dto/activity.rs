@@ -0,0 +1,55 @@
+use super::serde_time;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// What an account activity entry is about, for grouping and filtering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityCategory {
+    /// Successful sign-ins.
+    SignIn,
+    /// Password changes and resets, lockouts and suspected token theft.
+    Security,
+    /// Email address, preferences and other account settings.
+    Account,
+    /// Articles written, edited, published or deleted.
+    Content,
+    /// Changes made to other users' accounts.
+    Administration,
+}
+
+/// One entry of the signed-in user's activity feed, ready to display.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ActivityEntryDto {
+    pub id: i64,
+    pub category: ActivityCategory,
+    /// Icon name from the Lucide set, e.g. `log-in`.
+    pub icon: String,
+    /// Short English description, e.g. "Signed in".
+    pub summary: String,
+    /// Audit action the entry was built from, e.g. `user.login`.
+    pub action: String,
+    pub resource_type: String,
+    pub resource_id: Option<i64>,
+    /// Whether someone else, or the system, did this to the account, as
+    /// with a lockout or a role change by an administrator.
+    pub by_someone_else: bool,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    #[serde(with = "serde_time")]
+    pub created_at: DateTime<Utc>,
+    /// `created_at` rendered in the zone requested via `?tz=`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_local: Option<String>,
+}
+
+impl ActivityEntryDto {
+    /// Fill display-only local timestamps for `tz`.
+    #[must_use]
+    pub fn localized(mut self, tz: Option<Tz>) -> Self {
+        self.created_at_local = tz.map(|tz| serde_time::localize(&self.created_at, tz));
+        self
+    }
+}
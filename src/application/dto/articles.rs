@@ -1,4 +1,8 @@
-use crate::domain::{Article, ArticleRevision};
+use crate::application::{AppError, AppResult};
+use crate::domain::{
+    Article, ArticleContributor, ArticleRevision, ArticleSearchHit, ArticleVisibility,
+    CustomFieldDefinition, CustomFieldType, CustomFields, SearchFacets, Tag,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -11,14 +15,110 @@ pub struct ArticleDto {
     pub title: String,
     pub slug: String,
     pub body: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Values of the site's custom fields, keyed by field name.
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub custom_fields: CustomFields,
     pub published: bool,
     #[serde(default, with = "serde_time::option")]
     pub published_at: Option<DateTime<Utc>>,
+    /// `unlisted` articles are left out of public listings but readable by
+    /// slug; `private` ones only by their author and `articles:view:any`.
+    #[serde(default)]
+    pub visibility: ArticleVisibility,
     pub author_id: i64,
     #[serde(with = "serde_time")]
     pub created_at: DateTime<Utc>,
     #[serde(with = "serde_time")]
     pub updated_at: DateTime<Utc>,
+    /// Reads of the published article. Counts are flushed periodically, so
+    /// this trails the latest views by up to one flush interval.
+    #[serde(default)]
+    pub view_count: u64,
+}
+
+/// An article matching a search, with the passages that matched.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SearchResultDto {
+    #[serde(flatten)]
+    pub article: ArticleDto,
+    /// HTML-escaped excerpts of the body with matching words wrapped in
+    /// `<mark>`, or `null` when the store cannot highlight matches.
+    #[serde(default)]
+    pub headline: Option<String>,
+}
+
+/// Breakdown of the articles matching a search.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SearchFacetsDto {
+    pub published: u64,
+    /// Always 0 unless drafts are included.
+    pub drafts: u64,
+    /// Authors with the most matches, most first (at most 10).
+    pub authors: Vec<AuthorFacetDto>,
+    /// Tags most common among the matches, most first (at most 10).
+    pub tags: Vec<TagFacetDto>,
+}
+
+/// Matching articles written by one author.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuthorFacetDto {
+    pub author_id: i64,
+    pub count: u64,
+}
+
+/// Matching articles carrying one tag.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TagFacetDto {
+    pub tag: String,
+    pub count: u64,
+}
+
+/// Order of an article listing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ArticleSort {
+    /// Newest first (the default).
+    #[default]
+    Newest,
+    /// Most viewed first.
+    Popular,
+}
+
+impl From<ArticleSearchHit> for SearchResultDto {
+    fn from(hit: ArticleSearchHit) -> Self {
+        Self {
+            article: hit.article.into(),
+            headline: hit.headline,
+        }
+    }
+}
+
+impl From<SearchFacets> for SearchFacetsDto {
+    fn from(facets: SearchFacets) -> Self {
+        Self {
+            published: facets.published,
+            drafts: facets.drafts,
+            authors: facets
+                .authors
+                .into_iter()
+                .map(|facet| AuthorFacetDto {
+                    author_id: facet.value.into(),
+                    count: facet.count,
+                })
+                .collect(),
+            tags: facets
+                .tags
+                .into_iter()
+                .map(|facet| TagFacetDto {
+                    tag: facet.value.into_inner(),
+                    count: facet.count,
+                })
+                .collect(),
+        }
+    }
 }
 
 impl From<Article> for ArticleDto {
@@ -28,11 +128,35 @@ impl From<Article> for ArticleDto {
             title: article.title.into_inner(),
             slug: article.slug.into_inner(),
             body: article.body.into_inner(),
+            tags: article.tags.into_iter().map(Tag::into_inner).collect(),
+            custom_fields: article.custom_fields,
             published: article.published,
             published_at: article.published_at,
+            visibility: article.visibility,
             author_id: article.author_id.into(),
             created_at: article.created_at,
             updated_at: article.updated_at,
+            view_count: article.view_count,
+        }
+    }
+}
+
+/// A custom field articles may carry.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CustomFieldDefinitionDto {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: CustomFieldType,
+    /// Whether every article must have a value.
+    pub required: bool,
+}
+
+impl From<CustomFieldDefinition> for CustomFieldDefinitionDto {
+    fn from(definition: CustomFieldDefinition) -> Self {
+        Self {
+            name: definition.name,
+            field_type: definition.field_type,
+            required: definition.required,
         }
     }
 }
@@ -68,3 +192,117 @@ impl From<ArticleRevision> for ArticleRevisionDto {
         }
     }
 }
+
+/// A user who may edit an article alongside its author.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ArticleContributorDto {
+    pub article_id: i64,
+    pub user_id: i64,
+    /// Who added them; `null` once that user has been deleted.
+    #[serde(default)]
+    pub added_by: Option<i64>,
+    #[serde(with = "serde_time")]
+    pub added_at: DateTime<Utc>,
+}
+
+impl From<ArticleContributor> for ArticleContributorDto {
+    fn from(contributor: ArticleContributor) -> Self {
+        Self {
+            article_id: contributor.article_id.into(),
+            user_id: contributor.user_id.into(),
+            added_by: contributor.added_by.map(Into::into),
+            added_at: contributor.added_at,
+        }
+    }
+}
+
+/// Unit a revision diff compares text in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffGranularity {
+    #[default]
+    Line,
+    /// Words and the whitespace between them.
+    Word,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+/// A run of text that is unchanged, only in the newer revision, or only in
+/// the older one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DiffSegment {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+/// How one field changed between two revisions.
+///
+/// Joining the `equal` and `delete` segments gives the older text; joining
+/// the `equal` and `insert` segments gives the newer one.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FieldDiffDto {
+    pub changed: bool,
+    /// Lines or words only in the newer revision; whitespace is not counted.
+    pub added: usize,
+    /// Lines or words only in the older revision; whitespace is not counted.
+    pub removed: usize,
+    pub segments: Vec<DiffSegment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ArticleRevisionDiffDto {
+    pub article_id: i64,
+    pub from_version: i32,
+    pub to_version: i32,
+    /// Granularity of the body diff. Titles are always compared word by word.
+    pub granularity: DiffGranularity,
+    pub title: FieldDiffDto,
+    pub body: FieldDiffDto,
+}
+
+/// Draft created from an uploaded document.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ArticleImportDto {
+    pub article: ArticleDto,
+    /// Embedded images left out of the draft.
+    pub skipped_images: u32,
+}
+
+/// Markup variant produced by the HTML rendering endpoints.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderProfile {
+    /// Everything the Markdown expresses, including images.
+    #[default]
+    Full,
+    /// Text, lists and absolute links only, for strict embedders such as AMP
+    /// pages.
+    Minimal,
+    /// For paper: link targets are spelled out after their text.
+    Print,
+}
+
+impl RenderProfile {
+    /// Parse the `profile` query parameter.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error for an unknown profile.
+    pub fn parse(value: &str) -> AppResult<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "full" => Ok(Self::Full),
+            "minimal" => Ok(Self::Minimal),
+            "print" => Ok(Self::Print),
+            _ => Err(AppError::validation(
+                "profile must be full, minimal or print",
+            )),
+        }
+    }
+}
@@ -1,4 +1,7 @@
+use super::serde_time;
 use crate::domain::audit::entity::AuditLog;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -6,12 +9,29 @@ use utoipa::ToSchema;
 pub struct LogDto {
     pub id: i64,
     pub user_id: Option<i64>,
+    /// Admin who acted as `user_id` through an impersonation token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonator_id: Option<i64>,
     pub action: String,
     pub resource_type: String,
     pub resource_id: Option<i64>,
     pub details: Option<serde_json::Value>,
     pub ip_address: Option<String>,
     pub user_agent: Option<String>,
+    #[serde(with = "serde_time")]
+    pub created_at: DateTime<Utc>,
+    /// `created_at` rendered in the zone requested via `?tz=`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_local: Option<String>,
+}
+
+impl LogDto {
+    /// Fill display-only local timestamps for `tz`.
+    #[must_use]
+    pub fn localized(mut self, tz: Option<Tz>) -> Self {
+        self.created_at_local = tz.map(|tz| serde_time::localize(&self.created_at, tz));
+        self
+    }
 }
 
 impl From<AuditLog> for LogDto {
@@ -19,12 +39,15 @@ impl From<AuditLog> for LogDto {
         Self {
             id: a.id,
             user_id: a.user_id.map(Into::into),
+            impersonator_id: a.impersonator_id.map(Into::into),
             action: a.action,
             resource_type: a.resource_type,
             resource_id: a.resource_id,
             details: a.details,
             ip_address: a.ip_address,
             user_agent: a.user_agent,
+            created_at: a.created_at,
+            created_at_local: None,
         }
     }
 }
@@ -30,6 +30,10 @@ pub struct UserIdentity {
     pub expires_at: DateTime<Utc>,
     pub session_id: Option<String>,
     pub token_version: Option<u32>,
+    /// The custom role `capabilities` were taken from, if any.
+    pub custom_role: Option<String>,
+    /// Admin acting as this user through an impersonation token.
+    pub impersonator: Option<UserId>,
 }
 
 impl UserIdentity {
@@ -41,14 +45,24 @@ impl UserIdentity {
     }
 }
 
+/// Longest an impersonation token stays valid.
+pub const IMPERSONATION_TOKEN_TTL: std::time::Duration = std::time::Duration::from_mins(15);
+
 #[derive(Debug, Clone)]
 pub struct Subject {
     pub user_id: UserId,
     pub username: String,
     pub role: Role,
+    /// Everything the token grants: the custom role's capabilities when
+    /// `custom_role` is set, else those of `role`, plus any granted to the
+    /// user individually.
     pub capabilities: HashSet<Capability>,
     pub session_id: Option<String>,
     pub token_version: Option<u32>,
+    pub custom_role: Option<String>,
+    /// Admin the token is issued to act as this user; such tokens are
+    /// short-lived.
+    pub impersonator: Option<UserId>,
 }
 
 impl Subject {
@@ -61,6 +75,35 @@ impl Subject {
             capabilities: auth.capabilities.clone(),
             session_id: auth.session_id.clone(),
             token_version: auth.token_version,
+            custom_role: auth.custom_role.clone(),
+            impersonator: auth.impersonator,
+        }
+    }
+
+    /// Lifetime of a token for this subject: `default`, capped at
+    /// [`IMPERSONATION_TOKEN_TTL`] for impersonation tokens.
+    #[must_use]
+    pub fn token_ttl(&self, default: std::time::Duration) -> std::time::Duration {
+        if self.impersonator.is_some() {
+            default.min(IMPERSONATION_TOKEN_TTL)
+        } else {
+            default
         }
     }
 }
+
+/// Claims of an OIDC `id_token` (`OpenID` Connect Core §2).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    /// The `client_id` the authorization code was issued to.
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    pub preferred_username: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+}
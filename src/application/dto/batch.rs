@@ -0,0 +1,81 @@
+// False positive from `serde` + `utoipa` derive expansion on the generic result type.
+#![allow(clippy::option_if_let_else)]
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Maximum number of ids accepted by a single batch lookup.
+pub const MAX_BATCH_IDS: usize = 100;
+
+/// Outcome of a batch lookup by id.
+///
+/// `items` follow the order of the requested ids; ids that do not exist or
+/// are not visible to the caller are listed in `missing_ids`.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(bound(serialize = "T: Serialize"))]
+#[must_use]
+pub struct BatchResult<T> {
+    pub items: Vec<T>,
+    pub missing_ids: Vec<i64>,
+}
+
+impl<T> BatchResult<T> {
+    pub const fn new(items: Vec<T>, missing_ids: Vec<i64>) -> Self {
+        Self { items, missing_ids }
+    }
+}
+
+/// What a bulk operation did to one article.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkItemStatus {
+    Updated,
+    /// Already in the requested state; nothing was written.
+    Unchanged,
+    Deleted,
+    NotFound,
+    Forbidden,
+    /// Changed by someone else while the operation ran; retry it.
+    Conflict,
+    /// The change would break a rule, such as the tag limit.
+    Invalid,
+}
+
+impl BulkItemStatus {
+    #[must_use]
+    pub const fn succeeded(self) -> bool {
+        matches!(self, Self::Updated | Self::Unchanged | Self::Deleted)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct BulkItemResult {
+    pub id: i64,
+    pub status: BulkItemStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of a bulk operation: one result per requested id, in the order
+/// they were requested.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[must_use]
+pub struct BulkResult {
+    pub results: Vec<BulkItemResult>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+impl BulkResult {
+    pub fn new(results: Vec<BulkItemResult>) -> Self {
+        let succeeded = results
+            .iter()
+            .filter(|result| result.status.succeeded())
+            .count();
+        Self {
+            failed: results.len() - succeeded,
+            succeeded,
+            results,
+        }
+    }
+}
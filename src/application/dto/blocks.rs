@@ -0,0 +1,22 @@
+use super::serde_time;
+use crate::domain::UserBlock;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Someone the signed-in user has blocked.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UserBlockDto {
+    pub user_id: i64,
+    #[serde(with = "serde_time")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<UserBlock> for UserBlockDto {
+    fn from(block: UserBlock) -> Self {
+        Self {
+            user_id: block.target.into(),
+            created_at: block.created_at,
+        }
+    }
+}
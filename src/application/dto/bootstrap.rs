@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::status::FeaturesDto;
+use super::users::{CapabilityView, UserProfileDto};
+
+/// Deployment-wide settings a frontend needs before the user does anything.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SiteSettingsDto {
+    pub version: String,
+    /// Names usable in `/api/v1/auth/oidc/{provider}/start`, sorted.
+    pub login_providers: Vec<String>,
+}
+
+/// Everything a single-page app loads on startup, in one response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BootstrapDto {
+    /// The caller's profile; absent for anonymous requests.
+    pub profile: Option<UserProfileDto>,
+    /// The caller's effective capabilities; empty for anonymous requests.
+    pub capabilities: Vec<CapabilityView>,
+    pub features: FeaturesDto,
+    pub site: SiteSettingsDto,
+}
@@ -0,0 +1,181 @@
+use super::serde_time;
+use crate::application::{AppError, AppResult};
+use crate::domain::{ArticleVisibility, CustomFields, Role};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Identifies a bundle in its manifest.
+pub const BUNDLE_FORMAT: &str = "mokkan-bundle";
+
+/// Layout version written to, and the only one read from, manifests.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Largest bundle accepted by `POST /api/v1/import`.
+pub const MAX_BUNDLE_BYTES: usize = 64 * 1024 * 1024;
+
+/// How a bundle is laid out on the wire.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BundleFormat {
+    /// One JSON record per line, manifest first.
+    #[default]
+    Ndjson,
+    /// A manifest, one Markdown file with front matter per article, and
+    /// NDJSON files for revisions and users.
+    Zip,
+}
+
+impl BundleFormat {
+    /// Parse the `format` query parameter; empty means NDJSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error for anything but `ndjson` or `zip`.
+    pub fn parse(value: &str) -> AppResult<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "ndjson" => Ok(Self::Ndjson),
+            "zip" => Ok(Self::Zip),
+            _ => Err(AppError::validation("format must be ndjson or zip")),
+        }
+    }
+
+    #[must_use]
+    pub const fn content_type(self) -> &'static str {
+        match self {
+            Self::Ndjson => "application/x-ndjson",
+            Self::Zip => "application/zip",
+        }
+    }
+
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Ndjson => "ndjson",
+            Self::Zip => "zip",
+        }
+    }
+}
+
+/// What to do with an imported article whose slug is already taken.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictStrategy {
+    /// Leave the existing article untouched.
+    #[default]
+    Skip,
+    /// Replace the existing article's content, tags, fields and publish
+    /// state; its id and author stay.
+    Overwrite,
+    /// Import as a new article under the first free `<slug>-<n>`.
+    NewSlug,
+}
+
+/// First record of every bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// Always [`BUNDLE_FORMAT`].
+    pub format: String,
+    pub version: u32,
+    #[serde(with = "serde_time")]
+    pub exported_at: DateTime<Utc>,
+    pub includes_revisions: bool,
+    pub includes_users: bool,
+}
+
+/// An article as it stood when exported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleArticle {
+    /// Id in the exporting environment; links revisions to their article.
+    pub id: i64,
+    pub slug: String,
+    pub title: String,
+    pub body: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub custom_fields: CustomFields,
+    pub published: bool,
+    #[serde(default, with = "serde_time::option")]
+    pub published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub visibility: ArticleVisibility,
+    /// Username of the author; the importer is used when no such user exists.
+    pub author: Option<String>,
+    #[serde(with = "serde_time")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "serde_time")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One stored revision of an exported article.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleRevision {
+    /// [`BundleArticle::id`] of the article it belongs to.
+    pub article_id: i64,
+    pub version: i32,
+    pub slug: String,
+    pub title: String,
+    pub body: String,
+    pub published: bool,
+    #[serde(default, with = "serde_time::option")]
+    pub published_at: Option<DateTime<Utc>>,
+    /// Username of the editor, when known.
+    pub edited_by: Option<String>,
+    #[serde(with = "serde_time")]
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// A user account, without credentials or contact details.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleUser {
+    pub username: String,
+    pub role: Role,
+    pub is_active: bool,
+    #[serde(with = "serde_time")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// One line of an NDJSON bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BundleRecord {
+    Manifest(BundleManifest),
+    User(BundleUser),
+    Article(BundleArticle),
+    Revision(BundleRevision),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleItemStatus {
+    Created,
+    Overwritten,
+    /// Created under a new slug because the original was taken.
+    Renamed,
+    Skipped,
+    Failed,
+}
+
+/// Outcome of one article in an imported bundle.
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleImportItem {
+    /// Slug the article had in the bundle.
+    pub slug: String,
+    pub status: BundleItemStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub article_id: Option<i64>,
+    /// Slug the article was stored under, when it differs from `slug`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imported_slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// What an import changed, with one item per article in bundle order.
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleImportDto {
+    pub on_conflict: ConflictStrategy,
+    pub users_created: usize,
+    pub revisions_imported: usize,
+    pub articles: Vec<BundleImportItem>,
+}
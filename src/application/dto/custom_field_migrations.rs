@@ -0,0 +1,67 @@
+use super::serde_time;
+use crate::domain::CustomFieldType;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+/// A change to one custom field, applied to its definition and to the values
+/// stored on every article.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CustomFieldMigration {
+    /// Give the field a new name; stored values move with it.
+    Rename { to: String },
+    /// Change the field's type, converting stored values.
+    ChangeType {
+        to: CustomFieldType,
+        /// Remove values that cannot be converted instead of leaving those
+        /// articles untouched and reporting them as failures.
+        #[serde(default)]
+        drop_unconvertible: bool,
+    },
+    /// Store `value` on every article that has no value for the field.
+    Backfill {
+        #[schema(value_type = Object)]
+        value: Value,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MigrationJobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// An article the migration could not rewrite.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MigrationFailure {
+    pub article_id: i64,
+    pub error: String,
+}
+
+/// Progress of a custom field migration running in the background.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CustomFieldMigrationJobDto {
+    pub id: String,
+    /// Name of the field being migrated, before any rename.
+    pub field: String,
+    pub migration: CustomFieldMigration,
+    pub status: MigrationJobStatus,
+    /// Articles to examine; known once the job has started.
+    pub total_articles: usize,
+    pub processed_articles: usize,
+    /// Articles whose custom fields were rewritten.
+    pub updated_articles: usize,
+    pub failures: Vec<MigrationFailure>,
+    /// Why the job stopped early, when `status` is `failed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(with = "serde_time")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "serde_time::option")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,104 @@
+use super::serde_time;
+use crate::application::{AppError, AppResult};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Pdf,
+    Epub,
+}
+
+impl ExportFormat {
+    /// Parse the `format` query parameter.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error for anything but `pdf` or `epub`.
+    pub fn parse(value: &str) -> AppResult<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "pdf" => Ok(Self::Pdf),
+            "epub" => Ok(Self::Epub),
+            _ => Err(AppError::validation("format must be pdf or epub")),
+        }
+    }
+
+    #[must_use]
+    pub const fn content_type(self) -> &'static str {
+        match self {
+            Self::Pdf => "application/pdf",
+            Self::Epub => "application/epub+zip",
+        }
+    }
+
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Pdf => "pdf",
+            Self::Epub => "epub",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportJobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl ExportJobStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// A rendered article, ready to download.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedFile {
+    pub file_name: String,
+    pub content_type: &'static str,
+    pub bytes: Vec<u8>,
+}
+
+/// Progress of an article export rendered in the background.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ArticleExportJobDto {
+    pub id: String,
+    pub article_id: i64,
+    pub format: ExportFormat,
+    pub status: ExportJobStatus,
+    /// Signed link to the file once the job has completed. It needs no
+    /// authentication and expires; poll the job again for a fresh one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(with = "serde_time")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "serde_time::option")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// What a static site export wrote.
+#[derive(Debug, Clone, Serialize)]
+pub struct StaticSiteExportDto {
+    /// Where the files were written, e.g. the target directory.
+    pub location: String,
+    /// Published articles rendered, one page each.
+    pub articles: usize,
+    /// Pages written, the index included.
+    pub files_written: usize,
+    #[serde(with = "serde_time")]
+    pub generated_at: DateTime<Utc>,
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::articles::{ArticleDto, ArticleRevisionDto};
+use super::serde_time;
+use super::users::{CapabilityView, UserDto};
+
+/// Everything stored about an article, regardless of publication state.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ArticleInspectionDto {
+    pub article: ArticleDto,
+    /// `None` when the author record no longer exists.
+    pub author: Option<UserDto>,
+    pub revisions: Vec<ArticleRevisionDto>,
+}
+
+/// Raw session state as held by the session store.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SessionInspectionDto {
+    pub session_id: String,
+    pub user_id: i64,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at_unix: i64,
+    #[serde(default, with = "serde_time::option")]
+    pub created_at: Option<DateTime<Utc>>,
+    pub revoked: bool,
+    pub family_id: Option<String>,
+    /// Whether a refresh nonce is currently stored, i.e. the session can still
+    /// be refreshed.
+    pub has_refresh_nonce: bool,
+}
+
+/// A user record together with its role capabilities and sessions.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserInspectionDto {
+    pub user: UserDto,
+    pub role_capabilities: Vec<CapabilityView>,
+    pub min_token_version: Option<u32>,
+    pub sessions: Vec<SessionInspectionDto>,
+}
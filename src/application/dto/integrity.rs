@@ -0,0 +1,48 @@
+use super::serde_time;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// A kind of cross-table inconsistency the schema does not prevent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    /// A revision whose article no longer exists, e.g. after a manual delete
+    /// with foreign keys disabled.
+    OrphanedRevision,
+    /// An article value for a custom field whose definition was removed.
+    UndefinedCustomField,
+    /// An audit entry naming a resource that is gone without a recorded
+    /// `.delete` entry for it.
+    DanglingAuditResource,
+}
+
+impl AnomalyKind {
+    /// Whether a repair run fixes this kind. Audit entries are never
+    /// rewritten.
+    #[must_use]
+    pub const fn repairable(self) -> bool {
+        !matches!(self, Self::DanglingAuditResource)
+    }
+}
+
+/// One inconsistent row.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct IntegrityAnomalyDto {
+    pub kind: AnomalyKind,
+    /// Table-level name of the offending row, e.g. `article_revision`.
+    pub resource_type: String,
+    pub resource_id: i64,
+    pub detail: String,
+}
+
+/// Result of an integrity check, after any repair.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IntegrityReportDto {
+    #[serde(with = "serde_time")]
+    pub checked_at: DateTime<Utc>,
+    /// Rows deleted or rewritten by the repair, if one was requested.
+    pub repaired: u64,
+    /// Anomalies left after the repair.
+    pub anomalies: Vec<IntegrityAnomalyDto>,
+}
@@ -0,0 +1,94 @@
+use super::serde_time;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JobRunOutcome {
+    Succeeded,
+    Failed,
+    /// Another run still held the job's lock.
+    Skipped,
+}
+
+impl JobRunOutcome {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+            Self::Skipped => "skipped",
+        }
+    }
+}
+
+impl std::str::FromStr for JobRunOutcome {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "succeeded" => Ok(Self::Succeeded),
+            "failed" => Ok(Self::Failed),
+            "skipped" => Ok(Self::Skipped),
+            other => Err(format!("unknown job outcome '{other}'")),
+        }
+    }
+}
+
+/// Schedule and last run of a scheduled job.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct JobStatusDto {
+    pub name: String,
+    /// Cron expression the job runs on.
+    pub schedule: String,
+    pub running: bool,
+    #[serde(with = "serde_time::option")]
+    pub next_run_at: Option<DateTime<Utc>>,
+    #[serde(with = "serde_time::option")]
+    pub last_started_at: Option<DateTime<Utc>>,
+    #[serde(with = "serde_time::option")]
+    pub last_finished_at: Option<DateTime<Utc>>,
+    pub last_outcome: Option<JobRunOutcome>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Failed runs since the last successful one.
+    pub consecutive_failures: u32,
+}
+
+/// What started a job run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JobTrigger {
+    Schedule,
+    Manual,
+}
+
+impl JobTrigger {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Schedule => "schedule",
+            Self::Manual => "manual",
+        }
+    }
+}
+
+/// One recorded run of a scheduled job.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct JobRunDto {
+    pub id: i64,
+    pub job: String,
+    pub trigger: JobTrigger,
+    /// User who started a manual run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub triggered_by: Option<i64>,
+    #[serde(with = "serde_time")]
+    pub started_at: DateTime<Utc>,
+    /// `None` while the run is in progress.
+    #[serde(with = "serde_time::option")]
+    pub finished_at: Option<DateTime<Utc>>,
+    pub outcome: Option<JobRunOutcome>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
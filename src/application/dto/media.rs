@@ -0,0 +1,33 @@
+use super::serde_time;
+use crate::domain::MediaAsset;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// An uploaded file and where to fetch it.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MediaAssetDto {
+    pub id: i64,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: u64,
+    /// Path the file is served from, for use in article bodies.
+    pub url: String,
+    pub uploaded_by: i64,
+    #[serde(with = "serde_time")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<MediaAsset> for MediaAssetDto {
+    fn from(asset: MediaAsset) -> Self {
+        Self {
+            url: format!("/api/v1/media/{}", asset.id),
+            id: asset.id,
+            file_name: asset.file_name,
+            content_type: asset.content_type,
+            size_bytes: asset.size_bytes,
+            uploaded_by: asset.uploaded_by.into(),
+            created_at: asset.created_at,
+        }
+    }
+}
@@ -1,7 +1,26 @@
+pub mod access_rules;
+pub mod activity;
 pub mod articles;
 pub mod audit;
 pub mod auth;
+pub mod batch;
+pub mod blocks;
+pub mod bootstrap;
+pub mod bundles;
+pub mod custom_field_migrations;
+pub mod exports;
+pub mod inspect;
+pub mod integrity;
+pub mod jobs;
+pub mod media;
+pub mod moderation;
 pub mod pagination;
+pub mod roles;
+pub mod security;
 pub mod serde_time;
 pub mod sessions;
+pub mod status;
+pub mod syndication;
+pub mod user_import;
 pub mod users;
+pub mod webhooks;
@@ -0,0 +1,64 @@
+use super::serde_time;
+use crate::domain::{CaseStatus, ModerationCase, Report, ReportReason, Resolution};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReportDto {
+    pub reason: ReportReason,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+    #[serde(with = "serde_time")]
+    pub reported_at: DateTime<Utc>,
+}
+
+impl From<Report> for ReportDto {
+    fn from(report: Report) -> Self {
+        Self {
+            reason: report.reason,
+            details: report.details,
+            reported_at: report.reported_at,
+        }
+    }
+}
+
+/// Reports about one article, and what a moderator decided about them.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ModerationCaseDto {
+    pub id: i64,
+    pub article_id: i64,
+    pub status: CaseStatus,
+    /// Oldest first.
+    pub reports: Vec<ReportDto>,
+    #[serde(with = "serde_time")]
+    pub opened_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<Resolution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_by: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution_note: Option<String>,
+    #[serde(with = "serde_time::option")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl From<ModerationCase> for ModerationCaseDto {
+    fn from(case: ModerationCase) -> Self {
+        let status = case.status();
+        let decision = case.decision;
+        Self {
+            id: case.id,
+            article_id: case.article_id.into(),
+            status,
+            reports: case.reports.into_iter().map(Into::into).collect(),
+            opened_at: case.opened_at,
+            resolution: decision.as_ref().map(|decision| decision.resolution),
+            resolved_by: decision
+                .as_ref()
+                .map(|decision| decision.resolved_by.into()),
+            resolved_at: decision.as_ref().map(|decision| decision.resolved_at),
+            resolution_note: decision.and_then(|decision| decision.note),
+        }
+    }
+}
@@ -1,9 +1,60 @@
 // False positive from `serde` + `utoipa` derive expansion on the generic page type.
 #![allow(clippy::option_if_let_else)]
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+/// Page size used when a listing request asks for `limit=0`.
+pub const DEFAULT_PAGE_LIMIT: u32 = 20;
+/// Page size cap of a listing endpoint without a configured one.
+pub const MAX_PAGE_LIMIT: u32 = 100;
+
+/// Default and largest page size of one listing endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageLimits {
+    /// Page size used when `limit` is 0 or omitted.
+    pub default: u32,
+    /// Larger `limit` values are clamped to this.
+    pub max: u32,
+}
+
+impl PageLimits {
+    /// Effective page size for a requested `limit`.
+    #[must_use]
+    pub const fn apply(self, limit: u32) -> u32 {
+        if limit == 0 {
+            self.default
+        } else if limit > self.max {
+            self.max
+        } else {
+            limit
+        }
+    }
+}
+
+impl Default for PageLimits {
+    fn default() -> Self {
+        Self {
+            default: DEFAULT_PAGE_LIMIT,
+            max: MAX_PAGE_LIMIT,
+        }
+    }
+}
+
+/// Page limits of every listing endpoint, from `PAGE_LIMIT_*` settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PaginationPolicy {
+    /// Limits of endpoints without an override of their own.
+    pub shared: PageLimits,
+    pub articles: PageLimits,
+    pub users: PageLimits,
+    /// Audit log listings, including the account activity feed.
+    pub audit_logs: PageLimits,
+    pub moderation: PageLimits,
+    /// Job run history.
+    pub jobs: PageLimits,
+}
+
 #[derive(Debug, Clone, Serialize, ToSchema)]
 #[serde(bound(serialize = "T: Serialize"))]
 #[must_use]
@@ -12,6 +63,19 @@ pub struct CursorPage<T> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_cursor: Option<String>,
     pub has_more: bool,
+    /// Cursor pointing at the first item of this page, present when newer
+    /// items exist before it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
+    /// Effective page size after defaults and clamping.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    /// Approximate number of items across all pages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_estimate: Option<u64>,
+    /// Exact number of items across all pages, for numbered pages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<u64>,
 }
 
 impl<T> CursorPage<T> {
@@ -21,6 +85,94 @@ impl<T> CursorPage<T> {
             items,
             next_cursor,
             has_more,
+            prev_cursor: None,
+            limit: None,
+            total_estimate: None,
+            total_count: None,
+        }
+    }
+
+    /// A numbered page starting `offset` items into a listing of
+    /// `total_count`; it has no cursors.
+    pub fn numbered(items: Vec<T>, offset: u64, total_count: u64) -> Self {
+        let has_more = offset + (items.len() as u64) < total_count;
+        Self {
+            has_more,
+            total_count: Some(total_count),
+            ..Self::new(items, None)
         }
     }
+
+    pub const fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn with_prev_cursor(mut self, prev_cursor: Option<String>) -> Self {
+        self.prev_cursor = prev_cursor;
+        self
+    }
+
+    pub const fn with_total_estimate(mut self, total_estimate: Option<u64>) -> Self {
+        self.total_estimate = total_estimate;
+        self
+    }
+}
+
+/// Which way a cursor listing walks from its cursor.
+///
+/// `Forward` returns older items after the cursor (the default); `Backward`
+/// returns the newer items immediately before it, still in newest-first
+/// order, so a client can page back using `prev_cursor`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PageDirection {
+    #[default]
+    Forward,
+    Backward,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CursorPage, PageLimits};
+
+    #[test]
+    fn optional_metadata_is_omitted_until_set() {
+        let bare = serde_json::to_value(CursorPage::new(vec![1, 2], None)).unwrap();
+        assert_eq!(
+            bare,
+            serde_json::json!({ "items": [1, 2], "has_more": false })
+        );
+
+        let page = CursorPage::new(vec![3], Some("next".into()))
+            .with_limit(1)
+            .with_prev_cursor(Some("prev".into()))
+            .with_total_estimate(Some(5));
+        let value = serde_json::to_value(page).unwrap();
+        assert_eq!(value["limit"], 1);
+        assert_eq!(value["prev_cursor"], "prev");
+        assert_eq!(value["total_estimate"], 5);
+        assert_eq!(value["has_more"], true);
+    }
+
+    #[test]
+    fn numbered_pages_report_more_until_the_total_is_reached() {
+        let page = CursorPage::numbered(vec![1, 2], 2, 5);
+        assert!(page.has_more);
+        assert_eq!(page.total_count, Some(5));
+        assert!(page.next_cursor.is_none());
+        assert!(!CursorPage::numbered(vec![5], 4, 5).has_more);
+        assert!(!CursorPage::<i32>::numbered(vec![], 10, 5).has_more);
+    }
+
+    #[test]
+    fn page_limits_default_zero_and_clamp_large_requests() {
+        let limits = PageLimits {
+            default: 25,
+            max: 50,
+        };
+        assert_eq!(limits.apply(0), 25);
+        assert_eq!(limits.apply(10), 10);
+        assert_eq!(limits.apply(500), 50);
+    }
 }
@@ -0,0 +1,53 @@
+use super::serde_time;
+use crate::application::CapabilityView;
+use crate::domain::{CustomRole, Role};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// An operator-defined role.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CustomRoleDto {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Everything the role grants, sorted.
+    pub capabilities: Vec<CapabilityView>,
+    #[serde(with = "serde_time")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "serde_time")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<CustomRole> for CustomRoleDto {
+    fn from(role: CustomRole) -> Self {
+        Self {
+            name: role.name.to_string(),
+            description: role.description,
+            capabilities: role
+                .capabilities
+                .into_iter()
+                .map(CapabilityView::from)
+                .collect(),
+            created_at: role.created_at,
+            updated_at: role.updated_at,
+        }
+    }
+}
+
+/// The roles a user's next tokens are issued with.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RoleAssignmentDto {
+    pub user_id: i64,
+    pub role: Role,
+    /// Replaces the capabilities of `role` when set.
+    pub custom_role: Option<String>,
+}
+
+/// Capabilities granted to a user on top of those of their role.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CapabilityGrantsDto {
+    pub user_id: i64,
+    /// Sorted by name.
+    pub capabilities: Vec<CapabilityView>,
+}
@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::serde_time;
+use crate::application::ports::security_events::{ClientInfo, TokenReuseIncident};
+use crate::domain::audit::entity::AuditLog;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct RequestClientDto {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl From<ClientInfo> for RequestClientDto {
+    fn from(client: ClientInfo) -> Self {
+        Self {
+            ip_address: client.ip_address,
+            user_agent: client.user_agent,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TokenReuseIncidentDto {
+    pub id: i64,
+    pub user_id: Option<i64>,
+    pub session_id: Option<String>,
+    pub family_id: Option<String>,
+    pub legitimate: RequestClientDto,
+    pub suspect: RequestClientDto,
+    #[serde(with = "serde_time")]
+    pub detected_at: DateTime<Utc>,
+}
+
+impl From<AuditLog> for TokenReuseIncidentDto {
+    fn from(log: AuditLog) -> Self {
+        let incident = log
+            .details
+            .and_then(|details| serde_json::from_value::<TokenReuseIncident>(details).ok());
+
+        match incident {
+            Some(incident) => Self {
+                id: log.id,
+                user_id: Some(incident.user_id),
+                session_id: Some(incident.session_id),
+                family_id: incident.family_id,
+                legitimate: incident.legitimate.into(),
+                suspect: incident.suspect.into(),
+                detected_at: log.created_at,
+            },
+            // Fall back to the audit columns when the details payload is missing
+            // or was written in an unexpected shape.
+            None => Self {
+                id: log.id,
+                user_id: log.user_id.map(Into::into),
+                session_id: None,
+                family_id: None,
+                legitimate: RequestClientDto::default(),
+                suspect: RequestClientDto {
+                    ip_address: log.ip_address,
+                    user_agent: log.user_agent,
+                },
+                detected_at: log.created_at,
+            },
+        }
+    }
+}
@@ -1,6 +1,27 @@
-use chrono::{DateTime, Utc};
+//! RFC 3339 timestamps for DTOs.
+//!
+//! Timestamps are always emitted with an explicit offset (`+00:00` for UTC)
+//! and accepted with any offset, which is normalised to UTC.
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use chrono_tz::Tz;
 use serde::{self, Deserialize, Deserializer, Serializer};
 
+/// Format a timestamp as RFC 3339 with an explicit numeric offset.
+#[must_use]
+pub fn format<Z>(value: &DateTime<Z>) -> String
+where
+    Z: TimeZone,
+    Z::Offset: std::fmt::Display,
+{
+    value.to_rfc3339_opts(SecondsFormat::AutoSi, false)
+}
+
+/// Format a UTC timestamp in `tz`, for display hints such as `?tz=`.
+#[must_use]
+pub fn localize(value: &DateTime<Utc>, tz: Tz) -> String {
+    format(&value.with_timezone(&tz))
+}
+
 /// Serialize a `DateTime<Utc>` as an RFC 3339 string.
 ///
 /// # Errors
@@ -10,7 +31,7 @@ pub fn serialize<S>(value: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Er
 where
     S: Serializer,
 {
-    serializer.serialize_str(&value.to_rfc3339())
+    serializer.serialize_str(&format(value))
 }
 
 #[allow(dead_code)]
@@ -42,7 +63,7 @@ pub mod option {
         S: Serializer,
     {
         match value {
-            Some(dt) => serializer.serialize_some(&dt.to_rfc3339()),
+            Some(dt) => serializer.serialize_some(&super::format(dt)),
             None => serializer.serialize_none(),
         }
     }
@@ -66,3 +87,26 @@ pub mod option {
         .transpose()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{format, localize};
+    use chrono::{DateTime, TimeZone, Utc};
+
+    #[test]
+    fn timestamps_always_carry_an_offset() {
+        let value = Utc.with_ymd_and_hms(2024, 6, 1, 12, 30, 0).unwrap();
+        assert_eq!(format(&value), "2024-06-01T12:30:00+00:00");
+        assert_eq!(
+            localize(&value, chrono_tz::Asia::Tokyo),
+            "2024-06-01T21:30:00+09:00"
+        );
+    }
+
+    #[test]
+    fn offsets_are_normalised_to_utc_on_input() {
+        let parsed: DateTime<Utc> =
+            super::deserialize(serde_json::json!("2024-06-01T21:30:00+09:00")).unwrap();
+        assert_eq!(parsed, Utc.with_ymd_and_hms(2024, 6, 1, 12, 30, 0).unwrap());
+    }
+}
@@ -12,4 +12,7 @@ pub struct SessionInfoDto {
     #[serde(with = "serde_time")]
     pub created_at: DateTime<Utc>,
     pub revoked: bool,
+    /// Whether this is the session the request was made with.
+    #[serde(default)]
+    pub current: bool,
 }
@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::serde_time;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BackendsDto {
+    /// Primary data store, e.g. `postgres`.
+    pub storage: String,
+    /// Session store implementation, `redis` or `in_memory`.
+    pub session_store: String,
+    pub redis: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FeaturesDto {
+    pub security_webhook: bool,
+    /// Passwords are checked against a directory (LDAP) before local ones.
+    pub directory_login: bool,
+    /// At least one upstream OIDC login provider is configured.
+    pub federated_login: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ServiceStatusDto {
+    pub status: String,
+    pub version: String,
+    pub git_commit: String,
+    pub build_date: Option<String>,
+    #[serde(with = "serde_time")]
+    pub started_at: DateTime<Utc>,
+    pub uptime_seconds: i64,
+    pub backends: BackendsDto,
+    pub features: FeaturesDto,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SimulatedTimeDto {
+    /// The simulated current time.
+    #[serde(with = "serde_time")]
+    pub now: DateTime<Utc>,
+    /// How far the simulated clock runs ahead of real time.
+    pub offset_seconds: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReadOnlyDto {
+    /// Mutating requests are refused with 503 while this is set.
+    pub enabled: bool,
+}
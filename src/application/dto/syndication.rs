@@ -0,0 +1,14 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Whether an article is announced on the configured syndication targets
+/// when it is published.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ArticleSyndicationDto {
+    pub article_id: i64,
+    /// `false` once the article has been opted out.
+    pub enabled: bool,
+    /// Names of the targets announcements go to; empty when syndication is
+    /// not configured.
+    pub targets: Vec<String>,
+}
@@ -0,0 +1,65 @@
+use super::serde_time;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Most data rows accepted in one import.
+pub const MAX_IMPORT_ROWS: usize = 10_000;
+
+/// What to do with a row whose username already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateStrategy {
+    /// Leave the existing user untouched.
+    #[default]
+    Skip,
+    /// Report the row as failed.
+    Fail,
+    /// Apply the row's role, password hash and reset flag to the existing user.
+    Update,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportJobStatus {
+    Queued,
+    Running,
+    Completed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportRowStatus {
+    Created,
+    Updated,
+    Skipped,
+    Failed,
+}
+
+/// Outcome of one CSV data row.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ImportRowResult {
+    /// 1-based line number of the row in the uploaded CSV.
+    pub line: usize,
+    pub username: String,
+    pub status: ImportRowStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Progress and per-row results of a bulk user import.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UserImportJobDto {
+    pub id: String,
+    pub status: ImportJobStatus,
+    pub on_duplicate: DuplicateStrategy,
+    pub total_rows: usize,
+    pub processed_rows: usize,
+    pub results: Vec<ImportRowResult>,
+    #[serde(with = "serde_time")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "serde_time::option")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
@@ -1,5 +1,6 @@
-use crate::domain::{Capability, Role, User};
+use crate::domain::{Capability, PendingDeletion, Role, User};
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -15,6 +16,30 @@ pub struct UserDto {
     pub is_active: bool,
     #[serde(with = "serde_time")]
     pub created_at: DateTime<Utc>,
+    /// Preferred IANA timezone for displaying timestamps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// `created_at` rendered in the zone requested via `?tz=`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at_local: Option<String>,
+    /// True until the user replaces a password set by an import.
+    #[serde(default)]
+    pub password_reset_required: bool,
+    /// Address used for password resets, when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Whether the user has confirmed `email` with a mailed token.
+    #[serde(default)]
+    pub email_verified: bool,
+}
+
+impl UserDto {
+    /// Fill display-only local timestamps for `tz`.
+    #[must_use]
+    pub fn localized(mut self, tz: Option<Tz>) -> Self {
+        self.created_at_local = tz.map(|tz| serde_time::localize(&self.created_at, tz));
+        self
+    }
 }
 
 impl From<User> for UserDto {
@@ -25,6 +50,11 @@ impl From<User> for UserDto {
             role: user.role,
             is_active: user.is_active,
             created_at: user.created_at,
+            timezone: user.timezone.map(|tz| tz.as_str().to_string()),
+            created_at_local: None,
+            password_reset_required: user.password_reset_required,
+            email: user.email.map(|email| email.to_string()),
+            email_verified: user.email_verified,
         }
     }
 }
@@ -44,6 +74,17 @@ impl From<Capability> for CapabilityView {
     }
 }
 
+/// What the current token allows, for showing or hiding UI controls.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PermissionsDto {
+    pub role: Role,
+    /// Custom role the token's capabilities come from, when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_role: Option<String>,
+    /// Concrete resource/action pairs the token allows, sorted.
+    pub permissions: Vec<CapabilityView>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UserProfileDto {
     pub user: UserDto,
@@ -54,8 +95,10 @@ pub struct UserProfileDto {
 }
 
 impl UserProfileDto {
+    /// Profile of `user` as seen through `auth`, with `expires_in` counted
+    /// from `now`.
     #[must_use]
-    pub fn from_parts(user: User, auth: &AuthenticatedUser) -> Self {
+    pub fn from_parts(user: User, auth: &AuthenticatedUser, now: DateTime<Utc>) -> Self {
         let user_dto: UserDto = user.into();
         let mut capabilities: Vec<_> = auth
             .capabilities
@@ -70,7 +113,7 @@ impl UserProfileDto {
         });
         let expires_in = auth
             .expires_at
-            .signed_duration_since(Utc::now())
+            .signed_duration_since(now)
             .num_seconds()
             .max(0);
 
@@ -82,3 +125,26 @@ impl UserProfileDto {
         }
     }
 }
+
+/// A scheduled deletion of the caller's own account.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AccountDeletionDto {
+    #[serde(with = "serde_time")]
+    pub requested_at: DateTime<Utc>,
+    /// When the account is anonymized unless an admin cancels the deletion.
+    #[serde(with = "serde_time")]
+    pub purge_after: DateTime<Utc>,
+    /// User who takes over the account's articles.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transfer_articles_to: Option<i64>,
+}
+
+impl From<PendingDeletion> for AccountDeletionDto {
+    fn from(deletion: PendingDeletion) -> Self {
+        Self {
+            requested_at: deletion.requested_at,
+            purge_after: deletion.purge_after,
+            transfer_articles_to: deletion.transfer_to.map(i64::from),
+        }
+    }
+}
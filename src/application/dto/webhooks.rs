@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::serde_time;
+use crate::application::ports::security_events::TokenReuseIncident;
+use crate::domain::{DomainEvent, EventKind, Webhook, WebhookDeliveryAttempt, WebhookEvent};
+
+/// Event name of refresh-token reuse webhook deliveries.
+pub const TOKEN_REUSE_WEBHOOK_EVENT: &str = "refresh_token_reuse";
+
+/// Body posted to the security webhook when a refresh token is replayed.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TokenReuseWebhookPayload {
+    /// Always `refresh_token_reuse`.
+    pub event: String,
+    pub incident: TokenReuseIncident,
+}
+
+impl TokenReuseWebhookPayload {
+    #[must_use]
+    pub fn new(incident: TokenReuseIncident) -> Self {
+        Self {
+            event: TOKEN_REUSE_WEBHOOK_EVENT.into(),
+            incident,
+        }
+    }
+}
+
+/// A webhook subscription. The secret is shown once, when the webhook is
+/// created.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookDto {
+    pub id: i64,
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    pub active: bool,
+    /// Key deliveries are signed with; only in the response that created
+    /// the webhook.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<i64>,
+    #[serde(with = "serde_time")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "serde_time")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Webhook> for WebhookDto {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            id: webhook.id,
+            url: webhook.url,
+            events: webhook.events,
+            active: webhook.active,
+            secret: None,
+            created_by: webhook.created_by.map(i64::from),
+            created_at: webhook.created_at,
+            updated_at: webhook.updated_at,
+        }
+    }
+}
+
+/// One try at delivering an event to a webhook.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookDeliveryDto {
+    pub id: i64,
+    /// Shared by every try of one event; sent as `X-Mokkan-Delivery`.
+    pub delivery_id: String,
+    pub event: WebhookEvent,
+    pub attempt: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub succeeded: bool,
+    pub duration_ms: u64,
+    #[serde(with = "serde_time")]
+    pub attempted_at: DateTime<Utc>,
+}
+
+impl From<WebhookDeliveryAttempt> for WebhookDeliveryDto {
+    fn from(attempt: WebhookDeliveryAttempt) -> Self {
+        Self {
+            id: attempt.id,
+            delivery_id: attempt.delivery_id,
+            event: attempt.event,
+            attempt: attempt.attempt,
+            status_code: attempt.status_code,
+            error: attempt.error,
+            succeeded: attempt.succeeded,
+            duration_ms: attempt.duration_ms,
+            attempted_at: attempt.attempted_at,
+        }
+    }
+}
+
+/// The article a webhook payload is about, as it stood after the change; for
+/// deletions, just before it.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookArticle {
+    pub id: i64,
+    pub slug: String,
+    pub title: String,
+    pub published: bool,
+    #[serde(with = "serde_time::option", skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// Body posted to webhooks subscribed to an article event.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ArticleWebhookPayload {
+    /// One of the `article.*` events.
+    pub event: WebhookEvent,
+    #[serde(with = "serde_time")]
+    pub occurred_at: DateTime<Utc>,
+    pub article: WebhookArticle,
+}
+
+impl ArticleWebhookPayload {
+    /// The payload for an article event; `None` for other events and for
+    /// article events published without the article.
+    #[must_use]
+    pub fn from_event(event: &DomainEvent) -> Option<Self> {
+        let (webhook_event, id) = match event.kind {
+            EventKind::ArticleCreated(id) => (WebhookEvent::ArticleCreated, id),
+            EventKind::ArticleUpdated(id) => (WebhookEvent::ArticleUpdated, id),
+            EventKind::ArticlePublished(id) => (WebhookEvent::ArticlePublished, id),
+            EventKind::ArticleUnpublished(id) => (WebhookEvent::ArticleUnpublished, id),
+            EventKind::ArticleDeleted(id) => (WebhookEvent::ArticleDeleted, id),
+            _ => return None,
+        };
+        let article = event.article.as_ref()?;
+        Some(Self {
+            event: webhook_event,
+            occurred_at: event.occurred_at,
+            article: WebhookArticle {
+                id: id.into(),
+                slug: article.slug.clone(),
+                title: article.title.clone(),
+                published: article.published,
+                published_at: article.published_at,
+            },
+        })
+    }
+}
@@ -25,6 +25,19 @@ pub enum AppError {
     #[error("forbidden: {0}")]
     Forbidden(String),
 
+    /// The request is valid but cannot be served right now, e.g. a write in
+    /// read-only mode.
+    #[error("service unavailable: {0}")]
+    Unavailable(String),
+
+    /// The caller must wait `retry_after_secs` before trying again, e.g.
+    /// after repeated failed logins.
+    #[error("too many requests: {message}")]
+    TooManyRequests {
+        message: String,
+        retry_after_secs: u64,
+    },
+
     #[error("infrastructure failure: {0}")]
     Infrastructure(#[source] AnyhowError),
 }
@@ -50,6 +63,17 @@ impl AppError {
         Self::Forbidden(msg.into())
     }
 
+    pub fn unavailable(msg: impl Into<String>) -> Self {
+        Self::Unavailable(msg.into())
+    }
+
+    pub fn too_many_requests(msg: impl Into<String>, retry_after_secs: u64) -> Self {
+        Self::TooManyRequests {
+            message: msg.into(),
+            retry_after_secs,
+        }
+    }
+
     /// Create an infrastructure error from a message or an existing error.
     ///
     /// Many call sites pass `err.to_string()`; to keep those call sites simple
@@ -0,0 +1,238 @@
+// src/application/markdown/html.rs
+//! Markdown to HTML under a [`RenderProfile`].
+//!
+//! Article bodies never contain trusted markup: raw HTML in the source is
+//! escaped, and each profile's policy decides which link and image targets
+//! survive. Every profile emits the same small set of elements, without
+//! classes or inline styles, so embedders can style the output predictably.
+
+use super::{Block, Span, SpanKind, parse};
+use crate::application::RenderProfile;
+use std::fmt::Write as _;
+
+/// What a profile lets through.
+struct Policy {
+    /// URL schemes allowed in links and images.
+    schemes: &'static [&'static str],
+    /// Whether site-relative targets such as `/about` are kept.
+    relative: bool,
+    images: bool,
+    /// Print link targets after their text instead of linking.
+    spell_out_links: bool,
+}
+
+const fn policy(profile: RenderProfile) -> Policy {
+    match profile {
+        RenderProfile::Full => Policy {
+            schemes: &["http", "https", "mailto"],
+            relative: true,
+            images: true,
+            spell_out_links: false,
+        },
+        // Embedded elsewhere, relative links would point at the wrong site.
+        RenderProfile::Minimal => Policy {
+            schemes: &["http", "https"],
+            relative: false,
+            images: false,
+            spell_out_links: false,
+        },
+        RenderProfile::Print => Policy {
+            schemes: &["http", "https", "mailto"],
+            relative: true,
+            images: true,
+            spell_out_links: true,
+        },
+    }
+}
+
+impl Policy {
+    fn allows(&self, url: &str) -> bool {
+        if url.starts_with('#') || (url.starts_with('/') && !url.starts_with("//")) {
+            return self.relative;
+        }
+        url.split_once(':').is_some_and(|(scheme, _)| {
+            self.schemes
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(scheme))
+        })
+    }
+}
+
+/// Render `markdown` as an HTML fragment. Headings start at `<h2>`, leaving
+/// `<h1>` for the article title.
+#[must_use]
+pub fn render(markdown: &str, profile: RenderProfile) -> String {
+    let policy = policy(profile);
+    let mut out = String::new();
+    let mut open_list: Option<&str> = None;
+
+    for block in parse(markdown) {
+        let list = match &block {
+            Block::ListItem { number: None, .. } => Some("ul"),
+            Block::ListItem {
+                number: Some(_), ..
+            } => Some("ol"),
+            _ => None,
+        };
+        if open_list != list {
+            if let Some(tag) = open_list {
+                let _ = writeln!(out, "</{tag}>");
+            }
+            match &block {
+                Block::ListItem {
+                    number: Some(start),
+                    ..
+                } if *start != 1 => {
+                    let _ = writeln!(out, r#"<ol start="{start}">"#);
+                }
+                _ => {
+                    if let Some(tag) = list {
+                        let _ = writeln!(out, "<{tag}>");
+                    }
+                }
+            }
+            open_list = list;
+        }
+
+        match block {
+            Block::Heading(level, spans) => {
+                let level = (level + 1).min(6);
+                let _ = writeln!(out, "<h{level}>{}</h{level}>", inline(&spans, &policy));
+            }
+            Block::Paragraph(spans) => {
+                let _ = writeln!(out, "<p>{}</p>", inline(&spans, &policy));
+            }
+            Block::Quote(spans) => {
+                let _ = writeln!(
+                    out,
+                    "<blockquote><p>{}</p></blockquote>",
+                    inline(&spans, &policy)
+                );
+            }
+            Block::ListItem { spans, .. } => {
+                let _ = writeln!(out, "<li>{}</li>", inline(&spans, &policy));
+            }
+            Block::Code(code) => {
+                let _ = writeln!(out, "<pre><code>{}</code></pre>", escape(&code));
+            }
+            Block::Rule => out.push_str("<hr/>\n"),
+        }
+    }
+    if let Some(tag) = open_list {
+        let _ = writeln!(out, "</{tag}>");
+    }
+    out
+}
+
+fn inline(spans: &[Span], policy: &Policy) -> String {
+    let mut out = String::new();
+    for (index, span) in spans.iter().enumerate() {
+        let url = span.link.as_deref().filter(|url| policy.allows(url));
+        if span.kind == SpanKind::Image {
+            if let Some(src) = url.filter(|_| policy.images) {
+                let _ = write!(
+                    out,
+                    r#"<img src="{}" alt="{}"/>"#,
+                    escape(src),
+                    escape(&span.text)
+                );
+            }
+            continue;
+        }
+
+        let mut text = escape(&span.text);
+        if span.kind == SpanKind::Code {
+            text = format!("<code>{text}</code>");
+        }
+        if span.italic {
+            text = format!("<em>{text}</em>");
+        }
+        if span.bold {
+            text = format!("<strong>{text}</strong>");
+        }
+        match url {
+            Some(url) if policy.spell_out_links => {
+                out.push_str(&text);
+                let link_ends = spans
+                    .get(index + 1)
+                    .is_none_or(|next| next.link != span.link);
+                if link_ends && url != span.text {
+                    let _ = write!(out, " ({})", escape(url));
+                }
+            }
+            Some(url) => {
+                let _ = write!(out, r#"<a href="{}">{text}</a>"#, escape(url));
+            }
+            None => out.push_str(&text),
+        }
+    }
+    out
+}
+
+/// Escape `text` for use in element content or a quoted attribute, dropping
+/// control characters that XML forbids.
+#[must_use]
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            ch if ch.is_control() && !matches!(ch, '\n' | '\t') => {}
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "Hi <script>x</script> **[docs](/docs)** \
+        [ext](https://e.test/?a=1&b=2) [bad](javascript:alert) ![cat](https://e.test/c.png)";
+
+    #[test]
+    fn full_profile_keeps_safe_links_and_images() {
+        assert_eq!(
+            render(SOURCE, RenderProfile::Full),
+            "<p>Hi &lt;script&gt;x&lt;/script&gt; <a href=\"/docs\"><strong>docs</strong></a> \
+             <a href=\"https://e.test/?a=1&amp;b=2\">ext</a> bad \
+             <img src=\"https://e.test/c.png\" alt=\"cat\"/></p>\n"
+        );
+    }
+
+    #[test]
+    fn minimal_profile_drops_images_and_relative_links() {
+        assert_eq!(
+            render(SOURCE, RenderProfile::Minimal),
+            "<p>Hi &lt;script&gt;x&lt;/script&gt; <strong>docs</strong> \
+             <a href=\"https://e.test/?a=1&amp;b=2\">ext</a> bad </p>\n"
+        );
+    }
+
+    #[test]
+    fn print_profile_spells_out_link_targets() {
+        assert_eq!(
+            render(
+                "[*a* b](https://e.test) [https://e.test](https://e.test)",
+                RenderProfile::Print
+            ),
+            "<p><em>a</em> b (https://e.test) https://e.test</p>\n"
+        );
+    }
+
+    #[test]
+    fn blocks_and_lists() {
+        assert_eq!(
+            render(
+                "## Part\n\n- a\n- b\n\n3. c\n\n> q\n\n---",
+                RenderProfile::Full
+            ),
+            "<h3>Part</h3>\n<ul>\n<li>a</li>\n<li>b</li>\n</ul>\n<ol start=\"3\">\n\
+             <li>c</li>\n</ol>\n<blockquote><p>q</p></blockquote>\n<hr/>\n"
+        );
+    }
+}
@@ -0,0 +1,287 @@
+// src/application/markdown/mod.rs
+//! The subset of Markdown that article bodies use: ATX headings, paragraphs,
+//! quotes, bullet and numbered lists, fenced code, rules, and inline bold,
+//! italic, code, links and images. Anything else, raw HTML included, is kept
+//! as plain text.
+
+pub mod html;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    /// Level 1 to 6.
+    Heading(u8, Vec<Span>),
+    Paragraph(Vec<Span>),
+    Quote(Vec<Span>),
+    /// `number` is `None` for a bullet.
+    ListItem {
+        number: Option<u32>,
+        spans: Vec<Span>,
+    },
+    Code(String),
+    Rule,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpanKind {
+    #[default]
+    Text,
+    Code,
+    /// An image, with its alt text as the span text.
+    Image,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Span {
+    pub text: String,
+    pub kind: SpanKind,
+    pub bold: bool,
+    pub italic: bool,
+    /// Link target, or the source of an image.
+    pub link: Option<String>,
+}
+
+/// Split `markdown` into blocks.
+#[must_use]
+pub fn parse(markdown: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut lines = markdown.lines();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        let block = if trimmed.is_empty() {
+            None
+        } else if trimmed.starts_with("```") {
+            let code: Vec<&str> = lines
+                .by_ref()
+                .take_while(|line| !line.trim_start().starts_with("```"))
+                .collect();
+            Some(Block::Code(code.join("\n")))
+        } else if is_rule(trimmed) {
+            Some(Block::Rule)
+        } else if let Some(block) = heading(trimmed)
+            .or_else(|| {
+                trimmed
+                    .strip_prefix('>')
+                    .map(|text| Block::Quote(inline(text.trim_start())))
+            })
+            .or_else(|| list_item(trimmed))
+        {
+            Some(block)
+        } else {
+            paragraph.push(trimmed);
+            continue;
+        };
+
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(inline(&paragraph.join(" "))));
+            paragraph.clear();
+        }
+        blocks.extend(block);
+    }
+    if !paragraph.is_empty() {
+        blocks.push(Block::Paragraph(inline(&paragraph.join(" "))));
+    }
+    blocks
+}
+
+fn is_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|ch| !ch.is_whitespace()).collect();
+    compact.len() >= 3
+        && ['-', '*', '_']
+            .into_iter()
+            .any(|marker| compact.chars().all(|ch| ch == marker))
+}
+
+fn heading(line: &str) -> Option<Block> {
+    let level = line.chars().take_while(|&ch| ch == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    let level = u8::try_from(level)
+        .ok()
+        .filter(|level| (1..=6).contains(level))?;
+    Some(Block::Heading(
+        level,
+        inline(text.trim().trim_end_matches('#').trim_end()),
+    ))
+}
+
+fn list_item(line: &str) -> Option<Block> {
+    if let Some(text) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+    {
+        return Some(Block::ListItem {
+            number: None,
+            spans: inline(text.trim_start()),
+        });
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let text = line[digits..].strip_prefix(". ")?;
+    Some(Block::ListItem {
+        number: Some(line[..digits].parse().ok()?),
+        spans: inline(text.trim_start()),
+    })
+}
+
+/// Parse emphasis, code and links within one block.
+#[must_use]
+pub fn inline(text: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut current = Span::default();
+    let mut rest = text;
+
+    while let Some(ch) = rest.chars().next() {
+        if current.kind == SpanKind::Code {
+            if ch == '`' {
+                flush(&mut spans, &mut current);
+                current.kind = SpanKind::Text;
+            } else {
+                current.text.push(ch);
+            }
+            rest = &rest[ch.len_utf8()..];
+            continue;
+        }
+        if let Some((alt, src, after)) = rest.strip_prefix('!').and_then(link) {
+            flush(&mut spans, &mut current);
+            spans.push(Span {
+                text: alt.to_string(),
+                link: Some(src.to_string()),
+                kind: SpanKind::Image,
+                ..Span::default()
+            });
+            rest = after;
+            continue;
+        }
+
+        match ch {
+            '\\' if rest[1..].starts_with(|next: char| next.is_ascii_punctuation()) => {
+                current.text.push_str(&rest[1..2]);
+                rest = &rest[2..];
+            }
+            '*' if rest.starts_with("**") => {
+                flush(&mut spans, &mut current);
+                current.bold = !current.bold;
+                rest = &rest[2..];
+            }
+            '*' => {
+                flush(&mut spans, &mut current);
+                current.italic = !current.italic;
+                rest = &rest[1..];
+            }
+            '`' => {
+                flush(&mut spans, &mut current);
+                current.kind = SpanKind::Code;
+                rest = &rest[1..];
+            }
+            '[' => {
+                if let Some((label, url, after)) = link(rest) {
+                    flush(&mut spans, &mut current);
+                    for mut span in inline(label) {
+                        span.bold |= current.bold;
+                        span.italic |= current.italic;
+                        span.link = Some(url.to_string());
+                        spans.push(span);
+                    }
+                    rest = after;
+                } else {
+                    current.text.push('[');
+                    rest = &rest[1..];
+                }
+            }
+            _ => {
+                current.text.push(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+    }
+    flush(&mut spans, &mut current);
+    spans
+}
+
+/// Split `[label](url)rest`.
+fn link(text: &str) -> Option<(&str, &str, &str)> {
+    let text = text.strip_prefix('[')?;
+    let close = text.find("](")?;
+    let label = &text[..close];
+    let after = &text[close + 2..];
+    let end = after.find(')')?;
+    Some((label, after[..end].trim(), &after[end + 1..]))
+}
+
+fn flush(spans: &mut Vec<Span>, current: &mut Span) {
+    if !current.text.is_empty() {
+        spans.push(Span {
+            text: std::mem::take(&mut current.text),
+            ..current.clone()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(spans: &[Span]) -> String {
+        spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    fn text(text: &str) -> Span {
+        Span {
+            text: text.into(),
+            ..Span::default()
+        }
+    }
+
+    #[test]
+    fn parses_blocks() {
+        let blocks = parse(
+            "# Title\n\nfirst line\nsecond line\n> quoted\n- one\n2. two\n\n```\nlet x = 1;\n```\n---",
+        );
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Heading(1, vec![text("Title")]),
+                Block::Paragraph(vec![text("first line second line")]),
+                Block::Quote(vec![text("quoted")]),
+                Block::ListItem {
+                    number: None,
+                    spans: vec![text("one")],
+                },
+                Block::ListItem {
+                    number: Some(2),
+                    spans: vec![text("two")],
+                },
+                Block::Code("let x = 1;".into()),
+                Block::Rule,
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_inline_formatting() {
+        let spans = inline(r"a **b *c*** \*d\* `e*f` [**g**](https://example.com) [h");
+        assert_eq!(plain(&spans), "a b c *d* e*f g [h");
+        assert!(spans[1].bold && !spans[1].italic);
+        assert!(spans[2].bold && spans[2].italic);
+        assert_eq!(spans[4].kind, SpanKind::Code);
+        assert_eq!(spans[6].link.as_deref(), Some("https://example.com"));
+        assert!(spans[6].bold);
+    }
+
+    #[test]
+    fn parses_images() {
+        let spans = inline("see ![a cat](/cat.png)! <b>");
+        assert_eq!(plain(&spans), "see a cat! <b>");
+        assert_eq!(spans[1].kind, SpanKind::Image);
+        assert_eq!(spans[1].link.as_deref(), Some("/cat.png"));
+        assert_eq!(spans[2].kind, SpanKind::Text);
+    }
+
+    #[test]
+    fn hashes_without_a_space_are_text() {
+        assert_eq!(
+            parse("#hashtag"),
+            vec![Block::Paragraph(vec![text("#hashtag")])]
+        );
+    }
+}
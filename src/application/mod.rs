@@ -2,17 +2,61 @@
 pub mod commands;
 pub mod dto;
 pub mod error;
+pub(crate) mod markdown;
 pub mod ports;
 pub mod queries;
 pub(crate) mod random_id;
+pub mod read_only;
+pub mod request_context;
 pub mod services;
+pub(crate) mod text_diff;
 
-pub use dto::articles::{ArticleDto, ArticleRevisionDto};
+pub use dto::access_rules::AccessRuleDto;
+pub use dto::activity::{ActivityCategory, ActivityEntryDto};
+pub use dto::articles::{
+    ArticleContributorDto, ArticleDto, ArticleImportDto, ArticleRevisionDiffDto,
+    ArticleRevisionDto, ArticleSort, AuthorFacetDto, CustomFieldDefinitionDto, DiffGranularity,
+    DiffOp, DiffSegment, FieldDiffDto, RenderProfile, SearchFacetsDto, SearchResultDto,
+    TagFacetDto,
+};
 pub use dto::audit::LogDto as AuditLogDto;
 pub use dto::auth::{
-    Subject as TokenSubject, TokenDto as AuthTokenDto, UserIdentity as AuthenticatedUser,
+    IdTokenClaims, Subject as TokenSubject, TokenDto as AuthTokenDto,
+    UserIdentity as AuthenticatedUser,
+};
+pub use dto::batch::{BatchResult, BulkItemResult, BulkItemStatus, BulkResult, MAX_BATCH_IDS};
+pub use dto::blocks::UserBlockDto;
+pub use dto::bundles::{
+    BundleArticle, BundleFormat, BundleImportDto, BundleImportItem, BundleItemStatus,
+    BundleManifest, BundleRecord, BundleRevision, BundleUser, ConflictStrategy, MAX_BUNDLE_BYTES,
+};
+pub use dto::custom_field_migrations::{
+    CustomFieldMigration, CustomFieldMigrationJobDto, MigrationFailure, MigrationJobStatus,
+};
+pub use dto::exports::{
+    ArticleExportJobDto, ExportFormat, ExportJobStatus, ExportedFile, StaticSiteExportDto,
 };
-pub use dto::pagination::CursorPage;
+pub use dto::inspect::{ArticleInspectionDto, SessionInspectionDto, UserInspectionDto};
+pub use dto::integrity::{AnomalyKind, IntegrityAnomalyDto, IntegrityReportDto};
+pub use dto::jobs::{JobRunDto, JobRunOutcome, JobStatusDto, JobTrigger};
+pub use dto::media::MediaAssetDto;
+pub use dto::moderation::{ModerationCaseDto, ReportDto};
+pub use dto::pagination::{
+    CursorPage, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT, PageDirection, PageLimits, PaginationPolicy,
+};
+pub use dto::roles::{CapabilityGrantsDto, CustomRoleDto, RoleAssignmentDto};
+pub use dto::security::{RequestClientDto, TokenReuseIncidentDto};
 pub use dto::sessions::SessionInfoDto;
-pub use dto::users::{CapabilityView, UserDto, UserProfileDto};
+pub use dto::status::{ReadOnlyDto, ServiceStatusDto, SimulatedTimeDto};
+pub use dto::syndication::ArticleSyndicationDto;
+pub use dto::user_import::{
+    DuplicateStrategy, ImportJobStatus, ImportRowResult, ImportRowStatus, MAX_IMPORT_ROWS,
+    UserImportJobDto,
+};
+pub use dto::users::{AccountDeletionDto, CapabilityView, PermissionsDto, UserDto, UserProfileDto};
+pub use dto::webhooks::{
+    ArticleWebhookPayload, TOKEN_REUSE_WEBHOOK_EVENT, TokenReuseWebhookPayload, WebhookArticle,
+    WebhookDeliveryDto, WebhookDto,
+};
 pub use error::{AppError, AppResult};
+pub use read_only::ReadOnlySwitch;
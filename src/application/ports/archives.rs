@@ -0,0 +1,29 @@
+// src/application/ports/archives.rs
+use crate::application::AppResult;
+
+/// One file inside an archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// Path within the archive, `/`-separated.
+    pub name: String,
+    pub contents: Vec<u8>,
+}
+
+/// Packs files into a single compressed archive and back.
+pub trait Archiver: Send + Sync {
+    /// Compress `entries` into one archive, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive cannot be written.
+    fn pack(&self, entries: &[ArchiveEntry]) -> AppResult<Vec<u8>>;
+
+    /// Every entry of `archive`, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `archive` is malformed, uses an
+    /// unsupported format, or holds an entry larger than `max_entry_size`
+    /// bytes.
+    fn unpack(&self, archive: &[u8], max_entry_size: usize) -> AppResult<Vec<ArchiveEntry>>;
+}
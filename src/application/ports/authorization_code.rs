@@ -12,6 +12,8 @@ pub struct Code {
     pub scope: Option<String>,
     pub code_challenge: Option<String>,
     pub code_challenge_method: Option<String>,
+    /// OIDC `nonce` from the authorization request, echoed in the `id_token`.
+    pub nonce: Option<String>,
     pub created_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
 }
@@ -0,0 +1,34 @@
+// src/application/ports/blob_storage.rs
+//! Storage for uploaded files, such as a local directory or an
+//! S3-compatible bucket.
+
+use crate::application::AppResult;
+use crate::async_support::BoxFuture;
+use bytes::Bytes;
+
+/// How a stored blob reaches the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlobRead {
+    /// The bytes, served by this service.
+    Bytes(Bytes),
+    /// A short-lived URL the client is redirected to.
+    Redirect(String),
+}
+
+/// Keeps blobs under keys chosen by the caller.
+pub trait BlobStorage: Send + Sync {
+    /// Store `bytes` under `key`, replacing anything already there.
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        content_type: &'a str,
+        bytes: Bytes,
+    ) -> BoxFuture<'a, AppResult<()>>;
+
+    /// How the blob under `key` is read; `None` if there is none. Adapters
+    /// that redirect need not check that the blob exists.
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, AppResult<Option<BlobRead>>>;
+
+    /// Remove the blob under `key`; removing a missing blob succeeds.
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, AppResult<()>>;
+}
@@ -0,0 +1,163 @@
+// src/application/ports/command_journal.rs
+//! Append-only journal of the writes made through the command services.
+//!
+//! Each entry records the command, who ran it and how it ended, so a database
+//! can be rebuilt by replaying the successful ones against empty repositories
+//! (see `services::JournalReplayer`). Secrets are never journaled: passwords
+//! are left out of registrations and password changes are not recorded.
+
+use crate::application::commands::articles::BulkArticleOperation;
+use crate::application::{AppResult, AuthenticatedUser};
+use crate::async_support::BoxFuture;
+use crate::domain::{ArticleVisibility, Capability, CustomFields, Role, UserId};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A journaled write, with the inputs needed to run it again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JournaledCommand {
+    CreateArticle {
+        title: String,
+        body: String,
+        publish: bool,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        tags: Vec<String>,
+        #[serde(default, skip_serializing_if = "CustomFields::is_empty")]
+        custom_fields: CustomFields,
+        #[serde(default, skip_serializing_if = "ArticleVisibility::is_public")]
+        visibility: ArticleVisibility,
+    },
+    UpdateArticle {
+        id: i64,
+        title: Option<String>,
+        body: Option<String>,
+        publish: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tags: Option<Vec<String>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        custom_fields: Option<CustomFields>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility: Option<ArticleVisibility>,
+    },
+    SetPublishState {
+        id: i64,
+        publish: bool,
+    },
+    RevertArticle {
+        id: i64,
+        version: i32,
+    },
+    DeleteArticle {
+        id: i64,
+    },
+    AddContributor {
+        article_id: i64,
+        user_id: i64,
+    },
+    RemoveContributor {
+        article_id: i64,
+        user_id: i64,
+    },
+    BulkArticles {
+        operation: BulkArticleOperation,
+        ids: Vec<i64>,
+    },
+    PutCustomField {
+        name: String,
+        field_type: String,
+        required: bool,
+    },
+    DeleteCustomField {
+        name: String,
+    },
+    RegisterUser {
+        username: String,
+        role: Option<Role>,
+    },
+    UpdateUser {
+        user_id: i64,
+        is_active: Option<bool>,
+        role: Option<Role>,
+    },
+    GrantRole {
+        user_id: i64,
+        role: Role,
+    },
+    RevokeRole {
+        user_id: i64,
+    },
+    UpdatePreferences {
+        timezone: Option<String>,
+    },
+}
+
+/// The authenticated user a command ran as.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalActor {
+    pub id: i64,
+    pub username: String,
+    pub role: Role,
+    pub capabilities: Vec<Capability>,
+}
+
+impl JournalActor {
+    #[must_use]
+    pub fn from_user(user: &AuthenticatedUser) -> Self {
+        let mut capabilities: Vec<_> = user.capabilities.iter().cloned().collect();
+        capabilities.sort_by(|a, b| {
+            a.resource
+                .cmp(&b.resource)
+                .then_with(|| a.action.cmp(&b.action))
+        });
+        Self {
+            id: user.id.into(),
+            username: user.username.clone(),
+            role: user.role,
+            capabilities,
+        }
+    }
+
+    /// Rebuild the identity as `id`, valid around `at`.
+    #[must_use]
+    pub fn to_user(&self, id: UserId, at: DateTime<Utc>) -> AuthenticatedUser {
+        AuthenticatedUser {
+            id,
+            username: self.username.clone(),
+            role: self.role,
+            capabilities: self.capabilities.iter().cloned().collect(),
+            issued_at: at,
+            expires_at: at + Duration::hours(1),
+            session_id: None,
+            token_version: None,
+            custom_role: None,
+            impersonator: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum JournalOutcome {
+    /// `resource_id` is the id of the article or user the command wrote.
+    Succeeded {
+        resource_id: Option<i64>,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub recorded_at: DateTime<Utc>,
+    /// `None` for anonymous commands such as self-registration.
+    pub actor: Option<JournalActor>,
+    pub command: JournaledCommand,
+    pub outcome: JournalOutcome,
+}
+
+pub trait CommandJournal: Send + Sync {
+    /// Append `entry`; earlier entries are never rewritten.
+    fn append<'a>(&'a self, entry: &'a JournalEntry) -> BoxFuture<'a, AppResult<()>>;
+}
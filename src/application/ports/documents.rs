@@ -0,0 +1,24 @@
+// src/application/ports/documents.rs
+use crate::application::AppResult;
+
+/// A word-processor document converted to an article draft.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConvertedDocument {
+    /// Taken from the document properties or its first Title / Heading 1
+    /// paragraph, which is then left out of `markdown`.
+    pub title: Option<String>,
+    pub markdown: String,
+    /// Embedded images that were dropped; there is nowhere to store them yet.
+    pub skipped_images: u32,
+}
+
+/// Turns an uploaded document into Markdown.
+pub trait DocumentConverter: Send + Sync {
+    /// Convert `document`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the document is malformed or not in a
+    /// supported format.
+    fn to_markdown(&self, document: &[u8]) -> AppResult<ConvertedDocument>;
+}
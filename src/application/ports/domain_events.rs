@@ -0,0 +1,20 @@
+// src/application/ports/domain_events.rs
+//! Where command services announce stored changes, so other subsystems can
+//! react to them without the services knowing who listens.
+
+use crate::application::AppResult;
+use crate::async_support::BoxFuture;
+use crate::domain::DomainEvent;
+
+pub trait EventPublisher: Send + Sync {
+    /// Called once the change `event` describes has been stored.
+    fn publish<'a>(&'a self, event: &'a DomainEvent) -> BoxFuture<'a, AppResult<()>>;
+}
+
+/// Reacts to each published event before live subscribers see it, such as
+/// webhooks queueing deliveries or caches dropping stale entries.
+pub trait EventHandler: Send + Sync {
+    /// Called once per event; events the handler does not follow are
+    /// ignored.
+    fn handle<'a>(&'a self, event: &'a DomainEvent) -> BoxFuture<'a, AppResult<()>>;
+}
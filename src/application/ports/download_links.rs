@@ -0,0 +1,22 @@
+// src/application/ports/download_links.rs
+use crate::application::AppResult;
+use chrono::{DateTime, Utc};
+
+/// Signs and checks the tokens of `GET /api/v1/downloads/{token}` links.
+/// A token grants one resource, without further authentication, until the
+/// signer's link lifetime has passed.
+pub trait DownloadLinkSigner: Send + Sync {
+    /// A token for `resource`, valid from `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token cannot be signed.
+    fn sign(&self, resource: &str, now: DateTime<Utc>) -> AppResult<String>;
+
+    /// The resource `token` grants at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token is malformed, forged or expired.
+    fn verify(&self, token: &str, now: DateTime<Utc>) -> AppResult<String>;
+}
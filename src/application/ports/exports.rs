@@ -0,0 +1,12 @@
+// src/application/ports/exports.rs
+use crate::application::{AppResult, ArticleDto, dto::exports::ExportFormat};
+
+/// Lays out an article as a standalone document for offline reading.
+pub trait ArticleRenderer: Send + Sync {
+    /// Render `article`, whose body is Markdown, as `format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document cannot be produced.
+    fn render(&self, article: &ArticleDto, format: ExportFormat) -> AppResult<Vec<u8>>;
+}
@@ -0,0 +1,59 @@
+use crate::application::AppResult;
+use crate::async_support::BoxFuture;
+use crate::domain::Role;
+
+/// A user whose credentials an external directory accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalIdentity {
+    /// The directory's own name for the user (e.g. an LDAP DN).
+    pub subject: String,
+    /// Distinguished names (or plain names) of the groups the user is in.
+    pub groups: Vec<String>,
+}
+
+pub trait ExternalAuthenticator: Send + Sync {
+    /// Check `username`/`password` against the external directory.
+    ///
+    /// Returns `Ok(None)` when the directory rejects the credentials; errors
+    /// are reserved for the directory being unreachable or misbehaving.
+    fn authenticate<'a>(
+        &'a self,
+        username: &'a str,
+        password: &'a str,
+    ) -> BoxFuture<'a, AppResult<Option<ExternalIdentity>>>;
+}
+
+/// Which local role an externally authenticated user is provisioned with.
+///
+/// Rules are checked in order and the first group match wins; group names
+/// compare case-insensitively, as directory DNs do.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupRoleMapping {
+    rules: Vec<(String, Role)>,
+    default_role: Option<Role>,
+}
+
+impl GroupRoleMapping {
+    #[must_use]
+    pub const fn new(rules: Vec<(String, Role)>, default_role: Option<Role>) -> Self {
+        Self {
+            rules,
+            default_role,
+        }
+    }
+
+    /// Role for a user in `groups`, or `None` if no rule matches and there
+    /// is no default.
+    #[must_use]
+    pub fn role_for(&self, groups: &[String]) -> Option<Role> {
+        self.rules
+            .iter()
+            .find(|(group, _)| {
+                groups
+                    .iter()
+                    .any(|member_of| member_of.eq_ignore_ascii_case(group))
+            })
+            .map(|(_, role)| *role)
+            .or(self.default_role)
+    }
+}
@@ -0,0 +1,7 @@
+use std::net::IpAddr;
+
+/// Resolves client addresses to the autonomous system announcing them.
+pub trait AsnLookup: Send + Sync {
+    /// `None` when the address is not in the database.
+    fn asn(&self, ip: IpAddr) -> Option<u32>;
+}
@@ -0,0 +1,15 @@
+// src/application/ports/integrity.rs
+use crate::application::{AppResult, IntegrityAnomalyDto};
+use crate::async_support::BoxFuture;
+
+/// Scans storage for inconsistencies between tables that foreign keys
+/// cannot express.
+pub trait IntegrityChecker: Send + Sync {
+    /// Every anomaly found, grouped by kind.
+    fn scan(&self) -> BoxFuture<'_, AppResult<Vec<IntegrityAnomalyDto>>>;
+
+    /// Fix every anomaly whose kind is
+    /// [`repairable`](crate::application::AnomalyKind::repairable), in one
+    /// transaction. Returns the number of rows changed.
+    fn repair(&self) -> BoxFuture<'_, AppResult<u64>>;
+}
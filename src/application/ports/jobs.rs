@@ -0,0 +1,80 @@
+// src/application/ports/jobs.rs
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::application::{
+    AppResult,
+    dto::jobs::{JobRunDto, JobRunOutcome, JobStatusDto, JobTrigger},
+};
+use crate::async_support::BoxFuture;
+
+/// Runs long-lived work outside the request that scheduled it.
+///
+/// Jobs report their own progress (for example through a status table the
+/// scheduling service owns); the queue only guarantees they are started.
+pub trait JobQueue: Send + Sync {
+    /// Schedule `job`; `name` identifies the kind of work in logs.
+    fn enqueue(&self, name: &'static str, job: BoxFuture<'static, ()>);
+}
+
+/// A held lock; pass it back to [`LockManager::release`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockLease {
+    pub name: String,
+    /// Identifies this holder, so a lease that outlived its ttl cannot
+    /// release the lock someone else took since.
+    pub token: String,
+}
+
+/// Named locks that expire, so a crashed holder cannot keep one forever.
+pub trait LockManager: Send + Sync {
+    /// Take `name` for at most `ttl`. `None` if it is already held.
+    fn try_acquire<'a>(
+        &'a self,
+        name: &'a str,
+        ttl: Duration,
+    ) -> BoxFuture<'a, AppResult<Option<LockLease>>>;
+
+    /// Give up `lease`; a no-op once it has expired.
+    fn release<'a>(&'a self, lease: &'a LockLease) -> BoxFuture<'a, AppResult<()>>;
+}
+
+/// History of job runs.
+pub trait JobRunStore: Send + Sync {
+    /// Record a run that has just started; returns its id.
+    fn start<'a>(
+        &'a self,
+        job: &'a str,
+        trigger: JobTrigger,
+        triggered_by: Option<i64>,
+        started_at: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<i64>>;
+
+    fn finish<'a>(
+        &'a self,
+        id: i64,
+        finished_at: DateTime<Utc>,
+        outcome: JobRunOutcome,
+        error: Option<&'a str>,
+    ) -> BoxFuture<'a, AppResult<()>>;
+
+    /// The latest `limit` runs of `job`, newest first.
+    fn recent<'a>(&'a self, job: &'a str, limit: u32) -> BoxFuture<'a, AppResult<Vec<JobRunDto>>>;
+}
+
+/// Inspects and starts the scheduled jobs.
+pub trait JobControl: Send + Sync {
+    fn job_statuses(&self) -> Vec<JobStatusDto>;
+
+    /// Start `job` now, outside its schedule, as `requested_by`. Returns the
+    /// run once it has started; it finishes in the background.
+    fn trigger<'a>(
+        &'a self,
+        job: &'a str,
+        requested_by: i64,
+    ) -> BoxFuture<'a, AppResult<JobRunDto>>;
+
+    /// The latest `limit` runs of `job`, newest first.
+    fn runs<'a>(&'a self, job: &'a str, limit: u32) -> BoxFuture<'a, AppResult<Vec<JobRunDto>>>;
+}
@@ -1,10 +1,31 @@
 // src/application/ports/mod.rs
+pub mod archives;
 pub mod authorization_code;
+pub mod blob_storage;
+pub mod command_journal;
+pub mod documents;
+pub mod domain_events;
+pub mod download_links;
+pub mod exports;
+pub mod external_auth;
+pub mod geoip;
+pub mod integrity;
+pub mod jobs;
+pub mod notification;
+pub mod oidc_login;
+pub mod rate_limit;
 pub mod refresh_token;
 pub mod security;
+pub mod security_events;
 pub mod session_revocation;
+pub mod static_site;
+pub mod syndication;
 pub mod time;
+pub mod unit_of_work;
+pub mod user_tokens;
 pub mod util;
+pub mod views;
+pub mod webhooks;
 
 // Type aliases to make port injection sites more descriptive and reduce `dyn` noise
 pub type PasswordHasherPort = dyn security::PasswordHasher;
@@ -13,3 +34,26 @@ pub type RefreshTokenCodecPort = dyn refresh_token::Codec;
 pub type ClockPort = dyn time::Clock;
 pub type SlugGeneratorPort = dyn util::SlugGenerator;
 pub type CodeStorePort = dyn authorization_code::CodeStore;
+pub type JobQueuePort = dyn jobs::JobQueue;
+pub type LockManagerPort = dyn jobs::LockManager;
+pub type ExternalAuthenticatorPort = dyn external_auth::ExternalAuthenticator;
+pub type OidcRelyingPartyPort = dyn oidc_login::OidcRelyingParty;
+pub type CommandJournalPort = dyn command_journal::CommandJournal;
+pub type DocumentConverterPort = dyn documents::DocumentConverter;
+pub type ArticleRendererPort = dyn exports::ArticleRenderer;
+pub type ArchiverPort = dyn archives::Archiver;
+pub type AsnLookupPort = dyn geoip::AsnLookup;
+pub type DownloadLinkSignerPort = dyn download_links::DownloadLinkSigner;
+pub type EmailSenderPort = dyn notification::EmailSender;
+pub type UserTokenStorePort = dyn user_tokens::UserTokenStore;
+pub type IntegrityCheckerPort = dyn integrity::IntegrityChecker;
+pub type RateLimiterPort = dyn rate_limit::RateLimiter;
+pub type EventHandlerPort = dyn domain_events::EventHandler;
+pub type EventPublisherPort = dyn domain_events::EventPublisher;
+pub type SyndicatorPort = dyn syndication::Syndicator;
+pub type SyndicationOptOutStorePort = dyn syndication::SyndicationOptOutStore;
+pub type BlobStoragePort = dyn blob_storage::BlobStorage;
+pub type SiteWriterPort = dyn static_site::SiteWriter;
+pub type ViewCounterPort = dyn views::ViewCounter;
+pub type UnitOfWorkPort = dyn unit_of_work::UnitOfWork;
+pub type WebhookSenderPort = dyn webhooks::WebhookSender;
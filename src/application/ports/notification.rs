@@ -0,0 +1,17 @@
+// src/application/ports/notification.rs
+//! Mail sent to users, such as password reset and verification messages.
+
+use crate::application::AppResult;
+use crate::async_support::BoxFuture;
+
+/// A plain-text message to a single recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+pub trait EmailSender: Send + Sync {
+    fn send(&self, message: EmailMessage) -> BoxFuture<'_, AppResult<()>>;
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use crate::application::AppResult;
+use crate::async_support::BoxFuture;
+use crate::domain::Role;
+
+/// Per-login values sent to the upstream authorization endpoint.
+#[derive(Debug, Clone, Copy)]
+pub struct AuthorizationRequest<'a> {
+    pub state: &'a str,
+    pub nonce: &'a str,
+    /// PKCE `S256` challenge for the verifier presented at code exchange.
+    pub code_challenge: &'a str,
+}
+
+/// Claims taken from a validated upstream `id_token`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpstreamIdentity {
+    /// The provider's stable, never-reassigned user identifier (`sub`).
+    pub subject: String,
+    pub preferred_username: Option<String>,
+    pub email: Option<String>,
+}
+
+/// An upstream OIDC provider this service logs users in with.
+pub trait OidcRelyingParty: Send + Sync {
+    /// URL to send the browser to for login at the provider.
+    fn authorization_url<'a>(
+        &'a self,
+        request: AuthorizationRequest<'a>,
+    ) -> BoxFuture<'a, AppResult<String>>;
+
+    /// Redeem an authorization code and validate the returned `id_token`
+    /// (signature, issuer, audience, expiry and `nonce`).
+    fn exchange_code<'a>(
+        &'a self,
+        code: &'a str,
+        code_verifier: &'a str,
+        nonce: &'a str,
+    ) -> BoxFuture<'a, AppResult<UpstreamIdentity>>;
+}
+
+/// A configured upstream provider, addressed by `name` in login URLs.
+#[derive(Clone)]
+pub struct UpstreamProvider {
+    pub name: String,
+    pub client: Arc<dyn OidcRelyingParty>,
+    /// Role for users provisioned on their first login with this provider.
+    pub default_role: Role,
+}
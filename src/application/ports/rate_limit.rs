@@ -0,0 +1,84 @@
+// src/application/ports/rate_limit.rs
+//! Windowed counters and temporary locks behind rate limiting and
+//! brute-force protection.
+//!
+//! Keys are opaque strings chosen by the caller (e.g. `user:<name>` or
+//! `ip:<address>` for failed logins), so one store serves every counter.
+
+use crate::application::AppResult;
+use crate::async_support::BoxFuture;
+use chrono::{DateTime, Duration, Utc};
+
+/// When failed logins lock an account or shut out a client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoginThrottlePolicy {
+    /// Failures for one username within `window` that lock the account.
+    pub max_failures_per_user: u32,
+    /// Failures from one client address within `window`, whichever
+    /// usernames it tried, after which the client is refused.
+    pub max_failures_per_client: u32,
+    pub window: Duration,
+    /// How long a lock lasts.
+    pub lockout: Duration,
+}
+
+impl Default for LoginThrottlePolicy {
+    fn default() -> Self {
+        Self {
+            max_failures_per_user: 5,
+            max_failures_per_client: 20,
+            window: Duration::minutes(15),
+            lockout: Duration::minutes(15),
+        }
+    }
+}
+
+/// Seconds until `until`, rounded up and at least one; the `Retry-After`
+/// for a lock ending then.
+#[must_use]
+pub fn retry_after_secs(until: DateTime<Utc>, now: DateTime<Utc>) -> u64 {
+    let millis = (until - now).num_milliseconds().max(1);
+    u64::try_from(millis).unwrap_or(1).div_ceil(1000)
+}
+
+pub trait RateLimiter: Send + Sync {
+    /// When the lock on `key` ends, or `None` if it is not locked at `now`.
+    fn locked_until<'a>(
+        &'a self,
+        key: &'a str,
+        now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<Option<DateTime<Utc>>>>;
+
+    /// Count a hit for `key` and return its hits so far. A count lapses
+    /// `window` after the hit that started it.
+    fn increment<'a>(
+        &'a self,
+        key: &'a str,
+        window: Duration,
+        now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<u32>>;
+
+    /// Lock `key` until `until` and reset its count.
+    fn lock<'a>(
+        &'a self,
+        key: &'a str,
+        until: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<()>>;
+
+    /// Reset the count of `key`, e.g. after a successful login.
+    fn reset<'a>(&'a self, key: &'a str) -> BoxFuture<'a, AppResult<()>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_after_rounds_up_to_whole_seconds() {
+        let now = Utc::now();
+        assert_eq!(retry_after_secs(now + Duration::seconds(90), now), 90);
+        assert_eq!(retry_after_secs(now + Duration::milliseconds(1500), now), 2);
+        assert_eq!(retry_after_secs(now - Duration::seconds(3), now), 1);
+    }
+}
@@ -1,5 +1,5 @@
 // src/application/ports/security.rs
-use crate::application::{AppResult, AuthTokenDto, AuthenticatedUser, TokenSubject};
+use crate::application::{AppResult, AuthTokenDto, AuthenticatedUser, IdTokenClaims, TokenSubject};
 use crate::async_support::BoxFuture;
 
 pub trait PasswordHasher: Send + Sync {
@@ -9,6 +9,18 @@ pub trait PasswordHasher: Send + Sync {
         password: &'a str,
         expected_hash: &'a str,
     ) -> BoxFuture<'a, AppResult<()>>;
+
+    /// Whether `hash` was made with other parameters than new hashes get,
+    /// so it should be replaced the next time the password is known.
+    fn needs_rehash(&self, _hash: &str) -> bool {
+        false
+    }
+}
+
+/// Looks passwords up among those exposed in known data breaches.
+pub trait PasswordBreachChecker: Send + Sync {
+    /// How many times `password` appears in known breaches; zero if never.
+    fn breach_count<'a>(&'a self, password: &'a str) -> BoxFuture<'a, AppResult<u64>>;
 }
 
 pub trait TokenManager: Send + Sync {
@@ -19,4 +31,16 @@ pub trait TokenManager: Send + Sync {
     /// This is used to verify tokens issued by this `TokenManager` and powers
     /// the public keys endpoint.
     fn public_jwk(&self) -> BoxFuture<'_, AppResult<serde_json::Value>>;
+
+    /// Sign an OIDC `id_token` with a key listed by `public_jwk`.
+    ///
+    /// Returns `None` from managers that cannot sign JWTs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if signing fails.
+    fn issue_id_token(&self, claims: &IdTokenClaims) -> AppResult<Option<String>> {
+        let _ = claims;
+        Ok(None)
+    }
 }
@@ -0,0 +1,44 @@
+use crate::application::AppResult;
+use crate::async_support::BoxFuture;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Audit log action recorded for refresh-token reuse incidents.
+pub const TOKEN_REUSE_AUDIT_ACTION: &str = "auth.refresh_token_reuse";
+
+/// Network details of the client behind a request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ClientInfo {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl ClientInfo {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.ip_address.is_none() && self.user_agent.is_none()
+    }
+}
+
+/// A rotated refresh token was presented again for a session.
+///
+/// `legitimate` is the client that last used the session successfully;
+/// `suspect` is the client that replayed the stale token.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TokenReuseIncident {
+    pub user_id: i64,
+    pub session_id: String,
+    pub family_id: Option<String>,
+    pub legitimate: ClientInfo,
+    pub suspect: ClientInfo,
+    pub detected_at: DateTime<Utc>,
+}
+
+pub trait SecurityEventSink: Send + Sync {
+    /// Record a refresh-token reuse incident.
+    fn token_reuse_detected<'a>(
+        &'a self,
+        incident: &'a TokenReuseIncident,
+    ) -> BoxFuture<'a, AppResult<()>>;
+}
@@ -1,10 +1,15 @@
 use crate::application::AppResult;
+use crate::application::ports::security_events::ClientInfo;
 use crate::async_support::BoxFuture;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::Duration;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::net::IpAddr;
 use std::sync::Arc;
 
 /// Information about a session stored in the backing store.
-/// `created_at_unix` is seconds since epoch (UTC).
+/// Timestamps are seconds since epoch (UTC); `0` means unknown or unset.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionInfo {
     pub user_id: i64,
@@ -13,6 +18,131 @@ pub struct SessionInfo {
     pub ip_address: Option<String>,
     pub created_at_unix: i64,
     pub revoked: bool,
+    /// When the session was last logged into or refreshed.
+    #[serde(default)]
+    pub last_active_unix: i64,
+    /// Absolute deadline after which the session can no longer be refreshed.
+    #[serde(default)]
+    pub expires_at_unix: i64,
+    /// Fingerprint of the client that logged in, when refresh tokens were
+    /// bound to it; see [`RefreshBinding`].
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+}
+
+impl SessionInfo {
+    /// Whether `policy` no longer lets this session be refreshed at `now_unix`.
+    ///
+    /// Sessions stamped before lifetimes were tracked fall back to their
+    /// creation time for idle checks and have no absolute deadline.
+    #[must_use]
+    pub fn is_expired(&self, policy: SessionPolicy, now_unix: i64) -> bool {
+        if self.expires_at_unix > 0 && now_unix >= self.expires_at_unix {
+            return true;
+        }
+
+        let last_active = if self.last_active_unix > 0 {
+            self.last_active_unix
+        } else {
+            self.created_at_unix
+        };
+        policy
+            .idle_timeout
+            .is_some_and(|idle| last_active > 0 && now_unix - last_active >= idle.num_seconds())
+    }
+}
+
+/// How long a login session may be kept alive by refreshing it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionPolicy {
+    /// Refreshing stops working this long after login, however active the
+    /// session is. `None` lets sessions be refreshed indefinitely.
+    pub max_age: Option<Duration>,
+    /// A session that goes this long without a refresh expires. `None`
+    /// disables idle expiry.
+    pub idle_timeout: Option<Duration>,
+    /// Whether refreshing is tied to the client that logged in.
+    pub binding: RefreshBinding,
+}
+
+impl SessionPolicy {
+    /// The absolute deadline for a session started at `created_at_unix`, or
+    /// `0` when sessions have no maximum age.
+    #[must_use]
+    pub fn expires_at_unix(&self, created_at_unix: i64) -> i64 {
+        self.max_age
+            .map_or(0, |max_age| created_at_unix + max_age.num_seconds())
+    }
+}
+
+/// What a refresh from a client other than the one that logged in does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RefreshBindingMode {
+    /// Sessions are not fingerprinted.
+    #[default]
+    Off,
+    /// The mismatch is audited and the refresh goes ahead.
+    Audit,
+    /// The mismatch is audited and the refresh refused.
+    Enforce,
+}
+
+/// Which details of the logging-in client a session's refresh tokens are
+/// bound to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RefreshBinding {
+    pub mode: RefreshBindingMode,
+    /// Bind to the `User-Agent` header.
+    pub user_agent: bool,
+    /// Bind to the client's /24 (IPv4) or /64 (IPv6) network, so address
+    /// changes within one network are tolerated.
+    pub ip_subnet: bool,
+}
+
+impl RefreshBinding {
+    /// Hash of the bound details of `client`, or `None` when binding is off
+    /// or binds to nothing. Only the hash is stored with the session.
+    #[must_use]
+    pub fn fingerprint(&self, client: &ClientInfo) -> Option<String> {
+        if self.mode == RefreshBindingMode::Off || !(self.user_agent || self.ip_subnet) {
+            return None;
+        }
+
+        let mut hasher = Sha256::new();
+        if self.user_agent {
+            hasher.update(b"ua:");
+            hasher.update(client.user_agent.as_deref().unwrap_or_default());
+            hasher.update(b"\n");
+        }
+        if self.ip_subnet {
+            hasher.update(b"net:");
+            hasher.update(
+                client
+                    .ip_address
+                    .as_deref()
+                    .map(subnet_of)
+                    .unwrap_or_default(),
+            );
+            hasher.update(b"\n");
+        }
+        Some(URL_SAFE_NO_PAD.encode(hasher.finalize()))
+    }
+}
+
+/// The /24 or /64 network `ip` belongs to; unparsable addresses stand for
+/// themselves.
+fn subnet_of(ip: &str) -> String {
+    match ip.trim().parse::<IpAddr>() {
+        Ok(IpAddr::V4(v4)) => {
+            let [a, b, c, _] = v4.octets();
+            format!("{a}.{b}.{c}.0/24")
+        }
+        Ok(IpAddr::V6(v6)) => {
+            let [a, b, c, d, ..] = v6.segments();
+            format!("{a:x}:{b:x}:{c:x}:{d:x}::/64")
+        }
+        Err(_) => ip.to_string(),
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -22,6 +152,41 @@ pub struct RefreshTokenRecord {
     pub token_version: u32,
 }
 
+/// Outcome of a session metadata cleanup pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CleanupStats {
+    /// Sessions whose metadata was older than the cutoff and got removed.
+    pub expired_sessions: u64,
+    /// Per-user session set members that no longer had metadata.
+    pub stale_members: u64,
+    /// Store entries (keys, for Redis) deleted in total.
+    pub reclaimed_keys: u64,
+}
+
+impl CleanupStats {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.expired_sessions == 0 && self.stale_members == 0 && self.reclaimed_keys == 0
+    }
+}
+
+/// Which implementation backs the session store (reported by the status endpoint).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    InMemory,
+    Redis,
+}
+
+impl Backend {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::InMemory => "in_memory",
+            Self::Redis => "redis",
+        }
+    }
+}
+
 pub trait Revocation: Send + Sync {
     /// Return true if the given session id has been revoked.
     fn is_revoked<'a>(&'a self, session_id: &'a str) -> BoxFuture<'a, AppResult<bool>>;
@@ -29,7 +194,7 @@ pub trait Revocation: Send + Sync {
     /// Revoke the given session id (e.g. on logout).
     fn revoke<'a>(&'a self, session_id: &'a str) -> BoxFuture<'a, AppResult<()>>;
 
-    /// Revoke all sessions for a given user (used when refresh reuse is detected).
+    /// Revoke all sessions for a given user.
     fn revoke_sessions_for_user(&self, user_id: i64) -> BoxFuture<'_, AppResult<()>>;
 }
 
@@ -112,6 +277,8 @@ pub trait SessionMetadataStore: Send + Sync {
     ) -> BoxFuture<'_, AppResult<Vec<SessionInfo>>>;
 
     /// Store or update session metadata. `created_at_unix` is seconds since epoch UTC.
+    ///
+    /// Lifetime fields recorded by [`Self::set_session_lifetime`] are kept.
     fn set_session_metadata<'a>(
         &'a self,
         user_id: i64,
@@ -121,6 +288,27 @@ pub trait SessionMetadataStore: Send + Sync {
         created_at_unix: i64,
     ) -> BoxFuture<'a, AppResult<()>>;
 
+    /// Record when a session was last active and when it expires for good.
+    ///
+    /// Leaves the rest of the session metadata untouched; does nothing for
+    /// sessions without metadata.
+    fn set_session_lifetime<'a>(
+        &'a self,
+        session_id: &'a str,
+        last_active_unix: i64,
+        expires_at_unix: i64,
+    ) -> BoxFuture<'a, AppResult<()>>;
+
+    /// Record the fingerprint of the client that started a session.
+    ///
+    /// Leaves the rest of the session metadata untouched; does nothing for
+    /// sessions without metadata.
+    fn set_session_fingerprint<'a>(
+        &'a self,
+        session_id: &'a str,
+        fingerprint: &'a str,
+    ) -> BoxFuture<'a, AppResult<()>>;
+
     /// Get session metadata for a given session id.
     fn get_session_metadata<'a>(
         &'a self,
@@ -129,6 +317,17 @@ pub trait SessionMetadataStore: Send + Sync {
 
     /// Delete session metadata (e.g. when a session is removed from the user's list).
     fn delete_session_metadata<'a>(&'a self, session_id: &'a str) -> BoxFuture<'a, AppResult<()>>;
+
+    /// Remove every trace of sessions created before `cutoff_unix` (metadata,
+    /// refresh state, family links and revocation markers) and prune
+    /// per-user session set members that have no metadata left.
+    ///
+    /// Sessions with an unknown creation time (`created_at_unix <= 0`) are
+    /// kept.
+    fn purge_sessions_created_before(
+        &self,
+        cutoff_unix: i64,
+    ) -> BoxFuture<'_, AppResult<CleanupStats>>;
 }
 
 pub trait OpaqueRefreshTokenStore: Send + Sync {
@@ -156,12 +355,33 @@ pub trait OpaqueRefreshTokenStore: Send + Sync {
     ) -> BoxFuture<'a, AppResult<()>>;
 }
 
+pub trait RefreshTokenFamilyStore: Send + Sync {
+    /// Record that a session belongs to a refresh-token family (the lineage
+    /// started by the login that created it).
+    fn add_session_to_family<'a>(
+        &'a self,
+        family_id: &'a str,
+        session_id: &'a str,
+    ) -> BoxFuture<'a, AppResult<()>>;
+
+    /// Return the family id a session belongs to, if one was recorded.
+    fn family_for_session<'a>(
+        &'a self,
+        session_id: &'a str,
+    ) -> BoxFuture<'a, AppResult<Option<String>>>;
+
+    /// Revoke every session in a family and drop their refresh token handles
+    /// (used when refresh reuse is detected).
+    fn revoke_family<'a>(&'a self, family_id: &'a str) -> BoxFuture<'a, AppResult<()>>;
+}
+
 pub trait Store:
     Revocation
     + TokenVersionStore
     + RefreshNonceStore
     + SessionMetadataStore
     + OpaqueRefreshTokenStore
+    + RefreshTokenFamilyStore
     + Send
     + Sync
 {
@@ -173,6 +393,7 @@ impl<T> Store for T where
         + RefreshNonceStore
         + SessionMetadataStore
         + OpaqueRefreshTokenStore
+        + RefreshTokenFamilyStore
         + Send
         + Sync
 {
@@ -186,6 +407,7 @@ pub struct Ports {
     pub refresh_nonces: Arc<dyn RefreshNonceStore>,
     pub session_metadata: Arc<dyn SessionMetadataStore>,
     pub opaque_refresh_tokens: Arc<dyn OpaqueRefreshTokenStore>,
+    pub refresh_families: Arc<dyn RefreshTokenFamilyStore>,
 }
 
 impl Ports {
@@ -195,7 +417,8 @@ impl Ports {
             token_versions: store.clone(),
             refresh_nonces: store.clone(),
             session_metadata: store.clone(),
-            opaque_refresh_tokens: store,
+            opaque_refresh_tokens: store.clone(),
+            refresh_families: store,
         }
     }
 }
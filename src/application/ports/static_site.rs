@@ -0,0 +1,16 @@
+// src/application/ports/static_site.rs
+//! Where a static export of the site is written, such as a directory served
+//! by a plain web server.
+
+use crate::application::AppResult;
+use crate::async_support::BoxFuture;
+
+/// Receives the files of a static export.
+pub trait SiteWriter: Send + Sync {
+    /// Write `contents` to `path`, a `/`-separated path relative to the site
+    /// root, replacing anything already there.
+    fn write<'a>(&'a self, path: &'a str, contents: Vec<u8>) -> BoxFuture<'a, AppResult<()>>;
+
+    /// Where files end up, for reports and logs.
+    fn location(&self) -> String;
+}
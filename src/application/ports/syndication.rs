@@ -0,0 +1,93 @@
+// src/application/ports/syndication.rs
+//! Announcing published articles on external platforms such as Mastodon,
+//! Bluesky or a Slack channel.
+
+use std::sync::Arc;
+
+use crate::application::AppResult;
+use crate::async_support::BoxFuture;
+use crate::domain::ArticleId;
+
+/// Template used by targets that do not configure their own.
+pub const DEFAULT_TEMPLATE: &str = "{title} {url}";
+
+/// What is announced about a published article.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Announcement {
+    pub article_id: i64,
+    pub title: String,
+    pub slug: String,
+    /// Public link to the article.
+    pub url: String,
+    pub tags: Vec<String>,
+}
+
+impl Announcement {
+    /// Fill in `template`: `{title}`, `{url}` and `{slug}` are replaced as
+    /// is, `{tags}` by the tags as space-separated hashtags, dropping characters
+    /// hashtags cannot hold.
+    #[must_use]
+    pub fn render(&self, template: &str) -> String {
+        let hashtags = self
+            .tags
+            .iter()
+            .map(|tag| {
+                let word: String = tag
+                    .chars()
+                    .filter(|c| c.is_alphanumeric() || *c == '_')
+                    .collect();
+                format!("#{word}")
+            })
+            .filter(|tag| tag.len() > 1)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut text = String::with_capacity(template.len() + self.url.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            text.push_str(&rest[..start]);
+            let tail = &rest[start..];
+            let (value, len) = [
+                ("{title}", self.title.as_str()),
+                ("{url}", self.url.as_str()),
+                ("{slug}", self.slug.as_str()),
+                ("{tags}", hashtags.as_str()),
+            ]
+            .into_iter()
+            .find(|(placeholder, _)| tail.starts_with(placeholder))
+            .map_or(("{", 1), |(placeholder, value)| (value, placeholder.len()));
+            text.push_str(value);
+            rest = &tail[len..];
+        }
+        text.push_str(rest);
+        text
+    }
+}
+
+/// Posts announcements to one external platform.
+pub trait Syndicator: Send + Sync {
+    /// Post `text`, the rendered announcement. Adapters that can link to
+    /// the article richly use `announcement.url`.
+    fn post<'a>(
+        &'a self,
+        text: &'a str,
+        announcement: &'a Announcement,
+    ) -> BoxFuture<'a, AppResult<()>>;
+}
+
+/// A configured place announcements go to.
+#[derive(Clone)]
+pub struct SyndicationTarget {
+    /// Lowercase name from `SYNDICATION_TARGETS`, used in logs.
+    pub name: String,
+    /// Text posted, see [`Announcement::render`].
+    pub template: String,
+    pub syndicator: Arc<dyn Syndicator>,
+}
+
+/// Articles their authors asked not to announce.
+pub trait SyndicationOptOutStore: Send + Sync {
+    fn is_opted_out(&self, article_id: ArticleId) -> BoxFuture<'_, AppResult<bool>>;
+
+    fn set_opted_out(&self, article_id: ArticleId, opted_out: bool)
+    -> BoxFuture<'_, AppResult<()>>;
+}
@@ -1,6 +1,17 @@
 // src/application/ports/time.rs
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 
 pub trait Clock: Send + Sync {
     fn now(&self) -> DateTime<Utc>;
 }
+
+/// Moves a simulated clock, so time-dependent behaviour can be exercised in
+/// tests and staging without waiting. Only wired when simulated time is
+/// enabled.
+pub trait ClockControl: Send + Sync {
+    /// How far the clock runs ahead of real time.
+    fn offset(&self) -> Duration;
+
+    /// Move the clock forward by `by` and return the new offset.
+    fn advance(&self, by: Duration) -> Duration;
+}
@@ -0,0 +1,50 @@
+// src/application/ports/unit_of_work.rs
+//! Writes that span repositories and must land together, such as an article
+//! and the revision recording it.
+
+use crate::application::AppResult;
+use crate::application::ports::user_tokens::TokenPurpose;
+use crate::async_support::BoxFuture;
+use crate::domain::{Article, ArticleUpdate, NewArticle, User, UserId, UserUpdate};
+use chrono::{DateTime, Utc};
+
+/// Starts [`Work`] against the same storage the repositories use.
+pub trait UnitOfWork: Send + Sync {
+    fn begin(&self) -> BoxFuture<'_, AppResult<Box<dyn Work>>>;
+}
+
+/// Writes made through one unit of work. Other readers see none of them
+/// until [`commit`](Self::commit); dropping the work uncommitted, e.g. after
+/// a failed step, discards them all.
+///
+/// Each method behaves like the repository method of the same name. Reads
+/// the writes depend on belong here too: the work may hold the only
+/// connection to the database.
+pub trait Work: Send {
+    fn insert_article(&mut self, article: NewArticle) -> BoxFuture<'_, AppResult<Article>>;
+
+    fn update_article(&mut self, update: ArticleUpdate) -> BoxFuture<'_, AppResult<Article>>;
+
+    /// Record `article` as its next revision.
+    fn append_revision<'a>(
+        &'a mut self,
+        article: &'a Article,
+        edited_by: Option<UserId>,
+    ) -> BoxFuture<'a, AppResult<()>>;
+
+    fn find_user(&mut self, id: UserId) -> BoxFuture<'_, AppResult<Option<User>>>;
+
+    fn update_user(&mut self, update: UserUpdate) -> BoxFuture<'_, AppResult<User>>;
+
+    /// Spend a mailed token, as
+    /// [`UserTokenStore::consume`](super::user_tokens::UserTokenStore::consume)
+    /// does.
+    fn consume_user_token<'a>(
+        &'a mut self,
+        token_hash: &'a str,
+        purpose: TokenPurpose,
+        now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<Option<UserId>>>;
+
+    fn commit(self: Box<Self>) -> BoxFuture<'static, AppResult<()>>;
+}
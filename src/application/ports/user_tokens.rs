@@ -0,0 +1,49 @@
+// src/application/ports/user_tokens.rs
+//! Single-use, expiring tokens mailed to users. Stores only ever see a hash
+//! of the token; the token itself exists in the mail alone.
+
+use crate::application::AppResult;
+use crate::async_support::BoxFuture;
+use crate::domain::UserId;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenPurpose {
+    PasswordReset,
+    EmailVerification,
+}
+
+impl TokenPurpose {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::PasswordReset => "password_reset",
+            Self::EmailVerification => "email_verification",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserToken {
+    /// Base64url SHA-256 of the mailed token.
+    pub token_hash: String,
+    pub user_id: UserId,
+    pub purpose: TokenPurpose,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+pub trait UserTokenStore: Send + Sync {
+    /// Store `token`, replacing any unused token the user has for the same
+    /// purpose.
+    fn create(&self, token: UserToken) -> BoxFuture<'_, AppResult<()>>;
+
+    /// Remove the token with `token_hash` and `purpose` and return its user,
+    /// or `None` if there is no such token or it expired before `now`.
+    fn consume<'a>(
+        &'a self,
+        token_hash: &'a str,
+        purpose: TokenPurpose,
+        now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<Option<UserId>>>;
+}
@@ -0,0 +1,22 @@
+// src/application/ports/views.rs
+//! Buffered read counts for published articles.
+//!
+//! Reads only bump a counter here; a periodic job drains it into the
+//! article rows, so a hot article never costs a database write per view.
+
+use crate::application::AppResult;
+use crate::async_support::BoxFuture;
+use crate::domain::ArticleId;
+
+pub trait ViewCounter: Send + Sync {
+    /// Add `views` to the pending count of `article_id`.
+    fn add(&self, article_id: ArticleId, views: u64) -> BoxFuture<'_, AppResult<()>>;
+
+    /// Count one read of `article_id`.
+    fn record_view(&self, article_id: ArticleId) -> BoxFuture<'_, AppResult<()>> {
+        self.add(article_id, 1)
+    }
+
+    /// Take every pending count, leaving the counter empty.
+    fn drain(&self) -> BoxFuture<'_, AppResult<Vec<(ArticleId, u64)>>>;
+}
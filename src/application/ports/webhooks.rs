@@ -0,0 +1,34 @@
+// src/application/ports/webhooks.rs
+//! Posting signed event payloads to webhook endpoints.
+
+use chrono::{DateTime, Utc};
+
+use crate::application::AppResult;
+use crate::async_support::BoxFuture;
+use crate::domain::WebhookEvent;
+
+/// One signed POST of an event to a webhook.
+#[derive(Debug, Clone, Copy)]
+pub struct WebhookRequest<'a> {
+    pub url: &'a str,
+    /// Key the body and timestamp are signed with.
+    pub secret: &'a str,
+    pub event: WebhookEvent,
+    /// Shared by every try of one event, so receivers can drop repeats.
+    pub delivery_id: &'a str,
+    /// When this try was made; part of the signed data.
+    pub timestamp: DateTime<Utc>,
+    /// JSON payload.
+    pub body: &'a [u8],
+}
+
+/// Sends webhook requests.
+pub trait WebhookSender: Send + Sync {
+    /// POST `request` and return the response status, whatever it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when no response arrived, e.g. on connection
+    /// failures or timeouts.
+    fn send<'a>(&'a self, request: &'a WebhookRequest<'a>) -> BoxFuture<'a, AppResult<u16>>;
+}
@@ -0,0 +1,46 @@
+use super::ArticleQueryService;
+use crate::{
+    application::{ArticleDto, AuthenticatedUser, BatchResult, error::AppResult, queries::batch},
+    domain::ArticleId,
+};
+
+pub struct BatchGetArticlesQuery {
+    pub ids: Vec<i64>,
+}
+
+impl ArticleQueryService {
+    /// Load several articles by id in one round trip.
+    ///
+    /// Drafts are only returned to callers holding `articles:view:drafts`,
+    /// and private articles to their author and holders of
+    /// `articles:view:any`; others are reported as missing, like unknown ids.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if too many ids are requested or the repository lookup
+    /// fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn batch_get_articles(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        query: BatchGetArticlesQuery,
+    ) -> AppResult<BatchResult<ArticleDto>> {
+        let requested = batch::normalize_ids(&query.ids)?;
+        let include_drafts =
+            actor.is_some_and(|actor| actor.has_capability("articles", "view:drafts"));
+
+        let ids: Vec<ArticleId> = requested
+            .iter()
+            .filter_map(|id| ArticleId::new(*id).ok())
+            .collect();
+        let mut found = self.read_repo.find_by_ids(&ids).await?;
+        if !include_drafts {
+            found.retain(|article| article.published);
+        }
+        Self::retain_viewable(actor, &mut found);
+
+        Ok(batch::assemble(&requested, found, |article| {
+            i64::from(article.id)
+        }))
+    }
+}
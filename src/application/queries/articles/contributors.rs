@@ -0,0 +1,41 @@
+use super::ArticleQueryService;
+use crate::{
+    application::{
+        ArticleContributorDto, AuthenticatedUser,
+        error::{AppError, AppResult},
+    },
+    domain::ArticleId,
+};
+
+pub struct ListContributorsQuery {
+    pub article_id: i64,
+}
+
+impl ArticleQueryService {
+    /// The users who may edit an article besides its author, earliest added
+    /// first; empty when contributors are not enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the article id is invalid, the article is missing
+    /// or not readable by the actor, or repository reads fail.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn list_contributors(
+        &self,
+        actor: &AuthenticatedUser,
+        query: ListContributorsQuery,
+    ) -> AppResult<Vec<ArticleContributorDto>> {
+        let article = self
+            .read_repo
+            .find_by_id(ArticleId::new(query.article_id)?)
+            .await?
+            .ok_or_else(|| AppError::not_found("article not found"))?;
+        self.ensure_actor_can_view(Some(actor), &article).await?;
+
+        let Some(repo) = &self.contributors else {
+            return Ok(Vec::new());
+        };
+        let contributors = repo.list(article.id).await?;
+        Ok(contributors.into_iter().map(Into::into).collect())
+    }
+}
@@ -0,0 +1,19 @@
+use super::ArticleQueryService;
+use crate::application::{CustomFieldDefinitionDto, error::AppResult};
+
+impl ArticleQueryService {
+    /// The custom fields articles may carry, sorted by name; empty when
+    /// custom fields are not enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the definitions cannot be read.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn list_custom_fields(&self) -> AppResult<Vec<CustomFieldDefinitionDto>> {
+        let Some(repo) = &self.custom_fields else {
+            return Ok(Vec::new());
+        };
+        let definitions = repo.list().await?;
+        Ok(definitions.into_iter().map(Into::into).collect())
+    }
+}
@@ -1,10 +1,10 @@
 use super::ArticleQueryService;
 use crate::{
     application::{
-        ArticleDto,
+        ArticleDto, AuthenticatedUser,
         error::{AppError, AppResult},
     },
-    domain::ArticleId,
+    domain::{Article, ArticleId},
 };
 
 pub struct GetArticleByIdQuery {
@@ -18,13 +18,34 @@ impl ArticleQueryService {
     ///
     /// Returns an error if the id is invalid, the article does not exist, or
     /// the repository lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn get_article_by_id(&self, query: GetArticleByIdQuery) -> AppResult<ArticleDto> {
+        Ok(self.find_by_id(query).await?.into())
+    }
+
+    /// Load an article by id, including draft and private visibility checks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the id is invalid, the article is missing, the
+    /// caller cannot view the draft or private article, or the repository
+    /// lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn get_visible_article_by_id(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        query: GetArticleByIdQuery,
+    ) -> AppResult<ArticleDto> {
+        let article = self.find_by_id(query).await?;
+        self.ensure_actor_can_view(actor, &article).await?;
+        Ok(article.into())
+    }
+
+    async fn find_by_id(&self, query: GetArticleByIdQuery) -> AppResult<Article> {
         let id = ArticleId::new(query.id)?;
-        let article = self
-            .read_repo
+        self.read_repo
             .find_by_id(id)
             .await?
-            .ok_or_else(|| AppError::not_found("article not found"))?;
-        Ok(article.into())
+            .ok_or_else(|| AppError::not_found("article not found"))
     }
 }
@@ -4,7 +4,10 @@ use crate::{
         ArticleDto, AuthenticatedUser,
         error::{AppError, AppResult},
     },
-    domain::{Article, ArticleSlug},
+    domain::{
+        Article, ArticleSlug, ArticleVisibility, PrivateScope,
+        article::specifications::{ArticleSpecification, CanViewArticleSpec},
+    },
 };
 
 pub struct GetArticleBySlugQuery {
@@ -12,28 +15,66 @@ pub struct GetArticleBySlugQuery {
 }
 
 impl ArticleQueryService {
-    fn ensure_actor_can_view_unpublished(
+    /// Whether `actor` may read `article` whatever its publication state:
+    /// private articles are only readable by their author and holders of
+    /// `articles:view:any`.
+    pub(super) fn can_view(actor: Option<&AuthenticatedUser>, article: &Article) -> bool {
+        actor.map_or(article.visibility != ArticleVisibility::Private, |actor| {
+            CanViewArticleSpec::new(&actor.capabilities, article, actor.id).is_satisfied()
+        })
+    }
+
+    /// The private articles `actor` may read, for counts that must agree
+    /// with [`can_view`](Self::can_view).
+    pub(super) fn private_scope(actor: Option<&AuthenticatedUser>) -> PrivateScope {
+        match actor {
+            None => PrivateScope::Excluded,
+            Some(actor) if actor.has_capability("articles", "view:any") => PrivateScope::All,
+            Some(actor) => PrivateScope::AuthoredBy(actor.id),
+        }
+    }
+
+    /// Drop the private articles `actor` may not read. Only listings with
+    /// drafts return private articles, so only their pages may come back
+    /// short.
+    pub(super) fn retain_viewable(actor: Option<&AuthenticatedUser>, records: &mut Vec<Article>) {
+        records.retain(|article| Self::can_view(actor, article));
+    }
+
+    /// Fail unless `actor` may read `article`. Contributors may read it
+    /// whatever its publication state and visibility.
+    pub(super) async fn ensure_actor_can_view(
+        &self,
         actor: Option<&AuthenticatedUser>,
         article: &Article,
     ) -> AppResult<()> {
-        if article.published {
+        let readable = Self::can_view(actor, article)
+            && (article.published
+                || actor.is_some_and(|actor| {
+                    actor.has_capability("articles", "view:drafts") || actor.id == article.author_id
+                }));
+        if readable {
             return Ok(());
         }
 
         let actor = actor.ok_or_else(|| AppError::not_found("article not found"))?;
-        if !actor.has_capability("articles", "view:drafts") && actor.id != article.author_id {
+        if !self.contributor_ids(article.id).await?.contains(&actor.id) {
             return Err(AppError::not_found("article not found"));
         }
 
         Ok(())
     }
 
-    /// Load an article by slug, including draft visibility checks.
+    /// Load an article by slug, including draft and private visibility checks.
+    /// Unlisted articles are readable like public ones. Reads of published
+    /// articles are counted when a view counter is configured.
     ///
     /// # Errors
     ///
     /// Returns an error if the slug is invalid, the article is missing, the
-    /// caller cannot view the draft, or the repository lookup fails.
+    /// caller cannot view the draft or private article, or the repository
+    /// lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn get_article_by_slug(
         &self,
         actor: Option<&AuthenticatedUser>,
@@ -41,12 +82,20 @@ impl ArticleQueryService {
     ) -> AppResult<ArticleDto> {
         let slug = ArticleSlug::new(query.slug)?;
         let article = self
-            .read_repo
-            .find_by_slug(&slug)
+            .slug_cache
+            .find_by_slug(self.read_repo.as_ref(), &slug)
             .await?
             .ok_or_else(|| AppError::not_found("article not found"))?;
 
-        Self::ensure_actor_can_view_unpublished(actor, &article)?;
+        self.ensure_actor_can_view(actor, &article).await?;
+
+        if article.published
+            && let Some(views) = &self.views
+            && let Err(err) = views.record_view(article.id).await
+        {
+            // A lost view is not worth failing the read over.
+            tracing::warn!(article_id = i64::from(article.id), error = %err, "failed to record article view");
+        }
 
         Ok(article.into())
     }
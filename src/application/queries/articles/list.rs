@@ -1,50 +1,250 @@
 use super::ArticleQueryService;
 use crate::{
     application::{
-        ArticleDto, AuthenticatedUser, CursorPage,
+        ArticleDto, ArticleSort, AuthenticatedUser, CursorPage, PageDirection,
         error::{AppError, AppResult},
+        queries::numbered,
+    },
+    domain::{
+        Article, ArticleListCursor, ArticlePopularityCursor, Tag,
+        article::repository::ArticleQuery, errors::DomainError,
     },
-    domain::{ArticleListCursor, errors::DomainError},
 };
 
-const DEFAULT_LIMIT: u32 = 20;
-const MAX_LIMIT: u32 = 100;
-
 pub struct ListArticlesQuery {
     pub include_drafts: bool,
     pub limit: u32,
     pub cursor: Option<String>,
+    pub direction: PageDirection,
+    /// Only list articles carrying this tag. Blank values are ignored.
+    pub tag: Option<String>,
+    pub sort: ArticleSort,
+    /// 1-based page number. Lists numbered pages of `limit` articles with a
+    /// `total_count` instead of walking cursors.
+    pub page: Option<u32>,
 }
 
+type ArticleWindow = (
+    Vec<Article>,
+    Option<ArticleListCursor>,
+    Option<ArticleListCursor>,
+);
+
 impl ArticleQueryService {
-    /// List articles with optional draft visibility.
+    /// List articles with optional draft visibility. Only published public
+    /// articles are listed without drafts; with drafts, private articles are
+    /// still left out unless the actor may read them.
     ///
     /// # Errors
     ///
-    /// Returns an error if draft access is not allowed, the cursor is invalid,
-    /// or the repository lookup fails.
+    /// Returns an error if draft access is not allowed, the cursor is invalid
+    /// (or missing when paging backward), the tag is invalid or combined with
+    /// backward paging, backward paging is requested in popularity order, a
+    /// page number is zero or combined with cursors or popularity order, or
+    /// the repository lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn list_articles(
         &self,
         actor: Option<&AuthenticatedUser>,
         query: ListArticlesQuery,
     ) -> AppResult<CursorPage<ArticleDto>> {
         let (include_drafts, limit) =
-            Self::normalize_listing(actor, query.include_drafts, query.limit)?;
+            self.normalize_listing(actor, query.include_drafts, query.limit)?;
+        if let Some(page) = query.page {
+            return self
+                .list_numbered(actor, include_drafts, limit, page, query)
+                .await;
+        }
+        if query.sort == ArticleSort::Popular {
+            return self.list_popular(actor, include_drafts, limit, query).await;
+        }
         let cursor = Self::decode_cursor(query.cursor.as_deref())?;
 
+        if let Some(tag) = Self::parse_tag(query.tag.as_deref())? {
+            if query.direction == PageDirection::Backward {
+                return Err(AppError::validation(
+                    "direction=backward is not supported with a tag filter",
+                ));
+            }
+            return self
+                .list_tagged(actor, include_drafts, limit, cursor, None, tag)
+                .await;
+        }
+
+        let (mut records, next_cursor, prev_cursor) = match query.direction {
+            PageDirection::Forward => self.list_forward(include_drafts, limit, cursor).await?,
+            PageDirection::Backward => {
+                let cursor = cursor
+                    .ok_or_else(|| AppError::validation("direction=backward requires a cursor"))?;
+                self.list_backward(include_drafts, limit, cursor).await?
+            }
+        };
+        let total_estimate = self.read_repo.estimate_total(include_drafts).await?;
+        Self::retain_viewable(actor, &mut records);
+
+        let items = records.into_iter().map(Into::into).collect();
+        Ok(
+            CursorPage::new(items, next_cursor.map(|cursor| cursor.encode()))
+                .with_limit(limit)
+                .with_prev_cursor(prev_cursor.map(|cursor| cursor.encode()))
+                .with_total_estimate(total_estimate),
+        )
+    }
+
+    /// Page `page` of the newest-first listing, counted from 1.
+    async fn list_numbered(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        include_drafts: bool,
+        limit: u32,
+        page: u32,
+        query: ListArticlesQuery,
+    ) -> AppResult<CursorPage<ArticleDto>> {
+        let offset = numbered::page_offset(page, limit)?;
+        if query.cursor.is_some() || query.direction == PageDirection::Backward {
+            return Err(AppError::validation(
+                "page is not supported with cursor or direction",
+            ));
+        }
+        if query.sort == ArticleSort::Popular {
+            return Err(AppError::validation(
+                "page is not supported with sort=popular",
+            ));
+        }
+        let tag = Self::parse_tag(query.tag.as_deref())?;
+
+        let (mut records, total_count) = self
+            .read_repo
+            .list_offset(include_drafts, offset, limit, tag.as_ref())
+            .await?;
+        Self::retain_viewable(actor, &mut records);
+
+        let items = records.into_iter().map(Into::into).collect();
+        Ok(CursorPage::numbered(items, offset, total_count).with_limit(limit))
+    }
+
+    /// Most viewed first, forward only; cursors carry the view count, so they
+    /// are not interchangeable with those of the newest-first listing.
+    async fn list_popular(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        include_drafts: bool,
+        limit: u32,
+        query: ListArticlesQuery,
+    ) -> AppResult<CursorPage<ArticleDto>> {
+        if query.direction == PageDirection::Backward {
+            return Err(AppError::validation(
+                "direction=backward is not supported with sort=popular",
+            ));
+        }
+        let cursor = query
+            .cursor
+            .as_deref()
+            .map(ArticlePopularityCursor::decode)
+            .transpose()
+            .map_err(|err| match err {
+                DomainError::Validation(msg) => AppError::validation(msg),
+                other => AppError::from(other),
+            })?;
+        let tag = Self::parse_tag(query.tag.as_deref())?;
+
+        let (mut records, next_cursor) = self
+            .read_repo
+            .list_popular(include_drafts, limit, cursor, tag.as_ref())
+            .await?;
+        Self::retain_viewable(actor, &mut records);
+        let total_estimate = if tag.is_some() {
+            None
+        } else {
+            self.read_repo.estimate_total(include_drafts).await?
+        };
+
+        let items = records.into_iter().map(Into::into).collect();
+        Ok(
+            CursorPage::new(items, next_cursor.map(|cursor| cursor.encode()))
+                .with_limit(limit)
+                .with_total_estimate(total_estimate),
+        )
+    }
+
+    /// Forward page of articles carrying `tag`, optionally narrowed by a
+    /// search term.
+    pub(super) async fn list_tagged(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        include_drafts: bool,
+        limit: u32,
+        cursor: Option<ArticleListCursor>,
+        search: Option<&str>,
+        tag: Tag,
+    ) -> AppResult<CursorPage<ArticleDto>> {
+        let mut article_query = ArticleQuery::new()
+            .include_drafts(include_drafts)
+            .limit(limit)
+            .tag(tag);
+        if let Some(cursor) = cursor {
+            article_query = article_query.cursor(cursor);
+        }
+        if let Some(search) = search {
+            article_query = article_query.search(search);
+        }
+
+        let (mut records, next_cursor) = self.read_repo.list(article_query).await?;
+        Self::retain_viewable(actor, &mut records);
+
+        // The reverse-cursor and total lookups ignore tags, so filtered pages
+        // only report the effective limit.
+        let items = records.into_iter().map(Into::into).collect();
+        Ok(CursorPage::new(items, next_cursor.map(|cursor| cursor.encode())).with_limit(limit))
+    }
+
+    async fn list_forward(
+        &self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: Option<ArticleListCursor>,
+    ) -> AppResult<ArticleWindow> {
+        let paged = cursor.is_some();
         let (records, next_cursor) = self
             .read_repo
             .list_page(include_drafts, limit, cursor, None)
             .await?;
 
-        let items = records.into_iter().map(Into::into).collect();
-        Ok(CursorPage::new(
-            items,
-            next_cursor.map(|cursor| cursor.encode()),
-        ))
+        let prev_cursor = match records.first() {
+            Some(first) if paged => {
+                let first = ArticleListCursor::from_parts(first.created_at, first.id);
+                self.read_repo
+                    .has_preceding(include_drafts, &first)
+                    .await?
+                    .then_some(first)
+            }
+            _ => None,
+        };
+
+        Ok((records, next_cursor, prev_cursor))
+    }
+
+    /// Walk back from `cursor`. The cursor came from a later page, so older
+    /// items always follow the returned window.
+    async fn list_backward(
+        &self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: ArticleListCursor,
+    ) -> AppResult<ArticleWindow> {
+        let (records, prev_cursor) = self
+            .read_repo
+            .list_page_before(include_drafts, limit, cursor)
+            .await?;
+        let next_cursor = records
+            .last()
+            .map(|last| ArticleListCursor::from_parts(last.created_at, last.id));
+
+        Ok((records, next_cursor, prev_cursor))
     }
 
     pub(super) fn normalize_listing(
+        &self,
         actor: Option<&AuthenticatedUser>,
         include_drafts: bool,
         limit: u32,
@@ -62,13 +262,15 @@ impl ArticleQueryService {
             false
         };
 
-        let limit = if limit == 0 {
-            DEFAULT_LIMIT
-        } else {
-            limit.min(MAX_LIMIT)
-        };
+        Ok((include_drafts, self.page_limits.apply(limit)))
+    }
 
-        Ok((include_drafts, limit))
+    pub(super) fn parse_tag(tag: Option<&str>) -> AppResult<Option<Tag>> {
+        Ok(tag
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(Tag::new)
+            .transpose()?)
     }
 
     pub(super) fn decode_cursor(token: Option<&str>) -> AppResult<Option<ArticleListCursor>> {
@@ -1,13 +1,20 @@
+mod batch_get;
+mod contributors;
+mod custom_fields;
 mod get_by_id;
 mod get_by_slug;
 mod list;
+mod render;
 mod revisions;
 mod search;
 mod service;
 
+pub use batch_get::BatchGetArticlesQuery;
+pub use contributors::ListContributorsQuery;
 pub use get_by_id::GetArticleByIdQuery;
 pub use get_by_slug::GetArticleBySlugQuery;
 pub use list::ListArticlesQuery;
-pub use revisions::ListArticleRevisionsQuery;
+pub use render::RenderArticleQuery;
+pub use revisions::{DiffArticleRevisionsQuery, ListArticleRevisionsQuery};
 pub use search::SearchArticlesQuery;
 pub use service::ArticleQueryService;
@@ -0,0 +1,37 @@
+use super::{ArticleQueryService, GetArticleBySlugQuery};
+use crate::application::{
+    AuthenticatedUser, RenderProfile,
+    error::AppResult,
+    markdown::html::{self, escape},
+};
+
+pub struct RenderArticleQuery {
+    pub slug: String,
+    pub profile: RenderProfile,
+}
+
+impl ArticleQueryService {
+    /// Render an article as an `<article>` HTML fragment, including draft
+    /// visibility checks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slug is invalid, the article is missing, the
+    /// caller cannot view the draft, or the repository lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn render_article_html(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        query: RenderArticleQuery,
+    ) -> AppResult<String> {
+        let article = self
+            .get_article_by_slug(actor, GetArticleBySlugQuery { slug: query.slug })
+            .await?;
+
+        Ok(format!(
+            "<article>\n<h1>{}</h1>\n{}</article>\n",
+            escape(&article.title),
+            html::render(&article.body, query.profile)
+        ))
+    }
+}
@@ -1,8 +1,9 @@
 use super::ArticleQueryService;
 use crate::{
     application::{
-        ArticleRevisionDto, AuthenticatedUser,
+        ArticleRevisionDiffDto, ArticleRevisionDto, AuthenticatedUser, DiffGranularity,
         error::{AppError, AppResult},
+        text_diff,
     },
     domain::{
         ArticleId,
@@ -14,6 +15,13 @@ pub struct ListArticleRevisionsQuery {
     pub article_id: i64,
 }
 
+pub struct DiffArticleRevisionsQuery {
+    pub article_id: i64,
+    pub from_version: i32,
+    pub to_version: i32,
+    pub granularity: DiffGranularity,
+}
+
 impl ArticleQueryService {
     /// List revision history for an article.
     ///
@@ -21,12 +29,65 @@ impl ArticleQueryService {
     ///
     /// Returns an error if the article id is invalid, the article is missing,
     /// the actor lacks access, or repository reads fail.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn list_revisions(
         &self,
         actor: &AuthenticatedUser,
         query: ListArticleRevisionsQuery,
     ) -> AppResult<Vec<ArticleRevisionDto>> {
         let article_id = ArticleId::new(query.article_id)?;
+        self.ensure_can_view_revisions(actor, article_id).await?;
+
+        let revisions = self.revision_repo.list_by_article(article_id).await?;
+
+        Ok(revisions.into_iter().map(Into::into).collect())
+    }
+
+    /// Compare the title and body of two revisions of an article.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the article id is invalid, the article or either
+    /// revision is missing, the actor lacks access, or repository reads fail.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn diff_revisions(
+        &self,
+        actor: &AuthenticatedUser,
+        query: DiffArticleRevisionsQuery,
+    ) -> AppResult<ArticleRevisionDiffDto> {
+        let article_id = ArticleId::new(query.article_id)?;
+        self.ensure_can_view_revisions(actor, article_id).await?;
+
+        let mut revisions = Vec::with_capacity(2);
+        for version in [query.from_version, query.to_version] {
+            let revision = self
+                .revision_repo
+                .find_revision(article_id, version)
+                .await?
+                .ok_or_else(|| AppError::not_found(format!("revision {version} not found")))?;
+            revisions.push(revision);
+        }
+        let (from, to) = (&revisions[0], &revisions[1]);
+
+        Ok(ArticleRevisionDiffDto {
+            article_id: query.article_id,
+            from_version: query.from_version,
+            to_version: query.to_version,
+            granularity: query.granularity,
+            title: text_diff::diff(
+                from.title.as_str(),
+                to.title.as_str(),
+                DiffGranularity::Word,
+            ),
+            body: text_diff::diff(from.body.as_str(), to.body.as_str(), query.granularity),
+        })
+    }
+
+    async fn ensure_can_view_revisions(
+        &self,
+        actor: &AuthenticatedUser,
+        article_id: ArticleId,
+    ) -> AppResult<()> {
         let article = self
             .read_repo
             .find_by_id(article_id)
@@ -34,14 +95,15 @@ impl ArticleQueryService {
             .ok_or_else(|| AppError::not_found("article not found"))?;
 
         let spec = CanUpdateArticleSpec::new(&actor.capabilities, &article, actor.id);
-        if !spec.is_satisfied() {
+        if !spec.is_satisfied()
+            && !spec
+                .with_contributors(&self.contributor_ids(article_id).await?)
+                .is_satisfied()
+        {
             return Err(AppError::forbidden(
                 "insufficient privileges to view revisions",
             ));
         }
-
-        let revisions = self.revision_repo.list_by_article(article_id).await?;
-
-        Ok(revisions.into_iter().map(Into::into).collect())
+        Ok(())
     }
 }
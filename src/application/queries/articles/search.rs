@@ -1,11 +1,18 @@
 use super::{ArticleQueryService, list::ListArticlesQuery};
-use crate::application::{ArticleDto, AuthenticatedUser, CursorPage, error::AppResult};
+use crate::application::{
+    ArticleDto, ArticleSort, AuthenticatedUser, CursorPage, PageDirection, SearchFacetsDto,
+    SearchResultDto,
+    error::{AppError, AppResult},
+};
+use crate::domain::article::repository::ArticleQuery;
 
 pub struct SearchArticlesQuery {
     pub query: String,
     pub include_drafts: bool,
     pub limit: u32,
     pub cursor: Option<String>,
+    pub direction: PageDirection,
+    pub tag: Option<String>,
 }
 
 impl ArticleQueryService {
@@ -14,7 +21,9 @@ impl ArticleQueryService {
     /// # Errors
     ///
     /// Returns an error if draft access is not allowed, the cursor is invalid,
+    /// backward paging is requested for a non-blank query, the tag is invalid,
     /// or the repository lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn search_articles(
         &self,
         actor: Option<&AuthenticatedUser>,
@@ -29,24 +38,144 @@ impl ArticleQueryService {
                         include_drafts: query.include_drafts,
                         limit: query.limit,
                         cursor: query.cursor,
+                        direction: query.direction,
+                        tag: query.tag,
+                        sort: ArticleSort::Newest,
+                        page: None,
                     },
                 )
                 .await;
         }
 
+        if query.direction == PageDirection::Backward {
+            return Err(AppError::validation(
+                "direction=backward is not supported for search",
+            ));
+        }
+
         let (include_drafts, limit) =
-            Self::normalize_listing(actor, query.include_drafts, query.limit)?;
+            self.normalize_listing(actor, query.include_drafts, query.limit)?;
         let cursor = Self::decode_cursor(query.cursor.as_deref())?;
 
-        let (records, next_cursor) = self
+        if let Some(tag) = Self::parse_tag(query.tag.as_deref())? {
+            return self
+                .list_tagged(actor, include_drafts, limit, cursor, Some(trimmed), tag)
+                .await;
+        }
+
+        let (mut records, next_cursor) = self
             .read_repo
             .list_page(include_drafts, limit, cursor, Some(trimmed))
             .await?;
+        Self::retain_viewable(actor, &mut records);
 
+        // Relevance ordering has no stable reverse cursor, so search results
+        // only report the effective limit.
         let items = records.into_iter().map(Into::into).collect();
-        Ok(CursorPage::new(
-            items,
-            next_cursor.map(|cursor| cursor.encode()),
-        ))
+        Ok(CursorPage::new(items, next_cursor.map(|cursor| cursor.encode())).with_limit(limit))
+    }
+
+    /// Search articles and highlight the passages that matched.
+    ///
+    /// The query accepts web search syntax: quoted phrases, `-` to exclude a
+    /// term and `or` between alternatives.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query is blank, draft access is not allowed,
+    /// the cursor is invalid, backward paging is requested, the tag is
+    /// invalid, or the repository lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn search_results(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        query: SearchArticlesQuery,
+    ) -> AppResult<CursorPage<SearchResultDto>> {
+        let trimmed = query.query.trim();
+        if trimmed.is_empty() {
+            return Err(AppError::validation("q must not be blank"));
+        }
+        if query.direction == PageDirection::Backward {
+            return Err(AppError::validation(
+                "direction=backward is not supported for search",
+            ));
+        }
+
+        let (include_drafts, limit) =
+            self.normalize_listing(actor, query.include_drafts, query.limit)?;
+        let mut article_query = ArticleQuery::new()
+            .include_drafts(include_drafts)
+            .limit(limit)
+            .search(trimmed);
+        if let Some(cursor) = Self::decode_cursor(query.cursor.as_deref())? {
+            article_query = article_query.cursor(cursor);
+        }
+        if let Some(tag) = Self::parse_tag(query.tag.as_deref())? {
+            article_query = article_query.tag(tag);
+        }
+
+        let (mut hits, next_cursor) = self.read_repo.search(article_query).await?;
+        hits.retain(|hit| Self::can_view(actor, &hit.article));
+
+        let items = hits.into_iter().map(Into::into).collect();
+        Ok(CursorPage::new(items, next_cursor.map(|cursor| cursor.encode())).with_limit(limit))
+    }
+
+    /// Search like [`search_results`](Self::search_results), also counting
+    /// every match by publication state, author and tag. A blank query
+    /// browses all articles. Facets are `None` when the repository cannot
+    /// count them; otherwise their total is the page's `total_estimate`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if draft access is not allowed, the cursor is invalid,
+    /// backward paging is requested, the tag is invalid, or the repository
+    /// lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn faceted_search(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        query: SearchArticlesQuery,
+    ) -> AppResult<(CursorPage<SearchResultDto>, Option<SearchFacetsDto>)> {
+        if query.direction == PageDirection::Backward {
+            return Err(AppError::validation(
+                "direction=backward is not supported for search",
+            ));
+        }
+
+        let (include_drafts, limit) =
+            self.normalize_listing(actor, query.include_drafts, query.limit)?;
+        let search = Some(query.query.trim()).filter(|value| !value.is_empty());
+        let tag = Self::parse_tag(query.tag.as_deref())?;
+        let mut article_query = ArticleQuery::new()
+            .include_drafts(include_drafts)
+            .limit(limit);
+        if let Some(cursor) = Self::decode_cursor(query.cursor.as_deref())? {
+            article_query = article_query.cursor(cursor);
+        }
+        if let Some(search) = search {
+            article_query = article_query.search(search);
+        }
+        if let Some(tag) = tag.clone() {
+            article_query = article_query.tag(tag);
+        }
+
+        let facets = self
+            .read_repo
+            .search_facets(
+                include_drafts,
+                Self::private_scope(actor),
+                search,
+                tag.as_ref(),
+            )
+            .await?;
+        let (mut hits, next_cursor) = self.read_repo.search(article_query).await?;
+        hits.retain(|hit| Self::can_view(actor, &hit.article));
+
+        let items = hits.into_iter().map(Into::into).collect();
+        let page = CursorPage::new(items, next_cursor.map(|cursor| cursor.encode()))
+            .with_limit(limit)
+            .with_total_estimate(facets.as_ref().map(|facets| facets.total));
+        Ok((page, facets.map(Into::into)))
     }
 }
@@ -1,11 +1,22 @@
 use std::sync::Arc;
 
-use crate::domain::{ArticleReadRepository, ArticleRevisionRepository};
+use crate::{
+    application::{PageLimits, error::AppResult, ports::views::ViewCounter, services::SlugCache},
+    domain::{
+        ArticleId, ArticleReadRepository, ArticleRevisionRepository, ContributorRepository,
+        CustomFieldRepository, UserId,
+    },
+};
 
 #[must_use]
 pub struct ArticleQueryService {
     pub(super) read_repo: Arc<dyn ArticleReadRepository>,
     pub(super) revision_repo: Arc<dyn ArticleRevisionRepository>,
+    pub(super) custom_fields: Option<Arc<dyn CustomFieldRepository>>,
+    pub(super) contributors: Option<Arc<dyn ContributorRepository>>,
+    pub(super) slug_cache: SlugCache,
+    pub(super) page_limits: PageLimits,
+    pub(super) views: Option<Arc<dyn ViewCounter>>,
 }
 
 impl ArticleQueryService {
@@ -16,6 +27,56 @@ impl ArticleQueryService {
         Self {
             read_repo,
             revision_repo,
+            custom_fields: None,
+            contributors: None,
+            slug_cache: SlugCache::default(),
+            page_limits: PageLimits::default(),
+            views: None,
         }
     }
+
+    /// List custom field definitions from `repo`.
+    pub fn with_custom_fields(mut self, repo: Arc<dyn CustomFieldRepository>) -> Self {
+        self.custom_fields = Some(repo);
+        self
+    }
+
+    /// Let the contributors in `repo` read the drafts, private articles and
+    /// revisions they help edit.
+    pub fn with_contributors(mut self, repo: Arc<dyn ContributorRepository>) -> Self {
+        self.contributors = Some(repo);
+        self
+    }
+
+    /// Answer public slug lookups through `cache`.
+    pub fn with_slug_cache(mut self, cache: SlugCache) -> Self {
+        self.slug_cache = cache;
+        self
+    }
+
+    /// Size listing pages by `limits`.
+    pub const fn with_page_limits(mut self, limits: PageLimits) -> Self {
+        self.page_limits = limits;
+        self
+    }
+
+    /// Count public slug reads of published articles in `counter`.
+    pub fn with_view_counter(mut self, counter: Arc<dyn ViewCounter>) -> Self {
+        self.views = Some(counter);
+        self
+    }
+
+    /// Users who may edit the article besides its author; empty when
+    /// contributors are not enabled.
+    pub(super) async fn contributor_ids(&self, article_id: ArticleId) -> AppResult<Vec<UserId>> {
+        let Some(repo) = &self.contributors else {
+            return Ok(Vec::new());
+        };
+        Ok(repo
+            .list(article_id)
+            .await?
+            .into_iter()
+            .map(|contributor| contributor.user_id)
+            .collect())
+    }
 }
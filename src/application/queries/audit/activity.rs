@@ -0,0 +1,180 @@
+use super::{common, service::AuditQueryService};
+use crate::application::{
+    ActivityCategory, ActivityEntryDto, AuthenticatedUser, CursorPage,
+    error::{AppError, AppResult},
+    ports::security_events::TOKEN_REUSE_AUDIT_ACTION,
+};
+use crate::domain::audit::{entity::AuditLog, filter::AuditLogFilter};
+use chrono::{DateTime, Utc};
+
+pub struct ListMyActivityQuery {
+    pub category: Option<ActivityCategory>,
+    pub since: Option<DateTime<Utc>>,
+    pub limit: u32,
+    pub cursor: Option<String>,
+}
+
+/// How one audit action shows in the activity feed.
+struct ActivityKind {
+    action: &'static str,
+    category: ActivityCategory,
+    icon: &'static str,
+    summary: &'static str,
+}
+
+const fn kind(
+    action: &'static str,
+    category: ActivityCategory,
+    icon: &'static str,
+    summary: &'static str,
+) -> ActivityKind {
+    ActivityKind {
+        action,
+        category,
+        icon,
+        summary,
+    }
+}
+
+/// Audit actions shown in the feed; anything else stays admin-only.
+const ACTIVITY_KINDS: &[ActivityKind] = {
+    use ActivityCategory::{Account, Administration, Content, Security, SignIn};
+    &[
+        kind("user.login", SignIn, "log-in", "Signed in"),
+        kind(
+            "user.password_change",
+            Security,
+            "key-round",
+            "Password changed",
+        ),
+        kind(
+            "user.password_reset",
+            Security,
+            "key-round",
+            "Password reset",
+        ),
+        kind(
+            "user.locked",
+            Security,
+            "lock",
+            "Locked after failed sign-ins",
+        ),
+        kind(
+            TOKEN_REUSE_AUDIT_ACTION,
+            Security,
+            "shield-alert",
+            "Reuse of a signed-out session detected",
+        ),
+        kind(
+            "user.email_change",
+            Account,
+            "mail",
+            "Email address changed",
+        ),
+        kind(
+            "user.email_verify",
+            Account,
+            "mail-check",
+            "Email address verified",
+        ),
+        kind(
+            "user.preferences_update",
+            Account,
+            "settings",
+            "Preferences updated",
+        ),
+        kind("article.create", Content, "file-plus", "Article created"),
+        kind("article.update", Content, "file-pen", "Article edited"),
+        kind("article.revert", Content, "history", "Article reverted"),
+        kind("article.publish", Content, "send", "Article published"),
+        kind(
+            "article.unpublish",
+            Content,
+            "eye-off",
+            "Article unpublished",
+        ),
+        kind("article.delete", Content, "trash-2", "Article deleted"),
+        kind(
+            "article.syndication",
+            Content,
+            "share-2",
+            "Article announcements changed",
+        ),
+        kind(
+            "user.create",
+            Administration,
+            "user-plus",
+            "Account created",
+        ),
+        kind("user.update", Administration, "user-pen", "Account updated"),
+        kind("user.role_change", Administration, "shield", "Role changed"),
+    ]
+};
+
+fn activity_kind(action: &str) -> Option<&'static ActivityKind> {
+    ACTIVITY_KINDS.iter().find(|kind| kind.action == action)
+}
+
+fn entry(log: AuditLog, actor: &AuthenticatedUser) -> Option<ActivityEntryDto> {
+    let kind = activity_kind(&log.action)?;
+    Some(ActivityEntryDto {
+        id: log.id,
+        category: kind.category,
+        icon: kind.icon.to_owned(),
+        summary: kind.summary.to_owned(),
+        by_someone_else: log.user_id != Some(actor.id),
+        action: log.action,
+        resource_type: log.resource_type,
+        resource_id: log.resource_id,
+        ip_address: log.ip_address,
+        user_agent: log.user_agent,
+        created_at: log.created_at,
+        created_at_local: None,
+    })
+}
+
+impl AuditQueryService {
+    /// List the actor's own recent account activity, newest first: what they
+    /// did and what was done to their account.
+    ///
+    /// Needs no audit capability; only entries involving the actor are read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cursor is invalid or the repository lookup
+    /// fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn list_my_activity(
+        &self,
+        actor: &AuthenticatedUser,
+        query: ListMyActivityQuery,
+    ) -> AppResult<CursorPage<ActivityEntryDto>> {
+        let limit = self.page_limits.apply(query.limit);
+        let typed_cursor = common::decode_cursor(query.cursor.as_deref())?;
+        let actions = ACTIVITY_KINDS
+            .iter()
+            .filter(|kind| {
+                query
+                    .category
+                    .is_none_or(|category| kind.category == category)
+            })
+            .map(|kind| kind.action);
+        let mut filter = AuditLogFilter::new()
+            .involving_user(i64::from(actor.id))
+            .actions(actions);
+        if let Some(since) = query.since {
+            filter = filter.since(since);
+        }
+
+        let (items, next_cursor) = self
+            .repo
+            .search(&filter, limit, typed_cursor)
+            .await
+            .map_err(AppError::from)?;
+        let dtos: Vec<_> = items
+            .into_iter()
+            .filter_map(|log| entry(log, actor))
+            .collect();
+        Ok(common::page(dtos, next_cursor.as_ref(), limit))
+    }
+}
@@ -1,6 +1,9 @@
-use crate::application::{
-    AuthenticatedUser,
-    error::{AppError, AppResult},
+use crate::{
+    application::{
+        AuthenticatedUser, CursorPage,
+        error::{AppError, AppResult},
+    },
+    domain::{audit::cursor::AuditLogCursor, errors::DomainError},
 };
 
 pub(super) fn ensure_audit_capability(actor: &AuthenticatedUser) -> AppResult<()> {
@@ -11,13 +14,22 @@ pub(super) fn ensure_audit_capability(actor: &AuthenticatedUser) -> AppResult<()
     }
 }
 
-pub(super) fn normalize_limit(limit: u32) -> u32 {
-    const DEFAULT_LIMIT: u32 = 20;
-    const MAX_LIMIT: u32 = 100;
+/// Decode a client-supplied cursor token at the service boundary.
+pub(super) fn decode_cursor(token: Option<&str>) -> AppResult<Option<AuditLogCursor>> {
+    token
+        .map(AuditLogCursor::decode)
+        .transpose()
+        .map_err(|err| match err {
+            DomainError::Validation(msg) => AppError::validation(msg),
+            other => AppError::from(other),
+        })
+}
 
-    if limit == 0 {
-        DEFAULT_LIMIT
-    } else {
-        limit.min(MAX_LIMIT)
-    }
+/// Build a page, encoding the typed next cursor for the client.
+pub(super) fn page<T>(
+    items: Vec<T>,
+    next_cursor: Option<&AuditLogCursor>,
+    limit: u32,
+) -> CursorPage<T> {
+    CursorPage::new(items, next_cursor.map(AuditLogCursor::encode)).with_limit(limit)
 }
@@ -1,10 +1,7 @@
 use super::{common, service::AuditQueryService};
-use crate::{
-    application::{
-        AuditLogDto, AuthenticatedUser, CursorPage,
-        error::{AppError, AppResult},
-    },
-    domain::audit::cursor::Cursor,
+use crate::application::{
+    AuditLogDto, AuthenticatedUser, CursorPage,
+    error::{AppError, AppResult},
 };
 
 pub struct ListAuditLogsQuery {
@@ -32,14 +29,15 @@ impl AuditQueryService {
     ///
     /// Returns an error if the actor lacks audit access, the cursor is
     /// invalid, or the repository lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn list_audit_logs(
         &self,
         actor: &AuthenticatedUser,
         query: ListAuditLogsQuery,
     ) -> AppResult<CursorPage<AuditLogDto>> {
         common::ensure_audit_capability(actor)?;
-        let limit = common::normalize_limit(query.limit);
-        let typed_cursor = Self::decode_cursor(query.cursor.as_deref())?;
+        let limit = self.page_limits.apply(query.limit);
+        let typed_cursor = common::decode_cursor(query.cursor.as_deref())?;
 
         let (items, next_cursor) = self
             .repo
@@ -47,7 +45,7 @@ impl AuditQueryService {
             .await
             .map_err(AppError::from)?;
         let dtos: Vec<_> = items.into_iter().map(Into::<AuditLogDto>::into).collect();
-        Ok(CursorPage::new(dtos, next_cursor))
+        Ok(common::page(dtos, next_cursor.as_ref(), limit))
     }
 
     /// List audit logs associated with a user.
@@ -56,21 +54,22 @@ impl AuditQueryService {
     ///
     /// Returns an error if the actor lacks audit access, the cursor is
     /// invalid, or the repository lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn list_by_user(
         &self,
         actor: &AuthenticatedUser,
         query: ListAuditLogsByUserQuery,
     ) -> AppResult<CursorPage<AuditLogDto>> {
         common::ensure_audit_capability(actor)?;
-        let limit = common::normalize_limit(query.limit);
-        let typed_cursor = Self::decode_cursor(query.cursor.as_deref())?;
+        let limit = self.page_limits.apply(query.limit);
+        let typed_cursor = common::decode_cursor(query.cursor.as_deref())?;
         let (items, next_cursor) = self
             .repo
             .find_by_user(query.user_id, limit, typed_cursor)
             .await
             .map_err(AppError::from)?;
         let dtos: Vec<_> = items.into_iter().map(Into::<AuditLogDto>::into).collect();
-        Ok(CursorPage::new(dtos, next_cursor))
+        Ok(common::page(dtos, next_cursor.as_ref(), limit))
     }
 
     /// List audit logs for a specific resource.
@@ -79,27 +78,21 @@ impl AuditQueryService {
     ///
     /// Returns an error if the actor lacks audit access, the cursor is
     /// invalid, or the repository lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn list_by_resource(
         &self,
         actor: &AuthenticatedUser,
         query: ListAuditLogsByResourceQuery,
     ) -> AppResult<CursorPage<AuditLogDto>> {
         common::ensure_audit_capability(actor)?;
-        let limit = common::normalize_limit(query.limit);
-        let typed_cursor = Self::decode_cursor(query.cursor.as_deref())?;
+        let limit = self.page_limits.apply(query.limit);
+        let typed_cursor = common::decode_cursor(query.cursor.as_deref())?;
         let (items, next_cursor) = self
             .repo
             .find_by_resource(&query.resource_type, query.resource_id, limit, typed_cursor)
             .await
             .map_err(AppError::from)?;
         let dtos: Vec<_> = items.into_iter().map(Into::<AuditLogDto>::into).collect();
-        Ok(CursorPage::new(dtos, next_cursor))
-    }
-
-    fn decode_cursor(cursor: Option<&str>) -> AppResult<Option<Cursor>> {
-        cursor.map_or_else(
-            || Ok(None),
-            |token| Ok(Some(Cursor::decode(token).map_err(AppError::from)?)),
-        )
+        Ok(common::page(dtos, next_cursor.as_ref(), limit))
     }
 }
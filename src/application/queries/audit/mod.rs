@@ -1,3 +1,5 @@
+pub mod activity;
 mod common;
 pub mod list;
 pub mod service;
+pub mod token_reuse;
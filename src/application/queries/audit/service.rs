@@ -1,14 +1,25 @@
 use std::sync::Arc;
 
+use crate::application::PageLimits;
 use crate::domain::audit::repository::AuditLogRepository;
 
 #[must_use]
 pub struct AuditQueryService {
     pub(super) repo: Arc<dyn AuditLogRepository>,
+    pub(super) page_limits: PageLimits,
 }
 
 impl AuditQueryService {
     pub fn new(repo: Arc<dyn AuditLogRepository>) -> Self {
-        Self { repo }
+        Self {
+            repo,
+            page_limits: PageLimits::default(),
+        }
+    }
+
+    /// Size listing pages by `limits`.
+    pub const fn with_page_limits(mut self, limits: PageLimits) -> Self {
+        self.page_limits = limits;
+        self
     }
 }
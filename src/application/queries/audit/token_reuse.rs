@@ -0,0 +1,41 @@
+use super::{common, service::AuditQueryService};
+use crate::application::{
+    AuthenticatedUser, CursorPage, TokenReuseIncidentDto,
+    error::{AppError, AppResult},
+    ports::security_events::TOKEN_REUSE_AUDIT_ACTION,
+};
+
+pub struct ListTokenReuseIncidentsQuery {
+    pub limit: u32,
+    pub cursor: Option<String>,
+}
+
+impl AuditQueryService {
+    /// List recorded refresh-token reuse incidents, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks audit access, the cursor is
+    /// invalid, or the repository lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn list_token_reuse_incidents(
+        &self,
+        actor: &AuthenticatedUser,
+        query: ListTokenReuseIncidentsQuery,
+    ) -> AppResult<CursorPage<TokenReuseIncidentDto>> {
+        common::ensure_audit_capability(actor)?;
+        let limit = self.page_limits.apply(query.limit);
+        let typed_cursor = common::decode_cursor(query.cursor.as_deref())?;
+
+        let (items, next_cursor) = self
+            .repo
+            .find_by_action(TOKEN_REUSE_AUDIT_ACTION, limit, typed_cursor)
+            .await
+            .map_err(AppError::from)?;
+        let dtos: Vec<_> = items
+            .into_iter()
+            .map(Into::<TokenReuseIncidentDto>::into)
+            .collect();
+        Ok(common::page(dtos, next_cursor.as_ref(), limit))
+    }
+}
@@ -0,0 +1,62 @@
+// src/application/queries/batch.rs
+use crate::application::{
+    BatchResult, MAX_BATCH_IDS,
+    error::{AppError, AppResult},
+};
+use std::collections::{HashMap, HashSet};
+
+/// Validate requested ids, dropping duplicates while keeping the first
+/// occurrence's position.
+pub fn normalize_ids(ids: &[i64]) -> AppResult<Vec<i64>> {
+    if ids.len() > MAX_BATCH_IDS {
+        return Err(AppError::validation(format!(
+            "at most {MAX_BATCH_IDS} ids may be requested at once"
+        )));
+    }
+
+    let mut seen = HashSet::with_capacity(ids.len());
+    Ok(ids.iter().copied().filter(|id| seen.insert(*id)).collect())
+}
+
+/// Order `found` by the requested ids and report the ids that were not found.
+pub(super) fn assemble<T, D>(
+    requested: &[i64],
+    found: Vec<T>,
+    id_of: impl Fn(&T) -> i64,
+) -> BatchResult<D>
+where
+    T: Into<D>,
+{
+    let mut by_id: HashMap<i64, T> = found.into_iter().map(|item| (id_of(&item), item)).collect();
+
+    let mut items = Vec::with_capacity(by_id.len());
+    let mut missing_ids = Vec::new();
+    for id in requested {
+        match by_id.remove(id) {
+            Some(item) => items.push(item.into()),
+            None => missing_ids.push(*id),
+        }
+    }
+
+    BatchResult::new(items, missing_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assemble, normalize_ids};
+    use crate::application::MAX_BATCH_IDS;
+
+    #[test]
+    fn normalize_ids_dedupes_and_enforces_the_limit() {
+        assert_eq!(normalize_ids(&[3, 1, 3, 2, 1]).unwrap(), vec![3, 1, 2]);
+        let too_many: Vec<i64> = (1..=i64::try_from(MAX_BATCH_IDS).unwrap() + 1).collect();
+        assert!(normalize_ids(&too_many).is_err());
+    }
+
+    #[test]
+    fn assemble_follows_request_order_and_reports_missing() {
+        let result = assemble::<i64, i64>(&[5, 9, 1], vec![1, 5], |id| *id);
+        assert_eq!(result.items, vec![5, 1]);
+        assert_eq!(result.missing_ids, vec![9]);
+    }
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use crate::application::{
+    AppResult, AuthenticatedUser,
+    dto::bootstrap::{BootstrapDto, SiteSettingsDto},
+    queries::users::UserQueryService,
+    services::{FederatedLoginService, StatusService},
+};
+
+/// Assembles the startup payload for frontends from the services that own
+/// each part, so a client needs one request instead of several.
+#[must_use]
+pub struct BootstrapQueryService {
+    user_queries: Arc<UserQueryService>,
+    status: Arc<StatusService>,
+    federated_login: Arc<FederatedLoginService>,
+}
+
+impl BootstrapQueryService {
+    pub const fn new(
+        user_queries: Arc<UserQueryService>,
+        status: Arc<StatusService>,
+        federated_login: Arc<FederatedLoginService>,
+    ) -> Self {
+        Self {
+            user_queries,
+            status,
+            federated_login,
+        }
+    }
+
+    /// Build the bootstrap payload for `actor`, or for an anonymous visitor.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor's user record cannot be loaded.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn bootstrap(&self, actor: Option<&AuthenticatedUser>) -> AppResult<BootstrapDto> {
+        let profile = match actor {
+            Some(actor) => Some(self.user_queries.get_profile(actor).await?),
+            None => None,
+        };
+        let capabilities = profile
+            .as_ref()
+            .map(|profile| profile.capabilities.clone())
+            .unwrap_or_default();
+
+        Ok(BootstrapDto {
+            profile,
+            capabilities,
+            features: self.status.features(),
+            site: SiteSettingsDto {
+                version: env!("CARGO_PKG_VERSION").into(),
+                login_providers: self
+                    .federated_login
+                    .provider_names()
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect(),
+            },
+        })
+    }
+}
@@ -0,0 +1,46 @@
+use super::InspectQueryService;
+use super::common::ensure_inspect_capability;
+use crate::{
+    application::{
+        ArticleInspectionDto, AuthenticatedUser,
+        error::{AppError, AppResult},
+    },
+    domain::ArticleId,
+};
+
+pub struct InspectArticleQuery {
+    pub id: i64,
+}
+
+impl InspectQueryService {
+    /// Load an article in any publication state together with its author and
+    /// full revision history.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller lacks `admin:inspect`, the id is
+    /// invalid, the article does not exist, or a repository lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn inspect_article(
+        &self,
+        actor: &AuthenticatedUser,
+        query: InspectArticleQuery,
+    ) -> AppResult<ArticleInspectionDto> {
+        ensure_inspect_capability(actor)?;
+
+        let id = ArticleId::new(query.id)?;
+        let article = self
+            .article_read_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::not_found("article not found"))?;
+        let author = self.user_repo.find_by_id(article.author_id).await?;
+        let revisions = self.article_revision_repo.list_by_article(id).await?;
+
+        Ok(ArticleInspectionDto {
+            article: article.into(),
+            author: author.map(Into::into),
+            revisions: revisions.into_iter().map(Into::into).collect(),
+        })
+    }
+}
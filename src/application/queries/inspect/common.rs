@@ -0,0 +1,46 @@
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser, SessionInspectionDto,
+    ports::session_revocation::{Ports, SessionInfo},
+};
+use chrono::{TimeZone, Utc};
+
+pub(super) fn ensure_inspect_capability(actor: &AuthenticatedUser) -> AppResult<()> {
+    if actor.has_capability("admin", "inspect") {
+        Ok(())
+    } else {
+        Err(AppError::forbidden("missing capability admin:inspect"))
+    }
+}
+
+/// Combine stored session metadata with its revocation, family and refresh
+/// state.
+pub(super) async fn inspect_session(
+    stores: &Ports,
+    info: SessionInfo,
+) -> AppResult<SessionInspectionDto> {
+    let revoked = info.revoked || stores.revocation.is_revoked(&info.session_id).await?;
+    let family_id = stores
+        .refresh_families
+        .family_for_session(&info.session_id)
+        .await?;
+    let has_refresh_nonce = stores
+        .refresh_nonces
+        .get_session_refresh_nonce(&info.session_id)
+        .await?
+        .is_some();
+    let created_at = (info.created_at_unix > 0)
+        .then(|| Utc.timestamp_opt(info.created_at_unix, 0).single())
+        .flatten();
+
+    Ok(SessionInspectionDto {
+        session_id: info.session_id,
+        user_id: info.user_id,
+        user_agent: info.user_agent,
+        ip_address: info.ip_address,
+        created_at_unix: info.created_at_unix,
+        created_at,
+        revoked,
+        family_id,
+        has_refresh_nonce,
+    })
+}
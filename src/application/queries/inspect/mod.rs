@@ -0,0 +1,10 @@
+mod article;
+mod common;
+mod service;
+mod session;
+mod user;
+
+pub use article::InspectArticleQuery;
+pub use service::InspectQueryService;
+pub use session::InspectSessionQuery;
+pub use user::InspectUserQuery;
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use crate::application::ports::session_revocation::Ports;
+use crate::domain::{ArticleReadRepository, ArticleRevisionRepository, UserRepository};
+
+/// Read-only views over stored users, articles and sessions for operators,
+/// including drafts, inactive users and revoked sessions.
+#[must_use]
+pub struct InspectQueryService {
+    pub(super) user_repo: Arc<dyn UserRepository>,
+    pub(super) article_read_repo: Arc<dyn ArticleReadRepository>,
+    pub(super) article_revision_repo: Arc<dyn ArticleRevisionRepository>,
+    pub(super) session_stores: Ports,
+}
+
+impl InspectQueryService {
+    pub const fn new(
+        user_repo: Arc<dyn UserRepository>,
+        article_read_repo: Arc<dyn ArticleReadRepository>,
+        article_revision_repo: Arc<dyn ArticleRevisionRepository>,
+        session_stores: Ports,
+    ) -> Self {
+        Self {
+            user_repo,
+            article_read_repo,
+            article_revision_repo,
+            session_stores,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use super::InspectQueryService;
+use super::common::{ensure_inspect_capability, inspect_session};
+use crate::application::{
+    AuthenticatedUser, SessionInspectionDto,
+    error::{AppError, AppResult},
+};
+
+pub struct InspectSessionQuery {
+    pub session_id: String,
+}
+
+impl InspectQueryService {
+    /// Load a session's stored metadata with its revocation, family and
+    /// refresh state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller lacks `admin:inspect`, no metadata is
+    /// stored for the session, or a store lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn inspect_session(
+        &self,
+        actor: &AuthenticatedUser,
+        query: InspectSessionQuery,
+    ) -> AppResult<SessionInspectionDto> {
+        ensure_inspect_capability(actor)?;
+
+        let info = self
+            .session_stores
+            .session_metadata
+            .get_session_metadata(&query.session_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("session not found"))?;
+
+        inspect_session(&self.session_stores, info).await
+    }
+}
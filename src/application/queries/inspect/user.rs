@@ -0,0 +1,73 @@
+use super::InspectQueryService;
+use super::common::{ensure_inspect_capability, inspect_session};
+use crate::{
+    application::{
+        AuthenticatedUser, CapabilityView, UserInspectionDto,
+        error::{AppError, AppResult},
+    },
+    domain::UserId,
+};
+
+pub struct InspectUserQuery {
+    pub id: i64,
+}
+
+impl InspectQueryService {
+    /// Load a user (active or not) with its role capabilities, token version
+    /// floor and every tracked session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller lacks `admin:inspect`, the id is
+    /// invalid, the user does not exist, or a store lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn inspect_user(
+        &self,
+        actor: &AuthenticatedUser,
+        query: InspectUserQuery,
+    ) -> AppResult<UserInspectionDto> {
+        ensure_inspect_capability(actor)?;
+
+        let id = UserId::new(query.id)?;
+        let user = self
+            .user_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::not_found("user not found"))?;
+
+        let mut role_capabilities: Vec<_> = user
+            .role
+            .default_capabilities()
+            .into_iter()
+            .map(CapabilityView::from)
+            .collect();
+        role_capabilities.sort_by(|a, b| {
+            a.resource
+                .cmp(&b.resource)
+                .then_with(|| a.action.cmp(&b.action))
+        });
+
+        let min_token_version = self
+            .session_stores
+            .token_versions
+            .get_min_token_version(query.id)
+            .await?;
+
+        let infos = self
+            .session_stores
+            .session_metadata
+            .list_sessions_for_user_with_meta(query.id)
+            .await?;
+        let mut sessions = Vec::with_capacity(infos.len());
+        for info in infos {
+            sessions.push(inspect_session(&self.session_stores, info).await?);
+        }
+
+        Ok(UserInspectionDto {
+            user: user.into(),
+            role_capabilities,
+            min_token_version,
+            sessions,
+        })
+    }
+}
@@ -1,4 +1,8 @@
 // src/application/queries/mod.rs
 pub mod articles;
 pub mod audit;
+pub(crate) mod batch;
+pub mod bootstrap;
+pub mod inspect;
+mod numbered;
 pub mod users;
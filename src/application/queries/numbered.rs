@@ -0,0 +1,10 @@
+// src/application/queries/numbered.rs
+use crate::application::error::{AppError, AppResult};
+
+/// Items before page `page` (counted from 1) of `limit` items each.
+pub(super) fn page_offset(page: u32, limit: u32) -> AppResult<u64> {
+    if page == 0 {
+        return Err(AppError::validation("page must be at least 1"));
+    }
+    Ok(u64::from(page - 1) * u64::from(limit))
+}
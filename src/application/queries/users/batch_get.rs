@@ -0,0 +1,43 @@
+use super::UserQueryService;
+use crate::{
+    application::{
+        AuthenticatedUser, BatchResult, UserDto,
+        error::{AppError, AppResult},
+        queries::batch,
+    },
+    domain::UserId,
+};
+
+pub struct BatchGetUsersQuery {
+    pub ids: Vec<i64>,
+}
+
+impl UserQueryService {
+    /// Load several users by id in one round trip.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `users:read`, too many ids are
+    /// requested, or the repository lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn batch_get_users(
+        &self,
+        actor: &AuthenticatedUser,
+        query: BatchGetUsersQuery,
+    ) -> AppResult<BatchResult<UserDto>> {
+        if !actor.has_capability("users", "read") {
+            return Err(AppError::forbidden("missing capability users:read"));
+        }
+
+        let requested = batch::normalize_ids(&query.ids)?;
+        let ids: Vec<UserId> = requested
+            .iter()
+            .filter_map(|id| UserId::new(*id).ok())
+            .collect();
+        let found = self.user_repo.find_by_ids(&ids).await?;
+
+        Ok(batch::assemble(&requested, found, |user| {
+            i64::from(user.id)
+        }))
+    }
+}
@@ -1,25 +1,34 @@
 use super::UserQueryService;
 use crate::{
     application::{
-        AuthenticatedUser, CursorPage, UserDto,
+        AuthenticatedUser, CursorPage, PageDirection, UserDto,
         error::{AppError, AppResult},
+        queries::numbered,
     },
-    domain::UserListCursor,
+    domain::{User, UserListCursor},
 };
 
 pub struct ListUsersQuery {
     pub limit: u32,
     pub cursor: Option<String>,
     pub q: Option<String>,
+    pub direction: PageDirection,
+    /// 1-based page number. Lists numbered pages of `limit` users with a
+    /// `total_count` instead of walking cursors.
+    pub page: Option<u32>,
 }
 
+type UserWindow = (Vec<User>, Option<UserListCursor>, Option<UserListCursor>);
+
 impl UserQueryService {
     /// List users visible to an authenticated admin-like caller.
     ///
     /// # Errors
     ///
     /// Returns an error if the actor lacks `users:read`, the cursor is
-    /// invalid, or the repository lookup fails.
+    /// invalid (or missing when paging backward), a page number is zero or
+    /// combined with cursors, or the repository lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn list_users(
         &self,
         actor: &AuthenticatedUser,
@@ -29,30 +38,95 @@ impl UserQueryService {
             return Err(AppError::forbidden("missing capability users:read"));
         }
 
-        let limit = Self::normalize_limit(query.limit);
+        let limit = self.page_limits.apply(query.limit);
+        if let Some(page) = query.page {
+            return self.list_numbered(limit, page, query).await;
+        }
         let cursor = Self::decode_cursor(query.cursor.as_deref())?;
+        let search = query.q.as_deref();
+
+        let (users, next_cursor, prev_cursor) = match query.direction {
+            PageDirection::Forward => self.list_forward(limit, cursor, search).await?,
+            PageDirection::Backward => {
+                let cursor = cursor
+                    .ok_or_else(|| AppError::validation("direction=backward requires a cursor"))?;
+                self.list_backward(limit, cursor, search).await?
+            }
+        };
+        let total_estimate = self.user_repo.estimate_total(search).await?;
 
-        let (users, next_cursor) = self
+        let items = users.into_iter().map(Into::into).collect();
+        Ok(
+            CursorPage::new(items, next_cursor.map(|cursor| cursor.encode()))
+                .with_limit(limit)
+                .with_prev_cursor(prev_cursor.map(|cursor| cursor.encode()))
+                .with_total_estimate(total_estimate),
+        )
+    }
+
+    /// Page `page` of the newest-first listing, counted from 1.
+    async fn list_numbered(
+        &self,
+        limit: u32,
+        page: u32,
+        query: ListUsersQuery,
+    ) -> AppResult<CursorPage<UserDto>> {
+        let offset = numbered::page_offset(page, limit)?;
+        if query.cursor.is_some() || query.direction == PageDirection::Backward {
+            return Err(AppError::validation(
+                "page is not supported with cursor or direction",
+            ));
+        }
+
+        let (users, total_count) = self
             .user_repo
-            .list_page(limit, cursor, query.q.as_deref())
+            .list_offset(offset, limit, query.q.as_deref())
             .await?;
 
         let items = users.into_iter().map(Into::into).collect();
-        Ok(CursorPage::new(
-            items,
-            next_cursor.map(|cursor| cursor.encode()),
-        ))
+        Ok(CursorPage::numbered(items, offset, total_count).with_limit(limit))
     }
 
-    fn normalize_limit(limit: u32) -> u32 {
-        const DEFAULT_LIMIT: u32 = 20;
-        const MAX_LIMIT: u32 = 100;
+    async fn list_forward(
+        &self,
+        limit: u32,
+        cursor: Option<UserListCursor>,
+        search: Option<&str>,
+    ) -> AppResult<UserWindow> {
+        let paged = cursor.is_some();
+        let (users, next_cursor) = self.user_repo.list_page(limit, cursor, search).await?;
 
-        if limit == 0 {
-            DEFAULT_LIMIT
-        } else {
-            limit.min(MAX_LIMIT)
-        }
+        let prev_cursor = match users.first() {
+            Some(first) if paged => {
+                let first = UserListCursor::new(first.created_at, first.id);
+                self.user_repo
+                    .has_preceding(&first, search)
+                    .await?
+                    .then_some(first)
+            }
+            _ => None,
+        };
+
+        Ok((users, next_cursor, prev_cursor))
+    }
+
+    /// Walk back from `cursor`. The cursor came from a later page, so older
+    /// users always follow the returned window.
+    async fn list_backward(
+        &self,
+        limit: u32,
+        cursor: UserListCursor,
+        search: Option<&str>,
+    ) -> AppResult<UserWindow> {
+        let (users, prev_cursor) = self
+            .user_repo
+            .list_page_before(limit, cursor, search)
+            .await?;
+        let next_cursor = users
+            .last()
+            .map(|last| UserListCursor::new(last.created_at, last.id));
+
+        Ok((users, next_cursor, prev_cursor))
     }
 
     fn decode_cursor(token: Option<&str>) -> AppResult<Option<UserListCursor>> {
@@ -1,6 +1,9 @@
+mod batch_get;
 mod list;
+mod permissions;
 mod profile;
 mod service;
 
+pub use batch_get::BatchGetUsersQuery;
 pub use list::ListUsersQuery;
 pub use service::UserQueryService;
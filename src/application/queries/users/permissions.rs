@@ -0,0 +1,33 @@
+use super::UserQueryService;
+use crate::application::{AuthenticatedUser, CapabilityView, PermissionsDto};
+use crate::domain::Capability;
+
+impl UserQueryService {
+    /// List what `actor`'s token allows as concrete resource/action pairs.
+    ///
+    /// Known capabilities are tested with the same check authorization uses,
+    /// so broader grants expand to every pair they cover; grants outside the
+    /// known set are listed as they are.
+    #[must_use]
+    pub fn permissions(&self, actor: &AuthenticatedUser) -> PermissionsDto {
+        let known = Capability::known();
+        let mut permissions: Vec<_> = known
+            .iter()
+            .filter(|cap| actor.has_capability(&cap.resource, &cap.action))
+            .chain(actor.capabilities.iter().filter(|cap| !known.contains(cap)))
+            .cloned()
+            .map(CapabilityView::from)
+            .collect();
+        permissions.sort_by(|a, b| {
+            a.resource
+                .cmp(&b.resource)
+                .then_with(|| a.action.cmp(&b.action))
+        });
+
+        PermissionsDto {
+            role: actor.role,
+            custom_role: actor.custom_role.clone(),
+            permissions,
+        }
+    }
+}
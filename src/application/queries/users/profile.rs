@@ -11,6 +11,7 @@ impl UserQueryService {
     ///
     /// Returns an error if the backing user record no longer exists or the
     /// repository lookup fails.
+    #[tracing::instrument(level = "debug", skip_all)]
     pub async fn get_profile(&self, actor: &AuthenticatedUser) -> AppResult<UserProfileDto> {
         let user = self
             .user_repo
@@ -18,6 +19,65 @@ impl UserQueryService {
             .await?
             .ok_or_else(|| AppError::not_found("user not found"))?;
 
-        Ok(UserProfileDto::from_parts(user, actor))
+        Ok(UserProfileDto::from_parts(user, actor, self.clock.now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UserQueryService;
+    use crate::application::{AuthenticatedUser, ports::time::Clock};
+    use crate::domain::{NewUser, PasswordHash, Role, UserRepository, Username};
+    use crate::infrastructure::repositories::memory::InMemoryUserRepository;
+    use chrono::{DateTime, Duration, Utc};
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    struct FixedClock(DateTime<Utc>);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn expires_in_counts_from_the_injected_clock() {
+        let issued_at = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .expect("valid RFC3339")
+            .with_timezone(&Utc);
+        let repo: Arc<dyn UserRepository> = Arc::new(InMemoryUserRepository::new());
+        let user = repo
+            .insert(
+                NewUser::new(
+                    Username::new("alice").unwrap(),
+                    PasswordHash::new("$argon2id$v=19$m=19456,t=2,p=1$c2FsdA$aGFzaA").unwrap(),
+                    Role::Author,
+                    issued_at,
+                )
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        let actor = AuthenticatedUser {
+            id: user.id,
+            username: "alice".into(),
+            role: Role::Author,
+            capabilities: HashSet::new(),
+            issued_at,
+            expires_at: issued_at + Duration::hours(1),
+            session_id: None,
+            token_version: None,
+            custom_role: None,
+            impersonator: None,
+        };
+
+        for (elapsed, expected) in [(0, 3600), (45, 900), (90, 0)] {
+            let clock = FixedClock(issued_at + Duration::minutes(elapsed));
+            let service = UserQueryService::new(Arc::clone(&repo), Arc::new(clock));
+            let profile = service.get_profile(&actor).await.unwrap();
+            assert_eq!(profile.expires_at, actor.expires_at);
+            assert_eq!(profile.expires_in, expected);
+        }
     }
 }
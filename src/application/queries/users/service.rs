@@ -1,14 +1,27 @@
 use std::sync::Arc;
 
+use crate::application::{PageLimits, ports::time::Clock};
 use crate::domain::UserRepository;
 
 #[must_use]
 pub struct UserQueryService {
     pub(super) user_repo: Arc<dyn UserRepository>,
+    pub(super) clock: Arc<dyn Clock>,
+    pub(super) page_limits: PageLimits,
 }
 
 impl UserQueryService {
-    pub fn new(user_repo: Arc<dyn UserRepository>) -> Self {
-        Self { user_repo }
+    pub fn new(user_repo: Arc<dyn UserRepository>, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            user_repo,
+            clock,
+            page_limits: PageLimits::default(),
+        }
+    }
+
+    /// Size listing pages by `limits`.
+    pub const fn with_page_limits(mut self, limits: PageLimits) -> Self {
+        self.page_limits = limits;
+        self
     }
 }
@@ -1,4 +1,5 @@
 use crate::application::{AppResult, error::AppError};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 
 /// Generate a lowercase hyphenated RFC 4122 version 4 identifier string.
 ///
@@ -17,6 +18,19 @@ pub fn v4_string() -> AppResult<String> {
     Ok(encode_hyphenated_lowercase(&bytes))
 }
 
+/// Generate an unguessable token of 256 random bits, base64url-encoded.
+///
+/// # Errors
+///
+/// Returns an error if the operating system random source cannot provide
+/// enough entropy.
+pub fn secret_token() -> AppResult<String> {
+    let mut bytes = [0_u8; 32];
+    getrandom::fill(&mut bytes)
+        .map_err(|err| AppError::infrastructure(format!("failed to generate token: {err}")))?;
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
 fn encode_hyphenated_lowercase(bytes: &[u8; 16]) -> String {
     let mut value = String::with_capacity(36);
 
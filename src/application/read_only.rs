@@ -0,0 +1,65 @@
+// src/application/read_only.rs
+//! Process-wide read-only mode.
+//!
+//! Operators turn it on while traffic is served from a standby database
+//! during failover. The HTTP layer rejects mutating requests and the command
+//! services refuse writes, so a route that slips past the first check still
+//! cannot write.
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::application::{AppError, AppResult};
+
+/// Shared on/off switch; clones observe the same state.
+#[derive(Debug, Clone, Default)]
+pub struct ReadOnlySwitch(Arc<AtomicBool>);
+
+impl ReadOnlySwitch {
+    #[must_use]
+    pub fn new(enabled: bool) -> Self {
+        Self(Arc::new(AtomicBool::new(enabled)))
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Turn read-only mode on or off; returns the previous state.
+    #[must_use = "the previous state tells whether anything changed"]
+    pub fn set(&self, enabled: bool) -> bool {
+        self.0.swap(enabled, Ordering::Relaxed)
+    }
+
+    /// Fail while read-only mode is on.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Unavailable` when read-only mode is on.
+    pub fn ensure_writable(&self) -> AppResult<()> {
+        if self.is_enabled() {
+            Err(AppError::unavailable("the service is in read-only mode"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_the_switch() {
+        let switch = ReadOnlySwitch::default();
+        let observer = switch.clone();
+        assert!(observer.ensure_writable().is_ok());
+
+        assert!(!switch.set(true));
+        assert!(observer.is_enabled());
+        assert!(matches!(
+            observer.ensure_writable(),
+            Err(AppError::Unavailable(_))
+        ));
+    }
+}
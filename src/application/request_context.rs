@@ -0,0 +1,86 @@
+// src/application/request_context.rs
+//! Details of the request a command runs on behalf of.
+//!
+//! The HTTP layer runs each request inside [`scope`] so services can read the
+//! caller's network details with [`current`] without threading them through
+//! every command. Work started outside a request (jobs, the CLI, tests) sees
+//! an empty context.
+//!
+//! Authentication happens after the scope is entered, so the admin behind an
+//! impersonation token is recorded separately with [`set_impersonator`].
+use crate::application::ports::security_events::ClientInfo;
+use crate::domain::UserId;
+use std::cell::Cell;
+use std::future::Future;
+
+tokio::task_local! {
+    static CURRENT: RequestContext;
+    static IMPERSONATOR: Cell<Option<UserId>>;
+}
+
+/// What the application knows about the request being served.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestContext {
+    pub client: ClientInfo,
+}
+
+/// Run `fut` with `context` as the current request context.
+pub async fn scope<F>(context: RequestContext, fut: F) -> F::Output
+where
+    F: Future,
+{
+    CURRENT
+        .scope(context, IMPERSONATOR.scope(Cell::new(None), fut))
+        .await
+}
+
+/// Record the admin acting through the request's token, if any. No-op
+/// outside [`scope`].
+pub fn set_impersonator(impersonator: Option<UserId>) {
+    let _ = IMPERSONATOR.try_with(|current| current.set(impersonator));
+}
+
+/// Admin impersonating the authenticated user of the request being served.
+#[must_use]
+pub fn impersonator() -> Option<UserId> {
+    IMPERSONATOR.try_with(Cell::get).ok().flatten()
+}
+
+/// Context of the request being served, or an empty one outside [`scope`].
+#[must_use]
+pub fn current() -> RequestContext {
+    CURRENT.try_with(Clone::clone).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn current_is_empty_outside_a_scope() {
+        assert_eq!(current(), RequestContext::default());
+
+        let context = RequestContext {
+            client: ClientInfo {
+                ip_address: Some("203.0.113.9".into()),
+                user_agent: Some("curl/8.0".into()),
+            },
+        };
+        let seen = scope(context.clone(), async { current() }).await;
+        assert_eq!(seen, context);
+    }
+
+    #[tokio::test]
+    async fn impersonator_is_only_kept_inside_a_scope() {
+        let admin = UserId::new(1).unwrap();
+        set_impersonator(Some(admin));
+        assert_eq!(impersonator(), None);
+
+        let seen = scope(RequestContext::default(), async {
+            set_impersonator(Some(admin));
+            impersonator()
+        })
+        .await;
+        assert_eq!(seen, Some(admin));
+    }
+}
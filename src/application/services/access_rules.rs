@@ -0,0 +1,324 @@
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+
+use crate::application::{
+    AccessRuleDto, AppError, AppResult, AuthenticatedUser,
+    ports::{geoip::AsnLookup, security_events::ClientInfo, time::Clock},
+};
+use crate::domain::{
+    AccessRule, AccessRuleRepository, IpRange, NewAccessRule, RouteGroup, RuleAction, RuleTarget,
+    audit::{entity::NewAuditLog, repository::AuditLogRepository},
+};
+
+/// Priority given to rules created without one.
+pub const DEFAULT_RULE_PRIORITY: i32 = 100;
+
+/// How long rules are served from memory before being read again, so rules
+/// changed on another instance take effect within this time.
+const RULE_CACHE_TTL: Duration = Duration::seconds(30);
+
+pub struct CreateAccessRuleCommand {
+    pub group: RouteGroup,
+    pub action: RuleAction,
+    /// Exactly one of `cidr` and `asn` must be set.
+    pub cidr: Option<String>,
+    pub asn: Option<u32>,
+    pub priority: Option<i32>,
+    pub note: Option<String>,
+}
+
+/// Collaborators `AccessRuleService` acts through.
+pub struct AccessRulePorts {
+    pub rule_repo: Arc<dyn AccessRuleRepository>,
+    pub audit_log_repo: Arc<dyn AuditLogRepository>,
+    /// Resolves ASNs for `asn` rules; without it such rules cannot be
+    /// created.
+    pub asn_lookup: Option<Arc<dyn AsnLookup>>,
+}
+
+struct CachedRules {
+    loaded_at: DateTime<Utc>,
+    rules: Arc<[AccessRule]>,
+}
+
+/// Operator-managed allow/deny rules checked before authentication on the
+/// route groups they name.
+///
+/// Every rule a request matches, and every change to the rules, is written
+/// to the audit log.
+pub struct AccessRuleService {
+    ports: AccessRulePorts,
+    clock: Arc<dyn Clock>,
+    cache: Mutex<Option<CachedRules>>,
+}
+
+impl AccessRuleService {
+    #[must_use]
+    pub fn new(ports: AccessRulePorts, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            ports,
+            clock,
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Decide whether `client` may reach `path` in `group`. Requests without
+    /// a known client address match no rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` when a `deny` rule matches first, or an error if
+    /// the rules cannot be loaded.
+    pub async fn check(&self, group: RouteGroup, client: &ClientInfo, path: &str) -> AppResult<()> {
+        let Some(ip) = client
+            .ip_address
+            .as_deref()
+            .and_then(|ip| ip.parse::<IpAddr>().ok())
+        else {
+            return Ok(());
+        };
+        let rules = self.rules().await?;
+        let asn = self
+            .ports
+            .asn_lookup
+            .as_ref()
+            .and_then(|lookup| lookup.asn(ip));
+        let Some(rule) = AccessRule::first_match(&rules, group, ip, asn, self.clock.now()) else {
+            return Ok(());
+        };
+        // Temporary blocks are audited when placed; auditing every request a
+        // blocked scanner keeps sending would only add noise.
+        if rule.action == RuleAction::Deny && rule.expires_at.is_some() {
+            return Err(AppError::forbidden("access denied by rule"));
+        }
+
+        let entry = NewAuditLog {
+            user_id: None,
+            impersonator_id: None,
+            action: format!("access_rule.{}", rule.action),
+            resource_type: "access_rule".into(),
+            resource_id: Some(rule.id),
+            details: Some(json!({ "group": group, "path": path, "asn": asn })),
+            ip_address: client.ip_address.clone(),
+            user_agent: client.user_agent.clone(),
+        };
+        if let Err(err) = self.ports.audit_log_repo.insert(entry).await {
+            tracing::warn!(error = %err, rule = rule.id, "failed to audit access rule hit");
+        }
+        match rule.action {
+            RuleAction::Allow => Ok(()),
+            RuleAction::Deny => Err(AppError::forbidden("access denied by rule")),
+        }
+    }
+
+    /// Every rule, in evaluation order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `access_rules:manage` or the rules
+    /// cannot be loaded.
+    pub async fn list(&self, actor: &AuthenticatedUser) -> AppResult<Vec<AccessRuleDto>> {
+        ensure_can_manage(actor)?;
+        let rules = self.ports.rule_repo.list().await?;
+        Ok(rules.into_iter().map(AccessRuleDto::from).collect())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `access_rules:manage`, the rule
+    /// names neither or both of a CIDR range and an ASN, an ASN rule is
+    /// created without an ASN database, or persistence fails.
+    pub async fn create(
+        &self,
+        actor: &AuthenticatedUser,
+        client: &ClientInfo,
+        command: CreateAccessRuleCommand,
+    ) -> AppResult<AccessRuleDto> {
+        ensure_can_manage(actor)?;
+        let target = match (command.cidr, command.asn) {
+            (Some(cidr), None) => RuleTarget::Cidr(IpRange::new(&cidr)?),
+            (None, Some(_)) if self.ports.asn_lookup.is_none() => {
+                return Err(AppError::validation(
+                    "ASN rules need an ASN database (ASN_DATABASE_PATHS)",
+                ));
+            }
+            (None, Some(asn)) => RuleTarget::Asn(asn),
+            _ => {
+                return Err(AppError::validation(
+                    "a rule needs exactly one of cidr and asn",
+                ));
+            }
+        };
+        if let Some(note) = &command.note
+            && note.chars().count() > AccessRule::MAX_NOTE_CHARS
+        {
+            return Err(AppError::validation(format!(
+                "note must be at most {} characters",
+                AccessRule::MAX_NOTE_CHARS
+            )));
+        }
+
+        let rule = self
+            .ports
+            .rule_repo
+            .insert(NewAccessRule {
+                group: command.group,
+                action: command.action,
+                target,
+                priority: command.priority.unwrap_or(DEFAULT_RULE_PRIORITY),
+                note: command.note,
+                created_by: Some(actor.id),
+                created_at: self.clock.now(),
+                expires_at: None,
+            })
+            .await?;
+        self.invalidate();
+        let rule = AccessRuleDto::from(rule);
+        self.ports
+            .audit_log_repo
+            .insert(NewAuditLog {
+                user_id: Some(actor.id),
+                impersonator_id: actor.impersonator,
+                action: "access_rule.create".into(),
+                resource_type: "access_rule".into(),
+                resource_id: Some(rule.id),
+                details: Some(json!(rule)),
+                ip_address: client.ip_address.clone(),
+                user_agent: client.user_agent.clone(),
+            })
+            .await?;
+        Ok(rule)
+    }
+
+    /// Deny `ip` on every route for `duration`, unless an unexpired
+    /// temporary block already covers it. Expired rules are purged first.
+    /// Returns the rule created, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `duration` is out of range or persistence fails.
+    pub async fn block_temporarily(
+        &self,
+        ip: IpAddr,
+        duration: std::time::Duration,
+        reason: &str,
+        client: &ClientInfo,
+    ) -> AppResult<Option<AccessRuleDto>> {
+        let duration = Duration::from_std(duration)
+            .map_err(|_| AppError::validation("block duration is out of range"))?;
+        let now = self.clock.now();
+        self.ports.rule_repo.delete_expired(now).await?;
+        let rules = self.rules().await?;
+        let already_blocked = rules.iter().any(|rule| {
+            rule.group == RouteGroup::Site
+                && rule.action == RuleAction::Deny
+                && rule.expires_at.is_some()
+                && !rule.is_expired(now)
+                && rule.matches(ip, None)
+        });
+        if already_blocked {
+            return Ok(None);
+        }
+
+        let ip = ip.to_canonical();
+        let rule = self
+            .ports
+            .rule_repo
+            .insert(NewAccessRule {
+                group: RouteGroup::Site,
+                action: RuleAction::Deny,
+                target: RuleTarget::Cidr(IpRange::new(&ip.to_string())?),
+                priority: DEFAULT_RULE_PRIORITY,
+                note: Some(reason.chars().take(AccessRule::MAX_NOTE_CHARS).collect()),
+                created_by: None,
+                created_at: now,
+                expires_at: Some(now + duration),
+            })
+            .await?;
+        self.invalidate();
+        let rule = AccessRuleDto::from(rule);
+        self.ports
+            .audit_log_repo
+            .insert(NewAuditLog {
+                user_id: None,
+                impersonator_id: None,
+                action: "access_rule.create".into(),
+                resource_type: "access_rule".into(),
+                resource_id: Some(rule.id),
+                details: Some(json!(rule)),
+                ip_address: client.ip_address.clone(),
+                user_agent: client.user_agent.clone(),
+            })
+            .await?;
+        Ok(Some(rule))
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `access_rules:manage`, there is
+    /// no rule with `id`, or persistence fails.
+    pub async fn delete(
+        &self,
+        actor: &AuthenticatedUser,
+        client: &ClientInfo,
+        id: i64,
+    ) -> AppResult<()> {
+        ensure_can_manage(actor)?;
+        if !self.ports.rule_repo.delete(id).await? {
+            return Err(AppError::not_found("access rule not found"));
+        }
+        self.invalidate();
+        self.ports
+            .audit_log_repo
+            .insert(NewAuditLog {
+                user_id: Some(actor.id),
+                impersonator_id: actor.impersonator,
+                action: "access_rule.delete".into(),
+                resource_type: "access_rule".into(),
+                resource_id: Some(id),
+                details: None,
+                ip_address: client.ip_address.clone(),
+                user_agent: client.user_agent.clone(),
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn rules(&self) -> AppResult<Arc<[AccessRule]>> {
+        let now = self.clock.now();
+        let cached = self
+            .cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .filter(|cached| now - cached.loaded_at < RULE_CACHE_TTL)
+            .map(|cached| Arc::clone(&cached.rules));
+        if let Some(rules) = cached {
+            return Ok(rules);
+        }
+
+        let rules: Arc<[AccessRule]> = self.ports.rule_repo.list().await?.into();
+        *self.cache.lock().unwrap_or_else(PoisonError::into_inner) = Some(CachedRules {
+            loaded_at: now,
+            rules: Arc::clone(&rules),
+        });
+        Ok(rules)
+    }
+
+    fn invalidate(&self) {
+        *self.cache.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
+
+fn ensure_can_manage(actor: &AuthenticatedUser) -> AppResult<()> {
+    if actor.has_capability("access_rules", "manage") {
+        Ok(())
+    } else {
+        Err(AppError::forbidden(
+            "missing capability access_rules:manage",
+        ))
+    }
+}
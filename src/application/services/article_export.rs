@@ -0,0 +1,285 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::application::{
+    AppError, AppResult, ArticleDto, ArticleExportJobDto, AuthenticatedUser, ExportFormat,
+    ExportJobStatus, ExportedFile,
+    ports::{exports::ArticleRenderer, jobs::JobQueue, time::Clock},
+    queries::articles::{ArticleQueryService, GetArticleByIdQuery},
+    random_id,
+};
+use crate::domain::UserId;
+
+use super::downloads::{DownloadLinkService, DownloadTarget};
+
+/// Bodies longer than this are rendered on the job queue instead of inline.
+pub const INLINE_EXPORT_MAX_CHARS: usize = 20_000;
+
+/// Finished jobs beyond this many are forgotten, oldest first. Each one holds
+/// its rendered file, so keep this small.
+const MAX_RETAINED_JOBS: usize = 20;
+
+/// Result of asking for an export.
+#[derive(Debug)]
+pub enum ArticleExport {
+    /// The article was small enough to render during the request.
+    Ready(ExportedFile),
+    /// Rendering continues in the background; poll the job.
+    Queued(ArticleExportJobDto),
+}
+
+struct ExportJob {
+    dto: ArticleExportJobDto,
+    /// `None` for anonymous requests, whose job id alone grants access.
+    requested_by: Option<UserId>,
+    file: Option<ExportedFile>,
+}
+
+#[derive(Default)]
+struct JobTable {
+    jobs: HashMap<String, ExportJob>,
+    order: VecDeque<String>,
+}
+
+type SharedJobs = Arc<Mutex<JobTable>>;
+
+fn with_jobs<T>(jobs: &SharedJobs, f: impl FnOnce(&mut JobTable) -> T) -> T {
+    let mut table = jobs.lock().unwrap_or_else(PoisonError::into_inner);
+    f(&mut table)
+}
+
+/// Renders articles as PDF or EPUB for offline reading.
+///
+/// Large articles are rendered on the job queue. Their files are held in
+/// memory until evicted, so they are lost on restart.
+pub struct ArticleExportService {
+    article_queries: Arc<ArticleQueryService>,
+    renderer: Option<Arc<dyn ArticleRenderer>>,
+    clock: Arc<dyn Clock>,
+    job_queue: Arc<dyn JobQueue>,
+    links: Arc<DownloadLinkService>,
+    jobs: SharedJobs,
+}
+
+impl ArticleExportService {
+    /// `renderer` is `None` when the server was built without export support.
+    #[must_use]
+    pub fn new(
+        article_queries: Arc<ArticleQueryService>,
+        renderer: Option<Arc<dyn ArticleRenderer>>,
+        clock: Arc<dyn Clock>,
+        job_queue: Arc<dyn JobQueue>,
+        links: Arc<DownloadLinkService>,
+    ) -> Self {
+        Self {
+            article_queries,
+            renderer,
+            clock,
+            job_queue,
+            links,
+            jobs: SharedJobs::default(),
+        }
+    }
+
+    /// Render an article the actor can view, or queue it if it is large.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if exports are disabled, the format is unknown, the
+    /// article is missing or hidden from the actor, or rendering fails.
+    pub async fn export(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        article_id: i64,
+        format: &str,
+    ) -> AppResult<ArticleExport> {
+        let format = ExportFormat::parse(format)?;
+        let renderer = self
+            .renderer
+            .as_ref()
+            .ok_or_else(|| AppError::not_found("article export is not enabled"))?;
+        let article = self
+            .article_queries
+            .get_visible_article_by_id(actor, GetArticleByIdQuery { id: article_id })
+            .await?;
+
+        if article.body.chars().count() <= INLINE_EXPORT_MAX_CHARS {
+            let bytes = renderer.render(&article, format)?;
+            return Ok(ArticleExport::Ready(exported_file(&article, format, bytes)));
+        }
+
+        let id = random_id::v4_string()?;
+        let dto = ArticleExportJobDto {
+            id: id.clone(),
+            article_id: article.id,
+            format,
+            status: ExportJobStatus::Queued,
+            download_url: None,
+            error: None,
+            created_at: self.clock.now(),
+            finished_at: None,
+        };
+        with_jobs(&self.jobs, |table| {
+            table.order.push_back(id.clone());
+            table.jobs.insert(
+                id.clone(),
+                ExportJob {
+                    dto: dto.clone(),
+                    requested_by: actor.map(|actor| actor.id),
+                    file: None,
+                },
+            );
+            evict_finished(table);
+        });
+
+        let run = ExportRun {
+            renderer: Arc::clone(renderer),
+            clock: Arc::clone(&self.clock),
+            jobs: Arc::clone(&self.jobs),
+            job_id: id,
+        };
+        self.job_queue
+            .enqueue("article_export", Box::pin(run.execute(article, format)));
+
+        Ok(ArticleExport::Queued(dto))
+    }
+
+    /// Current state of an export job. Completed jobs carry a freshly signed
+    /// download link.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the job is unknown or belongs to another user, or
+    /// the link cannot be signed.
+    pub fn job(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        id: &str,
+    ) -> AppResult<ArticleExportJobDto> {
+        let mut dto = with_jobs(&self.jobs, |table| {
+            visible_job(table, actor, id).map(|job| job.dto.clone())
+        })?;
+        if dto.status == ExportJobStatus::Completed {
+            let target = DownloadTarget::ArticleExport(dto.id.clone());
+            dto.download_url = Some(self.links.link(&target)?);
+        }
+        Ok(dto)
+    }
+
+    /// The file produced by a completed export job.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the job is unknown, belongs to another user, or
+    /// has not completed.
+    pub fn download(&self, actor: Option<&AuthenticatedUser>, id: &str) -> AppResult<ExportedFile> {
+        with_jobs(&self.jobs, |table| {
+            let job = visible_job(table, actor, id)?;
+            job.file.clone().ok_or_else(|| {
+                AppError::conflict(format!("export job is {}", job.dto.status.as_str()))
+            })
+        })
+    }
+
+    /// The file of job `id`, for a signed download link. The link stands in
+    /// for the requester, so ownership is not checked again.
+    ///
+    /// # Errors
+    ///
+    /// Returns not found if the job is unknown, evicted or not completed.
+    pub fn download_for_link(&self, id: &str) -> AppResult<ExportedFile> {
+        with_jobs(&self.jobs, |table| {
+            table
+                .jobs
+                .get(id)
+                .and_then(|job| job.file.clone())
+                .ok_or_else(|| AppError::not_found("export file not found"))
+        })
+    }
+}
+
+fn visible_job<'a>(
+    table: &'a JobTable,
+    actor: Option<&AuthenticatedUser>,
+    id: &str,
+) -> AppResult<&'a ExportJob> {
+    table
+        .jobs
+        .get(id)
+        .filter(|job| {
+            job.requested_by
+                .is_none_or(|owner| actor.is_some_and(|actor| actor.id == owner))
+        })
+        .ok_or_else(|| AppError::not_found("export job not found"))
+}
+
+fn exported_file(article: &ArticleDto, format: ExportFormat, bytes: Vec<u8>) -> ExportedFile {
+    ExportedFile {
+        file_name: format!("{}.{}", article.slug, format.extension()),
+        content_type: format.content_type(),
+        bytes,
+    }
+}
+
+fn evict_finished(table: &mut JobTable) {
+    while table.order.len() > MAX_RETAINED_JOBS {
+        let Some(position) = table.order.iter().position(|id| {
+            table.jobs.get(id).is_none_or(|job| {
+                matches!(
+                    job.dto.status,
+                    ExportJobStatus::Completed | ExportJobStatus::Failed
+                )
+            })
+        }) else {
+            return;
+        };
+        if let Some(id) = table.order.remove(position) {
+            table.jobs.remove(&id);
+        }
+    }
+}
+
+struct ExportRun {
+    renderer: Arc<dyn ArticleRenderer>,
+    clock: Arc<dyn Clock>,
+    jobs: SharedJobs,
+    job_id: String,
+}
+
+impl ExportRun {
+    async fn execute(self, article: ArticleDto, format: ExportFormat) {
+        self.update(|job| job.dto.status = ExportJobStatus::Running);
+
+        let renderer = Arc::clone(&self.renderer);
+        let outcome = tokio::task::spawn_blocking(move || {
+            renderer
+                .render(&article, format)
+                .map(|bytes| exported_file(&article, format, bytes))
+        })
+        .await
+        .unwrap_or_else(|err| Err(AppError::infrastructure(err.to_string())));
+
+        let finished_at = self.clock.now();
+        self.update(|job| {
+            match outcome {
+                Ok(file) => {
+                    job.dto.status = ExportJobStatus::Completed;
+                    job.file = Some(file);
+                }
+                Err(err) => {
+                    job.dto.status = ExportJobStatus::Failed;
+                    job.dto.error = Some(err.to_string());
+                }
+            }
+            job.dto.finished_at = Some(finished_at);
+        });
+    }
+
+    fn update(&self, f: impl FnOnce(&mut ExportJob)) {
+        with_jobs(&self.jobs, |table| {
+            if let Some(job) = table.jobs.get_mut(&self.job_id) {
+                f(job);
+            }
+        });
+    }
+}
@@ -0,0 +1,60 @@
+// src/application/services/article_views.rs
+use std::sync::Arc;
+
+use crate::application::{AppResult, ports::views::ViewCounter};
+use crate::domain::ArticleWriteRepository;
+
+/// Moves buffered article reads from the view counter into the article rows.
+pub struct ArticleViewService {
+    counter: Arc<dyn ViewCounter>,
+    write_repo: Arc<dyn ArticleWriteRepository>,
+}
+
+impl ArticleViewService {
+    #[must_use]
+    pub fn new(counter: Arc<dyn ViewCounter>, write_repo: Arc<dyn ArticleWriteRepository>) -> Self {
+        Self {
+            counter,
+            write_repo,
+        }
+    }
+
+    /// Add every pending count to its article and return how many views were
+    /// flushed. Counts the repository refuses are put back for the next run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the counter cannot be drained or the repository
+    /// rejects the batch.
+    pub async fn flush(&self) -> AppResult<u64> {
+        let pending = self.counter.drain().await?;
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        if let Err(err) = self.write_repo.add_views(&pending).await {
+            for (article_id, views) in pending {
+                if let Err(restore) = self.counter.add(article_id, views).await {
+                    tracing::warn!(
+                        article_id = i64::from(article_id),
+                        views,
+                        error = %restore,
+                        "dropped article views that failed to flush"
+                    );
+                }
+            }
+            return Err(err.into());
+        }
+
+        let flushed = pending
+            .iter()
+            .fold(0_u64, |total, (_, views)| total.saturating_add(*views));
+        tracing::info!(
+            target: "article_views",
+            articles = pending.len(),
+            views = flushed,
+            "article views flushed"
+        );
+        Ok(flushed)
+    }
+}
@@ -0,0 +1,85 @@
+// src/application/services/audit_recorder.rs
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::{
+    application::{ports::security_events::ClientInfo, request_context},
+    domain::{
+        UserId,
+        audit::{entity::NewAuditLog, repository::AuditLogRepository},
+    },
+};
+
+/// A change worth an audit log entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct AuditEvent {
+    pub action: &'static str,
+    pub resource_type: &'static str,
+    pub resource_id: Option<i64>,
+    pub details: Option<Value>,
+}
+
+impl AuditEvent {
+    pub const fn new(
+        action: &'static str,
+        resource_type: &'static str,
+        resource_id: Option<i64>,
+    ) -> Self {
+        Self {
+            action,
+            resource_type,
+            resource_id,
+            details: None,
+        }
+    }
+
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+/// Writes audit log entries for successful commands, if a repository is
+/// configured.
+///
+/// The client address, user agent and any impersonating admin come from the
+/// current [`request_context`].
+#[derive(Clone, Default)]
+pub struct AuditRecorder(Option<Arc<dyn AuditLogRepository>>);
+
+impl AuditRecorder {
+    pub fn new(repo: Arc<dyn AuditLogRepository>) -> Self {
+        Self(Some(repo))
+    }
+
+    /// Record `event` as done by `actor`. The change has already taken
+    /// effect, so a failed insert is logged rather than returned.
+    pub async fn record(&self, actor: Option<UserId>, event: AuditEvent) {
+        self.record_from(&request_context::current().client, actor, event)
+            .await;
+    }
+
+    /// Like [`record`](Self::record), for callers that know the client
+    /// themselves, such as logins.
+    pub async fn record_from(&self, client: &ClientInfo, actor: Option<UserId>, event: AuditEvent) {
+        let Some(repo) = &self.0 else {
+            return;
+        };
+        let action = event.action;
+        let entry = NewAuditLog {
+            user_id: actor,
+            impersonator_id: request_context::impersonator(),
+            action: action.into(),
+            resource_type: event.resource_type.into(),
+            resource_id: event.resource_id,
+            details: event.details,
+            ip_address: client.ip_address.clone(),
+            user_agent: client.user_agent.clone(),
+        };
+        if let Err(err) = repo.insert(entry).await {
+            tracing::warn!(error = %err, action, "failed to write audit log entry");
+        }
+    }
+}
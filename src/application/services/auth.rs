@@ -6,7 +6,7 @@ use serde_json::Value as JsonValue;
 use sha2::{Digest, Sha256};
 
 use crate::application::{
-    AppError, AppResult, AuthTokenDto, AuthenticatedUser, TokenSubject,
+    AppError, AppResult, AuthTokenDto, AuthenticatedUser, IdTokenClaims, TokenSubject,
     ports::{
         authorization_code::{Code, CodeStore},
         security::TokenManager,
@@ -23,6 +23,7 @@ pub struct IssueAuthorizationCodeRequest {
     pub scope: Option<String>,
     pub code_challenge: Option<String>,
     pub code_challenge_method: Option<String>,
+    pub nonce: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -35,6 +36,17 @@ pub struct ExchangeAuthorizationCodeRequest {
     pub code: String,
     pub redirect_uri: Option<String>,
     pub code_verifier: Option<String>,
+    /// `iss` of the `id_token` issued for codes with the `openid` scope.
+    /// Without an issuer no `id_token` is issued.
+    pub issuer: Option<String>,
+}
+
+/// Tokens issued for an authorization code.
+#[derive(Debug, Clone)]
+pub struct AuthorizationCodeTokens {
+    pub token: AuthTokenDto,
+    /// Present when the code was issued for the `openid` scope.
+    pub id_token: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -158,6 +170,7 @@ impl AuthService {
             scope: request.scope,
             code_challenge: request.code_challenge,
             code_challenge_method: request.code_challenge_method,
+            nonce: request.nonce,
             created_at: now,
             expires_at: now + Duration::minutes(5),
         };
@@ -166,7 +179,8 @@ impl AuthService {
         Ok(IssueAuthorizationCodeResult { code })
     }
 
-    /// Exchange an authorization code for tokens.
+    /// Exchange an authorization code for tokens, including an `id_token`
+    /// when the code was issued for the `openid` scope.
     ///
     /// # Errors
     ///
@@ -175,7 +189,7 @@ impl AuthService {
     pub async fn exchange_authorization_code(
         &self,
         request: ExchangeAuthorizationCodeRequest,
-    ) -> AppResult<AuthTokenDto> {
+    ) -> AppResult<AuthorizationCodeTokens> {
         let stored = self
             .authorization_code_store
             .consume_code(&request.code)
@@ -185,7 +199,25 @@ impl AuthService {
         Self::validate_exchange_redirect_uri(&stored, request.redirect_uri.as_deref())?;
         Self::verify_pkce(&stored, request.code_verifier.as_deref())?;
 
-        self.token_manager.issue(stored.subject).await
+        let openid = stored
+            .scope
+            .as_deref()
+            .is_some_and(|scope| scope.split_whitespace().any(|scope| scope == "openid"));
+        let token = self.token_manager.issue(stored.subject.clone()).await?;
+        let id_token = match request.issuer.filter(|_| openid) {
+            Some(iss) => self.token_manager.issue_id_token(&IdTokenClaims {
+                aud: stored.client_id.unwrap_or_else(|| iss.clone()),
+                iss,
+                sub: i64::from(stored.subject.user_id).to_string(),
+                iat: token.issued_at.timestamp(),
+                exp: token.expires_at.timestamp(),
+                nonce: stored.nonce,
+                preferred_username: stored.subject.username,
+                sid: token.session_id.clone(),
+            })?,
+            None => None,
+        };
+        Ok(AuthorizationCodeTokens { token, id_token })
     }
 
     /// Introspect a raw token without enforcing revocation state.
@@ -347,7 +379,7 @@ mod tests {
     };
     use crate::{
         application::{
-            AppError, AuthTokenDto, AuthenticatedUser, TokenSubject,
+            AppError, AuthTokenDto, AuthenticatedUser, IdTokenClaims, TokenSubject,
             ports::{
                 security::TokenManager,
                 session_revocation::{Revocation, TokenVersionStore},
@@ -411,6 +443,15 @@ mod tests {
         fn public_jwk(&self) -> BoxFuture<'_, crate::application::AppResult<serde_json::Value>> {
             boxed(async move { Ok(serde_json::json!({ "keys": [] })) })
         }
+
+        fn issue_id_token(
+            &self,
+            claims: &IdTokenClaims,
+        ) -> crate::application::AppResult<Option<String>> {
+            Ok(Some(
+                serde_json::to_string(claims).expect("serialize claims"),
+            ))
+        }
     }
 
     fn authenticated_user() -> AuthenticatedUser {
@@ -432,6 +473,8 @@ mod tests {
             expires_at,
             session_id: Some("sid-42".into()),
             token_version: Some(1),
+            custom_role: None,
+            impersonator: None,
         }
     }
 
@@ -508,6 +551,7 @@ mod tests {
                     scope: None,
                     code_challenge: None,
                     code_challenge_method: None,
+                    nonce: None,
                 },
             )
             .await
@@ -531,6 +575,7 @@ mod tests {
                     scope: Some("openid".into()),
                     code_challenge: Some("verifier".into()),
                     code_challenge_method: Some("plain".into()),
+                    nonce: None,
                 },
             )
             .await
@@ -541,6 +586,7 @@ mod tests {
                 code: issued.code.clone(),
                 redirect_uri: Some("https://other.example/callback".into()),
                 code_verifier: Some("verifier".into()),
+                issuer: None,
             })
             .await
             .expect_err("redirect mismatch should fail");
@@ -557,6 +603,7 @@ mod tests {
                     scope: Some("openid".into()),
                     code_challenge: Some("verifier".into()),
                     code_challenge_method: Some("plain".into()),
+                    nonce: None,
                 },
             )
             .await
@@ -567,12 +614,58 @@ mod tests {
                 code: issued.code,
                 redirect_uri: Some("https://client.example/callback".into()),
                 code_verifier: Some("wrong".into()),
+                issuer: None,
             })
             .await
             .expect_err("invalid pkce should fail");
         assert!(matches!(pkce_err, AppError::Validation(msg) if msg == "invalid code_verifier"));
     }
 
+    #[tokio::test]
+    async fn openid_codes_also_yield_an_id_token() {
+        let user = authenticated_user();
+        let (service, _session_store, _auth_code_store) = build_service(user.clone());
+        let exchange = |scope: &str| {
+            let (service, user, scope) = (service.clone(), user.clone(), scope.to_string());
+            async move {
+                let issued = service
+                    .issue_authorization_code(
+                        &user,
+                        IssueAuthorizationCodeRequest {
+                            client_id: Some("client-id".into()),
+                            redirect_uri: None,
+                            scope: Some(scope),
+                            code_challenge: None,
+                            code_challenge_method: None,
+                            nonce: Some("n-1".into()),
+                        },
+                    )
+                    .await
+                    .expect("issue auth code");
+                service
+                    .exchange_authorization_code(ExchangeAuthorizationCodeRequest {
+                        code: issued.code,
+                        redirect_uri: None,
+                        code_verifier: None,
+                        issuer: Some("https://cms.example.com".into()),
+                    })
+                    .await
+                    .expect("exchange code")
+            }
+        };
+
+        let tokens = exchange("openid profile").await;
+        let claims: IdTokenClaims =
+            serde_json::from_str(&tokens.id_token.expect("id_token")).expect("claims");
+        assert_eq!(claims.iss, "https://cms.example.com");
+        assert_eq!(claims.aud, "client-id");
+        assert_eq!(claims.sub, "42");
+        assert_eq!(claims.nonce.as_deref(), Some("n-1"));
+        assert_eq!(claims.exp, tokens.token.expires_at.timestamp());
+
+        assert!(exchange("profile").await.id_token.is_none());
+    }
+
     #[tokio::test]
     async fn introspect_invalid_token_is_inactive() {
         let user = authenticated_user();
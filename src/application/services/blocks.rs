@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser, UserBlockDto, ports::time::Clock,
+};
+use crate::domain::{BlockList, UserBlock, UserId, UserRepository};
+
+/// The people each user has blocked.
+///
+/// Services that let one user reach another, such as adding them as an
+/// article contributor, call [`BlockListService::ensure_can_interact`]
+/// before acting.
+pub struct BlockListService {
+    block_list: Arc<dyn BlockList>,
+    user_repo: Arc<dyn UserRepository>,
+    clock: Arc<dyn Clock>,
+}
+
+impl BlockListService {
+    #[must_use]
+    pub fn new(
+        block_list: Arc<dyn BlockList>,
+        user_repo: Arc<dyn UserRepository>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            block_list,
+            user_repo,
+            clock,
+        }
+    }
+
+    /// Block `user_id`. Blocking someone already blocked returns the
+    /// existing entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user does not exist, is the actor, or
+    /// persistence fails.
+    pub async fn block(&self, actor: &AuthenticatedUser, user_id: i64) -> AppResult<UserBlockDto> {
+        let target = UserId::new(user_id)?;
+        if target == actor.id {
+            return Err(AppError::validation("users cannot block themselves"));
+        }
+        if self.user_repo.find_by_id(target).await?.is_none() {
+            return Err(AppError::not_found("user not found"));
+        }
+        let block = UserBlock::new(actor.id, target, self.clock.now())?;
+        Ok(self.block_list.add(block).await?.into())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the actor had not blocked the user, or
+    /// persistence fails.
+    pub async fn unblock(&self, actor: &AuthenticatedUser, user_id: i64) -> AppResult<()> {
+        let target = UserId::new(user_id)?;
+        if self.block_list.remove(actor.id, target).await? {
+            Ok(())
+        } else {
+            Err(AppError::not_found("block not found"))
+        }
+    }
+
+    /// Everyone the actor has blocked, most recent first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if persistence fails.
+    pub async fn list(&self, actor: &AuthenticatedUser) -> AppResult<Vec<UserBlockDto>> {
+        let blocks = self.block_list.list(actor.id).await?;
+        Ok(blocks.into_iter().map(UserBlockDto::from).collect())
+    }
+
+    /// Check that `actor` may reach `target`, for example by adding them to
+    /// an article.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Forbidden` if `target` has blocked `actor`, or an error if
+    /// persistence fails.
+    pub async fn ensure_can_interact(&self, actor: UserId, target: UserId) -> AppResult<()> {
+        if self.block_list.find(target, actor).await?.is_some() {
+            return Err(AppError::forbidden("this user has blocked you"));
+        }
+        Ok(())
+    }
+}
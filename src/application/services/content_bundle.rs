@@ -0,0 +1,674 @@
+//! Moving content between environments.
+//!
+//! Exports walk every article, newest first, a page at a time, so NDJSON
+//! bundles can be streamed as they are read. Imports are applied directly to
+//! the repositories, like seeds: nothing is journaled, and revision history
+//! is replayed with its content and editors but stamped with the import time.
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+use super::content_bundle_format::{self as format, ParsedBundle, REVISIONS_ENTRY, USERS_ENTRY};
+use super::slug_cache::SlugCache;
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser, BundleArticle, BundleFormat, BundleImportDto,
+    BundleImportItem, BundleItemStatus, BundleManifest, BundleRecord, BundleRevision, BundleUser,
+    ConflictStrategy, ReadOnlySwitch,
+    commands::users::unusable_password_hash,
+    dto::bundles::{BUNDLE_FORMAT, BUNDLE_VERSION, MAX_BUNDLE_BYTES},
+    ports::{
+        archives::{ArchiveEntry, Archiver},
+        security::PasswordHasher,
+        time::Clock,
+    },
+};
+use crate::domain::{
+    Article, ArticleBody, ArticleListCursor, ArticleReadRepository, ArticleRevisionRepository,
+    ArticleSlug, ArticleTitle, ArticleUpdate, ArticleWriteRepository, NewArticle, NewUser, Tag,
+    UserId, UserListCursor, UserRepository, Username,
+    audit::{entity::NewAuditLog, repository::AuditLogRepository},
+};
+
+/// Articles, or users, read per page while exporting.
+const EXPORT_PAGE_SIZE: u32 = 100;
+
+/// Zip archives start with a local file header.
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+pub struct ContentBundlePorts {
+    pub user_repo: Arc<dyn UserRepository>,
+    pub article_read_repo: Arc<dyn ArticleReadRepository>,
+    pub article_write_repo: Arc<dyn ArticleWriteRepository>,
+    pub article_revision_repo: Arc<dyn ArticleRevisionRepository>,
+    pub audit_log_repo: Arc<dyn AuditLogRepository>,
+    /// Gives imported users a password nobody knows.
+    pub password_hasher: Arc<dyn PasswordHasher>,
+    pub archiver: Arc<dyn Archiver>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExportBundleQuery {
+    pub format: BundleFormat,
+    pub include_revisions: bool,
+    pub include_users: bool,
+}
+
+pub struct ImportBundleCommand<'a> {
+    /// An NDJSON or zip bundle, told apart by its first bytes.
+    pub bundle: &'a [u8],
+    pub on_conflict: ConflictStrategy,
+}
+
+/// Exports and imports whole-site content bundles for admins.
+pub struct ContentBundleService {
+    ports: Arc<ContentBundlePorts>,
+    clock: Arc<dyn Clock>,
+    read_only: ReadOnlySwitch,
+    slug_cache: SlugCache,
+}
+
+impl ContentBundleService {
+    #[must_use]
+    pub fn new(ports: ContentBundlePorts, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            ports: Arc::new(ports),
+            clock,
+            read_only: ReadOnlySwitch::default(),
+            slug_cache: SlugCache::default(),
+        }
+    }
+
+    /// Refuse imports while `switch` is set.
+    #[must_use]
+    pub fn with_read_only(mut self, switch: ReadOnlySwitch) -> Self {
+        self.read_only = switch;
+        self
+    }
+
+    /// Drop overwritten articles from `cache`.
+    #[must_use]
+    pub fn with_slug_cache(mut self, cache: SlugCache) -> Self {
+        self.slug_cache = cache;
+        self
+    }
+
+    /// Start an export of every article, drafts included. Nothing is read
+    /// until the returned export is driven.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `admin:maintenance`, or
+    /// `users:read` when users are included, or the export cannot be
+    /// audited.
+    pub async fn export(
+        &self,
+        actor: &AuthenticatedUser,
+        query: ExportBundleQuery,
+    ) -> AppResult<BundleExport> {
+        ensure_capability(actor, "admin", "maintenance")?;
+        if query.include_users {
+            ensure_capability(actor, "users", "read")?;
+        }
+
+        self.audit(
+            actor,
+            "content.export",
+            json!({
+                "format": query.format,
+                "include_revisions": query.include_revisions,
+                "include_users": query.include_users,
+            }),
+        )
+        .await?;
+        Ok(BundleExport {
+            ports: Arc::clone(&self.ports),
+            query,
+            exported_at: self.clock.now(),
+            stage: Stage::Manifest,
+            usernames: HashMap::new(),
+        })
+    }
+
+    /// Apply a bundle: users first, then each article by `on_conflict`.
+    ///
+    /// Users missing here are created with their role and must reset their
+    /// password; existing users are left as they are. Articles keep their
+    /// slug, timestamps and author (matched by username, falling back to the
+    /// actor). Articles that are created get the bundle's revisions, or a
+    /// first revision when it has none; overwritten ones get one new
+    /// revision. A failing article is reported and the rest still apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writes are disabled, the actor lacks
+    /// `admin:maintenance`, or `users:create` for a bundle with users, the
+    /// bundle is malformed, or a user cannot be created.
+    pub async fn import(
+        &self,
+        actor: &AuthenticatedUser,
+        command: ImportBundleCommand<'_>,
+    ) -> AppResult<BundleImportDto> {
+        self.read_only.ensure_writable()?;
+        ensure_capability(actor, "admin", "maintenance")?;
+        let bundle = self.parse(command.bundle)?;
+        if !bundle.users.is_empty() {
+            ensure_capability(actor, "users", "create")?;
+        }
+
+        let mut authors = Authors::default();
+        let mut report = BundleImportDto {
+            on_conflict: command.on_conflict,
+            users_created: self.import_users(&bundle.users, &mut authors).await?,
+            revisions_imported: 0,
+            articles: Vec::with_capacity(bundle.articles.len()),
+        };
+
+        let mut revisions: HashMap<i64, Vec<BundleRevision>> = HashMap::new();
+        for revision in bundle.revisions {
+            revisions
+                .entry(revision.article_id)
+                .or_default()
+                .push(revision);
+        }
+        for article in &bundle.articles {
+            let history = revisions.remove(&article.id).unwrap_or_default();
+            let item = match self
+                .import_article(actor, article, history, command.on_conflict, &mut authors)
+                .await
+            {
+                Ok((item, imported)) => {
+                    report.revisions_imported += imported;
+                    item
+                }
+                Err(err) => BundleImportItem {
+                    slug: article.slug.clone(),
+                    status: BundleItemStatus::Failed,
+                    article_id: None,
+                    imported_slug: None,
+                    error: Some(err.to_string()),
+                },
+            };
+            report.articles.push(item);
+        }
+
+        let count = |status| {
+            report
+                .articles
+                .iter()
+                .filter(|item| item.status == status)
+                .count()
+        };
+        self.audit(
+            actor,
+            "content.import",
+            json!({
+                "on_conflict": command.on_conflict,
+                "created": count(BundleItemStatus::Created),
+                "overwritten": count(BundleItemStatus::Overwritten),
+                "renamed": count(BundleItemStatus::Renamed),
+                "skipped": count(BundleItemStatus::Skipped),
+                "failed": count(BundleItemStatus::Failed),
+                "users_created": report.users_created,
+            }),
+        )
+        .await?;
+        Ok(report)
+    }
+
+    fn parse(&self, bundle: &[u8]) -> AppResult<ParsedBundle> {
+        if bundle.len() > MAX_BUNDLE_BYTES {
+            return Err(AppError::validation(format!(
+                "bundle is larger than {MAX_BUNDLE_BYTES} bytes"
+            )));
+        }
+        if bundle.starts_with(ZIP_MAGIC) {
+            let entries = self.ports.archiver.unpack(bundle, MAX_BUNDLE_BYTES)?;
+            format::parse_archive(&entries)
+        } else {
+            format::parse_ndjson(bundle)
+        }
+    }
+
+    async fn import_users(&self, users: &[BundleUser], authors: &mut Authors) -> AppResult<usize> {
+        let mut created = 0;
+        for user in users {
+            let username = Username::new(user.username.clone())?;
+            if self
+                .ports
+                .user_repo
+                .find_by_username(&username)
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+            let password_hash = unusable_password_hash(self.ports.password_hasher.as_ref()).await?;
+            let mut new_user = NewUser::new(username, password_hash, user.role, user.created_at)?
+                .requiring_password_reset();
+            new_user.is_active = user.is_active;
+            let stored = self.ports.user_repo.insert(new_user).await?;
+            authors.insert(user.username.clone(), Some(stored.id));
+            created += 1;
+        }
+        Ok(created)
+    }
+
+    /// Import one article, returning its outcome and the number of bundle
+    /// revisions written for it.
+    async fn import_article(
+        &self,
+        actor: &AuthenticatedUser,
+        article: &BundleArticle,
+        history: Vec<BundleRevision>,
+        on_conflict: ConflictStrategy,
+        authors: &mut Authors,
+    ) -> AppResult<(BundleImportItem, usize)> {
+        let slug = ArticleSlug::new(article.slug.clone())?;
+        let read_repo = &self.ports.article_read_repo;
+        let (slug, status) = match (read_repo.find_by_slug(&slug).await?, on_conflict) {
+            (None, _) => (slug, BundleItemStatus::Created),
+            (Some(existing), ConflictStrategy::Skip) => {
+                return Ok((item(article, BundleItemStatus::Skipped, &existing), 0));
+            }
+            (Some(existing), ConflictStrategy::Overwrite) => {
+                let updated = self.overwrite(actor, existing, article).await?;
+                return Ok((item(article, BundleItemStatus::Overwritten, &updated), 0));
+            }
+            (Some(_), ConflictStrategy::NewSlug) => {
+                (self.free_slug(&slug).await?, BundleItemStatus::Renamed)
+            }
+        };
+
+        let author_id = match &article.author {
+            Some(username) => authors.resolve(self, username).await?,
+            None => None,
+        }
+        .unwrap_or(actor.id);
+        let published_at = article
+            .published
+            .then(|| article.published_at.unwrap_or(article.updated_at));
+        let created = self
+            .ports
+            .article_write_repo
+            .insert(NewArticle {
+                title: ArticleTitle::new(article.title.clone())?,
+                slug,
+                body: ArticleBody::new(article.body.clone())?,
+                tags: Tag::parse_list(&article.tags)?,
+                custom_fields: article.custom_fields.clone(),
+                published: article.published,
+                published_at,
+                visibility: article.visibility,
+                author_id,
+                created_at: article.created_at,
+                updated_at: article.updated_at,
+            })
+            .await?;
+        let imported = self
+            .import_revisions(&created, history, author_id, authors)
+            .await?;
+        Ok((item(article, status, &created), imported))
+    }
+
+    async fn overwrite(
+        &self,
+        actor: &AuthenticatedUser,
+        existing: Article,
+        article: &BundleArticle,
+    ) -> AppResult<Article> {
+        let published_at = article
+            .published
+            .then(|| article.published_at.unwrap_or(article.updated_at));
+        let mut update = ArticleUpdate::new(existing.id, existing.updated_at)
+            .with_title(ArticleTitle::new(article.title.clone())?)
+            .with_body(ArticleBody::new(article.body.clone())?)
+            .with_tags(Tag::parse_list(&article.tags)?)
+            .with_custom_fields(article.custom_fields.clone())
+            .with_publish_state(article.published, published_at)
+            .with_visibility(article.visibility);
+        update.set_updated_at(self.clock.now());
+
+        let updated = self.ports.article_write_repo.update(update).await?;
+        self.ports
+            .article_revision_repo
+            .append(&updated, Some(actor.id))
+            .await?;
+        self.slug_cache.forget(&updated.slug);
+        Ok(updated)
+    }
+
+    /// Replay `history` oldest first onto `created`, or record its first
+    /// revision when there is none. Returns how many were replayed.
+    async fn import_revisions(
+        &self,
+        created: &Article,
+        mut history: Vec<BundleRevision>,
+        author_id: UserId,
+        authors: &mut Authors,
+    ) -> AppResult<usize> {
+        let revisions = &self.ports.article_revision_repo;
+        if history.is_empty() {
+            revisions.append(created, Some(author_id)).await?;
+            return Ok(0);
+        }
+
+        history.sort_by_key(|revision| revision.version);
+        for revision in &history {
+            let mut snapshot = created.clone();
+            snapshot.title = ArticleTitle::new(revision.title.clone())?;
+            snapshot.slug = ArticleSlug::new(revision.slug.clone())?;
+            snapshot.body = ArticleBody::new(revision.body.clone())?;
+            snapshot.published = revision.published;
+            snapshot.published_at = revision.published_at;
+            let edited_by = match &revision.edited_by {
+                Some(username) => authors.resolve(self, username).await?,
+                None => None,
+            };
+            revisions.append(&snapshot, edited_by).await?;
+        }
+        Ok(history.len())
+    }
+
+    /// The first of `<slug>-1`, `<slug>-2`, ... not taken by an article.
+    async fn free_slug(&self, slug: &ArticleSlug) -> AppResult<ArticleSlug> {
+        for suffix in 1u32.. {
+            let candidate = ArticleSlug::new(format!("{}-{suffix}", slug.as_str()))?;
+            if self
+                .ports
+                .article_read_repo
+                .find_by_slug(&candidate)
+                .await?
+                .is_none()
+            {
+                return Ok(candidate);
+            }
+        }
+        Err(AppError::conflict("no free slug"))
+    }
+
+    async fn audit(
+        &self,
+        actor: &AuthenticatedUser,
+        action: &str,
+        details: serde_json::Value,
+    ) -> AppResult<()> {
+        self.ports
+            .audit_log_repo
+            .insert(NewAuditLog {
+                user_id: Some(actor.id),
+                impersonator_id: actor.impersonator,
+                action: action.into(),
+                resource_type: "content".into(),
+                resource_id: None,
+                details: Some(details),
+                ip_address: None,
+                user_agent: None,
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+fn item(article: &BundleArticle, status: BundleItemStatus, stored: &Article) -> BundleImportItem {
+    BundleImportItem {
+        slug: article.slug.clone(),
+        status,
+        article_id: Some(i64::from(stored.id)),
+        imported_slug: (stored.slug.as_str() != article.slug).then(|| stored.slug.to_string()),
+        error: None,
+    }
+}
+
+/// Local ids of usernames named by the bundle, `None` for unknown ones.
+#[derive(Default)]
+struct Authors(HashMap<String, Option<UserId>>);
+
+impl Authors {
+    fn insert(&mut self, username: String, id: Option<UserId>) {
+        self.0.insert(username, id);
+    }
+
+    async fn resolve(
+        &mut self,
+        service: &ContentBundleService,
+        username: &str,
+    ) -> AppResult<Option<UserId>> {
+        if let Some(id) = self.0.get(username) {
+            return Ok(*id);
+        }
+        let id = match Username::new(username.to_string()) {
+            Ok(name) => service
+                .ports
+                .user_repo
+                .find_by_username(&name)
+                .await?
+                .map(|user| user.id),
+            Err(_) => None,
+        };
+        self.insert(username.to_string(), id);
+        Ok(id)
+    }
+}
+
+enum Stage {
+    Manifest,
+    Users(Option<UserListCursor>),
+    Articles(Option<ArticleListCursor>),
+    Done,
+}
+
+/// An export in progress. Drive it with [`next_chunk`](Self::next_chunk) to
+/// stream NDJSON, or [`into_archive`](Self::into_archive) for a zip.
+pub struct BundleExport {
+    ports: Arc<ContentBundlePorts>,
+    query: ExportBundleQuery,
+    exported_at: DateTime<Utc>,
+    stage: Stage,
+    /// Usernames of authors and editors, `None` for deleted users.
+    usernames: HashMap<UserId, Option<String>>,
+}
+
+impl BundleExport {
+    #[must_use]
+    pub const fn format(&self) -> BundleFormat {
+        self.query.format
+    }
+
+    /// `mokkan-bundle-<timestamp>.<ext>`, for `Content-Disposition`.
+    #[must_use]
+    pub fn file_name(&self) -> String {
+        format!(
+            "{BUNDLE_FORMAT}-{}.{}",
+            self.exported_at.format("%Y%m%dT%H%M%SZ"),
+            self.query.format.extension()
+        )
+    }
+
+    /// The next NDJSON lines of the bundle; `None` once it is complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading or serializing fails; the bundle is then
+    /// incomplete.
+    pub async fn next_chunk(&mut self) -> AppResult<Option<Vec<u8>>> {
+        let Some(records) = self.next_records().await? else {
+            return Ok(None);
+        };
+        let mut chunk = Vec::new();
+        for record in &records {
+            chunk.extend(format::ndjson_line(record)?);
+        }
+        Ok(Some(chunk))
+    }
+
+    /// Read the whole bundle and pack it as a zip archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading, serializing or packing fails.
+    pub async fn into_archive(mut self) -> AppResult<Vec<u8>> {
+        let mut entries = Vec::new();
+        let mut users = Vec::new();
+        let mut revisions = Vec::new();
+        while let Some(records) = self.next_records().await? {
+            for record in &records {
+                match record {
+                    BundleRecord::Manifest(manifest) => {
+                        entries.push(format::manifest_entry(manifest)?);
+                    }
+                    BundleRecord::Article(article) => entries.push(format::article_entry(article)?),
+                    BundleRecord::User(_) => users.extend(format::ndjson_line(record)?),
+                    BundleRecord::Revision(_) => revisions.extend(format::ndjson_line(record)?),
+                }
+            }
+        }
+        for (name, contents, included) in [
+            (USERS_ENTRY, users, self.query.include_users),
+            (REVISIONS_ENTRY, revisions, self.query.include_revisions),
+        ] {
+            if included {
+                entries.push(ArchiveEntry {
+                    name: name.into(),
+                    contents,
+                });
+            }
+        }
+        self.ports.archiver.pack(&entries)
+    }
+
+    async fn next_records(&mut self) -> AppResult<Option<Vec<BundleRecord>>> {
+        match std::mem::replace(&mut self.stage, Stage::Done) {
+            Stage::Manifest => {
+                self.stage = if self.query.include_users {
+                    Stage::Users(None)
+                } else {
+                    Stage::Articles(None)
+                };
+                Ok(Some(vec![BundleRecord::Manifest(BundleManifest {
+                    format: BUNDLE_FORMAT.into(),
+                    version: BUNDLE_VERSION,
+                    exported_at: self.exported_at,
+                    includes_revisions: self.query.include_revisions,
+                    includes_users: self.query.include_users,
+                })]))
+            }
+            Stage::Users(cursor) => {
+                let (users, next) = self
+                    .ports
+                    .user_repo
+                    .list_page(EXPORT_PAGE_SIZE, cursor, None)
+                    .await?;
+                self.stage = next.map_or(Stage::Articles(None), |next| Stage::Users(Some(next)));
+                Ok(Some(
+                    users
+                        .into_iter()
+                        .map(|user| {
+                            BundleRecord::User(BundleUser {
+                                username: user.username.to_string(),
+                                role: user.role,
+                                is_active: user.is_active,
+                                created_at: user.created_at,
+                            })
+                        })
+                        .collect(),
+                ))
+            }
+            Stage::Articles(cursor) => {
+                let (articles, next) = self
+                    .ports
+                    .article_read_repo
+                    .list_page(true, EXPORT_PAGE_SIZE, cursor, None)
+                    .await?;
+                self.stage = next.map_or(Stage::Done, |next| Stage::Articles(Some(next)));
+                self.article_records(articles).await.map(Some)
+            }
+            Stage::Done => Ok(None),
+        }
+    }
+
+    async fn article_records(&mut self, articles: Vec<Article>) -> AppResult<Vec<BundleRecord>> {
+        let authors: Vec<UserId> = articles.iter().map(|article| article.author_id).collect();
+        self.load_usernames(&authors).await?;
+
+        let mut records = Vec::with_capacity(articles.len());
+        for article in articles {
+            let source_id = i64::from(article.id);
+            let history = if self.query.include_revisions {
+                self.ports
+                    .article_revision_repo
+                    .list_by_article(article.id)
+                    .await?
+            } else {
+                Vec::new()
+            };
+            let editors: Vec<UserId> = history.iter().filter_map(|rev| rev.edited_by).collect();
+            self.load_usernames(&editors).await?;
+
+            records.push(BundleRecord::Article(BundleArticle {
+                id: source_id,
+                slug: article.slug.into_inner(),
+                title: article.title.into_inner(),
+                body: article.body.into_inner(),
+                tags: article.tags.into_iter().map(Tag::into_inner).collect(),
+                custom_fields: article.custom_fields,
+                published: article.published,
+                published_at: article.published_at,
+                visibility: article.visibility,
+                author: self.username(article.author_id),
+                created_at: article.created_at,
+                updated_at: article.updated_at,
+            }));
+            for revision in history {
+                records.push(BundleRecord::Revision(BundleRevision {
+                    article_id: source_id,
+                    version: revision.version,
+                    slug: revision.slug.into_inner(),
+                    title: revision.title.into_inner(),
+                    body: revision.body.into_inner(),
+                    published: revision.published,
+                    published_at: revision.published_at,
+                    edited_by: revision.edited_by.and_then(|id| self.username(id)),
+                    recorded_at: revision.recorded_at,
+                }));
+            }
+        }
+        Ok(records)
+    }
+
+    async fn load_usernames(&mut self, ids: &[UserId]) -> AppResult<()> {
+        let missing: Vec<UserId> = ids
+            .iter()
+            .copied()
+            .filter(|id| !self.usernames.contains_key(id))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        for user in self.ports.user_repo.find_by_ids(&missing).await? {
+            self.usernames
+                .insert(user.id, Some(user.username.to_string()));
+        }
+        for id in missing {
+            self.usernames.entry(id).or_insert(None);
+        }
+        Ok(())
+    }
+
+    fn username(&self, id: UserId) -> Option<String> {
+        self.usernames.get(&id).cloned().flatten()
+    }
+}
+
+fn ensure_capability(actor: &AuthenticatedUser, resource: &str, action: &str) -> AppResult<()> {
+    if actor.has_capability(resource, action) {
+        Ok(())
+    } else {
+        Err(AppError::forbidden(format!(
+            "missing capability {resource}:{action}"
+        )))
+    }
+}
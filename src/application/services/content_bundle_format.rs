@@ -0,0 +1,280 @@
+//! Wire layouts of content bundles.
+//!
+//! NDJSON bundles are one [`BundleRecord`] per line, manifest first. Zip
+//! bundles hold `manifest.json`, one `articles/<slug>.md` per article (YAML
+//! front matter, then the Markdown body) and, when included,
+//! `revisions.ndjson` and `users.ndjson` in the NDJSON record format.
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::application::{
+    AppError, AppResult, BundleArticle, BundleManifest, BundleRecord, BundleRevision, BundleUser,
+    dto::bundles::{BUNDLE_FORMAT, BUNDLE_VERSION},
+    ports::archives::ArchiveEntry,
+};
+
+pub const MANIFEST_ENTRY: &str = "manifest.json";
+pub const USERS_ENTRY: &str = "users.ndjson";
+pub const REVISIONS_ENTRY: &str = "revisions.ndjson";
+const ARTICLES_DIR: &str = "articles/";
+const FRONT_MATTER_FENCE: &str = "---\n";
+
+/// A bundle read back, with its records sorted by kind.
+#[derive(Debug, Default)]
+pub struct ParsedBundle {
+    pub users: Vec<BundleUser>,
+    pub articles: Vec<BundleArticle>,
+    pub revisions: Vec<BundleRevision>,
+}
+
+impl ParsedBundle {
+    fn push(&mut self, record: BundleRecord) -> Result<(), String> {
+        match record {
+            BundleRecord::Manifest(_) => {
+                return Err("only the first record may be a manifest".into());
+            }
+            BundleRecord::User(user) => self.users.push(user),
+            BundleRecord::Article(article) => self.articles.push(article),
+            BundleRecord::Revision(revision) => self.revisions.push(revision),
+        }
+        Ok(())
+    }
+}
+
+/// `record` as one NDJSON line, newline included.
+///
+/// # Errors
+///
+/// Returns an error if the record cannot be serialized.
+pub fn ndjson_line(record: &BundleRecord) -> AppResult<Vec<u8>> {
+    let mut line = serde_json::to_vec(record).map_err(|err| {
+        AppError::infrastructure(format!("failed to serialize bundle record: {err}"))
+    })?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+/// `manifest` as the pretty-printed `manifest.json` of a zip bundle.
+///
+/// # Errors
+///
+/// Returns an error if the manifest cannot be serialized.
+pub fn manifest_entry(manifest: &BundleManifest) -> AppResult<ArchiveEntry> {
+    let contents = serde_json::to_vec_pretty(manifest).map_err(|err| {
+        AppError::infrastructure(format!("failed to serialize bundle manifest: {err}"))
+    })?;
+    Ok(ArchiveEntry {
+        name: MANIFEST_ENTRY.into(),
+        contents,
+    })
+}
+
+/// `article` as `articles/<slug>.md`: its fields as YAML front matter, then
+/// the body exactly as stored.
+///
+/// # Errors
+///
+/// Returns an error if the front matter cannot be serialized.
+pub fn article_entry(article: &BundleArticle) -> AppResult<ArchiveEntry> {
+    let failed =
+        |err: String| AppError::infrastructure(format!("failed to serialize article: {err}"));
+    let Value::Object(mut fields) =
+        serde_json::to_value(article).map_err(|err| failed(err.to_string()))?
+    else {
+        return Err(failed("not an object".into()));
+    };
+    fields.remove("body");
+    let front_matter = serde_yaml_ng::to_string(&fields).map_err(|err| failed(err.to_string()))?;
+
+    let contents = format!(
+        "{FRONT_MATTER_FENCE}{front_matter}{FRONT_MATTER_FENCE}{}",
+        article.body
+    );
+    Ok(ArchiveEntry {
+        name: format!("{ARTICLES_DIR}{}.md", article.slug),
+        contents: contents.into_bytes(),
+    })
+}
+
+/// Read an NDJSON bundle. Blank lines are ignored.
+///
+/// # Errors
+///
+/// Returns a validation error naming the line if a record is malformed, the
+/// manifest is missing or not first, or the bundle is of another format or
+/// version.
+pub fn parse_ndjson(data: &[u8]) -> AppResult<ParsedBundle> {
+    let text = std::str::from_utf8(data)
+        .map_err(|_| AppError::validation("bundle is neither a zip archive nor UTF-8 NDJSON"))?;
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+
+    let (line, first) = lines
+        .next()
+        .ok_or_else(|| AppError::validation("bundle is empty"))?;
+    match parse_record(first).map_err(|err| at_line(line, &err))? {
+        BundleRecord::Manifest(manifest) => check_manifest(&manifest)?,
+        _ => return Err(at_line(line, "the first record must be the manifest")),
+    }
+
+    let mut bundle = ParsedBundle::default();
+    for (line, text) in lines {
+        parse_record(text)
+            .and_then(|record| bundle.push(record))
+            .map_err(|err| at_line(line, &err))?;
+    }
+    Ok(bundle)
+}
+
+/// Read the entries of a zip bundle. Entries outside the layout are ignored.
+///
+/// # Errors
+///
+/// Returns a validation error naming the entry if it is malformed, the
+/// manifest is missing, or the bundle is of another format or version.
+pub fn parse_archive(entries: &[ArchiveEntry]) -> AppResult<ParsedBundle> {
+    let manifest = entries
+        .iter()
+        .find(|entry| entry.name == MANIFEST_ENTRY)
+        .ok_or_else(|| AppError::validation("bundle has no manifest.json"))?;
+    let manifest: BundleManifest = serde_json::from_slice(&manifest.contents)
+        .map_err(|err| in_entry(MANIFEST_ENTRY, &err.to_string()))?;
+    check_manifest(&manifest)?;
+
+    let mut bundle = ParsedBundle::default();
+    for entry in entries {
+        let result = match entry.name.as_str() {
+            USERS_ENTRY | REVISIONS_ENTRY => parse_entry_lines(&mut bundle, &entry.contents),
+            name if name.starts_with(ARTICLES_DIR)
+                && Path::new(name)
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("md")) =>
+            {
+                parse_article(&entry.contents).map(|article| bundle.articles.push(article))
+            }
+            _ => Ok(()),
+        };
+        result.map_err(|err| in_entry(&entry.name, &err))?;
+    }
+    Ok(bundle)
+}
+
+fn parse_record(line: &str) -> Result<BundleRecord, String> {
+    serde_json::from_str(line).map_err(|err| err.to_string())
+}
+
+fn parse_entry_lines(bundle: &mut ParsedBundle, contents: &[u8]) -> Result<(), String> {
+    let text = std::str::from_utf8(contents).map_err(|_| "not UTF-8".to_string())?;
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        parse_record(line)
+            .and_then(|record| bundle.push(record))
+            .map_err(|err| format!("line {}: {err}", index + 1))?;
+    }
+    Ok(())
+}
+
+fn parse_article(contents: &[u8]) -> Result<BundleArticle, String> {
+    let text = std::str::from_utf8(contents).map_err(|_| "not UTF-8".to_string())?;
+    let rest = text
+        .strip_prefix(FRONT_MATTER_FENCE)
+        .ok_or("missing front matter")?;
+    let end = rest
+        .find(&format!("\n{FRONT_MATTER_FENCE}"))
+        .ok_or("unterminated front matter")?;
+    let (front_matter, body) = (&rest[..=end], &rest[end + 1 + FRONT_MATTER_FENCE.len()..]);
+
+    let mut fields: serde_json::Map<String, Value> =
+        serde_yaml_ng::from_str(front_matter).map_err(|err| err.to_string())?;
+    fields.insert("body".into(), Value::String(body.to_string()));
+    serde_json::from_value(Value::Object(fields)).map_err(|err| err.to_string())
+}
+
+fn check_manifest(manifest: &BundleManifest) -> AppResult<()> {
+    if manifest.format != BUNDLE_FORMAT {
+        return Err(AppError::validation(format!(
+            "not a {BUNDLE_FORMAT} bundle: format is '{}'",
+            manifest.format
+        )));
+    }
+    if manifest.version != BUNDLE_VERSION {
+        return Err(AppError::validation(format!(
+            "unsupported bundle version {}; expected {BUNDLE_VERSION}",
+            manifest.version
+        )));
+    }
+    Ok(())
+}
+
+fn at_line(line: usize, err: &str) -> AppError {
+    AppError::validation(format!("bundle line {line}: {err}"))
+}
+
+fn in_entry(name: &str, err: &str) -> AppError {
+    AppError::validation(format!("bundle entry {name}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ArticleVisibility;
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    fn article(body: &str) -> BundleArticle {
+        let at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        BundleArticle {
+            id: 7,
+            slug: "fish-and-chips".into(),
+            title: "Fish: and chips".into(),
+            body: body.into(),
+            tags: vec!["food".into()],
+            custom_fields: json!({ "rating": 4 }).as_object().unwrap().clone(),
+            published: true,
+            published_at: Some(at),
+            visibility: ArticleVisibility::Unlisted,
+            author: Some("alice".into()),
+            created_at: at,
+            updated_at: at,
+        }
+    }
+
+    #[test]
+    fn markdown_entries_round_trip_the_body_verbatim() {
+        let original = article("# Fish\n\n---\n\nserved hot\n");
+        let entry = article_entry(&original).unwrap();
+        assert_eq!(entry.name, "articles/fish-and-chips.md");
+        assert!(entry.contents.starts_with(b"---\n"));
+        assert_eq!(parse_article(&entry.contents).unwrap(), original);
+    }
+
+    #[test]
+    fn ndjson_requires_the_manifest_first() {
+        let manifest = BundleRecord::Manifest(BundleManifest {
+            format: BUNDLE_FORMAT.into(),
+            version: BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            includes_revisions: false,
+            includes_users: false,
+        });
+        let record = BundleRecord::Article(article("body"));
+
+        let mut data = ndjson_line(&manifest).unwrap();
+        data.extend(b"\n");
+        data.extend(ndjson_line(&record).unwrap());
+        let bundle = parse_ndjson(&data).unwrap();
+        assert_eq!(bundle.articles, vec![article("body")]);
+
+        let err = parse_ndjson(&ndjson_line(&record).unwrap()).unwrap_err();
+        assert!(err.to_string().contains("line 1"), "{err}");
+        let mut twice = ndjson_line(&manifest).unwrap();
+        twice.extend(ndjson_line(&manifest).unwrap());
+        assert!(parse_ndjson(&twice).is_err());
+    }
+}
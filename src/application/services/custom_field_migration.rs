@@ -0,0 +1,381 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use super::slug_cache::SlugCache;
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser, CustomFieldMigration, CustomFieldMigrationJobDto,
+    MigrationFailure, MigrationJobStatus,
+    ports::{jobs::JobQueue, time::Clock},
+    random_id,
+};
+use crate::domain::{
+    ArticleReadRepository, ArticleWriteRepository, CustomFieldDefinition, CustomFieldRepository,
+    CustomFields,
+    article::{entity::Article, entity::ArticleUpdate, repository::ArticleQuery},
+};
+
+/// Finished jobs beyond this many are forgotten, oldest first.
+const MAX_RETAINED_JOBS: usize = 100;
+
+/// Articles loaded per listing request while collecting the job's input.
+const PAGE_SIZE: u32 = 100;
+
+#[derive(Default)]
+struct JobTable {
+    jobs: HashMap<String, CustomFieldMigrationJobDto>,
+    order: VecDeque<String>,
+}
+
+type SharedJobs = Arc<Mutex<JobTable>>;
+
+fn with_jobs<T>(jobs: &SharedJobs, f: impl FnOnce(&mut JobTable) -> T) -> T {
+    let mut table = jobs.lock().unwrap_or_else(PoisonError::into_inner);
+    f(&mut table)
+}
+
+/// Evolves custom field definitions on the job queue, rewriting the values
+/// stored on every article to match.
+///
+/// The definition changes first, so articles saved while the job runs are
+/// already checked against the new shape. Job state lives in memory; articles
+/// rewritten before a restart stay rewritten.
+pub struct CustomFieldMigrationService {
+    custom_field_repo: Arc<dyn CustomFieldRepository>,
+    read_repo: Arc<dyn ArticleReadRepository>,
+    write_repo: Arc<dyn ArticleWriteRepository>,
+    clock: Arc<dyn Clock>,
+    job_queue: Arc<dyn JobQueue>,
+    jobs: SharedJobs,
+    slug_cache: SlugCache,
+}
+
+impl CustomFieldMigrationService {
+    #[must_use]
+    pub fn new(
+        custom_field_repo: Arc<dyn CustomFieldRepository>,
+        read_repo: Arc<dyn ArticleReadRepository>,
+        write_repo: Arc<dyn ArticleWriteRepository>,
+        clock: Arc<dyn Clock>,
+        job_queue: Arc<dyn JobQueue>,
+    ) -> Self {
+        Self {
+            custom_field_repo,
+            read_repo,
+            write_repo,
+            clock,
+            job_queue,
+            jobs: SharedJobs::default(),
+            slug_cache: SlugCache::default(),
+        }
+    }
+
+    /// Empty `cache` once a migration has rewritten articles.
+    #[must_use]
+    pub fn with_slug_cache(mut self, cache: SlugCache) -> Self {
+        self.slug_cache = cache;
+        self
+    }
+
+    /// Check `migration` against the current definition of `field` and queue
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `custom_fields:manage`, the field
+    /// does not exist, the migration does not fit it, another migration of
+    /// the field is still running, or a job id cannot be generated.
+    pub async fn submit(
+        &self,
+        actor: &AuthenticatedUser,
+        field: &str,
+        migration: CustomFieldMigration,
+    ) -> AppResult<CustomFieldMigrationJobDto> {
+        ensure_can_migrate(actor)?;
+        let definitions = self.custom_field_repo.list().await?;
+        let current = definitions
+            .iter()
+            .find(|definition| definition.name == field)
+            .cloned()
+            .ok_or_else(|| AppError::not_found(format!("custom field '{field}' not found")))?;
+        let plan = Plan::new(current, &definitions, &migration)?;
+
+        let job = CustomFieldMigrationJobDto {
+            id: random_id::v4_string()?,
+            field: field.to_owned(),
+            migration,
+            status: MigrationJobStatus::Queued,
+            total_articles: 0,
+            processed_articles: 0,
+            updated_articles: 0,
+            failures: Vec::new(),
+            error: None,
+            created_at: self.clock.now(),
+            finished_at: None,
+        };
+        with_jobs(&self.jobs, |table| {
+            let busy = table.jobs.values().any(|other| {
+                other.field == job.field
+                    && matches!(
+                        other.status,
+                        MigrationJobStatus::Queued | MigrationJobStatus::Running
+                    )
+            });
+            if busy {
+                return Err(AppError::conflict(format!(
+                    "custom field '{field}' is already being migrated"
+                )));
+            }
+            table.order.push_back(job.id.clone());
+            table.jobs.insert(job.id.clone(), job.clone());
+            evict_finished(table);
+            Ok(())
+        })?;
+
+        let run = MigrationRun {
+            custom_field_repo: Arc::clone(&self.custom_field_repo),
+            read_repo: Arc::clone(&self.read_repo),
+            write_repo: Arc::clone(&self.write_repo),
+            clock: Arc::clone(&self.clock),
+            jobs: Arc::clone(&self.jobs),
+            job_id: job.id.clone(),
+            slug_cache: self.slug_cache.clone(),
+        };
+        self.job_queue
+            .enqueue("custom_field_migration", Box::pin(run.execute(plan)));
+
+        Ok(job)
+    }
+
+    /// Current state of a migration job.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `custom_fields:manage` or the job
+    /// is unknown.
+    pub fn job(
+        &self,
+        actor: &AuthenticatedUser,
+        id: &str,
+    ) -> AppResult<CustomFieldMigrationJobDto> {
+        ensure_can_migrate(actor)?;
+        with_jobs(&self.jobs, |table| table.jobs.get(id).cloned())
+            .ok_or_else(|| AppError::not_found("migration job not found"))
+    }
+}
+
+fn ensure_can_migrate(actor: &AuthenticatedUser) -> AppResult<()> {
+    if actor.has_capability("custom_fields", "manage") {
+        Ok(())
+    } else {
+        Err(AppError::forbidden(
+            "missing capability custom_fields:manage",
+        ))
+    }
+}
+
+fn evict_finished(table: &mut JobTable) {
+    while table.order.len() > MAX_RETAINED_JOBS {
+        let Some(position) = table.order.iter().position(|id| {
+            table.jobs.get(id).is_none_or(|job| {
+                matches!(
+                    job.status,
+                    MigrationJobStatus::Completed | MigrationJobStatus::Failed
+                )
+            })
+        }) else {
+            return;
+        };
+        if let Some(id) = table.order.remove(position) {
+            table.jobs.remove(&id);
+        }
+    }
+}
+
+/// A validated migration: the definition before and after, and how each
+/// article's values change.
+struct Plan {
+    current: CustomFieldDefinition,
+    target: CustomFieldDefinition,
+    migration: CustomFieldMigration,
+}
+
+impl Plan {
+    fn new(
+        current: CustomFieldDefinition,
+        definitions: &[CustomFieldDefinition],
+        migration: &CustomFieldMigration,
+    ) -> AppResult<Self> {
+        let target = match migration {
+            CustomFieldMigration::Rename { to } => {
+                if definitions.iter().any(|definition| &definition.name == to) {
+                    return Err(AppError::conflict(format!(
+                        "custom field '{to}' already exists"
+                    )));
+                }
+                CustomFieldDefinition::new(to.clone(), current.field_type, current.required)?
+            }
+            CustomFieldMigration::ChangeType { to, .. } => {
+                if *to == current.field_type {
+                    return Err(AppError::validation(format!(
+                        "custom field '{}' is already a {to} field",
+                        current.name
+                    )));
+                }
+                CustomFieldDefinition {
+                    field_type: *to,
+                    ..current.clone()
+                }
+            }
+            CustomFieldMigration::Backfill { value } => {
+                if !current.field_type.accepts(value) {
+                    return Err(AppError::validation(format!(
+                        "backfill value for custom field '{}' must be a {} value",
+                        current.name, current.field_type
+                    )));
+                }
+                current.clone()
+            }
+        };
+        Ok(Self {
+            current,
+            target,
+            migration: migration.clone(),
+        })
+    }
+
+    /// The article's new custom fields, or `None` when they need no change.
+    fn rewrite(&self, fields: &CustomFields) -> Result<Option<CustomFields>, String> {
+        let name = &self.current.name;
+        let mut fields = fields.clone();
+        match &self.migration {
+            CustomFieldMigration::Rename { to } => {
+                let Some(value) = fields.remove(name) else {
+                    return Ok(None);
+                };
+                fields.insert(to.clone(), value);
+            }
+            CustomFieldMigration::ChangeType {
+                to,
+                drop_unconvertible,
+            } => {
+                let Some(value) = fields.get(name) else {
+                    return Ok(None);
+                };
+                match to.convert(value) {
+                    Some(converted) if &converted == value => return Ok(None),
+                    Some(converted) => {
+                        fields.insert(name.clone(), converted);
+                    }
+                    None if *drop_unconvertible => {
+                        fields.remove(name);
+                    }
+                    None => return Err(format!("value {value} cannot be converted to {to}")),
+                }
+            }
+            CustomFieldMigration::Backfill { value } => {
+                if fields.contains_key(name) {
+                    return Ok(None);
+                }
+                fields.insert(name.clone(), value.clone());
+            }
+        }
+        Ok(Some(fields))
+    }
+}
+
+struct MigrationRun {
+    custom_field_repo: Arc<dyn CustomFieldRepository>,
+    read_repo: Arc<dyn ArticleReadRepository>,
+    write_repo: Arc<dyn ArticleWriteRepository>,
+    clock: Arc<dyn Clock>,
+    jobs: SharedJobs,
+    job_id: String,
+    slug_cache: SlugCache,
+}
+
+impl MigrationRun {
+    async fn execute(self, plan: Plan) {
+        self.update(|job| job.status = MigrationJobStatus::Running);
+
+        let outcome = self.migrate(&plan).await;
+        self.slug_cache.clear();
+
+        let finished_at = self.clock.now();
+        self.update(|job| {
+            match outcome {
+                Ok(()) => job.status = MigrationJobStatus::Completed,
+                Err(err) => {
+                    job.status = MigrationJobStatus::Failed;
+                    job.error = Some(err.to_string());
+                }
+            }
+            job.finished_at = Some(finished_at);
+        });
+    }
+
+    async fn migrate(&self, plan: &Plan) -> AppResult<()> {
+        if plan.target != plan.current {
+            self.custom_field_repo.upsert(plan.target.clone()).await?;
+        }
+
+        let articles = self.all_articles().await?;
+        self.update(|job| job.total_articles = articles.len());
+
+        for article in articles {
+            let outcome = match plan.rewrite(&article.custom_fields) {
+                Ok(None) => Ok(false),
+                Ok(Some(fields)) => self.save(&article, fields).await.map(|()| true),
+                Err(err) => Err(err),
+            };
+            self.update(|job| {
+                job.processed_articles += 1;
+                match outcome {
+                    Ok(updated) => job.updated_articles += usize::from(updated),
+                    Err(error) => job.failures.push(MigrationFailure {
+                        article_id: article.id.into(),
+                        error,
+                    }),
+                }
+            });
+        }
+
+        if plan.target.name != plan.current.name {
+            self.custom_field_repo.delete(&plan.current.name).await?;
+        }
+        Ok(())
+    }
+
+    async fn all_articles(&self) -> AppResult<Vec<Article>> {
+        let mut articles = Vec::new();
+        let mut query = ArticleQuery::new().include_drafts(true).limit(PAGE_SIZE);
+        loop {
+            let (page, next) = self.read_repo.list(query.clone()).await?;
+            articles.extend(page);
+            match next {
+                Some(cursor) => query = query.cursor(cursor),
+                None => return Ok(articles),
+            }
+        }
+    }
+
+    /// Rewrite one article's custom fields, failing if it was edited since
+    /// the job loaded it.
+    async fn save(&self, article: &Article, fields: CustomFields) -> Result<(), String> {
+        let mut update =
+            ArticleUpdate::new(article.id, article.updated_at).with_custom_fields(fields);
+        update.set_updated_at(self.clock.now());
+        self.write_repo
+            .update(update)
+            .await
+            .map(drop)
+            .map_err(|err| err.to_string())
+    }
+
+    fn update(&self, f: impl FnOnce(&mut CustomFieldMigrationJobDto)) {
+        with_jobs(&self.jobs, |table| {
+            if let Some(job) = table.jobs.get_mut(&self.job_id) {
+                f(job);
+            }
+        });
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::application::{
+    AppError, AppResult, ArticleImportDto, AuthenticatedUser,
+    commands::articles::{ArticleCommandService, CreateArticleCommand},
+    ports::documents::DocumentConverter,
+};
+use crate::domain::{ArticleVisibility, CustomFields};
+
+/// Creates draft articles from uploaded word-processor documents.
+pub struct DocumentImportService {
+    converter: Arc<dyn DocumentConverter>,
+    article_commands: Arc<ArticleCommandService>,
+}
+
+impl DocumentImportService {
+    #[must_use]
+    pub fn new(
+        converter: Arc<dyn DocumentConverter>,
+        article_commands: Arc<ArticleCommandService>,
+    ) -> Self {
+        Self {
+            converter,
+            article_commands,
+        }
+    }
+
+    /// Convert `document` to Markdown and save it as an unpublished draft.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `articles:create`, the document
+    /// cannot be converted or has no title, or creating the article fails.
+    pub async fn import(
+        &self,
+        actor: &AuthenticatedUser,
+        document: &[u8],
+    ) -> AppResult<ArticleImportDto> {
+        // Check before converting so unauthorized uploads cost nothing.
+        if !actor.has_capability("articles", "create") {
+            return Err(AppError::forbidden("missing capability articles:create"));
+        }
+
+        let converted = self.converter.to_markdown(document)?;
+        let title = converted.title.ok_or_else(|| {
+            AppError::validation("document has no title; add a Title or Heading 1 paragraph")
+        })?;
+        let article = self
+            .article_commands
+            .create_article(
+                actor,
+                CreateArticleCommand {
+                    title,
+                    body: converted.markdown,
+                    publish: false,
+                    tags: Vec::new(),
+                    custom_fields: CustomFields::new(),
+                    visibility: ArticleVisibility::Public,
+                },
+            )
+            .await?;
+
+        Ok(ArticleImportDto {
+            article,
+            skipped_images: converted.skipped_images,
+        })
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use tokio::sync::broadcast;
+
+use crate::{
+    application::{
+        AppResult,
+        ports::domain_events::{EventHandler, EventPublisher},
+    },
+    async_support::{BoxFuture, boxed},
+    domain::DomainEvent,
+};
+
+/// Events a subscriber may fall behind by before it starts missing them.
+pub const DOMAIN_EVENT_BUFFER: usize = 1024;
+
+/// Hands domain events to the handlers registered at startup, then to
+/// in-process subscribers such as the live event stream.
+///
+/// Subscribers only see events published by this process, from the moment
+/// they subscribe; nothing is stored or replayed.
+pub struct DomainEventBus {
+    sender: broadcast::Sender<DomainEvent>,
+    handlers: Vec<Arc<dyn EventHandler>>,
+}
+
+impl DomainEventBus {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self {
+            sender,
+            handlers: Vec::new(),
+        }
+    }
+
+    /// Also run `handler` for every event, in registration order, before
+    /// `publish` returns.
+    #[must_use]
+    pub fn with_handler(mut self, handler: Arc<dyn EventHandler>) -> Self {
+        self.handlers.push(handler);
+        self
+    }
+
+    /// Follow every event published from now on. A receiver that falls more
+    /// than the buffer behind is told how many events it missed.
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Connected subscribers.
+    #[must_use]
+    pub fn subscribers(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl Default for DomainEventBus {
+    fn default() -> Self {
+        Self::new(DOMAIN_EVENT_BUFFER)
+    }
+}
+
+impl EventPublisher for DomainEventBus {
+    /// The change is already stored, so a failing handler is logged rather
+    /// than returned, and the others still run.
+    fn publish<'a>(&'a self, event: &'a DomainEvent) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            for handler in &self.handlers {
+                if let Err(err) = handler.handle(event).await {
+                    tracing::warn!(
+                        error = %err,
+                        event = event.kind.name(),
+                        "domain event handler failed"
+                    );
+                }
+            }
+            // Sending only fails when nobody is listening, which is fine.
+            let _ = self.sender.send(event.clone());
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use crate::application::{
+    AppError, AppResult,
+    ports::{download_links::DownloadLinkSigner, time::Clock},
+};
+
+/// Something a download link can point at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadTarget {
+    /// The file of a completed article export job.
+    ArticleExport(String),
+}
+
+impl DownloadTarget {
+    fn encode(&self) -> String {
+        match self {
+            Self::ArticleExport(id) => format!("article_export:{id}"),
+        }
+    }
+
+    fn decode(resource: &str) -> Option<Self> {
+        let (kind, id) = resource.split_once(':')?;
+        match kind {
+            "article_export" if !id.is_empty() => Some(Self::ArticleExport(id.to_owned())),
+            _ => None,
+        }
+    }
+}
+
+/// Issues and redeems signed, expiring `GET /api/v1/downloads/{token}`
+/// links, so fetching a finished export does not need the requester's
+/// access token to still be valid.
+pub struct DownloadLinkService {
+    signer: Arc<dyn DownloadLinkSigner>,
+    clock: Arc<dyn Clock>,
+}
+
+impl DownloadLinkService {
+    #[must_use]
+    pub fn new(signer: Arc<dyn DownloadLinkSigner>, clock: Arc<dyn Clock>) -> Self {
+        Self { signer, clock }
+    }
+
+    /// Path of a fresh link to `target`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the link cannot be signed.
+    pub fn link(&self, target: &DownloadTarget) -> AppResult<String> {
+        let token = self.signer.sign(&target.encode(), self.clock.now())?;
+        Ok(format!("/api/v1/downloads/{token}"))
+    }
+
+    /// What the link token `token` points at.
+    ///
+    /// # Errors
+    ///
+    /// Returns not found if the token is invalid or has expired.
+    pub fn resolve(&self, token: &str) -> AppResult<DownloadTarget> {
+        let resource = self.signer.verify(token, self.clock.now())?;
+        DownloadTarget::decode(&resource)
+            .ok_or_else(|| AppError::not_found("download link is invalid or has expired"))
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser,
+    commands::users::{LoginResult, UserCommandService, unusable_password_hash},
+    ports::{
+        oidc_login::{AuthorizationRequest, UpstreamIdentity, UpstreamProvider},
+        security::PasswordHasher,
+        security_events::ClientInfo,
+        time::Clock,
+    },
+    random_id,
+};
+use crate::domain::{NewUser, User, UserId, UserRepository, Username};
+
+/// How long a started login may take to come back through the callback.
+const PENDING_LOGIN_TTL_MINUTES: i64 = 10;
+/// Cap on logins started but not yet completed, across all providers.
+const MAX_PENDING_LOGINS: usize = 10_000;
+
+/// Where to send the browser to log in at the provider.
+#[derive(Debug, Clone)]
+pub struct FederatedLoginStart {
+    pub authorization_url: String,
+    pub state: String,
+}
+
+/// Query parameters the provider redirects back with.
+pub struct FederatedCallback {
+    pub provider: String,
+    pub state: String,
+    pub code: String,
+}
+
+struct PendingLogin {
+    provider: String,
+    nonce: String,
+    code_verifier: String,
+    /// Set when a signed-in user is linking an account rather than logging in.
+    link_user_id: Option<UserId>,
+    expires_at: DateTime<Utc>,
+}
+
+/// Logs users in through upstream OIDC providers.
+///
+/// First logins provision a local account linked to the provider's subject;
+/// a signed-in user who starts the flow links the upstream account to
+/// themselves instead. Pending logins are kept in memory, like
+/// authorization codes, so the callback must reach the same instance.
+pub struct FederatedLoginService {
+    providers: HashMap<String, UpstreamProvider>,
+    user_repo: Arc<dyn UserRepository>,
+    password_hasher: Arc<dyn PasswordHasher>,
+    clock: Arc<dyn Clock>,
+    user_commands: Arc<UserCommandService>,
+    pending: Mutex<HashMap<String, PendingLogin>>,
+}
+
+impl FederatedLoginService {
+    #[must_use]
+    pub fn new(
+        providers: Vec<UpstreamProvider>,
+        user_repo: Arc<dyn UserRepository>,
+        password_hasher: Arc<dyn PasswordHasher>,
+        clock: Arc<dyn Clock>,
+        user_commands: Arc<UserCommandService>,
+    ) -> Self {
+        Self {
+            providers: providers
+                .into_iter()
+                .map(|provider| (provider.name.clone(), provider))
+                .collect(),
+            user_repo,
+            password_hasher,
+            clock,
+            user_commands,
+            pending: Mutex::default(),
+        }
+    }
+
+    /// Names of the configured providers, sorted.
+    #[must_use]
+    pub fn provider_names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.providers.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    fn provider(&self, name: &str) -> AppResult<&UpstreamProvider> {
+        self.providers
+            .get(name)
+            .ok_or_else(|| AppError::not_found("unknown login provider"))
+    }
+
+    /// Begin a login (or, with `actor`, an account link) at `provider`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provider is unknown or unreachable, or too
+    /// many logins are pending.
+    pub async fn start(
+        &self,
+        provider: &str,
+        actor: Option<&AuthenticatedUser>,
+    ) -> AppResult<FederatedLoginStart> {
+        let upstream = self.provider(provider)?;
+
+        let state = random_id::v4_string()?;
+        let nonce = random_id::v4_string()?;
+        let code_verifier = format!("{}{}", random_id::v4_string()?, random_id::v4_string()?);
+        let code_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()));
+
+        let authorization_url = upstream
+            .client
+            .authorization_url(AuthorizationRequest {
+                state: &state,
+                nonce: &nonce,
+                code_challenge: &code_challenge,
+            })
+            .await?;
+
+        let now = self.clock.now();
+        let login = PendingLogin {
+            provider: provider.to_string(),
+            nonce,
+            code_verifier,
+            link_user_id: actor.map(|actor| actor.id),
+            expires_at: now + Duration::minutes(PENDING_LOGIN_TTL_MINUTES),
+        };
+        {
+            let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+            pending.retain(|_, login| login.expires_at > now);
+            if pending.len() >= MAX_PENDING_LOGINS {
+                return Err(AppError::infrastructure("too many pending logins"));
+            }
+            pending.insert(state.clone(), login);
+        }
+
+        Ok(FederatedLoginStart {
+            authorization_url,
+            state,
+        })
+    }
+
+    /// Finish a login started with [`start`](Self::start) and open a session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state is unknown, expired or for another
+    /// provider, the code exchange or `id_token` validation fails, the
+    /// upstream account is linked elsewhere, or the account is disabled.
+    pub async fn callback(
+        &self,
+        callback: FederatedCallback,
+        client: &ClientInfo,
+    ) -> AppResult<LoginResult> {
+        let upstream = self.provider(&callback.provider)?;
+
+        // States are single use: removing it up front defeats replays even
+        // when the exchange below fails.
+        let login = self
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&callback.state)
+            .filter(|login| {
+                login.provider == callback.provider && login.expires_at > self.clock.now()
+            })
+            .ok_or_else(|| AppError::unauthorized("unknown or expired login state"))?;
+
+        let identity = upstream
+            .client
+            .exchange_code(&callback.code, &login.code_verifier, &login.nonce)
+            .await?;
+
+        let user = match login.link_user_id {
+            Some(user_id) => self.link(upstream, &identity, user_id).await?,
+            None => self.find_or_provision(upstream, &identity).await?,
+        };
+
+        self.user_commands.start_session(user, client).await
+    }
+
+    async fn link(
+        &self,
+        upstream: &UpstreamProvider,
+        identity: &UpstreamIdentity,
+        user_id: UserId,
+    ) -> AppResult<User> {
+        match self
+            .user_repo
+            .find_by_federated_identity(&upstream.name, &identity.subject)
+            .await?
+        {
+            Some(linked) if linked.id == user_id => return Ok(linked),
+            Some(_) => {
+                return Err(AppError::conflict(
+                    "this account is already linked to another user",
+                ));
+            }
+            None => {}
+        }
+
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("user not found"))?;
+        self.user_repo
+            .link_federated_identity(&upstream.name, &identity.subject, user.id)
+            .await?;
+        Ok(user)
+    }
+
+    async fn find_or_provision(
+        &self,
+        upstream: &UpstreamProvider,
+        identity: &UpstreamIdentity,
+    ) -> AppResult<User> {
+        if let Some(user) = self
+            .user_repo
+            .find_by_federated_identity(&upstream.name, &identity.subject)
+            .await?
+        {
+            return Ok(user);
+        }
+
+        // Never link to an existing local account by name or email: the
+        // upstream provider does not prove ownership of that account.
+        let username = self.available_username(identity).await?;
+        let password_hash = unusable_password_hash(self.password_hasher.as_ref()).await?;
+        let new_user = NewUser::new(
+            username,
+            password_hash,
+            upstream.default_role,
+            self.clock.now(),
+        )?;
+        let user = self.user_repo.insert(new_user).await?;
+        self.user_repo
+            .link_federated_identity(&upstream.name, &identity.subject, user.id)
+            .await?;
+        tracing::info!(
+            user_id = i64::from(user.id),
+            provider = %upstream.name,
+            "provisioned user from upstream OIDC login"
+        );
+        Ok(user)
+    }
+
+    /// The provider's preferred username (or email), cut at any `@` since
+    /// usernames cannot contain one, suffixed when a local user already has
+    /// it.
+    async fn available_username(&self, identity: &UpstreamIdentity) -> AppResult<Username> {
+        let base = identity
+            .preferred_username
+            .as_deref()
+            .or(identity.email.as_deref())
+            .and_then(|name| name.split('@').next())
+            .map(str::trim)
+            .filter(|name| name.len() >= Username::MIN_LEN)
+            .unwrap_or("user");
+
+        let preferred = Username::new(base)?;
+        if self.user_repo.find_by_username(&preferred).await?.is_none() {
+            return Ok(preferred);
+        }
+        let suffix = random_id::v4_string()?;
+        Ok(Username::new(format!("{base}-{}", &suffix[..8]))?)
+    }
+}
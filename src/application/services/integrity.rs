@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser, IntegrityReportDto,
+    ports::{integrity::IntegrityChecker, time::Clock},
+};
+use crate::domain::audit::{entity::NewAuditLog, repository::AuditLogRepository};
+
+/// Reports, and on request repairs, data the schema cannot keep consistent.
+pub struct IntegrityService {
+    checker: Option<Arc<dyn IntegrityChecker>>,
+    audit_log_repo: Arc<dyn AuditLogRepository>,
+    clock: Arc<dyn Clock>,
+}
+
+impl IntegrityService {
+    #[must_use]
+    pub fn new(
+        checker: Option<Arc<dyn IntegrityChecker>>,
+        audit_log_repo: Arc<dyn AuditLogRepository>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            checker,
+            audit_log_repo,
+            clock,
+        }
+    }
+
+    /// Scan for anomalies. With `repair`, fix the repairable ones first and
+    /// record the repair in the audit log; the report then lists what is
+    /// left.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller lacks `admin:inspect` (and
+    /// `admin:repair` to repair), no checker is configured, or storage fails.
+    pub async fn check(
+        &self,
+        actor: &AuthenticatedUser,
+        repair: bool,
+    ) -> AppResult<IntegrityReportDto> {
+        ensure_capability(actor, "admin", "inspect")?;
+        if repair {
+            ensure_capability(actor, "admin", "repair")?;
+        }
+        let checker = self
+            .checker
+            .as_ref()
+            .ok_or_else(|| AppError::infrastructure("integrity checks are not configured"))?;
+
+        let repaired = if repair {
+            let repaired = checker.repair().await?;
+            self.audit_log_repo
+                .insert(NewAuditLog {
+                    user_id: Some(actor.id),
+                    impersonator_id: actor.impersonator,
+                    action: "integrity.repair".into(),
+                    resource_type: "integrity".into(),
+                    resource_id: None,
+                    details: Some(json!({ "repaired": repaired })),
+                    ip_address: None,
+                    user_agent: None,
+                })
+                .await?;
+            repaired
+        } else {
+            0
+        };
+
+        Ok(IntegrityReportDto {
+            checked_at: self.clock.now(),
+            repaired,
+            anomalies: checker.scan().await?,
+        })
+    }
+}
+
+fn ensure_capability(actor: &AuthenticatedUser, resource: &str, action: &str) -> AppResult<()> {
+    if actor.has_capability(resource, action) {
+        Ok(())
+    } else {
+        Err(AppError::forbidden(format!(
+            "missing capability {resource}:{action}"
+        )))
+    }
+}
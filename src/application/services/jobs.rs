@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser,
+    dto::{
+        jobs::{JobRunDto, JobStatusDto},
+        pagination::PageLimits,
+    },
+    ports::jobs::JobControl,
+};
+
+/// Lists scheduled jobs and their runs, and starts runs on demand, for
+/// admins.
+pub struct JobsService {
+    control: Option<Arc<dyn JobControl>>,
+    page_limits: PageLimits,
+}
+
+impl JobsService {
+    #[must_use]
+    pub fn new(control: Option<Arc<dyn JobControl>>) -> Self {
+        Self {
+            control,
+            page_limits: PageLimits::default(),
+        }
+    }
+
+    /// Size run history pages by `limits`.
+    #[must_use]
+    pub const fn with_page_limits(mut self, limits: PageLimits) -> Self {
+        self.page_limits = limits;
+        self
+    }
+
+    /// Every scheduled job, sorted by name; empty when no scheduler runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller lacks `admin:inspect`.
+    pub fn list(&self, actor: &AuthenticatedUser) -> AppResult<Vec<JobStatusDto>> {
+        ensure_capability(actor, "admin", "inspect")?;
+        let mut jobs = self
+            .control
+            .as_ref()
+            .map(|control| control.job_statuses())
+            .unwrap_or_default();
+        jobs.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(jobs)
+    }
+
+    /// The latest runs of `job`, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller lacks `admin:inspect`, the job is
+    /// unknown, or the history cannot be read.
+    pub async fn runs(
+        &self,
+        actor: &AuthenticatedUser,
+        job: &str,
+        limit: Option<u32>,
+    ) -> AppResult<Vec<JobRunDto>> {
+        ensure_capability(actor, "admin", "inspect")?;
+        let limit = self.page_limits.apply(limit.unwrap_or(0));
+        self.control(job)?.runs(job, limit).await
+    }
+
+    /// Start `job` now, e.g. to retry after a failed run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller lacks `jobs:run`, the job is unknown or
+    /// already running, or the run cannot be recorded.
+    pub async fn trigger(&self, actor: &AuthenticatedUser, job: &str) -> AppResult<JobRunDto> {
+        ensure_capability(actor, "jobs", "run")?;
+        self.control(job)?.trigger(job, actor.id.into()).await
+    }
+
+    fn control(&self, job: &str) -> AppResult<&Arc<dyn JobControl>> {
+        self.control
+            .as_ref()
+            .ok_or_else(|| AppError::not_found(format!("unknown job '{job}'")))
+    }
+}
+
+fn ensure_capability(actor: &AuthenticatedUser, resource: &str, action: &str) -> AppResult<()> {
+    if actor.has_capability(resource, action) {
+        Ok(())
+    } else {
+        Err(AppError::forbidden(format!(
+            "missing capability {resource}:{action}"
+        )))
+    }
+}
@@ -0,0 +1,387 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser,
+    commands::{
+        articles::{
+            AddContributorCommand, ArticleCommandService, BulkArticleCommand, CreateArticleCommand,
+            DeleteArticleCommand, DeleteCustomFieldCommand, PutCustomFieldCommand,
+            RemoveContributorCommand, RevertArticleToRevisionCommand, SetPublishStateCommand,
+            UpdateArticleCommand,
+        },
+        users::{
+            GrantRoleCommand, RegisterUserCommand, RevokeRoleCommand, UpdatePreferencesCommand,
+            UpdateUserCommand, UserCommandService,
+        },
+    },
+    ports::{
+        command_journal::{JournalEntry, JournalOutcome, JournaledCommand},
+        time::Clock,
+    },
+    random_id,
+};
+use crate::domain::{Role, UserId};
+
+/// Clock the replayer sets to each entry's original time, so replayed rows
+/// keep their timestamps. Reads the system time between entries.
+#[derive(Default)]
+pub struct ReplayClock(Mutex<Option<DateTime<Utc>>>);
+
+impl ReplayClock {
+    fn set(&self, at: Option<DateTime<Utc>>) {
+        *self
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = at;
+    }
+}
+
+impl Clock for ReplayClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .unwrap_or_else(Utc::now)
+    }
+}
+
+/// An entry that succeeded when recorded but failed on replay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayDivergence {
+    /// 1-based position of the entry in the journal.
+    pub position: usize,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplaySummary {
+    pub replayed: usize,
+    /// Entries that had failed when recorded and were not run again.
+    pub skipped: usize,
+    pub diverged: Vec<ReplayDivergence>,
+}
+
+/// Rebuilds state by running journaled commands again, in order, through the
+/// command services.
+///
+/// Meant for empty repositories: ids recorded in the journal are mapped to
+/// the ids the replay produces. Passwords are not journaled, so replayed
+/// users get a random one and must reset it.
+///
+/// The services must be built with the replayer's [`ReplayClock`] and
+/// without a journal of their own.
+pub struct JournalReplayer {
+    users: Arc<UserCommandService>,
+    articles: Arc<ArticleCommandService>,
+    clock: Arc<ReplayClock>,
+    user_ids: HashMap<i64, i64>,
+    article_ids: HashMap<i64, i64>,
+}
+
+impl JournalReplayer {
+    #[must_use]
+    pub fn new(
+        users: Arc<UserCommandService>,
+        articles: Arc<ArticleCommandService>,
+        clock: Arc<ReplayClock>,
+    ) -> Self {
+        Self {
+            users,
+            articles,
+            clock,
+            user_ids: HashMap::new(),
+            article_ids: HashMap::new(),
+        }
+    }
+
+    /// Replay `entries`. A divergence does not stop the replay; later
+    /// entries touching the same rows will usually diverge too.
+    pub async fn replay(
+        &mut self,
+        entries: impl IntoIterator<Item = JournalEntry>,
+    ) -> ReplaySummary {
+        let mut summary = ReplaySummary::default();
+        for (index, entry) in entries.into_iter().enumerate() {
+            let JournalOutcome::Succeeded { resource_id } = entry.outcome else {
+                summary.skipped += 1;
+                continue;
+            };
+            self.clock.set(Some(entry.recorded_at));
+            match self.apply(&entry).await {
+                Ok(replayed_id) => {
+                    summary.replayed += 1;
+                    self.remember(&entry.command, resource_id, replayed_id);
+                }
+                Err(err) => summary.diverged.push(ReplayDivergence {
+                    position: index + 1,
+                    error: err.to_string(),
+                }),
+            }
+        }
+        self.clock.set(None);
+        summary
+    }
+
+    fn remember(
+        &mut self,
+        command: &JournaledCommand,
+        recorded: Option<i64>,
+        replayed: Option<i64>,
+    ) {
+        let (Some(recorded), Some(replayed)) = (recorded, replayed) else {
+            return;
+        };
+        let ids = match command {
+            JournaledCommand::CreateArticle { .. } => &mut self.article_ids,
+            JournaledCommand::RegisterUser { .. } => &mut self.user_ids,
+            _ => return,
+        };
+        ids.insert(recorded, replayed);
+    }
+
+    fn user_id(&self, recorded: i64) -> i64 {
+        self.user_ids.get(&recorded).copied().unwrap_or(recorded)
+    }
+
+    fn article_id(&self, recorded: i64) -> i64 {
+        self.article_ids.get(&recorded).copied().unwrap_or(recorded)
+    }
+
+    fn actor(&self, entry: &JournalEntry) -> AppResult<Option<AuthenticatedUser>> {
+        entry
+            .actor
+            .as_ref()
+            .map(|actor| {
+                let id = UserId::new(self.user_id(actor.id))?;
+                Ok(actor.to_user(id, entry.recorded_at))
+            })
+            .transpose()
+    }
+
+    /// Run the entry's command, returning the id of the article or user it
+    /// wrote, if any.
+    async fn apply(&self, entry: &JournalEntry) -> AppResult<Option<i64>> {
+        match (entry.command.clone(), self.actor(entry)?) {
+            (command, Some(actor)) => self.apply_as(&actor, command).await,
+            (JournaledCommand::RegisterUser { username, role }, None) => {
+                self.register(None, username, role).await.map(Some)
+            }
+            (_, None) => Err(AppError::validation("journal entry has no actor")),
+        }
+    }
+
+    async fn register(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        username: String,
+        role: Option<Role>,
+    ) -> AppResult<i64> {
+        let command = RegisterUserCommand {
+            username,
+            password: format!("Replayed-{}", random_id::v4_string()?),
+            role,
+        };
+        Ok(self.users.register(actor, command).await?.id)
+    }
+
+    async fn apply_as(
+        &self,
+        actor: &AuthenticatedUser,
+        command: JournaledCommand,
+    ) -> AppResult<Option<i64>> {
+        let id = match command {
+            JournaledCommand::CreateArticle {
+                title,
+                body,
+                publish,
+                tags,
+                custom_fields,
+                visibility,
+            } => {
+                let command = CreateArticleCommand {
+                    title,
+                    body,
+                    publish,
+                    tags,
+                    custom_fields,
+                    visibility,
+                };
+                self.articles.create_article(actor, command).await?.id
+            }
+            JournaledCommand::UpdateArticle {
+                id,
+                title,
+                body,
+                publish,
+                tags,
+                custom_fields,
+                visibility,
+            } => {
+                let command = UpdateArticleCommand {
+                    id: self.article_id(id),
+                    title,
+                    body,
+                    publish,
+                    tags,
+                    custom_fields,
+                    visibility,
+                };
+                self.articles.update_article(actor, command).await?.id
+            }
+            JournaledCommand::SetPublishState { id, publish } => {
+                let command = SetPublishStateCommand {
+                    id: self.article_id(id),
+                    publish,
+                };
+                self.articles.set_publish_state(actor, command).await?.id
+            }
+            JournaledCommand::RevertArticle { id, version } => {
+                let command = RevertArticleToRevisionCommand {
+                    id: self.article_id(id),
+                    version,
+                    expected_updated_at: None,
+                };
+                self.articles
+                    .revert_article_to_revision(actor, command)
+                    .await?
+                    .id
+            }
+            JournaledCommand::DeleteArticle { id } => {
+                let id = self.article_id(id);
+                self.articles
+                    .delete_article(actor, DeleteArticleCommand { id })
+                    .await?;
+                id
+            }
+            command @ (JournaledCommand::AddContributor { .. }
+            | JournaledCommand::RemoveContributor { .. }) => {
+                self.apply_contributor(actor, command).await?
+            }
+            JournaledCommand::BulkArticles { operation, ids } => {
+                let command = BulkArticleCommand {
+                    operation,
+                    ids: ids.into_iter().map(|id| self.article_id(id)).collect(),
+                };
+                let _ = self.articles.bulk_articles(actor, command).await?;
+                return Ok(None);
+            }
+            JournaledCommand::PutCustomField {
+                name,
+                field_type,
+                required: is_required,
+            } => {
+                let command = PutCustomFieldCommand {
+                    name,
+                    field_type,
+                    required: is_required,
+                };
+                self.articles.put_custom_field(actor, command).await?;
+                return Ok(None);
+            }
+            JournaledCommand::DeleteCustomField { name } => {
+                self.articles
+                    .delete_custom_field(actor, DeleteCustomFieldCommand { name })
+                    .await?;
+                return Ok(None);
+            }
+            command => self.apply_user(actor, command).await?,
+        };
+        Ok(Some(id))
+    }
+
+    /// Add or remove an article contributor on behalf of `actor`.
+    async fn apply_contributor(
+        &self,
+        actor: &AuthenticatedUser,
+        command: JournaledCommand,
+    ) -> AppResult<i64> {
+        let id = match command {
+            JournaledCommand::AddContributor {
+                article_id,
+                user_id,
+            } => {
+                let command = AddContributorCommand {
+                    article_id: self.article_id(article_id),
+                    user_id: self.user_id(user_id),
+                };
+                let id = command.article_id;
+                let _ = self.articles.add_contributor(actor, command).await?;
+                id
+            }
+            JournaledCommand::RemoveContributor {
+                article_id,
+                user_id,
+            } => {
+                let command = RemoveContributorCommand {
+                    article_id: self.article_id(article_id),
+                    user_id: self.user_id(user_id),
+                };
+                let id = command.article_id;
+                self.articles.remove_contributor(actor, command).await?;
+                id
+            }
+            other => {
+                return Err(AppError::validation(format!(
+                    "not a contributor command: {other:?}"
+                )));
+            }
+        };
+        Ok(id)
+    }
+
+    /// Run a user command on behalf of `actor`.
+    async fn apply_user(
+        &self,
+        actor: &AuthenticatedUser,
+        command: JournaledCommand,
+    ) -> AppResult<i64> {
+        let id = match command {
+            JournaledCommand::RegisterUser { username, role } => {
+                self.register(Some(actor), username, role).await?
+            }
+            JournaledCommand::UpdateUser {
+                user_id,
+                is_active,
+                role,
+            } => {
+                let command = UpdateUserCommand {
+                    user_id: self.user_id(user_id),
+                    is_active,
+                    role,
+                };
+                self.users.update_user(actor, command).await?.id
+            }
+            JournaledCommand::GrantRole { user_id, role } => {
+                let command = GrantRoleCommand {
+                    user_id: self.user_id(user_id),
+                    role,
+                };
+                self.users.grant_role(actor, command).await?.id
+            }
+            JournaledCommand::RevokeRole { user_id } => {
+                let command = RevokeRoleCommand {
+                    user_id: self.user_id(user_id),
+                };
+                self.users.revoke_role(actor, command).await?.id
+            }
+            JournaledCommand::UpdatePreferences { timezone } => {
+                self.users
+                    .update_preferences(actor, UpdatePreferencesCommand { timezone })
+                    .await?
+                    .user
+                    .id
+            }
+            other => {
+                return Err(AppError::validation(format!(
+                    "not a user command: {other:?}"
+                )));
+            }
+        };
+        Ok(id)
+    }
+}
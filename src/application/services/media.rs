@@ -0,0 +1,184 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use serde_json::json;
+
+use super::audit_recorder::{AuditEvent, AuditRecorder};
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser, MediaAssetDto, ReadOnlySwitch,
+    ports::{
+        blob_storage::{BlobRead, BlobStorage},
+        time::Clock,
+    },
+    random_id,
+};
+use crate::domain::{
+    MediaAsset, MediaRepository, NewMediaAsset, audit::repository::AuditLogRepository,
+};
+
+/// Which uploads `POST /api/v1/media` accepts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaPolicy {
+    /// Largest accepted file, in bytes.
+    pub max_bytes: usize,
+    /// Accepted content types, lowercase and without parameters.
+    pub allowed_types: Vec<String>,
+}
+
+impl Default for MediaPolicy {
+    fn default() -> Self {
+        Self {
+            max_bytes: 10 * 1024 * 1024,
+            allowed_types: ["image/png", "image/jpeg", "image/gif", "image/webp"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+pub struct UploadMediaCommand {
+    /// Name the client gave the file, if any.
+    pub file_name: Option<String>,
+    /// Content type the client declared for the file.
+    pub content_type: String,
+    pub bytes: Bytes,
+}
+
+/// A stored file and how it is read.
+pub struct MediaDownload {
+    pub asset: MediaAsset,
+    pub content: BlobRead,
+}
+
+/// Files uploaded for articles, kept in blob storage.
+///
+/// Uploads are written to storage before they are recorded, so a recorded
+/// asset always has its bytes; a failed insert removes them again.
+pub struct MediaService {
+    repo: Arc<dyn MediaRepository>,
+    storage: Arc<dyn BlobStorage>,
+    clock: Arc<dyn Clock>,
+    policy: MediaPolicy,
+    audit: AuditRecorder,
+    read_only: ReadOnlySwitch,
+}
+
+impl MediaService {
+    #[must_use]
+    pub fn new(
+        repo: Arc<dyn MediaRepository>,
+        storage: Arc<dyn BlobStorage>,
+        clock: Arc<dyn Clock>,
+        policy: MediaPolicy,
+    ) -> Self {
+        Self {
+            repo,
+            storage,
+            clock,
+            policy,
+            audit: AuditRecorder::default(),
+            read_only: ReadOnlySwitch::default(),
+        }
+    }
+
+    /// Record uploads in `audit_log_repo`.
+    #[must_use]
+    pub fn with_audit(mut self, audit_log_repo: Arc<dyn AuditLogRepository>) -> Self {
+        self.audit = AuditRecorder::new(audit_log_repo);
+        self
+    }
+
+    /// Refuse uploads while `switch` is on.
+    #[must_use]
+    pub fn with_read_only(mut self, switch: ReadOnlySwitch) -> Self {
+        self.read_only = switch;
+        self
+    }
+
+    #[must_use]
+    pub const fn policy(&self) -> &MediaPolicy {
+        &self.policy
+    }
+
+    /// Store an uploaded file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `articles:create`, the file is
+    /// empty, too large, of a content type that is not allowed or does not
+    /// match its content, or storing it fails.
+    pub async fn upload(
+        &self,
+        actor: &AuthenticatedUser,
+        command: UploadMediaCommand,
+    ) -> AppResult<MediaAssetDto> {
+        if !actor.has_capability("articles", "create") {
+            return Err(AppError::forbidden("missing capability articles:create"));
+        }
+        self.read_only.ensure_writable()?;
+        if command.bytes.len() > self.policy.max_bytes {
+            return Err(AppError::validation(format!(
+                "file must be at most {} bytes",
+                self.policy.max_bytes
+            )));
+        }
+
+        let asset = NewMediaAsset::new(
+            actor.id,
+            command.file_name.as_deref(),
+            &command.content_type,
+            &command.bytes,
+            random_id::v4_string()?,
+            self.clock.now(),
+        )?;
+        if !self.policy.allowed_types.contains(&asset.content_type) {
+            return Err(AppError::validation(format!(
+                "content type {} is not accepted; allowed: {}",
+                asset.content_type,
+                self.policy.allowed_types.join(", ")
+            )));
+        }
+
+        self.storage
+            .put(&asset.storage_key, &asset.content_type, command.bytes)
+            .await?;
+        let key = asset.storage_key.clone();
+        let asset = match self.repo.insert(asset).await {
+            Ok(asset) => asset,
+            Err(err) => {
+                if let Err(cleanup) = self.storage.delete(&key).await {
+                    tracing::warn!(error = %cleanup, key, "failed to remove unrecorded upload");
+                }
+                return Err(err.into());
+            }
+        };
+
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("media.upload", "media", Some(asset.id)).with_details(json!({
+                    "content_type": asset.content_type,
+                    "size_bytes": asset.size_bytes,
+                })),
+            )
+            .await;
+        Ok(asset.into())
+    }
+
+    /// The file `id` and how to read it. Media is public, like the
+    /// published articles it is embedded in.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no such file or storage fails.
+    pub async fn download(&self, id: i64) -> AppResult<MediaDownload> {
+        let not_found = || AppError::not_found("media not found");
+        let asset = self.repo.find_by_id(id).await?.ok_or_else(not_found)?;
+        let content = self
+            .storage
+            .get(&asset.storage_key)
+            .await?
+            .ok_or_else(not_found)?;
+        Ok(MediaDownload { asset, content })
+    }
+}
@@ -2,34 +2,132 @@ use std::sync::Arc;
 
 use crate::{
     application::{
-        AuthTokenDto, AuthenticatedUser,
+        AuthTokenDto, AuthenticatedUser, PaginationPolicy, ReadOnlySwitch,
         commands::{articles::ArticleCommandService, users::UserCommandService},
+        dto::status::FeaturesDto,
         ports::{
+            archives::Archiver,
             authorization_code::CodeStore,
+            blob_storage::BlobStorage,
+            command_journal::CommandJournal,
+            documents::DocumentConverter,
+            domain_events::{EventHandler, EventPublisher},
+            download_links::DownloadLinkSigner,
+            exports::ArticleRenderer,
+            external_auth::{ExternalAuthenticator, GroupRoleMapping},
+            geoip::AsnLookup,
+            integrity::IntegrityChecker,
+            jobs::{JobControl, JobQueue},
+            notification::EmailSender,
+            oidc_login::UpstreamProvider,
+            rate_limit::{LoginThrottlePolicy, RateLimiter},
             refresh_token::Codec,
-            security::{PasswordHasher, TokenManager},
+            security::{PasswordBreachChecker, PasswordHasher, TokenManager},
+            security_events::SecurityEventSink,
             session_revocation::{
-                Ports, Revocation, SessionMetadataStore, Store, TokenVersionStore,
+                Backend as SessionBackend, Ports, Revocation, SessionMetadataStore, SessionPolicy,
+                Store, TokenVersionStore,
             },
-            time::Clock,
+            static_site::SiteWriter,
+            syndication::{SyndicationOptOutStore, SyndicationTarget},
+            time::{Clock, ClockControl},
+            unit_of_work::UnitOfWork,
+            user_tokens::UserTokenStore,
             util::SlugGenerator,
+            views::ViewCounter,
+            webhooks::WebhookSender,
+        },
+        queries::{
+            articles::ArticleQueryService, audit::service::AuditQueryService,
+            bootstrap::BootstrapQueryService, inspect::InspectQueryService,
+            users::UserQueryService,
         },
-        queries::{articles::ArticleQueryService, users::UserQueryService},
     },
     domain::{
-        ArticleReadRepository, ArticleRevisionRepository, ArticleWriteRepository, UserRepository,
-        article::services::ArticleSlugService,
+        AccessRuleRepository, AccountDeletions, ArticleReadRepository, ArticleRevisionRepository,
+        ArticleWriteRepository, BlockList, ContributorRepository, CustomFieldRepository,
+        MediaRepository, ModerationRepository, PasswordHistory, RoleStore, UserRepository,
+        WebhookRepository, article::services::ArticleSlugService,
     },
 };
 
+mod access_rules;
+mod article_export;
+mod article_views;
+mod audit_recorder;
 mod auth;
+mod blocks;
+mod content_bundle;
+mod content_bundle_format;
+mod custom_field_migration;
+mod document_import;
+mod domain_events;
+mod downloads;
+mod federated_login;
+mod integrity;
+mod jobs;
+mod journal_replay;
+mod media;
+mod moderation;
+mod read_only;
+mod request_limits;
+mod roles;
+mod security_events;
+mod seed;
 mod session;
+mod session_cleanup;
+mod simulated_time;
+mod slug_cache;
+mod static_site;
+mod status;
+mod syndication;
+mod user_import;
+mod user_import_csv;
+mod webhooks;
 
+pub use access_rules::{
+    AccessRulePorts, AccessRuleService, CreateAccessRuleCommand, DEFAULT_RULE_PRIORITY,
+};
+pub use article_export::{ArticleExport, ArticleExportService, INLINE_EXPORT_MAX_CHARS};
+pub use article_views::ArticleViewService;
+pub use audit_recorder::{AuditEvent, AuditRecorder};
 pub use auth::{
-    AuthService, ExchangeAuthorizationCodeRequest, IssueAuthorizationCodeRequest,
-    IssueAuthorizationCodeResult, TokenIntrospection,
+    AuthService, AuthorizationCodeTokens, ExchangeAuthorizationCodeRequest,
+    IssueAuthorizationCodeRequest, IssueAuthorizationCodeResult, TokenIntrospection,
+};
+pub use blocks::BlockListService;
+pub use content_bundle::{
+    BundleExport, ContentBundlePorts, ContentBundleService, ExportBundleQuery, ImportBundleCommand,
+};
+pub use custom_field_migration::CustomFieldMigrationService;
+pub use document_import::DocumentImportService;
+pub use domain_events::{DOMAIN_EVENT_BUFFER, DomainEventBus};
+pub use downloads::{DownloadLinkService, DownloadTarget};
+pub use federated_login::{FederatedCallback, FederatedLoginService, FederatedLoginStart};
+pub use integrity::IntegrityService;
+pub use jobs::JobsService;
+pub use journal_replay::{JournalReplayer, ReplayClock, ReplayDivergence, ReplaySummary};
+pub use media::{MediaDownload, MediaPolicy, MediaService, UploadMediaCommand};
+pub use moderation::{
+    ListModerationCasesQuery, ModerationPorts, ModerationService, ReportArticleCommand,
+    ResolveModerationCaseCommand,
 };
-pub use session::{ListSessionsRequest, RevokeSessionRequest, SessionService};
+pub use read_only::ReadOnlyService;
+pub use request_limits::RequestLimitService;
+pub use roles::{GrantCapabilityCommand, PutRoleCommand, RevokeCapabilityCommand, RoleService};
+pub use security_events::SecurityEventService;
+pub use seed::{SeedCustomField, SeedDocument, SeedPage, SeedService, SeedSummary, SeedUser};
+pub use session::{
+    ListSessionsRequest, RevokeSessionRequest, RevokeUserSessionsRequest, SessionService,
+};
+pub use session_cleanup::SessionCleanupService;
+pub use simulated_time::{MAX_ADVANCE_SECONDS, SimulatedTimeService};
+pub use slug_cache::{SlugCache, SlugCachePolicy, SlugCacheStats};
+pub use static_site::{INDEX_PAGE, StaticSiteService};
+pub use status::StatusService;
+pub use syndication::{SyndicationPolicy, SyndicationService};
+pub use user_import::{UserImportCommand, UserImportService};
+pub use webhooks::{CreateWebhookCommand, WebhookPolicy, WebhookService};
 
 #[must_use]
 pub struct Registry {
@@ -37,8 +135,48 @@ pub struct Registry {
     pub article_commands: Arc<ArticleCommandService>,
     pub article_queries: Arc<ArticleQueryService>,
     pub user_queries: Arc<UserQueryService>,
+    pub bootstrap: Arc<BootstrapQueryService>,
     pub auth: Arc<AuthService>,
     pub sessions: Arc<SessionService>,
+    pub session_cleanup: Arc<SessionCleanupService>,
+    pub inspect: Arc<InspectQueryService>,
+    pub security_events: Arc<SecurityEventService>,
+    pub status: Arc<StatusService>,
+    pub user_import: Arc<UserImportService>,
+    pub document_import: Arc<DocumentImportService>,
+    pub article_exports: Arc<ArticleExportService>,
+    /// Flushes buffered article reads into `view_count`.
+    pub article_views: Arc<ArticleViewService>,
+    pub downloads: Arc<DownloadLinkService>,
+    pub custom_field_migrations: Arc<CustomFieldMigrationService>,
+    pub moderation: Arc<ModerationService>,
+    pub media: Arc<MediaService>,
+    pub blocks: Arc<BlockListService>,
+    /// Operator-defined roles and who has them.
+    pub roles: Arc<RoleService>,
+    pub access_rules: Arc<AccessRuleService>,
+    pub federated_login: Arc<FederatedLoginService>,
+    pub simulated_time: Arc<SimulatedTimeService>,
+    pub jobs: Arc<JobsService>,
+    pub integrity: Arc<IntegrityService>,
+    pub read_only: Arc<ReadOnlyService>,
+    pub request_limits: Arc<RequestLimitService>,
+    pub seed: Arc<SeedService>,
+    /// Whole-site export and import, for moving content between
+    /// environments.
+    pub content_bundles: Arc<ContentBundleService>,
+    /// Static HTML copies of the published site.
+    pub static_site: Arc<StaticSiteService>,
+    pub syndication: Arc<SyndicationService>,
+    pub webhooks: Arc<WebhookService>,
+    /// Every article and user change, for in-process handlers and
+    /// subscribers such as the live event stream.
+    pub domain_events: Arc<DomainEventBus>,
+    /// Public slug lookups; cleared by writes that bypass the services, such
+    /// as the ephemeral reset.
+    pub slug_cache: SlugCache,
+    /// Page sizes of every listing endpoint.
+    pub pagination: PaginationPolicy,
     token_manager: Arc<dyn TokenManager>,
     session_stores: Ports,
     session_revocation_store: Arc<dyn Store>,
@@ -55,83 +193,675 @@ pub struct Dependencies {
     pub article_write_repo: Arc<dyn ArticleWriteRepository>,
     pub article_read_repo: Arc<dyn ArticleReadRepository>,
     pub article_revision_repo: Arc<dyn ArticleRevisionRepository>,
+    pub custom_field_repo: Arc<dyn CustomFieldRepository>,
+    /// Users allowed to edit articles alongside their authors.
+    pub contributor_repo: Arc<dyn ContributorRepository>,
+    pub moderation_repo: Arc<dyn ModerationRepository>,
+    pub media_repo: Arc<dyn MediaRepository>,
+    pub block_list: Arc<dyn BlockList>,
+    /// Hashes of passwords users have replaced.
+    pub password_history: Arc<dyn PasswordHistory>,
+    /// Accounts their owners deleted, awaiting anonymization.
+    pub account_deletions: Arc<dyn AccountDeletions>,
+    /// Custom roles and their assignments to users.
+    pub role_store: Arc<dyn RoleStore>,
+    pub access_rule_repo: Arc<dyn AccessRuleRepository>,
     pub audit_log_repo: Arc<dyn crate::domain::audit::repository::AuditLogRepository>,
+    /// Mailed password reset and email verification tokens.
+    pub user_tokens: Arc<dyn UserTokenStore>,
+    /// Articles opted out of syndication.
+    pub syndication_opt_outs: Arc<dyn SyndicationOptOutStore>,
+    /// Cross-table consistency checks; `None` disables
+    /// `/api/v1/admin/integrity`.
+    pub integrity_checker: Option<Arc<dyn IntegrityChecker>>,
+    /// Lets article and token writes land in one transaction; `None` makes
+    /// each repository write on its own.
+    pub unit_of_work: Option<Arc<dyn UnitOfWork>>,
+    /// Webhooks and their delivery history.
+    pub webhook_repo: Arc<dyn WebhookRepository>,
 }
 
 /// Runtime-facing collaborators required to build `Registry`.
 pub struct RuntimeDependencies {
     pub password_hasher: Arc<dyn PasswordHasher>,
+    /// Finds new passwords that appeared in data breaches.
+    pub password_breach_checker: Arc<dyn PasswordBreachChecker>,
     pub token_manager: Arc<dyn TokenManager>,
     pub refresh_token_codec: Arc<dyn Codec>,
     pub session_revocation_store: Arc<dyn Store>,
+    /// Which implementation `session_revocation_store` is.
+    pub session_backend: SessionBackend,
     pub authorization_code_store: Arc<dyn CodeStore>,
     pub clock: Arc<dyn Clock>,
+    /// Advances `clock`; set only when running with simulated time.
+    pub clock_control: Option<Arc<dyn ClockControl>>,
     pub slugger: Arc<dyn SlugGenerator>,
+    /// Optional external receiver for security incidents (e.g. a webhook).
+    pub security_webhook: Option<Arc<dyn SecurityEventSink>>,
+    /// Runs background work such as bulk user imports.
+    pub job_queue: Arc<dyn JobQueue>,
+    /// Directory tried before local passwords at login (e.g. LDAP).
+    pub external_authenticator: Option<Arc<dyn ExternalAuthenticator>>,
+    /// Roles given to users provisioned by `external_authenticator`.
+    pub group_roles: GroupRoleMapping,
+    /// Upstream OIDC providers users may log in with.
+    pub oidc_providers: Vec<UpstreamProvider>,
+    /// Receives the outcome of every command-service write, for replay.
+    pub command_journal: Option<Arc<dyn CommandJournal>>,
+    /// Reports scheduled jobs for `GET /api/v1/admin/jobs`, when a scheduler
+    /// runs.
+    pub job_control: Option<Arc<dyn JobControl>>,
+    /// Converts uploads for `POST /api/v1/articles/import-document`.
+    pub document_converter: Arc<dyn DocumentConverter>,
+    /// Renders `GET /api/v1/articles/{id}/export`; `None` disables exports.
+    pub article_renderer: Option<Arc<dyn ArticleRenderer>>,
+    /// Packs and unpacks zip content bundles.
+    pub archiver: Arc<dyn Archiver>,
+    /// Receives static HTML exports of the published site.
+    pub site_writer: Arc<dyn SiteWriter>,
+    /// Signs the links completed exports are downloaded from.
+    pub download_links: Arc<dyn DownloadLinkSigner>,
+    /// Resolves client ASNs for access rules; `None` disables ASN rules.
+    pub asn_lookup: Option<Arc<dyn AsnLookup>>,
+    /// Delivers password reset and email verification mail.
+    pub email_sender: Arc<dyn EmailSender>,
+    /// Refuses writes while set; see [`ReadOnlySwitch`].
+    pub read_only: ReadOnlySwitch,
+    /// Counters behind login lockouts and other rate limits.
+    pub rate_limiter: Arc<dyn RateLimiter>,
+    /// When failed logins lock accounts and clients out.
+    pub login_throttle: LoginThrottlePolicy,
+    /// How long sessions can be kept alive by refreshing them.
+    pub session_policy: SessionPolicy,
+    /// How many of a user's passwords a new one may not repeat; zero
+    /// allows reuse.
+    pub password_history_depth: usize,
+    /// How long a deleted account stays restorable before it is anonymized.
+    pub account_deletion_grace: chrono::Duration,
+    /// Bounds of the cache in front of public slug lookups.
+    pub slug_cache: SlugCachePolicy,
+    /// Where published articles are announced; empty disables syndication.
+    pub syndication_targets: Vec<SyndicationTarget>,
+    pub syndication: SyndicationPolicy,
+    /// Posts article events to webhooks; `None` disables delivery.
+    pub webhook_sender: Option<Arc<dyn WebhookSender>>,
+    pub webhooks: WebhookPolicy,
+    /// Default and largest page sizes of listing endpoints.
+    pub pagination: PaginationPolicy,
+    /// Where uploaded media is kept.
+    pub blob_storage: Arc<dyn BlobStorage>,
+    /// Size and content types of accepted uploads.
+    pub media: MediaPolicy,
+    /// Buffers article reads until the next flush.
+    pub view_counter: Arc<dyn ViewCounter>,
+}
+
+/// Article writes and reads, with everything their changes are reported to.
+struct ArticleServices {
+    commands: Arc<ArticleCommandService>,
+    queries: Arc<ArticleQueryService>,
+    document_import: Arc<DocumentImportService>,
+    syndication: Arc<SyndicationService>,
+    webhooks: Arc<WebhookService>,
+    domain_events: Arc<DomainEventBus>,
 }
 
 impl Registry {
     pub fn new(deps: Dependencies, runtime: RuntimeDependencies) -> Self {
+        let status = Self::status_service(&runtime);
+        let slug_cache = SlugCache::new(runtime.slug_cache, Arc::clone(&runtime.clock));
+        let blocks = Self::block_list_service(&deps, &runtime.clock);
+        let articles = Self::article_services(&deps, &runtime, &slug_cache, &blocks);
+        let (seed, content_bundles) = Self::provisioning_services(&deps, &runtime, &slug_cache);
+        let (media, article_views) = Self::media_services(&deps, &runtime);
+        let (article_exports, downloads) =
+            Self::article_export_service(&articles.queries, &runtime);
+        let custom_field_migrations = Self::field_migration_service(&deps, &runtime, &slug_cache);
+        let (security_events, auth, user_commands, federated_login) =
+            Self::login_services(&deps, &runtime, &articles.domain_events);
         let RuntimeDependencies {
             password_hasher,
+            password_breach_checker: _,
             token_manager,
-            refresh_token_codec,
+            refresh_token_codec: _,
             session_revocation_store,
+            session_backend: _,
             authorization_code_store,
             clock,
-            slugger,
+            clock_control,
+            slugger: _,
+            security_webhook: _,
+            job_queue,
+            external_authenticator: _,
+            group_roles: _,
+            oidc_providers: _,
+            command_journal: _,
+            job_control,
+            document_converter: _,
+            article_renderer: _,
+            archiver: _,
+            site_writer,
+            download_links: _,
+            asn_lookup,
+            email_sender: _,
+            read_only,
+            rate_limiter,
+            login_throttle: _,
+            session_policy: _,
+            password_history_depth: _,
+            account_deletion_grace: _,
+            slug_cache: _,
+            syndication_targets: _,
+            syndication: _,
+            webhook_sender: _,
+            webhooks: _,
+            pagination,
+            blob_storage: _,
+            media: _,
+            view_counter: _,
         } = runtime;
-        let session_stores = Ports::from_store(Arc::clone(&session_revocation_store));
-        let user_commands = Arc::new(UserCommandService::new(
+        let (user_queries, bootstrap) =
+            Self::user_query_services(&deps, &clock, &pagination, &status, &federated_login);
+        let (session_stores, session_cleanup, sessions) =
+            Self::session_services(&session_revocation_store, &clock, &deps.audit_log_repo);
+
+        Self {
+            user_commands,
+            article_commands: articles.commands,
+            article_queries: articles.queries,
+            user_queries,
+            bootstrap,
+            auth,
+            sessions,
+            session_cleanup,
+            inspect: Self::inspect_service(&deps, &session_stores),
+            security_events,
+            status,
+            user_import: Self::user_import_service(&deps, &password_hasher, &clock, &job_queue),
+            document_import: articles.document_import,
+            article_exports,
+            article_views,
+            downloads,
+            custom_field_migrations,
+            moderation: Self::moderation_service(&deps, &clock, &slug_cache, &pagination),
+            media,
+            blocks,
+            roles: Self::role_service(&deps, &clock, &read_only),
+            access_rules: Self::access_rule_service(&deps, &clock, asn_lookup),
+            federated_login,
+            simulated_time: Arc::new(SimulatedTimeService::new(Arc::clone(&clock), clock_control)),
+            jobs: Arc::new(JobsService::new(job_control).with_page_limits(pagination.jobs)),
+            integrity: Self::integrity_service(&deps, &clock),
+            read_only: Self::read_only_service(&deps, read_only),
+            request_limits: Arc::new(RequestLimitService::new(rate_limiter, Arc::clone(&clock))),
+            seed,
+            content_bundles,
+            static_site: Self::static_site_service(&deps, &clock, site_writer),
+            syndication: articles.syndication,
+            webhooks: articles.webhooks,
+            domain_events: articles.domain_events,
+            slug_cache,
+            pagination,
+            token_manager,
+            session_stores,
+            session_revocation_store,
+            authorization_code_store,
+            audit_log_repo: deps.audit_log_repo,
+        }
+    }
+
+    fn user_command_service(
+        deps: &Dependencies,
+        runtime: &RuntimeDependencies,
+        security_events: &Arc<SecurityEventService>,
+        domain_events: &Arc<DomainEventBus>,
+    ) -> UserCommandService {
+        let mut user_commands = UserCommandService::new(
             Arc::clone(&deps.user_repo),
-            password_hasher,
-            Arc::clone(&token_manager),
-            refresh_token_codec,
-            Arc::clone(&session_revocation_store),
-            Arc::clone(&clock),
+            Arc::clone(&runtime.password_hasher),
+            Arc::clone(&runtime.token_manager),
+            Arc::clone(&runtime.refresh_token_codec),
+            Arc::clone(&runtime.session_revocation_store),
+            Arc::clone(&runtime.clock),
+        )
+        .with_security_events(Arc::clone(security_events) as Arc<dyn SecurityEventSink>)
+        .with_account_recovery(
+            Arc::clone(&deps.user_tokens),
+            Arc::clone(&runtime.email_sender),
+        )
+        .with_login_throttle(
+            Arc::clone(&runtime.rate_limiter),
+            runtime.login_throttle,
+            Arc::clone(&deps.audit_log_repo),
+        )
+        .with_session_policy(runtime.session_policy)
+        .with_breach_checker(Arc::clone(&runtime.password_breach_checker))
+        .with_password_history(
+            Arc::clone(&deps.password_history),
+            runtime.password_history_depth,
+        )
+        .with_account_deletion(
+            Arc::clone(&deps.account_deletions),
+            Arc::clone(&deps.article_write_repo),
+            runtime.account_deletion_grace,
+        )
+        .with_audit(Arc::clone(&deps.audit_log_repo))
+        .with_read_only(runtime.read_only.clone())
+        .with_custom_roles(Arc::clone(&deps.role_store))
+        .with_event_publisher(Arc::clone(domain_events) as Arc<dyn EventPublisher>);
+        if let Some(unit_of_work) = &deps.unit_of_work {
+            user_commands = user_commands.with_unit_of_work(Arc::clone(unit_of_work));
+        }
+        if let Some(authenticator) = &runtime.external_authenticator {
+            user_commands = user_commands.with_external_authenticator(
+                Arc::clone(authenticator),
+                runtime.group_roles.clone(),
+            );
+        }
+        if let Some(journal) = &runtime.command_journal {
+            user_commands = user_commands.with_journal(Arc::clone(journal));
+        }
+        user_commands
+    }
+
+    fn login_services(
+        deps: &Dependencies,
+        runtime: &RuntimeDependencies,
+        domain_events: &Arc<DomainEventBus>,
+    ) -> (
+        Arc<SecurityEventService>,
+        Arc<AuthService>,
+        Arc<UserCommandService>,
+        Arc<FederatedLoginService>,
+    ) {
+        let security_events = Self::security_event_service(deps, runtime.security_webhook.clone());
+        let user_commands = Arc::new(Self::user_command_service(
+            deps,
+            runtime,
+            &security_events,
+            domain_events,
+        ));
+        let federated_login = Arc::new(FederatedLoginService::new(
+            runtime.oidc_providers.clone(),
+            Arc::clone(&deps.user_repo),
+            Arc::clone(&runtime.password_hasher),
+            Arc::clone(&runtime.clock),
+            Arc::clone(&user_commands),
         ));
+        (
+            security_events,
+            Self::auth_service(runtime),
+            user_commands,
+            federated_login,
+        )
+    }
+
+    fn auth_service(runtime: &RuntimeDependencies) -> Arc<AuthService> {
+        Arc::new(AuthService::new(
+            Arc::clone(&runtime.token_manager),
+            Arc::clone(&runtime.session_revocation_store),
+            Arc::clone(&runtime.authorization_code_store),
+            Arc::clone(&runtime.clock),
+        ))
+    }
+
+    fn security_event_service(
+        deps: &Dependencies,
+        webhook: Option<Arc<dyn SecurityEventSink>>,
+    ) -> Arc<SecurityEventService> {
+        Arc::new(SecurityEventService::new(
+            Arc::clone(&deps.audit_log_repo),
+            webhook,
+        ))
+    }
+
+    fn read_only_service(deps: &Dependencies, switch: ReadOnlySwitch) -> Arc<ReadOnlyService> {
+        Arc::new(ReadOnlyService::new(
+            switch,
+            Arc::clone(&deps.audit_log_repo),
+        ))
+    }
+
+    fn block_list_service(deps: &Dependencies, clock: &Arc<dyn Clock>) -> Arc<BlockListService> {
+        Arc::new(BlockListService::new(
+            Arc::clone(&deps.block_list),
+            Arc::clone(&deps.user_repo),
+            Arc::clone(clock),
+        ))
+    }
+
+    fn role_service(
+        deps: &Dependencies,
+        clock: &Arc<dyn Clock>,
+        read_only: &ReadOnlySwitch,
+    ) -> Arc<RoleService> {
+        Arc::new(
+            RoleService::new(
+                Arc::clone(&deps.role_store),
+                Arc::clone(&deps.user_repo),
+                Arc::clone(clock),
+            )
+            .with_audit(Arc::clone(&deps.audit_log_repo))
+            .with_read_only(read_only.clone()),
+        )
+    }
+
+    fn access_rule_service(
+        deps: &Dependencies,
+        clock: &Arc<dyn Clock>,
+        asn_lookup: Option<Arc<dyn AsnLookup>>,
+    ) -> Arc<AccessRuleService> {
+        let ports = AccessRulePorts {
+            rule_repo: Arc::clone(&deps.access_rule_repo),
+            audit_log_repo: Arc::clone(&deps.audit_log_repo),
+            asn_lookup,
+        };
+        Arc::new(AccessRuleService::new(ports, Arc::clone(clock)))
+    }
+
+    fn status_service(runtime: &RuntimeDependencies) -> Arc<StatusService> {
+        Arc::new(StatusService::new(
+            Arc::clone(&runtime.clock),
+            runtime.session_backend,
+            Self::features(runtime),
+        ))
+    }
+
+    /// Optional features reported by `GET /api/v1/status`.
+    fn features(runtime: &RuntimeDependencies) -> FeaturesDto {
+        FeaturesDto {
+            security_webhook: runtime.security_webhook.is_some(),
+            directory_login: runtime.external_authenticator.is_some(),
+            federated_login: !runtime.oidc_providers.is_empty(),
+        }
+    }
+
+    fn syndication_service(
+        deps: &Dependencies,
+        runtime: &RuntimeDependencies,
+    ) -> Arc<SyndicationService> {
+        Arc::new(
+            SyndicationService::new(
+                runtime.syndication_targets.clone(),
+                Arc::clone(&deps.article_read_repo),
+                Arc::clone(&deps.syndication_opt_outs),
+                Arc::clone(&runtime.job_queue),
+                runtime.syndication.clone(),
+            )
+            .with_audit(Arc::clone(&deps.audit_log_repo))
+            .with_read_only(runtime.read_only.clone()),
+        )
+    }
 
+    fn webhook_service(deps: &Dependencies, runtime: &RuntimeDependencies) -> Arc<WebhookService> {
+        Arc::new(
+            WebhookService::new(
+                Arc::clone(&deps.webhook_repo),
+                runtime.webhook_sender.clone(),
+                Arc::clone(&runtime.job_queue),
+                Arc::clone(&runtime.clock),
+                runtime.webhooks.clone(),
+            )
+            .with_audit(Arc::clone(&deps.audit_log_repo))
+            .with_read_only(runtime.read_only.clone())
+            .with_page_limits(runtime.pagination.shared),
+        )
+    }
+
+    fn article_services(
+        deps: &Dependencies,
+        runtime: &RuntimeDependencies,
+        slug_cache: &SlugCache,
+        blocks: &Arc<BlockListService>,
+    ) -> ArticleServices {
+        let syndication = Self::syndication_service(deps, runtime);
+        let webhooks = Self::webhook_service(deps, runtime);
+        let domain_events = Arc::new(
+            DomainEventBus::default()
+                .with_handler(Arc::new(slug_cache.clone()) as Arc<dyn EventHandler>)
+                .with_handler(Arc::clone(&syndication) as Arc<dyn EventHandler>)
+                .with_handler(Arc::clone(&webhooks) as Arc<dyn EventHandler>),
+        );
         let slug_service = Arc::new(ArticleSlugService::new(
             Arc::clone(&deps.article_read_repo),
-            slugger,
+            Arc::clone(&runtime.slugger),
         ));
-
-        let article_commands = Arc::new(ArticleCommandService::new(
+        let mut article_commands = ArticleCommandService::new(
             Arc::clone(&deps.article_write_repo),
             Arc::clone(&deps.article_read_repo),
             Arc::clone(&deps.article_revision_repo),
-            Arc::clone(&slug_service),
-            Arc::clone(&clock),
+            slug_service,
+            Arc::clone(&runtime.clock),
+        )
+        .with_custom_fields(Arc::clone(&deps.custom_field_repo))
+        .with_contributors(Arc::clone(&deps.contributor_repo))
+        .with_blocks(Arc::clone(blocks))
+        .with_audit(Arc::clone(&deps.audit_log_repo))
+        .with_read_only(runtime.read_only.clone())
+        .with_event_publisher(Arc::clone(&domain_events) as Arc<dyn EventPublisher>);
+        if let Some(journal) = &runtime.command_journal {
+            article_commands = article_commands.with_journal(Arc::clone(journal));
+        }
+        if let Some(unit_of_work) = &deps.unit_of_work {
+            article_commands = article_commands.with_unit_of_work(Arc::clone(unit_of_work));
+        }
+        let article_commands = Arc::new(article_commands);
+        let article_queries = Arc::new(
+            ArticleQueryService::new(
+                Arc::clone(&deps.article_read_repo),
+                Arc::clone(&deps.article_revision_repo),
+            )
+            .with_custom_fields(Arc::clone(&deps.custom_field_repo))
+            .with_contributors(Arc::clone(&deps.contributor_repo))
+            .with_slug_cache(slug_cache.clone())
+            .with_page_limits(runtime.pagination.articles)
+            .with_view_counter(Arc::clone(&runtime.view_counter)),
+        );
+        let document_import = Arc::new(DocumentImportService::new(
+            Arc::clone(&runtime.document_converter),
+            Arc::clone(&article_commands),
         ));
+        ArticleServices {
+            commands: article_commands,
+            queries: article_queries,
+            document_import,
+            syndication,
+            webhooks,
+            domain_events,
+        }
+    }
 
-        let article_queries = Arc::new(ArticleQueryService::new(
-            Arc::clone(&deps.article_read_repo),
-            Arc::clone(&deps.article_revision_repo),
+    fn user_import_service(
+        deps: &Dependencies,
+        password_hasher: &Arc<dyn PasswordHasher>,
+        clock: &Arc<dyn Clock>,
+        job_queue: &Arc<dyn JobQueue>,
+    ) -> Arc<UserImportService> {
+        Arc::new(UserImportService::new(
+            Arc::clone(&deps.user_repo),
+            Arc::clone(password_hasher),
+            Arc::clone(clock),
+            Arc::clone(job_queue),
+        ))
+    }
+
+    fn field_migration_service(
+        deps: &Dependencies,
+        runtime: &RuntimeDependencies,
+        slug_cache: &SlugCache,
+    ) -> Arc<CustomFieldMigrationService> {
+        Arc::new(
+            CustomFieldMigrationService::new(
+                Arc::clone(&deps.custom_field_repo),
+                Arc::clone(&deps.article_read_repo),
+                Arc::clone(&deps.article_write_repo),
+                Arc::clone(&runtime.clock),
+                Arc::clone(&runtime.job_queue),
+            )
+            .with_slug_cache(slug_cache.clone()),
+        )
+    }
+
+    fn user_query_services(
+        deps: &Dependencies,
+        clock: &Arc<dyn Clock>,
+        pagination: &PaginationPolicy,
+        status: &Arc<StatusService>,
+        federated_login: &Arc<FederatedLoginService>,
+    ) -> (Arc<UserQueryService>, Arc<BootstrapQueryService>) {
+        let user_queries = Arc::new(
+            UserQueryService::new(Arc::clone(&deps.user_repo), Arc::clone(clock))
+                .with_page_limits(pagination.users),
+        );
+        let bootstrap = Arc::new(BootstrapQueryService::new(
+            Arc::clone(&user_queries),
+            Arc::clone(status),
+            Arc::clone(federated_login),
         ));
-        let user_queries = Arc::new(UserQueryService::new(Arc::clone(&deps.user_repo)));
-        let auth = Arc::new(AuthService::new(
-            Arc::clone(&token_manager),
-            Arc::clone(&session_revocation_store),
-            Arc::clone(&authorization_code_store),
-            Arc::clone(&clock),
+        (user_queries, bootstrap)
+    }
+
+    fn moderation_service(
+        deps: &Dependencies,
+        clock: &Arc<dyn Clock>,
+        slug_cache: &SlugCache,
+        pagination: &PaginationPolicy,
+    ) -> Arc<ModerationService> {
+        let ports = ModerationPorts {
+            moderation_repo: Arc::clone(&deps.moderation_repo),
+            article_read_repo: Arc::clone(&deps.article_read_repo),
+            article_write_repo: Arc::clone(&deps.article_write_repo),
+            article_revision_repo: Arc::clone(&deps.article_revision_repo),
+            user_repo: Arc::clone(&deps.user_repo),
+            audit_log_repo: Arc::clone(&deps.audit_log_repo),
+        };
+        Arc::new(
+            ModerationService::new(ports, Arc::clone(clock))
+                .with_slug_cache(slug_cache.clone())
+                .with_page_limits(pagination.moderation),
+        )
+    }
+
+    /// Uploaded media, and the buffered count of article reads.
+    fn media_services(
+        deps: &Dependencies,
+        runtime: &RuntimeDependencies,
+    ) -> (Arc<MediaService>, Arc<ArticleViewService>) {
+        let media = MediaService::new(
+            Arc::clone(&deps.media_repo),
+            Arc::clone(&runtime.blob_storage),
+            Arc::clone(&runtime.clock),
+            runtime.media.clone(),
+        )
+        .with_audit(Arc::clone(&deps.audit_log_repo))
+        .with_read_only(runtime.read_only.clone());
+        let article_views = ArticleViewService::new(
+            Arc::clone(&runtime.view_counter),
+            Arc::clone(&deps.article_write_repo),
+        );
+        (Arc::new(media), Arc::new(article_views))
+    }
+
+    fn article_export_service(
+        article_queries: &Arc<ArticleQueryService>,
+        runtime: &RuntimeDependencies,
+    ) -> (Arc<ArticleExportService>, Arc<DownloadLinkService>) {
+        let downloads = Arc::new(DownloadLinkService::new(
+            Arc::clone(&runtime.download_links),
+            Arc::clone(&runtime.clock),
         ));
-        let sessions = Arc::new(SessionService::new(
-            Arc::clone(&session_revocation_store),
-            clock,
+        let exports = Arc::new(ArticleExportService::new(
+            Arc::clone(article_queries),
+            runtime.article_renderer.clone(),
+            Arc::clone(&runtime.clock),
+            Arc::clone(&runtime.job_queue),
+            Arc::clone(&downloads),
         ));
+        (exports, downloads)
+    }
 
-        Self {
-            user_commands,
-            article_commands,
-            article_queries,
-            user_queries,
-            auth,
-            sessions,
-            token_manager,
-            session_stores,
-            session_revocation_store,
-            authorization_code_store,
-            audit_log_repo: deps.audit_log_repo,
-        }
+    fn inspect_service(deps: &Dependencies, session_stores: &Ports) -> Arc<InspectQueryService> {
+        Arc::new(InspectQueryService::new(
+            Arc::clone(&deps.user_repo),
+            Arc::clone(&deps.article_read_repo),
+            Arc::clone(&deps.article_revision_repo),
+            session_stores.clone(),
+        ))
+    }
+
+    fn provisioning_services(
+        deps: &Dependencies,
+        runtime: &RuntimeDependencies,
+        slug_cache: &SlugCache,
+    ) -> (Arc<SeedService>, Arc<ContentBundleService>) {
+        let ports = ContentBundlePorts {
+            user_repo: Arc::clone(&deps.user_repo),
+            article_read_repo: Arc::clone(&deps.article_read_repo),
+            article_write_repo: Arc::clone(&deps.article_write_repo),
+            article_revision_repo: Arc::clone(&deps.article_revision_repo),
+            audit_log_repo: Arc::clone(&deps.audit_log_repo),
+            password_hasher: Arc::clone(&runtime.password_hasher),
+            archiver: Arc::clone(&runtime.archiver),
+        };
+        let content_bundles = ContentBundleService::new(ports, Arc::clone(&runtime.clock))
+            .with_read_only(runtime.read_only.clone())
+            .with_slug_cache(slug_cache.clone());
+        (Self::seed_service(deps, runtime), Arc::new(content_bundles))
+    }
+
+    fn seed_service(deps: &Dependencies, runtime: &RuntimeDependencies) -> Arc<SeedService> {
+        Arc::new(
+            SeedService::new(
+                Arc::clone(&deps.user_repo),
+                Arc::clone(&runtime.password_hasher),
+                Arc::clone(&deps.audit_log_repo),
+                Arc::clone(&runtime.clock),
+            )
+            .with_pages(
+                Arc::clone(&deps.article_write_repo),
+                Arc::clone(&deps.article_read_repo),
+                Arc::clone(&deps.article_revision_repo),
+            )
+            .with_custom_fields(Arc::clone(&deps.custom_field_repo)),
+        )
+    }
+
+    fn static_site_service(
+        deps: &Dependencies,
+        clock: &Arc<dyn Clock>,
+        writer: Arc<dyn SiteWriter>,
+    ) -> Arc<StaticSiteService> {
+        Arc::new(StaticSiteService::new(
+            Arc::clone(&deps.article_read_repo),
+            Arc::clone(&deps.audit_log_repo),
+            writer,
+            Arc::clone(clock),
+        ))
+    }
+
+    fn integrity_service(deps: &Dependencies, clock: &Arc<dyn Clock>) -> Arc<IntegrityService> {
+        Arc::new(IntegrityService::new(
+            deps.integrity_checker.clone(),
+            Arc::clone(&deps.audit_log_repo),
+            Arc::clone(clock),
+        ))
+    }
+
+    fn session_services(
+        session_revocation_store: &Arc<dyn Store>,
+        clock: &Arc<dyn Clock>,
+        audit_log_repo: &Arc<dyn crate::domain::audit::repository::AuditLogRepository>,
+    ) -> (Ports, Arc<SessionCleanupService>, Arc<SessionService>) {
+        let session_stores = Ports::from_store(Arc::clone(session_revocation_store));
+        let session_cleanup = Arc::new(SessionCleanupService::new(
+            Arc::clone(&session_stores.session_metadata),
+            Arc::clone(clock),
+        ));
+        let sessions = Arc::new(
+            SessionService::new(Arc::clone(session_revocation_store), Arc::clone(clock))
+                .with_audit(Arc::clone(audit_log_repo)),
+        );
+        (session_stores, session_cleanup, sessions)
     }
 
     #[must_use]
@@ -181,8 +911,10 @@ impl Registry {
                 code: code.to_string(),
                 code_verifier: code_verifier.map(std::string::ToString::to_string),
                 redirect_uri: redirect_uri.map(std::string::ToString::to_string),
+                issuer: None,
             })
             .await
+            .map(|tokens| tokens.token)
     }
 
     #[must_use]
@@ -190,6 +922,12 @@ impl Registry {
         Arc::clone(&self.audit_log_repo)
     }
 
+    /// Audit log listings over `audit_log_repo`, paged by the configured
+    /// limits.
+    pub fn audit_queries(&self) -> AuditQueryService {
+        AuditQueryService::new(self.audit_log_repo()).with_page_limits(self.pagination.audit_logs)
+    }
+
     /// Backwards-compatible wrapper that delegates token authentication and
     /// capability checks to the dedicated auth service.
     ///
@@ -0,0 +1,297 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use super::slug_cache::SlugCache;
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser, CursorPage, ModerationCaseDto, PageLimits,
+    ports::{security_events::ClientInfo, time::Clock},
+};
+use crate::domain::{
+    ArticleId, ArticleReadRepository, ArticleRevisionRepository, ArticleUpdate,
+    ArticleWriteRepository, CaseStatus, ModerationCase, ModerationDecision, ModerationRepository,
+    NewReport, ReportReason, Resolution, UserRepository, UserUpdate,
+    audit::{entity::NewAuditLog, repository::AuditLogRepository},
+    errors::DomainError,
+};
+
+pub struct ReportArticleCommand {
+    pub article_id: i64,
+    pub reason: ReportReason,
+    pub details: Option<String>,
+}
+
+pub struct ListModerationCasesQuery {
+    pub status: CaseStatus,
+    pub limit: u32,
+    /// Id of the last case on the previous page.
+    pub cursor: Option<String>,
+}
+
+pub struct ResolveModerationCaseCommand {
+    pub case_id: i64,
+    pub resolution: Resolution,
+    pub note: Option<String>,
+}
+
+/// Collaborators `ModerationService` acts through when resolving cases.
+pub struct ModerationPorts {
+    pub moderation_repo: Arc<dyn ModerationRepository>,
+    pub article_read_repo: Arc<dyn ArticleReadRepository>,
+    pub article_write_repo: Arc<dyn ArticleWriteRepository>,
+    pub article_revision_repo: Arc<dyn ArticleRevisionRepository>,
+    pub user_repo: Arc<dyn UserRepository>,
+    pub audit_log_repo: Arc<dyn AuditLogRepository>,
+}
+
+/// Reader reports about published articles and the queue moderators work
+/// through.
+///
+/// Filing a report and every resolution is written to the audit log along
+/// with the client that made the request.
+pub struct ModerationService {
+    ports: ModerationPorts,
+    clock: Arc<dyn Clock>,
+    slug_cache: SlugCache,
+    page_limits: PageLimits,
+}
+
+impl ModerationService {
+    #[must_use]
+    pub fn new(ports: ModerationPorts, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            ports,
+            clock,
+            slug_cache: SlugCache::default(),
+            page_limits: PageLimits::default(),
+        }
+    }
+
+    /// Drop articles unpublished by a resolution from `cache`.
+    #[must_use]
+    pub fn with_slug_cache(mut self, cache: SlugCache) -> Self {
+        self.slug_cache = cache;
+        self
+    }
+
+    /// Size case listing pages by `limits`.
+    #[must_use]
+    pub const fn with_page_limits(mut self, limits: PageLimits) -> Self {
+        self.page_limits = limits;
+        self
+    }
+
+    /// File a report about a published article. Anyone may report; the
+    /// reporter is recorded when signed in.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the article does not exist or is not published,
+    /// the details are too long, or persistence fails.
+    pub async fn report(
+        &self,
+        reporter: Option<&AuthenticatedUser>,
+        client: &ClientInfo,
+        command: ReportArticleCommand,
+    ) -> AppResult<()> {
+        let article_id = ArticleId::new(command.article_id)?;
+        let published = self
+            .ports
+            .article_read_repo
+            .find_by_id(article_id)
+            .await?
+            .is_some_and(|article| article.published);
+        if !published {
+            return Err(AppError::not_found("article not found"));
+        }
+
+        let report = NewReport::new(
+            article_id,
+            command.reason,
+            command.details,
+            self.clock.now(),
+        )?;
+        let case = self.ports.moderation_repo.report(report).await?;
+
+        self.audit(NewAuditLog {
+            user_id: reporter.map(|reporter| reporter.id),
+            impersonator_id: reporter.and_then(|reporter| reporter.impersonator),
+            action: "moderation.report".into(),
+            resource_type: "article".into(),
+            resource_id: Some(command.article_id),
+            details: Some(json!({ "case_id": case.id, "reason": command.reason })),
+            ip_address: client.ip_address.clone(),
+            user_agent: client.user_agent.clone(),
+        })
+        .await
+    }
+
+    /// List cases with the given status, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `moderation:review`, the cursor is
+    /// invalid, or the repository lookup fails.
+    pub async fn list_cases(
+        &self,
+        actor: &AuthenticatedUser,
+        query: ListModerationCasesQuery,
+    ) -> AppResult<CursorPage<ModerationCaseDto>> {
+        ensure_can_review(actor)?;
+        let limit = self.page_limits.apply(query.limit);
+        let after = query
+            .cursor
+            .as_deref()
+            .map(|cursor| {
+                cursor
+                    .parse::<i64>()
+                    .map_err(|_| AppError::validation("invalid cursor"))
+            })
+            .transpose()?;
+
+        let mut cases = self
+            .ports
+            .moderation_repo
+            .list(query.status, limit + 1, after)
+            .await?;
+        let next_cursor = if cases.len() > limit as usize {
+            cases.truncate(limit as usize);
+            cases.last().map(|case| case.id.to_string())
+        } else {
+            None
+        };
+        let items = cases.into_iter().map(Into::into).collect();
+        Ok(CursorPage::new(items, next_cursor).with_limit(limit))
+    }
+
+    /// Resolve an open case, applying the moderator's decision to the
+    /// article and its author.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `moderation:review`, the case is
+    /// missing or already resolved, the note is too long, the actor would ban
+    /// themselves, or persistence fails.
+    pub async fn resolve(
+        &self,
+        actor: &AuthenticatedUser,
+        client: &ClientInfo,
+        command: ResolveModerationCaseCommand,
+    ) -> AppResult<ModerationCaseDto> {
+        ensure_can_review(actor)?;
+        let note = command
+            .note
+            .map(|note| note.trim().to_owned())
+            .filter(|note| !note.is_empty());
+        if note
+            .as_ref()
+            .is_some_and(|note| note.chars().count() > ModerationDecision::MAX_NOTE_CHARS)
+        {
+            return Err(AppError::validation(format!(
+                "resolution note must be at most {} characters",
+                ModerationDecision::MAX_NOTE_CHARS
+            )));
+        }
+
+        let mut case = self
+            .ports
+            .moderation_repo
+            .find_by_id(command.case_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("moderation case not found"))?;
+        case.resolve(ModerationDecision {
+            resolution: command.resolution,
+            resolved_by: actor.id,
+            note,
+            resolved_at: self.clock.now(),
+        })
+        .map_err(conflict)?;
+
+        self.apply(actor, &case, command.resolution).await?;
+        if !self.ports.moderation_repo.save_decision(&case).await? {
+            return Err(AppError::conflict(format!(
+                "moderation case {} is already resolved",
+                case.id
+            )));
+        }
+
+        self.audit(NewAuditLog {
+            user_id: Some(actor.id),
+            impersonator_id: actor.impersonator,
+            action: format!("moderation.{}", command.resolution),
+            resource_type: "moderation_case".into(),
+            resource_id: Some(case.id),
+            details: Some(json!({ "article_id": i64::from(case.article_id) })),
+            ip_address: client.ip_address.clone(),
+            user_agent: client.user_agent.clone(),
+        })
+        .await?;
+        Ok(case.into())
+    }
+
+    async fn apply(
+        &self,
+        actor: &AuthenticatedUser,
+        case: &ModerationCase,
+        resolution: Resolution,
+    ) -> AppResult<()> {
+        if resolution == Resolution::Dismiss {
+            return Ok(());
+        }
+        let mut article = self
+            .ports
+            .article_read_repo
+            .find_by_id(case.article_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("article not found"))?;
+        if resolution == Resolution::BanAuthor && article.author_id == actor.id {
+            return Err(AppError::validation("moderators cannot ban themselves"));
+        }
+
+        if article.published {
+            let original_updated_at = article.updated_at;
+            article.unpublish(self.clock.now());
+            let mut update =
+                ArticleUpdate::new(article.id, original_updated_at).with_publish_state(false, None);
+            update.set_updated_at(article.updated_at);
+            let updated = self
+                .ports
+                .article_write_repo
+                .update(update)
+                .await
+                .map_err(conflict)?;
+            self.slug_cache.forget(&updated.slug);
+            self.ports
+                .article_revision_repo
+                .append(&updated, Some(actor.id))
+                .await?;
+        }
+        if resolution == Resolution::BanAuthor {
+            self.ports
+                .user_repo
+                .update(UserUpdate::new(article.author_id).with_is_active(false))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn audit(&self, entry: NewAuditLog) -> AppResult<()> {
+        self.ports.audit_log_repo.insert(entry).await?;
+        Ok(())
+    }
+}
+
+fn ensure_can_review(actor: &AuthenticatedUser) -> AppResult<()> {
+    if actor.has_capability("moderation", "review") {
+        Ok(())
+    } else {
+        Err(AppError::forbidden("missing capability moderation:review"))
+    }
+}
+
+fn conflict(err: DomainError) -> AppError {
+    match err {
+        DomainError::Conflict(message) => AppError::conflict(message),
+        other => other.into(),
+    }
+}
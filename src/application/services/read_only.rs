@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser, ReadOnlySwitch, dto::status::ReadOnlyDto,
+};
+use crate::domain::audit::{entity::NewAuditLog, repository::AuditLogRepository};
+
+/// Reads and flips read-only mode for admins.
+pub struct ReadOnlyService {
+    switch: ReadOnlySwitch,
+    audit_log_repo: Arc<dyn AuditLogRepository>,
+}
+
+impl ReadOnlyService {
+    #[must_use]
+    pub fn new(switch: ReadOnlySwitch, audit_log_repo: Arc<dyn AuditLogRepository>) -> Self {
+        Self {
+            switch,
+            audit_log_repo,
+        }
+    }
+
+    /// Whether writes are currently refused.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.switch.is_enabled()
+    }
+
+    /// The current mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller lacks `admin:inspect`.
+    pub fn current(&self, actor: &AuthenticatedUser) -> AppResult<ReadOnlyDto> {
+        ensure_capability(actor, "admin", "inspect")?;
+        Ok(self.view())
+    }
+
+    /// Turn read-only mode on or off. The change applies to this process
+    /// only and lasts until the next change or restart.
+    ///
+    /// Changes are audited on a best-effort basis: with the database in
+    /// read-only mode the entry may not be writable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller lacks `admin:maintenance`.
+    pub async fn set(&self, actor: &AuthenticatedUser, enabled: bool) -> AppResult<ReadOnlyDto> {
+        ensure_capability(actor, "admin", "maintenance")?;
+        if self.switch.set(enabled) != enabled {
+            tracing::warn!(
+                actor = i64::from(actor.id),
+                enabled,
+                "read-only mode changed"
+            );
+            let entry = NewAuditLog {
+                user_id: Some(actor.id),
+                impersonator_id: actor.impersonator,
+                action: "read_only.set".into(),
+                resource_type: "read_only".into(),
+                resource_id: None,
+                details: Some(json!({ "enabled": enabled })),
+                ip_address: None,
+                user_agent: None,
+            };
+            if let Err(err) = self.audit_log_repo.insert(entry).await {
+                tracing::warn!(error = %err, "failed to audit read-only mode change");
+            }
+        }
+        Ok(self.view())
+    }
+
+    fn view(&self) -> ReadOnlyDto {
+        ReadOnlyDto {
+            enabled: self.switch.is_enabled(),
+        }
+    }
+}
+
+fn ensure_capability(actor: &AuthenticatedUser, resource: &str, action: &str) -> AppResult<()> {
+    if actor.has_capability(resource, action) {
+        Ok(())
+    } else {
+        Err(AppError::forbidden(format!(
+            "missing capability {resource}:{action}"
+        )))
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+
+use crate::application::{
+    AppError, AppResult,
+    ports::{
+        rate_limit::{RateLimiter, retry_after_secs},
+        time::Clock,
+    },
+};
+
+/// Per-client request budgets, counted in one-minute windows.
+pub struct RequestLimitService {
+    limiter: Arc<dyn RateLimiter>,
+    clock: Arc<dyn Clock>,
+}
+
+impl RequestLimitService {
+    #[must_use]
+    pub fn new(limiter: Arc<dyn RateLimiter>, clock: Arc<dyn Clock>) -> Self {
+        Self { limiter, clock }
+    }
+
+    /// Count a request against `key`, which may send `per_minute` requests a
+    /// minute. A key that goes over is refused for the next minute.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TooManyRequests` with the seconds left while `key` is over
+    /// its budget, or an error if the counters are unavailable.
+    pub async fn check(&self, key: &str, per_minute: u32) -> AppResult<()> {
+        let now = self.clock.now();
+        if let Some(until) = self.limiter.locked_until(key, now).await? {
+            return Err(too_many_requests(retry_after_secs(until, now)));
+        }
+        let window = Duration::minutes(1);
+        if self.limiter.increment(key, window, now).await? > per_minute {
+            let until = now + window;
+            self.limiter.lock(key, until, now).await?;
+            return Err(too_many_requests(retry_after_secs(until, now)));
+        }
+        Ok(())
+    }
+}
+
+fn too_many_requests(retry_after_secs: u64) -> AppError {
+    AppError::too_many_requests("too many requests; try again later", retry_after_secs)
+}
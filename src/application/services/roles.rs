@@ -0,0 +1,298 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use super::audit_recorder::{AuditEvent, AuditRecorder};
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser, CapabilityGrantsDto, CapabilityView, CustomRoleDto,
+    ReadOnlySwitch, RoleAssignmentDto, ports::time::Clock,
+};
+use crate::domain::{
+    Capability, CustomRole, RoleName, RoleStore, UserId, UserRepository,
+    audit::repository::AuditLogRepository,
+};
+
+pub struct PutRoleCommand {
+    pub name: String,
+    pub description: Option<String>,
+    pub capabilities: Vec<CapabilityView>,
+}
+
+pub struct GrantCapabilityCommand {
+    pub user_id: i64,
+    /// `resource:action`.
+    pub capability: String,
+}
+
+pub struct RevokeCapabilityCommand {
+    pub user_id: i64,
+    /// `resource:action`.
+    pub capability: String,
+}
+
+/// Operator-defined roles, which users have them, and capabilities granted
+/// to users individually.
+///
+/// Changes reach a user's tokens at their next login or token refresh;
+/// tokens already issued keep what they were issued with.
+pub struct RoleService {
+    store: Arc<dyn RoleStore>,
+    user_repo: Arc<dyn UserRepository>,
+    clock: Arc<dyn Clock>,
+    audit: AuditRecorder,
+    read_only: ReadOnlySwitch,
+}
+
+impl RoleService {
+    #[must_use]
+    pub fn new(
+        store: Arc<dyn RoleStore>,
+        user_repo: Arc<dyn UserRepository>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            store,
+            user_repo,
+            clock,
+            audit: AuditRecorder::default(),
+            read_only: ReadOnlySwitch::default(),
+        }
+    }
+
+    /// Record role changes and assignments in `audit_log_repo`.
+    #[must_use]
+    pub fn with_audit(mut self, audit_log_repo: Arc<dyn AuditLogRepository>) -> Self {
+        self.audit = AuditRecorder::new(audit_log_repo);
+        self
+    }
+
+    /// Refuse role changes while `switch` is on.
+    #[must_use]
+    pub fn with_read_only(mut self, switch: ReadOnlySwitch) -> Self {
+        self.read_only = switch;
+        self
+    }
+
+    /// Every custom role, by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `roles:manage` or the store
+    /// fails.
+    pub async fn list(&self, actor: &AuthenticatedUser) -> AppResult<Vec<CustomRoleDto>> {
+        ensure_capability(actor)?;
+        let roles = self.store.list().await?;
+        Ok(roles.into_iter().map(CustomRoleDto::from).collect())
+    }
+
+    /// Define a role, or redefine the one with the same name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error while read-only, if the actor lacks `roles:manage`,
+    /// the name, description or a capability is invalid, or the store
+    /// fails.
+    pub async fn put(
+        &self,
+        actor: &AuthenticatedUser,
+        command: PutRoleCommand,
+    ) -> AppResult<CustomRoleDto> {
+        ensure_capability(actor)?;
+        self.read_only.ensure_writable()?;
+        let name = RoleName::new(&command.name)?;
+        let capabilities = command
+            .capabilities
+            .into_iter()
+            .map(|cap| Capability::new(cap.resource, cap.action))
+            .collect();
+        let now = self.clock.now();
+        let role = match self.store.find(&name).await? {
+            Some(existing) => existing.redefine(command.description, capabilities, now)?,
+            None => CustomRole::new(name, command.description, capabilities, now)?,
+        };
+        let role = self.store.save(role).await?;
+        let capabilities: Vec<_> = role.capabilities.iter().map(ToString::to_string).collect();
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("role.put", "role", None).with_details(
+                    json!({ "name": role.name.as_str(), "capabilities": capabilities }),
+                ),
+            )
+            .await;
+        Ok(role.into())
+    }
+
+    /// Remove a role. Its users get their built-in role's capabilities from
+    /// their next token on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error while read-only, if the actor lacks `roles:manage`,
+    /// there is no such role, or the store fails.
+    pub async fn delete(&self, actor: &AuthenticatedUser, name: &str) -> AppResult<()> {
+        ensure_capability(actor)?;
+        self.read_only.ensure_writable()?;
+        let name = RoleName::new(name)?;
+        if !self.store.delete(&name).await? {
+            return Err(AppError::not_found("role not found"));
+        }
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("role.delete", "role", None)
+                    .with_details(json!({ "name": name.as_str() })),
+            )
+            .await;
+        Ok(())
+    }
+
+    /// Give `user_id` the custom role `name`, or take theirs away with
+    /// `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error while read-only, if the actor lacks `roles:manage`,
+    /// the user or role does not exist, or the store fails.
+    pub async fn assign(
+        &self,
+        actor: &AuthenticatedUser,
+        user_id: i64,
+        name: Option<&str>,
+    ) -> AppResult<RoleAssignmentDto> {
+        ensure_capability(actor)?;
+        self.read_only.ensure_writable()?;
+        let user_id = UserId::new(user_id)?;
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("user not found"))?;
+        let name = name.map(RoleName::new).transpose()?;
+        self.store.assign(user.id, name.as_ref()).await?;
+        let custom_role = name.map(|name| name.to_string());
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("user.custom_role_change", "user", Some(i64::from(user.id)))
+                    .with_details(json!({ "custom_role": custom_role })),
+            )
+            .await;
+        Ok(RoleAssignmentDto {
+            user_id: user.id.into(),
+            role: user.role,
+            custom_role,
+        })
+    }
+
+    /// Capabilities granted to `user_id` on top of those of their role.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `roles:manage`, the user does not
+    /// exist, or the store fails.
+    pub async fn capability_grants(
+        &self,
+        actor: &AuthenticatedUser,
+        user_id: i64,
+    ) -> AppResult<CapabilityGrantsDto> {
+        ensure_capability(actor)?;
+        let user_id = self.existing_user(user_id).await?;
+        self.grants_of(user_id).await
+    }
+
+    /// Grant a user a capability on top of those of their role. Granting
+    /// one the user already has changes nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error while read-only, if the actor lacks `roles:manage`,
+    /// the capability is not one the server checks, the user does not
+    /// exist, or the store fails.
+    pub async fn grant_capability(
+        &self,
+        actor: &AuthenticatedUser,
+        command: GrantCapabilityCommand,
+    ) -> AppResult<CapabilityGrantsDto> {
+        ensure_capability(actor)?;
+        self.read_only.ensure_writable()?;
+        let capability = known_capability(&command.capability)?;
+        let user_id = self.existing_user(command.user_id).await?;
+        if self.store.grant_capability(user_id, &capability).await? {
+            self.audit
+                .record(
+                    Some(actor.id),
+                    AuditEvent::new("user.capability_grant", "user", Some(i64::from(user_id)))
+                        .with_details(json!({ "capability": capability.to_string() })),
+                )
+                .await;
+        }
+        self.grants_of(user_id).await
+    }
+
+    /// Take back a capability granted to a user individually.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error while read-only, if the actor lacks `roles:manage`,
+    /// the capability is malformed, the user does not exist or was not
+    /// granted it, or the store fails.
+    pub async fn revoke_capability(
+        &self,
+        actor: &AuthenticatedUser,
+        command: RevokeCapabilityCommand,
+    ) -> AppResult<CapabilityGrantsDto> {
+        ensure_capability(actor)?;
+        self.read_only.ensure_writable()?;
+        let capability: Capability = command.capability.parse()?;
+        let user_id = self.existing_user(command.user_id).await?;
+        if !self.store.revoke_capability(user_id, &capability).await? {
+            return Err(AppError::not_found("capability not granted to this user"));
+        }
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("user.capability_revoke", "user", Some(i64::from(user_id)))
+                    .with_details(json!({ "capability": capability.to_string() })),
+            )
+            .await;
+        self.grants_of(user_id).await
+    }
+
+    async fn existing_user(&self, user_id: i64) -> AppResult<UserId> {
+        let user_id = UserId::new(user_id)?;
+        self.user_repo
+            .find_by_id(user_id)
+            .await?
+            .map(|user| user.id)
+            .ok_or_else(|| AppError::not_found("user not found"))
+    }
+
+    async fn grants_of(&self, user_id: UserId) -> AppResult<CapabilityGrantsDto> {
+        let capabilities = self.store.capabilities_of(user_id).await?;
+        Ok(CapabilityGrantsDto {
+            user_id: user_id.into(),
+            capabilities: capabilities.into_iter().map(CapabilityView::from).collect(),
+        })
+    }
+}
+
+/// Parse `name`, refusing capabilities the server never checks.
+fn known_capability(name: &str) -> AppResult<Capability> {
+    let capability: Capability = name.parse()?;
+    if Capability::known().contains(&capability) {
+        Ok(capability)
+    } else {
+        Err(AppError::validation(format!(
+            "unknown capability '{capability}'"
+        )))
+    }
+}
+
+fn ensure_capability(actor: &AuthenticatedUser) -> AppResult<()> {
+    if actor.has_capability("roles", "manage") {
+        Ok(())
+    } else {
+        Err(AppError::forbidden("missing capability roles:manage"))
+    }
+}
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{
+    application::{
+        AppError, AppResult,
+        ports::security_events::{SecurityEventSink, TOKEN_REUSE_AUDIT_ACTION, TokenReuseIncident},
+    },
+    async_support::{BoxFuture, boxed},
+    domain::{UserId, audit::entity::NewAuditLog, audit::repository::AuditLogRepository},
+};
+
+/// Turns security incidents into an audit entry, a structured `security`
+/// tracing event with a running counter, and an optional webhook delivery.
+pub struct SecurityEventService {
+    audit_log_repo: Arc<dyn AuditLogRepository>,
+    webhook: Option<Arc<dyn SecurityEventSink>>,
+    token_reuse_total: AtomicU64,
+}
+
+impl SecurityEventService {
+    #[must_use]
+    pub fn new(
+        audit_log_repo: Arc<dyn AuditLogRepository>,
+        webhook: Option<Arc<dyn SecurityEventSink>>,
+    ) -> Self {
+        Self {
+            audit_log_repo,
+            webhook,
+            token_reuse_total: AtomicU64::new(0),
+        }
+    }
+
+    /// Number of refresh-token reuse incidents reported since startup.
+    #[must_use]
+    pub fn token_reuse_total(&self) -> u64 {
+        self.token_reuse_total.load(Ordering::Relaxed)
+    }
+
+    fn audit_entry(incident: &TokenReuseIncident) -> AppResult<NewAuditLog> {
+        let details = serde_json::to_value(incident)
+            .map_err(|err| AppError::infrastructure(err.to_string()))?;
+
+        Ok(NewAuditLog {
+            user_id: UserId::new(incident.user_id).ok(),
+            impersonator_id: None,
+            action: TOKEN_REUSE_AUDIT_ACTION.into(),
+            resource_type: "session".into(),
+            resource_id: None,
+            details: Some(details),
+            ip_address: incident.suspect.ip_address.clone(),
+            user_agent: incident.suspect.user_agent.clone(),
+        })
+    }
+}
+
+impl SecurityEventSink for SecurityEventService {
+    fn token_reuse_detected<'a>(
+        &'a self,
+        incident: &'a TokenReuseIncident,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let total = self.token_reuse_total.fetch_add(1, Ordering::Relaxed) + 1;
+            tracing::warn!(
+                target: "security",
+                event = "refresh_token_reuse",
+                user_id = incident.user_id,
+                session_id = %incident.session_id,
+                family_id = incident.family_id.as_deref().unwrap_or_default(),
+                legitimate_ip = incident.legitimate.ip_address.as_deref().unwrap_or_default(),
+                legitimate_user_agent = incident.legitimate.user_agent.as_deref().unwrap_or_default(),
+                suspect_ip = incident.suspect.ip_address.as_deref().unwrap_or_default(),
+                suspect_user_agent = incident.suspect.user_agent.as_deref().unwrap_or_default(),
+                token_reuse_total = total,
+                "refresh token reuse detected"
+            );
+
+            if let Some(webhook) = &self.webhook
+                && let Err(err) = webhook.token_reuse_detected(incident).await
+            {
+                tracing::warn!(error = %err, "security webhook delivery failed");
+            }
+
+            self.audit_log_repo
+                .insert(Self::audit_entry(incident)?)
+                .await
+                .map_err(AppError::from)
+        })
+    }
+}
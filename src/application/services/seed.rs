@@ -0,0 +1,309 @@
+//! Declarative provisioning of fresh environments.
+//!
+//! A seed document lists users, custom field definitions and initial pages.
+//! Applying it creates what is missing and is safe to repeat on every start:
+//! existing users only have their role brought in line, and existing pages
+//! are left as they are, so edits made after seeding survive.
+use std::sync::Arc;
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::application::{
+    AppError, AppResult,
+    commands::users::{unusable_password_hash, validate_password},
+    ports::{security::PasswordHasher, time::Clock},
+};
+use crate::domain::{
+    ArticleBody, ArticleReadRepository, ArticleRevisionRepository, ArticleSlug, ArticleTitle,
+    ArticleVisibility, ArticleWriteRepository, CustomFieldDefinition, CustomFieldRepository,
+    CustomFields, Email, LoginIdentifier, NewArticle, NewUser, PasswordHash, Role, Tag,
+    UserRepository, UserUpdate, Username,
+    audit::{entity::NewAuditLog, repository::AuditLogRepository},
+};
+
+/// The state a fresh environment starts from.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeedDocument {
+    #[serde(default)]
+    pub users: Vec<SeedUser>,
+    #[serde(default)]
+    pub custom_fields: Vec<SeedCustomField>,
+    #[serde(default)]
+    pub pages: Vec<SeedPage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeedUser {
+    pub username: String,
+    #[serde(default)]
+    pub role: Role,
+    /// Stored as verified, since the operator declared it.
+    pub email: Option<String>,
+    /// Argon2 hash to store as is; preferred over `password`.
+    pub password_hash: Option<String>,
+    /// Plain password, for staging and preview environments. Without either
+    /// the user must reset their password before logging in.
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeedCustomField {
+    pub name: String,
+    /// One of `text`, `number`, `boolean` or `date`.
+    #[serde(rename = "type")]
+    pub field_type: String,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeedPage {
+    /// Identifies the page between runs.
+    pub slug: String,
+    pub title: String,
+    pub body: String,
+    /// Username of an existing or seeded user.
+    pub author: String,
+    #[serde(default)]
+    pub published: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// What applying a seed changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SeedSummary {
+    pub users_created: usize,
+    pub users_updated: usize,
+    pub custom_fields_saved: usize,
+    pub pages_created: usize,
+}
+
+impl SeedSummary {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.users_created == 0
+            && self.users_updated == 0
+            && self.custom_fields_saved == 0
+            && self.pages_created == 0
+    }
+}
+
+/// Applies [`SeedDocument`]s directly to the repositories; nothing is
+/// journaled and no capability is checked, as seeding runs before anyone
+/// can log in.
+pub struct SeedService {
+    user_repo: Arc<dyn UserRepository>,
+    password_hasher: Arc<dyn PasswordHasher>,
+    audit_log_repo: Arc<dyn AuditLogRepository>,
+    clock: Arc<dyn Clock>,
+    articles: Option<PageRepos>,
+    custom_field_repo: Option<Arc<dyn CustomFieldRepository>>,
+}
+
+struct PageRepos {
+    write: Arc<dyn ArticleWriteRepository>,
+    read: Arc<dyn ArticleReadRepository>,
+    revisions: Arc<dyn ArticleRevisionRepository>,
+}
+
+impl SeedService {
+    #[must_use]
+    pub fn new(
+        user_repo: Arc<dyn UserRepository>,
+        password_hasher: Arc<dyn PasswordHasher>,
+        audit_log_repo: Arc<dyn AuditLogRepository>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            user_repo,
+            password_hasher,
+            audit_log_repo,
+            clock,
+            articles: None,
+            custom_field_repo: None,
+        }
+    }
+
+    /// Allow seeds to create pages.
+    #[must_use]
+    pub fn with_pages(
+        mut self,
+        write: Arc<dyn ArticleWriteRepository>,
+        read: Arc<dyn ArticleReadRepository>,
+        revisions: Arc<dyn ArticleRevisionRepository>,
+    ) -> Self {
+        self.articles = Some(PageRepos {
+            write,
+            read,
+            revisions,
+        });
+        self
+    }
+
+    /// Allow seeds to define custom fields.
+    #[must_use]
+    pub fn with_custom_fields(mut self, repo: Arc<dyn CustomFieldRepository>) -> Self {
+        self.custom_field_repo = Some(repo);
+        self
+    }
+
+    /// Create what `seed` declares and is missing. Custom fields come first
+    /// and users before pages, so pages can name seeded authors. A change is
+    /// recorded in the audit log as `seed.apply`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry is invalid, a page's author does not
+    /// exist, the seed uses pages or custom fields without their
+    /// repositories, or persistence fails. Entries before the failing one
+    /// stay applied; fixing the seed and applying it again completes it.
+    pub async fn apply(&self, seed: &SeedDocument) -> AppResult<SeedSummary> {
+        let mut summary = SeedSummary::default();
+        for field in &seed.custom_fields {
+            if self.apply_custom_field(field).await? {
+                summary.custom_fields_saved += 1;
+            }
+        }
+        for user in &seed.users {
+            match self.apply_user(user).await? {
+                Applied::Created => summary.users_created += 1,
+                Applied::Updated => summary.users_updated += 1,
+                Applied::Unchanged => {}
+            }
+        }
+        for page in &seed.pages {
+            if self.apply_page(page).await? {
+                summary.pages_created += 1;
+            }
+        }
+
+        if !summary.is_empty() {
+            self.audit_log_repo
+                .insert(NewAuditLog {
+                    user_id: None,
+                    impersonator_id: None,
+                    action: "seed.apply".into(),
+                    resource_type: "seed".into(),
+                    resource_id: None,
+                    details: Some(json!({
+                        "users_created": summary.users_created,
+                        "users_updated": summary.users_updated,
+                        "custom_fields_saved": summary.custom_fields_saved,
+                        "pages_created": summary.pages_created,
+                    })),
+                    ip_address: None,
+                    user_agent: None,
+                })
+                .await?;
+        }
+        Ok(summary)
+    }
+
+    async fn apply_custom_field(&self, field: &SeedCustomField) -> AppResult<bool> {
+        let repo = self.custom_field_repo.as_ref().ok_or_else(|| {
+            AppError::validation("the seed defines custom fields, which are not enabled")
+        })?;
+        let definition = CustomFieldDefinition::new(
+            field.name.clone(),
+            field.field_type.parse()?,
+            field.required,
+        )?;
+        if repo.list().await?.contains(&definition) {
+            return Ok(false);
+        }
+        repo.upsert(definition).await?;
+        Ok(true)
+    }
+
+    async fn apply_user(&self, seed: &SeedUser) -> AppResult<Applied> {
+        let username = LoginIdentifier::parse_username(&seed.username)?;
+        if let Some(existing) = self.user_repo.find_by_username(&username).await? {
+            if existing.role == seed.role {
+                return Ok(Applied::Unchanged);
+            }
+            let mut update = UserUpdate::new(existing.id);
+            update.role = Some(seed.role);
+            self.user_repo.update(update).await?;
+            return Ok(Applied::Updated);
+        }
+
+        let email = seed.email.as_deref().map(Email::new).transpose()?;
+        let (password_hash, reset_required) = match (&seed.password_hash, &seed.password) {
+            (Some(hash), _) => (PasswordHash::new(hash.clone())?, false),
+            (None, Some(password)) => {
+                validate_password(password)?;
+                let hash = self.password_hasher.hash(password).await?;
+                (PasswordHash::new(hash)?, false)
+            }
+            (None, None) => (
+                unusable_password_hash(self.password_hasher.as_ref()).await?,
+                true,
+            ),
+        };
+        let mut new_user = NewUser::new(username, password_hash, seed.role, self.clock.now())?;
+        if reset_required {
+            new_user = new_user.requiring_password_reset();
+        }
+        let created = self.user_repo.insert(new_user).await?;
+        if let Some(email) = email {
+            let mut update = UserUpdate::new(created.id);
+            update.email = Some(Some(email));
+            update.email_verified = Some(true);
+            self.user_repo.update(update).await?;
+        }
+        Ok(Applied::Created)
+    }
+
+    async fn apply_page(&self, page: &SeedPage) -> AppResult<bool> {
+        let repos = self
+            .articles
+            .as_ref()
+            .ok_or_else(|| AppError::validation("the seed creates pages, which are not enabled"))?;
+        let slug = ArticleSlug::new(page.slug.clone())?;
+        if repos.read.find_by_slug(&slug).await?.is_some() {
+            return Ok(false);
+        }
+        let author = self
+            .user_repo
+            .find_by_username(&Username::new(page.author.clone())?)
+            .await?
+            .ok_or_else(|| {
+                AppError::validation(format!(
+                    "page '{}': author '{}' does not exist",
+                    page.slug, page.author
+                ))
+            })?;
+
+        let now = self.clock.now();
+        let created = repos
+            .write
+            .insert(NewArticle {
+                title: ArticleTitle::new(page.title.clone())?,
+                slug,
+                body: ArticleBody::new(page.body.clone())?,
+                tags: Tag::parse_list(page.tags.clone())?,
+                custom_fields: CustomFields::default(),
+                published: page.published,
+                published_at: page.published.then_some(now),
+                visibility: ArticleVisibility::Public,
+                author_id: author.id,
+                created_at: now,
+                updated_at: now,
+            })
+            .await?;
+        repos.revisions.append(&created, Some(author.id)).await?;
+        Ok(true)
+    }
+}
+
+enum Applied {
+    Created,
+    Updated,
+    Unchanged,
+}
@@ -8,11 +8,15 @@ use crate::application::{
         session_revocation::{Ports, Store},
         time::Clock,
     },
+    services::{AuditEvent, AuditRecorder},
 };
+use crate::domain::audit::repository::AuditLogRepository;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ListSessionsRequest {
     pub user_id: i64,
+    /// The session the caller is using, flagged `current` in the listing.
+    pub current_session_id: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -20,10 +24,16 @@ pub struct RevokeSessionRequest {
     pub session_id: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevokeUserSessionsRequest {
+    pub user_id: i64,
+}
+
 #[derive(Clone)]
 pub struct SessionService {
     session_stores: Ports,
     clock: Arc<dyn Clock>,
+    audit: AuditRecorder,
 }
 
 impl SessionService {
@@ -32,9 +42,17 @@ impl SessionService {
         Self {
             session_stores: Ports::from_store(session_revocation_store),
             clock,
+            audit: AuditRecorder::default(),
         }
     }
 
+    /// Record revocations of other users' sessions in `audit_log_repo`.
+    #[must_use]
+    pub fn with_audit(mut self, audit_log_repo: Arc<dyn AuditLogRepository>) -> Self {
+        self.audit = AuditRecorder::new(audit_log_repo);
+        self
+    }
+
     /// List sessions for a user and convert them into DTOs.
     ///
     /// # Errors
@@ -53,6 +71,7 @@ impl SessionService {
         Ok(infos
             .into_iter()
             .map(|info| SessionInfoDto {
+                current: request.current_session_id.as_ref() == Some(&info.session_id),
                 session_id: info.session_id,
                 user_agent: info.user_agent,
                 ip_address: info.ip_address,
@@ -112,6 +131,52 @@ impl SessionService {
         Ok(())
     }
 
+    /// Log a user out everywhere: revoke all their sessions and raise their
+    /// minimum token version past every token issued so far, so access
+    /// tokens stop working before they expire. Returns the new minimum.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller lacks `users:update` or backing store
+    /// operations fail.
+    pub async fn revoke_user_sessions(
+        &self,
+        actor: &AuthenticatedUser,
+        request: RevokeUserSessionsRequest,
+    ) -> AppResult<u32> {
+        if !actor.has_capability("users", "update") {
+            return Err(AppError::forbidden(
+                "not authorized to revoke this user's sessions",
+            ));
+        }
+
+        self.session_stores
+            .revocation
+            .revoke_sessions_for_user(request.user_id)
+            .await?;
+        // Tokens issued before any minimum was set carry version 1.
+        let min_version = self
+            .session_stores
+            .token_versions
+            .get_min_token_version(request.user_id)
+            .await?
+            .unwrap_or(1)
+            .saturating_add(1);
+        self.session_stores
+            .token_versions
+            .set_min_token_version(request.user_id, min_version)
+            .await?;
+
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("user.sessions_revoke", "user", Some(request.user_id))
+                    .with_details(serde_json::json!({ "min_token_version": min_version })),
+            )
+            .await;
+        Ok(min_version)
+    }
+
     fn created_at_from_unix(&self, created_at_unix: i64) -> chrono::DateTime<Utc> {
         if created_at_unix > 0 {
             Utc.timestamp_opt(created_at_unix, 0)
@@ -165,6 +230,8 @@ mod tests {
             expires_at: now,
             session_id: None,
             token_version: None,
+            custom_role: None,
+            impersonator: None,
         }
     }
 
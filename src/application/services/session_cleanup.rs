@@ -0,0 +1,167 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::application::{
+    AppError, AppResult,
+    ports::{
+        session_revocation::{CleanupStats, SessionMetadataStore},
+        time::Clock,
+    },
+};
+
+/// Removes session state that outlived the maximum session lifetime and
+/// keeps running totals of what was reclaimed.
+pub struct SessionCleanupService {
+    session_metadata: Arc<dyn SessionMetadataStore>,
+    clock: Arc<dyn Clock>,
+    runs_total: AtomicU64,
+    expired_sessions_total: AtomicU64,
+    stale_members_total: AtomicU64,
+    reclaimed_keys_total: AtomicU64,
+}
+
+impl SessionCleanupService {
+    #[must_use]
+    pub fn new(session_metadata: Arc<dyn SessionMetadataStore>, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            session_metadata,
+            clock,
+            runs_total: AtomicU64::new(0),
+            expired_sessions_total: AtomicU64::new(0),
+            stale_members_total: AtomicU64::new(0),
+            reclaimed_keys_total: AtomicU64::new(0),
+        }
+    }
+
+    /// Purge sessions created more than `max_lifetime` ago and prune stale
+    /// per-user session entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `max_lifetime` is out of range or the session
+    /// store fails.
+    pub async fn purge_expired(&self, max_lifetime: Duration) -> AppResult<CleanupStats> {
+        let max_lifetime = chrono::Duration::from_std(max_lifetime)
+            .map_err(|_| AppError::validation("session max lifetime is out of range"))?;
+        let cutoff = (self.clock.now() - max_lifetime).timestamp();
+
+        let stats = self
+            .session_metadata
+            .purge_sessions_created_before(cutoff)
+            .await?;
+
+        self.runs_total.fetch_add(1, Ordering::Relaxed);
+        self.expired_sessions_total
+            .fetch_add(stats.expired_sessions, Ordering::Relaxed);
+        self.stale_members_total
+            .fetch_add(stats.stale_members, Ordering::Relaxed);
+        self.reclaimed_keys_total
+            .fetch_add(stats.reclaimed_keys, Ordering::Relaxed);
+
+        tracing::info!(
+            target: "session_cleanup",
+            expired_sessions = stats.expired_sessions,
+            stale_members = stats.stale_members,
+            reclaimed_keys = stats.reclaimed_keys,
+            reclaimed_keys_total = self.reclaimed_keys_total.load(Ordering::Relaxed),
+            "session cleanup finished"
+        );
+
+        Ok(stats)
+    }
+
+    /// Number of cleanup passes completed since startup.
+    #[must_use]
+    pub fn runs_total(&self) -> u64 {
+        self.runs_total.load(Ordering::Relaxed)
+    }
+
+    /// Everything reclaimed since startup.
+    #[must_use]
+    pub fn totals(&self) -> CleanupStats {
+        CleanupStats {
+            expired_sessions: self.expired_sessions_total.load(Ordering::Relaxed),
+            stale_members: self.stale_members_total.load(Ordering::Relaxed),
+            reclaimed_keys: self.reclaimed_keys_total.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use chrono::{DateTime, Utc};
+
+    use super::SessionCleanupService;
+    use crate::application::ports::{
+        session_revocation::{
+            RefreshNonceStore, RefreshTokenFamilyStore, Revocation, SessionMetadataStore,
+        },
+        time::Clock,
+    };
+    use crate::infrastructure::security::session_store::InMemorySessionRevocationStore;
+
+    struct FixedClock(DateTime<Utc>);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.0
+        }
+    }
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    #[tokio::test]
+    async fn purges_only_sessions_older_than_the_max_lifetime() {
+        let now = DateTime::parse_from_rfc3339("2024-03-01T00:00:00Z")
+            .expect("valid RFC3339")
+            .with_timezone(&Utc);
+        let store = Arc::new(InMemorySessionRevocationStore::new());
+        let old = now.timestamp() - 40 * DAY;
+        let recent = now.timestamp() - DAY;
+
+        store
+            .set_session_metadata(1, "old", None, None, old)
+            .await
+            .unwrap();
+        store.set_session_refresh_nonce("old", "n1").await.unwrap();
+        store.add_session_to_family("fam", "old").await.unwrap();
+        store.revoke("old").await.unwrap();
+        store
+            .set_session_metadata(1, "recent", None, None, recent)
+            .await
+            .unwrap();
+        // Tracked for the user but never given metadata.
+        store.add_session_for_user(2, "orphan").await.unwrap();
+
+        let service = SessionCleanupService::new(store.clone(), Arc::new(FixedClock(now)));
+        let stats = service
+            .purge_expired(Duration::from_hours(30 * 24))
+            .await
+            .unwrap();
+
+        assert_eq!(stats.expired_sessions, 1);
+        assert_eq!(stats.stale_members, 2);
+        assert!(stats.reclaimed_keys >= 4);
+        assert!(store.get_session_metadata("old").await.unwrap().is_none());
+        assert!(
+            store
+                .get_session_refresh_nonce("old")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(store.family_for_session("old").await.unwrap().is_none());
+        assert!(!store.is_revoked("old").await.unwrap());
+        assert_eq!(
+            store.list_sessions_for_user(1).await.unwrap(),
+            vec!["recent".to_string()]
+        );
+        assert!(store.list_sessions_for_user(2).await.unwrap().is_empty());
+        assert_eq!(service.runs_total(), 1);
+        assert_eq!(service.totals(), stats);
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser,
+    dto::status::SimulatedTimeDto,
+    ports::time::{Clock, ClockControl},
+};
+
+/// Longest single step, so a typo cannot push the clock years ahead.
+pub const MAX_ADVANCE_SECONDS: i64 = 366 * 24 * 60 * 60;
+
+/// Reads and advances the simulated clock for admins. Every call fails with
+/// not found unless the deployment runs with simulated time.
+pub struct SimulatedTimeService {
+    clock: Arc<dyn Clock>,
+    control: Option<Arc<dyn ClockControl>>,
+}
+
+impl SimulatedTimeService {
+    #[must_use]
+    pub fn new(clock: Arc<dyn Clock>, control: Option<Arc<dyn ClockControl>>) -> Self {
+        Self { clock, control }
+    }
+
+    /// The simulated time and its offset from real time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller lacks `admin:inspect` or simulated time
+    /// is disabled.
+    pub fn current(&self, actor: &AuthenticatedUser) -> AppResult<SimulatedTimeDto> {
+        let control = self.control(actor)?;
+        Ok(self.view(control.offset()))
+    }
+
+    /// Move the simulated clock forward by `seconds`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the caller lacks `admin:inspect`, simulated time is
+    /// disabled, or `seconds` is not between 1 and [`MAX_ADVANCE_SECONDS`].
+    pub fn advance(&self, actor: &AuthenticatedUser, seconds: i64) -> AppResult<SimulatedTimeDto> {
+        let control = self.control(actor)?;
+        if !(1..=MAX_ADVANCE_SECONDS).contains(&seconds) {
+            return Err(AppError::validation(format!(
+                "seconds must be between 1 and {MAX_ADVANCE_SECONDS}"
+            )));
+        }
+        let offset = control.advance(Duration::seconds(seconds));
+        tracing::warn!(
+            actor = i64::from(actor.id),
+            seconds,
+            offset_seconds = offset.num_seconds(),
+            "simulated clock advanced"
+        );
+        Ok(self.view(offset))
+    }
+
+    fn control(&self, actor: &AuthenticatedUser) -> AppResult<&Arc<dyn ClockControl>> {
+        if !actor.has_capability("admin", "inspect") {
+            return Err(AppError::forbidden("missing capability admin:inspect"));
+        }
+        self.control
+            .as_ref()
+            .ok_or_else(|| AppError::not_found("simulated time is disabled"))
+    }
+
+    fn view(&self, offset: Duration) -> SimulatedTimeDto {
+        SimulatedTimeDto {
+            now: self.clock.now(),
+            offset_seconds: offset.num_seconds(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Capability, Role, UserId};
+    use crate::infrastructure::time::SimulatedClock;
+    use chrono::Utc;
+    use std::collections::HashSet;
+
+    fn actor(capabilities: HashSet<Capability>) -> AuthenticatedUser {
+        let now = Utc::now();
+        AuthenticatedUser {
+            id: UserId::new(1).unwrap(),
+            username: "admin".into(),
+            role: Role::Admin,
+            capabilities,
+            issued_at: now,
+            expires_at: now + Duration::hours(1),
+            session_id: None,
+            token_version: None,
+            custom_role: None,
+            impersonator: None,
+        }
+    }
+
+    #[test]
+    fn advances_only_when_enabled_and_permitted() {
+        let admin = actor(HashSet::from([Capability::new("admin", "inspect")]));
+        let disabled = SimulatedTimeService::new(Arc::new(SimulatedClock::new()), None);
+        assert!(matches!(
+            disabled.advance(&admin, 60),
+            Err(AppError::NotFound(_))
+        ));
+
+        let clock = SimulatedClock::new();
+        let service = SimulatedTimeService::new(Arc::new(clock.clone()), Some(Arc::new(clock)));
+        assert!(matches!(
+            service.advance(&actor(HashSet::new()), 60),
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(matches!(
+            service.advance(&admin, 0),
+            Err(AppError::Validation(_))
+        ));
+
+        let advanced = service.advance(&admin, 3600).unwrap();
+        assert_eq!(advanced.offset_seconds, 3600);
+        assert_eq!(service.current(&admin).unwrap().offset_seconds, 3600);
+    }
+}
@@ -0,0 +1,272 @@
+// src/application/services/slug_cache.rs
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::application::{
+    AppResult,
+    ports::{domain_events::EventHandler, time::Clock},
+};
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::{Article, ArticleReadRepository, ArticleSlug, DomainEvent};
+
+/// Size and lifetimes of the public slug lookup cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlugCachePolicy {
+    /// Most slugs remembered at once; zero turns the cache off.
+    pub capacity: usize,
+    /// How long a found article is served from memory. Changes made on
+    /// another instance show after at most this long.
+    pub ttl: Duration,
+    /// How long a slug no article has is answered from memory.
+    pub negative_ttl: Duration,
+}
+
+impl Default for SlugCachePolicy {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            ttl: Duration::seconds(60),
+            negative_ttl: Duration::seconds(10),
+        }
+    }
+}
+
+/// Lookups answered since startup, by where the answer came from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SlugCacheStats {
+    /// Served a cached article.
+    pub hits: u64,
+    /// Served a cached "no such slug".
+    pub negative_hits: u64,
+    /// Went to the repository.
+    pub misses: u64,
+    /// Slugs currently cached, expired ones included until they are next
+    /// looked up or evicted.
+    pub entries: usize,
+}
+
+struct Entry {
+    /// `None` remembers that no article has the slug.
+    article: Option<Article>,
+    expires_at: DateTime<Utc>,
+    seq: u64,
+}
+
+enum Cached {
+    Article(Article),
+    NoArticle,
+    Uncached,
+}
+
+#[derive(Default)]
+struct Entries {
+    by_slug: HashMap<String, Entry>,
+    /// Slugs by insertion order, oldest first, for eviction.
+    order: BTreeMap<u64, String>,
+    next_seq: u64,
+    /// Bumped by every invalidation, so a lookup that raced a write does not
+    /// cache what it read before the write.
+    generation: u64,
+}
+
+impl Entries {
+    fn get(&mut self, slug: &str, now: DateTime<Utc>) -> Cached {
+        let Some(entry) = self.by_slug.get(slug) else {
+            return Cached::Uncached;
+        };
+        if entry.expires_at <= now {
+            self.remove(slug);
+            return Cached::Uncached;
+        }
+        entry
+            .article
+            .clone()
+            .map_or(Cached::NoArticle, Cached::Article)
+    }
+
+    /// Cache what a lookup begun at `generation` found, unless an
+    /// invalidation has happened since.
+    fn insert(
+        &mut self,
+        generation: u64,
+        slug: &str,
+        article: Option<Article>,
+        expires_at: DateTime<Utc>,
+        capacity: usize,
+    ) {
+        if generation != self.generation {
+            return;
+        }
+        self.remove(slug);
+        while self.by_slug.len() >= capacity {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            self.by_slug.remove(&oldest);
+        }
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.order.insert(seq, slug.to_owned());
+        self.by_slug.insert(
+            slug.to_owned(),
+            Entry {
+                article,
+                expires_at,
+                seq,
+            },
+        );
+    }
+
+    fn remove(&mut self, slug: &str) {
+        if let Some(entry) = self.by_slug.remove(slug) {
+            self.order.remove(&entry.seq);
+        }
+    }
+
+    fn invalidate(&mut self, slug: &str) {
+        self.generation += 1;
+        self.remove(slug);
+    }
+
+    fn clear(&mut self) {
+        self.generation += 1;
+        self.by_slug.clear();
+        self.order.clear();
+    }
+}
+
+struct Inner {
+    policy: SlugCachePolicy,
+    clock: Arc<dyn Clock>,
+    entries: Mutex<Entries>,
+    hits: AtomicU64,
+    negative_hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Inner {
+    fn entries(&self) -> MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// In-process cache in front of public article lookups by slug, including
+/// slugs that match nothing.
+///
+/// Entries expire after the policy's TTLs and the oldest is evicted once
+/// `capacity` slugs are held. Article commands reach it as a domain event
+/// handler and other writers call [`forget`](Self::forget) or
+/// [`clear`](Self::clear), so local changes show at once.
+#[derive(Clone, Default)]
+pub struct SlugCache(Option<Arc<Inner>>);
+
+impl SlugCache {
+    pub fn new(policy: SlugCachePolicy, clock: Arc<dyn Clock>) -> Self {
+        if policy.capacity == 0 {
+            return Self::default();
+        }
+        Self(Some(Arc::new(Inner {
+            policy,
+            clock,
+            entries: Mutex::new(Entries::default()),
+            hits: AtomicU64::new(0),
+            negative_hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        })))
+    }
+
+    /// The article with `slug`, from memory when cached and otherwise from
+    /// `repo`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the repository lookup fails; failures are not
+    /// cached.
+    pub async fn find_by_slug(
+        &self,
+        repo: &dyn ArticleReadRepository,
+        slug: &ArticleSlug,
+    ) -> AppResult<Option<Article>> {
+        let Some(inner) = &self.0 else {
+            return Ok(repo.find_by_slug(slug).await?);
+        };
+        let now = inner.clock.now();
+        let (cached, generation) = {
+            let mut entries = inner.entries();
+            (entries.get(slug.as_str(), now), entries.generation)
+        };
+        match cached {
+            Cached::Article(article) => {
+                inner.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(Some(article));
+            }
+            Cached::NoArticle => {
+                inner.negative_hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(None);
+            }
+            Cached::Uncached => {
+                inner.misses.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let article = repo.find_by_slug(slug).await?;
+        let ttl = if article.is_some() {
+            inner.policy.ttl
+        } else {
+            inner.policy.negative_ttl
+        };
+        inner.entries().insert(
+            generation,
+            slug.as_str(),
+            article.clone(),
+            now + ttl,
+            inner.policy.capacity,
+        );
+        Ok(article)
+    }
+
+    /// Drop whatever is cached for `slug`.
+    pub fn forget(&self, slug: &ArticleSlug) {
+        self.forget_str(slug.as_str());
+    }
+
+    fn forget_str(&self, slug: &str) {
+        if let Some(inner) = &self.0 {
+            inner.entries().invalidate(slug);
+        }
+    }
+
+    /// Drop every entry, for writes that touch many articles at once.
+    pub fn clear(&self) {
+        if let Some(inner) = &self.0 {
+            inner.entries().clear();
+        }
+    }
+
+    #[must_use]
+    pub fn stats(&self) -> SlugCacheStats {
+        self.0
+            .as_ref()
+            .map_or_else(SlugCacheStats::default, |inner| SlugCacheStats {
+                hits: inner.hits.load(Ordering::Relaxed),
+                negative_hits: inner.negative_hits.load(Ordering::Relaxed),
+                misses: inner.misses.load(Ordering::Relaxed),
+                entries: inner.entries().by_slug.len(),
+            })
+    }
+}
+
+impl EventHandler for SlugCache {
+    fn handle<'a>(&'a self, event: &'a DomainEvent) -> BoxFuture<'a, AppResult<()>> {
+        if let Some(article) = &event.article {
+            self.forget_str(&article.slug);
+            if let Some(previous) = &article.previous_slug {
+                self.forget_str(previous);
+            }
+        }
+        boxed(async { Ok(()) })
+    }
+}
@@ -0,0 +1,204 @@
+//! Static HTML exports of the published site.
+//!
+//! Every published article becomes `articles/<slug>.html`, its body rendered
+//! with the full Markdown profile, and `index.html` links them newest first.
+//! Slugs that are not plain words, as imports may bring, are written as
+//! `articles/article-<id>.html` instead.
+//! Drafts are never written, and pages left over from an earlier export are
+//! not removed.
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::json;
+
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser, RenderProfile, StaticSiteExportDto,
+    markdown::html::{self, escape},
+    ports::{static_site::SiteWriter, time::Clock},
+};
+use crate::domain::{
+    Article, ArticleReadRepository,
+    audit::{entity::NewAuditLog, repository::AuditLogRepository},
+};
+
+/// Published articles read per page while exporting.
+const EXPORT_PAGE_SIZE: u32 = 100;
+
+pub const INDEX_PAGE: &str = "index.html";
+
+/// One line of the index page.
+struct IndexEntry {
+    path: String,
+    title: String,
+    published_at: Option<DateTime<Utc>>,
+}
+
+/// Renders the published site as static HTML for hosting without this
+/// service.
+pub struct StaticSiteService {
+    article_read_repo: Arc<dyn ArticleReadRepository>,
+    audit_log_repo: Arc<dyn AuditLogRepository>,
+    writer: Arc<dyn SiteWriter>,
+    clock: Arc<dyn Clock>,
+}
+
+impl StaticSiteService {
+    #[must_use]
+    pub fn new(
+        article_read_repo: Arc<dyn ArticleReadRepository>,
+        audit_log_repo: Arc<dyn AuditLogRepository>,
+        writer: Arc<dyn SiteWriter>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self {
+            article_read_repo,
+            audit_log_repo,
+            writer,
+            clock,
+        }
+    }
+
+    /// Write the static site on behalf of an admin.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `admin:maintenance`, or the
+    /// export fails or cannot be audited.
+    pub async fn export(&self, actor: &AuthenticatedUser) -> AppResult<StaticSiteExportDto> {
+        ensure_capability(actor, "admin", "maintenance")?;
+        let report = self.write_site().await?;
+        self.audit_log_repo
+            .insert(NewAuditLog {
+                user_id: Some(actor.id),
+                impersonator_id: actor.impersonator,
+                action: "content.static_export".into(),
+                resource_type: "content".into(),
+                resource_id: None,
+                details: Some(json!({
+                    "location": report.location,
+                    "articles": report.articles,
+                })),
+                ip_address: None,
+                user_agent: None,
+            })
+            .await?;
+        Ok(report)
+    }
+
+    /// Write a page per published article, then the index. Unchecked and
+    /// unaudited; for the `EXPORT_STATIC=1` startup mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if articles cannot be listed or a page cannot be
+    /// written. Pages written before the failure stay.
+    pub async fn write_site(&self) -> AppResult<StaticSiteExportDto> {
+        let generated_at = self.clock.now();
+        let mut entries = Vec::new();
+        let mut cursor = None;
+        loop {
+            let (articles, next) = self
+                .article_read_repo
+                .list_page(false, EXPORT_PAGE_SIZE, cursor, None)
+                .await?;
+            for article in articles {
+                let path = format!("articles/{}.html", file_stem(&article));
+                self.writer
+                    .write(&path, article_page(&article).into_bytes())
+                    .await?;
+                entries.push(IndexEntry {
+                    path,
+                    title: article.title.to_string(),
+                    published_at: article.published_at,
+                });
+            }
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        self.writer
+            .write(INDEX_PAGE, index_page(&entries).into_bytes())
+            .await?;
+
+        Ok(StaticSiteExportDto {
+            location: self.writer.location(),
+            articles: entries.len(),
+            files_written: entries.len() + 1,
+            generated_at,
+        })
+    }
+}
+
+fn file_stem(article: &Article) -> String {
+    let slug = article.slug.as_str();
+    if slug
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_'))
+    {
+        slug.to_string()
+    } else {
+        format!("article-{}", i64::from(article.id))
+    }
+}
+
+fn document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape(title)
+    )
+}
+
+fn time(at: DateTime<Utc>) -> String {
+    format!(
+        "<time datetime=\"{}\">{}</time>",
+        at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        at.format("%Y-%m-%d")
+    )
+}
+
+fn article_page(article: &Article) -> String {
+    let mut body = String::from("<nav><a href=\"../index.html\">All articles</a></nav>\n");
+    let _ = writeln!(
+        body,
+        "<article>\n<h1>{}</h1>",
+        escape(article.title.as_str())
+    );
+    if let Some(at) = article.published_at {
+        let _ = writeln!(body, "<p>{}</p>", time(at));
+    }
+    body.push_str(&html::render(article.body.as_str(), RenderProfile::Full));
+    body.push_str("</article>\n");
+    document(article.title.as_str(), &body)
+}
+
+fn index_page(entries: &[IndexEntry]) -> String {
+    let mut body = String::from("<h1>Articles</h1>\n<ul>\n");
+    for entry in entries {
+        let _ = write!(
+            body,
+            "<li><a href=\"{}\">{}</a>",
+            escape(&entry.path),
+            escape(&entry.title)
+        );
+        if let Some(at) = entry.published_at {
+            let _ = write!(body, " {}", time(at));
+        }
+        body.push_str("</li>\n");
+    }
+    body.push_str("</ul>\n");
+    document("Articles", &body)
+}
+
+fn ensure_capability(actor: &AuthenticatedUser, resource: &str, action: &str) -> AppResult<()> {
+    if actor.has_capability(resource, action) {
+        Ok(())
+    } else {
+        Err(AppError::forbidden(format!(
+            "missing capability {resource}:{action}"
+        )))
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::application::{
+    dto::status::{BackendsDto, FeaturesDto, ServiceStatusDto},
+    ports::{session_revocation::Backend, time::Clock},
+};
+
+/// Reports what a running deployment is: build identity, uptime and the
+/// back-ends it was wired with.
+pub struct StatusService {
+    clock: Arc<dyn Clock>,
+    started_at: DateTime<Utc>,
+    session_backend: Backend,
+    features: FeaturesDto,
+}
+
+impl StatusService {
+    #[must_use]
+    pub fn new(clock: Arc<dyn Clock>, session_backend: Backend, features: FeaturesDto) -> Self {
+        let started_at = clock.now();
+        Self {
+            clock,
+            started_at,
+            session_backend,
+            features,
+        }
+    }
+
+    /// Optional features this deployment was configured with.
+    #[must_use]
+    pub fn features(&self) -> FeaturesDto {
+        self.features.clone()
+    }
+
+    #[must_use]
+    pub fn status(&self) -> ServiceStatusDto {
+        let uptime = self.clock.now() - self.started_at;
+
+        ServiceStatusDto {
+            status: "ok".into(),
+            version: env!("CARGO_PKG_VERSION").into(),
+            git_commit: env!("GIT_COMMIT").into(),
+            build_date: option_env!("BUILD_DATE").map(Into::into),
+            started_at: self.started_at,
+            uptime_seconds: uptime.num_seconds().max(0),
+            backends: BackendsDto {
+                storage: "postgres".into(),
+                session_store: self.session_backend.as_str().into(),
+                redis: self.session_backend == Backend::Redis,
+            },
+            features: self.features(),
+        }
+    }
+}
@@ -0,0 +1,332 @@
+// src/application/services/syndication.rs
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use super::AuditEvent;
+use super::audit_recorder::AuditRecorder;
+use crate::{
+    application::{
+        AppError, AppResult, ArticleSyndicationDto, AuthenticatedUser, ReadOnlySwitch,
+        ports::{
+            domain_events::EventHandler,
+            jobs::JobQueue,
+            syndication::{Announcement, SyndicationOptOutStore, SyndicationTarget},
+        },
+    },
+    async_support::{BoxFuture, boxed},
+    domain::{
+        Article, ArticleId, ArticleReadRepository, DomainEvent, EventKind,
+        article::specifications::{ArticleSpecification, CanUpdateArticleSpec},
+        audit::repository::AuditLogRepository,
+    },
+};
+
+/// When and where announcements link to and how they are retried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyndicationPolicy {
+    /// Public link to an article; `{slug}` and `{id}` are filled in.
+    pub article_url: String,
+    /// Wait between publishing and announcing, so an article unpublished or
+    /// opted out right away is never announced.
+    pub delay: Duration,
+    /// Tries per target, the first included.
+    pub max_attempts: u32,
+    /// Wait before the first retry; doubled for each one after.
+    pub retry_backoff: Duration,
+}
+
+impl Default for SyndicationPolicy {
+    fn default() -> Self {
+        Self {
+            article_url: "/articles/{slug}".into(),
+            delay: Duration::from_mins(1),
+            max_attempts: 5,
+            retry_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+struct Shared {
+    targets: Vec<SyndicationTarget>,
+    read_repo: Arc<dyn ArticleReadRepository>,
+    opt_outs: Arc<dyn SyndicationOptOutStore>,
+    job_queue: Arc<dyn JobQueue>,
+    policy: SyndicationPolicy,
+}
+
+/// Announces newly published articles on every configured target.
+///
+/// Each target gets its own job on the queue, run after the policy's delay.
+/// The job re-reads the article, so one unpublished, republished or opted
+/// out in the meantime is not announced, and re-queues itself with a growing
+/// backoff when the target fails. Jobs live in memory and are lost on
+/// restart.
+pub struct SyndicationService {
+    shared: Arc<Shared>,
+    audit: AuditRecorder,
+    read_only: ReadOnlySwitch,
+}
+
+impl SyndicationService {
+    #[must_use]
+    pub fn new(
+        targets: Vec<SyndicationTarget>,
+        read_repo: Arc<dyn ArticleReadRepository>,
+        opt_outs: Arc<dyn SyndicationOptOutStore>,
+        job_queue: Arc<dyn JobQueue>,
+        policy: SyndicationPolicy,
+    ) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                targets,
+                read_repo,
+                opt_outs,
+                job_queue,
+                policy,
+            }),
+            audit: AuditRecorder::default(),
+            read_only: ReadOnlySwitch::default(),
+        }
+    }
+
+    /// Record opt-out changes in `audit_log_repo`.
+    #[must_use]
+    pub fn with_audit(mut self, audit_log_repo: Arc<dyn AuditLogRepository>) -> Self {
+        self.audit = AuditRecorder::new(audit_log_repo);
+        self
+    }
+
+    /// Refuse opt-out changes while `switch` is on.
+    #[must_use]
+    pub fn with_read_only(mut self, switch: ReadOnlySwitch) -> Self {
+        self.read_only = switch;
+        self
+    }
+
+    /// Names of the configured targets.
+    #[must_use]
+    pub fn target_names(&self) -> Vec<String> {
+        self.shared
+            .targets
+            .iter()
+            .map(|target| target.name.clone())
+            .collect()
+    }
+
+    /// Whether article `id` is announced when published.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the article is missing, the actor may not update
+    /// it, or the store fails.
+    pub async fn settings(
+        &self,
+        actor: &AuthenticatedUser,
+        id: i64,
+    ) -> AppResult<ArticleSyndicationDto> {
+        let article = self.editable_article(actor, id).await?;
+        let opted_out = self.shared.opt_outs.is_opted_out(article.id).await?;
+        Ok(self.dto(article.id, !opted_out))
+    }
+
+    /// Opt article `id` in to or out of announcements. Announcements already
+    /// waiting for their delay are dropped when opting out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error while read-only, if the article is missing, the
+    /// actor may not update it, or the store fails.
+    pub async fn set_enabled(
+        &self,
+        actor: &AuthenticatedUser,
+        id: i64,
+        enabled: bool,
+    ) -> AppResult<ArticleSyndicationDto> {
+        self.read_only.ensure_writable()?;
+        let article = self.editable_article(actor, id).await?;
+        self.shared
+            .opt_outs
+            .set_opted_out(article.id, !enabled)
+            .await?;
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new(
+                    "article.syndication",
+                    "article",
+                    Some(i64::from(article.id)),
+                )
+                .with_details(serde_json::json!({ "enabled": enabled })),
+            )
+            .await;
+        Ok(self.dto(article.id, enabled))
+    }
+
+    async fn editable_article(&self, actor: &AuthenticatedUser, id: i64) -> AppResult<Article> {
+        let article = self
+            .shared
+            .read_repo
+            .find_by_id(ArticleId::new(id)?)
+            .await?
+            .ok_or_else(|| AppError::not_found("article not found"))?;
+        if !CanUpdateArticleSpec::new(&actor.capabilities, &article, actor.id).is_satisfied() {
+            return Err(AppError::forbidden(
+                "insufficient privileges to update article",
+            ));
+        }
+        Ok(article)
+    }
+
+    fn dto(&self, article_id: ArticleId, enabled: bool) -> ArticleSyndicationDto {
+        ArticleSyndicationDto {
+            article_id: article_id.into(),
+            enabled,
+            targets: self.target_names(),
+        }
+    }
+}
+
+impl EventHandler for SyndicationService {
+    fn handle<'a>(&'a self, event: &'a DomainEvent) -> BoxFuture<'a, AppResult<()>> {
+        let publication = match (event.kind, &event.article) {
+            (EventKind::ArticlePublished(article_id), Some(article)) => {
+                article.published_at.map(|published_at| Publication {
+                    article_id,
+                    published_at,
+                })
+            }
+            _ => None,
+        };
+        boxed(async move {
+            let Some(publication) = publication else {
+                return Ok(());
+            };
+            for target in &self.shared.targets {
+                Delivery {
+                    shared: Arc::clone(&self.shared),
+                    target: target.clone(),
+                    event: publication,
+                    attempt: 1,
+                }
+                .schedule(self.shared.policy.delay);
+            }
+            Ok(())
+        })
+    }
+}
+
+/// An article went from draft to published, on creation or later.
+#[derive(Debug, Clone, Copy)]
+struct Publication {
+    article_id: ArticleId,
+    /// Distinguishes this publication from a later one of the same article.
+    published_at: DateTime<Utc>,
+}
+
+/// One announcement of one publication to one target.
+struct Delivery {
+    shared: Arc<Shared>,
+    target: SyndicationTarget,
+    event: Publication,
+    attempt: u32,
+}
+
+impl Delivery {
+    fn schedule(self, wait: Duration) {
+        let queue = Arc::clone(&self.shared.job_queue);
+        queue.enqueue(
+            "syndication",
+            boxed(async move {
+                tokio::time::sleep(wait).await;
+                self.run().await;
+            }),
+        );
+    }
+
+    async fn run(self) {
+        let article_id = i64::from(self.event.article_id);
+        let target = self.target.name.as_str();
+        let error = match self.announcement().await {
+            Ok(Some(announcement)) => {
+                let text = announcement.render(&self.target.template);
+                match self.target.syndicator.post(&text, &announcement).await {
+                    Ok(()) => {
+                        tracing::info!(article_id, target, "article announced");
+                        return;
+                    }
+                    Err(err) => err,
+                }
+            }
+            Ok(None) => {
+                tracing::debug!(article_id, target, "announcement no longer wanted");
+                return;
+            }
+            Err(err) => err,
+        };
+
+        let policy = &self.shared.policy;
+        if self.attempt >= policy.max_attempts {
+            tracing::error!(
+                error = %error,
+                article_id,
+                target,
+                attempts = self.attempt,
+                "giving up on announcing article"
+            );
+            return;
+        }
+        let wait = policy
+            .retry_backoff
+            .saturating_mul(2u32.saturating_pow(self.attempt - 1));
+        tracing::warn!(
+            error = %error,
+            article_id,
+            target,
+            attempt = self.attempt,
+            retry_in_secs = wait.as_secs(),
+            "announcing article failed; retrying"
+        );
+        Self {
+            attempt: self.attempt + 1,
+            ..self
+        }
+        .schedule(wait);
+    }
+
+    /// What to post, or `None` once the article is no longer published as
+    /// of this event, is not public or has been opted out.
+    async fn announcement(&self) -> AppResult<Option<Announcement>> {
+        let article_id = self.event.article_id;
+        let Some(article) = self.shared.read_repo.find_by_id(article_id).await? else {
+            return Ok(None);
+        };
+        if !article.published
+            || article.published_at != Some(self.event.published_at)
+            || !article.visibility.is_public()
+        {
+            return Ok(None);
+        }
+        if self.shared.opt_outs.is_opted_out(article_id).await? {
+            return Ok(None);
+        }
+        let url = self
+            .shared
+            .policy
+            .article_url
+            .replace("{slug}", article.slug.as_str())
+            .replace("{id}", &i64::from(article_id).to_string());
+        Ok(Some(Announcement {
+            article_id: article_id.into(),
+            title: article.title.as_str().to_owned(),
+            slug: article.slug.as_str().to_owned(),
+            url,
+            tags: article
+                .tags
+                .iter()
+                .map(|tag| tag.as_str().to_owned())
+                .collect(),
+        }))
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use super::user_import_csv::{self, ImportRow};
+use crate::application::{
+    AppError, AppResult, AuthenticatedUser, DuplicateStrategy, ImportJobStatus, ImportRowResult,
+    ImportRowStatus, UserImportJobDto,
+    commands::users::unusable_password_hash,
+    ports::{jobs::JobQueue, security::PasswordHasher, time::Clock},
+    random_id,
+};
+use crate::domain::{
+    LoginIdentifier, NewUser, PasswordHash, Role, UserId, UserRepository, UserUpdate,
+};
+
+/// Finished jobs beyond this many are forgotten, oldest first.
+const MAX_RETAINED_JOBS: usize = 100;
+
+pub struct UserImportCommand {
+    /// CSV with a `username,role[,password_hash][,force_password_reset]` header.
+    pub csv: String,
+    pub on_duplicate: DuplicateStrategy,
+    /// Require a password reset for every imported user.
+    pub force_password_reset: bool,
+}
+
+#[derive(Default)]
+struct JobTable {
+    jobs: HashMap<String, UserImportJobDto>,
+    order: VecDeque<String>,
+}
+
+type SharedJobs = Arc<Mutex<JobTable>>;
+
+fn with_jobs<T>(jobs: &SharedJobs, f: impl FnOnce(&mut JobTable) -> T) -> T {
+    let mut table = jobs.lock().unwrap_or_else(PoisonError::into_inner);
+    f(&mut table)
+}
+
+/// Imports users from CSV on the job queue and tracks per-row results.
+///
+/// Job state lives in memory, so results are lost on restart; the rows
+/// already imported are not.
+pub struct UserImportService {
+    user_repo: Arc<dyn UserRepository>,
+    password_hasher: Arc<dyn PasswordHasher>,
+    clock: Arc<dyn Clock>,
+    job_queue: Arc<dyn JobQueue>,
+    jobs: SharedJobs,
+}
+
+impl UserImportService {
+    #[must_use]
+    pub fn new(
+        user_repo: Arc<dyn UserRepository>,
+        password_hasher: Arc<dyn PasswordHasher>,
+        clock: Arc<dyn Clock>,
+        job_queue: Arc<dyn JobQueue>,
+    ) -> Self {
+        Self {
+            user_repo,
+            password_hasher,
+            clock,
+            job_queue,
+            jobs: SharedJobs::default(),
+        }
+    }
+
+    /// Validate the CSV and queue it for import.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `users:create`, the CSV is
+    /// malformed, or a job id cannot be generated.
+    pub fn submit(
+        &self,
+        actor: &AuthenticatedUser,
+        command: &UserImportCommand,
+    ) -> AppResult<UserImportJobDto> {
+        ensure_can_import(actor)?;
+        let rows = user_import_csv::parse(&command.csv)?;
+
+        let job = UserImportJobDto {
+            id: random_id::v4_string()?,
+            status: ImportJobStatus::Queued,
+            on_duplicate: command.on_duplicate,
+            total_rows: rows.len(),
+            processed_rows: 0,
+            results: Vec::new(),
+            created_at: self.clock.now(),
+            finished_at: None,
+        };
+        with_jobs(&self.jobs, |table| {
+            table.order.push_back(job.id.clone());
+            table.jobs.insert(job.id.clone(), job.clone());
+            evict_finished(table);
+        });
+
+        let run = ImportRun {
+            user_repo: Arc::clone(&self.user_repo),
+            password_hasher: Arc::clone(&self.password_hasher),
+            clock: Arc::clone(&self.clock),
+            jobs: Arc::clone(&self.jobs),
+            job_id: job.id.clone(),
+            on_duplicate: command.on_duplicate,
+            force_password_reset: command.force_password_reset,
+        };
+        self.job_queue
+            .enqueue("user_import", Box::pin(run.execute(rows)));
+
+        Ok(job)
+    }
+
+    /// Current state of an import job.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `users:create` or the job is
+    /// unknown.
+    pub fn job(&self, actor: &AuthenticatedUser, id: &str) -> AppResult<UserImportJobDto> {
+        ensure_can_import(actor)?;
+        with_jobs(&self.jobs, |table| table.jobs.get(id).cloned())
+            .ok_or_else(|| AppError::not_found("import job not found"))
+    }
+}
+
+fn ensure_can_import(actor: &AuthenticatedUser) -> AppResult<()> {
+    if actor.has_capability("users", "create") {
+        Ok(())
+    } else {
+        Err(AppError::forbidden("missing capability users:create"))
+    }
+}
+
+fn evict_finished(table: &mut JobTable) {
+    while table.order.len() > MAX_RETAINED_JOBS {
+        let Some(position) = table.order.iter().position(|id| {
+            table
+                .jobs
+                .get(id)
+                .is_none_or(|job| job.status == ImportJobStatus::Completed)
+        }) else {
+            return;
+        };
+        if let Some(id) = table.order.remove(position) {
+            table.jobs.remove(&id);
+        }
+    }
+}
+
+struct ImportRun {
+    user_repo: Arc<dyn UserRepository>,
+    password_hasher: Arc<dyn PasswordHasher>,
+    clock: Arc<dyn Clock>,
+    jobs: SharedJobs,
+    job_id: String,
+    on_duplicate: DuplicateStrategy,
+    force_password_reset: bool,
+}
+
+impl ImportRun {
+    async fn execute(self, rows: Vec<ImportRow>) {
+        self.update(|job| job.status = ImportJobStatus::Running);
+
+        for row in rows {
+            let result = match self.import_row(&row).await {
+                Ok((status, user_id)) => ImportRowResult {
+                    line: row.line,
+                    username: row.username,
+                    status,
+                    user_id: Some(user_id.into()),
+                    error: None,
+                },
+                Err(err) => ImportRowResult {
+                    line: row.line,
+                    username: row.username,
+                    status: ImportRowStatus::Failed,
+                    user_id: None,
+                    error: Some(err.to_string()),
+                },
+            };
+            self.update(|job| {
+                job.processed_rows += 1;
+                job.results.push(result);
+            });
+        }
+
+        let finished_at = self.clock.now();
+        self.update(|job| {
+            job.status = ImportJobStatus::Completed;
+            job.finished_at = Some(finished_at);
+        });
+    }
+
+    fn update(&self, f: impl FnOnce(&mut UserImportJobDto)) {
+        with_jobs(&self.jobs, |table| {
+            if let Some(job) = table.jobs.get_mut(&self.job_id) {
+                f(job);
+            }
+        });
+    }
+
+    async fn import_row(&self, row: &ImportRow) -> AppResult<(ImportRowStatus, UserId)> {
+        let username = LoginIdentifier::parse_username(&row.username)?;
+        let role: Role = row.role.parse()?;
+        let provided_hash = row
+            .password_hash
+            .as_deref()
+            .map(imported_hash)
+            .transpose()?;
+        let force_reset = self.force_password_reset || row.force_password_reset;
+
+        if let Some(existing) = self.user_repo.find_by_username(&username).await? {
+            return match self.on_duplicate {
+                DuplicateStrategy::Skip => Ok((ImportRowStatus::Skipped, existing.id)),
+                DuplicateStrategy::Fail => Err(AppError::conflict("username already exists")),
+                DuplicateStrategy::Update => {
+                    let mut update = UserUpdate::new(existing.id).with_role(role);
+                    if let Some(password_hash) = provided_hash {
+                        update = update.with_password_hash(password_hash);
+                    }
+                    if force_reset {
+                        update = update.with_password_reset_required(true);
+                    }
+                    let user = self.user_repo.update(update).await?;
+                    Ok((ImportRowStatus::Updated, user.id))
+                }
+            };
+        }
+
+        // Without a migrated hash nobody knows the password, so a reset is
+        // the only way in.
+        let force_reset = force_reset || provided_hash.is_none();
+        let password_hash = match provided_hash {
+            Some(password_hash) => password_hash,
+            None => unusable_password_hash(self.password_hasher.as_ref()).await?,
+        };
+        let mut new_user = NewUser::new(username, password_hash, role, self.clock.now())?;
+        if force_reset {
+            new_user = new_user.requiring_password_reset();
+        }
+        let user = self.user_repo.insert(new_user).await?;
+        Ok((ImportRowStatus::Created, user.id))
+    }
+}
+
+/// Accept only hashes the configured Argon2 verifier can check.
+fn imported_hash(value: &str) -> AppResult<PasswordHash> {
+    if !value.starts_with("$argon2") {
+        return Err(AppError::validation(
+            "unsupported password hash format; expected an Argon2 PHC string",
+        ));
+    }
+    Ok(PasswordHash::new(value)?)
+}
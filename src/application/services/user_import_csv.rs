@@ -0,0 +1,227 @@
+//! Minimal RFC 4180 reader for the user import format.
+//!
+//! The first record is a header naming the columns. `username` and `role`
+//! are required; `password_hash` and `force_password_reset` are optional.
+use crate::application::{
+    MAX_IMPORT_ROWS,
+    error::{AppError, AppResult},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ImportRow {
+    pub line: usize,
+    pub username: String,
+    pub role: String,
+    pub password_hash: Option<String>,
+    pub force_password_reset: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Username,
+    Role,
+    PasswordHash,
+    ForcePasswordReset,
+}
+
+impl Column {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Username => "username",
+            Self::Role => "role",
+            Self::PasswordHash => "password_hash",
+            Self::ForcePasswordReset => "force_password_reset",
+        }
+    }
+
+    fn parse(name: &str) -> AppResult<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "username" => Ok(Self::Username),
+            "role" => Ok(Self::Role),
+            "password_hash" => Ok(Self::PasswordHash),
+            "force_password_reset" => Ok(Self::ForcePasswordReset),
+            other => Err(AppError::validation(format!(
+                "unknown CSV column '{other}'"
+            ))),
+        }
+    }
+}
+
+/// Parse an import CSV into data rows.
+pub(super) fn parse(input: &str) -> AppResult<Vec<ImportRow>> {
+    let mut records = records(input.trim_start_matches('\u{feff}'))?.into_iter();
+    let (_, header) = records
+        .next()
+        .ok_or_else(|| AppError::validation("CSV must start with a header row"))?;
+    let columns = header
+        .iter()
+        .map(|name| Column::parse(name))
+        .collect::<AppResult<Vec<_>>>()?;
+    for required in [Column::Username, Column::Role] {
+        if !columns.contains(&required) {
+            return Err(AppError::validation(format!(
+                "CSV header is missing the '{}' column",
+                required.name()
+            )));
+        }
+    }
+
+    let rows = records
+        .map(|(line, fields)| row(&columns, line, fields))
+        .collect::<AppResult<Vec<_>>>()?;
+    if rows.is_empty() {
+        return Err(AppError::validation("CSV contains no rows"));
+    }
+    if rows.len() > MAX_IMPORT_ROWS {
+        return Err(AppError::validation(format!(
+            "CSV contains more than {MAX_IMPORT_ROWS} rows"
+        )));
+    }
+    Ok(rows)
+}
+
+fn row(columns: &[Column], line: usize, fields: Vec<String>) -> AppResult<ImportRow> {
+    if fields.len() != columns.len() {
+        return Err(AppError::validation(format!(
+            "line {line}: expected {} fields, found {}",
+            columns.len(),
+            fields.len()
+        )));
+    }
+
+    let mut row = ImportRow {
+        line,
+        username: String::new(),
+        role: String::new(),
+        password_hash: None,
+        force_password_reset: false,
+    };
+    for (column, value) in columns.iter().zip(fields) {
+        let value = value.trim().to_string();
+        match column {
+            Column::Username => row.username = value,
+            Column::Role => row.role = value.to_ascii_lowercase(),
+            Column::PasswordHash => row.password_hash = Some(value).filter(|v| !v.is_empty()),
+            Column::ForcePasswordReset => row.force_password_reset = flag(line, &value)?,
+        }
+    }
+    Ok(row)
+}
+
+fn flag(line: usize, value: &str) -> AppResult<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "" | "false" | "0" | "no" => Ok(false),
+        "true" | "1" | "yes" => Ok(true),
+        other => Err(AppError::validation(format!(
+            "line {line}: invalid force_password_reset value '{other}'"
+        ))),
+    }
+}
+
+/// Split `input` into records, returning each with its starting line number.
+/// Blank lines are skipped.
+fn records(input: &str) -> AppResult<Vec<(usize, Vec<String>)>> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut start_line = 1;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+            ('\n', false) => {
+                fields.push(std::mem::take(&mut field));
+                push_record(&mut records, start_line, std::mem::take(&mut fields));
+                line += 1;
+                start_line = line;
+            }
+            ('\n', true) => {
+                line += 1;
+                field.push(c);
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(AppError::validation(format!(
+            "line {start_line}: unterminated quoted field"
+        )));
+    }
+    fields.push(field);
+    push_record(&mut records, start_line, fields);
+    Ok(records)
+}
+
+fn push_record(records: &mut Vec<(usize, Vec<String>)>, line: usize, fields: Vec<String>) {
+    let blank = fields.len() == 1 && fields[0].trim().is_empty();
+    if !blank {
+        records.push((line, fields));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImportRow, parse};
+
+    #[test]
+    fn parses_required_and_optional_columns() {
+        let csv = "username,role,password_hash,force_password_reset\r\n\
+                   alice,admin,$argon2id$v=19$x,true\r\n\
+                   \r\n\
+                   bob,Author,,\r\n";
+        let rows = parse(csv).unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                ImportRow {
+                    line: 2,
+                    username: "alice".into(),
+                    role: "admin".into(),
+                    password_hash: Some("$argon2id$v=19$x".into()),
+                    force_password_reset: true,
+                },
+                ImportRow {
+                    line: 4,
+                    username: "bob".into(),
+                    role: "author".into(),
+                    password_hash: None,
+                    force_password_reset: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn quoted_fields_may_contain_separators_and_quotes() {
+        let csv = "role,username\n\"author\",\"o\"\"brien, jr\"\n";
+        let rows = parse(csv).unwrap();
+        assert_eq!(rows[0].username, "o\"brien, jr");
+        assert_eq!(rows[0].role, "author");
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        for csv in [
+            "",
+            "username\nalice\n",
+            "username,role,email\nalice,author,a@example.com\n",
+            "username,role\n",
+            "username,role\nalice\n",
+            "username,role\n\"alice,author\n",
+            "username,role,force_password_reset\nalice,author,maybe\n",
+        ] {
+            assert!(parse(csv).is_err(), "{csv:?} should be rejected");
+        }
+    }
+}
@@ -0,0 +1,441 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+
+use super::audit_recorder::{AuditEvent, AuditRecorder};
+use crate::{
+    application::{
+        AppError, AppResult, ArticleWebhookPayload, AuthenticatedUser, ReadOnlySwitch,
+        WebhookDeliveryDto, WebhookDto,
+        dto::pagination::PageLimits,
+        ports::{
+            domain_events::EventHandler,
+            jobs::JobQueue,
+            time::Clock,
+            webhooks::{WebhookRequest, WebhookSender},
+        },
+        random_id,
+    },
+    async_support::{BoxFuture, boxed},
+    domain::{
+        ArticleVisibility, DomainEvent, NewWebhook, NewWebhookDeliveryAttempt, WebhookEvent,
+        WebhookRepository, WebhookUpdate, audit::repository::AuditLogRepository,
+    },
+};
+
+/// How failed deliveries are retried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookPolicy {
+    /// Tries per delivery, the first included.
+    pub max_attempts: u32,
+    /// Wait before the first retry; doubled for each one after.
+    pub retry_backoff: Duration,
+}
+
+impl Default for WebhookPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            retry_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+pub struct CreateWebhookCommand {
+    pub url: String,
+    /// Generated when absent.
+    pub secret: Option<String>,
+    pub events: Vec<WebhookEvent>,
+}
+
+struct Shared {
+    repo: Arc<dyn WebhookRepository>,
+    /// Without one, events are not delivered.
+    sender: Option<Arc<dyn WebhookSender>>,
+    job_queue: Arc<dyn JobQueue>,
+    clock: Arc<dyn Clock>,
+    policy: WebhookPolicy,
+}
+
+/// Admin-managed webhooks, and delivery of article events to them. Events
+/// about private articles are never delivered.
+///
+/// Every event is delivered to each subscribed webhook by its own job on
+/// the queue. The job re-reads the webhook before each try, so one deleted,
+/// deactivated or unsubscribed in the meantime gets nothing more, records
+/// the try, and re-queues itself with a growing backoff when the endpoint
+/// fails or answers with anything but 2xx. Jobs live in memory and are lost
+/// on restart.
+pub struct WebhookService {
+    shared: Arc<Shared>,
+    audit: AuditRecorder,
+    read_only: ReadOnlySwitch,
+    page_limits: PageLimits,
+}
+
+impl WebhookService {
+    #[must_use]
+    pub fn new(
+        repo: Arc<dyn WebhookRepository>,
+        sender: Option<Arc<dyn WebhookSender>>,
+        job_queue: Arc<dyn JobQueue>,
+        clock: Arc<dyn Clock>,
+        policy: WebhookPolicy,
+    ) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                repo,
+                sender,
+                job_queue,
+                clock,
+                policy,
+            }),
+            audit: AuditRecorder::default(),
+            read_only: ReadOnlySwitch::default(),
+            page_limits: PageLimits::default(),
+        }
+    }
+
+    /// Record webhook changes in `audit_log_repo`.
+    #[must_use]
+    pub fn with_audit(mut self, audit_log_repo: Arc<dyn AuditLogRepository>) -> Self {
+        self.audit = AuditRecorder::new(audit_log_repo);
+        self
+    }
+
+    /// Refuse webhook changes while `switch` is on.
+    #[must_use]
+    pub fn with_read_only(mut self, switch: ReadOnlySwitch) -> Self {
+        self.read_only = switch;
+        self
+    }
+
+    /// Size delivery history pages by `limits`.
+    #[must_use]
+    pub const fn with_page_limits(mut self, limits: PageLimits) -> Self {
+        self.page_limits = limits;
+        self
+    }
+
+    /// Every webhook, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `webhooks:manage` or the store
+    /// fails.
+    pub async fn list(&self, actor: &AuthenticatedUser) -> AppResult<Vec<WebhookDto>> {
+        ensure_capability(actor)?;
+        let webhooks = self.shared.repo.list().await?;
+        Ok(webhooks.into_iter().map(WebhookDto::from).collect())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `webhooks:manage`, there is no
+    /// such webhook, or the store fails.
+    pub async fn get(&self, actor: &AuthenticatedUser, id: i64) -> AppResult<WebhookDto> {
+        ensure_capability(actor)?;
+        Ok(self.find(id).await?.into())
+    }
+
+    /// Subscribe a URL to events. The response is the only one that shows
+    /// the secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error while read-only, if the actor lacks
+    /// `webhooks:manage`, the webhook is invalid, or the store fails.
+    pub async fn create(
+        &self,
+        actor: &AuthenticatedUser,
+        command: CreateWebhookCommand,
+    ) -> AppResult<WebhookDto> {
+        ensure_capability(actor)?;
+        self.read_only.ensure_writable()?;
+        let secret = match command.secret {
+            Some(secret) => secret,
+            None => random_id::secret_token()?,
+        };
+        let webhook = NewWebhook::new(
+            &command.url,
+            secret,
+            command.events,
+            Some(actor.id),
+            self.shared.clock.now(),
+        )?;
+        let webhook = self.shared.repo.insert(webhook).await?;
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("webhook.create", "webhook", Some(webhook.id))
+                    .with_details(json!({ "url": webhook.url, "events": webhook.events })),
+            )
+            .await;
+        let secret = webhook.secret.clone();
+        Ok(WebhookDto {
+            secret: Some(secret),
+            ..webhook.into()
+        })
+    }
+
+    /// Change a webhook. Deliveries already queued pick the change up on
+    /// their next try.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error while read-only, if the actor lacks
+    /// `webhooks:manage`, there is no such webhook, a change is invalid, or
+    /// the store fails.
+    pub async fn update(
+        &self,
+        actor: &AuthenticatedUser,
+        id: i64,
+        update: WebhookUpdate,
+    ) -> AppResult<WebhookDto> {
+        ensure_capability(actor)?;
+        self.read_only.ensure_writable()?;
+        let secret_rotated = update.secret.is_some();
+        let webhook = update.apply(self.find(id).await?, self.shared.clock.now())?;
+        if !self.shared.repo.update(&webhook).await? {
+            return Err(AppError::not_found("webhook not found"));
+        }
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("webhook.update", "webhook", Some(webhook.id)).with_details(
+                    json!({
+                        "url": webhook.url,
+                        "events": webhook.events,
+                        "active": webhook.active,
+                        "secret_rotated": secret_rotated,
+                    }),
+                ),
+            )
+            .await;
+        Ok(webhook.into())
+    }
+
+    /// Remove a webhook and its delivery history.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error while read-only, if the actor lacks
+    /// `webhooks:manage`, there is no such webhook, or the store fails.
+    pub async fn delete(&self, actor: &AuthenticatedUser, id: i64) -> AppResult<()> {
+        ensure_capability(actor)?;
+        self.read_only.ensure_writable()?;
+        let webhook = self.find(id).await?;
+        if !self.shared.repo.delete(id).await? {
+            return Err(AppError::not_found("webhook not found"));
+        }
+        self.audit
+            .record(
+                Some(actor.id),
+                AuditEvent::new("webhook.delete", "webhook", Some(id))
+                    .with_details(json!({ "url": webhook.url })),
+            )
+            .await;
+        Ok(())
+    }
+
+    /// The latest tries at delivering to webhook `id`, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the actor lacks `webhooks:manage`, there is no
+    /// such webhook, or the store fails.
+    pub async fn deliveries(
+        &self,
+        actor: &AuthenticatedUser,
+        id: i64,
+        limit: Option<u32>,
+    ) -> AppResult<Vec<WebhookDeliveryDto>> {
+        ensure_capability(actor)?;
+        let webhook = self.find(id).await?;
+        let limit = self.page_limits.apply(limit.unwrap_or(0));
+        let attempts = self.shared.repo.list_attempts(webhook.id, limit).await?;
+        Ok(attempts.into_iter().map(WebhookDeliveryDto::from).collect())
+    }
+
+    async fn find(&self, id: i64) -> AppResult<crate::domain::Webhook> {
+        self.shared
+            .repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::not_found("webhook not found"))
+    }
+}
+
+impl EventHandler for WebhookService {
+    fn handle<'a>(&'a self, event: &'a DomainEvent) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let private = event
+                .article
+                .as_ref()
+                .is_some_and(|article| article.visibility == ArticleVisibility::Private);
+            if self.shared.sender.is_none() || private {
+                return Ok(());
+            }
+            let Some(payload) = ArticleWebhookPayload::from_event(event) else {
+                return Ok(());
+            };
+            let webhook_event = payload.event;
+            let webhooks = self.shared.repo.subscribed(webhook_event).await?;
+            if webhooks.is_empty() {
+                return Ok(());
+            }
+            let body: Arc<[u8]> = serde_json::to_vec(&payload)
+                .map_err(|err| AppError::infrastructure(err.to_string()))?
+                .into();
+            for webhook in webhooks {
+                Delivery {
+                    shared: Arc::clone(&self.shared),
+                    webhook_id: webhook.id,
+                    event: webhook_event,
+                    id: random_id::v4_string()?,
+                    body: Arc::clone(&body),
+                    attempt: 1,
+                }
+                .schedule(Duration::ZERO);
+            }
+            Ok(())
+        })
+    }
+}
+
+/// One event on its way to one webhook.
+struct Delivery {
+    shared: Arc<Shared>,
+    webhook_id: i64,
+    event: WebhookEvent,
+    /// Shared by every try, so receivers can drop repeats.
+    id: String,
+    body: Arc<[u8]>,
+    attempt: u32,
+}
+
+impl Delivery {
+    fn schedule(self, wait: Duration) {
+        let queue = Arc::clone(&self.shared.job_queue);
+        queue.enqueue(
+            "webhook",
+            boxed(async move {
+                tokio::time::sleep(wait).await;
+                self.run().await;
+            }),
+        );
+    }
+
+    async fn run(self) {
+        let webhook_id = self.webhook_id;
+        let event = self.event.as_str();
+        match self.try_once().await {
+            Ok(None) => {
+                tracing::debug!(webhook_id, event, "webhook no longer receives event");
+                return;
+            }
+            Ok(Some(true)) => {
+                tracing::info!(webhook_id, event, "webhook delivered");
+                return;
+            }
+            Ok(Some(false)) => {}
+            Err(err) => {
+                tracing::warn!(error = %err, webhook_id, event, "webhook delivery not tried");
+            }
+        }
+
+        let policy = &self.shared.policy;
+        if self.attempt >= policy.max_attempts {
+            tracing::error!(
+                webhook_id,
+                event,
+                attempts = self.attempt,
+                "giving up on delivering webhook"
+            );
+            return;
+        }
+        let wait = policy
+            .retry_backoff
+            .saturating_mul(2u32.saturating_pow(self.attempt - 1));
+        tracing::warn!(
+            webhook_id,
+            event,
+            attempt = self.attempt,
+            retry_in_secs = wait.as_secs(),
+            "delivering webhook failed; retrying"
+        );
+        Self {
+            attempt: self.attempt + 1,
+            ..self
+        }
+        .schedule(wait);
+    }
+
+    /// Post the event once and record the try. `None` once the webhook is
+    /// gone or no longer receives the event; otherwise whether it was
+    /// accepted.
+    async fn try_once(&self) -> AppResult<Option<bool>> {
+        let Some(sender) = &self.shared.sender else {
+            return Ok(None);
+        };
+        let Some(webhook) = self
+            .shared
+            .repo
+            .find_by_id(self.webhook_id)
+            .await?
+            .filter(|webhook| webhook.receives(self.event))
+        else {
+            return Ok(None);
+        };
+
+        let attempted_at = self.shared.clock.now();
+        let started = Instant::now();
+        let request = WebhookRequest {
+            url: &webhook.url,
+            secret: &webhook.secret,
+            event: self.event,
+            delivery_id: &self.id,
+            timestamp: attempted_at,
+            body: &self.body,
+        };
+        let (status_code, error) = match sender.send(&request).await {
+            Ok(status) if (200..300).contains(&status) => (Some(status), None),
+            Ok(status) => (Some(status), Some(format!("responded with {status}"))),
+            Err(err) => (
+                None,
+                Some(NewWebhookDeliveryAttempt::truncate_error(&err.to_string())),
+            ),
+        };
+        let succeeded = error.is_none();
+        let attempt = NewWebhookDeliveryAttempt {
+            webhook_id: webhook.id,
+            delivery_id: self.id.clone(),
+            event: self.event,
+            attempt: self.attempt,
+            status_code,
+            error,
+            succeeded,
+            duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            attempted_at,
+        };
+        // The try happened either way; failing to record it must not send
+        // the event again.
+        if let Err(err) = self.shared.repo.record_attempt(attempt).await {
+            tracing::warn!(
+                error = %err,
+                webhook_id = webhook.id,
+                "failed to record webhook delivery attempt"
+            );
+        }
+        Ok(Some(succeeded))
+    }
+}
+
+fn ensure_capability(actor: &AuthenticatedUser) -> AppResult<()> {
+    if actor.has_capability("webhooks", "manage") {
+        Ok(())
+    } else {
+        Err(AppError::forbidden("missing capability webhooks:manage"))
+    }
+}
@@ -0,0 +1,237 @@
+// src/application/text_diff.rs
+//! Line and word diffs for comparing article revisions, using Myers'
+//! shortest edit script over tokens that keep their whitespace, so the
+//! segments reassemble into the original texts exactly.
+
+use crate::application::{DiffGranularity, DiffOp, DiffSegment, FieldDiffDto};
+
+/// Edit distance, in tokens, beyond which the differing middle of the texts
+/// is reported as one deletion and one insertion instead of being searched
+/// further. Bounds the work a near-total rewrite of a long body can cause.
+const MAX_EDIT_DISTANCE: usize = 1000;
+
+/// Compare `from` with `to`.
+pub fn diff(from: &str, to: &str, granularity: DiffGranularity) -> FieldDiffDto {
+    let old = tokenize(from, granularity);
+    let new = tokenize(to, granularity);
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut edits: Vec<(DiffOp, &str)> = old[..prefix]
+        .iter()
+        .map(|token| (DiffOp::Equal, *token))
+        .collect();
+    if let Some(middle) = shortest_edit(old_middle, new_middle) {
+        edits.extend(middle);
+    } else {
+        edits.extend(old_middle.iter().map(|token| (DiffOp::Delete, *token)));
+        edits.extend(new_middle.iter().map(|token| (DiffOp::Insert, *token)));
+    }
+    edits.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|token| (DiffOp::Equal, *token)),
+    );
+
+    let counted = |op| {
+        edits
+            .iter()
+            .filter(|(edit, token)| *edit == op && !token.trim().is_empty())
+            .count()
+    };
+    FieldDiffDto {
+        changed: from != to,
+        added: counted(DiffOp::Insert),
+        removed: counted(DiffOp::Delete),
+        segments: merge(&edits),
+    }
+}
+
+/// Lines with their terminators, or alternating runs of whitespace and
+/// non-whitespace.
+fn tokenize(text: &str, granularity: DiffGranularity) -> Vec<&str> {
+    match granularity {
+        DiffGranularity::Line => text.split_inclusive('\n').collect(),
+        DiffGranularity::Word => {
+            let mut tokens = Vec::new();
+            let mut start = 0;
+            let mut in_space = None;
+            for (index, ch) in text.char_indices() {
+                let space = ch.is_whitespace();
+                if in_space.is_some_and(|previous| previous != space) {
+                    tokens.push(&text[start..index]);
+                    start = index;
+                }
+                in_space = Some(space);
+            }
+            if start < text.len() {
+                tokens.push(&text[start..]);
+            }
+            tokens
+        }
+    }
+}
+
+/// Myers' greedy forward search, keeping each round's frontier so the path
+/// can be walked back. `None` when the texts differ by more than
+/// [`MAX_EDIT_DISTANCE`] tokens.
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+fn shortest_edit<'a>(old: &[&'a str], new: &[&'a str]) -> Option<Vec<(DiffOp, &'a str)>> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = MAX_EDIT_DISTANCE.min(old.len() + new.len()) as isize;
+    let offset = max + 1;
+    let mut frontier = vec![0_isize; 2 * max as usize + 3];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    for d in 0..=max {
+        for k in (-d..=d).step_by(2) {
+            let down = k == -d
+                || (k != d
+                    && frontier[(offset + k - 1) as usize] < frontier[(offset + k + 1) as usize]);
+            let mut x = if down {
+                frontier[(offset + k + 1) as usize]
+            } else {
+                frontier[(offset + k - 1) as usize] + 1
+            };
+            while x < n && x - k < m && old[x as usize] == new[(x - k) as usize] {
+                x += 1;
+            }
+            let y = x - k;
+            frontier[(offset + k) as usize] = x;
+            if x >= n && y >= m {
+                trace.push(frontier[(offset - d) as usize..=(offset + d) as usize].to_vec());
+                return Some(backtrack(&trace, old, new));
+            }
+        }
+        trace.push(frontier[(offset - d) as usize..=(offset + d) as usize].to_vec());
+    }
+    None
+}
+
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+fn backtrack<'a>(trace: &[Vec<isize>], old: &[&'a str], new: &[&'a str]) -> Vec<(DiffOp, &'a str)> {
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (old.len() as isize, new.len() as isize);
+    for d in (1..trace.len() as isize).rev() {
+        let previous = &trace[(d - 1) as usize];
+        let at = |k: isize| previous[(k + d - 1) as usize];
+        let k = x - y;
+        let previous_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let previous_x = at(previous_k);
+        let previous_y = previous_x - previous_k;
+        while x > previous_x && y > previous_y {
+            x -= 1;
+            y -= 1;
+            edits.push((DiffOp::Equal, old[x as usize]));
+        }
+        if x == previous_x {
+            edits.push((DiffOp::Insert, new[previous_y as usize]));
+        } else {
+            edits.push((DiffOp::Delete, old[previous_x as usize]));
+        }
+        (x, y) = (previous_x, previous_y);
+    }
+    while x > 0 && y > 0 {
+        x -= 1;
+        y -= 1;
+        edits.push((DiffOp::Equal, old[x as usize]));
+    }
+    edits.reverse();
+    edits
+}
+
+/// Join neighbouring tokens with the same operation.
+fn merge(edits: &[(DiffOp, &str)]) -> Vec<DiffSegment> {
+    let mut segments: Vec<DiffSegment> = Vec::new();
+    for (op, token) in edits {
+        match segments.last_mut() {
+            Some(last) if last.op == *op => last.text.push_str(token),
+            _ => segments.push(DiffSegment {
+                op: *op,
+                text: (*token).to_owned(),
+            }),
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rebuild(segments: &[DiffSegment], skip: DiffOp) -> String {
+        segments
+            .iter()
+            .filter(|segment| segment.op != skip)
+            .map(|segment| segment.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn line_diff_reassembles_both_texts() {
+        let from = "one\ntwo\nthree\nfour\n";
+        let to = "one\n2\nthree\nfour\nfive";
+        let result = diff(from, to, DiffGranularity::Line);
+
+        assert!(result.changed);
+        assert_eq!((result.added, result.removed), (2, 1));
+        assert_eq!(rebuild(&result.segments, DiffOp::Insert), from);
+        assert_eq!(rebuild(&result.segments, DiffOp::Delete), to);
+        assert_eq!(
+            result.segments[1],
+            DiffSegment {
+                op: DiffOp::Delete,
+                text: "two\n".into()
+            }
+        );
+    }
+
+    #[test]
+    fn word_diff_ignores_whitespace_in_counts() {
+        let result = diff(
+            "the quick brown fox",
+            "the slow brown fox jumps",
+            DiffGranularity::Word,
+        );
+
+        assert_eq!((result.added, result.removed), (2, 1));
+        assert_eq!(
+            rebuild(&result.segments, DiffOp::Delete),
+            "the slow brown fox jumps"
+        );
+    }
+
+    #[test]
+    fn identical_texts_are_one_equal_segment() {
+        let result = diff("same\ntext", "same\ntext", DiffGranularity::Line);
+        assert!(!result.changed);
+        assert_eq!(result.segments.len(), 1);
+        assert_eq!(result.segments[0].op, DiffOp::Equal);
+    }
+
+    #[test]
+    fn large_rewrites_fall_back_to_replacing_the_middle() {
+        let lines = |prefix: &str| -> String {
+            (0..1200)
+                .map(|i| [prefix, &i.to_string(), "\n"].concat())
+                .collect()
+        };
+        let (from, to) = (lines("a"), lines("b"));
+        let result = diff(&from, &to, DiffGranularity::Line);
+
+        assert_eq!((result.added, result.removed), (1200, 1200));
+        assert_eq!(result.segments.len(), 2);
+    }
+}
@@ -0,0 +1,400 @@
+// src/client/mod.rs
+//! Typed async client for the HTTP API, built with the `client` feature.
+//!
+//! Requests and responses are the server's own DTOs, so the client cannot
+//! drift from the handlers. Every call is listed in [`OPERATIONS`], which
+//! `tests/client_openapi.rs` checks against `spec/openapi.json`.
+mod transport;
+
+use crate::application::{ArticleDto, AuthTokenDto, ServiceStatusDto, UserProfileDto};
+use crate::presentation::http::controllers::articles::PublishRequest;
+use crate::presentation::http::controllers::user_requests::{LoginRequest, RefreshTokenRequest};
+use crate::presentation::http::error::ResponsePayload;
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::{Method, Request, StatusCode, header};
+use serde::{Serialize, de::DeserializeOwned};
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Duration;
+use transport::Endpoint;
+
+pub use crate::presentation::http::controllers::articles::{
+    ArticleListParams, CreateArticleRequest, UpdateArticleRequest,
+};
+pub use crate::presentation::http::controllers::user_requests::LoginResponse;
+pub use crate::presentation::http::openapi::{ArticleListResponse, StatusResponse};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Errors returned by [`ApiClient`].
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("invalid API url: {0}")]
+    InvalidUrl(String),
+    #[error("request failed: {0}")]
+    Transport(String),
+    #[error("request timed out")]
+    Timeout,
+    /// The server answered with an error status.
+    #[error("{status}: {}", payload.message)]
+    Api {
+        status: StatusCode,
+        payload: ResponsePayload,
+    },
+    #[error("unexpected response body: {0}")]
+    Decode(String),
+}
+
+impl ClientError {
+    /// HTTP status of an [`ClientError::Api`] error.
+    #[must_use]
+    pub const fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Api { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+pub type ClientResult<T> = Result<T, ClientError>;
+
+/// An API operation the client calls, as documented in the `OpenAPI` spec.
+#[derive(Debug, Clone, Copy)]
+pub struct Operation {
+    pub method: &'static str,
+    /// Path template, with `{name}` parameters.
+    pub path: &'static str,
+    /// Schema name of the JSON request body, if any.
+    pub request: Option<&'static str>,
+    /// Schema name of the `200` response body.
+    pub response: &'static str,
+}
+
+const fn operation(
+    method: &'static str,
+    path: &'static str,
+    request: Option<&'static str>,
+    response: &'static str,
+) -> Operation {
+    Operation {
+        method,
+        path,
+        request,
+        response,
+    }
+}
+
+const STATUS: Operation = operation("get", "/api/v1/status", None, "ServiceStatusDto");
+const LOGIN: Operation = operation(
+    "post",
+    "/api/v1/auth/login",
+    Some("LoginRequest"),
+    "LoginResponse",
+);
+const REFRESH: Operation = operation(
+    "post",
+    "/api/v1/auth/refresh",
+    Some("RefreshTokenRequest"),
+    "AuthTokenDto",
+);
+const ME: Operation = operation("get", "/api/v1/auth/me", None, "UserProfileDto");
+const LIST_ARTICLES: Operation = operation("get", "/api/v1/articles", None, "ArticleListResponse");
+const CREATE_ARTICLE: Operation = operation(
+    "post",
+    "/api/v1/articles",
+    Some("CreateArticleRequest"),
+    "ArticleDto",
+);
+const ARTICLE_BY_SLUG: Operation =
+    operation("get", "/api/v1/articles/by-slug/{slug}", None, "ArticleDto");
+const UPDATE_ARTICLE: Operation = operation(
+    "put",
+    "/api/v1/articles/{id}",
+    Some("UpdateArticleRequest"),
+    "ArticleDto",
+);
+const DELETE_ARTICLE: Operation =
+    operation("delete", "/api/v1/articles/{id}", None, "StatusResponse");
+const PUBLISH_ARTICLE: Operation = operation(
+    "post",
+    "/api/v1/articles/{id}/publish",
+    Some("PublishRequest"),
+    "ArticleDto",
+);
+
+/// Every operation [`ApiClient`] calls.
+pub const OPERATIONS: &[Operation] = &[
+    STATUS,
+    LOGIN,
+    REFRESH,
+    ME,
+    LIST_ARTICLES,
+    CREATE_ARTICLE,
+    ARTICLE_BY_SLUG,
+    UPDATE_ARTICLE,
+    DELETE_ARTICLE,
+    PUBLISH_ARTICLE,
+];
+
+/// Client for a remote instance.
+///
+/// Each request opens its own connection; responses use the baseline API
+/// profile.
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    endpoint: Arc<Endpoint>,
+    token: Option<String>,
+    timeout: Duration,
+}
+
+impl ApiClient {
+    /// A client for the instance at `base_url`, such as
+    /// `https://cms.example.com`. A path in the URL is kept as a prefix.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `base_url` is not an `http(s)` URL.
+    pub fn new(base_url: &str) -> ClientResult<Self> {
+        Ok(Self {
+            endpoint: Arc::new(Endpoint::parse(base_url)?),
+            token: None,
+            timeout: DEFAULT_TIMEOUT,
+        })
+    }
+
+    /// Send `token` as a bearer token on every request.
+    #[must_use]
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Give up on requests that take longer than `timeout` (default 30s).
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// `GET /api/v1/status`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the server rejects it.
+    pub async fn status(&self) -> ClientResult<ServiceStatusDto> {
+        self.call(&STATUS, STATUS.path.to_string(), None::<&()>)
+            .await
+    }
+
+    /// `POST /api/v1/auth/login`. Pass the returned token to
+    /// [`ApiClient::with_token`] for authenticated calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the credentials are wrong.
+    pub async fn login(&self, username: &str, password: &str) -> ClientResult<LoginResponse> {
+        let body = LoginRequest {
+            username: username.to_string(),
+            password: password.to_string(),
+        };
+        self.call(&LOGIN, LOGIN.path.to_string(), Some(&body)).await
+    }
+
+    /// `POST /api/v1/auth/refresh`, exchanging a refresh token for a new
+    /// access token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the refresh token is not
+    /// accepted.
+    pub async fn refresh(&self, refresh_token: &str) -> ClientResult<AuthTokenDto> {
+        let body = RefreshTokenRequest {
+            token: refresh_token.to_string(),
+        };
+        self.call(&REFRESH, REFRESH.path.to_string(), Some(&body))
+            .await
+    }
+
+    /// `GET /api/v1/auth/me`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the client has no valid
+    /// token.
+    pub async fn me(&self) -> ClientResult<UserProfileDto> {
+        self.call(&ME, ME.path.to_string(), None::<&()>).await
+    }
+
+    /// `GET /api/v1/articles`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the parameters are
+    /// rejected.
+    pub async fn list_articles(
+        &self,
+        params: &ArticleListParams,
+    ) -> ClientResult<ArticleListResponse> {
+        let query = serde_urlencoded::to_string(params)
+            .map_err(|err| ClientError::InvalidUrl(err.to_string()))?;
+        let path = format!("{}?{query}", LIST_ARTICLES.path);
+        self.call(&LIST_ARTICLES, path, None::<&()>).await
+    }
+
+    /// `GET /api/v1/articles/by-slug/{slug}`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the article is missing or
+    /// hidden from the caller.
+    pub async fn article_by_slug(&self, slug: &str) -> ClientResult<ArticleDto> {
+        let path = fill(ARTICLE_BY_SLUG.path, "slug", slug);
+        self.call(&ARTICLE_BY_SLUG, path, None::<&()>).await
+    }
+
+    /// `POST /api/v1/articles`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the article is rejected.
+    pub async fn create_article(&self, request: &CreateArticleRequest) -> ClientResult<ArticleDto> {
+        self.call(
+            &CREATE_ARTICLE,
+            CREATE_ARTICLE.path.to_string(),
+            Some(request),
+        )
+        .await
+    }
+
+    /// `PUT /api/v1/articles/{id}`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the article is missing, or
+    /// the change is rejected.
+    pub async fn update_article(
+        &self,
+        id: i64,
+        request: &UpdateArticleRequest,
+    ) -> ClientResult<ArticleDto> {
+        let path = fill(UPDATE_ARTICLE.path, "id", &id.to_string());
+        self.call(&UPDATE_ARTICLE, path, Some(request)).await
+    }
+
+    /// `POST /api/v1/articles/{id}/publish`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the article is missing.
+    pub async fn set_published(&self, id: i64, publish: bool) -> ClientResult<ArticleDto> {
+        let path = fill(PUBLISH_ARTICLE.path, "id", &id.to_string());
+        self.call(&PUBLISH_ARTICLE, path, Some(&PublishRequest { publish }))
+            .await
+    }
+
+    /// `DELETE /api/v1/articles/{id}`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the article is missing.
+    pub async fn delete_article(&self, id: i64) -> ClientResult<StatusResponse> {
+        let path = fill(DELETE_ARTICLE.path, "id", &id.to_string());
+        self.call(&DELETE_ARTICLE, path, None::<&()>).await
+    }
+
+    async fn call<B, T>(
+        &self,
+        operation: &Operation,
+        path: String,
+        body: Option<&B>,
+    ) -> ClientResult<T>
+    where
+        B: Serialize + ?Sized + Sync,
+        T: DeserializeOwned,
+    {
+        let method = Method::from_bytes(operation.method.to_uppercase().as_bytes())
+            .map_err(|err| ClientError::InvalidUrl(err.to_string()))?;
+        let mut request = Request::builder()
+            .method(method)
+            .uri(format!("{}{path}", self.endpoint.base_path))
+            .header(header::HOST, &self.endpoint.authority)
+            .header(header::ACCEPT, "application/json");
+        if let Some(token) = &self.token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        let payload = match body {
+            Some(body) => {
+                request = request.header(header::CONTENT_TYPE, "application/json");
+                serde_json::to_vec(body)
+                    .map(Bytes::from)
+                    .map_err(|err| ClientError::Decode(err.to_string()))?
+            }
+            None => Bytes::new(),
+        };
+        let request = request
+            .body(Full::new(payload))
+            .map_err(|err| ClientError::InvalidUrl(err.to_string()))?;
+
+        let (status, body) = tokio::time::timeout(self.timeout, self.endpoint.send(request))
+            .await
+            .map_err(|_| ClientError::Timeout)??;
+        if !status.is_success() {
+            return Err(api_error(status, &body));
+        }
+        serde_json::from_slice(&body).map_err(|err| ClientError::Decode(err.to_string()))
+    }
+}
+
+/// The error a failed response describes, falling back to its text for
+/// bodies that are not the API's error JSON.
+fn api_error(status: StatusCode, body: &[u8]) -> ClientError {
+    let payload = serde_json::from_slice(body).unwrap_or_else(|_| ResponsePayload {
+        error: status.canonical_reason().unwrap_or_default().to_string(),
+        message: String::from_utf8_lossy(body).trim().to_string(),
+        code: None,
+    });
+    ClientError::Api { status, payload }
+}
+
+/// `template` with `{name}` replaced by the percent-encoded `value`.
+fn fill(template: &str, name: &str, value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    template.replace(&format!("{{{name}}}"), &encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_parameters_are_percent_encoded() {
+        assert_eq!(
+            fill(ARTICLE_BY_SLUG.path, "slug", "hello world/ü"),
+            "/api/v1/articles/by-slug/hello%20world%2F%C3%BC"
+        );
+        assert_eq!(fill(DELETE_ARTICLE.path, "id", "7"), "/api/v1/articles/7");
+    }
+
+    #[test]
+    fn non_json_error_bodies_keep_their_text() {
+        let err = api_error(StatusCode::TOO_MANY_REQUESTS, b"slow down\n");
+        let ClientError::Api { status, payload } = err else {
+            panic!("expected an API error");
+        };
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(payload.message, "slow down");
+
+        let body = br#"{"error":"Conflict","message":"slug taken","code":"slug_taken"}"#;
+        let err = api_error(StatusCode::CONFLICT, body);
+        assert_eq!(err.status(), Some(StatusCode::CONFLICT));
+        assert!(err.to_string().ends_with("slug taken"), "{err}");
+    }
+}
@@ -0,0 +1,129 @@
+// src/client/transport.rs
+//! One HTTP/1.1 request per connection, over TLS for `https://` instances.
+use super::{ClientError, ClientResult};
+use bytes::Bytes;
+use http_body_util::{BodyExt as _, Full, Limited};
+use hyper::{Request, StatusCode, Uri};
+use hyper_util::rt::TokioIo;
+use std::sync::{Arc, LazyLock};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::{self, RootCertStore, pki_types::ServerName};
+
+/// Largest response body accepted from the server.
+const MAX_RESPONSE_BYTES: usize = 16 << 20;
+
+static TLS: LazyLock<TlsConnector> = LazyLock::new(|| {
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .expect("ring supports the default protocol versions")
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    TlsConnector::from(Arc::new(config))
+});
+
+/// Where an instance is reached, parsed from its base URL.
+#[derive(Debug)]
+pub(super) struct Endpoint {
+    tls: bool,
+    host: String,
+    port: u16,
+    /// Value of the `Host` header.
+    pub(super) authority: String,
+    /// Path prefix the API is mounted under, without a trailing slash.
+    pub(super) base_path: String,
+}
+
+impl Endpoint {
+    pub(super) fn parse(base_url: &str) -> ClientResult<Self> {
+        let invalid = || ClientError::InvalidUrl(base_url.to_string());
+        let uri: Uri = base_url.parse().map_err(|_| invalid())?;
+        let tls = match uri.scheme_str() {
+            Some("https") => true,
+            Some("http") => false,
+            _ => return Err(invalid()),
+        };
+        let host = uri.host().ok_or_else(invalid)?.to_string();
+        let port = uri.port_u16().unwrap_or(if tls { 443 } else { 80 });
+        let authority = uri
+            .authority()
+            .map_or_else(|| host.clone(), ToString::to_string);
+        Ok(Self {
+            tls,
+            host,
+            port,
+            authority,
+            base_path: uri.path().trim_end_matches('/').to_string(),
+        })
+    }
+
+    pub(super) async fn send(
+        &self,
+        request: Request<Full<Bytes>>,
+    ) -> ClientResult<(StatusCode, Bytes)> {
+        let io_error = |err: std::io::Error| ClientError::Transport(err.to_string());
+        let host = self.host.trim_start_matches('[').trim_end_matches(']');
+        let stream = TcpStream::connect((host, self.port))
+            .await
+            .map_err(io_error)?;
+        if self.tls {
+            let server_name = ServerName::try_from(host.to_string())
+                .map_err(|_| ClientError::InvalidUrl(self.host.clone()))?;
+            let stream = TLS.connect(server_name, stream).await.map_err(io_error)?;
+            exchange(stream, request).await
+        } else {
+            exchange(stream, request).await
+        }
+    }
+}
+
+async fn exchange<S>(stream: S, request: Request<Full<Bytes>>) -> ClientResult<(StatusCode, Bytes)>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let transport = |err: &dyn std::fmt::Display| ClientError::Transport(err.to_string());
+    let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+        .await
+        .map_err(|err| transport(&err))?;
+    tokio::spawn(async move {
+        if let Err(err) = connection.await {
+            tracing::debug!(error = %err, "API connection closed with error");
+        }
+    });
+
+    let response = sender
+        .send_request(request)
+        .await
+        .map_err(|err| transport(&err))?;
+    let status = response.status();
+    let body = Limited::new(response.into_body(), MAX_RESPONSE_BYTES)
+        .collect()
+        .await
+        .map_err(|err| transport(&err))?
+        .to_bytes();
+    Ok((status, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_urls_keep_their_path_prefix() {
+        let endpoint = Endpoint::parse("https://cms.example.com/mokkan/").unwrap();
+        assert!(endpoint.tls);
+        assert_eq!(endpoint.port, 443);
+        assert_eq!(endpoint.authority, "cms.example.com");
+        assert_eq!(endpoint.base_path, "/mokkan");
+
+        let endpoint = Endpoint::parse("http://127.0.0.1:3000").unwrap();
+        assert_eq!((endpoint.port, endpoint.base_path.as_str()), (3000, ""));
+        assert!(Endpoint::parse("ftp://cms.example.com").is_err());
+    }
+}
@@ -1,5 +1,8 @@
 // src/config.rs
-use std::{env, time::Duration};
+use crate::application::ports::session_revocation::{RefreshBinding, RefreshBindingMode};
+use crate::application::{DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
+use crate::domain::{IpRange, PAGE_LIMIT_CEILING, Role};
+use std::{collections::HashMap, env, time::Duration};
 use thiserror::Error;
 
 #[derive(Clone, Debug)]
@@ -7,12 +10,243 @@ pub struct Settings {
     database_url: String,
     listen_addr: String,
     biscuit_private_key: String,
+    // Rotating Biscuit root keys, re-read on SIGHUP
+    biscuit_root_keys_file: Option<String>,
+    // Access token format, from `TOKEN_BACKEND`
+    token_backend: TokenBackend,
     refresh_token_secret: String,
+    // Signs export download links; defaults to the refresh token secret
+    download_link_secret: String,
+    download_link_ttl: Duration,
     token_ttl: Duration,
-    allowed_origins: Vec<String>,
+    // Which browser origins may call the API, and how
+    cors: CorsSettings,
     // Redis-related runtime options
     redis_used_nonce_ttl_secs: usize,
     redis_preload_cas_script: bool,
+    // Optional receiver for security incidents
+    security_webhook_url: Option<String>,
+    command_journal_path: Option<String>,
+    // ASN databases used by access rules
+    asn_database_paths: Vec<String>,
+    audit_buffer: Option<AuditBufferSettings>,
+    // Tenant ids routed to their own `tenant_<id>` schema
+    tenant_schemas: Vec<String>,
+    // Propagate the acting user to Postgres row-level security policies
+    row_level_security: bool,
+    // Session metadata retention and refresh limits
+    sessions: SessionSettings,
+    // Cost of new password hashes
+    password_hashing: PasswordHashSettings,
+    // How many recent passwords a new one may not repeat
+    password_history_depth: usize,
+    // Pwned Passwords range API new passwords are checked against
+    pwned_passwords_url: Option<String>,
+    // How long a deleted account stays recoverable before it is anonymized
+    account_deletion_grace: Duration,
+    // Cron overrides from `JOB_SCHEDULE_<JOB>`, keyed by lowercase job name
+    job_schedules: HashMap<String, String>,
+    job_jitter: Duration,
+    // External directory login, enabled by `LDAP_URL`
+    ldap: Option<LdapSettings>,
+    // Upstream OIDC providers users may log in with
+    oidc_login_providers: Vec<OidcLoginProvider>,
+    // Where published articles are announced
+    syndication: SyndicationSettings,
+    webhooks: WebhookSettings,
+    pagination: PaginationSettings,
+    // Accepted uploads and where they are stored
+    media: MediaSettings,
+    // Text search configuration articles are indexed and searched with
+    search_language: String,
+    // Directory static site exports are written to
+    static_export_dir: String,
+    // Span export, enabled by `OTEL_EXPORTER_OTLP_ENDPOINT`
+    otel: Option<OtelSettings>,
+}
+
+/// Connection and provisioning settings for LDAP login.
+#[derive(Clone, Debug)]
+pub struct LdapSettings {
+    pub url: String,
+    /// Bind DN with a `{username}` placeholder.
+    pub user_dn_template: String,
+    /// Attribute on the user entry listing their groups.
+    pub group_attribute: String,
+    /// `(group DN, role)` pairs, first match wins.
+    pub group_roles: Vec<(String, Role)>,
+    /// Role for users in none of the mapped groups; unmapped users are
+    /// refused when unset.
+    pub default_role: Option<Role>,
+}
+
+/// Which `TokenManager` signs access tokens.
+#[derive(Clone, Debug)]
+pub enum TokenBackend {
+    /// Biscuit tokens signed with `BISCUIT_ROOT_PRIVATE_KEY`.
+    Biscuit,
+    /// JWTs, for clients that only understand standard OIDC tokens.
+    Jwt(JwtSettings),
+}
+
+/// Which database the repositories live in, from `DATABASE_BACKEND`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DatabaseBackend {
+    /// `PostgreSQL`, with row-level security and full-text search.
+    Postgres,
+    /// One `SQLite` file, for embedded single-process instances.
+    Sqlite,
+}
+
+/// Signing settings for JWT access tokens, from `JWT_*` variables.
+#[derive(Clone, Debug)]
+pub struct JwtSettings {
+    /// `ES256` or `EdDSA`.
+    pub algorithm: String,
+    /// PEM-encoded PKCS#8 private key.
+    pub private_key_path: String,
+    /// Published `kid`; the key's JWK thumbprint when unset.
+    pub key_id: Option<String>,
+    pub issuer: String,
+    /// Required `aud`, if any.
+    pub audience: Option<String>,
+}
+
+/// Decoy paths that trap scanners, from `HONEYPOT_*` variables.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HoneypotSettings {
+    /// Paths no legitimate client requests, e.g. `/wp-login.php`.
+    pub paths: Vec<String>,
+    /// How long a client that requests one is denied every route; zero only
+    /// logs the hit.
+    pub block_for: Duration,
+    /// How long the decoy response is held back; zero answers at once.
+    pub tarpit: Duration,
+}
+
+/// Failed-login limits, from `LOGIN_*` variables.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoginThrottleSettings {
+    /// Failures for one username within `window` that lock the account.
+    pub max_failures_per_user: u32,
+    /// Failures from one client address within `window` that lock it out.
+    pub max_failures_per_client: u32,
+    pub window: Duration,
+    pub lockout: Duration,
+}
+
+/// Session lifetimes, from `SESSION_MAX_LIFETIME_SECS`, `SESSION_MAX_AGE`,
+/// `SESSION_IDLE_TIMEOUT` and the `IN_MEMORY_*_TTL_SECS` retention of the
+/// in-memory session store (all in seconds).
+///
+/// Also the client binding of refresh tokens, from `REFRESH_TOKEN_BINDING*`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SessionSettings {
+    /// Sessions created longer ago than this are purged by the cleanup job.
+    pub max_lifetime: Duration,
+    /// Refreshing stops working this long after login.
+    pub max_age: Duration,
+    /// Sessions not refreshed for this long expire; `None` disables idle
+    /// expiry.
+    pub idle_timeout: Option<Duration>,
+    /// How long the in-memory store remembers used refresh nonces.
+    pub in_memory_used_nonce_ttl: Duration,
+    /// How long the in-memory store remembers revoked sessions.
+    pub in_memory_revoked_ttl: Duration,
+    /// Whether refresh tokens only work for the client that logged in.
+    pub refresh_binding: RefreshBinding,
+}
+
+/// Argon2id cost of new password hashes, from `ARGON2_MEMORY_KIB`,
+/// `ARGON2_ITERATIONS` and `ARGON2_PARALLELISM`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PasswordHashSettings {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+/// Requests per minute each client may send to a group of routes, from
+/// `RATE_LIMIT_*_PER_MINUTE` variables. Zero lifts a group's limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RouteRateLimitSettings {
+    /// Everything under `/api/v1/auth`.
+    pub auth: u32,
+    /// Creating, changing and deleting articles.
+    pub article_writes: u32,
+    /// Everything under `/api/v1/admin`.
+    pub admin: u32,
+}
+
+/// Bounds of the public slug lookup cache, from `SLUG_CACHE_*` variables.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SlugCacheSettings {
+    /// Most slugs held; zero turns the cache off.
+    pub capacity: usize,
+    pub ttl: Duration,
+    /// How long a slug that matched nothing is remembered.
+    pub negative_ttl: Duration,
+}
+
+/// Default and largest page size of a listing endpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PageLimitSettings {
+    pub default: u32,
+    pub max: u32,
+}
+
+/// Page sizes of listing endpoints, from `PAGE_LIMIT_*` variables.
+///
+/// `PAGE_LIMIT_DEFAULT` and `PAGE_LIMIT_MAX` set `shared`, which every
+/// endpoint inherits unless `PAGE_LIMIT_<ENDPOINT>_DEFAULT` or `_MAX`
+/// overrides it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PaginationSettings {
+    pub shared: PageLimitSettings,
+    pub articles: PageLimitSettings,
+    pub users: PageLimitSettings,
+    pub audit_logs: PageLimitSettings,
+    pub moderation: PageLimitSettings,
+    pub jobs: PageLimitSettings,
+}
+
+/// Uploaded media, from `MEDIA_*` variables.
+#[derive(Clone, Debug)]
+pub struct MediaSettings {
+    /// Largest accepted upload, in bytes.
+    pub max_bytes: usize,
+    /// Accepted content types, lowercase.
+    pub allowed_types: Vec<String>,
+    pub storage: MediaStorageSettings,
+}
+
+/// Where uploaded media is kept, chosen by `MEDIA_STORAGE`.
+#[derive(Clone, Debug)]
+pub enum MediaStorageSettings {
+    /// Files in a local directory, served by this service.
+    Local { dir: String },
+    /// An S3-compatible bucket downloads are redirected to.
+    S3 {
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key_id: String,
+        secret_access_key: String,
+        /// Lifetime of presigned download URLs.
+        url_ttl: Duration,
+        /// Public base URL of the bucket, used instead of presigned URLs.
+        public_url: Option<String>,
+    },
+}
+
+/// Queueing of audit log writes, from `AUDIT_BUFFER_*` variables.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuditBufferSettings {
+    pub capacity: usize,
+    pub batch_size: usize,
+    pub flush_interval: Duration,
+    /// Drop entries when the queue is full instead of waiting for room.
+    pub drop_when_full: bool,
 }
 
 #[derive(Debug, Error)]
@@ -35,8 +269,464 @@ const fn default_token_ttl() -> u64 {
     3600
 }
 
-fn default_allowed_origins() -> Vec<String> {
-    vec!["http://localhost:3000".into()]
+const fn default_session_max_lifetime() -> u64 {
+    60 * 60 * 24 * 30
+}
+
+const fn default_job_jitter() -> u64 {
+    30
+}
+
+/// Cross-origin browser access, from `ALLOWED_ORIGINS` and `CORS_*`
+/// variables. `*` in a list allows any value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CorsSettings {
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+    /// Let browsers send cookies; never combined with any origin.
+    pub allow_credentials: bool,
+    /// How long browsers may cache a preflight response.
+    pub max_age: Duration,
+}
+
+impl Default for CorsSettings {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec!["http://localhost:3000".into()],
+            allowed_methods: ["GET", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"]
+                .map(String::from)
+                .to_vec(),
+            allowed_headers: vec!["*".into()],
+            allow_credentials: false,
+            max_age: Duration::from_hours(1),
+        }
+    }
+}
+
+/// Read `ALLOWED_ORIGINS` and `CORS_*` through `var`; unset variables keep
+/// the [`CorsSettings`] defaults.
+fn parse_cors(var: impl Fn(&str) -> Option<String>) -> Result<CorsSettings, Error> {
+    let defaults = CorsSettings::default();
+    let list = |name: &str| var(name).map(|v| parse_list(&v)).filter(|v| !v.is_empty());
+    let tokens = |name: &str, default: Vec<String>| {
+        let values = list(name).unwrap_or(default);
+        let token =
+            |v: &String| v == "*" || v.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if let Some(bad) = values.iter().find(|v| !token(v)) {
+            return Err(Error::Invalid(format!("{name}: invalid entry '{bad}'")));
+        }
+        Ok(values)
+    };
+    let settings = CorsSettings {
+        allowed_origins: list("ALLOWED_ORIGINS").unwrap_or(defaults.allowed_origins),
+        allowed_methods: tokens("CORS_ALLOWED_METHODS", defaults.allowed_methods)?
+            .into_iter()
+            .map(|method| method.to_ascii_uppercase())
+            .collect(),
+        allowed_headers: tokens("CORS_ALLOWED_HEADERS", defaults.allowed_headers)?,
+        allow_credentials: var("CORS_ALLOW_CREDENTIALS")
+            .is_some_and(|v| v.trim() == "1" || v.trim().eq_ignore_ascii_case("true")),
+        max_age: var("CORS_MAX_AGE_SECS")
+            .map(|v| {
+                v.trim()
+                    .parse::<u64>()
+                    .map_err(|err| Error::Invalid(format!("CORS_MAX_AGE_SECS: {err}")))
+            })
+            .transpose()?
+            .map_or(defaults.max_age, Duration::from_secs),
+    };
+    if settings.allow_credentials && settings.allowed_origins.iter().any(|o| o == "*") {
+        return Err(Error::Invalid(
+            "CORS_ALLOW_CREDENTIALS needs ALLOWED_ORIGINS to list origins, not '*'".into(),
+        ));
+    }
+    Ok(settings)
+}
+
+/// `SEARCH_LANGUAGE`, e.g. `english`; `simple` when unset. Postgres checks
+/// the configuration exists at startup, so only its shape is checked here.
+fn parse_search_language(value: Option<&str>) -> Result<String, Error> {
+    let language = value.map(str::trim).filter(|v| !v.is_empty());
+    let Some(language) = language else {
+        return Ok("simple".into());
+    };
+    if !language
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '.')
+    {
+        return Err(Error::Invalid(format!(
+            "SEARCH_LANGUAGE must name a text search configuration such as `english`, got `{language}`"
+        )));
+    }
+    Ok(language.to_string())
+}
+
+/// `DATABASE_BACKEND`: `postgres` (the default) or `sqlite`.
+fn parse_database_backend(value: Option<&str>) -> Result<DatabaseBackend, Error> {
+    match value
+        .map(str::trim)
+        .unwrap_or_default()
+        .to_lowercase()
+        .as_str()
+    {
+        "" | "postgres" | "postgresql" => Ok(DatabaseBackend::Postgres),
+        "sqlite" => Ok(DatabaseBackend::Sqlite),
+        other => Err(Error::Invalid(format!(
+            "DATABASE_BACKEND must be 'postgres' or 'sqlite', got '{other}'"
+        ))),
+    }
+}
+
+/// `name` trimmed, or `None` when it is unset or blank.
+fn optional_var(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn parse_trusted_proxies(value: &str) -> Vec<IpRange> {
+    parse_list(value)
+        .iter()
+        .filter_map(|entry| match entry.parse::<IpRange>() {
+            Ok(range) => Some(range),
+            Err(err) => {
+                tracing::warn!(entry = %entry, error = %err, "ignoring invalid trusted proxy");
+                None
+            }
+        })
+        .collect()
+}
+
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// An upstream OIDC provider, from `OIDC_LOGIN_<NAME>_*` variables.
+#[derive(Clone, Debug)]
+pub struct OidcLoginProvider {
+    /// Lowercase name used in `/api/v1/auth/oidc/{provider}/...` URLs.
+    pub name: String,
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    /// Must point at this service's callback for the provider.
+    pub redirect_uri: String,
+    pub default_role: Role,
+}
+
+/// Announcing published articles, from `SYNDICATION_*` variables.
+#[derive(Clone, Debug, Default)]
+pub struct SyndicationSettings {
+    pub targets: Vec<SyndicationTargetSettings>,
+    /// Public article link with `{slug}` and `{id}` placeholders; required
+    /// once any target is configured.
+    pub article_url: String,
+    pub delay: Duration,
+    pub max_attempts: u32,
+    pub retry_backoff: Duration,
+}
+
+/// Delivering article events to webhooks, from `WEBHOOK_*` variables.
+#[derive(Clone, Debug)]
+pub struct WebhookSettings {
+    pub max_attempts: u32,
+    pub retry_backoff: Duration,
+    /// How long one delivery waits for a response.
+    pub timeout: Duration,
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            retry_backoff: Duration::from_secs(30),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Exporting trace spans over OTLP/HTTP, from `OTEL_*` variables.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OtelSettings {
+    /// Full URL spans are posted to.
+    pub traces_url: String,
+    pub service_name: String,
+    pub headers: Vec<(String, String)>,
+    /// `EnvFilter` directives selecting the exported spans.
+    pub filter: String,
+}
+
+/// One syndication target, from `SYNDICATION_<NAME>_*` variables.
+#[derive(Clone, Debug)]
+pub struct SyndicationTargetSettings {
+    /// Lowercase name from `SYNDICATION_TARGETS`.
+    pub name: String,
+    /// Text posted, with `{title}`, `{url}`, `{slug}` and `{tags}`
+    /// placeholders.
+    pub template: String,
+    pub platform: SyndicationPlatform,
+}
+
+/// Where a syndication target posts and the credentials it posts with.
+#[derive(Clone, Debug)]
+pub enum SyndicationPlatform {
+    Mastodon {
+        instance_url: String,
+        access_token: String,
+    },
+    Bluesky {
+        service_url: String,
+        identifier: String,
+        app_password: String,
+    },
+    Slack {
+        webhook_url: String,
+    },
+}
+
+/// Parse `LDAP_GROUP_ROLES`: `;`-separated `<group dn>=<role>` entries. The
+/// role follows the last `=`, so group DNs need no quoting.
+fn parse_group_roles(value: &str) -> Result<Vec<(String, Role)>, Error> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (group, role) = entry
+                .rsplit_once('=')
+                .filter(|(group, _)| !group.trim().is_empty())
+                .ok_or_else(|| {
+                    Error::Invalid(format!("LDAP_GROUP_ROLES entry '{entry}' has no role"))
+                })?;
+            let role = role
+                .trim()
+                .parse()
+                .map_err(|err| Error::Invalid(format!("LDAP_GROUP_ROLES: {err}")))?;
+            Ok((group.trim().to_string(), role))
+        })
+        .collect()
+}
+
+/// Read `PAGE_LIMIT_*` through `var`. Each endpoint's limits must satisfy
+/// `1 <= default <= max <= PAGE_LIMIT_CEILING`.
+fn parse_pagination(var: impl Fn(&str) -> Option<String>) -> Result<PaginationSettings, Error> {
+    let number = |name: &str| {
+        var(name)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .map(|v| {
+                v.parse::<u32>()
+                    .map_err(|err| Error::Invalid(format!("{name}: {err}")))
+            })
+            .transpose()
+    };
+    let limits = |prefix: &str, inherited: PageLimitSettings| {
+        let limits = PageLimitSettings {
+            default: number(&format!("{prefix}DEFAULT"))?.unwrap_or(inherited.default),
+            max: number(&format!("{prefix}MAX"))?.unwrap_or(inherited.max),
+        };
+        if limits.default == 0 || limits.default > limits.max || limits.max > PAGE_LIMIT_CEILING {
+            return Err(Error::Invalid(format!(
+                "{prefix}DEFAULT ({}) and {prefix}MAX ({}) must satisfy 1 <= default <= max <= {PAGE_LIMIT_CEILING}",
+                limits.default, limits.max
+            )));
+        }
+        Ok(limits)
+    };
+    let shared = limits(
+        "PAGE_LIMIT_",
+        PageLimitSettings {
+            default: DEFAULT_PAGE_LIMIT,
+            max: MAX_PAGE_LIMIT,
+        },
+    )?;
+    Ok(PaginationSettings {
+        shared,
+        articles: limits("PAGE_LIMIT_ARTICLES_", shared)?,
+        users: limits("PAGE_LIMIT_USERS_", shared)?,
+        audit_logs: limits("PAGE_LIMIT_AUDIT_LOGS_", shared)?,
+        moderation: limits("PAGE_LIMIT_MODERATION_", shared)?,
+        jobs: limits("PAGE_LIMIT_JOBS_", shared)?,
+    })
+}
+
+/// Read the `SESSION_*` lifetimes through `var`. `SESSION_MAX_AGE` defaults
+/// to `SESSION_MAX_LIFETIME_SECS` and may not exceed it, since older
+/// sessions are purged anyway; `SESSION_IDLE_TIMEOUT` is off when unset or
+/// `0`. The in-memory store keeps used nonces for a week and revoked
+/// sessions for `SESSION_MAX_LIFETIME_SECS` unless `IN_MEMORY_*_TTL_SECS`
+/// say otherwise. None but the nonce TTL may be shorter than
+/// `TOKEN_TTL_SECONDS`.
+fn parse_sessions(
+    var: impl Fn(&str) -> Option<String>,
+    token_ttl_secs: u64,
+) -> Result<SessionSettings, Error> {
+    let number = |name: &str| {
+        var(name)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .map(|v| {
+                v.parse::<u64>()
+                    .map_err(|err| Error::Invalid(format!("{name}: {err}")))
+            })
+            .transpose()
+    };
+    let max_lifetime = var("SESSION_MAX_LIFETIME_SECS")
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or_else(default_session_max_lifetime);
+    if max_lifetime < token_ttl_secs {
+        return Err(Error::Invalid(
+            "SESSION_MAX_LIFETIME_SECS must not be shorter than TOKEN_TTL_SECONDS".into(),
+        ));
+    }
+
+    let max_age = number("SESSION_MAX_AGE")?.unwrap_or(max_lifetime);
+    if max_age < token_ttl_secs || max_age > max_lifetime {
+        return Err(Error::Invalid(
+            "SESSION_MAX_AGE must be between TOKEN_TTL_SECONDS and SESSION_MAX_LIFETIME_SECS"
+                .into(),
+        ));
+    }
+
+    let idle_timeout = number("SESSION_IDLE_TIMEOUT")?.filter(|secs| *secs > 0);
+    if idle_timeout.is_some_and(|secs| secs < token_ttl_secs) {
+        return Err(Error::Invalid(
+            "SESSION_IDLE_TIMEOUT must not be shorter than TOKEN_TTL_SECONDS".into(),
+        ));
+    }
+
+    let revoked_ttl = number("IN_MEMORY_REVOKED_SESSION_TTL_SECS")?.unwrap_or(max_lifetime);
+    if revoked_ttl < token_ttl_secs {
+        return Err(Error::Invalid(
+            "IN_MEMORY_REVOKED_SESSION_TTL_SECS must not be shorter than TOKEN_TTL_SECONDS".into(),
+        ));
+    }
+
+    Ok(SessionSettings {
+        max_lifetime: Duration::from_secs(max_lifetime),
+        max_age: Duration::from_secs(max_age),
+        idle_timeout: idle_timeout.map(Duration::from_secs),
+        in_memory_used_nonce_ttl: Duration::from_secs(
+            number("IN_MEMORY_USED_NONCE_TTL_SECS")?.unwrap_or(60 * 60 * 24 * 7),
+        ),
+        in_memory_revoked_ttl: Duration::from_secs(revoked_ttl),
+        refresh_binding: parse_refresh_binding(&var)?,
+    })
+}
+
+/// Read `REFRESH_TOKEN_BINDING` (`off`, the default, `audit` or `enforce`)
+/// and `REFRESH_TOKEN_BINDING_FACTORS`, a list of `user_agent` and
+/// `ip_subnet` that defaults to both.
+fn parse_refresh_binding(var: impl Fn(&str) -> Option<String>) -> Result<RefreshBinding, Error> {
+    let mode = match var("REFRESH_TOKEN_BINDING")
+        .map(|v| v.trim().to_lowercase())
+        .as_deref()
+    {
+        None | Some("" | "off") => RefreshBindingMode::Off,
+        Some("audit") => RefreshBindingMode::Audit,
+        Some("enforce") => RefreshBindingMode::Enforce,
+        Some(other) => {
+            return Err(Error::Invalid(format!(
+                "REFRESH_TOKEN_BINDING: unknown mode {other}"
+            )));
+        }
+    };
+    let factors = var("REFRESH_TOKEN_BINDING_FACTORS")
+        .map(|v| parse_list(&v.to_lowercase()))
+        .filter(|factors| !factors.is_empty())
+        .unwrap_or_else(|| vec!["user_agent".into(), "ip_subnet".into()]);
+    let mut binding = RefreshBinding {
+        mode,
+        ..RefreshBinding::default()
+    };
+    for factor in factors {
+        match factor.as_str() {
+            "user_agent" => binding.user_agent = true,
+            "ip_subnet" => binding.ip_subnet = true,
+            other => {
+                return Err(Error::Invalid(format!(
+                    "REFRESH_TOKEN_BINDING_FACTORS: unknown factor {other}"
+                )));
+            }
+        }
+    }
+    Ok(binding)
+}
+
+/// Read `ARGON2_*` through `var`. Defaults are the argon2 crate's (19 MiB,
+/// two passes, one lane), as recommended by OWASP; each must be positive.
+fn parse_password_hashing(
+    var: impl Fn(&str) -> Option<String>,
+) -> Result<PasswordHashSettings, Error> {
+    let number = |name: &str, default: u32| {
+        let value = var(name)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .map_or(Ok(default), |v| {
+                v.parse::<u32>()
+                    .map_err(|err| Error::Invalid(format!("{name}: {err}")))
+            })?;
+        if value == 0 {
+            return Err(Error::Invalid(format!("{name} must be positive")));
+        }
+        Ok(value)
+    };
+    Ok(PasswordHashSettings {
+        memory_kib: number("ARGON2_MEMORY_KIB", 19 * 1024)?,
+        iterations: number("ARGON2_ITERATIONS", 2)?,
+        parallelism: number("ARGON2_PARALLELISM", 1)?,
+    })
+}
+
+/// Read `OTEL_*` through `var`. `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT` is used
+/// as given; `OTEL_EXPORTER_OTLP_ENDPOINT` is a collector base URL that gets
+/// `/v1/traces` appended, as in the OpenTelemetry SDKs.
+fn parse_otel(var: impl Fn(&str) -> Option<String>) -> Result<Option<OtelSettings>, Error> {
+    let optional = |name: &str| {
+        var(name)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let Some(traces_url) = optional("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT").or_else(|| {
+        optional("OTEL_EXPORTER_OTLP_ENDPOINT")
+            .map(|base| format!("{}/v1/traces", base.trim_end_matches('/')))
+    }) else {
+        return Ok(None);
+    };
+    if !(traces_url.starts_with("http://") || traces_url.starts_with("https://")) {
+        return Err(Error::Invalid(format!(
+            "OTLP endpoint must be an http(s) URL, got '{traces_url}'"
+        )));
+    }
+    let headers = optional("OTEL_EXPORTER_OTLP_HEADERS")
+        .map(|v| parse_list(&v))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|entry| {
+            entry
+                .split_once('=')
+                .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+                .filter(|(name, _)| !name.is_empty())
+                .ok_or_else(|| {
+                    Error::Invalid(format!(
+                        "OTEL_EXPORTER_OTLP_HEADERS entry '{entry}' is not <name>=<value>"
+                    ))
+                })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Some(OtelSettings {
+        traces_url,
+        service_name: optional("OTEL_SERVICE_NAME").unwrap_or_else(|| "mokkan-core".into()),
+        headers,
+        filter: optional("OTEL_TRACES_FILTER").unwrap_or_else(|| "info,mokkan_core=debug".into()),
+    }))
 }
 
 fn validate_biscuit_private_key(value: &str) -> Result<(), Error> {
@@ -49,6 +739,28 @@ fn validate_biscuit_private_key(value: &str) -> Result<(), Error> {
     Ok(())
 }
 
+/// Parse a Biscuit root key file: one `<id>:<hex private key>` per line,
+/// ignoring blank lines and `#` comments.
+///
+/// # Errors
+///
+/// Returns an error naming the first malformed line.
+pub fn parse_root_keys(text: &str) -> Result<Vec<(u32, String)>, Error> {
+    text.lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(index, line)| {
+            let invalid = || Error::Invalid(format!("root key file line {}", index + 1));
+            let (id, key) = line.split_once(':').ok_or_else(invalid)?;
+            let id = id.trim().parse::<u32>().map_err(|_| invalid())?;
+            let key = key.trim();
+            validate_biscuit_private_key(key).map_err(|_| invalid())?;
+            Ok((id, key.to_string()))
+        })
+        .collect()
+}
+
 impl Settings {
     /// Build configuration from environment variables. Uses sensible defaults
     /// for optional values and validates required keys.
@@ -69,18 +781,20 @@ impl Settings {
         validate_biscuit_private_key(&biscuit_private_key)?;
         let refresh_token_secret =
             env::var("REFRESH_TOKEN_SECRET").unwrap_or_else(|_| biscuit_private_key.clone());
+        let download_link_secret = env::var("DOWNLOAD_LINK_SECRET")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| refresh_token_secret.clone());
+        let download_link_ttl_secs = env::var("DOWNLOAD_LINK_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(15 * 60);
 
         let token_ttl_secs = env::var("TOKEN_TTL_SECONDS")
             .ok()
             .and_then(|v| v.parse::<u64>().ok())
             .unwrap_or_else(default_token_ttl);
 
-        let allowed_origins = env::var("ALLOWED_ORIGINS")
-            .ok()
-            .map_or_else(default_allowed_origins, |s| {
-                s.split(',').map(|p| p.trim().to_string()).collect()
-            });
-
         let redis_used_nonce_ttl_secs = env::var("REDIS_USED_NONCE_TTL_SECS")
             .ok()
             .and_then(|v| v.parse::<usize>().ok())
@@ -90,18 +804,392 @@ impl Settings {
             .ok()
             .is_some_and(|v| v == "1" || v.to_lowercase() == "true");
 
+        let security_webhook_url = optional_var("SECURITY_WEBHOOK_URL");
+
+        let command_journal_path = optional_var("COMMAND_JOURNAL_PATH");
+
+        let asn_database_paths = env::var("ASN_DATABASE_PATHS")
+            .ok()
+            .map(|v| parse_list(&v))
+            .unwrap_or_default();
+
+        let audit_buffer = Self::audit_buffer_from_env()?;
+        let tenant_schemas = Self::tenant_schemas_from_env();
+        let row_level_security = Self::row_level_security_from_env();
+
+        let sessions = parse_sessions(|name| env::var(name).ok(), token_ttl_secs)?;
+
+        let job_schedules = Self::job_schedules_from_env();
+        let job_jitter = env::var("JOB_JITTER_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or_else(default_job_jitter);
+
+        let biscuit_root_keys_file = optional_var("BISCUIT_ROOT_KEYS_FILE");
+        let token_backend = Self::token_backend_from_env()?;
+        let ldap = Self::ldap_from_env()?;
+        let oidc_login_providers = Self::oidc_login_providers_from_env()?;
+        let syndication = Self::syndication_from_env()?;
+        let pagination = parse_pagination(|name| env::var(name).ok())?;
+
         Ok(Self {
             database_url,
             listen_addr,
             biscuit_private_key,
+            biscuit_root_keys_file,
+            token_backend,
             refresh_token_secret,
+            download_link_secret,
+            download_link_ttl: Duration::from_secs(download_link_ttl_secs),
             token_ttl: Duration::from_secs(token_ttl_secs),
-            allowed_origins,
+            cors: Self::cors_from_env()?,
             redis_used_nonce_ttl_secs,
             redis_preload_cas_script,
+            security_webhook_url,
+            command_journal_path,
+            asn_database_paths,
+            audit_buffer,
+            tenant_schemas,
+            row_level_security,
+            sessions,
+            password_hashing: parse_password_hashing(|name| env::var(name).ok())?,
+            password_history_depth: env::var("PASSWORD_HISTORY_DEPTH")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(5),
+            pwned_passwords_url: optional_var("PWNED_PASSWORDS_URL"),
+            account_deletion_grace: Duration::from_secs(
+                env::var("ACCOUNT_DELETION_GRACE_DAYS")
+                    .ok()
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or(30)
+                    .saturating_mul(60 * 60 * 24),
+            ),
+            job_schedules,
+            job_jitter: Duration::from_secs(job_jitter),
+            ldap,
+            oidc_login_providers,
+            syndication,
+            webhooks: Self::webhooks_from_env()?,
+            pagination,
+            media: Self::media_from_env()?,
+            search_language: Self::search_language_from_env()?,
+            static_export_dir: optional_var("STATIC_EXPORT_DIR")
+                .unwrap_or_else(|| "static-site".to_string()),
+            otel: Self::otel_from_env()?,
+        })
+    }
+
+    /// Read `MEDIA_*`. `MEDIA_STORAGE` is `local` (the default, files under
+    /// `MEDIA_LOCAL_DIR`) or `s3`, which needs `MEDIA_S3_ENDPOINT`,
+    /// `MEDIA_S3_BUCKET` and credentials.
+    fn media_from_env() -> Result<MediaSettings, Error> {
+        let optional = |name: &str| {
+            env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let var = |name: &str| {
+            optional(name)
+                .ok_or_else(|| Error::Invalid(format!("missing environment variable: {name}")))
+        };
+        let number = |name: &str, default: u64| {
+            optional(name).map_or(Ok(default), |v| {
+                v.parse::<u64>()
+                    .map_err(|err| Error::Invalid(format!("{name}: {err}")))
+            })
+        };
+        let storage = match optional("MEDIA_STORAGE")
+            .map_or_else(|| "local".to_string(), |v| v.to_lowercase())
+            .as_str()
+        {
+            "local" => MediaStorageSettings::Local {
+                dir: optional("MEDIA_LOCAL_DIR").unwrap_or_else(|| "media".into()),
+            },
+            "s3" => MediaStorageSettings::S3 {
+                endpoint: var("MEDIA_S3_ENDPOINT")?,
+                bucket: var("MEDIA_S3_BUCKET")?,
+                region: optional("MEDIA_S3_REGION").unwrap_or_else(|| "us-east-1".into()),
+                access_key_id: var("MEDIA_S3_ACCESS_KEY_ID")?,
+                secret_access_key: var("MEDIA_S3_SECRET_ACCESS_KEY")?,
+                url_ttl: Duration::from_secs(number("MEDIA_S3_URL_TTL_SECS", 300)?),
+                public_url: optional("MEDIA_S3_PUBLIC_URL"),
+            },
+            other => {
+                return Err(Error::Invalid(format!(
+                    "MEDIA_STORAGE: unknown media storage '{other}'"
+                )));
+            }
+        };
+        let allowed_types = optional("MEDIA_ALLOWED_TYPES").map_or_else(
+            || {
+                ["image/png", "image/jpeg", "image/gif", "image/webp"]
+                    .map(String::from)
+                    .to_vec()
+            },
+            |v| parse_list(&v.to_lowercase()),
+        );
+        Ok(MediaSettings {
+            max_bytes: usize::try_from(number("MEDIA_MAX_BYTES", 10 * 1024 * 1024)?)
+                .unwrap_or(usize::MAX),
+            allowed_types,
+            storage,
         })
     }
 
+    /// Read `SEARCH_LANGUAGE`, the text search configuration for articles.
+    fn search_language_from_env() -> Result<String, Error> {
+        parse_search_language(env::var("SEARCH_LANGUAGE").ok().as_deref())
+    }
+
+    /// Read `JOB_SCHEDULE_<JOB>` overrides, keyed by lowercase job name.
+    fn job_schedules_from_env() -> HashMap<String, String> {
+        env::vars()
+            .filter_map(|(key, value)| {
+                let job = key.strip_prefix("JOB_SCHEDULE_")?.to_lowercase();
+                Some((job, value.trim().to_string()))
+            })
+            .collect()
+    }
+
+    fn oidc_login_providers_from_env() -> Result<Vec<OidcLoginProvider>, Error> {
+        let names = env::var("OIDC_LOGIN_PROVIDERS")
+            .ok()
+            .map(|s| parse_list(&s.to_lowercase()))
+            .unwrap_or_default();
+
+        names
+            .into_iter()
+            .map(|name| {
+                let prefix = format!("OIDC_LOGIN_{}_", name.to_uppercase().replace('-', "_"));
+                let var = |key: &str| {
+                    let key = format!("{prefix}{key}");
+                    env::var(&key)
+                        .ok()
+                        .filter(|v| !v.trim().is_empty())
+                        .ok_or_else(|| {
+                            Error::Invalid(format!("missing environment variable: {key}"))
+                        })
+                };
+                let default_role = match var("DEFAULT_ROLE") {
+                    Ok(role) => role
+                        .trim()
+                        .parse()
+                        .map_err(|err| Error::Invalid(format!("{prefix}DEFAULT_ROLE: {err}")))?,
+                    Err(_) => Role::Author,
+                };
+                Ok(OidcLoginProvider {
+                    issuer: var("ISSUER")?,
+                    client_id: var("CLIENT_ID")?,
+                    client_secret: var("CLIENT_SECRET")?,
+                    redirect_uri: var("REDIRECT_URI")?,
+                    default_role,
+                    name,
+                })
+            })
+            .collect()
+    }
+
+    /// Read `SYNDICATION_*`. Each name in `SYNDICATION_TARGETS` needs
+    /// `SYNDICATION_<NAME>_KIND` (`mastodon`, `bluesky` or `slack`; defaults
+    /// to the name) and the credentials that kind posts with.
+    fn syndication_from_env() -> Result<SyndicationSettings, Error> {
+        let names = env::var("SYNDICATION_TARGETS")
+            .ok()
+            .map(|s| parse_list(&s.to_lowercase()))
+            .unwrap_or_default();
+        let number = |name: &str, default: u64| {
+            env::var(name).ok().map_or(Ok(default), |v| {
+                v.trim()
+                    .parse::<u64>()
+                    .map_err(|err| Error::Invalid(format!("{name}: {err}")))
+            })
+        };
+        let article_url = env::var("SYNDICATION_ARTICLE_URL")
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+        if !names.is_empty() && article_url.is_none() {
+            return Err(Error::Invalid(
+                "SYNDICATION_ARTICLE_URL is required when SYNDICATION_TARGETS is set".into(),
+            ));
+        }
+        let max_attempts = number("SYNDICATION_MAX_ATTEMPTS", 5)?;
+
+        Ok(SyndicationSettings {
+            targets: names
+                .into_iter()
+                .map(Self::syndication_target_from_env)
+                .collect::<Result<_, _>>()?,
+            article_url: article_url.unwrap_or_default(),
+            delay: Duration::from_secs(number("SYNDICATION_DELAY_SECS", 60)?),
+            max_attempts: u32::try_from(max_attempts.max(1)).unwrap_or(u32::MAX),
+            retry_backoff: Duration::from_secs(number("SYNDICATION_RETRY_SECS", 30)?),
+        })
+    }
+
+    /// Read `WEBHOOK_MAX_ATTEMPTS`, `WEBHOOK_RETRY_SECS` and
+    /// `WEBHOOK_TIMEOUT_SECS`.
+    fn webhooks_from_env() -> Result<WebhookSettings, Error> {
+        let defaults = WebhookSettings::default();
+        let number = |name: &str, default: u64| {
+            env::var(name).ok().map_or(Ok(default), |v| {
+                v.trim()
+                    .parse::<u64>()
+                    .map_err(|err| Error::Invalid(format!("{name}: {err}")))
+            })
+        };
+        let max_attempts = number("WEBHOOK_MAX_ATTEMPTS", u64::from(defaults.max_attempts))?;
+        let timeout = number("WEBHOOK_TIMEOUT_SECS", defaults.timeout.as_secs())?;
+        Ok(WebhookSettings {
+            max_attempts: u32::try_from(max_attempts.max(1)).unwrap_or(u32::MAX),
+            retry_backoff: Duration::from_secs(number(
+                "WEBHOOK_RETRY_SECS",
+                defaults.retry_backoff.as_secs(),
+            )?),
+            timeout: Duration::from_secs(timeout.max(1)),
+        })
+    }
+
+    fn syndication_target_from_env(name: String) -> Result<SyndicationTargetSettings, Error> {
+        let prefix = format!("SYNDICATION_{}_", name.to_uppercase().replace('-', "_"));
+        let optional = |key: &str| {
+            env::var(format!("{prefix}{key}"))
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let var = |key: &str| {
+            optional(key).ok_or_else(|| {
+                Error::Invalid(format!("missing environment variable: {prefix}{key}"))
+            })
+        };
+        let kind = optional("KIND").map_or_else(|| name.clone(), |kind| kind.to_lowercase());
+        let platform = match kind.as_str() {
+            "mastodon" => SyndicationPlatform::Mastodon {
+                instance_url: var("URL")?,
+                access_token: var("TOKEN")?,
+            },
+            "bluesky" => SyndicationPlatform::Bluesky {
+                service_url: optional("URL").unwrap_or_else(|| "https://bsky.social".into()),
+                identifier: var("IDENTIFIER")?,
+                app_password: var("PASSWORD")?,
+            },
+            "slack" => SyndicationPlatform::Slack {
+                webhook_url: var("URL")?,
+            },
+            other => {
+                return Err(Error::Invalid(format!(
+                    "{prefix}KIND: unknown syndication platform '{other}'"
+                )));
+            }
+        };
+        Ok(SyndicationTargetSettings {
+            template: optional("TEMPLATE").unwrap_or_else(|| "{title} {url}".into()),
+            platform,
+            name,
+        })
+    }
+
+    /// Read `AUDIT_BUFFER_*`. Buffering is on by default;
+    /// `AUDIT_BUFFER_CAPACITY=0` writes audit entries inline instead.
+    fn audit_buffer_from_env() -> Result<Option<AuditBufferSettings>, Error> {
+        let number = |name: &str, default: u64| {
+            env::var(name).ok().map_or(Ok(default), |v| {
+                v.trim()
+                    .parse::<u64>()
+                    .map_err(|err| Error::Invalid(format!("{name}: {err}")))
+            })
+        };
+        let capacity = number("AUDIT_BUFFER_CAPACITY", 1024)?;
+        if capacity == 0 {
+            return Ok(None);
+        }
+        let batch_size = number("AUDIT_BUFFER_BATCH_SIZE", 100)?;
+        let flush_ms = number("AUDIT_BUFFER_FLUSH_MS", 200)?;
+        let drop_when_full = match env::var("AUDIT_BUFFER_OVERFLOW")
+            .ok()
+            .map(|v| v.trim().to_lowercase())
+            .as_deref()
+        {
+            None | Some("" | "block") => false,
+            Some("drop") => true,
+            Some(other) => {
+                return Err(Error::Invalid(format!(
+                    "AUDIT_BUFFER_OVERFLOW must be 'block' or 'drop', got '{other}'"
+                )));
+            }
+        };
+        let size = |value: u64| usize::try_from(value).unwrap_or(usize::MAX);
+        Ok(Some(AuditBufferSettings {
+            capacity: size(capacity),
+            batch_size: size(batch_size.max(1)),
+            flush_interval: Duration::from_millis(flush_ms),
+            drop_when_full,
+        }))
+    }
+
+    fn token_backend_from_env() -> Result<TokenBackend, Error> {
+        let backend = env::var("TOKEN_BACKEND").unwrap_or_default();
+        match backend.trim().to_lowercase().as_str() {
+            "" | "biscuit" => return Ok(TokenBackend::Biscuit),
+            "jwt" => {}
+            other => {
+                return Err(Error::Invalid(format!(
+                    "TOKEN_BACKEND must be 'biscuit' or 'jwt', got '{other}'"
+                )));
+            }
+        }
+        let optional = |name| {
+            env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Ok(TokenBackend::Jwt(JwtSettings {
+            algorithm: optional("JWT_ALGORITHM").unwrap_or_else(|| "ES256".to_string()),
+            private_key_path: optional("JWT_PRIVATE_KEY_PATH")
+                .ok_or(Error::Missing("JWT_PRIVATE_KEY_PATH"))?,
+            key_id: optional("JWT_KEY_ID"),
+            issuer: optional("JWT_ISSUER").unwrap_or_else(Self::oidc_issuer_from_env),
+            audience: optional("JWT_AUDIENCE"),
+        }))
+    }
+
+    fn ldap_from_env() -> Result<Option<LdapSettings>, Error> {
+        let Some(url) = env::var("LDAP_URL")
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+        else {
+            return Ok(None);
+        };
+        let user_dn_template = env::var("LDAP_USER_DN_TEMPLATE")
+            .map_err(|_| Error::Missing("LDAP_USER_DN_TEMPLATE"))?;
+        let group_attribute =
+            env::var("LDAP_GROUP_ATTRIBUTE").unwrap_or_else(|_| "memberOf".to_string());
+        let group_roles = env::var("LDAP_GROUP_ROLES")
+            .ok()
+            .map(|v| parse_group_roles(&v))
+            .transpose()?
+            .unwrap_or_default();
+        let default_role = env::var("LDAP_DEFAULT_ROLE")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| v.trim().parse())
+            .transpose()
+            .map_err(|err| Error::Invalid(format!("LDAP_DEFAULT_ROLE: {err}")))?;
+
+        Ok(Some(LdapSettings {
+            url,
+            user_dn_template,
+            group_attribute,
+            group_roles,
+            default_role,
+        }))
+    }
+
     #[must_use]
     pub fn database_url(&self) -> &str {
         &self.database_url
@@ -117,11 +1205,30 @@ impl Settings {
         &self.biscuit_private_key
     }
 
+    /// File of rotating Biscuit root keys (see [`parse_root_keys`]), used
+    /// instead of `BISCUIT_ROOT_PRIVATE_KEY` when set.
+    #[must_use]
+    pub fn biscuit_root_keys_file(&self) -> Option<&str> {
+        self.biscuit_root_keys_file.as_deref()
+    }
+
+    /// Which token format access tokens are issued in.
+    #[must_use]
+    pub const fn token_backend(&self) -> &TokenBackend {
+        &self.token_backend
+    }
+
     #[must_use]
     pub fn refresh_token_secret(&self) -> &str {
         &self.refresh_token_secret
     }
 
+    /// Secret and lifetime of signed export download links.
+    #[must_use]
+    pub fn download_links(&self) -> (&str, Duration) {
+        (&self.download_link_secret, self.download_link_ttl)
+    }
+
     #[must_use]
     pub const fn token_ttl(&self) -> Duration {
         self.token_ttl
@@ -130,7 +1237,12 @@ impl Settings {
     /// Return the allowed `CORS` origins as configured on `Settings`.
     #[must_use]
     pub fn allowed_origins(&self) -> &[String] {
-        &self.allowed_origins
+        &self.cors.allowed_origins
+    }
+
+    #[must_use]
+    pub const fn cors(&self) -> &CorsSettings {
+        &self.cors
     }
 
     /// Backwards-compatible helper used by router construction in a few places
@@ -139,9 +1251,20 @@ impl Settings {
     pub fn allowed_origins_from_env() -> Vec<String> {
         env::var("ALLOWED_ORIGINS")
             .ok()
-            .map_or_else(default_allowed_origins, |s| {
-                s.split(',').map(|p| p.trim().to_string()).collect()
-            })
+            .map(|s| parse_list(&s))
+            .filter(|origins| !origins.is_empty())
+            .unwrap_or_else(|| CorsSettings::default().allowed_origins)
+    }
+
+    /// Read `ALLOWED_ORIGINS` and the `CORS_*` variables without building a
+    /// full `Settings`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when a method or header name is malformed, the max
+    /// age is not a number, or credentials are allowed for any origin.
+    pub fn cors_from_env() -> Result<CorsSettings, Error> {
+        parse_cors(|name| env::var(name).ok())
     }
 
     /// TTL for used refresh nonces (seconds)
@@ -156,6 +1279,381 @@ impl Settings {
         self.redis_preload_cas_script
     }
 
+    /// `http://` endpoint that receives security incidents, if configured.
+    #[must_use]
+    pub fn security_webhook_url(&self) -> Option<&str> {
+        self.security_webhook_url.as_deref()
+    }
+
+    /// File the command journal is appended to, if configured.
+    #[must_use]
+    pub fn command_journal_path(&self) -> Option<&str> {
+        self.command_journal_path.as_deref()
+    }
+
+    /// ASN CSV exports (`ASN_DATABASE_PATHS`, comma separated) that access
+    /// rules resolve client ASNs from. Empty disables ASN rules.
+    #[must_use]
+    pub fn asn_database_paths(&self) -> &[String] {
+        &self.asn_database_paths
+    }
+
+    /// How audit writes are queued; `None` writes them inline.
+    #[must_use]
+    pub const fn audit_buffer(&self) -> Option<AuditBufferSettings> {
+        self.audit_buffer
+    }
+
+    /// Tenant ids configured for schema-per-tenant routing. Empty when
+    /// tenancy is disabled.
+    #[must_use]
+    pub fn tenant_schemas(&self) -> &[String] {
+        &self.tenant_schemas
+    }
+
+    /// Read `TENANT_SCHEMAS` (comma separated tenant ids) without building a
+    /// full `Settings`, mirroring `allowed_origins_from_env`.
+    #[must_use]
+    pub fn tenant_schemas_from_env() -> Vec<String> {
+        env::var("TENANT_SCHEMAS")
+            .ok()
+            .map(|s| parse_list(&s))
+            .unwrap_or_default()
+    }
+
+    /// Read `CSRF_EXEMPT_PATHS` (comma separated path prefixes that skip the
+    /// CSRF checks for cookie-authenticated requests).
+    #[must_use]
+    pub fn csrf_exempt_paths_from_env() -> Vec<String> {
+        env::var("CSRF_EXEMPT_PATHS")
+            .ok()
+            .map(|s| parse_list(&s))
+            .unwrap_or_default()
+    }
+
+    /// Read `TRUSTED_PROXIES` (comma separated addresses or CIDR ranges of the
+    /// proxies whose `X-Forwarded-For`/`X-Real-IP` are believed). Entries
+    /// that do not parse are skipped with a warning.
+    #[must_use]
+    pub fn trusted_proxies_from_env() -> Vec<IpRange> {
+        env::var("TRUSTED_PROXIES")
+            .ok()
+            .map(|s| parse_trusted_proxies(&s))
+            .unwrap_or_default()
+    }
+
+    /// Read the `HONEYPOT_*` variables without building a full `Settings`.
+    /// `None` unless `HONEYPOT_PATHS` lists at least one path.
+    #[must_use]
+    pub fn honeypot_from_env() -> Option<HoneypotSettings> {
+        let paths = env::var("HONEYPOT_PATHS")
+            .ok()
+            .map(|s| parse_list(&s))
+            .unwrap_or_default();
+        if paths.is_empty() {
+            return None;
+        }
+        let secs = |name, default| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .unwrap_or(default)
+        };
+        Some(HoneypotSettings {
+            paths,
+            block_for: Duration::from_secs(secs("HONEYPOT_BLOCK_SECS", 3600)),
+            tarpit: Duration::from_secs(secs("HONEYPOT_TARPIT_SECS", 0)),
+        })
+    }
+
+    /// Read the `LOGIN_*` variables without building a full `Settings`.
+    /// Defaults: five failures per username or twenty per client address
+    /// within 15 minutes lock it out for 15 minutes.
+    #[must_use]
+    pub fn login_throttle_from_env() -> LoginThrottleSettings {
+        let number = |name, default| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u32>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(default)
+        };
+        LoginThrottleSettings {
+            max_failures_per_user: number("LOGIN_MAX_FAILURES", 5),
+            max_failures_per_client: number("LOGIN_MAX_FAILURES_PER_IP", 20),
+            window: Duration::from_secs(number("LOGIN_FAILURE_WINDOW_SECS", 900).into()),
+            lockout: Duration::from_secs(number("LOGIN_LOCKOUT_SECS", 900).into()),
+        }
+    }
+
+    /// Read `MAX_REQUEST_BODY_BYTES`: the largest body most endpoints accept.
+    /// `None` when unset or not a positive number, keeping the built-in
+    /// limit.
+    #[must_use]
+    pub fn max_request_body_bytes_from_env() -> Option<usize> {
+        env::var("MAX_REQUEST_BODY_BYTES")
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .filter(|v| *v > 0)
+    }
+
+    /// Read `HSTS_MAX_AGE_SECS`: how long browsers should insist on HTTPS.
+    /// Defaults to a year; `0` sends no `Strict-Transport-Security`.
+    #[must_use]
+    pub fn hsts_max_age_from_env() -> u64 {
+        env::var("HSTS_MAX_AGE_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(365 * 24 * 60 * 60)
+    }
+
+    /// Read the `RATE_LIMIT_*_PER_MINUTE` variables without building a full
+    /// `Settings`. Defaults: 60 auth requests, 60 article writes and 120
+    /// admin requests per minute.
+    #[must_use]
+    pub fn route_rate_limits_from_env() -> RouteRateLimitSettings {
+        let number = |name, default| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u32>().ok())
+                .unwrap_or(default)
+        };
+        RouteRateLimitSettings {
+            auth: number("RATE_LIMIT_AUTH_PER_MINUTE", 60),
+            article_writes: number("RATE_LIMIT_ARTICLE_WRITES_PER_MINUTE", 60),
+            admin: number("RATE_LIMIT_ADMIN_PER_MINUTE", 120),
+        }
+    }
+
+    /// Read the `SLUG_CACHE_*` variables without building a full `Settings`.
+    /// Defaults: up to 1024 slugs, articles kept 60 seconds and misses 10.
+    #[must_use]
+    pub fn slug_cache_from_env() -> SlugCacheSettings {
+        let number = |name, default| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .unwrap_or(default)
+        };
+        SlugCacheSettings {
+            capacity: usize::try_from(number("SLUG_CACHE_CAPACITY", 1024)).unwrap_or(usize::MAX),
+            ttl: Duration::from_secs(number("SLUG_CACHE_TTL_SECS", 60)),
+            negative_ttl: Duration::from_secs(number("SLUG_CACHE_NEGATIVE_TTL_SECS", 10)),
+        }
+    }
+
+    /// Read `REDACT_FIELDS`: field names masked in audit details and logs in
+    /// addition to the built-in list.
+    #[must_use]
+    pub fn redact_fields_from_env() -> Vec<String> {
+        env::var("REDACT_FIELDS")
+            .ok()
+            .map(|s| parse_list(&s))
+            .unwrap_or_default()
+    }
+
+    /// Whether requests carry the acting user into row-level security
+    /// policies.
+    #[must_use]
+    pub const fn row_level_security(&self) -> bool {
+        self.row_level_security
+    }
+
+    /// Sessions created longer ago than this are purged by the cleanup job.
+    #[must_use]
+    pub const fn session_max_lifetime(&self) -> Duration {
+        self.sessions.max_lifetime
+    }
+
+    /// How long sessions may be kept alive by refreshing them.
+    #[must_use]
+    pub const fn sessions(&self) -> SessionSettings {
+        self.sessions
+    }
+
+    /// Cost of new password hashes.
+    #[must_use]
+    pub const fn password_hashing(&self) -> PasswordHashSettings {
+        self.password_hashing
+    }
+
+    /// How many of a user's passwords, the current one included, a new
+    /// password may not repeat; zero allows reuse.
+    #[must_use]
+    pub const fn password_history_depth(&self) -> usize {
+        self.password_history_depth
+    }
+
+    /// Time between a user deleting their account and its anonymization,
+    /// during which an admin can cancel the deletion.
+    #[must_use]
+    pub const fn account_deletion_grace(&self) -> Duration {
+        self.account_deletion_grace
+    }
+
+    /// Base URL of the Pwned Passwords range API new passwords are checked
+    /// against; `None` disables the check.
+    #[must_use]
+    pub fn pwned_passwords_url(&self) -> Option<&str> {
+        self.pwned_passwords_url.as_deref()
+    }
+
+    /// Cron expression for the scheduled job `job`: `JOB_SCHEDULE_<JOB>` if
+    /// set, else `default`. `None` when set to `off`.
+    #[must_use]
+    pub fn job_schedule<'a>(&'a self, job: &str, default: &'a str) -> Option<&'a str> {
+        match self.job_schedules.get(job).map(String::as_str) {
+            Some("off") => None,
+            Some(schedule) => Some(schedule),
+            None => Some(default),
+        }
+    }
+
+    /// Most a scheduled run is delayed past its cron time (`JOB_JITTER_SECS`).
+    #[must_use]
+    pub const fn job_jitter(&self) -> Duration {
+        self.job_jitter
+    }
+
+    /// LDAP login settings, if `LDAP_URL` is configured.
+    #[must_use]
+    pub const fn ldap(&self) -> Option<&LdapSettings> {
+        self.ldap.as_ref()
+    }
+
+    /// Upstream OIDC providers configured via `OIDC_LOGIN_PROVIDERS`.
+    #[must_use]
+    pub fn oidc_login_providers(&self) -> &[OidcLoginProvider] {
+        &self.oidc_login_providers
+    }
+
+    /// Syndication targets and delivery options from `SYNDICATION_*`.
+    #[must_use]
+    pub const fn syndication(&self) -> &SyndicationSettings {
+        &self.syndication
+    }
+
+    /// Webhook delivery options from `WEBHOOK_*`.
+    #[must_use]
+    pub const fn webhooks(&self) -> &WebhookSettings {
+        &self.webhooks
+    }
+
+    /// Upload limits and storage from `MEDIA_*`.
+    #[must_use]
+    pub const fn media(&self) -> &MediaSettings {
+        &self.media
+    }
+
+    /// Text search configuration of article search (`SEARCH_LANGUAGE`).
+    #[must_use]
+    pub fn search_language(&self) -> &str {
+        &self.search_language
+    }
+
+    /// Directory static site exports are written to (`STATIC_EXPORT_DIR`,
+    /// default `static-site`).
+    #[must_use]
+    pub fn static_export_dir(&self) -> &str {
+        &self.static_export_dir
+    }
+
+    /// Span export settings, `None` when no OTLP endpoint is configured.
+    #[must_use]
+    pub const fn otel(&self) -> Option<&OtelSettings> {
+        self.otel.as_ref()
+    }
+
+    /// Read the `OTEL_*` variables without building a full `Settings`;
+    /// tracing starts before the rest of the configuration is read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the endpoint is not an http(s) URL or a header
+    /// entry is malformed.
+    pub fn otel_from_env() -> Result<Option<OtelSettings>, Error> {
+        parse_otel(|name| env::var(name).ok())
+    }
+
+    /// Page sizes of listing endpoints from `PAGE_LIMIT_*`.
+    #[must_use]
+    pub const fn pagination(&self) -> PaginationSettings {
+        self.pagination
+    }
+
+    /// Read `ROW_LEVEL_SECURITY` without building a full `Settings`.
+    #[must_use]
+    pub fn row_level_security_from_env() -> bool {
+        env::var("ROW_LEVEL_SECURITY")
+            .ok()
+            .is_some_and(|v| v == "1" || v.to_lowercase() == "true")
+    }
+
+    /// Read `OPEN_TOKEN_INTROSPECTION`: let anyone call the token
+    /// introspection and revocation endpoints, as before they required a
+    /// `tokens:introspect` bearer token.
+    #[must_use]
+    pub fn open_token_introspection_from_env() -> bool {
+        env::var("OPEN_TOKEN_INTROSPECTION")
+            .ok()
+            .is_some_and(|v| v == "1" || v.to_lowercase() == "true")
+    }
+
+    /// Read `SIMULATED_TIME`: run on a clock admins can advance through
+    /// `POST /api/v1/admin/time/advance`. For test and staging environments
+    /// only.
+    #[must_use]
+    pub fn simulated_time_from_env() -> bool {
+        env::var("SIMULATED_TIME")
+            .ok()
+            .is_some_and(|v| v == "1" || v.to_lowercase() == "true")
+    }
+
+    /// Read `SEED_FILE`: the YAML seed applied at startup. Defaults to
+    /// `seed.yaml` in the working directory when that file exists.
+    #[must_use]
+    pub fn seed_file_from_env() -> Option<String> {
+        env::var("SEED_FILE")
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .or_else(|| {
+                std::path::Path::new("seed.yaml")
+                    .is_file()
+                    .then(|| "seed.yaml".to_string())
+            })
+    }
+
+    /// Read `READ_ONLY`: start in read-only mode, refusing writes until an
+    /// admin turns it off through `PUT /api/v1/admin/read-only`. For serving
+    /// from a standby database during failover.
+    #[must_use]
+    pub fn read_only_from_env() -> bool {
+        env::var("READ_ONLY")
+            .ok()
+            .is_some_and(|v| v == "1" || v.to_lowercase() == "true")
+    }
+
+    /// Read `EPHEMERAL_MODE`: keep all data in process memory instead of
+    /// Postgres, seed demo content and mark every response, for throwaway
+    /// preview deployments.
+    #[must_use]
+    pub fn ephemeral_mode_from_env() -> bool {
+        env::var("EPHEMERAL_MODE")
+            .ok()
+            .is_some_and(|v| v == "1" || v.to_lowercase() == "true")
+    }
+
+    /// Read `DATABASE_BACKEND`. Ignored in ephemeral mode, which keeps
+    /// everything in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the value names an unknown backend.
+    pub fn database_backend_from_env() -> Result<DatabaseBackend, Error> {
+        parse_database_backend(env::var("DATABASE_BACKEND").ok().as_deref())
+    }
+
     /// Determine the issuer URL for OIDC discovery. Prefer explicit env var
     /// `OIDC_ISSUER` if present; otherwise derive a sensible default using
     /// the configured listen address.
@@ -167,7 +1665,15 @@ impl Settings {
 
 #[cfg(test)]
 mod tests {
-    use super::validate_biscuit_private_key;
+    use super::{
+        CorsSettings, DatabaseBackend, PageLimitSettings, RefreshBinding, RefreshBindingMode,
+        parse_cors, parse_database_backend, parse_group_roles, parse_list, parse_otel,
+        parse_pagination, parse_password_hashing, parse_refresh_binding, parse_root_keys,
+        parse_search_language, parse_sessions, parse_trusted_proxies, validate_biscuit_private_key,
+    };
+    use crate::domain::Role;
+    use std::collections::HashMap;
+    use std::time::Duration;
 
     #[test]
     fn biscuit_private_key_rejects_non_hex_input() {
@@ -180,4 +1686,285 @@ mod tests {
         let key = "a".repeat(64);
         assert!(validate_biscuit_private_key(&key).is_ok());
     }
+
+    #[test]
+    fn root_key_files_list_ids_and_keys() {
+        let text = format!(
+            "# rotated 2026-10\n1:{}\n\n 2 : {} \n",
+            "a".repeat(64),
+            "b".repeat(64)
+        );
+        let keys = parse_root_keys(&text).unwrap();
+        assert_eq!(keys, vec![(1, "a".repeat(64)), (2, "b".repeat(64))]);
+        assert!(parse_root_keys("x:abc").is_err());
+        assert!(parse_root_keys(&"c".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn tenant_schemas_skip_blank_entries() {
+        assert_eq!(
+            parse_list(" acme, ,globex ,"),
+            vec!["acme".to_string(), "globex".to_string()]
+        );
+    }
+
+    #[test]
+    fn trusted_proxies_skip_invalid_entries() {
+        let proxies = parse_trusted_proxies("10.0.0.0/8, nonsense, ::1");
+        assert_eq!(proxies.len(), 2);
+        assert!(proxies[0].contains("10.1.2.3".parse().unwrap()));
+        assert!(proxies[1].contains("::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn database_backend_defaults_to_postgres() {
+        assert_eq!(
+            parse_database_backend(None).unwrap(),
+            DatabaseBackend::Postgres
+        );
+        assert_eq!(
+            parse_database_backend(Some("PostgreSQL")).unwrap(),
+            DatabaseBackend::Postgres
+        );
+        assert_eq!(
+            parse_database_backend(Some(" sqlite ")).unwrap(),
+            DatabaseBackend::Sqlite
+        );
+        assert!(parse_database_backend(Some("mysql")).is_err());
+    }
+
+    #[test]
+    fn search_language_defaults_to_simple_and_rejects_sql() {
+        assert_eq!(parse_search_language(None).unwrap(), "simple");
+        assert_eq!(parse_search_language(Some(" ")).unwrap(), "simple");
+        assert_eq!(parse_search_language(Some(" english ")).unwrap(), "english");
+        assert_eq!(
+            parse_search_language(Some("pg_catalog.german")).unwrap(),
+            "pg_catalog.german"
+        );
+        assert!(parse_search_language(Some("english'; DROP TABLE articles")).is_err());
+        assert!(parse_search_language(Some("English")).is_err());
+    }
+
+    #[test]
+    fn group_roles_split_on_the_last_equals_sign() {
+        assert_eq!(
+            parse_group_roles("cn=cms-admins,ou=groups,dc=example,dc=com=admin; cn=staff,dc=example,dc=com = author ;")
+                .unwrap(),
+            vec![
+                ("cn=cms-admins,ou=groups,dc=example,dc=com".to_string(), Role::Admin),
+                ("cn=staff,dc=example,dc=com".to_string(), Role::Author),
+            ]
+        );
+        assert!(parse_group_roles("cn=staff,dc=example,dc=com=owner").is_err());
+        assert!(parse_group_roles("admin").is_err());
+    }
+
+    #[test]
+    fn page_limits_inherit_shared_values_and_reject_bad_bounds() {
+        let vars = HashMap::from([
+            ("PAGE_LIMIT_MAX", "200"),
+            ("PAGE_LIMIT_ARTICLES_DEFAULT", "12"),
+            ("PAGE_LIMIT_JOBS_MAX", "50"),
+        ]);
+        let settings = parse_pagination(|name| vars.get(name).map(ToString::to_string)).unwrap();
+        assert_eq!(
+            settings.articles,
+            PageLimitSettings {
+                default: 12,
+                max: 200
+            }
+        );
+        assert_eq!(settings.users, settings.shared);
+        assert_eq!(settings.shared.default, 20);
+        assert_eq!(settings.jobs.max, 50);
+
+        for (name, value) in [
+            ("PAGE_LIMIT_DEFAULT", "0"),
+            ("PAGE_LIMIT_USERS_DEFAULT", "150"),
+            ("PAGE_LIMIT_MAX", "100000"),
+            ("PAGE_LIMIT_MODERATION_MAX", "many"),
+        ] {
+            let vars = HashMap::from([(name, value)]);
+            assert!(
+                parse_pagination(|name| vars.get(name).map(ToString::to_string)).is_err(),
+                "{name}={value}"
+            );
+        }
+    }
+
+    #[test]
+    fn session_max_age_defaults_to_the_lifetime_and_idle_timeout_is_optional() {
+        let parse = |pairs: &[(&str, &str)]| {
+            let vars: HashMap<_, _> = pairs.iter().copied().collect();
+            parse_sessions(|name| vars.get(name).map(ToString::to_string), 3600)
+        };
+        let defaults = parse(&[("SESSION_MAX_LIFETIME_SECS", "86400")]).unwrap();
+        assert_eq!(defaults.max_age, Duration::from_hours(24));
+        assert_eq!(defaults.idle_timeout, None);
+        assert_eq!(defaults.in_memory_revoked_ttl, Duration::from_hours(24));
+        assert_eq!(
+            defaults.in_memory_used_nonce_ttl,
+            Duration::from_hours(24 * 7)
+        );
+
+        let custom = parse(&[
+            ("SESSION_MAX_AGE", "43200"),
+            ("SESSION_IDLE_TIMEOUT", "7200"),
+        ])
+        .unwrap();
+        assert_eq!(custom.max_age, Duration::from_hours(12));
+        assert_eq!(custom.idle_timeout, Some(Duration::from_hours(2)));
+        assert_eq!(
+            parse(&[("SESSION_IDLE_TIMEOUT", "0")])
+                .unwrap()
+                .idle_timeout,
+            None
+        );
+
+        for pairs in [
+            &[("SESSION_MAX_AGE", "60")][..],
+            &[
+                ("SESSION_MAX_LIFETIME_SECS", "7200"),
+                ("SESSION_MAX_AGE", "86400"),
+            ],
+            &[("SESSION_IDLE_TIMEOUT", "600")],
+            &[("SESSION_IDLE_TIMEOUT", "soon")],
+            &[("IN_MEMORY_REVOKED_SESSION_TTL_SECS", "60")],
+        ] {
+            assert!(parse(pairs).is_err(), "{pairs:?}");
+        }
+    }
+
+    #[test]
+    fn refresh_binding_is_off_unless_configured_and_binds_both_factors() {
+        let parse = |pairs: &[(&str, &str)]| {
+            let vars: HashMap<_, _> = pairs.iter().copied().collect();
+            parse_refresh_binding(|name| vars.get(name).map(ToString::to_string))
+        };
+        assert_eq!(parse(&[]).unwrap().mode, RefreshBindingMode::Off);
+
+        let audit = parse(&[("REFRESH_TOKEN_BINDING", "Audit")]).unwrap();
+        assert_eq!(
+            audit,
+            RefreshBinding {
+                mode: RefreshBindingMode::Audit,
+                user_agent: true,
+                ip_subnet: true,
+            }
+        );
+        let enforce = parse(&[
+            ("REFRESH_TOKEN_BINDING", "enforce"),
+            ("REFRESH_TOKEN_BINDING_FACTORS", "ip_subnet"),
+        ])
+        .unwrap();
+        assert_eq!(enforce.mode, RefreshBindingMode::Enforce);
+        assert!(enforce.ip_subnet && !enforce.user_agent);
+
+        for pairs in [
+            &[("REFRESH_TOKEN_BINDING", "strict")][..],
+            &[("REFRESH_TOKEN_BINDING_FACTORS", "user_agent,cookie")],
+        ] {
+            assert!(parse(pairs).is_err(), "{pairs:?}");
+        }
+    }
+
+    #[test]
+    fn argon2_cost_defaults_and_overrides() {
+        let parse = |pairs: &[(&str, &str)]| {
+            let vars: HashMap<_, _> = pairs.iter().copied().collect();
+            parse_password_hashing(|name| vars.get(name).map(ToString::to_string))
+        };
+        let defaults = parse(&[]).unwrap();
+        assert_eq!(
+            (
+                defaults.memory_kib,
+                defaults.iterations,
+                defaults.parallelism
+            ),
+            (19 * 1024, 2, 1)
+        );
+        let custom = parse(&[("ARGON2_MEMORY_KIB", "65536"), ("ARGON2_ITERATIONS", "3")]).unwrap();
+        assert_eq!((custom.memory_kib, custom.iterations), (65536, 3));
+        assert!(parse(&[("ARGON2_PARALLELISM", "0")]).is_err());
+        assert!(parse(&[("ARGON2_ITERATIONS", "many")]).is_err());
+    }
+
+    #[test]
+    fn otel_export_is_enabled_by_an_endpoint() {
+        let parse = |pairs: &[(&str, &str)]| {
+            let vars: HashMap<_, _> = pairs.iter().copied().collect();
+            parse_otel(|name| vars.get(name).map(ToString::to_string))
+        };
+        assert_eq!(parse(&[("OTEL_SERVICE_NAME", "cms")]).unwrap(), None);
+
+        let settings = parse(&[
+            ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://collector:4318/"),
+            (
+                "OTEL_EXPORTER_OTLP_HEADERS",
+                "x-api-key=secret, tenant = a=b",
+            ),
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(settings.traces_url, "http://collector:4318/v1/traces");
+        assert_eq!(settings.service_name, "mokkan-core");
+        assert_eq!(
+            settings.headers,
+            vec![
+                ("x-api-key".to_string(), "secret".to_string()),
+                ("tenant".to_string(), "a=b".to_string()),
+            ]
+        );
+
+        let settings = parse(&[
+            ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://ignored:4318"),
+            ("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT", "https://gw/traces"),
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(settings.traces_url, "https://gw/traces");
+
+        assert!(parse(&[("OTEL_EXPORTER_OTLP_ENDPOINT", "collector:4317")]).is_err());
+        assert!(
+            parse(&[
+                ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://collector:4318"),
+                ("OTEL_EXPORTER_OTLP_HEADERS", "novalue"),
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn cors_defaults_and_overrides() {
+        let parse = |pairs: &[(&str, &str)]| {
+            let vars: HashMap<_, _> = pairs.iter().copied().collect();
+            parse_cors(|name| vars.get(name).map(ToString::to_string))
+        };
+        assert_eq!(parse(&[]).unwrap(), CorsSettings::default());
+
+        let settings = parse(&[
+            (
+                "ALLOWED_ORIGINS",
+                "https://app.example.com, https://admin.example.com",
+            ),
+            ("CORS_ALLOWED_METHODS", "get,post"),
+            ("CORS_ALLOWED_HEADERS", "content-type,x-csrf-token"),
+            ("CORS_ALLOW_CREDENTIALS", "true"),
+            ("CORS_MAX_AGE_SECS", "600"),
+        ])
+        .unwrap();
+        assert_eq!(settings.allowed_origins.len(), 2);
+        assert_eq!(settings.allowed_methods, vec!["GET", "POST"]);
+        assert_eq!(
+            settings.allowed_headers,
+            vec!["content-type", "x-csrf-token"]
+        );
+        assert!(settings.allow_credentials);
+        assert_eq!(settings.max_age.as_secs(), 600);
+
+        assert!(parse(&[("ALLOWED_ORIGINS", "*"), ("CORS_ALLOW_CREDENTIALS", "1")]).is_err());
+        assert!(parse(&[("CORS_ALLOWED_HEADERS", "x-token; evil")]).is_err());
+        assert!(parse(&[("CORS_MAX_AGE_SECS", "soon")]).is_err());
+    }
 }
@@ -0,0 +1,364 @@
+// src/domain/access/entity.rs
+//! Operator-managed rules that allow or deny requests to sensitive route
+//! groups by client address or autonomous system.
+//!
+//! Rules for a group are tried in ascending priority (then id) order and the
+//! first that matches decides; a request no rule matches is allowed. An
+//! allowlist is therefore a set of `allow` rules followed by a catch-all
+//! `deny` of `0.0.0.0/0` and `::/0`.
+//!
+//! Rules with an expiry, such as the temporary blocks the honeypot creates,
+//! stop matching once it passes.
+
+use crate::domain::UserId;
+use crate::domain::errors::{DomainError, DomainResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Routes that access rules can be attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteGroup {
+    /// Every route, checked before the more specific groups.
+    Site,
+    /// Everything under `/api/v1/admin`.
+    Admin,
+    /// `POST /api/v1/auth/register`.
+    Registration,
+}
+
+impl RouteGroup {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Site => "site",
+            Self::Admin => "admin",
+            Self::Registration => "registration",
+        }
+    }
+}
+
+impl fmt::Display for RouteGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RouteGroup {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "site" => Ok(Self::Site),
+            "admin" => Ok(Self::Admin),
+            "registration" => Ok(Self::Registration),
+            other => Err(DomainError::Validation(format!(
+                "unknown route group '{other}'"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleAction {
+    Allow,
+    Deny,
+}
+
+impl RuleAction {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Deny => "deny",
+        }
+    }
+}
+
+impl fmt::Display for RuleAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RuleAction {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Self::Allow),
+            "deny" => Ok(Self::Deny),
+            other => Err(DomainError::Validation(format!(
+                "unknown rule action '{other}'"
+            ))),
+        }
+    }
+}
+
+/// An IPv4 or IPv6 network in CIDR notation, e.g. `203.0.113.0/24`. A bare
+/// address is a network of one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpRange {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl IpRange {
+    /// # Errors
+    ///
+    /// Returns an error if `value` is not an address or CIDR network, the
+    /// prefix is too long for the address family, or host bits are set.
+    pub fn new(value: &str) -> DomainResult<Self> {
+        let invalid = || DomainError::Validation(format!("invalid CIDR range '{value}'"));
+        let (address, prefix) = match value.trim().split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (value.trim(), None),
+        };
+        let network: IpAddr = address.parse().map_err(|_| invalid())?;
+        let max = Self::bits(network);
+        let prefix = match prefix {
+            Some(prefix) => prefix.parse::<u8>().map_err(|_| invalid())?,
+            None => max,
+        };
+        if prefix > max {
+            return Err(invalid());
+        }
+        let range = Self { network, prefix };
+        if Self::to_bits(network) & !range.mask() != 0 {
+            return Err(DomainError::Validation(format!(
+                "CIDR range '{value}' has host bits set"
+            )));
+        }
+        Ok(range)
+    }
+
+    #[must_use]
+    pub const fn network(&self) -> IpAddr {
+        self.network
+    }
+
+    #[must_use]
+    pub const fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Whether `ip` is in this network. IPv4-mapped IPv6 addresses are
+    /// treated as the IPv4 address they carry.
+    #[must_use]
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        if ip.is_ipv4() != self.network.is_ipv4() {
+            return false;
+        }
+        Self::to_bits(ip) & self.mask() == Self::to_bits(self.network)
+    }
+
+    const fn bits(ip: IpAddr) -> u8 {
+        match ip {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        }
+    }
+
+    fn to_bits(ip: IpAddr) -> u128 {
+        match ip {
+            IpAddr::V4(ip) => u128::from(ip.to_bits()),
+            IpAddr::V6(ip) => ip.to_bits(),
+        }
+    }
+
+    fn mask(&self) -> u128 {
+        let width = Self::bits(self.network);
+        let host_bits = u32::from(width - self.prefix);
+        let all = if width == 32 {
+            u128::from(u32::MAX)
+        } else {
+            u128::MAX
+        };
+        all.checked_shl(host_bits).unwrap_or(0) & all
+    }
+}
+
+impl fmt::Display for IpRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix)
+    }
+}
+
+impl FromStr for IpRange {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+/// What a rule compares the client against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleTarget {
+    Cidr(IpRange),
+    /// Autonomous system number, resolved from the client address through an
+    /// ASN database.
+    Asn(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessRule {
+    pub id: i64,
+    pub group: RouteGroup,
+    pub action: RuleAction,
+    pub target: RuleTarget,
+    /// Lower values are tried first.
+    pub priority: i32,
+    pub note: Option<String>,
+    pub created_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+    /// The rule stops matching at this time; permanent when `None`.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl AccessRule {
+    /// Longest note an operator can attach to a rule, in characters.
+    pub const MAX_NOTE_CHARS: usize = 500;
+
+    /// Whether a client at `ip`, announced by `asn` when known, matches.
+    #[must_use]
+    pub fn matches(&self, ip: IpAddr, asn: Option<u32>) -> bool {
+        match self.target {
+            RuleTarget::Cidr(range) => range.contains(ip),
+            RuleTarget::Asn(number) => asn == Some(number),
+        }
+    }
+
+    #[must_use]
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// The unexpired rule that decides a request to `group` from `ip` at
+    /// `now`, if any.
+    #[must_use]
+    pub fn first_match(
+        rules: &[Self],
+        group: RouteGroup,
+        ip: IpAddr,
+        asn: Option<u32>,
+        now: DateTime<Utc>,
+    ) -> Option<&Self> {
+        rules
+            .iter()
+            .filter(|rule| rule.group == group && !rule.is_expired(now) && rule.matches(ip, asn))
+            .min_by_key(|rule| (rule.priority, rule.id))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewAccessRule {
+    pub group: RouteGroup,
+    pub action: RuleAction,
+    pub target: RuleTarget,
+    pub priority: i32,
+    pub note: Option<String>,
+    pub created_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    fn rule(id: i64, action: RuleAction, target: RuleTarget, priority: i32) -> AccessRule {
+        AccessRule {
+            id,
+            group: RouteGroup::Admin,
+            action,
+            target,
+            priority,
+            note: None,
+            created_by: None,
+            created_at: Utc::now(),
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn ranges_match_their_own_family() {
+        let v4 = IpRange::new("203.0.113.0/24").unwrap();
+        assert!(v4.contains(ip("203.0.113.77")));
+        assert!(v4.contains(ip("::ffff:203.0.113.77")));
+        assert!(!v4.contains(ip("203.0.114.1")));
+        assert!(!v4.contains(ip("2001:db8::1")));
+
+        let v6 = IpRange::new("2001:db8::/32").unwrap();
+        assert!(v6.contains(ip("2001:db8:ffff::1")));
+        assert!(!v6.contains(ip("2001:db9::1")));
+
+        assert!(IpRange::new("0.0.0.0/0").unwrap().contains(ip("8.8.8.8")));
+        assert!(IpRange::new("::/0").unwrap().contains(ip("::1")));
+        assert_eq!(IpRange::new("10.1.2.3").unwrap().to_string(), "10.1.2.3/32");
+    }
+
+    #[test]
+    fn malformed_ranges_are_rejected() {
+        for value in [
+            "10.0.0.0/33",
+            "10.0.0.1/8",
+            "example.com",
+            "::/129",
+            "1.2.3.4/x",
+        ] {
+            assert!(IpRange::new(value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn lowest_priority_match_decides() {
+        let any = RuleTarget::Cidr(IpRange::new("0.0.0.0/0").unwrap());
+        let office = RuleTarget::Cidr(IpRange::new("198.51.100.0/24").unwrap());
+        let rules = [
+            rule(1, RuleAction::Deny, any, 100),
+            rule(2, RuleAction::Allow, office, 10),
+            rule(3, RuleAction::Deny, RuleTarget::Asn(64500), 5),
+        ];
+
+        let now = Utc::now();
+        let decide = |addr: &str, asn| {
+            AccessRule::first_match(&rules, RouteGroup::Admin, ip(addr), asn, now).map(|r| r.id)
+        };
+        assert_eq!(decide("198.51.100.7", None), Some(2));
+        assert_eq!(decide("198.51.100.7", Some(64500)), Some(3));
+        assert_eq!(decide("192.0.2.1", None), Some(1));
+        assert_eq!(
+            AccessRule::first_match(&rules, RouteGroup::Registration, ip("192.0.2.1"), None, now),
+            None
+        );
+    }
+
+    #[test]
+    fn expired_rules_no_longer_match() {
+        let now = Utc::now();
+        let mut block = rule(
+            1,
+            RuleAction::Deny,
+            RuleTarget::Cidr(IpRange::new("192.0.2.1").unwrap()),
+            100,
+        );
+        block.expires_at = Some(now + chrono::Duration::minutes(5));
+        let rules = [block];
+
+        let decide =
+            |at| AccessRule::first_match(&rules, RouteGroup::Admin, ip("192.0.2.1"), None, at);
+        assert!(decide(now).is_some());
+        assert!(decide(now + chrono::Duration::minutes(5)).is_none());
+    }
+}
@@ -0,0 +1,3 @@
+// src/domain/access/mod.rs
+pub mod entity;
+pub mod repository;
@@ -0,0 +1,18 @@
+// src/domain/access/repository.rs
+use crate::async_support::BoxFuture;
+use crate::domain::access::entity::{AccessRule, NewAccessRule};
+use crate::domain::errors::DomainResult;
+use chrono::{DateTime, Utc};
+
+pub trait Repo: Send + Sync {
+    /// Every rule, in evaluation order.
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<AccessRule>>>;
+
+    fn insert(&self, rule: NewAccessRule) -> BoxFuture<'_, DomainResult<AccessRule>>;
+
+    /// Returns `false` if there was no rule with `id`.
+    fn delete(&self, id: i64) -> BoxFuture<'_, DomainResult<bool>>;
+
+    /// Remove rules that expired at or before `now`, returning how many.
+    fn delete_expired(&self, now: DateTime<Utc>) -> BoxFuture<'_, DomainResult<u64>>;
+}
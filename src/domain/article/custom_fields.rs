@@ -0,0 +1,292 @@
+// src/domain/article/custom_fields.rs
+//! Site-defined metadata on articles.
+//!
+//! Admins declare which fields exist; every article then carries a map of
+//! values that must match those definitions whenever it is saved.
+
+use crate::domain::errors::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Values of an article's custom fields, keyed by field name.
+pub type CustomFields = serde_json::Map<String, Value>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    Text,
+    Number,
+    Boolean,
+    /// A calendar date written as `YYYY-MM-DD`.
+    Date,
+}
+
+impl FieldType {
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::Date => "date",
+        }
+    }
+
+    /// Whether `value` is a valid value of this type.
+    #[must_use]
+    pub fn accepts(self, value: &Value) -> bool {
+        match (self, value) {
+            (Self::Text, Value::String(text)) => {
+                text.chars().count() <= FieldDefinition::MAX_TEXT_CHARS
+            }
+            (Self::Number, Value::Number(_)) | (Self::Boolean, Value::Bool(_)) => true,
+            (Self::Date, Value::String(date)) => {
+                chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()
+            }
+            _ => false,
+        }
+    }
+
+    /// Convert `value` into this type, or `None` when it has no sensible
+    /// equivalent. Values this type already accepts are returned unchanged.
+    ///
+    /// Numbers and booleans become their text form and back; text parses as
+    /// a number, as `true`/`false`/`yes`/`no`, or as a date (RFC 3339
+    /// timestamps keep only their date part); `0` and `1` become booleans.
+    #[must_use]
+    pub fn convert(self, value: &Value) -> Option<Value> {
+        if self.accepts(value) {
+            return Some(value.clone());
+        }
+        let converted = match (self, value) {
+            (Self::Text, Value::Number(number)) => Value::String(number.to_string()),
+            (Self::Text, Value::Bool(flag)) => Value::String(flag.to_string()),
+            (Self::Number, Value::String(text)) => number_from_text(text.trim())?,
+            (Self::Number, Value::Bool(flag)) => Value::from(u8::from(*flag)),
+            (Self::Boolean, Value::String(text)) => {
+                match text.trim().to_ascii_lowercase().as_str() {
+                    "true" | "yes" => Value::Bool(true),
+                    "false" | "no" => Value::Bool(false),
+                    _ => return None,
+                }
+            }
+            (Self::Boolean, Value::Number(number)) => match number.as_i64() {
+                Some(0) => Value::Bool(false),
+                Some(1) => Value::Bool(true),
+                _ => return None,
+            },
+            (Self::Date, Value::String(text)) => {
+                let timestamp = chrono::DateTime::parse_from_rfc3339(text.trim()).ok()?;
+                Value::String(timestamp.date_naive().format("%Y-%m-%d").to_string())
+            }
+            _ => return None,
+        };
+        self.accepts(&converted).then_some(converted)
+    }
+}
+
+fn number_from_text(text: &str) -> Option<Value> {
+    if let Ok(integer) = text.parse::<i64>() {
+        return Some(Value::from(integer));
+    }
+    let float = text.parse::<f64>().ok()?;
+    serde_json::Number::from_f64(float).map(Value::Number)
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FieldType {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "number" => Ok(Self::Number),
+            "boolean" => Ok(Self::Boolean),
+            "date" => Ok(Self::Date),
+            other => Err(DomainError::Validation(format!(
+                "unknown custom field type '{other}'"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDefinition {
+    pub name: String,
+    pub field_type: FieldType,
+    pub required: bool,
+}
+
+impl FieldDefinition {
+    /// Longest accepted field name, in bytes.
+    pub const MAX_NAME_LEN: usize = 64;
+    /// Longest accepted text value, in characters.
+    pub const MAX_TEXT_CHARS: usize = 2000;
+
+    /// Create a definition with a validated name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name does not start with a lowercase ASCII
+    /// letter, contains anything but lowercase letters, digits and `_`, or is
+    /// longer than [`Self::MAX_NAME_LEN`].
+    pub fn new(
+        name: impl Into<String>,
+        field_type: FieldType,
+        required: bool,
+    ) -> DomainResult<Self> {
+        let name = name.into();
+        let valid = name.len() <= Self::MAX_NAME_LEN
+            && name.starts_with(|c: char| c.is_ascii_lowercase())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !valid {
+            return Err(DomainError::Validation(format!(
+                "custom field name '{name}' must start with a lowercase letter and contain only \
+                 lowercase letters, digits and '_' (at most {} characters)",
+                Self::MAX_NAME_LEN
+            )));
+        }
+        Ok(Self {
+            name,
+            field_type,
+            required,
+        })
+    }
+}
+
+/// Check `fields` against `definitions`.
+///
+/// # Errors
+///
+/// Returns a validation error naming the first unknown field, value of the
+/// wrong type, or missing required field.
+pub fn validate(definitions: &[FieldDefinition], fields: &CustomFields) -> DomainResult<()> {
+    for (name, value) in fields {
+        let definition = definitions
+            .iter()
+            .find(|definition| &definition.name == name)
+            .ok_or_else(|| DomainError::Validation(format!("unknown custom field '{name}'")))?;
+        if !definition.field_type.accepts(value) {
+            return Err(DomainError::Validation(format!(
+                "custom field '{name}' must be a {} value",
+                definition.field_type
+            )));
+        }
+    }
+    if let Some(missing) = definitions
+        .iter()
+        .find(|definition| definition.required && !fields.contains_key(&definition.name))
+    {
+        return Err(DomainError::Validation(format!(
+            "custom field '{}' is required",
+            missing.name
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields(value: Value) -> CustomFields {
+        match value {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        }
+    }
+
+    fn definitions() -> Vec<FieldDefinition> {
+        vec![
+            FieldDefinition::new("isbn", FieldType::Text, true).unwrap(),
+            FieldDefinition::new("pages", FieldType::Number, false).unwrap(),
+            FieldDefinition::new("signed", FieldType::Boolean, false).unwrap(),
+            FieldDefinition::new("released_on", FieldType::Date, false).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn accepts_values_matching_their_definitions() {
+        let values = fields(json!({
+            "isbn": "978-0", "pages": 320, "signed": true, "released_on": "2024-02-29"
+        }));
+        assert!(validate(&definitions(), &values).is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_mistyped_and_missing_fields() {
+        let cases = [
+            (
+                json!({"isbn": "1", "colour": "red"}),
+                "unknown custom field 'colour'",
+            ),
+            (
+                json!({"isbn": 1}),
+                "custom field 'isbn' must be a text value",
+            ),
+            (
+                json!({"isbn": "1", "released_on": "2023-02-29"}),
+                "must be a date value",
+            ),
+            (
+                json!({"isbn": "1", "signed": null}),
+                "must be a boolean value",
+            ),
+            (json!({"pages": 3}), "custom field 'isbn' is required"),
+        ];
+        for (value, expected) in cases {
+            let err = validate(&definitions(), &fields(value)).unwrap_err();
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+    }
+
+    #[test]
+    fn converts_values_between_types() {
+        let cases = [
+            (FieldType::Text, json!(320), Some(json!("320"))),
+            (FieldType::Text, json!(false), Some(json!("false"))),
+            (FieldType::Number, json!(" 42 "), Some(json!(42))),
+            (FieldType::Number, json!("2.5"), Some(json!(2.5))),
+            (FieldType::Number, json!("many"), None),
+            (FieldType::Boolean, json!("Yes"), Some(json!(true))),
+            (FieldType::Boolean, json!(0), Some(json!(false))),
+            (FieldType::Boolean, json!(2), None),
+            (
+                FieldType::Date,
+                json!("2024-02-29T23:00:00Z"),
+                Some(json!("2024-02-29")),
+            ),
+            (
+                FieldType::Date,
+                json!("2024-02-29"),
+                Some(json!("2024-02-29")),
+            ),
+            (FieldType::Date, json!(20_240_229), None),
+        ];
+        for (field_type, value, expected) in cases {
+            assert_eq!(field_type.convert(&value), expected, "{field_type} {value}");
+        }
+    }
+
+    #[test]
+    fn field_names_are_restricted() {
+        assert!(FieldDefinition::new("sub_title2", FieldType::Text, false).is_ok());
+        for name in ["", "2nd", "Title", "sub-title", &"a".repeat(65)] {
+            assert!(
+                FieldDefinition::new(name, FieldType::Text, false).is_err(),
+                "{name}"
+            );
+        }
+    }
+}
@@ -1,6 +1,9 @@
 // src/domain/article/entity.rs
 use crate::domain::UserId;
-use crate::domain::article::value_objects::{ArticleBody, ArticleId, ArticleSlug, ArticleTitle};
+use crate::domain::article::custom_fields::CustomFields;
+use crate::domain::article::value_objects::{
+    ArticleBody, ArticleId, ArticleSlug, ArticleTitle, Tag, Visibility,
+};
 use crate::domain::errors::DomainResult;
 use chrono::{DateTime, Utc};
 
@@ -10,11 +13,96 @@ pub struct Article {
     pub title: ArticleTitle,
     pub slug: ArticleSlug,
     pub body: ArticleBody,
+    pub tags: Vec<Tag>,
+    pub custom_fields: CustomFields,
     pub published: bool,
     pub published_at: Option<DateTime<Utc>>,
+    pub visibility: Visibility,
     pub author_id: UserId,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Times the published article was read, as of the last flush of
+    /// counted views.
+    pub view_count: u64,
+}
+
+/// An article matching a search, with an excerpt of its body around the
+/// matched terms when the repository can produce one.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub article: Article,
+    /// HTML-escaped body excerpt with matched terms wrapped in `<mark>`.
+    pub headline: Option<String>,
+}
+
+/// Which private articles a facet count includes, so the counts cover
+/// exactly the articles the caller may read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrivateScope {
+    /// Leave private articles out.
+    Excluded,
+    /// Only the private articles this user wrote.
+    AuthoredBy(UserId),
+    /// Every private article.
+    All,
+}
+
+impl PrivateScope {
+    #[must_use]
+    pub fn includes(self, article: &Article) -> bool {
+        match self {
+            _ if article.visibility != Visibility::Private => true,
+            Self::Excluded => false,
+            Self::AuthoredBy(user_id) => article.author_id == user_id,
+            Self::All => true,
+        }
+    }
+}
+
+/// Most values listed per facet of [`SearchFacets`].
+pub const FACET_LIMIT: usize = 10;
+
+/// How many matching articles share one value of a facet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FacetCount<T> {
+    pub value: T,
+    pub count: u64,
+}
+
+/// Breakdown of the articles matching a search, for narrowing it down.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchFacets {
+    pub total: u64,
+    pub published: u64,
+    pub drafts: u64,
+    /// The [`FACET_LIMIT`] authors with the most matches, most first.
+    pub authors: Vec<FacetCount<UserId>>,
+    /// The [`FACET_LIMIT`] most common tags among the matches, most first.
+    pub tags: Vec<FacetCount<Tag>>,
+}
+
+impl SearchFacets {
+    /// Rank and trim the counted facets; ties are broken by author id or
+    /// tag name.
+    #[must_use]
+    pub fn new(
+        published: u64,
+        drafts: u64,
+        mut authors: Vec<FacetCount<UserId>>,
+        mut tags: Vec<FacetCount<Tag>>,
+    ) -> Self {
+        authors.sort_by_key(|facet| (std::cmp::Reverse(facet.count), i64::from(facet.value)));
+        authors.truncate(FACET_LIMIT);
+        tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+        tags.truncate(FACET_LIMIT);
+        Self {
+            total: published + drafts,
+            published,
+            drafts,
+            authors,
+            tags,
+        }
+    }
 }
 
 impl Article {
@@ -30,6 +118,21 @@ impl Article {
         self.updated_at = now;
     }
 
+    pub fn set_tags(&mut self, tags: Vec<Tag>, now: DateTime<Utc>) {
+        self.tags = tags;
+        self.updated_at = now;
+    }
+
+    pub fn set_custom_fields(&mut self, custom_fields: CustomFields, now: DateTime<Utc>) {
+        self.custom_fields = custom_fields;
+        self.updated_at = now;
+    }
+
+    pub const fn set_visibility(&mut self, visibility: Visibility, now: DateTime<Utc>) {
+        self.visibility = visibility;
+        self.updated_at = now;
+    }
+
     pub fn set_slug(&mut self, slug: ArticleSlug, now: DateTime<Utc>) {
         self.slug = slug;
         self.updated_at = now;
@@ -67,11 +170,15 @@ mod tests {
             title: ArticleTitle::new("title").unwrap(),
             slug: ArticleSlug::new("title").unwrap(),
             body: ArticleBody::new("body").unwrap(),
+            tags: Vec::new(),
+            custom_fields: CustomFields::new(),
             published: false,
             published_at: None,
+            visibility: Visibility::Public,
             author_id: crate::domain::UserId::new(1).unwrap(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            view_count: 0,
         }
     }
 
@@ -117,8 +224,11 @@ pub struct NewArticle {
     pub title: ArticleTitle,
     pub slug: ArticleSlug,
     pub body: ArticleBody,
+    pub tags: Vec<Tag>,
+    pub custom_fields: CustomFields,
     pub published: bool,
     pub published_at: Option<DateTime<Utc>>,
+    pub visibility: Visibility,
     pub author_id: UserId,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -137,7 +247,10 @@ pub struct ArticleUpdate {
     pub title: Option<ArticleTitle>,
     pub slug: Option<ArticleSlug>,
     pub body: Option<ArticleBody>,
+    pub tags: Option<Vec<Tag>>,
+    pub custom_fields: Option<CustomFields>,
     pub publish_state: Option<PublishStateUpdate>,
+    pub visibility: Option<Visibility>,
     pub original_updated_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -149,7 +262,10 @@ impl ArticleUpdate {
             title: None,
             slug: None,
             body: None,
+            tags: None,
+            custom_fields: None,
             publish_state: None,
+            visibility: None,
             original_updated_at,
             updated_at: original_updated_at,
         }
@@ -170,6 +286,16 @@ impl ArticleUpdate {
         self
     }
 
+    pub fn with_tags(mut self, tags: Vec<Tag>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    pub fn with_custom_fields(mut self, custom_fields: CustomFields) -> Self {
+        self.custom_fields = Some(custom_fields);
+        self
+    }
+
     pub const fn with_publish_state(
         mut self,
         published: bool,
@@ -182,7 +308,22 @@ impl ArticleUpdate {
         self
     }
 
+    pub const fn with_visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = Some(visibility);
+        self
+    }
+
     pub const fn set_updated_at(&mut self, updated_at: DateTime<Utc>) {
         self.updated_at = updated_at;
     }
 }
+
+/// A user other than the author who may edit an article.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contributor {
+    pub article_id: ArticleId,
+    pub user_id: UserId,
+    /// Who added them; `None` once that user has been deleted.
+    pub added_by: Option<UserId>,
+    pub added_at: DateTime<Utc>,
+}
@@ -1,4 +1,5 @@
 // src/domain/article/mod.rs
+pub mod custom_fields;
 pub mod entity;
 pub mod repository;
 pub mod revision;
@@ -1,15 +1,83 @@
 // src/domain/article/repository.rs
 use crate::async_support::{BoxFuture, boxed};
+use crate::domain::PAGE_LIMIT_CEILING;
 use crate::domain::UserId;
-use crate::domain::article::entity::{Article, ArticleUpdate, NewArticle};
+use crate::domain::article::custom_fields::FieldDefinition;
+use crate::domain::article::entity::{
+    Article, ArticleUpdate, Contributor, NewArticle, PrivateScope, SearchFacets, SearchHit,
+};
 use crate::domain::article::revision::Revision;
-use crate::domain::article::value_objects::{ArticleId, ArticleListCursor, ArticleSlug};
-use crate::domain::errors::DomainResult;
+use crate::domain::article::value_objects::{
+    ArticleId, ArticleListCursor, ArticlePopularityCursor, ArticleSlug, Tag,
+};
+use crate::domain::errors::{DomainError, DomainResult};
 
 pub trait WriteRepo: Send + Sync {
     fn insert(&self, article: NewArticle) -> BoxFuture<'_, DomainResult<Article>>;
     fn update(&self, update: ArticleUpdate) -> BoxFuture<'_, DomainResult<Article>>;
     fn delete(&self, id: ArticleId) -> BoxFuture<'_, DomainResult<()>>;
+
+    /// Apply each of `updates` like [`update`](Self::update), returning the
+    /// articles that were stored. Articles that changed since their
+    /// `original_updated_at`, or are gone, are left out instead of failing
+    /// the rest. The default implementation issues one update per article.
+    fn update_many(
+        &self,
+        updates: Vec<ArticleUpdate>,
+    ) -> BoxFuture<'_, DomainResult<Vec<Article>>> {
+        boxed(async move {
+            let mut stored = Vec::with_capacity(updates.len());
+            for update in updates {
+                match self.update(update).await {
+                    Ok(article) => stored.push(article),
+                    Err(DomainError::Conflict(_) | DomainError::NotFound(_)) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            Ok(stored)
+        })
+    }
+
+    /// Delete every article in `ids`, returning the ids that existed. The
+    /// default implementation issues one delete per article.
+    fn delete_many<'a>(
+        &'a self,
+        ids: &'a [ArticleId],
+    ) -> BoxFuture<'a, DomainResult<Vec<ArticleId>>> {
+        boxed(async move {
+            let mut deleted = Vec::with_capacity(ids.len());
+            for id in ids {
+                match self.delete(*id).await {
+                    Ok(()) => deleted.push(*id),
+                    Err(DomainError::NotFound(_)) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            Ok(deleted)
+        })
+    }
+
+    /// Add counted views to each listed article's `view_count`, leaving
+    /// `updated_at` alone. Unknown ids are skipped.
+    fn add_views<'a>(&'a self, views: &'a [(ArticleId, u64)]) -> BoxFuture<'a, DomainResult<()>> {
+        let _ = views;
+        boxed(async {
+            Err(DomainError::Validation(
+                "view counting is not supported".into(),
+            ))
+        })
+    }
+
+    /// Make `to` the author of every article `from` wrote, returning how
+    /// many there were. Revisions keep the author who wrote them.
+    fn reassign_author(&self, from: UserId, to: UserId) -> BoxFuture<'_, DomainResult<u64>> {
+        let _ = (from, to);
+        boxed(async {
+            Err(DomainError::Validation(
+                "reassigning articles is not supported".into(),
+            ))
+        })
+    }
 }
 
 pub trait ReadRepo: Send + Sync {
@@ -18,7 +86,29 @@ pub trait ReadRepo: Send + Sync {
         &'a self,
         slug: &'a ArticleSlug,
     ) -> BoxFuture<'a, DomainResult<Option<Article>>>;
+    /// Load every article whose id is in `ids`, in no particular order.
+    /// Unknown ids are skipped. The default implementation issues one lookup
+    /// per id.
+    fn find_by_ids<'a>(
+        &'a self,
+        ids: &'a [ArticleId],
+    ) -> BoxFuture<'a, DomainResult<Vec<Article>>> {
+        boxed(async move {
+            let mut found = Vec::with_capacity(ids.len());
+            for id in ids {
+                if let Some(article) = self.find_by_id(*id).await? {
+                    found.push(article);
+                }
+            }
+            Ok(found)
+        })
+    }
+
     /// Existing page-oriented listing API. Keep for backward compatibility.
+    ///
+    /// Without drafts, every listing method only returns published public
+    /// articles; with drafts, it returns every article whatever its
+    /// visibility.
     fn list_page<'a>(
         &'a self,
         include_drafts: bool,
@@ -28,12 +118,18 @@ pub trait ReadRepo: Send + Sync {
     ) -> BoxFuture<'a, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>>;
 
     /// New builder-style query API. Default implementation delegates to
-    /// `list_page` so existing implementations remain compatible.
+    /// `list_page` so existing implementations remain compatible; it rejects
+    /// tag filters, which `list_page` cannot express.
     fn list(
         &self,
         query: ArticleQuery,
     ) -> BoxFuture<'_, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
         boxed(async move {
+            if query.tag.is_some() {
+                return Err(DomainError::Validation(
+                    "tag filtering is not supported".into(),
+                ));
+            }
             // Convert Option<String> -> Option<&str> for the old API
             let search = query.search.as_deref();
             self.list_page(
@@ -45,6 +141,110 @@ pub trait ReadRepo: Send + Sync {
             .await
         })
     }
+
+    /// Articles matching `query.search`, most relevant first, each with a
+    /// highlighted excerpt when the repository can produce one. The default
+    /// implementation delegates to [`list`](Self::list) without excerpts.
+    fn search(
+        &self,
+        query: ArticleQuery,
+    ) -> BoxFuture<'_, DomainResult<(Vec<SearchHit>, Option<ArticleListCursor>)>> {
+        boxed(async move {
+            let (articles, next_cursor) = self.list(query).await?;
+            let hits = articles
+                .into_iter()
+                .map(|article| SearchHit {
+                    article,
+                    headline: None,
+                })
+                .collect();
+            Ok((hits, next_cursor))
+        })
+    }
+
+    /// Facet counts over every article [`search`](Self::search) would page
+    /// through for the same filters, keeping only the private articles in
+    /// `private`. `None` when the repository cannot count them.
+    fn search_facets<'a>(
+        &'a self,
+        include_drafts: bool,
+        private: PrivateScope,
+        search: Option<&'a str>,
+        tag: Option<&'a Tag>,
+    ) -> BoxFuture<'a, DomainResult<Option<SearchFacets>>> {
+        let _ = (include_drafts, private, search, tag);
+        boxed(async { Ok(None) })
+    }
+
+    /// Page of articles sorting immediately before `cursor` (i.e. newer),
+    /// returned newest-first like [`list_page`](Self::list_page). The returned
+    /// cursor points at the first item when even newer articles remain.
+    fn list_page_before(
+        &self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: ArticleListCursor,
+    ) -> BoxFuture<'_, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        let _ = (include_drafts, limit, cursor);
+        boxed(async {
+            Err(DomainError::Validation(
+                "backward pagination is not supported".into(),
+            ))
+        })
+    }
+
+    /// Whether any article sorts before `cursor` (i.e. is newer) in the default
+    /// listing order. Used to decide if a previous page exists.
+    fn has_preceding<'a>(
+        &'a self,
+        include_drafts: bool,
+        cursor: &'a ArticleListCursor,
+    ) -> BoxFuture<'a, DomainResult<bool>> {
+        let _ = (include_drafts, cursor);
+        boxed(async { Ok(false) })
+    }
+
+    /// Page of articles ordered by view count, most viewed first and newest
+    /// id first among equals, optionally only those carrying `tag`. The
+    /// returned cursor points at the last item when more remain.
+    fn list_popular<'a>(
+        &'a self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: Option<ArticlePopularityCursor>,
+        tag: Option<&'a Tag>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, Option<ArticlePopularityCursor>)>> {
+        let _ = (include_drafts, limit, cursor, tag);
+        boxed(async {
+            Err(DomainError::Validation(
+                "ordering by popularity is not supported".into(),
+            ))
+        })
+    }
+
+    /// Articles at `offset` in the newest-first listing, optionally only those
+    /// carrying `tag`, with the number of articles across all offsets.
+    fn list_offset<'a>(
+        &'a self,
+        include_drafts: bool,
+        offset: u64,
+        limit: u32,
+        tag: Option<&'a Tag>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, u64)>> {
+        let _ = (include_drafts, offset, limit, tag);
+        boxed(async {
+            Err(DomainError::Validation(
+                "offset pagination is not supported".into(),
+            ))
+        })
+    }
+
+    /// Approximate number of articles in the unfiltered listing. `None` when
+    /// the repository cannot provide one cheaply.
+    fn estimate_total(&self, include_drafts: bool) -> BoxFuture<'_, DomainResult<Option<u64>>> {
+        let _ = include_drafts;
+        boxed(async { Ok(None) })
+    }
 }
 
 /// Builder-style query for listing articles.
@@ -55,6 +255,7 @@ pub struct ArticleQuery {
     pub limit: u32,
     pub cursor: Option<ArticleListCursor>,
     pub search: Option<String>,
+    pub tag: Option<Tag>,
 }
 
 impl ArticleQuery {
@@ -64,6 +265,7 @@ impl ArticleQuery {
             limit: 20,
             cursor: None,
             search: None,
+            tag: None,
         }
     }
 
@@ -73,7 +275,7 @@ impl ArticleQuery {
     }
 
     pub fn limit(mut self, value: u32) -> Self {
-        self.limit = value.clamp(1, 100);
+        self.limit = value.clamp(1, PAGE_LIMIT_CEILING);
         self
     }
 
@@ -86,6 +288,11 @@ impl ArticleQuery {
         self.search = Some(value.into());
         self
     }
+
+    pub fn tag(mut self, value: Tag) -> Self {
+        self.tag = Some(value);
+        self
+    }
 }
 
 impl Default for ArticleQuery {
@@ -101,5 +308,62 @@ pub trait RevisionRepo: Send + Sync {
         edited_by: Option<UserId>,
     ) -> BoxFuture<'a, DomainResult<()>>;
 
+    /// Record each of `articles` as its next revision. The default
+    /// implementation appends them one at a time.
+    fn append_many<'a>(
+        &'a self,
+        articles: &'a [Article],
+        edited_by: Option<UserId>,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        boxed(async move {
+            for article in articles {
+                self.append(article, edited_by).await?;
+            }
+            Ok(())
+        })
+    }
+
     fn list_by_article(&self, article_id: ArticleId) -> BoxFuture<'_, DomainResult<Vec<Revision>>>;
+
+    /// The revision of `article_id` numbered `version`, if recorded. The
+    /// default implementation scans [`list_by_article`](Self::list_by_article).
+    fn find_revision(
+        &self,
+        article_id: ArticleId,
+        version: i32,
+    ) -> BoxFuture<'_, DomainResult<Option<Revision>>> {
+        boxed(async move {
+            Ok(self
+                .list_by_article(article_id)
+                .await?
+                .into_iter()
+                .find(|revision| revision.version == version))
+        })
+    }
+}
+
+/// Custom field definitions, keyed by name.
+pub trait CustomFieldRepo: Send + Sync {
+    /// Every definition, sorted by name.
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<FieldDefinition>>>;
+
+    /// Add `definition`, or replace the one with the same name.
+    fn upsert(&self, definition: FieldDefinition) -> BoxFuture<'_, DomainResult<FieldDefinition>>;
+
+    /// Remove the definition named `name`, returning whether it existed.
+    /// Values already stored on articles are left in place.
+    fn delete<'a>(&'a self, name: &'a str) -> BoxFuture<'a, DomainResult<bool>>;
+}
+
+/// Users who may edit articles besides their authors.
+pub trait ContributorRepo: Send + Sync {
+    /// Add `contributor`, or return the stored entry if the user already
+    /// contributes to the article.
+    fn add(&self, contributor: Contributor) -> BoxFuture<'_, DomainResult<Contributor>>;
+
+    /// Returns `false` if the user was not a contributor.
+    fn remove(&self, article_id: ArticleId, user_id: UserId) -> BoxFuture<'_, DomainResult<bool>>;
+
+    /// Contributors of `article_id`, earliest added first.
+    fn list(&self, article_id: ArticleId) -> BoxFuture<'_, DomainResult<Vec<Contributor>>>;
 }
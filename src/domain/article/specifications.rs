@@ -2,6 +2,7 @@
 use std::collections::HashSet;
 
 use crate::domain::article::entity::Article;
+use crate::domain::article::value_objects::Visibility;
 use crate::domain::user::value_objects::{Capability, UserId};
 
 pub trait ArticleSpecification {
@@ -14,6 +15,7 @@ pub struct CanUpdateArticleSpec<'a> {
     capabilities: &'a HashSet<Capability>,
     article: &'a Article,
     user_id: UserId,
+    contributors: &'a [UserId],
 }
 
 impl<'a> CanUpdateArticleSpec<'a> {
@@ -26,9 +28,16 @@ impl<'a> CanUpdateArticleSpec<'a> {
             capabilities,
             article,
             user_id,
+            contributors: &[],
         }
     }
 
+    /// Also let `contributors` edit the article as if they were its author.
+    pub const fn with_contributors(mut self, contributors: &'a [UserId]) -> Self {
+        self.contributors = contributors;
+        self
+    }
+
     fn has_capability(&self, resource: &str, action: &str) -> bool {
         self.capabilities
             .iter()
@@ -40,7 +49,8 @@ impl ArticleSpecification for CanUpdateArticleSpec<'_> {
     fn is_satisfied(&self) -> bool {
         self.has_capability("articles", "update:any")
             || (self.has_capability("articles", "update:own")
-                && self.article.author_id == self.user_id)
+                && (self.article.author_id == self.user_id
+                    || self.contributors.contains(&self.user_id)))
     }
 }
 
@@ -79,6 +89,43 @@ impl ArticleSpecification for CanDeleteArticleSpec<'_> {
     }
 }
 
+/// Whether a signed-in user may read an article given its visibility.
+/// Publication state is checked separately.
+#[must_use]
+pub struct CanViewArticleSpec<'a> {
+    capabilities: &'a HashSet<Capability>,
+    article: &'a Article,
+    user_id: UserId,
+}
+
+impl<'a> CanViewArticleSpec<'a> {
+    pub const fn new(
+        capabilities: &'a HashSet<Capability>,
+        article: &'a Article,
+        user_id: UserId,
+    ) -> Self {
+        Self {
+            capabilities,
+            article,
+            user_id,
+        }
+    }
+
+    fn has_capability(&self, resource: &str, action: &str) -> bool {
+        self.capabilities
+            .iter()
+            .any(|cap| cap.matches(resource, action))
+    }
+}
+
+impl ArticleSpecification for CanViewArticleSpec<'_> {
+    fn is_satisfied(&self) -> bool {
+        self.article.visibility != Visibility::Private
+            || self.article.author_id == self.user_id
+            || self.has_capability("articles", "view:any")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,11 +143,15 @@ mod tests {
             title: ArticleTitle::new("title").unwrap(),
             slug: ArticleSlug::new("title").unwrap(),
             body: ArticleBody::new("body").unwrap(),
+            tags: Vec::new(),
+            custom_fields: serde_json::Map::new(),
             published: false,
             published_at: None,
+            visibility: Visibility::Public,
             author_id: UserId::new(author_id).unwrap(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            view_count: 0,
         }
     }
 
@@ -121,6 +172,21 @@ mod tests {
         assert!(!spec.is_satisfied());
     }
 
+    #[test]
+    fn update_spec_lets_contributors_edit_with_own_capability() {
+        let mut caps = HashSet::new();
+        caps.insert(Capability::new("articles", "update:own"));
+        let article = article(1);
+        let helper = UserId::new(2).unwrap();
+        let spec = CanUpdateArticleSpec::new(&caps, &article, helper);
+        assert!(!spec.is_satisfied());
+        assert!(spec.with_contributors(&[helper]).is_satisfied());
+
+        let none = HashSet::new();
+        let spec = CanUpdateArticleSpec::new(&none, &article, helper);
+        assert!(!spec.with_contributors(&[helper]).is_satisfied());
+    }
+
     #[test]
     fn delete_spec_allows_owner_with_capability() {
         let mut caps = HashSet::new();
@@ -130,4 +196,22 @@ mod tests {
         let spec = CanDeleteArticleSpec::new(&caps, &article, user_id);
         assert!(spec.is_satisfied());
     }
+
+    #[test]
+    fn view_spec_limits_private_articles_to_author_and_view_any() {
+        let mut private = article(1);
+        private.visibility = Visibility::Private;
+        let stranger = UserId::new(2).unwrap();
+        let caps = HashSet::new();
+        assert!(!CanViewArticleSpec::new(&caps, &private, stranger).is_satisfied());
+        assert!(CanViewArticleSpec::new(&caps, &private, UserId::new(1).unwrap()).is_satisfied());
+
+        let mut caps = HashSet::new();
+        caps.insert(Capability::new("articles", "view:any"));
+        assert!(CanViewArticleSpec::new(&caps, &private, stranger).is_satisfied());
+
+        let mut unlisted = article(1);
+        unlisted.visibility = Visibility::Unlisted;
+        assert!(CanViewArticleSpec::new(&HashSet::new(), &unlisted, stranger).is_satisfied());
+    }
 }
@@ -2,7 +2,10 @@
 use crate::domain::errors::{DomainError, DomainResult};
 use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ArticleId(pub i64);
@@ -34,16 +37,26 @@ impl From<ArticleId> for i64 {
 pub struct ArticleTitle(String);
 
 impl ArticleTitle {
+    /// Longest accepted title, in characters.
+    pub const MAX_CHARS: usize = 200;
+
     /// Create a validated article title.
     ///
     /// # Errors
     ///
-    /// Returns an error if the title is blank.
+    /// Returns an error if the title is blank or longer than
+    /// [`Self::MAX_CHARS`] characters.
     pub fn new(value: impl Into<String>) -> DomainResult<Self> {
         let value = value.into();
         if value.trim().is_empty() {
             return Err(DomainError::Validation("title cannot be empty".into()));
         }
+        if value.chars().count() > Self::MAX_CHARS {
+            return Err(DomainError::Validation(format!(
+                "title must be at most {} characters",
+                Self::MAX_CHARS
+            )));
+        }
         Ok(Self(value))
     }
 
@@ -128,16 +141,26 @@ impl From<ArticleSlug> for String {
 pub struct ArticleBody(String);
 
 impl ArticleBody {
+    /// Longest accepted body, in characters.
+    pub const MAX_CHARS: usize = 100_000;
+
     /// Create a validated article body.
     ///
     /// # Errors
     ///
-    /// Returns an error if the body is blank.
+    /// Returns an error if the body is blank or longer than
+    /// [`Self::MAX_CHARS`] characters.
     pub fn new(value: impl Into<String>) -> DomainResult<Self> {
         let value = value.into();
         if value.trim().is_empty() {
             return Err(DomainError::Validation("body cannot be empty".into()));
         }
+        if value.chars().count() > Self::MAX_CHARS {
+            return Err(DomainError::Validation(format!(
+                "body must be at most {} characters",
+                Self::MAX_CHARS
+            )));
+        }
         Ok(Self(value))
     }
 
@@ -171,6 +194,156 @@ impl From<ArticleBody> for String {
     }
 }
 
+/// Normalized article tag: trimmed, lowercase ASCII letters, digits, `-` and
+/// `_`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Tag(String);
+
+impl Tag {
+    /// Longest accepted tag, in characters.
+    pub const MAX_CHARS: usize = 32;
+    /// Most tags a single article may carry.
+    pub const MAX_PER_ARTICLE: usize = 10;
+
+    /// Create a validated tag, trimming and lowercasing the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tag is blank, longer than [`Self::MAX_CHARS`]
+    /// characters, or contains characters other than ASCII letters, digits,
+    /// `-` and `_`.
+    pub fn new(value: impl AsRef<str>) -> DomainResult<Self> {
+        let value = value.as_ref().trim().to_ascii_lowercase();
+        if value.is_empty() {
+            return Err(DomainError::Validation("tag cannot be empty".into()));
+        }
+        if value.chars().count() > Self::MAX_CHARS {
+            return Err(DomainError::Validation(format!(
+                "tag must be at most {} characters",
+                Self::MAX_CHARS
+            )));
+        }
+        if !value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(DomainError::Validation(format!(
+                "tag '{value}' may only contain letters, digits, '-' and '_'"
+            )));
+        }
+        Ok(Self(value))
+    }
+
+    /// Validate a set of tags for one article, dropping duplicates and
+    /// returning them sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any tag is invalid or more than
+    /// [`Self::MAX_PER_ARTICLE`] distinct tags are given.
+    pub fn parse_list<I, S>(values: I) -> DomainResult<Vec<Self>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut tags = values
+            .into_iter()
+            .map(Self::new)
+            .collect::<DomainResult<Vec<_>>>()?;
+        tags.sort();
+        tags.dedup();
+        if tags.len() > Self::MAX_PER_ARTICLE {
+            return Err(DomainError::Validation(format!(
+                "an article may have at most {} tags",
+                Self::MAX_PER_ARTICLE
+            )));
+        }
+        Ok(tags)
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consume the value object and return the inner String.
+    #[must_use]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Tag {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<Tag> for String {
+    fn from(value: Tag) -> Self {
+        value.0
+    }
+}
+
+/// Who may find and read an article, on top of its publication state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[schema(as = ArticleVisibility)]
+pub enum Visibility {
+    /// Listed, and readable by anyone once published.
+    #[default]
+    Public,
+    /// Readable by anyone with the link once published, but never listed,
+    /// searched or syndicated.
+    Unlisted,
+    /// Only readable, and only listed to, its author and holders of
+    /// `articles:view:any`.
+    Private,
+}
+
+impl Visibility {
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Unlisted => "unlisted",
+            Self::Private => "private",
+        }
+    }
+
+    #[must_use]
+    pub const fn is_public(&self) -> bool {
+        matches!(self, Self::Public)
+    }
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Visibility {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "public" => Ok(Self::Public),
+            "unlisted" => Ok(Self::Unlisted),
+            "private" => Ok(Self::Private),
+            other => Err(DomainError::Validation(format!(
+                "unknown article visibility '{other}'"
+            ))),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[must_use]
 pub struct ArticleListCursor {
@@ -234,3 +407,95 @@ impl ArticleListCursor {
         })
     }
 }
+
+/// Position in a listing ordered by view count, most viewed first and
+/// newest id first among equals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct ArticlePopularityCursor {
+    pub view_count: u64,
+    pub article_id: ArticleId,
+}
+
+impl ArticlePopularityCursor {
+    /// Marks these tokens apart from [`ArticleListCursor`] ones.
+    const PREFIX: &'static str = "popular|";
+
+    pub const fn new(view_count: u64, article_id: ArticleId) -> Self {
+        Self {
+            view_count,
+            article_id,
+        }
+    }
+
+    #[must_use]
+    pub fn encode(&self) -> String {
+        let raw = format!(
+            "{}{}|{}",
+            Self::PREFIX,
+            self.view_count,
+            i64::from(self.article_id)
+        );
+        URL_SAFE_NO_PAD.encode(raw.as_bytes())
+    }
+
+    /// Decode a popularity cursor token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token is malformed, contains invalid data or
+    /// belongs to another ordering.
+    pub fn decode(token: &str) -> DomainResult<Self> {
+        let invalid = || DomainError::Validation("invalid cursor token".into());
+        let bytes = URL_SAFE_NO_PAD.decode(token).map_err(|_| invalid())?;
+        let raw = String::from_utf8(bytes).map_err(|_| invalid())?;
+        let (views, id) = raw
+            .strip_prefix(Self::PREFIX)
+            .and_then(|rest| rest.split_once('|'))
+            .ok_or_else(invalid)?;
+        let view_count = views.parse::<u64>().map_err(|_| invalid())?;
+        let id = id.parse::<i64>().map_err(|_| invalid())?;
+        Ok(Self::new(view_count, ArticleId::new(id)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_is_normalized() {
+        let tag = Tag::new("  Rust-Lang ").unwrap();
+        assert_eq!(tag.as_str(), "rust-lang");
+    }
+
+    #[test]
+    fn tag_rejects_invalid_input() {
+        assert!(Tag::new("   ").is_err());
+        assert!(Tag::new("two words").is_err());
+        assert!(Tag::new("x".repeat(Tag::MAX_CHARS + 1)).is_err());
+    }
+
+    #[test]
+    fn tag_list_dedupes_and_limits() {
+        let tags = Tag::parse_list(["web", "Rust", "rust"]).unwrap();
+        let names: Vec<_> = tags.iter().map(Tag::as_str).collect();
+        assert_eq!(names, ["rust", "web"]);
+
+        let too_many = (0..=Tag::MAX_PER_ARTICLE).map(|i| format!("t{i}"));
+        assert!(Tag::parse_list(too_many).is_err());
+    }
+
+    #[test]
+    fn popularity_cursors_round_trip_and_reject_list_cursors() {
+        let cursor = ArticlePopularityCursor::new(42, ArticleId::new(7).unwrap());
+        assert_eq!(
+            ArticlePopularityCursor::decode(&cursor.encode()).unwrap(),
+            cursor
+        );
+
+        let listing = ArticleListCursor::new(Utc::now(), ArticleId::new(7).unwrap());
+        assert!(ArticlePopularityCursor::decode(&listing.encode()).is_err());
+        assert!(ArticleListCursor::decode(&cursor.encode()).is_err());
+    }
+}
@@ -1,20 +1,42 @@
 // src/domain/audit/cursor.rs
+use crate::domain::audit::entity::AuditLog;
 use crate::domain::errors::{DomainError, DomainResult};
 use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
 use chrono::{DateTime, Utc};
 
-#[derive(Debug, Clone)]
+/// Position in the `(created_at DESC, id DESC)` audit log ordering.
+///
+/// Query services decode incoming tokens with [`AuditLogCursor::decode`] and
+/// repositories only ever see the typed value; [`AuditLogCursor::encode`] is
+/// the single place tokens are produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[must_use]
-pub struct Cursor {
+pub struct AuditLogCursor {
     pub created_at: DateTime<Utc>,
     pub id: i64,
 }
 
-impl Cursor {
+impl AuditLogCursor {
     pub const fn new(created_at: DateTime<Utc>, id: i64) -> Self {
         Self { created_at, id }
     }
 
+    /// Cursor positioned just after `log`.
+    pub const fn after(log: &AuditLog) -> Self {
+        Self::new(log.created_at, log.id)
+    }
+
+    /// Trim a `limit + 1` fetch down to one page and return the cursor for the
+    /// next page, if the extra row proved there is one.
+    pub fn trim_page(items: &mut Vec<AuditLog>, limit: u32) -> Option<Self> {
+        if items.len() <= limit as usize {
+            return None;
+        }
+
+        items.truncate(limit as usize);
+        items.last().map(Self::after)
+    }
+
     #[must_use]
     pub fn encode(&self) -> String {
         let raw = format!("{}|{}", self.created_at.to_rfc3339(), self.id);
@@ -25,26 +47,22 @@ impl Cursor {
     ///
     /// # Errors
     ///
-    /// Returns an error if the token is malformed or contains invalid data.
+    /// Returns a validation error if the token is malformed or contains
+    /// invalid data.
     pub fn decode(token: &str) -> DomainResult<Self> {
-        let bytes = URL_SAFE_NO_PAD
-            .decode(token)
-            .map_err(|_| DomainError::Validation("invalid cursor token".into()))?;
-        let raw = String::from_utf8(bytes)
-            .map_err(|_| DomainError::Validation("invalid cursor token".into()))?;
-        let mut parts = raw.splitn(2, '|');
-        let created_at_s = parts
-            .next()
-            .ok_or_else(|| DomainError::Validation("invalid cursor token".into()))?;
-        let id_s = parts
-            .next()
-            .ok_or_else(|| DomainError::Validation("invalid cursor token".into()))?;
+        let invalid = || DomainError::Validation("invalid cursor token".into());
+
+        let bytes = URL_SAFE_NO_PAD.decode(token).map_err(|_| invalid())?;
+        let raw = String::from_utf8(bytes).map_err(|_| invalid())?;
+        let (created_at_s, id_s) = raw.split_once('|').ok_or_else(invalid)?;
         let created_at = DateTime::parse_from_rfc3339(created_at_s)
-            .map_err(|_| DomainError::Validation("invalid cursor token".into()))?
+            .map_err(|_| invalid())?
             .with_timezone(&Utc);
-        let id = id_s
-            .parse::<i64>()
-            .map_err(|_| DomainError::Validation("invalid cursor token".into()))?;
+        let id = id_s.parse::<i64>().map_err(|_| invalid())?;
+        if id <= 0 {
+            return Err(invalid());
+        }
+
         Ok(Self::new(created_at, id))
     }
 }
@@ -1,17 +1,129 @@
 // src/domain/audit/cursor_tests.rs
 #[cfg(test)]
 mod tests {
-    use crate::domain::audit::cursor::Cursor;
-    use chrono::Utc;
+    use crate::domain::audit::cursor::AuditLogCursor;
+    use crate::domain::audit::entity::AuditLog;
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use proptest::prelude::*;
+
+    fn audit_log(id: i64, created_at: DateTime<Utc>) -> AuditLog {
+        AuditLog {
+            id,
+            user_id: None,
+            impersonator_id: None,
+            action: "test".into(),
+            resource_type: "article".into(),
+            resource_id: Some(id),
+            details: None,
+            ip_address: None,
+            user_agent: None,
+            created_at,
+        }
+    }
+
+    /// Mirrors the repository query: rows strictly after the cursor in
+    /// `(created_at DESC, id DESC)` order, fetching one extra row.
+    fn fetch_page(
+        sorted: &[AuditLog],
+        limit: u32,
+        cursor: Option<&AuditLogCursor>,
+    ) -> (Vec<AuditLog>, Option<AuditLogCursor>) {
+        let mut items: Vec<AuditLog> = sorted
+            .iter()
+            .filter(|log| cursor.is_none_or(|c| (log.created_at, log.id) < (c.created_at, c.id)))
+            .take(limit as usize + 1)
+            .cloned()
+            .collect();
+        let next = AuditLogCursor::trim_page(&mut items, limit);
+        (items, next)
+    }
 
     #[test]
     fn cursor_encode_decode_roundtrip() {
         let now = Utc::now();
         let id = 42i64;
-        let c = Cursor::new(now, id);
+        let c = AuditLogCursor::new(now, id);
         let token = c.encode();
-        let decoded = Cursor::decode(&token).expect("decode should succeed");
-        assert_eq!(decoded.id, id);
-        assert_eq!(decoded.created_at.timestamp(), now.timestamp());
+        let decoded = AuditLogCursor::decode(&token).expect("decode should succeed");
+        assert_eq!(decoded, c);
+    }
+
+    #[test]
+    fn next_cursor_uses_last_item_in_current_page() {
+        let now = Utc::now();
+        let third = audit_log(3, now - Duration::minutes(3));
+        let mut items = vec![
+            audit_log(1, now - Duration::minutes(1)),
+            audit_log(2, now - Duration::minutes(2)),
+            third.clone(),
+        ];
+
+        let next_cursor = AuditLogCursor::trim_page(&mut items, 2).expect("next cursor");
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(next_cursor, AuditLogCursor::after(&items[1]));
+        assert_ne!(next_cursor.id, third.id);
+    }
+
+    #[test]
+    fn malformed_tokens_are_validation_errors() {
+        use crate::domain::errors::DomainError;
+        use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+
+        let cases = [
+            "not base64!".to_string(),
+            URL_SAFE_NO_PAD.encode("no-separator"),
+            URL_SAFE_NO_PAD.encode("yesterday|1"),
+            URL_SAFE_NO_PAD.encode("2024-01-01T00:00:00Z|abc"),
+            URL_SAFE_NO_PAD.encode("2024-01-01T00:00:00Z|0"),
+        ];
+        for token in cases {
+            assert!(
+                matches!(
+                    AuditLogCursor::decode(&token),
+                    Err(DomainError::Validation(_))
+                ),
+                "{token} should be rejected"
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn pages_never_overlap_or_skip(
+            offsets in prop::collection::vec((0i64..4, 0u32..3), 0..40),
+            limit in 1u32..7,
+        ) {
+            let base = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+            let mut logs: Vec<AuditLog> = offsets
+                .iter()
+                .zip(1i64..)
+                .map(|(&(secs, micros), id)| {
+                    let created_at = base
+                        + Duration::seconds(secs)
+                        + Duration::microseconds(i64::from(micros));
+                    audit_log(id, created_at)
+                })
+                .collect();
+            logs.sort_by_key(|log| std::cmp::Reverse((log.created_at, log.id)));
+
+            let mut seen = Vec::new();
+            let mut cursor: Option<AuditLogCursor> = None;
+            loop {
+                let (page, next) = fetch_page(&logs, limit, cursor.as_ref());
+                prop_assert!(page.len() <= limit as usize);
+                seen.extend(page.iter().map(|log| log.id));
+                match next {
+                    Some(next) => {
+                        prop_assert_eq!(page.len(), limit as usize);
+                        cursor = Some(AuditLogCursor::decode(&next.encode()).unwrap());
+                    }
+                    None => break,
+                }
+            }
+
+            let expected: Vec<i64> = logs.iter().map(|log| log.id).collect();
+            prop_assert_eq!(seen, expected);
+        }
     }
 }
@@ -5,6 +5,8 @@ use chrono::{DateTime, Utc};
 #[derive(Debug, Clone)]
 pub struct NewAuditLog {
     pub user_id: Option<UserId>,
+    /// Admin who acted as `user_id` through an impersonation token.
+    pub impersonator_id: Option<UserId>,
     pub action: String,
     pub resource_type: String,
     pub resource_id: Option<i64>,
@@ -17,6 +19,7 @@ pub struct NewAuditLog {
 pub struct AuditLog {
     pub id: i64,
     pub user_id: Option<UserId>,
+    pub impersonator_id: Option<UserId>,
     pub action: String,
     pub resource_type: String,
     pub resource_id: Option<i64>,
@@ -0,0 +1,57 @@
+// src/domain/audit/filter.rs
+use crate::domain::audit::entity::AuditLog;
+use chrono::{DateTime, Utc};
+
+/// Conditions for [`AuditLogRepository::search`]; an empty filter matches
+/// every entry and the conditions that are set must all hold.
+///
+/// [`AuditLogRepository::search`]: crate::domain::audit::repository::AuditLogRepository::search
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[must_use]
+pub struct AuditLogFilter {
+    /// Entries the user made, or that someone made about their account
+    /// (a `user` resource with their id), such as a lockout.
+    pub involving_user: Option<i64>,
+    /// Entries with one of these actions; empty allows any action.
+    pub actions: Vec<String>,
+    /// Entries created at or after this instant.
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl AuditLogFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub const fn involving_user(mut self, user_id: i64) -> Self {
+        self.involving_user = Some(user_id);
+        self
+    }
+
+    pub fn actions<I, S>(mut self, actions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.actions = actions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub const fn since(mut self, since: DateTime<Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Whether `log` satisfies every condition, for stores that filter in
+    /// memory.
+    #[must_use]
+    pub fn matches(&self, log: &AuditLog) -> bool {
+        let involves = self.involving_user.is_none_or(|user_id| {
+            log.user_id.map(i64::from) == Some(user_id)
+                || (log.resource_type == "user" && log.resource_id == Some(user_id))
+        });
+        involves
+            && (self.actions.is_empty() || self.actions.contains(&log.action))
+            && self.since.is_none_or(|since| log.created_at >= since)
+    }
+}
@@ -1,4 +1,6 @@
 // src/domain/audit/mod.rs
 pub mod cursor;
+mod cursor_tests;
 pub mod entity;
+pub mod filter;
 pub mod repository;
@@ -1,30 +1,56 @@
 // src/domain/audit/repository.rs
-use crate::async_support::BoxFuture;
-use crate::domain::audit::cursor::Cursor;
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::audit::cursor::AuditLogCursor;
 use crate::domain::audit::entity::{AuditLog, NewAuditLog};
+use crate::domain::audit::filter::AuditLogFilter;
 use crate::domain::errors::DomainResult;
 
 pub trait AuditLogRepository: Send + Sync {
     fn insert(&self, log: NewAuditLog) -> BoxFuture<'_, DomainResult<()>>;
 
+    /// Insert several entries; stops at the first failure.
+    fn insert_batch(&self, logs: Vec<NewAuditLog>) -> BoxFuture<'_, DomainResult<()>> {
+        boxed(async move {
+            for log in logs {
+                self.insert(log).await?;
+            }
+            Ok(())
+        })
+    }
+
     fn list(
         &self,
         limit: u32,
-        cursor: Option<Cursor>,
-    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<String>)>>;
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>>;
 
     fn find_by_user(
         &self,
         user_id: i64,
         limit: u32,
-        cursor: Option<Cursor>,
-    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<String>)>>;
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>>;
 
     fn find_by_resource<'a>(
         &'a self,
         resource_type: &'a str,
         resource_id: i64,
         limit: u32,
-        cursor: Option<Cursor>,
-    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<String>)>>;
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>>;
+
+    fn find_by_action<'a>(
+        &'a self,
+        action: &'a str,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>>;
+
+    /// Entries matching every condition in `filter`, newest first.
+    fn search<'a>(
+        &'a self,
+        filter: &'a AuditLogFilter,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>>;
 }
@@ -9,6 +9,13 @@ pub enum DomainError {
     Validation(String),
     #[error("conflict: {0}")]
     Conflict(String),
+    /// Another article already uses the slug. `slug` is the taken value when
+    /// the store reports it.
+    #[error("conflict: slug already exists")]
+    SlugTaken { slug: Option<String> },
+    /// Another user already has the username.
+    #[error("conflict: username already exists")]
+    UsernameTaken { username: Option<String> },
     #[error("not found: {0}")]
     NotFound(String),
     #[error("persistence error: {0}")]
@@ -0,0 +1,96 @@
+// src/domain/events.rs
+//! Facts about articles and users, published once the change they describe
+//! has been stored.
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::{Article, ArticleId, ArticleVisibility, UserId};
+
+/// Something that happened to an aggregate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    ArticleCreated(ArticleId),
+    /// Content or metadata changed, including reverts to a revision.
+    ArticleUpdated(ArticleId),
+    ArticlePublished(ArticleId),
+    ArticleUnpublished(ArticleId),
+    ArticleDeleted(ArticleId),
+    UserRegistered(UserId),
+    /// Role or active state changed.
+    UserUpdated(UserId),
+    UserPasswordChanged(UserId),
+    /// The account was anonymized once its deletion grace period ended.
+    UserDeleted(UserId),
+}
+
+impl EventKind {
+    /// Dotted name subscribers can filter and log by, such as
+    /// `article.published`.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::ArticleCreated(_) => "article.created",
+            Self::ArticleUpdated(_) => "article.updated",
+            Self::ArticlePublished(_) => "article.published",
+            Self::ArticleUnpublished(_) => "article.unpublished",
+            Self::ArticleDeleted(_) => "article.deleted",
+            Self::UserRegistered(_) => "user.registered",
+            Self::UserUpdated(_) => "user.updated",
+            Self::UserPasswordChanged(_) => "user.password_changed",
+            Self::UserDeleted(_) => "user.deleted",
+        }
+    }
+}
+
+/// The article an article event is about, as it stood after the change; for
+/// deletions, just before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArticleSnapshot {
+    pub slug: String,
+    /// The slug the change replaced, when it renamed the article.
+    pub previous_slug: Option<String>,
+    pub title: String,
+    pub published: bool,
+    pub published_at: Option<DateTime<Utc>>,
+    pub visibility: ArticleVisibility,
+    pub author_id: UserId,
+}
+
+impl From<&Article> for ArticleSnapshot {
+    fn from(article: &Article) -> Self {
+        Self {
+            slug: article.slug.as_str().to_owned(),
+            previous_slug: None,
+            title: article.title.as_str().to_owned(),
+            published: article.published,
+            published_at: article.published_at,
+            visibility: article.visibility,
+            author_id: article.author_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DomainEvent {
+    pub kind: EventKind,
+    pub occurred_at: DateTime<Utc>,
+    /// Set on article events published by the article commands.
+    pub article: Option<ArticleSnapshot>,
+}
+
+impl DomainEvent {
+    #[must_use]
+    pub const fn new(kind: EventKind, occurred_at: DateTime<Utc>) -> Self {
+        Self {
+            kind,
+            occurred_at,
+            article: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_article(mut self, article: ArticleSnapshot) -> Self {
+        self.article = Some(article);
+        self
+    }
+}
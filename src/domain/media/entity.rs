@@ -0,0 +1,198 @@
+//! Files uploaded for use in articles, such as images.
+//!
+//! An asset records what was uploaded and by whom; its bytes live in blob
+//! storage under `storage_key`.
+
+use crate::domain::UserId;
+use crate::domain::errors::{DomainError, DomainResult};
+use chrono::{DateTime, Utc};
+
+/// Name given to uploads that arrive without a usable file name.
+pub const DEFAULT_FILE_NAME: &str = "upload";
+
+/// Content types recognised from their leading bytes, with those bytes.
+const SIGNATURES: &[(&str, &[u8])] = &[
+    ("image/png", b"\x89PNG\r\n\x1a\n"),
+    ("image/jpeg", b"\xff\xd8\xff"),
+    ("image/gif", b"GIF87a"),
+    ("image/gif", b"GIF89a"),
+    ("application/pdf", b"%PDF-"),
+];
+
+/// The content type `content` starts like, if it is one of the recognised
+/// formats.
+#[must_use]
+pub fn sniff_content_type(content: &[u8]) -> Option<&'static str> {
+    // WebP is a RIFF container: "RIFF", a 4-byte length, then "WEBP".
+    if content.len() >= 12 && content.starts_with(b"RIFF") && &content[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+    SIGNATURES
+        .iter()
+        .find(|(_, signature)| content.starts_with(signature))
+        .map(|(content_type, _)| *content_type)
+}
+
+#[derive(Debug, Clone)]
+pub struct MediaAsset {
+    pub id: i64,
+    pub uploaded_by: UserId,
+    pub file_name: String,
+    /// Essence only, e.g. `image/png`, without parameters.
+    pub content_type: String,
+    pub size_bytes: u64,
+    /// Where blob storage keeps the bytes.
+    pub storage_key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewMediaAsset {
+    pub uploaded_by: UserId,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: u64,
+    pub storage_key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NewMediaAsset {
+    /// Longest kept file name, in characters.
+    pub const MAX_FILE_NAME_CHARS: usize = 255;
+
+    /// Describe an upload of `content`, declared as `content_type`.
+    ///
+    /// The file name is reduced to its last path segment without control
+    /// characters or quotes, falling back to [`DEFAULT_FILE_NAME`]. The
+    /// content type loses its parameters and is lowercased.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `content` is empty, the content type is not a
+    /// `type/subtype` pair, or `content` starts like a recognised format
+    /// other than the declared one (or the declared format is recognised
+    /// and `content` does not start like it).
+    pub fn new(
+        uploaded_by: UserId,
+        file_name: Option<&str>,
+        content_type: &str,
+        content: &[u8],
+        storage_key: String,
+        created_at: DateTime<Utc>,
+    ) -> DomainResult<Self> {
+        if content.is_empty() {
+            return Err(DomainError::Validation("the uploaded file is empty".into()));
+        }
+        let content_type = normalize_content_type(content_type)?;
+        let sniffed = sniff_content_type(content);
+        let recognised = SIGNATURES.iter().any(|(known, _)| *known == content_type)
+            || content_type == "image/webp";
+        if (sniffed.is_some() || recognised) && sniffed != Some(content_type.as_str()) {
+            return Err(DomainError::Validation(format!(
+                "the file content does not match its content type {content_type}"
+            )));
+        }
+        Ok(Self {
+            uploaded_by,
+            file_name: file_name.map_or_else(|| DEFAULT_FILE_NAME.to_string(), sanitize_file_name),
+            content_type,
+            size_bytes: content.len() as u64,
+            storage_key,
+            created_at,
+        })
+    }
+}
+
+fn normalize_content_type(value: &str) -> DomainResult<String> {
+    let essence = value
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let token = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$&^_.+-".contains(c))
+    };
+    match essence.split_once('/') {
+        Some((kind, subtype)) if token(kind) && token(subtype) => Ok(essence),
+        _ => Err(DomainError::Validation(format!(
+            "invalid content type '{value}'"
+        ))),
+    }
+}
+
+fn sanitize_file_name(value: &str) -> String {
+    let last = value.rsplit(['/', '\\']).next().unwrap_or_default();
+    let name: String = last
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .take(NewMediaAsset::MAX_FILE_NAME_CHARS)
+        .collect();
+    let name = name.trim();
+    if name.is_empty() || name == "." || name == ".." {
+        DEFAULT_FILE_NAME.to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    fn upload(
+        file_name: Option<&str>,
+        content_type: &str,
+        content: &[u8],
+    ) -> DomainResult<NewMediaAsset> {
+        NewMediaAsset::new(
+            UserId::new(1).unwrap(),
+            file_name,
+            content_type,
+            content,
+            "key".into(),
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn file_names_lose_paths_and_unsafe_characters() {
+        let asset = upload(Some("C:\\photos\\../cat \"1\".png"), "image/png", PNG).unwrap();
+        assert_eq!(asset.file_name, "cat 1.png");
+        assert_eq!(
+            upload(Some(" .. "), "image/png", PNG).unwrap().file_name,
+            DEFAULT_FILE_NAME
+        );
+        assert_eq!(
+            upload(None, "image/png", PNG).unwrap().file_name,
+            DEFAULT_FILE_NAME
+        );
+    }
+
+    #[test]
+    fn content_must_match_recognised_content_types() {
+        let asset = upload(None, "Image/PNG; charset=binary", PNG).unwrap();
+        assert_eq!(asset.content_type, "image/png");
+        assert_eq!(asset.size_bytes, PNG.len() as u64);
+        assert!(upload(None, "image/jpeg", PNG).is_err());
+        assert!(upload(None, "image/png", b"<svg/>").is_err());
+        assert!(upload(None, "text/plain", PNG).is_err());
+        assert!(upload(None, "text/plain", b"hello").is_ok());
+        assert!(upload(None, "image/png", b"").is_err());
+        assert!(upload(None, "png", PNG).is_err());
+    }
+
+    #[test]
+    fn webp_is_recognised_inside_its_riff_container() {
+        assert_eq!(
+            sniff_content_type(b"RIFF\x10\0\0\0WEBPVP8 "),
+            Some("image/webp")
+        );
+        assert_eq!(sniff_content_type(b"RIFF\x10\0\0\0WAVEfmt "), None);
+    }
+}
@@ -0,0 +1,3 @@
+// src/domain/media/mod.rs
+pub mod entity;
+pub mod repository;
@@ -0,0 +1,11 @@
+// src/domain/media/repository.rs
+use crate::async_support::BoxFuture;
+use crate::domain::errors::DomainResult;
+use crate::domain::media::entity::{MediaAsset, NewMediaAsset};
+
+pub trait Repo: Send + Sync {
+    /// Record an asset whose bytes are already stored.
+    fn insert(&self, asset: NewMediaAsset) -> BoxFuture<'_, DomainResult<MediaAsset>>;
+
+    fn find_by_id(&self, id: i64) -> BoxFuture<'_, DomainResult<Option<MediaAsset>>>;
+}
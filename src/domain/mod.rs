@@ -1,18 +1,56 @@
 // src/domain/mod.rs
+pub mod access;
 pub mod article;
 pub mod audit;
 pub mod errors;
+pub mod events;
+pub mod media;
+pub mod moderation;
 pub mod user;
+pub mod webhook;
 
-pub use article::entity::{Article, ArticleUpdate, NewArticle};
+/// Largest page any repository returns, whatever page caps are configured.
+pub const PAGE_LIMIT_CEILING: u32 = 500;
+
+pub use access::entity::{AccessRule, IpRange, NewAccessRule, RouteGroup, RuleAction, RuleTarget};
+pub use access::repository::Repo as AccessRuleRepository;
+pub use article::custom_fields::{
+    CustomFields, FieldDefinition as CustomFieldDefinition, FieldType as CustomFieldType,
+};
+pub use article::entity::{
+    Article, ArticleUpdate, Contributor as ArticleContributor, FACET_LIMIT, FacetCount, NewArticle,
+    PrivateScope, SearchFacets, SearchHit as ArticleSearchHit,
+};
 pub use article::repository::{
+    ContributorRepo as ContributorRepository, CustomFieldRepo as CustomFieldRepository,
     ReadRepo as ArticleReadRepository, RevisionRepo as ArticleRevisionRepository,
     WriteRepo as ArticleWriteRepository,
 };
 pub use article::revision::{Parts as ArticleRevisionParts, Revision as ArticleRevision};
 pub use article::value_objects::{
-    ArticleBody, ArticleId, ArticleListCursor, ArticleSlug, ArticleTitle,
+    ArticleBody, ArticleId, ArticleListCursor, ArticlePopularityCursor, ArticleSlug, ArticleTitle,
+    Tag, Visibility as ArticleVisibility,
 };
+pub use events::{ArticleSnapshot, DomainEvent, EventKind};
+pub use media::entity::{MediaAsset, NewMediaAsset};
+pub use media::repository::Repo as MediaRepository;
+pub use moderation::entity::{
+    CaseStatus, Decision as ModerationDecision, ModerationCase, NewReport, Report, ReportReason,
+    Resolution,
+};
+pub use moderation::repository::Repo as ModerationRepository;
+pub use user::blocks::{BlockList, UserBlock};
+pub use user::deletion::{AccountDeletions, PendingDeletion};
 pub use user::entity::{NewUser, User, UserUpdate};
+pub use user::password_history::PasswordHistory;
 pub use user::repository::Repo as UserRepository;
-pub use user::value_objects::{Capability, PasswordHash, Role, UserId, UserListCursor, Username};
+pub use user::roles::{CustomRole, RoleName, RoleStore};
+pub use user::value_objects::{
+    Capability, Email, LoginIdentifier, PasswordHash, Role, Timezone, UserId, UserListCursor,
+    Username,
+};
+pub use webhook::entity::{
+    DeliveryAttempt as WebhookDeliveryAttempt, NewDeliveryAttempt as NewWebhookDeliveryAttempt,
+    NewWebhook, Webhook, WebhookEvent, WebhookUpdate,
+};
+pub use webhook::repository::Repo as WebhookRepository;
@@ -0,0 +1,277 @@
+// src/domain/moderation/entity.rs
+//! Reader reports about articles and the cases moderators work through.
+//!
+//! Reports about the same article collect in one open case until a moderator
+//! resolves it; a later report opens a new case.
+
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{ArticleId, UserId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Why a reader flagged an article.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    Misinformation,
+    Illegal,
+    Other,
+}
+
+impl ReportReason {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Spam => "spam",
+            Self::Harassment => "harassment",
+            Self::Misinformation => "misinformation",
+            Self::Illegal => "illegal",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl FromStr for ReportReason {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "spam" => Ok(Self::Spam),
+            "harassment" => Ok(Self::Harassment),
+            "misinformation" => Ok(Self::Misinformation),
+            "illegal" => Ok(Self::Illegal),
+            "other" => Ok(Self::Other),
+            other => Err(DomainError::Validation(format!(
+                "unknown report reason '{other}'"
+            ))),
+        }
+    }
+}
+
+/// What a moderator decided about a case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    /// The reports were unfounded; nothing changes.
+    Dismiss,
+    /// Take the article down.
+    Unpublish,
+    /// Take the article down and deactivate its author.
+    BanAuthor,
+}
+
+impl Resolution {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Dismiss => "dismiss",
+            Self::Unpublish => "unpublish",
+            Self::BanAuthor => "ban_author",
+        }
+    }
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Resolution {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dismiss" => Ok(Self::Dismiss),
+            "unpublish" => Ok(Self::Unpublish),
+            "ban_author" => Ok(Self::BanAuthor),
+            other => Err(DomainError::Validation(format!(
+                "unknown resolution '{other}'"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CaseStatus {
+    Open,
+    Resolved,
+}
+
+impl CaseStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Resolved => "resolved",
+        }
+    }
+}
+
+impl FromStr for CaseStatus {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(Self::Open),
+            "resolved" => Ok(Self::Resolved),
+            other => Err(DomainError::Validation(format!(
+                "unknown case status '{other}'"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub reason: ReportReason,
+    pub details: Option<String>,
+    pub reported_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewReport {
+    pub article_id: ArticleId,
+    pub report: Report,
+}
+
+impl NewReport {
+    /// Longest accepted free-text explanation, in characters.
+    pub const MAX_DETAILS_CHARS: usize = 1000;
+
+    /// Create a report, dropping blank details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `details` is longer than
+    /// [`Self::MAX_DETAILS_CHARS`].
+    pub fn new(
+        article_id: ArticleId,
+        reason: ReportReason,
+        details: Option<String>,
+        reported_at: DateTime<Utc>,
+    ) -> DomainResult<Self> {
+        let details = details
+            .map(|details| details.trim().to_owned())
+            .filter(|details| !details.is_empty());
+        if details
+            .as_ref()
+            .is_some_and(|details| details.chars().count() > Self::MAX_DETAILS_CHARS)
+        {
+            return Err(DomainError::Validation(format!(
+                "report details must be at most {} characters",
+                Self::MAX_DETAILS_CHARS
+            )));
+        }
+        Ok(Self {
+            article_id,
+            report: Report {
+                reason,
+                details,
+                reported_at,
+            },
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Decision {
+    pub resolution: Resolution,
+    pub resolved_by: UserId,
+    pub note: Option<String>,
+    pub resolved_at: DateTime<Utc>,
+}
+
+impl Decision {
+    /// Longest accepted moderator note, in characters.
+    pub const MAX_NOTE_CHARS: usize = 1000;
+}
+
+#[derive(Debug, Clone)]
+pub struct ModerationCase {
+    pub id: i64,
+    pub article_id: ArticleId,
+    /// Oldest first.
+    pub reports: Vec<Report>,
+    pub opened_at: DateTime<Utc>,
+    /// `None` while the case is open.
+    pub decision: Option<Decision>,
+}
+
+impl ModerationCase {
+    #[must_use]
+    pub const fn status(&self) -> CaseStatus {
+        if self.decision.is_some() {
+            CaseStatus::Resolved
+        } else {
+            CaseStatus::Open
+        }
+    }
+
+    /// Record the moderator's decision.
+    ///
+    /// # Errors
+    ///
+    /// Returns a conflict if the case is already resolved.
+    pub fn resolve(&mut self, decision: Decision) -> DomainResult<()> {
+        if self.decision.is_some() {
+            return Err(DomainError::Conflict(format!(
+                "moderation case {} is already resolved",
+                self.id
+            )));
+        }
+        self.decision = Some(decision);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case() -> ModerationCase {
+        ModerationCase {
+            id: 7,
+            article_id: ArticleId::new(1).unwrap(),
+            reports: Vec::new(),
+            opened_at: Utc::now(),
+            decision: None,
+        }
+    }
+
+    fn decision() -> Decision {
+        Decision {
+            resolution: Resolution::Dismiss,
+            resolved_by: UserId::new(1).unwrap(),
+            note: None,
+            resolved_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn cases_resolve_once() {
+        let mut case = case();
+        assert_eq!(case.status(), CaseStatus::Open);
+        case.resolve(decision()).unwrap();
+        assert_eq!(case.status(), CaseStatus::Resolved);
+        assert!(matches!(
+            case.resolve(decision()),
+            Err(DomainError::Conflict(_))
+        ));
+    }
+
+    #[test]
+    fn report_details_are_trimmed_and_bounded() {
+        let id = ArticleId::new(1).unwrap();
+        let blank = NewReport::new(id, ReportReason::Spam, Some("  ".into()), Utc::now()).unwrap();
+        assert_eq!(blank.report.details, None);
+        let long = "x".repeat(NewReport::MAX_DETAILS_CHARS + 1);
+        assert!(NewReport::new(id, ReportReason::Other, Some(long), Utc::now()).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+// src/domain/moderation/mod.rs
+pub mod entity;
+pub mod repository;
@@ -0,0 +1,24 @@
+// src/domain/moderation/repository.rs
+use crate::async_support::BoxFuture;
+use crate::domain::errors::DomainResult;
+use crate::domain::moderation::entity::{CaseStatus, ModerationCase, NewReport};
+
+pub trait Repo: Send + Sync {
+    /// Add a report to the article's open case, opening one if there is none.
+    fn report(&self, report: NewReport) -> BoxFuture<'_, DomainResult<ModerationCase>>;
+
+    fn find_by_id(&self, id: i64) -> BoxFuture<'_, DomainResult<Option<ModerationCase>>>;
+
+    /// Up to `limit` cases with `status`, oldest first, starting after the
+    /// case with id `after`.
+    fn list(
+        &self,
+        status: CaseStatus,
+        limit: u32,
+        after: Option<i64>,
+    ) -> BoxFuture<'_, DomainResult<Vec<ModerationCase>>>;
+
+    /// Persist the decision on `case`. Returns `false`, changing nothing, if
+    /// the case was resolved by someone else in the meantime.
+    fn save_decision<'a>(&'a self, case: &'a ModerationCase) -> BoxFuture<'a, DomainResult<bool>>;
+}
@@ -0,0 +1,59 @@
+// src/domain/user/blocks.rs
+//! Per-user lists of people a user does not want to hear from.
+
+use crate::async_support::BoxFuture;
+use crate::domain::UserId;
+use crate::domain::errors::{DomainError, DomainResult};
+use chrono::{DateTime, Utc};
+
+/// The listed user may not comment on or mention the list's owner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserBlock {
+    pub owner: UserId,
+    pub target: UserId,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserBlock {
+    /// # Errors
+    ///
+    /// Returns an error if `owner` and `target` are the same user.
+    pub fn new(owner: UserId, target: UserId, created_at: DateTime<Utc>) -> DomainResult<Self> {
+        if owner == target {
+            return Err(DomainError::Validation(
+                "users cannot block themselves".into(),
+            ));
+        }
+        Ok(Self {
+            owner,
+            target,
+            created_at,
+        })
+    }
+}
+
+pub trait BlockList: Send + Sync {
+    /// Add `block`, keeping the existing entry if the pair is already
+    /// listed. Returns the stored entry.
+    fn add(&self, block: UserBlock) -> BoxFuture<'_, DomainResult<UserBlock>>;
+
+    /// Returns `false` if there was nothing to remove.
+    fn remove(&self, owner: UserId, target: UserId) -> BoxFuture<'_, DomainResult<bool>>;
+
+    /// Everyone `owner` has blocked, most recent first.
+    fn list(&self, owner: UserId) -> BoxFuture<'_, DomainResult<Vec<UserBlock>>>;
+
+    fn find(&self, owner: UserId, target: UserId)
+    -> BoxFuture<'_, DomainResult<Option<UserBlock>>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn users_cannot_block_themselves() {
+        let id = UserId::new(3).unwrap();
+        assert!(UserBlock::new(id, id, Utc::now()).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+// src/domain/user/deletion.rs
+//! Accounts their owners asked to delete, kept disabled for a grace period
+//! before they are anonymized for good.
+
+use crate::async_support::BoxFuture;
+use crate::domain::UserId;
+use crate::domain::errors::DomainResult;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingDeletion {
+    pub user_id: UserId,
+    pub requested_at: DateTime<Utc>,
+    /// When the account is anonymized unless the deletion is cancelled.
+    pub purge_after: DateTime<Utc>,
+    /// Who gets the user's articles; `None` leaves them with the anonymized
+    /// account.
+    pub transfer_to: Option<UserId>,
+}
+
+pub trait AccountDeletions: Send + Sync {
+    /// Record `deletion`, replacing any pending one for the same user.
+    fn schedule(&self, deletion: PendingDeletion) -> BoxFuture<'_, DomainResult<()>>;
+
+    /// Forget `user_id`'s pending deletion. Returns `false` if there was
+    /// none.
+    fn remove(&self, user_id: UserId) -> BoxFuture<'_, DomainResult<bool>>;
+
+    fn find(&self, user_id: UserId) -> BoxFuture<'_, DomainResult<Option<PendingDeletion>>>;
+
+    /// Up to `limit` deletions whose grace period ended by `now`, oldest
+    /// first.
+    fn due(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> BoxFuture<'_, DomainResult<Vec<PendingDeletion>>>;
+}
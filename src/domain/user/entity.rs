@@ -1,6 +1,6 @@
 // src/domain/user/entity.rs
 use crate::domain::errors::DomainResult;
-use crate::domain::user::value_objects::{PasswordHash, Role, UserId, Username};
+use crate::domain::user::value_objects::{Email, PasswordHash, Role, Timezone, UserId, Username};
 use chrono::{DateTime, Utc};
 
 #[derive(Debug, Clone)]
@@ -11,6 +11,12 @@ pub struct User {
     pub role: Role,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
+    pub timezone: Option<Timezone>,
+    /// Set for imported accounts that must choose a new password.
+    pub password_reset_required: bool,
+    pub email: Option<Email>,
+    /// Whether the user has proven they receive mail at `email`.
+    pub email_verified: bool,
 }
 
 impl User {
@@ -38,6 +44,7 @@ pub struct NewUser {
     pub role: Role,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
+    pub password_reset_required: bool,
 }
 
 impl NewUser {
@@ -58,29 +65,54 @@ impl NewUser {
             role,
             is_active: true,
             created_at,
+            password_reset_required: false,
         })
     }
+
+    /// Require the user to choose a new password after first login.
+    #[must_use]
+    pub const fn requiring_password_reset(mut self) -> Self {
+        self.password_reset_required = true;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
 #[must_use]
 pub struct UserUpdate {
     pub id: UserId,
+    pub username: Option<Username>,
     pub is_active: Option<bool>,
     pub role: Option<Role>,
     pub password_hash: Option<PasswordHash>,
+    /// `Some(None)` clears the stored preference.
+    pub timezone: Option<Option<Timezone>>,
+    pub password_reset_required: Option<bool>,
+    /// `Some(None)` removes the address.
+    pub email: Option<Option<Email>>,
+    pub email_verified: Option<bool>,
 }
 
 impl UserUpdate {
     pub const fn new(id: UserId) -> Self {
         Self {
             id,
+            username: None,
             is_active: None,
             role: None,
             password_hash: None,
+            timezone: None,
+            password_reset_required: None,
+            email: None,
+            email_verified: None,
         }
     }
 
+    pub fn with_username(mut self, username: Username) -> Self {
+        self.username = Some(username);
+        self
+    }
+
     pub const fn with_is_active(mut self, is_active: bool) -> Self {
         self.is_active = Some(is_active);
         self
@@ -95,4 +127,24 @@ impl UserUpdate {
         self.password_hash = Some(password_hash);
         self
     }
+
+    pub const fn with_timezone(mut self, timezone: Option<Timezone>) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    pub const fn with_password_reset_required(mut self, required: bool) -> Self {
+        self.password_reset_required = Some(required);
+        self
+    }
+
+    pub fn with_email(mut self, email: Option<Email>) -> Self {
+        self.email = Some(email);
+        self
+    }
+
+    pub const fn with_email_verified(mut self, verified: bool) -> Self {
+        self.email_verified = Some(verified);
+        self
+    }
 }
@@ -1,4 +1,8 @@
 // src/domain/user/mod.rs
+pub mod blocks;
+pub mod deletion;
 pub mod entity;
+pub mod password_history;
 pub mod repository;
+pub mod roles;
 pub mod value_objects;
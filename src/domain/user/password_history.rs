@@ -0,0 +1,23 @@
+// src/domain/user/password_history.rs
+//! Hashes of passwords users had before, so they can be kept from going
+//! back to them.
+
+use crate::async_support::BoxFuture;
+use crate::domain::errors::DomainResult;
+use crate::domain::{PasswordHash, UserId};
+use chrono::{DateTime, Utc};
+
+pub trait PasswordHistory: Send + Sync {
+    /// Remember `hash` as a password `user` stopped using at `replaced_at`,
+    /// then forget all but their `keep` most recent former passwords.
+    fn record(
+        &self,
+        user: UserId,
+        hash: PasswordHash,
+        replaced_at: DateTime<Utc>,
+        keep: usize,
+    ) -> BoxFuture<'_, DomainResult<()>>;
+
+    /// Up to `limit` of `user`'s former password hashes, most recent first.
+    fn recent(&self, user: UserId, limit: usize) -> BoxFuture<'_, DomainResult<Vec<PasswordHash>>>;
+}
@@ -1,13 +1,22 @@
 // src/domain/user/repository.rs
-use crate::async_support::BoxFuture;
-use crate::domain::errors::DomainResult;
-use crate::domain::{NewUser, User, UserId, UserListCursor, UserUpdate, Username};
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{Email, NewUser, User, UserId, UserListCursor, UserUpdate, Username};
 
 pub trait Repo: Send + Sync {
     fn count(&self) -> BoxFuture<'_, DomainResult<u64>>;
 
     fn insert(&self, new_user: NewUser) -> BoxFuture<'_, DomainResult<User>>;
 
+    /// Atomically insert the first administrator.
+    ///
+    /// Returns `None` when the instance has already been bootstrapped, so
+    /// concurrent first registrations cannot all be promoted.
+    fn insert_bootstrap_admin(
+        &self,
+        new_user: NewUser,
+    ) -> BoxFuture<'_, DomainResult<Option<User>>>;
+
     fn find_by_username<'a>(
         &'a self,
         username: &'a Username,
@@ -15,6 +24,26 @@ pub trait Repo: Send + Sync {
 
     fn find_by_id(&self, id: UserId) -> BoxFuture<'_, DomainResult<Option<User>>>;
 
+    /// The user whose address is `email`. Addresses are unique per instance.
+    fn find_by_email<'a>(&'a self, email: &'a Email) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        let _ = email;
+        boxed(async { Ok(None) })
+    }
+
+    /// Load every user whose id is in `ids`, in no particular order. Unknown
+    /// ids are skipped. The default implementation issues one lookup per id.
+    fn find_by_ids<'a>(&'a self, ids: &'a [UserId]) -> BoxFuture<'a, DomainResult<Vec<User>>> {
+        boxed(async move {
+            let mut found = Vec::with_capacity(ids.len());
+            for id in ids {
+                if let Some(user) = self.find_by_id(*id).await? {
+                    found.push(user);
+                }
+            }
+            Ok(found)
+        })
+    }
+
     fn update(&self, update: UserUpdate) -> BoxFuture<'_, DomainResult<User>>;
 
     fn list_page<'a>(
@@ -23,4 +52,84 @@ pub trait Repo: Send + Sync {
         cursor: Option<UserListCursor>,
         search: Option<&'a str>,
     ) -> BoxFuture<'a, DomainResult<(Vec<User>, Option<UserListCursor>)>>;
+
+    /// Page of users matching `search` that sort immediately before `cursor`
+    /// (i.e. newer), returned newest-first like [`list_page`](Self::list_page).
+    /// The returned cursor points at the first item when even newer users
+    /// remain.
+    fn list_page_before<'a>(
+        &'a self,
+        limit: u32,
+        cursor: UserListCursor,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, Option<UserListCursor>)>> {
+        let _ = (limit, cursor, search);
+        boxed(async {
+            Err(DomainError::Validation(
+                "backward pagination is not supported".into(),
+            ))
+        })
+    }
+
+    /// Whether any user matching `search` sorts before `cursor` (i.e. is
+    /// newer) in the listing order. Used to decide if a previous page exists.
+    fn has_preceding<'a>(
+        &'a self,
+        cursor: &'a UserListCursor,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<bool>> {
+        let _ = (cursor, search);
+        boxed(async { Ok(false) })
+    }
+
+    /// Users matching `search` at `offset` in the listing order, newest
+    /// first, with the number of matches across all offsets.
+    fn list_offset<'a>(
+        &'a self,
+        offset: u64,
+        limit: u32,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, u64)>> {
+        let _ = (offset, limit, search);
+        boxed(async {
+            Err(DomainError::Validation(
+                "offset pagination is not supported".into(),
+            ))
+        })
+    }
+
+    /// Approximate number of users matching `search`. `None` when the
+    /// repository cannot provide one cheaply.
+    fn estimate_total<'a>(
+        &'a self,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<Option<u64>>> {
+        let _ = search;
+        boxed(async { Ok(None) })
+    }
+
+    /// The user linked to `subject` at the upstream identity `provider`.
+    fn find_by_federated_identity<'a>(
+        &'a self,
+        provider: &'a str,
+        subject: &'a str,
+    ) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        let _ = (provider, subject);
+        boxed(async { Ok(None) })
+    }
+
+    /// Link `subject` at `provider` to `user_id`.
+    fn link_federated_identity<'a>(
+        &'a self,
+        provider: &'a str,
+        subject: &'a str,
+        user_id: UserId,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        let _ = (provider, subject, user_id);
+        boxed(async {
+            Err(DomainError::Validation(
+                "federated identities are not supported".into(),
+            ))
+        })
+    }
 }
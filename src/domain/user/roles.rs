@@ -0,0 +1,241 @@
+// src/domain/user/roles.rs
+//! Roles defined by operators, each a named set of capabilities.
+//!
+//! A user given a custom role keeps their built-in [`Role`], but their
+//! tokens carry the custom role's capabilities instead of that role's
+//! defaults. Capabilities granted to a user individually are added on top of
+//! either.
+
+use crate::async_support::BoxFuture;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{Capability, Role, UserId};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Name of a custom role: lowercase letters, digits, `-` and `_`, starting
+/// with a letter. The built-in role names are reserved.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RoleName(String);
+
+impl RoleName {
+    /// Longest accepted name, in bytes.
+    pub const MAX_LEN: usize = 32;
+
+    /// # Errors
+    ///
+    /// Returns an error if the name is empty, too long, not of the accepted
+    /// characters, or names a built-in role.
+    pub fn new(name: &str) -> DomainResult<Self> {
+        let valid = name.len() <= Self::MAX_LEN
+            && name.starts_with(|c: char| c.is_ascii_lowercase())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_'));
+        if !valid {
+            return Err(DomainError::Validation(format!(
+                "role name '{name}' must be 1 to {} lowercase letters, digits, '-' or '_', starting with a letter",
+                Self::MAX_LEN
+            )));
+        }
+        if Role::ALL.iter().any(|role| role.as_str() == name) {
+            return Err(DomainError::Validation(format!(
+                "role name '{name}' is reserved"
+            )));
+        }
+        Ok(Self(name.to_string()))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RoleName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomRole {
+    pub name: RoleName,
+    pub description: Option<String>,
+    /// Sorted, without repeats; only [known](Capability::known) ones.
+    pub capabilities: Vec<Capability>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CustomRole {
+    /// Longest accepted description, in characters.
+    pub const MAX_DESCRIPTION_CHARS: usize = 500;
+
+    /// A role granting `capabilities`. Repeats are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the description is too long or a capability is
+    /// not one the server checks.
+    pub fn new(
+        name: RoleName,
+        description: Option<String>,
+        capabilities: Vec<Capability>,
+        now: DateTime<Utc>,
+    ) -> DomainResult<Self> {
+        Ok(Self {
+            name,
+            description: validate_description(description)?,
+            capabilities: validate_capabilities(capabilities)?,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// This role with its description and capabilities replaced, at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`Self::new`].
+    pub fn redefine(
+        self,
+        description: Option<String>,
+        capabilities: Vec<Capability>,
+        now: DateTime<Utc>,
+    ) -> DomainResult<Self> {
+        Ok(Self {
+            description: validate_description(description)?,
+            capabilities: validate_capabilities(capabilities)?,
+            updated_at: now,
+            ..self
+        })
+    }
+}
+
+fn validate_description(description: Option<String>) -> DomainResult<Option<String>> {
+    let description = description
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty());
+    if let Some(text) = &description
+        && text.chars().count() > CustomRole::MAX_DESCRIPTION_CHARS
+    {
+        return Err(DomainError::Validation(format!(
+            "role description must be at most {} characters",
+            CustomRole::MAX_DESCRIPTION_CHARS
+        )));
+    }
+    Ok(description)
+}
+
+fn validate_capabilities(capabilities: Vec<Capability>) -> DomainResult<Vec<Capability>> {
+    let wanted: HashSet<Capability> = capabilities.into_iter().collect();
+    let known = Capability::known();
+    if let Some(unknown) = wanted.iter().find(|cap| !known.contains(cap)) {
+        return Err(DomainError::Validation(format!(
+            "unknown capability '{unknown}'"
+        )));
+    }
+    Ok(known
+        .into_iter()
+        .filter(|cap| wanted.contains(cap))
+        .collect())
+}
+
+pub trait RoleStore: Send + Sync {
+    /// Every custom role, by name.
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<CustomRole>>>;
+
+    fn find<'a>(&'a self, name: &'a RoleName) -> BoxFuture<'a, DomainResult<Option<CustomRole>>>;
+
+    /// Store `role`, replacing the description and capabilities of one with
+    /// the same name. Returns the stored role.
+    fn save(&self, role: CustomRole) -> BoxFuture<'_, DomainResult<CustomRole>>;
+
+    /// Remove the role; its users fall back to their built-in role. Returns
+    /// `false` if there was no such role.
+    fn delete<'a>(&'a self, name: &'a RoleName) -> BoxFuture<'a, DomainResult<bool>>;
+
+    /// Give `user` the role `name`, or take their custom role away with
+    /// `None`.
+    ///
+    /// Fails with `NotFound` if the user or the role does not exist.
+    fn assign<'a>(
+        &'a self,
+        user: UserId,
+        name: Option<&'a RoleName>,
+    ) -> BoxFuture<'a, DomainResult<()>>;
+
+    /// The custom role given to `user`, if any.
+    fn role_of(&self, user: UserId) -> BoxFuture<'_, DomainResult<Option<CustomRole>>>;
+
+    /// Give `user` `capability` on top of what their role grants. Returns
+    /// `false` if they already had it.
+    ///
+    /// Fails with `NotFound` if the user does not exist.
+    fn grant_capability<'a>(
+        &'a self,
+        user: UserId,
+        capability: &'a Capability,
+    ) -> BoxFuture<'a, DomainResult<bool>>;
+
+    /// Take back a capability given with
+    /// [`grant_capability`](Self::grant_capability). Returns `false` if
+    /// `user` had not been given it.
+    fn revoke_capability<'a>(
+        &'a self,
+        user: UserId,
+        capability: &'a Capability,
+    ) -> BoxFuture<'a, DomainResult<bool>>;
+
+    /// Capabilities given to `user` individually, sorted by name.
+    fn capabilities_of(&self, user: UserId) -> BoxFuture<'_, DomainResult<Vec<Capability>>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_checked_and_builtin_ones_reserved() {
+        assert_eq!(RoleName::new("editor-2").unwrap().as_str(), "editor-2");
+        for name in [
+            "",
+            "Editor",
+            "2nd",
+            "a b",
+            "admin",
+            "author",
+            &"x".repeat(33),
+        ] {
+            assert!(RoleName::new(name).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn capabilities_must_be_known_and_are_sorted() {
+        let name = RoleName::new("editor").unwrap();
+        let role = CustomRole::new(
+            name.clone(),
+            Some("  ".into()),
+            vec![
+                Capability::new("articles", "publish"),
+                Capability::new("articles", "create"),
+                Capability::new("articles", "publish"),
+            ],
+            Utc::now(),
+        )
+        .unwrap();
+        assert_eq!(role.description, None);
+        assert_eq!(
+            role.capabilities,
+            vec![
+                Capability::new("articles", "create"),
+                Capability::new("articles", "publish"),
+            ]
+        );
+
+        let unknown = vec![Capability::new("articles", "launch")];
+        assert!(CustomRole::new(name, None, unknown, Utc::now()).is_err());
+    }
+}
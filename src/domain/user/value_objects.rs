@@ -2,9 +2,11 @@
 use crate::domain::errors::{DomainError, DomainResult};
 use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use sqlx::Type;
 use std::{collections::HashSet, fmt, str::FromStr};
+use unicode_normalization::UnicodeNormalization;
 use utoipa::ToSchema;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -49,6 +51,65 @@ impl Capability {
     pub fn matches(&self, resource: &str, action: &str) -> bool {
         self.resource == resource && self.action == action
     }
+
+    /// Whether holding `self` allows everything `other` does: the same
+    /// capability, or its `:any` form when `other` is limited to `:own`.
+    #[must_use]
+    pub fn covers(&self, other: &Self) -> bool {
+        if self.resource != other.resource {
+            return false;
+        }
+        self.action == other.action
+            || other
+                .action
+                .strip_suffix(":own")
+                .is_some_and(|verb| self.action.strip_suffix(":any") == Some(verb))
+    }
+
+    /// Every capability the server checks, sorted: those of each role plus
+    /// ones only granted individually.
+    #[must_use]
+    pub fn known() -> Vec<Self> {
+        let mut known: Vec<_> = Role::ALL
+            .iter()
+            .flat_map(Role::default_capabilities)
+            .chain([
+                Self::new("audit", "read"),
+                Self::new("tokens", "introspect"),
+                Self::new("users", "impersonate"),
+            ])
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        known.sort_by(|a, b| {
+            a.resource
+                .cmp(&b.resource)
+                .then_with(|| a.action.cmp(&b.action))
+        });
+        known
+    }
+}
+
+/// `resource:action`; the action may itself contain colons.
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.resource, self.action)
+    }
+}
+
+impl FromStr for Capability {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((resource, action)) if !resource.is_empty() && !action.is_empty() => {
+                Ok(Self::new(resource, action))
+            }
+            _ => Err(DomainError::Validation(format!(
+                "capability '{s}' is not of the form resource:action"
+            ))),
+        }
+    }
 }
 
 #[derive(
@@ -63,6 +124,8 @@ pub enum Role {
 }
 
 impl Role {
+    pub const ALL: [Self; 2] = [Self::Admin, Self::Author];
+
     #[must_use]
     pub const fn as_str(&self) -> &'static str {
         match self {
@@ -76,14 +139,24 @@ impl Role {
         use Capability as Cap;
         match self {
             Self::Admin => HashSet::from([
+                Cap::new("access_rules", "manage"),
+                Cap::new("admin", "inspect"),
+                Cap::new("admin", "maintenance"),
+                Cap::new("admin", "repair"),
                 Cap::new("articles", "create"),
                 Cap::new("articles", "update:any"),
                 Cap::new("articles", "delete:any"),
                 Cap::new("articles", "publish"),
+                Cap::new("articles", "view:any"),
                 Cap::new("articles", "view:drafts"),
+                Cap::new("custom_fields", "manage"),
+                Cap::new("jobs", "run"),
+                Cap::new("moderation", "review"),
+                Cap::new("roles", "manage"),
                 Cap::new("users", "create"),
                 Cap::new("users", "read"),
                 Cap::new("users", "update"),
+                Cap::new("webhooks", "manage"),
             ]),
             Self::Author => HashSet::from([
                 Cap::new("articles", "create"),
@@ -118,6 +191,9 @@ impl FromStr for Role {
 pub struct Username(String);
 
 impl Username {
+    /// Shortest accepted username, in bytes.
+    pub const MIN_LEN: usize = 3;
+
     /// Create a validated username.
     ///
     /// # Errors
@@ -129,10 +205,11 @@ impl Username {
         if value.trim().is_empty() {
             return Err(DomainError::Validation("username cannot be empty".into()));
         }
-        if value.len() < 3 {
-            return Err(DomainError::Validation(
-                "username must be at least 3 characters long".into(),
-            ));
+        if value.len() < Self::MIN_LEN {
+            return Err(DomainError::Validation(format!(
+                "username must be at least {} characters long",
+                Self::MIN_LEN
+            )));
         }
         Ok(Self(value))
     }
@@ -141,6 +218,12 @@ impl Username {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Compare usernames the way the `users` table does: case-insensitively.
+    #[must_use]
+    pub fn eq_ignore_case(&self, other: &str) -> bool {
+        self.0.to_lowercase() == other.to_lowercase()
+    }
 }
 
 impl From<Username> for String {
@@ -169,6 +252,114 @@ impl AsRef<str> for Username {
     }
 }
 
+/// An email address, trimmed and lowercased. Only the shape is checked;
+/// ownership is proven by the verification flow.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Email(String);
+
+impl Email {
+    /// Longest accepted address, in bytes (RFC 5321 path limit).
+    pub const MAX_LEN: usize = 254;
+
+    /// Create a validated email address.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is longer than 254 bytes, contains
+    /// whitespace, or is not a non-empty local part and a dotted domain
+    /// joined by a single `@`.
+    pub fn new(value: &str) -> DomainResult<Self> {
+        let value = value.trim().to_lowercase();
+        let invalid = || DomainError::Validation(format!("invalid email address `{value}`"));
+        if value.len() > Self::MAX_LEN || value.chars().any(char::is_whitespace) {
+            return Err(invalid());
+        }
+        let (local, domain) = value.split_once('@').ok_or_else(invalid)?;
+        let domain_ok = !domain.contains('@')
+            && domain.contains('.')
+            && domain.split('.').all(|label| !label.is_empty());
+        if local.is_empty() || !domain_ok {
+            return Err(invalid());
+        }
+        Ok(Self(value))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Email {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Identifier supplied by a client at the authentication boundary.
+///
+/// Raw input is trimmed and NFKC-normalised before validation so that
+/// visually identical identifiers (full-width forms, composed vs. decomposed
+/// accents) resolve to the same account. Input containing `@` is an email
+/// address; anything else is a username.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoginIdentifier {
+    Username(Username),
+    Email(Email),
+}
+
+impl LoginIdentifier {
+    /// Normalise and classify a raw login identifier.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the normalised value is neither a valid username
+    /// nor a valid email address.
+    pub fn parse(raw: &str) -> DomainResult<Self> {
+        let normalized = Self::normalize(raw);
+        if normalized.contains('@') {
+            Email::new(&normalized).map(Self::Email)
+        } else {
+            Username::new(normalized).map(Self::Username)
+        }
+    }
+
+    /// Normalise a raw username for a new account. Usernames cannot contain
+    /// `@`, so that they never read as an email address at login.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the normalised value contains `@` or is not a
+    /// valid username.
+    pub fn parse_username(raw: &str) -> DomainResult<Username> {
+        let normalized = Self::normalize(raw);
+        if normalized.contains('@') {
+            return Err(DomainError::Validation(
+                "username cannot contain `@`".into(),
+            ));
+        }
+        Username::new(normalized)
+    }
+
+    fn normalize(raw: &str) -> String {
+        raw.trim().nfkc().collect()
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Username(username) => username.as_str(),
+            Self::Email(email) => email.as_str(),
+        }
+    }
+}
+
+impl fmt::Display for LoginIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PasswordHash(String);
 
@@ -273,3 +464,128 @@ impl UserListCursor {
         })
     }
 }
+
+/// A user's preferred IANA time zone, e.g. `Europe/Berlin`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timezone(Tz);
+
+impl Timezone {
+    /// Parse an IANA time zone name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is not a known IANA time zone.
+    pub fn new(name: &str) -> DomainResult<Self> {
+        name.trim()
+            .parse::<Tz>()
+            .map(Self)
+            .map_err(|_| DomainError::Validation(format!("unknown time zone `{name}`")))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        self.0.name()
+    }
+
+    #[must_use]
+    pub const fn tz(&self) -> Tz {
+        self.0
+    }
+}
+
+impl fmt::Display for Timezone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Capability, Email, LoginIdentifier, Role, Timezone, Username};
+
+    #[test]
+    fn username_comparison_ignores_case() {
+        let username = Username::new("Alice").unwrap();
+        assert!(username.eq_ignore_case("alice"));
+        assert!(username.eq_ignore_case("ALICE"));
+        assert!(!username.eq_ignore_case("alicia"));
+    }
+
+    #[test]
+    fn login_identifier_trims_and_normalizes() {
+        let identifier = LoginIdentifier::parse("  \u{ff41}lice\u{301}  ").unwrap();
+        assert_eq!(identifier.as_str(), "alic\u{e9}");
+    }
+
+    #[test]
+    fn login_identifier_rejects_blank_input() {
+        assert!(LoginIdentifier::parse("   ").is_err());
+    }
+
+    #[test]
+    fn login_identifier_with_an_at_sign_is_an_email() {
+        let identifier = LoginIdentifier::parse(" Alice@Example.com ").unwrap();
+        assert_eq!(
+            identifier,
+            LoginIdentifier::Email(Email::new("alice@example.com").unwrap())
+        );
+        assert!(LoginIdentifier::parse("alice@localhost").is_err());
+        assert!(LoginIdentifier::parse_username("alice\u{ff20}example.com").is_err());
+        assert_eq!(
+            LoginIdentifier::parse_username(" alice ").unwrap().as_str(),
+            "alice"
+        );
+    }
+
+    #[test]
+    fn known_capabilities_cover_every_role() {
+        let known = Capability::known();
+        for role in Role::ALL {
+            assert!(
+                role.default_capabilities()
+                    .iter()
+                    .all(|cap| known.contains(cap))
+            );
+        }
+        assert!(known.contains(&Capability::new("audit", "read")));
+        assert!(known.contains(&Capability::new("tokens", "introspect")));
+        assert!(known.contains(&Capability::new("users", "impersonate")));
+        assert!(known.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn any_covers_own_but_not_the_reverse() {
+        let any = Capability::new("articles", "update:any");
+        let own = Capability::new("articles", "update:own");
+        assert!(any.covers(&own));
+        assert!(own.covers(&own));
+        assert!(!own.covers(&any));
+        assert!(!any.covers(&Capability::new("articles", "delete:own")));
+        assert!(!any.covers(&Capability::new("media", "update:own")));
+    }
+
+    #[test]
+    fn email_is_normalized_and_shape_checked() {
+        assert_eq!(
+            Email::new("  Alice@Example.COM ").unwrap().as_str(),
+            "alice@example.com"
+        );
+        for invalid in [
+            "alice",
+            "@example.com",
+            "alice@localhost",
+            "a@b@c.com",
+            "a b@c.com",
+            "a@.com",
+        ] {
+            assert!(Email::new(invalid).is_err(), "{invalid} accepted");
+        }
+    }
+
+    #[test]
+    fn timezone_accepts_iana_names_only() {
+        assert_eq!(Timezone::new("Asia/Tokyo").unwrap().as_str(), "Asia/Tokyo");
+        assert!(Timezone::new("Mars/Olympus").is_err());
+        assert!(Timezone::new("+09:00").is_err());
+    }
+}
@@ -0,0 +1,324 @@
+// src/domain/webhook/entity.rs
+//! Endpoints that subscribe to content events, and the record of posting
+//! events to them.
+//!
+//! Each event a webhook subscribes to is posted to its `url`, signed with
+//! its `secret`. A failed post is retried; every try is kept as a
+//! [`DeliveryAttempt`], and the tries of one event share a `delivery_id`.
+
+use crate::domain::UserId;
+use crate::domain::errors::{DomainError, DomainResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Content events webhooks can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum WebhookEvent {
+    #[serde(rename = "article.created")]
+    ArticleCreated,
+    /// Content or metadata changed, including reverts to a revision.
+    #[serde(rename = "article.updated")]
+    ArticleUpdated,
+    #[serde(rename = "article.published")]
+    ArticlePublished,
+    #[serde(rename = "article.unpublished")]
+    ArticleUnpublished,
+    #[serde(rename = "article.deleted")]
+    ArticleDeleted,
+}
+
+impl WebhookEvent {
+    pub const ALL: [Self; 5] = [
+        Self::ArticleCreated,
+        Self::ArticleUpdated,
+        Self::ArticlePublished,
+        Self::ArticleUnpublished,
+        Self::ArticleDeleted,
+    ];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ArticleCreated => "article.created",
+            Self::ArticleUpdated => "article.updated",
+            Self::ArticlePublished => "article.published",
+            Self::ArticleUnpublished => "article.unpublished",
+            Self::ArticleDeleted => "article.deleted",
+        }
+    }
+}
+
+impl fmt::Display for WebhookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for WebhookEvent {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|event| event.as_str() == s)
+            .ok_or_else(|| DomainError::Validation(format!("unknown webhook event '{s}'")))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    pub id: i64,
+    pub url: String,
+    /// Key every delivery is signed with.
+    pub secret: String,
+    /// Events posted to `url`; never empty.
+    pub events: Vec<WebhookEvent>,
+    /// Inactive webhooks keep their history but receive nothing.
+    pub active: bool,
+    pub created_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    /// Longest accepted URL, in characters.
+    pub const MAX_URL_CHARS: usize = 2048;
+    /// Shortest and longest accepted secret, in characters.
+    pub const SECRET_CHARS: (usize, usize) = (16, 256);
+
+    /// Whether `event` is posted to this webhook now.
+    #[must_use]
+    pub fn receives(&self, event: WebhookEvent) -> bool {
+        self.active && self.events.contains(&event)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NewWebhook {
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<WebhookEvent>,
+    pub active: bool,
+    pub created_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NewWebhook {
+    /// An active webhook posting `events` to `url`. Repeated events are
+    /// dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `url` is not an absolute `http(s)` URL, `secret`
+    /// is too short or too long, or `events` is empty.
+    pub fn new(
+        url: &str,
+        secret: String,
+        events: Vec<WebhookEvent>,
+        created_by: Option<UserId>,
+        created_at: DateTime<Utc>,
+    ) -> DomainResult<Self> {
+        Ok(Self {
+            url: validate_url(url)?,
+            secret: validate_secret(secret)?,
+            events: validate_events(events)?,
+            active: true,
+            created_by,
+            created_at,
+        })
+    }
+}
+
+/// Changes to a webhook; `None` fields are left as they are.
+#[derive(Debug, Clone, Default)]
+pub struct WebhookUpdate {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub events: Option<Vec<WebhookEvent>>,
+    pub active: Option<bool>,
+}
+
+impl WebhookUpdate {
+    /// `webhook` with the changes applied, at `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a changed field is invalid, as for
+    /// [`NewWebhook::new`].
+    pub fn apply(self, mut webhook: Webhook, now: DateTime<Utc>) -> DomainResult<Webhook> {
+        if let Some(url) = self.url {
+            webhook.url = validate_url(&url)?;
+        }
+        if let Some(secret) = self.secret {
+            webhook.secret = validate_secret(secret)?;
+        }
+        if let Some(events) = self.events {
+            webhook.events = validate_events(events)?;
+        }
+        if let Some(active) = self.active {
+            webhook.active = active;
+        }
+        webhook.updated_at = now;
+        Ok(webhook)
+    }
+}
+
+/// One try at posting an event to a webhook.
+#[derive(Debug, Clone)]
+pub struct DeliveryAttempt {
+    pub id: i64,
+    pub webhook_id: i64,
+    /// Shared by every try of one event.
+    pub delivery_id: String,
+    pub event: WebhookEvent,
+    /// 1 for the first try.
+    pub attempt: u32,
+    /// Response status, absent when no response arrived.
+    pub status_code: Option<u16>,
+    /// Why the try failed; absent on success.
+    pub error: Option<String>,
+    pub succeeded: bool,
+    pub duration_ms: u64,
+    pub attempted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewDeliveryAttempt {
+    pub webhook_id: i64,
+    pub delivery_id: String,
+    pub event: WebhookEvent,
+    pub attempt: u32,
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    pub succeeded: bool,
+    pub duration_ms: u64,
+    pub attempted_at: DateTime<Utc>,
+}
+
+impl NewDeliveryAttempt {
+    /// Longest kept error message, in characters.
+    pub const MAX_ERROR_CHARS: usize = 500;
+
+    /// `error`, cut to [`Self::MAX_ERROR_CHARS`].
+    #[must_use]
+    pub fn truncate_error(error: &str) -> String {
+        error.chars().take(Self::MAX_ERROR_CHARS).collect()
+    }
+}
+
+fn validate_url(url: &str) -> DomainResult<String> {
+    let url = url.trim();
+    let invalid = || DomainError::Validation(format!("invalid webhook url '{url}'"));
+    if url.chars().count() > Webhook::MAX_URL_CHARS {
+        return Err(DomainError::Validation(format!(
+            "webhook url must be at most {} characters",
+            Webhook::MAX_URL_CHARS
+        )));
+    }
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(invalid)?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    if host.is_empty() || host.starts_with(':') || url.chars().any(char::is_whitespace) {
+        return Err(invalid());
+    }
+    Ok(url.to_string())
+}
+
+fn validate_secret(secret: String) -> DomainResult<String> {
+    let (min, max) = Webhook::SECRET_CHARS;
+    let len = secret.chars().count();
+    if (min..=max).contains(&len) {
+        Ok(secret)
+    } else {
+        Err(DomainError::Validation(format!(
+            "webhook secret must be {min} to {max} characters"
+        )))
+    }
+}
+
+fn validate_events(events: Vec<WebhookEvent>) -> DomainResult<Vec<WebhookEvent>> {
+    let mut unique = Vec::with_capacity(events.len());
+    for event in events {
+        if !unique.contains(&event) {
+            unique.push(event);
+        }
+    }
+    if unique.is_empty() {
+        return Err(DomainError::Validation(
+            "a webhook needs at least one event".into(),
+        ));
+    }
+    Ok(unique)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn webhook(url: &str, secret: &str, events: Vec<WebhookEvent>) -> DomainResult<NewWebhook> {
+        NewWebhook::new(url, secret.into(), events, None, Utc::now())
+    }
+
+    #[test]
+    fn urls_must_be_absolute_http() {
+        let events = || vec![WebhookEvent::ArticleCreated];
+        let secret = "0123456789abcdef";
+        assert!(webhook("https://hooks.example/cms", secret, events()).is_ok());
+        assert!(webhook(" http://relay.local:8080 ", secret, events()).is_ok());
+        assert!(webhook("ftp://hooks.example/", secret, events()).is_err());
+        assert!(webhook("https:///path", secret, events()).is_err());
+        assert!(webhook("https://:443/path", secret, events()).is_err());
+        assert!(webhook("/relative", secret, events()).is_err());
+        assert!(webhook("https://hooks.example/a b", secret, events()).is_err());
+    }
+
+    #[test]
+    fn secrets_and_events_are_checked() {
+        let url = "https://hooks.example/";
+        assert!(webhook(url, "short", vec![WebhookEvent::ArticleCreated]).is_err());
+        assert!(webhook(url, "0123456789abcdef", Vec::new()).is_err());
+        let created = webhook(
+            url,
+            "0123456789abcdef",
+            vec![WebhookEvent::ArticleDeleted, WebhookEvent::ArticleDeleted],
+        )
+        .unwrap();
+        assert_eq!(created.events, vec![WebhookEvent::ArticleDeleted]);
+    }
+
+    #[test]
+    fn event_names_round_trip() {
+        for event in WebhookEvent::ALL {
+            assert_eq!(event.as_str().parse::<WebhookEvent>().unwrap(), event);
+            assert_eq!(
+                serde_json::to_value(event).unwrap(),
+                serde_json::json!(event.as_str())
+            );
+        }
+        assert!("article.viewed".parse::<WebhookEvent>().is_err());
+    }
+
+    #[test]
+    fn inactive_webhooks_receive_nothing() {
+        let mut webhook = Webhook {
+            id: 1,
+            url: "https://hooks.example/".into(),
+            secret: "0123456789abcdef".into(),
+            events: vec![WebhookEvent::ArticlePublished],
+            active: true,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        assert!(webhook.receives(WebhookEvent::ArticlePublished));
+        assert!(!webhook.receives(WebhookEvent::ArticleCreated));
+        webhook.active = false;
+        assert!(!webhook.receives(WebhookEvent::ArticlePublished));
+    }
+}
@@ -0,0 +1,3 @@
+// src/domain/webhook/mod.rs
+pub mod entity;
+pub mod repository;
@@ -0,0 +1,39 @@
+// src/domain/webhook/repository.rs
+use crate::async_support::BoxFuture;
+use crate::domain::errors::DomainResult;
+use crate::domain::webhook::entity::{
+    DeliveryAttempt, NewDeliveryAttempt, NewWebhook, Webhook, WebhookEvent,
+};
+
+pub trait Repo: Send + Sync {
+    /// Every webhook, oldest first.
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<Webhook>>>;
+
+    fn find_by_id(&self, id: i64) -> BoxFuture<'_, DomainResult<Option<Webhook>>>;
+
+    /// Active webhooks subscribed to `event`.
+    fn subscribed(&self, event: WebhookEvent) -> BoxFuture<'_, DomainResult<Vec<Webhook>>>;
+
+    fn insert(&self, webhook: NewWebhook) -> BoxFuture<'_, DomainResult<Webhook>>;
+
+    /// Store every field of `webhook` but its id and creation. Returns
+    /// `false` if there is no webhook with its id.
+    fn update<'a>(&'a self, webhook: &'a Webhook) -> BoxFuture<'a, DomainResult<bool>>;
+
+    /// Remove the webhook and its delivery history. Returns `false` if there
+    /// was no webhook with `id`.
+    fn delete(&self, id: i64) -> BoxFuture<'_, DomainResult<bool>>;
+
+    fn record_attempt(
+        &self,
+        attempt: NewDeliveryAttempt,
+    ) -> BoxFuture<'_, DomainResult<DeliveryAttempt>>;
+
+    /// Up to `limit` attempts at delivering to webhook `webhook_id`, newest
+    /// first.
+    fn list_attempts(
+        &self,
+        webhook_id: i64,
+        limit: u32,
+    ) -> BoxFuture<'_, DomainResult<Vec<DeliveryAttempt>>>;
+}
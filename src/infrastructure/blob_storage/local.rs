@@ -0,0 +1,118 @@
+// src/infrastructure/blob_storage/local.rs
+use super::check_key;
+use crate::application::{
+    AppError, AppResult,
+    ports::blob_storage::{BlobRead, BlobStorage},
+};
+use crate::async_support::{BoxFuture, boxed};
+use bytes::Bytes;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Blobs kept as files in one directory, served by this service.
+#[derive(Clone, Debug)]
+pub struct LocalBlobStorage {
+    root: PathBuf,
+}
+
+impl LocalBlobStorage {
+    /// Keep blobs in `root`, creating it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created.
+    pub fn new(root: impl Into<PathBuf>) -> AppResult<Self> {
+        let root = root.into();
+        std::fs::create_dir_all(&root).map_err(|err| storage_error(&root, &err))?;
+        Ok(Self { root })
+    }
+
+    fn path(&self, key: &str) -> AppResult<PathBuf> {
+        check_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+fn storage_error(path: &Path, err: &std::io::Error) -> AppError {
+    AppError::infrastructure(format!("blob storage {}: {err}", path.display()))
+}
+
+/// Run blocking file IO off the async runtime.
+async fn blocking<T: Send + 'static>(
+    path: PathBuf,
+    io: impl FnOnce(&Path) -> std::io::Result<T> + Send + 'static,
+) -> AppResult<T> {
+    tokio::task::spawn_blocking(move || io(&path).map_err(|err| storage_error(&path, &err)))
+        .await
+        .map_err(|err| AppError::infrastructure(format!("blob storage: {err}")))?
+}
+
+impl BlobStorage for LocalBlobStorage {
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        _content_type: &'a str,
+        bytes: Bytes,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let path = self.path(key)?;
+            // Write beside the target and rename, so readers never see a
+            // partial file.
+            blocking(path, move |path| {
+                let partial = path.with_extension("partial");
+                std::fs::write(&partial, &bytes)?;
+                std::fs::rename(&partial, path)
+            })
+            .await
+        })
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, AppResult<Option<BlobRead>>> {
+        boxed(async move {
+            let path = self.path(key)?;
+            blocking(path, |path| match std::fs::read(path) {
+                Ok(bytes) => Ok(Some(BlobRead::Bytes(Bytes::from(bytes)))),
+                Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(err),
+            })
+            .await
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let path = self.path(key)?;
+            blocking(path, |path| match std::fs::remove_file(path) {
+                Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+                _ => Ok(()),
+            })
+            .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn blobs_round_trip_through_files() {
+        let root = std::env::temp_dir().join(format!("mokkan-blobs-{}", std::process::id()));
+        let storage = LocalBlobStorage::new(&root).unwrap();
+
+        storage
+            .put("abc-1", "image/png", Bytes::from_static(b"png"))
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.get("abc-1").await.unwrap(),
+            Some(BlobRead::Bytes(Bytes::from_static(b"png")))
+        );
+        storage.delete("abc-1").await.unwrap();
+        storage.delete("abc-1").await.unwrap();
+        assert_eq!(storage.get("abc-1").await.unwrap(), None);
+        assert!(storage.get("../etc/passwd").await.is_err());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
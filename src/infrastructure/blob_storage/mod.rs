@@ -0,0 +1,25 @@
+// src/infrastructure/blob_storage/mod.rs
+//! Adapters for the `BlobStorage` port that keeps uploaded media.
+mod local;
+mod s3;
+
+pub use local::LocalBlobStorage;
+pub use s3::{S3BlobStorage, S3Options};
+
+use crate::application::{AppError, AppResult};
+
+/// Keys are generated by the media service; refusing anything else keeps
+/// them from naming paths outside the storage root.
+fn check_key(key: &str) -> AppResult<()> {
+    let valid = !key.is_empty()
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_'));
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::infrastructure(format!(
+            "invalid blob key '{key}'"
+        )))
+    }
+}
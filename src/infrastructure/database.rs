@@ -1,5 +1,9 @@
 // src/infrastructure/database.rs
-use sqlx::{PgPool, postgres::PgPoolOptions};
+use crate::infrastructure::tenancy::{self, TenantSchema};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use sqlx::{Executor, PgPool, SqlitePool, postgres::PgPoolOptions};
+use std::str::FromStr;
+use std::time::Duration;
 
 /// Initialize the `PostgreSQL` connection pool.
 ///
@@ -13,6 +17,36 @@ pub async fn init_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
         .await
 }
 
+/// Initialize a pool whose connections follow the current tenant scope.
+///
+/// Every connection handed out has its `search_path` set to the schema of
+/// the tenant scoped via [`tenancy::scope`], or to `public` outside a scope,
+/// so a connection never leaks the previous borrower's tenant.
+///
+/// # Errors
+///
+/// Returns any `sqlx` error raised while connecting to the database.
+pub async fn init_tenant_routed_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
+    PgPoolOptions::new()
+        .max_connections(16)
+        .after_connect(|conn, _meta| {
+            Box::pin(async move {
+                conn.execute(tenancy::search_path_statement().as_str())
+                    .await?;
+                Ok(())
+            })
+        })
+        .before_acquire(|conn, _meta| {
+            Box::pin(async move {
+                conn.execute(tenancy::search_path_statement().as_str())
+                    .await?;
+                Ok(true)
+            })
+        })
+        .connect(database_url)
+        .await
+}
+
 /// Run embedded SQL migrations against the configured pool.
 ///
 /// # Errors
@@ -21,3 +55,119 @@ pub async fn init_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
 pub async fn run_migrations(pool: &PgPool) -> Result<(), sqlx::migrate::MigrateError> {
     sqlx::migrate!("./migrations").run(pool).await
 }
+
+/// Initialize a `SQLite` pool, creating the database file if it is missing.
+///
+/// Foreign keys are enforced and the journal is write-ahead, so readers do
+/// not wait on the writer. An in-memory database lives only as long as its
+/// connection, so it gets exactly one that is never recycled.
+///
+/// # Errors
+///
+/// Returns any `sqlx` error raised while parsing `database_url` or opening
+/// the database.
+pub async fn init_sqlite_pool(database_url: &str) -> Result<SqlitePool, sqlx::Error> {
+    let options = SqliteConnectOptions::from_str(database_url)?
+        .create_if_missing(true)
+        .foreign_keys(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .busy_timeout(Duration::from_secs(5));
+
+    let in_memory = database_url.contains(":memory:") || database_url.contains("mode=memory");
+    let pool = if in_memory {
+        SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+    } else {
+        SqlitePoolOptions::new().max_connections(8)
+    };
+    pool.connect_with(options).await
+}
+
+/// Run the embedded `SQLite` migrations against the pool.
+///
+/// # Errors
+///
+/// Returns any migration error reported by `sqlx`.
+pub async fn run_sqlite_migrations(pool: &SqlitePool) -> Result<(), sqlx::migrate::MigrateError> {
+    sqlx::migrate!("./migrations_sqlite").run(pool).await
+}
+
+/// Create each tenant's schema if needed and migrate it.
+///
+/// Each schema keeps its own `_sqlx_migrations` history, so tenants can be
+/// added later and brought up to date independently.
+///
+/// # Errors
+///
+/// Returns any migration error reported by `sqlx`.
+pub async fn run_tenant_migrations(
+    pool: &PgPool,
+    tenants: &[TenantSchema],
+) -> Result<(), sqlx::migrate::MigrateError> {
+    for tenant in tenants {
+        let mut conn = pool.acquire().await?;
+        conn.execute(format!("CREATE SCHEMA IF NOT EXISTS \"{}\"", tenant.schema_name()).as_str())
+            .await?;
+        conn.execute(format!("SET search_path TO {}", tenant.search_path()).as_str())
+            .await?;
+
+        let result = sqlx::migrate!("./migrations").run(&mut *conn).await;
+
+        // Reset before the connection returns to the pool.
+        conn.execute(tenancy::search_path_statement().as_str())
+            .await?;
+        result?;
+
+        tracing::info!(tenant = %tenant, "tenant schema migrated");
+    }
+
+    Ok(())
+}
+
+/// Re-index articles written under another text search configuration than
+/// `language`, in the public schema and every tenant's.
+///
+/// # Errors
+///
+/// Returns any `sqlx` error, including when Postgres has no text search
+/// configuration named `language`.
+pub async fn apply_search_language(
+    pool: &PgPool,
+    tenants: &[TenantSchema],
+    language: &str,
+) -> Result<(), sqlx::Error> {
+    const REINDEX: &str = "UPDATE articles SET search_language = $1::regconfig
+         WHERE search_language <> $1::regconfig";
+
+    let reindexed = sqlx::query(REINDEX).bind(language).execute(pool).await?;
+    if reindexed.rows_affected() > 0 {
+        tracing::info!(
+            language,
+            articles = reindexed.rows_affected(),
+            "articles re-indexed for search"
+        );
+    }
+
+    for tenant in tenants {
+        let mut conn = pool.acquire().await?;
+        conn.execute(format!("SET search_path TO {}", tenant.search_path()).as_str())
+            .await?;
+
+        let result = sqlx::query(REINDEX)
+            .bind(language)
+            .execute(&mut *conn)
+            .await;
+
+        // Reset before the connection returns to the pool.
+        conn.execute(tenancy::search_path_statement().as_str())
+            .await?;
+        let reindexed = result?;
+        if reindexed.rows_affected() > 0 {
+            tracing::info!(tenant = %tenant, language, articles = reindexed.rows_affected(), "articles re-indexed for search");
+        }
+    }
+
+    Ok(())
+}
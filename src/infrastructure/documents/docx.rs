@@ -0,0 +1,559 @@
+// src/infrastructure/documents/docx.rs
+//! Converts Word (.docx) documents, including Google Docs exports, to
+//! Markdown. Paragraph styles become headings, quotes and list items; bold,
+//! italic and external hyperlinks survive. Tables flatten to paragraphs and
+//! embedded images are counted but dropped.
+
+use super::xml::{Event, Reader, Tag, XmlError};
+use crate::application::{
+    AppError, AppResult,
+    ports::documents::{ConvertedDocument, DocumentConverter},
+};
+use crate::infrastructure::zip::{Archive, ZipError};
+use std::collections::HashMap;
+
+/// Largest document part inflated, in bytes.
+const MAX_PART_BYTES: usize = 8 * 1024 * 1024;
+
+const DOCUMENT_PART: &str = "word/document.xml";
+const RELATIONSHIPS_PART: &str = "word/_rels/document.xml.rels";
+const CORE_PROPERTIES_PART: &str = "docProps/core.xml";
+
+/// [`DocumentConverter`] for Office Open XML word-processing documents.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DocxConverter;
+
+impl DocumentConverter for DocxConverter {
+    fn to_markdown(&self, document: &[u8]) -> AppResult<ConvertedDocument> {
+        let archive = Archive::new(document).map_err(zip_error)?;
+        let body = read_part(&archive, DOCUMENT_PART)?
+            .ok_or_else(|| AppError::validation("document is not a .docx file"))?;
+        let links = read_part(&archive, RELATIONSHIPS_PART)?
+            .map(|xml| external_links(&xml))
+            .transpose()?
+            .unwrap_or_default();
+        let core_title = read_part(&archive, CORE_PROPERTIES_PART)?
+            .map(|xml| core_title(&xml))
+            .transpose()?
+            .flatten();
+
+        let parsed = Body::parse(&body, &links).map_err(xml_error)?;
+        Ok(parsed.into_document(core_title))
+    }
+}
+
+fn read_part(archive: &Archive<'_>, name: &str) -> AppResult<Option<String>> {
+    archive
+        .read(name, MAX_PART_BYTES)
+        .map_err(zip_error)?
+        .map(|bytes| {
+            String::from_utf8(bytes)
+                .map_err(|_| AppError::validation(format!("document part {name} is not UTF-8")))
+        })
+        .transpose()
+}
+
+fn zip_error(err: ZipError) -> AppError {
+    match err {
+        ZipError::NotAnArchive => AppError::validation("document is not a .docx file"),
+        other => AppError::validation(format!("invalid document: {other}")),
+    }
+}
+
+fn xml_error(err: XmlError) -> AppError {
+    AppError::validation(format!("invalid document: {err}"))
+}
+
+/// Relationship id to target for every external link.
+fn external_links(xml: &str) -> AppResult<HashMap<String, String>> {
+    let mut reader = Reader::new(xml);
+    let mut links = HashMap::new();
+    while let Some(event) = reader.next_event().map_err(xml_error)? {
+        if let Event::Start(tag) | Event::Empty(tag) = event
+            && tag.name == "Relationship"
+            && tag.attribute("TargetMode").as_deref() == Some("External")
+            && let (Some(id), Some(target)) = (tag.attribute("Id"), tag.attribute("Target"))
+        {
+            links.insert(id, target);
+        }
+    }
+    Ok(links)
+}
+
+fn core_title(xml: &str) -> AppResult<Option<String>> {
+    let mut reader = Reader::new(xml);
+    let mut in_title = false;
+    let mut title = String::new();
+    while let Some(event) = reader.next_event().map_err(xml_error)? {
+        match event {
+            Event::Start(tag) if tag.name == "dc:title" => in_title = true,
+            Event::End("dc:title") => break,
+            Event::Text(text) if in_title => title.push_str(&text),
+            _ => {}
+        }
+    }
+    let title = title.trim();
+    Ok((!title.is_empty()).then(|| title.to_string()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Paragraph,
+    Title,
+    Heading(usize),
+    Quote,
+    ListItem,
+}
+
+impl Kind {
+    fn from_style(style: Option<&str>, numbered: bool) -> Self {
+        let style = style
+            .map(|style| style.to_ascii_lowercase().replace(' ', ""))
+            .unwrap_or_default();
+        if let Some(level) = style
+            .strip_prefix("heading")
+            .and_then(|level| level.parse::<usize>().ok())
+        {
+            return Self::Heading(level.clamp(1, 6));
+        }
+        match style.as_str() {
+            "title" => Self::Title,
+            "quote" | "intensequote" => Self::Quote,
+            _ if numbered => Self::ListItem,
+            _ => Self::Paragraph,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Run {
+    text: String,
+    bold: bool,
+    italic: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Inline {
+    Run(Run),
+    Link { url: String, runs: Vec<Run> },
+}
+
+#[derive(Debug, Clone)]
+struct Block {
+    kind: Kind,
+    content: Vec<Inline>,
+}
+
+impl Block {
+    fn plain_text(&self) -> String {
+        let mut text = String::new();
+        for inline in &self.content {
+            match inline {
+                Inline::Run(run) => text.push_str(&run.text),
+                Inline::Link { runs, .. } => runs.iter().for_each(|run| text.push_str(&run.text)),
+            }
+        }
+        text.trim().to_string()
+    }
+
+    fn markdown(&self) -> String {
+        let mut text = String::new();
+        for inline in &self.content {
+            match inline {
+                Inline::Run(run) => text.push_str(&emphasize(run)),
+                Inline::Link { url, runs } => {
+                    let label: String = merge(runs).iter().map(emphasize).collect();
+                    if label.trim().is_empty() {
+                        continue;
+                    }
+                    text.push('[');
+                    text.push_str(label.trim());
+                    text.push_str("](");
+                    text.push_str(&url.replace(' ', "%20").replace(')', "%29"));
+                    text.push(')');
+                }
+            }
+        }
+        let text = text.trim();
+        match self.kind {
+            Kind::Paragraph => escape_line_start(text),
+            Kind::Title => format!("# {text}"),
+            Kind::Heading(level) => format!("{} {text}", "#".repeat(level)),
+            Kind::Quote => format!("> {text}"),
+            Kind::ListItem => format!("- {text}"),
+        }
+    }
+}
+
+/// Paragraph state while its events stream past.
+#[derive(Debug, Default)]
+struct Open {
+    style: Option<String>,
+    numbered: bool,
+    content: Vec<Inline>,
+    link: Option<(String, Vec<Run>)>,
+    run: Option<Run>,
+}
+
+impl Open {
+    fn push_run(&mut self, run: Run) {
+        if run.text.is_empty() {
+            return;
+        }
+        match &mut self.link {
+            Some((_, runs)) => runs.push(run),
+            None => self.content.push(Inline::Run(run)),
+        }
+    }
+
+    fn close_link(&mut self) {
+        if let Some((url, runs)) = self.link.take() {
+            self.content.push(Inline::Link { url, runs });
+        }
+    }
+
+    fn finish(mut self) -> Block {
+        if let Some(run) = self.run.take() {
+            self.push_run(run);
+        }
+        self.close_link();
+        let mut content = Vec::new();
+        let mut runs = Vec::new();
+        for inline in self.content {
+            match inline {
+                Inline::Run(run) => runs.push(run),
+                link @ Inline::Link { .. } => {
+                    content.extend(merge(&runs).into_iter().map(Inline::Run));
+                    runs.clear();
+                    content.push(link);
+                }
+            }
+        }
+        content.extend(merge(&runs).into_iter().map(Inline::Run));
+        Block {
+            kind: Kind::from_style(self.style.as_deref(), self.numbered),
+            content,
+        }
+    }
+}
+
+struct Body {
+    blocks: Vec<Block>,
+    skipped_images: u32,
+}
+
+impl Body {
+    fn parse(xml: &str, links: &HashMap<String, String>) -> Result<Self, XmlError> {
+        let mut reader = Reader::new(xml);
+        let mut blocks = Vec::new();
+        let mut skipped_images = 0_u32;
+        let mut paragraph: Option<Open> = None;
+        let mut in_text = false;
+        let mut in_run_properties = false;
+        let mut drawing_depth = 0_usize;
+
+        while let Some(event) = reader.next_event()? {
+            if drawing_depth > 0 {
+                match event {
+                    Event::Start(tag) if is_drawing(&tag) => drawing_depth += 1,
+                    Event::End(name) if name == "w:drawing" || name == "w:pict" => {
+                        drawing_depth -= 1;
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            match event {
+                Event::Start(tag) if is_drawing(&tag) => {
+                    skipped_images += 1;
+                    drawing_depth = 1;
+                }
+                Event::Empty(tag) if is_drawing(&tag) => skipped_images += 1,
+                Event::Start(tag) if tag.name == "w:p" => paragraph = Some(Open::default()),
+                Event::Empty(tag) if tag.name == "w:p" => {}
+                Event::End("w:p") => {
+                    if let Some(open) = paragraph.take() {
+                        blocks.push(open.finish());
+                    }
+                }
+                Event::Text(text) if in_text => {
+                    if let Some(run) = paragraph.as_mut().and_then(|open| open.run.as_mut()) {
+                        run.text.push_str(&text);
+                    }
+                }
+                Event::Start(tag) if tag.name == "w:t" => in_text = true,
+                Event::End("w:t") => in_text = false,
+                Event::Start(tag) if tag.name == "w:rPr" => in_run_properties = true,
+                Event::End("w:rPr") => in_run_properties = false,
+                Event::End(name) => {
+                    if let Some(open) = paragraph.as_mut() {
+                        match name {
+                            "w:r" => {
+                                if let Some(run) = open.run.take() {
+                                    open.push_run(run);
+                                }
+                            }
+                            "w:hyperlink" => open.close_link(),
+                            _ => {}
+                        }
+                    }
+                }
+                Event::Start(tag) | Event::Empty(tag) => {
+                    if let Some(open) = paragraph.as_mut() {
+                        apply_tag(open, &tag, in_run_properties, links);
+                    }
+                }
+                Event::Text(_) => {}
+            }
+        }
+
+        Ok(Self {
+            blocks,
+            skipped_images,
+        })
+    }
+
+    fn into_document(mut self, core_title: Option<String>) -> ConvertedDocument {
+        self.blocks.retain(|block| !block.plain_text().is_empty());
+
+        let title = if let Some(title) = core_title {
+            // Templates usually repeat the property as a Title paragraph.
+            if let Some(index) = self
+                .blocks
+                .iter()
+                .position(|block| block.kind == Kind::Title && block.plain_text() == title)
+            {
+                self.blocks.remove(index);
+            }
+            Some(title)
+        } else {
+            self.blocks
+                .iter()
+                .position(|block| block.kind == Kind::Title)
+                .or_else(|| {
+                    self.blocks
+                        .iter()
+                        .position(|block| block.kind == Kind::Heading(1))
+                })
+                .map(|index| self.blocks.remove(index).plain_text())
+        };
+
+        let mut markdown = String::new();
+        let mut previous = None;
+        for block in &self.blocks {
+            if let Some(previous) = previous {
+                let tight = previous == Kind::ListItem && block.kind == Kind::ListItem;
+                markdown.push_str(if tight { "\n" } else { "\n\n" });
+            }
+            markdown.push_str(&block.markdown());
+            previous = Some(block.kind);
+        }
+        if !markdown.is_empty() {
+            markdown.push('\n');
+        }
+
+        ConvertedDocument {
+            title,
+            markdown,
+            skipped_images: self.skipped_images,
+        }
+    }
+}
+
+fn is_drawing(tag: &Tag<'_>) -> bool {
+    tag.name == "w:drawing" || tag.name == "w:pict"
+}
+
+fn apply_tag(
+    open: &mut Open,
+    tag: &Tag<'_>,
+    in_run_properties: bool,
+    links: &HashMap<String, String>,
+) {
+    match tag.name {
+        "w:pStyle" => open.style = tag.attribute("w:val"),
+        "w:numPr" => open.numbered = true,
+        "w:r" => open.run = Some(Run::default()),
+        "w:hyperlink" => {
+            open.close_link();
+            if let Some(url) = tag.attribute("r:id").and_then(|id| links.get(&id)) {
+                open.link = Some((url.clone(), Vec::new()));
+            }
+        }
+        "w:b" | "w:i" if in_run_properties => {
+            let on = !matches!(
+                tag.attribute("w:val").as_deref(),
+                Some("0" | "false" | "off")
+            );
+            if let Some(run) = open.run.as_mut() {
+                if tag.name == "w:b" {
+                    run.bold = on;
+                } else {
+                    run.italic = on;
+                }
+            }
+        }
+        "w:tab" | "w:br" | "w:cr" if !in_run_properties => {
+            if let Some(run) = open.run.as_mut() {
+                run.text.push(if tag.name == "w:tab" { '\t' } else { ' ' });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Join neighbouring runs with the same formatting; Word splits text into
+/// runs freely.
+fn merge(runs: &[Run]) -> Vec<Run> {
+    let mut merged: Vec<Run> = Vec::new();
+    for run in runs {
+        match merged.last_mut() {
+            Some(last) if last.bold == run.bold && last.italic == run.italic => {
+                last.text.push_str(&run.text);
+            }
+            _ => merged.push(run.clone()),
+        }
+    }
+    merged
+}
+
+fn emphasize(run: &Run) -> String {
+    let text = escape_inline(&run.text);
+    let marker = match (run.bold, run.italic) {
+        (true, true) => "***",
+        (true, false) => "**",
+        (false, true) => "*",
+        (false, false) => return text,
+    };
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return text;
+    }
+    let leading = &text[..text.len() - text.trim_start().len()];
+    let trailing = &text[text.trim_end().len()..];
+    format!("{leading}{marker}{trimmed}{marker}{trailing}")
+}
+
+fn escape_inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(ch, '\\' | '`' | '*' | '_' | '[' | ']' | '<') {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// Keep a plain paragraph from being read as a heading, quote or list item.
+fn escape_line_start(text: &str) -> String {
+    if text.starts_with(['#', '>', '-', '+']) {
+        return format!("\\{text}");
+    }
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 && text[digits..].starts_with(['.', ')']) {
+        return format!("{}\\{}", &text[..digits], &text[digits..]);
+    }
+    text.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::zip::archive;
+
+    fn document(body: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{body}</w:body></w:document>"#
+        )
+    }
+
+    fn paragraph(style: Option<&str>, text: &str) -> String {
+        let properties = style.map_or_else(String::new, |style| {
+            format!(r#"<w:pPr><w:pStyle w:val="{style}"/></w:pPr>"#)
+        });
+        format!(r"<w:p>{properties}<w:r><w:t>{text}</w:t></w:r></w:p>")
+    }
+
+    #[test]
+    fn converts_styles_formatting_links_and_lists() {
+        let body = [
+            paragraph(Some("Title"), "My Trip"),
+            paragraph(Some("Heading2"), "Day one"),
+            r#"<w:p><w:r><w:t xml:space="preserve">We saw </w:t></w:r><w:r><w:rPr><w:b/></w:rPr><w:t>big</w:t></w:r><w:r><w:rPr><w:b w:val="true"/></w:rPr><w:t xml:space="preserve"> whales </w:t></w:r><w:r><w:t>and </w:t></w:r><w:hyperlink r:id="rId9"><w:r><w:t>a map</w:t></w:r></w:hyperlink><w:r><w:t>.</w:t></w:r></w:p>"#.to_string(),
+            r#"<w:p><w:pPr><w:numPr><w:ilvl w:val="0"/></w:numPr></w:pPr><w:r><w:t>boat</w:t></w:r></w:p>"#.to_string(),
+            r#"<w:p><w:pPr><w:numPr><w:ilvl w:val="0"/></w:numPr></w:pPr><w:r><w:t>rain_coat</w:t></w:r></w:p>"#.to_string(),
+            r"<w:p><w:r><w:drawing><wp:inline><w:p><w:r><w:t>caption</w:t></w:r></w:p></wp:inline></w:drawing></w:r></w:p>".to_string(),
+            paragraph(None, "1. not a list"),
+        ]
+        .concat();
+        let rels = r#"<Relationships><Relationship Id="rId9" Type="hyperlink" Target="https://example.com/map" TargetMode="External"/></Relationships>"#;
+        let docx = archive(&[
+            (DOCUMENT_PART, document(&body).as_bytes()),
+            (RELATIONSHIPS_PART, rels.as_bytes()),
+        ]);
+
+        let converted = DocxConverter.to_markdown(&docx).unwrap();
+        assert_eq!(converted.title.as_deref(), Some("My Trip"));
+        assert_eq!(converted.skipped_images, 1);
+        assert_eq!(
+            converted.markdown,
+            "## Day one\n\nWe saw **big whales** and [a map](https://example.com/map).\n\n- boat\n- rain\\_coat\n\n1\\. not a list\n"
+        );
+    }
+
+    #[test]
+    fn prefers_the_core_title_and_drops_its_repeat() {
+        let body = [
+            paragraph(Some("Title"), "Report"),
+            paragraph(Some("Heading1"), "Intro"),
+            paragraph(None, "Text"),
+        ]
+        .concat();
+        let core = r"<cp:coreProperties><dc:title>Report</dc:title></cp:coreProperties>";
+        let docx = archive(&[
+            (DOCUMENT_PART, document(&body).as_bytes()),
+            (CORE_PROPERTIES_PART, core.as_bytes()),
+        ]);
+
+        let converted = DocxConverter.to_markdown(&docx).unwrap();
+        assert_eq!(converted.title.as_deref(), Some("Report"));
+        assert_eq!(converted.markdown, "# Intro\n\nText\n");
+    }
+
+    #[test]
+    fn falls_back_to_the_first_heading_or_none() {
+        let docx = archive(&[(
+            DOCUMENT_PART,
+            document(
+                &[
+                    paragraph(Some("Heading1"), "Intro"),
+                    paragraph(None, "Text"),
+                ]
+                .concat(),
+            )
+            .as_bytes(),
+        )]);
+        let converted = DocxConverter.to_markdown(&docx).unwrap();
+        assert_eq!(converted.title.as_deref(), Some("Intro"));
+        assert_eq!(converted.markdown, "Text\n");
+
+        let docx = archive(&[(DOCUMENT_PART, document(&paragraph(None, "Text")).as_bytes())]);
+        assert_eq!(DocxConverter.to_markdown(&docx).unwrap().title, None);
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(matches!(
+            DocxConverter.to_markdown(b"%PDF-1.7"),
+            Err(AppError::Validation(_))
+        ));
+        let zip = archive(&[("readme.txt", b"hi")]);
+        assert!(matches!(
+            DocxConverter.to_markdown(&zip),
+            Err(AppError::Validation(_))
+        ));
+    }
+}
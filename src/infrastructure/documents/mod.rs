@@ -0,0 +1,8 @@
+// src/infrastructure/documents/mod.rs
+//! [`DocumentConverter`](crate::application::ports::documents::DocumentConverter)
+//! implementations.
+
+mod docx;
+mod xml;
+
+pub use docx::DocxConverter;
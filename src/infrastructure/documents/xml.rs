@@ -0,0 +1,176 @@
+// src/infrastructure/documents/xml.rs
+//! A forgiving pull reader for the XML parts of Office documents. It knows
+//! elements, attributes, text and the predefined and numeric entities;
+//! declarations, comments and processing instructions are skipped.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<'a> {
+    Start(Tag<'a>),
+    /// A self-closing element such as `<w:b/>`.
+    Empty(Tag<'a>),
+    End(&'a str),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag<'a> {
+    pub name: &'a str,
+    attributes: &'a str,
+}
+
+impl Tag<'_> {
+    /// Value of the attribute called `name`, unescaped.
+    #[must_use]
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        let mut rest = self.attributes;
+        loop {
+            rest = rest.trim_start();
+            let (key, after) = rest.split_once('=')?;
+            let after = after.trim_start();
+            let quote = after.chars().next()?;
+            if quote != '"' && quote != '\'' {
+                return None;
+            }
+            let (value, next) = after[1..].split_once(quote)?;
+            if key.trim() == name {
+                return Some(unescape(value));
+            }
+            rest = next;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("malformed xml")]
+pub struct XmlError;
+
+pub struct Reader<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Reader<'a> {
+    #[must_use]
+    pub const fn new(xml: &'a str) -> Self {
+        Self { rest: xml }
+    }
+
+    /// The next event, or `Ok(None)` at the end of the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a tag is never closed.
+    pub fn next_event(&mut self) -> Result<Option<Event<'a>>, XmlError> {
+        loop {
+            if self.rest.is_empty() {
+                return Ok(None);
+            }
+            if !self.rest.starts_with('<') {
+                let end = self.rest.find('<').unwrap_or(self.rest.len());
+                let text = &self.rest[..end];
+                self.rest = &self.rest[end..];
+                return Ok(Some(Event::Text(unescape(text))));
+            }
+
+            if let Some(skip) = ["<?", "<!--", "<!"]
+                .iter()
+                .zip(["?>", "-->", ">"])
+                .find_map(|(open, close)| self.rest.starts_with(open).then_some(close))
+            {
+                let end = self.rest.find(skip).ok_or(XmlError)?;
+                self.rest = &self.rest[end + skip.len()..];
+                continue;
+            }
+
+            let end = self.rest.find('>').ok_or(XmlError)?;
+            let inner = &self.rest[1..end];
+            self.rest = &self.rest[end + 1..];
+
+            if let Some(name) = inner.strip_prefix('/') {
+                return Ok(Some(Event::End(name.trim())));
+            }
+            let (inner, empty) = inner
+                .strip_suffix('/')
+                .map_or((inner, false), |inner| (inner, true));
+            let (name, attributes) = inner.split_once(char::is_whitespace).unwrap_or((inner, ""));
+            let tag = Tag { name, attributes };
+            return Ok(Some(if empty {
+                Event::Empty(tag)
+            } else {
+                Event::Start(tag)
+            }));
+        }
+    }
+}
+
+fn unescape(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let ch = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity
+                    .strip_prefix("#x")
+                    .map_or_else(
+                        || entity.strip_prefix('#').and_then(|n| n.parse().ok()),
+                        |hex| u32::from_str_radix(hex, 16).ok(),
+                    )
+                    .and_then(char::from_u32),
+            };
+            ch.map(|ch| (ch, end))
+        });
+        if let Some((ch, end)) = decoded {
+            out.push(ch);
+            rest = &rest[end + 1..];
+        } else {
+            out.push('&');
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_elements_attributes_and_text() {
+        let mut reader = Reader::new(
+            r#"<?xml version="1.0"?><!-- c --><w:p a="1" w:val='x &amp; y'><w:b/>a &lt; &#x42;</w:p>"#,
+        );
+        let Some(Event::Start(tag)) = reader.next_event().unwrap() else {
+            panic!("expected a start tag");
+        };
+        assert_eq!(tag.name, "w:p");
+        assert_eq!(tag.attribute("w:val").as_deref(), Some("x & y"));
+        assert_eq!(tag.attribute("a").as_deref(), Some("1"));
+        assert_eq!(tag.attribute("missing"), None);
+        assert!(matches!(
+            reader.next_event().unwrap(),
+            Some(Event::Empty(Tag { name: "w:b", .. }))
+        ));
+        assert_eq!(
+            reader.next_event().unwrap(),
+            Some(Event::Text("a < B".into()))
+        );
+        assert_eq!(reader.next_event().unwrap(), Some(Event::End("w:p")));
+        assert_eq!(reader.next_event().unwrap(), None);
+    }
+
+    #[test]
+    fn unclosed_tags_are_errors() {
+        assert_eq!(Reader::new("<w:p").next_event(), Err(XmlError));
+    }
+}
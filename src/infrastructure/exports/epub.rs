@@ -0,0 +1,132 @@
+// src/infrastructure/exports/epub.rs
+//! Writes an article as a single-chapter EPUB 3 book.
+
+use crate::application::markdown::html::{self, escape};
+use crate::application::{ArticleDto, RenderProfile};
+use crate::infrastructure::zip::{Writer, ZipError};
+use std::fmt::Write as _;
+
+const CONTAINER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#;
+
+/// Build the book.
+///
+/// # Errors
+///
+/// Returns an error if the archive cannot be written.
+pub fn render(article: &ArticleDto) -> Result<Vec<u8>, ZipError> {
+    let mut writer = Writer::new();
+    // Readers sniff the first entry, which must be stored, not deflated.
+    writer.add_stored("mimetype", b"application/epub+zip")?;
+    writer.add_deflated("META-INF/container.xml", CONTAINER.as_bytes())?;
+    writer.add_deflated("OEBPS/content.opf", package(article).as_bytes())?;
+    writer.add_deflated("OEBPS/nav.xhtml", navigation(article).as_bytes())?;
+    writer.add_deflated("OEBPS/article.xhtml", chapter(article).as_bytes())?;
+    writer.finish()
+}
+
+fn package(article: &ArticleDto) -> String {
+    let title = escape(&article.title);
+    let modified = article.updated_at.format("%Y-%m-%dT%H:%M:%SZ");
+    let identifier = format!(
+        "urn:mokkan:article:{}:{}",
+        article.id,
+        article.updated_at.timestamp()
+    );
+    let subjects = article.tags.iter().fold(String::new(), |mut out, tag| {
+        let _ = write!(out, "\n    <dc:subject>{}</dc:subject>", escape(tag));
+        out
+    });
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="id">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:identifier id="id">{identifier}</dc:identifier>
+    <dc:title>{title}</dc:title>
+    <dc:language>und</dc:language>{subjects}
+    <meta property="dcterms:modified">{modified}</meta>
+  </metadata>
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+    <item id="article" href="article.xhtml" media-type="application/xhtml+xml"/>
+  </manifest>
+  <spine>
+    <itemref idref="article"/>
+  </spine>
+</package>
+"#
+    )
+}
+
+fn navigation(article: &ArticleDto) -> String {
+    let title = escape(&article.title);
+    document(
+        &title,
+        &format!(
+            r#"<nav epub:type="toc"><ol><li><a href="article.xhtml">{title}</a></li></ol></nav>"#
+        ),
+    )
+}
+
+fn chapter(article: &ArticleDto) -> String {
+    let title = escape(&article.title);
+    // Remote images would have to be declared in the package; leave them out.
+    let body = html::render(&article.body, RenderProfile::Minimal);
+    document(&title, &format!("<h1>{title}</h1>\n{body}"))
+}
+
+fn document(title: &str, body: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops">
+<head><title>{title}</title></head>
+<body>
+{body}
+</body>
+</html>
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::exports::tests::article;
+    use crate::infrastructure::zip::Archive;
+
+    fn entry(book: &[u8], name: &str) -> String {
+        let bytes = Archive::new(book)
+            .unwrap()
+            .read(name, 1 << 20)
+            .unwrap()
+            .unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn writes_a_readable_book() {
+        let book = render(&article(
+            "Fish & Chips",
+            "Intro\n\n## Part\n\n- a\n- b\n\n3. c",
+        ))
+        .unwrap();
+        assert_eq!(&book[30..38], b"mimetype");
+        assert_eq!(entry(&book, "mimetype"), "application/epub+zip");
+        assert!(entry(&book, "META-INF/container.xml").contains("OEBPS/content.opf"));
+
+        let package = entry(&book, "OEBPS/content.opf");
+        assert!(package.contains("<dc:title>Fish &amp; Chips</dc:title>"));
+        assert!(package.contains("<dc:subject>food</dc:subject>"));
+
+        let chapter = entry(&book, "OEBPS/article.xhtml");
+        assert!(chapter.contains("<h1>Fish &amp; Chips</h1>\n<p>Intro</p>\n<h3>Part</h3>"));
+        assert!(chapter.contains("<ul>\n<li>a</li>\n<li>b</li>\n</ul>\n<ol start=\"3\">"));
+        assert!(chapter.trim_end().ends_with("</ol>\n\n</body>\n</html>"));
+    }
+}
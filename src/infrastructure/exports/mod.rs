@@ -0,0 +1,52 @@
+// src/infrastructure/exports/mod.rs
+//! Self-contained PDF and EPUB writers for article exports.
+//!
+//! They need no external tools or fonts, at the cost of only understanding
+//! the Markdown that articles are written in and, for PDF, only Latin text.
+
+mod epub;
+mod pdf;
+
+use crate::application::{
+    AppError, AppResult, ArticleDto, dto::exports::ExportFormat, ports::exports::ArticleRenderer,
+};
+
+/// [`ArticleRenderer`] that writes both formats in-process.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BuiltinArticleRenderer;
+
+impl ArticleRenderer for BuiltinArticleRenderer {
+    fn render(&self, article: &ArticleDto, format: ExportFormat) -> AppResult<Vec<u8>> {
+        match format {
+            ExportFormat::Pdf => Ok(pdf::render(article)),
+            ExportFormat::Epub => epub::render(article)
+                .map_err(|err| AppError::infrastructure(format!("failed to write epub: {err}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use crate::application::ArticleDto;
+    use crate::domain::ArticleVisibility;
+    use chrono::{TimeZone, Utc};
+
+    pub fn article(title: &str, body: &str) -> ArticleDto {
+        let at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        ArticleDto {
+            id: 7,
+            title: title.into(),
+            slug: "fish-and-chips".into(),
+            body: body.into(),
+            tags: vec!["food".into()],
+            custom_fields: serde_json::Map::new(),
+            published: false,
+            published_at: None,
+            visibility: ArticleVisibility::Public,
+            author_id: 1,
+            created_at: at,
+            updated_at: at,
+            view_count: 0,
+        }
+    }
+}
@@ -0,0 +1,557 @@
+// src/infrastructure/exports/pdf.rs
+//! Lays an article out on A4 pages using the PDF standard fonts, so no font
+//! files are embedded. Those fonts only cover Latin-1 and a few typographic
+//! marks (`WinAnsiEncoding`); other characters print as `?`.
+
+use crate::application::ArticleDto;
+use crate::application::markdown::{self, Block, Span, SpanKind};
+use flate2::{Compression, write::ZlibEncoder};
+use std::fmt::Write as _;
+use std::io::Write as _;
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+const BODY_SIZE: f32 = 11.0;
+const CODE_SIZE: f32 = 9.5;
+const LINE_SPACING: f32 = 1.4;
+const INDENT: f32 = 20.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Font {
+    Regular,
+    Bold,
+    Italic,
+    BoldItalic,
+    Mono,
+}
+
+impl Font {
+    const ALL: [(Self, &'static str); 5] = [
+        (Self::Regular, "Helvetica"),
+        (Self::Bold, "Helvetica-Bold"),
+        (Self::Italic, "Helvetica-Oblique"),
+        (Self::BoldItalic, "Helvetica-BoldOblique"),
+        (Self::Mono, "Courier"),
+    ];
+
+    /// `self` with bold and italic added where the span asks for them.
+    const fn emphasized(self, bold: bool, italic: bool) -> Self {
+        let bold = bold || matches!(self, Self::Bold | Self::BoldItalic);
+        let italic = italic || matches!(self, Self::Italic | Self::BoldItalic);
+        match (self, bold, italic) {
+            (Self::Mono, _, _) => Self::Mono,
+            (_, true, true) => Self::BoldItalic,
+            (_, true, false) => Self::Bold,
+            (_, false, true) => Self::Italic,
+            (_, false, false) => Self::Regular,
+        }
+    }
+
+    const fn resource(self) -> &'static str {
+        match self {
+            Self::Regular => "F1",
+            Self::Bold => "F2",
+            Self::Italic => "F3",
+            Self::BoldItalic => "F4",
+            Self::Mono => "F5",
+        }
+    }
+
+    /// Advance width of `byte` in thousandths of the font size.
+    fn width(self, byte: u8) -> u16 {
+        let bold = matches!(self, Self::Bold | Self::BoldItalic);
+        match byte {
+            _ if self == Self::Mono => 600,
+            32..=126 => {
+                let table = if bold { &HELVETICA_BOLD } else { &HELVETICA };
+                table[usize::from(byte - 32)]
+            }
+            0x85 | 0x97 => 1000,
+            0x91 | 0x92 => {
+                if bold {
+                    278
+                } else {
+                    222
+                }
+            }
+            0x93 | 0x94 => {
+                if bold {
+                    500
+                } else {
+                    333
+                }
+            }
+            0x95 => 350,
+            _ => {
+                if bold {
+                    611
+                } else {
+                    556
+                }
+            }
+        }
+    }
+}
+
+/// Helvetica widths for ASCII 32 to 126, from the Adobe font metrics.
+const HELVETICA: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+const HELVETICA_BOLD: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, 975, 722, 722, 722, 722, 667,
+    611, 778, 722, 278, 556, 722, 611, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 333, 278, 333, 584, 556, 333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556,
+    278, 889, 611, 611, 611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// `ch` in `WinAnsiEncoding`, or `?` if the standard fonts cannot show it.
+fn win_ansi(ch: char) -> u8 {
+    match ch {
+        ' '..='~' | '\u{a0}'..='\u{ff}' => u8::try_from(ch).unwrap_or(b'?'),
+        '\u{2026}' => 0x85,
+        '\u{2018}' => 0x91,
+        '\u{2019}' => 0x92,
+        '\u{201c}' => 0x93,
+        '\u{201d}' => 0x94,
+        '\u{2022}' => 0x95,
+        '\u{2013}' => 0x96,
+        '\u{2014}' => 0x97,
+        '\u{20ac}' => 0x80,
+        '\t' => b' ',
+        _ => b'?',
+    }
+}
+
+fn encode(text: &str) -> Vec<u8> {
+    text.chars().map(win_ansi).collect()
+}
+
+fn text_width(font: Font, size: f32, text: &[u8]) -> f32 {
+    let units: f32 = text.iter().map(|&byte| f32::from(font.width(byte))).sum();
+    units * size / 1000.0
+}
+
+/// A word, or a run of a word, set in one font.
+struct Run {
+    font: Font,
+    text: Vec<u8>,
+}
+
+/// Words of `spans`, each a sequence of runs, with links spelled out since
+/// paper cannot follow them.
+fn words(spans: &[Span], base: Font) -> Vec<Vec<Run>> {
+    let mut words: Vec<Vec<Run>> = Vec::new();
+    let mut joined = true;
+    let mut push = |font: Font, text: &str, words: &mut Vec<Vec<Run>>| {
+        let starts_with_space = text.starts_with(char::is_whitespace);
+        for (index, piece) in text.split_whitespace().enumerate() {
+            let run = Run {
+                font,
+                text: encode(piece),
+            };
+            match words.last_mut() {
+                Some(word) if index == 0 && joined && !starts_with_space => word.push(run),
+                _ => words.push(vec![run]),
+            }
+        }
+        joined = !text.ends_with(char::is_whitespace);
+    };
+
+    for (index, span) in spans.iter().enumerate() {
+        // Images are not embedded; their alt text stands in.
+        if span.kind == SpanKind::Image {
+            if !span.text.is_empty() {
+                push(Font::Italic, &format!("[{}]", span.text), &mut words);
+            }
+            continue;
+        }
+        let font = if span.kind == SpanKind::Code {
+            Font::Mono
+        } else {
+            base.emphasized(span.bold, span.italic)
+        };
+        push(font, &span.text, &mut words);
+        let link_ends = spans
+            .get(index + 1)
+            .is_none_or(|next| next.link != span.link);
+        if let Some(url) = span.link.as_deref().filter(|_| link_ends)
+            && url != span.text
+        {
+            push(base, &format!(" ({url})"), &mut words);
+        }
+    }
+    words
+}
+
+#[derive(Default)]
+struct Page {
+    content: String,
+}
+
+struct Layout {
+    pages: Vec<Page>,
+    /// Baseline of the next line, measured from the bottom of the page.
+    y: f32,
+}
+
+impl Layout {
+    fn new() -> Self {
+        Self {
+            pages: vec![Page::default()],
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn page(&mut self) -> &mut Page {
+        if self.pages.is_empty() {
+            self.pages.push(Page::default());
+        }
+        let last = self.pages.len() - 1;
+        &mut self.pages[last]
+    }
+
+    /// Move down `height`, starting a new page if it would not fit.
+    fn advance(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            self.pages.push(Page::default());
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+        self.y -= height;
+    }
+
+    fn gap(&mut self, height: f32) {
+        if self.y < PAGE_HEIGHT - MARGIN {
+            self.y -= height;
+        }
+    }
+
+    fn line(&mut self, x: f32, size: f32, runs: &[Run]) {
+        self.advance(size * LINE_SPACING);
+        let y = self.y;
+        let content = &mut self.page().content;
+        let _ = write!(content, "BT {x:.2} {y:.2} Td");
+        let mut font = None;
+        for run in runs {
+            if font != Some(run.font) {
+                let _ = write!(content, " /{} {size} Tf", run.font.resource());
+                font = Some(run.font);
+            }
+            let _ = write!(content, " {} Tj", literal(&run.text));
+        }
+        content.push_str(" ET\n");
+    }
+
+    /// Fill lines between `left` and the right margin with `words`.
+    fn paragraph(&mut self, left: f32, size: f32, words: Vec<Vec<Run>>) {
+        let width = PAGE_WIDTH - MARGIN - left;
+        let mut line: Vec<Run> = Vec::new();
+        let mut used = 0.0;
+        for word in words {
+            let word_width: f32 = word
+                .iter()
+                .map(|run| text_width(run.font, size, &run.text))
+                .sum();
+            let space = line.last().map_or(Font::Regular, |run| run.font);
+            let space_width = text_width(space, size, b" ");
+            if !line.is_empty() && used + space_width + word_width > width {
+                self.line(left, size, &line);
+                line.clear();
+                used = 0.0;
+            }
+            if !line.is_empty() {
+                line.push(Run {
+                    font: space,
+                    text: vec![b' '],
+                });
+                used += space_width;
+            }
+            used += word_width;
+            line.extend(word);
+        }
+        if !line.is_empty() {
+            self.line(left, size, &line);
+        }
+    }
+
+    fn rule(&mut self) {
+        self.advance(BODY_SIZE);
+        let y = self.y + BODY_SIZE / 2.0;
+        let right = PAGE_WIDTH - MARGIN;
+        let _ = writeln!(
+            self.page().content,
+            "0.5 w {MARGIN} {y:.2} m {right} {y:.2} l S"
+        );
+    }
+}
+
+/// Lay out and serialize the document.
+#[must_use]
+pub fn render(article: &ArticleDto) -> Vec<u8> {
+    let mut layout = Layout::new();
+    let title = [Span {
+        text: article.title.clone(),
+        bold: true,
+        ..Span::default()
+    }];
+    layout.paragraph(MARGIN, 22.0, words(&title, Font::Regular));
+    if let Some(published_at) = article.published_at {
+        let date = format!("Published {}", published_at.format("%Y-%m-%d"));
+        layout.paragraph(
+            MARGIN,
+            9.0,
+            vec![vec![Run {
+                font: Font::Italic,
+                text: encode(&date),
+            }]],
+        );
+    }
+    layout.gap(BODY_SIZE);
+
+    for block in markdown::parse(&article.body) {
+        match block {
+            Block::Heading(level, spans) => {
+                let size = match level {
+                    1 => 18.0,
+                    2 => 15.0,
+                    3 => 13.0,
+                    _ => 12.0,
+                };
+                layout.gap(size * 0.5);
+                layout.paragraph(MARGIN, size, words(&spans, Font::Bold));
+            }
+            Block::Paragraph(spans) => {
+                layout.paragraph(MARGIN, BODY_SIZE, words(&spans, Font::Regular));
+            }
+            Block::Quote(spans) => {
+                layout.paragraph(MARGIN + INDENT, BODY_SIZE, words(&spans, Font::Italic));
+            }
+            Block::ListItem { number, spans } => {
+                let marker = number.map_or_else(|| "\u{2022}".to_string(), |n| format!("{n}."));
+                let mut item = words(&spans, Font::Regular);
+                if let Some(first) = item.first_mut() {
+                    first.insert(
+                        0,
+                        Run {
+                            font: Font::Regular,
+                            text: encode(&format!("{marker} ")),
+                        },
+                    );
+                }
+                layout.paragraph(MARGIN + INDENT, BODY_SIZE, item);
+                continue;
+            }
+            Block::Code(code) => {
+                for line in code.lines() {
+                    let run = Run {
+                        font: Font::Mono,
+                        text: encode(line),
+                    };
+                    layout.line(MARGIN + INDENT, CODE_SIZE, &[run]);
+                }
+            }
+            Block::Rule => layout.rule(),
+        }
+        layout.gap(BODY_SIZE * 0.5);
+    }
+
+    serialize(&article.title, &layout.pages)
+}
+
+/// A PDF literal string.
+fn literal(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() + 2);
+    out.push('(');
+    for &byte in bytes {
+        match byte {
+            b'(' | b')' | b'\\' => {
+                out.push('\\');
+                out.push(char::from(byte));
+            }
+            b' '..=b'~' => out.push(char::from(byte)),
+            _ => {
+                let _ = write!(out, "\\{byte:03o}");
+            }
+        }
+    }
+    out.push(')');
+    out
+}
+
+/// A text string in UTF-16, which viewers show in document properties.
+fn text_string(text: &str) -> String {
+    let mut out = String::from("<FEFF");
+    for unit in text.encode_utf16() {
+        let _ = write!(out, "{unit:04X}");
+    }
+    out.push('>');
+    out
+}
+
+fn compress(content: &str) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    // Writing to a Vec cannot fail.
+    let _ = encoder.write_all(content.as_bytes());
+    encoder.finish().unwrap_or_default()
+}
+
+/// Objects are numbered: catalog 1, page tree 2, info 3, fonts from 4, then
+/// a page and its content stream for each page.
+fn serialize(title: &str, pages: &[Page]) -> Vec<u8> {
+    let first_page = 4 + Font::ALL.len();
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| first_page + 2 * i).collect();
+
+    let mut objects: Vec<Vec<u8>> = Vec::new();
+    objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+    let kids: Vec<String> = page_ids.iter().map(|id| format!("{id} 0 R")).collect();
+    objects.push(
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+    );
+    objects.push(format!("<< /Title {} /Producer (mokkan) >>", text_string(title)).into_bytes());
+    let mut fonts = String::new();
+    for (index, (font, name)) in Font::ALL.iter().enumerate() {
+        objects.push(
+            format!(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /{name} /Encoding /WinAnsiEncoding >>"
+            )
+            .into_bytes(),
+        );
+        let _ = write!(fonts, " /{} {} 0 R", font.resource(), 4 + index);
+    }
+    for (page, id) in pages.iter().zip(&page_ids) {
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+                 /Resources << /Font <<{fonts} >> >> /Contents {} 0 R >>",
+                id + 1
+            )
+            .into_bytes(),
+        );
+        let stream = compress(&page.content);
+        let mut object = format!(
+            "<< /Length {} /Filter /FlateDecode >>\nstream\n",
+            stream.len()
+        )
+        .into_bytes();
+        object.extend_from_slice(&stream);
+        object.extend_from_slice(b"\nendstream");
+        objects.push(object);
+    }
+
+    let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+        out.extend_from_slice(object);
+        out.extend_from_slice(b"\nendobj\n");
+    }
+    let xref = out.len();
+    let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(table, "{offset:010} 00000 n ");
+    }
+    let _ = write!(
+        table,
+        "trailer\n<< /Size {} /Root 1 0 R /Info 3 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+        objects.len() + 1
+    );
+    out.extend_from_slice(table.as_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::exports::tests::article;
+    use flate2::read::ZlibDecoder;
+    use std::io::Read as _;
+
+    fn page_contents(pdf: &[u8]) -> Vec<String> {
+        let mut contents = Vec::new();
+        let mut rest = pdf;
+        while let Some(start) = find(rest, b"stream\n") {
+            let body = &rest[start + 7..];
+            let end = find(body, b"\nendstream").unwrap();
+            let mut text = String::new();
+            ZlibDecoder::new(&body[..end])
+                .read_to_string(&mut text)
+                .unwrap();
+            contents.push(text);
+            rest = &body[end + b"\nendstream".len()..];
+        }
+        contents
+    }
+
+    fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+    }
+
+    #[test]
+    fn writes_a_valid_cross_reference_table() {
+        let pdf = render(&article("Caf\u{e9}", "Hello **world**"));
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+
+        let trailer = std::str::from_utf8(&pdf[pdf.len() - 32..]).unwrap();
+        let startxref: usize = trailer
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .and_then(|line| line.parse().ok())
+            .unwrap();
+        let table = std::str::from_utf8(&pdf[startxref..]).unwrap();
+        // Catalog, pages, info, five fonts, one page and its contents.
+        assert!(table.starts_with("xref\n0 11\n"));
+        for (number, line) in table.lines().skip(3).take(10).enumerate() {
+            assert_eq!(line.len(), 19, "entries are 20 bytes with the newline");
+            let offset: usize = line[..10].parse().unwrap();
+            let header = format!("{} 0 obj", number + 1);
+            assert!(pdf[offset..].starts_with(header.as_bytes()), "{header}");
+        }
+        assert!(find(&pdf, b"/Title <FEFF00430061006600E9>").is_some());
+
+        let contents = page_contents(&pdf);
+        assert_eq!(contents.len(), 1);
+        assert!(contents[0].contains("/F2 22 Tf (Caf\\351) Tj"));
+        assert!(contents[0].contains("/F1 11 Tf (Hello) Tj ( ) Tj /F2 11 Tf (world) Tj"));
+    }
+
+    #[test]
+    fn long_articles_wrap_and_break_pages() {
+        let body = "word ".repeat(2_000);
+        let contents = page_contents(&render(&article("Long", &body)));
+        assert!(contents.len() > 1);
+        for content in contents {
+            for line in content.lines() {
+                let x: f32 = line.split(' ').nth(1).unwrap().parse().unwrap();
+                let y: f32 = line.split(' ').nth(2).unwrap().parse().unwrap();
+                assert!(x >= MARGIN);
+                assert!((MARGIN..=PAGE_HEIGHT - MARGIN).contains(&y));
+            }
+        }
+    }
+
+    #[test]
+    fn escapes_literals_and_replaces_unsupported_characters() {
+        assert_eq!(
+            literal(&encode("a(b)\\ \u{2014} \u{65e5}")),
+            r"(a\(b\)\\ \227 ?)"
+        );
+        let width = text_width(Font::Regular, 10.0, b"Hi");
+        assert!((width - (722.0 + 222.0) * 10.0 / 1000.0).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,130 @@
+// src/infrastructure/geoip.rs
+//! ASN lookups from `GeoLite2-ASN-Blocks` CSV exports.
+//!
+//! Each file has a header line, then `network,autonomous_system_number,...`
+//! rows; the IPv4 and IPv6 files can be loaded into one database.
+
+use crate::application::{AppError, AppResult, ports::geoip::AsnLookup};
+use crate::domain::IpRange;
+use std::net::IpAddr;
+use std::path::Path;
+
+/// Networks held as IPv6 ranges, IPv4 ones mapped into `::ffff:0:0/96`, so
+/// both families share one sorted table.
+#[derive(Debug, Default)]
+pub struct CsvAsnDatabase {
+    /// `(first, last, asn)`, sorted by `first`, non-overlapping.
+    ranges: Vec<(u128, u128, u32)>,
+}
+
+const IPV4_MAPPED: u128 = 0xffff_0000_0000;
+
+fn mapped_bits(ip: IpAddr) -> u128 {
+    match ip.to_canonical() {
+        IpAddr::V4(ip) => IPV4_MAPPED | u128::from(ip.to_bits()),
+        IpAddr::V6(ip) => ip.to_bits(),
+    }
+}
+
+impl CsvAsnDatabase {
+    /// Load every file in `paths`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read or has a malformed row.
+    pub fn open<P: AsRef<Path>>(paths: &[P]) -> AppResult<Self> {
+        let mut database = Self::default();
+        for path in paths {
+            let path = path.as_ref();
+            let contents = std::fs::read_to_string(path).map_err(|err| {
+                AppError::infrastructure(format!("ASN database {}: {err}", path.display()))
+            })?;
+            database.load(&contents).map_err(|err| {
+                AppError::infrastructure(format!("ASN database {}: {err}", path.display()))
+            })?;
+        }
+        database.ranges.sort_unstable_by_key(|(first, _, _)| *first);
+        Ok(database)
+    }
+
+    fn load(&mut self, contents: &str) -> Result<(), String> {
+        for (index, line) in contents.lines().enumerate().skip(1) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut fields = line.split(',');
+            let (Some(network), Some(asn)) = (fields.next(), fields.next()) else {
+                return Err(format!("line {} has too few fields", index + 1));
+            };
+            let range =
+                IpRange::new(network).map_err(|err| format!("line {}: {err}", index + 1))?;
+            let asn = asn
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| format!("line {}: invalid ASN '{asn}'", index + 1))?;
+            let (width, prefix) = match range.network() {
+                IpAddr::V4(_) => (32, u32::from(range.prefix())),
+                IpAddr::V6(_) => (128, u32::from(range.prefix())),
+            };
+            let first = mapped_bits(range.network());
+            let host_bits = width - prefix;
+            let last = first | u128::MAX.checked_shr(128 - host_bits).unwrap_or(0);
+            self.ranges.push((first, last, asn));
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+impl AsnLookup for CsvAsnDatabase {
+    fn asn(&self, ip: IpAddr) -> Option<u32> {
+        let bits = mapped_bits(ip);
+        let index = self.ranges.partition_point(|(first, _, _)| *first <= bits);
+        let (_, last, asn) = self.ranges.get(index.checked_sub(1)?)?;
+        (bits <= *last).then_some(*asn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCKS: &str = "network,autonomous_system_number,autonomous_system_organization
+1.0.0.0/24,13335,CLOUDFLARENET
+8.8.8.0/24,15169,\"Google, LLC\"
+2001:4860::/32,15169,\"Google, LLC\"
+";
+
+    #[test]
+    fn resolves_addresses_in_either_family() {
+        let mut database = CsvAsnDatabase::default();
+        database.load(BLOCKS).unwrap();
+        database.ranges.sort_unstable_by_key(|(first, _, _)| *first);
+
+        let asn = |ip: &str| database.asn(ip.parse().unwrap());
+        assert_eq!(asn("1.0.0.255"), Some(13335));
+        assert_eq!(asn("8.8.8.8"), Some(15169));
+        assert_eq!(asn("::ffff:8.8.8.8"), Some(15169));
+        assert_eq!(asn("2001:4860:4860::8888"), Some(15169));
+        assert_eq!(asn("1.0.1.0"), None);
+        assert_eq!(asn("9.9.9.9"), None);
+    }
+
+    #[test]
+    fn malformed_rows_are_reported_with_their_line() {
+        let mut database = CsvAsnDatabase::default();
+        let err = database
+            .load("network,autonomous_system_number\n10.0.0.0/8,AS1\n")
+            .unwrap_err();
+        assert!(err.starts_with("line 2"), "{err}");
+    }
+}
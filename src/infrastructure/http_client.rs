@@ -0,0 +1,147 @@
+// src/infrastructure/http_client.rs
+//! Minimal outbound HTTP/1.1 client shared by adapters that call other
+//! services, such as upstream OIDC providers and syndication targets.
+//!
+//! Every request opens a fresh connection; `https://` URLs are verified
+//! against the bundled web PKI roots.
+use crate::application::AppResult;
+use crate::application::error::AppError;
+use bytes::Bytes;
+use http_body_util::{BodyExt as _, Full, Limited};
+use hyper::header::HeaderName;
+use hyper::{Method, Request, StatusCode, Uri, header};
+use hyper_util::rt::TokioIo;
+use std::sync::{Arc, LazyLock};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::{self, RootCertStore, pki_types::ServerName};
+
+/// Largest response body read from another service.
+const MAX_RESPONSE_BYTES: usize = 1 << 20;
+
+static TLS: LazyLock<TlsConnector> = LazyLock::new(|| {
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .expect("ring supports the default protocol versions")
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    TlsConnector::from(Arc::new(config))
+});
+
+/// Whether `url` is an absolute `http(s)` URL with a host.
+pub(crate) fn is_http_url(url: &str) -> bool {
+    url.parse::<Uri>()
+        .is_ok_and(|uri| matches!(uri.scheme_str(), Some("https" | "http")) && uri.host().is_some())
+}
+
+/// One HTTP/1.1 request over a fresh connection, TLS for `https://`.
+///
+/// `body` is a content type and payload; `headers` are sent as given.
+pub(crate) async fn send(
+    method: Method,
+    url: &str,
+    headers: &[(HeaderName, &str)],
+    body: Option<(&str, Vec<u8>)>,
+) -> AppResult<(StatusCode, Bytes)> {
+    let uri: Uri = url
+        .parse()
+        .map_err(|_| AppError::infrastructure(format!("invalid url: {url}")))?;
+    let host = uri
+        .host()
+        .ok_or_else(|| AppError::infrastructure(format!("url is missing a host: {url}")))?
+        .to_string();
+    let tls = match uri.scheme_str() {
+        Some("https") => true,
+        Some("http") => false,
+        _ => {
+            return Err(AppError::infrastructure(format!(
+                "url must use http(s): {url}"
+            )));
+        }
+    };
+    let port = uri.port_u16().unwrap_or(if tls { 443 } else { 80 });
+
+    let authority = uri
+        .authority()
+        .map_or_else(|| host.clone(), ToString::to_string);
+    let path = uri
+        .path_and_query()
+        .map_or_else(|| "/".to_string(), ToString::to_string);
+    let mut request = Request::builder()
+        .method(method)
+        .uri(path)
+        .header(header::HOST, authority)
+        .header(header::ACCEPT, "application/json");
+    for (name, value) in headers {
+        request = request.header(name, *value);
+    }
+    let payload = match body {
+        Some((content_type, bytes)) => {
+            request = request.header(header::CONTENT_TYPE, content_type);
+            Bytes::from(bytes)
+        }
+        None => Bytes::new(),
+    };
+    let request = request
+        .body(Full::new(payload))
+        .map_err(|err| AppError::infrastructure(err.to_string()))?;
+
+    let io_error = |err: std::io::Error| AppError::infrastructure(err.to_string());
+    let stream = TcpStream::connect((host.as_str(), port))
+        .await
+        .map_err(io_error)?;
+    if tls {
+        let server_name = ServerName::try_from(host)
+            .map_err(|_| AppError::infrastructure(format!("invalid host in url: {url}")))?;
+        let stream = TLS.connect(server_name, stream).await.map_err(io_error)?;
+        exchange(stream, request).await
+    } else {
+        exchange(stream, request).await
+    }
+}
+
+async fn exchange<S>(stream: S, request: Request<Full<Bytes>>) -> AppResult<(StatusCode, Bytes)>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let infra = |err: &dyn std::fmt::Display| AppError::infrastructure(err.to_string());
+    let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+        .await
+        .map_err(|err| infra(&err))?;
+    tokio::spawn(async move {
+        if let Err(err) = connection.await {
+            tracing::debug!(error = %err, "outbound HTTP connection closed with error");
+        }
+    });
+
+    let response = sender
+        .send_request(request)
+        .await
+        .map_err(|err| infra(&err))?;
+    let status = response.status();
+    let body = Limited::new(response.into_body(), MAX_RESPONSE_BYTES)
+        .collect()
+        .await
+        .map_err(|err| infra(&err))?
+        .to_bytes();
+    Ok((status, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_http_url;
+
+    #[test]
+    fn only_absolute_http_urls_are_accepted() {
+        assert!(is_http_url("https://mastodon.example/"));
+        assert!(is_http_url("http://relay.local:8080/hook"));
+        assert!(!is_http_url("ftp://files.example/"));
+        assert!(!is_http_url("/relative/path"));
+    }
+}
@@ -0,0 +1,162 @@
+// src/infrastructure/integrity.rs
+//! Cross-table consistency checks in Postgres.
+//!
+//! Each check reports at most [`MAX_ANOMALIES_PER_KIND`] rows so a badly
+//! damaged database still yields a readable report; repairs are not limited.
+use crate::application::ports::integrity::IntegrityChecker;
+use crate::application::{AnomalyKind, AppError, AppResult, IntegrityAnomalyDto};
+use crate::async_support::{BoxFuture, boxed};
+use crate::infrastructure::rls;
+use sqlx::PgPool;
+
+/// Most anomalies of one kind listed in a report.
+pub const MAX_ANOMALIES_PER_KIND: i64 = 500;
+
+const ORPHANED_REVISIONS: &str = "
+    FROM article_revisions r
+    WHERE NOT EXISTS (SELECT 1 FROM articles a WHERE a.id = r.article_id)";
+
+const UNDEFINED_FIELD_KEYS: &str = "
+    SELECT key FROM jsonb_object_keys(a.custom_fields) AS key
+    WHERE NOT EXISTS (SELECT 1 FROM custom_field_definitions d WHERE d.name = key)";
+
+/// Audit resource types backed by a table, and that table.
+const AUDITED_TABLES: [(&str, &str); 5] = [
+    ("article", "articles"),
+    ("access_rule", "access_rules"),
+    ("media", "media_assets"),
+    ("moderation_case", "moderation_cases"),
+    ("webhook", "webhooks"),
+];
+
+#[derive(Clone)]
+#[must_use]
+pub struct PostgresIntegrityChecker {
+    pool: PgPool,
+}
+
+impl PostgresIntegrityChecker {
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn check_error(err: &sqlx::Error) -> AppError {
+    AppError::infrastructure(format!("integrity check: {err}"))
+}
+
+fn anomaly(
+    kind: AnomalyKind,
+    resource_type: &str,
+    resource_id: i64,
+    detail: String,
+) -> IntegrityAnomalyDto {
+    IntegrityAnomalyDto {
+        kind,
+        resource_type: resource_type.into(),
+        resource_id,
+        detail,
+    }
+}
+
+impl IntegrityChecker for PostgresIntegrityChecker {
+    fn scan(&self) -> BoxFuture<'_, AppResult<Vec<IntegrityAnomalyDto>>> {
+        boxed(async move {
+            let mut tx = rls::begin(&self.pool)
+                .await
+                .map_err(|err| check_error(&err))?;
+            let mut anomalies = Vec::new();
+
+            let revisions = sqlx::query_as::<_, (i64, i64)>(&format!(
+                "SELECT r.id, r.article_id {ORPHANED_REVISIONS} ORDER BY r.id LIMIT $1"
+            ))
+            .bind(MAX_ANOMALIES_PER_KIND)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|err| check_error(&err))?;
+            anomalies.extend(revisions.into_iter().map(|(id, article_id)| {
+                anomaly(
+                    AnomalyKind::OrphanedRevision,
+                    "article_revision",
+                    id,
+                    format!("article {article_id} does not exist"),
+                )
+            }));
+
+            let fields = sqlx::query_as::<_, (i64, String)>(&format!(
+                "SELECT a.id, k.key FROM articles a CROSS JOIN LATERAL ({UNDEFINED_FIELD_KEYS}) AS k
+                 ORDER BY a.id, k.key LIMIT $1"
+            ))
+            .bind(MAX_ANOMALIES_PER_KIND)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|err| check_error(&err))?;
+            anomalies.extend(fields.into_iter().map(|(id, key)| {
+                anomaly(
+                    AnomalyKind::UndefinedCustomField,
+                    "article",
+                    id,
+                    format!("custom field `{key}` has no definition"),
+                )
+            }));
+
+            let missing = AUDITED_TABLES
+                .iter()
+                .map(|(resource_type, table)| {
+                    format!(
+                        "(l.resource_type = '{resource_type}' AND NOT EXISTS (SELECT 1 FROM {table} t WHERE t.id = l.resource_id))"
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" OR ");
+            let audit = sqlx::query_as::<_, (i64, String, i64)>(&format!(
+                "SELECT l.id, l.resource_type, l.resource_id FROM audit_logs l
+                 WHERE l.resource_id IS NOT NULL AND ({missing})
+                   AND NOT EXISTS (
+                       SELECT 1 FROM audit_logs d
+                       WHERE d.resource_type = l.resource_type AND d.resource_id = l.resource_id
+                         AND d.action LIKE '%.delete'
+                   )
+                 ORDER BY l.id LIMIT $1"
+            ))
+            .bind(MAX_ANOMALIES_PER_KIND)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|err| check_error(&err))?;
+            anomalies.extend(audit.into_iter().map(|(id, resource_type, resource_id)| {
+                anomaly(
+                    AnomalyKind::DanglingAuditResource,
+                    "audit_log",
+                    id,
+                    format!("{resource_type} {resource_id} does not exist"),
+                )
+            }));
+
+            tx.commit().await.map_err(|err| check_error(&err))?;
+            Ok(anomalies)
+        })
+    }
+
+    fn repair(&self) -> BoxFuture<'_, AppResult<u64>> {
+        boxed(async move {
+            let mut tx = rls::begin(&self.pool)
+                .await
+                .map_err(|err| check_error(&err))?;
+            let revisions = sqlx::query(&format!("DELETE {ORPHANED_REVISIONS}"))
+                .execute(&mut *tx)
+                .await
+                .map_err(|err| check_error(&err))?
+                .rows_affected();
+            let articles = sqlx::query(&format!(
+                "UPDATE articles a SET custom_fields = a.custom_fields - ARRAY({UNDEFINED_FIELD_KEYS})
+                 WHERE EXISTS ({UNDEFINED_FIELD_KEYS})"
+            ))
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| check_error(&err))?
+            .rows_affected();
+            tx.commit().await.map_err(|err| check_error(&err))?;
+            Ok(revisions + articles)
+        })
+    }
+}
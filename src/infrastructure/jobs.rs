@@ -0,0 +1,20 @@
+// src/infrastructure/jobs.rs
+use crate::application::ports::jobs::JobQueue;
+use crate::async_support::BoxFuture;
+
+/// In-process job queue that runs each job on the Tokio runtime.
+///
+/// Jobs do not survive a restart; callers that need durability must record
+/// their own progress.
+#[derive(Debug, Default, Clone)]
+pub struct TokioJobQueue;
+
+impl JobQueue for TokioJobQueue {
+    fn enqueue(&self, name: &'static str, job: BoxFuture<'static, ()>) {
+        tracing::debug!(job = name, "job enqueued");
+        tokio::spawn(async move {
+            job.await;
+            tracing::debug!(job = name, "job finished");
+        });
+    }
+}
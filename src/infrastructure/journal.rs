@@ -0,0 +1,133 @@
+// src/infrastructure/journal.rs
+//! Command journal kept as a JSON Lines file, one entry per line.
+
+use crate::application::{
+    AppError, AppResult,
+    ports::command_journal::{CommandJournal, JournalEntry},
+};
+use crate::async_support::{BoxFuture, boxed};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+#[derive(Clone)]
+pub struct FileCommandJournal {
+    file: Arc<Mutex<File>>,
+}
+
+impl FileCommandJournal {
+    /// Open `path` for appending, creating it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened.
+    pub fn open(path: impl AsRef<Path>) -> AppResult<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| AppError::infrastructure(format!("command journal: {err}")))?;
+        Ok(Self {
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+}
+
+impl CommandJournal for FileCommandJournal {
+    fn append<'a>(&'a self, entry: &'a JournalEntry) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let mut line = serde_json::to_vec(entry)
+                .map_err(|err| AppError::infrastructure(format!("command journal: {err}")))?;
+            line.push(b'\n');
+            let file = Arc::clone(&self.file);
+            tokio::task::spawn_blocking(move || {
+                let mut file = file
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner);
+                file.write_all(&line)?;
+                file.flush()
+            })
+            .await
+            .map_err(|err| AppError::infrastructure(format!("command journal: {err}")))?
+            .map_err(|err| AppError::infrastructure(format!("command journal: {err}")))
+        })
+    }
+}
+
+/// Read every entry of the journal at `path`, skipping blank lines.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or a line is not a valid
+/// entry.
+pub fn read_entries(path: impl AsRef<Path>) -> AppResult<Vec<JournalEntry>> {
+    let file = File::open(path)
+        .map_err(|err| AppError::infrastructure(format!("command journal: {err}")))?;
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.as_ref().is_ok_and(|line| !line.trim().is_empty()))
+        .map(|(index, line)| {
+            let line =
+                line.map_err(|err| AppError::infrastructure(format!("command journal: {err}")))?;
+            serde_json::from_str(&line).map_err(|err| {
+                AppError::validation(format!("command journal line {}: {err}", index + 1))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::command_journal::{
+        JournalActor, JournalOutcome, JournaledCommand,
+    };
+    use crate::domain::{Capability, Role};
+    use chrono::Utc;
+
+    #[tokio::test]
+    async fn appended_entries_read_back_in_order() {
+        let path = std::env::temp_dir().join(format!(
+            "mokkan-journal-{}.jsonl",
+            crate::application::random_id::v4_string().unwrap()
+        ));
+        let journal = FileCommandJournal::open(&path).unwrap();
+        let entries = [
+            JournalEntry {
+                recorded_at: Utc::now(),
+                actor: None,
+                command: JournaledCommand::RegisterUser {
+                    username: "root".into(),
+                    role: None,
+                },
+                outcome: JournalOutcome::Succeeded {
+                    resource_id: Some(1),
+                },
+            },
+            JournalEntry {
+                recorded_at: Utc::now(),
+                actor: Some(JournalActor {
+                    id: 1,
+                    username: "root".into(),
+                    role: Role::Admin,
+                    capabilities: vec![Capability::new("articles", "create")],
+                }),
+                command: JournaledCommand::DeleteArticle { id: 7 },
+                outcome: JournalOutcome::Failed {
+                    error: "resource not found: article not found".into(),
+                },
+            },
+        ];
+        for entry in &entries {
+            journal.append(entry).await.unwrap();
+        }
+
+        let read = read_entries(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read, entries);
+    }
+}
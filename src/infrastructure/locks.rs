@@ -0,0 +1,170 @@
+// src/infrastructure/locks.rs
+//! [`LockManager`] implementations: one per process, or shared by every
+//! instance through Postgres.
+
+use crate::application::{
+    AppError, AppResult,
+    ports::jobs::{LockLease, LockManager},
+    random_id,
+};
+use crate::async_support::{BoxFuture, boxed};
+use sqlx::PgPool;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Locks held in memory; only keeps tasks of a single process apart.
+#[derive(Debug, Default)]
+pub struct InMemoryLockManager {
+    held: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl LockManager for InMemoryLockManager {
+    fn try_acquire<'a>(
+        &'a self,
+        name: &'a str,
+        ttl: Duration,
+    ) -> BoxFuture<'a, AppResult<Option<LockLease>>> {
+        boxed(async move {
+            let token = random_id::v4_string()?;
+            let now = Instant::now();
+            let mut held = self
+                .held
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            if held.get(name).is_some_and(|(_, expires)| *expires > now) {
+                return Ok(None);
+            }
+            held.insert(name.to_string(), (token.clone(), now + ttl));
+            drop(held);
+            Ok(Some(LockLease {
+                name: name.to_string(),
+                token,
+            }))
+        })
+    }
+
+    fn release<'a>(&'a self, lease: &'a LockLease) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let mut held = self
+                .held
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            if held
+                .get(&lease.name)
+                .is_some_and(|(token, _)| *token == lease.token)
+            {
+                held.remove(&lease.name);
+            }
+            drop(held);
+            Ok(())
+        })
+    }
+}
+
+/// Locks kept in the `scheduler_locks` table, so instances sharing a
+/// database never run the same job at once.
+#[derive(Clone)]
+pub struct PostgresLockManager {
+    pool: PgPool,
+}
+
+impl PostgresLockManager {
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl LockManager for PostgresLockManager {
+    fn try_acquire<'a>(
+        &'a self,
+        name: &'a str,
+        ttl: Duration,
+    ) -> BoxFuture<'a, AppResult<Option<LockLease>>> {
+        boxed(async move {
+            let token = random_id::v4_string()?;
+            let ttl_ms = i64::try_from(ttl.as_millis()).unwrap_or(i64::MAX);
+            // Takes the row when it is missing or expired; a live lock makes
+            // the update's WHERE fail and nothing is returned.
+            let acquired = sqlx::query_scalar::<_, String>(
+                r"
+                INSERT INTO scheduler_locks (name, token, expires_at)
+                VALUES ($1, $2, NOW() + $3 * INTERVAL '1 millisecond')
+                ON CONFLICT (name) DO UPDATE
+                    SET token = EXCLUDED.token, expires_at = EXCLUDED.expires_at
+                    WHERE scheduler_locks.expires_at <= NOW()
+                RETURNING token
+                ",
+            )
+            .bind(name)
+            .bind(&token)
+            .bind(ttl_ms)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| AppError::infrastructure(format!("scheduler lock: {err}")))?;
+            Ok(acquired.map(|token| LockLease {
+                name: name.to_string(),
+                token,
+            }))
+        })
+    }
+
+    fn release<'a>(&'a self, lease: &'a LockLease) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            sqlx::query("DELETE FROM scheduler_locks WHERE name = $1 AND token = $2")
+                .bind(&lease.name)
+                .bind(&lease.token)
+                .execute(&self.pool)
+                .await
+                .map_err(|err| AppError::infrastructure(format!("scheduler lock: {err}")))?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn held_lock_is_refused_until_released_or_expired() {
+        let locks = InMemoryLockManager::default();
+        let lease = locks
+            .try_acquire("job:a", Duration::from_mins(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            locks
+                .try_acquire("job:a", Duration::from_mins(1))
+                .await
+                .unwrap()
+                .is_none()
+        );
+        locks.release(&lease).await.unwrap();
+
+        let short = locks
+            .try_acquire("job:a", Duration::ZERO)
+            .await
+            .unwrap()
+            .unwrap();
+        let next = locks
+            .try_acquire("job:a", Duration::from_mins(1))
+            .await
+            .unwrap()
+            .unwrap();
+        // The expired lease no longer releases the lock it lost.
+        locks.release(&short).await.unwrap();
+        assert!(
+            locks
+                .try_acquire("job:a", Duration::from_mins(1))
+                .await
+                .unwrap()
+                .is_none()
+        );
+        locks.release(&next).await.unwrap();
+    }
+}
@@ -1,6 +1,28 @@
 // src/infrastructure/mod.rs
+pub mod blob_storage;
 pub mod database;
+pub mod documents;
+#[cfg(feature = "article-export")]
+pub mod exports;
+pub mod geoip;
+pub mod http_client;
+pub mod integrity;
+pub mod jobs;
+pub mod journal;
+pub mod locks;
+pub mod notification;
+pub mod redaction;
 pub mod repositories;
+pub mod rls;
+pub mod scheduler;
 pub mod security;
+pub mod seed;
+pub mod static_site;
+pub mod syndication;
+pub mod telemetry;
+pub mod tenancy;
 pub mod time;
 pub mod util;
+pub mod views;
+pub mod webhooks;
+pub mod zip;
@@ -0,0 +1,28 @@
+// src/infrastructure/notification.rs
+//! Email senders.
+
+use crate::application::AppResult;
+use crate::application::ports::notification::{EmailMessage, EmailSender};
+use crate::async_support::{BoxFuture, boxed};
+
+/// Writes messages to the log instead of delivering them.
+///
+/// Meant for development and tests: the body, including any reset or
+/// verification token, is logged at `info`.
+#[derive(Debug, Default, Clone, Copy)]
+#[must_use]
+pub struct LogEmailSender;
+
+impl EmailSender for LogEmailSender {
+    fn send(&self, message: EmailMessage) -> BoxFuture<'_, AppResult<()>> {
+        boxed(async move {
+            tracing::info!(
+                to = %message.to,
+                subject = %message.subject,
+                body = %message.body,
+                "email not delivered: no mail transport is configured"
+            );
+            Ok(())
+        })
+    }
+}
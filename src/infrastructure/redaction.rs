@@ -0,0 +1,321 @@
+// src/infrastructure/redaction.rs
+//! Masking of secrets before they reach the audit log or the process logs.
+//!
+//! A field is sensitive when its name, lowercased with `-` read as `_`, is
+//! one of the configured names or ends in `_<name>`: `password` covers
+//! `new_password` but not `password_policy`. In JSON the values of such
+//! fields are replaced, whatever their type. In free text, such as formatted
+//! log lines, `name=value`, `name: value` and `"name":"value"` are masked,
+//! as are the credentials of `Bearer` and `Basic` authorization values.
+
+use serde_json::Value;
+use std::borrow::Cow;
+use std::io::{self, Write};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// What masked values are replaced with.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Field names masked without any configuration.
+pub const DEFAULT_SENSITIVE_FIELDS: &[&str] = &[
+    "password",
+    "password_hash",
+    "passwd",
+    "secret",
+    "token",
+    "authorization",
+    "cookie",
+    "api_key",
+    "private_key",
+    "code_verifier",
+];
+
+/// Authorization schemes whose credentials are masked in text.
+const AUTH_SCHEMES: &[&str] = &["bearer ", "basic "];
+
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    fields: Vec<String>,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new(std::iter::empty::<&str>())
+    }
+}
+
+impl Redactor {
+    /// Mask [`DEFAULT_SENSITIVE_FIELDS`] and `extra_fields`.
+    #[must_use]
+    pub fn new<I, S>(extra_fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut fields: Vec<String> = DEFAULT_SENSITIVE_FIELDS
+            .iter()
+            .map(|field| (*field).to_string())
+            .chain(
+                extra_fields
+                    .into_iter()
+                    .map(|field| normalize(field.as_ref())),
+            )
+            .filter(|field| !field.is_empty())
+            .collect();
+        fields.sort_unstable();
+        fields.dedup();
+        Self { fields }
+    }
+
+    #[must_use]
+    pub fn is_sensitive(&self, field: &str) -> bool {
+        let field = normalize(field);
+        self.fields.iter().any(|name| {
+            field == *name
+                || field
+                    .strip_suffix(name.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('_'))
+        })
+    }
+
+    /// Mask sensitive fields at any depth of `value`, and secrets embedded
+    /// in its strings.
+    pub fn redact_value(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if self.is_sensitive(key) {
+                        *value = Value::String(REDACTED.into());
+                    } else {
+                        self.redact_value(value);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_value(item)),
+            Value::String(text) => {
+                if let Cow::Owned(masked) = self.redact_text(text) {
+                    *text = masked;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Mask secrets in free text; borrows `text` when there are none.
+    #[must_use]
+    pub fn redact_text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        // ASCII lowercasing keeps byte offsets, so matches index `text`.
+        let lower = text.to_ascii_lowercase();
+        let mut spans: Vec<(usize, usize)> = Vec::new();
+        for scheme in AUTH_SCHEMES {
+            for (start, _) in lower.match_indices(scheme) {
+                if is_word_start(&lower, start) {
+                    let value_start = start + scheme.len();
+                    spans.push((value_start, value_end(text, value_start)));
+                }
+            }
+        }
+        for field in &self.fields {
+            for (start, _) in lower.match_indices(field.as_str()) {
+                if let Some(span) = assignment_value(text, start, start + field.len()) {
+                    spans.push(span);
+                }
+            }
+        }
+        spans.retain(|(start, end)| start < end);
+        if spans.is_empty() {
+            return Cow::Borrowed(text);
+        }
+
+        spans.sort_unstable();
+        let mut masked = String::with_capacity(text.len());
+        let mut copied = 0;
+        for (start, end) in spans {
+            if start < copied {
+                continue;
+            }
+            masked.push_str(&text[copied..start]);
+            masked.push_str(REDACTED);
+            copied = end;
+        }
+        masked.push_str(&text[copied..]);
+        Cow::Owned(masked)
+    }
+}
+
+fn normalize(field: &str) -> String {
+    field.trim().to_ascii_lowercase().replace('-', "_")
+}
+
+fn is_word_start(text: &str, start: usize) -> bool {
+    text[..start]
+        .chars()
+        .next_back()
+        .is_none_or(|c| !c.is_ascii_alphanumeric())
+}
+
+/// End of the value starting at `start`: the next whitespace, quote or
+/// separator.
+fn value_end(text: &str, start: usize) -> usize {
+    text[start..]
+        .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | ',' | ';' | '&' | '}' | ']'))
+        .map_or(text.len(), |offset| start + offset)
+}
+
+/// The value assigned to the field name at `name_start..name_end`, if the
+/// name stands alone (or after a `_`-joined prefix) and is followed by `=`
+/// or `:`.
+fn assignment_value(text: &str, name_start: usize, name_end: usize) -> Option<(usize, usize)> {
+    let before = text[..name_start].chars().next_back();
+    // Part of a longer word; `_`-joined prefixes such as `client_` are fine.
+    if before.is_some_and(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    let rest = &text[name_end..];
+    let after_name = rest.trim_start_matches(['"', '\'']);
+    let after_space = after_name.trim_start();
+    let mut chars = after_space.chars();
+    if !matches!(chars.next(), Some('=' | ':')) {
+        return None;
+    }
+    let value = chars.as_str().trim_start();
+    let quoted = value.starts_with(['"', '\'']);
+    let start = text.len() - value.len() + usize::from(quoted);
+    // `Authorization: Bearer <token>` masks the scheme with the token.
+    let scheme = AUTH_SCHEMES
+        .iter()
+        .find(|scheme| {
+            text.get(start..start + scheme.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+        })
+        .map_or(0, |scheme| scheme.len());
+    Some((start, value_end(text, start + scheme)))
+}
+
+/// A [`MakeWriter`] that masks secrets in each formatted event before
+/// passing it on.
+#[derive(Clone)]
+pub struct RedactingMakeWriter<M> {
+    inner: M,
+    redactor: Redactor,
+}
+
+impl<M> RedactingMakeWriter<M> {
+    pub const fn new(inner: M, redactor: Redactor) -> Self {
+        Self { inner, redactor }
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for RedactingMakeWriter<M> {
+    type Writer = RedactingWriter<'a, M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter {
+            inner: self.inner.make_writer(),
+            redactor: &self.redactor,
+        }
+    }
+}
+
+/// Writer returned by [`RedactingMakeWriter`]. The formatter writes each
+/// event in one call, so patterns never straddle writes.
+pub struct RedactingWriter<'a, W> {
+    inner: W,
+    redactor: &'a Redactor,
+}
+
+impl<W: Write> Write for RedactingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match std::str::from_utf8(buf) {
+            Ok(text) => self
+                .inner
+                .write_all(self.redactor.redact_text(text).as_bytes())
+                .map(|()| buf.len()),
+            Err(_) => self.inner.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn field_names_match_whole_or_as_suffix() {
+        let redactor = Redactor::new(["X-Session-Key"]);
+        assert!(redactor.is_sensitive("password"));
+        assert!(redactor.is_sensitive("New-Password"));
+        assert!(redactor.is_sensitive("refresh_token"));
+        assert!(redactor.is_sensitive("x_session_key"));
+        assert!(!redactor.is_sensitive("token_type"));
+        assert!(!redactor.is_sensitive("passwordless"));
+    }
+
+    #[test]
+    fn nested_json_is_masked_at_any_depth() {
+        let redactor = Redactor::default();
+        let mut details = json!({
+            "user": { "username": "alice", "password": "hunter2" },
+            "headers": [
+                { "name": "accept", "value": "*/*" },
+                { "Authorization": "Bearer abc.def" },
+            ],
+            "tokens": { "access_token": { "value": "x" }, "token_type": "access" },
+            "note": "retried with Bearer abc.def, api_key=k-123 and ok",
+            "count": 3,
+        });
+        redactor.redact_value(&mut details);
+
+        assert_eq!(details["user"]["password"], REDACTED);
+        assert_eq!(details["user"]["username"], "alice");
+        assert_eq!(details["headers"][0]["value"], "*/*");
+        assert_eq!(details["headers"][1]["Authorization"], REDACTED);
+        assert_eq!(details["tokens"]["access_token"], REDACTED);
+        assert_eq!(details["tokens"]["token_type"], "access");
+        assert_eq!(
+            details["note"],
+            "retried with Bearer [REDACTED], api_key=[REDACTED] and ok"
+        );
+        assert_eq!(details["count"], 3);
+    }
+
+    #[test]
+    fn log_lines_mask_assignments_and_credentials() {
+        let redactor = Redactor::default();
+        let line = r#"INFO login: password="hunter2" user=alice client_secret: s3cr3t {"refresh_token":"r-1"}"#;
+        assert_eq!(
+            redactor.redact_text(line),
+            r#"INFO login: password="[REDACTED]" user=alice client_secret: [REDACTED] {"refresh_token":"[REDACTED]"}"#
+        );
+        assert_eq!(
+            redactor.redact_text("authorization: Basic dXNlcjpwYXNz"),
+            "authorization: [REDACTED]"
+        );
+        assert!(matches!(
+            redactor.redact_text("token_type=access passwordless=true"),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn writer_masks_each_event() {
+        let redactor = Redactor::default();
+        let mut out = Vec::new();
+        {
+            let mut writer = RedactingWriter {
+                inner: &mut out,
+                redactor: &redactor,
+            };
+            writer.write_all(b"cookie=abc; path=/\n").unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "cookie=[REDACTED]; path=/\n"
+        );
+    }
+}
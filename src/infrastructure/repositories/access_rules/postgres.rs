@@ -0,0 +1,134 @@
+// src/infrastructure/repositories/access_rules/postgres.rs
+use super::super::map_sqlx;
+use crate::async_support::BoxFuture;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{AccessRule, AccessRuleRepository, NewAccessRule, RuleTarget, UserId};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+
+const RULE_COLUMNS: &str =
+    "id, route_group, action, cidr, asn, priority, note, created_by, created_at, expires_at";
+
+#[derive(Clone)]
+#[must_use]
+pub struct PostgresAccessRuleRepository {
+    pool: PgPool,
+}
+
+impl PostgresAccessRuleRepository {
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct RuleRow {
+    id: i64,
+    route_group: String,
+    action: String,
+    cidr: Option<String>,
+    asn: Option<i64>,
+    priority: i32,
+    note: Option<String>,
+    created_by: Option<i64>,
+    created_at: DateTime<Utc>,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<RuleRow> for AccessRule {
+    type Error = DomainError;
+
+    fn try_from(row: RuleRow) -> Result<Self, Self::Error> {
+        let target = match (row.cidr, row.asn) {
+            (Some(cidr), None) => RuleTarget::Cidr(cidr.parse()?),
+            (None, Some(asn)) => RuleTarget::Asn(u32::try_from(asn).map_err(|_| {
+                DomainError::Persistence(format!("access rule {} has an invalid ASN", row.id))
+            })?),
+            _ => {
+                return Err(DomainError::Persistence(format!(
+                    "access rule {} must have exactly one of cidr and asn",
+                    row.id
+                )));
+            }
+        };
+        Ok(Self {
+            id: row.id,
+            group: row.route_group.parse()?,
+            action: row.action.parse()?,
+            target,
+            priority: row.priority,
+            note: row.note,
+            created_by: row.created_by.map(UserId::new).transpose()?,
+            created_at: row.created_at,
+            expires_at: row.expires_at,
+        })
+    }
+}
+
+impl AccessRuleRepository for PostgresAccessRuleRepository {
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<AccessRule>>> {
+        traced("PostgresAccessRuleRepository::list", async move {
+            sqlx::query_as::<_, RuleRow>(&format!(
+                "SELECT {RULE_COLUMNS} FROM access_rules ORDER BY priority, id"
+            ))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .into_iter()
+            .map(AccessRule::try_from)
+            .collect()
+        })
+    }
+
+    fn insert(&self, rule: NewAccessRule) -> BoxFuture<'_, DomainResult<AccessRule>> {
+        traced("PostgresAccessRuleRepository::insert", async move {
+            let (cidr, asn) = match rule.target {
+                RuleTarget::Cidr(range) => (Some(range.to_string()), None),
+                RuleTarget::Asn(asn) => (None, Some(i64::from(asn))),
+            };
+            sqlx::query_as::<_, RuleRow>(&format!(
+                "INSERT INTO access_rules
+                     (route_group, action, cidr, asn, priority, note, created_by, created_at,
+                      expires_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                 RETURNING {RULE_COLUMNS}"
+            ))
+            .bind(rule.group.as_str())
+            .bind(rule.action.as_str())
+            .bind(cidr)
+            .bind(asn)
+            .bind(rule.priority)
+            .bind(rule.note)
+            .bind(rule.created_by.map(i64::from))
+            .bind(rule.created_at)
+            .bind(rule.expires_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .try_into()
+        })
+    }
+
+    fn delete(&self, id: i64) -> BoxFuture<'_, DomainResult<bool>> {
+        traced("PostgresAccessRuleRepository::delete", async move {
+            let result = sqlx::query("DELETE FROM access_rules WHERE id = $1")
+                .bind(id)
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlx)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn delete_expired(&self, now: DateTime<Utc>) -> BoxFuture<'_, DomainResult<u64>> {
+        traced("PostgresAccessRuleRepository::delete_expired", async move {
+            let result = sqlx::query("DELETE FROM access_rules WHERE expires_at <= $1")
+                .bind(now)
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlx)?;
+            Ok(result.rows_affected())
+        })
+    }
+}
@@ -0,0 +1,97 @@
+// src/infrastructure/repositories/articles/contributors.rs
+use super::super::map_sqlx;
+use crate::async_support::BoxFuture;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{ArticleContributor, ArticleId, ContributorRepository, UserId};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+
+#[derive(Clone)]
+#[must_use]
+pub struct PostgresContributorRepository {
+    pool: PgPool,
+}
+
+impl PostgresContributorRepository {
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct ContributorRow {
+    article_id: i64,
+    user_id: i64,
+    added_by: Option<i64>,
+    added_at: DateTime<Utc>,
+}
+
+impl TryFrom<ContributorRow> for ArticleContributor {
+    type Error = DomainError;
+
+    fn try_from(row: ContributorRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            article_id: ArticleId::new(row.article_id)?,
+            user_id: UserId::new(row.user_id)?,
+            added_by: row.added_by.map(UserId::new).transpose()?,
+            added_at: row.added_at,
+        })
+    }
+}
+
+impl ContributorRepository for PostgresContributorRepository {
+    fn add(
+        &self,
+        contributor: ArticleContributor,
+    ) -> BoxFuture<'_, DomainResult<ArticleContributor>> {
+        traced("PostgresContributorRepository::add", async move {
+            // The no-op update makes RETURNING yield the stored row on conflict.
+            let row = sqlx::query_as::<_, ContributorRow>(
+                "INSERT INTO article_contributors (article_id, user_id, added_by, added_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (article_id, user_id)
+                 DO UPDATE SET added_at = article_contributors.added_at
+                 RETURNING article_id, user_id, added_by, added_at",
+            )
+            .bind(i64::from(contributor.article_id))
+            .bind(i64::from(contributor.user_id))
+            .bind(contributor.added_by.map(i64::from))
+            .bind(contributor.added_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+            row.try_into()
+        })
+    }
+
+    fn remove(&self, article_id: ArticleId, user_id: UserId) -> BoxFuture<'_, DomainResult<bool>> {
+        traced("PostgresContributorRepository::remove", async move {
+            let result = sqlx::query(
+                "DELETE FROM article_contributors WHERE article_id = $1 AND user_id = $2",
+            )
+            .bind(i64::from(article_id))
+            .bind(i64::from(user_id))
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn list(&self, article_id: ArticleId) -> BoxFuture<'_, DomainResult<Vec<ArticleContributor>>> {
+        traced("PostgresContributorRepository::list", async move {
+            sqlx::query_as::<_, ContributorRow>(
+                "SELECT article_id, user_id, added_by, added_at FROM article_contributors
+                 WHERE article_id = $1 ORDER BY added_at, user_id",
+            )
+            .bind(i64::from(article_id))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .into_iter()
+            .map(ArticleContributor::try_from)
+            .collect()
+        })
+    }
+}
@@ -0,0 +1,85 @@
+// src/infrastructure/repositories/articles/custom_fields.rs
+use super::super::map_sqlx;
+use crate::async_support::BoxFuture;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{CustomFieldDefinition, CustomFieldRepository};
+use crate::infrastructure::repositories::traced;
+use sqlx::{FromRow, PgPool};
+
+#[derive(Clone)]
+#[must_use]
+pub struct PostgresCustomFieldRepository {
+    pool: PgPool,
+}
+
+impl PostgresCustomFieldRepository {
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct DefinitionRow {
+    name: String,
+    field_type: String,
+    required: bool,
+}
+
+impl TryFrom<DefinitionRow> for CustomFieldDefinition {
+    type Error = DomainError;
+
+    fn try_from(row: DefinitionRow) -> Result<Self, Self::Error> {
+        Self::new(row.name, row.field_type.parse()?, row.required)
+    }
+}
+
+impl CustomFieldRepository for PostgresCustomFieldRepository {
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<CustomFieldDefinition>>> {
+        traced("PostgresCustomFieldRepository::list", async move {
+            let rows = sqlx::query_as::<_, DefinitionRow>(
+                "SELECT name, field_type, required FROM custom_field_definitions ORDER BY name",
+            )
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+
+            rows.into_iter()
+                .map(CustomFieldDefinition::try_from)
+                .collect()
+        })
+    }
+
+    fn upsert(
+        &self,
+        definition: CustomFieldDefinition,
+    ) -> BoxFuture<'_, DomainResult<CustomFieldDefinition>> {
+        traced("PostgresCustomFieldRepository::upsert", async move {
+            let row = sqlx::query_as::<_, DefinitionRow>(
+                "INSERT INTO custom_field_definitions (name, field_type, required)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (name) DO UPDATE
+                 SET field_type = EXCLUDED.field_type, required = EXCLUDED.required, updated_at = NOW()
+                 RETURNING name, field_type, required",
+            )
+            .bind(&definition.name)
+            .bind(definition.field_type.as_str())
+            .bind(definition.required)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+
+            CustomFieldDefinition::try_from(row)
+        })
+    }
+
+    fn delete<'a>(&'a self, name: &'a str) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("PostgresCustomFieldRepository::delete", async move {
+            let result = sqlx::query("DELETE FROM custom_field_definitions WHERE name = $1")
+                .bind(name)
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlx)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+}
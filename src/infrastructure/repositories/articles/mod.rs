@@ -1,5 +1,13 @@
+mod contributors;
+mod custom_fields;
 mod postgres;
 mod revision;
+mod syndication;
 
+pub use contributors::PostgresContributorRepository;
+pub use custom_fields::PostgresCustomFieldRepository;
 pub use postgres::{PostgresArticleReadRepository, PostgresArticleWriteRepository};
+pub(super) use postgres::{insert_article, update_article};
 pub use revision::PostgresArticleRevisionRepository;
+pub(super) use revision::append_revision;
+pub use syndication::PostgresSyndicationOptOutStore;
@@ -1,24 +1,43 @@
 // src/infrastructure/repositories/articles/postgres.rs
 use super::super::map_sqlx;
-use crate::async_support::{BoxFuture, boxed};
+use crate::async_support::BoxFuture;
+use crate::domain::PAGE_LIMIT_CEILING;
 use crate::domain::UserId;
+use crate::domain::article::repository::ArticleQuery;
 use crate::domain::errors::{DomainError, DomainResult};
 use crate::domain::{
-    Article, ArticleBody, ArticleId, ArticleListCursor, ArticleReadRepository, ArticleSlug,
-    ArticleTitle, ArticleUpdate, ArticleWriteRepository, NewArticle,
+    Article, ArticleBody, ArticleId, ArticleListCursor, ArticlePopularityCursor,
+    ArticleReadRepository, ArticleSearchHit, ArticleSlug, ArticleTitle, ArticleUpdate,
+    ArticleWriteRepository, CustomFields, FacetCount, NewArticle, PrivateScope, SearchFacets, Tag,
 };
+use crate::infrastructure::repositories::traced;
+use crate::infrastructure::rls;
 use chrono::{DateTime, Utc};
-use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
+use sqlx::types::Json;
+use sqlx::{FromRow, PgConnection, PgPool, Postgres, QueryBuilder};
+
+/// Text search configuration used until one is configured.
+const DEFAULT_SEARCH_LANGUAGE: &str = "simple";
 
 #[derive(Clone)]
 #[must_use]
 pub struct PostgresArticleWriteRepository {
     pool: PgPool,
+    search_language: String,
 }
 
 impl PostgresArticleWriteRepository {
-    pub const fn new(pool: PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            search_language: DEFAULT_SEARCH_LANGUAGE.into(),
+        }
+    }
+
+    /// Index written articles with the text search configuration `language`.
+    pub fn with_search_language(mut self, language: impl Into<String>) -> Self {
+        self.search_language = language.into();
+        self
     }
 }
 
@@ -26,11 +45,22 @@ impl PostgresArticleWriteRepository {
 #[must_use]
 pub struct PostgresArticleReadRepository {
     pool: PgPool,
+    search_language: String,
 }
 
 impl PostgresArticleReadRepository {
-    pub const fn new(pool: PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            search_language: DEFAULT_SEARCH_LANGUAGE.into(),
+        }
+    }
+
+    /// Parse search queries with the text search configuration `language`;
+    /// it should match the one articles are written with.
+    pub fn with_search_language(mut self, language: impl Into<String>) -> Self {
+        self.search_language = language.into();
+        self
     }
 }
 
@@ -40,11 +70,15 @@ struct ArticleRow {
     title: String,
     slug: String,
     body: String,
+    tags: Vec<String>,
+    custom_fields: Json<CustomFields>,
     published: bool,
     published_at: Option<DateTime<Utc>>,
+    visibility: String,
     author_id: i64,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    view_count: i64,
 }
 
 impl TryFrom<ArticleRow> for Article {
@@ -56,145 +90,443 @@ impl TryFrom<ArticleRow> for Article {
             title: ArticleTitle::new(row.title)?,
             slug: ArticleSlug::new(row.slug)?,
             body: ArticleBody::new(row.body)?,
+            tags: row
+                .tags
+                .into_iter()
+                .map(Tag::new)
+                .collect::<DomainResult<_>>()?,
+            custom_fields: row.custom_fields.0,
             published: row.published,
             published_at: row.published_at,
+            visibility: row.visibility.parse()?,
             author_id: UserId::new(row.author_id)?,
             created_at: row.created_at,
             updated_at: row.updated_at,
+            view_count: u64::try_from(row.view_count)
+                .map_err(|_| DomainError::Persistence("article view count is negative".into()))?,
         })
     }
 }
 
-impl ArticleWriteRepository for PostgresArticleWriteRepository {
-    fn insert(&self, article: NewArticle) -> BoxFuture<'_, DomainResult<Article>> {
-        boxed(async move {
-            let NewArticle {
-                title,
-                slug,
-                body,
-                published,
-                published_at,
-                author_id,
-                created_at,
-                updated_at,
-            } = article;
+fn tag_strings(tags: Vec<Tag>) -> Vec<String> {
+    tags.into_iter().map(Tag::into_inner).collect()
+}
 
-            let row = sqlx::query_as::<_, ArticleRow>(
-                "INSERT INTO articles (title, slug, body, published, published_at, author_id, created_at, updated_at)
-                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-                 RETURNING id, title, slug, body, published, published_at, author_id, created_at, updated_at",
-            )
-            .bind(title.as_str())
-            .bind(slug.as_str())
-            .bind(body.as_str())
-            .bind(published)
-            .bind(published_at)
-            .bind(i64::from(author_id))
-            .bind(created_at)
-            .bind(updated_at)
-            .fetch_one(&self.pool)
-            .await
-            .map_err(map_sqlx)?;
+/// Insert `article` on `conn`, indexed with the text search configuration
+/// `language`.
+pub(in crate::infrastructure::repositories) async fn insert_article(
+    conn: &mut PgConnection,
+    article: NewArticle,
+    language: &str,
+) -> DomainResult<Article> {
+    let NewArticle {
+        title,
+        slug,
+        body,
+        tags,
+        custom_fields,
+        published,
+        published_at,
+        visibility,
+        author_id,
+        created_at,
+        updated_at,
+    } = article;
 
-            Article::try_from(row)
-        })
+    let row = sqlx::query_as::<_, ArticleRow>(
+        "INSERT INTO articles (title, slug, body, tags, custom_fields, published, published_at, visibility, author_id, created_at, updated_at, search_language)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12::regconfig)
+         RETURNING id, title, slug, body, tags, custom_fields, published, published_at, visibility, author_id, created_at, updated_at, view_count",
+    )
+    .bind(title.as_str())
+    .bind(slug.as_str())
+    .bind(body.as_str())
+    .bind(tag_strings(tags))
+    .bind(Json(custom_fields))
+    .bind(published)
+    .bind(published_at)
+    .bind(visibility.as_str())
+    .bind(i64::from(author_id))
+    .bind(created_at)
+    .bind(updated_at)
+    .bind(language)
+    .fetch_one(conn)
+    .await
+    .map_err(map_sqlx)?;
+
+    Article::try_from(row)
+}
+
+/// Apply `update` on `conn`, re-indexing with the text search configuration
+/// `language`. Fails with a conflict if the article changed since
+/// `original_updated_at`.
+pub(in crate::infrastructure::repositories) async fn update_article(
+    conn: &mut PgConnection,
+    update: ArticleUpdate,
+    language: &str,
+) -> DomainResult<Article> {
+    let ArticleUpdate {
+        id,
+        title,
+        slug,
+        body,
+        tags,
+        custom_fields,
+        publish_state,
+        visibility,
+        original_updated_at,
+        updated_at,
+    } = update;
+
+    let mut builder: QueryBuilder<Postgres> =
+        QueryBuilder::new("UPDATE articles SET updated_at = ");
+    builder.push_bind(updated_at);
+    builder.push(", search_language = ");
+    builder.push_bind(language);
+    builder.push("::regconfig");
+
+    if let Some(title) = title {
+        let title_str: String = title.into();
+        builder.push(", title = ");
+        builder.push_bind(title_str);
     }
 
-    fn update(&self, update: ArticleUpdate) -> BoxFuture<'_, DomainResult<Article>> {
-        boxed(async move {
-            let ArticleUpdate {
-                id,
-                title,
-                slug,
-                body,
-                publish_state,
-                original_updated_at,
-                updated_at,
-            } = update;
-
-            let mut builder: QueryBuilder<Postgres> =
-                QueryBuilder::new("UPDATE articles SET updated_at = ");
-            builder.push_bind(updated_at);
-
-            if let Some(title) = title {
-                let title_str: String = title.into();
-                builder.push(", title = ");
-                builder.push_bind(title_str);
-            }
+    if let Some(slug) = slug {
+        let slug_str: String = slug.into();
+        builder.push(", slug = ");
+        builder.push_bind(slug_str);
+    }
 
-            if let Some(slug) = slug {
-                let slug_str: String = slug.into();
-                builder.push(", slug = ");
-                builder.push_bind(slug_str);
-            }
+    if let Some(body) = body {
+        let body_str: String = body.into();
+        builder.push(", body = ");
+        builder.push_bind(body_str);
+    }
 
-            if let Some(body) = body {
-                let body_str: String = body.into();
-                builder.push(", body = ");
-                builder.push_bind(body_str);
-            }
+    if let Some(tags) = tags {
+        builder.push(", tags = ");
+        builder.push_bind(tag_strings(tags));
+    }
 
-            if let Some(state) = publish_state {
-                builder.push(", published = ");
-                builder.push_bind(state.published);
-                builder.push(", published_at = ");
-                builder.push_bind(state.published_at);
-            }
+    if let Some(custom_fields) = custom_fields {
+        builder.push(", custom_fields = ");
+        builder.push_bind(Json(custom_fields));
+    }
 
-            builder.push(" WHERE id = ");
-            builder.push_bind(i64::from(id));
-            builder.push(" AND updated_at = ");
-            builder.push_bind(original_updated_at);
-            builder.push(
-                " RETURNING id, title, slug, body, published, published_at, author_id, created_at, updated_at",
-            );
+    if let Some(state) = publish_state {
+        builder.push(", published = ");
+        builder.push_bind(state.published);
+        builder.push(", published_at = ");
+        builder.push_bind(state.published_at);
+    }
 
-            let maybe_row = builder
-                .build_query_as::<ArticleRow>()
-                .fetch_optional(&self.pool)
-                .await
-                .map_err(map_sqlx)?;
+    if let Some(visibility) = visibility {
+        builder.push(", visibility = ");
+        builder.push_bind(visibility.as_str());
+    }
+
+    builder.push(" WHERE id = ");
+    builder.push_bind(i64::from(id));
+    builder.push(" AND updated_at = ");
+    builder.push_bind(original_updated_at);
+    builder.push(
+        " RETURNING id, title, slug, body, tags, custom_fields, published, published_at, visibility, author_id, created_at, updated_at, view_count",
+    );
+
+    let row = builder
+        .build_query_as::<ArticleRow>()
+        .fetch_optional(conn)
+        .await
+        .map_err(map_sqlx)?
+        .ok_or_else(|| DomainError::Conflict("article update conflict, please retry".into()))?;
+
+    Article::try_from(row)
+}
+
+/// Whether `update` leaves the indexed content alone, touching only the
+/// publish state and tags.
+const fn changes_state_only(update: &ArticleUpdate) -> bool {
+    update.title.is_none()
+        && update.slug.is_none()
+        && update.body.is_none()
+        && update.custom_fields.is_none()
+        && update.visibility.is_none()
+}
+
+/// Apply publish state and tag `updates` on `conn` in one statement, skipping
+/// articles that changed since their `original_updated_at`.
+async fn update_states(
+    conn: &mut PgConnection,
+    updates: Vec<ArticleUpdate>,
+) -> DomainResult<Vec<Article>> {
+    let mut ids = Vec::with_capacity(updates.len());
+    let mut original_updated_ats = Vec::with_capacity(updates.len());
+    let mut updated_ats = Vec::with_capacity(updates.len());
+    let mut published = Vec::with_capacity(updates.len());
+    let mut published_ats = Vec::with_capacity(updates.len());
+    let mut tags = Vec::with_capacity(updates.len());
+    for update in updates {
+        ids.push(i64::from(update.id));
+        original_updated_ats.push(update.original_updated_at);
+        updated_ats.push(update.updated_at);
+        published.push(update.publish_state.as_ref().map(|state| state.published));
+        published_ats.push(update.publish_state.and_then(|state| state.published_at));
+        tags.push(update.tags.map(|tags| Json(tag_strings(tags))));
+    }
+
+    // A NULL state or tag list keeps the stored one.
+    let rows = sqlx::query_as::<_, ArticleRow>(
+        "UPDATE articles AS a SET
+             updated_at = v.updated_at,
+             published = COALESCE(v.published, a.published),
+             published_at = CASE WHEN v.published IS NULL THEN a.published_at ELSE v.published_at END,
+             tags = CASE WHEN v.tags IS NULL THEN a.tags ELSE ARRAY(SELECT jsonb_array_elements_text(v.tags)) END
+         FROM UNNEST($1::BIGINT[], $2::TIMESTAMPTZ[], $3::TIMESTAMPTZ[], $4::BOOLEAN[], $5::TIMESTAMPTZ[], $6::JSONB[])
+             AS v(id, original_updated_at, updated_at, published, published_at, tags)
+         WHERE a.id = v.id AND a.updated_at = v.original_updated_at
+         RETURNING a.id, a.title, a.slug, a.body, a.tags, a.custom_fields, a.published, a.published_at, a.visibility, a.author_id, a.created_at, a.updated_at, a.view_count",
+    )
+    .bind(ids)
+    .bind(original_updated_ats)
+    .bind(updated_ats)
+    .bind(published)
+    .bind(published_ats)
+    .bind(tags)
+    .fetch_all(conn)
+    .await
+    .map_err(map_sqlx)?;
 
-            let row = maybe_row.ok_or_else(|| {
-                DomainError::Conflict("article update conflict, please retry".into())
-            })?;
+    rows.into_iter().map(Article::try_from).collect()
+}
 
-            Article::try_from(row)
+impl ArticleWriteRepository for PostgresArticleWriteRepository {
+    fn insert(&self, article: NewArticle) -> BoxFuture<'_, DomainResult<Article>> {
+        traced("PostgresArticleWriteRepository::insert", async move {
+            let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+            let created = insert_article(&mut tx, article, &self.search_language).await?;
+            tx.commit().await.map_err(map_sqlx)?;
+            Ok(created)
+        })
+    }
+
+    fn update(&self, update: ArticleUpdate) -> BoxFuture<'_, DomainResult<Article>> {
+        traced("PostgresArticleWriteRepository::update", async move {
+            let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+            let updated = update_article(&mut tx, update, &self.search_language).await?;
+            tx.commit().await.map_err(map_sqlx)?;
+            Ok(updated)
         })
     }
 
     fn delete(&self, id: ArticleId) -> BoxFuture<'_, DomainResult<()>> {
-        boxed(async move {
+        traced("PostgresArticleWriteRepository::delete", async move {
+            let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
             let result = sqlx::query("DELETE FROM articles WHERE id = $1")
                 .bind(i64::from(id))
-                .execute(&self.pool)
+                .execute(&mut *tx)
                 .await
                 .map_err(map_sqlx)?;
+            tx.commit().await.map_err(map_sqlx)?;
             if result.rows_affected() == 0 {
                 return Err(DomainError::NotFound("article not found".into()));
             }
             Ok(())
         })
     }
+
+    fn update_many(
+        &self,
+        updates: Vec<ArticleUpdate>,
+    ) -> BoxFuture<'_, DomainResult<Vec<Article>>> {
+        traced("PostgresArticleWriteRepository::update_many", async move {
+            let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+            let stored = if updates.iter().all(changes_state_only) {
+                update_states(&mut tx, updates).await?
+            } else {
+                let mut stored = Vec::with_capacity(updates.len());
+                for update in updates {
+                    match update_article(&mut tx, update, &self.search_language).await {
+                        Ok(article) => stored.push(article),
+                        Err(DomainError::Conflict(_)) => {}
+                        Err(err) => return Err(err),
+                    }
+                }
+                stored
+            };
+            tx.commit().await.map_err(map_sqlx)?;
+            Ok(stored)
+        })
+    }
+
+    fn delete_many<'a>(
+        &'a self,
+        ids: &'a [ArticleId],
+    ) -> BoxFuture<'a, DomainResult<Vec<ArticleId>>> {
+        traced("PostgresArticleWriteRepository::delete_many", async move {
+            let ids: Vec<i64> = ids.iter().copied().map(i64::from).collect();
+            let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+            let deleted: Vec<i64> =
+                sqlx::query_scalar("DELETE FROM articles WHERE id = ANY($1) RETURNING id")
+                    .bind(ids)
+                    .fetch_all(&mut *tx)
+                    .await
+                    .map_err(map_sqlx)?;
+            tx.commit().await.map_err(map_sqlx)?;
+            deleted.into_iter().map(ArticleId::new).collect()
+        })
+    }
+
+    fn add_views<'a>(&'a self, views: &'a [(ArticleId, u64)]) -> BoxFuture<'a, DomainResult<()>> {
+        traced("PostgresArticleWriteRepository::add_views", async move {
+            if views.is_empty() {
+                return Ok(());
+            }
+            let ids: Vec<i64> = views.iter().map(|(id, _)| i64::from(*id)).collect();
+            let counts: Vec<i64> = views
+                .iter()
+                .map(|(_, count)| i64::try_from(*count).unwrap_or(i64::MAX))
+                .collect();
+
+            // Deleted articles simply drop out of the join.
+            let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+            sqlx::query(
+                "UPDATE articles AS a SET view_count = a.view_count + v.views
+                 FROM UNNEST($1::BIGINT[], $2::BIGINT[]) AS v(id, views)
+                 WHERE a.id = v.id",
+            )
+            .bind(ids)
+            .bind(counts)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx)?;
+            tx.commit().await.map_err(map_sqlx)?;
+            Ok(())
+        })
+    }
+
+    fn reassign_author(&self, from: UserId, to: UserId) -> BoxFuture<'_, DomainResult<u64>> {
+        traced(
+            "PostgresArticleWriteRepository::reassign_author",
+            async move {
+                let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+                let result = sqlx::query("UPDATE articles SET author_id = $2 WHERE author_id = $1")
+                    .bind(i64::from(from))
+                    .bind(i64::from(to))
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(map_sqlx)?;
+                tx.commit().await.map_err(map_sqlx)?;
+                Ok(result.rows_affected())
+            },
+        )
+    }
 }
 
 enum SearchMode<'q> {
     None,
+    /// `websearch_to_tsquery` syntax: quoted phrases, `-` negation and `or`.
     FullText(&'q str),
-    Trigram(&'q str),
+    /// Substring fallback; `query` is kept to highlight what still matches.
+    Trigram {
+        pattern: &'q str,
+        query: &'q str,
+    },
+}
+
+impl SearchMode<'_> {
+    const fn query(&self) -> Option<&str> {
+        match self {
+            Self::None => None,
+            Self::FullText(query) | Self::Trigram { query, .. } => Some(query),
+        }
+    }
+}
+
+/// Excerpt options: matches wrapped in `<mark>`, at most two fragments.
+const HEADLINE_OPTIONS: &str =
+    "'StartSel=<mark>, StopSel=</mark>, MaxFragments=2, MaxWords=30, MinWords=10'";
+
+#[derive(Debug, FromRow)]
+struct SearchRow {
+    #[sqlx(flatten)]
+    article: ArticleRow,
+    headline: Option<String>,
+}
+
+impl TryFrom<SearchRow> for ArticleSearchHit {
+    type Error = DomainError;
+
+    fn try_from(row: SearchRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            article: Article::try_from(row.article)?,
+            headline: row.headline,
+        })
+    }
+}
+
+type HitPage = (Vec<ArticleSearchHit>, Option<ArticleListCursor>);
+
+/// One count of the facets query: an author, a tag, or the `published` or
+/// `drafts` total.
+#[derive(Debug, FromRow)]
+struct FacetRow {
+    facet: String,
+    author_id: Option<i64>,
+    tag: Option<String>,
+    count: i64,
+}
+
+/// The `ILIKE` pattern of the substring fallback; quotes only mean something
+/// to the full-text parser.
+fn trigram_pattern(query: &str) -> String {
+    format!("%{}%", query.replace('"', ""))
 }
 
 impl PostgresArticleReadRepository {
+    fn push_tsquery<'a>(&'a self, builder: &mut QueryBuilder<'a, Postgres>, query: &'a str) {
+        builder.push("websearch_to_tsquery(");
+        builder.push_bind(self.search_language.as_str());
+        builder.push("::regconfig, ");
+        builder.push_bind(query);
+        builder.push(")");
+    }
+
+    /// The `headline` column: an HTML-escaped excerpt of the body around
+    /// `query`, or `NULL` without one.
+    fn push_headline<'a>(
+        &'a self,
+        builder: &mut QueryBuilder<'a, Postgres>,
+        query: Option<&'a str>,
+    ) {
+        let Some(query) = query else {
+            builder.push("NULL::TEXT");
+            return;
+        };
+        builder.push("ts_headline(");
+        builder.push_bind(self.search_language.as_str());
+        builder.push(
+            "::regconfig, replace(replace(replace(body, '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), ",
+        );
+        self.push_tsquery(builder, query);
+        builder.push(", ");
+        builder.push(HEADLINE_OPTIONS);
+        builder.push(")");
+    }
+
+    /// Public listings only show public articles; unlisted and private ones
+    /// are reached by slug or id, or in listings that include drafts.
     fn apply_conditions<'a>(
+        &'a self,
         builder: &mut QueryBuilder<'a, Postgres>,
         include_drafts: bool,
         cursor: Option<&'a ArticleListCursor>,
         mode: &SearchMode<'a>,
+        tag: Option<&'a str>,
     ) {
         let mut has_where = if include_drafts {
             false
         } else {
-            builder.push(" WHERE published = TRUE");
+            builder.push(" WHERE published = TRUE AND visibility = 'public'");
             true
         };
 
@@ -206,11 +538,10 @@ impl PostgresArticleReadRepository {
                     builder.push(" WHERE ");
                     has_where = true;
                 }
-                builder.push("search @@ plainto_tsquery('simple', ");
-                builder.push_bind(*query);
-                builder.push(")");
+                builder.push("search @@ ");
+                self.push_tsquery(builder, query);
             }
-            SearchMode::Trigram(pattern) => {
+            SearchMode::Trigram { pattern, .. } => {
                 if has_where {
                     builder.push(" AND (");
                 } else {
@@ -226,6 +557,18 @@ impl PostgresArticleReadRepository {
             SearchMode::None => {}
         }
 
+        if let Some(tag) = tag {
+            if has_where {
+                builder.push(" AND ");
+            } else {
+                builder.push(" WHERE ");
+                has_where = true;
+            }
+            builder.push("tags @> ARRAY[");
+            builder.push_bind(tag);
+            builder.push("]::TEXT[]");
+        }
+
         if let Some(cursor) = cursor {
             if has_where {
                 builder.push(" AND ");
@@ -240,12 +583,16 @@ impl PostgresArticleReadRepository {
         }
     }
 
-    fn apply_ordering<'a>(builder: &mut QueryBuilder<'a, Postgres>, mode: &SearchMode<'a>) {
+    fn apply_ordering<'a>(
+        &'a self,
+        builder: &mut QueryBuilder<'a, Postgres>,
+        mode: &SearchMode<'a>,
+    ) {
         match mode {
             SearchMode::FullText(query) => {
-                builder.push(" ORDER BY ts_rank(search, plainto_tsquery('simple', ");
-                builder.push_bind(*query);
-                builder.push(")) DESC, created_at DESC, id DESC");
+                builder.push(" ORDER BY ts_rank(search, ");
+                self.push_tsquery(builder, query);
+                builder.push(") DESC, created_at DESC, id DESC");
             }
             _ => {
                 builder.push(" ORDER BY created_at DESC, id DESC");
@@ -253,58 +600,193 @@ impl PostgresArticleReadRepository {
         }
     }
 
+    /// One page in `mode`; excerpts are only computed when `highlight` is set.
     async fn fetch_page(
         &self,
         include_drafts: bool,
         limit: u32,
         cursor: Option<&ArticleListCursor>,
         mode: SearchMode<'_>,
-    ) -> DomainResult<(Vec<Article>, Option<ArticleListCursor>)> {
-        let limit = limit.clamp(1, 100);
+        tag: Option<&str>,
+        highlight: bool,
+    ) -> DomainResult<HitPage> {
+        let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
         let fetch_limit = i64::from(limit) + 1;
 
         let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
-            "SELECT id, title, slug, body, published, published_at, author_id, created_at, updated_at FROM articles",
+            "SELECT id, title, slug, body, tags, custom_fields, published, published_at, visibility, author_id, created_at, updated_at, view_count, ",
         );
-        Self::apply_conditions(&mut builder, include_drafts, cursor, &mode);
-        Self::apply_ordering(&mut builder, &mode);
+        self.push_headline(&mut builder, mode.query().filter(|_| highlight));
+        builder.push(" AS headline FROM articles");
+        self.apply_conditions(&mut builder, include_drafts, cursor, &mode, tag);
+        self.apply_ordering(&mut builder, &mode);
         builder.push(" LIMIT ");
         builder.push_bind(fetch_limit);
 
+        let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
         let rows = builder
-            .build_query_as::<ArticleRow>()
-            .fetch_all(&self.pool)
+            .build_query_as::<SearchRow>()
+            .fetch_all(&mut *tx)
             .await
             .map_err(map_sqlx)?;
+        tx.commit().await.map_err(map_sqlx)?;
 
-        let mut articles = rows
+        let mut hits = rows
             .into_iter()
-            .map(Article::try_from)
+            .map(ArticleSearchHit::try_from)
             .collect::<Result<Vec<_>, _>>()?;
 
         let mut next_cursor = None;
-        if articles.len() > limit as usize {
-            articles.pop();
-            if let Some(last) = articles.last() {
-                next_cursor = Some(ArticleListCursor::from_parts(last.created_at, last.id));
+        if hits.len() > limit as usize {
+            hits.pop();
+            if let Some(last) = hits.last() {
+                next_cursor = Some(ArticleListCursor::from_parts(
+                    last.article.created_at,
+                    last.article.id,
+                ));
             }
         }
 
-        Ok((articles, next_cursor))
+        Ok((hits, next_cursor))
     }
+
+    /// Full-text search with a trigram fallback when it finds nothing; a plain
+    /// listing when `search` is blank.
+    async fn search_page(
+        &self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: Option<&ArticleListCursor>,
+        search: Option<&str>,
+        tag: Option<&str>,
+        highlight: bool,
+    ) -> DomainResult<HitPage> {
+        if let Some(query) = search.map(str::trim).filter(|value| !value.is_empty()) {
+            let (hits, next_cursor) = self
+                .fetch_page(
+                    include_drafts,
+                    limit,
+                    cursor,
+                    SearchMode::FullText(query),
+                    tag,
+                    highlight,
+                )
+                .await?;
+
+            if !hits.is_empty() {
+                return Ok((hits, next_cursor));
+            }
+
+            let pattern = trigram_pattern(query);
+            return self
+                .fetch_page(
+                    include_drafts,
+                    limit,
+                    cursor,
+                    SearchMode::Trigram {
+                        pattern: &pattern,
+                        query,
+                    },
+                    tag,
+                    highlight,
+                )
+                .await;
+        }
+
+        self.fetch_page(
+            include_drafts,
+            limit,
+            cursor,
+            SearchMode::None,
+            tag,
+            highlight,
+        )
+        .await
+    }
+
+    async fn count_facets(
+        &self,
+        include_drafts: bool,
+        private: PrivateScope,
+        mode: SearchMode<'_>,
+        tag: Option<&str>,
+    ) -> DomainResult<SearchFacets> {
+        let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
+            "WITH found AS (SELECT author_id, published, tags, visibility FROM articles",
+        );
+        self.apply_conditions(&mut builder, include_drafts, None, &mode, tag);
+        builder.push("), matched AS (SELECT author_id, published, tags FROM found");
+        match private {
+            PrivateScope::Excluded => {
+                builder.push(" WHERE visibility <> 'private'");
+            }
+            PrivateScope::AuthoredBy(user_id) => {
+                builder.push(" WHERE visibility <> 'private' OR author_id = ");
+                builder.push_bind(i64::from(user_id));
+            }
+            PrivateScope::All => {}
+        }
+        builder.push(
+            ") SELECT 'published' AS facet, NULL::BIGINT AS author_id, NULL::TEXT AS tag, \
+             COUNT(*) FILTER (WHERE published) AS count FROM matched \
+             UNION ALL SELECT 'drafts', NULL, NULL, COUNT(*) FILTER (WHERE NOT published) FROM matched \
+             UNION ALL SELECT 'author', author_id, NULL, COUNT(*) FROM matched GROUP BY author_id \
+             UNION ALL SELECT 'tag', NULL, tag, COUNT(*) FROM matched \
+             CROSS JOIN LATERAL unnest(tags) AS tag GROUP BY tag",
+        );
+
+        let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+        let rows = builder
+            .build_query_as::<FacetRow>()
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(map_sqlx)?;
+        tx.commit().await.map_err(map_sqlx)?;
+
+        let (mut published, mut drafts) = (0, 0);
+        let mut authors = Vec::new();
+        let mut tags = Vec::new();
+        for row in rows {
+            let count = u64::try_from(row.count).unwrap_or_default();
+            match (row.facet.as_str(), row.author_id, row.tag) {
+                ("published", _, _) => published = count,
+                ("drafts", _, _) => drafts = count,
+                ("author", Some(author_id), _) => authors.push(FacetCount {
+                    value: UserId::new(author_id)?,
+                    count,
+                }),
+                ("tag", _, Some(tag)) => tags.push(FacetCount {
+                    value: Tag::new(tag)?,
+                    count,
+                }),
+                _ => {}
+            }
+        }
+
+        Ok(SearchFacets::new(published, drafts, authors, tags))
+    }
+}
+
+fn articles_of((hits, next_cursor): HitPage) -> (Vec<Article>, Option<ArticleListCursor>) {
+    (
+        hits.into_iter().map(|hit| hit.article).collect(),
+        next_cursor,
+    )
 }
 
 impl ArticleReadRepository for PostgresArticleReadRepository {
     fn find_by_id(&self, id: ArticleId) -> BoxFuture<'_, DomainResult<Option<Article>>> {
-        boxed(async move {
+        traced("PostgresArticleReadRepository::find_by_id", async move {
+            let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
             let row = sqlx::query_as::<_, ArticleRow>(
-                "SELECT id, title, slug, body, published, published_at, author_id, created_at, updated_at
+                "SELECT id, title, slug, body, tags, custom_fields, published, published_at, visibility, author_id, created_at, updated_at, view_count
                  FROM articles WHERE id = $1",
             )
             .bind(i64::from(id))
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *tx)
             .await
             .map_err(map_sqlx)?;
+            tx.commit().await.map_err(map_sqlx)?;
 
             row.map(Article::try_from).transpose()
         })
@@ -314,20 +796,44 @@ impl ArticleReadRepository for PostgresArticleReadRepository {
         &'a self,
         slug: &'a ArticleSlug,
     ) -> BoxFuture<'a, DomainResult<Option<Article>>> {
-        boxed(async move {
+        traced("PostgresArticleReadRepository::find_by_slug", async move {
+            let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
             let row = sqlx::query_as::<_, ArticleRow>(
-                "SELECT id, title, slug, body, published, published_at, author_id, created_at, updated_at
+                "SELECT id, title, slug, body, tags, custom_fields, published, published_at, visibility, author_id, created_at, updated_at, view_count
                  FROM articles WHERE slug = $1",
             )
             .bind(slug.as_str())
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *tx)
             .await
             .map_err(map_sqlx)?;
+            tx.commit().await.map_err(map_sqlx)?;
 
             row.map(Article::try_from).transpose()
         })
     }
 
+    fn find_by_ids<'a>(
+        &'a self,
+        ids: &'a [ArticleId],
+    ) -> BoxFuture<'a, DomainResult<Vec<Article>>> {
+        traced("PostgresArticleReadRepository::find_by_ids", async move {
+            let ids: Vec<i64> = ids.iter().copied().map(i64::from).collect();
+
+            let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+            let rows = sqlx::query_as::<_, ArticleRow>(
+                "SELECT id, title, slug, body, tags, custom_fields, published, published_at, visibility, author_id, created_at, updated_at, view_count
+                 FROM articles WHERE id = ANY($1)",
+            )
+            .bind(ids)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(map_sqlx)?;
+            tx.commit().await.map_err(map_sqlx)?;
+
+            rows.into_iter().map(Article::try_from).collect()
+        })
+    }
+
     fn list_page<'a>(
         &'a self,
         include_drafts: bool,
@@ -335,36 +841,287 @@ impl ArticleReadRepository for PostgresArticleReadRepository {
         cursor: Option<ArticleListCursor>,
         search: Option<&'a str>,
     ) -> BoxFuture<'a, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
-        boxed(async move {
-            let cursor_ref = cursor.as_ref();
-
-            if let Some(query) = search.map(str::trim).filter(|value| !value.is_empty()) {
-                let (articles, next_cursor) = self
-                    .fetch_page(
-                        include_drafts,
-                        limit,
-                        cursor_ref,
-                        SearchMode::FullText(query),
-                    )
-                    .await?;
-
-                if !articles.is_empty() {
-                    return Ok((articles, next_cursor));
-                }
+        traced("PostgresArticleReadRepository::list_page", async move {
+            self.search_page(include_drafts, limit, cursor.as_ref(), search, None, false)
+                .await
+                .map(articles_of)
+        })
+    }
+
+    fn list(
+        &self,
+        query: ArticleQuery,
+    ) -> BoxFuture<'_, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        traced("PostgresArticleReadRepository::list", async move {
+            self.search_page(
+                query.include_drafts,
+                query.limit,
+                query.cursor.as_ref(),
+                query.search.as_deref(),
+                query.tag.as_ref().map(Tag::as_str),
+                false,
+            )
+            .await
+            .map(articles_of)
+        })
+    }
 
-                let pattern = format!("%{query}%");
+    fn search(&self, query: ArticleQuery) -> BoxFuture<'_, DomainResult<HitPage>> {
+        traced("PostgresArticleReadRepository::search", async move {
+            self.search_page(
+                query.include_drafts,
+                query.limit,
+                query.cursor.as_ref(),
+                query.search.as_deref(),
+                query.tag.as_ref().map(Tag::as_str),
+                true,
+            )
+            .await
+        })
+    }
+
+    fn search_facets<'a>(
+        &'a self,
+        include_drafts: bool,
+        private: PrivateScope,
+        search: Option<&'a str>,
+        tag: Option<&'a Tag>,
+    ) -> BoxFuture<'a, DomainResult<Option<SearchFacets>>> {
+        traced("PostgresArticleReadRepository::search_facets", async move {
+            let tag = tag.map(Tag::as_str);
+            let Some(query) = search.map(str::trim).filter(|value| !value.is_empty()) else {
                 return self
-                    .fetch_page(
-                        include_drafts,
-                        limit,
-                        cursor_ref,
-                        SearchMode::Trigram(&pattern),
-                    )
-                    .await;
+                    .count_facets(include_drafts, private, SearchMode::None, tag)
+                    .await
+                    .map(Some);
+            };
+
+            // Count what `search` pages through, including its fallback.
+            let facets = self
+                .count_facets(include_drafts, private, SearchMode::FullText(query), tag)
+                .await?;
+            if facets.total > 0 {
+                return Ok(Some(facets));
             }
+            let pattern = trigram_pattern(query);
+            self.count_facets(
+                include_drafts,
+                private,
+                SearchMode::Trigram {
+                    pattern: &pattern,
+                    query,
+                },
+                tag,
+            )
+            .await
+            .map(Some)
+        })
+    }
+
+    fn list_offset<'a>(
+        &'a self,
+        include_drafts: bool,
+        offset: u64,
+        limit: u32,
+        tag: Option<&'a Tag>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, u64)>> {
+        traced("PostgresArticleReadRepository::list_offset", async move {
+            let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+            let tag = tag.map(Tag::as_str);
+
+            let mut page: QueryBuilder<Postgres> = QueryBuilder::new(
+                "SELECT id, title, slug, body, tags, custom_fields, published, published_at, visibility, author_id, created_at, updated_at, view_count FROM articles",
+            );
+            self.apply_conditions(&mut page, include_drafts, None, &SearchMode::None, tag);
+            page.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+            page.push_bind(i64::from(limit));
+            page.push(" OFFSET ");
+            page.push_bind(i64::try_from(offset).unwrap_or(i64::MAX));
 
-            self.fetch_page(include_drafts, limit, cursor_ref, SearchMode::None)
+            let mut count: QueryBuilder<Postgres> =
+                QueryBuilder::new("SELECT COUNT(1) FROM articles");
+            self.apply_conditions(&mut count, include_drafts, None, &SearchMode::None, tag);
+
+            let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+            let rows = page
+                .build_query_as::<ArticleRow>()
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(map_sqlx)?;
+            let total = count
+                .build_query_scalar::<i64>()
+                .fetch_one(&mut *tx)
                 .await
+                .map_err(map_sqlx)?;
+            tx.commit().await.map_err(map_sqlx)?;
+
+            let articles = rows
+                .into_iter()
+                .map(Article::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok((articles, u64::try_from(total).unwrap_or_default()))
         })
     }
+
+    fn list_page_before(
+        &self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: ArticleListCursor,
+    ) -> BoxFuture<'_, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        traced(
+            "PostgresArticleReadRepository::list_page_before",
+            async move {
+                let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+                let fetch_limit = i64::from(limit) + 1;
+
+                let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
+                    "SELECT id, title, slug, body, tags, custom_fields, published, published_at, visibility, author_id, created_at, updated_at, view_count FROM articles WHERE (created_at, id) > (",
+                );
+                builder.push_bind(cursor.created_at);
+                builder.push(", ");
+                builder.push_bind(i64::from(cursor.article_id));
+                builder.push(")");
+                if !include_drafts {
+                    builder.push(" AND published = TRUE AND visibility = 'public'");
+                }
+                builder.push(" ORDER BY created_at ASC, id ASC LIMIT ");
+                builder.push_bind(fetch_limit);
+
+                let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+                let rows = builder
+                    .build_query_as::<ArticleRow>()
+                    .fetch_all(&mut *tx)
+                    .await
+                    .map_err(map_sqlx)?;
+                tx.commit().await.map_err(map_sqlx)?;
+
+                let mut articles = rows
+                    .into_iter()
+                    .map(Article::try_from)
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let mut prev_cursor = None;
+                if articles.len() > limit as usize {
+                    articles.pop();
+                    if let Some(newest) = articles.last() {
+                        prev_cursor =
+                            Some(ArticleListCursor::from_parts(newest.created_at, newest.id));
+                    }
+                }
+                articles.reverse();
+
+                Ok((articles, prev_cursor))
+            },
+        )
+    }
+
+    fn has_preceding<'a>(
+        &'a self,
+        include_drafts: bool,
+        cursor: &'a ArticleListCursor,
+    ) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("PostgresArticleReadRepository::has_preceding", async move {
+            let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
+                "SELECT EXISTS(SELECT 1 FROM articles WHERE (created_at, id) > (",
+            );
+            builder.push_bind(cursor.created_at);
+            builder.push(", ");
+            builder.push_bind(i64::from(cursor.article_id));
+            builder.push(")");
+            if !include_drafts {
+                builder.push(" AND published = TRUE AND visibility = 'public'");
+            }
+            builder.push(")");
+
+            let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+            let exists = builder
+                .build_query_scalar::<bool>()
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(map_sqlx)?;
+            tx.commit().await.map_err(map_sqlx)?;
+
+            Ok(exists)
+        })
+    }
+
+    fn list_popular<'a>(
+        &'a self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: Option<ArticlePopularityCursor>,
+        tag: Option<&'a Tag>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, Option<ArticlePopularityCursor>)>> {
+        traced("PostgresArticleReadRepository::list_popular", async move {
+            let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+            let fetch_limit = i64::from(limit) + 1;
+
+            let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
+                "SELECT id, title, slug, body, tags, custom_fields, published, published_at, visibility, author_id, created_at, updated_at, view_count FROM articles WHERE TRUE",
+            );
+            if !include_drafts {
+                builder.push(" AND published = TRUE AND visibility = 'public'");
+            }
+            if let Some(tag) = tag {
+                builder.push(" AND tags @> ARRAY[");
+                builder.push_bind(tag.as_str());
+                builder.push("]::TEXT[]");
+            }
+            if let Some(cursor) = cursor {
+                builder.push(" AND (view_count, id) < (");
+                builder.push_bind(i64::try_from(cursor.view_count).unwrap_or(i64::MAX));
+                builder.push(", ");
+                builder.push_bind(i64::from(cursor.article_id));
+                builder.push(")");
+            }
+            builder.push(" ORDER BY view_count DESC, id DESC LIMIT ");
+            builder.push_bind(fetch_limit);
+
+            let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+            let rows = builder
+                .build_query_as::<ArticleRow>()
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(map_sqlx)?;
+            tx.commit().await.map_err(map_sqlx)?;
+
+            let mut articles = rows
+                .into_iter()
+                .map(Article::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let mut next_cursor = None;
+            if articles.len() > limit as usize {
+                articles.pop();
+                if let Some(last) = articles.last() {
+                    next_cursor = Some(ArticlePopularityCursor::new(last.view_count, last.id));
+                }
+            }
+
+            Ok((articles, next_cursor))
+        })
+    }
+
+    fn estimate_total(&self, include_drafts: bool) -> BoxFuture<'_, DomainResult<Option<u64>>> {
+        traced(
+            "PostgresArticleReadRepository::estimate_total",
+            async move {
+                let sql = if include_drafts {
+                    "SELECT COUNT(1) FROM articles"
+                } else {
+                    "SELECT COUNT(1) FROM articles WHERE visibility = 'public' AND published = TRUE"
+                };
+
+                let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+                let count = sqlx::query_scalar::<_, i64>(sql)
+                    .fetch_one(&mut *tx)
+                    .await
+                    .map_err(map_sqlx)?;
+                tx.commit().await.map_err(map_sqlx)?;
+
+                Ok(u64::try_from(count).ok())
+            },
+        )
+    }
 }
@@ -1,14 +1,16 @@
 // src/infrastructure/repositories/articles/revision.rs
 use super::super::map_sqlx;
-use crate::async_support::{BoxFuture, boxed};
+use crate::async_support::BoxFuture;
 use crate::domain::UserId;
 use crate::domain::errors::DomainResult;
 use crate::domain::{
     Article, ArticleBody, ArticleId, ArticleRevision, ArticleRevisionParts,
     ArticleRevisionRepository, ArticleSlug, ArticleTitle,
 };
+use crate::infrastructure::repositories::traced;
+use crate::infrastructure::rls;
 use chrono::{DateTime, Utc};
-use sqlx::{FromRow, PgPool};
+use sqlx::{FromRow, PgConnection, PgPool};
 
 #[derive(Clone)]
 #[must_use]
@@ -56,71 +58,163 @@ impl TryFrom<ArticleRevisionRow> for ArticleRevision {
     }
 }
 
+/// Record `article` on `conn` as its next revision.
+pub(in crate::infrastructure::repositories) async fn append_revision(
+    conn: &mut PgConnection,
+    article: &Article,
+    edited_by: Option<UserId>,
+) -> DomainResult<()> {
+    sqlx::query(
+        r"
+        WITH next_version AS (
+            SELECT COALESCE(MAX(version) + 1, 1) AS version
+            FROM article_revisions
+            WHERE article_id = $1
+        )
+        INSERT INTO article_revisions (
+            article_id, version, title, slug, body, published, published_at,
+            author_id, edited_by
+        )
+        SELECT
+            $1,
+            next_version.version,
+            $2, $3, $4, $5, $6,
+            $7, $8
+        FROM next_version
+        ",
+    )
+    .bind(i64::from(article.id))
+    .bind(article.title.as_str())
+    .bind(article.slug.as_str())
+    .bind(article.body.as_str())
+    .bind(article.published)
+    .bind(article.published_at)
+    .bind(i64::from(article.author_id))
+    .bind(edited_by.map(i64::from))
+    .execute(conn)
+    .await
+    .map_err(map_sqlx)?;
+
+    Ok(())
+}
+
 impl ArticleRevisionRepository for PostgresArticleRevisionRepository {
     fn append<'a>(
         &'a self,
         article: &'a Article,
         edited_by: Option<UserId>,
     ) -> BoxFuture<'a, DomainResult<()>> {
-        let edited_by = edited_by.map(i64::from);
-        boxed(async move {
-            sqlx::query(
+        traced("PostgresArticleRevisionRepository::append", async move {
+            let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+            append_revision(&mut tx, article, edited_by).await?;
+            tx.commit().await.map_err(map_sqlx)
+        })
+    }
+
+    fn append_many<'a>(
+        &'a self,
+        articles: &'a [Article],
+        edited_by: Option<UserId>,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        traced(
+            "PostgresArticleRevisionRepository::append_many",
+            async move {
+                if articles.is_empty() {
+                    return Ok(());
+                }
+                let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+                sqlx::query(
                 r"
-                WITH next_version AS (
-                    SELECT COALESCE(MAX(version) + 1, 1) AS version
-                    FROM article_revisions
-                    WHERE article_id = $1
-                )
                 INSERT INTO article_revisions (
                     article_id, version, title, slug, body, published, published_at,
                     author_id, edited_by
                 )
                 SELECT
-                    $1,
-                    next_version.version,
-                    $2, $3, $4, $5, $6,
-                    $7, $8
-                FROM next_version
+                    v.article_id,
+                    COALESCE(
+                        (SELECT MAX(r.version) FROM article_revisions r WHERE r.article_id = v.article_id),
+                        0
+                    ) + 1,
+                    v.title, v.slug, v.body, v.published, v.published_at,
+                    v.author_id, $8
+                FROM UNNEST(
+                    $1::BIGINT[], $2::TEXT[], $3::TEXT[], $4::TEXT[], $5::BOOLEAN[],
+                    $6::TIMESTAMPTZ[], $7::BIGINT[]
+                ) AS v(article_id, title, slug, body, published, published_at, author_id)
                 ",
             )
-            .bind(i64::from(article.id))
-            .bind(article.title.as_str())
-            .bind(article.slug.as_str())
-            .bind(article.body.as_str())
-            .bind(article.published)
-            .bind(article.published_at)
-            .bind(i64::from(article.author_id))
-            .bind(edited_by)
-            .execute(&self.pool)
+            .bind(articles.iter().map(|a| i64::from(a.id)).collect::<Vec<_>>())
+            .bind(articles.iter().map(|a| a.title.as_str()).collect::<Vec<_>>())
+            .bind(articles.iter().map(|a| a.slug.as_str()).collect::<Vec<_>>())
+            .bind(articles.iter().map(|a| a.body.as_str()).collect::<Vec<_>>())
+            .bind(articles.iter().map(|a| a.published).collect::<Vec<_>>())
+            .bind(articles.iter().map(|a| a.published_at).collect::<Vec<_>>())
+            .bind(articles.iter().map(|a| i64::from(a.author_id)).collect::<Vec<_>>())
+            .bind(edited_by.map(i64::from))
+            .execute(&mut *tx)
             .await
             .map_err(map_sqlx)?;
-
-            Ok(())
-        })
+                tx.commit().await.map_err(map_sqlx)
+            },
+        )
     }
 
     fn list_by_article(
         &self,
         article_id: ArticleId,
     ) -> BoxFuture<'_, DomainResult<Vec<ArticleRevision>>> {
-        boxed(async move {
-            let rows = sqlx::query_as::<_, ArticleRevisionRow>(
-                r"
+        traced(
+            "PostgresArticleRevisionRepository::list_by_article",
+            async move {
+                let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+                let rows = sqlx::query_as::<_, ArticleRevisionRow>(
+                    r"
                 SELECT article_id, version, title, slug, body, published, published_at,
                        author_id, edited_by, recorded_at
                 FROM article_revisions
                 WHERE article_id = $1
                 ORDER BY version DESC
                 ",
-            )
-            .bind(i64::from(article_id))
-            .fetch_all(&self.pool)
-            .await
-            .map_err(map_sqlx)?;
+                )
+                .bind(i64::from(article_id))
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(map_sqlx)?;
+                tx.commit().await.map_err(map_sqlx)?;
 
-            rows.into_iter()
-                .map(ArticleRevision::try_from)
-                .collect::<Result<Vec<_>, _>>()
-        })
+                rows.into_iter()
+                    .map(ArticleRevision::try_from)
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )
+    }
+
+    fn find_revision(
+        &self,
+        article_id: ArticleId,
+        version: i32,
+    ) -> BoxFuture<'_, DomainResult<Option<ArticleRevision>>> {
+        traced(
+            "PostgresArticleRevisionRepository::find_revision",
+            async move {
+                let mut tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+                let row = sqlx::query_as::<_, ArticleRevisionRow>(
+                    r"
+                SELECT article_id, version, title, slug, body, published, published_at,
+                       author_id, edited_by, recorded_at
+                FROM article_revisions
+                WHERE article_id = $1 AND version = $2
+                ",
+                )
+                .bind(i64::from(article_id))
+                .bind(version)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(map_sqlx)?;
+                tx.commit().await.map_err(map_sqlx)?;
+
+                row.map(ArticleRevision::try_from).transpose()
+            },
+        )
     }
 }
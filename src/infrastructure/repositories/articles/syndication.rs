@@ -0,0 +1,62 @@
+// src/infrastructure/repositories/articles/syndication.rs
+use crate::application::ports::syndication::SyndicationOptOutStore;
+use crate::application::{AppError, AppResult};
+use crate::async_support::BoxFuture;
+use crate::domain::ArticleId;
+use crate::infrastructure::repositories::traced;
+use sqlx::PgPool;
+
+/// Syndication opt-outs in the `article_syndication_opt_outs` table.
+#[derive(Clone)]
+#[must_use]
+pub struct PostgresSyndicationOptOutStore {
+    pool: PgPool,
+}
+
+impl PostgresSyndicationOptOutStore {
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn store_error(err: &sqlx::Error) -> AppError {
+    AppError::infrastructure(format!("syndication opt-outs: {err}"))
+}
+
+impl SyndicationOptOutStore for PostgresSyndicationOptOutStore {
+    fn is_opted_out(&self, article_id: ArticleId) -> BoxFuture<'_, AppResult<bool>> {
+        traced("PostgresSyndicationOptOutStore::is_opted_out", async move {
+            sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS (SELECT 1 FROM article_syndication_opt_outs WHERE article_id = $1)",
+            )
+            .bind(i64::from(article_id))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| store_error(&err))
+        })
+    }
+
+    fn set_opted_out(
+        &self,
+        article_id: ArticleId,
+        opted_out: bool,
+    ) -> BoxFuture<'_, AppResult<()>> {
+        traced(
+            "PostgresSyndicationOptOutStore::set_opted_out",
+            async move {
+                let sql = if opted_out {
+                    "INSERT INTO article_syndication_opt_outs (article_id) VALUES ($1)
+                 ON CONFLICT (article_id) DO NOTHING"
+                } else {
+                    "DELETE FROM article_syndication_opt_outs WHERE article_id = $1"
+                };
+                sqlx::query(sql)
+                    .bind(i64::from(article_id))
+                    .execute(&self.pool)
+                    .await
+                    .map_err(|err| store_error(&err))?;
+                Ok(())
+            },
+        )
+    }
+}
@@ -0,0 +1,322 @@
+// src/infrastructure/repositories/audit/buffered.rs
+//! Audit writes taken off the request path.
+//!
+//! `insert` only queues the entry; a background task writes queued entries
+//! in batches. Reads go straight to the wrapped repository, so an entry may
+//! be missing from them until its batch is flushed, and entries still queued
+//! when the process dies are lost.
+
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::audit::cursor::AuditLogCursor;
+use crate::domain::audit::entity::{AuditLog, NewAuditLog};
+use crate::domain::audit::filter::AuditLogFilter;
+use crate::domain::audit::repository::AuditLogRepository;
+use crate::domain::errors::{DomainError, DomainResult};
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+use std::time::Duration;
+use tokio::sync::mpsc::{self, Receiver, Sender, error::TrySendError};
+
+/// What `insert` does when the queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Wait for room, slowing the caller down instead of losing entries.
+    #[default]
+    Block,
+    /// Discard the entry and count it in [`BufferedAuditLogRepository::dropped`].
+    Drop,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BufferOptions {
+    /// Entries that may wait to be written.
+    pub capacity: usize,
+    /// Most entries written in one batch.
+    pub batch_size: usize,
+    /// Longest a queued entry waits for its batch to fill.
+    pub flush_interval: Duration,
+    pub overflow: OverflowPolicy,
+}
+
+impl Default for BufferOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            batch_size: 100,
+            flush_interval: Duration::from_millis(200),
+            overflow: OverflowPolicy::Block,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BufferedAuditLogRepository {
+    inner: Arc<dyn AuditLogRepository>,
+    sender: Sender<NewAuditLog>,
+    overflow: OverflowPolicy,
+    dropped: Arc<AtomicU64>,
+}
+
+impl BufferedAuditLogRepository {
+    /// Wrap `inner` and start the task that flushes to it. Must be called
+    /// inside a Tokio runtime; the task ends once every clone is dropped and
+    /// the queue is drained.
+    #[must_use]
+    pub fn spawn(inner: Arc<dyn AuditLogRepository>, options: BufferOptions) -> Self {
+        let (sender, receiver) = mpsc::channel(options.capacity.max(1));
+        tokio::spawn(flush(
+            Arc::clone(&inner),
+            receiver,
+            options.batch_size.max(1),
+            options.flush_interval,
+        ));
+        Self {
+            inner,
+            sender,
+            overflow: options.overflow,
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Entries discarded because the queue was full.
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+async fn flush(
+    inner: Arc<dyn AuditLogRepository>,
+    mut receiver: Receiver<NewAuditLog>,
+    batch_size: usize,
+    flush_interval: Duration,
+) {
+    let mut batch = Vec::with_capacity(batch_size);
+    while receiver.recv_many(&mut batch, batch_size).await > 0 {
+        let deadline = tokio::time::sleep(flush_interval);
+        tokio::pin!(deadline);
+        while batch.len() < batch_size {
+            let room = batch_size - batch.len();
+            tokio::select! {
+                count = receiver.recv_many(&mut batch, room) => {
+                    if count == 0 {
+                        break;
+                    }
+                }
+                () = &mut deadline => break,
+            }
+        }
+
+        let count = batch.len();
+        if let Err(err) = inner.insert_batch(std::mem::take(&mut batch)).await {
+            tracing::error!(error = %err, count, "failed to write audit log batch");
+        }
+    }
+}
+
+impl AuditLogRepository for BufferedAuditLogRepository {
+    fn insert(&self, log: NewAuditLog) -> BoxFuture<'_, DomainResult<()>> {
+        boxed(async move {
+            let closed = || DomainError::Persistence("audit writer has stopped".into());
+            match self.overflow {
+                OverflowPolicy::Block => self.sender.send(log).await.map_err(|_| closed()),
+                OverflowPolicy::Drop => match self.sender.try_send(log) {
+                    Ok(()) => Ok(()),
+                    Err(TrySendError::Full(log)) => {
+                        let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                        tracing::warn!(action = %log.action, dropped, "audit queue full, entry dropped");
+                        Ok(())
+                    }
+                    Err(TrySendError::Closed(_)) => Err(closed()),
+                },
+            }
+        })
+    }
+
+    fn list(
+        &self,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        self.inner.list(limit, cursor)
+    }
+
+    fn find_by_user(
+        &self,
+        user_id: i64,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        self.inner.find_by_user(user_id, limit, cursor)
+    }
+
+    fn find_by_resource<'a>(
+        &'a self,
+        resource_type: &'a str,
+        resource_id: i64,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        self.inner
+            .find_by_resource(resource_type, resource_id, limit, cursor)
+    }
+
+    fn find_by_action<'a>(
+        &'a self,
+        action: &'a str,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        self.inner.find_by_action(action, limit, cursor)
+    }
+
+    fn search<'a>(
+        &'a self,
+        filter: &'a AuditLogFilter,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        self.inner.search(filter, limit, cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Records batch sizes; `gate` holds writes back until released.
+    struct Recording {
+        batches: Mutex<Vec<usize>>,
+        gate: tokio::sync::Semaphore,
+    }
+
+    impl Recording {
+        fn gated() -> Arc<Self> {
+            Arc::new(Self {
+                batches: Mutex::new(Vec::new()),
+                gate: tokio::sync::Semaphore::new(0),
+            })
+        }
+    }
+
+    impl AuditLogRepository for Recording {
+        fn insert(&self, log: NewAuditLog) -> BoxFuture<'_, DomainResult<()>> {
+            self.insert_batch(vec![log])
+        }
+
+        fn insert_batch(&self, logs: Vec<NewAuditLog>) -> BoxFuture<'_, DomainResult<()>> {
+            boxed(async move {
+                self.gate.acquire().await.unwrap().forget();
+                self.batches.lock().unwrap().push(logs.len());
+                Ok(())
+            })
+        }
+
+        fn list(
+            &self,
+            _limit: u32,
+            _cursor: Option<AuditLogCursor>,
+        ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+            boxed(async move { Ok((vec![], None)) })
+        }
+
+        fn find_by_user(
+            &self,
+            _user_id: i64,
+            _limit: u32,
+            _cursor: Option<AuditLogCursor>,
+        ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+            boxed(async move { Ok((vec![], None)) })
+        }
+
+        fn find_by_resource<'a>(
+            &'a self,
+            _resource_type: &'a str,
+            _resource_id: i64,
+            _limit: u32,
+            _cursor: Option<AuditLogCursor>,
+        ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+            boxed(async move { Ok((vec![], None)) })
+        }
+
+        fn find_by_action<'a>(
+            &'a self,
+            _action: &'a str,
+            _limit: u32,
+            _cursor: Option<AuditLogCursor>,
+        ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+            boxed(async move { Ok((vec![], None)) })
+        }
+
+        fn search<'a>(
+            &'a self,
+            _filter: &'a AuditLogFilter,
+            _limit: u32,
+            _cursor: Option<AuditLogCursor>,
+        ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+            boxed(async move { Ok((vec![], None)) })
+        }
+    }
+
+    fn log() -> NewAuditLog {
+        NewAuditLog {
+            user_id: None,
+            impersonator_id: None,
+            action: "test".into(),
+            resource_type: "test".into(),
+            resource_id: None,
+            details: None,
+            ip_address: None,
+            user_agent: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn queued_entries_are_written_in_batches() {
+        let inner = Recording::gated();
+        let buffered = BufferedAuditLogRepository::spawn(
+            Arc::clone(&inner) as Arc<dyn AuditLogRepository>,
+            BufferOptions {
+                batch_size: 3,
+                flush_interval: Duration::from_millis(20),
+                ..BufferOptions::default()
+            },
+        );
+        for _ in 0..5 {
+            buffered.insert(log()).await.unwrap();
+        }
+        inner.gate.add_permits(10);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(inner.batches.lock().unwrap().iter().sum::<usize>(), 5);
+        assert!(inner.batches.lock().unwrap().iter().all(|&size| size <= 3));
+    }
+
+    #[tokio::test]
+    async fn drop_policy_discards_and_counts_overflow() {
+        let inner = Recording::gated();
+        let buffered = BufferedAuditLogRepository::spawn(
+            Arc::clone(&inner) as Arc<dyn AuditLogRepository>,
+            BufferOptions {
+                capacity: 2,
+                batch_size: 1,
+                flush_interval: Duration::from_millis(1),
+                overflow: OverflowPolicy::Drop,
+            },
+        );
+        // The writer holds one entry while blocked on the gate; two more fit
+        // in the queue and the rest overflow.
+        for _ in 0..6 {
+            buffered.insert(log()).await.unwrap();
+            tokio::task::yield_now().await;
+        }
+
+        assert_eq!(buffered.dropped(), 3);
+        inner.gate.add_permits(10);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(inner.batches.lock().unwrap().len(), 3);
+    }
+}
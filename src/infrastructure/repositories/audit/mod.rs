@@ -1,3 +1,7 @@
+mod buffered;
 mod postgres;
+mod redacting;
 
+pub use buffered::{BufferOptions, BufferedAuditLogRepository, OverflowPolicy};
 pub use postgres::PostgresAuditLogRepository;
+pub use redacting::RedactingAuditLogRepository;
@@ -1,17 +1,22 @@
 // src/infrastructure/repositories/audit/postgres.rs
 use super::super::map_sqlx;
-use crate::async_support::{BoxFuture, boxed};
-use crate::domain::audit::cursor::Cursor;
+use crate::async_support::BoxFuture;
+use crate::domain::audit::cursor::AuditLogCursor;
 use crate::domain::audit::entity::{AuditLog, NewAuditLog};
+use crate::domain::audit::filter::AuditLogFilter;
 use crate::domain::errors::DomainResult;
+use crate::infrastructure::repositories::traced;
 use chrono::Utc;
-use sqlx::PgPool;
-const QUERY_LIST_WITH_CURSOR: &str = "SELECT id, user_id, action, resource_type, resource_id, details, ip_address, user_agent, created_at FROM audit_logs WHERE (created_at, id) < ($1, $2) ORDER BY created_at DESC, id DESC LIMIT $3";
-const QUERY_LIST_NO_CURSOR: &str = "SELECT id, user_id, action, resource_type, resource_id, details, ip_address, user_agent, created_at FROM audit_logs ORDER BY created_at DESC, id DESC LIMIT $1";
-const QUERY_FIND_BY_USER_WITH_CURSOR: &str = "SELECT id, user_id, action, resource_type, resource_id, details, ip_address, user_agent, created_at FROM audit_logs WHERE user_id = $1 AND (created_at, id) < ($2, $3) ORDER BY created_at DESC, id DESC LIMIT $4";
-const QUERY_FIND_BY_USER_NO_CURSOR: &str = "SELECT id, user_id, action, resource_type, resource_id, details, ip_address, user_agent, created_at FROM audit_logs WHERE user_id = $1 ORDER BY created_at DESC, id DESC LIMIT $2";
-const QUERY_FIND_BY_RESOURCE_WITH_CURSOR: &str = "SELECT id, user_id, action, resource_type, resource_id, details, ip_address, user_agent, created_at FROM audit_logs WHERE resource_type = $1 AND resource_id = $2 AND (created_at, id) < ($3, $4) ORDER BY created_at DESC, id DESC LIMIT $5";
-const QUERY_FIND_BY_RESOURCE_NO_CURSOR: &str = "SELECT id, user_id, action, resource_type, resource_id, details, ip_address, user_agent, created_at FROM audit_logs WHERE resource_type = $1 AND resource_id = $2 ORDER BY created_at DESC, id DESC LIMIT $3";
+use sqlx::{PgPool, Postgres, QueryBuilder};
+const QUERY_LIST_WITH_CURSOR: &str = "SELECT id, user_id, impersonator_id, action, resource_type, resource_id, details, host(ip_address) AS ip_address, user_agent, created_at FROM audit_logs WHERE (created_at, id) < ($1, $2) ORDER BY created_at DESC, id DESC LIMIT $3";
+const QUERY_LIST_NO_CURSOR: &str = "SELECT id, user_id, impersonator_id, action, resource_type, resource_id, details, host(ip_address) AS ip_address, user_agent, created_at FROM audit_logs ORDER BY created_at DESC, id DESC LIMIT $1";
+const QUERY_FIND_BY_USER_WITH_CURSOR: &str = "SELECT id, user_id, impersonator_id, action, resource_type, resource_id, details, host(ip_address) AS ip_address, user_agent, created_at FROM audit_logs WHERE user_id = $1 AND (created_at, id) < ($2, $3) ORDER BY created_at DESC, id DESC LIMIT $4";
+const QUERY_FIND_BY_USER_NO_CURSOR: &str = "SELECT id, user_id, impersonator_id, action, resource_type, resource_id, details, host(ip_address) AS ip_address, user_agent, created_at FROM audit_logs WHERE user_id = $1 ORDER BY created_at DESC, id DESC LIMIT $2";
+const QUERY_FIND_BY_RESOURCE_WITH_CURSOR: &str = "SELECT id, user_id, impersonator_id, action, resource_type, resource_id, details, host(ip_address) AS ip_address, user_agent, created_at FROM audit_logs WHERE resource_type = $1 AND resource_id = $2 AND (created_at, id) < ($3, $4) ORDER BY created_at DESC, id DESC LIMIT $5";
+const QUERY_FIND_BY_RESOURCE_NO_CURSOR: &str = "SELECT id, user_id, impersonator_id, action, resource_type, resource_id, details, host(ip_address) AS ip_address, user_agent, created_at FROM audit_logs WHERE resource_type = $1 AND resource_id = $2 ORDER BY created_at DESC, id DESC LIMIT $3";
+const QUERY_FIND_BY_ACTION_WITH_CURSOR: &str = "SELECT id, user_id, impersonator_id, action, resource_type, resource_id, details, host(ip_address) AS ip_address, user_agent, created_at FROM audit_logs WHERE action = $1 AND (created_at, id) < ($2, $3) ORDER BY created_at DESC, id DESC LIMIT $4";
+const QUERY_SEARCH: &str = "SELECT id, user_id, impersonator_id, action, resource_type, resource_id, details, host(ip_address) AS ip_address, user_agent, created_at FROM audit_logs WHERE TRUE";
+const QUERY_FIND_BY_ACTION_NO_CURSOR: &str = "SELECT id, user_id, impersonator_id, action, resource_type, resource_id, details, host(ip_address) AS ip_address, user_agent, created_at FROM audit_logs WHERE action = $1 ORDER BY created_at DESC, id DESC LIMIT $2";
 
 #[derive(Clone)]
 #[must_use]
@@ -27,14 +32,15 @@ impl PostgresAuditLogRepository {
 
 impl crate::domain::audit::repository::AuditLogRepository for PostgresAuditLogRepository {
     fn insert(&self, log: NewAuditLog) -> BoxFuture<'_, DomainResult<()>> {
-        boxed(async move {
+        traced("PostgresAuditLogRepository::insert", async move {
             sqlx::query(
                 r"
-                INSERT INTO audit_logs (user_id, action, resource_type, resource_id, details, ip_address, user_agent)
-                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                INSERT INTO audit_logs (user_id, impersonator_id, action, resource_type, resource_id, details, ip_address, user_agent)
+                VALUES ($1, $2, $3, $4, $5, $6, $7::inet, $8)
                 ",
             )
             .bind(log.user_id.map(i64::from))
+            .bind(log.impersonator_id.map(i64::from))
             .bind(log.action)
             .bind(log.resource_type)
             .bind(log.resource_id)
@@ -49,12 +55,41 @@ impl crate::domain::audit::repository::AuditLogRepository for PostgresAuditLogRe
         })
     }
 
+    fn insert_batch(&self, logs: Vec<NewAuditLog>) -> BoxFuture<'_, DomainResult<()>> {
+        traced("PostgresAuditLogRepository::insert_batch", async move {
+            if logs.is_empty() {
+                return Ok(());
+            }
+            let mut builder: QueryBuilder<'_, Postgres> = QueryBuilder::new(
+                "INSERT INTO audit_logs (user_id, impersonator_id, action, resource_type, resource_id, details, ip_address, user_agent) ",
+            );
+            builder.push_values(logs, |mut row, log| {
+                row.push_bind(log.user_id.map(i64::from))
+                    .push_bind(log.impersonator_id.map(i64::from))
+                    .push_bind(log.action)
+                    .push_bind(log.resource_type)
+                    .push_bind(log.resource_id)
+                    .push_bind(log.details)
+                    .push_bind(log.ip_address)
+                    .push_unseparated("::inet")
+                    .push_bind(log.user_agent);
+            });
+            builder
+                .build()
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlx)?;
+
+            Ok(())
+        })
+    }
+
     fn list(
         &self,
         limit: u32,
-        cursor: Option<Cursor>,
-    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<String>)>> {
-        boxed(async move {
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        traced("PostgresAuditLogRepository::list", async move {
             if let Some(c) = cursor {
                 let rows = sqlx::query(QUERY_LIST_WITH_CURSOR)
                     .bind(c.created_at)
@@ -81,9 +116,9 @@ impl crate::domain::audit::repository::AuditLogRepository for PostgresAuditLogRe
         &self,
         user_id: i64,
         limit: u32,
-        cursor: Option<Cursor>,
-    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<String>)>> {
-        boxed(async move {
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        traced("PostgresAuditLogRepository::find_by_user", async move {
             if let Some(c) = cursor {
                 let rows = sqlx::query(QUERY_FIND_BY_USER_WITH_CURSOR)
                     .bind(user_id)
@@ -112,9 +147,9 @@ impl crate::domain::audit::repository::AuditLogRepository for PostgresAuditLogRe
         resource_type: &'a str,
         resource_id: i64,
         limit: u32,
-        cursor: Option<Cursor>,
-    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<String>)>> {
-        boxed(async move {
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        traced("PostgresAuditLogRepository::find_by_resource", async move {
             if let Some(c) = cursor {
                 let rows = sqlx::query(QUERY_FIND_BY_RESOURCE_WITH_CURSOR)
                     .bind(resource_type)
@@ -139,12 +174,89 @@ impl crate::domain::audit::repository::AuditLogRepository for PostgresAuditLogRe
             Ok(map_rows_to_logs(rows, limit))
         })
     }
+
+    fn find_by_action<'a>(
+        &'a self,
+        action: &'a str,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        traced("PostgresAuditLogRepository::find_by_action", async move {
+            if let Some(c) = cursor {
+                let rows = sqlx::query(QUERY_FIND_BY_ACTION_WITH_CURSOR)
+                    .bind(action)
+                    .bind(c.created_at)
+                    .bind(c.id)
+                    .bind(i64::from(limit) + 1)
+                    .fetch_all(&self.pool)
+                    .await
+                    .map_err(map_sqlx)?;
+                return Ok(map_rows_to_logs(rows, limit));
+            }
+
+            let rows = sqlx::query(QUERY_FIND_BY_ACTION_NO_CURSOR)
+                .bind(action)
+                .bind(i64::from(limit) + 1)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(map_sqlx)?;
+
+            Ok(map_rows_to_logs(rows, limit))
+        })
+    }
+
+    fn search<'a>(
+        &'a self,
+        filter: &'a AuditLogFilter,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        traced("PostgresAuditLogRepository::search", async move {
+            let mut builder: QueryBuilder<'_, Postgres> = QueryBuilder::new(QUERY_SEARCH);
+            if let Some(user_id) = filter.involving_user {
+                builder
+                    .push(" AND (user_id = ")
+                    .push_bind(user_id)
+                    .push(" OR (resource_type = 'user' AND resource_id = ")
+                    .push_bind(user_id)
+                    .push("))");
+            }
+            if !filter.actions.is_empty() {
+                builder
+                    .push(" AND action = ANY(")
+                    .push_bind(&filter.actions)
+                    .push(")");
+            }
+            if let Some(since) = filter.since {
+                builder.push(" AND created_at >= ").push_bind(since);
+            }
+            if let Some(c) = cursor {
+                builder
+                    .push(" AND (created_at, id) < (")
+                    .push_bind(c.created_at)
+                    .push(", ")
+                    .push_bind(c.id)
+                    .push(")");
+            }
+            builder
+                .push(" ORDER BY created_at DESC, id DESC LIMIT ")
+                .push_bind(i64::from(limit) + 1);
+
+            let rows = builder
+                .build()
+                .fetch_all(&self.pool)
+                .await
+                .map_err(map_sqlx)?;
+
+            Ok(map_rows_to_logs(rows, limit))
+        })
+    }
 }
 
 fn map_rows_to_logs(
     rows: Vec<sqlx::postgres::PgRow>,
     limit: u32,
-) -> (Vec<AuditLog>, Option<String>) {
+) -> (Vec<AuditLog>, Option<AuditLogCursor>) {
     use sqlx::Row;
     let mut items = rows
         .into_iter()
@@ -153,6 +265,11 @@ fn map_rows_to_logs(
             let user_id: Option<i64> = row.try_get::<Option<i64>, _>("user_id").ok().flatten();
             let user_id =
                 user_id.and_then(|id| crate::domain::user::value_objects::UserId::new(id).ok());
+            let impersonator_id = row
+                .try_get::<Option<i64>, _>("impersonator_id")
+                .ok()
+                .flatten()
+                .and_then(|id| crate::domain::user::value_objects::UserId::new(id).ok());
             let action: String = row.try_get("action").expect("audit log action");
             let resource_type: String = row
                 .try_get("resource_type")
@@ -167,6 +284,7 @@ fn map_rows_to_logs(
             AuditLog {
                 id,
                 user_id,
+                impersonator_id,
                 action,
                 resource_type,
                 resource_id,
@@ -178,59 +296,7 @@ fn map_rows_to_logs(
         })
         .collect::<Vec<_>>();
 
-    let next_cursor = trim_to_page_and_build_cursor(&mut items, limit);
+    let next_cursor = AuditLogCursor::trim_page(&mut items, limit);
 
     (items, next_cursor)
 }
-
-fn trim_to_page_and_build_cursor(items: &mut Vec<AuditLog>, limit: u32) -> Option<String> {
-    if items.len() <= limit as usize {
-        return None;
-    }
-
-    items.truncate(limit as usize);
-    items
-        .last()
-        .map(|last| Cursor::new(last.created_at, last.id).encode())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::trim_to_page_and_build_cursor;
-    use crate::domain::audit::cursor::Cursor;
-    use crate::domain::audit::entity::AuditLog;
-    use chrono::{Duration, Utc};
-
-    fn audit_log(id: i64, created_at: chrono::DateTime<Utc>) -> AuditLog {
-        AuditLog {
-            id,
-            user_id: None,
-            action: "test".into(),
-            resource_type: "article".into(),
-            resource_id: Some(id),
-            details: None,
-            ip_address: None,
-            user_agent: None,
-            created_at,
-        }
-    }
-
-    #[test]
-    fn next_cursor_uses_last_item_in_current_page() {
-        let now = Utc::now();
-        let third = audit_log(3, now - Duration::minutes(3));
-        let mut items = vec![
-            audit_log(1, now - Duration::minutes(1)),
-            audit_log(2, now - Duration::minutes(2)),
-            third.clone(),
-        ];
-
-        let next_cursor = trim_to_page_and_build_cursor(&mut items, 2);
-
-        assert_eq!(items.len(), 2);
-        let cursor = Cursor::decode(&next_cursor.expect("next cursor")).unwrap();
-        assert_eq!(cursor.id, 2);
-        assert_eq!(cursor.created_at, items[1].created_at);
-        assert_ne!(cursor.id, third.id);
-    }
-}
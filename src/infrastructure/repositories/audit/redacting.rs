@@ -0,0 +1,90 @@
+// src/infrastructure/repositories/audit/redacting.rs
+//! Audit writes with secrets masked out of their details.
+
+use crate::async_support::BoxFuture;
+use crate::domain::audit::cursor::AuditLogCursor;
+use crate::domain::audit::entity::{AuditLog, NewAuditLog};
+use crate::domain::audit::filter::AuditLogFilter;
+use crate::domain::audit::repository::AuditLogRepository;
+use crate::domain::errors::DomainResult;
+use crate::infrastructure::redaction::Redactor;
+use std::sync::Arc;
+
+/// Masks sensitive fields in `details` (see [`Redactor`]) before handing
+/// entries to the wrapped repository. Reads are passed through.
+#[derive(Clone)]
+pub struct RedactingAuditLogRepository {
+    inner: Arc<dyn AuditLogRepository>,
+    redactor: Redactor,
+}
+
+impl RedactingAuditLogRepository {
+    #[must_use]
+    pub fn new(inner: Arc<dyn AuditLogRepository>, redactor: Redactor) -> Self {
+        Self { inner, redactor }
+    }
+
+    fn redact(&self, mut log: NewAuditLog) -> NewAuditLog {
+        if let Some(details) = log.details.as_mut() {
+            self.redactor.redact_value(details);
+        }
+        log
+    }
+}
+
+impl AuditLogRepository for RedactingAuditLogRepository {
+    fn insert(&self, log: NewAuditLog) -> BoxFuture<'_, DomainResult<()>> {
+        self.inner.insert(self.redact(log))
+    }
+
+    fn insert_batch(&self, logs: Vec<NewAuditLog>) -> BoxFuture<'_, DomainResult<()>> {
+        self.inner
+            .insert_batch(logs.into_iter().map(|log| self.redact(log)).collect())
+    }
+
+    fn list(
+        &self,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        self.inner.list(limit, cursor)
+    }
+
+    fn find_by_user(
+        &self,
+        user_id: i64,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        self.inner.find_by_user(user_id, limit, cursor)
+    }
+
+    fn find_by_resource<'a>(
+        &'a self,
+        resource_type: &'a str,
+        resource_id: i64,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        self.inner
+            .find_by_resource(resource_type, resource_id, limit, cursor)
+    }
+
+    fn find_by_action<'a>(
+        &'a self,
+        action: &'a str,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        self.inner.find_by_action(action, limit, cursor)
+    }
+
+    fn search<'a>(
+        &'a self,
+        filter: &'a AuditLogFilter,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        self.inner.search(filter, limit, cursor)
+    }
+}
@@ -5,15 +5,35 @@ const CNT_ARTICLE_SLUG: &str = "articles_slug_key";
 const CNT_ARTICLE_AUTHOR: &str = "articles_author_id_fkey";
 const CNT_ARTICLE_PUBLISHED_CHECK: &str = "articles_published_requires_timestamp_chk";
 const CNT_USER_USERNAME: &str = "users_username_key";
+const CNT_FEDERATED_SUBJECT: &str = "federated_identities_pkey";
+const CNT_FEDERATED_USER_PROVIDER: &str = "federated_identities_user_provider_key";
+const CNT_USER_ROLE_USER: &str = "user_custom_roles_user_id_fkey";
+const CNT_USER_ROLE_NAME: &str = "user_custom_roles_role_name_fkey";
+const CNT_CONTRIBUTOR_USER: &str = "article_contributors_user_id_fkey";
+const CNT_USER_CAPABILITY_USER: &str = "user_capabilities_user_id_fkey";
 
 pub fn map_sqlx(err: sqlx::Error) -> DomainError {
     match err {
         sqlx::Error::Database(db_err) => {
             if let Some(constraint) = db_err.constraint() {
                 return match constraint {
-                    CNT_ARTICLE_SLUG => DomainError::Conflict("slug already exists".into()),
-                    CNT_USER_USERNAME => DomainError::Conflict("username already exists".into()),
+                    CNT_ARTICLE_SLUG => DomainError::SlugTaken {
+                        slug: conflicting_value(db_err.as_ref()),
+                    },
+                    CNT_USER_USERNAME => DomainError::UsernameTaken {
+                        username: conflicting_value(db_err.as_ref()),
+                    },
+                    CNT_FEDERATED_SUBJECT => {
+                        DomainError::Conflict("external account is already linked".into())
+                    }
+                    CNT_FEDERATED_USER_PROVIDER => DomainError::Conflict(
+                        "user already has a linked account at this provider".into(),
+                    ),
                     CNT_ARTICLE_AUTHOR => DomainError::NotFound("author not found".into()),
+                    CNT_USER_ROLE_USER | CNT_CONTRIBUTOR_USER | CNT_USER_CAPABILITY_USER => {
+                        DomainError::NotFound("user not found".into())
+                    }
+                    CNT_USER_ROLE_NAME => DomainError::NotFound("role not found".into()),
                     CNT_ARTICLE_PUBLISHED_CHECK => {
                         DomainError::Validation("published articles require published_at".into())
                     }
@@ -43,3 +63,32 @@ pub fn map_sqlx(err: sqlx::Error) -> DomainError {
         other => DomainError::Persistence(other.to_string()),
     }
 }
+
+/// The value from a unique violation's detail, which Postgres reports as
+/// `Key (column)=(value) already exists.`
+fn conflicting_value(db_err: &dyn sqlx::error::DatabaseError) -> Option<String> {
+    let detail = db_err
+        .try_downcast_ref::<sqlx::postgres::PgDatabaseError>()?
+        .detail()?;
+    parse_key_value(detail)
+}
+
+fn parse_key_value(detail: &str) -> Option<String> {
+    let (_, rest) = detail.split_once(")=(")?;
+    let (value, _) = rest.rsplit_once(") already exists")?;
+    Some(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_key_value;
+
+    #[test]
+    fn key_value_is_read_from_unique_violation_detail() {
+        assert_eq!(
+            parse_key_value("Key (slug)=(hello-(world)) already exists.").as_deref(),
+            Some("hello-(world)")
+        );
+        assert_eq!(parse_key_value("Failing row contains (1)."), None);
+    }
+}
@@ -0,0 +1,91 @@
+// src/infrastructure/repositories/media/postgres.rs
+use super::super::map_sqlx;
+use crate::async_support::BoxFuture;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{MediaAsset, MediaRepository, NewMediaAsset, UserId};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+
+const ASSET_COLUMNS: &str =
+    "id, uploaded_by, file_name, content_type, size_bytes, storage_key, created_at";
+
+#[derive(Clone)]
+#[must_use]
+pub struct PostgresMediaRepository {
+    pool: PgPool,
+}
+
+impl PostgresMediaRepository {
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct AssetRow {
+    id: i64,
+    uploaded_by: i64,
+    file_name: String,
+    content_type: String,
+    size_bytes: i64,
+    storage_key: String,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<AssetRow> for MediaAsset {
+    type Error = DomainError;
+
+    fn try_from(row: AssetRow) -> DomainResult<Self> {
+        Ok(Self {
+            id: row.id,
+            uploaded_by: UserId::new(row.uploaded_by)?,
+            file_name: row.file_name,
+            content_type: row.content_type,
+            size_bytes: u64::try_from(row.size_bytes).map_err(|_| {
+                DomainError::Persistence(format!("media asset {} has a negative size", row.id))
+            })?,
+            storage_key: row.storage_key,
+            created_at: row.created_at,
+        })
+    }
+}
+
+impl MediaRepository for PostgresMediaRepository {
+    fn insert(&self, asset: NewMediaAsset) -> BoxFuture<'_, DomainResult<MediaAsset>> {
+        traced("PostgresMediaRepository::insert", async move {
+            let size = i64::try_from(asset.size_bytes)
+                .map_err(|_| DomainError::Validation("the uploaded file is too large".into()))?;
+            let row = sqlx::query_as::<_, AssetRow>(&format!(
+                "INSERT INTO media_assets
+                     (uploaded_by, file_name, content_type, size_bytes, storage_key, created_at)
+                 VALUES ($1, $2, $3, $4, $5, $6)
+                 RETURNING {ASSET_COLUMNS}"
+            ))
+            .bind(i64::from(asset.uploaded_by))
+            .bind(&asset.file_name)
+            .bind(&asset.content_type)
+            .bind(size)
+            .bind(&asset.storage_key)
+            .bind(asset.created_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+            row.try_into()
+        })
+    }
+
+    fn find_by_id(&self, id: i64) -> BoxFuture<'_, DomainResult<Option<MediaAsset>>> {
+        traced("PostgresMediaRepository::find_by_id", async move {
+            sqlx::query_as::<_, AssetRow>(&format!(
+                "SELECT {ASSET_COLUMNS} FROM media_assets WHERE id = $1"
+            ))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .map(TryInto::try_into)
+            .transpose()
+        })
+    }
+}
@@ -0,0 +1,80 @@
+// src/infrastructure/repositories/memory/access_rules.rs
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::errors::DomainResult;
+use crate::domain::{AccessRule, AccessRuleRepository, NewAccessRule};
+use chrono::{DateTime, Utc};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+#[derive(Default)]
+struct Rules {
+    last_id: i64,
+    rows: Vec<AccessRule>,
+}
+
+/// Access rules kept in process memory, for ephemeral instances.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryAccessRuleRepository(Mutex<Rules>);
+
+impl InMemoryAccessRuleRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&self) {
+        self.lock().rows.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Rules> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl AccessRuleRepository for InMemoryAccessRuleRepository {
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<AccessRule>>> {
+        let mut rules = self.lock().rows.clone();
+        rules.sort_by_key(|rule| (rule.priority, rule.id));
+        boxed(async move { Ok(rules) })
+    }
+
+    fn insert(&self, rule: NewAccessRule) -> BoxFuture<'_, DomainResult<AccessRule>> {
+        let stored = {
+            let mut rules = self.lock();
+            rules.last_id += 1;
+            let stored = AccessRule {
+                id: rules.last_id,
+                group: rule.group,
+                action: rule.action,
+                target: rule.target,
+                priority: rule.priority,
+                note: rule.note,
+                created_by: rule.created_by,
+                created_at: rule.created_at,
+                expires_at: rule.expires_at,
+            };
+            rules.rows.push(stored.clone());
+            stored
+        };
+        boxed(async move { Ok(stored) })
+    }
+
+    fn delete(&self, id: i64) -> BoxFuture<'_, DomainResult<bool>> {
+        let removed = {
+            let mut rules = self.lock();
+            let before = rules.rows.len();
+            rules.rows.retain(|rule| rule.id != id);
+            before != rules.rows.len()
+        };
+        boxed(async move { Ok(removed) })
+    }
+
+    fn delete_expired(&self, now: DateTime<Utc>) -> BoxFuture<'_, DomainResult<u64>> {
+        let removed = {
+            let mut rules = self.lock();
+            let before = rules.rows.len();
+            rules.rows.retain(|rule| !rule.is_expired(now));
+            (before - rules.rows.len()) as u64
+        };
+        boxed(async move { Ok(removed) })
+    }
+}
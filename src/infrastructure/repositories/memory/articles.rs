@@ -0,0 +1,555 @@
+// src/infrastructure/repositories/memory/articles.rs
+use super::super::search::WebSearch;
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::PAGE_LIMIT_CEILING;
+use crate::domain::article::custom_fields::FieldDefinition;
+use crate::domain::article::repository::ArticleQuery;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{
+    Article, ArticleId, ArticleListCursor, ArticlePopularityCursor, ArticleReadRepository,
+    ArticleRevision, ArticleRevisionRepository, ArticleSearchHit, ArticleSlug, ArticleUpdate,
+    ArticleWriteRepository, CustomFieldRepository, FacetCount, NewArticle, PrivateScope,
+    SearchFacets, Tag, UserId,
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+#[derive(Default)]
+struct Articles {
+    last_id: i64,
+    rows: Vec<Article>,
+    revisions: Vec<ArticleRevision>,
+}
+
+impl Articles {
+    fn slug_taken(&self, slug: &ArticleSlug, except: Option<ArticleId>) -> bool {
+        self.rows
+            .iter()
+            .any(|article| article.slug == *slug && Some(article.id) != except)
+    }
+
+    fn insert(&mut self, article: NewArticle) -> DomainResult<Article> {
+        if self.slug_taken(&article.slug, None) {
+            return Err(DomainError::SlugTaken {
+                slug: Some(article.slug.into()),
+            });
+        }
+        self.last_id += 1;
+        let stored = Article {
+            id: ArticleId::new(self.last_id)?,
+            title: article.title,
+            slug: article.slug,
+            body: article.body,
+            tags: article.tags,
+            custom_fields: article.custom_fields,
+            published: article.published,
+            published_at: article.published_at,
+            visibility: article.visibility,
+            author_id: article.author_id,
+            created_at: article.created_at,
+            updated_at: article.updated_at,
+            view_count: 0,
+        };
+        self.rows.push(stored.clone());
+        Ok(stored)
+    }
+
+    fn update(&mut self, update: ArticleUpdate) -> DomainResult<Article> {
+        if let Some(slug) = &update.slug
+            && self.slug_taken(slug, Some(update.id))
+        {
+            return Err(DomainError::SlugTaken {
+                slug: Some(slug.as_str().to_string()),
+            });
+        }
+        let article = self
+            .rows
+            .iter_mut()
+            .find(|article| article.id == update.id)
+            .filter(|article| article.updated_at == update.original_updated_at)
+            .ok_or_else(|| DomainError::Conflict("article update conflict, please retry".into()))?;
+        article.updated_at = update.updated_at;
+        if let Some(title) = update.title {
+            article.title = title;
+        }
+        if let Some(slug) = update.slug {
+            article.slug = slug;
+        }
+        if let Some(body) = update.body {
+            article.body = body;
+        }
+        if let Some(tags) = update.tags {
+            article.tags = tags;
+        }
+        if let Some(custom_fields) = update.custom_fields {
+            article.custom_fields = custom_fields;
+        }
+        if let Some(state) = update.publish_state {
+            article.published = state.published;
+            article.published_at = state.published_at;
+        }
+        if let Some(visibility) = update.visibility {
+            article.visibility = visibility;
+        }
+        Ok(article.clone())
+    }
+
+    /// Remove the article and, as the foreign key cascade does, its history.
+    fn delete(&mut self, id: ArticleId) -> DomainResult<()> {
+        let before = self.rows.len();
+        self.rows.retain(|article| article.id != id);
+        if self.rows.len() == before {
+            return Err(DomainError::NotFound("article not found".into()));
+        }
+        self.revisions.retain(|rev| rev.article_id != id);
+        Ok(())
+    }
+
+    /// Articles visible in a listing, newest first; public listings only
+    /// show public ones.
+    fn listing(
+        &self,
+        include_drafts: bool,
+        search: Option<&str>,
+        tag: Option<&str>,
+    ) -> Vec<&Article> {
+        let search = search.and_then(WebSearch::parse);
+        let mut articles: Vec<_> = self
+            .rows
+            .iter()
+            .filter(|article| {
+                include_drafts || (article.published && article.visibility.is_public())
+            })
+            .filter(|article| {
+                search
+                    .as_ref()
+                    .is_none_or(|s| s.matches(article.title.as_str(), article.body.as_str()))
+            })
+            .filter(|article| {
+                tag.is_none_or(|tag| article.tags.iter().any(|own| own.as_str() == tag))
+            })
+            .collect();
+        articles.sort_by_key(|article| std::cmp::Reverse(cursor_of(article)));
+        articles
+    }
+
+    /// `limit` listed articles from `offset` on, and how many are listed.
+    fn numbered(
+        &self,
+        include_drafts: bool,
+        offset: u64,
+        limit: u32,
+        tag: Option<&str>,
+    ) -> (Vec<Article>, u64) {
+        let listing = self.listing(include_drafts, None, tag);
+        let total = listing.len() as u64;
+        let page = listing
+            .into_iter()
+            .skip(usize::try_from(offset).unwrap_or(usize::MAX))
+            .take(limit as usize)
+            .cloned()
+            .collect();
+        (page, total)
+    }
+
+    fn facets(
+        &self,
+        include_drafts: bool,
+        private: PrivateScope,
+        search: Option<&str>,
+        tag: Option<&str>,
+    ) -> SearchFacets {
+        let mut published = 0;
+        let mut authors: HashMap<UserId, u64> = HashMap::new();
+        let mut tags: HashMap<&Tag, u64> = HashMap::new();
+        let mut matches = self.listing(include_drafts, search, tag);
+        matches.retain(|article| private.includes(article));
+        for article in &matches {
+            published += u64::from(article.published);
+            *authors.entry(article.author_id).or_default() += 1;
+            for tag in &article.tags {
+                *tags.entry(tag).or_default() += 1;
+            }
+        }
+        SearchFacets::new(
+            published,
+            matches.len() as u64 - published,
+            authors
+                .into_iter()
+                .map(|(value, count)| FacetCount { value, count })
+                .collect(),
+            tags.into_iter()
+                .map(|(value, count)| FacetCount {
+                    value: value.clone(),
+                    count,
+                })
+                .collect(),
+        )
+    }
+}
+
+fn cursor_of(article: &Article) -> (DateTime<Utc>, i64) {
+    (article.created_at, i64::from(article.id))
+}
+
+fn position(cursor: &ArticleListCursor) -> (DateTime<Utc>, i64) {
+    (cursor.created_at, i64::from(cursor.article_id))
+}
+
+/// Trim a `limit + 1` listing to one page and the cursor for the next.
+fn page(mut articles: Vec<Article>, limit: u32) -> (Vec<Article>, Option<ArticleListCursor>) {
+    if articles.len() <= limit as usize {
+        return (articles, None);
+    }
+    articles.truncate(limit as usize);
+    let next = articles
+        .last()
+        .map(|article| ArticleListCursor::from_parts(article.created_at, article.id));
+    (articles, next)
+}
+
+/// Articles and their revisions kept in process memory, for ephemeral
+/// instances.
+///
+/// One value serves as the write, read and revision repository, so deleting
+/// an article drops its history. Search understands the same quoted phrases
+/// and `-` negation as the Postgres repository, matched as case-insensitive
+/// substrings and listed newest first.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryArticleRepository(Mutex<Articles>);
+
+impl InMemoryArticleRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget every article and revision. Ids keep counting up.
+    pub fn clear(&self) {
+        let mut articles = self.lock();
+        articles.rows.clear();
+        articles.revisions.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Articles> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn fetch_page(
+        &self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: Option<&ArticleListCursor>,
+        search: Option<&str>,
+        tag: Option<&str>,
+    ) -> (Vec<Article>, Option<ArticleListCursor>) {
+        let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+        let articles: Vec<Article> = self
+            .lock()
+            .listing(include_drafts, search, tag)
+            .into_iter()
+            .filter(|article| cursor.is_none_or(|c| cursor_of(article) < position(c)))
+            .take(limit as usize + 1)
+            .cloned()
+            .collect();
+        page(articles, limit)
+    }
+}
+
+impl ArticleWriteRepository for InMemoryArticleRepository {
+    fn insert(&self, article: NewArticle) -> BoxFuture<'_, DomainResult<Article>> {
+        let result = self.lock().insert(article);
+        boxed(async move { result })
+    }
+
+    fn update(&self, update: ArticleUpdate) -> BoxFuture<'_, DomainResult<Article>> {
+        let result = self.lock().update(update);
+        boxed(async move { result })
+    }
+
+    fn delete(&self, id: ArticleId) -> BoxFuture<'_, DomainResult<()>> {
+        let result = self.lock().delete(id);
+        boxed(async move { result })
+    }
+
+    fn add_views<'a>(&'a self, views: &'a [(ArticleId, u64)]) -> BoxFuture<'a, DomainResult<()>> {
+        {
+            let mut articles = self.lock();
+            for (id, count) in views {
+                if let Some(article) = articles.rows.iter_mut().find(|a| a.id == *id) {
+                    article.view_count = article.view_count.saturating_add(*count);
+                }
+            }
+        }
+        boxed(async { Ok(()) })
+    }
+
+    fn reassign_author(&self, from: UserId, to: UserId) -> BoxFuture<'_, DomainResult<u64>> {
+        let mut reassigned = 0;
+        for article in &mut self.lock().rows {
+            if article.author_id == from {
+                article.author_id = to;
+                reassigned += 1;
+            }
+        }
+        boxed(async move { Ok(reassigned) })
+    }
+}
+
+impl ArticleReadRepository for InMemoryArticleRepository {
+    fn find_by_id(&self, id: ArticleId) -> BoxFuture<'_, DomainResult<Option<Article>>> {
+        let found = self.lock().rows.iter().find(|a| a.id == id).cloned();
+        boxed(async move { Ok(found) })
+    }
+
+    fn find_by_slug<'a>(
+        &'a self,
+        slug: &'a ArticleSlug,
+    ) -> BoxFuture<'a, DomainResult<Option<Article>>> {
+        let found = self.lock().rows.iter().find(|a| a.slug == *slug).cloned();
+        boxed(async move { Ok(found) })
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: Option<ArticleListCursor>,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        let page = self.fetch_page(include_drafts, limit, cursor.as_ref(), search, None);
+        boxed(async move { Ok(page) })
+    }
+
+    fn list(
+        &self,
+        query: ArticleQuery,
+    ) -> BoxFuture<'_, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        let page = self.fetch_page(
+            query.include_drafts,
+            query.limit,
+            query.cursor.as_ref(),
+            query.search.as_deref(),
+            query.tag.as_ref().map(Tag::as_str),
+        );
+        boxed(async move { Ok(page) })
+    }
+
+    fn search(
+        &self,
+        query: ArticleQuery,
+    ) -> BoxFuture<'_, DomainResult<(Vec<ArticleSearchHit>, Option<ArticleListCursor>)>> {
+        let search = query.search.as_deref().and_then(WebSearch::parse);
+        let (articles, next_cursor) = self.fetch_page(
+            query.include_drafts,
+            query.limit,
+            query.cursor.as_ref(),
+            query.search.as_deref(),
+            query.tag.as_ref().map(Tag::as_str),
+        );
+        let hits = articles
+            .into_iter()
+            .map(|article| ArticleSearchHit {
+                headline: search
+                    .as_ref()
+                    .map(|search| search.headline(article.body.as_str())),
+                article,
+            })
+            .collect();
+        boxed(async move { Ok((hits, next_cursor)) })
+    }
+
+    fn search_facets<'a>(
+        &'a self,
+        include_drafts: bool,
+        private: PrivateScope,
+        search: Option<&'a str>,
+        tag: Option<&'a Tag>,
+    ) -> BoxFuture<'a, DomainResult<Option<SearchFacets>>> {
+        let facets = self
+            .lock()
+            .facets(include_drafts, private, search, tag.map(Tag::as_str));
+        boxed(async move { Ok(Some(facets)) })
+    }
+
+    fn list_offset<'a>(
+        &'a self,
+        include_drafts: bool,
+        offset: u64,
+        limit: u32,
+        tag: Option<&'a Tag>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, u64)>> {
+        let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+        let (articles, total) =
+            self.lock()
+                .numbered(include_drafts, offset, limit, tag.map(Tag::as_str));
+        boxed(async move { Ok((articles, total)) })
+    }
+
+    fn list_page_before(
+        &self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: ArticleListCursor,
+    ) -> BoxFuture<'_, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+        let articles: Vec<Article> = self
+            .lock()
+            .listing(include_drafts, None, None)
+            .into_iter()
+            .rev()
+            .filter(|article| cursor_of(article) > position(&cursor))
+            .take(limit as usize + 1)
+            .cloned()
+            .collect();
+        let (mut articles, previous) = page(articles, limit);
+        articles.reverse();
+        boxed(async move { Ok((articles, previous)) })
+    }
+
+    fn has_preceding<'a>(
+        &'a self,
+        include_drafts: bool,
+        cursor: &'a ArticleListCursor,
+    ) -> BoxFuture<'a, DomainResult<bool>> {
+        let found = self
+            .lock()
+            .listing(include_drafts, None, None)
+            .into_iter()
+            .any(|article| cursor_of(article) > position(cursor));
+        boxed(async move { Ok(found) })
+    }
+
+    fn list_popular<'a>(
+        &'a self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: Option<ArticlePopularityCursor>,
+        tag: Option<&'a Tag>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, Option<ArticlePopularityCursor>)>> {
+        let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+        let popularity = |article: &Article| (article.view_count, i64::from(article.id));
+        let mut articles: Vec<Article> = self
+            .lock()
+            .listing(include_drafts, None, tag.map(Tag::as_str))
+            .into_iter()
+            .filter(|article| {
+                cursor
+                    .as_ref()
+                    .is_none_or(|c| popularity(article) < (c.view_count, i64::from(c.article_id)))
+            })
+            .cloned()
+            .collect();
+        articles.sort_by_key(|article| std::cmp::Reverse(popularity(article)));
+        articles.truncate(limit as usize + 1);
+        let next = if articles.len() > limit as usize {
+            articles.pop();
+            articles
+                .last()
+                .map(|article| ArticlePopularityCursor::new(article.view_count, article.id))
+        } else {
+            None
+        };
+        boxed(async move { Ok((articles, next)) })
+    }
+
+    fn estimate_total(&self, include_drafts: bool) -> BoxFuture<'_, DomainResult<Option<u64>>> {
+        let total = self.lock().listing(include_drafts, None, None).len() as u64;
+        boxed(async move { Ok(Some(total)) })
+    }
+}
+
+impl ArticleRevisionRepository for InMemoryArticleRepository {
+    fn append<'a>(
+        &'a self,
+        article: &'a Article,
+        edited_by: Option<UserId>,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        {
+            let mut articles = self.lock();
+            let version = articles
+                .revisions
+                .iter()
+                .filter(|rev| rev.article_id == article.id)
+                .map(|rev| rev.version)
+                .max()
+                .unwrap_or(0)
+                + 1;
+            articles.revisions.push(ArticleRevision {
+                article_id: article.id,
+                version,
+                title: article.title.clone(),
+                slug: article.slug.clone(),
+                body: article.body.clone(),
+                published: article.published,
+                published_at: article.published_at,
+                author_id: article.author_id,
+                edited_by,
+                recorded_at: Utc::now(),
+            });
+        }
+        boxed(async { Ok(()) })
+    }
+
+    fn list_by_article(
+        &self,
+        article_id: ArticleId,
+    ) -> BoxFuture<'_, DomainResult<Vec<ArticleRevision>>> {
+        let mut found: Vec<_> = self
+            .lock()
+            .revisions
+            .iter()
+            .filter(|rev| rev.article_id == article_id)
+            .cloned()
+            .collect();
+        found.sort_by_key(|rev| std::cmp::Reverse(rev.version));
+        boxed(async move { Ok(found) })
+    }
+}
+
+/// Custom field definitions kept in process memory, for ephemeral instances.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryCustomFieldRepository(Mutex<Vec<FieldDefinition>>);
+
+impl InMemoryCustomFieldRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<FieldDefinition>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl CustomFieldRepository for InMemoryCustomFieldRepository {
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<FieldDefinition>>> {
+        let mut definitions = self.lock().clone();
+        definitions.sort_by(|a, b| a.name.cmp(&b.name));
+        boxed(async move { Ok(definitions) })
+    }
+
+    fn upsert(&self, definition: FieldDefinition) -> BoxFuture<'_, DomainResult<FieldDefinition>> {
+        {
+            let mut definitions = self.lock();
+            definitions.retain(|existing| existing.name != definition.name);
+            definitions.push(definition.clone());
+        }
+        boxed(async move { Ok(definition) })
+    }
+
+    fn delete<'a>(&'a self, name: &'a str) -> BoxFuture<'a, DomainResult<bool>> {
+        let removed = {
+            let mut definitions = self.lock();
+            let before = definitions.len();
+            definitions.retain(|definition| definition.name != name);
+            before != definitions.len()
+        };
+        boxed(async move { Ok(removed) })
+    }
+}
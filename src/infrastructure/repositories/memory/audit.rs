@@ -0,0 +1,132 @@
+// src/infrastructure/repositories/memory/audit.rs
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::audit::cursor::AuditLogCursor;
+use crate::domain::audit::entity::{AuditLog, NewAuditLog};
+use crate::domain::audit::filter::AuditLogFilter;
+use crate::domain::audit::repository::AuditLogRepository;
+use crate::domain::errors::DomainResult;
+use chrono::Utc;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+#[derive(Default)]
+struct Logs {
+    last_id: i64,
+    rows: Vec<AuditLog>,
+}
+
+/// Audit log kept in process memory, for ephemeral instances. Entries are
+/// stamped with the wall clock on insert, as Postgres does with `now()`.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryAuditLogRepository(Mutex<Logs>);
+
+impl InMemoryAuditLogRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&self) {
+        self.lock().rows.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Logs> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn page(
+        &self,
+        limit: u32,
+        cursor: Option<&AuditLogCursor>,
+        matches: impl Fn(&AuditLog) -> bool,
+    ) -> (Vec<AuditLog>, Option<AuditLogCursor>) {
+        let mut logs: Vec<AuditLog> = self
+            .lock()
+            .rows
+            .iter()
+            .rev()
+            .filter(|log| matches(log))
+            .filter(|log| cursor.is_none_or(|c| (log.created_at, log.id) < (c.created_at, c.id)))
+            .take(limit as usize + 1)
+            .cloned()
+            .collect();
+        let next = AuditLogCursor::trim_page(&mut logs, limit);
+        (logs, next)
+    }
+}
+
+impl AuditLogRepository for InMemoryAuditLogRepository {
+    fn insert(&self, log: NewAuditLog) -> BoxFuture<'_, DomainResult<()>> {
+        {
+            let mut logs = self.lock();
+            logs.last_id += 1;
+            let id = logs.last_id;
+            logs.rows.push(AuditLog {
+                id,
+                user_id: log.user_id,
+                impersonator_id: log.impersonator_id,
+                action: log.action,
+                resource_type: log.resource_type,
+                resource_id: log.resource_id,
+                details: log.details,
+                ip_address: log.ip_address,
+                user_agent: log.user_agent,
+                created_at: Utc::now(),
+            });
+        }
+        boxed(async { Ok(()) })
+    }
+
+    fn list(
+        &self,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        let page = self.page(limit, cursor.as_ref(), |_| true);
+        boxed(async move { Ok(page) })
+    }
+
+    fn find_by_user(
+        &self,
+        user_id: i64,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        let page = self.page(limit, cursor.as_ref(), |log| {
+            log.user_id.map(i64::from) == Some(user_id)
+        });
+        boxed(async move { Ok(page) })
+    }
+
+    fn find_by_resource<'a>(
+        &'a self,
+        resource_type: &'a str,
+        resource_id: i64,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        let page = self.page(limit, cursor.as_ref(), |log| {
+            log.resource_type == resource_type && log.resource_id == Some(resource_id)
+        });
+        boxed(async move { Ok(page) })
+    }
+
+    fn find_by_action<'a>(
+        &'a self,
+        action: &'a str,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        let page = self.page(limit, cursor.as_ref(), |log| log.action == action);
+        boxed(async move { Ok(page) })
+    }
+
+    fn search<'a>(
+        &'a self,
+        filter: &'a AuditLogFilter,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        let page = self.page(limit, cursor.as_ref(), |log| filter.matches(log));
+        boxed(async move { Ok(page) })
+    }
+}
@@ -0,0 +1,66 @@
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::errors::DomainResult;
+use crate::domain::{ArticleContributor, ArticleId, ContributorRepository, UserId};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Article contributors kept in process memory, for ephemeral instances.
+/// Users and articles are not checked to exist.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryContributorRepository(Mutex<Vec<ArticleContributor>>);
+
+impl InMemoryContributorRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<ArticleContributor>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl ContributorRepository for InMemoryContributorRepository {
+    fn add(
+        &self,
+        contributor: ArticleContributor,
+    ) -> BoxFuture<'_, DomainResult<ArticleContributor>> {
+        let mut contributors = self.lock();
+        let existing = contributors
+            .iter()
+            .find(|stored| {
+                stored.article_id == contributor.article_id && stored.user_id == contributor.user_id
+            })
+            .cloned();
+        let stored = existing.unwrap_or_else(|| {
+            contributors.push(contributor.clone());
+            contributor
+        });
+        drop(contributors);
+        boxed(async move { Ok(stored) })
+    }
+
+    fn remove(&self, article_id: ArticleId, user_id: UserId) -> BoxFuture<'_, DomainResult<bool>> {
+        let removed = {
+            let mut contributors = self.lock();
+            let before = contributors.len();
+            contributors
+                .retain(|stored| stored.article_id != article_id || stored.user_id != user_id);
+            contributors.len() != before
+        };
+        boxed(async move { Ok(removed) })
+    }
+
+    fn list(&self, article_id: ArticleId) -> BoxFuture<'_, DomainResult<Vec<ArticleContributor>>> {
+        let listed = self
+            .lock()
+            .iter()
+            .filter(|stored| stored.article_id == article_id)
+            .cloned()
+            .collect();
+        boxed(async move { Ok(listed) })
+    }
+}
@@ -0,0 +1,61 @@
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::errors::DomainResult;
+use crate::domain::{MediaAsset, MediaRepository, NewMediaAsset};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+#[derive(Default)]
+struct Assets {
+    last_id: i64,
+    rows: Vec<MediaAsset>,
+}
+
+/// Media records kept in process memory, for ephemeral instances. The bytes
+/// stay in the configured blob storage.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryMediaRepository(Mutex<Assets>);
+
+impl InMemoryMediaRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&self) {
+        self.lock().rows.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Assets> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl MediaRepository for InMemoryMediaRepository {
+    fn insert(&self, asset: NewMediaAsset) -> BoxFuture<'_, DomainResult<MediaAsset>> {
+        let asset = {
+            let mut assets = self.lock();
+            assets.last_id += 1;
+            let asset = MediaAsset {
+                id: assets.last_id,
+                uploaded_by: asset.uploaded_by,
+                file_name: asset.file_name,
+                content_type: asset.content_type,
+                size_bytes: asset.size_bytes,
+                storage_key: asset.storage_key,
+                created_at: asset.created_at,
+            };
+            assets.rows.push(asset.clone());
+            asset
+        };
+        boxed(async move { Ok(asset) })
+    }
+
+    fn find_by_id(&self, id: i64) -> BoxFuture<'_, DomainResult<Option<MediaAsset>>> {
+        let asset = self
+            .lock()
+            .rows
+            .iter()
+            .find(|asset| asset.id == id)
+            .cloned();
+        boxed(async move { Ok(asset) })
+    }
+}
@@ -0,0 +1,73 @@
+// src/infrastructure/repositories/memory/mod.rs
+//! Repositories that keep their data in process memory, backing
+//! `EPHEMERAL_MODE` instances that run without a database.
+mod access_rules;
+mod articles;
+mod audit;
+mod contributors;
+mod media;
+mod moderation;
+mod roles;
+mod syndication;
+mod users;
+mod webhooks;
+
+use std::sync::Arc;
+
+pub use access_rules::InMemoryAccessRuleRepository;
+pub use articles::{InMemoryArticleRepository, InMemoryCustomFieldRepository};
+pub use audit::InMemoryAuditLogRepository;
+pub use contributors::InMemoryContributorRepository;
+pub use media::InMemoryMediaRepository;
+pub use moderation::InMemoryModerationRepository;
+pub use roles::InMemoryRoleStore;
+pub use syndication::InMemorySyndicationOptOutStore;
+pub use users::{
+    InMemoryAccountDeletions, InMemoryBlockList, InMemoryPasswordHistory, InMemoryUserRepository,
+};
+pub use webhooks::InMemoryWebhookRepository;
+
+/// Every in-memory repository of one instance, so they can be wired up and
+/// wiped together.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryStores {
+    pub users: Arc<InMemoryUserRepository>,
+    pub blocks: Arc<InMemoryBlockList>,
+    pub password_history: Arc<InMemoryPasswordHistory>,
+    pub account_deletions: Arc<InMemoryAccountDeletions>,
+    pub roles: Arc<InMemoryRoleStore>,
+    pub articles: Arc<InMemoryArticleRepository>,
+    pub custom_fields: Arc<InMemoryCustomFieldRepository>,
+    pub contributors: Arc<InMemoryContributorRepository>,
+    pub syndication_opt_outs: Arc<InMemorySyndicationOptOutStore>,
+    pub moderation: Arc<InMemoryModerationRepository>,
+    pub media: Arc<InMemoryMediaRepository>,
+    pub access_rules: Arc<InMemoryAccessRuleRepository>,
+    pub webhooks: Arc<InMemoryWebhookRepository>,
+    pub audit_logs: Arc<InMemoryAuditLogRepository>,
+}
+
+impl InMemoryStores {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop all stored data. Ids are not reused afterwards.
+    pub fn reset(&self) {
+        self.users.clear();
+        self.blocks.clear();
+        self.password_history.clear();
+        self.account_deletions.clear();
+        self.roles.clear();
+        self.articles.clear();
+        self.custom_fields.clear();
+        self.contributors.clear();
+        self.syndication_opt_outs.clear();
+        self.moderation.clear();
+        self.media.clear();
+        self.access_rules.clear();
+        self.webhooks.clear();
+        self.audit_logs.clear();
+    }
+}
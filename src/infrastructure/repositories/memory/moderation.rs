@@ -0,0 +1,99 @@
+// src/infrastructure/repositories/memory/moderation.rs
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::errors::DomainResult;
+use crate::domain::{CaseStatus, ModerationCase, ModerationRepository, NewReport};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+#[derive(Default)]
+struct Cases {
+    last_id: i64,
+    rows: Vec<ModerationCase>,
+}
+
+/// Moderation queue kept in process memory, for ephemeral instances. Each
+/// article has at most one open case.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryModerationRepository(Mutex<Cases>);
+
+impl InMemoryModerationRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&self) {
+        self.lock().rows.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Cases> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl ModerationRepository for InMemoryModerationRepository {
+    fn report(&self, report: NewReport) -> BoxFuture<'_, DomainResult<ModerationCase>> {
+        let case = {
+            let mut cases = self.lock();
+            let open = cases.rows.iter().position(|case| {
+                case.article_id == report.article_id && case.status() == CaseStatus::Open
+            });
+            let index = if let Some(index) = open {
+                index
+            } else {
+                cases.last_id += 1;
+                let id = cases.last_id;
+                cases.rows.push(ModerationCase {
+                    id,
+                    article_id: report.article_id,
+                    reports: Vec::new(),
+                    opened_at: report.report.reported_at,
+                    decision: None,
+                });
+                cases.rows.len() - 1
+            };
+            cases.rows[index].reports.push(report.report);
+            cases.rows[index].clone()
+        };
+        boxed(async move { Ok(case) })
+    }
+
+    fn find_by_id(&self, id: i64) -> BoxFuture<'_, DomainResult<Option<ModerationCase>>> {
+        let case = self.lock().rows.iter().find(|case| case.id == id).cloned();
+        boxed(async move { Ok(case) })
+    }
+
+    fn list(
+        &self,
+        status: CaseStatus,
+        limit: u32,
+        after: Option<i64>,
+    ) -> BoxFuture<'_, DomainResult<Vec<ModerationCase>>> {
+        let cases = self
+            .lock()
+            .rows
+            .iter()
+            .filter(|case| case.status() == status && after.is_none_or(|after| case.id > after))
+            .take(limit as usize)
+            .cloned()
+            .collect();
+        boxed(async move { Ok(cases) })
+    }
+
+    fn save_decision<'a>(&'a self, case: &'a ModerationCase) -> BoxFuture<'a, DomainResult<bool>> {
+        let saved = {
+            let mut cases = self.lock();
+            match cases
+                .rows
+                .iter_mut()
+                .find(|stored| stored.id == case.id && stored.decision.is_none())
+            {
+                Some(stored) => {
+                    stored.decision.clone_from(&case.decision);
+                    true
+                }
+                None => false,
+            }
+        };
+        boxed(async move { Ok(saved) })
+    }
+}
@@ -0,0 +1,149 @@
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{Capability, CustomRole, RoleName, RoleStore, UserId};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+#[derive(Default)]
+struct Roles {
+    by_name: BTreeMap<String, CustomRole>,
+    assigned: HashMap<UserId, String>,
+    granted: HashMap<UserId, BTreeSet<String>>,
+}
+
+/// Custom roles and who has them, kept in process memory, for ephemeral
+/// instances. Assignments and capability grants are not checked against the
+/// users.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryRoleStore(Mutex<Roles>);
+
+impl InMemoryRoleStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&self) {
+        let mut roles = self.lock();
+        roles.by_name.clear();
+        roles.assigned.clear();
+        roles.granted.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Roles> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl RoleStore for InMemoryRoleStore {
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<CustomRole>>> {
+        let roles = self.lock().by_name.values().cloned().collect();
+        boxed(async move { Ok(roles) })
+    }
+
+    fn find<'a>(&'a self, name: &'a RoleName) -> BoxFuture<'a, DomainResult<Option<CustomRole>>> {
+        let role = self.lock().by_name.get(name.as_str()).cloned();
+        boxed(async move { Ok(role) })
+    }
+
+    fn save(&self, role: CustomRole) -> BoxFuture<'_, DomainResult<CustomRole>> {
+        let stored = {
+            let mut roles = self.lock();
+            let stored = match roles.by_name.get(role.name.as_str()) {
+                Some(existing) => CustomRole {
+                    created_at: existing.created_at,
+                    ..role
+                },
+                None => role,
+            };
+            roles
+                .by_name
+                .insert(stored.name.to_string(), stored.clone());
+            stored
+        };
+        boxed(async move { Ok(stored) })
+    }
+
+    fn delete<'a>(&'a self, name: &'a RoleName) -> BoxFuture<'a, DomainResult<bool>> {
+        let removed = {
+            let mut roles = self.lock();
+            roles.assigned.retain(|_, role| role != name.as_str());
+            roles.by_name.remove(name.as_str()).is_some()
+        };
+        boxed(async move { Ok(removed) })
+    }
+
+    fn assign<'a>(
+        &'a self,
+        user: UserId,
+        name: Option<&'a RoleName>,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        let mut roles = self.lock();
+        let result = match name {
+            Some(name) if !roles.by_name.contains_key(name.as_str()) => {
+                Err(DomainError::NotFound("role not found".into()))
+            }
+            Some(name) => {
+                roles.assigned.insert(user, name.to_string());
+                Ok(())
+            }
+            None => {
+                roles.assigned.remove(&user);
+                Ok(())
+            }
+        };
+        drop(roles);
+        boxed(async move { result })
+    }
+
+    fn role_of(&self, user: UserId) -> BoxFuture<'_, DomainResult<Option<CustomRole>>> {
+        let role = {
+            let roles = self.lock();
+            roles
+                .assigned
+                .get(&user)
+                .and_then(|name| roles.by_name.get(name))
+                .cloned()
+        };
+        boxed(async move { Ok(role) })
+    }
+
+    fn grant_capability<'a>(
+        &'a self,
+        user: UserId,
+        capability: &'a Capability,
+    ) -> BoxFuture<'a, DomainResult<bool>> {
+        let added = self
+            .lock()
+            .granted
+            .entry(user)
+            .or_default()
+            .insert(capability.to_string());
+        boxed(async move { Ok(added) })
+    }
+
+    fn revoke_capability<'a>(
+        &'a self,
+        user: UserId,
+        capability: &'a Capability,
+    ) -> BoxFuture<'a, DomainResult<bool>> {
+        let removed = self
+            .lock()
+            .granted
+            .get_mut(&user)
+            .is_some_and(|granted| granted.remove(&capability.to_string()));
+        boxed(async move { Ok(removed) })
+    }
+
+    fn capabilities_of(&self, user: UserId) -> BoxFuture<'_, DomainResult<Vec<Capability>>> {
+        let granted: DomainResult<Vec<Capability>> = self
+            .lock()
+            .granted
+            .get(&user)
+            .into_iter()
+            .flatten()
+            .map(|cap| cap.parse())
+            .collect();
+        boxed(async move { granted })
+    }
+}
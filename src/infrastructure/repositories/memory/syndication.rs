@@ -0,0 +1,49 @@
+// src/infrastructure/repositories/memory/syndication.rs
+use crate::application::AppResult;
+use crate::application::ports::syndication::SyndicationOptOutStore;
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::ArticleId;
+use std::collections::HashSet;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Syndication opt-outs kept in process memory, for ephemeral instances.
+#[derive(Default)]
+#[must_use]
+pub struct InMemorySyndicationOptOutStore(Mutex<HashSet<ArticleId>>);
+
+impl InMemorySyndicationOptOutStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashSet<ArticleId>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl SyndicationOptOutStore for InMemorySyndicationOptOutStore {
+    fn is_opted_out(&self, article_id: ArticleId) -> BoxFuture<'_, AppResult<bool>> {
+        let opted_out = self.lock().contains(&article_id);
+        boxed(async move { Ok(opted_out) })
+    }
+
+    fn set_opted_out(
+        &self,
+        article_id: ArticleId,
+        opted_out: bool,
+    ) -> BoxFuture<'_, AppResult<()>> {
+        {
+            let mut ids = self.lock();
+            if opted_out {
+                ids.insert(article_id);
+            } else {
+                ids.remove(&article_id);
+            }
+        }
+        boxed(async move { Ok(()) })
+    }
+}
@@ -0,0 +1,630 @@
+// src/infrastructure/repositories/memory/users.rs
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::PAGE_LIMIT_CEILING;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{
+    AccountDeletions, BlockList, Email, NewUser, PasswordHash, PasswordHistory, PendingDeletion,
+    User, UserBlock, UserId, UserListCursor, UserRepository, UserUpdate, Username,
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+#[derive(Default)]
+struct Users {
+    last_id: i64,
+    rows: Vec<User>,
+    // (provider, subject) -> user
+    federated: HashMap<(String, String), UserId>,
+    bootstrapped: bool,
+}
+
+impl Users {
+    fn insert(&mut self, new_user: NewUser) -> DomainResult<User> {
+        if self
+            .rows
+            .iter()
+            .any(|user| user.username.eq_ignore_case(new_user.username.as_str()))
+        {
+            return Err(DomainError::UsernameTaken {
+                username: Some(new_user.username.into()),
+            });
+        }
+        self.last_id += 1;
+        let user = User {
+            id: UserId::new(self.last_id)?,
+            username: new_user.username,
+            password_hash: new_user.password_hash,
+            role: new_user.role,
+            is_active: new_user.is_active,
+            created_at: new_user.created_at,
+            timezone: None,
+            password_reset_required: new_user.password_reset_required,
+            email: None,
+            email_verified: false,
+        };
+        self.rows.push(user.clone());
+        Ok(user)
+    }
+
+    fn update(&mut self, update: UserUpdate) -> DomainResult<User> {
+        if update.username.is_none()
+            && update.is_active.is_none()
+            && update.role.is_none()
+            && update.password_hash.is_none()
+            && update.timezone.is_none()
+            && update.password_reset_required.is_none()
+            && update.email.is_none()
+            && update.email_verified.is_none()
+        {
+            return Err(DomainError::Validation(
+                "no fields provided for update".into(),
+            ));
+        }
+        if let Some(Some(email)) = &update.email
+            && self.rows.iter().any(|user| {
+                user.id != update.id
+                    && user
+                        .email
+                        .as_ref()
+                        .is_some_and(|own| own.as_str().eq_ignore_ascii_case(email.as_str()))
+            })
+        {
+            return Err(DomainError::Conflict("email is already in use".into()));
+        }
+        if let Some(username) = &update.username
+            && self
+                .rows
+                .iter()
+                .any(|user| user.id != update.id && user.username.eq_ignore_case(username.as_str()))
+        {
+            return Err(DomainError::UsernameTaken {
+                username: Some(username.as_str().to_owned()),
+            });
+        }
+        let user = self
+            .rows
+            .iter_mut()
+            .find(|user| user.id == update.id)
+            .ok_or_else(|| DomainError::NotFound("user not found".into()))?;
+        if let Some(username) = update.username {
+            user.username = username;
+        }
+        if let Some(is_active) = update.is_active {
+            user.is_active = is_active;
+        }
+        if let Some(role) = update.role {
+            user.role = role;
+        }
+        if let Some(password_hash) = update.password_hash {
+            user.password_hash = password_hash;
+        }
+        if let Some(timezone) = update.timezone {
+            user.timezone = timezone;
+        }
+        if let Some(required) = update.password_reset_required {
+            user.password_reset_required = required;
+        }
+        if let Some(email) = update.email {
+            user.email = email;
+        }
+        if let Some(verified) = update.email_verified {
+            user.email_verified = verified;
+        }
+        Ok(user.clone())
+    }
+
+    /// Link an external account, which may belong to one user and be the
+    /// only one that user has at `provider`.
+    fn link(&mut self, provider: &str, subject: &str, user_id: UserId) -> DomainResult<()> {
+        let key = (provider.to_string(), subject.to_string());
+        if self.federated.contains_key(&key) {
+            return Err(DomainError::Conflict(
+                "external account is already linked".into(),
+            ));
+        }
+        if self
+            .federated
+            .iter()
+            .any(|((linked, _), id)| linked == provider && *id == user_id)
+        {
+            return Err(DomainError::Conflict(
+                "user already has a linked account at this provider".into(),
+            ));
+        }
+        self.federated.insert(key, user_id);
+        Ok(())
+    }
+
+    /// Users matching `search`, newest first.
+    /// `limit` listed users from `offset` on, and how many are listed.
+    fn numbered(&self, offset: u64, limit: u32, search: Option<&str>) -> (Vec<User>, u64) {
+        let listing = self.listing(search);
+        let total = listing.len() as u64;
+        let page = listing
+            .into_iter()
+            .skip(usize::try_from(offset).unwrap_or(usize::MAX))
+            .take(limit as usize)
+            .cloned()
+            .collect();
+        (page, total)
+    }
+
+    fn listing(&self, search: Option<&str>) -> Vec<&User> {
+        let search = search
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_lowercase);
+        let mut users: Vec<_> = self
+            .rows
+            .iter()
+            .filter(|user| {
+                search
+                    .as_deref()
+                    .is_none_or(|s| user.username.as_str().to_lowercase().contains(s))
+            })
+            .collect();
+        users.sort_by_key(|user| std::cmp::Reverse(cursor_of(user)));
+        users
+    }
+}
+
+fn cursor_of(user: &User) -> (chrono::DateTime<chrono::Utc>, i64) {
+    (user.created_at, i64::from(user.id))
+}
+
+fn position(cursor: &UserListCursor) -> (chrono::DateTime<chrono::Utc>, i64) {
+    (cursor.created_at, i64::from(cursor.user_id))
+}
+
+/// Trim a `limit + 1` listing to one page and the cursor for the next.
+fn page(mut users: Vec<User>, limit: u32) -> (Vec<User>, Option<UserListCursor>) {
+    if users.len() <= limit as usize {
+        return (users, None);
+    }
+    users.truncate(limit as usize);
+    let next = users
+        .last()
+        .map(|user| UserListCursor::new(user.created_at, user.id));
+    (users, next)
+}
+
+/// Users kept in process memory, for ephemeral instances. Usernames and
+/// emails are unique regardless of case, as in Postgres.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryUserRepository(Mutex<Users>);
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget every user. Ids keep counting up, so tokens issued to removed
+    /// users never match a new one.
+    pub fn clear(&self) {
+        let mut users = self.lock();
+        users.rows.clear();
+        users.federated.clear();
+        users.bootstrapped = false;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Users> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl UserRepository for InMemoryUserRepository {
+    fn count(&self) -> BoxFuture<'_, DomainResult<u64>> {
+        let count = self.lock().rows.len() as u64;
+        boxed(async move { Ok(count) })
+    }
+
+    fn insert(&self, new_user: NewUser) -> BoxFuture<'_, DomainResult<User>> {
+        let result = self.lock().insert(new_user);
+        boxed(async move { result })
+    }
+
+    fn insert_bootstrap_admin(
+        &self,
+        new_user: NewUser,
+    ) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        let result = {
+            let mut users = self.lock();
+            if users.bootstrapped {
+                Ok(None)
+            } else {
+                users.insert(new_user).map(|user| {
+                    users.bootstrapped = true;
+                    Some(user)
+                })
+            }
+        };
+        boxed(async move { result })
+    }
+
+    fn find_by_username<'a>(
+        &'a self,
+        username: &'a Username,
+    ) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        let found = self
+            .lock()
+            .rows
+            .iter()
+            .find(|user| user.username.eq_ignore_case(username.as_str()))
+            .cloned();
+        boxed(async move { Ok(found) })
+    }
+
+    fn find_by_id(&self, id: UserId) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        let found = self.lock().rows.iter().find(|user| user.id == id).cloned();
+        boxed(async move { Ok(found) })
+    }
+
+    fn find_by_email<'a>(&'a self, email: &'a Email) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        let found = self
+            .lock()
+            .rows
+            .iter()
+            .find(|user| {
+                user.email
+                    .as_ref()
+                    .is_some_and(|own| own.as_str().eq_ignore_ascii_case(email.as_str()))
+            })
+            .cloned();
+        boxed(async move { Ok(found) })
+    }
+
+    fn update(&self, update: UserUpdate) -> BoxFuture<'_, DomainResult<User>> {
+        let result = self.lock().update(update);
+        boxed(async move { result })
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        limit: u32,
+        cursor: Option<UserListCursor>,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, Option<UserListCursor>)>> {
+        let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+        let users: Vec<User> = self
+            .lock()
+            .listing(search)
+            .into_iter()
+            .filter(|user| {
+                cursor
+                    .as_ref()
+                    .is_none_or(|c| cursor_of(user) < position(c))
+            })
+            .take(limit as usize + 1)
+            .cloned()
+            .collect();
+        boxed(async move { Ok(page(users, limit)) })
+    }
+
+    fn list_page_before<'a>(
+        &'a self,
+        limit: u32,
+        cursor: UserListCursor,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, Option<UserListCursor>)>> {
+        let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+        let users: Vec<User> = self
+            .lock()
+            .listing(search)
+            .into_iter()
+            .rev()
+            .filter(|user| cursor_of(user) > position(&cursor))
+            .take(limit as usize + 1)
+            .cloned()
+            .collect();
+        let (mut users, previous) = page(users, limit);
+        users.reverse();
+        boxed(async move { Ok((users, previous)) })
+    }
+
+    fn has_preceding<'a>(
+        &'a self,
+        cursor: &'a UserListCursor,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<bool>> {
+        let found = self
+            .lock()
+            .listing(search)
+            .into_iter()
+            .any(|user| cursor_of(user) > position(cursor));
+        boxed(async move { Ok(found) })
+    }
+
+    fn list_offset<'a>(
+        &'a self,
+        offset: u64,
+        limit: u32,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, u64)>> {
+        let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+        let (users, total) = self.lock().numbered(offset, limit, search);
+        boxed(async move { Ok((users, total)) })
+    }
+
+    fn estimate_total<'a>(
+        &'a self,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<Option<u64>>> {
+        let total = self.lock().listing(search).len() as u64;
+        boxed(async move { Ok(Some(total)) })
+    }
+
+    fn find_by_federated_identity<'a>(
+        &'a self,
+        provider: &'a str,
+        subject: &'a str,
+    ) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        let found = {
+            let users = self.lock();
+            users
+                .federated
+                .get(&(provider.to_string(), subject.to_string()))
+                .and_then(|id| users.rows.iter().find(|user| user.id == *id))
+                .cloned()
+        };
+        boxed(async move { Ok(found) })
+    }
+
+    fn link_federated_identity<'a>(
+        &'a self,
+        provider: &'a str,
+        subject: &'a str,
+        user_id: UserId,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        let result = self.lock().link(provider, subject, user_id);
+        boxed(async move { result })
+    }
+}
+
+/// Blocks kept in process memory, for ephemeral instances.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryBlockList(Mutex<Vec<UserBlock>>);
+
+impl InMemoryBlockList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<UserBlock>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl BlockList for InMemoryBlockList {
+    fn add(&self, block: UserBlock) -> BoxFuture<'_, DomainResult<UserBlock>> {
+        let stored = {
+            let mut blocks = self.lock();
+            let existing = blocks
+                .iter()
+                .find(|b| b.owner == block.owner && b.target == block.target)
+                .cloned();
+            let stored = existing.unwrap_or_else(|| {
+                blocks.push(block.clone());
+                block
+            });
+            drop(blocks);
+            stored
+        };
+        boxed(async move { Ok(stored) })
+    }
+
+    fn remove(&self, owner: UserId, target: UserId) -> BoxFuture<'_, DomainResult<bool>> {
+        let removed = {
+            let mut blocks = self.lock();
+            let before = blocks.len();
+            blocks.retain(|b| !(b.owner == owner && b.target == target));
+            before != blocks.len()
+        };
+        boxed(async move { Ok(removed) })
+    }
+
+    fn list(&self, owner: UserId) -> BoxFuture<'_, DomainResult<Vec<UserBlock>>> {
+        let found: Vec<_> = self
+            .lock()
+            .iter()
+            .rev()
+            .filter(|b| b.owner == owner)
+            .cloned()
+            .collect();
+        boxed(async move { Ok(found) })
+    }
+
+    fn find(
+        &self,
+        owner: UserId,
+        target: UserId,
+    ) -> BoxFuture<'_, DomainResult<Option<UserBlock>>> {
+        let found = self
+            .lock()
+            .iter()
+            .find(|b| b.owner == owner && b.target == target)
+            .cloned();
+        boxed(async move { Ok(found) })
+    }
+}
+
+/// Former password hashes per user, most recent first.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryPasswordHistory(Mutex<HashMap<i64, Vec<PasswordHash>>>);
+
+impl InMemoryPasswordHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<i64, Vec<PasswordHash>>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl PasswordHistory for InMemoryPasswordHistory {
+    fn record(
+        &self,
+        user: UserId,
+        hash: PasswordHash,
+        _replaced_at: DateTime<Utc>,
+        keep: usize,
+    ) -> BoxFuture<'_, DomainResult<()>> {
+        let mut history = self.lock();
+        let hashes = history.entry(i64::from(user)).or_default();
+        hashes.insert(0, hash);
+        hashes.truncate(keep);
+        drop(history);
+        boxed(async move { Ok(()) })
+    }
+
+    fn recent(&self, user: UserId, limit: usize) -> BoxFuture<'_, DomainResult<Vec<PasswordHash>>> {
+        let found = self
+            .lock()
+            .get(&i64::from(user))
+            .map(|hashes| hashes.iter().take(limit).cloned().collect())
+            .unwrap_or_default();
+        boxed(async move { Ok(found) })
+    }
+}
+
+/// Scheduled account deletions kept in process memory.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryAccountDeletions(Mutex<Vec<PendingDeletion>>);
+
+impl InMemoryAccountDeletions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<PendingDeletion>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl AccountDeletions for InMemoryAccountDeletions {
+    fn schedule(&self, deletion: PendingDeletion) -> BoxFuture<'_, DomainResult<()>> {
+        let mut deletions = self.lock();
+        deletions.retain(|pending| pending.user_id != deletion.user_id);
+        deletions.push(deletion);
+        drop(deletions);
+        boxed(async move { Ok(()) })
+    }
+
+    fn remove(&self, user_id: UserId) -> BoxFuture<'_, DomainResult<bool>> {
+        let mut deletions = self.lock();
+        let before = deletions.len();
+        deletions.retain(|pending| pending.user_id != user_id);
+        let removed = deletions.len() != before;
+        drop(deletions);
+        boxed(async move { Ok(removed) })
+    }
+
+    fn find(&self, user_id: UserId) -> BoxFuture<'_, DomainResult<Option<PendingDeletion>>> {
+        let found = self
+            .lock()
+            .iter()
+            .find(|pending| pending.user_id == user_id)
+            .cloned();
+        boxed(async move { Ok(found) })
+    }
+
+    fn due(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> BoxFuture<'_, DomainResult<Vec<PendingDeletion>>> {
+        let mut due: Vec<_> = self
+            .lock()
+            .iter()
+            .filter(|pending| pending.purge_after <= now)
+            .cloned()
+            .collect();
+        due.sort_by_key(|pending| (pending.purge_after, i64::from(pending.user_id)));
+        due.truncate(limit as usize);
+        boxed(async move { Ok(due) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{PasswordHash, Role};
+    use chrono::{Duration, Utc};
+
+    fn new_user(name: &str, minutes: i64) -> NewUser {
+        NewUser::new(
+            Username::new(name).unwrap(),
+            PasswordHash::new("$argon2id$v=19$m=19456,t=2,p=1$c2FsdA$aGFzaA").unwrap(),
+            Role::Author,
+            Utc::now() + Duration::minutes(minutes),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn usernames_are_unique_and_ids_survive_clearing() {
+        let repo = InMemoryUserRepository::new();
+        let alice = repo.insert(new_user("alice", 0)).await.unwrap();
+        assert!(matches!(
+            repo.insert(new_user("ALICE", 1)).await,
+            Err(DomainError::UsernameTaken { .. })
+        ));
+
+        repo.clear();
+        let again = repo.insert(new_user("alice", 2)).await.unwrap();
+        assert!(i64::from(again.id) > i64::from(alice.id));
+        assert_eq!(repo.count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn listing_pages_newest_first() {
+        let repo = InMemoryUserRepository::new();
+        for (minutes, name) in ["ann", "bob", "cat"].into_iter().enumerate() {
+            repo.insert(new_user(name, i64::try_from(minutes).unwrap()))
+                .await
+                .unwrap();
+        }
+
+        let (first, next) = repo.list_page(2, None, None).await.unwrap();
+        let names: Vec<_> = first.iter().map(|u| u.username.as_str()).collect();
+        assert_eq!(names, ["cat", "bob"]);
+        let (rest, last) = repo.list_page(2, next.clone(), None).await.unwrap();
+        assert_eq!(rest[0].username.as_str(), "ann");
+        assert!(last.is_none());
+        assert!(repo.has_preceding(&next.unwrap(), None).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn offset_listing_counts_every_match() {
+        let repo = InMemoryUserRepository::new();
+        for (minutes, name) in ["ann", "bob", "cat", "anna"].into_iter().enumerate() {
+            repo.insert(new_user(name, i64::try_from(minutes).unwrap()))
+                .await
+                .unwrap();
+        }
+
+        let (page, total) = repo.list_offset(1, 2, None).await.unwrap();
+        let names: Vec<_> = page.iter().map(|u| u.username.as_str()).collect();
+        assert_eq!((names, total), (vec!["cat", "bob"], 4));
+        let (page, total) = repo.list_offset(1, 2, Some("an")).await.unwrap();
+        assert_eq!(page[0].username.as_str(), "ann");
+        assert_eq!(total, 2);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::errors::DomainResult;
+use crate::domain::{
+    NewWebhook, NewWebhookDeliveryAttempt, Webhook, WebhookDeliveryAttempt, WebhookEvent,
+    WebhookRepository,
+};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+#[derive(Default)]
+struct Webhooks {
+    last_id: i64,
+    rows: Vec<Webhook>,
+    last_attempt_id: i64,
+    attempts: Vec<WebhookDeliveryAttempt>,
+}
+
+/// Webhooks and their delivery history kept in process memory, for
+/// ephemeral instances.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryWebhookRepository(Mutex<Webhooks>);
+
+impl InMemoryWebhookRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&self) {
+        let mut webhooks = self.lock();
+        webhooks.rows.clear();
+        webhooks.attempts.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Webhooks> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl WebhookRepository for InMemoryWebhookRepository {
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<Webhook>>> {
+        let rows = self.lock().rows.clone();
+        boxed(async move { Ok(rows) })
+    }
+
+    fn find_by_id(&self, id: i64) -> BoxFuture<'_, DomainResult<Option<Webhook>>> {
+        let webhook = self.lock().rows.iter().find(|row| row.id == id).cloned();
+        boxed(async move { Ok(webhook) })
+    }
+
+    fn subscribed(&self, event: WebhookEvent) -> BoxFuture<'_, DomainResult<Vec<Webhook>>> {
+        let rows = self
+            .lock()
+            .rows
+            .iter()
+            .filter(|row| row.receives(event))
+            .cloned()
+            .collect();
+        boxed(async move { Ok(rows) })
+    }
+
+    fn insert(&self, webhook: NewWebhook) -> BoxFuture<'_, DomainResult<Webhook>> {
+        let webhook = {
+            let mut webhooks = self.lock();
+            webhooks.last_id += 1;
+            let webhook = Webhook {
+                id: webhooks.last_id,
+                url: webhook.url,
+                secret: webhook.secret,
+                events: webhook.events,
+                active: webhook.active,
+                created_by: webhook.created_by,
+                created_at: webhook.created_at,
+                updated_at: webhook.created_at,
+            };
+            webhooks.rows.push(webhook.clone());
+            webhook
+        };
+        boxed(async move { Ok(webhook) })
+    }
+
+    fn update<'a>(&'a self, webhook: &'a Webhook) -> BoxFuture<'a, DomainResult<bool>> {
+        let updated = self
+            .lock()
+            .rows
+            .iter_mut()
+            .find(|row| row.id == webhook.id)
+            .map(|row| {
+                *row = Webhook {
+                    created_by: row.created_by,
+                    created_at: row.created_at,
+                    ..webhook.clone()
+                };
+            })
+            .is_some();
+        boxed(async move { Ok(updated) })
+    }
+
+    fn delete(&self, id: i64) -> BoxFuture<'_, DomainResult<bool>> {
+        let deleted = {
+            let mut webhooks = self.lock();
+            let before = webhooks.rows.len();
+            webhooks.rows.retain(|row| row.id != id);
+            webhooks.attempts.retain(|attempt| attempt.webhook_id != id);
+            webhooks.rows.len() < before
+        };
+        boxed(async move { Ok(deleted) })
+    }
+
+    fn record_attempt(
+        &self,
+        attempt: NewWebhookDeliveryAttempt,
+    ) -> BoxFuture<'_, DomainResult<WebhookDeliveryAttempt>> {
+        let attempt = {
+            let mut webhooks = self.lock();
+            webhooks.last_attempt_id += 1;
+            let attempt = WebhookDeliveryAttempt {
+                id: webhooks.last_attempt_id,
+                webhook_id: attempt.webhook_id,
+                delivery_id: attempt.delivery_id,
+                event: attempt.event,
+                attempt: attempt.attempt,
+                status_code: attempt.status_code,
+                error: attempt.error,
+                succeeded: attempt.succeeded,
+                duration_ms: attempt.duration_ms,
+                attempted_at: attempt.attempted_at,
+            };
+            webhooks.attempts.push(attempt.clone());
+            attempt
+        };
+        boxed(async move { Ok(attempt) })
+    }
+
+    fn list_attempts(
+        &self,
+        webhook_id: i64,
+        limit: u32,
+    ) -> BoxFuture<'_, DomainResult<Vec<WebhookDeliveryAttempt>>> {
+        let attempts = self
+            .lock()
+            .attempts
+            .iter()
+            .rev()
+            .filter(|attempt| attempt.webhook_id == webhook_id)
+            .take(usize::try_from(limit).unwrap_or(usize::MAX))
+            .cloned()
+            .collect();
+        boxed(async move { Ok(attempts) })
+    }
+}
@@ -1,13 +1,37 @@
 // src/infrastructure/repositories/mod.rs
+pub mod access_rules;
 pub mod articles;
 pub mod audit;
 mod error;
+pub mod media;
+pub mod memory;
+pub mod moderation;
+mod search;
+mod spans;
+pub mod sqlite;
+pub mod unit_of_work;
 pub mod users;
+pub mod webhooks;
 
+pub use access_rules::PostgresAccessRuleRepository;
 pub use articles::{
     PostgresArticleReadRepository, PostgresArticleRevisionRepository,
-    PostgresArticleWriteRepository,
+    PostgresArticleWriteRepository, PostgresContributorRepository, PostgresCustomFieldRepository,
+    PostgresSyndicationOptOutStore,
+};
+pub use audit::{
+    BufferOptions, BufferedAuditLogRepository, OverflowPolicy, PostgresAuditLogRepository,
+    RedactingAuditLogRepository,
 };
-pub use audit::PostgresAuditLogRepository;
 pub(crate) use error::map_sqlx;
-pub use users::PostgresUserRepository;
+pub use media::PostgresMediaRepository;
+pub use memory::InMemoryStores;
+pub use moderation::PostgresModerationRepository;
+pub(crate) use spans::traced;
+pub use sqlite::SqliteStores;
+pub use unit_of_work::PostgresUnitOfWork;
+pub use users::{
+    PostgresAccountDeletions, PostgresBlockList, PostgresPasswordHistory, PostgresRoleStore,
+    PostgresUserRepository, PostgresUserTokenStore,
+};
+pub use webhooks::PostgresWebhookRepository;
@@ -0,0 +1,197 @@
+// src/infrastructure/repositories/moderation/postgres.rs
+use super::super::map_sqlx;
+use crate::async_support::BoxFuture;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{
+    ArticleId, CaseStatus, ModerationCase, ModerationDecision, ModerationRepository, NewReport,
+    Report, UserId,
+};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use std::collections::HashMap;
+
+const CASE_COLUMNS: &str =
+    "id, article_id, opened_at, resolution, resolved_by, resolution_note, resolved_at";
+
+#[derive(Clone)]
+#[must_use]
+pub struct PostgresModerationRepository {
+    pool: PgPool,
+}
+
+impl PostgresModerationRepository {
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Attach each case's reports, oldest first.
+    async fn with_reports(&self, rows: Vec<CaseRow>) -> DomainResult<Vec<ModerationCase>> {
+        let ids: Vec<i64> = rows.iter().map(|row| row.id).collect();
+        let report_rows = sqlx::query_as::<_, ReportRow>(
+            "SELECT case_id, reason, details, reported_at FROM moderation_reports
+             WHERE case_id = ANY($1) ORDER BY id",
+        )
+        .bind(&ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_sqlx)?;
+
+        let mut reports: HashMap<i64, Vec<Report>> = HashMap::new();
+        for row in report_rows {
+            reports.entry(row.case_id).or_default().push(Report {
+                reason: row.reason.parse()?,
+                details: row.details,
+                reported_at: row.reported_at,
+            });
+        }
+        rows.into_iter()
+            .map(|row| {
+                let case_reports = reports.remove(&row.id).unwrap_or_default();
+                row.into_case(case_reports)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct CaseRow {
+    id: i64,
+    article_id: i64,
+    opened_at: DateTime<Utc>,
+    resolution: Option<String>,
+    resolved_by: Option<i64>,
+    resolution_note: Option<String>,
+    resolved_at: Option<DateTime<Utc>>,
+}
+
+impl CaseRow {
+    fn into_case(self, reports: Vec<Report>) -> DomainResult<ModerationCase> {
+        let decision = match (self.resolution, self.resolved_by, self.resolved_at) {
+            (Some(resolution), Some(resolved_by), Some(resolved_at)) => Some(ModerationDecision {
+                resolution: resolution.parse()?,
+                resolved_by: UserId::new(resolved_by)?,
+                note: self.resolution_note,
+                resolved_at,
+            }),
+            (None, _, _) => None,
+            _ => {
+                return Err(DomainError::Persistence(format!(
+                    "moderation case {} has an incomplete resolution",
+                    self.id
+                )));
+            }
+        };
+        Ok(ModerationCase {
+            id: self.id,
+            article_id: ArticleId::new(self.article_id)?,
+            reports,
+            opened_at: self.opened_at,
+            decision,
+        })
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct ReportRow {
+    case_id: i64,
+    reason: String,
+    details: Option<String>,
+    reported_at: DateTime<Utc>,
+}
+
+impl ModerationRepository for PostgresModerationRepository {
+    fn report(&self, report: NewReport) -> BoxFuture<'_, DomainResult<ModerationCase>> {
+        traced("PostgresModerationRepository::report", async move {
+            let mut tx = self.pool.begin().await.map_err(map_sqlx)?;
+            let (case_id,): (i64,) = sqlx::query_as(
+                "INSERT INTO moderation_cases (article_id, opened_at) VALUES ($1, $2)
+                 ON CONFLICT (article_id) WHERE status = 'open'
+                 DO UPDATE SET article_id = EXCLUDED.article_id
+                 RETURNING id",
+            )
+            .bind(i64::from(report.article_id))
+            .bind(report.report.reported_at)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(map_sqlx)?;
+            sqlx::query(
+                "INSERT INTO moderation_reports (case_id, reason, details, reported_at)
+                 VALUES ($1, $2, $3, $4)",
+            )
+            .bind(case_id)
+            .bind(report.report.reason.as_str())
+            .bind(report.report.details.as_deref())
+            .bind(report.report.reported_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx)?;
+            tx.commit().await.map_err(map_sqlx)?;
+
+            self.find_by_id(case_id).await?.ok_or_else(|| {
+                DomainError::Persistence(format!("moderation case {case_id} vanished"))
+            })
+        })
+    }
+
+    fn find_by_id(&self, id: i64) -> BoxFuture<'_, DomainResult<Option<ModerationCase>>> {
+        traced("PostgresModerationRepository::find_by_id", async move {
+            let row = sqlx::query_as::<_, CaseRow>(&format!(
+                "SELECT {CASE_COLUMNS} FROM moderation_cases WHERE id = $1"
+            ))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+            let Some(row) = row else {
+                return Ok(None);
+            };
+            Ok(self.with_reports(vec![row]).await?.pop())
+        })
+    }
+
+    fn list(
+        &self,
+        status: CaseStatus,
+        limit: u32,
+        after: Option<i64>,
+    ) -> BoxFuture<'_, DomainResult<Vec<ModerationCase>>> {
+        traced("PostgresModerationRepository::list", async move {
+            let rows = sqlx::query_as::<_, CaseRow>(&format!(
+                "SELECT {CASE_COLUMNS} FROM moderation_cases
+                 WHERE status = $1 AND ($2::BIGINT IS NULL OR id > $2)
+                 ORDER BY id LIMIT $3"
+            ))
+            .bind(status.as_str())
+            .bind(after)
+            .bind(i64::from(limit))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+            self.with_reports(rows).await
+        })
+    }
+
+    fn save_decision<'a>(&'a self, case: &'a ModerationCase) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("PostgresModerationRepository::save_decision", async move {
+            let decision = case.decision.as_ref().ok_or_else(|| {
+                DomainError::Validation(format!("moderation case {} is not resolved", case.id))
+            })?;
+            let result = sqlx::query(
+                "UPDATE moderation_cases
+                 SET status = 'resolved', resolution = $2, resolved_by = $3,
+                     resolution_note = $4, resolved_at = $5
+                 WHERE id = $1 AND status = 'open'",
+            )
+            .bind(case.id)
+            .bind(decision.resolution.as_str())
+            .bind(i64::from(decision.resolved_by))
+            .bind(decision.note.as_deref())
+            .bind(decision.resolved_at)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+}
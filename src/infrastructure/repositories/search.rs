@@ -0,0 +1,129 @@
+// src/infrastructure/repositories/search.rs
+//! A small take on `websearch_to_tsquery` for article stores without a text
+//! search engine: case-insensitive substrings instead of stemmed lexemes.
+
+/// How many words an excerpt shows before the first match.
+const LEAD_WORDS: usize = 5;
+/// Most words in an excerpt.
+const EXCERPT_WORDS: usize = 30;
+
+/// A parsed search: every `required` term must occur and no `excluded` one.
+/// Quoted phrases are single terms, `-` excludes the term it prefixes and
+/// `or` is ignored, so alternatives are all required.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct WebSearch {
+    required: Vec<String>,
+    excluded: Vec<String>,
+}
+
+impl WebSearch {
+    /// `None` when `query` holds no terms.
+    pub(super) fn parse(query: &str) -> Option<Self> {
+        let mut search = Self::default();
+        for (index, chunk) in query.to_lowercase().split('"').enumerate() {
+            if index % 2 == 1 {
+                let phrase = chunk.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !phrase.is_empty() {
+                    search.required.push(phrase);
+                }
+                continue;
+            }
+            for word in chunk.split_whitespace() {
+                if word == "or" {
+                    continue;
+                }
+                match word.strip_prefix('-') {
+                    Some(excluded) if !excluded.is_empty() => {
+                        search.excluded.push(excluded.to_string());
+                    }
+                    Some(_) => {}
+                    None => search.required.push(word.to_string()),
+                }
+            }
+        }
+        (!search.required.is_empty() || !search.excluded.is_empty()).then_some(search)
+    }
+
+    /// Lowercased terms that must all occur.
+    pub(super) fn required(&self) -> &[String] {
+        &self.required
+    }
+
+    /// Lowercased terms none of which may occur.
+    pub(super) fn excluded(&self) -> &[String] {
+        &self.excluded
+    }
+
+    pub(super) fn matches(&self, title: &str, body: &str) -> bool {
+        let text = format!("{}\n{}", title.to_lowercase(), body.to_lowercase());
+        self.required
+            .iter()
+            .all(|term| text.contains(term.as_str()))
+            && !self
+                .excluded
+                .iter()
+                .any(|term| text.contains(term.as_str()))
+    }
+
+    /// HTML-escaped excerpt of `body` starting shortly before the first
+    /// matching word, with matching words wrapped in `<mark>`.
+    pub(super) fn headline(&self, body: &str) -> String {
+        let words: Vec<&str> = body.split_whitespace().collect();
+        let terms: Vec<&str> = self
+            .required
+            .iter()
+            .flat_map(|term| term.split(' '))
+            .collect();
+        let hit = |word: &str| {
+            let word = word.to_lowercase();
+            terms.iter().any(|term| word.contains(term))
+        };
+        let start = words
+            .iter()
+            .position(|word| hit(word))
+            .map_or(0, |first| first.saturating_sub(LEAD_WORDS));
+        words
+            .iter()
+            .skip(start)
+            .take(EXCERPT_WORDS)
+            .map(|word| {
+                let escaped = escape_html(word);
+                if hit(word) {
+                    format!("<mark>{escaped}</mark>")
+                } else {
+                    escaped
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WebSearch;
+
+    #[test]
+    fn phrases_and_negation_narrow_matches() {
+        let search = WebSearch::parse(r#""fish and chips" -vinegar or salt"#).unwrap();
+        assert!(search.matches("Lunch", "Fish and chips with salt"));
+        assert!(!search.matches("Lunch", "Fish and chips with salt and vinegar"));
+        assert!(!search.matches("Lunch", "Chips and fish with salt"));
+        assert!(WebSearch::parse(r#" "" - or "#).is_none());
+    }
+
+    #[test]
+    fn headlines_escape_the_body_and_mark_matches() {
+        let search = WebSearch::parse("chips").unwrap();
+        assert_eq!(
+            search.headline("<b>Fish</b> & Chips, hot"),
+            "&lt;b&gt;Fish&lt;/b&gt; &amp; <mark>Chips,</mark> hot"
+        );
+    }
+}
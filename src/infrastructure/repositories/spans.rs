@@ -0,0 +1,13 @@
+// src/infrastructure/repositories/spans.rs
+use crate::async_support::BoxFuture;
+use tracing::Instrument as _;
+
+/// Box a storage call inside a debug span named after it, e.g.
+/// `PostgresUserRepository::find_by_id`, so exported traces show where a
+/// request spent its time.
+pub fn traced<'a, F, T>(operation: &'static str, future: F) -> BoxFuture<'a, T>
+where
+    F: Future<Output = T> + Send + 'a,
+{
+    Box::pin(future.instrument(tracing::debug_span!("repository", otel.name = operation)))
+}
@@ -0,0 +1,898 @@
+// src/infrastructure/repositories/sqlite/articles.rs
+use super::super::search::WebSearch;
+use super::{count, json_ids, map_sqlite};
+use crate::async_support::BoxFuture;
+use crate::domain::PAGE_LIMIT_CEILING;
+use crate::domain::article::repository::ArticleQuery;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{
+    Article, ArticleBody, ArticleId, ArticleListCursor, ArticlePopularityCursor,
+    ArticleReadRepository, ArticleRevision, ArticleRevisionParts, ArticleRevisionRepository,
+    ArticleSearchHit, ArticleSlug, ArticleTitle, ArticleUpdate, ArticleWriteRepository,
+    CustomFieldDefinition, CustomFieldRepository, CustomFields, FacetCount, NewArticle,
+    PrivateScope, SearchFacets, Tag, UserId,
+};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, QueryBuilder, Sqlite, SqliteExecutor, SqlitePool};
+
+const ARTICLE_COLUMNS: &str = "id, title, slug, body, tags, custom_fields, published, published_at, visibility, author_id, created_at, updated_at, view_count";
+
+/// What the search matches against: title and body, lowercased like the
+/// terms [`WebSearch`] parses. `lower` only folds ASCII letters.
+const SEARCH_TEXT: &str = "lower(title || char(10) || body)";
+
+/// Articles and their revisions. Search parses queries like
+/// `websearch_to_tsquery` but matches substrings, newest first, as the
+/// in-memory store does.
+#[derive(Clone)]
+#[must_use]
+pub struct SqliteArticleRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteArticleRepository {
+    pub const fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct ArticleRow {
+    id: i64,
+    title: String,
+    slug: String,
+    body: String,
+    tags: String,
+    custom_fields: String,
+    published: bool,
+    published_at: Option<DateTime<Utc>>,
+    visibility: String,
+    author_id: i64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    view_count: i64,
+}
+
+fn corrupt(id: i64, what: &str, err: &serde_json::Error) -> DomainError {
+    DomainError::Persistence(format!("article {id} has invalid {what}: {err}"))
+}
+
+impl TryFrom<ArticleRow> for Article {
+    type Error = DomainError;
+
+    fn try_from(row: ArticleRow) -> Result<Self, Self::Error> {
+        let tags: Vec<String> =
+            serde_json::from_str(&row.tags).map_err(|err| corrupt(row.id, "tags", &err))?;
+        let custom_fields: CustomFields = serde_json::from_str(&row.custom_fields)
+            .map_err(|err| corrupt(row.id, "custom fields", &err))?;
+        Ok(Self {
+            id: ArticleId::new(row.id)?,
+            title: ArticleTitle::new(row.title)?,
+            slug: ArticleSlug::new(row.slug)?,
+            body: ArticleBody::new(row.body)?,
+            tags: tags
+                .into_iter()
+                .map(Tag::new)
+                .collect::<DomainResult<_>>()?,
+            custom_fields,
+            published: row.published,
+            published_at: row.published_at,
+            visibility: row.visibility.parse()?,
+            author_id: UserId::new(row.author_id)?,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            view_count: u64::try_from(row.view_count)
+                .map_err(|_| DomainError::Persistence("article view count is negative".into()))?,
+        })
+    }
+}
+
+fn articles_of(rows: Vec<ArticleRow>) -> DomainResult<Vec<Article>> {
+    rows.into_iter().map(Article::try_from).collect()
+}
+
+fn tags_json(tags: &[Tag]) -> String {
+    serde_json::Value::from(tags.iter().map(Tag::as_str).collect::<Vec<_>>()).to_string()
+}
+
+fn custom_fields_json(fields: &CustomFields) -> DomainResult<String> {
+    serde_json::to_string(fields)
+        .map_err(|err| DomainError::Persistence(format!("custom fields: {err}")))
+}
+
+/// `AND` conditions selecting listed articles; public listings only show
+/// public ones. The query must already have a `WHERE` clause.
+fn push_conditions(
+    builder: &mut QueryBuilder<'_, Sqlite>,
+    include_drafts: bool,
+    search: Option<&WebSearch>,
+    tag: Option<&str>,
+) {
+    if !include_drafts {
+        builder.push(" AND published = TRUE AND visibility = 'public'");
+    }
+    if let Some(search) = search {
+        for term in search.required() {
+            builder.push(format_args!(" AND instr({SEARCH_TEXT}, "));
+            builder.push_bind(term.clone());
+            builder.push(") > 0");
+        }
+        for term in search.excluded() {
+            builder.push(format_args!(" AND instr({SEARCH_TEXT}, "));
+            builder.push_bind(term.clone());
+            builder.push(") = 0");
+        }
+    }
+    if let Some(tag) = tag {
+        builder.push(" AND EXISTS (SELECT 1 FROM json_each(tags) WHERE value = ");
+        builder.push_bind(tag.to_string());
+        builder.push(")");
+    }
+}
+
+/// One count of the facets query: an author, a tag, or the `published` or
+/// `drafts` total.
+#[derive(Debug, FromRow)]
+struct FacetRow {
+    facet: String,
+    author_id: Option<i64>,
+    tag: Option<String>,
+    count: i64,
+}
+
+impl SqliteArticleRepository {
+    async fn find_one(&self, column: &str, value: String) -> DomainResult<Option<Article>> {
+        sqlx::query_as::<_, ArticleRow>(&format!(
+            "SELECT {ARTICLE_COLUMNS} FROM articles WHERE {column} = ?"
+        ))
+        .bind(value)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_sqlite)?
+        .map(Article::try_from)
+        .transpose()
+    }
+
+    async fn fetch_page(
+        &self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: Option<&ArticleListCursor>,
+        search: Option<&WebSearch>,
+        tag: Option<&str>,
+    ) -> DomainResult<(Vec<Article>, Option<ArticleListCursor>)> {
+        let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+
+        let mut builder: QueryBuilder<Sqlite> =
+            QueryBuilder::new(format!("SELECT {ARTICLE_COLUMNS} FROM articles WHERE TRUE"));
+        push_conditions(&mut builder, include_drafts, search, tag);
+        if let Some(cursor) = cursor {
+            builder.push(" AND (created_at, id) < (");
+            builder.push_bind(cursor.created_at);
+            builder.push(", ");
+            builder.push_bind(i64::from(cursor.article_id));
+            builder.push(")");
+        }
+        builder.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+        builder.push_bind(i64::from(limit) + 1);
+
+        let rows = builder
+            .build_query_as::<ArticleRow>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?;
+        let mut articles = articles_of(rows)?;
+
+        let mut next_cursor = None;
+        if articles.len() > limit as usize {
+            articles.pop();
+            if let Some(last) = articles.last() {
+                next_cursor = Some(ArticleListCursor::from_parts(last.created_at, last.id));
+            }
+        }
+
+        Ok((articles, next_cursor))
+    }
+
+    async fn count_facets(
+        &self,
+        include_drafts: bool,
+        private: PrivateScope,
+        search: Option<&WebSearch>,
+        tag: Option<&str>,
+    ) -> DomainResult<SearchFacets> {
+        let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+            "WITH matched AS (SELECT author_id, published, tags FROM articles WHERE TRUE",
+        );
+        push_conditions(&mut builder, include_drafts, search, tag);
+        match private {
+            PrivateScope::Excluded => {
+                builder.push(" AND visibility <> 'private'");
+            }
+            PrivateScope::AuthoredBy(user_id) => {
+                builder.push(" AND (visibility <> 'private' OR author_id = ");
+                builder.push_bind(i64::from(user_id));
+                builder.push(")");
+            }
+            PrivateScope::All => {}
+        }
+        builder.push(
+            ") SELECT 'published' AS facet, NULL AS author_id, NULL AS tag, \
+             COUNT(*) FILTER (WHERE published) AS count FROM matched \
+             UNION ALL SELECT 'drafts', NULL, NULL, COUNT(*) FILTER (WHERE NOT published) FROM matched \
+             UNION ALL SELECT 'author', author_id, NULL, COUNT(*) FROM matched GROUP BY author_id \
+             UNION ALL SELECT 'tag', NULL, tag.value, COUNT(*) FROM matched, json_each(matched.tags) AS tag \
+             GROUP BY tag.value",
+        );
+
+        let rows = builder
+            .build_query_as::<FacetRow>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?;
+
+        let (mut published, mut drafts) = (0, 0);
+        let mut authors = Vec::new();
+        let mut tags = Vec::new();
+        for row in rows {
+            let total = count(row.count);
+            match (row.facet.as_str(), row.author_id, row.tag) {
+                ("published", _, _) => published = total,
+                ("drafts", _, _) => drafts = total,
+                ("author", Some(author_id), _) => authors.push(FacetCount {
+                    value: UserId::new(author_id)?,
+                    count: total,
+                }),
+                ("tag", _, Some(tag)) => tags.push(FacetCount {
+                    value: Tag::new(tag)?,
+                    count: total,
+                }),
+                _ => {}
+            }
+        }
+
+        Ok(SearchFacets::new(published, drafts, authors, tags))
+    }
+}
+
+/// Insert `article` on `executor`.
+pub(super) async fn insert_article<'c>(
+    executor: impl SqliteExecutor<'c>,
+    article: NewArticle,
+) -> DomainResult<Article> {
+    let NewArticle {
+        title,
+        slug,
+        body,
+        tags,
+        custom_fields,
+        published,
+        published_at,
+        visibility,
+        author_id,
+        created_at,
+        updated_at,
+    } = article;
+
+    let row = sqlx::query_as::<_, ArticleRow>(&format!(
+        "INSERT INTO articles (title, slug, body, tags, custom_fields, published, published_at, visibility, author_id, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+         RETURNING {ARTICLE_COLUMNS}"
+    ))
+    .bind(title.as_str())
+    .bind(slug.as_str())
+    .bind(body.as_str())
+    .bind(tags_json(&tags))
+    .bind(custom_fields_json(&custom_fields)?)
+    .bind(published)
+    .bind(published_at)
+    .bind(visibility.as_str())
+    .bind(i64::from(author_id))
+    .bind(created_at)
+    .bind(updated_at)
+    .fetch_one(executor)
+    .await
+    .map_err(map_sqlite)?;
+
+    Article::try_from(row)
+}
+
+/// Apply `update` on `executor`, failing with a conflict if the article
+/// changed since `original_updated_at`.
+pub(super) async fn update_article<'c>(
+    executor: impl SqliteExecutor<'c>,
+    update: ArticleUpdate,
+) -> DomainResult<Article> {
+    let ArticleUpdate {
+        id,
+        title,
+        slug,
+        body,
+        tags,
+        custom_fields,
+        publish_state,
+        visibility,
+        original_updated_at,
+        updated_at,
+    } = update;
+
+    let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new("UPDATE articles SET updated_at = ");
+    builder.push_bind(updated_at);
+    if let Some(title) = title {
+        builder.push(", title = ");
+        builder.push_bind(String::from(title));
+    }
+    if let Some(slug) = slug {
+        builder.push(", slug = ");
+        builder.push_bind(String::from(slug));
+    }
+    if let Some(body) = body {
+        builder.push(", body = ");
+        builder.push_bind(String::from(body));
+    }
+    if let Some(tags) = tags {
+        builder.push(", tags = ");
+        builder.push_bind(tags_json(&tags));
+    }
+    if let Some(custom_fields) = custom_fields {
+        builder.push(", custom_fields = ");
+        builder.push_bind(custom_fields_json(&custom_fields)?);
+    }
+    if let Some(state) = publish_state {
+        builder.push(", published = ");
+        builder.push_bind(state.published);
+        builder.push(", published_at = ");
+        builder.push_bind(state.published_at);
+    }
+    if let Some(visibility) = visibility {
+        builder.push(", visibility = ");
+        builder.push_bind(visibility.as_str());
+    }
+    builder.push(" WHERE id = ");
+    builder.push_bind(i64::from(id));
+    builder.push(" AND updated_at = ");
+    builder.push_bind(original_updated_at);
+    builder.push(" RETURNING ");
+    builder.push(ARTICLE_COLUMNS);
+
+    let row = builder
+        .build_query_as::<ArticleRow>()
+        .fetch_optional(executor)
+        .await
+        .map_err(map_sqlite)?
+        .ok_or_else(|| DomainError::Conflict("article update conflict, please retry".into()))?;
+
+    Article::try_from(row)
+}
+
+impl ArticleWriteRepository for SqliteArticleRepository {
+    fn insert(&self, article: NewArticle) -> BoxFuture<'_, DomainResult<Article>> {
+        traced(
+            "SqliteArticleRepository::insert",
+            insert_article(&self.pool, article),
+        )
+    }
+
+    fn update(&self, update: ArticleUpdate) -> BoxFuture<'_, DomainResult<Article>> {
+        traced(
+            "SqliteArticleRepository::update",
+            update_article(&self.pool, update),
+        )
+    }
+
+    fn delete(&self, id: ArticleId) -> BoxFuture<'_, DomainResult<()>> {
+        traced("SqliteArticleRepository::delete", async move {
+            let result = sqlx::query("DELETE FROM articles WHERE id = ?")
+                .bind(i64::from(id))
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlite)?;
+            if result.rows_affected() == 0 {
+                return Err(DomainError::NotFound("article not found".into()));
+            }
+            Ok(())
+        })
+    }
+
+    fn add_views<'a>(&'a self, views: &'a [(ArticleId, u64)]) -> BoxFuture<'a, DomainResult<()>> {
+        traced("SqliteArticleRepository::add_views", async move {
+            let mut tx = self.pool.begin().await.map_err(map_sqlite)?;
+            for (id, views) in views {
+                sqlx::query("UPDATE articles SET view_count = view_count + ? WHERE id = ?")
+                    .bind(i64::try_from(*views).unwrap_or(i64::MAX))
+                    .bind(i64::from(*id))
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(map_sqlite)?;
+            }
+            tx.commit().await.map_err(map_sqlite)
+        })
+    }
+
+    fn reassign_author(&self, from: UserId, to: UserId) -> BoxFuture<'_, DomainResult<u64>> {
+        traced("SqliteArticleRepository::reassign_author", async move {
+            let result = sqlx::query("UPDATE articles SET author_id = ? WHERE author_id = ?")
+                .bind(i64::from(to))
+                .bind(i64::from(from))
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlite)?;
+            Ok(result.rows_affected())
+        })
+    }
+}
+
+impl ArticleReadRepository for SqliteArticleRepository {
+    fn find_by_id(&self, id: ArticleId) -> BoxFuture<'_, DomainResult<Option<Article>>> {
+        traced("SqliteArticleRepository::find_by_id", async move {
+            self.find_one("id", i64::from(id).to_string()).await
+        })
+    }
+
+    fn find_by_slug<'a>(
+        &'a self,
+        slug: &'a ArticleSlug,
+    ) -> BoxFuture<'a, DomainResult<Option<Article>>> {
+        // `slug` is `COLLATE NOCASE`, like the Postgres `CITEXT` column.
+        traced("SqliteArticleRepository::find_by_slug", async move {
+            self.find_one("slug", slug.as_str().to_string()).await
+        })
+    }
+
+    fn find_by_ids<'a>(
+        &'a self,
+        ids: &'a [ArticleId],
+    ) -> BoxFuture<'a, DomainResult<Vec<Article>>> {
+        traced("SqliteArticleRepository::find_by_ids", async move {
+            let rows = sqlx::query_as::<_, ArticleRow>(&format!(
+                "SELECT {ARTICLE_COLUMNS} FROM articles WHERE id IN (SELECT value FROM json_each(?))"
+            ))
+            .bind(json_ids(ids.iter().copied().map(i64::from)))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?;
+            articles_of(rows)
+        })
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: Option<ArticleListCursor>,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        traced("SqliteArticleRepository::list_page", async move {
+            let search = search.and_then(WebSearch::parse);
+            self.fetch_page(
+                include_drafts,
+                limit,
+                cursor.as_ref(),
+                search.as_ref(),
+                None,
+            )
+            .await
+        })
+    }
+
+    fn list(
+        &self,
+        query: ArticleQuery,
+    ) -> BoxFuture<'_, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        traced("SqliteArticleRepository::list", async move {
+            let search = query.search.as_deref().and_then(WebSearch::parse);
+            self.fetch_page(
+                query.include_drafts,
+                query.limit,
+                query.cursor.as_ref(),
+                search.as_ref(),
+                query.tag.as_ref().map(Tag::as_str),
+            )
+            .await
+        })
+    }
+
+    fn search(
+        &self,
+        query: ArticleQuery,
+    ) -> BoxFuture<'_, DomainResult<(Vec<ArticleSearchHit>, Option<ArticleListCursor>)>> {
+        traced("SqliteArticleRepository::search", async move {
+            let search = query.search.as_deref().and_then(WebSearch::parse);
+            let (articles, next_cursor) = self
+                .fetch_page(
+                    query.include_drafts,
+                    query.limit,
+                    query.cursor.as_ref(),
+                    search.as_ref(),
+                    query.tag.as_ref().map(Tag::as_str),
+                )
+                .await?;
+            let hits = articles
+                .into_iter()
+                .map(|article| ArticleSearchHit {
+                    headline: search
+                        .as_ref()
+                        .map(|search| search.headline(article.body.as_str())),
+                    article,
+                })
+                .collect();
+            Ok((hits, next_cursor))
+        })
+    }
+
+    fn search_facets<'a>(
+        &'a self,
+        include_drafts: bool,
+        private: PrivateScope,
+        search: Option<&'a str>,
+        tag: Option<&'a Tag>,
+    ) -> BoxFuture<'a, DomainResult<Option<SearchFacets>>> {
+        traced("SqliteArticleRepository::search_facets", async move {
+            let search = search.and_then(WebSearch::parse);
+            self.count_facets(
+                include_drafts,
+                private,
+                search.as_ref(),
+                tag.map(Tag::as_str),
+            )
+            .await
+            .map(Some)
+        })
+    }
+
+    fn list_page_before(
+        &self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: ArticleListCursor,
+    ) -> BoxFuture<'_, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        traced("SqliteArticleRepository::list_page_before", async move {
+            let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+
+            let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+                "SELECT {ARTICLE_COLUMNS} FROM articles WHERE (created_at, id) > ("
+            ));
+            builder.push_bind(cursor.created_at);
+            builder.push(", ");
+            builder.push_bind(i64::from(cursor.article_id));
+            builder.push(")");
+            push_conditions(&mut builder, include_drafts, None, None);
+            builder.push(" ORDER BY created_at ASC, id ASC LIMIT ");
+            builder.push_bind(i64::from(limit) + 1);
+
+            let rows = builder
+                .build_query_as::<ArticleRow>()
+                .fetch_all(&self.pool)
+                .await
+                .map_err(map_sqlite)?;
+            let mut articles = articles_of(rows)?;
+
+            let mut prev_cursor = None;
+            if articles.len() > limit as usize {
+                articles.pop();
+                if let Some(newest) = articles.last() {
+                    prev_cursor = Some(ArticleListCursor::from_parts(newest.created_at, newest.id));
+                }
+            }
+            articles.reverse();
+
+            Ok((articles, prev_cursor))
+        })
+    }
+
+    fn has_preceding<'a>(
+        &'a self,
+        include_drafts: bool,
+        cursor: &'a ArticleListCursor,
+    ) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("SqliteArticleRepository::has_preceding", async move {
+            let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+                "SELECT EXISTS(SELECT 1 FROM articles WHERE (created_at, id) > (",
+            );
+            builder.push_bind(cursor.created_at);
+            builder.push(", ");
+            builder.push_bind(i64::from(cursor.article_id));
+            builder.push(")");
+            push_conditions(&mut builder, include_drafts, None, None);
+            builder.push(")");
+
+            builder
+                .build_query_scalar::<bool>()
+                .fetch_one(&self.pool)
+                .await
+                .map_err(map_sqlite)
+        })
+    }
+
+    fn list_popular<'a>(
+        &'a self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: Option<ArticlePopularityCursor>,
+        tag: Option<&'a Tag>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, Option<ArticlePopularityCursor>)>> {
+        traced("SqliteArticleRepository::list_popular", async move {
+            let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+
+            let mut builder: QueryBuilder<Sqlite> =
+                QueryBuilder::new(format!("SELECT {ARTICLE_COLUMNS} FROM articles WHERE TRUE"));
+            push_conditions(&mut builder, include_drafts, None, tag.map(Tag::as_str));
+            if let Some(cursor) = cursor {
+                builder.push(" AND (view_count, id) < (");
+                builder.push_bind(i64::try_from(cursor.view_count).unwrap_or(i64::MAX));
+                builder.push(", ");
+                builder.push_bind(i64::from(cursor.article_id));
+                builder.push(")");
+            }
+            builder.push(" ORDER BY view_count DESC, id DESC LIMIT ");
+            builder.push_bind(i64::from(limit) + 1);
+
+            let rows = builder
+                .build_query_as::<ArticleRow>()
+                .fetch_all(&self.pool)
+                .await
+                .map_err(map_sqlite)?;
+            let mut articles = articles_of(rows)?;
+
+            let mut next_cursor = None;
+            if articles.len() > limit as usize {
+                articles.pop();
+                if let Some(last) = articles.last() {
+                    next_cursor = Some(ArticlePopularityCursor::new(last.view_count, last.id));
+                }
+            }
+
+            Ok((articles, next_cursor))
+        })
+    }
+
+    fn list_offset<'a>(
+        &'a self,
+        include_drafts: bool,
+        offset: u64,
+        limit: u32,
+        tag: Option<&'a Tag>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, u64)>> {
+        traced("SqliteArticleRepository::list_offset", async move {
+            let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+            let tag = tag.map(Tag::as_str);
+
+            let mut page: QueryBuilder<Sqlite> =
+                QueryBuilder::new(format!("SELECT {ARTICLE_COLUMNS} FROM articles WHERE TRUE"));
+            push_conditions(&mut page, include_drafts, None, tag);
+            page.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+            page.push_bind(i64::from(limit));
+            page.push(" OFFSET ");
+            page.push_bind(i64::try_from(offset).unwrap_or(i64::MAX));
+
+            let mut total: QueryBuilder<Sqlite> =
+                QueryBuilder::new("SELECT COUNT(1) FROM articles WHERE TRUE");
+            push_conditions(&mut total, include_drafts, None, tag);
+
+            // One read transaction, so the count matches the page.
+            let mut tx = self.pool.begin().await.map_err(map_sqlite)?;
+            let rows = page
+                .build_query_as::<ArticleRow>()
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(map_sqlite)?;
+            let total = total
+                .build_query_scalar::<i64>()
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(map_sqlite)?;
+            tx.commit().await.map_err(map_sqlite)?;
+
+            Ok((articles_of(rows)?, count(total)))
+        })
+    }
+
+    fn estimate_total(&self, include_drafts: bool) -> BoxFuture<'_, DomainResult<Option<u64>>> {
+        traced("SqliteArticleRepository::estimate_total", async move {
+            let sql = if include_drafts {
+                "SELECT COUNT(1) FROM articles"
+            } else {
+                "SELECT COUNT(1) FROM articles WHERE visibility = 'public' AND published = TRUE"
+            };
+            let total = sqlx::query_scalar::<_, i64>(sql)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(map_sqlite)?;
+            Ok(Some(count(total)))
+        })
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct RevisionRow {
+    article_id: i64,
+    version: i32,
+    title: String,
+    slug: String,
+    body: String,
+    published: bool,
+    published_at: Option<DateTime<Utc>>,
+    author_id: i64,
+    edited_by: Option<i64>,
+    recorded_at: DateTime<Utc>,
+}
+
+impl TryFrom<RevisionRow> for ArticleRevision {
+    type Error = DomainError;
+
+    fn try_from(row: RevisionRow) -> Result<Self, Self::Error> {
+        Ok(ArticleRevisionParts {
+            article_id: ArticleId::new(row.article_id)?,
+            version: row.version,
+            title: ArticleTitle::new(row.title)?,
+            slug: ArticleSlug::new(row.slug)?,
+            body: ArticleBody::new(row.body)?,
+            published: row.published,
+            published_at: row.published_at,
+            author_id: UserId::new(row.author_id)?,
+            edited_by: row.edited_by.map(UserId::new).transpose()?,
+            recorded_at: row.recorded_at,
+        }
+        .into())
+    }
+}
+
+const REVISION_COLUMNS: &str = "article_id, version, title, slug, body, published, published_at, author_id, edited_by, recorded_at";
+
+/// Record `article` on `executor` as its next revision.
+pub(super) async fn append_revision<'c>(
+    executor: impl SqliteExecutor<'c>,
+    article: &Article,
+    edited_by: Option<UserId>,
+) -> DomainResult<()> {
+    sqlx::query(
+        "INSERT INTO article_revisions (
+             article_id, version, title, slug, body, published, published_at,
+             author_id, edited_by, recorded_at
+         )
+         SELECT ?1, COALESCE(MAX(version) + 1, 1), ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9
+         FROM article_revisions WHERE article_id = ?1",
+    )
+    .bind(i64::from(article.id))
+    .bind(article.title.as_str())
+    .bind(article.slug.as_str())
+    .bind(article.body.as_str())
+    .bind(article.published)
+    .bind(article.published_at)
+    .bind(i64::from(article.author_id))
+    .bind(edited_by.map(i64::from))
+    .bind(Utc::now())
+    .execute(executor)
+    .await
+    .map_err(map_sqlite)?;
+    Ok(())
+}
+
+impl ArticleRevisionRepository for SqliteArticleRepository {
+    fn append<'a>(
+        &'a self,
+        article: &'a Article,
+        edited_by: Option<UserId>,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        traced(
+            "SqliteArticleRepository::append",
+            append_revision(&self.pool, article, edited_by),
+        )
+    }
+
+    fn list_by_article(
+        &self,
+        article_id: ArticleId,
+    ) -> BoxFuture<'_, DomainResult<Vec<ArticleRevision>>> {
+        traced("SqliteArticleRepository::list_by_article", async move {
+            sqlx::query_as::<_, RevisionRow>(&format!(
+                "SELECT {REVISION_COLUMNS} FROM article_revisions
+                 WHERE article_id = ? ORDER BY version DESC"
+            ))
+            .bind(i64::from(article_id))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .into_iter()
+            .map(ArticleRevision::try_from)
+            .collect()
+        })
+    }
+
+    fn find_revision(
+        &self,
+        article_id: ArticleId,
+        version: i32,
+    ) -> BoxFuture<'_, DomainResult<Option<ArticleRevision>>> {
+        traced("SqliteArticleRepository::find_revision", async move {
+            sqlx::query_as::<_, RevisionRow>(&format!(
+                "SELECT {REVISION_COLUMNS} FROM article_revisions
+                 WHERE article_id = ? AND version = ?"
+            ))
+            .bind(i64::from(article_id))
+            .bind(version)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .map(ArticleRevision::try_from)
+            .transpose()
+        })
+    }
+}
+
+#[derive(Clone)]
+#[must_use]
+pub struct SqliteCustomFieldRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteCustomFieldRepository {
+    pub const fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct DefinitionRow {
+    name: String,
+    field_type: String,
+    required: bool,
+}
+
+impl TryFrom<DefinitionRow> for CustomFieldDefinition {
+    type Error = DomainError;
+
+    fn try_from(row: DefinitionRow) -> Result<Self, Self::Error> {
+        Self::new(row.name, row.field_type.parse()?, row.required)
+    }
+}
+
+impl CustomFieldRepository for SqliteCustomFieldRepository {
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<CustomFieldDefinition>>> {
+        traced("SqliteCustomFieldRepository::list", async move {
+            sqlx::query_as::<_, DefinitionRow>(
+                "SELECT name, field_type, required FROM custom_field_definitions ORDER BY name",
+            )
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .into_iter()
+            .map(CustomFieldDefinition::try_from)
+            .collect()
+        })
+    }
+
+    fn upsert(
+        &self,
+        definition: CustomFieldDefinition,
+    ) -> BoxFuture<'_, DomainResult<CustomFieldDefinition>> {
+        traced("SqliteCustomFieldRepository::upsert", async move {
+            sqlx::query_as::<_, DefinitionRow>(
+                "INSERT INTO custom_field_definitions (name, field_type, required)
+                 VALUES (?, ?, ?)
+                 ON CONFLICT (name) DO UPDATE
+                 SET field_type = excluded.field_type, required = excluded.required
+                 RETURNING name, field_type, required",
+            )
+            .bind(&definition.name)
+            .bind(definition.field_type.as_str())
+            .bind(definition.required)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .try_into()
+        })
+    }
+
+    fn delete<'a>(&'a self, name: &'a str) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("SqliteCustomFieldRepository::delete", async move {
+            let result = sqlx::query("DELETE FROM custom_field_definitions WHERE name = ?")
+                .bind(name)
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlite)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+}
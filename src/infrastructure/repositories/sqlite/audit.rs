@@ -0,0 +1,223 @@
+// src/infrastructure/repositories/sqlite/audit.rs
+use super::map_sqlite;
+use crate::async_support::BoxFuture;
+use crate::domain::audit::cursor::AuditLogCursor;
+use crate::domain::audit::entity::{AuditLog, NewAuditLog};
+use crate::domain::audit::filter::AuditLogFilter;
+use crate::domain::audit::repository::AuditLogRepository;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::user::value_objects::UserId;
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, QueryBuilder, Sqlite, SqlitePool};
+
+const QUERY_SELECT: &str = "SELECT id, user_id, impersonator_id, action, resource_type, resource_id, details, ip_address, user_agent, created_at FROM audit_logs WHERE TRUE";
+
+/// Audit logs with `details` stored as JSON text. `created_at` is stamped
+/// here rather than by a column default, so it keeps the text format the
+/// cursors compare against.
+#[derive(Clone)]
+#[must_use]
+pub struct SqliteAuditLogRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteAuditLogRepository {
+    pub const fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Finish a query started from [`QUERY_SELECT`] with the cursor, order
+    /// and one extra row, then trim it to a page.
+    async fn page(
+        &self,
+        mut builder: QueryBuilder<'_, Sqlite>,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)> {
+        if let Some(c) = cursor {
+            builder
+                .push(" AND (created_at, id) < (")
+                .push_bind(c.created_at)
+                .push(", ")
+                .push_bind(c.id)
+                .push(")");
+        }
+        builder
+            .push(" ORDER BY created_at DESC, id DESC LIMIT ")
+            .push_bind(i64::from(limit) + 1);
+
+        let mut items = builder
+            .build_query_as::<LogRow>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .into_iter()
+            .map(AuditLog::try_from)
+            .collect::<DomainResult<Vec<_>>>()?;
+        let next_cursor = AuditLogCursor::trim_page(&mut items, limit);
+
+        Ok((items, next_cursor))
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct LogRow {
+    id: i64,
+    user_id: Option<i64>,
+    impersonator_id: Option<i64>,
+    action: String,
+    resource_type: String,
+    resource_id: Option<i64>,
+    details: Option<String>,
+    ip_address: Option<String>,
+    user_agent: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<LogRow> for AuditLog {
+    type Error = DomainError;
+
+    fn try_from(row: LogRow) -> Result<Self, Self::Error> {
+        let details = row
+            .details
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|err| {
+                DomainError::Persistence(format!("audit log {} has invalid details: {err}", row.id))
+            })?;
+        Ok(Self {
+            id: row.id,
+            user_id: row.user_id.and_then(|id| UserId::new(id).ok()),
+            impersonator_id: row.impersonator_id.and_then(|id| UserId::new(id).ok()),
+            action: row.action,
+            resource_type: row.resource_type,
+            resource_id: row.resource_id,
+            details,
+            ip_address: row.ip_address,
+            user_agent: row.user_agent,
+            created_at: row.created_at,
+        })
+    }
+}
+
+impl AuditLogRepository for SqliteAuditLogRepository {
+    fn insert(&self, log: NewAuditLog) -> BoxFuture<'_, DomainResult<()>> {
+        self.insert_batch(vec![log])
+    }
+
+    fn insert_batch(&self, logs: Vec<NewAuditLog>) -> BoxFuture<'_, DomainResult<()>> {
+        traced("SqliteAuditLogRepository::insert_batch", async move {
+            if logs.is_empty() {
+                return Ok(());
+            }
+            let now = Utc::now();
+            let mut builder: QueryBuilder<'_, Sqlite> = QueryBuilder::new(
+                "INSERT INTO audit_logs (user_id, impersonator_id, action, resource_type, resource_id, details, ip_address, user_agent, created_at) ",
+            );
+            builder.push_values(logs, |mut row, log| {
+                row.push_bind(log.user_id.map(i64::from))
+                    .push_bind(log.impersonator_id.map(i64::from))
+                    .push_bind(log.action)
+                    .push_bind(log.resource_type)
+                    .push_bind(log.resource_id)
+                    .push_bind(log.details.map(|details| details.to_string()))
+                    .push_bind(log.ip_address)
+                    .push_bind(log.user_agent)
+                    .push_bind(now);
+            });
+            builder
+                .build()
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlite)?;
+
+            Ok(())
+        })
+    }
+
+    fn list(
+        &self,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        traced("SqliteAuditLogRepository::list", async move {
+            self.page(QueryBuilder::new(QUERY_SELECT), limit, cursor)
+                .await
+        })
+    }
+
+    fn find_by_user(
+        &self,
+        user_id: i64,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'_, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        traced("SqliteAuditLogRepository::find_by_user", async move {
+            let mut builder = QueryBuilder::new(QUERY_SELECT);
+            builder.push(" AND user_id = ").push_bind(user_id);
+            self.page(builder, limit, cursor).await
+        })
+    }
+
+    fn find_by_resource<'a>(
+        &'a self,
+        resource_type: &'a str,
+        resource_id: i64,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        traced("SqliteAuditLogRepository::find_by_resource", async move {
+            let mut builder = QueryBuilder::new(QUERY_SELECT);
+            builder
+                .push(" AND resource_type = ")
+                .push_bind(resource_type)
+                .push(" AND resource_id = ")
+                .push_bind(resource_id);
+            self.page(builder, limit, cursor).await
+        })
+    }
+
+    fn find_by_action<'a>(
+        &'a self,
+        action: &'a str,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        traced("SqliteAuditLogRepository::find_by_action", async move {
+            let mut builder = QueryBuilder::new(QUERY_SELECT);
+            builder.push(" AND action = ").push_bind(action);
+            self.page(builder, limit, cursor).await
+        })
+    }
+
+    fn search<'a>(
+        &'a self,
+        filter: &'a AuditLogFilter,
+        limit: u32,
+        cursor: Option<AuditLogCursor>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<AuditLog>, Option<AuditLogCursor>)>> {
+        traced("SqliteAuditLogRepository::search", async move {
+            let mut builder = QueryBuilder::new(QUERY_SELECT);
+            if let Some(user_id) = filter.involving_user {
+                builder
+                    .push(" AND (user_id = ")
+                    .push_bind(user_id)
+                    .push(" OR (resource_type = 'user' AND resource_id = ")
+                    .push_bind(user_id)
+                    .push("))");
+            }
+            if !filter.actions.is_empty() {
+                builder
+                    .push(" AND action IN (SELECT value FROM json_each(")
+                    .push_bind(serde_json::Value::from(filter.actions.clone()).to_string())
+                    .push("))");
+            }
+            if let Some(since) = filter.since {
+                builder.push(" AND created_at >= ").push_bind(since);
+            }
+            self.page(builder, limit, cursor).await
+        })
+    }
+}
@@ -0,0 +1,173 @@
+// src/infrastructure/repositories/sqlite/contributors.rs
+use super::map_sqlite;
+use crate::async_support::BoxFuture;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{ArticleContributor, ArticleId, ContributorRepository, UserId};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+
+#[derive(Clone)]
+#[must_use]
+pub struct SqliteContributorRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteContributorRepository {
+    pub const fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct ContributorRow {
+    article_id: i64,
+    user_id: i64,
+    added_by: Option<i64>,
+    added_at: DateTime<Utc>,
+}
+
+impl TryFrom<ContributorRow> for ArticleContributor {
+    type Error = DomainError;
+
+    fn try_from(row: ContributorRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            article_id: ArticleId::new(row.article_id)?,
+            user_id: UserId::new(row.user_id)?,
+            added_by: row.added_by.map(UserId::new).transpose()?,
+            added_at: row.added_at,
+        })
+    }
+}
+
+impl ContributorRepository for SqliteContributorRepository {
+    fn add(
+        &self,
+        contributor: ArticleContributor,
+    ) -> BoxFuture<'_, DomainResult<ArticleContributor>> {
+        traced("SqliteContributorRepository::add", async move {
+            // The no-op update makes RETURNING yield the stored row on conflict.
+            let row = sqlx::query_as::<_, ContributorRow>(
+                "INSERT INTO article_contributors (article_id, user_id, added_by, added_at)
+                 VALUES (?, ?, ?, ?)
+                 ON CONFLICT (article_id, user_id)
+                 DO UPDATE SET added_at = article_contributors.added_at
+                 RETURNING article_id, user_id, added_by, added_at",
+            )
+            .bind(i64::from(contributor.article_id))
+            .bind(i64::from(contributor.user_id))
+            .bind(contributor.added_by.map(i64::from))
+            .bind(contributor.added_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlite)?;
+            row.try_into()
+        })
+    }
+
+    fn remove(&self, article_id: ArticleId, user_id: UserId) -> BoxFuture<'_, DomainResult<bool>> {
+        traced("SqliteContributorRepository::remove", async move {
+            let result = sqlx::query(
+                "DELETE FROM article_contributors WHERE article_id = ? AND user_id = ?",
+            )
+            .bind(i64::from(article_id))
+            .bind(i64::from(user_id))
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlite)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn list(&self, article_id: ArticleId) -> BoxFuture<'_, DomainResult<Vec<ArticleContributor>>> {
+        traced("SqliteContributorRepository::list", async move {
+            sqlx::query_as::<_, ContributorRow>(
+                "SELECT article_id, user_id, added_by, added_at FROM article_contributors
+                 WHERE article_id = ? ORDER BY added_at, user_id",
+            )
+            .bind(i64::from(article_id))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .into_iter()
+            .map(ArticleContributor::try_from)
+            .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::pool;
+    use super::super::{SqliteArticleRepository, SqliteUserRepository};
+    use super::*;
+    use crate::domain::{
+        ArticleBody, ArticleSlug, ArticleTitle, ArticleVisibility, ArticleWriteRepository,
+        CustomFields, NewArticle, NewUser, PasswordHash, Role, UserRepository, Username,
+    };
+
+    #[tokio::test]
+    async fn contributors_are_added_once_and_need_an_existing_user() {
+        let pool = pool().await;
+        let store = SqliteContributorRepository::new(pool.clone());
+        let users = SqliteUserRepository::new(pool.clone());
+        let mut ids = Vec::new();
+        for name in ["alice", "bob"] {
+            let user = users
+                .insert(
+                    NewUser::new(
+                        Username::new(name).unwrap(),
+                        PasswordHash::new("$argon2id$v=19$m=19456,t=2,p=1$c2FsdA$aGFzaA").unwrap(),
+                        Role::Author,
+                        Utc::now(),
+                    )
+                    .unwrap(),
+                )
+                .await
+                .unwrap();
+            ids.push(user.id);
+        }
+        let now = Utc::now();
+        let article = SqliteArticleRepository::new(pool)
+            .insert(NewArticle {
+                title: ArticleTitle::new("Shared").unwrap(),
+                slug: ArticleSlug::new("shared").unwrap(),
+                body: ArticleBody::new("Written together").unwrap(),
+                tags: Vec::new(),
+                custom_fields: CustomFields::default(),
+                published: false,
+                published_at: None,
+                visibility: ArticleVisibility::Public,
+                author_id: ids[0],
+                created_at: now,
+                updated_at: now,
+            })
+            .await
+            .unwrap();
+
+        let contributor = ArticleContributor {
+            article_id: article.id,
+            user_id: ids[1],
+            added_by: Some(ids[0]),
+            added_at: now,
+        };
+        let added = store.add(contributor.clone()).await.unwrap();
+        let again = ArticleContributor {
+            added_at: now + chrono::Duration::minutes(5),
+            ..contributor.clone()
+        };
+        assert_eq!(store.add(again).await.unwrap().added_at, added.added_at);
+        assert_eq!(store.list(article.id).await.unwrap(), [added]);
+
+        let stranger = ArticleContributor {
+            user_id: UserId::new(99).unwrap(),
+            ..contributor
+        };
+        let err = store.add(stranger).await.unwrap_err();
+        assert!(matches!(err, DomainError::NotFound(_)), "{err}");
+
+        assert!(store.remove(article.id, ids[1]).await.unwrap());
+        assert!(!store.remove(article.id, ids[1]).await.unwrap());
+        assert!(store.list(article.id).await.unwrap().is_empty());
+    }
+}
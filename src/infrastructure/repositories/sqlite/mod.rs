@@ -0,0 +1,145 @@
+// src/infrastructure/repositories/sqlite/mod.rs
+//! Repositories backed by a single `SQLite` database.
+//!
+//! For embedded and demo instances run with `DATABASE_BACKEND=sqlite`. They
+//! implement every repository trait the Postgres ones do, with two
+//! differences: there is no row-level security, so the application's own
+//! checks are the only guard, and article search matches substrings the way
+//! the in-memory store does.
+mod access_rules;
+mod articles;
+mod audit;
+mod contributors;
+mod media;
+mod moderation;
+mod roles;
+mod syndication;
+mod unit_of_work;
+mod users;
+mod webhooks;
+
+use std::sync::Arc;
+
+use crate::domain::errors::DomainError;
+use sqlx::SqlitePool;
+use sqlx::error::ErrorKind;
+
+pub use access_rules::SqliteAccessRuleRepository;
+pub use articles::{SqliteArticleRepository, SqliteCustomFieldRepository};
+pub use audit::SqliteAuditLogRepository;
+pub use contributors::SqliteContributorRepository;
+pub use media::SqliteMediaRepository;
+pub use moderation::SqliteModerationRepository;
+pub use roles::SqliteRoleStore;
+pub use syndication::SqliteSyndicationOptOutStore;
+pub use unit_of_work::SqliteUnitOfWork;
+pub use users::{
+    SqliteAccountDeletions, SqliteBlockList, SqlitePasswordHistory, SqliteUserRepository,
+    SqliteUserTokenStore,
+};
+pub use webhooks::SqliteWebhookRepository;
+
+/// Every `SQLite` repository of one instance, sharing one pool.
+#[must_use]
+pub struct SqliteStores {
+    pub users: Arc<SqliteUserRepository>,
+    pub blocks: Arc<SqliteBlockList>,
+    pub password_history: Arc<SqlitePasswordHistory>,
+    pub account_deletions: Arc<SqliteAccountDeletions>,
+    pub roles: Arc<SqliteRoleStore>,
+    pub user_tokens: Arc<SqliteUserTokenStore>,
+    pub articles: Arc<SqliteArticleRepository>,
+    pub custom_fields: Arc<SqliteCustomFieldRepository>,
+    pub contributors: Arc<SqliteContributorRepository>,
+    pub syndication_opt_outs: Arc<SqliteSyndicationOptOutStore>,
+    pub moderation: Arc<SqliteModerationRepository>,
+    pub media: Arc<SqliteMediaRepository>,
+    pub access_rules: Arc<SqliteAccessRuleRepository>,
+    pub webhooks: Arc<SqliteWebhookRepository>,
+    pub audit_logs: Arc<SqliteAuditLogRepository>,
+    pub unit_of_work: Arc<SqliteUnitOfWork>,
+}
+
+impl SqliteStores {
+    pub fn new(pool: &SqlitePool) -> Self {
+        Self {
+            users: Arc::new(SqliteUserRepository::new(pool.clone())),
+            blocks: Arc::new(SqliteBlockList::new(pool.clone())),
+            password_history: Arc::new(SqlitePasswordHistory::new(pool.clone())),
+            account_deletions: Arc::new(SqliteAccountDeletions::new(pool.clone())),
+            roles: Arc::new(SqliteRoleStore::new(pool.clone())),
+            user_tokens: Arc::new(SqliteUserTokenStore::new(pool.clone())),
+            articles: Arc::new(SqliteArticleRepository::new(pool.clone())),
+            custom_fields: Arc::new(SqliteCustomFieldRepository::new(pool.clone())),
+            contributors: Arc::new(SqliteContributorRepository::new(pool.clone())),
+            syndication_opt_outs: Arc::new(SqliteSyndicationOptOutStore::new(pool.clone())),
+            moderation: Arc::new(SqliteModerationRepository::new(pool.clone())),
+            media: Arc::new(SqliteMediaRepository::new(pool.clone())),
+            access_rules: Arc::new(SqliteAccessRuleRepository::new(pool.clone())),
+            webhooks: Arc::new(SqliteWebhookRepository::new(pool.clone())),
+            audit_logs: Arc::new(SqliteAuditLogRepository::new(pool.clone())),
+            unit_of_work: Arc::new(SqliteUnitOfWork::new(pool.clone())),
+        }
+    }
+}
+
+/// The domain error [`map_sqlx`](super::map_sqlx) gives for the same
+/// constraint in Postgres. `SQLite` reports the columns of a failed unique
+/// constraint rather than its name, and never the conflicting value.
+fn map_sqlite(err: sqlx::Error) -> DomainError {
+    let sqlx::Error::Database(db_err) = err else {
+        return DomainError::Persistence(err.to_string());
+    };
+    let message = db_err.message();
+    match db_err.kind() {
+        ErrorKind::UniqueViolation => {
+            match message.trim_start_matches("UNIQUE constraint failed: ") {
+                "articles.slug" => DomainError::SlugTaken { slug: None },
+                "users.username" => DomainError::UsernameTaken { username: None },
+                "federated_identities.provider, federated_identities.subject" => {
+                    DomainError::Conflict("external account is already linked".into())
+                }
+                "federated_identities.user_id, federated_identities.provider" => {
+                    DomainError::Conflict(
+                        "user already has a linked account at this provider".into(),
+                    )
+                }
+                _ => DomainError::Conflict("unique constraint violated".into()),
+            }
+        }
+        ErrorKind::ForeignKeyViolation => {
+            DomainError::NotFound("referenced record not found".into())
+        }
+        ErrorKind::CheckViolation
+            if message.ends_with("articles_published_requires_timestamp_chk") =>
+        {
+            DomainError::Validation("published articles require published_at".into())
+        }
+        ErrorKind::CheckViolation => DomainError::Validation("check constraint violated".into()),
+        _ => DomainError::Persistence(message.to_string()),
+    }
+}
+
+/// `ids` as a JSON array, bound once and read back with `json_each` where
+/// Postgres would take an array parameter.
+fn json_ids(ids: impl IntoIterator<Item = i64>) -> String {
+    serde_json::Value::from(ids.into_iter().collect::<Vec<_>>()).to_string()
+}
+
+/// A `COUNT` as `u64`; counts are never negative.
+fn count(value: i64) -> u64 {
+    u64::try_from(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use crate::infrastructure::database::{init_sqlite_pool, run_sqlite_migrations};
+    use sqlx::SqlitePool;
+
+    /// A fresh, migrated in-memory database.
+    pub(super) async fn pool() -> SqlitePool {
+        let pool = init_sqlite_pool("sqlite::memory:").await.unwrap();
+        run_sqlite_migrations(&pool).await.unwrap();
+        pool
+    }
+}
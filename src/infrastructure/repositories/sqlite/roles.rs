@@ -0,0 +1,301 @@
+// src/infrastructure/repositories/sqlite/roles.rs
+use super::map_sqlite;
+use crate::async_support::BoxFuture;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{Capability, CustomRole, RoleName, RoleStore, UserId};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+
+const ROLE_COLUMNS: &str = "name, description, capabilities, created_at, updated_at";
+
+#[derive(Clone)]
+#[must_use]
+pub struct SqliteRoleStore {
+    pool: SqlitePool,
+}
+
+impl SqliteRoleStore {
+    pub const fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct RoleRow {
+    name: String,
+    description: Option<String>,
+    capabilities: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<RoleRow> for CustomRole {
+    type Error = DomainError;
+
+    fn try_from(row: RoleRow) -> DomainResult<Self> {
+        let capabilities: Vec<String> = serde_json::from_str(&row.capabilities).map_err(|err| {
+            DomainError::Persistence(format!("role {} has invalid capabilities: {err}", row.name))
+        })?;
+        Ok(Self {
+            name: RoleName::new(&row.name)?,
+            description: row.description,
+            capabilities: capabilities
+                .iter()
+                .map(|cap| cap.parse())
+                .collect::<DomainResult<_>>()?,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+fn capabilities_json(capabilities: &[Capability]) -> String {
+    serde_json::Value::from(
+        capabilities
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+    )
+    .to_string()
+}
+
+impl RoleStore for SqliteRoleStore {
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<CustomRole>>> {
+        traced("SqliteRoleStore::list", async move {
+            sqlx::query_as::<_, RoleRow>(&format!(
+                "SELECT {ROLE_COLUMNS} FROM custom_roles ORDER BY name"
+            ))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .into_iter()
+            .map(CustomRole::try_from)
+            .collect()
+        })
+    }
+
+    fn find<'a>(&'a self, name: &'a RoleName) -> BoxFuture<'a, DomainResult<Option<CustomRole>>> {
+        traced("SqliteRoleStore::find", async move {
+            sqlx::query_as::<_, RoleRow>(&format!(
+                "SELECT {ROLE_COLUMNS} FROM custom_roles WHERE name = ?"
+            ))
+            .bind(name.as_str())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .map(CustomRole::try_from)
+            .transpose()
+        })
+    }
+
+    fn save(&self, role: CustomRole) -> BoxFuture<'_, DomainResult<CustomRole>> {
+        traced("SqliteRoleStore::save", async move {
+            sqlx::query_as::<_, RoleRow>(&format!(
+                "INSERT INTO custom_roles ({ROLE_COLUMNS}) VALUES (?, ?, ?, ?, ?)
+                 ON CONFLICT (name) DO UPDATE SET description = excluded.description,
+                     capabilities = excluded.capabilities, updated_at = excluded.updated_at
+                 RETURNING {ROLE_COLUMNS}"
+            ))
+            .bind(role.name.as_str())
+            .bind(&role.description)
+            .bind(capabilities_json(&role.capabilities))
+            .bind(role.created_at)
+            .bind(role.updated_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .try_into()
+        })
+    }
+
+    fn delete<'a>(&'a self, name: &'a RoleName) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("SqliteRoleStore::delete", async move {
+            let result = sqlx::query("DELETE FROM custom_roles WHERE name = ?")
+                .bind(name.as_str())
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlite)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn assign<'a>(
+        &'a self,
+        user: UserId,
+        name: Option<&'a RoleName>,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        traced("SqliteRoleStore::assign", async move {
+            let query = name.map_or_else(
+                || {
+                    sqlx::query("DELETE FROM user_custom_roles WHERE user_id = ?")
+                        .bind(i64::from(user))
+                },
+                |name| {
+                    sqlx::query(
+                        "INSERT INTO user_custom_roles (user_id, role_name) VALUES (?, ?)
+                         ON CONFLICT (user_id) DO UPDATE SET role_name = excluded.role_name",
+                    )
+                    .bind(i64::from(user))
+                    .bind(name.as_str())
+                },
+            );
+            query.execute(&self.pool).await.map_err(map_sqlite)?;
+            Ok(())
+        })
+    }
+
+    fn role_of(&self, user: UserId) -> BoxFuture<'_, DomainResult<Option<CustomRole>>> {
+        traced("SqliteRoleStore::role_of", async move {
+            sqlx::query_as::<_, RoleRow>(
+                "SELECT r.name, r.description, r.capabilities, r.created_at, r.updated_at
+                 FROM custom_roles r JOIN user_custom_roles u ON u.role_name = r.name
+                 WHERE u.user_id = ?",
+            )
+            .bind(i64::from(user))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .map(CustomRole::try_from)
+            .transpose()
+        })
+    }
+
+    fn grant_capability<'a>(
+        &'a self,
+        user: UserId,
+        capability: &'a Capability,
+    ) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("SqliteRoleStore::grant_capability", async move {
+            let result = sqlx::query(
+                "INSERT INTO user_capabilities (user_id, capability) VALUES (?, ?)
+                 ON CONFLICT DO NOTHING",
+            )
+            .bind(i64::from(user))
+            .bind(capability.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlite)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn revoke_capability<'a>(
+        &'a self,
+        user: UserId,
+        capability: &'a Capability,
+    ) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("SqliteRoleStore::revoke_capability", async move {
+            let result =
+                sqlx::query("DELETE FROM user_capabilities WHERE user_id = ? AND capability = ?")
+                    .bind(i64::from(user))
+                    .bind(capability.to_string())
+                    .execute(&self.pool)
+                    .await
+                    .map_err(map_sqlite)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn capabilities_of(&self, user: UserId) -> BoxFuture<'_, DomainResult<Vec<Capability>>> {
+        traced("SqliteRoleStore::capabilities_of", async move {
+            sqlx::query_scalar::<_, String>(
+                "SELECT capability FROM user_capabilities WHERE user_id = ? ORDER BY capability",
+            )
+            .bind(i64::from(user))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .iter()
+            .map(|cap| cap.parse())
+            .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SqliteUserRepository;
+    use super::super::tests::pool;
+    use super::*;
+    use crate::domain::{NewUser, PasswordHash, Role, UserRepository, Username};
+
+    #[tokio::test]
+    async fn assignments_follow_the_role_and_go_with_it() {
+        let pool = pool().await;
+        let store = SqliteRoleStore::new(pool.clone());
+        let user = SqliteUserRepository::new(pool)
+            .insert(
+                NewUser::new(
+                    Username::new("alice").unwrap(),
+                    PasswordHash::new("$argon2id$v=19$m=19456,t=2,p=1$c2FsdA$aGFzaA").unwrap(),
+                    Role::Author,
+                    Utc::now(),
+                )
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        let name = RoleName::new("viewer").unwrap();
+        let err = store.assign(user.id, Some(&name)).await.unwrap_err();
+        assert!(matches!(err, DomainError::NotFound(_)), "{err}");
+
+        let role = CustomRole::new(
+            name.clone(),
+            None,
+            vec![Capability::new("articles", "view:drafts")],
+            Utc::now(),
+        )
+        .unwrap();
+        store.save(role.clone()).await.unwrap();
+        store.assign(user.id, Some(&name)).await.unwrap();
+        let redefined = role
+            .redefine(Some("read only".into()), Vec::new(), Utc::now())
+            .unwrap();
+        store.save(redefined).await.unwrap();
+
+        let assigned = store.role_of(user.id).await.unwrap().unwrap();
+        assert_eq!(assigned.description.as_deref(), Some("read only"));
+        assert!(assigned.capabilities.is_empty());
+
+        assert!(store.delete(&name).await.unwrap());
+        assert!(store.role_of(user.id).await.unwrap().is_none());
+        assert!(!store.delete(&name).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn capability_grants_are_kept_per_user() {
+        let pool = pool().await;
+        let store = SqliteRoleStore::new(pool.clone());
+        let user = SqliteUserRepository::new(pool)
+            .insert(
+                NewUser::new(
+                    Username::new("bob").unwrap(),
+                    PasswordHash::new("$argon2id$v=19$m=19456,t=2,p=1$c2FsdA$aGFzaA").unwrap(),
+                    Role::Author,
+                    Utc::now(),
+                )
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        let audit = Capability::new("audit", "read");
+        let jobs = Capability::new("jobs", "run");
+
+        assert!(store.grant_capability(user.id, &jobs).await.unwrap());
+        assert!(store.grant_capability(user.id, &audit).await.unwrap());
+        assert!(!store.grant_capability(user.id, &audit).await.unwrap());
+        assert_eq!(
+            store.capabilities_of(user.id).await.unwrap(),
+            [audit.clone(), jobs]
+        );
+
+        assert!(store.revoke_capability(user.id, &audit).await.unwrap());
+        assert!(!store.revoke_capability(user.id, &audit).await.unwrap());
+        assert_eq!(store.capabilities_of(user.id).await.unwrap().len(), 1);
+
+        let missing = UserId::new(i64::from(user.id) + 1).unwrap();
+        let err = store.grant_capability(missing, &audit).await.unwrap_err();
+        assert!(matches!(err, DomainError::NotFound(_)), "{err}");
+    }
+}
@@ -0,0 +1,97 @@
+// src/infrastructure/repositories/sqlite/unit_of_work.rs
+use super::articles::{append_revision, insert_article, update_article};
+use super::map_sqlite;
+use super::users::{consume_token, find_user, update_user};
+use crate::application::AppResult;
+use crate::application::ports::unit_of_work::{UnitOfWork, Work};
+use crate::application::ports::user_tokens::TokenPurpose;
+use crate::async_support::BoxFuture;
+use crate::domain::{Article, ArticleUpdate, NewArticle, User, UserId, UserUpdate};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+
+/// Units of work as `SQLite` transactions. Each takes the write lock when it
+/// begins, so it never has to upgrade a read lock other writers also hold.
+#[derive(Clone)]
+#[must_use]
+pub struct SqliteUnitOfWork {
+    pool: SqlitePool,
+}
+
+impl SqliteUnitOfWork {
+    pub const fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl UnitOfWork for SqliteUnitOfWork {
+    fn begin(&self) -> BoxFuture<'_, AppResult<Box<dyn Work>>> {
+        traced("SqliteUnitOfWork::begin", async move {
+            let tx = self
+                .pool
+                .begin_with("BEGIN IMMEDIATE")
+                .await
+                .map_err(map_sqlite)?;
+            Ok(Box::new(SqliteWork { tx }) as Box<dyn Work>)
+        })
+    }
+}
+
+struct SqliteWork {
+    tx: Transaction<'static, Sqlite>,
+}
+
+impl Work for SqliteWork {
+    fn insert_article(&mut self, article: NewArticle) -> BoxFuture<'_, AppResult<Article>> {
+        traced("SqliteWork::insert_article", async move {
+            Ok(insert_article(&mut *self.tx, article).await?)
+        })
+    }
+
+    fn update_article(&mut self, update: ArticleUpdate) -> BoxFuture<'_, AppResult<Article>> {
+        traced("SqliteWork::update_article", async move {
+            Ok(update_article(&mut *self.tx, update).await?)
+        })
+    }
+
+    fn append_revision<'a>(
+        &'a mut self,
+        article: &'a Article,
+        edited_by: Option<UserId>,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        traced("SqliteWork::append_revision", async move {
+            Ok(append_revision(&mut *self.tx, article, edited_by).await?)
+        })
+    }
+
+    fn find_user(&mut self, id: UserId) -> BoxFuture<'_, AppResult<Option<User>>> {
+        traced("SqliteWork::find_user", async move {
+            Ok(find_user(&mut *self.tx, id).await?)
+        })
+    }
+
+    fn update_user(&mut self, update: UserUpdate) -> BoxFuture<'_, AppResult<User>> {
+        traced("SqliteWork::update_user", async move {
+            Ok(update_user(&mut *self.tx, update).await?)
+        })
+    }
+
+    fn consume_user_token<'a>(
+        &'a mut self,
+        token_hash: &'a str,
+        purpose: TokenPurpose,
+        now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<Option<UserId>>> {
+        traced(
+            "SqliteWork::consume_user_token",
+            consume_token(&mut *self.tx, token_hash, purpose, now),
+        )
+    }
+
+    fn commit(self: Box<Self>) -> BoxFuture<'static, AppResult<()>> {
+        traced("SqliteWork::commit", async move {
+            Ok(self.tx.commit().await.map_err(map_sqlite)?)
+        })
+    }
+}
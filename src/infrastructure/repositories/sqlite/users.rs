@@ -0,0 +1,1016 @@
+// src/infrastructure/repositories/sqlite/users.rs
+use super::{count, json_ids, map_sqlite};
+use crate::application::ports::user_tokens::{TokenPurpose, UserToken, UserTokenStore};
+use crate::application::{AppError, AppResult};
+use crate::async_support::BoxFuture;
+use crate::domain::PAGE_LIMIT_CEILING;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{
+    AccountDeletions, BlockList, Email, NewUser, PasswordHash, PasswordHistory, PendingDeletion,
+    Timezone, User, UserBlock, UserId, UserListCursor, UserRepository, UserUpdate, Username,
+};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, QueryBuilder, Sqlite, SqliteExecutor, SqlitePool};
+
+const USER_COLUMNS: &str = "id, username, password_hash, role, is_active, created_at, timezone, password_reset_required, email, email_verified";
+
+#[derive(Clone)]
+#[must_use]
+pub struct SqliteUserRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteUserRepository {
+    pub const fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    fn build_update_query(update: UserUpdate) -> QueryBuilder<'static, Sqlite> {
+        let UserUpdate {
+            id,
+            username,
+            is_active,
+            role,
+            password_hash,
+            timezone,
+            password_reset_required,
+            email,
+            email_verified,
+        } = update;
+        let mut builder: QueryBuilder<'static, Sqlite> = QueryBuilder::new("UPDATE users SET ");
+        let mut assignments = builder.separated(", ");
+
+        if let Some(username) = username {
+            assignments
+                .push("username = ")
+                .push_bind_unseparated(username.into_inner());
+        }
+        if let Some(is_active) = is_active {
+            assignments
+                .push("is_active = ")
+                .push_bind_unseparated(is_active);
+        }
+        if let Some(role) = role {
+            assignments
+                .push("role = ")
+                .push_bind_unseparated(role.as_str());
+        }
+        if let Some(password_hash) = password_hash {
+            let value: String = password_hash.into();
+            assignments
+                .push("password_hash = ")
+                .push_bind_unseparated(value);
+        }
+        if let Some(timezone) = timezone {
+            assignments
+                .push("timezone = ")
+                .push_bind_unseparated(timezone.map(|tz| tz.as_str().to_string()));
+        }
+        if let Some(required) = password_reset_required {
+            assignments
+                .push("password_reset_required = ")
+                .push_bind_unseparated(required);
+        }
+        if let Some(email) = email {
+            assignments
+                .push("email = ")
+                .push_bind_unseparated(email.map(|email| email.as_str().to_string()));
+        }
+        if let Some(verified) = email_verified {
+            assignments
+                .push("email_verified = ")
+                .push_bind_unseparated(verified);
+        }
+
+        builder.push(" WHERE id = ");
+        builder.push_bind(i64::from(id));
+        builder.push(" RETURNING ");
+        builder.push(USER_COLUMNS);
+        builder
+    }
+
+    fn normalize_search(search: Option<&str>) -> Option<String> {
+        search
+            .map(str::trim)
+            .filter(|trimmed| !trimmed.is_empty())
+            .map(|trimmed| format!("%{trimmed}%"))
+    }
+
+    async fn insert_user<'e, E>(executor: E, new_user: NewUser) -> DomainResult<User>
+    where
+        E: sqlx::Executor<'e, Database = Sqlite>,
+    {
+        let NewUser {
+            username,
+            password_hash,
+            role,
+            is_active,
+            created_at,
+            password_reset_required,
+        } = new_user;
+
+        let row = sqlx::query_as::<_, UserRow>(&format!(
+            "INSERT INTO users (username, password_hash, role, is_active, created_at, password_reset_required)
+             VALUES (?, ?, ?, ?, ?, ?)
+             RETURNING {USER_COLUMNS}"
+        ))
+        .bind(username.as_str())
+        .bind(password_hash.as_str())
+        .bind(role.as_str())
+        .bind(is_active)
+        .bind(created_at)
+        .bind(password_reset_required)
+        .fetch_one(executor)
+        .await
+        .map_err(map_sqlite)?;
+
+        User::try_from(row)
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct UserRow {
+    id: i64,
+    username: String,
+    password_hash: String,
+    role: String,
+    is_active: bool,
+    created_at: DateTime<Utc>,
+    timezone: Option<String>,
+    password_reset_required: bool,
+    email: Option<String>,
+    email_verified: bool,
+}
+
+impl TryFrom<UserRow> for User {
+    type Error = DomainError;
+
+    fn try_from(row: UserRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: UserId::new(row.id)?,
+            username: Username::new(row.username)?,
+            password_hash: PasswordHash::new(row.password_hash)?,
+            role: row.role.parse()?,
+            is_active: row.is_active,
+            created_at: row.created_at,
+            timezone: row.timezone.as_deref().map(Timezone::new).transpose()?,
+            password_reset_required: row.password_reset_required,
+            email: row.email.as_deref().map(Email::new).transpose()?,
+            email_verified: row.email_verified,
+        })
+    }
+}
+
+fn users_of(rows: Vec<UserRow>) -> DomainResult<Vec<User>> {
+    rows.into_iter().map(User::try_from).collect()
+}
+
+/// Look up the user with `id` on `executor`.
+pub(super) async fn find_user<'c>(
+    executor: impl SqliteExecutor<'c>,
+    id: UserId,
+) -> DomainResult<Option<User>> {
+    sqlx::query_as::<_, UserRow>(&format!("SELECT {USER_COLUMNS} FROM users WHERE id = ?"))
+        .bind(i64::from(id))
+        .fetch_optional(executor)
+        .await
+        .map_err(map_sqlite)?
+        .map(User::try_from)
+        .transpose()
+}
+
+/// Apply `update` on `executor`.
+pub(super) async fn update_user<'c>(
+    executor: impl SqliteExecutor<'c>,
+    update: UserUpdate,
+) -> DomainResult<User> {
+    if update.username.is_none()
+        && update.is_active.is_none()
+        && update.role.is_none()
+        && update.password_hash.is_none()
+        && update.timezone.is_none()
+        && update.password_reset_required.is_none()
+        && update.email.is_none()
+        && update.email_verified.is_none()
+    {
+        return Err(DomainError::Validation(
+            "no fields provided for update".into(),
+        ));
+    }
+
+    let row = SqliteUserRepository::build_update_query(update)
+        .build_query_as::<UserRow>()
+        .fetch_optional(executor)
+        .await
+        .map_err(map_sqlite)?
+        .ok_or_else(|| DomainError::NotFound("user not found".into()))?;
+
+    User::try_from(row)
+}
+
+impl UserRepository for SqliteUserRepository {
+    fn count(&self) -> BoxFuture<'_, DomainResult<u64>> {
+        traced("SqliteUserRepository::count", async move {
+            let total = sqlx::query_scalar::<_, i64>("SELECT COUNT(1) FROM users")
+                .fetch_one(&self.pool)
+                .await
+                .map_err(map_sqlite)?;
+            Ok(count(total))
+        })
+    }
+
+    fn insert(&self, new_user: NewUser) -> BoxFuture<'_, DomainResult<User>> {
+        traced("SqliteUserRepository::insert", async move {
+            Self::insert_user(&self.pool, new_user).await
+        })
+    }
+
+    fn insert_bootstrap_admin(
+        &self,
+        new_user: NewUser,
+    ) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        traced("SqliteUserRepository::insert_bootstrap_admin", async move {
+            // The singleton marker and the user are written in one
+            // transaction; SQLite serialises writers, so the loser sees the
+            // marker and inserts nothing.
+            let mut tx = self.pool.begin().await.map_err(map_sqlite)?;
+            let claimed = sqlx::query(
+                "INSERT INTO admin_bootstrap (singleton, created_at) VALUES (TRUE, ?)
+                 ON CONFLICT DO NOTHING",
+            )
+            .bind(new_user.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlite)?;
+            if claimed.rows_affected() == 0 {
+                return Ok(None);
+            }
+            let user = Self::insert_user(&mut *tx, new_user).await?;
+            tx.commit().await.map_err(map_sqlite)?;
+            Ok(Some(user))
+        })
+    }
+
+    fn find_by_username<'a>(
+        &'a self,
+        username: &'a Username,
+    ) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        traced("SqliteUserRepository::find_by_username", async move {
+            // `username` is `COLLATE NOCASE`, so the comparison ignores case.
+            sqlx::query_as::<_, UserRow>(&format!(
+                "SELECT {USER_COLUMNS} FROM users WHERE username = ?"
+            ))
+            .bind(username.as_str())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .map(User::try_from)
+            .transpose()
+        })
+    }
+
+    fn find_by_id(&self, id: UserId) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        traced(
+            "SqliteUserRepository::find_by_id",
+            find_user(&self.pool, id),
+        )
+    }
+
+    fn find_by_email<'a>(&'a self, email: &'a Email) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        traced("SqliteUserRepository::find_by_email", async move {
+            sqlx::query_as::<_, UserRow>(&format!(
+                "SELECT {USER_COLUMNS} FROM users WHERE email = ?"
+            ))
+            .bind(email.as_str())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .map(User::try_from)
+            .transpose()
+        })
+    }
+
+    fn find_by_ids<'a>(&'a self, ids: &'a [UserId]) -> BoxFuture<'a, DomainResult<Vec<User>>> {
+        traced("SqliteUserRepository::find_by_ids", async move {
+            let rows = sqlx::query_as::<_, UserRow>(&format!(
+                "SELECT {USER_COLUMNS} FROM users WHERE id IN (SELECT value FROM json_each(?))"
+            ))
+            .bind(json_ids(ids.iter().copied().map(i64::from)))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?;
+            users_of(rows)
+        })
+    }
+
+    fn update(&self, update: UserUpdate) -> BoxFuture<'_, DomainResult<User>> {
+        traced(
+            "SqliteUserRepository::update",
+            update_user(&self.pool, update),
+        )
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        limit: u32,
+        cursor: Option<UserListCursor>,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, Option<UserListCursor>)>> {
+        traced("SqliteUserRepository::list_page", async move {
+            let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+
+            let mut builder: QueryBuilder<Sqlite> =
+                QueryBuilder::new(format!("SELECT {USER_COLUMNS} FROM users WHERE TRUE"));
+            if let Some(pattern) = Self::normalize_search(search) {
+                builder.push(" AND username LIKE ");
+                builder.push_bind(pattern);
+            }
+            if let Some(cursor) = cursor {
+                builder.push(" AND (created_at, id) < (");
+                builder.push_bind(cursor.created_at);
+                builder.push(", ");
+                builder.push_bind(i64::from(cursor.user_id));
+                builder.push(")");
+            }
+            builder.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+            builder.push_bind(i64::from(limit) + 1);
+
+            let rows = builder
+                .build_query_as::<UserRow>()
+                .fetch_all(&self.pool)
+                .await
+                .map_err(map_sqlite)?;
+            let mut users = users_of(rows)?;
+
+            let next_cursor = if users.len() > limit as usize {
+                users.pop();
+                users
+                    .last()
+                    .map(|user| UserListCursor::new(user.created_at, user.id))
+            } else {
+                None
+            };
+
+            Ok((users, next_cursor))
+        })
+    }
+
+    fn list_page_before<'a>(
+        &'a self,
+        limit: u32,
+        cursor: UserListCursor,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, Option<UserListCursor>)>> {
+        traced("SqliteUserRepository::list_page_before", async move {
+            let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+
+            let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+                "SELECT {USER_COLUMNS} FROM users WHERE (created_at, id) > ("
+            ));
+            builder.push_bind(cursor.created_at);
+            builder.push(", ");
+            builder.push_bind(i64::from(cursor.user_id));
+            builder.push(")");
+            if let Some(pattern) = Self::normalize_search(search) {
+                builder.push(" AND username LIKE ");
+                builder.push_bind(pattern);
+            }
+            builder.push(" ORDER BY created_at ASC, id ASC LIMIT ");
+            builder.push_bind(i64::from(limit) + 1);
+
+            let rows = builder
+                .build_query_as::<UserRow>()
+                .fetch_all(&self.pool)
+                .await
+                .map_err(map_sqlite)?;
+            let mut users = users_of(rows)?;
+
+            let prev_cursor = if users.len() > limit as usize {
+                users.pop();
+                users
+                    .last()
+                    .map(|user| UserListCursor::new(user.created_at, user.id))
+            } else {
+                None
+            };
+            users.reverse();
+
+            Ok((users, prev_cursor))
+        })
+    }
+
+    fn has_preceding<'a>(
+        &'a self,
+        cursor: &'a UserListCursor,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("SqliteUserRepository::has_preceding", async move {
+            let mut builder: QueryBuilder<Sqlite> =
+                QueryBuilder::new("SELECT EXISTS(SELECT 1 FROM users WHERE (created_at, id) > (");
+            builder.push_bind(cursor.created_at);
+            builder.push(", ");
+            builder.push_bind(i64::from(cursor.user_id));
+            builder.push(")");
+            if let Some(pattern) = Self::normalize_search(search) {
+                builder.push(" AND username LIKE ");
+                builder.push_bind(pattern);
+            }
+            builder.push(")");
+
+            builder
+                .build_query_scalar::<bool>()
+                .fetch_one(&self.pool)
+                .await
+                .map_err(map_sqlite)
+        })
+    }
+
+    fn list_offset<'a>(
+        &'a self,
+        offset: u64,
+        limit: u32,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, u64)>> {
+        traced("SqliteUserRepository::list_offset", async move {
+            let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+
+            let mut builder: QueryBuilder<Sqlite> =
+                QueryBuilder::new(format!("SELECT {USER_COLUMNS} FROM users WHERE TRUE"));
+            if let Some(pattern) = Self::normalize_search(search) {
+                builder.push(" AND username LIKE ");
+                builder.push_bind(pattern);
+            }
+            builder.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+            builder.push_bind(i64::from(limit));
+            builder.push(" OFFSET ");
+            builder.push_bind(i64::try_from(offset).unwrap_or(i64::MAX));
+
+            let rows = builder
+                .build_query_as::<UserRow>()
+                .fetch_all(&self.pool)
+                .await
+                .map_err(map_sqlite)?;
+            let total = self.estimate_total(search).await?.unwrap_or_default();
+
+            Ok((users_of(rows)?, total))
+        })
+    }
+
+    fn estimate_total<'a>(
+        &'a self,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<Option<u64>>> {
+        traced("SqliteUserRepository::estimate_total", async move {
+            let Some(pattern) = Self::normalize_search(search) else {
+                return self.count().await.map(Some);
+            };
+            let total =
+                sqlx::query_scalar::<_, i64>("SELECT COUNT(1) FROM users WHERE username LIKE ?")
+                    .bind(pattern)
+                    .fetch_one(&self.pool)
+                    .await
+                    .map_err(map_sqlite)?;
+            Ok(Some(count(total)))
+        })
+    }
+
+    fn find_by_federated_identity<'a>(
+        &'a self,
+        provider: &'a str,
+        subject: &'a str,
+    ) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        traced(
+            "SqliteUserRepository::find_by_federated_identity",
+            async move {
+                sqlx::query_as::<_, UserRow>(&format!(
+                "SELECT {USER_COLUMNS} FROM users
+                 WHERE id = (SELECT user_id FROM federated_identities WHERE provider = ? AND subject = ?)"
+            ))
+            .bind(provider)
+            .bind(subject)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .map(User::try_from)
+            .transpose()
+            },
+        )
+    }
+
+    fn link_federated_identity<'a>(
+        &'a self,
+        provider: &'a str,
+        subject: &'a str,
+        user_id: UserId,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        traced(
+            "SqliteUserRepository::link_federated_identity",
+            async move {
+                sqlx::query(
+                "INSERT INTO federated_identities (provider, subject, user_id) VALUES (?, ?, ?)",
+            )
+            .bind(provider)
+            .bind(subject)
+            .bind(i64::from(user_id))
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlite)?;
+                Ok(())
+            },
+        )
+    }
+}
+
+#[derive(Clone)]
+#[must_use]
+pub struct SqliteBlockList {
+    pool: SqlitePool,
+}
+
+impl SqliteBlockList {
+    pub const fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct BlockRow {
+    blocker_id: i64,
+    blocked_id: i64,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<BlockRow> for UserBlock {
+    type Error = DomainError;
+
+    fn try_from(row: BlockRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            owner: UserId::new(row.blocker_id)?,
+            target: UserId::new(row.blocked_id)?,
+            created_at: row.created_at,
+        })
+    }
+}
+
+impl BlockList for SqliteBlockList {
+    fn add(&self, block: UserBlock) -> BoxFuture<'_, DomainResult<UserBlock>> {
+        traced("SqliteBlockList::add", async move {
+            sqlx::query_as::<_, BlockRow>(
+                "INSERT INTO user_blocks (blocker_id, blocked_id, created_at)
+                 VALUES (?, ?, ?)
+                 ON CONFLICT (blocker_id, blocked_id)
+                 DO UPDATE SET blocker_id = excluded.blocker_id
+                 RETURNING blocker_id, blocked_id, created_at",
+            )
+            .bind(i64::from(block.owner))
+            .bind(i64::from(block.target))
+            .bind(block.created_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .try_into()
+        })
+    }
+
+    fn remove(&self, owner: UserId, target: UserId) -> BoxFuture<'_, DomainResult<bool>> {
+        traced("SqliteBlockList::remove", async move {
+            let result =
+                sqlx::query("DELETE FROM user_blocks WHERE blocker_id = ? AND blocked_id = ?")
+                    .bind(i64::from(owner))
+                    .bind(i64::from(target))
+                    .execute(&self.pool)
+                    .await
+                    .map_err(map_sqlite)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn list(&self, owner: UserId) -> BoxFuture<'_, DomainResult<Vec<UserBlock>>> {
+        traced("SqliteBlockList::list", async move {
+            sqlx::query_as::<_, BlockRow>(
+                "SELECT blocker_id, blocked_id, created_at FROM user_blocks
+                 WHERE blocker_id = ? ORDER BY created_at DESC, blocked_id DESC",
+            )
+            .bind(i64::from(owner))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .into_iter()
+            .map(UserBlock::try_from)
+            .collect()
+        })
+    }
+
+    fn find(
+        &self,
+        owner: UserId,
+        target: UserId,
+    ) -> BoxFuture<'_, DomainResult<Option<UserBlock>>> {
+        traced("SqliteBlockList::find", async move {
+            sqlx::query_as::<_, BlockRow>(
+                "SELECT blocker_id, blocked_id, created_at FROM user_blocks
+                 WHERE blocker_id = ? AND blocked_id = ?",
+            )
+            .bind(i64::from(owner))
+            .bind(i64::from(target))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .map(UserBlock::try_from)
+            .transpose()
+        })
+    }
+}
+
+/// Replaced password hashes in the `password_history` table.
+#[derive(Clone)]
+#[must_use]
+pub struct SqlitePasswordHistory {
+    pool: SqlitePool,
+}
+
+impl SqlitePasswordHistory {
+    pub const fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+impl PasswordHistory for SqlitePasswordHistory {
+    fn record(
+        &self,
+        user: UserId,
+        hash: PasswordHash,
+        replaced_at: DateTime<Utc>,
+        keep: usize,
+    ) -> BoxFuture<'_, DomainResult<()>> {
+        traced("SqlitePasswordHistory::record", async move {
+            let mut tx = self.pool.begin().await.map_err(map_sqlite)?;
+            sqlx::query(
+                "INSERT INTO password_history (user_id, password_hash, replaced_at)
+                 VALUES (?, ?, ?)",
+            )
+            .bind(i64::from(user))
+            .bind(hash.as_str())
+            .bind(replaced_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlite)?;
+            sqlx::query(
+                "DELETE FROM password_history WHERE user_id = ?1 AND id IN (
+                     SELECT id FROM password_history WHERE user_id = ?1
+                     ORDER BY id DESC LIMIT -1 OFFSET ?2
+                 )",
+            )
+            .bind(i64::from(user))
+            .bind(i64::try_from(keep).unwrap_or(i64::MAX))
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlite)?;
+            tx.commit().await.map_err(map_sqlite)
+        })
+    }
+
+    fn recent(&self, user: UserId, limit: usize) -> BoxFuture<'_, DomainResult<Vec<PasswordHash>>> {
+        traced("SqlitePasswordHistory::recent", async move {
+            let hashes: Vec<String> = sqlx::query_scalar(
+                "SELECT password_hash FROM password_history WHERE user_id = ?
+                 ORDER BY id DESC LIMIT ?",
+            )
+            .bind(i64::from(user))
+            .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?;
+            hashes.into_iter().map(PasswordHash::new).collect()
+        })
+    }
+}
+
+/// Scheduled account deletions in the `account_deletions` table.
+#[derive(Clone)]
+#[must_use]
+pub struct SqliteAccountDeletions {
+    pool: SqlitePool,
+}
+
+impl SqliteAccountDeletions {
+    pub const fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct DeletionRow {
+    user_id: i64,
+    requested_at: DateTime<Utc>,
+    purge_after: DateTime<Utc>,
+    transfer_to: Option<i64>,
+}
+
+impl TryFrom<DeletionRow> for PendingDeletion {
+    type Error = DomainError;
+
+    fn try_from(row: DeletionRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            user_id: UserId::new(row.user_id)?,
+            requested_at: row.requested_at,
+            purge_after: row.purge_after,
+            transfer_to: row.transfer_to.map(UserId::new).transpose()?,
+        })
+    }
+}
+
+impl AccountDeletions for SqliteAccountDeletions {
+    fn schedule(&self, deletion: PendingDeletion) -> BoxFuture<'_, DomainResult<()>> {
+        traced("SqliteAccountDeletions::schedule", async move {
+            sqlx::query(
+                "INSERT INTO account_deletions (user_id, requested_at, purge_after, transfer_to)
+                 VALUES (?, ?, ?, ?)
+                 ON CONFLICT (user_id) DO UPDATE SET requested_at = excluded.requested_at,
+                     purge_after = excluded.purge_after, transfer_to = excluded.transfer_to",
+            )
+            .bind(i64::from(deletion.user_id))
+            .bind(deletion.requested_at)
+            .bind(deletion.purge_after)
+            .bind(deletion.transfer_to.map(i64::from))
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlite)?;
+            Ok(())
+        })
+    }
+
+    fn remove(&self, user_id: UserId) -> BoxFuture<'_, DomainResult<bool>> {
+        traced("SqliteAccountDeletions::remove", async move {
+            let result = sqlx::query("DELETE FROM account_deletions WHERE user_id = ?")
+                .bind(i64::from(user_id))
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlite)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn find(&self, user_id: UserId) -> BoxFuture<'_, DomainResult<Option<PendingDeletion>>> {
+        traced("SqliteAccountDeletions::find", async move {
+            sqlx::query_as::<_, DeletionRow>(
+                "SELECT user_id, requested_at, purge_after, transfer_to FROM account_deletions
+                 WHERE user_id = ?",
+            )
+            .bind(i64::from(user_id))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .map(PendingDeletion::try_from)
+            .transpose()
+        })
+    }
+
+    fn due(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> BoxFuture<'_, DomainResult<Vec<PendingDeletion>>> {
+        traced("SqliteAccountDeletions::due", async move {
+            sqlx::query_as::<_, DeletionRow>(
+                "SELECT user_id, requested_at, purge_after, transfer_to FROM account_deletions
+                 WHERE purge_after <= ? ORDER BY purge_after, user_id LIMIT ?",
+            )
+            .bind(now)
+            .bind(i64::from(limit))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .into_iter()
+            .map(PendingDeletion::try_from)
+            .collect()
+        })
+    }
+}
+
+/// Mailed user tokens in the `user_tokens` table.
+#[derive(Clone)]
+#[must_use]
+pub struct SqliteUserTokenStore {
+    pool: SqlitePool,
+}
+
+impl SqliteUserTokenStore {
+    pub const fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+fn token_error(err: &sqlx::Error) -> AppError {
+    AppError::infrastructure(format!("user tokens: {err}"))
+}
+
+impl UserTokenStore for SqliteUserTokenStore {
+    fn create(&self, token: UserToken) -> BoxFuture<'_, AppResult<()>> {
+        traced("SqliteUserTokenStore::create", async move {
+            let mut tx = self.pool.begin().await.map_err(|err| token_error(&err))?;
+            sqlx::query("DELETE FROM user_tokens WHERE user_id = ? AND purpose = ?")
+                .bind(i64::from(token.user_id))
+                .bind(token.purpose.as_str())
+                .execute(&mut *tx)
+                .await
+                .map_err(|err| token_error(&err))?;
+            sqlx::query(
+                "INSERT INTO user_tokens (token_hash, user_id, purpose, created_at, expires_at)
+                 VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(&token.token_hash)
+            .bind(i64::from(token.user_id))
+            .bind(token.purpose.as_str())
+            .bind(token.created_at)
+            .bind(token.expires_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| token_error(&err))?;
+            tx.commit().await.map_err(|err| token_error(&err))
+        })
+    }
+
+    fn consume<'a>(
+        &'a self,
+        token_hash: &'a str,
+        purpose: TokenPurpose,
+        now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<Option<UserId>>> {
+        traced(
+            "SqliteUserTokenStore::consume",
+            consume_token(&self.pool, token_hash, purpose, now),
+        )
+    }
+}
+
+/// Remove the token with `token_hash` and `purpose` on `executor` and return
+/// its user, unless it expired before `now`.
+pub(super) async fn consume_token<'c>(
+    executor: impl SqliteExecutor<'c>,
+    token_hash: &str,
+    purpose: TokenPurpose,
+    now: DateTime<Utc>,
+) -> AppResult<Option<UserId>> {
+    let row = sqlx::query_as::<_, (i64, DateTime<Utc>)>(
+        "DELETE FROM user_tokens WHERE token_hash = ? AND purpose = ?
+         RETURNING user_id, expires_at",
+    )
+    .bind(token_hash)
+    .bind(purpose.as_str())
+    .fetch_optional(executor)
+    .await
+    .map_err(|err| token_error(&err))?;
+
+    row.filter(|(_, expires_at)| *expires_at > now)
+        .map(|(user_id, _)| UserId::new(user_id).map_err(AppError::from))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::pool;
+    use super::*;
+    use crate::domain::Role;
+
+    fn new_user(name: &str) -> NewUser {
+        NewUser::new(
+            Username::new(name).unwrap(),
+            PasswordHash::new("$argon2id$v=19$m=19456,t=2,p=1$c2FsdA$aGFzaA").unwrap(),
+            Role::Author,
+            Utc::now(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn usernames_are_unique_regardless_of_case() {
+        let repo = SqliteUserRepository::new(pool().await);
+        let alice = repo.insert(new_user("alice")).await.unwrap();
+
+        let err = repo.insert(new_user("ALICE")).await.unwrap_err();
+        assert!(matches!(err, DomainError::UsernameTaken { .. }), "{err}");
+        let found = repo
+            .find_by_username(&Username::new("Alice").unwrap())
+            .await
+            .unwrap();
+        assert_eq!(found.map(|user| user.id), Some(alice.id));
+    }
+
+    #[tokio::test]
+    async fn only_the_first_bootstrap_admin_is_inserted() {
+        let repo = SqliteUserRepository::new(pool().await);
+
+        let first = repo
+            .insert_bootstrap_admin(new_user("first"))
+            .await
+            .unwrap();
+        let second = repo
+            .insert_bootstrap_admin(new_user("second"))
+            .await
+            .unwrap();
+        assert!(first.is_some());
+        assert!(second.is_none());
+        assert_eq!(repo.count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn pages_walk_the_listing_in_both_directions() {
+        let repo = SqliteUserRepository::new(pool().await);
+        for name in ["ann", "bob", "cat", "dan", "eve"] {
+            repo.insert(new_user(name)).await.unwrap();
+        }
+
+        let (first, cursor) = repo.list_page(2, None, None).await.unwrap();
+        let names = |users: &[User]| {
+            users
+                .iter()
+                .map(|user| user.username.as_str().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&first), vec!["eve", "dan"]);
+        let (second, cursor) = repo.list_page(2, cursor, None).await.unwrap();
+        assert_eq!(names(&second), vec!["cat", "bob"]);
+        let cursor = cursor.unwrap();
+        assert!(repo.has_preceding(&cursor, None).await.unwrap());
+        let (back, _) = repo.list_page_before(2, cursor, None).await.unwrap();
+        assert_eq!(names(&back), vec!["dan", "cat"]);
+
+        let (page, total) = repo.list_offset(1, 10, Some("a")).await.unwrap();
+        assert_eq!(names(&page), vec!["cat", "ann"]);
+        assert_eq!(total, 3);
+    }
+
+    #[tokio::test]
+    async fn password_history_keeps_the_most_recent_hashes() {
+        let pool = pool().await;
+        let user = SqliteUserRepository::new(pool.clone())
+            .insert(new_user("fay"))
+            .await
+            .unwrap();
+        let history = SqlitePasswordHistory::new(pool);
+        for n in 1..=4 {
+            let hash = PasswordHash::new(format!("hash-{n}")).unwrap();
+            history.record(user.id, hash, Utc::now(), 3).await.unwrap();
+        }
+
+        let hashes: Vec<_> = history
+            .recent(user.id, 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|hash| hash.as_str().to_owned())
+            .collect();
+        assert_eq!(hashes, ["hash-4", "hash-3", "hash-2"]);
+        assert_eq!(history.recent(user.id, 1).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn account_deletions_come_due_after_their_grace_period() {
+        let pool = pool().await;
+        let users = SqliteUserRepository::new(pool.clone());
+        let gus = users.insert(new_user("gus")).await.unwrap();
+        let hal = users.insert(new_user("hal")).await.unwrap();
+        let deletions = SqliteAccountDeletions::new(pool);
+        let now = Utc::now();
+        let pending = |user: &User, days| PendingDeletion {
+            user_id: user.id,
+            requested_at: now,
+            purge_after: now + chrono::Duration::days(days),
+            transfer_to: None,
+        };
+        deletions.schedule(pending(&gus, 1)).await.unwrap();
+        deletions.schedule(pending(&gus, 3)).await.unwrap();
+        deletions
+            .schedule(PendingDeletion {
+                transfer_to: Some(gus.id),
+                ..pending(&hal, 2)
+            })
+            .await
+            .unwrap();
+
+        let due = deletions
+            .due(now + chrono::Duration::days(2), 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            due,
+            [PendingDeletion {
+                transfer_to: Some(gus.id),
+                ..pending(&hal, 2)
+            }]
+        );
+        assert_eq!(
+            deletions
+                .due(now + chrono::Duration::days(5), 10)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(deletions.remove(gus.id).await.unwrap());
+        assert!(!deletions.remove(gus.id).await.unwrap());
+        assert!(deletions.find(gus.id).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,349 @@
+// src/infrastructure/repositories/sqlite/webhooks.rs
+use super::map_sqlite;
+use crate::async_support::BoxFuture;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{
+    NewWebhook, NewWebhookDeliveryAttempt, UserId, Webhook, WebhookDeliveryAttempt, WebhookEvent,
+    WebhookRepository,
+};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+
+const WEBHOOK_COLUMNS: &str = "id, url, secret, events, active, created_by, created_at, updated_at";
+const ATTEMPT_COLUMNS: &str = "id, webhook_id, delivery_id, event, attempt, status_code, error, \
+                               succeeded, duration_ms, attempted_at";
+
+#[derive(Clone)]
+#[must_use]
+pub struct SqliteWebhookRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteWebhookRepository {
+    pub const fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct WebhookRow {
+    id: i64,
+    url: String,
+    secret: String,
+    events: String,
+    active: bool,
+    created_by: Option<i64>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<WebhookRow> for Webhook {
+    type Error = DomainError;
+
+    fn try_from(row: WebhookRow) -> DomainResult<Self> {
+        let events: Vec<WebhookEvent> = serde_json::from_str(&row.events).map_err(|err| {
+            DomainError::Persistence(format!("webhook {} has invalid events: {err}", row.id))
+        })?;
+        Ok(Self {
+            id: row.id,
+            url: row.url,
+            secret: row.secret,
+            events,
+            active: row.active,
+            created_by: row.created_by.map(UserId::new).transpose()?,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct AttemptRow {
+    id: i64,
+    webhook_id: i64,
+    delivery_id: String,
+    event: String,
+    attempt: i64,
+    status_code: Option<i64>,
+    error: Option<String>,
+    succeeded: bool,
+    duration_ms: i64,
+    attempted_at: DateTime<Utc>,
+}
+
+impl TryFrom<AttemptRow> for WebhookDeliveryAttempt {
+    type Error = DomainError;
+
+    fn try_from(row: AttemptRow) -> DomainResult<Self> {
+        let invalid = |field: &str| {
+            DomainError::Persistence(format!(
+                "webhook delivery attempt {} has an invalid {field}",
+                row.id
+            ))
+        };
+        Ok(Self {
+            id: row.id,
+            webhook_id: row.webhook_id,
+            delivery_id: row.delivery_id.clone(),
+            event: row.event.parse()?,
+            attempt: u32::try_from(row.attempt).map_err(|_| invalid("attempt"))?,
+            status_code: row
+                .status_code
+                .map(u16::try_from)
+                .transpose()
+                .map_err(|_| invalid("status code"))?,
+            error: row.error.clone(),
+            succeeded: row.succeeded,
+            duration_ms: u64::try_from(row.duration_ms).map_err(|_| invalid("duration"))?,
+            attempted_at: row.attempted_at,
+        })
+    }
+}
+
+/// `events` as the JSON array the `events` column holds.
+fn json_events(events: &[WebhookEvent]) -> String {
+    serde_json::Value::from(
+        events
+            .iter()
+            .map(|event| event.as_str())
+            .collect::<Vec<_>>(),
+    )
+    .to_string()
+}
+
+impl WebhookRepository for SqliteWebhookRepository {
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<Webhook>>> {
+        traced("SqliteWebhookRepository::list", async move {
+            sqlx::query_as::<_, WebhookRow>(&format!(
+                "SELECT {WEBHOOK_COLUMNS} FROM webhooks ORDER BY id"
+            ))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .into_iter()
+            .map(Webhook::try_from)
+            .collect()
+        })
+    }
+
+    fn find_by_id(&self, id: i64) -> BoxFuture<'_, DomainResult<Option<Webhook>>> {
+        traced("SqliteWebhookRepository::find_by_id", async move {
+            sqlx::query_as::<_, WebhookRow>(&format!(
+                "SELECT {WEBHOOK_COLUMNS} FROM webhooks WHERE id = ?"
+            ))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .map(TryInto::try_into)
+            .transpose()
+        })
+    }
+
+    fn subscribed(&self, event: WebhookEvent) -> BoxFuture<'_, DomainResult<Vec<Webhook>>> {
+        traced("SqliteWebhookRepository::subscribed", async move {
+            sqlx::query_as::<_, WebhookRow>(&format!(
+                "SELECT {WEBHOOK_COLUMNS} FROM webhooks
+                 WHERE active AND EXISTS (SELECT 1 FROM json_each(events) WHERE value = ?)
+                 ORDER BY id"
+            ))
+            .bind(event.as_str())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .into_iter()
+            .map(Webhook::try_from)
+            .collect()
+        })
+    }
+
+    fn insert(&self, webhook: NewWebhook) -> BoxFuture<'_, DomainResult<Webhook>> {
+        traced("SqliteWebhookRepository::insert", async move {
+            sqlx::query_as::<_, WebhookRow>(&format!(
+                "INSERT INTO webhooks
+                     (url, secret, events, active, created_by, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+                 RETURNING {WEBHOOK_COLUMNS}"
+            ))
+            .bind(&webhook.url)
+            .bind(&webhook.secret)
+            .bind(json_events(&webhook.events))
+            .bind(webhook.active)
+            .bind(webhook.created_by.map(i64::from))
+            .bind(webhook.created_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .try_into()
+        })
+    }
+
+    fn update<'a>(&'a self, webhook: &'a Webhook) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("SqliteWebhookRepository::update", async move {
+            let result = sqlx::query(
+                "UPDATE webhooks
+                 SET url = ?2, secret = ?3, events = ?4, active = ?5, updated_at = ?6
+                 WHERE id = ?1",
+            )
+            .bind(webhook.id)
+            .bind(&webhook.url)
+            .bind(&webhook.secret)
+            .bind(json_events(&webhook.events))
+            .bind(webhook.active)
+            .bind(webhook.updated_at)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlite)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn delete(&self, id: i64) -> BoxFuture<'_, DomainResult<bool>> {
+        traced("SqliteWebhookRepository::delete", async move {
+            let result = sqlx::query("DELETE FROM webhooks WHERE id = ?")
+                .bind(id)
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlite)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn record_attempt(
+        &self,
+        attempt: NewWebhookDeliveryAttempt,
+    ) -> BoxFuture<'_, DomainResult<WebhookDeliveryAttempt>> {
+        traced("SqliteWebhookRepository::record_attempt", async move {
+            let duration_ms = i64::try_from(attempt.duration_ms).unwrap_or(i64::MAX);
+            sqlx::query_as::<_, AttemptRow>(&format!(
+                "INSERT INTO webhook_deliveries
+                     (webhook_id, delivery_id, event, attempt, status_code, error, succeeded,
+                      duration_ms, attempted_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                 RETURNING {ATTEMPT_COLUMNS}"
+            ))
+            .bind(attempt.webhook_id)
+            .bind(&attempt.delivery_id)
+            .bind(attempt.event.as_str())
+            .bind(i64::from(attempt.attempt))
+            .bind(attempt.status_code.map(i64::from))
+            .bind(&attempt.error)
+            .bind(attempt.succeeded)
+            .bind(duration_ms)
+            .bind(attempt.attempted_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .try_into()
+        })
+    }
+
+    fn list_attempts(
+        &self,
+        webhook_id: i64,
+        limit: u32,
+    ) -> BoxFuture<'_, DomainResult<Vec<WebhookDeliveryAttempt>>> {
+        traced("SqliteWebhookRepository::list_attempts", async move {
+            sqlx::query_as::<_, AttemptRow>(&format!(
+                "SELECT {ATTEMPT_COLUMNS} FROM webhook_deliveries
+                 WHERE webhook_id = ?
+                 ORDER BY id DESC
+                 LIMIT ?"
+            ))
+            .bind(webhook_id)
+            .bind(i64::from(limit))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlite)?
+            .into_iter()
+            .map(WebhookDeliveryAttempt::try_from)
+            .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::pool;
+    use super::*;
+
+    #[tokio::test]
+    async fn subscriptions_are_matched_and_history_goes_with_the_webhook() {
+        let repo = SqliteWebhookRepository::new(pool().await);
+        let new = |events| {
+            NewWebhook::new(
+                "https://hooks.example/",
+                "0123456789abcdef".into(),
+                events,
+                None,
+                Utc::now(),
+            )
+            .unwrap()
+        };
+        let published = repo
+            .insert(new(vec![
+                WebhookEvent::ArticlePublished,
+                WebhookEvent::ArticleDeleted,
+            ]))
+            .await
+            .unwrap();
+        let mut inactive = repo
+            .insert(new(vec![WebhookEvent::ArticlePublished]))
+            .await
+            .unwrap();
+        inactive.active = false;
+        assert!(repo.update(&inactive).await.unwrap());
+
+        let subscribed = repo
+            .subscribed(WebhookEvent::ArticlePublished)
+            .await
+            .unwrap();
+        assert_eq!(
+            subscribed
+                .iter()
+                .map(|webhook| webhook.id)
+                .collect::<Vec<_>>(),
+            vec![published.id]
+        );
+        assert!(
+            repo.subscribed(WebhookEvent::ArticleCreated)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        for attempt in 1..=2 {
+            repo.record_attempt(NewWebhookDeliveryAttempt {
+                webhook_id: published.id,
+                delivery_id: "d-1".into(),
+                event: WebhookEvent::ArticlePublished,
+                attempt,
+                status_code: Some(500),
+                error: Some("responded with 500".into()),
+                succeeded: false,
+                duration_ms: 12,
+                attempted_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        }
+        let history = repo.list_attempts(published.id, 10).await.unwrap();
+        assert_eq!(
+            history
+                .iter()
+                .map(|attempt| attempt.attempt)
+                .collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+
+        assert!(repo.delete(published.id).await.unwrap());
+        assert!(!repo.delete(published.id).await.unwrap());
+        assert!(
+            repo.list_attempts(published.id, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,109 @@
+// src/infrastructure/repositories/unit_of_work/postgres.rs
+use super::super::articles::{append_revision, insert_article, update_article};
+use super::super::map_sqlx;
+use super::super::users::{consume_token, find_user, update_user};
+use crate::application::AppResult;
+use crate::application::ports::unit_of_work::{UnitOfWork, Work};
+use crate::application::ports::user_tokens::TokenPurpose;
+use crate::async_support::BoxFuture;
+use crate::domain::{Article, ArticleUpdate, NewArticle, User, UserId, UserUpdate};
+use crate::infrastructure::repositories::traced;
+use crate::infrastructure::rls;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+
+/// Units of work as Postgres transactions, carrying the row-level security
+/// context like every repository transaction.
+#[derive(Clone)]
+#[must_use]
+pub struct PostgresUnitOfWork {
+    pool: PgPool,
+    search_language: String,
+}
+
+impl PostgresUnitOfWork {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            search_language: "simple".into(),
+        }
+    }
+
+    /// Index written articles with the text search configuration `language`,
+    /// as the article repositories do.
+    pub fn with_search_language(mut self, language: impl Into<String>) -> Self {
+        self.search_language = language.into();
+        self
+    }
+}
+
+impl UnitOfWork for PostgresUnitOfWork {
+    fn begin(&self) -> BoxFuture<'_, AppResult<Box<dyn Work>>> {
+        traced("PostgresUnitOfWork::begin", async move {
+            let tx = rls::begin(&self.pool).await.map_err(map_sqlx)?;
+            Ok(Box::new(PostgresWork {
+                tx,
+                search_language: self.search_language.clone(),
+            }) as Box<dyn Work>)
+        })
+    }
+}
+
+struct PostgresWork {
+    tx: Transaction<'static, Postgres>,
+    search_language: String,
+}
+
+impl Work for PostgresWork {
+    fn insert_article(&mut self, article: NewArticle) -> BoxFuture<'_, AppResult<Article>> {
+        traced("PostgresWork::insert_article", async move {
+            Ok(insert_article(&mut self.tx, article, &self.search_language).await?)
+        })
+    }
+
+    fn update_article(&mut self, update: ArticleUpdate) -> BoxFuture<'_, AppResult<Article>> {
+        traced("PostgresWork::update_article", async move {
+            Ok(update_article(&mut self.tx, update, &self.search_language).await?)
+        })
+    }
+
+    fn append_revision<'a>(
+        &'a mut self,
+        article: &'a Article,
+        edited_by: Option<UserId>,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        traced("PostgresWork::append_revision", async move {
+            Ok(append_revision(&mut self.tx, article, edited_by).await?)
+        })
+    }
+
+    fn find_user(&mut self, id: UserId) -> BoxFuture<'_, AppResult<Option<User>>> {
+        traced("PostgresWork::find_user", async move {
+            Ok(find_user(&mut *self.tx, id).await?)
+        })
+    }
+
+    fn update_user(&mut self, update: UserUpdate) -> BoxFuture<'_, AppResult<User>> {
+        traced("PostgresWork::update_user", async move {
+            Ok(update_user(&mut *self.tx, update).await?)
+        })
+    }
+
+    fn consume_user_token<'a>(
+        &'a mut self,
+        token_hash: &'a str,
+        purpose: TokenPurpose,
+        now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<Option<UserId>>> {
+        traced(
+            "PostgresWork::consume_user_token",
+            consume_token(&mut *self.tx, token_hash, purpose, now),
+        )
+    }
+
+    fn commit(self: Box<Self>) -> BoxFuture<'static, AppResult<()>> {
+        traced("PostgresWork::commit", async move {
+            Ok(self.tx.commit().await.map_err(map_sqlx)?)
+        })
+    }
+}
@@ -0,0 +1,109 @@
+// src/infrastructure/repositories/users/blocks.rs
+use super::super::map_sqlx;
+use crate::async_support::BoxFuture;
+use crate::domain::errors::DomainResult;
+use crate::domain::{BlockList, UserBlock, UserId};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+
+#[derive(Clone)]
+#[must_use]
+pub struct PostgresBlockList {
+    pool: PgPool,
+}
+
+impl PostgresBlockList {
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct BlockRow {
+    blocker_id: i64,
+    blocked_id: i64,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<BlockRow> for UserBlock {
+    type Error = crate::domain::errors::DomainError;
+
+    fn try_from(row: BlockRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            owner: UserId::new(row.blocker_id)?,
+            target: UserId::new(row.blocked_id)?,
+            created_at: row.created_at,
+        })
+    }
+}
+
+impl BlockList for PostgresBlockList {
+    fn add(&self, block: UserBlock) -> BoxFuture<'_, DomainResult<UserBlock>> {
+        traced("PostgresBlockList::add", async move {
+            let row = sqlx::query_as::<_, BlockRow>(
+                "INSERT INTO user_blocks (blocker_id, blocked_id, created_at)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (blocker_id, blocked_id)
+                 DO UPDATE SET blocker_id = EXCLUDED.blocker_id
+                 RETURNING blocker_id, blocked_id, created_at",
+            )
+            .bind(i64::from(block.owner))
+            .bind(i64::from(block.target))
+            .bind(block.created_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+            row.try_into()
+        })
+    }
+
+    fn remove(&self, owner: UserId, target: UserId) -> BoxFuture<'_, DomainResult<bool>> {
+        traced("PostgresBlockList::remove", async move {
+            let result =
+                sqlx::query("DELETE FROM user_blocks WHERE blocker_id = $1 AND blocked_id = $2")
+                    .bind(i64::from(owner))
+                    .bind(i64::from(target))
+                    .execute(&self.pool)
+                    .await
+                    .map_err(map_sqlx)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn list(&self, owner: UserId) -> BoxFuture<'_, DomainResult<Vec<UserBlock>>> {
+        traced("PostgresBlockList::list", async move {
+            sqlx::query_as::<_, BlockRow>(
+                "SELECT blocker_id, blocked_id, created_at FROM user_blocks
+                 WHERE blocker_id = $1 ORDER BY created_at DESC, blocked_id DESC",
+            )
+            .bind(i64::from(owner))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .into_iter()
+            .map(UserBlock::try_from)
+            .collect()
+        })
+    }
+
+    fn find(
+        &self,
+        owner: UserId,
+        target: UserId,
+    ) -> BoxFuture<'_, DomainResult<Option<UserBlock>>> {
+        traced("PostgresBlockList::find", async move {
+            sqlx::query_as::<_, BlockRow>(
+                "SELECT blocker_id, blocked_id, created_at FROM user_blocks
+                 WHERE blocker_id = $1 AND blocked_id = $2",
+            )
+            .bind(i64::from(owner))
+            .bind(i64::from(target))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .map(UserBlock::try_from)
+            .transpose()
+        })
+    }
+}
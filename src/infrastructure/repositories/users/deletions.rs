@@ -0,0 +1,109 @@
+// src/infrastructure/repositories/users/deletions.rs
+use super::super::map_sqlx;
+use crate::async_support::BoxFuture;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{AccountDeletions, PendingDeletion, UserId};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+
+#[derive(Clone)]
+#[must_use]
+pub struct PostgresAccountDeletions {
+    pool: PgPool,
+}
+
+impl PostgresAccountDeletions {
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct DeletionRow {
+    user_id: i64,
+    requested_at: DateTime<Utc>,
+    purge_after: DateTime<Utc>,
+    transfer_to: Option<i64>,
+}
+
+impl TryFrom<DeletionRow> for PendingDeletion {
+    type Error = DomainError;
+
+    fn try_from(row: DeletionRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            user_id: UserId::new(row.user_id)?,
+            requested_at: row.requested_at,
+            purge_after: row.purge_after,
+            transfer_to: row.transfer_to.map(UserId::new).transpose()?,
+        })
+    }
+}
+
+impl AccountDeletions for PostgresAccountDeletions {
+    fn schedule(&self, deletion: PendingDeletion) -> BoxFuture<'_, DomainResult<()>> {
+        traced("PostgresAccountDeletions::schedule", async move {
+            sqlx::query(
+                "INSERT INTO account_deletions (user_id, requested_at, purge_after, transfer_to)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (user_id) DO UPDATE SET requested_at = EXCLUDED.requested_at,
+                     purge_after = EXCLUDED.purge_after, transfer_to = EXCLUDED.transfer_to",
+            )
+            .bind(i64::from(deletion.user_id))
+            .bind(deletion.requested_at)
+            .bind(deletion.purge_after)
+            .bind(deletion.transfer_to.map(i64::from))
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+            Ok(())
+        })
+    }
+
+    fn remove(&self, user_id: UserId) -> BoxFuture<'_, DomainResult<bool>> {
+        traced("PostgresAccountDeletions::remove", async move {
+            let result = sqlx::query("DELETE FROM account_deletions WHERE user_id = $1")
+                .bind(i64::from(user_id))
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlx)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn find(&self, user_id: UserId) -> BoxFuture<'_, DomainResult<Option<PendingDeletion>>> {
+        traced("PostgresAccountDeletions::find", async move {
+            sqlx::query_as::<_, DeletionRow>(
+                "SELECT user_id, requested_at, purge_after, transfer_to FROM account_deletions
+                 WHERE user_id = $1",
+            )
+            .bind(i64::from(user_id))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .map(PendingDeletion::try_from)
+            .transpose()
+        })
+    }
+
+    fn due(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> BoxFuture<'_, DomainResult<Vec<PendingDeletion>>> {
+        traced("PostgresAccountDeletions::due", async move {
+            sqlx::query_as::<_, DeletionRow>(
+                "SELECT user_id, requested_at, purge_after, transfer_to FROM account_deletions
+                 WHERE purge_after <= $1 ORDER BY purge_after, user_id LIMIT $2",
+            )
+            .bind(now)
+            .bind(i64::from(limit))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .into_iter()
+            .map(PendingDeletion::try_from)
+            .collect()
+        })
+    }
+}
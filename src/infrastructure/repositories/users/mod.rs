@@ -1,3 +1,15 @@
+mod blocks;
+mod deletions;
+mod password_history;
 mod postgres;
+mod roles;
+mod tokens;
 
+pub use blocks::PostgresBlockList;
+pub use deletions::PostgresAccountDeletions;
+pub use password_history::PostgresPasswordHistory;
 pub use postgres::PostgresUserRepository;
+pub(super) use postgres::{find_user, update_user};
+pub use roles::PostgresRoleStore;
+pub use tokens::PostgresUserTokenStore;
+pub(super) use tokens::consume_token;
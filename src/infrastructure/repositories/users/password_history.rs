@@ -0,0 +1,71 @@
+// src/infrastructure/repositories/users/password_history.rs
+use super::super::map_sqlx;
+use crate::async_support::BoxFuture;
+use crate::domain::errors::DomainResult;
+use crate::domain::{PasswordHash, PasswordHistory, UserId};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Clone)]
+#[must_use]
+pub struct PostgresPasswordHistory {
+    pool: PgPool,
+}
+
+impl PostgresPasswordHistory {
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl PasswordHistory for PostgresPasswordHistory {
+    fn record(
+        &self,
+        user: UserId,
+        hash: PasswordHash,
+        replaced_at: DateTime<Utc>,
+        keep: usize,
+    ) -> BoxFuture<'_, DomainResult<()>> {
+        traced("PostgresPasswordHistory::record", async move {
+            let mut tx = self.pool.begin().await.map_err(map_sqlx)?;
+            sqlx::query(
+                "INSERT INTO password_history (user_id, password_hash, replaced_at)
+                 VALUES ($1, $2, $3)",
+            )
+            .bind(i64::from(user))
+            .bind(hash.as_str())
+            .bind(replaced_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx)?;
+            sqlx::query(
+                "DELETE FROM password_history WHERE user_id = $1 AND id IN (
+                     SELECT id FROM password_history WHERE user_id = $1
+                     ORDER BY id DESC OFFSET $2
+                 )",
+            )
+            .bind(i64::from(user))
+            .bind(i64::try_from(keep).unwrap_or(i64::MAX))
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx)?;
+            tx.commit().await.map_err(map_sqlx)
+        })
+    }
+
+    fn recent(&self, user: UserId, limit: usize) -> BoxFuture<'_, DomainResult<Vec<PasswordHash>>> {
+        traced("PostgresPasswordHistory::recent", async move {
+            let hashes: Vec<String> = sqlx::query_scalar(
+                "SELECT password_hash FROM password_history WHERE user_id = $1
+                 ORDER BY id DESC LIMIT $2",
+            )
+            .bind(i64::from(user))
+            .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+            hashes.into_iter().map(PasswordHash::new).collect()
+        })
+    }
+}
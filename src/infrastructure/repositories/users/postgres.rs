@@ -1,12 +1,15 @@
 // src/infrastructure/repositories/users/postgres.rs
 use super::super::map_sqlx;
-use crate::async_support::{BoxFuture, boxed};
+use crate::async_support::BoxFuture;
+use crate::domain::PAGE_LIMIT_CEILING;
 use crate::domain::errors::{DomainError, DomainResult};
 use crate::domain::{
-    NewUser, PasswordHash, Role, User, UserId, UserListCursor, UserRepository, UserUpdate, Username,
+    Email, NewUser, PasswordHash, Role, Timezone, User, UserId, UserListCursor, UserRepository,
+    UserUpdate, Username,
 };
+use crate::infrastructure::repositories::traced;
 use chrono::{DateTime, Utc};
-use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
+use sqlx::{FromRow, PgExecutor, PgPool, Postgres, QueryBuilder};
 
 #[derive(Clone)]
 #[must_use]
@@ -19,15 +22,27 @@ impl PostgresUserRepository {
         Self { pool }
     }
 
-    fn build_update_query(
-        id: UserId,
-        is_active: Option<bool>,
-        role: Option<Role>,
-        password_hash: Option<PasswordHash>,
-    ) -> QueryBuilder<'static, Postgres> {
+    fn build_update_query(update: UserUpdate) -> QueryBuilder<'static, Postgres> {
+        let UserUpdate {
+            id,
+            username,
+            is_active,
+            role,
+            password_hash,
+            timezone,
+            password_reset_required,
+            email,
+            email_verified,
+        } = update;
         let mut builder: QueryBuilder<'static, Postgres> = QueryBuilder::new("UPDATE users SET ");
         let mut first = true;
 
+        if let Some(username) = username {
+            first = false;
+            builder.push("username = ");
+            builder.push_bind(username.into_inner());
+        }
+
         if let Some(is_active) = is_active {
             if !first {
                 builder.push(", ");
@@ -50,14 +65,51 @@ impl PostgresUserRepository {
             if !first {
                 builder.push(", ");
             }
+            first = false;
             builder.push("password_hash = ");
             let value: String = password_hash.into();
             builder.push_bind(value);
         }
 
+        if let Some(timezone) = timezone {
+            if !first {
+                builder.push(", ");
+            }
+            first = false;
+            builder.push("timezone = ");
+            builder.push_bind(timezone.map(|tz| tz.as_str()));
+        }
+
+        if let Some(required) = password_reset_required {
+            if !first {
+                builder.push(", ");
+            }
+            first = false;
+            builder.push("password_reset_required = ");
+            builder.push_bind(required);
+        }
+
+        if let Some(email) = email {
+            if !first {
+                builder.push(", ");
+            }
+            first = false;
+            builder.push("email = ");
+            builder.push_bind(email.map(|email| email.as_str().to_string()));
+        }
+
+        if let Some(verified) = email_verified {
+            if !first {
+                builder.push(", ");
+            }
+            builder.push("email_verified = ");
+            builder.push_bind(verified);
+        }
+
         builder.push(" WHERE id = ");
         builder.push_bind(i64::from(id));
-        builder.push(" RETURNING id, username, password_hash, role, is_active, created_at");
+        builder
+            .push(" RETURNING id, username, password_hash, role, is_active, created_at, timezone, password_reset_required, email, email_verified");
 
         builder
     }
@@ -82,6 +134,10 @@ struct UserRow {
     role: Role,
     is_active: bool,
     created_at: DateTime<Utc>,
+    timezone: Option<String>,
+    password_reset_required: bool,
+    email: Option<String>,
+    email_verified: bool,
 }
 
 impl TryFrom<UserRow> for User {
@@ -95,13 +151,65 @@ impl TryFrom<UserRow> for User {
             role: row.role,
             is_active: row.is_active,
             created_at: row.created_at,
+            timezone: row.timezone.as_deref().map(Timezone::new).transpose()?,
+            password_reset_required: row.password_reset_required,
+            email: row.email.as_deref().map(Email::new).transpose()?,
+            email_verified: row.email_verified,
         })
     }
 }
 
+/// Look up the user with `id` on `executor`.
+pub(in crate::infrastructure::repositories) async fn find_user<'c>(
+    executor: impl PgExecutor<'c>,
+    id: UserId,
+) -> DomainResult<Option<User>> {
+    let row = sqlx::query_as::<_, UserRow>(
+        "SELECT id, username, password_hash, role, is_active, created_at, timezone, password_reset_required, email, email_verified
+         FROM users WHERE id = $1",
+    )
+    .bind(i64::from(id))
+    .fetch_optional(executor)
+    .await
+    .map_err(map_sqlx)?;
+
+    row.map(User::try_from).transpose()
+}
+
+/// Apply `update` on `executor`.
+pub(in crate::infrastructure::repositories) async fn update_user<'c>(
+    executor: impl PgExecutor<'c>,
+    update: UserUpdate,
+) -> DomainResult<User> {
+    if update.username.is_none()
+        && update.is_active.is_none()
+        && update.role.is_none()
+        && update.password_hash.is_none()
+        && update.timezone.is_none()
+        && update.password_reset_required.is_none()
+        && update.email.is_none()
+        && update.email_verified.is_none()
+    {
+        return Err(DomainError::Validation(
+            "no fields provided for update".into(),
+        ));
+    }
+
+    let mut builder = PostgresUserRepository::build_update_query(update);
+
+    let row = builder
+        .build_query_as::<UserRow>()
+        .fetch_optional(executor)
+        .await
+        .map_err(map_sqlx)?
+        .ok_or_else(|| DomainError::NotFound("user not found".into()))?;
+
+    User::try_from(row)
+}
+
 impl UserRepository for PostgresUserRepository {
     fn count(&self) -> BoxFuture<'_, DomainResult<u64>> {
-        boxed(async move {
+        traced("PostgresUserRepository::count", async move {
             let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(1) FROM users")
                 .fetch_one(&self.pool)
                 .await
@@ -113,25 +221,27 @@ impl UserRepository for PostgresUserRepository {
     }
 
     fn insert(&self, new_user: NewUser) -> BoxFuture<'_, DomainResult<User>> {
-        boxed(async move {
+        traced("PostgresUserRepository::insert", async move {
             let NewUser {
                 username,
                 password_hash,
                 role,
                 is_active,
                 created_at,
+                password_reset_required,
             } = new_user;
 
             let row = sqlx::query_as::<_, UserRow>(
-                "INSERT INTO users (username, password_hash, role, is_active, created_at)
-                 VALUES ($1, $2, $3, $4, $5)
-                RETURNING id, username, password_hash, role, is_active, created_at",
+                "INSERT INTO users (username, password_hash, role, is_active, created_at, password_reset_required)
+                 VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING id, username, password_hash, role, is_active, created_at, timezone, password_reset_required, email, email_verified",
             )
             .bind(username.as_str())
             .bind(password_hash.as_str())
             .bind(role)
             .bind(is_active)
             .bind(created_at)
+            .bind(password_reset_required)
             .fetch_one(&self.pool)
             .await
             .map_err(map_sqlx)?;
@@ -140,14 +250,60 @@ impl UserRepository for PostgresUserRepository {
         })
     }
 
+    fn insert_bootstrap_admin(
+        &self,
+        new_user: NewUser,
+    ) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        traced(
+            "PostgresUserRepository::insert_bootstrap_admin",
+            async move {
+                let NewUser {
+                    username,
+                    password_hash,
+                    role,
+                    is_active,
+                    created_at,
+                    password_reset_required,
+                } = new_user;
+
+                // Claiming the singleton marker serialises concurrent bootstrap
+                // attempts: the loser blocks on the primary key, then inserts nothing.
+                let row = sqlx::query_as::<_, UserRow>(
+                "WITH claimed AS (
+                    INSERT INTO admin_bootstrap (singleton, created_at)
+                    VALUES (TRUE, $5)
+                    ON CONFLICT DO NOTHING
+                    RETURNING singleton
+                 )
+                 INSERT INTO users (username, password_hash, role, is_active, created_at, password_reset_required)
+                 SELECT $1, $2, $3, $4, $5, $6 FROM claimed
+                RETURNING id, username, password_hash, role, is_active, created_at, timezone, password_reset_required, email, email_verified",
+            )
+            .bind(username.as_str())
+            .bind(password_hash.as_str())
+            .bind(role)
+            .bind(is_active)
+            .bind(created_at)
+            .bind(password_reset_required)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+
+                row.map(User::try_from).transpose()
+            },
+        )
+    }
+
     fn find_by_username<'a>(
         &'a self,
         username: &'a Username,
     ) -> BoxFuture<'a, DomainResult<Option<User>>> {
-        boxed(async move {
+        traced("PostgresUserRepository::find_by_username", async move {
+            // Cast explicitly: a plain text parameter resolves to the
+            // case-sensitive `text = text` operator despite the CITEXT column.
             let row = sqlx::query_as::<_, UserRow>(
-                "SELECT id, username, password_hash, role, is_active, created_at
-                 FROM users WHERE username = $1",
+                "SELECT id, username, password_hash, role, is_active, created_at, timezone, password_reset_required, email, email_verified
+                 FROM users WHERE username = $1::citext",
             )
             .bind(username.as_str())
             .fetch_optional(&self.pool)
@@ -159,12 +315,19 @@ impl UserRepository for PostgresUserRepository {
     }
 
     fn find_by_id(&self, id: UserId) -> BoxFuture<'_, DomainResult<Option<User>>> {
-        boxed(async move {
+        traced(
+            "PostgresUserRepository::find_by_id",
+            find_user(&self.pool, id),
+        )
+    }
+
+    fn find_by_email<'a>(&'a self, email: &'a Email) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        traced("PostgresUserRepository::find_by_email", async move {
             let row = sqlx::query_as::<_, UserRow>(
-                "SELECT id, username, password_hash, role, is_active, created_at
-                 FROM users WHERE id = $1",
+                "SELECT id, username, password_hash, role, is_active, created_at, timezone, password_reset_required, email, email_verified
+                 FROM users WHERE email = $1::citext",
             )
-            .bind(i64::from(id))
+            .bind(email.as_str())
             .fetch_optional(&self.pool)
             .await
             .map_err(map_sqlx)?;
@@ -173,48 +336,43 @@ impl UserRepository for PostgresUserRepository {
         })
     }
 
-    fn update(&self, update: UserUpdate) -> BoxFuture<'_, DomainResult<User>> {
-        boxed(async move {
-            let UserUpdate {
-                id,
-                is_active,
-                role,
-                password_hash,
-            } = update;
-
-            if is_active.is_none() && role.is_none() && password_hash.is_none() {
-                return Err(DomainError::Validation(
-                    "no fields provided for update".into(),
-                ));
-            }
-
-            let mut builder = Self::build_update_query(id, is_active, role, password_hash);
-
-            let row = builder
-                .build_query_as::<UserRow>()
-                .fetch_optional(&self.pool)
-                .await
-                .map_err(map_sqlx)?
-                .ok_or_else(|| DomainError::NotFound("user not found".into()))?;
+    fn find_by_ids<'a>(&'a self, ids: &'a [UserId]) -> BoxFuture<'a, DomainResult<Vec<User>>> {
+        traced("PostgresUserRepository::find_by_ids", async move {
+            let ids: Vec<i64> = ids.iter().copied().map(i64::from).collect();
+            let rows = sqlx::query_as::<_, UserRow>(
+                "SELECT id, username, password_hash, role, is_active, created_at, timezone, password_reset_required, email, email_verified
+                 FROM users WHERE id = ANY($1)",
+            )
+            .bind(ids)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
 
-            User::try_from(row)
+            rows.into_iter().map(User::try_from).collect()
         })
     }
 
+    fn update(&self, update: UserUpdate) -> BoxFuture<'_, DomainResult<User>> {
+        traced(
+            "PostgresUserRepository::update",
+            update_user(&self.pool, update),
+        )
+    }
+
     fn list_page<'a>(
         &'a self,
         limit: u32,
         cursor: Option<UserListCursor>,
         search: Option<&'a str>,
     ) -> BoxFuture<'a, DomainResult<(Vec<User>, Option<UserListCursor>)>> {
-        boxed(async move {
-            let limit = limit.clamp(1, 100);
+        traced("PostgresUserRepository::list_page", async move {
+            let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
             let fetch_limit = i64::from(limit) + 1;
 
             let search = Self::normalize_search(search);
 
             let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
-                "SELECT id, username, password_hash, role, is_active, created_at FROM users",
+                "SELECT id, username, password_hash, role, is_active, created_at, timezone, password_reset_required, email, email_verified FROM users",
             );
 
             let has_where = search.as_deref().is_some_and(|pattern| {
@@ -258,4 +416,187 @@ impl UserRepository for PostgresUserRepository {
             Ok((users, next_cursor))
         })
     }
+
+    fn list_page_before<'a>(
+        &'a self,
+        limit: u32,
+        cursor: UserListCursor,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, Option<UserListCursor>)>> {
+        traced("PostgresUserRepository::list_page_before", async move {
+            let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+            let fetch_limit = i64::from(limit) + 1;
+
+            let search = Self::normalize_search(search);
+
+            let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
+                "SELECT id, username, password_hash, role, is_active, created_at, timezone, password_reset_required, email, email_verified FROM users WHERE (created_at, id) > (",
+            );
+            builder.push_bind(cursor.created_at);
+            builder.push(", ");
+            builder.push_bind(i64::from(cursor.user_id));
+            builder.push(")");
+            if let Some(pattern) = search {
+                builder.push(" AND username ILIKE ");
+                builder.push_bind(pattern);
+            }
+            builder.push(" ORDER BY created_at ASC, id ASC LIMIT ");
+            builder.push_bind(fetch_limit);
+
+            let rows = builder
+                .build_query_as::<UserRow>()
+                .fetch_all(&self.pool)
+                .await
+                .map_err(map_sqlx)?;
+
+            let mut users = rows
+                .into_iter()
+                .map(User::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let prev_cursor = if users.len() > limit as usize {
+                let _ = users.pop();
+                users
+                    .last()
+                    .map(|user| UserListCursor::new(user.created_at, user.id))
+            } else {
+                None
+            };
+            users.reverse();
+
+            Ok((users, prev_cursor))
+        })
+    }
+
+    fn has_preceding<'a>(
+        &'a self,
+        cursor: &'a UserListCursor,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("PostgresUserRepository::has_preceding", async move {
+            let search = Self::normalize_search(search);
+
+            let mut builder: QueryBuilder<Postgres> =
+                QueryBuilder::new("SELECT EXISTS(SELECT 1 FROM users WHERE (created_at, id) > (");
+            builder.push_bind(cursor.created_at);
+            builder.push(", ");
+            builder.push_bind(i64::from(cursor.user_id));
+            builder.push(")");
+            if let Some(pattern) = search {
+                builder.push(" AND username ILIKE ");
+                builder.push_bind(pattern);
+            }
+            builder.push(")");
+
+            builder
+                .build_query_scalar::<bool>()
+                .fetch_one(&self.pool)
+                .await
+                .map_err(map_sqlx)
+        })
+    }
+
+    fn list_offset<'a>(
+        &'a self,
+        offset: u64,
+        limit: u32,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, u64)>> {
+        traced("PostgresUserRepository::list_offset", async move {
+            let limit = limit.clamp(1, PAGE_LIMIT_CEILING);
+            let pattern = Self::normalize_search(search);
+
+            let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
+                "SELECT id, username, password_hash, role, is_active, created_at, timezone, password_reset_required, email, email_verified FROM users",
+            );
+            if let Some(pattern) = pattern.as_deref() {
+                builder.push(" WHERE username ILIKE ");
+                builder.push_bind(pattern);
+            }
+            builder.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+            builder.push_bind(i64::from(limit));
+            builder.push(" OFFSET ");
+            builder.push_bind(i64::try_from(offset).unwrap_or(i64::MAX));
+
+            let rows = builder
+                .build_query_as::<UserRow>()
+                .fetch_all(&self.pool)
+                .await
+                .map_err(map_sqlx)?;
+            let users = rows
+                .into_iter()
+                .map(User::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+            let total = self.estimate_total(search).await?.unwrap_or_default();
+
+            Ok((users, total))
+        })
+    }
+
+    fn estimate_total<'a>(
+        &'a self,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<Option<u64>>> {
+        traced("PostgresUserRepository::estimate_total", async move {
+            let Some(pattern) = Self::normalize_search(search) else {
+                return self.count().await.map(Some);
+            };
+
+            let count =
+                sqlx::query_scalar::<_, i64>("SELECT COUNT(1) FROM users WHERE username ILIKE $1")
+                    .bind(pattern)
+                    .fetch_one(&self.pool)
+                    .await
+                    .map_err(map_sqlx)?;
+
+            Ok(u64::try_from(count).ok())
+        })
+    }
+
+    fn find_by_federated_identity<'a>(
+        &'a self,
+        provider: &'a str,
+        subject: &'a str,
+    ) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        traced(
+            "PostgresUserRepository::find_by_federated_identity",
+            async move {
+                let row = sqlx::query_as::<_, UserRow>(
+                "SELECT u.id, u.username, u.password_hash, u.role, u.is_active, u.created_at, u.timezone, u.password_reset_required, u.email, u.email_verified
+                 FROM federated_identities f JOIN users u ON u.id = f.user_id
+                 WHERE f.provider = $1 AND f.subject = $2",
+            )
+            .bind(provider)
+            .bind(subject)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+
+                row.map(User::try_from).transpose()
+            },
+        )
+    }
+
+    fn link_federated_identity<'a>(
+        &'a self,
+        provider: &'a str,
+        subject: &'a str,
+        user_id: UserId,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        traced(
+            "PostgresUserRepository::link_federated_identity",
+            async move {
+                sqlx::query(
+                "INSERT INTO federated_identities (provider, subject, user_id) VALUES ($1, $2, $3)",
+            )
+            .bind(provider)
+            .bind(subject)
+            .bind(i64::from(user_id))
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+                Ok(())
+            },
+        )
+    }
 }
@@ -0,0 +1,204 @@
+// src/infrastructure/repositories/users/roles.rs
+use super::super::map_sqlx;
+use crate::async_support::BoxFuture;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{Capability, CustomRole, RoleName, RoleStore, UserId};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+
+const ROLE_COLUMNS: &str = "name, description, capabilities, created_at, updated_at";
+
+#[derive(Clone)]
+#[must_use]
+pub struct PostgresRoleStore {
+    pool: PgPool,
+}
+
+impl PostgresRoleStore {
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct RoleRow {
+    name: String,
+    description: Option<String>,
+    capabilities: Vec<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<RoleRow> for CustomRole {
+    type Error = DomainError;
+
+    fn try_from(row: RoleRow) -> DomainResult<Self> {
+        Ok(Self {
+            name: RoleName::new(&row.name)?,
+            description: row.description,
+            capabilities: row
+                .capabilities
+                .iter()
+                .map(|cap| cap.parse())
+                .collect::<DomainResult<_>>()?,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+impl RoleStore for PostgresRoleStore {
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<CustomRole>>> {
+        traced("PostgresRoleStore::list", async move {
+            sqlx::query_as::<_, RoleRow>(&format!(
+                "SELECT {ROLE_COLUMNS} FROM custom_roles ORDER BY name"
+            ))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .into_iter()
+            .map(CustomRole::try_from)
+            .collect()
+        })
+    }
+
+    fn find<'a>(&'a self, name: &'a RoleName) -> BoxFuture<'a, DomainResult<Option<CustomRole>>> {
+        traced("PostgresRoleStore::find", async move {
+            sqlx::query_as::<_, RoleRow>(&format!(
+                "SELECT {ROLE_COLUMNS} FROM custom_roles WHERE name = $1"
+            ))
+            .bind(name.as_str())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .map(CustomRole::try_from)
+            .transpose()
+        })
+    }
+
+    fn save(&self, role: CustomRole) -> BoxFuture<'_, DomainResult<CustomRole>> {
+        traced("PostgresRoleStore::save", async move {
+            let capabilities: Vec<String> =
+                role.capabilities.iter().map(ToString::to_string).collect();
+            sqlx::query_as::<_, RoleRow>(&format!(
+                "INSERT INTO custom_roles ({ROLE_COLUMNS}) VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (name) DO UPDATE SET description = EXCLUDED.description,
+                     capabilities = EXCLUDED.capabilities, updated_at = EXCLUDED.updated_at
+                 RETURNING {ROLE_COLUMNS}"
+            ))
+            .bind(role.name.as_str())
+            .bind(&role.description)
+            .bind(&capabilities)
+            .bind(role.created_at)
+            .bind(role.updated_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .try_into()
+        })
+    }
+
+    fn delete<'a>(&'a self, name: &'a RoleName) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("PostgresRoleStore::delete", async move {
+            let result = sqlx::query("DELETE FROM custom_roles WHERE name = $1")
+                .bind(name.as_str())
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlx)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn assign<'a>(
+        &'a self,
+        user: UserId,
+        name: Option<&'a RoleName>,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        traced("PostgresRoleStore::assign", async move {
+            let query = name.map_or_else(
+                || {
+                    sqlx::query("DELETE FROM user_custom_roles WHERE user_id = $1")
+                        .bind(i64::from(user))
+                },
+                |name| {
+                    sqlx::query(
+                        "INSERT INTO user_custom_roles (user_id, role_name) VALUES ($1, $2)
+                         ON CONFLICT (user_id) DO UPDATE SET role_name = EXCLUDED.role_name",
+                    )
+                    .bind(i64::from(user))
+                    .bind(name.as_str())
+                },
+            );
+            query.execute(&self.pool).await.map_err(map_sqlx)?;
+            Ok(())
+        })
+    }
+
+    fn role_of(&self, user: UserId) -> BoxFuture<'_, DomainResult<Option<CustomRole>>> {
+        traced("PostgresRoleStore::role_of", async move {
+            sqlx::query_as::<_, RoleRow>(
+                "SELECT r.name, r.description, r.capabilities, r.created_at, r.updated_at
+                 FROM custom_roles r JOIN user_custom_roles u ON u.role_name = r.name
+                 WHERE u.user_id = $1",
+            )
+            .bind(i64::from(user))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .map(CustomRole::try_from)
+            .transpose()
+        })
+    }
+
+    fn grant_capability<'a>(
+        &'a self,
+        user: UserId,
+        capability: &'a Capability,
+    ) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("PostgresRoleStore::grant_capability", async move {
+            let result = sqlx::query(
+                "INSERT INTO user_capabilities (user_id, capability) VALUES ($1, $2)
+                 ON CONFLICT DO NOTHING",
+            )
+            .bind(i64::from(user))
+            .bind(capability.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn revoke_capability<'a>(
+        &'a self,
+        user: UserId,
+        capability: &'a Capability,
+    ) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("PostgresRoleStore::revoke_capability", async move {
+            let result =
+                sqlx::query("DELETE FROM user_capabilities WHERE user_id = $1 AND capability = $2")
+                    .bind(i64::from(user))
+                    .bind(capability.to_string())
+                    .execute(&self.pool)
+                    .await
+                    .map_err(map_sqlx)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn capabilities_of(&self, user: UserId) -> BoxFuture<'_, DomainResult<Vec<Capability>>> {
+        traced("PostgresRoleStore::capabilities_of", async move {
+            sqlx::query_scalar::<_, String>(
+                "SELECT capability FROM user_capabilities WHERE user_id = $1 ORDER BY capability",
+            )
+            .bind(i64::from(user))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .iter()
+            .map(|cap| cap.parse())
+            .collect()
+        })
+    }
+}
@@ -0,0 +1,87 @@
+// src/infrastructure/repositories/users/tokens.rs
+use crate::application::ports::user_tokens::{TokenPurpose, UserToken, UserTokenStore};
+use crate::application::{AppError, AppResult};
+use crate::async_support::BoxFuture;
+use crate::domain::UserId;
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool};
+
+/// Mailed user tokens in the `user_tokens` table.
+#[derive(Clone)]
+#[must_use]
+pub struct PostgresUserTokenStore {
+    pool: PgPool,
+}
+
+impl PostgresUserTokenStore {
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn store_error(err: &sqlx::Error) -> AppError {
+    AppError::infrastructure(format!("user tokens: {err}"))
+}
+
+impl UserTokenStore for PostgresUserTokenStore {
+    fn create(&self, token: UserToken) -> BoxFuture<'_, AppResult<()>> {
+        traced("PostgresUserTokenStore::create", async move {
+            let mut tx = self.pool.begin().await.map_err(|err| store_error(&err))?;
+            sqlx::query("DELETE FROM user_tokens WHERE user_id = $1 AND purpose = $2")
+                .bind(i64::from(token.user_id))
+                .bind(token.purpose.as_str())
+                .execute(&mut *tx)
+                .await
+                .map_err(|err| store_error(&err))?;
+            sqlx::query(
+                "INSERT INTO user_tokens (token_hash, user_id, purpose, created_at, expires_at)
+                 VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(&token.token_hash)
+            .bind(i64::from(token.user_id))
+            .bind(token.purpose.as_str())
+            .bind(token.created_at)
+            .bind(token.expires_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| store_error(&err))?;
+            tx.commit().await.map_err(|err| store_error(&err))
+        })
+    }
+
+    fn consume<'a>(
+        &'a self,
+        token_hash: &'a str,
+        purpose: TokenPurpose,
+        now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<Option<UserId>>> {
+        traced(
+            "PostgresUserTokenStore::consume",
+            consume_token(&self.pool, token_hash, purpose, now),
+        )
+    }
+}
+
+/// Remove the token with `token_hash` and `purpose` on `executor` and return
+/// its user, unless it expired before `now`.
+pub(in crate::infrastructure::repositories) async fn consume_token<'c>(
+    executor: impl PgExecutor<'c>,
+    token_hash: &str,
+    purpose: TokenPurpose,
+    now: DateTime<Utc>,
+) -> AppResult<Option<UserId>> {
+    let row = sqlx::query_as::<_, (i64, DateTime<Utc>)>(
+        "DELETE FROM user_tokens WHERE token_hash = $1 AND purpose = $2
+         RETURNING user_id, expires_at",
+    )
+    .bind(token_hash)
+    .bind(purpose.as_str())
+    .fetch_optional(executor)
+    .await
+    .map_err(|err| store_error(&err))?;
+
+    row.filter(|(_, expires_at)| *expires_at > now)
+        .map(|(user_id, _)| UserId::new(user_id).map_err(AppError::from))
+        .transpose()
+}
@@ -0,0 +1,3 @@
+mod postgres;
+
+pub use postgres::PostgresWebhookRepository;
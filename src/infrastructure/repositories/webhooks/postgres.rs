@@ -0,0 +1,260 @@
+// src/infrastructure/repositories/webhooks/postgres.rs
+use super::super::map_sqlx;
+use crate::async_support::BoxFuture;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::{
+    NewWebhook, NewWebhookDeliveryAttempt, UserId, Webhook, WebhookDeliveryAttempt, WebhookEvent,
+    WebhookRepository,
+};
+use crate::infrastructure::repositories::traced;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+
+const WEBHOOK_COLUMNS: &str = "id, url, secret, events, active, created_by, created_at, updated_at";
+const ATTEMPT_COLUMNS: &str = "id, webhook_id, delivery_id, event, attempt, status_code, error, \
+                               succeeded, duration_ms, attempted_at";
+
+#[derive(Clone)]
+#[must_use]
+pub struct PostgresWebhookRepository {
+    pool: PgPool,
+}
+
+impl PostgresWebhookRepository {
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct WebhookRow {
+    id: i64,
+    url: String,
+    secret: String,
+    events: Vec<String>,
+    active: bool,
+    created_by: Option<i64>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<WebhookRow> for Webhook {
+    type Error = DomainError;
+
+    fn try_from(row: WebhookRow) -> DomainResult<Self> {
+        Ok(Self {
+            id: row.id,
+            url: row.url,
+            secret: row.secret,
+            events: row
+                .events
+                .iter()
+                .map(|event| event.parse())
+                .collect::<DomainResult<_>>()?,
+            active: row.active,
+            created_by: row.created_by.map(UserId::new).transpose()?,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct AttemptRow {
+    id: i64,
+    webhook_id: i64,
+    delivery_id: String,
+    event: String,
+    attempt: i32,
+    status_code: Option<i32>,
+    error: Option<String>,
+    succeeded: bool,
+    duration_ms: i64,
+    attempted_at: DateTime<Utc>,
+}
+
+impl TryFrom<AttemptRow> for WebhookDeliveryAttempt {
+    type Error = DomainError;
+
+    fn try_from(row: AttemptRow) -> DomainResult<Self> {
+        let invalid = |field: &str| {
+            DomainError::Persistence(format!(
+                "webhook delivery attempt {} has an invalid {field}",
+                row.id
+            ))
+        };
+        Ok(Self {
+            id: row.id,
+            webhook_id: row.webhook_id,
+            delivery_id: row.delivery_id.clone(),
+            event: row.event.parse()?,
+            attempt: u32::try_from(row.attempt).map_err(|_| invalid("attempt"))?,
+            status_code: row
+                .status_code
+                .map(u16::try_from)
+                .transpose()
+                .map_err(|_| invalid("status code"))?,
+            error: row.error.clone(),
+            succeeded: row.succeeded,
+            duration_ms: u64::try_from(row.duration_ms).map_err(|_| invalid("duration"))?,
+            attempted_at: row.attempted_at,
+        })
+    }
+}
+
+fn event_names(events: &[WebhookEvent]) -> Vec<&'static str> {
+    events.iter().map(|event| event.as_str()).collect()
+}
+
+impl WebhookRepository for PostgresWebhookRepository {
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<Webhook>>> {
+        traced("PostgresWebhookRepository::list", async move {
+            sqlx::query_as::<_, WebhookRow>(&format!(
+                "SELECT {WEBHOOK_COLUMNS} FROM webhooks ORDER BY id"
+            ))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .into_iter()
+            .map(Webhook::try_from)
+            .collect()
+        })
+    }
+
+    fn find_by_id(&self, id: i64) -> BoxFuture<'_, DomainResult<Option<Webhook>>> {
+        traced("PostgresWebhookRepository::find_by_id", async move {
+            sqlx::query_as::<_, WebhookRow>(&format!(
+                "SELECT {WEBHOOK_COLUMNS} FROM webhooks WHERE id = $1"
+            ))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .map(TryInto::try_into)
+            .transpose()
+        })
+    }
+
+    fn subscribed(&self, event: WebhookEvent) -> BoxFuture<'_, DomainResult<Vec<Webhook>>> {
+        traced("PostgresWebhookRepository::subscribed", async move {
+            sqlx::query_as::<_, WebhookRow>(&format!(
+                "SELECT {WEBHOOK_COLUMNS} FROM webhooks
+                 WHERE active AND $1 = ANY(events)
+                 ORDER BY id"
+            ))
+            .bind(event.as_str())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .into_iter()
+            .map(Webhook::try_from)
+            .collect()
+        })
+    }
+
+    fn insert(&self, webhook: NewWebhook) -> BoxFuture<'_, DomainResult<Webhook>> {
+        traced("PostgresWebhookRepository::insert", async move {
+            sqlx::query_as::<_, WebhookRow>(&format!(
+                "INSERT INTO webhooks
+                     (url, secret, events, active, created_by, created_at, updated_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $6)
+                 RETURNING {WEBHOOK_COLUMNS}"
+            ))
+            .bind(&webhook.url)
+            .bind(&webhook.secret)
+            .bind(event_names(&webhook.events))
+            .bind(webhook.active)
+            .bind(webhook.created_by.map(i64::from))
+            .bind(webhook.created_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .try_into()
+        })
+    }
+
+    fn update<'a>(&'a self, webhook: &'a Webhook) -> BoxFuture<'a, DomainResult<bool>> {
+        traced("PostgresWebhookRepository::update", async move {
+            let result = sqlx::query(
+                "UPDATE webhooks
+                 SET url = $2, secret = $3, events = $4, active = $5, updated_at = $6
+                 WHERE id = $1",
+            )
+            .bind(webhook.id)
+            .bind(&webhook.url)
+            .bind(&webhook.secret)
+            .bind(event_names(&webhook.events))
+            .bind(webhook.active)
+            .bind(webhook.updated_at)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn delete(&self, id: i64) -> BoxFuture<'_, DomainResult<bool>> {
+        traced("PostgresWebhookRepository::delete", async move {
+            let result = sqlx::query("DELETE FROM webhooks WHERE id = $1")
+                .bind(id)
+                .execute(&self.pool)
+                .await
+                .map_err(map_sqlx)?;
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn record_attempt(
+        &self,
+        attempt: NewWebhookDeliveryAttempt,
+    ) -> BoxFuture<'_, DomainResult<WebhookDeliveryAttempt>> {
+        traced("PostgresWebhookRepository::record_attempt", async move {
+            let number = i32::try_from(attempt.attempt)
+                .map_err(|_| DomainError::Validation("attempt number is too large".into()))?;
+            let duration_ms = i64::try_from(attempt.duration_ms).unwrap_or(i64::MAX);
+            sqlx::query_as::<_, AttemptRow>(&format!(
+                "INSERT INTO webhook_deliveries
+                     (webhook_id, delivery_id, event, attempt, status_code, error, succeeded,
+                      duration_ms, attempted_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                 RETURNING {ATTEMPT_COLUMNS}"
+            ))
+            .bind(attempt.webhook_id)
+            .bind(&attempt.delivery_id)
+            .bind(attempt.event.as_str())
+            .bind(number)
+            .bind(attempt.status_code.map(i32::from))
+            .bind(&attempt.error)
+            .bind(attempt.succeeded)
+            .bind(duration_ms)
+            .bind(attempt.attempted_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .try_into()
+        })
+    }
+
+    fn list_attempts(
+        &self,
+        webhook_id: i64,
+        limit: u32,
+    ) -> BoxFuture<'_, DomainResult<Vec<WebhookDeliveryAttempt>>> {
+        traced("PostgresWebhookRepository::list_attempts", async move {
+            sqlx::query_as::<_, AttemptRow>(&format!(
+                "SELECT {ATTEMPT_COLUMNS} FROM webhook_deliveries
+                 WHERE webhook_id = $1
+                 ORDER BY id DESC
+                 LIMIT $2"
+            ))
+            .bind(webhook_id)
+            .bind(i64::from(limit))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_sqlx)?
+            .into_iter()
+            .map(WebhookDeliveryAttempt::try_from)
+            .collect()
+        })
+    }
+}
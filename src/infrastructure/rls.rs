@@ -0,0 +1,90 @@
+// src/infrastructure/rls.rs
+//! Row-level security context.
+//!
+//! When enabled, each request runs inside [`scope`] and the authenticated
+//! user is recorded with [`set_current_user`]. Repositories open their
+//! transactions through [`begin`], which copies the user and the current
+//! tenant into the `app.current_user_id` / `app.current_site_id` settings
+//! (transaction-local, like `SET LOCAL`) so the policies from
+//! `migrations/0009_row_level_security.sql` can enforce them.
+use crate::domain::UserId;
+use crate::infrastructure::tenancy;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::cell::Cell;
+use std::future::Future;
+
+tokio::task_local! {
+    static CURRENT_USER: Cell<Option<i64>>;
+}
+
+/// Run `fut` with an empty row-level security context.
+pub async fn scope<F>(fut: F) -> F::Output
+where
+    F: Future,
+{
+    CURRENT_USER.scope(Cell::new(None), fut).await
+}
+
+/// Record the authenticated user for the current scope. No-op outside
+/// [`scope`].
+pub fn set_current_user(user_id: UserId) {
+    let _ = CURRENT_USER.try_with(|current| current.set(Some(i64::from(user_id))));
+}
+
+/// User recorded for the current scope, if any.
+#[must_use]
+pub fn current_user() -> Option<i64> {
+    CURRENT_USER.try_with(Cell::get).ok().flatten()
+}
+
+fn in_scope() -> bool {
+    CURRENT_USER.try_with(|_| ()).is_ok()
+}
+
+/// Begin a transaction carrying the current row-level security context.
+///
+/// Outside [`scope`] this is a plain transaction, leaving the settings unset.
+///
+/// # Errors
+///
+/// Returns any `sqlx` error raised while starting the transaction or applying
+/// the settings.
+pub async fn begin(pool: &PgPool) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    if in_scope() {
+        sqlx::query(
+            "SELECT set_config('app.current_user_id', $1, true), set_config('app.current_site_id', $2, true)",
+        )
+        .bind(current_user().map(|id| id.to_string()).unwrap_or_default())
+        .bind(
+            tenancy::current()
+                .map(|tenant| tenant.id().to_string())
+                .unwrap_or_default(),
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    Ok(tx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{current_user, scope, set_current_user};
+    use crate::domain::UserId;
+
+    #[tokio::test]
+    async fn current_user_is_only_recorded_inside_a_scope() {
+        set_current_user(UserId::new(7).unwrap());
+        assert_eq!(current_user(), None);
+
+        let recorded = scope(async {
+            assert_eq!(current_user(), None);
+            set_current_user(UserId::new(7).unwrap());
+            current_user()
+        })
+        .await;
+        assert_eq!(recorded, Some(7));
+    }
+}
@@ -0,0 +1,212 @@
+// src/infrastructure/scheduler/cron.rs
+//! Five-field cron expressions (`minute hour day-of-month month
+//! day-of-week`), evaluated in UTC.
+//!
+//! Fields take `*`, numbers, ranges (`1-5`), steps (`*/15`, `0-30/10`) and
+//! comma-separated lists of those. Day-of-week runs from 0 (Sunday) to 7
+//! (Sunday again). As in Vixie cron, when both day fields are restricted a
+//! day matching either one qualifies. `@hourly`, `@daily`, `@weekly`,
+//! `@monthly` and `@yearly` are accepted as shorthands.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeDelta, Timelike, Utc};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+/// Furthest ahead [`CronSchedule::next_after`] looks, so impossible dates
+/// such as `0 0 30 2 *` end the search.
+const SEARCH_YEARS: i32 = 5;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("invalid cron expression '{expression}': {reason}")]
+pub struct CronError {
+    expression: String,
+    reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Day-of-month and day-of-week are both restricted, so either matches.
+    either_day: bool,
+}
+
+impl CronSchedule {
+    /// First matching minute strictly after `after`, or `None` when nothing
+    /// matches within the next few years.
+    #[must_use]
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let limit = after.with_year(after.year() + SEARCH_YEARS)?;
+        let mut at = (after + Duration::minutes(1))
+            .with_second(0)?
+            .with_nanosecond(0)?;
+        while at <= limit {
+            if !bit(self.months, at.month()) {
+                at = start_of_month(at.year(), at.month() + 1)?;
+            } else if !self.day_matches(at) {
+                at = start_of_day(at.date_naive().succ_opt()?);
+            } else if !bit(self.hours, at.hour()) {
+                at = (at + TimeDelta::hours(1)).with_minute(0)?;
+            } else if !bit(self.minutes, at.minute()) {
+                at += TimeDelta::minutes(1);
+            } else {
+                return Some(at);
+            }
+        }
+        None
+    }
+
+    fn day_matches(&self, at: DateTime<Utc>) -> bool {
+        let day = bit(self.days, at.day());
+        let weekday = bit(self.weekdays, at.weekday().num_days_from_sunday());
+        if self.either_day {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = CronError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let error = |reason: String| CronError {
+            expression: expression.to_string(),
+            reason,
+        };
+        let expanded = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(error(format!("expected 5 fields, got {}", fields.len())));
+        };
+        let mut weekdays = parse_field(weekday, 0, 7).map_err(error)?;
+        if bit(weekdays, 7) {
+            weekdays |= 1;
+        }
+        Ok(Self {
+            expression: expression.trim().to_string(),
+            minutes: parse_field(minute, 0, 59).map_err(error)?,
+            hours: parse_field(hour, 0, 23).map_err(error)?,
+            days: parse_field(day, 1, 31).map_err(error)?,
+            months: parse_field(month, 1, 12).map_err(error)?,
+            weekdays,
+            either_day: !day.starts_with('*') && !weekday.starts_with('*'),
+        })
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+const fn bit(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let number = |value: &str| {
+        value
+            .parse::<u32>()
+            .map_err(|_| format!("'{value}' is not a number"))
+    };
+    let mut set = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, Some(number(step)?)),
+            None => (part, None),
+        };
+        let (low, high) = if range == "*" {
+            (min, max)
+        } else if let Some((low, high)) = range.split_once('-') {
+            (number(low)?, number(high)?)
+        } else {
+            let value = number(range)?;
+            (value, if step.is_some() { max } else { value })
+        };
+        if low < min || high > max || low > high {
+            return Err(format!("'{part}' is outside {min}-{max}"));
+        }
+        let step = step.unwrap_or(1);
+        if step == 0 {
+            return Err(format!("'{part}' has a zero step"));
+        }
+        for value in (low..=high).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+const fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(chrono::NaiveTime::MIN).and_utc()
+}
+
+fn start_of_month(year: i32, month: u32) -> Option<DateTime<Utc>> {
+    let (year, month) = if month > 12 {
+        (year + 1, 1)
+    } else {
+        (year, month)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1).map(start_of_day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    fn next(expression: &str, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        expression
+            .parse::<CronSchedule>()
+            .unwrap()
+            .next_after(after)
+    }
+
+    #[test]
+    fn next_run_follows_each_field() {
+        let now = at(2025, 1, 31, 10, 7);
+        assert_eq!(next("*/15 * * * *", now), Some(at(2025, 1, 31, 10, 15)));
+        assert_eq!(next("@hourly", now), Some(at(2025, 1, 31, 11, 0)));
+        assert_eq!(next("30 2 * * *", now), Some(at(2025, 2, 1, 2, 30)));
+        assert_eq!(next("0 0 1 */3 *", now), Some(at(2025, 4, 1, 0, 0)));
+        // 2025-01-31 is a Friday, past 09:00.
+        assert_eq!(next("0 9 * * 1-5", now), Some(at(2025, 2, 3, 9, 0)));
+        assert_eq!(next("0 0 * * 7", now), Some(at(2025, 2, 2, 0, 0)));
+        // Restricted day-of-month and day-of-week: either one qualifies.
+        assert_eq!(next("0 0 15 * 0", now), Some(at(2025, 2, 2, 0, 0)));
+        assert_eq!(next("0 0 29 2 *", now), Some(at(2028, 2, 29, 0, 0)));
+        assert_eq!(next("0 0 30 2 *", now), None);
+    }
+
+    #[test]
+    fn malformed_expressions_are_rejected() {
+        for expression in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+        ] {
+            assert!(expression.parse::<CronSchedule>().is_err(), "{expression}");
+        }
+    }
+}
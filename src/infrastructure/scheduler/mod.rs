@@ -0,0 +1,481 @@
+// src/infrastructure/scheduler/mod.rs
+//! Runs registered jobs on cron schedules.
+//!
+//! Each job waits for its next cron time plus a random jitter, takes the
+//! job's lock from the [`LockManager`] and runs on its own task, so a panic
+//! is reported as a failed run instead of stopping the schedule. A run is
+//! skipped while another one (in this or another instance) holds the lock.
+//! Time is read from the [`Clock`], so simulated time moves schedules too.
+
+mod cron;
+mod runs;
+
+pub use cron::{CronError, CronSchedule};
+pub use runs::{InMemoryJobRunStore, PostgresJobRunStore};
+
+use crate::application::{
+    AppError, AppResult,
+    dto::jobs::{JobRunDto, JobRunOutcome, JobStatusDto, JobTrigger},
+    ports::{
+        jobs::{JobControl, JobRunStore, LockLease, LockManager},
+        time::Clock,
+    },
+};
+use crate::async_support::{BoxFuture, boxed};
+use chrono::{DateTime, Utc};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Longest single sleep while waiting for a run, so a clock that jumps
+/// ahead is noticed.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+type RunFn = dyn Fn() -> BoxFuture<'static, AppResult<()>> + Send + Sync;
+
+pub struct Job {
+    name: &'static str,
+    schedule: CronSchedule,
+    run: Arc<RunFn>,
+}
+
+impl Job {
+    pub fn new(
+        name: &'static str,
+        schedule: CronSchedule,
+        run: impl Fn() -> BoxFuture<'static, AppResult<()>> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name,
+            schedule,
+            run: Arc::new(run),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SchedulerOptions {
+    /// Most a run is delayed past its cron time, to spread instances out.
+    pub jitter: Duration,
+    /// How long a run's lock lasts; a run outliving it may overlap the next.
+    pub lock_ttl: Duration,
+}
+
+impl Default for SchedulerOptions {
+    fn default() -> Self {
+        Self {
+            jitter: Duration::from_secs(30),
+            lock_ttl: Duration::from_hours(1),
+        }
+    }
+}
+
+struct Entry {
+    job: Job,
+    status: Mutex<JobStatusDto>,
+}
+
+impl Entry {
+    fn new(job: Job) -> Self {
+        Self {
+            status: Mutex::new(JobStatusDto {
+                name: job.name.to_string(),
+                schedule: job.schedule.to_string(),
+                running: false,
+                next_run_at: None,
+                last_started_at: None,
+                last_finished_at: None,
+                last_outcome: None,
+                last_error: None,
+                consecutive_failures: 0,
+            }),
+            job,
+        }
+    }
+
+    fn update(&self, change: impl FnOnce(&mut JobStatusDto)) {
+        change(
+            &mut self
+                .status
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        );
+    }
+}
+
+/// What a run needs besides its job; cheap to clone into spawned tasks.
+#[derive(Clone)]
+struct Runner {
+    clock: Arc<dyn Clock>,
+    locks: Arc<dyn LockManager>,
+    runs: Arc<dyn JobRunStore>,
+    lock_ttl: Duration,
+}
+
+pub struct Scheduler {
+    runner: Runner,
+    jitter: Duration,
+    entries: Mutex<Vec<Arc<Entry>>>,
+}
+
+impl Scheduler {
+    #[must_use]
+    pub fn new(
+        clock: Arc<dyn Clock>,
+        locks: Arc<dyn LockManager>,
+        runs: Arc<dyn JobRunStore>,
+        options: SchedulerOptions,
+    ) -> Self {
+        Self {
+            runner: Runner {
+                clock,
+                locks,
+                runs,
+                lock_ttl: options.lock_ttl,
+            },
+            jitter: options.jitter,
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Add `job` and start running it on its schedule. Must be called inside
+    /// a Tokio runtime.
+    pub fn schedule(&self, job: Job) {
+        let entry = Arc::new(Entry::new(job));
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(Arc::clone(&entry));
+        tracing::info!(job = entry.job.name, schedule = %entry.job.schedule, "job scheduled");
+        tokio::spawn(run_schedule(entry, self.runner.clone(), self.jitter));
+    }
+
+    fn entry(&self, name: &str) -> AppResult<Arc<Entry>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .find(|entry| entry.job.name == name)
+            .cloned()
+            .ok_or_else(|| AppError::not_found(format!("unknown job '{name}'")))
+    }
+}
+
+impl JobControl for Scheduler {
+    fn job_statuses(&self) -> Vec<JobStatusDto> {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .map(|entry| {
+                entry
+                    .status
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .clone()
+            })
+            .collect()
+    }
+
+    fn trigger<'a>(
+        &'a self,
+        job: &'a str,
+        requested_by: i64,
+    ) -> BoxFuture<'a, AppResult<JobRunDto>> {
+        boxed(async move {
+            let entry = self.entry(job)?;
+            let (lease, run) = self
+                .runner
+                .begin(&entry, JobTrigger::Manual, Some(requested_by))
+                .await?
+                .ok_or_else(|| AppError::conflict(format!("job '{job}' is already running")))?;
+            tracing::info!(job, requested_by, run = run.id, "job run requested");
+            let runner = self.runner.clone();
+            let started = run.clone();
+            tokio::spawn(async move { runner.execute(&entry, lease, started).await });
+            Ok(run)
+        })
+    }
+
+    fn runs<'a>(&'a self, job: &'a str, limit: u32) -> BoxFuture<'a, AppResult<Vec<JobRunDto>>> {
+        boxed(async move {
+            self.entry(job)?;
+            self.runner.runs.recent(job, limit).await
+        })
+    }
+}
+
+async fn run_schedule(entry: Arc<Entry>, runner: Runner, jitter_max: Duration) {
+    loop {
+        let Some(next) = entry.job.schedule.next_after(runner.clock.now()) else {
+            tracing::warn!(job = entry.job.name, "schedule has no upcoming run");
+            entry.update(|status| status.next_run_at = None);
+            return;
+        };
+        let due = next + jitter(jitter_max);
+        entry.update(|status| status.next_run_at = Some(due));
+        wait_until(runner.clock.as_ref(), due).await;
+        runner.run_scheduled(&entry).await;
+    }
+}
+
+async fn wait_until(clock: &dyn Clock, due: DateTime<Utc>) {
+    loop {
+        let Ok(remaining) = (due - clock.now()).to_std() else {
+            return;
+        };
+        if remaining.is_zero() {
+            return;
+        }
+        tokio::time::sleep(remaining.min(POLL_INTERVAL)).await;
+    }
+}
+
+impl Runner {
+    async fn run_scheduled(&self, entry: &Entry) {
+        let name = entry.job.name;
+        match self.begin(entry, JobTrigger::Schedule, None).await {
+            Ok(Some((lease, run))) => self.execute(entry, lease, run).await,
+            Ok(None) => {
+                tracing::info!(job = name, "job still running elsewhere, run skipped");
+                entry.update(|status| status.last_outcome = Some(JobRunOutcome::Skipped));
+            }
+            Err(err) => {
+                tracing::warn!(job = name, error = %err, "could not start job");
+                entry.update(|status| record_failure(status, err.to_string()));
+            }
+        }
+    }
+
+    /// Take the job's lock and record the start of a run. `None` while the
+    /// lock is held elsewhere.
+    async fn begin(
+        &self,
+        entry: &Entry,
+        trigger: JobTrigger,
+        triggered_by: Option<i64>,
+    ) -> AppResult<Option<(LockLease, JobRunDto)>> {
+        let name = entry.job.name;
+        let Some(lease) = self
+            .locks
+            .try_acquire(&format!("job:{name}"), self.lock_ttl)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let started_at = self.clock.now();
+        let id = match self
+            .runs
+            .start(name, trigger, triggered_by, started_at)
+            .await
+        {
+            Ok(id) => id,
+            Err(err) => {
+                self.release(name, &lease).await;
+                return Err(err);
+            }
+        };
+        entry.update(|status| {
+            status.running = true;
+            status.last_started_at = Some(started_at);
+        });
+        Ok(Some((
+            lease,
+            JobRunDto {
+                id,
+                job: name.to_string(),
+                trigger,
+                triggered_by,
+                started_at,
+                finished_at: None,
+                outcome: None,
+                error: None,
+            },
+        )))
+    }
+
+    /// Run the job on its own task, then release its lock and record how the
+    /// run went.
+    async fn execute(&self, entry: &Entry, lease: LockLease, run: JobRunDto) {
+        let name = entry.job.name;
+        let result = tokio::spawn((entry.job.run)()).await;
+        self.release(name, &lease).await;
+
+        let finished_at = self.clock.now();
+        let error = match result {
+            Ok(Ok(())) => None,
+            Ok(Err(err)) => Some(err.to_string()),
+            Err(_) => Some("job panicked".to_string()),
+        };
+        let outcome = if let Some(error) = &error {
+            tracing::warn!(job = name, run = run.id, error, "job failed");
+            JobRunOutcome::Failed
+        } else {
+            tracing::debug!(job = name, run = run.id, "job finished");
+            JobRunOutcome::Succeeded
+        };
+        if let Err(err) = self
+            .runs
+            .finish(run.id, finished_at, outcome, error.as_deref())
+            .await
+        {
+            tracing::warn!(job = name, run = run.id, error = %err, "could not record job run");
+        }
+        entry.update(|status| {
+            status.running = false;
+            status.last_finished_at = Some(finished_at);
+            if let Some(error) = error {
+                record_failure(status, error);
+            } else {
+                status.last_outcome = Some(JobRunOutcome::Succeeded);
+                status.last_error = None;
+                status.consecutive_failures = 0;
+            }
+        });
+    }
+
+    async fn release(&self, name: &str, lease: &LockLease) {
+        if let Err(err) = self.locks.release(lease).await {
+            tracing::warn!(job = name, error = %err, "could not release job lock");
+        }
+    }
+}
+
+fn record_failure(status: &mut JobStatusDto, error: String) {
+    status.last_outcome = Some(JobRunOutcome::Failed);
+    status.last_error = Some(error);
+    status.consecutive_failures += 1;
+}
+
+/// A random delay of at most `max`.
+fn jitter(max: Duration) -> chrono::Duration {
+    let max_ms = u64::try_from(max.as_millis()).unwrap_or(u64::MAX);
+    if max_ms == 0 {
+        return chrono::Duration::zero();
+    }
+    let mut bytes = [0_u8; 8];
+    if getrandom::fill(&mut bytes).is_err() {
+        return chrono::Duration::zero();
+    }
+    let ms = u64::from_le_bytes(bytes) % (max_ms + 1);
+    chrono::Duration::milliseconds(i64::try_from(ms).unwrap_or(i64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::{locks::InMemoryLockManager, time::SystemClock};
+
+    fn runner() -> Runner {
+        Runner {
+            clock: Arc::new(SystemClock),
+            locks: Arc::new(InMemoryLockManager::default()),
+            runs: Arc::new(InMemoryJobRunStore::default()),
+            lock_ttl: Duration::from_mins(1),
+        }
+    }
+
+    fn entry(run: impl Fn() -> BoxFuture<'static, AppResult<()>> + Send + Sync + 'static) -> Entry {
+        Entry::new(Job::new("test", "@hourly".parse().unwrap(), run))
+    }
+
+    fn status(entry: &Entry) -> JobStatusDto {
+        entry.status.lock().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn runs_record_outcomes_and_skip_while_locked() {
+        let runner = runner();
+
+        let failing = entry(|| boxed(async { Err(AppError::infrastructure("boom")) }));
+        runner.run_scheduled(&failing).await;
+        runner.run_scheduled(&failing).await;
+        let failed = status(&failing);
+        assert_eq!(failed.last_outcome, Some(JobRunOutcome::Failed));
+        assert_eq!(failed.consecutive_failures, 2);
+        assert!(!failed.running);
+
+        let panicking = entry(|| boxed(async { panic!("job bug") }));
+        runner.run_scheduled(&panicking).await;
+        assert_eq!(
+            status(&panicking).last_error.as_deref(),
+            Some("job panicked")
+        );
+
+        let working = entry(|| boxed(async { Ok(()) }));
+        let ttl = Duration::from_mins(1);
+        let held = runner
+            .locks
+            .try_acquire("job:test", ttl)
+            .await
+            .unwrap()
+            .unwrap();
+        runner.run_scheduled(&working).await;
+        assert_eq!(status(&working).last_outcome, Some(JobRunOutcome::Skipped));
+        assert!(status(&working).last_started_at.is_none());
+
+        runner.locks.release(&held).await.unwrap();
+        runner.run_scheduled(&working).await;
+        let succeeded = status(&working);
+        assert_eq!(succeeded.last_outcome, Some(JobRunOutcome::Succeeded));
+        assert_eq!(succeeded.consecutive_failures, 0);
+        assert!(succeeded.last_finished_at.is_some());
+
+        // Skipped runs are not recorded; the others are, newest first.
+        let history = runner.runs.recent("test", 10).await.unwrap();
+        let outcomes: Vec<_> = history.iter().map(|run| run.outcome).collect();
+        assert_eq!(
+            outcomes,
+            [
+                Some(JobRunOutcome::Succeeded),
+                Some(JobRunOutcome::Failed),
+                Some(JobRunOutcome::Failed),
+                Some(JobRunOutcome::Failed),
+            ]
+        );
+        assert_eq!(
+            history[3].error.as_deref(),
+            Some("infrastructure failure: boom")
+        );
+    }
+
+    #[tokio::test]
+    async fn manual_runs_are_recorded_and_never_overlap() {
+        let gate = Arc::new(tokio::sync::Semaphore::new(0));
+        let job_gate = Arc::clone(&gate);
+        let runner = runner();
+        let scheduler = Scheduler {
+            runner: runner.clone(),
+            jitter: Duration::ZERO,
+            entries: Mutex::new(vec![Arc::new(entry(move || {
+                let gate = Arc::clone(&job_gate);
+                boxed(async move {
+                    gate.acquire().await.unwrap().forget();
+                    Ok(())
+                })
+            }))]),
+        };
+
+        assert!(matches!(
+            scheduler.trigger("missing", 1).await,
+            Err(AppError::NotFound(_))
+        ));
+        let run = scheduler.trigger("test", 7).await.unwrap();
+        assert_eq!(run.trigger, JobTrigger::Manual);
+        assert_eq!(run.triggered_by, Some(7));
+        assert!(run.outcome.is_none());
+        assert!(matches!(
+            scheduler.trigger("test", 7).await,
+            Err(AppError::Conflict(_))
+        ));
+
+        gate.add_permits(1);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let history = scheduler.runs("test", 10).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].id, run.id);
+        assert_eq!(history[0].outcome, Some(JobRunOutcome::Succeeded));
+        assert!(scheduler.trigger("test", 7).await.is_ok());
+    }
+}
@@ -0,0 +1,209 @@
+// src/infrastructure/scheduler/runs.rs
+//! [`JobRunStore`] implementations.
+
+use crate::application::{
+    AppError, AppResult,
+    dto::jobs::{JobRunDto, JobRunOutcome, JobTrigger},
+    ports::jobs::JobRunStore,
+};
+use crate::async_support::{BoxFuture, boxed};
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use std::sync::Mutex;
+
+/// Keeps run history in memory; it is lost on restart.
+#[derive(Debug, Default)]
+pub struct InMemoryJobRunStore {
+    runs: Mutex<Vec<JobRunDto>>,
+}
+
+impl JobRunStore for InMemoryJobRunStore {
+    fn start<'a>(
+        &'a self,
+        job: &'a str,
+        trigger: JobTrigger,
+        triggered_by: Option<i64>,
+        started_at: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<i64>> {
+        boxed(async move {
+            let mut runs = self
+                .runs
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            let id = i64::try_from(runs.len()).unwrap_or(i64::MAX) + 1;
+            runs.push(JobRunDto {
+                id,
+                job: job.to_string(),
+                trigger,
+                triggered_by,
+                started_at,
+                finished_at: None,
+                outcome: None,
+                error: None,
+            });
+            drop(runs);
+            Ok(id)
+        })
+    }
+
+    fn finish<'a>(
+        &'a self,
+        id: i64,
+        finished_at: DateTime<Utc>,
+        outcome: JobRunOutcome,
+        error: Option<&'a str>,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let mut runs = self
+                .runs
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            if let Some(run) = runs.iter_mut().find(|run| run.id == id) {
+                run.finished_at = Some(finished_at);
+                run.outcome = Some(outcome);
+                run.error = error.map(ToString::to_string);
+            }
+            drop(runs);
+            Ok(())
+        })
+    }
+
+    fn recent<'a>(&'a self, job: &'a str, limit: u32) -> BoxFuture<'a, AppResult<Vec<JobRunDto>>> {
+        boxed(async move {
+            let runs = self
+                .runs
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            Ok(runs
+                .iter()
+                .rev()
+                .filter(|run| run.job == job)
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        })
+    }
+}
+
+/// Keeps run history in the `job_runs` table.
+#[derive(Clone)]
+pub struct PostgresJobRunStore {
+    pool: PgPool,
+}
+
+impl PostgresJobRunStore {
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(FromRow)]
+struct JobRunRow {
+    id: i64,
+    job: String,
+    trigger: String,
+    triggered_by: Option<i64>,
+    started_at: DateTime<Utc>,
+    finished_at: Option<DateTime<Utc>>,
+    outcome: Option<String>,
+    error: Option<String>,
+}
+
+impl TryFrom<JobRunRow> for JobRunDto {
+    type Error = AppError;
+
+    fn try_from(row: JobRunRow) -> Result<Self, Self::Error> {
+        let trigger = match row.trigger.as_str() {
+            "manual" => JobTrigger::Manual,
+            _ => JobTrigger::Schedule,
+        };
+        let outcome = row
+            .outcome
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .map_err(AppError::infrastructure)?;
+        Ok(Self {
+            id: row.id,
+            job: row.job,
+            trigger,
+            triggered_by: row.triggered_by,
+            started_at: row.started_at,
+            finished_at: row.finished_at,
+            outcome,
+            error: row.error,
+        })
+    }
+}
+
+fn store_error(err: &sqlx::Error) -> AppError {
+    AppError::infrastructure(format!("job runs: {err}"))
+}
+
+impl JobRunStore for PostgresJobRunStore {
+    fn start<'a>(
+        &'a self,
+        job: &'a str,
+        trigger: JobTrigger,
+        triggered_by: Option<i64>,
+        started_at: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<i64>> {
+        boxed(async move {
+            sqlx::query_scalar::<_, i64>(
+                "INSERT INTO job_runs (job, trigger, triggered_by, started_at) VALUES ($1, $2, $3, $4) RETURNING id",
+            )
+            .bind(job)
+            .bind(trigger.as_str())
+            .bind(triggered_by)
+            .bind(started_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| store_error(&err))
+        })
+    }
+
+    fn finish<'a>(
+        &'a self,
+        id: i64,
+        finished_at: DateTime<Utc>,
+        outcome: JobRunOutcome,
+        error: Option<&'a str>,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            sqlx::query(
+                "UPDATE job_runs SET finished_at = $2, outcome = $3, error = $4 WHERE id = $1",
+            )
+            .bind(id)
+            .bind(finished_at)
+            .bind(outcome.as_str())
+            .bind(error)
+            .execute(&self.pool)
+            .await
+            .map_err(|err| store_error(&err))?;
+            Ok(())
+        })
+    }
+
+    fn recent<'a>(&'a self, job: &'a str, limit: u32) -> BoxFuture<'a, AppResult<Vec<JobRunDto>>> {
+        boxed(async move {
+            sqlx::query_as::<_, JobRunRow>(
+                r"
+                SELECT id, job, trigger, triggered_by, started_at, finished_at, outcome, error
+                FROM job_runs
+                WHERE job = $1
+                ORDER BY started_at DESC, id DESC
+                LIMIT $2
+                ",
+            )
+            .bind(job)
+            .bind(i64::from(limit))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| store_error(&err))?
+            .into_iter()
+            .map(JobRunDto::try_from)
+            .collect()
+        })
+    }
+}
@@ -0,0 +1,142 @@
+// src/infrastructure/security/breach.rs
+//! Breached-password lookups against a Pwned Passwords range API.
+//!
+//! Only the first five hex digits of the password's SHA-1 leave the
+//! process (k-anonymity); the API answers with every breached suffix in
+//! that range, padded with decoys so the response size gives nothing away.
+use crate::application::AppResult;
+use crate::application::error::AppError;
+use crate::application::ports::security::PasswordBreachChecker;
+use crate::async_support::{BoxFuture, boxed};
+use crate::infrastructure::http_client::{is_http_url, send};
+use hyper::header::HeaderName;
+use hyper::{Method, StatusCode};
+use ring::digest::{SHA1_FOR_LEGACY_USE_ONLY, digest};
+use std::fmt::Write as _;
+use std::time::Duration;
+
+/// The public Pwned Passwords service.
+pub const HIBP_BASE_URL: &str = "https://api.pwnedpasswords.com";
+const HTTP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Checks passwords against the range endpoint under `base_url`, which is
+/// [`HIBP_BASE_URL`] or a self-hosted mirror of it.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct HibpBreachChecker {
+    base_url: String,
+}
+
+impl HibpBreachChecker {
+    /// # Errors
+    ///
+    /// Returns an error if `base_url` is not an absolute `http(s)` URL.
+    pub fn new(base_url: &str) -> AppResult<Self> {
+        if !is_http_url(base_url) {
+            return Err(AppError::validation(format!(
+                "invalid pwned passwords url: {base_url}"
+            )));
+        }
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+        })
+    }
+
+    async fn lookup(&self, password: &str) -> AppResult<u64> {
+        let (prefix, suffix) = sha1_range(password);
+        let url = format!("{}/range/{prefix}", self.base_url);
+        let (status, body) = send(
+            Method::GET,
+            &url,
+            &[(HeaderName::from_static("add-padding"), "true")],
+            None,
+        )
+        .await?;
+        if status != StatusCode::OK {
+            return Err(AppError::infrastructure(format!(
+                "pwned passwords responded with {status}"
+            )));
+        }
+        Ok(count_in_range(&String::from_utf8_lossy(&body), &suffix))
+    }
+}
+
+impl PasswordBreachChecker for HibpBreachChecker {
+    fn breach_count<'a>(&'a self, password: &'a str) -> BoxFuture<'a, AppResult<u64>> {
+        boxed(async move {
+            tokio::time::timeout(HTTP_TIMEOUT, self.lookup(password))
+                .await
+                .map_err(|_| AppError::infrastructure("pwned passwords timed out"))?
+        })
+    }
+}
+
+/// Finds no password breached, for instances that may not call out or do
+/// not want the check.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OfflineBreachChecker;
+
+impl PasswordBreachChecker for OfflineBreachChecker {
+    fn breach_count<'a>(&'a self, _password: &'a str) -> BoxFuture<'a, AppResult<u64>> {
+        boxed(async { Ok(0) })
+    }
+}
+
+/// The uppercase hex SHA-1 of `password`, split into the five-digit range
+/// prefix that is sent and the suffix that is looked up locally.
+fn sha1_range(password: &str) -> (String, String) {
+    let hash = digest(&SHA1_FOR_LEGACY_USE_ONLY, password.as_bytes());
+    let mut hex = String::with_capacity(40);
+    for byte in hash.as_ref() {
+        let _ = write!(hex, "{byte:02X}");
+    }
+    let suffix = hex.split_off(5);
+    (hex, suffix)
+}
+
+/// The count for `suffix` in a range response of `SUFFIX:COUNT` lines.
+/// Padding entries carry a count of zero.
+fn count_in_range(body: &str, suffix: &str) -> u64 {
+    body.lines()
+        .filter_map(|line| line.trim().split_once(':'))
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(suffix))
+        .and_then(|(_, count)| count.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_range_prefix_is_sent() {
+        let (prefix, suffix) = sha1_range("password");
+        assert_eq!(prefix, "5BAA6");
+        assert_eq!(suffix, "1E4C9B93F3F0682250B6CF8331B7EE68FD8");
+    }
+
+    #[test]
+    fn range_responses_are_searched_for_the_suffix() {
+        let body = "0018A45C4D1DEF81644B54AB7F969B88D65:3\r\n\
+                    1E4C9B93F3F0682250B6CF8331B7EE68FD8:10437277\r\n\
+                    1E4C9B93F3F0682250B6CF8331B7EE68FD9:0\r\n";
+        assert_eq!(
+            count_in_range(body, "1E4C9B93F3F0682250B6CF8331B7EE68FD8"),
+            10_437_277
+        );
+        assert_eq!(
+            count_in_range(body, "1E4C9B93F3F0682250B6CF8331B7EE68FD9"),
+            0
+        );
+        assert_eq!(
+            count_in_range(body, "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"),
+            0
+        );
+    }
+
+    #[test]
+    fn base_urls_must_be_http() {
+        assert!(HibpBreachChecker::new(HIBP_BASE_URL).is_ok());
+        assert!(HibpBreachChecker::new("ftp://example.com").is_err());
+    }
+}
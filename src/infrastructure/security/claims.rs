@@ -24,7 +24,12 @@ fn build_authenticated_user(ctx: ClaimsContext) -> AppResult<AuthenticatedUser>
 
     let user_id = crate::domain::UserId::new(user_id_i64).map_err(AppError::from)?;
 
-    let mut all_caps = role.default_capabilities();
+    // A custom role's capabilities replace the role's defaults.
+    let mut all_caps = if ctx.custom_role.is_some() {
+        std::collections::HashSet::new()
+    } else {
+        role.default_capabilities()
+    };
     all_caps.extend(ctx.capabilities);
 
     Ok(AuthenticatedUser {
@@ -36,6 +41,8 @@ fn build_authenticated_user(ctx: ClaimsContext) -> AppResult<AuthenticatedUser>
         expires_at: DateTime::<Utc>::from(expires_at),
         session_id: ctx.session_id,
         token_version: ctx.token_version,
+        custom_role: ctx.custom_role,
+        impersonator: ctx.impersonator,
     })
 }
 
@@ -76,6 +83,8 @@ struct ClaimsContext {
     token_version: Option<u32>,
     invalid_token_version: bool,
     capabilities: std::collections::HashSet<Capability>,
+    custom_role: Option<String>,
+    impersonator: Option<crate::domain::UserId>,
 }
 
 impl ClaimsContext {
@@ -95,6 +104,8 @@ impl ClaimsContext {
             "expires_at" => self.handle_expires_at(predicate),
             "right" => self.handle_right(predicate),
             "session" => self.handle_session(predicate),
+            "custom_role" => self.handle_custom_role(predicate),
+            "impersonator" => self.handle_impersonator(predicate),
             _ => {}
         }
     }
@@ -142,6 +153,18 @@ impl ClaimsContext {
         }
     }
 
+    fn handle_custom_role(&mut self, predicate: &biscuit_auth::builder::Predicate) {
+        if let Some(biscuit_auth::builder::Term::Str(name)) = predicate.terms.first() {
+            self.custom_role = Some(name.clone());
+        }
+    }
+
+    fn handle_impersonator(&mut self, predicate: &biscuit_auth::builder::Predicate) {
+        if let Some(biscuit_auth::builder::Term::Integer(id)) = predicate.terms.first() {
+            self.impersonator = crate::domain::UserId::new(*id).ok();
+        }
+    }
+
     fn handle_session(&mut self, predicate: &biscuit_auth::builder::Predicate) {
         if predicate.terms.len() == 2 {
             if let biscuit_auth::builder::Term::Str(sid) = predicate.terms[0].clone() {
@@ -0,0 +1,121 @@
+// src/infrastructure/security/download_links.rs
+use crate::application::{AppResult, error::AppError, ports::download_links::DownloadLinkSigner};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+use std::time::Duration;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const DOWNLOAD_TOKEN_PREFIX: &str = "dl1";
+
+/// Download tokens of the form `dl1.<payload>.<signature>`, where the
+/// payload is `<resource>\n<expiry unix seconds>` and the signature an
+/// HMAC-SHA256 over `dl1.<payload>`, both base64url encoded.
+#[derive(Clone)]
+pub struct HmacDownloadLinkSigner {
+    secret: Vec<u8>,
+    ttl: Duration,
+}
+
+impl HmacDownloadLinkSigner {
+    /// Create a signer whose links are valid for `ttl`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the secret is empty.
+    pub fn new(secret: &str, ttl: Duration) -> AppResult<Self> {
+        if secret.is_empty() {
+            return Err(AppError::infrastructure(
+                "download link secret must not be empty",
+            ));
+        }
+        Ok(Self {
+            secret: secret.as_bytes().to_vec(),
+            ttl,
+        })
+    }
+
+    fn mac(&self, signed: &str) -> AppResult<HmacSha256> {
+        let mut mac = HmacSha256::new_from_slice(&self.secret)
+            .map_err(|_| AppError::infrastructure("invalid download link secret"))?;
+        mac.update(signed.as_bytes());
+        Ok(mac)
+    }
+}
+
+fn invalid() -> AppError {
+    AppError::not_found("download link is invalid or has expired")
+}
+
+impl DownloadLinkSigner for HmacDownloadLinkSigner {
+    fn sign(&self, resource: &str, now: DateTime<Utc>) -> AppResult<String> {
+        let ttl = i64::try_from(self.ttl.as_secs()).unwrap_or(i64::MAX);
+        let expires_at = now.timestamp().saturating_add(ttl);
+        let payload = URL_SAFE_NO_PAD.encode(format!("{resource}\n{expires_at}"));
+        let signed = format!("{DOWNLOAD_TOKEN_PREFIX}.{payload}");
+        let signature = URL_SAFE_NO_PAD.encode(self.mac(&signed)?.finalize().into_bytes());
+        Ok(format!("{signed}.{signature}"))
+    }
+
+    fn verify(&self, token: &str, now: DateTime<Utc>) -> AppResult<String> {
+        let (signed, signature) = token.rsplit_once('.').ok_or_else(invalid)?;
+        let payload = signed
+            .strip_prefix(DOWNLOAD_TOKEN_PREFIX)
+            .and_then(|rest| rest.strip_prefix('.'))
+            .ok_or_else(invalid)?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| invalid())?;
+        self.mac(signed)?
+            .verify_slice(&signature)
+            .map_err(|_| invalid())?;
+
+        let payload = URL_SAFE_NO_PAD
+            .decode(payload)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or_else(invalid)?;
+        let (resource, expires_at) = payload.rsplit_once('\n').ok_or_else(invalid)?;
+        let expires_at: i64 = expires_at.parse().map_err(|_| invalid())?;
+        if now.timestamp() > expires_at {
+            return Err(invalid());
+        }
+        Ok(resource.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signer() -> HmacDownloadLinkSigner {
+        HmacDownloadLinkSigner::new("test-secret", Duration::from_mins(15)).unwrap()
+    }
+
+    #[test]
+    fn links_grant_their_resource_until_they_expire() {
+        let now = Utc::now();
+        let token = signer().sign("article_export:abc", now).unwrap();
+
+        assert_eq!(signer().verify(&token, now).unwrap(), "article_export:abc");
+        assert!(
+            signer()
+                .verify(&token, now + chrono::Duration::minutes(16))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn forged_links_are_rejected() {
+        let now = Utc::now();
+        let token = signer().sign("article_export:abc", now).unwrap();
+        let other = HmacDownloadLinkSigner::new("other", Duration::from_mins(15)).unwrap();
+        assert!(other.verify(&token, now).is_err());
+
+        let (_, signature) = token.rsplit_once('.').unwrap();
+        let payload = URL_SAFE_NO_PAD.encode(format!("article_export:xyz\n{}", i64::MAX));
+        let forged = format!("{DOWNLOAD_TOKEN_PREFIX}.{payload}.{signature}");
+        assert!(signer().verify(&forged, now).is_err());
+        assert!(signer().verify("dl1.abc", now).is_err());
+    }
+}
@@ -0,0 +1,612 @@
+// src/infrastructure/security/jwt.rs
+//! Access tokens as compact JWS (RFC 7515) JWTs signed with `ES256` or `EdDSA`,
+//! for clients that expect standard OIDC tokens.
+//!
+//! Tokens carry the same facts as the Biscuit tokens: `sub` is the user id,
+//! `preferred_username` and `role` identify the user, `scope` lists extra
+//! capabilities as space-separated `resource:action` pairs, and `sid`/`ver`
+//! bind the token to a session.
+
+use crate::application::{
+    AuthTokenDto, AuthenticatedUser, IdTokenClaims, TokenSubject,
+    error::{AppError, AppResult},
+    ports::{security::TokenManager, time::Clock},
+};
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::{Capability, Role, UserId};
+use base64::{
+    Engine as _,
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+};
+use chrono::{DateTime, Utc};
+use ring::rand::SystemRandom;
+use ring::signature::{
+    ECDSA_P256_SHA256_FIXED, ECDSA_P256_SHA256_FIXED_SIGNING, ED25519, EcdsaKeyPair,
+    Ed25519KeyPair, KeyPair as _, UnparsedPublicKey,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::{collections::HashSet, fmt, str::FromStr, sync::Arc, time::Duration};
+
+/// Signature algorithm, named as in the JWS `alg` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwtAlgorithm {
+    /// ECDSA over P-256 with SHA-256.
+    Es256,
+    /// `Ed25519`.
+    EdDsa,
+}
+
+impl JwtAlgorithm {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Es256 => "ES256",
+            Self::EdDsa => "EdDSA",
+        }
+    }
+}
+
+impl fmt::Display for JwtAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JwtAlgorithm {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ES256" => Ok(Self::Es256),
+            "EdDSA" => Ok(Self::EdDsa),
+            other => Err(AppError::infrastructure(format!(
+                "unsupported JWT algorithm '{other}' (expected ES256 or EdDSA)"
+            ))),
+        }
+    }
+}
+
+enum SigningKey {
+    Es256(EcdsaKeyPair),
+    EdDsa(Ed25519KeyPair),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    alg: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    typ: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kid: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    iss: String,
+    sub: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    aud: Option<String>,
+    iat: i64,
+    exp: i64,
+    preferred_username: String,
+    role: Role,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    scope: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ver: Option<u32>,
+    /// Set when `scope` lists a custom role's capabilities, which then
+    /// replace rather than extend those of `role`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    custom_role: Option<String>,
+    /// Id of the admin acting as `sub`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    impersonator: Option<i64>,
+    token_type: String,
+}
+
+#[derive(Clone)]
+pub struct JwtTokenManager {
+    key: Arc<SigningKey>,
+    algorithm: JwtAlgorithm,
+    public_key: Vec<u8>,
+    kid: String,
+    issuer: String,
+    audience: Option<String>,
+    ttl: Duration,
+    clock: Option<Arc<dyn Clock>>,
+    rng: SystemRandom,
+}
+
+impl JwtTokenManager {
+    /// Create a token manager signing with a PKCS#8 DER private key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is not a valid key for `algorithm`.
+    pub fn from_pkcs8(
+        algorithm: JwtAlgorithm,
+        pkcs8: &[u8],
+        issuer: impl Into<String>,
+        ttl: Duration,
+    ) -> AppResult<Self> {
+        let rng = SystemRandom::new();
+        let rejected = |err: ring::error::KeyRejected| {
+            AppError::infrastructure(format!("invalid {algorithm} private key: {err}"))
+        };
+        let key = match algorithm {
+            JwtAlgorithm::Es256 => SigningKey::Es256(
+                EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8, &rng)
+                    .map_err(rejected)?,
+            ),
+            JwtAlgorithm::EdDsa => SigningKey::EdDsa(
+                Ed25519KeyPair::from_pkcs8_maybe_unchecked(pkcs8).map_err(rejected)?,
+            ),
+        };
+        let public_key = match &key {
+            SigningKey::Es256(pair) => pair.public_key().as_ref().to_vec(),
+            SigningKey::EdDsa(pair) => pair.public_key().as_ref().to_vec(),
+        };
+        let mut manager = Self {
+            key: Arc::new(key),
+            algorithm,
+            public_key,
+            kid: String::new(),
+            issuer: issuer.into(),
+            audience: None,
+            ttl,
+            clock: None,
+            rng,
+        };
+        manager.kid = manager.thumbprint();
+        Ok(manager)
+    }
+
+    /// Create a token manager from a PEM `PRIVATE KEY` (PKCS#8) document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the PEM cannot be decoded or the key is not a
+    /// valid key for `algorithm`.
+    pub fn from_pem(
+        algorithm: JwtAlgorithm,
+        pem: &str,
+        issuer: impl Into<String>,
+        ttl: Duration,
+    ) -> AppResult<Self> {
+        let body: String = pem
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with("-----"))
+            .collect();
+        let der = STANDARD
+            .decode(body)
+            .map_err(|err| AppError::infrastructure(format!("invalid PEM private key: {err}")))?;
+        Self::from_pkcs8(algorithm, &der, issuer, ttl)
+    }
+
+    /// Generate a new PKCS#8 DER private key for `algorithm`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the system random source fails.
+    pub fn generate_pkcs8(algorithm: JwtAlgorithm) -> AppResult<Vec<u8>> {
+        let rng = SystemRandom::new();
+        let document = match algorithm {
+            JwtAlgorithm::Es256 => {
+                EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng)
+            }
+            JwtAlgorithm::EdDsa => Ed25519KeyPair::generate_pkcs8(&rng),
+        }
+        .map_err(|_| AppError::infrastructure("failed to generate JWT signing key"))?;
+        Ok(document.as_ref().to_vec())
+    }
+
+    /// Require and set `aud` to `audience`.
+    #[must_use]
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = Some(audience.into());
+        self
+    }
+
+    /// Publish the key under `kid` instead of its RFC 7638 thumbprint.
+    #[must_use]
+    pub fn with_key_id(mut self, kid: impl Into<String>) -> Self {
+        self.kid = kid.into();
+        self
+    }
+
+    /// Stamp and check tokens against `clock` instead of the system time.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        self.clock
+            .as_ref()
+            .map_or_else(Utc::now, |clock| clock.now())
+    }
+
+    /// The public key as a JWK, without `alg`, `use` or `kid`.
+    fn jwk_members(&self) -> serde_json::Value {
+        match self.algorithm {
+            // Uncompressed SEC1 point: 0x04 || x || y.
+            JwtAlgorithm::Es256 => json!({
+                "crv": "P-256",
+                "kty": "EC",
+                "x": URL_SAFE_NO_PAD.encode(&self.public_key[1..33]),
+                "y": URL_SAFE_NO_PAD.encode(&self.public_key[33..]),
+            }),
+            JwtAlgorithm::EdDsa => json!({
+                "crv": "Ed25519",
+                "kty": "OKP",
+                "x": URL_SAFE_NO_PAD.encode(&self.public_key),
+            }),
+        }
+    }
+
+    /// RFC 7638 thumbprint: SHA-256 over the required members, which
+    /// `serde_json` writes sorted and without whitespace.
+    fn thumbprint(&self) -> String {
+        let canonical = self.jwk_members().to_string();
+        URL_SAFE_NO_PAD.encode(Sha256::digest(canonical.as_bytes()))
+    }
+
+    fn sign(&self, message: &[u8]) -> AppResult<Vec<u8>> {
+        match self.key.as_ref() {
+            SigningKey::Es256(pair) => pair
+                .sign(&self.rng, message)
+                .map(|signature| signature.as_ref().to_vec())
+                .map_err(|_| AppError::infrastructure("failed to sign token")),
+            SigningKey::EdDsa(pair) => Ok(pair.sign(message).as_ref().to_vec()),
+        }
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> AppResult<()> {
+        let algorithm: &'static dyn ring::signature::VerificationAlgorithm = match self.algorithm {
+            JwtAlgorithm::Es256 => &ECDSA_P256_SHA256_FIXED,
+            JwtAlgorithm::EdDsa => &ED25519,
+        };
+        UnparsedPublicKey::new(algorithm, &self.public_key)
+            .verify(message, signature)
+            .map_err(|_| AppError::unauthorized("invalid token signature"))
+    }
+
+    fn encode(&self, claims: &impl Serialize) -> AppResult<String> {
+        compact_jws(self.algorithm, &self.kid, claims, |message| {
+            self.sign(message)
+        })
+    }
+
+    /// Check the header and signature of `token` and return its claims.
+    fn decode(&self, token: &str) -> AppResult<Claims> {
+        let malformed = || AppError::unauthorized("malformed token");
+        let mut parts = token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(malformed());
+        };
+        let decode_part = |part: &str| URL_SAFE_NO_PAD.decode(part).map_err(|_| malformed());
+
+        let header: Header =
+            serde_json::from_slice(&decode_part(header)?).map_err(|_| malformed())?;
+        // Never let the token choose how it is verified.
+        if header.alg != self.algorithm.as_str() {
+            return Err(AppError::unauthorized("unexpected token algorithm"));
+        }
+        if header.kid.as_deref().is_some_and(|kid| kid != self.kid) {
+            return Err(AppError::unauthorized("unknown token key"));
+        }
+        let signing_input = &token[..header_len(token)];
+        self.verify(signing_input.as_bytes(), &decode_part(signature)?)?;
+
+        serde_json::from_slice(&decode_part(payload)?).map_err(|_| malformed())
+    }
+}
+
+/// A compact JWS of `claims`, with `sign` producing the raw signature over
+/// `header.payload`.
+pub(crate) fn compact_jws(
+    algorithm: JwtAlgorithm,
+    kid: &str,
+    claims: &impl Serialize,
+    sign: impl FnOnce(&[u8]) -> AppResult<Vec<u8>>,
+) -> AppResult<String> {
+    let header = Header {
+        alg: algorithm.as_str().into(),
+        typ: Some("JWT".into()),
+        kid: Some(kid.to_string()),
+    };
+    let encode_part = |value: serde_json::Value| URL_SAFE_NO_PAD.encode(value.to_string());
+    let signing_input = format!(
+        "{}.{}",
+        encode_part(json!(header)),
+        encode_part(json!(claims))
+    );
+    let signature = sign(signing_input.as_bytes())?;
+    Ok(format!(
+        "{signing_input}.{}",
+        URL_SAFE_NO_PAD.encode(signature)
+    ))
+}
+
+/// Length of `header.payload` in a compact JWS.
+fn header_len(token: &str) -> usize {
+    token.rfind('.').unwrap_or(token.len())
+}
+
+fn timestamp(seconds: i64) -> AppResult<DateTime<Utc>> {
+    DateTime::from_timestamp(seconds, 0)
+        .ok_or_else(|| AppError::unauthorized("token timestamp out of range"))
+}
+
+impl TokenManager for JwtTokenManager {
+    fn issue(&self, subject: TokenSubject) -> BoxFuture<'_, AppResult<AuthTokenDto>> {
+        boxed(async move {
+            let issued_at = self.now().timestamp();
+            let ttl = i64::try_from(subject.token_ttl(self.ttl).as_secs()).unwrap_or(i64::MAX);
+            let expires_at = issued_at
+                .checked_add(ttl)
+                .ok_or_else(|| AppError::infrastructure("token expiration overflow"))?;
+
+            let mut scope: Vec<String> = subject
+                .capabilities
+                .iter()
+                .map(|cap| format!("{}:{}", cap.resource, cap.action))
+                .collect();
+            scope.sort_unstable();
+            let claims = Claims {
+                iss: self.issuer.clone(),
+                sub: i64::from(subject.user_id).to_string(),
+                aud: self.audience.clone(),
+                iat: issued_at,
+                exp: expires_at,
+                preferred_username: subject.username,
+                role: subject.role,
+                scope: scope.join(" "),
+                sid: subject.session_id.clone(),
+                ver: subject
+                    .session_id
+                    .as_ref()
+                    .map(|_| subject.token_version.unwrap_or(1)),
+                custom_role: subject.custom_role,
+                impersonator: subject.impersonator.map(i64::from),
+                token_type: "access".into(),
+            };
+
+            Ok(AuthTokenDto {
+                token: self.encode(&claims)?,
+                issued_at: timestamp(issued_at)?,
+                expires_at: timestamp(expires_at)?,
+                expires_in: ttl.max(0),
+                session_id: subject.session_id,
+                refresh_token: None,
+            })
+        })
+    }
+
+    fn authenticate<'a>(&'a self, token: &'a str) -> BoxFuture<'a, AppResult<AuthenticatedUser>> {
+        boxed(async move {
+            let claims = self.decode(token)?;
+            if claims.token_type != "access" {
+                return Err(AppError::unauthorized("not an access token"));
+            }
+            if claims.iss != self.issuer {
+                return Err(AppError::unauthorized("unexpected token issuer"));
+            }
+            if self.audience.is_some() && claims.aud != self.audience {
+                return Err(AppError::unauthorized("unexpected token audience"));
+            }
+            let issued_at = timestamp(claims.iat)?;
+            let expires_at = timestamp(claims.exp)?;
+            let now = self.now();
+            if now < issued_at || now > expires_at {
+                return Err(AppError::unauthorized("token is expired or not yet valid"));
+            }
+
+            let id = claims
+                .sub
+                .parse::<i64>()
+                .map_err(|_| AppError::unauthorized("invalid subject"))?;
+            let mut capabilities = if claims.custom_role.is_some() {
+                HashSet::new()
+            } else {
+                claims.role.default_capabilities()
+            };
+            capabilities.extend(claims.scope.split_whitespace().filter_map(|entry| {
+                let (resource, action) = entry.split_once(':')?;
+                Some(Capability::new(resource, action))
+            }));
+
+            Ok(AuthenticatedUser {
+                id: UserId::new(id)?,
+                username: claims.preferred_username,
+                role: claims.role,
+                capabilities,
+                issued_at,
+                expires_at,
+                session_id: claims.sid,
+                token_version: claims.ver,
+                custom_role: claims.custom_role,
+                impersonator: claims.impersonator.map(UserId::new).transpose()?,
+            })
+        })
+    }
+
+    fn public_jwk(&self) -> BoxFuture<'_, AppResult<serde_json::Value>> {
+        boxed(async move {
+            let mut jwk = self.jwk_members();
+            jwk["alg"] = self.algorithm.as_str().into();
+            jwk["use"] = "sig".into();
+            jwk["kid"] = self.kid.clone().into();
+            Ok(json!({ "keys": [jwk] }))
+        })
+    }
+    /// `id_token`s lack `role` and `token_type`, so `authenticate` never
+    /// accepts one as an access token.
+    fn issue_id_token(&self, claims: &IdTokenClaims) -> AppResult<Option<String>> {
+        self.encode(claims).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(algorithm: JwtAlgorithm) -> JwtTokenManager {
+        let pkcs8 = JwtTokenManager::generate_pkcs8(algorithm).unwrap();
+        JwtTokenManager::from_pkcs8(
+            algorithm,
+            &pkcs8,
+            "https://cms.example.com",
+            Duration::from_hours(1),
+        )
+        .unwrap()
+    }
+
+    fn subject() -> TokenSubject {
+        TokenSubject {
+            user_id: UserId::new(7).unwrap(),
+            username: "alice".into(),
+            role: Role::Author,
+            capabilities: HashSet::from([Capability::new("audit", "read")]),
+            session_id: Some("s-1".into()),
+            token_version: Some(3),
+            custom_role: None,
+            impersonator: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn tokens_round_trip_with_either_algorithm() {
+        for algorithm in [JwtAlgorithm::Es256, JwtAlgorithm::EdDsa] {
+            let manager = manager(algorithm);
+            let issued = manager.issue(subject()).await.unwrap();
+            let user = manager.authenticate(&issued.token).await.unwrap();
+
+            assert_eq!(i64::from(user.id), 7);
+            assert_eq!(user.username, "alice");
+            assert!(user.has_capability("audit", "read"));
+            assert!(user.has_capability("articles", "update:own"));
+            assert_eq!(user.session_id.as_deref(), Some("s-1"));
+            assert_eq!(user.token_version, Some(3));
+            assert_eq!(user.expires_at, issued.expires_at);
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_roles_replace_the_builtin_defaults() {
+        let manager = manager(JwtAlgorithm::EdDsa);
+        let issued = manager
+            .issue(TokenSubject {
+                custom_role: Some("auditor".into()),
+                ..subject()
+            })
+            .await
+            .unwrap();
+        let user = manager.authenticate(&issued.token).await.unwrap();
+
+        assert_eq!(user.custom_role.as_deref(), Some("auditor"));
+        assert!(user.has_capability("audit", "read"));
+        assert!(!user.has_capability("articles", "update:own"));
+    }
+
+    #[tokio::test]
+    async fn impersonation_tokens_name_the_admin_and_expire_early() {
+        let manager = manager(JwtAlgorithm::Es256);
+        let issued = manager
+            .issue(TokenSubject {
+                impersonator: Some(UserId::new(1).unwrap()),
+                ..subject()
+            })
+            .await
+            .unwrap();
+        let user = manager.authenticate(&issued.token).await.unwrap();
+
+        assert_eq!(user.impersonator.map(i64::from), Some(1));
+        assert_eq!(issued.expires_in, 15 * 60);
+    }
+
+    #[tokio::test]
+    async fn tampered_and_foreign_tokens_are_rejected() {
+        let manager = manager(JwtAlgorithm::Es256);
+        let token = manager.issue(subject()).await.unwrap().token;
+
+        let mut parts: Vec<&str> = token.split('.').collect();
+        let forged = URL_SAFE_NO_PAD.encode(
+            URL_SAFE_NO_PAD
+                .decode(parts[1])
+                .map(|payload| String::from_utf8(payload).unwrap())
+                .unwrap()
+                .replace("\"author\"", "\"admin\""),
+        );
+        parts[1] = &forged;
+        assert!(manager.authenticate(&parts.join(".")).await.is_err());
+
+        let other = self::manager(JwtAlgorithm::Es256).with_key_id(manager.kid.clone());
+        assert!(other.authenticate(&token).await.is_err());
+
+        let unsigned = format!(
+            "{}.{}.",
+            URL_SAFE_NO_PAD.encode(r#"{"alg":"none"}"#),
+            token.split('.').nth(1).unwrap()
+        );
+        assert!(manager.authenticate(&unsigned).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn jwks_describes_the_signing_key() {
+        let manager = manager(JwtAlgorithm::Es256);
+        let jwks = manager.public_jwk().await.unwrap();
+        let key = &jwks["keys"][0];
+        assert_eq!(key["kty"], "EC");
+        assert_eq!(key["crv"], "P-256");
+        assert_eq!(key["alg"], "ES256");
+        assert_eq!(key["kid"], manager.kid.as_str());
+        assert_eq!(
+            URL_SAFE_NO_PAD
+                .decode(key["x"].as_str().unwrap())
+                .unwrap()
+                .len(),
+            32
+        );
+        assert_eq!(
+            URL_SAFE_NO_PAD
+                .decode(key["y"].as_str().unwrap())
+                .unwrap()
+                .len(),
+            32
+        );
+    }
+
+    #[tokio::test]
+    async fn id_tokens_are_signed_but_not_accepted_as_access_tokens() {
+        let manager = manager(JwtAlgorithm::EdDsa);
+        let claims = IdTokenClaims {
+            iss: "https://cms.example.com".into(),
+            sub: "7".into(),
+            aud: "client".into(),
+            iat: 0,
+            exp: i64::MAX,
+            nonce: Some("n-1".into()),
+            preferred_username: "alice".into(),
+            sid: None,
+        };
+        let token = manager.issue_id_token(&claims).unwrap().unwrap();
+
+        let signing_input = &token[..header_len(&token)];
+        let signature = URL_SAFE_NO_PAD
+            .decode(&token[header_len(&token) + 1..])
+            .unwrap();
+        manager
+            .verify(signing_input.as_bytes(), &signature)
+            .unwrap();
+        assert!(manager.authenticate(&token).await.is_err());
+    }
+}
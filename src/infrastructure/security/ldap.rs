@@ -0,0 +1,507 @@
+// src/infrastructure/security/ldap.rs
+//! Minimal LDAP v3 client: a simple bind as the user, then a base-scope search
+//! of their own entry for group membership.
+//!
+//! Only plain `ldap://` is supported; run a TLS-terminating proxy next to the
+//! service (as with the security webhook) when the directory requires LDAPS.
+use crate::application::AppResult;
+use crate::application::error::AppError;
+use crate::application::ports::external_auth::{ExternalAuthenticator, ExternalIdentity};
+use crate::async_support::{BoxFuture, boxed};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const LDAP_TIMEOUT: Duration = Duration::from_secs(5);
+/// Largest response message accepted from the directory.
+const MAX_MESSAGE_BYTES: usize = 1 << 20;
+const USERNAME_PLACEHOLDER: &str = "{username}";
+
+// BER tags (RFC 4511).
+const BOOLEAN: u8 = 0x01;
+const INTEGER: u8 = 0x02;
+const OCTET_STRING: u8 = 0x04;
+const ENUMERATED: u8 = 0x0a;
+const SEQUENCE: u8 = 0x30;
+const SET: u8 = 0x31;
+const BIND_REQUEST: u8 = 0x60;
+const BIND_RESPONSE: u8 = 0x61;
+const UNBIND_REQUEST: u8 = 0x42;
+const SEARCH_REQUEST: u8 = 0x63;
+const SEARCH_RESULT_ENTRY: u8 = 0x64;
+const SEARCH_RESULT_DONE: u8 = 0x65;
+const SIMPLE_AUTH: u8 = 0x80;
+const PRESENT_FILTER: u8 = 0x87;
+
+const RESULT_SUCCESS: u32 = 0;
+const RESULT_INVALID_CREDENTIALS: u32 = 49;
+
+/// Authenticates users with an LDAP simple bind against a DN built from a
+/// template such as `uid={username},ou=people,dc=example,dc=com`.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct LdapAuthenticator {
+    host: String,
+    port: u16,
+    user_dn_template: String,
+    group_attribute: String,
+}
+
+impl LdapAuthenticator {
+    /// Build an authenticator from an `ldap://host[:port]` URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL does not use the `ldap` scheme or has no
+    /// host, or the template lacks a `{username}` placeholder.
+    pub fn new(
+        url: &str,
+        user_dn_template: impl Into<String>,
+        group_attribute: impl Into<String>,
+    ) -> Result<Self, AppError> {
+        let authority = url
+            .strip_prefix("ldap://")
+            .ok_or_else(|| AppError::validation("ldap url must use the ldap scheme"))?
+            .trim_end_matches('/');
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse()
+                    .map_err(|_| AppError::validation("invalid ldap url port"))?,
+            ),
+            None => (authority, 389),
+        };
+        if host.is_empty() || host.contains('/') {
+            return Err(AppError::validation("ldap url is missing a host"));
+        }
+
+        let user_dn_template = user_dn_template.into();
+        if !user_dn_template.contains(USERNAME_PLACEHOLDER) {
+            return Err(AppError::validation(
+                "ldap user dn template must contain {username}",
+            ));
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            user_dn_template,
+            group_attribute: group_attribute.into(),
+        })
+    }
+
+    fn user_dn(&self, username: &str) -> String {
+        self.user_dn_template
+            .replace(USERNAME_PLACEHOLDER, &escape_dn_value(username))
+    }
+
+    async fn bind_and_fetch_groups(
+        &self,
+        dn: &str,
+        password: &str,
+    ) -> AppResult<Option<ExternalIdentity>> {
+        let mut stream = TcpStream::connect((self.host.as_str(), self.port))
+            .await
+            .map_err(|err| AppError::infrastructure(err.to_string()))?;
+
+        let Some(()) = bind(&mut stream, dn, password).await? else {
+            return Ok(None);
+        };
+        let groups = search_attribute(&mut stream, dn, &self.group_attribute).await?;
+        // Best effort: the server closes the connection either way.
+        let _ = stream
+            .write_all(&message(3, &tlv(UNBIND_REQUEST, &[])))
+            .await;
+
+        Ok(Some(ExternalIdentity {
+            subject: dn.to_string(),
+            groups,
+        }))
+    }
+}
+
+impl ExternalAuthenticator for LdapAuthenticator {
+    fn authenticate<'a>(
+        &'a self,
+        username: &'a str,
+        password: &'a str,
+    ) -> BoxFuture<'a, AppResult<Option<ExternalIdentity>>> {
+        boxed(async move {
+            // An empty password is an "unauthenticated bind", which many
+            // servers accept without checking anything (RFC 4513 §5.1.2).
+            if password.is_empty() {
+                return Ok(None);
+            }
+            let dn = self.user_dn(username);
+            tokio::time::timeout(LDAP_TIMEOUT, self.bind_and_fetch_groups(&dn, password))
+                .await
+                .map_err(|_| AppError::infrastructure("ldap server timed out"))?
+        })
+    }
+}
+
+/// Returns `Ok(None)` when the directory rejects the credentials.
+async fn bind<S>(stream: &mut S, dn: &str, password: &str) -> AppResult<Option<()>>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let request = [
+        integer(INTEGER, 3),
+        tlv(OCTET_STRING, dn.as_bytes()),
+        tlv(SIMPLE_AUTH, password.as_bytes()),
+    ]
+    .concat();
+    write(stream, &message(1, &tlv(BIND_REQUEST, &request))).await?;
+
+    let (op, body) = read_response(stream, 1).await?;
+    if op != BIND_RESPONSE {
+        return Err(protocol_error("expected a bind response"));
+    }
+    match result_code(&body)? {
+        RESULT_SUCCESS => Ok(Some(())),
+        RESULT_INVALID_CREDENTIALS => Ok(None),
+        code => Err(AppError::infrastructure(format!(
+            "ldap bind failed with result code {code}"
+        ))),
+    }
+}
+
+/// Read every value of `attribute` on the entry at `dn`.
+async fn search_attribute<S>(stream: &mut S, dn: &str, attribute: &str) -> AppResult<Vec<String>>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let request = [
+        tlv(OCTET_STRING, dn.as_bytes()),
+        integer(ENUMERATED, 0), // scope: baseObject
+        integer(ENUMERATED, 0), // derefAliases: never
+        integer(INTEGER, 0),    // sizeLimit
+        integer(INTEGER, 0),    // timeLimit
+        tlv(BOOLEAN, &[0]),     // typesOnly
+        tlv(PRESENT_FILTER, b"objectClass"),
+        tlv(SEQUENCE, &tlv(OCTET_STRING, attribute.as_bytes())),
+    ]
+    .concat();
+    write(stream, &message(2, &tlv(SEARCH_REQUEST, &request))).await?;
+
+    let mut values = Vec::new();
+    loop {
+        let (op, body) = read_response(stream, 2).await?;
+        match op {
+            SEARCH_RESULT_ENTRY => values.extend(entry_values(&body, attribute)?),
+            SEARCH_RESULT_DONE => {
+                return match result_code(&body)? {
+                    RESULT_SUCCESS => Ok(values),
+                    code => Err(AppError::infrastructure(format!(
+                        "ldap search failed with result code {code}"
+                    ))),
+                };
+            }
+            // Search result references and anything else are irrelevant for
+            // a base-scope lookup of the user's own entry.
+            _ => {}
+        }
+    }
+}
+
+async fn write<S: AsyncWrite + Unpin>(stream: &mut S, bytes: &[u8]) -> AppResult<()> {
+    stream
+        .write_all(bytes)
+        .await
+        .map_err(|err| AppError::infrastructure(err.to_string()))
+}
+
+/// Read one `LDAPMessage` and return its protocol op tag and body.
+async fn read_response<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    expected_id: u32,
+) -> AppResult<(u8, Vec<u8>)> {
+    let content = read_message(stream).await?;
+    let mut reader = Reader::new(&content);
+    let (tag, id) = reader.next()?;
+    if tag != INTEGER || decode_uint(id)? != expected_id {
+        return Err(protocol_error("unexpected message id"));
+    }
+    let (op, body) = reader.next()?;
+    Ok((op, body.to_vec()))
+}
+
+async fn read_message<S: AsyncRead + Unpin>(stream: &mut S) -> AppResult<Vec<u8>> {
+    let io = |err: std::io::Error| AppError::infrastructure(err.to_string());
+
+    let mut header = [0u8; 2];
+    stream.read_exact(&mut header).await.map_err(io)?;
+    if header[0] != SEQUENCE {
+        return Err(protocol_error("expected an LDAPMessage"));
+    }
+    let len = if header[1] < 0x80 {
+        usize::from(header[1])
+    } else {
+        let count = usize::from(header[1] & 0x7f);
+        if count == 0 || count > 4 {
+            return Err(protocol_error("unsupported length encoding"));
+        }
+        let mut bytes = [0u8; 4];
+        stream
+            .read_exact(&mut bytes[4 - count..])
+            .await
+            .map_err(io)?;
+        usize::try_from(u32::from_be_bytes(bytes))
+            .map_err(|_| protocol_error("message too large"))?
+    };
+    if len > MAX_MESSAGE_BYTES {
+        return Err(protocol_error("message too large"));
+    }
+
+    let mut content = vec![0u8; len];
+    stream.read_exact(&mut content).await.map_err(io)?;
+    Ok(content)
+}
+
+/// `resultCode` is the first element of every LDAP result.
+fn result_code(body: &[u8]) -> AppResult<u32> {
+    let (tag, code) = Reader::new(body).next()?;
+    if tag != ENUMERATED {
+        return Err(protocol_error("expected a result code"));
+    }
+    decode_uint(code)
+}
+
+/// Values of `attribute` in a `SearchResultEntry` body.
+fn entry_values(body: &[u8], attribute: &str) -> AppResult<Vec<String>> {
+    let mut entry = Reader::new(body);
+    entry.next()?; // objectName
+    let (_, attributes) = entry.next()?;
+
+    let mut values = Vec::new();
+    let mut attributes = Reader::new(attributes);
+    while !attributes.is_empty() {
+        let (_, partial) = attributes.next()?;
+        let mut partial = Reader::new(partial);
+        let (_, name) = partial.next()?;
+        let (tag, vals) = partial.next()?;
+        if tag != SET || !String::from_utf8_lossy(name).eq_ignore_ascii_case(attribute) {
+            continue;
+        }
+        let mut vals = Reader::new(vals);
+        while !vals.is_empty() {
+            let (_, value) = vals.next()?;
+            values.push(String::from_utf8_lossy(value).into_owned());
+        }
+    }
+    Ok(values)
+}
+
+fn protocol_error(detail: &str) -> AppError {
+    AppError::infrastructure(format!("ldap protocol error: {detail}"))
+}
+
+/// Escape a value for use inside a DN attribute value (RFC 4514 §2.4).
+fn escape_dn_value(value: &str) -> String {
+    let last = value.chars().count().saturating_sub(1);
+    let mut escaped = String::with_capacity(value.len());
+    for (index, ch) in value.chars().enumerate() {
+        match ch {
+            '"' | '+' | ',' | ';' | '<' | '=' | '>' | '\\' => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            '#' if index == 0 => escaped.push_str("\\#"),
+            ' ' if index == 0 || index == last => escaped.push_str("\\ "),
+            '\0' => escaped.push_str("\\00"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn message(id: u32, op: &[u8]) -> Vec<u8> {
+    tlv(SEQUENCE, &[integer(INTEGER, id), op.to_vec()].concat())
+}
+
+fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    let len = content.len();
+    if len < 0x80 {
+        out.push(u8::try_from(len).unwrap_or_default());
+    } else {
+        let bytes = len.to_be_bytes();
+        let skip = bytes.iter().take_while(|byte| **byte == 0).count();
+        out.push(0x80 | u8::try_from(bytes.len() - skip).unwrap_or_default());
+        out.extend_from_slice(&bytes[skip..]);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+/// Non-negative INTEGER/ENUMERATED in minimal two's-complement form.
+fn integer(tag: u8, value: u32) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take(3).take_while(|byte| **byte == 0).count();
+    let mut content = bytes[skip..].to_vec();
+    if content[0] & 0x80 != 0 {
+        content.insert(0, 0);
+    }
+    tlv(tag, &content)
+}
+
+fn decode_uint(bytes: &[u8]) -> AppResult<u32> {
+    if bytes.is_empty() || bytes.len() > 4 || bytes[0] & 0x80 != 0 {
+        return Err(protocol_error("unsupported integer"));
+    }
+    Ok(bytes
+        .iter()
+        .fold(0u32, |acc, byte| (acc << 8) | u32::from(*byte)))
+}
+
+/// Iterates the TLVs inside a constructed BER value.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn next(&mut self) -> AppResult<(u8, &'a [u8])> {
+        let truncated = || protocol_error("truncated value");
+        let (&tag, rest) = self.data.split_first().ok_or_else(truncated)?;
+        let (&first, mut rest) = rest.split_first().ok_or_else(truncated)?;
+        let len = if first < 0x80 {
+            usize::from(first)
+        } else {
+            let count = usize::from(first & 0x7f);
+            if count == 0 || count > 4 || rest.len() < count {
+                return Err(truncated());
+            }
+            let (len_bytes, after) = rest.split_at(count);
+            rest = after;
+            len_bytes
+                .iter()
+                .fold(0usize, |acc, byte| (acc << 8) | usize::from(*byte))
+        };
+        if rest.len() < len {
+            return Err(truncated());
+        }
+        let (value, after) = rest.split_at(len);
+        self.data = after;
+        Ok((tag, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::duplex;
+
+    fn result(op: u8, code: u32) -> Vec<u8> {
+        let body = [
+            integer(ENUMERATED, code),
+            tlv(OCTET_STRING, b""),
+            tlv(OCTET_STRING, b""),
+        ]
+        .concat();
+        tlv(op, &body)
+    }
+
+    fn entry(dn: &str, attribute: &str, values: &[&str]) -> Vec<u8> {
+        let vals: Vec<u8> = values
+            .iter()
+            .flat_map(|value| tlv(OCTET_STRING, value.as_bytes()))
+            .collect();
+        let partial = [tlv(OCTET_STRING, attribute.as_bytes()), tlv(SET, &vals)].concat();
+        let body = [
+            tlv(OCTET_STRING, dn.as_bytes()),
+            tlv(SEQUENCE, &tlv(SEQUENCE, &partial)),
+        ]
+        .concat();
+        tlv(SEARCH_RESULT_ENTRY, &body)
+    }
+
+    #[test]
+    fn new_parses_url_and_requires_placeholder() {
+        let ldap =
+            LdapAuthenticator::new("ldap://dir.local", "uid={username},dc=x", "memberOf").unwrap();
+        assert_eq!((ldap.host.as_str(), ldap.port), ("dir.local", 389));
+        let ldap =
+            LdapAuthenticator::new("ldap://dir.local:3389/", "uid={username}", "memberOf").unwrap();
+        assert_eq!(ldap.port, 3389);
+
+        assert!(LdapAuthenticator::new("ldaps://dir.local", "uid={username}", "m").is_err());
+        assert!(LdapAuthenticator::new("ldap://dir.local", "uid=fixed", "m").is_err());
+    }
+
+    #[test]
+    fn usernames_are_escaped_into_the_dn() {
+        let ldap =
+            LdapAuthenticator::new("ldap://dir.local", "uid={username},dc=x", "memberOf").unwrap();
+        assert_eq!(ldap.user_dn("alice"), "uid=alice,dc=x");
+        assert_eq!(
+            ldap.user_dn("#eve,dc=evil "),
+            "uid=\\#eve\\,dc\\=evil\\ ,dc=x"
+        );
+    }
+
+    #[test]
+    fn lengths_and_integers_use_minimal_encodings() {
+        assert_eq!(integer(INTEGER, 3), vec![INTEGER, 1, 3]);
+        assert_eq!(integer(INTEGER, 200), vec![INTEGER, 2, 0, 200]);
+        let long = tlv(OCTET_STRING, &[0u8; 300]);
+        assert_eq!(&long[..4], &[OCTET_STRING, 0x82, 0x01, 0x2c]);
+        assert_eq!(Reader::new(&long).next().unwrap().1.len(), 300);
+    }
+
+    #[tokio::test]
+    async fn bind_and_search_against_a_scripted_server() {
+        let (mut client, mut server) = duplex(4096);
+        let dn = "uid=alice,dc=x";
+        let script = tokio::spawn(async move {
+            let bind_request = read_message(&mut server).await.unwrap();
+            let mut reader = Reader::new(&bind_request);
+            reader.next().unwrap();
+            assert_eq!(reader.next().unwrap().0, BIND_REQUEST);
+            server
+                .write_all(&message(1, &result(BIND_RESPONSE, RESULT_SUCCESS)))
+                .await
+                .unwrap();
+
+            read_message(&mut server).await.unwrap();
+            let groups = ["cn=admins,dc=x", "cn=staff,dc=x"];
+            server
+                .write_all(&message(2, &entry(dn, "memberOf", &groups)))
+                .await
+                .unwrap();
+            server
+                .write_all(&message(2, &result(SEARCH_RESULT_DONE, RESULT_SUCCESS)))
+                .await
+                .unwrap();
+        });
+
+        assert_eq!(bind(&mut client, dn, "secret").await.unwrap(), Some(()));
+        let groups = search_attribute(&mut client, dn, "memberOf").await.unwrap();
+        assert_eq!(groups, vec!["cn=admins,dc=x", "cn=staff,dc=x"]);
+        script.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn invalid_credentials_are_a_rejection_not_an_error() {
+        let (mut client, mut server) = duplex(4096);
+        let script = tokio::spawn(async move {
+            read_message(&mut server).await.unwrap();
+            server
+                .write_all(&message(
+                    1,
+                    &result(BIND_RESPONSE, RESULT_INVALID_CREDENTIALS),
+                ))
+                .await
+                .unwrap();
+        });
+        assert_eq!(bind(&mut client, "uid=a,dc=x", "nope").await.unwrap(), None);
+        script.await.unwrap();
+    }
+}
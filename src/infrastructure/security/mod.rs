@@ -1,8 +1,18 @@
 // src/infrastructure/security/mod.rs
 pub mod authorization_code_store;
+pub mod breach;
 pub mod claims;
+pub mod download_links;
+pub mod jwt;
+pub mod ldap;
 pub mod password;
+pub mod rate_limiter;
+pub mod redis_authorization_code_store;
+pub mod redis_rate_limiter;
 pub mod redis_session_store;
 pub mod refresh_token;
 pub mod session_store;
 pub mod token;
+pub mod upstream_oidc;
+pub mod user_tokens;
+pub mod webhook;
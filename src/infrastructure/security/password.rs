@@ -5,22 +5,46 @@ use crate::application::{
 };
 use crate::async_support::{BoxFuture, boxed};
 use argon2::{
-    Argon2,
+    Algorithm, Argon2, Params, Version,
     password_hash::{
         PasswordHash, PasswordHasher as _, PasswordVerifier, SaltString, rand_core::OsRng,
     },
 };
 
-#[derive(Default, Clone)]
-pub struct Argon2PasswordHasher;
+/// Argon2id hashing with configurable cost. Hashes made with other
+/// parameters still verify and are reported by `needs_rehash`.
+#[derive(Clone, Default)]
+pub struct Argon2PasswordHasher {
+    params: Params,
+}
+
+impl Argon2PasswordHasher {
+    /// Hash with `memory_kib` KiB of memory, `iterations` passes and
+    /// `parallelism` lanes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if argon2 rejects the parameters (e.g. less than
+    /// 8 KiB of memory per lane).
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> AppResult<Self> {
+        let params = Params::new(memory_kib, iterations, parallelism, None)
+            .map_err(|err| AppError::validation(format!("invalid argon2 parameters: {err}")))?;
+        Ok(Self { params })
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+}
 
 impl PasswordHasher for Argon2PasswordHasher {
     fn hash<'a>(&'a self, password: &'a str) -> BoxFuture<'a, AppResult<String>> {
         let password = password.to_owned();
+        let argon2 = self.argon2();
         boxed(async move {
             tokio::task::spawn_blocking(move || {
                 let salt = SaltString::generate(&mut OsRng);
-                let hash = Argon2::default()
+                let hash = argon2
                     .hash_password(password.as_bytes(), &salt)
                     .map_err(|err| AppError::infrastructure(err.to_string()))?;
                 Ok(hash.to_string())
@@ -41,6 +65,7 @@ impl PasswordHasher for Argon2PasswordHasher {
             tokio::task::spawn_blocking(move || -> Result<(), AppError> {
                 let parsed = PasswordHash::new(&expected_hash)
                     .map_err(|err| AppError::infrastructure(err.to_string()))?;
+                // The hash carries its own algorithm and parameters.
                 Argon2::default()
                     .verify_password(password.as_bytes(), &parsed)
                     .map_err(|_| AppError::unauthorized("invalid credentials"))
@@ -50,4 +75,49 @@ impl PasswordHasher for Argon2PasswordHasher {
             Ok(())
         })
     }
+
+    fn needs_rehash(&self, hash: &str) -> bool {
+        // Hashes that do not parse cannot be verified either, so there is
+        // nothing to upgrade.
+        let Ok(parsed) = PasswordHash::new(hash) else {
+            return false;
+        };
+        let Ok(params) = Params::try_from(&parsed) else {
+            return false;
+        };
+
+        parsed.algorithm != Algorithm::Argon2id.ident()
+            || parsed.version != Some(Version::V0x13.into())
+            || params.m_cost() != self.params.m_cost()
+            || params.t_cost() != self.params.t_cost()
+            || params.p_cost() != self.params.p_cost()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn hashes_from_other_parameters_verify_but_need_rehash() {
+        let old = Argon2PasswordHasher::new(8 * 1024, 1, 1).unwrap();
+        let current = Argon2PasswordHasher::new(16 * 1024, 2, 1).unwrap();
+        let hash = old.hash("correct horse").await.unwrap();
+
+        current.verify("correct horse", &hash).await.unwrap();
+        assert!(current.verify("wrong", &hash).await.is_err());
+        assert!(current.needs_rehash(&hash));
+        assert!(!old.needs_rehash(&hash));
+
+        let rehashed = current.hash("correct horse").await.unwrap();
+        assert!(rehashed.contains("m=16384,t=2,p=1"));
+        assert!(!current.needs_rehash(&rehashed));
+        assert!(!current.needs_rehash("not a hash"));
+    }
+
+    #[test]
+    fn rejects_parameters_argon2_cannot_use() {
+        assert!(Argon2PasswordHasher::new(1, 1, 1).is_err());
+        assert!(Argon2PasswordHasher::new(19 * 1024, 0, 1).is_err());
+    }
 }
@@ -0,0 +1,105 @@
+// src/infrastructure/security/rate_limiter.rs
+use crate::application::AppResult;
+use crate::application::ports::rate_limit::RateLimiter;
+use crate::async_support::{BoxFuture, boxed};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Rate-limit counters kept in process memory, for tests and single-node
+/// setups. Each replica counts on its own.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryRateLimiter {
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+#[derive(Debug, Default)]
+struct Entry {
+    count: u32,
+    window_ends: Option<DateTime<Utc>>,
+    locked_until: Option<DateTime<Utc>>,
+}
+
+impl Entry {
+    fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        self.window_ends.is_none_or(|end| end <= now)
+            && self.locked_until.is_none_or(|until| until <= now)
+    }
+}
+
+impl InMemoryRateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RateLimiter for InMemoryRateLimiter {
+    fn locked_until<'a>(
+        &'a self,
+        key: &'a str,
+        now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<Option<DateTime<Utc>>>> {
+        boxed(async move {
+            let entries = self.entries.lock().unwrap();
+            let until = entries.get(key).and_then(|entry| entry.locked_until);
+            drop(entries);
+            Ok(until.filter(|until| *until > now))
+        })
+    }
+
+    fn increment<'a>(
+        &'a self,
+        key: &'a str,
+        window: Duration,
+        now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<u32>> {
+        boxed(async move {
+            let mut entries = self.entries.lock().unwrap();
+            // Dropping lapsed entries here keeps a flood of distinct keys
+            // from growing the map without bound.
+            entries.retain(|_, entry| !entry.is_stale(now));
+            let entry = entries.entry(key.to_string()).or_default();
+            if entry.window_ends.is_none_or(|end| end <= now) {
+                entry.count = 0;
+                entry.window_ends = Some(now + window);
+            }
+            entry.count = entry.count.saturating_add(1);
+            let count = entry.count;
+            drop(entries);
+            Ok(count)
+        })
+    }
+
+    fn lock<'a>(
+        &'a self,
+        key: &'a str,
+        until: DateTime<Utc>,
+        _now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let mut entries = self.entries.lock().unwrap();
+            entries.insert(
+                key.to_string(),
+                Entry {
+                    locked_until: Some(until),
+                    ..Entry::default()
+                },
+            );
+            drop(entries);
+            Ok(())
+        })
+    }
+
+    fn reset<'a>(&'a self, key: &'a str) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let mut entries = self.entries.lock().unwrap();
+            if let Some(entry) = entries.get_mut(key) {
+                entry.count = 0;
+                entry.window_ends = None;
+            }
+            drop(entries);
+            Ok(())
+        })
+    }
+}
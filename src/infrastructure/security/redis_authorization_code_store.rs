@@ -0,0 +1,221 @@
+// src/infrastructure/security/redis_authorization_code_store.rs
+use crate::application::AppResult;
+use crate::application::TokenSubject;
+use crate::application::error::AppError;
+use crate::application::ports::authorization_code::{Code, CodeStore};
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::{Capability, Role, UserId};
+use chrono::{DateTime, Utc};
+use deadpool_redis::{Config as DeadpoolConfig, Connection, Pool, Runtime};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Authorization codes shared by every replica, each stored as JSON under
+/// `oauth_code:<code>` and expiring with the code.
+#[derive(Clone)]
+#[must_use]
+pub struct RedisAuthorizationCodeStore {
+    pool: Pool,
+}
+
+/// Stored form of a [`Code`].
+#[derive(Debug, Serialize, Deserialize)]
+struct CodeRecord {
+    client_id: Option<String>,
+    redirect_uri: Option<String>,
+    user_id: i64,
+    username: String,
+    role: Role,
+    capabilities: HashSet<Capability>,
+    session_id: Option<String>,
+    token_version: Option<u32>,
+    #[serde(default)]
+    custom_role: Option<String>,
+    scope: Option<String>,
+    code_challenge: Option<String>,
+    code_challenge_method: Option<String>,
+    #[serde(default)]
+    nonce: Option<String>,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+impl From<Code> for CodeRecord {
+    fn from(code: Code) -> Self {
+        Self {
+            client_id: code.client_id,
+            redirect_uri: code.redirect_uri,
+            user_id: i64::from(code.subject.user_id),
+            username: code.subject.username,
+            role: code.subject.role,
+            capabilities: code.subject.capabilities,
+            session_id: code.subject.session_id,
+            token_version: code.subject.token_version,
+            custom_role: code.subject.custom_role,
+            scope: code.scope,
+            code_challenge: code.code_challenge,
+            code_challenge_method: code.code_challenge_method,
+            nonce: code.nonce,
+            created_at: code.created_at,
+            expires_at: code.expires_at,
+        }
+    }
+}
+
+impl CodeRecord {
+    fn into_code(self, code: &str) -> AppResult<Code> {
+        Ok(Code {
+            code: code.to_string(),
+            client_id: self.client_id,
+            redirect_uri: self.redirect_uri,
+            subject: TokenSubject {
+                user_id: UserId::new(self.user_id)?,
+                username: self.username,
+                role: self.role,
+                capabilities: self.capabilities,
+                session_id: self.session_id,
+                token_version: self.token_version,
+                custom_role: self.custom_role,
+                impersonator: None,
+            },
+            scope: self.scope,
+            code_challenge: self.code_challenge,
+            code_challenge_method: self.code_challenge_method,
+            nonce: self.nonce,
+            created_at: self.created_at,
+            expires_at: self.expires_at,
+        })
+    }
+}
+
+impl RedisAuthorizationCodeStore {
+    /// Create a store from a Redis URL, e.g. `<redis://:password@host:6379/0>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Redis pool cannot be created.
+    pub fn from_url(url: &str) -> Result<Self, AppError> {
+        let pool = DeadpoolConfig::from_url(url)
+            .create_pool(Some(Runtime::Tokio1))
+            .map_err(|err| AppError::infrastructure(err.to_string()))?;
+        Ok(Self { pool })
+    }
+
+    fn code_key(code: &str) -> String {
+        format!("oauth_code:{code}")
+    }
+
+    async fn connection(&self) -> AppResult<Connection> {
+        self.pool
+            .get()
+            .await
+            .map_err(|err| AppError::infrastructure(err.to_string()))
+    }
+}
+
+/// Seconds until `expires_at`, at least one so Redis accepts the expiry.
+fn ttl_secs(expires_at: DateTime<Utc>, now: DateTime<Utc>) -> u64 {
+    u64::try_from((expires_at - now).num_seconds())
+        .unwrap_or(0)
+        .max(1)
+}
+
+fn decode(code: &str, json: Option<String>) -> AppResult<Option<Code>> {
+    json.map(|json| {
+        serde_json::from_str::<CodeRecord>(&json)
+            .map_err(|err| AppError::infrastructure(format!("corrupt authorization code: {err}")))?
+            .into_code(code)
+    })
+    .transpose()
+}
+
+impl CodeStore for RedisAuthorizationCodeStore {
+    fn create_code(&self, code: Code) -> BoxFuture<'_, AppResult<()>> {
+        boxed(async move {
+            let key = Self::code_key(&code.code);
+            let ttl = ttl_secs(code.expires_at, Utc::now());
+            let json = serde_json::to_string(&CodeRecord::from(code))
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+            let mut conn = self.connection().await?;
+            conn.set_ex::<_, _, ()>(key, json, ttl)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))
+        })
+    }
+
+    fn get_code<'a>(&'a self, code: &'a str) -> BoxFuture<'a, AppResult<Option<Code>>> {
+        boxed(async move {
+            let mut conn = self.connection().await?;
+            let json: Option<String> = conn
+                .get(Self::code_key(code))
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+            decode(code, json)
+        })
+    }
+
+    fn consume_code<'a>(&'a self, code: &'a str) -> BoxFuture<'a, AppResult<Option<Code>>> {
+        boxed(async move {
+            let mut conn = self.connection().await?;
+            // GETDEL hands the code to exactly one of several concurrent
+            // exchanges.
+            let json: Option<String> = redis::cmd("GETDEL")
+                .arg(Self::code_key(code))
+                .query_async(&mut conn)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+            decode(code, json)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn records_round_trip_through_json() {
+        let now = Utc::now();
+        let code = Code {
+            code: "abc".into(),
+            client_id: Some("cli".into()),
+            redirect_uri: Some("https://app.example.com/cb".into()),
+            subject: TokenSubject {
+                user_id: UserId::new(5).unwrap(),
+                username: "alice".into(),
+                role: Role::Author,
+                capabilities: HashSet::from([Capability::new("articles", "create")]),
+                session_id: Some("s-1".into()),
+                token_version: Some(2),
+                custom_role: Some("editor".into()),
+                impersonator: None,
+            },
+            scope: Some("openid".into()),
+            code_challenge: Some("challenge".into()),
+            code_challenge_method: Some("S256".into()),
+            nonce: Some("n-1".into()),
+            created_at: now,
+            expires_at: now + Duration::minutes(5),
+        };
+
+        let json = serde_json::to_string(&CodeRecord::from(code.clone())).unwrap();
+        let restored = decode("abc", Some(json)).unwrap().unwrap();
+        assert_eq!(restored.code, "abc");
+        assert_eq!(restored.subject.user_id, code.subject.user_id);
+        assert_eq!(restored.subject.capabilities, code.subject.capabilities);
+        assert_eq!(restored.subject.custom_role.as_deref(), Some("editor"));
+        assert_eq!(restored.nonce, code.nonce);
+        assert_eq!(restored.code_challenge, code.code_challenge);
+        assert_eq!(restored.expires_at, code.expires_at);
+        assert!(decode("abc", Some("{}".into())).is_err());
+    }
+
+    #[test]
+    fn expired_codes_still_get_a_positive_ttl() {
+        let now = Utc::now();
+        assert_eq!(ttl_secs(now + Duration::seconds(90), now), 90);
+        assert_eq!(ttl_secs(now - Duration::seconds(5), now), 1);
+    }
+}
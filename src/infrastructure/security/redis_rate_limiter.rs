@@ -0,0 +1,144 @@
+// src/infrastructure/security/redis_rate_limiter.rs
+use crate::application::AppResult;
+use crate::application::error::AppError;
+use crate::application::ports::rate_limit::RateLimiter;
+use crate::async_support::{BoxFuture, boxed};
+use chrono::{DateTime, Duration, Utc};
+use deadpool_redis::{Config as DeadpoolConfig, Connection, Pool, Runtime};
+use redis::AsyncCommands;
+
+/// Rate-limit counters shared by every replica: a count under
+/// `rate_count:<key>` expiring with its window, and a lock under
+/// `rate_lock:<key>` holding its end as a Unix timestamp.
+#[derive(Clone)]
+#[must_use]
+pub struct RedisRateLimiter {
+    pool: Pool,
+}
+
+impl RedisRateLimiter {
+    /// Create a limiter from a Redis URL, e.g. `<redis://:password@host:6379/0>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Redis pool cannot be created.
+    pub fn from_url(url: &str) -> Result<Self, AppError> {
+        let pool = DeadpoolConfig::from_url(url)
+            .create_pool(Some(Runtime::Tokio1))
+            .map_err(|err| AppError::infrastructure(err.to_string()))?;
+        Ok(Self { pool })
+    }
+
+    fn count_key(key: &str) -> String {
+        format!("rate_count:{key}")
+    }
+
+    fn lock_key(key: &str) -> String {
+        format!("rate_lock:{key}")
+    }
+
+    async fn connection(&self) -> AppResult<Connection> {
+        self.pool
+            .get()
+            .await
+            .map_err(|err| AppError::infrastructure(err.to_string()))
+    }
+}
+
+/// Whole seconds in `duration`, at least one so Redis accepts the expiry.
+fn ttl_secs(duration: Duration) -> u64 {
+    u64::try_from(duration.num_seconds()).unwrap_or(0).max(1)
+}
+
+impl RateLimiter for RedisRateLimiter {
+    fn locked_until<'a>(
+        &'a self,
+        key: &'a str,
+        now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<Option<DateTime<Utc>>>> {
+        boxed(async move {
+            let mut conn = self.connection().await?;
+            let until: Option<i64> = conn
+                .get(Self::lock_key(key))
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+            Ok(until
+                .and_then(|secs| DateTime::from_timestamp(secs, 0))
+                .filter(|until| *until > now))
+        })
+    }
+
+    fn increment<'a>(
+        &'a self,
+        key: &'a str,
+        window: Duration,
+        _now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<u32>> {
+        boxed(async move {
+            let key = Self::count_key(key);
+            let mut conn = self.connection().await?;
+            // SET NX starts the window with the first hit; later hits only
+            // increment, so the expiry stays put.
+            let (count,): (u32,) = redis::pipe()
+                .atomic()
+                .cmd("SET")
+                .arg(&key)
+                .arg(0)
+                .arg("NX")
+                .arg("EX")
+                .arg(ttl_secs(window))
+                .ignore()
+                .cmd("INCR")
+                .arg(&key)
+                .query_async(&mut conn)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+            Ok(count)
+        })
+    }
+
+    fn lock<'a>(
+        &'a self,
+        key: &'a str,
+        until: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let mut conn = self.connection().await?;
+            redis::pipe()
+                .atomic()
+                .set_ex(
+                    Self::lock_key(key),
+                    until.timestamp(),
+                    ttl_secs(until - now),
+                )
+                .ignore()
+                .del(Self::count_key(key))
+                .ignore()
+                .query_async::<()>(&mut conn)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))
+        })
+    }
+
+    fn reset<'a>(&'a self, key: &'a str) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let mut conn = self.connection().await?;
+            conn.del::<_, ()>(Self::count_key(key))
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_or_negative_durations_still_get_a_positive_ttl() {
+        assert_eq!(ttl_secs(Duration::minutes(15)), 900);
+        assert_eq!(ttl_secs(Duration::milliseconds(300)), 1);
+        assert_eq!(ttl_secs(Duration::seconds(-5)), 1);
+    }
+}
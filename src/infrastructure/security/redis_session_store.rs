@@ -2,8 +2,8 @@
 use crate::application::AppResult;
 use crate::application::error::AppError;
 use crate::application::ports::session_revocation::{
-    OpaqueRefreshTokenStore, RefreshNonceStore, RefreshTokenRecord, Revocation,
-    SessionMetadataStore, Store, TokenVersionStore,
+    CleanupStats, OpaqueRefreshTokenStore, RefreshNonceStore, RefreshTokenFamilyStore,
+    RefreshTokenRecord, Revocation, SessionMetadataStore, Store, TokenVersionStore,
 };
 use crate::async_support::{BoxFuture, boxed};
 use deadpool_redis::{Config as DeadpoolConfig, Connection, Pool, Runtime};
@@ -15,6 +15,25 @@ use tokio::sync::Mutex;
 // TTL for used refresh-nonce markers (in seconds). 7 days by default.
 const USED_NONCE_TTL_SECS: usize = 60 * 60 * 24 * 7; // 604800
 
+// TTL for refresh-token family keys (in seconds). 30 days by default, the
+// default session lifetime.
+const REFRESH_FAMILY_TTL_SECS: u64 = 60 * 60 * 24 * 30;
+
+// Keys requested per SCAN round trip during session cleanup.
+const CLEANUP_SCAN_COUNT: usize = 500;
+
+// Session metadata hash fields, in the order `SessionMetaFields::from_values`
+// reads them.
+const SESSION_META_FIELDS: [&str; 7] = [
+    "user_agent",
+    "ip",
+    "created_at",
+    "user_id",
+    "last_active",
+    "expires_at",
+    "fingerprint",
+];
+
 // Lua script used to atomically rotate the refresh nonce and mark the old
 // nonce as used (with a TTL). Extracted as a constant so helpers can reuse
 // it without inflating function bodies (also helps with Lizard line-count).
@@ -39,18 +58,50 @@ pub struct RedisSessionRevocationStore {
     /// Number of times the CAS script was loaded into Redis (SCRIPT LOAD).
     /// Used by tests to assert EVALSHA caching behavior.
     script_load_count: Arc<AtomicUsize>,
+    /// Number of round trips made by session metadata reads.
+    /// Used by tests to assert the reads are batched.
+    metadata_round_trips: Arc<AtomicUsize>,
     /// TTL for used refresh nonce markers, in seconds.
     ///
     /// Configurable via `REDIS_USED_NONCE_TTL_SECS`.
     used_nonce_ttl_secs: usize,
+    /// TTL for the `refresh_family:sessions:*` and `session_refresh_family:*`
+    /// keys, in seconds; refreshed on every login into the family.
+    refresh_family_ttl_secs: u64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 struct SessionMetaFields {
     user_id: Option<i64>,
     user_agent: Option<String>,
     ip_address: Option<String>,
     created_at_unix: i64,
+    last_active_unix: i64,
+    expires_at_unix: i64,
+    fingerprint: Option<String>,
+}
+
+impl SessionMetaFields {
+    /// Build from an `HMGET` of [`SESSION_META_FIELDS`]. `None` when every
+    /// field is missing, i.e. the hash does not exist.
+    fn from_values(values: Vec<Option<String>>) -> Option<Self> {
+        if values.iter().all(Option::is_none) {
+            return None;
+        }
+
+        let number = |value: Option<String>| value.and_then(|value| value.parse::<i64>().ok());
+        let mut values = values.into_iter();
+        let mut next = || values.next().flatten();
+        Some(Self {
+            user_agent: next(),
+            ip_address: next(),
+            created_at_unix: number(next()).unwrap_or(0),
+            user_id: number(next()),
+            last_active_unix: number(next()).unwrap_or(0),
+            expires_at_unix: number(next()).unwrap_or(0),
+            fingerprint: next(),
+        })
+    }
 }
 
 impl RedisSessionRevocationStore {
@@ -96,7 +147,9 @@ impl RedisSessionRevocationStore {
             pool: pool.clone(),
             cas_script_sha: Arc::new(Mutex::new(None)),
             script_load_count: Arc::new(AtomicUsize::new(0)),
+            metadata_round_trips: Arc::new(AtomicUsize::new(0)),
             used_nonce_ttl_secs,
+            refresh_family_ttl_secs: REFRESH_FAMILY_TTL_SECS,
         };
 
         if preload_cas_script {
@@ -128,6 +181,13 @@ impl RedisSessionRevocationStore {
         Ok(store)
     }
 
+    /// Keep refresh-token family links for `ttl`, the refresh-token
+    /// lifetime, instead of the 30-day default.
+    pub const fn with_refresh_family_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.refresh_family_ttl_secs = ttl.as_secs();
+        self
+    }
+
     /// Helper that executes the CAS lua script using a cached SHA when possible.
     /// Loads the script (SCRIPT LOAD) on first use or when a NOSCRIPT is returned.
     ///
@@ -228,6 +288,13 @@ impl RedisSessionRevocationStore {
         self.script_load_count.load(Ordering::SeqCst)
     }
 
+    /// Return the number of round trips made by session metadata reads
+    /// (test hook).
+    #[must_use]
+    pub fn metadata_round_trips(&self) -> usize {
+        self.metadata_round_trips.load(Ordering::SeqCst)
+    }
+
     async fn evalsha_by_sha(
         &self,
         conn: &mut Connection,
@@ -271,18 +338,34 @@ impl RedisSessionRevocationStore {
         format!("used_refresh_nonce:{session_id}:{nonce}")
     }
 
+    const fn user_sessions_key_prefix() -> &'static str {
+        "user_sessions:"
+    }
+
     fn user_sessions_key(user_id: i64) -> String {
-        format!("user_sessions:{user_id}")
+        format!("{}{user_id}", Self::user_sessions_key_prefix())
+    }
+
+    const fn session_meta_key_prefix() -> &'static str {
+        "session:meta:"
     }
 
     fn session_meta_key(session_id: &str) -> String {
-        format!("session:meta:{session_id}")
+        format!("{}{session_id}", Self::session_meta_key_prefix())
     }
 
     fn session_refresh_tokens_key(session_id: &str) -> String {
         format!("session_refresh_tokens:{session_id}")
     }
 
+    fn refresh_family_sessions_key(family_id: &str) -> String {
+        format!("refresh_family:sessions:{family_id}")
+    }
+
+    fn session_refresh_family_key(session_id: &str) -> String {
+        format!("session_refresh_family:{session_id}")
+    }
+
     async fn connection(&self) -> AppResult<Connection> {
         self.pool
             .get()
@@ -317,36 +400,149 @@ impl RedisSessionRevocationStore {
         Ok(())
     }
 
-    async fn read_session_meta_fields(
-        conn: &mut Connection,
-        session_id: &str,
-    ) -> AppResult<SessionMetaFields> {
-        let meta_key = Self::session_meta_key(session_id);
-        let user_agent: Option<String> = conn
-            .hget(&meta_key, "user_agent")
+    async fn scan_keys(conn: &mut Connection, pattern: &str) -> AppResult<Vec<String>> {
+        let mut cursor: u64 = 0;
+        let mut keys = Vec::new();
+        loop {
+            let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(CLEANUP_SCAN_COUNT)
+                .query_async(conn)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+            keys.extend(batch);
+            if next == 0 {
+                return Ok(keys);
+            }
+            cursor = next;
+        }
+    }
+
+    /// Delete every key kept for a session (metadata, refresh state, family
+    /// link and revocation marker), returning how many keys were removed.
+    async fn forget_session(conn: &mut Connection, session_id: &str) -> AppResult<u64> {
+        let family_key = Self::session_refresh_family_key(session_id);
+        let tokens_key = Self::session_refresh_tokens_key(session_id);
+        let family_id: Option<String> = conn
+            .get(&family_key)
             .await
             .map_err(|err| AppError::infrastructure(err.to_string()))?;
-        let ip_address: Option<String> = conn
-            .hget(&meta_key, "ip")
+        let token_ids: Vec<String> = conn
+            .smembers(&tokens_key)
             .await
             .map_err(|err| AppError::infrastructure(err.to_string()))?;
-        let created_at: Option<String> = conn
-            .hget(&meta_key, "created_at")
+
+        let mut keys = vec![
+            Self::session_meta_key(session_id),
+            Self::session_refresh_nonce_key(session_id),
+            Self::revoked_session_key(session_id),
+            family_key,
+            tokens_key,
+        ];
+        keys.extend(
+            token_ids
+                .iter()
+                .map(|id| Self::refresh_token_record_key(id)),
+        );
+        let removed: u64 = conn
+            .del(&keys)
             .await
             .map_err(|err| AppError::infrastructure(err.to_string()))?;
-        let user_id: Option<i64> = conn
-            .hget(&meta_key, "user_id")
+
+        if let Some(family_id) = family_id {
+            conn.srem::<_, _, ()>(Self::refresh_family_sessions_key(&family_id), session_id)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+        }
+
+        Ok(removed)
+    }
+
+    /// Remove `user_sessions:*` members without metadata. Returns the number
+    /// of members removed and the number of sets that became empty (Redis
+    /// drops empty sets).
+    async fn prune_stale_user_sessions(conn: &mut Connection) -> AppResult<(u64, u64)> {
+        let mut members_removed = 0;
+        let mut sets_removed = 0;
+
+        for key in Self::scan_keys(conn, &format!("{}*", Self::user_sessions_key_prefix())).await? {
+            let members: Vec<String> = conn
+                .smembers(&key)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+
+            let mut stale = Vec::new();
+            for session_id in members {
+                if !Self::session_meta_exists(conn, &session_id).await? {
+                    stale.push(session_id);
+                }
+            }
+            if stale.is_empty() {
+                continue;
+            }
+
+            let removed: u64 = conn
+                .srem(&key, &stale)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+            members_removed += removed;
+
+            let still_exists: bool = conn
+                .exists(&key)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+            if !still_exists {
+                sets_removed += 1;
+            }
+        }
+
+        Ok((members_removed, sets_removed))
+    }
+
+    /// Queue one `HMGET` of the metadata fields and one `EXISTS` of the
+    /// revocation marker per session, to be sent in a single round trip.
+    fn session_reads_pipeline(session_ids: &[String]) -> redis::Pipeline {
+        let mut pipe = redis::pipe();
+        for session_id in session_ids {
+            pipe.cmd("HMGET")
+                .arg(Self::session_meta_key(session_id))
+                .arg(&SESSION_META_FIELDS[..]);
+            pipe.exists(Self::revoked_session_key(session_id));
+        }
+        pipe
+    }
+
+    /// Read the metadata and revocation state of `session_ids` in one round
+    /// trip. Sessions without metadata come back as `None`.
+    async fn read_sessions(
+        &self,
+        conn: &mut Connection,
+        session_ids: &[String],
+    ) -> AppResult<Vec<(Option<SessionMetaFields>, bool)>> {
+        if session_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.metadata_round_trips.fetch_add(1, Ordering::SeqCst);
+        let replies: Vec<redis::Value> = Self::session_reads_pipeline(session_ids)
+            .query_async(conn)
             .await
             .map_err(|err| AppError::infrastructure(err.to_string()))?;
 
-        Ok(SessionMetaFields {
-            user_id,
-            user_agent,
-            ip_address,
-            created_at_unix: created_at
-                .and_then(|value| value.parse::<i64>().ok())
-                .unwrap_or(0),
-        })
+        let mut replies = replies.into_iter();
+        let mut out = Vec::with_capacity(session_ids.len());
+        while let (Some(meta), Some(revoked)) = (replies.next(), replies.next()) {
+            let meta: Vec<Option<String>> = redis::from_redis_value(meta)
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+            let revoked: bool = redis::from_redis_value(revoked)
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+            out.push((SessionMetaFields::from_values(meta), revoked));
+        }
+
+        Ok(out)
     }
 
     async fn session_meta_exists(conn: &mut Connection, session_id: &str) -> AppResult<bool> {
@@ -374,6 +570,9 @@ impl RedisSessionRevocationStore {
             ip_address: meta.ip_address,
             created_at_unix: meta.created_at_unix,
             revoked,
+            last_active_unix: meta.last_active_unix,
+            expires_at_unix: meta.expires_at_unix,
+            fingerprint: meta.fingerprint,
         }
     }
 }
@@ -614,19 +813,20 @@ impl SessionMetadataStore for RedisSessionRevocationStore {
         boxed(async move {
             let mut conn = self.connection().await?;
             let key = Self::user_sessions_key(user_id);
+            self.metadata_round_trips.fetch_add(1, Ordering::SeqCst);
             let sessions: Vec<String> = conn
                 .smembers(&key)
                 .await
                 .map_err(|err| AppError::infrastructure(err.to_string()))?;
 
-            let mut out = Vec::with_capacity(sessions.len());
-            for sid in sessions {
-                let meta = Self::read_session_meta_fields(&mut conn, &sid).await?;
-                let revoked = Self::session_is_revoked(&mut conn, &sid).await?;
-                out.push(Self::build_session_info(&sid, user_id, meta, revoked));
-            }
-
-            Ok(out)
+            let reads = self.read_sessions(&mut conn, &sessions).await?;
+            Ok(sessions
+                .iter()
+                .zip(reads)
+                .map(|(sid, (meta, revoked))| {
+                    Self::build_session_info(sid, user_id, meta.unwrap_or_default(), revoked)
+                })
+                .collect())
         })
     }
 
@@ -671,23 +871,72 @@ impl SessionMetadataStore for RedisSessionRevocationStore {
         })
     }
 
+    fn set_session_lifetime<'a>(
+        &'a self,
+        session_id: &'a str,
+        last_active_unix: i64,
+        expires_at_unix: i64,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let mut conn = self.connection().await?;
+            // Writing to a missing hash would create a metadata-less stub.
+            if !Self::session_meta_exists(&mut conn, session_id).await? {
+                return Ok(());
+            }
+
+            let _: i32 = redis::cmd("HSET")
+                .arg(Self::session_meta_key(session_id))
+                .arg("last_active")
+                .arg(last_active_unix)
+                .arg("expires_at")
+                .arg(expires_at_unix)
+                .query_async(&mut conn)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+
+            Ok(())
+        })
+    }
+
+    fn set_session_fingerprint<'a>(
+        &'a self,
+        session_id: &'a str,
+        fingerprint: &'a str,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let mut conn = self.connection().await?;
+            // Writing to a missing hash would create a metadata-less stub.
+            if !Self::session_meta_exists(&mut conn, session_id).await? {
+                return Ok(());
+            }
+
+            let _: i32 = redis::cmd("HSET")
+                .arg(Self::session_meta_key(session_id))
+                .arg("fingerprint")
+                .arg(fingerprint)
+                .query_async(&mut conn)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+
+            Ok(())
+        })
+    }
+
     fn get_session_metadata<'a>(
         &'a self,
         session_id: &'a str,
     ) -> BoxFuture<'a, AppResult<Option<crate::application::ports::session_revocation::SessionInfo>>>
     {
         boxed(async move {
-            // If the meta hash does not exist, return None
             let mut conn = self.connection().await?;
-            let exists = Self::session_meta_exists(&mut conn, session_id).await?;
-
-            if !exists {
-                return Ok(None);
-            }
+            let reads = self
+                .read_sessions(&mut conn, &[session_id.to_string()])
+                .await?;
 
-            let meta = Self::read_session_meta_fields(&mut conn, session_id).await?;
-            let revoked = Self::session_is_revoked(&mut conn, session_id).await?;
-            Ok(Some(Self::build_session_info(session_id, 0, meta, revoked)))
+            // If the meta hash does not exist, return None
+            Ok(reads.into_iter().next().and_then(|(meta, revoked)| {
+                meta.map(|meta| Self::build_session_info(session_id, 0, meta, revoked))
+            }))
         })
     }
 
@@ -702,6 +951,40 @@ impl SessionMetadataStore for RedisSessionRevocationStore {
             Ok(())
         })
     }
+
+    fn purge_sessions_created_before(
+        &self,
+        cutoff_unix: i64,
+    ) -> BoxFuture<'_, AppResult<CleanupStats>> {
+        boxed(async move {
+            let mut conn = self.connection().await?;
+            let mut stats = CleanupStats::default();
+            let prefix = Self::session_meta_key_prefix();
+
+            for meta_key in Self::scan_keys(&mut conn, &format!("{prefix}*")).await? {
+                let created_at: Option<String> = conn
+                    .hget(&meta_key, "created_at")
+                    .await
+                    .map_err(|err| AppError::infrastructure(err.to_string()))?;
+                let expired = created_at
+                    .and_then(|value| value.parse::<i64>().ok())
+                    .is_some_and(|created| created > 0 && created < cutoff_unix);
+                if !expired {
+                    continue;
+                }
+
+                let session_id = &meta_key[prefix.len()..];
+                stats.expired_sessions += 1;
+                stats.reclaimed_keys += Self::forget_session(&mut conn, session_id).await?;
+            }
+
+            let (stale_members, emptied_sets) = Self::prune_stale_user_sessions(&mut conn).await?;
+            stats.stale_members = stale_members;
+            stats.reclaimed_keys += emptied_sets;
+
+            Ok(stats)
+        })
+    }
 }
 
 impl OpaqueRefreshTokenStore for RedisSessionRevocationStore {
@@ -794,7 +1077,128 @@ impl OpaqueRefreshTokenStore for RedisSessionRevocationStore {
     }
 }
 
+impl RefreshTokenFamilyStore for RedisSessionRevocationStore {
+    fn add_session_to_family<'a>(
+        &'a self,
+        family_id: &'a str,
+        session_id: &'a str,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let sessions_key = Self::refresh_family_sessions_key(family_id);
+            let mut conn = self.connection().await?;
+            redis::pipe()
+                .atomic()
+                .sadd(&sessions_key, session_id)
+                .ignore()
+                .cmd("EXPIRE")
+                .arg(&sessions_key)
+                .arg(self.refresh_family_ttl_secs)
+                .ignore()
+                .set_ex(
+                    Self::session_refresh_family_key(session_id),
+                    family_id,
+                    self.refresh_family_ttl_secs,
+                )
+                .ignore()
+                .query_async::<()>(&mut conn)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))
+        })
+    }
+
+    fn family_for_session<'a>(
+        &'a self,
+        session_id: &'a str,
+    ) -> BoxFuture<'a, AppResult<Option<String>>> {
+        boxed(async move {
+            let mut conn = self.connection().await?;
+            let family_id: Option<String> = conn
+                .get(Self::session_refresh_family_key(session_id))
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+            Ok(family_id)
+        })
+    }
+
+    fn revoke_family<'a>(&'a self, family_id: &'a str) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let sessions_key = Self::refresh_family_sessions_key(family_id);
+            let mut conn = self.connection().await?;
+            let sessions: Vec<String> = conn
+                .smembers(&sessions_key)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+
+            let mut links = vec![sessions_key];
+            for session_id in sessions {
+                conn.set::<_, _, ()>(Self::revoked_session_key(&session_id), 1)
+                    .await
+                    .map_err(|err| AppError::infrastructure(err.to_string()))?;
+                self.delete_refresh_tokens_for_session_inner(&mut conn, &session_id)
+                    .await?;
+                links.push(Self::session_refresh_family_key(&session_id));
+            }
+
+            // The revoked sessions stay revoked; the family has nothing left
+            // to link.
+            conn.del::<_, ()>(&links)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))
+        })
+    }
+}
+
 #[must_use]
 pub fn into_arc(store: RedisSessionRevocationStore) -> std::sync::Arc<dyn Store> {
     std::sync::Arc::new(store)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_reads_queue_two_commands_per_session() {
+        let sessions: Vec<String> = (0..50).map(|i| format!("sid-{i}")).collect();
+        let pipe = RedisSessionRevocationStore::session_reads_pipeline(&sessions);
+
+        // Reading field by field took one HGET per field plus an EXISTS, each
+        // its own round trip; the pipeline sends everything at once.
+        let per_session_before = SESSION_META_FIELDS.len() + 1;
+        assert_eq!(pipe.len(), 2 * sessions.len());
+        assert!(pipe.len() < per_session_before * sessions.len());
+        let hmget = pipe.cmd_iter().next().unwrap().get_packed_command();
+        let hmget = String::from_utf8_lossy(&hmget);
+        assert!(hmget.contains("HMGET") && hmget.contains("session:meta:sid-0"));
+        assert!(hmget.contains("expires_at"));
+    }
+
+    #[test]
+    fn meta_fields_follow_the_field_order() {
+        let values = ["ua", "192.0.2.1", "100", "7", "150", "200", "fp"]
+            .map(|value| Some(value.to_string()))
+            .to_vec();
+        let meta = SessionMetaFields::from_values(values).unwrap();
+        assert_eq!(meta.user_agent.as_deref(), Some("ua"));
+        assert_eq!(meta.ip_address.as_deref(), Some("192.0.2.1"));
+        assert_eq!(meta.created_at_unix, 100);
+        assert_eq!(meta.user_id, Some(7));
+        assert_eq!(meta.last_active_unix, 150);
+        assert_eq!(meta.expires_at_unix, 200);
+        assert_eq!(meta.fingerprint.as_deref(), Some("fp"));
+
+        let legacy = vec![
+            Some("ua".into()),
+            None,
+            Some("100".into()),
+            None,
+            None,
+            None,
+            None,
+        ];
+        let meta = SessionMetaFields::from_values(legacy).unwrap();
+        assert_eq!((meta.user_id, meta.expires_at_unix), (None, 0));
+        assert_eq!(meta.fingerprint, None);
+        assert!(SessionMetaFields::from_values(vec![None; 7]).is_none());
+    }
+}
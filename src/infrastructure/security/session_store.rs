@@ -1,14 +1,47 @@
 // src/infrastructure/security/session_store.rs
 use crate::application::AppResult;
 use crate::application::ports::session_revocation::{
-    OpaqueRefreshTokenStore, RefreshNonceStore, RefreshTokenRecord, Revocation,
-    SessionMetadataStore, Store, TokenVersionStore,
+    CleanupStats, OpaqueRefreshTokenStore, RefreshNonceStore, RefreshTokenFamilyStore,
+    RefreshTokenRecord, Revocation, SessionMetadataStore, Store, TokenVersionStore,
 };
+use crate::application::ports::time::Clock;
 use crate::async_support::{BoxFuture, boxed};
+use crate::infrastructure::time::SystemClock;
+use chrono::Duration;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::sync::Mutex;
 
+/// How long the store remembers markers that only matter for a while.
+/// Expired markers are ignored on read and dropped by the cleanup pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retention {
+    /// Used refresh nonces are kept this long to detect refresh-token reuse.
+    pub used_nonce_ttl: Duration,
+    /// Revoked session markers are kept this long; it must outlast the
+    /// access tokens issued for the session.
+    pub revoked_ttl: Duration,
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self {
+            used_nonce_ttl: Duration::days(7),
+            revoked_ttl: Duration::days(30),
+        }
+    }
+}
+
+/// Entries held by the store, by kind.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EntryCounts {
+    pub sessions: usize,
+    pub revoked_sessions: usize,
+    pub used_nonces: usize,
+    pub refresh_tokens: usize,
+    pub families: usize,
+}
+
 // Local helper struct to store session metadata in-memory
 #[derive(Debug, Clone)]
 struct SessionMeta {
@@ -16,17 +49,20 @@ struct SessionMeta {
     user_agent: Option<String>,
     ip_address: Option<String>,
     created_at_unix: i64,
+    last_active_unix: i64,
+    expires_at_unix: i64,
+    fingerprint: Option<String>,
 }
 
-#[derive(Default)]
 #[must_use]
 pub struct InMemorySessionRevocationStore {
-    revoked: Mutex<HashSet<String>>,
+    // revoked sessions (session_id -> revoked at, unix seconds)
+    revoked: Mutex<HashMap<String, i64>>,
     min_versions: Mutex<HashMap<i64, u32>>,
     // per-session refresh nonce storage (session_id -> nonce)
     session_nonces: Mutex<HashMap<String, String>>,
-    // per-session used nonces (session_id -> set of used nonces)
-    used_nonces: Mutex<HashMap<String, HashSet<String>>>,
+    // per-session used nonces (session_id -> nonce -> used at, unix seconds)
+    used_nonces: Mutex<HashMap<String, HashMap<String, i64>>>,
     // per-user sessions (user_id -> set of session_ids)
     user_sessions: Mutex<HashMap<i64, HashSet<String>>>,
     // per-session metadata (session_id -> SessionMeta)
@@ -35,6 +71,18 @@ pub struct InMemorySessionRevocationStore {
     refresh_token_records: Mutex<HashMap<String, RefreshTokenRecord>>,
     // reverse index for refresh token cleanup (session_id -> token_ids)
     session_refresh_tokens: Mutex<HashMap<String, HashSet<String>>>,
+    // refresh token families (family_id -> set of session_ids)
+    family_sessions: Mutex<HashMap<String, HashSet<String>>>,
+    // reverse index for family lookup (session_id -> family_id)
+    session_families: Mutex<HashMap<String, String>>,
+    clock: Arc<dyn Clock>,
+    retention: Retention,
+}
+
+impl Default for InMemorySessionRevocationStore {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl InMemorySessionRevocationStore {
@@ -44,7 +92,7 @@ impl InMemorySessionRevocationStore {
         // initialization. This is equivalent to `Self::default()` but
         // clearer to readers.
         Self {
-            revoked: Mutex::new(HashSet::new()),
+            revoked: Mutex::new(HashMap::new()),
             min_versions: Mutex::new(HashMap::new()),
             session_nonces: Mutex::new(HashMap::new()),
             used_nonces: Mutex::new(HashMap::new()),
@@ -52,7 +100,101 @@ impl InMemorySessionRevocationStore {
             session_meta: Mutex::new(HashMap::new()),
             refresh_token_records: Mutex::new(HashMap::new()),
             session_refresh_tokens: Mutex::new(HashMap::new()),
+            family_sessions: Mutex::new(HashMap::new()),
+            session_families: Mutex::new(HashMap::new()),
+            clock: Arc::new(SystemClock),
+            retention: Retention::default(),
+        }
+    }
+
+    /// Timestamp revocations and used nonces with `clock`.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Forget used nonces and revocations after `retention`.
+    pub const fn with_retention(mut self, retention: Retention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Count the entries held, e.g. to watch the store's memory use.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a lock was poisoned by a panic while it was held.
+    pub fn entry_counts(&self) -> EntryCounts {
+        EntryCounts {
+            sessions: self.session_meta.lock().unwrap().len(),
+            revoked_sessions: self.revoked.lock().unwrap().len(),
+            used_nonces: self
+                .used_nonces
+                .lock()
+                .unwrap()
+                .values()
+                .map(HashMap::len)
+                .sum(),
+            refresh_tokens: self.refresh_token_records.lock().unwrap().len(),
+            families: self.family_sessions.lock().unwrap().len(),
+        }
+    }
+
+    /// Drop used nonces and revocation markers past their retention,
+    /// returning how many entries were removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a lock was poisoned by a panic while it was held.
+    pub fn sweep_expired(&self) -> u64 {
+        let now = self.clock.now().timestamp();
+        let nonce_cutoff = now - self.retention.used_nonce_ttl.num_seconds();
+        let mut removed = 0;
+
+        let mut used_guard = self.used_nonces.lock().unwrap();
+        for nonces in used_guard.values_mut() {
+            let before = nonces.len();
+            nonces.retain(|_, used_at| *used_at > nonce_cutoff);
+            removed += (before - nonces.len()) as u64;
         }
+        used_guard.retain(|_, nonces| !nonces.is_empty());
+        drop(used_guard);
+
+        let revoked_cutoff = now - self.retention.revoked_ttl.num_seconds();
+        let mut revoked_guard = self.revoked.lock().unwrap();
+        let before = revoked_guard.len();
+        revoked_guard.retain(|_, revoked_at| *revoked_at > revoked_cutoff);
+        removed += (before - revoked_guard.len()) as u64;
+        drop(revoked_guard);
+
+        removed
+    }
+
+    fn is_revoked_in(&self, revoked: &HashMap<String, i64>, session_id: &str) -> bool {
+        let cutoff = self.clock.now().timestamp() - self.retention.revoked_ttl.num_seconds();
+        revoked
+            .get(session_id)
+            .is_some_and(|revoked_at| *revoked_at > cutoff)
+    }
+
+    fn mark_revoked<I>(&self, session_ids: I)
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let now = self.clock.now().timestamp();
+        let mut guard = self.revoked.lock().unwrap();
+        guard.extend(session_ids.into_iter().map(|session_id| (session_id, now)));
+        drop(guard);
+    }
+
+    fn mark_nonce_used(&self, session_id: &str, nonce: &str) {
+        let now = self.clock.now().timestamp();
+        let mut used_guard = self.used_nonces.lock().unwrap();
+        used_guard
+            .entry(session_id.to_string())
+            .or_default()
+            .insert(nonce.to_string(), now);
+        drop(used_guard);
     }
 
     fn delete_refresh_tokens_for_session_inner(&self, session_id: &str) {
@@ -88,6 +230,76 @@ impl InMemorySessionRevocationStore {
         }
     }
 
+    /// Drop the refresh state, family link and revocation marker kept for a
+    /// session, returning how many entries were removed.
+    fn forget_session(&self, session_id: &str) -> u64 {
+        let mut removed = 0;
+        removed += u64::from(
+            self.session_nonces
+                .lock()
+                .unwrap()
+                .remove(session_id)
+                .is_some(),
+        );
+        removed += u64::from(
+            self.used_nonces
+                .lock()
+                .unwrap()
+                .remove(session_id)
+                .is_some(),
+        );
+        removed += u64::from(self.revoked.lock().unwrap().remove(session_id).is_some());
+
+        let token_ids = self
+            .session_refresh_tokens
+            .lock()
+            .unwrap()
+            .remove(session_id);
+        if let Some(token_ids) = token_ids {
+            removed += 1;
+            let mut records_guard = self.refresh_token_records.lock().unwrap();
+            for token_id in token_ids {
+                removed += u64::from(records_guard.remove(&token_id).is_some());
+            }
+        }
+
+        let family_id = self.session_families.lock().unwrap().remove(session_id);
+        if let Some(family_id) = family_id {
+            removed += 1;
+            let mut families_guard = self.family_sessions.lock().unwrap();
+            if let Some(sessions) = families_guard.get_mut(&family_id) {
+                sessions.remove(session_id);
+                if sessions.is_empty() {
+                    families_guard.remove(&family_id);
+                    removed += 1;
+                }
+            }
+            drop(families_guard);
+        }
+
+        removed
+    }
+
+    /// Remove per-user session members without metadata. Returns the number
+    /// of members and the number of emptied user sets removed.
+    fn prune_stale_user_sessions(&self) -> (u64, u64) {
+        let known: HashSet<String> = self.session_meta.lock().unwrap().keys().cloned().collect();
+
+        let mut guard = self.user_sessions.lock().unwrap();
+        let mut members = 0;
+        for sessions in guard.values_mut() {
+            let before = sessions.len();
+            sessions.retain(|session_id| known.contains(session_id));
+            members += (before - sessions.len()) as u64;
+        }
+        let users_before = guard.len();
+        guard.retain(|_, sessions| !sessions.is_empty());
+        let emptied = (users_before - guard.len()) as u64;
+        drop(guard);
+
+        (members, emptied)
+    }
+
     fn session_info_from_meta(
         session_id: String,
         fallback_user_id: i64,
@@ -101,6 +313,9 @@ impl InMemorySessionRevocationStore {
             ip_address: meta.and_then(|value| value.ip_address.clone()),
             created_at_unix: meta.map_or(0, |value| value.created_at_unix),
             revoked,
+            last_active_unix: meta.map_or(0, |value| value.last_active_unix),
+            expires_at_unix: meta.map_or(0, |value| value.expires_at_unix),
+            fingerprint: meta.and_then(|value| value.fingerprint.clone()),
         }
     }
 }
@@ -109,15 +324,15 @@ impl Revocation for InMemorySessionRevocationStore {
     fn is_revoked<'a>(&'a self, session_id: &'a str) -> BoxFuture<'a, AppResult<bool>> {
         boxed(async move {
             let guard = self.revoked.lock().unwrap();
-            Ok(guard.contains(session_id))
+            let revoked = self.is_revoked_in(&guard, session_id);
+            drop(guard);
+            Ok(revoked)
         })
     }
 
     fn revoke<'a>(&'a self, session_id: &'a str) -> BoxFuture<'a, AppResult<()>> {
         boxed(async move {
-            let mut guard = self.revoked.lock().unwrap();
-            guard.insert(session_id.to_string());
-            drop(guard);
+            self.mark_revoked([session_id.to_string()]);
             self.delete_refresh_tokens_for_session_inner(session_id);
             Ok(())
         })
@@ -133,9 +348,7 @@ impl Revocation for InMemorySessionRevocationStore {
             };
 
             if !sessions.is_empty() {
-                let mut revoked_guard = self.revoked.lock().unwrap();
-                revoked_guard.extend(sessions.iter().cloned());
-                drop(revoked_guard);
+                self.mark_revoked(sessions.iter().cloned());
                 self.delete_refresh_tokens_for_sessions(sessions);
             }
 
@@ -209,12 +422,7 @@ impl RefreshNonceStore for InMemorySessionRevocationStore {
             };
 
             if swapped {
-                let mut used_guard = self.used_nonces.lock().unwrap();
-                used_guard
-                    .entry(session_id.to_string())
-                    .or_default()
-                    .insert(expected.to_string());
-                drop(used_guard);
+                self.mark_nonce_used(session_id, expected);
             }
 
             Ok(swapped)
@@ -227,12 +435,7 @@ impl RefreshNonceStore for InMemorySessionRevocationStore {
         nonce: &'a str,
     ) -> BoxFuture<'a, AppResult<()>> {
         boxed(async move {
-            let mut used_guard = self.used_nonces.lock().unwrap();
-            used_guard
-                .entry(session_id.to_string())
-                .or_default()
-                .insert(nonce.to_string());
-            drop(used_guard);
+            self.mark_nonce_used(session_id, nonce);
             Ok(())
         })
     }
@@ -243,10 +446,14 @@ impl RefreshNonceStore for InMemorySessionRevocationStore {
         nonce: &'a str,
     ) -> BoxFuture<'a, AppResult<bool>> {
         boxed(async move {
+            let cutoff = self.clock.now().timestamp() - self.retention.used_nonce_ttl.num_seconds();
             let used_guard = self.used_nonces.lock().unwrap();
-            Ok(used_guard
+            let used = used_guard
                 .get(session_id)
-                .is_some_and(|set| set.contains(nonce)))
+                .and_then(|nonces| nonces.get(nonce))
+                .is_some_and(|used_at| *used_at > cutoff);
+            drop(used_guard);
+            Ok(used)
         })
     }
 }
@@ -287,6 +494,14 @@ impl SessionMetadataStore for InMemorySessionRevocationStore {
             }
 
             let mut meta_guard = self.session_meta.lock().unwrap();
+            let (last_active_unix, expires_at_unix, fingerprint) =
+                meta_guard.get_mut(session_id).map_or((0, 0, None), |meta| {
+                    (
+                        meta.last_active_unix,
+                        meta.expires_at_unix,
+                        meta.fingerprint.take(),
+                    )
+                });
             meta_guard.insert(
                 session_id.to_string(),
                 SessionMeta {
@@ -294,6 +509,9 @@ impl SessionMetadataStore for InMemorySessionRevocationStore {
                     user_agent: user_agent.map(std::string::ToString::to_string),
                     ip_address: ip_address.map(std::string::ToString::to_string),
                     created_at_unix,
+                    last_active_unix,
+                    expires_at_unix,
+                    fingerprint,
                 },
             );
             drop(meta_guard);
@@ -302,6 +520,38 @@ impl SessionMetadataStore for InMemorySessionRevocationStore {
         })
     }
 
+    fn set_session_lifetime<'a>(
+        &'a self,
+        session_id: &'a str,
+        last_active_unix: i64,
+        expires_at_unix: i64,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let mut meta_guard = self.session_meta.lock().unwrap();
+            if let Some(meta) = meta_guard.get_mut(session_id) {
+                meta.last_active_unix = last_active_unix;
+                meta.expires_at_unix = expires_at_unix;
+            }
+            drop(meta_guard);
+            Ok(())
+        })
+    }
+
+    fn set_session_fingerprint<'a>(
+        &'a self,
+        session_id: &'a str,
+        fingerprint: &'a str,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let mut meta_guard = self.session_meta.lock().unwrap();
+            if let Some(meta) = meta_guard.get_mut(session_id) {
+                meta.fingerprint = Some(fingerprint.to_string());
+            }
+            drop(meta_guard);
+            Ok(())
+        })
+    }
+
     fn remove_session_for_user<'a>(
         &'a self,
         user_id: i64,
@@ -359,7 +609,7 @@ impl SessionMetadataStore for InMemorySessionRevocationStore {
                     sid.clone(),
                     user_id,
                     meta_guard.get(&sid),
-                    revoked_guard.contains(&sid),
+                    self.is_revoked_in(&revoked_guard, &sid),
                 ));
             }
 
@@ -388,12 +638,60 @@ impl SessionMetadataStore for InMemorySessionRevocationStore {
                 session_id.to_string(),
                 meta.user_id,
                 Some(&meta),
-                revoked_guard.contains(session_id),
+                self.is_revoked_in(&revoked_guard, session_id),
             );
             drop(revoked_guard);
             Ok(Some(session))
         })
     }
+
+    fn purge_sessions_created_before(
+        &self,
+        cutoff_unix: i64,
+    ) -> BoxFuture<'_, AppResult<CleanupStats>> {
+        boxed(async move {
+            let expired = {
+                let mut meta_guard = self.session_meta.lock().unwrap();
+                let expired = meta_guard
+                    .iter()
+                    .filter(|(_, meta)| {
+                        meta.created_at_unix > 0 && meta.created_at_unix < cutoff_unix
+                    })
+                    .map(|(session_id, _)| session_id.clone())
+                    .collect::<Vec<_>>();
+                for session_id in &expired {
+                    meta_guard.remove(session_id);
+                }
+                expired
+            };
+
+            let mut stats = CleanupStats {
+                expired_sessions: expired.len() as u64,
+                reclaimed_keys: expired.len() as u64,
+                ..CleanupStats::default()
+            };
+            for session_id in &expired {
+                stats.reclaimed_keys += self.forget_session(session_id);
+            }
+
+            let (stale_members, emptied_sets) = self.prune_stale_user_sessions();
+            stats.stale_members = stale_members;
+            stats.reclaimed_keys += emptied_sets + self.sweep_expired();
+
+            let counts = self.entry_counts();
+            tracing::info!(
+                target: "session_store",
+                sessions = counts.sessions,
+                revoked_sessions = counts.revoked_sessions,
+                used_nonces = counts.used_nonces,
+                refresh_tokens = counts.refresh_tokens,
+                families = counts.families,
+                "in-memory session store entries"
+            );
+
+            Ok(stats)
+        })
+    }
 }
 
 impl OpaqueRefreshTokenStore for InMemorySessionRevocationStore {
@@ -467,7 +765,103 @@ impl OpaqueRefreshTokenStore for InMemorySessionRevocationStore {
     }
 }
 
+impl RefreshTokenFamilyStore for InMemorySessionRevocationStore {
+    fn add_session_to_family<'a>(
+        &'a self,
+        family_id: &'a str,
+        session_id: &'a str,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let mut families_guard = self.family_sessions.lock().unwrap();
+            families_guard
+                .entry(family_id.to_string())
+                .or_default()
+                .insert(session_id.to_string());
+            drop(families_guard);
+
+            let mut session_guard = self.session_families.lock().unwrap();
+            session_guard.insert(session_id.to_string(), family_id.to_string());
+            drop(session_guard);
+            Ok(())
+        })
+    }
+
+    fn family_for_session<'a>(
+        &'a self,
+        session_id: &'a str,
+    ) -> BoxFuture<'a, AppResult<Option<String>>> {
+        boxed(async move {
+            let guard = self.session_families.lock().unwrap();
+            Ok(guard.get(session_id).cloned())
+        })
+    }
+
+    fn revoke_family<'a>(&'a self, family_id: &'a str) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let sessions = {
+                let guard = self.family_sessions.lock().unwrap();
+                guard
+                    .get(family_id)
+                    .map_or_else(Vec::new, |set| set.iter().cloned().collect::<Vec<_>>())
+            };
+
+            if !sessions.is_empty() {
+                self.mark_revoked(sessions.iter().cloned());
+                self.delete_refresh_tokens_for_sessions(sessions);
+            }
+
+            Ok(())
+        })
+    }
+}
+
 #[must_use]
 pub fn into_arc(store: InMemorySessionRevocationStore) -> Arc<dyn Store> {
     Arc::new(store)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ports::time::ClockControl;
+    use crate::infrastructure::time::SimulatedClock;
+
+    #[tokio::test]
+    async fn used_nonces_and_revocations_expire_after_their_retention() {
+        let clock = SimulatedClock::new();
+        let store = InMemorySessionRevocationStore::new()
+            .with_clock(Arc::new(clock.clone()))
+            .with_retention(Retention {
+                used_nonce_ttl: Duration::hours(1),
+                revoked_ttl: Duration::hours(2),
+            });
+        store
+            .mark_session_refresh_nonce_used("s1", "n1")
+            .await
+            .unwrap();
+        store.revoke("s1").await.unwrap();
+        assert!(
+            store
+                .is_session_refresh_nonce_used("s1", "n1")
+                .await
+                .unwrap()
+        );
+        assert_eq!(store.entry_counts().used_nonces, 1);
+
+        clock.advance(Duration::minutes(90));
+        assert!(
+            !store
+                .is_session_refresh_nonce_used("s1", "n1")
+                .await
+                .unwrap()
+        );
+        assert!(store.is_revoked("s1").await.unwrap());
+        assert_eq!(store.sweep_expired(), 1);
+
+        clock.advance(Duration::minutes(31));
+        assert!(!store.is_revoked("s1").await.unwrap());
+        let stats = store.purge_sessions_created_before(0).await.unwrap();
+        assert_eq!(stats.reclaimed_keys, 1);
+        assert_eq!(store.entry_counts(), EntryCounts::default());
+    }
+}
@@ -1,10 +1,11 @@
 // src/infrastructure/security/token.rs
 use crate::application::{
-    AuthTokenDto, AuthenticatedUser, TokenSubject,
+    AuthTokenDto, AuthenticatedUser, IdTokenClaims, TokenSubject,
     error::{AppError, AppResult},
-    ports::security::TokenManager,
+    ports::{security::TokenManager, time::Clock},
 };
 use crate::async_support::{BoxFuture, boxed};
+use crate::infrastructure::security::jwt::{JwtAlgorithm, compact_jws};
 use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use biscuit_auth::{
     Biscuit, KeyPair, PrivateKey, PublicKey,
@@ -16,35 +17,142 @@ use sha2::{Digest, Sha256};
 use std::{
     collections::HashMap,
     fmt::Write as _,
-    sync::Arc,
+    sync::{Arc, PoisonError, RwLock},
     time::{Duration, SystemTime},
 };
 
+/// Root keys by id. Tokens are signed with the highest id and carry it as
+/// their root key id; any key in the ring verifies the tokens it signed.
+struct KeyRing {
+    keys: Vec<(u32, KeyPair)>,
+}
+
+impl KeyRing {
+    fn new(keys: &[(u32, String)]) -> AppResult<Self> {
+        let mut ring = keys
+            .iter()
+            .map(|(id, hex)| {
+                let private = PrivateKey::from_bytes_hex(hex, Algorithm::Ed25519)
+                    .map_err(|err| AppError::infrastructure(format!("root key {id}: {err}")))?;
+                Ok((*id, KeyPair::from(&private)))
+            })
+            .collect::<AppResult<Vec<_>>>()?;
+        ring.sort_by_key(|(id, _)| *id);
+        if ring.is_empty() {
+            return Err(AppError::infrastructure(
+                "at least one root key is required",
+            ));
+        }
+        if ring.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+            return Err(AppError::infrastructure("root key ids must be unique"));
+        }
+        Ok(Self { keys: ring })
+    }
+
+    fn signing(&self) -> (u32, &KeyPair) {
+        let (id, keypair) = self.keys.last().expect("key ring is never empty");
+        (*id, keypair)
+    }
+
+    /// The key for `id`. Tokens issued before key ids were recorded carry
+    /// none and are checked against key 0.
+    fn public(&self, id: Option<u32>) -> Option<PublicKey> {
+        let id = id.unwrap_or(0);
+        self.keys
+            .iter()
+            .find(|(key_id, _)| *key_id == id)
+            .map(|(_, keypair)| keypair.public())
+    }
+}
+
 #[derive(Clone)]
 pub struct BiscuitTokenManager {
-    root: Arc<KeyPair>,
-    public: PublicKey,
+    keys: Arc<RwLock<Arc<KeyRing>>>,
     ttl: Duration,
+    clock: Option<Arc<dyn Clock>>,
 }
 
 impl BiscuitTokenManager {
-    /// Create a Biscuit-backed token manager from the configured signing key.
+    /// Create a Biscuit-backed token manager from the configured signing key,
+    /// used as root key 0.
     ///
     /// # Errors
     ///
     /// Returns an error if the private key cannot be parsed.
     pub fn new(private_key_hex: &str, ttl: Duration) -> AppResult<Self> {
-        let private = PrivateKey::from_bytes_hex(private_key_hex, Algorithm::Ed25519)
-            .map_err(|err| AppError::infrastructure(err.to_string()))?;
-        let keypair = KeyPair::from(&private);
-        let public = keypair.public();
+        Self::with_keys(&[(0, private_key_hex.to_string())], ttl)
+    }
 
+    /// Create a token manager from `(id, hex private key)` pairs; see
+    /// [`Self::rotate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no keys, ids repeat, or a key cannot be
+    /// parsed.
+    pub fn with_keys(keys: &[(u32, String)], ttl: Duration) -> AppResult<Self> {
         Ok(Self {
-            root: Arc::new(keypair),
-            public,
+            keys: Arc::new(RwLock::new(Arc::new(KeyRing::new(keys)?))),
             ttl,
+            clock: None,
         })
     }
+
+    /// Replace the root keys for this manager and its clones. New tokens are
+    /// signed with the highest id; tokens signed with a key that is still in
+    /// `keys` stay valid, so add the new key before dropping the old one
+    /// once its tokens have expired.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, keeping the current keys, if there are no keys, ids
+    /// repeat, or a key cannot be parsed.
+    pub fn rotate(&self, keys: &[(u32, String)]) -> AppResult<()> {
+        let ring = Arc::new(KeyRing::new(keys)?);
+        *self.keys.write().unwrap_or_else(PoisonError::into_inner) = ring;
+        Ok(())
+    }
+
+    /// Stamp and check tokens against `clock` instead of the system time.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        self.clock
+            .as_ref()
+            .map_or_else(Utc::now, |clock| clock.now())
+    }
+
+    fn key_ring(&self) -> Arc<KeyRing> {
+        Arc::clone(&self.keys.read().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+/// The Ed25519 public key as a JWK whose `kid` is its RFC 7638 thumbprint.
+fn okp_jwk(public: &PublicKey) -> serde_json::Value {
+    // For Ed25519 (OKP) produce a minimal JWK with x parameter (base64url)
+    let x = URL_SAFE_NO_PAD.encode(public.to_bytes());
+
+    json!({
+        "kty": "OKP",
+        "crv": "Ed25519",
+        "alg": "EdDSA",
+        "use": "sig",
+        "kid": okp_kid(&x),
+        "x": x,
+    })
+}
+
+/// RFC 7638 thumbprint of the Ed25519 key whose base64url public key is `x`.
+fn okp_kid(x: &str) -> String {
+    // For OKP/Ed25519, the canonical members are {"crv":"Ed25519","kty":"OKP","x":"<x>"}
+    let thumbprint_input = format!(r#"{{"crv":"Ed25519","kty":"OKP","x":"{x}"}}"#);
+    let mut hasher = Sha256::new();
+    hasher.update(thumbprint_input.as_bytes());
+    URL_SAFE_NO_PAD.encode(hasher.finalize())
 }
 
 fn build_code_and_params(
@@ -81,6 +189,17 @@ fn build_code_and_params(
         params.insert("ver".to_string(), ver.into());
     }
 
+    // A custom role's capabilities stand in for the role's defaults.
+    if let Some(custom_role) = subject.custom_role.as_ref() {
+        params.insert("crole".to_string(), custom_role.clone().into());
+        code.push_str("custom_role({crole});\n");
+    }
+
+    if let Some(impersonator) = subject.impersonator {
+        params.insert("imp".to_string(), i64::from(impersonator).into());
+        code.push_str("impersonator({imp});\n");
+    }
+
     // Include token_type as a root fact so caveat checks can validate against it.
     // Default to "access" for issued tokens from the manager.
     params.insert("tt".to_string(), "access".to_string().into());
@@ -156,9 +275,10 @@ fn build_and_serialize_biscuit_with_block(
     root_params: HashMap<String, Term>,
     block_code: &str,
     block_params: HashMap<String, Term>,
-    root: &KeyPair,
+    (root_key_id, root): (u32, &KeyPair),
 ) -> Result<String, AppError> {
     let builder = Biscuit::builder()
+        .root_key_id(root_key_id)
         .code_with_params(root_code, root_params, HashMap::new())
         .map_err(|err| AppError::infrastructure(err.to_string()))?;
 
@@ -222,25 +342,27 @@ fn ensure_checks_match_root_tt(
 impl TokenManager for BiscuitTokenManager {
     fn issue(&self, subject: TokenSubject) -> BoxFuture<'_, AppResult<AuthTokenDto>> {
         boxed(async move {
-            let issued_at = SystemTime::now();
+            let ttl = subject.token_ttl(self.ttl);
+            let issued_at = SystemTime::from(self.now());
             let expires_at = issued_at
-                .checked_add(self.ttl)
+                .checked_add(ttl)
                 .ok_or_else(|| AppError::infrastructure("token expiration overflow"))?;
             let (code, params) = build_code_and_params(&subject, issued_at, expires_at);
 
             // Build a separate caveat block for token_type and merge it into the biscuit.
             let (caveat_code, caveat_params) = build_caveat_code_and_params("access");
+            let ring = self.key_ring();
             let serialized = build_and_serialize_biscuit_with_block(
                 &code,
                 params,
                 &caveat_code,
                 caveat_params,
-                self.root.as_ref(),
+                ring.signing(),
             )?;
 
             let issued_at_dt = DateTime::<Utc>::from(issued_at);
             let expires_at_dt = DateTime::<Utc>::from(expires_at);
-            let expires_in = ttl_to_expires_in_seconds(self.ttl);
+            let expires_in = ttl_to_expires_in_seconds(ttl);
             let session_id = subject.session_id;
 
             Ok(AuthTokenDto {
@@ -256,38 +378,42 @@ impl TokenManager for BiscuitTokenManager {
 
     fn public_jwk(&self) -> BoxFuture<'_, AppResult<serde_json::Value>> {
         boxed(async move {
-            // For Ed25519 (OKP) produce a minimal JWK with x parameter (base64url)
-            let key_bytes = self.public.to_bytes();
-            let x = URL_SAFE_NO_PAD.encode(key_bytes);
-
-            // Compute JWK thumbprint (RFC 7638) for a stable `kid` value.
-            // For OKP/Ed25519, the canonical members are {"crv":"Ed25519","kty":"OKP","x":"<x>"}
-            let thumbprint_input = format!(r#"{{"crv":"Ed25519","kty":"OKP","x":"{x}"}}"#);
-            let mut hasher = Sha256::new();
-            hasher.update(thumbprint_input.as_bytes());
-            let kid = URL_SAFE_NO_PAD.encode(hasher.finalize());
-
-            let jwk = json!({
-                "keys": [
-                    {
-                        "kty": "OKP",
-                        "crv": "Ed25519",
-                        "alg": "EdDSA",
-                        "use": "sig",
-                        "x": x,
-                        "kid": kid,
-                    }
-                ]
-            });
+            // Newest key first, so clients that only read one pick the
+            // signing key.
+            let keys: Vec<_> = self
+                .key_ring()
+                .keys
+                .iter()
+                .rev()
+                .map(|(_, keypair)| okp_jwk(&keypair.public()))
+                .collect();
+            Ok(json!({ "keys": keys }))
+        })
+    }
 
-            Ok(jwk)
+    /// Signed as an `EdDSA` JWT with the current root key, which the JWKS
+    /// lists under the same `kid`.
+    fn issue_id_token(&self, claims: &IdTokenClaims) -> AppResult<Option<String>> {
+        let ring = self.key_ring();
+        let (_, keypair) = ring.signing();
+        let kid = okp_kid(&URL_SAFE_NO_PAD.encode(keypair.public().to_bytes()));
+        compact_jws(JwtAlgorithm::EdDsa, &kid, claims, |message| {
+            keypair
+                .sign(message)
+                .map(|signature| signature.to_bytes().to_vec())
+                .map_err(|err| AppError::infrastructure(format!("failed to sign id_token: {err}")))
         })
+        .map(Some)
     }
 
     fn authenticate<'a>(&'a self, token: &'a str) -> BoxFuture<'a, AppResult<AuthenticatedUser>> {
         boxed(async move {
-            let biscuit = Biscuit::from_base64(token, self.public)
-                .map_err(|err| AppError::unauthorized(err.to_string()))?;
+            let ring = self.key_ring();
+            let biscuit = Biscuit::from_base64(token, |id| {
+                ring.public(id)
+                    .ok_or(biscuit_auth::error::Format::UnknownPublicKey)
+            })
+            .map_err(|err| AppError::unauthorized(err.to_string()))?;
 
             // Inspect the biscuit view before authorizing so we can surface meaningful
             // debug information when checks fail.
@@ -314,7 +440,7 @@ impl TokenManager for BiscuitTokenManager {
             // Parse claims into an AuthenticatedUser and perform simple time checks
             // (issued_at <= now <= expires_at).
             let user = crate::infrastructure::security::claims::parse(&facts)?;
-            let now = chrono::Utc::now();
+            let now = self.now();
             if now < user.issued_at || now > user.expires_at {
                 return Err(AppError::unauthorized("token is expired or not yet valid"));
             }
@@ -338,14 +464,10 @@ mod tests {
         let private = PrivateKey::from_bytes_hex(private_hex, Algorithm::Ed25519)
             .expect("create private key");
         let keypair = KeyPair::from(&private);
-        let public = keypair.public();
         let root = Arc::new(keypair);
 
-        let manager = BiscuitTokenManager {
-            root: root.clone(),
-            public,
-            ttl: StdDuration::from_secs(3600),
-        };
+        let manager = BiscuitTokenManager::new(private_hex, StdDuration::from_hours(1))
+            .expect("create manager");
 
         // Create a simple subject
         let mut caps = HashSet::new();
@@ -358,17 +480,18 @@ mod tests {
             capabilities: caps,
             session_id: None,
             token_version: None,
+            custom_role: None,
+            impersonator: None,
         };
 
         let issued_at = SystemTime::now();
         let expires_at = issued_at
-            .checked_add(StdDuration::from_secs(3600))
+            .checked_add(StdDuration::from_hours(1))
             .expect("overflow");
 
         // Build a biscuit WITHOUT the separate caveat block
         let (code, params) = build_code_and_params(&subject, issued_at, expires_at);
-        let token =
-            build_and_serialize_biscuit(&code, params, manager.root.as_ref()).expect("build token");
+        let token = build_and_serialize_biscuit(&code, params, root.as_ref()).expect("build token");
 
         let res = manager.authenticate(&token).await;
         assert!(
@@ -383,14 +506,10 @@ mod tests {
         let private = PrivateKey::from_bytes_hex(private_hex, Algorithm::Ed25519)
             .expect("create private key");
         let keypair = KeyPair::from(&private);
-        let public = keypair.public();
         let root = Arc::new(keypair);
 
-        let manager = BiscuitTokenManager {
-            root: root.clone(),
-            public,
-            ttl: StdDuration::from_secs(3600),
-        };
+        let manager = BiscuitTokenManager::new(private_hex, StdDuration::from_hours(1))
+            .expect("create manager");
 
         let mut caps = HashSet::new();
         caps.insert(Capability::new("articles", "create"));
@@ -402,11 +521,13 @@ mod tests {
             capabilities: caps,
             session_id: None,
             token_version: None,
+            custom_role: None,
+            impersonator: None,
         };
 
         let issued_at = SystemTime::now();
         let expires_at = issued_at
-            .checked_add(StdDuration::from_secs(3600))
+            .checked_add(StdDuration::from_hours(1))
             .expect("overflow");
 
         // Build a biscuit WITH the separate caveat block for token_type("access")
@@ -417,7 +538,7 @@ mod tests {
             params,
             &caveat_code,
             caveat_params,
-            manager.root.as_ref(),
+            (0, root.as_ref()),
         )
         .expect("build token with block");
 
@@ -437,14 +558,10 @@ mod tests {
         let private = PrivateKey::from_bytes_hex(private_hex, Algorithm::Ed25519)
             .expect("create private key");
         let keypair = KeyPair::from(&private);
-        let public = keypair.public();
         let root = Arc::new(keypair);
 
-        let manager = BiscuitTokenManager {
-            root: root.clone(),
-            public,
-            ttl: StdDuration::from_secs(3600),
-        };
+        let manager = BiscuitTokenManager::new(private_hex, StdDuration::from_hours(1))
+            .expect("create manager");
 
         let mut caps = HashSet::new();
         caps.insert(Capability::new("articles", "create"));
@@ -456,11 +573,13 @@ mod tests {
             capabilities: caps,
             session_id: None,
             token_version: None,
+            custom_role: None,
+            impersonator: None,
         };
 
         let issued_at = SystemTime::now();
         let expires_at = issued_at
-            .checked_add(StdDuration::from_secs(3600))
+            .checked_add(StdDuration::from_hours(1))
             .expect("overflow");
 
         // Build a biscuit WITH a caveat block that expects token_type("refresh")
@@ -472,7 +591,7 @@ mod tests {
             params,
             &caveat_code,
             caveat_params,
-            manager.root.as_ref(),
+            (0, root.as_ref()),
         )
         .expect("build token with bad caveat");
 
@@ -482,4 +601,144 @@ mod tests {
             "expected authentication to fail for token with mismatched caveat"
         );
     }
+
+    fn subject() -> TokenSubject {
+        TokenSubject {
+            user_id: UserId::new(1).unwrap(),
+            username: "alice".to_string(),
+            role: Role::Author,
+            capabilities: HashSet::new(),
+            session_id: None,
+            token_version: None,
+            custom_role: None,
+            impersonator: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn rotation_keeps_tokens_signed_with_retained_keys_valid() {
+        let old = "6937d945f8dbe222ae559a9d341a9c70071ef4565367dcf02bf7d5b03a46df1f".to_string();
+        let new = "a".repeat(64);
+        let manager =
+            BiscuitTokenManager::with_keys(&[(1, old.clone())], StdDuration::from_hours(1))
+                .expect("create manager");
+        let before = manager.issue(subject()).await.expect("issue").token;
+
+        manager
+            .rotate(&[(1, old), (2, new.clone())])
+            .expect("rotate");
+        let after = manager.issue(subject()).await.expect("issue").token;
+        assert!(manager.authenticate(&before).await.is_ok());
+        assert!(manager.authenticate(&after).await.is_ok());
+        assert_eq!(
+            Biscuit::from_base64(&after, |_| Ok(manager.key_ring().signing().1.public()))
+                .expect("parse")
+                .root_key_id(),
+            Some(2)
+        );
+
+        let jwks = manager.public_jwk().await.expect("jwks");
+        assert_eq!(jwks["keys"].as_array().map(Vec::len), Some(2));
+
+        manager.rotate(&[(2, new)]).expect("rotate");
+        assert!(manager.authenticate(&before).await.is_err());
+        assert!(manager.authenticate(&after).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn custom_roles_replace_the_builtin_defaults() {
+        let manager =
+            BiscuitTokenManager::with_keys(&[(1, "a".repeat(64))], StdDuration::from_hours(1))
+                .expect("create manager");
+        let token = manager
+            .issue(TokenSubject {
+                capabilities: HashSet::from([Capability::new("moderation", "review")]),
+                custom_role: Some("moderator".into()),
+                ..subject()
+            })
+            .await
+            .expect("issue")
+            .token;
+
+        let user = manager.authenticate(&token).await.expect("authenticate");
+        assert_eq!(user.custom_role.as_deref(), Some("moderator"));
+        assert!(user.has_capability("moderation", "review"));
+        assert!(!user.has_capability("articles", "create"));
+    }
+
+    #[tokio::test]
+    async fn impersonation_tokens_name_the_admin_and_expire_early() {
+        let manager =
+            BiscuitTokenManager::with_keys(&[(1, "a".repeat(64))], StdDuration::from_hours(1))
+                .expect("create manager");
+        let issued = manager
+            .issue(TokenSubject {
+                impersonator: Some(UserId::new(9).unwrap()),
+                ..subject()
+            })
+            .await
+            .expect("issue");
+
+        let user = manager
+            .authenticate(&issued.token)
+            .await
+            .expect("authenticate");
+        assert_eq!(user.impersonator.map(i64::from), Some(9));
+        assert_eq!(issued.expires_in, 15 * 60);
+    }
+
+    #[tokio::test]
+    async fn id_tokens_are_signed_with_the_published_key() {
+        let manager =
+            BiscuitTokenManager::with_keys(&[(1, "a".repeat(64))], StdDuration::from_hours(1))
+                .expect("create manager");
+        let claims = IdTokenClaims {
+            iss: "https://cms.example.com".into(),
+            sub: "1".into(),
+            aud: "client".into(),
+            iat: 0,
+            exp: i64::MAX,
+            nonce: None,
+            preferred_username: "alice".into(),
+            sid: None,
+        };
+        let token = manager
+            .issue_id_token(&claims)
+            .expect("sign")
+            .expect("biscuit managers sign id_tokens");
+
+        let jwks = manager.public_jwk().await.expect("jwks");
+        let jwk = &jwks["keys"][0];
+        let (signing_input, signature) = token.rsplit_once('.').expect("compact JWS");
+        let header: serde_json::Value = serde_json::from_slice(
+            &URL_SAFE_NO_PAD
+                .decode(signing_input.split('.').next().unwrap())
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(header["alg"], "EdDSA");
+        assert_eq!(header["kid"], jwk["kid"]);
+
+        let public = URL_SAFE_NO_PAD.decode(jwk["x"].as_str().unwrap()).unwrap();
+        ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, public)
+            .verify(
+                signing_input.as_bytes(),
+                &URL_SAFE_NO_PAD.decode(signature).unwrap(),
+            )
+            .expect("signature verifies against the JWKS key");
+        assert!(manager.authenticate(&token).await.is_err());
+    }
+
+    #[test]
+    fn key_rings_need_unique_ids() {
+        let key = "a".repeat(64);
+        assert!(BiscuitTokenManager::with_keys(&[], StdDuration::from_hours(1)).is_err());
+        assert!(
+            BiscuitTokenManager::with_keys(
+                &[(1, key.clone()), (1, key)],
+                StdDuration::from_hours(1)
+            )
+            .is_err()
+        );
+    }
 }
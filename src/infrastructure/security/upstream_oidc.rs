@@ -0,0 +1,414 @@
+// src/infrastructure/security/upstream_oidc.rs
+//! OIDC relying party for logging in through upstream providers.
+//!
+//! Provider endpoints come from the issuer's discovery document. Codes are
+//! redeemed with `client_secret_post` and PKCE, and `id_token`s must be
+//! `RS256`-signed by a key from the provider's JWKS.
+use crate::application::AppResult;
+use crate::application::error::AppError;
+use crate::application::ports::oidc_login::{
+    AuthorizationRequest, OidcRelyingParty, UpstreamIdentity,
+};
+use crate::async_support::{BoxFuture, boxed};
+use crate::infrastructure::http_client::send;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use hyper::{Method, Uri};
+use rsa::pkcs1v15::{Signature, VerifyingKey};
+use rsa::sha2::Sha256;
+use rsa::signature::Verifier as _;
+use rsa::{BigUint, RsaPublicKey};
+use serde::Deserialize;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+/// Tolerated clock difference when checking `exp`.
+const CLOCK_SKEW_SECS: i64 = 60;
+const SCOPES: &str = "openid profile email";
+
+/// Endpoints from the provider's discovery document.
+#[derive(Debug, Clone, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct JwkSet {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Jwk {
+    kty: String,
+    kid: Option<String>,
+    n: Option<String>,
+    e: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JwtHeader {
+    alg: String,
+    kid: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Deserialize)]
+struct IdTokenClaims {
+    iss: String,
+    sub: String,
+    aud: Audience,
+    azp: Option<String>,
+    exp: i64,
+    nonce: Option<String>,
+    preferred_username: Option<String>,
+    email: Option<String>,
+}
+
+/// What a valid `id_token` must say about itself.
+struct Expected<'a> {
+    issuer: &'a str,
+    client_id: &'a str,
+    nonce: &'a str,
+    now: DateTime<Utc>,
+}
+
+/// Relying-party client for one upstream provider.
+///
+/// `https://` issuers are expected; `http://` is accepted for local
+/// development providers only.
+#[must_use]
+pub struct UpstreamOidcClient {
+    issuer: String,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+    metadata: Mutex<Option<Arc<ProviderMetadata>>>,
+    keys: Mutex<Arc<JwkSet>>,
+}
+
+impl UpstreamOidcClient {
+    /// # Errors
+    ///
+    /// Returns an error if the issuer is not an `http(s)` URL.
+    pub fn new(
+        issuer: impl Into<String>,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        redirect_uri: impl Into<String>,
+    ) -> Result<Self, AppError> {
+        let issuer = issuer.into();
+        let uri: Uri = issuer
+            .parse()
+            .map_err(|_| AppError::validation("invalid OIDC issuer url"))?;
+        if !matches!(uri.scheme_str(), Some("https" | "http")) || uri.host().is_none() {
+            return Err(AppError::validation(
+                "OIDC issuer must be an http(s) url with a host",
+            ));
+        }
+
+        Ok(Self {
+            issuer,
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            redirect_uri: redirect_uri.into(),
+            metadata: Mutex::default(),
+            keys: Mutex::default(),
+        })
+    }
+
+    async fn metadata(&self) -> AppResult<Arc<ProviderMetadata>> {
+        let cached = self
+            .metadata
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        if let Some(metadata) = cached {
+            return Ok(metadata);
+        }
+
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            self.issuer.trim_end_matches('/')
+        );
+        let metadata: ProviderMetadata = get_json(&url).await?;
+        if metadata.issuer != self.issuer {
+            return Err(AppError::infrastructure(
+                "OIDC discovery document is for a different issuer",
+            ));
+        }
+        let metadata = Arc::new(metadata);
+        *self.metadata.lock().unwrap_or_else(PoisonError::into_inner) = Some(Arc::clone(&metadata));
+        Ok(metadata)
+    }
+
+    /// Signing keys, refetched when `kid` is not among the cached ones so
+    /// provider key rotation is picked up.
+    async fn keys_for(&self, jwks_uri: &str, kid: Option<&str>) -> AppResult<Arc<JwkSet>> {
+        let cached = Arc::clone(&self.keys.lock().unwrap_or_else(PoisonError::into_inner));
+        if find_key(&cached, kid).is_some() {
+            return Ok(cached);
+        }
+        let fresh = Arc::new(get_json::<JwkSet>(jwks_uri).await?);
+        *self.keys.lock().unwrap_or_else(PoisonError::into_inner) = Arc::clone(&fresh);
+        Ok(fresh)
+    }
+
+    async fn redeem(
+        &self,
+        code: &str,
+        code_verifier: &str,
+        nonce: &str,
+    ) -> AppResult<UpstreamIdentity> {
+        let metadata = self.metadata().await?;
+        let form = serde_urlencoded::to_string([
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", self.redirect_uri.as_str()),
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+            ("code_verifier", code_verifier),
+        ])
+        .map_err(|err| AppError::infrastructure(err.to_string()))?;
+
+        let (status, body) = send(
+            Method::POST,
+            &metadata.token_endpoint,
+            &[],
+            Some(("application/x-www-form-urlencoded", form.into_bytes())),
+        )
+        .await?;
+        if !status.is_success() {
+            return Err(AppError::unauthorized(format!(
+                "OIDC provider rejected the authorization code ({status})"
+            )));
+        }
+        let token: TokenResponse = parse_json(&body)?;
+        let id_token = token
+            .id_token
+            .ok_or_else(|| AppError::unauthorized("OIDC provider returned no id_token"))?;
+
+        let kid = decode_header(&id_token)?.kid;
+        let keys = self.keys_for(&metadata.jwks_uri, kid.as_deref()).await?;
+        validate_id_token(
+            &id_token,
+            &keys,
+            &Expected {
+                issuer: &self.issuer,
+                client_id: &self.client_id,
+                nonce,
+                now: Utc::now(),
+            },
+        )
+    }
+}
+
+impl OidcRelyingParty for UpstreamOidcClient {
+    fn authorization_url<'a>(
+        &'a self,
+        request: AuthorizationRequest<'a>,
+    ) -> BoxFuture<'a, AppResult<String>> {
+        boxed(async move {
+            let metadata = self.metadata().await?;
+            let query = serde_urlencoded::to_string([
+                ("response_type", "code"),
+                ("client_id", self.client_id.as_str()),
+                ("redirect_uri", self.redirect_uri.as_str()),
+                ("scope", SCOPES),
+                ("state", request.state),
+                ("nonce", request.nonce),
+                ("code_challenge", request.code_challenge),
+                ("code_challenge_method", "S256"),
+            ])
+            .map_err(|err| AppError::infrastructure(err.to_string()))?;
+            let separator = if metadata.authorization_endpoint.contains('?') {
+                '&'
+            } else {
+                '?'
+            };
+            Ok(format!(
+                "{}{separator}{query}",
+                metadata.authorization_endpoint
+            ))
+        })
+    }
+
+    fn exchange_code<'a>(
+        &'a self,
+        code: &'a str,
+        code_verifier: &'a str,
+        nonce: &'a str,
+    ) -> BoxFuture<'a, AppResult<UpstreamIdentity>> {
+        boxed(async move {
+            tokio::time::timeout(HTTP_TIMEOUT, self.redeem(code, code_verifier, nonce))
+                .await
+                .map_err(|_| AppError::infrastructure("OIDC provider timed out"))?
+        })
+    }
+}
+
+fn find_key<'a>(keys: &'a JwkSet, kid: Option<&str>) -> Option<&'a Jwk> {
+    let mut rsa_keys = keys.keys.iter().filter(|key| key.kty == "RSA");
+    match kid {
+        Some(kid) => rsa_keys.find(|key| key.kid.as_deref() == Some(kid)),
+        // Without a `kid` the choice is only unambiguous for a single key.
+        None => rsa_keys.next().filter(|_| keys.keys.len() == 1),
+    }
+}
+
+fn decode_segment<T: for<'de> Deserialize<'de>>(segment: &str) -> AppResult<T> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(segment)
+        .map_err(|_| AppError::unauthorized("malformed id_token"))?;
+    serde_json::from_slice(&bytes).map_err(|_| AppError::unauthorized("malformed id_token"))
+}
+
+fn decode_header(token: &str) -> AppResult<JwtHeader> {
+    let header = token
+        .split('.')
+        .next()
+        .ok_or_else(|| AppError::unauthorized("malformed id_token"))?;
+    decode_segment(header)
+}
+
+fn validate_id_token(
+    token: &str,
+    keys: &JwkSet,
+    expected: &Expected<'_>,
+) -> AppResult<UpstreamIdentity> {
+    let invalid = |reason: &str| AppError::unauthorized(format!("invalid id_token: {reason}"));
+
+    let mut parts = token.split('.');
+    let (Some(header), Some(payload), Some(signature), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid("not a signed JWT"));
+    };
+
+    let jwt_header: JwtHeader = decode_segment(header)?;
+    if jwt_header.alg != "RS256" {
+        return Err(invalid("unsupported signing algorithm"));
+    }
+    let key = find_key(keys, jwt_header.kid.as_deref()).ok_or_else(|| invalid("unknown key"))?;
+    let verifying_key = rsa_verifying_key(key).ok_or_else(|| invalid("unusable key"))?;
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .ok()
+        .and_then(|bytes| Signature::try_from(bytes.as_slice()).ok())
+        .ok_or_else(|| invalid("malformed signature"))?;
+    let signed = &token[..header.len() + 1 + payload.len()];
+    verifying_key
+        .verify(signed.as_bytes(), &signature)
+        .map_err(|_| invalid("bad signature"))?;
+
+    let claims: IdTokenClaims = decode_segment(payload)?;
+    if claims.iss != expected.issuer {
+        return Err(invalid("wrong issuer"));
+    }
+    if !audience_matches(&claims.aud, claims.azp.as_deref(), expected.client_id) {
+        return Err(invalid("wrong audience"));
+    }
+    if claims.exp + CLOCK_SKEW_SECS < expected.now.timestamp() {
+        return Err(invalid("expired"));
+    }
+    if claims.nonce.as_deref() != Some(expected.nonce) {
+        return Err(invalid("nonce mismatch"));
+    }
+    if claims.sub.is_empty() {
+        return Err(invalid("missing subject"));
+    }
+
+    Ok(UpstreamIdentity {
+        subject: claims.sub,
+        preferred_username: claims.preferred_username,
+        email: claims.email,
+    })
+}
+
+/// `aud` must contain the client; with several audiences the token must
+/// also have been issued to it (`azp`), per OIDC Core §3.1.3.7.
+fn audience_matches(aud: &Audience, azp: Option<&str>, client_id: &str) -> bool {
+    match aud {
+        Audience::One(aud) => aud == client_id && azp.is_none_or(|azp| azp == client_id),
+        Audience::Many(auds) => {
+            auds.iter().any(|aud| aud == client_id) && (auds.len() == 1 || azp == Some(client_id))
+        }
+    }
+}
+
+fn rsa_verifying_key(key: &Jwk) -> Option<VerifyingKey<Sha256>> {
+    let n = URL_SAFE_NO_PAD.decode(key.n.as_deref()?).ok()?;
+    let e = URL_SAFE_NO_PAD.decode(key.e.as_deref()?).ok()?;
+    let public_key =
+        RsaPublicKey::new(BigUint::from_bytes_be(&n), BigUint::from_bytes_be(&e)).ok()?;
+    Some(VerifyingKey::new(public_key))
+}
+
+fn parse_json<T: for<'de> Deserialize<'de>>(body: &[u8]) -> AppResult<T> {
+    serde_json::from_slice(body)
+        .map_err(|err| AppError::infrastructure(format!("invalid OIDC provider response: {err}")))
+}
+
+async fn get_json<T: for<'de> Deserialize<'de>>(url: &str) -> AppResult<T> {
+    let (status, body) = send(Method::GET, url, &[], None).await?;
+    if !status.is_success() {
+        return Err(AppError::infrastructure(format!(
+            "OIDC provider responded with {status} for {url}"
+        )));
+    }
+    parse_json(&body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Audience, JwkSet, UpstreamOidcClient, audience_matches, find_key};
+
+    #[test]
+    fn issuer_must_be_an_http_url() {
+        assert!(UpstreamOidcClient::new("https://accounts.example.com", "id", "s", "r").is_ok());
+        assert!(UpstreamOidcClient::new("accounts.example.com", "id", "s", "r").is_err());
+        assert!(UpstreamOidcClient::new("ftp://accounts.example.com", "id", "s", "r").is_err());
+    }
+
+    #[test]
+    fn multiple_audiences_require_matching_azp() {
+        let many = Audience::Many(vec!["app".into(), "other".into()]);
+        assert!(audience_matches(&many, Some("app"), "app"));
+        assert!(!audience_matches(&many, None, "app"));
+        assert!(!audience_matches(&many, Some("other"), "app"));
+        assert!(audience_matches(&Audience::One("app".into()), None, "app"));
+        assert!(!audience_matches(
+            &Audience::One("other".into()),
+            None,
+            "app"
+        ));
+    }
+
+    #[test]
+    fn keys_without_kid_are_only_used_when_unambiguous() {
+        let keys: JwkSet = serde_json::from_str(
+            r#"{"keys":[{"kty":"RSA","kid":"a","n":"AQ","e":"AQ"},{"kty":"RSA","kid":"b","n":"AQ","e":"AQ"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            find_key(&keys, Some("b")).and_then(|key| key.kid.as_deref()),
+            Some("b")
+        );
+        assert!(find_key(&keys, None).is_none());
+        assert!(find_key(&keys, Some("c")).is_none());
+    }
+}
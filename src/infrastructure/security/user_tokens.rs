@@ -0,0 +1,57 @@
+// src/infrastructure/security/user_tokens.rs
+use crate::application::AppResult;
+use crate::application::ports::user_tokens::{TokenPurpose, UserToken, UserTokenStore};
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::UserId;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Mailed user tokens kept in process memory, for tests and single-node
+/// development setups.
+#[derive(Default)]
+#[must_use]
+pub struct InMemoryUserTokenStore {
+    // token hash -> token
+    tokens: Mutex<HashMap<String, UserToken>>,
+}
+
+impl InMemoryUserTokenStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl UserTokenStore for InMemoryUserTokenStore {
+    fn create(&self, token: UserToken) -> BoxFuture<'_, AppResult<()>> {
+        boxed(async move {
+            let mut tokens = self.tokens.lock().unwrap();
+            tokens.retain(|_, existing| {
+                existing.user_id != token.user_id || existing.purpose != token.purpose
+            });
+            tokens.insert(token.token_hash.clone(), token);
+            drop(tokens);
+            Ok(())
+        })
+    }
+
+    fn consume<'a>(
+        &'a self,
+        token_hash: &'a str,
+        purpose: TokenPurpose,
+        now: DateTime<Utc>,
+    ) -> BoxFuture<'a, AppResult<Option<UserId>>> {
+        boxed(async move {
+            let mut tokens = self.tokens.lock().unwrap();
+            let found = tokens
+                .get(token_hash)
+                .is_some_and(|token| token.purpose == purpose)
+                .then(|| tokens.remove(token_hash))
+                .flatten();
+            drop(tokens);
+            Ok(found
+                .filter(|token| token.expires_at > now)
+                .map(|token| token.user_id))
+        })
+    }
+}
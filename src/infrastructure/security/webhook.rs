@@ -0,0 +1,126 @@
+// src/infrastructure/security/webhook.rs
+use crate::application::error::AppError;
+use crate::application::ports::security_events::{SecurityEventSink, TokenReuseIncident};
+use crate::application::{AppResult, TokenReuseWebhookPayload};
+use crate::async_support::{BoxFuture, boxed};
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::{Request, Uri, header};
+use hyper_util::rt::TokioIo;
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Posts security incidents as JSON to an `http://` endpoint.
+///
+/// Only plain HTTP is supported; point it at a local relay or sidecar when the
+/// final receiver requires TLS.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct HttpSecurityWebhook {
+    uri: Uri,
+    host: String,
+    port: u16,
+}
+
+impl HttpSecurityWebhook {
+    /// Build a webhook sink from an `http://host[:port]/path` URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is malformed, has no host, or does not use
+    /// the `http` scheme.
+    pub fn from_url(url: &str) -> Result<Self, AppError> {
+        let uri: Uri = url
+            .parse()
+            .map_err(|_| AppError::validation("invalid security webhook url"))?;
+
+        if uri.scheme_str() != Some("http") {
+            return Err(AppError::validation(
+                "security webhook url must use the http scheme",
+            ));
+        }
+
+        let host = uri
+            .host()
+            .ok_or_else(|| AppError::validation("security webhook url is missing a host"))?
+            .to_string();
+        let port = uri.port_u16().unwrap_or(80);
+
+        Ok(Self { uri, host, port })
+    }
+
+    async fn post_json(&self, body: Vec<u8>) -> AppResult<()> {
+        let stream = TcpStream::connect((self.host.as_str(), self.port))
+            .await
+            .map_err(|err| AppError::infrastructure(err.to_string()))?;
+        let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .map_err(|err| AppError::infrastructure(err.to_string()))?;
+        tokio::spawn(async move {
+            if let Err(err) = connection.await {
+                tracing::debug!(error = %err, "security webhook connection closed with error");
+            }
+        });
+
+        let authority = self
+            .uri
+            .authority()
+            .map_or_else(|| self.host.clone(), ToString::to_string);
+        let request = Request::post(self.uri.clone())
+            .header(header::HOST, authority)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Full::new(Bytes::from(body)))
+            .map_err(|err| AppError::infrastructure(err.to_string()))?;
+
+        let response = sender
+            .send_request(request)
+            .await
+            .map_err(|err| AppError::infrastructure(err.to_string()))?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(AppError::infrastructure(format!(
+                "security webhook responded with {}",
+                response.status()
+            )))
+        }
+    }
+}
+
+impl SecurityEventSink for HttpSecurityWebhook {
+    fn token_reuse_detected<'a>(
+        &'a self,
+        incident: &'a TokenReuseIncident,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let body = serde_json::to_vec(&TokenReuseWebhookPayload::new(incident.clone()))
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+
+            tokio::time::timeout(WEBHOOK_TIMEOUT, self.post_json(body))
+                .await
+                .map_err(|_| AppError::infrastructure("security webhook timed out"))?
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HttpSecurityWebhook;
+
+    #[test]
+    fn from_url_defaults_port_and_keeps_path() {
+        let webhook = HttpSecurityWebhook::from_url("http://relay.local/hooks/security").unwrap();
+        assert_eq!(webhook.host, "relay.local");
+        assert_eq!(webhook.port, 80);
+        assert_eq!(webhook.uri.path(), "/hooks/security");
+    }
+
+    #[test]
+    fn from_url_rejects_non_http_schemes() {
+        assert!(HttpSecurityWebhook::from_url("https://example.com/hook").is_err());
+        assert!(HttpSecurityWebhook::from_url("not a url").is_err());
+    }
+}
@@ -0,0 +1,93 @@
+// src/infrastructure/seed.rs
+//! Reading seed documents from YAML.
+use crate::application::services::SeedDocument;
+use crate::application::{AppError, AppResult};
+use std::path::Path;
+
+/// Parse a seed document from YAML text.
+///
+/// # Errors
+///
+/// Returns a validation error if the text is not a valid seed document,
+/// including unknown keys.
+pub fn parse(yaml: &str) -> AppResult<SeedDocument> {
+    serde_yaml_ng::from_str::<Option<SeedDocument>>(yaml)
+        .map(Option::unwrap_or_default)
+        .map_err(|err| AppError::validation(format!("seed: {err}")))
+}
+
+/// Read the seed document at `path`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not a valid seed
+/// document.
+pub fn read_file(path: impl AsRef<Path>) -> AppResult<SeedDocument> {
+    let yaml = std::fs::read_to_string(path)
+        .map_err(|err| AppError::infrastructure(format!("seed: {err}")))?;
+    parse(&yaml)
+}
+
+/// The demo content ephemeral instances start from when no `SEED_FILE` is
+/// configured.
+///
+/// # Panics
+///
+/// Never in practice: the embedded document is checked by a unit test.
+#[must_use]
+pub fn demo() -> SeedDocument {
+    parse(include_str!("demo_seed.yaml")).expect("built-in demo seed is valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Role;
+
+    #[test]
+    fn parses_every_section_with_defaults() {
+        let seed = parse(
+            "
+users:
+  - username: admin
+    role: admin
+    email: admin@example.com
+  - username: writer
+custom_fields:
+  - name: subtitle
+    type: text
+pages:
+  - slug: about
+    title: About
+    body: Who we are.
+    author: admin
+    published: true
+",
+        )
+        .unwrap();
+        assert_eq!(seed.users.len(), 2);
+        assert_eq!(seed.users[0].role, Role::Admin);
+        assert_eq!(seed.users[1].role, Role::Author);
+        assert_eq!(seed.custom_fields[0].field_type, "text");
+        assert!(!seed.custom_fields[0].required);
+        assert!(seed.pages[0].published);
+        assert!(seed.pages[0].tags.is_empty());
+    }
+
+    #[test]
+    fn demo_seed_parses_with_valid_passwords() {
+        let seed = demo();
+        assert!(seed.users.iter().any(|user| user.role == Role::Admin));
+        for user in &seed.users {
+            let password = user.password.as_deref().unwrap();
+            crate::application::commands::users::validate_password(password).unwrap();
+        }
+    }
+
+    #[test]
+    fn empty_files_seed_nothing_and_typos_are_rejected() {
+        assert!(parse("").unwrap().users.is_empty());
+        let err = parse("users:\n  - username: admin\n    rol: admin\n").unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+}
@@ -0,0 +1,99 @@
+// src/infrastructure/static_site.rs
+//! Static exports written to a local directory.
+use crate::application::{AppError, AppResult, ports::static_site::SiteWriter};
+use crate::async_support::{BoxFuture, boxed};
+use std::path::{Path, PathBuf};
+
+/// Writes a static export below one directory, created on first write.
+/// Files from earlier exports that are not rewritten are left in place.
+#[derive(Clone, Debug)]
+pub struct DirectorySiteWriter {
+    root: PathBuf,
+}
+
+impl DirectorySiteWriter {
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Paths are built by the export service; refusing anything but plain
+    /// relative segments keeps them inside the root.
+    fn path(&self, path: &str) -> AppResult<PathBuf> {
+        let valid = !path.is_empty()
+            && path.split('/').all(|segment| {
+                !segment.is_empty()
+                    && !segment.starts_with('.')
+                    && segment
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            });
+        if valid {
+            Ok(self.root.join(path))
+        } else {
+            Err(AppError::infrastructure(format!(
+                "invalid static site path '{path}'"
+            )))
+        }
+    }
+}
+
+fn write_error(path: &Path, err: &std::io::Error) -> AppError {
+    AppError::infrastructure(format!("static site {}: {err}", path.display()))
+}
+
+impl SiteWriter for DirectorySiteWriter {
+    fn write<'a>(&'a self, path: &'a str, contents: Vec<u8>) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let path = self.path(path)?;
+            // Write beside the target and rename, so a server publishing the
+            // directory never serves a partial page.
+            tokio::task::spawn_blocking(move || {
+                let write = || {
+                    if let Some(parent) = path.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    let partial = path.with_extension("partial");
+                    std::fs::write(&partial, &contents)?;
+                    std::fs::rename(&partial, &path)
+                };
+                write().map_err(|err| write_error(&path, &err))
+            })
+            .await
+            .map_err(|err| AppError::infrastructure(format!("static site: {err}")))?
+        })
+    }
+
+    fn location(&self) -> String {
+        self.root.display().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn pages_are_written_below_the_root_only() {
+        let root = std::env::temp_dir().join(format!("mokkan-site-{}", std::process::id()));
+        let writer = DirectorySiteWriter::new(&root);
+
+        writer
+            .write("articles/hello.html", b"<p>hi</p>".to_vec())
+            .await
+            .unwrap();
+        writer
+            .write("articles/hello.html", b"<p>again</p>".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read(root.join("articles/hello.html")).unwrap(),
+            b"<p>again</p>"
+        );
+        for path in ["../escape.html", "/etc/passwd", "a//b.html", ""] {
+            assert!(writer.write(path, Vec::new()).await.is_err(), "{path}");
+        }
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
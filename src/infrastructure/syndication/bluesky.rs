@@ -0,0 +1,136 @@
+// src/infrastructure/syndication/bluesky.rs
+use super::{post_json, truncate, validate_url};
+use crate::application::AppResult;
+use crate::application::error::AppError;
+use crate::application::ports::syndication::{Announcement, Syndicator};
+use crate::async_support::{BoxFuture, boxed};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// Longest post Bluesky accepts, in graphemes; counted here in characters.
+const MAX_CHARS: usize = 300;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Session {
+    access_jwt: String,
+    did: String,
+}
+
+/// Posts announcements to one Bluesky account, logging in with an app
+/// password for every post.
+///
+/// The article link is attached as a link facet so it is clickable.
+#[derive(Clone)]
+#[must_use]
+pub struct BlueskySyndicator {
+    service_url: String,
+    identifier: String,
+    app_password: String,
+}
+
+impl BlueskySyndicator {
+    /// `service_url` is the account's PDS, usually `https://bsky.social`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `service_url` is not an `http(s)` URL.
+    pub fn new(
+        service_url: &str,
+        identifier: impl Into<String>,
+        app_password: impl Into<String>,
+    ) -> Result<Self, AppError> {
+        validate_url("Bluesky service", service_url)?;
+        Ok(Self {
+            service_url: service_url.trim_end_matches('/').to_owned(),
+            identifier: identifier.into(),
+            app_password: app_password.into(),
+        })
+    }
+
+    fn xrpc(&self, method: &str) -> String {
+        format!("{}/xrpc/{method}", self.service_url)
+    }
+
+    async fn session(&self) -> AppResult<Session> {
+        let body = post_json(
+            "Bluesky",
+            &self.xrpc("com.atproto.server.createSession"),
+            None,
+            &json!({ "identifier": self.identifier, "password": self.app_password }),
+        )
+        .await?;
+        serde_json::from_slice(&body)
+            .map_err(|err| AppError::infrastructure(format!("invalid Bluesky session: {err}")))
+    }
+}
+
+/// The `app.bsky.feed.post` record for `text`, linking `url` where it
+/// appears.
+fn post_record(text: &str, url: &str, created_at: DateTime<Utc>) -> Value {
+    let text = truncate(text, MAX_CHARS);
+    let mut record = json!({
+        "$type": "app.bsky.feed.post",
+        "text": text,
+        "createdAt": created_at.to_rfc3339_opts(SecondsFormat::Millis, true),
+    });
+    if !url.is_empty()
+        && let Some(start) = text.find(url)
+    {
+        record["facets"] = json!([{
+            "index": { "byteStart": start, "byteEnd": start + url.len() },
+            "features": [{ "$type": "app.bsky.richtext.facet#link", "uri": url }]
+        }]);
+    }
+    record
+}
+
+impl Syndicator for BlueskySyndicator {
+    fn post<'a>(
+        &'a self,
+        text: &'a str,
+        announcement: &'a Announcement,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let session = self.session().await?;
+            let request = json!({
+                "repo": session.did,
+                "collection": "app.bsky.feed.post",
+                "record": post_record(text, &announcement.url, Utc::now()),
+            });
+            post_json(
+                "Bluesky",
+                &self.xrpc("com.atproto.repo.createRecord"),
+                Some(&session.access_jwt),
+                &request,
+            )
+            .await?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::post_record;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn link_facet_uses_byte_offsets() {
+        let created_at = Utc.with_ymd_and_hms(2024, 5, 2, 14, 5, 0).unwrap();
+        let record = post_record(
+            "新着: Hello https://blog.example/hello",
+            "https://blog.example/hello",
+            created_at,
+        );
+        assert_eq!(record["createdAt"], "2024-05-02T14:05:00.000Z");
+        let index = &record["facets"][0]["index"];
+        assert_eq!(index["byteStart"], 14);
+        assert_eq!(index["byteEnd"], 40);
+
+        let cut = post_record(&"x".repeat(400), "https://blog.example/hello", created_at);
+        assert_eq!(cut["text"].as_str().unwrap().chars().count(), 300);
+        assert!(cut.get("facets").is_none());
+    }
+}
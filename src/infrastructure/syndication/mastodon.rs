@@ -0,0 +1,72 @@
+// src/infrastructure/syndication/mastodon.rs
+use super::{post_json, truncate, validate_url};
+use crate::application::AppResult;
+use crate::application::error::AppError;
+use crate::application::ports::syndication::{Announcement, Syndicator};
+use crate::async_support::{BoxFuture, boxed};
+use serde_json::json;
+
+/// Longest status a default Mastodon instance accepts.
+const MAX_CHARS: usize = 500;
+
+/// Posts announcements as public statuses of one Mastodon account.
+#[derive(Clone)]
+#[must_use]
+pub struct MastodonSyndicator {
+    statuses_url: String,
+    access_token: String,
+}
+
+impl MastodonSyndicator {
+    /// `instance_url` is the server's base URL; `access_token` needs the
+    /// `write:statuses` scope.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `instance_url` is not an `http(s)` URL.
+    pub fn new(instance_url: &str, access_token: impl Into<String>) -> Result<Self, AppError> {
+        validate_url("Mastodon instance", instance_url)?;
+        Ok(Self {
+            statuses_url: format!("{}/api/v1/statuses", instance_url.trim_end_matches('/')),
+            access_token: access_token.into(),
+        })
+    }
+}
+
+impl Syndicator for MastodonSyndicator {
+    fn post<'a>(
+        &'a self,
+        text: &'a str,
+        _announcement: &'a Announcement,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let status = json!({
+                "status": truncate(text, MAX_CHARS),
+                "visibility": "public"
+            });
+            post_json(
+                "Mastodon",
+                &self.statuses_url,
+                Some(&self.access_token),
+                &status,
+            )
+            .await?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MastodonSyndicator;
+
+    #[test]
+    fn statuses_url_is_derived_from_the_instance() {
+        let mastodon = MastodonSyndicator::new("https://mastodon.example/", "token").unwrap();
+        assert_eq!(
+            mastodon.statuses_url,
+            "https://mastodon.example/api/v1/statuses"
+        );
+        assert!(MastodonSyndicator::new("mastodon.example", "token").is_err());
+    }
+}
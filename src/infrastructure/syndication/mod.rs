@@ -0,0 +1,88 @@
+// src/infrastructure/syndication/mod.rs
+//! Adapters that announce published articles on external platforms.
+mod bluesky;
+mod mastodon;
+mod slack;
+
+pub use bluesky::BlueskySyndicator;
+pub use mastodon::MastodonSyndicator;
+pub use slack::SlackSyndicator;
+
+use crate::application::AppResult;
+use crate::application::error::AppError;
+use crate::infrastructure::http_client::{is_http_url, send};
+use bytes::Bytes;
+use hyper::Method;
+use hyper::header::{self, HeaderName};
+use serde_json::Value;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn validate_url(platform: &str, url: &str) -> Result<(), AppError> {
+    if is_http_url(url) {
+        Ok(())
+    } else {
+        Err(AppError::validation(format!(
+            "{platform} url must be an http(s) url"
+        )))
+    }
+}
+
+/// Cut `text` to at most `max_chars` characters, ending in `…` when cut.
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_owned();
+    }
+    let mut cut: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    cut.truncate(cut.trim_end().len());
+    cut.push('…');
+    cut
+}
+
+/// POST `body` as JSON, with `token` as a bearer token when given, and
+/// return the response body of a successful call.
+async fn post_json(
+    platform: &str,
+    url: &str,
+    token: Option<&str>,
+    body: &Value,
+) -> AppResult<Bytes> {
+    let authorization = token.map(|token| format!("Bearer {token}"));
+    let headers: Vec<(HeaderName, &str)> = authorization
+        .as_deref()
+        .map(|value| (header::AUTHORIZATION, value))
+        .into_iter()
+        .collect();
+    let payload =
+        serde_json::to_vec(body).map_err(|err| AppError::infrastructure(err.to_string()))?;
+    let (status, body) = tokio::time::timeout(
+        TIMEOUT,
+        send(
+            Method::POST,
+            url,
+            &headers,
+            Some(("application/json", payload)),
+        ),
+    )
+    .await
+    .map_err(|_| AppError::infrastructure(format!("{platform} timed out")))??;
+    if !status.is_success() {
+        return Err(AppError::infrastructure(format!(
+            "{platform} responded with {status}"
+        )));
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::truncate;
+
+    #[test]
+    fn truncate_keeps_short_text_and_marks_cuts() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("ちょうど五字", 6), "ちょうど五字");
+        assert_eq!(truncate("one two three", 8), "one two…");
+    }
+}
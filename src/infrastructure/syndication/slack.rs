@@ -0,0 +1,38 @@
+// src/infrastructure/syndication/slack.rs
+use super::{post_json, validate_url};
+use crate::application::AppResult;
+use crate::application::error::AppError;
+use crate::application::ports::syndication::{Announcement, Syndicator};
+use crate::async_support::{BoxFuture, boxed};
+use serde_json::json;
+
+/// Posts announcements to a Slack channel through an incoming webhook.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct SlackSyndicator {
+    webhook_url: String,
+}
+
+impl SlackSyndicator {
+    /// # Errors
+    ///
+    /// Returns an error if `webhook_url` is not an `http(s)` URL.
+    pub fn new(webhook_url: impl Into<String>) -> Result<Self, AppError> {
+        let webhook_url = webhook_url.into();
+        validate_url("Slack webhook", &webhook_url)?;
+        Ok(Self { webhook_url })
+    }
+}
+
+impl Syndicator for SlackSyndicator {
+    fn post<'a>(
+        &'a self,
+        text: &'a str,
+        _announcement: &'a Announcement,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            post_json("Slack", &self.webhook_url, None, &json!({ "text": text })).await?;
+            Ok(())
+        })
+    }
+}
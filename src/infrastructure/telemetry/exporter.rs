@@ -0,0 +1,306 @@
+// src/infrastructure/telemetry/exporter.rs
+//! Batches finished spans and posts them to an OTLP/HTTP collector as JSON.
+use super::trace_context::{SpanId, TraceId, hex};
+use crate::application::{AppError, AppResult};
+use crate::infrastructure::http_client;
+use hyper::{Method, header::HeaderName};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{mpsc, oneshot};
+
+/// Spans waiting for export; more are dropped.
+const QUEUE_CAPACITY: usize = 4096;
+/// Spans sent per request.
+const MAX_BATCH: usize = 512;
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+/// How long shutdown waits for the last batch.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanKind {
+    Internal,
+    /// Handles a request from another service.
+    Server,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    String(String),
+    Int(i64),
+    Double(f64),
+    Bool(bool),
+}
+
+/// A closed span, ready for export.
+#[derive(Debug, Clone)]
+pub struct FinishedSpan {
+    pub trace_id: TraceId,
+    pub span_id: SpanId,
+    pub parent_span_id: Option<SpanId>,
+    pub name: String,
+    pub kind: SpanKind,
+    pub start: SystemTime,
+    pub end: SystemTime,
+    pub attributes: Vec<(String, AttributeValue)>,
+    /// Why the span failed, if it did.
+    pub error: Option<String>,
+}
+
+pub(super) enum Message {
+    Span(Box<FinishedSpan>),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Where and as whom spans are exported.
+#[derive(Debug, Clone)]
+pub struct ExportTarget {
+    /// Full URL spans are posted to, e.g. `http://collector:4318/v1/traces`.
+    pub url: String,
+    pub service_name: String,
+    /// Sent with every export, e.g. collector credentials.
+    pub headers: Vec<(String, String)>,
+}
+
+/// Queue between the tracing layer and the export task.
+#[derive(Clone)]
+pub(super) struct SpanQueue {
+    sender: mpsc::Sender<Message>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl SpanQueue {
+    pub(super) fn new() -> (Self, mpsc::Receiver<Message>) {
+        let (sender, receiver) = mpsc::channel(QUEUE_CAPACITY);
+        let queue = Self {
+            sender,
+            dropped: Arc::new(AtomicU64::new(0)),
+        };
+        (queue, receiver)
+    }
+
+    /// Enqueue without waiting; a full queue drops the span.
+    pub(super) fn push(&self, span: FinishedSpan) {
+        if self.sender.try_send(Message::Span(Box::new(span))).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Handle on the background export task.
+pub struct Telemetry {
+    sender: mpsc::Sender<Message>,
+}
+
+impl Telemetry {
+    /// Export the spans still queued, waiting a few seconds at most.
+    pub async fn shutdown(self) {
+        let (done, flushed) = oneshot::channel();
+        if self.sender.send(Message::Flush(done)).await.is_ok() {
+            let _ = tokio::time::timeout(SHUTDOWN_TIMEOUT, flushed).await;
+        }
+    }
+}
+
+/// Start the export task. Must run inside a Tokio runtime.
+///
+/// # Errors
+///
+/// Returns an error if the URL is not `http(s)` or a header name is
+/// invalid.
+pub(super) fn spawn(target: ExportTarget) -> AppResult<(SpanQueue, Telemetry)> {
+    if !http_client::is_http_url(&target.url) {
+        return Err(AppError::validation(format!(
+            "span export url must be http(s): {}",
+            target.url
+        )));
+    }
+    let headers = target
+        .headers
+        .iter()
+        .map(|(name, value)| {
+            HeaderName::from_bytes(name.as_bytes())
+                .map(|name| (name, value.clone()))
+                .map_err(|_| AppError::validation(format!("invalid span export header: {name}")))
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+    let (queue, receiver) = SpanQueue::new();
+    let telemetry = Telemetry {
+        sender: queue.sender.clone(),
+    };
+    tokio::spawn(run(
+        Endpoint {
+            url: target.url,
+            service_name: target.service_name,
+            headers,
+        },
+        receiver,
+        Arc::clone(&queue.dropped),
+    ));
+    Ok((queue, telemetry))
+}
+
+/// [`ExportTarget`] with its headers checked.
+struct Endpoint {
+    url: String,
+    service_name: String,
+    headers: Vec<(HeaderName, String)>,
+}
+
+async fn run(target: Endpoint, mut receiver: mpsc::Receiver<Message>, dropped: Arc<AtomicU64>) {
+    let mut batch = Vec::new();
+    let mut ticker = tokio::time::interval(FLUSH_INTERVAL);
+    loop {
+        tokio::select! {
+            message = receiver.recv() => match message {
+                Some(Message::Span(span)) => {
+                    batch.push(*span);
+                    if batch.len() >= MAX_BATCH {
+                        export(&target, &mut batch, &dropped).await;
+                    }
+                }
+                Some(Message::Flush(done)) => {
+                    export(&target, &mut batch, &dropped).await;
+                    let _ = done.send(());
+                }
+                None => {
+                    export(&target, &mut batch, &dropped).await;
+                    return;
+                }
+            },
+            _ = ticker.tick() => export(&target, &mut batch, &dropped).await,
+        }
+    }
+}
+
+async fn export(target: &Endpoint, batch: &mut Vec<FinishedSpan>, dropped: &AtomicU64) {
+    let lost = dropped.swap(0, Ordering::Relaxed);
+    if lost > 0 {
+        tracing::warn!(dropped = lost, "span export queue full, spans dropped");
+    }
+    if batch.is_empty() {
+        return;
+    }
+    let spans = std::mem::take(batch);
+    let body = encode(&target.service_name, &spans)
+        .to_string()
+        .into_bytes();
+    let headers: Vec<(HeaderName, &str)> = target
+        .headers
+        .iter()
+        .map(|(name, value)| (name.clone(), value.as_str()))
+        .collect();
+    match http_client::send(
+        Method::POST,
+        &target.url,
+        &headers,
+        Some(("application/json", body)),
+    )
+    .await
+    {
+        Ok((status, _)) if status.is_success() => {}
+        Ok((status, _)) => {
+            tracing::warn!(%status, spans = spans.len(), "span export rejected");
+        }
+        Err(err) => tracing::warn!(error = %err, spans = spans.len(), "span export failed"),
+    }
+}
+
+/// `spans` as an OTLP/JSON `ExportTraceServiceRequest`.
+pub(super) fn encode(service_name: &str, spans: &[FinishedSpan]) -> Value {
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [attribute("service.name", &AttributeValue::String(service_name.into()))],
+            },
+            "scopeSpans": [{
+                "scope": { "name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION") },
+                "spans": spans.iter().map(encode_span).collect::<Vec<_>>(),
+            }],
+        }],
+    })
+}
+
+fn encode_span(span: &FinishedSpan) -> Value {
+    let mut encoded = json!({
+        "traceId": hex(&span.trace_id),
+        "spanId": hex(&span.span_id),
+        "name": span.name,
+        "kind": match span.kind {
+            SpanKind::Internal => 1,
+            SpanKind::Server => 2,
+        },
+        "startTimeUnixNano": unix_nanos(span.start),
+        "endTimeUnixNano": unix_nanos(span.end),
+        "attributes": span
+            .attributes
+            .iter()
+            .map(|(key, value)| attribute(key, value))
+            .collect::<Vec<_>>(),
+    });
+    if let Some(parent) = &span.parent_span_id {
+        encoded["parentSpanId"] = json!(hex(parent));
+    }
+    if let Some(message) = &span.error {
+        encoded["status"] = json!({ "code": 2, "message": message });
+    }
+    encoded
+}
+
+fn attribute(key: &str, value: &AttributeValue) -> Value {
+    // 64-bit integers are strings in OTLP/JSON.
+    let value = match value {
+        AttributeValue::String(text) => json!({ "stringValue": text }),
+        AttributeValue::Int(number) => json!({ "intValue": number.to_string() }),
+        AttributeValue::Double(number) => json!({ "doubleValue": number }),
+        AttributeValue::Bool(flag) => json!({ "boolValue": flag }),
+    };
+    json!({ "key": key, "value": value })
+}
+
+fn unix_nanos(at: SystemTime) -> String {
+    at.duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos())
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_are_encoded_as_otlp_json() {
+        let start = UNIX_EPOCH + Duration::from_secs(1);
+        let span = FinishedSpan {
+            trace_id: [0xab; 16],
+            span_id: [1; 8],
+            parent_span_id: Some([2; 8]),
+            name: "GET".into(),
+            kind: SpanKind::Server,
+            start,
+            end: start + Duration::from_millis(5),
+            attributes: vec![
+                ("http.response.status_code".into(), AttributeValue::Int(500)),
+                ("url.path".into(), AttributeValue::String("/x".into())),
+            ],
+            error: Some("boom".into()),
+        };
+
+        let body = encode("mokkan", &[span]);
+        let resource = &body["resourceSpans"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "mokkan"
+        );
+        let span = &resource["scopeSpans"][0]["spans"][0];
+        assert_eq!(span["traceId"], "ab".repeat(16));
+        assert_eq!(span["spanId"], "0101010101010101");
+        assert_eq!(span["parentSpanId"], "0202020202020202");
+        assert_eq!(span["kind"], 2);
+        assert_eq!(span["startTimeUnixNano"], "1000000000");
+        assert_eq!(span["endTimeUnixNano"], "1005000000");
+        assert_eq!(span["attributes"][0]["value"]["intValue"], "500");
+        assert_eq!(span["status"]["code"], 2);
+    }
+}
@@ -0,0 +1,274 @@
+// src/infrastructure/telemetry/layer.rs
+//! A `tracing` layer that turns closed spans into OpenTelemetry spans.
+//!
+//! A few span fields are read rather than exported, following the usual
+//! `tracing` conventions: `otel.name` renames the span, `otel.kind =
+//! "server"` marks request handling, `otel.status_code = "ERROR"` fails it,
+//! and a `traceparent` field on a root span continues the caller's trace.
+//! Spans also fail when an `ERROR` event is recorded inside them.
+use super::exporter::{AttributeValue, FinishedSpan, SpanKind, SpanQueue};
+use super::trace_context::{SpanId, TraceId, TraceParent, random_id};
+use std::fmt;
+use std::time::SystemTime;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Exports every sampled span it sees once the span closes.
+pub struct OtlpLayer {
+    queue: SpanQueue,
+}
+
+impl OtlpLayer {
+    pub(super) const fn new(queue: SpanQueue) -> Self {
+        Self { queue }
+    }
+}
+
+/// What the layer keeps in a span's extensions while it is open.
+struct SpanState {
+    trace_id: TraceId,
+    span_id: SpanId,
+    parent_span_id: Option<SpanId>,
+    sampled: bool,
+    start: SystemTime,
+    fields: Fields,
+}
+
+#[derive(Default)]
+struct Fields {
+    name: Option<String>,
+    kind: Option<SpanKind>,
+    traceparent: Option<TraceParent>,
+    error: Option<String>,
+    attributes: Vec<(String, AttributeValue)>,
+}
+
+impl Fields {
+    fn set(&mut self, field: &Field, value: AttributeValue) {
+        let text = || match &value {
+            AttributeValue::String(text) => text.clone(),
+            other => format!("{other:?}"),
+        };
+        match field.name() {
+            "otel.name" => self.name = Some(text()),
+            "otel.kind" => {
+                self.kind = Some(if text().eq_ignore_ascii_case("server") {
+                    SpanKind::Server
+                } else {
+                    SpanKind::Internal
+                });
+            }
+            "otel.status_code" => {
+                if text().eq_ignore_ascii_case("error") {
+                    self.error.get_or_insert_with(String::new);
+                }
+            }
+            "traceparent" => self.traceparent = TraceParent::parse(&text()),
+            name => {
+                self.attributes.retain(|(key, _)| key != name);
+                self.attributes.push((name.to_string(), value));
+            }
+        }
+    }
+}
+
+impl Visit for Fields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.set(field, AttributeValue::Double(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.set(field, AttributeValue::Int(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.set(
+            field,
+            AttributeValue::Int(i64::try_from(value).unwrap_or(i64::MAX)),
+        );
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.set(field, AttributeValue::Bool(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.set(field, AttributeValue::String(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.set(field, AttributeValue::String(format!("{value:?}")));
+    }
+}
+
+/// The `message` of an event, for failed span statuses.
+#[derive(Default)]
+struct Message(Option<String>);
+
+impl Visit for Message {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
+
+impl<S> Layer<S> for OtlpLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+
+        let parent = span.parent().and_then(|parent| {
+            parent
+                .extensions()
+                .get::<SpanState>()
+                .map(|state| (state.trace_id, state.span_id, state.sampled))
+        });
+        let (trace_id, parent_span_id, sampled) = match (parent, fields.traceparent) {
+            (Some((trace_id, span_id, sampled)), _) => (trace_id, Some(span_id), sampled),
+            (None, Some(remote)) => (remote.trace_id, Some(remote.span_id), remote.sampled),
+            (None, None) => (random_id(), None, true),
+        };
+        span.extensions_mut().insert(SpanState {
+            trace_id,
+            span_id: random_id(),
+            parent_span_id,
+            sampled,
+            start: SystemTime::now(),
+            fields,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && let Some(state) = span.extensions_mut().get_mut::<SpanState>()
+        {
+            values.record(&mut state.fields);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if *event.metadata().level() != Level::ERROR {
+            return;
+        }
+        if let Some(span) = ctx.event_span(event)
+            && let Some(state) = span.extensions_mut().get_mut::<SpanState>()
+        {
+            let mut message = Message::default();
+            event.record(&mut message);
+            state.fields.error = Some(message.0.unwrap_or_default());
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(state) = span.extensions_mut().remove::<SpanState>() else {
+            return;
+        };
+        if !state.sampled {
+            return;
+        }
+        self.queue.push(FinishedSpan {
+            trace_id: state.trace_id,
+            span_id: state.span_id,
+            parent_span_id: state.parent_span_id,
+            name: state.fields.name.unwrap_or_else(|| span.name().to_string()),
+            kind: state.fields.kind.unwrap_or(SpanKind::Internal),
+            start: state.start,
+            end: SystemTime::now(),
+            attributes: state.fields.attributes,
+            error: state.fields.error,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::exporter::Message as Queued;
+    use super::super::trace_context::hex;
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt as _;
+
+    fn capture(work: impl FnOnce()) -> Vec<FinishedSpan> {
+        let (queue, mut receiver) = SpanQueue::new();
+        let subscriber = tracing_subscriber::registry().with(OtlpLayer::new(queue));
+        tracing::subscriber::with_default(subscriber, work);
+        let mut spans = Vec::new();
+        while let Ok(message) = receiver.try_recv() {
+            if let Queued::Span(span) = message {
+                spans.push(*span);
+            }
+        }
+        spans
+    }
+
+    #[test]
+    fn child_spans_join_the_callers_trace() {
+        let spans = capture(|| {
+            let request = tracing::info_span!(
+                "request",
+                otel.name = "GET /articles",
+                otel.kind = "server",
+                traceparent = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+                url.path = "/articles",
+            );
+            let _request = request.enter();
+            let _repository = tracing::info_span!("repository", otel.name = "list").entered();
+            tracing::error!("database unavailable");
+        });
+
+        let [child, root] = spans.as_slice() else {
+            panic!("{spans:?}");
+        };
+        assert_eq!(hex(&root.trace_id), "0af7651916cd43dd8448eb211c80319c");
+        assert_eq!(
+            root.parent_span_id.map(|id| hex(&id)).as_deref(),
+            Some("b7ad6b7169203331")
+        );
+        assert_eq!(root.name, "GET /articles");
+        assert_eq!(root.kind, SpanKind::Server);
+        assert_eq!(
+            root.attributes,
+            vec![(
+                "url.path".into(),
+                AttributeValue::String("/articles".into())
+            )]
+        );
+        assert!(root.error.is_none());
+
+        assert_eq!(child.trace_id, root.trace_id);
+        assert_eq!(child.parent_span_id, Some(root.span_id));
+        assert_eq!(child.name, "list");
+        assert_eq!(child.error.as_deref(), Some("database unavailable"));
+    }
+
+    #[test]
+    fn unsampled_callers_are_not_exported() {
+        let spans = capture(|| {
+            let _request = tracing::info_span!(
+                "request",
+                traceparent = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-00",
+            )
+            .entered();
+            let _child = tracing::info_span!("child").entered();
+        });
+        assert!(spans.is_empty(), "{spans:?}");
+    }
+}
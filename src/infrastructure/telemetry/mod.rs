@@ -0,0 +1,24 @@
+// src/infrastructure/telemetry/mod.rs
+//! OpenTelemetry trace export over OTLP/HTTP with JSON bodies.
+//!
+//! [`OtlpLayer`] turns closed `tracing` spans into OpenTelemetry spans and a
+//! background task posts them in batches, so no OpenTelemetry SDK is linked.
+mod exporter;
+mod layer;
+pub mod trace_context;
+
+pub use exporter::{ExportTarget, Telemetry};
+pub use layer::OtlpLayer;
+
+use crate::application::AppResult;
+
+/// Build the layer and start exporting its spans to `target`. Must run
+/// inside a Tokio runtime; call [`Telemetry::shutdown`] before exiting.
+///
+/// # Errors
+///
+/// Returns an error if the target URL or a header name is invalid.
+pub fn pipeline(target: ExportTarget) -> AppResult<(OtlpLayer, Telemetry)> {
+    let (queue, telemetry) = exporter::spawn(target)?;
+    Ok((OtlpLayer::new(queue), telemetry))
+}
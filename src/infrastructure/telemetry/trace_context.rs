@@ -0,0 +1,126 @@
+// src/infrastructure/telemetry/trace_context.rs
+//! W3C Trace Context `traceparent` values.
+use std::fmt::{self, Write as _};
+
+pub type TraceId = [u8; 16];
+pub type SpanId = [u8; 8];
+
+/// The caller's span, as carried by a `traceparent` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceParent {
+    pub trace_id: TraceId,
+    pub span_id: SpanId,
+    /// The caller recorded its span; unsampled traces are not exported.
+    pub sampled: bool,
+}
+
+impl TraceParent {
+    /// Parse `00-<trace id>-<parent id>-<flags>`. Later versions are read
+    /// by their first four fields, as the specification asks.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split('-');
+        let version = parts.next().filter(|v| v.len() == 2)?;
+        let trace_id = parts.next().and_then(decode::<16>)?;
+        let span_id = parts.next().and_then(decode::<8>)?;
+        let flags = parts.next().and_then(decode::<1>)?;
+        let valid_version = match version {
+            "00" => parts.next().is_none(),
+            "ff" => false,
+            _ => decode::<1>(version).is_some(),
+        };
+        let valid = valid_version && trace_id != [0; 16] && span_id != [0; 8];
+        valid.then_some(Self {
+            trace_id,
+            span_id,
+            sampled: flags[0] & 1 == 1,
+        })
+    }
+}
+
+impl fmt::Display for TraceParent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "00-{}-{}-{:02x}",
+            hex(&self.trace_id),
+            hex(&self.span_id),
+            u8::from(self.sampled)
+        )
+    }
+}
+
+/// Lowercase hex, as ids are written in headers and OTLP/JSON.
+#[must_use]
+pub fn hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(out, "{byte:02x}");
+    }
+    out
+}
+
+fn decode<const N: usize>(text: &str) -> Option<[u8; N]> {
+    if text.len() != N * 2 || !text.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+        return None;
+    }
+    let mut bytes = [0; N];
+    for (byte, pair) in bytes.iter_mut().zip(text.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+/// Fresh random ids. Falls back to a clock-derived value in the unlikely
+/// case the system random source fails, so tracing never fails a request.
+#[must_use]
+pub fn random_id<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+    if getrandom::fill(&mut bytes).is_err() || bytes == [0; N] {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(1, |elapsed| elapsed.as_nanos() | 1);
+        for (byte, source) in bytes.iter_mut().zip(nanos.to_le_bytes().iter().cycle()) {
+            *byte = *source;
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn traceparent_round_trips() {
+        let parent = TraceParent::parse(HEADER).unwrap();
+        assert_eq!(hex(&parent.trace_id), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(hex(&parent.span_id), "00f067aa0ba902b7");
+        assert!(parent.sampled);
+        assert_eq!(parent.to_string(), HEADER);
+    }
+
+    #[test]
+    fn malformed_traceparents_are_ignored() {
+        for value in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        ] {
+            assert_eq!(TraceParent::parse(value), None, "{value}");
+        }
+        let future = format!("01{}-extra", &HEADER[2..]);
+        assert!(TraceParent::parse(&future).is_some());
+        assert!(
+            !TraceParent::parse(&HEADER.replace("-01", "-00"))
+                .unwrap()
+                .sampled
+        );
+    }
+}
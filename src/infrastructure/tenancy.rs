@@ -0,0 +1,127 @@
+// src/infrastructure/tenancy.rs
+//! Schema-per-tenant routing.
+//!
+//! Each tenant owns a Postgres schema named `tenant_<id>` containing the full
+//! application schema. The tenant resolved for a request is carried in a
+//! task-local, and tenant-routed pools (see
+//! [`database::init_tenant_routed_pool`](super::database::init_tenant_routed_pool))
+//! point every acquired connection's `search_path` at that schema, so the
+//! repositories stay unaware of tenancy.
+use std::fmt;
+use std::future::Future;
+
+/// Prefix applied to tenant ids to form their schema names.
+pub const TENANT_SCHEMA_PREFIX: &str = "tenant_";
+
+const MAX_TENANT_ID_LEN: usize = 48;
+
+tokio::task_local! {
+    static CURRENT_TENANT: TenantSchema;
+}
+
+/// A validated tenant id and the schema that holds its data.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TenantSchema {
+    id: String,
+}
+
+impl TenantSchema {
+    /// Validate a tenant id.
+    ///
+    /// Ids are limited to lowercase ASCII letters, digits and `_` so the
+    /// derived schema name never needs escaping beyond double quotes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error message if the id is empty, too long or contains
+    /// characters outside `[a-z0-9_]`.
+    pub fn new(id: &str) -> Result<Self, String> {
+        if id.is_empty() || id.len() > MAX_TENANT_ID_LEN {
+            return Err(format!(
+                "tenant id must be between 1 and {MAX_TENANT_ID_LEN} characters"
+            ));
+        }
+
+        if !id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(format!(
+                "tenant id `{id}` may only contain lowercase letters, digits and underscores"
+            ));
+        }
+
+        Ok(Self { id: id.to_string() })
+    }
+
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    #[must_use]
+    pub fn schema_name(&self) -> String {
+        format!("{TENANT_SCHEMA_PREFIX}{}", self.id)
+    }
+
+    /// `search_path` value for this tenant. `public` stays on the path so
+    /// extension types and operators (`citext`, `pg_trgm`) still resolve.
+    #[must_use]
+    pub fn search_path(&self) -> String {
+        format!("\"{}\", public", self.schema_name())
+    }
+}
+
+impl fmt::Display for TenantSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.id)
+    }
+}
+
+/// Run `fut` with `tenant` as the current tenant.
+pub async fn scope<F>(tenant: TenantSchema, fut: F) -> F::Output
+where
+    F: Future,
+{
+    CURRENT_TENANT.scope(tenant, fut).await
+}
+
+/// Tenant of the current task, if one was scoped.
+#[must_use]
+pub fn current() -> Option<TenantSchema> {
+    CURRENT_TENANT.try_with(Clone::clone).ok()
+}
+
+/// `SET search_path` statement for the current task's tenant, falling back to
+/// the default `public` schema outside a tenant scope.
+#[must_use]
+pub fn search_path_statement() -> String {
+    current().map_or_else(
+        || "SET search_path TO public".to_string(),
+        |tenant| format!("SET search_path TO {}", tenant.search_path()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TenantSchema, current, scope, search_path_statement};
+
+    #[test]
+    fn tenant_ids_are_restricted_to_safe_identifiers() {
+        assert!(TenantSchema::new("acme_01").is_ok());
+        assert!(TenantSchema::new("").is_err());
+        assert!(TenantSchema::new("Acme").is_err());
+        assert!(TenantSchema::new("acme\"; DROP SCHEMA public; --").is_err());
+        assert!(TenantSchema::new(&"a".repeat(49)).is_err());
+    }
+
+    #[tokio::test]
+    async fn search_path_follows_the_scoped_tenant() {
+        assert!(current().is_none());
+        assert_eq!(search_path_statement(), "SET search_path TO public");
+
+        let tenant = TenantSchema::new("acme").unwrap();
+        let statement = scope(tenant, async { search_path_statement() }).await;
+        assert_eq!(statement, "SET search_path TO \"tenant_acme\", public");
+    }
+}
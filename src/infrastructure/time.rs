@@ -1,6 +1,10 @@
 // src/infrastructure/time.rs
-use crate::application::ports::time::Clock;
-use chrono::{DateTime, Utc};
+use crate::application::ports::time::{Clock, ClockControl};
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{
+    Arc,
+    atomic::{AtomicI64, Ordering},
+};
 
 #[derive(Default, Clone)]
 pub struct SystemClock;
@@ -10,3 +14,51 @@ impl Clock for SystemClock {
         Utc::now()
     }
 }
+
+/// Real time plus an offset that can only grow. Clones share the offset, so
+/// one handle can serve as the `Clock` and another as the `ClockControl`.
+#[derive(Default, Clone)]
+pub struct SimulatedClock {
+    offset_ms: Arc<AtomicI64>,
+}
+
+impl SimulatedClock {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now() + self.offset()
+    }
+}
+
+impl ClockControl for SimulatedClock {
+    fn offset(&self) -> Duration {
+        Duration::milliseconds(self.offset_ms.load(Ordering::SeqCst))
+    }
+
+    fn advance(&self, by: Duration) -> Duration {
+        let by = by.num_milliseconds().max(0);
+        let previous = self.offset_ms.fetch_add(by, Ordering::SeqCst);
+        Duration::milliseconds(previous + by)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advancing_moves_every_clone_forward() {
+        let clock = SimulatedClock::new();
+        let control = clock.clone();
+        let before = clock.now();
+
+        assert_eq!(control.advance(Duration::hours(2)), Duration::hours(2));
+        assert_eq!(control.advance(Duration::minutes(-5)), Duration::hours(2));
+        assert!(clock.now() - before >= Duration::hours(2));
+    }
+}
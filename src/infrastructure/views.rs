@@ -0,0 +1,133 @@
+// src/infrastructure/views.rs
+//! [`ViewCounter`] implementations: one per process, or shared by every
+//! instance through Redis.
+
+use crate::application::{AppError, AppResult, ports::views::ViewCounter};
+use crate::async_support::{BoxFuture, boxed};
+use crate::domain::ArticleId;
+use deadpool_redis::{Config as DeadpoolConfig, Connection, Pool, Runtime};
+use redis::AsyncCommands;
+use std::{collections::HashMap, sync::Mutex};
+
+/// Pending counts held in memory; lost if the process exits before a flush.
+#[derive(Debug, Default)]
+pub struct InMemoryViewCounter {
+    pending: Mutex<HashMap<ArticleId, u64>>,
+}
+
+impl InMemoryViewCounter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ViewCounter for InMemoryViewCounter {
+    fn add(&self, article_id: ArticleId, views: u64) -> BoxFuture<'_, AppResult<()>> {
+        boxed(async move {
+            let mut pending = self
+                .pending
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            let count = pending.entry(article_id).or_default();
+            *count = count.saturating_add(views);
+            drop(pending);
+            Ok(())
+        })
+    }
+
+    fn drain(&self) -> BoxFuture<'_, AppResult<Vec<(ArticleId, u64)>>> {
+        boxed(async move {
+            let drained = std::mem::take(
+                &mut *self
+                    .pending
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner),
+            );
+            Ok(drained.into_iter().collect())
+        })
+    }
+}
+
+/// Pending counts in the Redis hash `article_views`, keyed by article id, so
+/// every replica feeds the same flush.
+#[derive(Clone)]
+#[must_use]
+pub struct RedisViewCounter {
+    pool: Pool,
+}
+
+impl RedisViewCounter {
+    const KEY: &'static str = "article_views";
+
+    /// Create a counter from a Redis URL, e.g. `<redis://:password@host:6379/0>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Redis pool cannot be created.
+    pub fn from_url(url: &str) -> Result<Self, AppError> {
+        let pool = DeadpoolConfig::from_url(url)
+            .create_pool(Some(Runtime::Tokio1))
+            .map_err(|err| AppError::infrastructure(err.to_string()))?;
+        Ok(Self { pool })
+    }
+
+    async fn connection(&self) -> AppResult<Connection> {
+        self.pool
+            .get()
+            .await
+            .map_err(|err| AppError::infrastructure(err.to_string()))
+    }
+}
+
+impl ViewCounter for RedisViewCounter {
+    fn add(&self, article_id: ArticleId, views: u64) -> BoxFuture<'_, AppResult<()>> {
+        boxed(async move {
+            let mut conn = self.connection().await?;
+            conn.hincr::<_, _, _, ()>(Self::KEY, i64::from(article_id), views)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))
+        })
+    }
+
+    fn drain(&self) -> BoxFuture<'_, AppResult<Vec<(ArticleId, u64)>>> {
+        boxed(async move {
+            let mut conn = self.connection().await?;
+            // Read and clear in one transaction so views counted meanwhile
+            // land in a fresh hash instead of being dropped.
+            let (pending,): (HashMap<i64, u64>,) = redis::pipe()
+                .atomic()
+                .hgetall(Self::KEY)
+                .del(Self::KEY)
+                .ignore()
+                .query_async(&mut conn)
+                .await
+                .map_err(|err| AppError::infrastructure(err.to_string()))?;
+            pending
+                .into_iter()
+                .filter(|(_, views)| *views > 0)
+                .map(|(id, views)| Ok((ArticleId::new(id)?, views)))
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn in_memory_counts_accumulate_until_drained() {
+        let counter = InMemoryViewCounter::new();
+        let first = ArticleId::new(1).unwrap();
+        let second = ArticleId::new(2).unwrap();
+        counter.record_view(first).await.unwrap();
+        counter.record_view(first).await.unwrap();
+        counter.add(second, 5).await.unwrap();
+
+        let mut drained = counter.drain().await.unwrap();
+        drained.sort_unstable_by_key(|(id, _)| i64::from(*id));
+        assert_eq!(drained, vec![(first, 2), (second, 5)]);
+        assert!(counter.drain().await.unwrap().is_empty());
+    }
+}
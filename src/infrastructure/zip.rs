@@ -0,0 +1,403 @@
+// src/infrastructure/zip.rs
+//! Just enough of the ZIP format for Office documents, EPUB books and
+//! content bundles: stored and deflated entries, located through the central
+//! directory.
+
+use crate::application::{
+    AppError, AppResult,
+    ports::archives::{ArchiveEntry, Archiver},
+};
+use flate2::{Compression, Crc, read::DeflateDecoder, write::DeflateEncoder};
+use std::io::{Read, Write};
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+const END_OF_DIRECTORY_LEN: usize = 22;
+
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ZipError {
+    #[error("not a zip archive")]
+    NotAnArchive,
+    #[error("zip archive is truncated or corrupt")]
+    Corrupt,
+    #[error("zip entry {0} uses an unsupported compression method")]
+    UnsupportedMethod(String),
+    #[error("zip entry {0} is larger than {1} bytes")]
+    TooLarge(String, usize),
+    #[error("zip entry {0} could not be compressed")]
+    Compression(String),
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    name: String,
+    method: u16,
+    crc: u32,
+    compressed_size: usize,
+    size: usize,
+    header_offset: usize,
+}
+
+/// A ZIP archive held in memory.
+#[derive(Debug)]
+pub struct Archive<'a> {
+    data: &'a [u8],
+    entries: Vec<Entry>,
+}
+
+impl<'a> Archive<'a> {
+    /// Read the central directory of `data`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is not a ZIP archive or its directory is
+    /// corrupt.
+    pub fn new(data: &'a [u8]) -> Result<Self, ZipError> {
+        let end = find_end_of_directory(data).ok_or(ZipError::NotAnArchive)?;
+        let count = usize::from(read_u16(data, end + 10)?);
+        let mut offset = read_u32_usize(data, end + 16)?;
+
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            if read_u32(data, offset)? != CENTRAL_HEADER_SIGNATURE {
+                return Err(ZipError::Corrupt);
+            }
+            let name_len = usize::from(read_u16(data, offset + 28)?);
+            let extra_len = usize::from(read_u16(data, offset + 30)?);
+            let comment_len = usize::from(read_u16(data, offset + 32)?);
+            let name = slice(data, offset + 46, name_len)?;
+            entries.push(Entry {
+                name: String::from_utf8_lossy(name).into_owned(),
+                method: read_u16(data, offset + 10)?,
+                crc: read_u32(data, offset + 16)?,
+                compressed_size: read_u32_usize(data, offset + 20)?,
+                size: read_u32_usize(data, offset + 24)?,
+                header_offset: read_u32_usize(data, offset + 42)?,
+            });
+            offset += 46 + name_len + extra_len + comment_len;
+        }
+
+        Ok(Self { data, entries })
+    }
+
+    /// Entry names, in directory order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.name.as_str())
+    }
+
+    /// Decompress the entry called `name`; `Ok(None)` when there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry is corrupt, larger than `max_size` bytes
+    /// once decompressed, or uses a method other than stored or deflate.
+    pub fn read(&self, name: &str, max_size: usize) -> Result<Option<Vec<u8>>, ZipError> {
+        let Some(entry) = self.entries.iter().find(|entry| entry.name == name) else {
+            return Ok(None);
+        };
+        if entry.size > max_size {
+            return Err(ZipError::TooLarge(entry.name.clone(), max_size));
+        }
+
+        let offset = entry.header_offset;
+        if read_u32(self.data, offset)? != LOCAL_HEADER_SIGNATURE {
+            return Err(ZipError::Corrupt);
+        }
+        let name_len = usize::from(read_u16(self.data, offset + 26)?);
+        let extra_len = usize::from(read_u16(self.data, offset + 28)?);
+        let raw = slice(
+            self.data,
+            offset + 30 + name_len + extra_len,
+            entry.compressed_size,
+        )?;
+
+        let contents = match entry.method {
+            STORED => raw.to_vec(),
+            DEFLATED => {
+                let mut contents = Vec::with_capacity(entry.size);
+                // The directory's size can lie; never inflate past the cap.
+                DeflateDecoder::new(raw)
+                    .take(max_size as u64 + 1)
+                    .read_to_end(&mut contents)
+                    .map_err(|_| ZipError::Corrupt)?;
+                if contents.len() > max_size {
+                    return Err(ZipError::TooLarge(entry.name.clone(), max_size));
+                }
+                contents
+            }
+            _ => return Err(ZipError::UnsupportedMethod(entry.name.clone())),
+        };
+
+        let mut crc = Crc::new();
+        crc.update(&contents);
+        if contents.len() != entry.size || crc.sum() != entry.crc {
+            return Err(ZipError::Corrupt);
+        }
+        Ok(Some(contents))
+    }
+}
+
+/// The end-of-directory record sits in the last 22 bytes plus an optional
+/// comment of up to 64 KiB.
+fn find_end_of_directory(data: &[u8]) -> Option<usize> {
+    let last = data.len().checked_sub(END_OF_DIRECTORY_LEN)?;
+    let first = last.saturating_sub(usize::from(u16::MAX));
+    (first..=last)
+        .rev()
+        .find(|&offset| read_u32(data, offset).ok() == Some(END_OF_DIRECTORY_SIGNATURE))
+}
+
+fn slice(data: &[u8], offset: usize, len: usize) -> Result<&[u8], ZipError> {
+    offset
+        .checked_add(len)
+        .and_then(|end| data.get(offset..end))
+        .ok_or(ZipError::Corrupt)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, ZipError> {
+    let bytes = slice(data, offset, 2)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, ZipError> {
+    let bytes = slice(data, offset, 4)?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_u32_usize(data: &[u8], offset: usize) -> Result<usize, ZipError> {
+    usize::try_from(read_u32(data, offset)?).map_err(|_| ZipError::Corrupt)
+}
+
+/// Builds an archive in memory, one entry at a time.
+#[derive(Debug, Default)]
+#[must_use]
+pub struct Writer {
+    out: Vec<u8>,
+    directory: Vec<u8>,
+    count: u16,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `contents` uncompressed, as EPUB requires for its `mimetype`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive outgrows the 32-bit ZIP limits.
+    pub fn add_stored(&mut self, name: &str, contents: &[u8]) -> Result<(), ZipError> {
+        self.add(name, contents, STORED, contents)
+    }
+
+    /// Add `contents` deflated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if compression fails or the archive outgrows the
+    /// 32-bit ZIP limits.
+    pub fn add_deflated(&mut self, name: &str, contents: &[u8]) -> Result<(), ZipError> {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        let compressed = encoder
+            .write_all(contents)
+            .and_then(|()| encoder.finish())
+            .map_err(|_| ZipError::Compression(name.to_string()))?;
+        self.add(name, contents, DEFLATED, &compressed)
+    }
+
+    fn add(
+        &mut self,
+        name: &str,
+        contents: &[u8],
+        method: u16,
+        raw: &[u8],
+    ) -> Result<(), ZipError> {
+        let too_large = || ZipError::TooLarge(name.to_string(), u32::MAX as usize);
+        let mut crc = Crc::new();
+        crc.update(contents);
+        let offset = u32::try_from(self.out.len()).map_err(|_| too_large())?;
+        let fields = Fields {
+            method,
+            crc: crc.sum(),
+            compressed: u32::try_from(raw.len()).map_err(|_| too_large())?,
+            size: u32::try_from(contents.len()).map_err(|_| too_large())?,
+            name_len: u16::try_from(name.len()).map_err(|_| too_large())?,
+        };
+        self.count = self.count.checked_add(1).ok_or_else(too_large)?;
+
+        self.out
+            .extend_from_slice(&LOCAL_HEADER_SIGNATURE.to_le_bytes());
+        self.out.extend_from_slice(&[20, 0, 0, 0]);
+        fields.write(&mut self.out);
+        self.out.extend_from_slice(&[0, 0]);
+        self.out.extend_from_slice(name.as_bytes());
+        self.out.extend_from_slice(raw);
+
+        self.directory
+            .extend_from_slice(&CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+        self.directory.extend_from_slice(&[20, 0, 20, 0, 0, 0]);
+        fields.write(&mut self.directory);
+        self.directory.extend_from_slice(&[0; 12]);
+        self.directory.extend_from_slice(&offset.to_le_bytes());
+        self.directory.extend_from_slice(name.as_bytes());
+        Ok(())
+    }
+
+    /// Append the central directory and return the archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive outgrows the 32-bit ZIP limits.
+    pub fn finish(mut self) -> Result<Vec<u8>, ZipError> {
+        let too_large = || ZipError::TooLarge("archive".into(), u32::MAX as usize);
+        let directory_offset = u32::try_from(self.out.len()).map_err(|_| too_large())?;
+        let directory_len = u32::try_from(self.directory.len()).map_err(|_| too_large())?;
+        self.out.append(&mut self.directory);
+        self.out
+            .extend_from_slice(&END_OF_DIRECTORY_SIGNATURE.to_le_bytes());
+        self.out.extend_from_slice(&[0; 4]);
+        self.out.extend_from_slice(&self.count.to_le_bytes());
+        self.out.extend_from_slice(&self.count.to_le_bytes());
+        self.out.extend_from_slice(&directory_len.to_le_bytes());
+        self.out.extend_from_slice(&directory_offset.to_le_bytes());
+        self.out.extend_from_slice(&[0, 0]);
+        Ok(self.out)
+    }
+}
+
+/// Method through name length, shared by local and central headers.
+struct Fields {
+    method: u16,
+    crc: u32,
+    compressed: u32,
+    size: u32,
+    name_len: u16,
+}
+
+impl Fields {
+    fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.method.to_le_bytes());
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(&self.crc.to_le_bytes());
+        out.extend_from_slice(&self.compressed.to_le_bytes());
+        out.extend_from_slice(&self.size.to_le_bytes());
+        out.extend_from_slice(&self.name_len.to_le_bytes());
+    }
+}
+
+/// [`Archiver`] writing deflated ZIP archives.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ZipArchiver;
+
+impl Archiver for ZipArchiver {
+    fn pack(&self, entries: &[ArchiveEntry]) -> AppResult<Vec<u8>> {
+        let failed =
+            |err: ZipError| AppError::infrastructure(format!("failed to write zip: {err}"));
+        let mut writer = Writer::new();
+        for entry in entries {
+            writer
+                .add_deflated(&entry.name, &entry.contents)
+                .map_err(failed)?;
+        }
+        writer.finish().map_err(failed)
+    }
+
+    fn unpack(&self, archive: &[u8], max_entry_size: usize) -> AppResult<Vec<ArchiveEntry>> {
+        let invalid = |err: ZipError| AppError::validation(err.to_string());
+        let archive = Archive::new(archive).map_err(invalid)?;
+        let mut entries = Vec::with_capacity(archive.entries.len());
+        for name in archive.names().filter(|name| !name.ends_with('/')) {
+            let contents = archive
+                .read(name, max_entry_size)
+                .map_err(invalid)?
+                .unwrap_or_default();
+            entries.push(ArchiveEntry {
+                name: name.to_string(),
+                contents,
+            });
+        }
+        Ok(entries)
+    }
+}
+
+/// Zip `files`, deflating each one.
+#[cfg(test)]
+pub(crate) fn archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut writer = Writer::new();
+    for (name, contents) in files {
+        writer.add_deflated(name, contents).unwrap();
+    }
+    writer.finish().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_deflated_entries() {
+        let data = archive(&[("a.txt", b"hello hello hello"), ("b.txt", b"")]);
+        let archive = Archive::new(&data).unwrap();
+        assert_eq!(
+            archive.read("a.txt", 1024).unwrap().unwrap(),
+            b"hello hello hello"
+        );
+        assert!(archive.read("missing", 1024).unwrap().is_none());
+    }
+
+    #[test]
+    fn stored_entries_round_trip() {
+        let mut writer = Writer::new();
+        writer
+            .add_stored("mimetype", b"application/epub+zip")
+            .unwrap();
+        let data = writer.finish().unwrap();
+        assert_eq!(&data[30..38], b"mimetype");
+        let archive = Archive::new(&data).unwrap();
+        assert_eq!(
+            archive.read("mimetype", 64).unwrap().unwrap(),
+            b"application/epub+zip"
+        );
+    }
+
+    #[test]
+    fn archiver_round_trips_entries_and_skips_directories() {
+        let entries = vec![
+            ArchiveEntry {
+                name: "manifest.json".into(),
+                contents: b"{}".to_vec(),
+            },
+            ArchiveEntry {
+                name: "articles/".into(),
+                contents: Vec::new(),
+            },
+            ArchiveEntry {
+                name: "articles/hello.md".into(),
+                contents: b"# Hello".to_vec(),
+            },
+        ];
+        let data = ZipArchiver.pack(&entries).unwrap();
+        let unpacked = ZipArchiver.unpack(&data, 1024).unwrap();
+        assert_eq!(unpacked, vec![entries[0].clone(), entries[2].clone()]);
+        assert!(ZipArchiver.unpack(&data, 4).is_err());
+    }
+
+    #[test]
+    fn rejects_oversized_entries_and_garbage() {
+        let data = archive(&[("a.txt", &[b'x'; 64])]);
+        let archive = Archive::new(&data).unwrap();
+        assert!(matches!(
+            archive.read("a.txt", 10),
+            Err(ZipError::TooLarge(..))
+        ));
+        assert_eq!(
+            Archive::new(b"plain text").unwrap_err(),
+            ZipError::NotAnArchive
+        );
+    }
+}
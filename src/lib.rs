@@ -2,6 +2,8 @@
 
 pub mod application;
 pub mod async_support;
+#[cfg(feature = "client")]
+pub mod client;
 pub mod config;
 pub mod domain;
 pub mod infrastructure;
@@ -1,41 +1,105 @@
 #![allow(clippy::multiple_crate_versions)]
 
 // src/main.rs
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use axum::{ServiceExt, body::Body};
-use mokkan_core::application::ports::session_revocation::Store;
+use mokkan_core::application::ports::authorization_code::CodeStore;
+use mokkan_core::application::ports::blob_storage::BlobStorage;
+use mokkan_core::application::ports::command_journal::CommandJournal;
+#[cfg(feature = "article-export")]
+use mokkan_core::application::ports::exports::ArticleRenderer;
+use mokkan_core::application::ports::external_auth::{ExternalAuthenticator, GroupRoleMapping};
+use mokkan_core::application::ports::geoip::AsnLookup;
+use mokkan_core::application::ports::jobs::{JobControl, JobRunStore, LockManager};
+use mokkan_core::application::ports::oidc_login::UpstreamProvider;
+use mokkan_core::application::ports::rate_limit::{LoginThrottlePolicy, RateLimiter};
+use mokkan_core::application::ports::security_events::SecurityEventSink;
+use mokkan_core::application::ports::session_revocation::{
+    Backend as SessionBackend, SessionPolicy, Store,
+};
+use mokkan_core::application::ports::syndication::{SyndicationTarget, Syndicator};
 use mokkan_core::application::ports::util::SlugGenerator;
+use mokkan_core::application::ports::views::ViewCounter;
+use mokkan_core::application::ports::webhooks::WebhookSender;
 use mokkan_core::application::{
+    PageLimits, PaginationPolicy, ReadOnlySwitch,
     ports::{
-        security::{PasswordHasher, TokenManager},
-        time::Clock,
+        security::{PasswordBreachChecker, PasswordHasher, TokenManager},
+        time::{Clock, ClockControl},
+    },
+    services::{
+        Dependencies, JournalReplayer, MediaPolicy, Registry, ReplayClock, RuntimeDependencies,
+        SeedDocument, SlugCachePolicy, SyndicationPolicy, WebhookPolicy,
     },
-    services::{Dependencies, Registry, RuntimeDependencies},
 };
-use mokkan_core::config::Settings;
-use mokkan_core::domain::{
-    ArticleReadRepository, ArticleRevisionRepository, ArticleWriteRepository, UserRepository,
+use mokkan_core::async_support::boxed;
+use mokkan_core::config::{
+    DatabaseBackend, MediaStorageSettings, PageLimitSettings, Settings, SyndicationPlatform,
+    TokenBackend, parse_root_keys,
 };
+use mokkan_core::domain::UserRepository;
+use mokkan_core::domain::audit::repository::AuditLogRepository;
+#[cfg(feature = "article-export")]
+use mokkan_core::infrastructure::exports::BuiltinArticleRenderer;
+use mokkan_core::infrastructure::redaction::{RedactingMakeWriter, Redactor};
 use mokkan_core::infrastructure::security::authorization_code_store::InMemoryStore;
 use mokkan_core::infrastructure::security::authorization_code_store::into_arc as into_auth_code_store;
+use mokkan_core::infrastructure::security::download_links::HmacDownloadLinkSigner;
+use mokkan_core::infrastructure::security::ldap::LdapAuthenticator;
+use mokkan_core::infrastructure::security::rate_limiter::InMemoryRateLimiter;
+use mokkan_core::infrastructure::security::redis_authorization_code_store::RedisAuthorizationCodeStore;
+use mokkan_core::infrastructure::security::redis_rate_limiter::RedisRateLimiter;
 use mokkan_core::infrastructure::security::redis_session_store::RedisSessionRevocationStore;
 use mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec;
-use mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore;
+use mokkan_core::infrastructure::security::session_store::{
+    InMemorySessionRevocationStore, Retention,
+};
+use mokkan_core::infrastructure::security::upstream_oidc::UpstreamOidcClient;
+use mokkan_core::infrastructure::security::user_tokens::InMemoryUserTokenStore;
+use mokkan_core::infrastructure::security::webhook::HttpSecurityWebhook;
+use mokkan_core::infrastructure::static_site::DirectorySiteWriter;
+use mokkan_core::infrastructure::zip::ZipArchiver;
 use mokkan_core::infrastructure::{
+    blob_storage::{LocalBlobStorage, S3BlobStorage, S3Options},
     database,
+    documents::DocxConverter,
+    geoip::CsvAsnDatabase,
+    integrity::PostgresIntegrityChecker,
+    jobs::TokioJobQueue,
+    journal::{self, FileCommandJournal},
+    locks::{InMemoryLockManager, PostgresLockManager},
+    notification::LogEmailSender,
     repositories::{
-        PostgresArticleReadRepository, PostgresArticleRevisionRepository,
-        PostgresArticleWriteRepository, PostgresAuditLogRepository, PostgresUserRepository,
+        BufferOptions, BufferedAuditLogRepository, InMemoryStores, OverflowPolicy,
+        PostgresAccessRuleRepository, PostgresAccountDeletions, PostgresArticleReadRepository,
+        PostgresArticleRevisionRepository, PostgresArticleWriteRepository,
+        PostgresAuditLogRepository, PostgresBlockList, PostgresContributorRepository,
+        PostgresCustomFieldRepository, PostgresMediaRepository, PostgresModerationRepository,
+        PostgresPasswordHistory, PostgresRoleStore, PostgresSyndicationOptOutStore,
+        PostgresUnitOfWork, PostgresUserRepository, PostgresUserTokenStore,
+        PostgresWebhookRepository, RedactingAuditLogRepository, SqliteStores,
     },
-    security::{password::Argon2PasswordHasher, token::BiscuitTokenManager},
-    time::SystemClock,
+    scheduler::{InMemoryJobRunStore, Job, PostgresJobRunStore, Scheduler, SchedulerOptions},
+    security::{
+        breach::{HibpBreachChecker, OfflineBreachChecker},
+        jwt::{JwtAlgorithm, JwtTokenManager},
+        password::Argon2PasswordHasher,
+        token::BiscuitTokenManager,
+    },
+    seed,
+    syndication::{BlueskySyndicator, MastodonSyndicator, SlackSyndicator},
+    telemetry::{self, ExportTarget, Telemetry},
+    tenancy::TenantSchema,
+    time::{SimulatedClock, SystemClock},
     util::DefaultSlugGenerator,
+    views::{InMemoryViewCounter, RedisViewCounter},
+    webhooks::HttpWebhookSender,
 };
 use mokkan_core::presentation::http::{routes::build_router, state::HttpContext};
-use sqlx::PgPool;
+use sqlx::{PgPool, postgres::PgPoolOptions};
 use std::{env, net::SocketAddr, sync::Arc};
 use tokio::signal;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{EnvFilter, Layer as _, layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
 async fn main() {
@@ -53,6 +117,26 @@ async fn main() {
         return;
     }
 
+    // Write the published site as static HTML instead of serving.
+    if std::env::var("EXPORT_STATIC").as_deref() == Ok("1") {
+        if let Err(err) = export_static().await {
+            tracing::error!(error = %err, "static export failed");
+            eprintln!("static export failed: {err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Rebuild an empty database from a command journal instead of serving.
+    if let Ok(path) = std::env::var("REPLAY_COMMAND_JOURNAL") {
+        if let Err(err) = replay_journal(&path).await {
+            tracing::error!(error = %err, "journal replay failed");
+            eprintln!("journal replay failed: {err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     if let Err(err) = bootstrap().await {
         tracing::error!(error = %err, "fatal error");
         eprintln!("fatal error: {err}");
@@ -61,17 +145,25 @@ async fn main() {
 }
 
 async fn bootstrap() -> Result<()> {
-    init_tracing();
+    let telemetry = init_tracing()?;
 
-    let (config, pool) = init_config_and_db().await?;
+    let (config, storage) = init_config_and_storage().await?;
 
-    let (_services, state) = build_services_and_state(&pool, &config)?;
+    let (services, state, scheduler) = build_services_and_state(&storage, &config, None)?;
+    let seed = startup_seed(&storage)?;
+    apply_seed(&services, seed.as_ref()).await?;
+    schedule_jobs(&scheduler, &services, &config)?;
+    if let Storage::Memory(stores) = &storage {
+        schedule_ephemeral_reset(&scheduler, &services, &config, Arc::clone(stores), seed)?;
+    }
 
     let app = build_router(state);
     if let Err(err) = mokkan_core::presentation::http::openapi::write_snapshot() {
         tracing::warn!(error = %err, "failed to write OpenAPI snapshot");
     }
-    let service = app.into_service::<Body>().into_make_service();
+    let service = app
+        .into_service::<Body>()
+        .into_make_service_with_connect_info::<SocketAddr>();
 
     let listener = tokio::net::TcpListener::bind(config.listen_addr()).await?;
     let address: SocketAddr = listener.local_addr()?;
@@ -81,104 +173,937 @@ async fn bootstrap() -> Result<()> {
         .with_graceful_shutdown(shutdown_signal())
         .await?;
 
+    flush_traces(telemetry).await;
+    Ok(())
+}
+
+async fn export_static() -> Result<()> {
+    let telemetry = init_tracing()?;
+
+    let (config, storage) = init_config_and_storage().await?;
+    let (services, _state, _scheduler) = build_services_and_state(&storage, &config, None)?;
+    // Ephemeral instances start empty; export the content they would serve.
+    apply_seed(&services, startup_seed(&storage)?.as_ref()).await?;
+
+    let report = services.static_site.write_site().await?;
+    tracing::info!(
+        location = report.location,
+        articles = report.articles,
+        files_written = report.files_written,
+        "static site exported"
+    );
+    println!(
+        "Static site with {} articles written to {}",
+        report.articles, report.location
+    );
+    flush_traces(telemetry).await;
+    Ok(())
+}
+
+async fn replay_journal(path: &str) -> Result<()> {
+    let telemetry = init_tracing()?;
+
+    let (config, pool) = init_config_and_db().await?;
+    if PostgresUserRepository::new(pool.clone()).count().await? > 0 {
+        anyhow::bail!("journal replay needs an empty database");
+    }
+    let entries = journal::read_entries(path)?;
+
+    let clock = Arc::new(ReplayClock::default());
+    let (services, _state, _scheduler) =
+        build_services_and_state(&Storage::Postgres(pool), &config, Some(Arc::clone(&clock)))?;
+    let mut replayer = JournalReplayer::new(
+        Arc::clone(&services.user_commands),
+        Arc::clone(&services.article_commands),
+        clock,
+    );
+    let summary = replayer.replay(entries).await;
+    for divergence in &summary.diverged {
+        tracing::warn!(position = divergence.position, error = %divergence.error, "journal entry diverged");
+    }
+    tracing::info!(
+        replayed = summary.replayed,
+        skipped = summary.skipped,
+        diverged = summary.diverged.len(),
+        "journal replay finished"
+    );
+    flush_traces(telemetry).await;
+    if !summary.diverged.is_empty() {
+        anyhow::bail!("{} journal entries diverged", summary.diverged.len());
+    }
+    Ok(())
+}
+
+/// The seed applied at startup, named by its source: `SEED_FILE` when set,
+/// else the built-in demo content on ephemeral instances.
+fn startup_seed(storage: &Storage) -> Result<Option<(String, SeedDocument)>> {
+    if let Some(path) = Settings::seed_file_from_env() {
+        let seed = seed::read_file(&path)?;
+        return Ok(Some((path, seed)));
+    }
+    Ok(matches!(storage, Storage::Memory(_)).then(|| ("built-in demo".to_owned(), seed::demo())))
+}
+
+/// Apply the startup seed, if there is one. Skipped in read-only mode,
+/// where the database may be a standby.
+async fn apply_seed(services: &Registry, seed: Option<&(String, SeedDocument)>) -> Result<()> {
+    let Some((path, seed)) = seed else {
+        return Ok(());
+    };
+    if services.read_only.is_enabled() {
+        tracing::warn!(path, "read-only mode, seed not applied");
+        return Ok(());
+    }
+    let summary = services.seed.apply(seed).await?;
+    tracing::info!(
+        path,
+        users_created = summary.users_created,
+        users_updated = summary.users_updated,
+        custom_fields_saved = summary.custom_fields_saved,
+        pages_created = summary.pages_created,
+        "seed applied"
+    );
     Ok(())
 }
 
+fn init_command_journal(config: &Settings) -> Result<Option<Arc<dyn CommandJournal>>> {
+    let Some(path) = config.command_journal_path() else {
+        return Ok(None);
+    };
+    tracing::info!(path, "command journal enabled");
+    Ok(Some(Arc::new(FileCommandJournal::open(path)?)))
+}
+
+fn init_token_manager(config: &Settings, clock: &Arc<dyn Clock>) -> Result<Arc<dyn TokenManager>> {
+    let TokenBackend::Jwt(jwt) = config.token_backend() else {
+        let Some(path) = config.biscuit_root_keys_file() else {
+            let manager =
+                BiscuitTokenManager::new(config.biscuit_private_key(), config.token_ttl())?
+                    .with_clock(Arc::clone(clock));
+            return Ok(Arc::new(manager));
+        };
+        let manager = BiscuitTokenManager::with_keys(&read_root_keys(path)?, config.token_ttl())?
+            .with_clock(Arc::clone(clock));
+        #[cfg(unix)]
+        reload_root_keys_on_hangup(manager.clone(), path.to_owned());
+        return Ok(Arc::new(manager));
+    };
+    let algorithm: JwtAlgorithm = jwt.algorithm.parse()?;
+    let pem = std::fs::read_to_string(&jwt.private_key_path)
+        .with_context(|| format!("failed to read {}", jwt.private_key_path))?;
+    let mut manager =
+        JwtTokenManager::from_pem(algorithm, &pem, jwt.issuer.clone(), config.token_ttl())?
+            .with_clock(Arc::clone(clock));
+    if let Some(kid) = &jwt.key_id {
+        manager = manager.with_key_id(kid.clone());
+    }
+    if let Some(audience) = &jwt.audience {
+        manager = manager.with_audience(audience.clone());
+    }
+    tracing::info!(%algorithm, "issuing JWT access tokens");
+    Ok(Arc::new(manager))
+}
+
+fn read_root_keys(path: &str) -> Result<Vec<(u32, String)>> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("failed to read {path}"))?;
+    Ok(parse_root_keys(&text)?)
+}
+
+/// Re-read the root key file on SIGHUP, so keys rotate without a restart.
+#[cfg(unix)]
+fn reload_root_keys_on_hangup(manager: BiscuitTokenManager, path: String) {
+    tokio::spawn(async move {
+        let mut hangup = match signal::unix::signal(signal::unix::SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(err) => {
+                tracing::warn!(error = %err, "cannot reload root keys on SIGHUP");
+                return;
+            }
+        };
+        while hangup.recv().await.is_some() {
+            let reloaded = read_root_keys(&path)
+                .and_then(|keys| Ok(manager.rotate(&keys).map(|()| keys.len())?));
+            match reloaded {
+                Ok(count) => tracing::info!(count, "root keys reloaded"),
+                Err(err) => {
+                    tracing::warn!(error = %err, "root key reload failed; keeping current keys");
+                }
+            }
+        }
+    });
+}
+
+fn init_asn_lookup(config: &Settings) -> Result<Option<Arc<dyn AsnLookup>>> {
+    let paths = config.asn_database_paths();
+    if paths.is_empty() {
+        return Ok(None);
+    }
+    let database = CsvAsnDatabase::open(paths)?;
+    tracing::info!(networks = database.len(), "ASN database loaded");
+    Ok(Some(Arc::new(database)))
+}
+
+/// Where the repositories keep their data.
+enum Storage {
+    Postgres(PgPool),
+    /// `EPHEMERAL_MODE`: process memory, with no database at all.
+    Memory(Arc<InMemoryStores>),
+    /// `DATABASE_BACKEND=sqlite`: one database file, for embedded instances.
+    Sqlite(Arc<SqliteStores>),
+}
+
+impl Storage {
+    /// `REDIS_URL`, unless the instance is ephemeral: a shared session store
+    /// would outlive the in-memory users its sessions belong to.
+    fn redis_url(&self) -> Option<String> {
+        match self {
+            Self::Postgres(_) | Self::Sqlite(_) => std::env::var("REDIS_URL").ok(),
+            Self::Memory(_) => None,
+        }
+    }
+}
+
+async fn init_config_and_storage() -> Result<(Settings, Storage)> {
+    dotenvy::dotenv().ok();
+    if !Settings::ephemeral_mode_from_env() {
+        if Settings::database_backend_from_env()? == DatabaseBackend::Sqlite {
+            let config = Settings::from_env()?;
+            let stores = init_sqlite_stores(config.database_url()).await?;
+            return Ok((config, Storage::Sqlite(stores)));
+        }
+        let (config, pool) = init_config_and_db().await?;
+        return Ok((config, Storage::Postgres(pool)));
+    }
+    tracing::warn!(
+        "ephemeral mode: data is kept in memory, seeded with demo content and reset daily"
+    );
+    Ok((
+        Settings::from_env()?,
+        Storage::Memory(Arc::new(InMemoryStores::new())),
+    ))
+}
+
 async fn init_config_and_db() -> Result<(Settings, PgPool)> {
     dotenvy::dotenv().ok();
     let config = Settings::from_env()?;
 
-    let pool = database::init_pool(config.database_url()).await?;
+    let tenants = config
+        .tenant_schemas()
+        .iter()
+        .map(|id| TenantSchema::new(id))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| anyhow::anyhow!("invalid TENANT_SCHEMAS: {err}"))?;
+
+    let pool = if tenants.is_empty() {
+        database::init_pool(config.database_url()).await?
+    } else {
+        database::init_tenant_routed_pool(config.database_url()).await?
+    };
     database::run_migrations(&pool).await?;
+    database::run_tenant_migrations(&pool, &tenants).await?;
+    database::apply_search_language(&pool, &tenants, config.search_language()).await?;
 
     Ok((config, pool))
 }
 
-fn init_session_store(config: &Settings) -> Arc<dyn Store> {
-    if let Ok(redis_url) = std::env::var("REDIS_URL") {
-        match RedisSessionRevocationStore::from_url_with_options(
-            &redis_url,
-            config.redis_used_nonce_ttl_secs(),
-            config.redis_preload_cas_script(),
-        ) {
-            Ok(store) => Arc::new(store),
-            Err(err) => {
-                tracing::error!(error = %err, "failed to initialise redis session store, falling back to in-memory store");
-                Arc::new(InMemorySessionRevocationStore::new())
-            }
+/// Open and migrate the `SQLite` database at `DATABASE_URL`.
+async fn init_sqlite_stores(database_url: &str) -> Result<Arc<SqliteStores>> {
+    if !database_url.starts_with("sqlite:") {
+        anyhow::bail!(
+            "DATABASE_BACKEND=sqlite needs a sqlite: DATABASE_URL, e.g. sqlite://./data/cms.db"
+        );
+    }
+    let pool = database::init_sqlite_pool(database_url).await?;
+    database::run_sqlite_migrations(&pool).await?;
+    tracing::info!("storing data in SQLite; full-text search matches substrings");
+    Ok(Arc::new(SqliteStores::new(&pool)))
+}
+
+fn in_memory_session_store(
+    config: &Settings,
+    clock: &Arc<dyn Clock>,
+) -> (Arc<dyn Store>, SessionBackend) {
+    let settings = config.sessions();
+    let defaults = Retention::default();
+    let retention = Retention {
+        used_nonce_ttl: chrono::Duration::from_std(settings.in_memory_used_nonce_ttl)
+            .unwrap_or(defaults.used_nonce_ttl),
+        revoked_ttl: chrono::Duration::from_std(settings.in_memory_revoked_ttl)
+            .unwrap_or(defaults.revoked_ttl),
+    };
+    let store = InMemorySessionRevocationStore::new()
+        .with_clock(Arc::clone(clock))
+        .with_retention(retention);
+    (Arc::new(store), SessionBackend::InMemory)
+}
+
+fn init_session_store(
+    config: &Settings,
+    redis_url: Option<&str>,
+    clock: &Arc<dyn Clock>,
+) -> (Arc<dyn Store>, SessionBackend) {
+    let Some(redis_url) = redis_url else {
+        return in_memory_session_store(config, clock);
+    };
+
+    match RedisSessionRevocationStore::from_url_with_options(
+        redis_url,
+        config.redis_used_nonce_ttl_secs(),
+        config.redis_preload_cas_script(),
+    ) {
+        Ok(store) => (
+            Arc::new(store.with_refresh_family_ttl(config.sessions().max_age)),
+            SessionBackend::Redis,
+        ),
+        Err(err) => {
+            tracing::error!(error = %err, "failed to initialise redis session store, falling back to in-memory store");
+            in_memory_session_store(config, clock)
         }
-    } else {
-        Arc::new(InMemorySessionRevocationStore::new())
     }
 }
 
+/// Keep pending authorization codes in Redis when `REDIS_URL` is set, so
+/// they survive restarts and any replica can exchange them.
+fn init_auth_code_store(redis_url: Option<&str>) -> Arc<dyn CodeStore> {
+    let Some(redis_url) = redis_url else {
+        return into_auth_code_store(InMemoryStore::new());
+    };
+
+    match RedisAuthorizationCodeStore::from_url(redis_url) {
+        Ok(store) => Arc::new(store),
+        Err(err) => {
+            tracing::error!(error = %err, "failed to initialise redis authorization code store, falling back to in-memory store");
+            into_auth_code_store(InMemoryStore::new())
+        }
+    }
+}
+
+/// Keep rate-limit counters in Redis when `REDIS_URL` is set, so a lockout holds
+/// on every replica.
+fn init_slug_cache() -> SlugCachePolicy {
+    let settings = Settings::slug_cache_from_env();
+    let defaults = SlugCachePolicy::default();
+    SlugCachePolicy {
+        capacity: settings.capacity,
+        ttl: chrono::Duration::from_std(settings.ttl).unwrap_or(defaults.ttl),
+        negative_ttl: chrono::Duration::from_std(settings.negative_ttl)
+            .unwrap_or(defaults.negative_ttl),
+    }
+}
+
+fn init_pagination(config: &Settings) -> PaginationPolicy {
+    let limits = |settings: PageLimitSettings| PageLimits {
+        default: settings.default,
+        max: settings.max,
+    };
+    let settings = config.pagination();
+    PaginationPolicy {
+        shared: limits(settings.shared),
+        articles: limits(settings.articles),
+        users: limits(settings.users),
+        audit_logs: limits(settings.audit_logs),
+        moderation: limits(settings.moderation),
+        jobs: limits(settings.jobs),
+    }
+}
+
+fn init_password_hasher(config: &Settings) -> Result<Arc<dyn PasswordHasher>> {
+    let settings = config.password_hashing();
+    let hasher = Argon2PasswordHasher::new(
+        settings.memory_kib,
+        settings.iterations,
+        settings.parallelism,
+    )?;
+    Ok(Arc::new(hasher))
+}
+
+fn init_breach_checker(config: &Settings) -> Result<Arc<dyn PasswordBreachChecker>> {
+    Ok(match config.pwned_passwords_url() {
+        Some(url) => Arc::new(HibpBreachChecker::new(url)?),
+        None => Arc::new(OfflineBreachChecker),
+    })
+}
+
+fn init_session_policy(config: &Settings) -> SessionPolicy {
+    let settings = config.sessions();
+    let duration = |value| chrono::Duration::from_std(value).ok();
+    SessionPolicy {
+        max_age: duration(settings.max_age),
+        idle_timeout: settings.idle_timeout.and_then(duration),
+        binding: settings.refresh_binding,
+    }
+}
+
+/// Uploads are kept where `MEDIA_STORAGE` says, for ephemeral instances
+/// too.
+fn init_media(config: &Settings) -> Result<(Arc<dyn BlobStorage>, MediaPolicy)> {
+    let settings = config.media();
+    let storage: Arc<dyn BlobStorage> = match &settings.storage {
+        MediaStorageSettings::Local { dir } => Arc::new(LocalBlobStorage::new(dir)?),
+        MediaStorageSettings::S3 {
+            endpoint,
+            bucket,
+            region,
+            access_key_id,
+            secret_access_key,
+            url_ttl,
+            public_url,
+        } => {
+            tracing::info!(%endpoint, %bucket, "media stored in S3");
+            Arc::new(S3BlobStorage::new(
+                S3Options {
+                    endpoint: endpoint.clone(),
+                    bucket: bucket.clone(),
+                    region: region.clone(),
+                    access_key_id: access_key_id.clone(),
+                    secret_access_key: secret_access_key.clone(),
+                    url_ttl: *url_ttl,
+                    public_url: public_url.clone(),
+                },
+                // S3 checks signatures against real time.
+                Arc::new(SystemClock),
+            )?)
+        }
+    };
+    let policy = MediaPolicy {
+        max_bytes: settings.max_bytes,
+        allowed_types: settings.allowed_types.clone(),
+    };
+    Ok((storage, policy))
+}
+
+fn init_login_throttle(redis_url: Option<&str>) -> (Arc<dyn RateLimiter>, LoginThrottlePolicy) {
+    let settings = Settings::login_throttle_from_env();
+    let defaults = LoginThrottlePolicy::default();
+    let policy = LoginThrottlePolicy {
+        max_failures_per_user: settings.max_failures_per_user,
+        max_failures_per_client: settings.max_failures_per_client,
+        window: chrono::Duration::from_std(settings.window).unwrap_or(defaults.window),
+        lockout: chrono::Duration::from_std(settings.lockout).unwrap_or(defaults.lockout),
+    };
+    let Some(redis_url) = redis_url else {
+        return (Arc::new(InMemoryRateLimiter::new()), policy);
+    };
+
+    match RedisRateLimiter::from_url(redis_url) {
+        Ok(limiter) => (Arc::new(limiter), policy),
+        Err(err) => {
+            tracing::error!(error = %err, "failed to initialise redis rate limiter, falling back to in-memory counters");
+            (Arc::new(InMemoryRateLimiter::new()), policy)
+        }
+    }
+}
+
+/// Buffer article reads in Redis when `REDIS_URL` is set, so every replica
+/// feeds the same flush and pending counts survive restarts.
+fn init_view_counter(redis_url: Option<&str>) -> Arc<dyn ViewCounter> {
+    let Some(redis_url) = redis_url else {
+        return Arc::new(InMemoryViewCounter::new());
+    };
+
+    match RedisViewCounter::from_url(redis_url) {
+        Ok(counter) => Arc::new(counter),
+        Err(err) => {
+            tracing::error!(error = %err, "failed to initialise redis view counter, falling back to in-memory counts");
+            Arc::new(InMemoryViewCounter::new())
+        }
+    }
+}
+
+fn init_security_webhook(config: &Settings) -> Option<Arc<dyn SecurityEventSink>> {
+    let url = config.security_webhook_url()?;
+    match HttpSecurityWebhook::from_url(url) {
+        Ok(webhook) => Some(Arc::new(webhook)),
+        Err(err) => {
+            tracing::error!(error = %err, "invalid SECURITY_WEBHOOK_URL, security webhook disabled");
+            None
+        }
+    }
+}
+
+fn init_external_auth(
+    config: &Settings,
+) -> Result<(Option<Arc<dyn ExternalAuthenticator>>, GroupRoleMapping)> {
+    let Some(ldap) = config.ldap() else {
+        return Ok((None, GroupRoleMapping::default()));
+    };
+    let authenticator = LdapAuthenticator::new(
+        &ldap.url,
+        ldap.user_dn_template.as_str(),
+        ldap.group_attribute.as_str(),
+    )
+    .map_err(|err| anyhow::anyhow!("invalid LDAP configuration: {err}"))?;
+    tracing::info!(url = %ldap.url, "LDAP login enabled");
+    Ok((
+        Some(Arc::new(authenticator)),
+        GroupRoleMapping::new(ldap.group_roles.clone(), ldap.default_role),
+    ))
+}
+
+fn init_oidc_providers(config: &Settings) -> Result<Vec<UpstreamProvider>> {
+    config
+        .oidc_login_providers()
+        .iter()
+        .map(|provider| {
+            let client = UpstreamOidcClient::new(
+                provider.issuer.as_str(),
+                provider.client_id.as_str(),
+                provider.client_secret.as_str(),
+                provider.redirect_uri.as_str(),
+            )
+            .map_err(|err| anyhow::anyhow!("invalid OIDC login provider {}: {err}", provider.name))?;
+            tracing::info!(provider = %provider.name, issuer = %provider.issuer, "OIDC login enabled");
+            Ok(UpstreamProvider {
+                name: provider.name.clone(),
+                client: Arc::new(client),
+                default_role: provider.default_role,
+            })
+        })
+        .collect()
+}
+
+/// Syndication targets from `SYNDICATION_*`, with the policy their jobs
+/// follow.
+fn init_syndication(config: &Settings) -> Result<(Vec<SyndicationTarget>, SyndicationPolicy)> {
+    let settings = config.syndication();
+    let targets = settings
+        .targets
+        .iter()
+        .map(|target| {
+            let invalid =
+                |err| anyhow::anyhow!("invalid syndication target {}: {err}", target.name);
+            let syndicator: Arc<dyn Syndicator> = match &target.platform {
+                SyndicationPlatform::Mastodon {
+                    instance_url,
+                    access_token,
+                } => Arc::new(
+                    MastodonSyndicator::new(instance_url, access_token.as_str())
+                        .map_err(invalid)?,
+                ),
+                SyndicationPlatform::Bluesky {
+                    service_url,
+                    identifier,
+                    app_password,
+                } => Arc::new(
+                    BlueskySyndicator::new(service_url, identifier.as_str(), app_password.as_str())
+                        .map_err(invalid)?,
+                ),
+                SyndicationPlatform::Slack { webhook_url } => {
+                    Arc::new(SlackSyndicator::new(webhook_url.as_str()).map_err(invalid)?)
+                }
+            };
+            tracing::info!(target_name = %target.name, "article syndication enabled");
+            Ok(SyndicationTarget {
+                name: target.name.clone(),
+                template: target.template.clone(),
+                syndicator,
+            })
+        })
+        .collect::<Result<_>>()?;
+    let policy = SyndicationPolicy {
+        article_url: settings.article_url.clone(),
+        delay: settings.delay,
+        max_attempts: settings.max_attempts,
+        retry_backoff: settings.retry_backoff,
+    };
+    Ok((targets, policy))
+}
+
+/// The webhook sender, with the policy its deliveries follow.
+fn init_webhooks(config: &Settings) -> (Arc<dyn WebhookSender>, WebhookPolicy) {
+    let settings = config.webhooks();
+    let policy = WebhookPolicy {
+        max_attempts: settings.max_attempts,
+        retry_backoff: settings.retry_backoff,
+    };
+    (Arc::new(HttpWebhookSender::new(settings.timeout)), policy)
+}
+
+fn init_audit_log_repo(
+    store: Arc<dyn AuditLogRepository>,
+    config: &Settings,
+) -> Arc<dyn AuditLogRepository> {
+    let redactor = Redactor::new(Settings::redact_fields_from_env());
+    let Some(buffer) = config.audit_buffer() else {
+        return Arc::new(RedactingAuditLogRepository::new(store, redactor));
+    };
+    let buffered = Arc::new(BufferedAuditLogRepository::spawn(
+        store,
+        BufferOptions {
+            capacity: buffer.capacity,
+            batch_size: buffer.batch_size,
+            flush_interval: buffer.flush_interval,
+            overflow: if buffer.drop_when_full {
+                OverflowPolicy::Drop
+            } else {
+                OverflowPolicy::Block
+            },
+        },
+    ));
+    Arc::new(RedactingAuditLogRepository::new(buffered, redactor))
+}
+
+fn init_clock() -> (Arc<dyn Clock>, Option<Arc<dyn ClockControl>>) {
+    if !Settings::simulated_time_from_env() {
+        return (Arc::new(SystemClock), None);
+    }
+    tracing::warn!("simulated time enabled; admins can advance the clock");
+    let clock = SimulatedClock::new();
+    (Arc::new(clock.clone()), Some(Arc::new(clock)))
+}
+
+fn init_read_only() -> ReadOnlySwitch {
+    let enabled = Settings::read_only_from_env();
+    if enabled {
+        tracing::warn!("starting in read-only mode; writes are refused");
+    }
+    ReadOnlySwitch::new(enabled)
+}
+
+/// With `replay_clock` the services run on it and journal nothing, for
+/// replaying a journal.
 fn build_services_and_state(
-    pool: &PgPool,
+    storage: &Storage,
     config: &Settings,
-) -> Result<(Arc<Registry>, HttpContext)> {
-    let user_repo: Arc<dyn UserRepository> = Arc::new(PostgresUserRepository::new(pool.clone()));
-    let article_write_repo: Arc<dyn ArticleWriteRepository> =
-        Arc::new(PostgresArticleWriteRepository::new(pool.clone()));
-    let article_read_repo: Arc<dyn ArticleReadRepository> =
-        Arc::new(PostgresArticleReadRepository::new(pool.clone()));
-    let article_revision_repo: Arc<dyn ArticleRevisionRepository> =
-        Arc::new(PostgresArticleRevisionRepository::new(pool.clone()));
-
-    let password_hasher: Arc<dyn PasswordHasher> = Arc::new(Argon2PasswordHasher);
-    let token_manager_impl =
-        BiscuitTokenManager::new(config.biscuit_private_key(), config.token_ttl())?;
-    let token_manager: Arc<dyn TokenManager> = Arc::new(token_manager_impl);
+    replay_clock: Option<Arc<ReplayClock>>,
+) -> Result<(Arc<Registry>, HttpContext, Arc<Scheduler>)> {
+    let password_hasher: Arc<dyn PasswordHasher> = init_password_hasher(config)?;
+    let replaying = replay_clock.is_some();
+    let command_journal = if replaying {
+        None
+    } else {
+        init_command_journal(config)?
+    };
+    let (clock, clock_control) =
+        replay_clock.map_or_else(init_clock, |clock| (clock as Arc<dyn Clock>, None));
+    let token_manager = init_token_manager(config, &clock)?;
     let refresh_token_codec = Arc::new(HmacRefreshTokenCodec::new(config.refresh_token_secret())?);
-    let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+    let (link_secret, link_ttl) = config.download_links();
+    let download_links = Arc::new(HmacDownloadLinkSigner::new(link_secret, link_ttl)?);
     let slugger: Arc<dyn SlugGenerator> = Arc::new(DefaultSlugGenerator);
 
-    let audit_log_repo: Arc<dyn mokkan_core::domain::audit::repository::AuditLogRepository> =
-        Arc::new(PostgresAuditLogRepository::new(pool.clone()));
+    let redis_url = storage.redis_url();
+    let (session_store, session_backend) = init_session_store(config, redis_url.as_deref(), &clock);
+    let auth_code_store = init_auth_code_store(redis_url.as_deref());
+    let (rate_limiter, login_throttle) = init_login_throttle(redis_url.as_deref());
+    let slug_cache = init_slug_cache();
+    let (blob_storage, media) = init_media(config)?;
+    // Replays and ephemeral instances announce and deliver nothing.
+    let (syndication_targets, syndication) = match storage {
+        Storage::Postgres(_) | Storage::Sqlite(_) if !replaying => init_syndication(config)?,
+        _ => (Vec::new(), SyndicationPolicy::default()),
+    };
+    let (webhook_sender, webhooks) = match storage {
+        Storage::Postgres(_) | Storage::Sqlite(_) if !replaying => {
+            let (sender, policy) = init_webhooks(config);
+            (Some(sender), policy)
+        }
+        _ => (None, WebhookPolicy::default()),
+    };
+    let (external_authenticator, group_roles) = init_external_auth(config)?;
+    let scheduler = init_scheduler(storage, &clock, config);
 
-    let session_store = init_session_store(config);
-    let auth_code_store = into_auth_code_store(InMemoryStore::new());
+    #[cfg(feature = "article-export")]
+    let article_renderer: Option<Arc<dyn ArticleRenderer>> = Some(Arc::new(BuiltinArticleRenderer));
+    #[cfg(not(feature = "article-export"))]
+    let article_renderer = None;
 
-    let deps = Dependencies {
-        user_repo: Arc::clone(&user_repo),
-        article_write_repo: Arc::clone(&article_write_repo),
-        article_read_repo: Arc::clone(&article_read_repo),
-        article_revision_repo: Arc::clone(&article_revision_repo),
-        audit_log_repo: Arc::clone(&audit_log_repo),
+    let deps = match storage {
+        Storage::Postgres(pool) => postgres_dependencies(pool, config),
+        Storage::Memory(stores) => in_memory_dependencies(stores, config),
+        Storage::Sqlite(stores) => sqlite_dependencies(stores, config),
     };
 
     let services = Arc::new(Registry::new(
         deps,
         RuntimeDependencies {
             password_hasher: Arc::clone(&password_hasher),
+            password_breach_checker: init_breach_checker(config)?,
             token_manager: Arc::clone(&token_manager),
             refresh_token_codec,
             session_revocation_store: Arc::clone(&session_store),
+            session_backend,
             authorization_code_store: Arc::clone(&auth_code_store),
             clock: Arc::clone(&clock),
+            clock_control,
             slugger: Arc::clone(&slugger),
+            security_webhook: match storage {
+                Storage::Postgres(_) | Storage::Sqlite(_) => init_security_webhook(config),
+                Storage::Memory(_) => None,
+            },
+            job_queue: Arc::new(TokioJobQueue),
+            external_authenticator,
+            group_roles,
+            oidc_providers: init_oidc_providers(config)?,
+            command_journal,
+            job_control: Some(Arc::clone(&scheduler) as Arc<dyn JobControl>),
+            document_converter: Arc::new(DocxConverter),
+            article_renderer,
+            archiver: Arc::new(ZipArchiver),
+            site_writer: Arc::new(DirectorySiteWriter::new(config.static_export_dir())),
+            download_links,
+            asn_lookup: init_asn_lookup(config)?,
+            email_sender: Arc::new(LogEmailSender),
+            read_only: init_read_only(),
+            rate_limiter,
+            login_throttle,
+            session_policy: init_session_policy(config),
+            password_history_depth: config.password_history_depth(),
+            account_deletion_grace: chrono::Duration::from_std(config.account_deletion_grace())?,
+            slug_cache,
+            syndication_targets,
+            syndication,
+            webhook_sender,
+            webhooks,
+            pagination: init_pagination(config),
+            blob_storage,
+            media,
+            view_counter: init_view_counter(redis_url.as_deref()),
         },
     ));
 
+    let db_pool = match storage {
+        Storage::Postgres(pool) => pool.clone(),
+        // Never connected: nothing reads the pool without Postgres.
+        Storage::Memory(_) | Storage::Sqlite(_) => {
+            PgPoolOptions::new().connect_lazy("postgres://ephemeral.invalid/unused")?
+        }
+    };
     let state = HttpContext {
         services: Arc::clone(&services),
-        db_pool: pool.clone(),
+        db_pool,
     };
 
-    Ok((services, state))
+    Ok((services, state, scheduler))
+}
+
+fn postgres_dependencies(pool: &PgPool, config: &Settings) -> Dependencies {
+    Dependencies {
+        user_repo: Arc::new(PostgresUserRepository::new(pool.clone())),
+        article_write_repo: Arc::new(
+            PostgresArticleWriteRepository::new(pool.clone())
+                .with_search_language(config.search_language()),
+        ),
+        article_read_repo: Arc::new(
+            PostgresArticleReadRepository::new(pool.clone())
+                .with_search_language(config.search_language()),
+        ),
+        article_revision_repo: Arc::new(PostgresArticleRevisionRepository::new(pool.clone())),
+        custom_field_repo: Arc::new(PostgresCustomFieldRepository::new(pool.clone())),
+        contributor_repo: Arc::new(PostgresContributorRepository::new(pool.clone())),
+        moderation_repo: Arc::new(PostgresModerationRepository::new(pool.clone())),
+        media_repo: Arc::new(PostgresMediaRepository::new(pool.clone())),
+        block_list: Arc::new(PostgresBlockList::new(pool.clone())),
+        password_history: Arc::new(PostgresPasswordHistory::new(pool.clone())),
+        account_deletions: Arc::new(PostgresAccountDeletions::new(pool.clone())),
+        role_store: Arc::new(PostgresRoleStore::new(pool.clone())),
+        access_rule_repo: Arc::new(PostgresAccessRuleRepository::new(pool.clone())),
+        audit_log_repo: init_audit_log_repo(
+            Arc::new(PostgresAuditLogRepository::new(pool.clone())),
+            config,
+        ),
+        user_tokens: Arc::new(PostgresUserTokenStore::new(pool.clone())),
+        syndication_opt_outs: Arc::new(PostgresSyndicationOptOutStore::new(pool.clone())),
+        integrity_checker: Some(Arc::new(PostgresIntegrityChecker::new(pool.clone()))),
+        unit_of_work: Some(Arc::new(
+            PostgresUnitOfWork::new(pool.clone()).with_search_language(config.search_language()),
+        )),
+        webhook_repo: Arc::new(PostgresWebhookRepository::new(pool.clone())),
+    }
+}
+
+/// The integrity check compares Postgres tables, so ephemeral instances go
+/// without it.
+fn in_memory_dependencies(stores: &InMemoryStores, config: &Settings) -> Dependencies {
+    Dependencies {
+        user_repo: stores.users.clone(),
+        article_write_repo: stores.articles.clone(),
+        article_read_repo: stores.articles.clone(),
+        article_revision_repo: stores.articles.clone(),
+        custom_field_repo: stores.custom_fields.clone(),
+        contributor_repo: stores.contributors.clone(),
+        moderation_repo: stores.moderation.clone(),
+        media_repo: stores.media.clone(),
+        block_list: stores.blocks.clone(),
+        password_history: stores.password_history.clone(),
+        account_deletions: stores.account_deletions.clone(),
+        role_store: stores.roles.clone(),
+        access_rule_repo: stores.access_rules.clone(),
+        audit_log_repo: init_audit_log_repo(stores.audit_logs.clone(), config),
+        user_tokens: Arc::new(InMemoryUserTokenStore::new()),
+        syndication_opt_outs: stores.syndication_opt_outs.clone(),
+        integrity_checker: None,
+        unit_of_work: None,
+        webhook_repo: stores.webhooks.clone(),
+    }
+}
+
+/// The integrity check compares Postgres tables, so `SQLite` instances go
+/// without it too.
+fn sqlite_dependencies(stores: &SqliteStores, config: &Settings) -> Dependencies {
+    Dependencies {
+        user_repo: stores.users.clone(),
+        article_write_repo: stores.articles.clone(),
+        article_read_repo: stores.articles.clone(),
+        article_revision_repo: stores.articles.clone(),
+        custom_field_repo: stores.custom_fields.clone(),
+        contributor_repo: stores.contributors.clone(),
+        moderation_repo: stores.moderation.clone(),
+        media_repo: stores.media.clone(),
+        block_list: stores.blocks.clone(),
+        password_history: stores.password_history.clone(),
+        account_deletions: stores.account_deletions.clone(),
+        role_store: stores.roles.clone(),
+        access_rule_repo: stores.access_rules.clone(),
+        audit_log_repo: init_audit_log_repo(stores.audit_logs.clone(), config),
+        user_tokens: stores.user_tokens.clone(),
+        syndication_opt_outs: stores.syndication_opt_outs.clone(),
+        integrity_checker: None,
+        unit_of_work: Some(stores.unit_of_work.clone()),
+        webhook_repo: stores.webhooks.clone(),
+    }
 }
 
-fn init_tracing() {
+/// Job locks and run history live next to the data: in Postgres, shared by
+/// every instance, or in memory for an ephemeral or `SQLite` instance, which
+/// runs as a single process.
+fn init_scheduler(storage: &Storage, clock: &Arc<dyn Clock>, config: &Settings) -> Arc<Scheduler> {
+    let (locks, runs): (Arc<dyn LockManager>, Arc<dyn JobRunStore>) = match storage {
+        Storage::Postgres(pool) => (
+            Arc::new(PostgresLockManager::new(pool.clone())),
+            Arc::new(PostgresJobRunStore::new(pool.clone())),
+        ),
+        Storage::Memory(_) | Storage::Sqlite(_) => (
+            Arc::new(InMemoryLockManager::default()),
+            Arc::new(InMemoryJobRunStore::default()),
+        ),
+    };
+    Arc::new(Scheduler::new(
+        Arc::clone(clock),
+        locks,
+        runs,
+        SchedulerOptions {
+            jitter: config.job_jitter(),
+            ..SchedulerOptions::default()
+        },
+    ))
+}
+
+fn schedule_jobs(scheduler: &Scheduler, services: &Registry, config: &Settings) -> Result<()> {
+    if let Some(schedule) = config.job_schedule("session_cleanup", "@hourly") {
+        let cleanup = Arc::clone(&services.session_cleanup);
+        let max_lifetime = config.session_max_lifetime();
+        scheduler.schedule(Job::new("session_cleanup", schedule.parse()?, move || {
+            let cleanup = Arc::clone(&cleanup);
+            boxed(async move { cleanup.purge_expired(max_lifetime).await.map(|_| ()) })
+        }));
+    } else {
+        tracing::info!("session cleanup job disabled");
+    }
+    if let Some(schedule) = config.job_schedule("article_view_flush", "* * * * *") {
+        let views = Arc::clone(&services.article_views);
+        scheduler.schedule(Job::new(
+            "article_view_flush",
+            schedule.parse()?,
+            move || {
+                let views = Arc::clone(&views);
+                boxed(async move { views.flush().await.map(|_| ()) })
+            },
+        ));
+    } else {
+        tracing::info!("article view flush job disabled");
+    }
+    if let Some(schedule) = config.job_schedule("account_deletion_purge", "@hourly") {
+        let users = Arc::clone(&services.user_commands);
+        scheduler.schedule(Job::new(
+            "account_deletion_purge",
+            schedule.parse()?,
+            move || {
+                let users = Arc::clone(&users);
+                boxed(async move { users.purge_due_account_deletions().await.map(|_| ()) })
+            },
+        ));
+    } else {
+        tracing::info!("account deletion purge job disabled");
+    }
+    Ok(())
+}
+
+/// Wipe an ephemeral instance back to its seed, `@daily` unless
+/// `JOB_SCHEDULE_EPHEMERAL_RESET` says otherwise.
+fn schedule_ephemeral_reset(
+    scheduler: &Scheduler,
+    services: &Registry,
+    config: &Settings,
+    stores: Arc<InMemoryStores>,
+    seed: Option<(String, SeedDocument)>,
+) -> Result<()> {
+    let Some(schedule) = config.job_schedule("ephemeral_reset", "@daily") else {
+        tracing::info!("ephemeral reset job disabled");
+        return Ok(());
+    };
+    let seeder = Arc::clone(&services.seed);
+    let slug_cache = services.slug_cache.clone();
+    let seed = Arc::new(seed.map(|(_, document)| document));
+    scheduler.schedule(Job::new("ephemeral_reset", schedule.parse()?, move || {
+        let (stores, seeder, seed) = (Arc::clone(&stores), Arc::clone(&seeder), Arc::clone(&seed));
+        let slug_cache = slug_cache.clone();
+        boxed(async move {
+            stores.reset();
+            if let Some(seed) = seed.as_ref() {
+                seeder.apply(seed).await?;
+            }
+            slug_cache.clear();
+            Ok(())
+        })
+    }));
+    Ok(())
+}
+
+/// Log to stdout and, when an OTLP endpoint is configured, export spans.
+/// The returned handle flushes the last spans on shutdown.
+fn init_tracing() -> Result<Option<Telemetry>> {
+    dotenvy::dotenv().ok();
     let env_filter = std::env::var("RUST_LOG")
         .ok()
         .unwrap_or_else(|| "info,tower_http=info,sqlx=warn".to_string());
 
+    // Each layer filters on its own, so exported debug spans stay out of the
+    // console log.
+    let (otlp, telemetry) = match Settings::otel_from_env()? {
+        Some(otel) => {
+            let (layer, telemetry) = telemetry::pipeline(ExportTarget {
+                url: otel.traces_url,
+                service_name: otel.service_name,
+                headers: otel.headers,
+            })?;
+            let filter = EnvFilter::new(otel.filter);
+            (Some(layer.with_filter(filter)), Some(telemetry))
+        }
+        None => (None, None),
+    };
     let subscriber = tracing_subscriber::registry()
-        .with(tracing_subscriber::EnvFilter::new(env_filter))
-        .with(tracing_subscriber::fmt::layer());
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(RedactingMakeWriter::new(
+                    std::io::stdout,
+                    Redactor::new(Settings::redact_fields_from_env()),
+                ))
+                .with_filter(EnvFilter::new(env_filter)),
+        )
+        .with(otlp);
 
     if subscriber.try_init().is_err() {
         tracing::warn!("tracing subscriber already initialised");
     }
+    Ok(telemetry)
+}
+
+/// Export the spans still queued before the process exits.
+async fn flush_traces(telemetry: Option<Telemetry>) {
+    if let Some(telemetry) = telemetry {
+        telemetry.shutdown().await;
+    }
 }
 
 async fn shutdown_signal() {
@@ -0,0 +1,126 @@
+// src/presentation/http/conditional.rs
+//! Conditional GET for JSON reads.
+//!
+//! Responses carry an `ETag` computed from the exact body sent and, when the
+//! resource knows when it last changed, a `Last-Modified` date. A request
+//! whose `If-None-Match` or `If-Modified-Since` shows the client already has
+//! this representation gets `304 Not Modified` with no body.
+use crate::presentation::http::openapi::openapi_meta::{compute_simple_etag, inm_matches};
+use axum::http::{HeaderMap, HeaderValue, header};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use std::time::SystemTime;
+
+/// Validators for one representation of a resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validators {
+    etag: String,
+    last_modified: Option<SystemTime>,
+}
+
+impl Validators {
+    /// Validators for `body`, last changed at `last_modified` if known.
+    #[must_use]
+    pub fn new(body: &Bytes, last_modified: Option<DateTime<Utc>>) -> Self {
+        Self {
+            etag: compute_simple_etag(body),
+            last_modified: last_modified.map(SystemTime::from),
+        }
+    }
+
+    /// Whether `request` shows the client already has this representation.
+    ///
+    /// `If-None-Match` takes precedence: when present, `If-Modified-Since`
+    /// is ignored. Dates compare at whole seconds, as HTTP dates carry.
+    #[must_use]
+    pub fn not_modified(&self, request: &HeaderMap) -> bool {
+        if request.contains_key(header::IF_NONE_MATCH) {
+            return inm_matches(request, &self.etag);
+        }
+        let Some(last_modified) = self.last_modified else {
+            return false;
+        };
+        request
+            .get(header::IF_MODIFIED_SINCE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| httpdate::parse_http_date(value).ok())
+            .is_some_and(|since| truncate_to_seconds(last_modified) <= since)
+    }
+
+    /// Add `ETag` and `Last-Modified` to `response`.
+    pub fn apply(&self, response: &mut HeaderMap) {
+        if let Ok(etag) = HeaderValue::from_str(&self.etag) {
+            response.insert(header::ETAG, etag);
+        }
+        if let Some(last_modified) = self.last_modified
+            && let Ok(date) = HeaderValue::from_str(&httpdate::fmt_http_date(last_modified))
+        {
+            response.insert(header::LAST_MODIFIED, date);
+        }
+    }
+}
+
+fn truncate_to_seconds(time: SystemTime) -> SystemTime {
+    httpdate::parse_http_date(&httpdate::fmt_http_date(time)).unwrap_or(time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Validators;
+    use axum::http::{HeaderMap, HeaderValue, header};
+    use bytes::Bytes;
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+
+    fn changed_at() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 2, 14, 5, 0).unwrap() + Duration::milliseconds(250)
+    }
+
+    fn request(name: header::HeaderName, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn headers_carry_the_body_hash_and_change_date() {
+        let validators = Validators::new(&Bytes::from_static(b"{}"), Some(changed_at()));
+        let mut headers = HeaderMap::new();
+        validators.apply(&mut headers);
+        assert!(headers[header::ETAG].to_str().unwrap().starts_with('"'));
+        assert_eq!(
+            headers[header::LAST_MODIFIED],
+            "Thu, 02 May 2024 14:05:00 GMT"
+        );
+    }
+
+    #[test]
+    fn if_modified_since_compares_whole_seconds() {
+        let validators = Validators::new(&Bytes::from_static(b"{}"), Some(changed_at()));
+        let same = request(header::IF_MODIFIED_SINCE, "Thu, 02 May 2024 14:05:00 GMT");
+        let earlier = request(header::IF_MODIFIED_SINCE, "Thu, 02 May 2024 14:04:59 GMT");
+        let garbage = request(header::IF_MODIFIED_SINCE, "yesterday");
+        assert!(validators.not_modified(&same));
+        assert!(!validators.not_modified(&earlier));
+        assert!(!validators.not_modified(&garbage));
+
+        let undated = Validators::new(&Bytes::from_static(b"{}"), None);
+        assert!(!undated.not_modified(&same));
+    }
+
+    #[test]
+    fn if_none_match_takes_precedence() {
+        let validators = Validators::new(&Bytes::from_static(b"{}"), Some(changed_at()));
+        let mut headers = request(header::IF_NONE_MATCH, "\"stale\"");
+        headers.insert(
+            header::IF_MODIFIED_SINCE,
+            HeaderValue::from_static("Thu, 02 May 2024 14:05:00 GMT"),
+        );
+        assert!(!validators.not_modified(&headers));
+
+        let mut current = HeaderMap::new();
+        validators.apply(&mut current);
+        let etag = current[header::ETAG].to_str().unwrap();
+        assert!(validators.not_modified(&request(header::IF_NONE_MATCH, etag)));
+        assert!(validators.not_modified(&request(header::IF_NONE_MATCH, &format!("W/{etag}"))));
+    }
+}
@@ -0,0 +1,94 @@
+// src/presentation/http/controllers/account_deletion.rs
+use crate::application::AccountDeletionDto;
+use crate::application::commands::users::DeleteAccountCommand;
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::openapi::StatusResponse;
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct DeleteAccountRequest {
+    /// The caller's password, confirming the deletion.
+    pub current_password: String,
+    /// User who takes over the caller's articles; omitted, they stay with
+    /// the anonymized account.
+    #[serde(default)]
+    pub transfer_articles_to: Option<i64>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/me/deletion",
+    request_body = DeleteAccountRequest,
+    responses(
+        (status = 202, description = "Account disabled, signed out everywhere and scheduled for anonymization.", body = AccountDeletionDto),
+        (status = 400, description = "Invalid transfer target.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized or wrong password.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Auth"
+)]
+/// Delete the current user's account after a grace period.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails, the password is wrong, the
+/// transfer target is invalid, or the deletion cannot be scheduled.
+pub async fn delete_account(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Json(payload): Json<DeleteAccountRequest>,
+) -> HttpResult<(StatusCode, Json<AccountDeletionDto>)> {
+    let deletion = state
+        .services
+        .user_commands
+        .delete_account(
+            &user,
+            DeleteAccountCommand {
+                current_password: payload.current_password,
+                transfer_articles_to: payload.transfer_articles_to,
+            },
+        )
+        .await
+        .into_http()?;
+    Ok((StatusCode::ACCEPTED, Json(deletion)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/{id}/deletion",
+    params(("id" = i64, Path, description = "User identifier")),
+    responses(
+        (status = 200, description = "Deletion cancelled and account reactivated.", body = StatusResponse),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "No pending deletion for this user.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Users"
+)]
+/// Cancel a user's pending account deletion.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails, the caller lacks
+/// `users:update`, or the user has no pending deletion.
+pub async fn cancel_account_deletion(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Path(id): Path<i64>,
+) -> HttpResult<Json<StatusResponse>> {
+    state
+        .services
+        .user_commands
+        .cancel_account_deletion(&user, id)
+        .await
+        .into_http()?;
+    Ok(Json(StatusResponse {
+        status: "deletion_cancelled".into(),
+    }))
+}
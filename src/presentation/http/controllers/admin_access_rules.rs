@@ -0,0 +1,97 @@
+// src/presentation/http/controllers/admin_access_rules.rs
+use crate::application::AccessRuleDto;
+use crate::application::services::CreateAccessRuleCommand;
+use crate::domain::{RouteGroup, RuleAction};
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::{Authenticated, RequestClient};
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAccessRuleRequest {
+    /// `admin` or `registration`.
+    pub group: RouteGroup,
+    /// `allow` or `deny`.
+    pub action: RuleAction,
+    /// CIDR range such as `203.0.113.0/24`; set this or `asn`.
+    #[serde(default)]
+    pub cidr: Option<String>,
+    /// Autonomous system number; needs an ASN database.
+    #[serde(default)]
+    pub asn: Option<u32>,
+    /// Lower values are tried first; defaults to 100.
+    #[serde(default)]
+    pub priority: Option<i32>,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// List access rules in evaluation order.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the rules
+/// cannot be loaded.
+pub async fn list_access_rules(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+) -> HttpResult<Json<Vec<AccessRuleDto>>> {
+    state
+        .services
+        .access_rules
+        .list(&actor)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+/// Add an allow or deny rule for a route group.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the rule is
+/// invalid.
+pub async fn create_access_rule(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    RequestClient(client): RequestClient,
+    Json(payload): Json<CreateAccessRuleRequest>,
+) -> HttpResult<(StatusCode, Json<AccessRuleDto>)> {
+    let command = CreateAccessRuleCommand {
+        group: payload.group,
+        action: payload.action,
+        cidr: payload.cidr,
+        asn: payload.asn,
+        priority: payload.priority,
+        note: payload.note,
+    };
+    let rule = state
+        .services
+        .access_rules
+        .create(&actor, &client, command)
+        .await
+        .into_http()?;
+    Ok((StatusCode::CREATED, Json(rule)))
+}
+
+/// Remove an access rule.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the rule
+/// does not exist.
+pub async fn delete_access_rule(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    RequestClient(client): RequestClient,
+    Path(id): Path<i64>,
+) -> HttpResult<StatusCode> {
+    state
+        .services
+        .access_rules
+        .delete(&actor, &client, id)
+        .await
+        .into_http()?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,107 @@
+// src/presentation/http/controllers/admin_custom_fields.rs
+use crate::application::{
+    CustomFieldDefinitionDto, CustomFieldMigration, CustomFieldMigrationJobDto,
+    commands::articles::{DeleteCustomFieldCommand, PutCustomFieldCommand},
+};
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct PutCustomFieldRequest {
+    /// `text`, `number`, `boolean` or `date`.
+    #[serde(rename = "type")]
+    pub field_type: String,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Define a custom field, or redefine the one with the same name.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the name or
+/// type is invalid.
+pub async fn put_custom_field(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(name): Path<String>,
+    Json(payload): Json<PutCustomFieldRequest>,
+) -> HttpResult<Json<CustomFieldDefinitionDto>> {
+    let command = PutCustomFieldCommand {
+        name,
+        field_type: payload.field_type,
+        required: payload.required,
+    };
+    state
+        .services
+        .article_commands
+        .put_custom_field(&actor, command)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+/// Remove a custom field definition.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the field
+/// does not exist.
+pub async fn delete_custom_field(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(name): Path<String>,
+) -> HttpResult<StatusCode> {
+    state
+        .services
+        .article_commands
+        .delete_custom_field(&actor, DeleteCustomFieldCommand { name })
+        .await
+        .into_http()?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Queue a migration of a custom field and the values stored on articles.
+/// Poll the returned job for progress.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the field does
+/// not exist, the migration does not fit it, or the field is already being
+/// migrated.
+pub async fn migrate_custom_field(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(name): Path<String>,
+    Json(migration): Json<CustomFieldMigration>,
+) -> HttpResult<(StatusCode, Json<CustomFieldMigrationJobDto>)> {
+    let job = state
+        .services
+        .custom_field_migrations
+        .submit(&actor, &name, migration)
+        .await
+        .into_http()?;
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// Report the progress of a custom field migration.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails or the job is
+/// unknown.
+pub async fn custom_field_migration_job(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(id): Path<String>,
+) -> HttpResult<Json<CustomFieldMigrationJobDto>> {
+    state
+        .services
+        .custom_field_migrations
+        .job(&actor, &id)
+        .into_http()
+        .map(Json)
+}
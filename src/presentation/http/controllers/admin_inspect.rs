@@ -0,0 +1,69 @@
+// src/presentation/http/controllers/admin_inspect.rs
+use crate::application::queries::inspect::{
+    InspectArticleQuery, InspectSessionQuery, InspectUserQuery,
+};
+use crate::application::{ArticleInspectionDto, SessionInspectionDto, UserInspectionDto};
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, Json, extract::Path};
+
+/// Inspect an article in any publication state, with author and revisions.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the article
+/// does not exist, or a repository lookup fails.
+pub async fn inspect_article(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(id): Path<i64>,
+) -> HttpResult<Json<ArticleInspectionDto>> {
+    let res = state
+        .services
+        .inspect
+        .inspect_article(&actor, InspectArticleQuery { id })
+        .await
+        .into_http()?;
+    Ok(Json(res))
+}
+
+/// Inspect a user, including inactive ones, with sessions and token state.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the user does
+/// not exist, or a store lookup fails.
+pub async fn inspect_user(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(id): Path<i64>,
+) -> HttpResult<Json<UserInspectionDto>> {
+    let res = state
+        .services
+        .inspect
+        .inspect_user(&actor, InspectUserQuery { id })
+        .await
+        .into_http()?;
+    Ok(Json(res))
+}
+
+/// Inspect a session's stored metadata, revocation and refresh state.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the session is
+/// unknown, or a store lookup fails.
+pub async fn inspect_session(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(session_id): Path<String>,
+) -> HttpResult<Json<SessionInspectionDto>> {
+    let res = state
+        .services
+        .inspect
+        .inspect_session(&actor, InspectSessionQuery { session_id })
+        .await
+        .into_http()?;
+    Ok(Json(res))
+}
@@ -0,0 +1,55 @@
+// src/presentation/http/controllers/admin_integrity.rs
+use crate::application::IntegrityReportDto;
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, Json, extract::Query};
+use serde::Deserialize;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct IntegrityCheckParams {
+    /// Fix repairable anomalies before reporting.
+    #[serde(default)]
+    pub repair: bool,
+}
+
+/// Report cross-table anomalies: revisions without articles, custom field
+/// values without definitions, and audit entries naming missing rows.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails or the scan
+/// cannot run.
+pub async fn check_integrity(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+) -> HttpResult<Json<IntegrityReportDto>> {
+    state
+        .services
+        .integrity
+        .check(&actor, false)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+/// Run the integrity check, repairing what can be repaired first with
+/// `?repair=true`.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the
+/// repair or scan fails.
+pub async fn run_integrity_check(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Query(params): Query<IntegrityCheckParams>,
+) -> HttpResult<Json<IntegrityReportDto>> {
+    state
+        .services
+        .integrity
+        .check(&actor, params.repair)
+        .await
+        .into_http()
+        .map(Json)
+}
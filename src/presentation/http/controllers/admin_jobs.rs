@@ -0,0 +1,71 @@
+// src/presentation/http/controllers/admin_jobs.rs
+use crate::application::{JobRunDto, JobStatusDto};
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::state::HttpContext;
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    http::StatusCode,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct JobRunsParams {
+    /// Runs returned; see `pagination.endpoints.jobs` in
+    /// `GET /api/v1/discovery/limits` for the default and cap.
+    pub limit: Option<u32>,
+}
+
+/// List scheduled jobs with their next run and last outcome.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails.
+pub async fn list_jobs(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+) -> HttpResult<Json<Vec<JobStatusDto>>> {
+    state.services.jobs.list(&actor).into_http().map(Json)
+}
+
+/// List the latest runs of a job, newest first.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails or the job is
+/// unknown.
+pub async fn list_job_runs(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(name): Path<String>,
+    Query(params): Query<JobRunsParams>,
+) -> HttpResult<Json<Vec<JobRunDto>>> {
+    state
+        .services
+        .jobs
+        .runs(&actor, &name, params.limit)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+/// Start a job now; it finishes in the background.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the job is
+/// unknown, or it is already running.
+pub async fn run_job(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(name): Path<String>,
+) -> HttpResult<(StatusCode, Json<JobRunDto>)> {
+    let run = state
+        .services
+        .jobs
+        .trigger(&actor, &name)
+        .await
+        .into_http()?;
+    Ok((StatusCode::ACCEPTED, Json(run)))
+}
@@ -0,0 +1,48 @@
+// src/presentation/http/controllers/admin_read_only.rs
+use crate::application::ReadOnlyDto;
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, Json};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct SetReadOnlyRequest {
+    pub enabled: bool,
+}
+
+/// Report whether read-only mode is on.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails.
+pub async fn read_only(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+) -> HttpResult<Json<ReadOnlyDto>> {
+    state
+        .services
+        .read_only
+        .current(&actor)
+        .into_http()
+        .map(Json)
+}
+
+/// Turn read-only mode on or off for this instance.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails.
+pub async fn set_read_only(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Json(payload): Json<SetReadOnlyRequest>,
+) -> HttpResult<Json<ReadOnlyDto>> {
+    state
+        .services
+        .read_only
+        .set(&actor, payload.enabled)
+        .await
+        .into_http()
+        .map(Json)
+}
@@ -0,0 +1,185 @@
+// src/presentation/http/controllers/admin_roles.rs
+use crate::application::services::{
+    GrantCapabilityCommand, PutRoleCommand, RevokeCapabilityCommand,
+};
+use crate::application::{CapabilityGrantsDto, CapabilityView, CustomRoleDto, RoleAssignmentDto};
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct PutRoleRequest {
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Everything the role grants; see `GET /api/v1/auth/me/permissions`
+    /// for the names.
+    pub capabilities: Vec<CapabilityView>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssignRoleRequest {
+    /// Custom role to give the user, or `null` to take theirs away.
+    pub role: Option<String>,
+}
+
+/// List custom roles by name.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails.
+pub async fn list_roles(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+) -> HttpResult<Json<Vec<CustomRoleDto>>> {
+    state
+        .services
+        .roles
+        .list(&actor)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+/// Define a custom role, or redefine the one with the same name.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the name,
+/// description or a capability is invalid.
+pub async fn put_role(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(name): Path<String>,
+    Json(payload): Json<PutRoleRequest>,
+) -> HttpResult<Json<CustomRoleDto>> {
+    let command = PutRoleCommand {
+        name,
+        description: payload.description,
+        capabilities: payload.capabilities,
+    };
+    state
+        .services
+        .roles
+        .put(&actor, command)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+/// Remove a custom role; its users fall back to their built-in role.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the role
+/// does not exist.
+pub async fn delete_role(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(name): Path<String>,
+) -> HttpResult<StatusCode> {
+    state
+        .services
+        .roles
+        .delete(&actor, &name)
+        .await
+        .into_http()?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Give a user a custom role, or take theirs away. Takes effect from the
+/// user's next login or token refresh.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the user or
+/// role does not exist.
+pub async fn assign_role(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(user_id): Path<i64>,
+    Json(payload): Json<AssignRoleRequest>,
+) -> HttpResult<Json<RoleAssignmentDto>> {
+    state
+        .services
+        .roles
+        .assign(&actor, user_id, payload.role.as_deref())
+        .await
+        .into_http()
+        .map(Json)
+}
+
+/// List the capabilities granted to a user on top of those of their role.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the user
+/// does not exist.
+pub async fn list_capability_grants(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(user_id): Path<i64>,
+) -> HttpResult<Json<CapabilityGrantsDto>> {
+    state
+        .services
+        .roles
+        .capability_grants(&actor, user_id)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+/// Grant a user a capability, written `resource:action`. Takes effect from
+/// the user's next login or token refresh.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the
+/// capability is unknown, or the user does not exist.
+pub async fn grant_capability(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path((user_id, capability)): Path<(i64, String)>,
+) -> HttpResult<Json<CapabilityGrantsDto>> {
+    state
+        .services
+        .roles
+        .grant_capability(
+            &actor,
+            GrantCapabilityCommand {
+                user_id,
+                capability,
+            },
+        )
+        .await
+        .into_http()
+        .map(Json)
+}
+
+/// Take back a capability granted to a user. Tokens already issued keep it
+/// until they expire.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the user
+/// was not granted the capability.
+pub async fn revoke_capability(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path((user_id, capability)): Path<(i64, String)>,
+) -> HttpResult<Json<CapabilityGrantsDto>> {
+    state
+        .services
+        .roles
+        .revoke_capability(
+            &actor,
+            RevokeCapabilityCommand {
+                user_id,
+                capability,
+            },
+        )
+        .await
+        .into_http()
+        .map(Json)
+}
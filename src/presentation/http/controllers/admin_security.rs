@@ -0,0 +1,33 @@
+// src/presentation/http/controllers/admin_security.rs
+use crate::application::queries::audit::token_reuse::ListTokenReuseIncidentsQuery;
+use crate::application::{CursorPage, TokenReuseIncidentDto};
+use crate::presentation::http::controllers::audit::ListAuditParams;
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, Json, extract::Query};
+
+/// List detected refresh-token reuse incidents.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the cursor is
+/// invalid, or the query service fails.
+pub async fn list_token_reuse_incidents(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Query(params): Query<ListAuditParams>,
+) -> HttpResult<Json<CursorPage<TokenReuseIncidentDto>>> {
+    let service = state.services.audit_queries();
+    let res = service
+        .list_token_reuse_incidents(
+            &actor,
+            ListTokenReuseIncidentsQuery {
+                limit: params.limit,
+                cursor: params.cursor,
+            },
+        )
+        .await
+        .into_http()?;
+    Ok(Json(res))
+}
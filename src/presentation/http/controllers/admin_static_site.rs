@@ -0,0 +1,26 @@
+// src/presentation/http/controllers/admin_static_site.rs
+use crate::application::StaticSiteExportDto;
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, Json};
+
+/// Write every published article, and an index linking them, as static
+/// HTML to the server's `STATIC_EXPORT_DIR`.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails or a page
+/// cannot be written.
+pub async fn export_static_site(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+) -> HttpResult<Json<StaticSiteExportDto>> {
+    state
+        .services
+        .static_site
+        .export(&actor)
+        .await
+        .into_http()
+        .map(Json)
+}
@@ -0,0 +1,49 @@
+// src/presentation/http/controllers/admin_time.rs
+use crate::application::SimulatedTimeDto;
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, Json};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct AdvanceTimeRequest {
+    pub seconds: i64,
+}
+
+/// Show the simulated clock.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails or simulated
+/// time is disabled.
+pub async fn simulated_time(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+) -> HttpResult<Json<SimulatedTimeDto>> {
+    state
+        .services
+        .simulated_time
+        .current(&actor)
+        .into_http()
+        .map(Json)
+}
+
+/// Move the simulated clock forward, e.g. past a token's expiry.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, simulated time
+/// is disabled, or the step is out of range.
+pub async fn advance_time(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Json(request): Json<AdvanceTimeRequest>,
+) -> HttpResult<Json<SimulatedTimeDto>> {
+    state
+        .services
+        .simulated_time
+        .advance(&actor, request.seconds)
+        .into_http()
+        .map(Json)
+}
@@ -0,0 +1,70 @@
+// src/presentation/http/controllers/admin_users.rs
+use crate::application::services::UserImportCommand;
+use crate::application::{DuplicateStrategy, UserImportJobDto};
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::state::HttpContext;
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    http::StatusCode,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ImportUsersParams {
+    #[serde(default)]
+    pub on_duplicate: DuplicateStrategy,
+    /// Require a password reset for every imported user.
+    #[serde(default)]
+    pub force_password_reset: bool,
+}
+
+/// Queue a CSV of users for import.
+///
+/// The body is CSV with a `username,role[,password_hash][,force_password_reset]`
+/// header. Poll the returned job for per-row results.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails or the CSV is
+/// malformed.
+pub async fn import_users(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Query(params): Query<ImportUsersParams>,
+    csv: String,
+) -> HttpResult<(StatusCode, Json<UserImportJobDto>)> {
+    let job = state
+        .services
+        .user_import
+        .submit(
+            &actor,
+            &UserImportCommand {
+                csv,
+                on_duplicate: params.on_duplicate,
+                force_password_reset: params.force_password_reset,
+            },
+        )
+        .into_http()?;
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// Report the progress and per-row results of a user import.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails or the job is
+/// unknown.
+pub async fn user_import_job(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(id): Path<String>,
+) -> HttpResult<Json<UserImportJobDto>> {
+    state
+        .services
+        .user_import
+        .job(&actor, &id)
+        .into_http()
+        .map(Json)
+}
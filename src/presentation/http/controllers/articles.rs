@@ -1,66 +1,210 @@
 // src/presentation/http/controllers/articles.rs
 use crate::application::{
-    ArticleDto, ArticleRevisionDto,
+    AppError, ArticleContributorDto, ArticleDto, ArticleExportJobDto, ArticleImportDto,
+    ArticleRevisionDiffDto, ArticleRevisionDto, ArticleSort, ArticleSyndicationDto, BulkResult,
+    CustomFieldDefinitionDto, DiffGranularity, ExportedFile, PageDirection, RenderProfile,
     commands::articles::{
-        CreateArticleCommand, DeleteArticleCommand, SetPublishStateCommand, UpdateArticleCommand,
+        AddContributorCommand, BulkArticleCommand, BulkArticleOperation, CreateArticleCommand,
+        DeleteArticleCommand, RemoveContributorCommand, RevertArticleToRevisionCommand,
+        SetPublishStateCommand, UpdateArticleCommand,
     },
+    dto::serde_time,
     queries::articles::{
-        GetArticleBySlugQuery, ListArticleRevisionsQuery, ListArticlesQuery, SearchArticlesQuery,
+        BatchGetArticlesQuery, DiffArticleRevisionsQuery, GetArticleBySlugQuery,
+        ListArticleRevisionsQuery, ListArticlesQuery, ListContributorsQuery, RenderArticleQuery,
+        SearchArticlesQuery,
     },
+    services::ArticleExport,
 };
+use crate::domain::{ArticleVisibility, CustomFields};
 use crate::presentation::http::error::{HttpResult, IntoHttpResult};
 use crate::presentation::http::extractors::{Authenticated, MaybeAuthenticated};
-use crate::presentation::http::openapi::{ArticleListResponse, StatusResponse};
+use crate::presentation::http::openapi::{
+    ArticleBatchResponse, ArticleListResponse, SearchResultPage, StatusResponse,
+};
+use crate::presentation::http::profiles::{ApiProfile, Profiled};
 use crate::presentation::http::state::HttpContext;
 use axum::{
     Extension, Json,
+    body::Bytes,
     extract::{Path, Query},
+    http::{
+        HeaderMap, StatusCode,
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE, LOCATION},
+    },
+    response::{Html, IntoResponse, Response},
 };
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use utoipa::IntoParams;
 
-const fn default_limit() -> u32 {
-    20
-}
-
-#[derive(Debug, Deserialize, IntoParams, utoipa::ToSchema)]
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams, utoipa::ToSchema)]
 pub struct ArticleListParams {
     #[serde(default)]
     pub include_drafts: bool,
-    #[serde(default = "default_limit")]
+    /// Page size. 0 or omitted uses this endpoint's default and larger
+    /// values are clamped to its cap; see `pagination.endpoints.articles` in
+    /// `GET /api/v1/discovery/limits`.
+    #[serde(default)]
     pub limit: u32,
     #[serde(default)]
     pub cursor: Option<String>,
     #[serde(default)]
     pub q: Option<String>,
+    /// Only list articles carrying this tag. Not supported with
+    /// `direction=backward`.
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// `backward` returns the page before `cursor` (requires a cursor).
+    #[serde(default)]
+    pub direction: PageDirection,
+    /// `popular` lists the most viewed articles first. Not supported with
+    /// `q` or `direction=backward`, and its cursors only page this order.
+    #[serde(default)]
+    pub sort: ArticleSort,
+    /// 1-based page number: returns numbered pages with a `total_count`
+    /// instead of cursors. Not supported with `q`, `cursor`, `direction` or
+    /// `sort=popular`.
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// Page size for numbered pages; overrides `limit` when present.
+    #[serde(default)]
+    pub page_size: Option<u32>,
 }
 
-#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams, utoipa::ToSchema)]
+pub struct ArticleSearchParams {
+    /// Web search syntax: quoted phrases, `-` to exclude a term and `or`
+    /// between alternatives.
+    pub q: String,
+    #[serde(default)]
+    pub include_drafts: bool,
+    /// Page size, as for `GET /api/v1/articles`.
+    #[serde(default)]
+    pub limit: u32,
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Only search articles carrying this tag.
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct CreateArticleRequest {
     pub title: String,
     pub body: String,
     #[serde(default)]
     pub publish: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Values for the site's custom fields, keyed by field name.
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub custom_fields: CustomFields,
+    /// Who may read the article; `public` when omitted.
+    #[serde(default)]
+    pub visibility: ArticleVisibility,
 }
 
-#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[derive(Debug, Default, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct UpdateArticleRequest {
     pub title: Option<String>,
     pub body: Option<String>,
     pub publish: Option<bool>,
+    /// Replaces the article's tags when present.
+    pub tags: Option<Vec<String>>,
+    /// Replaces all of the article's custom field values when present.
+    #[schema(value_type = Option<Object>)]
+    pub custom_fields: Option<CustomFields>,
+    /// Changes who may read the article when present.
+    pub visibility: Option<ArticleVisibility>,
 }
 
-#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct PublishRequest {
     pub publish: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SyndicationRequest {
+    /// `false` keeps the article from being announced when it is published.
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AddContributorRequest {
+    /// The user to let edit the article.
+    pub user_id: i64,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct RevertArticleRequest {
+    /// The article's `updated_at` as last read; the revert is refused with
+    /// 409 if the article has changed since.
+    #[serde(default, with = "serde_time::option")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub expected_updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RevisionDiffParams {
+    /// `line` (the default) or `word`; applies to the body.
+    #[serde(default)]
+    pub granularity: DiffGranularity,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct BatchGetArticlesRequest {
+    /// Article ids to resolve (at most 100).
+    pub ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkArticleAction {
+    Publish,
+    Unpublish,
+    Delete,
+    Tag,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct BulkArticlesRequest {
+    pub operation: BulkArticleAction,
+    /// Articles to change (at most 100).
+    pub ids: Vec<i64>,
+    /// Tags to add; only for the `tag` operation.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl TryFrom<BulkArticlesRequest> for BulkArticleCommand {
+    type Error = AppError;
+
+    fn try_from(request: BulkArticlesRequest) -> Result<Self, Self::Error> {
+        let operation = match request.operation {
+            BulkArticleAction::Tag => BulkArticleOperation::Tag { tags: request.tags },
+            _ if !request.tags.is_empty() => {
+                return Err(AppError::validation("tags only apply to the tag operation"));
+            }
+            BulkArticleAction::Publish => BulkArticleOperation::Publish,
+            BulkArticleAction::Unpublish => BulkArticleOperation::Unpublish,
+            BulkArticleAction::Delete => BulkArticleOperation::Delete,
+        };
+        Ok(Self {
+            operation,
+            ids: request.ids,
+        })
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/api/v1/articles",
     params(ArticleListParams),
     responses(
         (status = 200, description = "List articles.", body = ArticleListResponse),
+        (status = 304, description = "The page is unchanged since the client's copy."),
         (status = 400, description = "Invalid query parameters.", body = crate::presentation::http::error::ResponsePayload),
         (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
     ),
@@ -69,6 +213,10 @@ pub struct PublishRequest {
 )]
 /// List articles visible to the caller.
 ///
+/// Supports conditional GET with `If-None-Match` against the page's `ETag`.
+/// Pages carry no `Last-Modified`: removing, unpublishing or reordering
+/// articles changes a page without changing any article's `updated_at`.
+///
 /// # Errors
 ///
 /// Returns an error if query validation fails, draft access is forbidden, or
@@ -76,13 +224,24 @@ pub struct PublishRequest {
 pub async fn list(
     Extension(state): Extension<HttpContext>,
     actor: MaybeAuthenticated,
+    profile: ApiProfile,
+    headers: HeaderMap,
     Query(params): Query<ArticleListParams>,
-) -> HttpResult<Json<ArticleListResponse>> {
+) -> HttpResult<Response> {
     let include_drafts = params.include_drafts;
-    let limit = params.limit;
+    let limit = params.page_size.unwrap_or(params.limit);
     let cursor = params.cursor.clone();
+    let direction = params.direction;
+    let tag = params.tag.clone();
+    let sort = params.sort;
 
     let result = if let Some(query) = params.q.clone() {
+        if sort == ArticleSort::Popular {
+            return Err(AppError::validation("sort=popular is not supported with q")).into_http();
+        }
+        if params.page.is_some() {
+            return Err(AppError::validation("page is not supported with q")).into_http();
+        }
         state
             .services
             .article_queries
@@ -93,6 +252,8 @@ pub async fn list(
                     include_drafts,
                     limit,
                     cursor: cursor.clone(),
+                    direction,
+                    tag,
                 },
             )
             .await
@@ -107,13 +268,138 @@ pub async fn list(
                     include_drafts,
                     limit,
                     cursor,
+                    direction,
+                    tag,
+                    sort,
+                    page: params.page,
                 },
             )
             .await
             .into_http()?
     };
 
-    Ok(Json(ArticleListResponse::from(result)))
+    profile
+        .render_items("ArticleDto", &ArticleListResponse::from(result))?
+        .conditional(&headers, None)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/articles/search",
+    params(ArticleSearchParams),
+    responses(
+        (status = 200, description = "Matching articles with highlighted excerpts.", body = SearchResultPage),
+        (status = 400, description = "Invalid query parameters.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security([]),
+    tag = "Articles"
+)]
+/// Search articles, best match first, with the matching passages of each
+/// body highlighted.
+///
+/// # Errors
+///
+/// Returns an error if query validation fails, draft access is forbidden, or
+/// the article query service fails.
+pub async fn search(
+    Extension(state): Extension<HttpContext>,
+    actor: MaybeAuthenticated,
+    profile: ApiProfile,
+    Query(params): Query<ArticleSearchParams>,
+) -> HttpResult<Profiled> {
+    let result = state
+        .services
+        .article_queries
+        .search_results(
+            actor.0.as_ref(),
+            SearchArticlesQuery {
+                query: params.q,
+                include_drafts: params.include_drafts,
+                limit: params.limit,
+                cursor: params.cursor,
+                direction: PageDirection::Forward,
+                tag: params.tag,
+            },
+        )
+        .await
+        .into_http()?;
+
+    profile.render_items("ArticleDto", &SearchResultPage::from(result))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/articles/batch-get",
+    request_body = BatchGetArticlesRequest,
+    responses(
+        (status = 200, description = "Articles found and ids that were not.", body = ArticleBatchResponse),
+        (status = 400, description = "Too many ids requested.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security([]),
+    tag = "Articles"
+)]
+/// Resolve several articles by id in one request.
+///
+/// # Errors
+///
+/// Returns an error if too many ids are requested or the article query
+/// service fails.
+pub async fn batch_get(
+    Extension(state): Extension<HttpContext>,
+    actor: MaybeAuthenticated,
+    profile: ApiProfile,
+    Json(payload): Json<BatchGetArticlesRequest>,
+) -> HttpResult<Profiled> {
+    let result = state
+        .services
+        .article_queries
+        .batch_get_articles(actor.0.as_ref(), BatchGetArticlesQuery { ids: payload.ids })
+        .await
+        .into_http()?;
+
+    profile.render_items("ArticleDto", &ArticleBatchResponse::from(result))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/articles/bulk",
+    request_body = BulkArticlesRequest,
+    responses(
+        (status = 200, description = "What happened to each article, in request order.", body = BulkResult),
+        (status = 400, description = "Invalid operation, tags or too many ids.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Articles"
+)]
+/// Publish, unpublish, delete or tag several articles in one request.
+///
+/// Articles that are missing or that the caller may not change are reported
+/// in the results; the others are still changed.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails, the request is invalid, a
+/// publish state change lacks `articles:publish`, or the command service
+/// fails.
+pub async fn bulk(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Json(payload): Json<BulkArticlesRequest>,
+) -> HttpResult<Json<BulkResult>> {
+    let command = BulkArticleCommand::try_from(payload).into_http()?;
+    let result = state
+        .services
+        .article_commands
+        .bulk_articles(&user, command)
+        .await
+        .into_http()?;
+
+    Ok(Json(result))
 }
 
 #[utoipa::path(
@@ -124,6 +410,7 @@ pub async fn list(
     ),
     responses(
         (status = 200, description = "Article by slug.", body = ArticleDto),
+        (status = 304, description = "The article is unchanged since the client's copy."),
         (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
         (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
         (status = 404, description = "Article not found.", body = crate::presentation::http::error::ResponsePayload),
@@ -134,6 +421,9 @@ pub async fn list(
 )]
 /// Load a single article by slug.
 ///
+/// Supports conditional GET with `If-None-Match` and `If-Modified-Since`
+/// against the article's `ETag` and `updated_at`.
+///
 /// # Errors
 ///
 /// Returns an error if the slug is invalid, the article is missing, or the
@@ -141,15 +431,76 @@ pub async fn list(
 pub async fn get_by_slug(
     Extension(state): Extension<HttpContext>,
     actor: MaybeAuthenticated,
+    profile: ApiProfile,
+    headers: HeaderMap,
     Path(slug): Path<String>,
-) -> HttpResult<Json<ArticleDto>> {
-    state
+) -> HttpResult<Response> {
+    let article = state
         .services
         .article_queries
         .get_article_by_slug(actor.0.as_ref(), GetArticleBySlugQuery { slug })
         .await
+        .into_http()?;
+
+    profile
+        .render("ArticleDto", &article)?
+        .conditional(&headers, Some(article.updated_at))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RenderHtmlParams {
+    /// `full` (the default), `minimal` or `print`.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/articles/by-slug/{slug}/html",
+    params(
+        ("slug" = String, Path, description = "Article slug"),
+        RenderHtmlParams
+    ),
+    responses(
+        (status = 200, description = "The article as an `<article>` HTML fragment.", body = String, content_type = "text/html"),
+        (status = 400, description = "Unknown profile.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "Article not found.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security([]),
+    tag = "Articles"
+)]
+/// Render an article as HTML for embedding.
+///
+/// The `profile` picks the markup: `full` keeps images and relative links,
+/// `minimal` keeps only text and absolute links for strict embedders such
+/// as AMP pages, and `print` spells out link targets. Raw HTML in the
+/// article body is always escaped.
+///
+/// # Errors
+///
+/// Returns an error if the profile is unknown, the slug is invalid, the
+/// article is missing, or the caller cannot view an unpublished article.
+pub async fn render_html(
+    Extension(state): Extension<HttpContext>,
+    actor: MaybeAuthenticated,
+    Path(slug): Path<String>,
+    Query(params): Query<RenderHtmlParams>,
+) -> HttpResult<Html<String>> {
+    let profile = params
+        .profile
+        .as_deref()
+        .map(RenderProfile::parse)
+        .transpose()
+        .into_http()?
+        .unwrap_or_default();
+    state
+        .services
+        .article_queries
+        .render_article_html(actor.0.as_ref(), RenderArticleQuery { slug, profile })
+        .await
         .into_http()
-        .map(Json)
+        .map(Html)
 }
 
 #[utoipa::path(
@@ -175,19 +526,66 @@ pub async fn get_by_slug(
 pub async fn create(
     Extension(state): Extension<HttpContext>,
     Authenticated(user): Authenticated,
+    profile: ApiProfile,
     Json(payload): Json<CreateArticleRequest>,
-) -> HttpResult<Json<ArticleDto>> {
+) -> HttpResult<Profiled> {
     let command = CreateArticleCommand {
         title: payload.title,
         body: payload.body,
         publish: payload.publish,
+        tags: payload.tags,
+        custom_fields: payload.custom_fields,
+        visibility: payload.visibility,
     };
 
-    state
+    let article = state
         .services
         .article_commands
         .create_article(&user, command)
         .await
+        .into_http()?;
+
+    profile.render("ArticleDto", &article)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/articles/import-document",
+    request_body(
+        content = Vec<u8>,
+        content_type = "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        description = "A .docx file, as saved by Word or exported from Google Docs."
+    ),
+    responses(
+        (status = 200, description = "Draft created from the document.", body = ArticleImportDto),
+        (status = 400, description = "Not a .docx file, or the document has no title.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Articles"
+)]
+/// Create a draft article from an uploaded Word document.
+///
+/// The title comes from the document properties or its first Title or
+/// Heading 1 paragraph; the rest becomes the Markdown body. Embedded images
+/// are dropped and counted in `skipped_images`.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the upload is
+/// not a convertible document, or the article cannot be created.
+pub async fn import_document(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    document: Bytes,
+) -> HttpResult<Json<ArticleImportDto>> {
+    state
+        .services
+        .document_import
+        .import(&user, &document)
+        .await
         .into_http()
         .map(Json)
 }
@@ -219,23 +617,28 @@ pub async fn create(
 pub async fn update(
     Extension(state): Extension<HttpContext>,
     Authenticated(user): Authenticated,
+    profile: ApiProfile,
     Path(id): Path<i64>,
     Json(payload): Json<UpdateArticleRequest>,
-) -> HttpResult<Json<ArticleDto>> {
+) -> HttpResult<Profiled> {
     let command = UpdateArticleCommand {
         id,
         title: payload.title,
         body: payload.body,
         publish: payload.publish,
+        tags: payload.tags,
+        custom_fields: payload.custom_fields,
+        visibility: payload.visibility,
     };
 
-    state
+    let article = state
         .services
         .article_commands
         .update_article(&user, command)
         .await
-        .into_http()
-        .map(Json)
+        .into_http()?;
+
+    profile.render("ArticleDto", &article)
 }
 
 #[utoipa::path(
@@ -304,23 +707,224 @@ pub async fn delete(
 pub async fn set_publish_state(
     Extension(state): Extension<HttpContext>,
     Authenticated(user): Authenticated,
+    profile: ApiProfile,
     Path(id): Path<i64>,
     Json(payload): Json<PublishRequest>,
-) -> HttpResult<Json<ArticleDto>> {
+) -> HttpResult<Profiled> {
     let command = SetPublishStateCommand {
         id,
         publish: payload.publish,
     };
 
-    state
+    let article = state
         .services
         .article_commands
         .set_publish_state(&user, command)
         .await
+        .into_http()?;
+
+    profile.render("ArticleDto", &article)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/articles/{id}/syndication",
+    params(
+        ("id" = i64, Path, description = "Article identifier")
+    ),
+    responses(
+        (status = 200, description = "Whether the article is announced when published.", body = ArticleSyndicationDto),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "Article not found.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Articles"
+)]
+/// Show whether an article is announced on the syndication targets.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the article is
+/// missing, or the syndication service fails.
+pub async fn get_syndication(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Path(id): Path<i64>,
+) -> HttpResult<Json<ArticleSyndicationDto>> {
+    state
+        .services
+        .syndication
+        .settings(&user, id)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/articles/{id}/syndication",
+    params(
+        ("id" = i64, Path, description = "Article identifier")
+    ),
+    request_body = SyndicationRequest,
+    responses(
+        (status = 200, description = "Syndication opt-out updated.", body = ArticleSyndicationDto),
+        (status = 400, description = "Invalid input.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "Article not found.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Articles"
+)]
+/// Opt an article in to or out of announcements on the syndication
+/// targets.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the article is
+/// missing, or the syndication service fails.
+pub async fn set_syndication(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Path(id): Path<i64>,
+    Json(payload): Json<SyndicationRequest>,
+) -> HttpResult<Json<ArticleSyndicationDto>> {
+    state
+        .services
+        .syndication
+        .set_enabled(&user, id, payload.enabled)
+        .await
         .into_http()
         .map(Json)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/articles/{id}/contributors",
+    params(
+        ("id" = i64, Path, description = "Article identifier")
+    ),
+    responses(
+        (status = 200, description = "Users who may edit the article besides its author.", body = [ArticleContributorDto]),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "Article not found.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Articles"
+)]
+/// List the users who may edit an article besides its author.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails, the article is missing or not
+/// readable by the caller, or the query service fails.
+pub async fn list_contributors(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Path(id): Path<i64>,
+) -> HttpResult<Json<Vec<ArticleContributorDto>>> {
+    state
+        .services
+        .article_queries
+        .list_contributors(&user, ListContributorsQuery { article_id: id })
+        .await
+        .into_http()
+        .map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/articles/{id}/contributors",
+    params(
+        ("id" = i64, Path, description = "Article identifier")
+    ),
+    request_body = AddContributorRequest,
+    responses(
+        (status = 200, description = "The user may now edit the article.", body = ArticleContributorDto),
+        (status = 400, description = "Invalid input.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "Article or user not found.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Articles"
+)]
+/// Let another user edit an article. Only its author and holders of
+/// `articles:update:any` may add contributors.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the article or
+/// user is missing, the user is the author, or the command service fails.
+pub async fn add_contributor(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Path(id): Path<i64>,
+    Json(payload): Json<AddContributorRequest>,
+) -> HttpResult<Json<ArticleContributorDto>> {
+    let command = AddContributorCommand {
+        article_id: id,
+        user_id: payload.user_id,
+    };
+    state
+        .services
+        .article_commands
+        .add_contributor(&user, command)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/articles/{id}/contributors/{user_id}",
+    params(
+        ("id" = i64, Path, description = "Article identifier"),
+        ("user_id" = i64, Path, description = "Contributor to remove")
+    ),
+    responses(
+        (status = 200, description = "The user may no longer edit the article.", body = StatusResponse),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "Article or contributor not found.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Articles"
+)]
+/// Stop a contributor from editing an article.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the article is
+/// missing, the user does not contribute to it, or the command service fails.
+pub async fn remove_contributor(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Path((id, user_id)): Path<(i64, i64)>,
+) -> HttpResult<Json<StatusResponse>> {
+    let command = RemoveContributorCommand {
+        article_id: id,
+        user_id,
+    };
+    state
+        .services
+        .article_commands
+        .remove_contributor(&user, command)
+        .await
+        .into_http()?;
+
+    Ok(Json(StatusResponse {
+        status: "removed".into(),
+    }))
+}
+
 #[utoipa::path(
     get,
     path = "/api/v1/articles/{id}/revisions",
@@ -356,3 +960,237 @@ pub async fn list_revisions(
         .into_http()
         .map(Json)
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/articles/{id}/revisions/{from}/diff/{to}",
+    params(
+        ("id" = i64, Path, description = "Article identifier"),
+        ("from" = i32, Path, description = "Older revision"),
+        ("to" = i32, Path, description = "Newer revision"),
+        RevisionDiffParams
+    ),
+    responses(
+        (status = 200, description = "Changes between the two revisions.", body = ArticleRevisionDiffDto),
+        (status = 400, description = "Invalid input.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "Article or revision not found.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Articles"
+)]
+/// Diff the title and body of two revisions of an article.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the article
+/// or either revision is missing.
+pub async fn diff_revisions(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Path((id, from, to)): Path<(i64, i32, i32)>,
+    Query(params): Query<RevisionDiffParams>,
+) -> HttpResult<Json<ArticleRevisionDiffDto>> {
+    let query = DiffArticleRevisionsQuery {
+        article_id: id,
+        from_version: from,
+        to_version: to,
+        granularity: params.granularity,
+    };
+    state
+        .services
+        .article_queries
+        .diff_revisions(&user, query)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/articles/{id}/revisions/{version}/revert",
+    params(
+        ("id" = i64, Path, description = "Article identifier"),
+        ("version" = i32, Path, description = "Revision to restore")
+    ),
+    request_body = RevertArticleRequest,
+    responses(
+        (status = 200, description = "Article content restored from the revision.", body = ArticleDto),
+        (status = 400, description = "Invalid input.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "Article or revision not found.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 409, description = "Article changed since it was read.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Articles"
+)]
+/// Restore an article's title and body from an earlier revision.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the article or
+/// revision is missing, or the article changed since `expected_updated_at`.
+pub async fn revert_to_revision(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    profile: ApiProfile,
+    Path((id, version)): Path<(i64, i32)>,
+    Json(payload): Json<RevertArticleRequest>,
+) -> HttpResult<Profiled> {
+    let command = RevertArticleToRevisionCommand {
+        id,
+        version,
+        expected_updated_at: payload.expected_updated_at,
+    };
+
+    let article = state
+        .services
+        .article_commands
+        .revert_article_to_revision(&user, command)
+        .await
+        .into_http()?;
+
+    profile.render("ArticleDto", &article)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/articles/custom-fields",
+    responses(
+        (status = 200, description = "Custom fields articles may carry, sorted by name.", body = [CustomFieldDefinitionDto]),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security([]),
+    tag = "Articles"
+)]
+/// List the custom fields defined for articles.
+///
+/// # Errors
+///
+/// Returns an error if the definitions cannot be read.
+pub async fn list_custom_fields(
+    Extension(state): Extension<HttpContext>,
+) -> HttpResult<Json<Vec<CustomFieldDefinitionDto>>> {
+    state
+        .services
+        .article_queries
+        .list_custom_fields()
+        .await
+        .into_http()
+        .map(Json)
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ArticleExportParams {
+    /// `pdf` or `epub`.
+    #[serde(default)]
+    pub format: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/articles/{id}/export",
+    params(
+        ("id" = i64, Path, description = "Article identifier"),
+        ArticleExportParams
+    ),
+    responses(
+        (status = 200, description = "The rendered document, as an attachment.", content(
+            (Vec<u8> = "application/pdf"),
+            (Vec<u8> = "application/epub+zip")
+        )),
+        (status = 202, description = "The article is large; poll the job in `Location` for a download link.", body = ArticleExportJobDto),
+        (status = 400, description = "Unknown format.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "Article not found, or exports are disabled.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security([]),
+    tag = "Articles"
+)]
+/// Export an article as PDF or EPUB for offline reading.
+///
+/// Small articles are rendered during the request. Large ones are rendered
+/// in the background: the response is `202 Accepted` with the export job.
+///
+/// # Errors
+///
+/// Returns an error if the format is unknown, the article is missing or the
+/// caller cannot view it, or rendering fails.
+pub async fn export(
+    Extension(state): Extension<HttpContext>,
+    actor: MaybeAuthenticated,
+    Path(id): Path<i64>,
+    Query(params): Query<ArticleExportParams>,
+) -> HttpResult<Response> {
+    let export = state
+        .services
+        .article_exports
+        .export(actor.0.as_ref(), id, &params.format)
+        .await
+        .into_http()?;
+
+    Ok(match export {
+        ArticleExport::Ready(file) => file_response(file),
+        ArticleExport::Queued(job) => (
+            StatusCode::ACCEPTED,
+            [(LOCATION, format!("/api/v1/articles/exports/{}", job.id))],
+            Json(job),
+        )
+            .into_response(),
+    })
+}
+
+/// Report the progress of a background article export.
+///
+/// # Errors
+///
+/// Returns an error if the job is unknown or was requested by someone else.
+pub async fn export_job(
+    Extension(state): Extension<HttpContext>,
+    actor: MaybeAuthenticated,
+    Path(id): Path<String>,
+) -> HttpResult<Json<ArticleExportJobDto>> {
+    state
+        .services
+        .article_exports
+        .job(actor.0.as_ref(), &id)
+        .into_http()
+        .map(Json)
+}
+
+/// Download the file produced by a completed export job.
+///
+/// # Errors
+///
+/// Returns an error if the job is unknown, was requested by someone else, or
+/// has not completed.
+pub async fn download_export(
+    Extension(state): Extension<HttpContext>,
+    actor: MaybeAuthenticated,
+    Path(id): Path<String>,
+) -> HttpResult<Response> {
+    state
+        .services
+        .article_exports
+        .download(actor.0.as_ref(), &id)
+        .into_http()
+        .map(file_response)
+}
+
+pub(crate) fn file_response(file: ExportedFile) -> Response {
+    (
+        [
+            (CONTENT_TYPE, file.content_type.to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file.file_name),
+            ),
+        ],
+        file.bytes,
+    )
+        .into_response()
+}
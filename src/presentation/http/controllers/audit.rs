@@ -1,28 +1,83 @@
 // src/presentation/http/controllers/audit.rs
+use crate::application::ActivityCategory;
 use crate::application::AuditLogDto;
 use crate::application::CursorPage;
+use crate::application::error::AppResult;
 use crate::application::queries::audit::{
+    activity::ListMyActivityQuery,
     list::{ListAuditLogsByResourceQuery, ListAuditLogsByUserQuery, ListAuditLogsQuery},
-    service::AuditQueryService,
 };
+use crate::domain::Timezone;
 use crate::presentation::http::error::{HttpResult, IntoHttpResult};
 use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::openapi::ActivityFeedResponse;
 use crate::presentation::http::state::HttpContext;
 use axum::{
     Extension, Json,
     extract::{Path, Query},
 };
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use utoipa::IntoParams;
 
 #[derive(Debug, serde::Deserialize)]
 pub struct ListAuditParams {
-    #[serde(default = "default_limit")]
+    /// Page size. 0 or omitted uses this endpoint's default and larger
+    /// values are clamped to its cap; see `pagination.endpoints.audit_logs` in
+    /// `GET /api/v1/discovery/limits`.
+    #[serde(default)]
     pub limit: u32,
     #[serde(default)]
     pub cursor: Option<String>,
+    /// IANA timezone used to fill `created_at_local` on each entry.
+    #[serde(default)]
+    pub tz: Option<String>,
+}
+
+impl ListAuditParams {
+    /// Resolve the `?tz=` display hint.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `tz` is not a known IANA timezone.
+    pub fn display_tz(&self) -> AppResult<Option<Tz>> {
+        display_tz(self.tz.as_deref())
+    }
+}
+
+fn display_tz(tz: Option<&str>) -> AppResult<Option<Tz>> {
+    Ok(tz.map(Timezone::new).transpose()?.map(|tz| tz.tz()))
+}
+
+#[derive(Debug, serde::Deserialize, IntoParams)]
+pub struct ActivityParams {
+    /// Only list entries of this category.
+    #[serde(default)]
+    pub category: Option<ActivityCategory>,
+    /// Only list entries recorded at or after this RFC 3339 instant.
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    /// Page size. 0 or omitted uses this endpoint's default and larger
+    /// values are clamped to its cap; see `pagination.endpoints.audit_logs` in
+    /// `GET /api/v1/discovery/limits`.
+    #[serde(default)]
+    pub limit: u32,
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// IANA timezone used to fill `created_at_local` on each entry.
+    #[serde(default)]
+    pub tz: Option<String>,
 }
 
-const fn default_limit() -> u32 {
-    20
+fn localize(mut page: CursorPage<AuditLogDto>, tz: Option<Tz>) -> CursorPage<AuditLogDto> {
+    if tz.is_some() {
+        page.items = page
+            .items
+            .into_iter()
+            .map(|dto| dto.localized(tz))
+            .collect();
+    }
+    page
 }
 
 /// List audit logs across all resources.
@@ -36,7 +91,8 @@ pub async fn list_audit_logs(
     Authenticated(actor): Authenticated,
     Query(params): Query<ListAuditParams>,
 ) -> HttpResult<Json<CursorPage<AuditLogDto>>> {
-    let service = AuditQueryService::new(state.services.audit_log_repo());
+    let tz = params.display_tz().into_http()?;
+    let service = state.services.audit_queries();
     let res = service
         .list_audit_logs(
             &actor,
@@ -47,7 +103,7 @@ pub async fn list_audit_logs(
         )
         .await
         .into_http()?;
-    Ok(Json(res))
+    Ok(Json(localize(res, tz)))
 }
 
 /// List audit logs associated with a user id.
@@ -62,7 +118,8 @@ pub async fn list_audit_logs_by_user(
     Path(user_id): Path<i64>,
     Query(params): Query<ListAuditParams>,
 ) -> HttpResult<Json<CursorPage<AuditLogDto>>> {
-    let service = AuditQueryService::new(state.services.audit_log_repo());
+    let tz = params.display_tz().into_http()?;
+    let service = state.services.audit_queries();
     let res = service
         .list_by_user(
             &actor,
@@ -74,7 +131,7 @@ pub async fn list_audit_logs_by_user(
         )
         .await
         .into_http()?;
-    Ok(Json(res))
+    Ok(Json(localize(res, tz)))
 }
 
 /// List audit logs associated with a resource.
@@ -89,7 +146,8 @@ pub async fn list_audit_logs_by_resource(
     Path((resource_type, resource_id)): Path<(String, i64)>,
     Query(params): Query<ListAuditParams>,
 ) -> HttpResult<Json<CursorPage<AuditLogDto>>> {
-    let service = AuditQueryService::new(state.services.audit_log_repo());
+    let tz = params.display_tz().into_http()?;
+    let service = state.services.audit_queries();
     let res = service
         .list_by_resource(
             &actor,
@@ -102,5 +160,54 @@ pub async fn list_audit_logs_by_resource(
         )
         .await
         .into_http()?;
-    Ok(Json(res))
+    Ok(Json(localize(res, tz)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/me/activity",
+    params(ActivityParams),
+    responses(
+        (status = 200, description = "Recent activity on the current user's account, newest first.", body = ActivityFeedResponse),
+        (status = 400, description = "Invalid input.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Auth"
+)]
+/// List the current user's recent account activity, built from the audit
+/// log entries they made or that concern their account.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails, the cursor or timezone is
+/// invalid, or the query service fails.
+pub async fn list_my_activity(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Query(params): Query<ActivityParams>,
+) -> HttpResult<Json<ActivityFeedResponse>> {
+    let tz = display_tz(params.tz.as_deref()).into_http()?;
+    let service = state.services.audit_queries();
+    let mut page = service
+        .list_my_activity(
+            &actor,
+            ListMyActivityQuery {
+                category: params.category,
+                since: params.since,
+                limit: params.limit,
+                cursor: params.cursor,
+            },
+        )
+        .await
+        .into_http()?;
+    if tz.is_some() {
+        page.items = page
+            .items
+            .into_iter()
+            .map(|entry| entry.localized(tz))
+            .collect();
+    }
+    Ok(Json(page.into()))
 }
@@ -1,15 +1,25 @@
 // src/presentation/http/controllers/auth.rs
 use crate::application::{
-    AuthTokenDto, UserDto, UserProfileDto,
-    commands::users::{LoginUserCommand, RefreshTokenCommand, RegisterUserCommand},
+    AuthTokenDto, PermissionsDto, UserDto, UserProfileDto,
+    commands::users::{
+        LoginUserCommand, RefreshTokenCommand, RegisterUserCommand, UpdatePreferencesCommand,
+    },
+    random_id,
 };
 use crate::presentation::http::controllers::user_requests::{
-    LoginRequest, LoginResponse, RefreshTokenRequest, RegisterRequest,
+    CookieLoginResponse, CsrfTokenResponse, LoginMode, LoginParams, LoginRequest, LoginResponse,
+    RefreshTokenRequest, RegisterRequest, UpdatePreferencesRequest,
 };
 use crate::presentation::http::error::{HttpResult, IntoHttpResult};
-use crate::presentation::http::extractors::{Authenticated, MaybeAuthenticated};
+use crate::presentation::http::extractors::{Authenticated, MaybeAuthenticated, RequestClient};
+use crate::presentation::http::session_cookie;
 use crate::presentation::http::state::HttpContext;
-use axum::{Extension, Json};
+use axum::{
+    Extension, Json,
+    extract::Query,
+    http::header::SET_COOKIE,
+    response::{AppendHeaders, IntoResponse, Response},
+};
 use serde_json::Value as JsonValue;
 
 #[utoipa::path(
@@ -54,10 +64,12 @@ pub async fn register(
 #[utoipa::path(
     post,
     path = "/api/v1/auth/login",
+    params(LoginParams),
     request_body = LoginRequest,
     responses(
-        (status = 200, description = "Login successful.", body = LoginResponse),
+        (status = 200, description = "Login successful. With `mode=cookie` the body is a `CookieLoginResponse` and the session is set in cookies.", body = LoginResponse),
         (status = 401, description = "Invalid credentials.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 429, description = "Too many failed logins for this account or client; retry after the `Retry-After` seconds.", body = crate::presentation::http::error::ResponsePayload),
         (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
     ),
     security([]),
@@ -65,13 +77,19 @@ pub async fn register(
 )]
 /// Log a user in and issue tokens.
 ///
+/// With `mode=cookie` the access token is set in an `HttpOnly` session
+/// cookie instead of being returned, and no refresh token is issued.
+///
 /// # Errors
 ///
-/// Returns an error if the credentials are invalid or token issuance fails.
+/// Returns an error if the credentials are invalid, the account or client is
+/// locked out after failed logins, or token issuance fails.
 pub async fn login(
     Extension(state): Extension<HttpContext>,
+    RequestClient(client): RequestClient,
+    Query(params): Query<LoginParams>,
     Json(payload): Json<LoginRequest>,
-) -> HttpResult<Json<LoginResponse>> {
+) -> HttpResult<Response> {
     let command = LoginUserCommand {
         username: payload.username,
         password: payload.password,
@@ -80,14 +98,35 @@ pub async fn login(
     let result = state
         .services
         .user_commands
-        .login(command)
+        .login_from_client(command, &client)
         .await
         .into_http()?;
 
+    if params.mode == LoginMode::Cookie {
+        let csrf_token = random_id::v4_string().into_http()?;
+        let [session, csrf] = session_cookie::session_cookies(
+            &result.token.token,
+            &csrf_token,
+            result.token.expires_in,
+        )
+        .into_http()?;
+        let body = CookieLoginResponse {
+            user: result.user,
+            expires_in: result.token.expires_in,
+            csrf_token,
+        };
+        return Ok((
+            AppendHeaders([(SET_COOKIE, session), (SET_COOKIE, csrf)]),
+            Json(body),
+        )
+            .into_response());
+    }
+
     Ok(Json(LoginResponse {
         token: result.token,
         user: result.user,
-    }))
+    })
+    .into_response())
 }
 
 #[utoipa::path(
@@ -111,6 +150,7 @@ pub async fn login(
 /// refresh command fails.
 pub async fn refresh_token(
     Extension(state): Extension<HttpContext>,
+    RequestClient(client): RequestClient,
     Json(payload): Json<RefreshTokenRequest>,
 ) -> HttpResult<Json<AuthTokenDto>> {
     let command = RefreshTokenCommand {
@@ -120,7 +160,7 @@ pub async fn refresh_token(
     state
         .services
         .user_commands
-        .refresh_token(command)
+        .refresh_token_from_client(command, &client)
         .await
         .into_http()
         .map(Json)
@@ -157,6 +197,67 @@ pub async fn profile(
         .map(Json)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/me/permissions",
+    responses(
+        (status = 200, description = "Resource/action pairs the current token allows.", body = PermissionsDto),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Auth"
+)]
+/// List what the current token allows, so UIs can hide or disable controls.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails.
+pub async fn permissions(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+) -> HttpResult<Json<PermissionsDto>> {
+    Ok(Json(state.services.user_queries.permissions(&user)))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/auth/me",
+    request_body = UpdatePreferencesRequest,
+    responses(
+        (status = 200, description = "Updated profile.", body = UserProfileDto),
+        (status = 400, description = "Unknown timezone.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "User not found.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Auth"
+)]
+/// Update the current user's display preferences.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails, the timezone is not a known IANA
+/// name, or the update cannot be persisted.
+pub async fn update_preferences(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Json(payload): Json<UpdatePreferencesRequest>,
+) -> HttpResult<Json<UserProfileDto>> {
+    let command = UpdatePreferencesCommand {
+        timezone: payload.timezone,
+    };
+
+    state
+        .services
+        .user_commands
+        .update_preferences(&user, command)
+        .await
+        .into_http()
+        .map(Json)
+}
+
 // Session endpoints are implemented in `auth_sessions.rs` (OpenAPI paths defined there)
 
 // JWKS-like public keys endpoint. Returns the public key material used to verify tokens.
@@ -179,7 +280,7 @@ pub async fn keys(Extension(state): Extension<HttpContext>) -> HttpResult<Json<J
     security(("bearerAuth" = [])),
     tag = "Auth"
 )]
-/// Revoke the current session-backed token.
+/// Revoke the current session-backed token and clear any session cookies.
 ///
 /// # Errors
 ///
@@ -188,10 +289,41 @@ pub async fn keys(Extension(state): Extension<HttpContext>) -> HttpResult<Json<J
 pub async fn logout(
     Extension(state): Extension<HttpContext>,
     Authenticated(user): Authenticated,
-) -> HttpResult<Json<crate::presentation::http::openapi::StatusResponse>> {
+) -> HttpResult<impl IntoResponse> {
     state.services.auth.logout(&user).await.into_http()?;
 
-    Ok(Json(crate::presentation::http::openapi::StatusResponse {
-        status: "logged_out".into(),
-    }))
+    let [session, csrf] = session_cookie::cleared_cookies();
+    Ok((
+        AppendHeaders([(SET_COOKIE, session), (SET_COOKIE, csrf)]),
+        Json(crate::presentation::http::openapi::StatusResponse {
+            status: "logged_out".into(),
+        }),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/csrf",
+    responses(
+        (status = 200, description = "CSRF token rotated; also set in the `mokkan_csrf` cookie.", body = CsrfTokenResponse),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = []), ("cookieAuth" = [])),
+    tag = "Auth"
+)]
+/// Issue a fresh CSRF token for the current cookie session.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails or a token cannot be generated.
+pub async fn rotate_csrf_token(
+    Authenticated(_user): Authenticated,
+) -> HttpResult<impl IntoResponse> {
+    let csrf_token = random_id::v4_string().into_http()?;
+    let cookie = session_cookie::csrf_cookie(&csrf_token).into_http()?;
+    Ok((
+        AppendHeaders([(SET_COOKIE, cookie)]),
+        Json(CsrfTokenResponse { csrf_token }),
+    ))
 }
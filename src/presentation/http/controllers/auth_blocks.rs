@@ -0,0 +1,106 @@
+// src/presentation/http/controllers/auth_blocks.rs
+use crate::application::UserBlockDto;
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::openapi::StatusResponse;
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, Json, extract::Path};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/me/blocks",
+    responses(
+        (status = 200, description = "Users the current user has blocked, most recent first.", body = [UserBlockDto]),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Auth"
+)]
+/// List the users the current user has blocked.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails or the list cannot be loaded.
+pub async fn list_blocks(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+) -> HttpResult<Json<Vec<UserBlockDto>>> {
+    state
+        .services
+        .blocks
+        .list(&user)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/auth/me/blocks/{user_id}",
+    params(("user_id" = i64, Path, description = "User to block")),
+    responses(
+        (status = 200, description = "User blocked.", body = UserBlockDto),
+        (status = 400, description = "Invalid input.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "User not found.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Auth"
+)]
+/// Block a user. Blocking someone already blocked changes nothing.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails, the user does not exist or is
+/// the caller, or the block cannot be stored.
+pub async fn block_user(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Path(user_id): Path<i64>,
+) -> HttpResult<Json<UserBlockDto>> {
+    state
+        .services
+        .blocks
+        .block(&user, user_id)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/auth/me/blocks/{user_id}",
+    params(("user_id" = i64, Path, description = "User to unblock")),
+    responses(
+        (status = 200, description = "Block removed.", body = StatusResponse),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "The user was not blocked.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Auth"
+)]
+/// Unblock a user.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails, the user was not blocked,
+/// or the block cannot be removed.
+pub async fn unblock_user(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Path(user_id): Path<i64>,
+) -> HttpResult<Json<StatusResponse>> {
+    state
+        .services
+        .blocks
+        .unblock(&user, user_id)
+        .await
+        .into_http()?;
+
+    Ok(Json(StatusResponse {
+        status: "unblocked".into(),
+    }))
+}
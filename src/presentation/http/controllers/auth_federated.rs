@@ -0,0 +1,153 @@
+// src/presentation/http/controllers/auth_federated.rs
+//! Login through upstream OIDC providers ("Sign in with ..."). Unlike
+//! `auth_oidc`, where this service is the provider, here it is the client.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    response::{IntoResponse, Redirect, Response},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::application::error::AppError;
+use crate::application::services::FederatedCallback;
+use crate::presentation::http::controllers::user_requests::LoginResponse;
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::{MaybeAuthenticated, RequestClient};
+use crate::presentation::http::state::HttpContext;
+
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+pub struct FederatedStartParams {
+    /// `false` returns the authorization URL as JSON instead of redirecting,
+    /// for clients that must send a bearer token to link an account.
+    pub redirect: Option<bool>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FederatedStartResponse {
+    pub authorization_url: String,
+    pub state: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+pub struct FederatedCallbackParams {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    /// Set by the provider when the user denied or the request failed.
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oidc/{provider}/start",
+    params(
+        ("provider" = String, Path, description = "Configured upstream provider name"),
+        FederatedStartParams
+    ),
+    responses(
+        (status = 303, description = "Redirect to the provider's login page."),
+        (status = 200, description = "Authorization URL (with `redirect=false`).", body = FederatedStartResponse),
+        (status = 404, description = "Unknown provider.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Provider unreachable or unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security([]),
+    tag = "Auth"
+)]
+/// Start logging in at an upstream OIDC provider.
+///
+/// With a bearer token the upstream account is linked to the caller instead.
+///
+/// # Errors
+///
+/// Returns an error if the provider is unknown or its discovery document
+/// cannot be fetched.
+pub async fn start(
+    Extension(state): Extension<HttpContext>,
+    Path(provider): Path<String>,
+    actor: MaybeAuthenticated,
+    Query(params): Query<FederatedStartParams>,
+) -> HttpResult<Response> {
+    let started = state
+        .services
+        .federated_login
+        .start(&provider, actor.0.as_ref())
+        .await
+        .into_http()?;
+
+    if params.redirect == Some(false) {
+        return Ok(Json(FederatedStartResponse {
+            authorization_url: started.authorization_url,
+            state: started.state,
+        })
+        .into_response());
+    }
+    Ok(Redirect::to(&started.authorization_url).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oidc/{provider}/callback",
+    params(
+        ("provider" = String, Path, description = "Configured upstream provider name"),
+        FederatedCallbackParams
+    ),
+    responses(
+        (status = 200, description = "Login successful.", body = LoginResponse),
+        (status = 400, description = "Missing code or state.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Login denied, state unknown or expired, or id_token invalid.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Account is disabled.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "Unknown provider.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 409, description = "Upstream account is linked to another user.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security([]),
+    tag = "Auth"
+)]
+/// Complete an upstream OIDC login and issue tokens.
+///
+/// # Errors
+///
+/// Returns an error if the provider reported a failure, the state is not
+/// one this service issued, or the code exchange or `id_token` validation
+/// fails.
+pub async fn callback(
+    Extension(state): Extension<HttpContext>,
+    Path(provider): Path<String>,
+    RequestClient(client): RequestClient,
+    Query(params): Query<FederatedCallbackParams>,
+) -> HttpResult<Json<LoginResponse>> {
+    if let Some(error) = params.error {
+        let detail = params.error_description.map_or_else(
+            || error.clone(),
+            |description| format!("{error}: {description}"),
+        );
+        return Err(AppError::unauthorized(format!(
+            "upstream login failed: {detail}"
+        )))
+        .into_http();
+    }
+    let (Some(code), Some(login_state)) = (params.code, params.state) else {
+        return Err(AppError::validation("code and state are required")).into_http();
+    };
+
+    let result = state
+        .services
+        .federated_login
+        .callback(
+            FederatedCallback {
+                provider,
+                state: login_state,
+                code,
+            },
+            &client,
+        )
+        .await
+        .into_http()?;
+
+    Ok(Json(LoginResponse {
+        token: result.token,
+        user: result.user,
+    }))
+}
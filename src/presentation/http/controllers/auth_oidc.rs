@@ -1,5 +1,5 @@
 // src/presentation/http/controllers/auth_oidc.rs
-//! OIDC/OAuth2-style endpoints (authorization code + PKCE), token introspection and revocation.
+//! OIDC/OAuth2-style endpoints (authorization code + PKCE), userinfo, token introspection and revocation.
 //! This file parses either JSON or x-www-form-urlencoded bodies for /token.
 
 use axum::{
@@ -16,7 +16,7 @@ use crate::application::services::{
 };
 use crate::application::{AuthTokenDto, error::AppError};
 use crate::presentation::http::error::{HttpResult, IntoHttpResult};
-use crate::presentation::http::extractors::MaybeAuthenticated;
+use crate::presentation::http::extractors::{Authenticated, MaybeAuthenticated};
 use crate::presentation::http::state::HttpContext;
 
 // ---------- Requests / Responses ----------
@@ -35,6 +35,26 @@ pub struct TokenExchangeRequest {
     pub client_id: Option<String>,
 }
 
+/// Access token, plus an `id_token` when the code was issued for the
+/// `openid` scope.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct TokenResponse {
+    #[serde(flatten)]
+    pub token: AuthTokenDto,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+}
+
+/// Standard claims about the bearer of an access token.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct UserInfoResponse {
+    pub sub: String,
+    pub preferred_username: String,
+    /// The user's preferred IANA timezone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zoneinfo: Option<String>,
+}
+
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct IntrospectResponse {
     pub active: bool,
@@ -75,6 +95,8 @@ pub struct AuthorizeRequest {
     pub state: Option<String>,
     pub code_challenge: Option<String>,
     pub code_challenge_method: Option<String>,
+    /// Echoed in the `id_token` when the `openid` scope is granted.
+    pub nonce: Option<String>,
     /// For programmatic test flows you can pass `consent=approve` (otherwise a consent prompt JSON is returned)
     pub consent: Option<String>,
 }
@@ -86,14 +108,17 @@ pub struct AuthorizeRequest {
     path = "/api/v1/auth/token",
     request_body = TokenExchangeRequest,
     responses(
-        (status = 200, description = "Tokens issued", body = crate::application::AuthTokenDto),
+        (status = 200, description = "Tokens issued", body = TokenResponse),
         (status = 400, description = "Bad request", body = crate::presentation::http::error::ResponsePayload),
+        (status = 413, description = "Request body over 16 KiB", body = crate::presentation::http::error::ResponsePayload),
     ),
     security([]),
     tag = "Auth"
 )]
 /// Exchange an authorization code for tokens.
 ///
+/// Codes issued for the `openid` scope also yield an `id_token`.
+///
 /// # Errors
 ///
 /// Returns an error if the request body is malformed, the grant type is not
@@ -101,7 +126,7 @@ pub struct AuthorizeRequest {
 pub async fn token(
     Extension(state): Extension<HttpContext>,
     body_bytes: axum::body::Bytes,
-) -> HttpResult<Json<AuthTokenDto>> {
+) -> HttpResult<Json<TokenResponse>> {
     // Received body as Bytes extractor. Try to parse either JSON or x-www-form-urlencoded
     let whole = body_bytes;
 
@@ -128,18 +153,57 @@ pub async fn token(
         crate::presentation::http::error::Error::from_error(AppError::validation("code required"))
     })?;
 
-    let token = state
+    let issuer = crate::config::Settings::oidc_issuer_from_env();
+    let tokens = state
         .services
         .auth
         .exchange_authorization_code(ExchangeAuthorizationCodeRequest {
             code,
             code_verifier: payload.code_verifier,
             redirect_uri: payload.redirect_uri,
+            issuer: Some(issuer.trim_end_matches('/').to_string()),
         })
         .await
         .into_http()?;
 
-    Ok(Json(token))
+    Ok(Json(TokenResponse {
+        token: tokens.token,
+        id_token: tokens.id_token,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/userinfo",
+    responses(
+        (status = 200, description = "Standard claims for the bearer token", body = UserInfoResponse),
+        (status = 401, description = "Unauthorized", body = crate::presentation::http::error::ResponsePayload),
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Auth"
+)]
+/// Return `OpenID Connect` standard claims for the bearer token's user.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails or the user record cannot be
+/// loaded.
+pub async fn userinfo(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+) -> HttpResult<Json<UserInfoResponse>> {
+    let profile = state
+        .services
+        .user_queries
+        .get_profile(&user)
+        .await
+        .into_http()?;
+
+    Ok(Json(UserInfoResponse {
+        sub: profile.user.id.to_string(),
+        preferred_username: profile.user.username,
+        zoneinfo: profile.user.timezone,
+    }))
 }
 
 #[utoipa::path(
@@ -148,12 +212,17 @@ pub async fn token(
     request_body = TokenRequest,
     responses(
         (status = 200, description = "Token introspection", body = IntrospectResponse),
+        (status = 401, description = "Unauthorized", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Caller lacks tokens:introspect", body = crate::presentation::http::error::ResponsePayload),
     ),
-    security([]),
+    security(("bearerAuth" = [])),
     tag = "Auth"
 )]
 /// Introspect a token and report whether it is active.
 ///
+/// The caller authenticates with a bearer token holding `tokens:introspect`
+/// unless `OPEN_TOKEN_INTROSPECTION` is set.
+///
 /// # Errors
 ///
 /// Returns an error only if request extraction fails before the handler runs.
@@ -177,12 +246,16 @@ pub async fn introspect(
     request_body = TokenRequest,
     responses(
         (status = 200, description = "Token revocation acknowledged", body = crate::presentation::http::openapi::StatusResponse),
+        (status = 401, description = "Unauthorized", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Caller lacks tokens:introspect", body = crate::presentation::http::error::ResponsePayload),
     ),
-    security([]),
+    security(("bearerAuth" = [])),
     tag = "Auth"
 )]
 /// Revoke a token's backing session when possible.
 ///
+/// Guarded like [`introspect`].
+///
 /// # Errors
 ///
 /// Returns an error if session revocation fails after token authentication.
@@ -256,6 +329,7 @@ pub async fn authorize(
                 scope: params.scope.clone(),
                 code_challenge: params.code_challenge.clone(),
                 code_challenge_method: params.code_challenge_method.clone(),
+                nonce: params.nonce.clone(),
             },
         )
         .await
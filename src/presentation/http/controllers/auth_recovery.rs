@@ -0,0 +1,186 @@
+// src/presentation/http/controllers/auth_recovery.rs
+use crate::application::UserProfileDto;
+use crate::application::commands::users::{
+    ChangeEmailCommand, RequestPasswordResetCommand, ResetPasswordCommand, VerifyEmailCommand,
+};
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::openapi::StatusResponse;
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, Json, http::StatusCode};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct PasswordResetRequest {
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct PasswordResetConfirmRequest {
+    /// Token from the reset email.
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct ChangeEmailRequest {
+    /// New address; `null` or an empty string removes it.
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct VerifyEmailRequest {
+    /// Token from the verification email.
+    pub token: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/password-reset",
+    request_body = PasswordResetRequest,
+    responses(
+        (status = 202, description = "A reset token is mailed if an active user has verified this address.", body = StatusResponse),
+        (status = 400, description = "Malformed email address.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    tag = "Auth"
+)]
+/// Mail a password reset token.
+///
+/// The response does not reveal whether the address is registered.
+///
+/// # Errors
+///
+/// Returns an error if the address is malformed or the token cannot be
+/// stored or mailed.
+pub async fn request_password_reset(
+    Extension(state): Extension<HttpContext>,
+    Json(payload): Json<PasswordResetRequest>,
+) -> HttpResult<(StatusCode, Json<StatusResponse>)> {
+    state
+        .services
+        .user_commands
+        .request_password_reset(RequestPasswordResetCommand {
+            email: payload.email,
+        })
+        .await
+        .into_http()?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(StatusResponse {
+            status: "accepted".into(),
+        }),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/password-reset/confirm",
+    request_body = PasswordResetConfirmRequest,
+    responses(
+        (status = 200, description = "Password changed and every session revoked.", body = StatusResponse),
+        (status = 400, description = "Weak password, or the token is invalid or has expired.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    tag = "Auth"
+)]
+/// Choose a new password with a mailed reset token.
+///
+/// # Errors
+///
+/// Returns an error if the password is too weak, the token is invalid or
+/// expired, or the change cannot be persisted.
+pub async fn confirm_password_reset(
+    Extension(state): Extension<HttpContext>,
+    Json(payload): Json<PasswordResetConfirmRequest>,
+) -> HttpResult<Json<StatusResponse>> {
+    state
+        .services
+        .user_commands
+        .reset_password(ResetPasswordCommand {
+            token: payload.token,
+            new_password: payload.new_password,
+        })
+        .await
+        .into_http()?;
+
+    Ok(Json(StatusResponse {
+        status: "password_reset".into(),
+    }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/auth/me/email",
+    request_body = ChangeEmailRequest,
+    responses(
+        (status = 200, description = "Address changed; a verification token was mailed to it.", body = UserProfileDto),
+        (status = 400, description = "Malformed email address.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 409, description = "Address used by another user.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Auth"
+)]
+/// Change or remove the current user's email address.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails, the address is malformed or
+/// taken, or the change cannot be persisted or mailed.
+pub async fn change_email(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Json(payload): Json<ChangeEmailRequest>,
+) -> HttpResult<Json<UserProfileDto>> {
+    state
+        .services
+        .user_commands
+        .change_email(
+            &user,
+            ChangeEmailCommand {
+                email: payload.email,
+            },
+        )
+        .await
+        .into_http()
+        .map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/email/verify",
+    request_body = VerifyEmailRequest,
+    responses(
+        (status = 200, description = "Email address verified.", body = StatusResponse),
+        (status = 400, description = "The token is invalid or has expired.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    tag = "Auth"
+)]
+/// Verify an email address with a mailed token.
+///
+/// # Errors
+///
+/// Returns an error if the token is invalid or expired, or the user has
+/// since removed their address.
+pub async fn verify_email(
+    Extension(state): Extension<HttpContext>,
+    Json(payload): Json<VerifyEmailRequest>,
+) -> HttpResult<Json<StatusResponse>> {
+    state
+        .services
+        .user_commands
+        .verify_email(VerifyEmailCommand {
+            token: payload.token,
+        })
+        .await
+        .into_http()?;
+
+    Ok(Json(StatusResponse {
+        status: "verified".into(),
+    }))
+}
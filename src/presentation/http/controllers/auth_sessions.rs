@@ -8,7 +8,7 @@ use axum::{Extension, Json, extract::Path};
     get,
     path = "/api/v1/auth/sessions",
     responses(
-        (status = 200, description = "List of sessions for the current user", body = [crate::application::SessionInfoDto]),
+        (status = 200, description = "List of sessions for the current user, with the one making the request flagged `current`.", body = [crate::application::SessionInfoDto]),
         (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
         (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
     ),
@@ -29,6 +29,7 @@ pub async fn list_sessions(
         .sessions
         .list_sessions(crate::application::services::ListSessionsRequest {
             user_id: user.id.into(),
+            current_session_id: user.session_id,
         })
         .await
         .into_http()
@@ -73,3 +74,43 @@ pub async fn revoke_session(
         status: "session_revoked".into(),
     }))
 }
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/{id}/sessions",
+    params(("id" = i64, Path, description = "User identifier")),
+    responses(
+        (status = 200, description = "All of the user's sessions and access tokens revoked.", body = crate::presentation::http::openapi::StatusResponse),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Users"
+)]
+/// Log a user out everywhere, revoking their sessions and the access tokens
+/// already issued to them.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails, the caller lacks
+/// `users:update`, or session store operations fail.
+pub async fn revoke_user_sessions(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Path(id): Path<i64>,
+) -> HttpResult<Json<crate::presentation::http::openapi::StatusResponse>> {
+    state
+        .services
+        .sessions
+        .revoke_user_sessions(
+            &user,
+            crate::application::services::RevokeUserSessionsRequest { user_id: id },
+        )
+        .await
+        .into_http()?;
+
+    Ok(Json(crate::presentation::http::openapi::StatusResponse {
+        status: "sessions_revoked".into(),
+    }))
+}
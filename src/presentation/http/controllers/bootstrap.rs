@@ -0,0 +1,65 @@
+// src/presentation/http/controllers/bootstrap.rs
+use crate::application::{
+    CapabilityView, UserProfileDto,
+    dto::{bootstrap::SiteSettingsDto, status::FeaturesDto},
+};
+use crate::presentation::http::controllers::discovery::ServerLimits;
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::MaybeAuthenticated;
+use crate::presentation::http::routes::max_request_body_bytes;
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, Json};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Startup payload for frontends: who the caller is, what they may do and
+/// how the server is configured.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BootstrapResponse {
+    /// The caller's profile; `null` for anonymous requests.
+    pub profile: Option<UserProfileDto>,
+    /// The caller's effective capabilities; empty for anonymous requests.
+    pub capabilities: Vec<CapabilityView>,
+    pub features: FeaturesDto,
+    pub site: SiteSettingsDto,
+    pub limits: ServerLimits,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/bootstrap",
+    responses(
+        (status = 200, description = "Startup payload.", body = BootstrapResponse),
+        (status = 401, description = "Invalid credentials were sent.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "The authenticated user no longer exists.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security([], ("bearerAuth" = []), ("cookieAuth" = [])),
+    tag = "System"
+)]
+/// Return the profile, capabilities, feature flags, site settings and limits
+/// a frontend needs on load.
+///
+/// # Errors
+///
+/// Returns an error if invalid credentials were sent or the caller's user
+/// record cannot be loaded.
+pub async fn bootstrap(
+    Extension(state): Extension<HttpContext>,
+    actor: MaybeAuthenticated,
+) -> HttpResult<Json<BootstrapResponse>> {
+    let bootstrap = state
+        .services
+        .bootstrap
+        .bootstrap(actor.0.as_ref())
+        .await
+        .into_http()?;
+
+    Ok(Json(BootstrapResponse {
+        profile: bootstrap.profile,
+        capabilities: bootstrap.capabilities,
+        features: bootstrap.features,
+        site: bootstrap.site,
+        limits: ServerLimits::new(state.services.pagination, max_request_body_bytes()),
+    }))
+}
@@ -0,0 +1,116 @@
+// src/presentation/http/controllers/bundles.rs
+use crate::application::{
+    BundleFormat, BundleImportDto, ConflictStrategy,
+    services::{ExportBundleQuery, ImportBundleCommand},
+};
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::state::HttpContext;
+use axum::{
+    Extension, Json,
+    body::{Body, Bytes},
+    extract::Query,
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::{IntoResponse, Response},
+};
+use futures_util::stream;
+use serde::Deserialize;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ExportBundleParams {
+    /// `ndjson` (the default) or `zip`.
+    #[serde(default)]
+    pub format: String,
+    #[serde(default)]
+    pub include_revisions: bool,
+    /// Needs `users:read`.
+    #[serde(default)]
+    pub include_users: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ImportBundleParams {
+    /// `skip` (the default), `overwrite` or `new-slug`.
+    #[serde(default)]
+    pub on_conflict: ConflictStrategy,
+}
+
+/// Download every article, drafts included, as a content bundle.
+///
+/// NDJSON bundles are streamed a page of articles at a time; zip bundles
+/// are assembled before the response starts. A failure partway through an
+/// NDJSON stream cuts the response short.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails or the format
+/// is unknown.
+pub async fn export(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Query(params): Query<ExportBundleParams>,
+) -> HttpResult<Response> {
+    let query = ExportBundleQuery {
+        format: BundleFormat::parse(&params.format).into_http()?,
+        include_revisions: params.include_revisions,
+        include_users: params.include_users,
+    };
+    let export = state
+        .services
+        .content_bundles
+        .export(&actor, query)
+        .await
+        .into_http()?;
+    let headers = [
+        (CONTENT_TYPE, export.format().content_type().to_string()),
+        (
+            CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", export.file_name()),
+        ),
+    ];
+
+    let body = match export.format() {
+        BundleFormat::Zip => Body::from(export.into_archive().await.into_http()?),
+        BundleFormat::Ndjson => Body::from_stream(stream::unfold(Some(export), |export| async {
+            let mut export = export?;
+            match export.next_chunk().await {
+                Ok(Some(chunk)) => Some((Ok(Bytes::from(chunk)), Some(export))),
+                Ok(None) => None,
+                Err(err) => {
+                    tracing::warn!(error = %err, "content bundle export failed");
+                    Some((Err(err), None))
+                }
+            }
+        })),
+    };
+    Ok((headers, body).into_response())
+}
+
+/// Apply an NDJSON or zip content bundle, such as one from
+/// `GET /api/v1/export`, resolving slug clashes by `on_conflict`.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, writes are
+/// disabled, or the bundle is malformed. Articles that fail are reported in
+/// the response instead.
+pub async fn import(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Query(params): Query<ImportBundleParams>,
+    bundle: Bytes,
+) -> HttpResult<Json<BundleImportDto>> {
+    state
+        .services
+        .content_bundles
+        .import(
+            &actor,
+            ImportBundleCommand {
+                bundle: &bundle,
+                on_conflict: params.on_conflict,
+            },
+        )
+        .await
+        .into_http()
+        .map(Json)
+}
@@ -1,10 +1,50 @@
 // src/presentation/http/controllers/discovery.rs
+use crate::application::{
+    MAX_BATCH_IDS, PageLimits, PaginationPolicy, commands::users::MIN_PASSWORD_LENGTH,
+};
+use crate::domain::{ArticleBody, ArticleTitle, Username};
 use crate::presentation::http::error::HttpResult;
+use crate::presentation::http::routes::max_request_body_bytes;
 use crate::presentation::http::state::HttpContext;
 use axum::{Extension, Json};
 use serde::Serialize;
 use utoipa::ToSchema;
 
+/// Grant types the token endpoint accepts.
+const GRANT_TYPES: &[&str] = &["authorization_code"];
+/// PKCE challenge methods accepted by the authorize endpoint.
+const CODE_CHALLENGE_METHODS: &[&str] = &["S256", "plain"];
+/// Clients are public; the token endpoint relies on PKCE, not client secrets.
+const TOKEN_ENDPOINT_AUTH_METHODS: &[&str] = &["none"];
+const RESPONSE_TYPES: &[&str] = &["code"];
+const SCOPES: &[&str] = &["openid", "profile"];
+
+/// Absolute URLs of the authorization server's endpoints.
+struct Endpoints {
+    issuer: String,
+    authorization: String,
+    token: String,
+    jwks: String,
+    revocation: String,
+    introspection: String,
+}
+
+impl Endpoints {
+    /// Endpoints under the configured issuer URL.
+    fn from_settings() -> Self {
+        let issuer = crate::config::Settings::oidc_issuer_from_env();
+        let issuer = issuer.trim_end_matches('/').to_string();
+        Self {
+            authorization: format!("{issuer}/api/v1/auth/authorize"),
+            token: format!("{issuer}/api/v1/auth/token"),
+            jwks: format!("{issuer}/api/v1/auth/keys"),
+            revocation: format!("{issuer}/api/v1/auth/revoke"),
+            introspection: format!("{issuer}/api/v1/auth/introspect"),
+            issuer,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct OpenIdConfiguration {
     pub issuer: String,
@@ -22,19 +62,35 @@ pub struct OpenIdConfiguration {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub introspection_endpoint: Option<String>,
 
-    pub response_types_supported: Vec<String>,
-    pub response_modes_supported: Vec<String>,
-    pub grant_types_supported: Vec<String>,
-    pub subject_types_supported: Vec<String>,
-    pub id_token_signing_alg_values_supported: Vec<String>,
-    pub token_endpoint_auth_methods_supported: Vec<String>,
-    pub scopes_supported: Vec<String>,
-    pub code_challenge_methods_supported: Vec<String>,
-    pub claims_supported: Vec<String>,
-    pub claim_types_supported: Vec<String>,
+    pub response_types_supported: &'static [&'static str],
+    pub response_modes_supported: &'static [&'static str],
+    pub grant_types_supported: &'static [&'static str],
+    pub subject_types_supported: &'static [&'static str],
+    pub id_token_signing_alg_values_supported: &'static [&'static str],
+    pub token_endpoint_auth_methods_supported: &'static [&'static str],
+    pub scopes_supported: &'static [&'static str],
+    pub code_challenge_methods_supported: &'static [&'static str],
+    pub claims_supported: &'static [&'static str],
+    pub claim_types_supported: &'static [&'static str],
     pub request_parameter_supported: bool,
 }
 
+/// `OAuth` 2.0 Authorization Server Metadata (RFC 8414).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuthorizationServerMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+    pub revocation_endpoint: String,
+    pub introspection_endpoint: String,
+    pub response_types_supported: &'static [&'static str],
+    pub grant_types_supported: &'static [&'static str],
+    pub token_endpoint_auth_methods_supported: &'static [&'static str],
+    pub scopes_supported: &'static [&'static str],
+    pub code_challenge_methods_supported: &'static [&'static str],
+}
+
 #[utoipa::path(
     get,
     path = "/.well-known/openid-configuration",
@@ -52,58 +108,181 @@ pub struct OpenIdConfiguration {
 pub async fn openid_configuration(
     Extension(_state): Extension<HttpContext>,
 ) -> HttpResult<Json<OpenIdConfiguration>> {
-    let issuer = crate::config::Settings::oidc_issuer_from_env();
-    let issuer = issuer.trim_end_matches('/').to_string();
-
-    // Map discovery endpoints to our existing (or conventional) routes
-    let authorization_endpoint = format!("{issuer}/api/v1/auth/authorize");
-    let token_endpoint = format!("{issuer}/api/v1/auth/token");
-    let userinfo_endpoint = format!("{issuer}/api/v1/auth/me");
-    let end_session_endpoint = format!("{issuer}/api/v1/auth/logout");
-    let jwks_uri = format!("{issuer}/api/v1/auth/keys");
-    let revocation_endpoint = format!("{issuer}/api/v1/auth/revoke");
-    let introspection_endpoint = format!("{issuer}/api/v1/auth/introspect");
+    let endpoints = Endpoints::from_settings();
+    let issuer = &endpoints.issuer;
 
     let cfg = OpenIdConfiguration {
-        issuer,
-        authorization_endpoint: Some(authorization_endpoint),
-        token_endpoint: Some(token_endpoint),
-        userinfo_endpoint: Some(userinfo_endpoint),
-        end_session_endpoint: Some(end_session_endpoint),
-        jwks_uri,
-        revocation_endpoint: Some(revocation_endpoint),
-        introspection_endpoint: Some(introspection_endpoint),
-
-        response_types_supported: vec!["code".into(), "token".into(), "id_token".into()],
-        response_modes_supported: vec!["query".into(), "fragment".into(), "form_post".into()],
-        grant_types_supported: vec![
-            "authorization_code".into(),
-            "refresh_token".into(),
-            "client_credentials".into(),
-        ],
-        subject_types_supported: vec!["public".into()],
-        id_token_signing_alg_values_supported: vec!["RS256".into()],
-        token_endpoint_auth_methods_supported: vec![
-            "client_secret_basic".into(),
-            "private_key_jwt".into(),
-        ],
-        scopes_supported: vec![
-            "openid".into(),
-            "profile".into(),
-            "email".into(),
-            "offline_access".into(),
-        ],
-        code_challenge_methods_supported: vec!["S256".into()],
-        claims_supported: vec![
-            "sub".into(),
-            "name".into(),
-            "email".into(),
-            "email_verified".into(),
-            "preferred_username".into(),
-        ],
-        claim_types_supported: vec!["normal".into()],
+        userinfo_endpoint: Some(format!("{issuer}/api/v1/auth/userinfo")),
+        end_session_endpoint: Some(format!("{issuer}/api/v1/auth/logout")),
+        issuer: endpoints.issuer,
+        authorization_endpoint: Some(endpoints.authorization),
+        token_endpoint: Some(endpoints.token),
+        jwks_uri: endpoints.jwks,
+        revocation_endpoint: Some(endpoints.revocation),
+        introspection_endpoint: Some(endpoints.introspection),
+
+        response_types_supported: RESPONSE_TYPES,
+        response_modes_supported: &["query"],
+        grant_types_supported: GRANT_TYPES,
+        subject_types_supported: &["public"],
+        id_token_signing_alg_values_supported: &["EdDSA", "ES256"],
+        token_endpoint_auth_methods_supported: TOKEN_ENDPOINT_AUTH_METHODS,
+        scopes_supported: SCOPES,
+        code_challenge_methods_supported: CODE_CHALLENGE_METHODS,
+        claims_supported: &["sub", "preferred_username", "zoneinfo", "nonce"],
+        claim_types_supported: &["normal"],
         request_parameter_supported: false,
     };
 
     Ok(Json(cfg))
 }
+
+#[utoipa::path(
+    get,
+    path = "/.well-known/oauth-authorization-server",
+    responses(
+        (status = 200, description = "OAuth 2.0 Authorization Server Metadata", body = AuthorizationServerMetadata),
+    ),
+    security([]),
+    tag = "Auth"
+)]
+/// Serve the `OAuth` 2.0 authorization server metadata document.
+pub async fn oauth_authorization_server() -> Json<AuthorizationServerMetadata> {
+    let endpoints = Endpoints::from_settings();
+    Json(AuthorizationServerMetadata {
+        issuer: endpoints.issuer,
+        authorization_endpoint: endpoints.authorization,
+        token_endpoint: endpoints.token,
+        jwks_uri: endpoints.jwks,
+        revocation_endpoint: endpoints.revocation,
+        introspection_endpoint: endpoints.introspection,
+        response_types_supported: RESPONSE_TYPES,
+        grant_types_supported: GRANT_TYPES,
+        token_endpoint_auth_methods_supported: TOKEN_ENDPOINT_AUTH_METHODS,
+        scopes_supported: SCOPES,
+        code_challenge_methods_supported: CODE_CHALLENGE_METHODS,
+    })
+}
+
+/// Limits the server enforces on article content.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ArticleLimits {
+    pub max_title_chars: usize,
+    pub max_body_chars: usize,
+}
+
+/// Default and largest page size of one listing endpoint.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PageSizeLimits {
+    /// Page size used when `limit` is 0 or omitted.
+    pub default_limit: u32,
+    /// Larger `limit` values are clamped to this.
+    pub max_limit: u32,
+}
+
+impl From<PageLimits> for PageSizeLimits {
+    fn from(limits: PageLimits) -> Self {
+        Self {
+            default_limit: limits.default,
+            max_limit: limits.max,
+        }
+    }
+}
+
+/// Page sizes of each listing endpoint.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EndpointPageLimits {
+    /// Article listings and search.
+    pub articles: PageSizeLimits,
+    /// `GET /api/v1/users`.
+    pub users: PageSizeLimits,
+    /// `GET /api/v1/audit-logs` and its variants, and the account activity
+    /// feed.
+    pub audit_logs: PageSizeLimits,
+    /// `GET /api/v1/moderation/cases`.
+    pub moderation: PageSizeLimits,
+    /// `GET /api/v1/admin/jobs/{name}/runs`.
+    pub jobs: PageSizeLimits,
+}
+
+/// Page-size and batch caps of listing endpoints.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PaginationLimits {
+    /// Page size used when `limit` is 0, unless `endpoints` says otherwise.
+    pub default_limit: u32,
+    /// Larger `limit` values are clamped to this, unless `endpoints` says
+    /// otherwise.
+    pub max_limit: u32,
+    /// Most ids accepted by the batch-get endpoints.
+    pub max_batch_ids: usize,
+    /// Limits in effect for each listing endpoint.
+    pub endpoints: EndpointPageLimits,
+}
+
+/// Rules applied to new passwords.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    /// Character classes that must each appear at least once.
+    pub required_character_classes: &'static [&'static str],
+}
+
+/// Server-enforced limits, so clients can validate before submitting.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ServerLimits {
+    pub article: ArticleLimits,
+    pub pagination: PaginationLimits,
+    pub password: PasswordPolicy,
+    pub min_username_length: usize,
+    /// Largest accepted request body, in bytes.
+    pub max_request_body_bytes: usize,
+}
+
+impl ServerLimits {
+    /// Limits of this server, with listings paged by `pagination` and
+    /// bodies up to `max_request_body_bytes`.
+    #[must_use]
+    pub fn new(pagination: PaginationPolicy, max_request_body_bytes: usize) -> Self {
+        Self {
+            article: ArticleLimits {
+                max_title_chars: ArticleTitle::MAX_CHARS,
+                max_body_chars: ArticleBody::MAX_CHARS,
+            },
+            pagination: PaginationLimits {
+                default_limit: pagination.shared.default,
+                max_limit: pagination.shared.max,
+                max_batch_ids: MAX_BATCH_IDS,
+                endpoints: EndpointPageLimits {
+                    articles: pagination.articles.into(),
+                    users: pagination.users.into(),
+                    audit_logs: pagination.audit_logs.into(),
+                    moderation: pagination.moderation.into(),
+                    jobs: pagination.jobs.into(),
+                },
+            },
+            password: PasswordPolicy {
+                min_length: MIN_PASSWORD_LENGTH,
+                required_character_classes: &["uppercase", "lowercase", "digit", "special"],
+            },
+            min_username_length: Username::MIN_LEN,
+            max_request_body_bytes,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/discovery/limits",
+    responses(
+        (status = 200, description = "Server-enforced limits.", body = ServerLimits),
+    ),
+    security([]),
+    tag = "System"
+)]
+/// Publish the limits the server enforces on requests.
+pub async fn limits(Extension(state): Extension<HttpContext>) -> Json<ServerLimits> {
+    Json(ServerLimits::new(
+        state.services.pagination,
+        max_request_body_bytes(),
+    ))
+}
@@ -0,0 +1,44 @@
+// src/presentation/http/controllers/downloads.rs
+use crate::application::services::DownloadTarget;
+use crate::presentation::http::controllers::articles::file_response;
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, extract::Path, response::Response};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/downloads/{token}",
+    params(("token" = String, Path, description = "Signed download token")),
+    responses(
+        (status = 200, description = "The file, as an attachment.", content(
+            (Vec<u8> = "application/pdf"),
+            (Vec<u8> = "application/epub+zip")
+        )),
+        (status = 404, description = "The link is invalid or has expired, or the file is gone.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security([]),
+    tag = "Articles"
+)]
+/// Download a file through a signed, expiring link.
+///
+/// The link itself grants access, so no `Authorization` header is needed.
+///
+/// # Errors
+///
+/// Returns an error if the token is invalid or expired, or the file it
+/// points at is no longer held.
+pub async fn download(
+    Extension(state): Extension<HttpContext>,
+    Path(token): Path<String>,
+) -> HttpResult<Response> {
+    let target = state.services.downloads.resolve(&token).into_http()?;
+    match target {
+        DownloadTarget::ArticleExport(id) => state
+            .services
+            .article_exports
+            .download_for_link(&id)
+            .into_http()
+            .map(file_response),
+    }
+}
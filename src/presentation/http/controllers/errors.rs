@@ -0,0 +1,26 @@
+// src/presentation/http/controllers/errors.rs
+use crate::presentation::http::openapi::error_catalog::{ERROR_CODES, ErrorCode};
+use axum::Json;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct ErrorCatalogResponse {
+    pub errors: Vec<ErrorCode>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/errors",
+    responses(
+        (status = 200, description = "Every `code` an error response may carry, with its HTTP status.", body = ErrorCatalogResponse)
+    ),
+    security([]),
+    tag = "System"
+)]
+/// List the machine-readable error codes and what they mean.
+pub async fn catalog() -> Json<ErrorCatalogResponse> {
+    Json(ErrorCatalogResponse {
+        errors: ERROR_CODES.to_vec(),
+    })
+}
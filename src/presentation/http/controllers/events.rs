@@ -0,0 +1,79 @@
+// src/presentation/http/controllers/events.rs
+use crate::application::{ArticleWebhookPayload, AuthenticatedUser};
+use crate::domain::{ArticleSnapshot, ArticleVisibility};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::state::HttpContext;
+use axum::{
+    Extension,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures_util::stream::{self, Stream};
+use serde_json::json;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+/// How often an idle stream sends a comment, so proxies keep it open.
+pub const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Sent in place of the changes a slow client missed; reload to catch up.
+pub const RESYNC_EVENT: &str = "resync";
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/events/stream",
+    responses(
+        (status = 200, description = "Server-sent events named after the webhook events (`article.created`, `article.published`, ...), each carrying an `ArticleWebhookPayload`. A `resync` event reports how many changes were missed.", content_type = "text/event-stream", body = String),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Articles"
+)]
+/// Follow article changes as they happen, drafts included, for live editor
+/// views. Events come from the domain event bus; other events on it are
+/// skipped.
+///
+/// Events about private articles reach only callers who may read them.
+/// The route requires `articles:view:drafts`.
+pub async fn stream(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let receiver = state.services.domain_events.subscribe();
+
+    let events = stream::unfold((receiver, user), |(mut receiver, user)| async move {
+        let event = loop {
+            match receiver.recv().await {
+                Ok(event) => {
+                    let visible = event
+                        .article
+                        .as_ref()
+                        .is_some_and(|article| can_see(&user, article));
+                    if let Some(payload) =
+                        ArticleWebhookPayload::from_event(&event).filter(|_| visible)
+                    {
+                        break Event::default()
+                            .event(payload.event.as_str())
+                            .json_data(&payload);
+                    }
+                }
+                Err(RecvError::Lagged(missed)) => {
+                    break Event::default()
+                        .event(RESYNC_EVENT)
+                        .json_data(json!({ "missed": missed }));
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        };
+        Some((event, (receiver, user)))
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::new().interval(KEEP_ALIVE_INTERVAL))
+}
+
+fn can_see(user: &AuthenticatedUser, article: &ArticleSnapshot) -> bool {
+    article.visibility != ArticleVisibility::Private
+        || article.author_id == user.id
+        || user.has_capability("articles", "view:any")
+}
@@ -0,0 +1,189 @@
+// src/presentation/http/controllers/media.rs
+use crate::application::ports::blob_storage::BlobRead;
+use crate::application::services::{MediaDownload, UploadMediaCommand};
+use crate::application::{AppError, MediaAssetDto};
+use crate::presentation::http::error::{Error as HttpError, HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::state::HttpContext;
+use axum::{
+    Extension, Json,
+    extract::{
+        Multipart, Path,
+        multipart::{MultipartError, MultipartRejection},
+    },
+    http::{
+        StatusCode,
+        header::{
+            CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_SECURITY_POLICY, CONTENT_TYPE, LOCATION,
+            X_CONTENT_TYPE_OPTIONS,
+        },
+    },
+    response::{IntoResponse, Redirect, Response},
+};
+use std::fmt::Write as _;
+
+/// Multipart field the file is sent in.
+const FILE_FIELD: &str = "file";
+
+fn multipart_error(err: &MultipartError) -> HttpError {
+    HttpError::from_error(AppError::validation(err.body_text()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/media",
+    request_body(
+        content = Vec<u8>,
+        content_type = "multipart/form-data",
+        description = "The file in a part named `file`, with its content type and file name."
+    ),
+    responses(
+        (status = 201, description = "File stored.", body = MediaAssetDto),
+        (status = 400, description = "No file, an empty or oversized file, or a content type that is not accepted or does not match the content.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Media"
+)]
+/// Upload a file, such as an image to embed in an article.
+///
+/// The response's `url` is where the file is served from.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the request
+/// has no `file` part, or the file is rejected or cannot be stored.
+pub async fn upload_media(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    multipart: Result<Multipart, MultipartRejection>,
+) -> HttpResult<Response> {
+    let mut multipart =
+        multipart.map_err(|err| HttpError::from_error(AppError::validation(err.body_text())))?;
+    let mut command = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|err| multipart_error(&err))?
+    {
+        if field.name() != Some(FILE_FIELD) {
+            continue;
+        }
+        let file_name = field.file_name().map(ToString::to_string);
+        let content_type = field
+            .content_type()
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let bytes = field.bytes().await.map_err(|err| multipart_error(&err))?;
+        command = Some(UploadMediaCommand {
+            file_name,
+            content_type,
+            bytes,
+        });
+        break;
+    }
+    let command = command
+        .ok_or_else(|| AppError::validation(format!("multipart part '{FILE_FIELD}' is missing")))
+        .into_http()?;
+
+    let asset = state
+        .services
+        .media
+        .upload(&user, command)
+        .await
+        .into_http()?;
+    Ok((
+        StatusCode::CREATED,
+        [(LOCATION, asset.url.clone())],
+        Json(asset),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/media/{id}",
+    params(("id" = i64, Path, description = "Media identifier")),
+    responses(
+        (status = 200, description = "The file, served by this service.", content(
+            (Vec<u8> = "application/octet-stream")
+        )),
+        (status = 307, description = "The file is served by the storage bucket; follow `Location`."),
+        (status = 404, description = "Media not found.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security([]),
+    tag = "Media"
+)]
+/// Download an uploaded file.
+///
+/// Files are public so published articles can embed them. Depending on the
+/// configured storage the file is returned directly or through a redirect
+/// to a short-lived storage URL.
+///
+/// # Errors
+///
+/// Returns an error if there is no such file or it cannot be read.
+pub async fn download_media(
+    Extension(state): Extension<HttpContext>,
+    Path(id): Path<i64>,
+) -> HttpResult<Response> {
+    let MediaDownload { asset, content } = state.services.media.download(id).await.into_http()?;
+    Ok(match content {
+        BlobRead::Redirect(url) => Redirect::temporary(&url).into_response(),
+        BlobRead::Bytes(bytes) => (
+            [
+                (CONTENT_TYPE, asset.content_type),
+                (CONTENT_DISPOSITION, inline_disposition(&asset.file_name)),
+                // An asset never changes once uploaded.
+                (CACHE_CONTROL, "public, max-age=31536000, immutable".into()),
+                (X_CONTENT_TYPE_OPTIONS, "nosniff".into()),
+                (
+                    CONTENT_SECURITY_POLICY,
+                    "default-src 'none'; sandbox".into(),
+                ),
+            ],
+            bytes,
+        )
+            .into_response(),
+    })
+}
+
+/// `inline` with the file name, ASCII-only in `filename` and exact in
+/// `filename*` (RFC 6266).
+fn inline_disposition(file_name: &str) -> String {
+    let ascii: String = file_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_graphic() || c == ' ' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let mut encoded = String::with_capacity(file_name.len());
+    for byte in file_name.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    format!("inline; filename=\"{ascii}\"; filename*=UTF-8''{encoded}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::inline_disposition;
+
+    #[test]
+    fn dispositions_keep_non_ascii_names_in_the_extended_parameter() {
+        assert_eq!(
+            inline_disposition("猫 1.png"),
+            "inline; filename=\"_ 1.png\"; filename*=UTF-8''%E7%8C%AB%201.png"
+        );
+    }
+}
@@ -1,9 +1,34 @@
 // src/presentation/http/controllers/mod.rs
+pub mod account_deletion;
+pub mod admin_access_rules;
+pub mod admin_custom_fields;
+pub mod admin_inspect;
+pub mod admin_integrity;
+pub mod admin_jobs;
+pub mod admin_read_only;
+pub mod admin_roles;
+pub mod admin_security;
+pub mod admin_static_site;
+pub mod admin_time;
+pub mod admin_users;
 pub mod articles;
 pub mod audit;
 pub mod auth;
+pub mod auth_blocks;
+pub mod auth_federated;
 pub mod auth_oidc;
+pub mod auth_recovery;
 pub mod auth_sessions;
+pub mod bootstrap;
+pub mod bundles;
 pub mod discovery;
+pub mod downloads;
+pub mod errors;
+pub mod events;
+pub mod media;
+pub mod moderation;
+pub mod search;
+pub mod status;
 pub mod user_requests;
 pub mod users;
+pub mod webhooks;
@@ -0,0 +1,175 @@
+// src/presentation/http/controllers/moderation.rs
+use crate::application::ModerationCaseDto;
+use crate::application::services::{
+    ListModerationCasesQuery, ReportArticleCommand, ResolveModerationCaseCommand,
+};
+use crate::domain::{CaseStatus, ReportReason, Resolution};
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::{Authenticated, MaybeAuthenticated, RequestClient};
+use crate::presentation::http::openapi::ModerationCaseListResponse;
+use crate::presentation::http::state::HttpContext;
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    http::StatusCode,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+const fn default_status() -> CaseStatus {
+    CaseStatus::Open
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct ReportRequest {
+    pub article_id: i64,
+    pub reason: ReportReason,
+    /// Optional explanation, at most 1000 characters.
+    #[serde(default)]
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ModerationCaseListParams {
+    /// `open` (the default) or `resolved`.
+    #[serde(default = "default_status")]
+    pub status: CaseStatus,
+    /// Page size. 0 or omitted uses this endpoint's default and larger
+    /// values are clamped to its cap; see `pagination.endpoints.moderation` in
+    /// `GET /api/v1/discovery/limits`.
+    #[serde(default)]
+    pub limit: u32,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct ResolveCaseRequest {
+    pub action: Resolution,
+    /// Optional note for the audit trail, at most 1000 characters.
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/public/v1/report",
+    request_body = ReportRequest,
+    responses(
+        (status = 202, description = "Report received."),
+        (status = 400, description = "Invalid input.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "Article not found.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security([]),
+    tag = "Moderation"
+)]
+/// Flag a published article for moderator review.
+///
+/// # Errors
+///
+/// Returns an error if the article is not published or the report is
+/// invalid.
+pub async fn report(
+    Extension(state): Extension<HttpContext>,
+    MaybeAuthenticated(reporter): MaybeAuthenticated,
+    RequestClient(client): RequestClient,
+    Json(payload): Json<ReportRequest>,
+) -> HttpResult<StatusCode> {
+    let command = ReportArticleCommand {
+        article_id: payload.article_id,
+        reason: payload.reason,
+        details: payload.details,
+    };
+    state
+        .services
+        .moderation
+        .report(reporter.as_ref(), &client, command)
+        .await
+        .into_http()?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/moderation/cases",
+    params(ModerationCaseListParams),
+    responses(
+        (status = 200, description = "Moderation cases, oldest first.", body = ModerationCaseListResponse),
+        (status = 400, description = "Invalid input.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Moderation"
+)]
+/// List moderation cases.
+///
+/// # Errors
+///
+/// Returns an error if the actor lacks `moderation:review` or the cursor is
+/// invalid.
+pub async fn list_cases(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Query(params): Query<ModerationCaseListParams>,
+) -> HttpResult<Json<ModerationCaseListResponse>> {
+    let query = ListModerationCasesQuery {
+        status: params.status,
+        limit: params.limit,
+        cursor: params.cursor,
+    };
+    state
+        .services
+        .moderation
+        .list_cases(&actor, query)
+        .await
+        .into_http()
+        .map(|page| Json(page.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/moderation/cases/{id}/resolve",
+    params(("id" = i64, Path, description = "Moderation case identifier")),
+    request_body = ResolveCaseRequest,
+    responses(
+        (status = 200, description = "Case resolved.", body = ModerationCaseDto),
+        (status = 400, description = "Invalid input.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "Case not found.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 409, description = "Case already resolved.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Moderation"
+)]
+/// Resolve an open case: dismiss it, unpublish the article, or unpublish it
+/// and deactivate its author.
+///
+/// # Errors
+///
+/// Returns an error if the actor lacks `moderation:review`, the case is
+/// missing or already resolved, or the action cannot be applied.
+pub async fn resolve_case(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    RequestClient(client): RequestClient,
+    Path(id): Path<i64>,
+    Json(payload): Json<ResolveCaseRequest>,
+) -> HttpResult<Json<ModerationCaseDto>> {
+    let command = ResolveModerationCaseCommand {
+        case_id: id,
+        resolution: payload.action,
+        note: payload.note,
+    };
+    state
+        .services
+        .moderation
+        .resolve(&actor, &client, command)
+        .await
+        .into_http()
+        .map(Json)
+}
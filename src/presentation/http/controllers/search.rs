@@ -0,0 +1,73 @@
+// src/presentation/http/controllers/search.rs
+use crate::application::{PageDirection, queries::articles::SearchArticlesQuery};
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::MaybeAuthenticated;
+use crate::presentation::http::openapi::SearchResponse;
+use crate::presentation::http::profiles::{ApiProfile, Profiled};
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, extract::Query};
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams, utoipa::ToSchema)]
+pub struct SearchParams {
+    /// Web search syntax: quoted phrases, `-` to exclude a term and `or`
+    /// between alternatives. Blank or omitted browses every article.
+    #[serde(default)]
+    pub q: Option<String>,
+    #[serde(default)]
+    pub include_drafts: bool,
+    /// Page size, as for `GET /api/v1/articles`.
+    #[serde(default)]
+    pub limit: u32,
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Only search articles carrying this tag; the facets are narrowed too.
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/search",
+    params(SearchParams),
+    responses(
+        (status = 200, description = "Matching articles with facet counts and a total.", body = SearchResponse),
+        (status = 400, description = "Invalid query parameters.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security([]),
+    tag = "Articles"
+)]
+/// Search articles and count every match by publication state, author and
+/// tag, for building a search page with filters.
+///
+/// # Errors
+///
+/// Returns an error if query validation fails, draft access is forbidden, or
+/// the article query service fails.
+pub async fn search(
+    Extension(state): Extension<HttpContext>,
+    actor: MaybeAuthenticated,
+    profile: ApiProfile,
+    Query(params): Query<SearchParams>,
+) -> HttpResult<Profiled> {
+    let (page, facets) = state
+        .services
+        .article_queries
+        .faceted_search(
+            actor.0.as_ref(),
+            SearchArticlesQuery {
+                query: params.q.unwrap_or_default(),
+                include_drafts: params.include_drafts,
+                limit: params.limit,
+                cursor: params.cursor,
+                direction: PageDirection::Forward,
+                tag: params.tag,
+            },
+        )
+        .await
+        .into_http()?;
+
+    profile.render_items("ArticleDto", &SearchResponse::new(page, facets))
+}
@@ -0,0 +1,18 @@
+// src/presentation/http/controllers/status.rs
+use crate::application::ServiceStatusDto;
+use crate::presentation::http::state::HttpContext;
+use axum::{Extension, Json};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/status",
+    responses(
+        (status = 200, description = "Version, build and back-end information for this deployment.", body = ServiceStatusDto)
+    ),
+    security([]),
+    tag = "System"
+)]
+/// Report the running version, git commit, uptime and configured back-ends.
+pub async fn status(Extension(state): Extension<HttpContext>) -> Json<ServiceStatusDto> {
+    Json(state.services.status.status())
+}
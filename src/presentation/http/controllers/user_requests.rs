@@ -1,10 +1,6 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-const fn default_limit() -> u32 {
-    20
-}
-
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct RegisterRequest {
     pub username: String,
@@ -12,31 +8,110 @@ pub struct RegisterRequest {
     pub role: Option<crate::domain::Role>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct LoginRequest {
+    /// A username, or an email address the account has verified.
     pub username: String,
     pub password: String,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct RefreshTokenRequest {
     pub token: String,
 }
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct LoginResponse {
     pub token: crate::application::AuthTokenDto,
     pub user: crate::application::UserDto,
 }
 
+/// How login hands out credentials.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LoginMode {
+    /// Tokens in the response body, sent back as `Authorization: Bearer`.
+    #[default]
+    Bearer,
+    /// An `HttpOnly` session cookie plus a CSRF cookie, for browser frontends.
+    Cookie,
+}
+
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams, ToSchema)]
+pub struct LoginParams {
+    #[serde(default)]
+    pub mode: LoginMode,
+}
+
+/// Login response in cookie mode; the tokens are only in cookies.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CookieLoginResponse {
+    pub user: crate::application::UserDto,
+    /// Seconds until the session cookie expires and the user must log in again.
+    pub expires_in: i64,
+    /// Value to send in `X-CSRF-Token` on mutating requests (also in the
+    /// `mokkan_csrf` cookie).
+    pub csrf_token: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CsrfTokenResponse {
+    /// New value for `X-CSRF-Token`; the previous one stops working.
+    pub csrf_token: String,
+}
+
 #[derive(Debug, Deserialize, utoipa::IntoParams, ToSchema)]
 pub struct ListUsersParams {
-    #[serde(default = "default_limit")]
+    /// Page size. 0 or omitted uses this endpoint's default and larger
+    /// values are clamped to its cap; see `pagination.endpoints.users` in
+    /// `GET /api/v1/discovery/limits`.
+    #[serde(default)]
     pub limit: u32,
     #[serde(default)]
     pub cursor: Option<String>,
     #[serde(default)]
     pub q: Option<String>,
+    /// `backward` returns the page before `cursor` (requires a cursor).
+    #[serde(default)]
+    pub direction: crate::application::PageDirection,
+    /// 1-based page number: returns numbered pages with a `total_count`
+    /// instead of cursors. Not supported with `cursor` or `direction`.
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// Page size for numbered pages; overrides `limit` when present.
+    #[serde(default)]
+    pub page_size: Option<u32>,
+    /// IANA timezone used to fill `created_at_local` on each user.
+    #[serde(default)]
+    pub tz: Option<String>,
+}
+
+impl ListUsersParams {
+    /// Resolve the `?tz=` display hint.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `tz` is not a known IANA timezone.
+    pub fn display_tz(&self) -> crate::application::error::AppResult<Option<chrono_tz::Tz>> {
+        Ok(self
+            .tz
+            .as_deref()
+            .map(crate::domain::Timezone::new)
+            .transpose()?
+            .map(|tz| tz.tz()))
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePreferencesRequest {
+    /// IANA timezone name such as `Asia/Tokyo`; `null` clears the preference.
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BatchGetUsersRequest {
+    /// User ids to resolve (at most 100).
+    pub ids: Vec<i64>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
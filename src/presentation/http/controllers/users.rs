@@ -1,16 +1,17 @@
 use crate::application::{
-    UserDto,
+    AuthTokenDto, UserDto,
     commands::users::{
         ChangePasswordCommand, GrantRoleCommand, RevokeRoleCommand, UpdateUserCommand,
     },
-    queries::users::ListUsersQuery,
+    queries::users::{BatchGetUsersQuery, ListUsersQuery},
 };
 use crate::presentation::http::controllers::user_requests::{
-    ChangePasswordRequest, GrantRoleRequest, ListUsersParams, UpdateUserRequest,
+    BatchGetUsersRequest, ChangePasswordRequest, GrantRoleRequest, ListUsersParams,
+    UpdateUserRequest,
 };
 use crate::presentation::http::error::{HttpResult, IntoHttpResult};
-use crate::presentation::http::extractors::Authenticated;
-use crate::presentation::http::openapi::{StatusResponse, UserListResponse};
+use crate::presentation::http::extractors::{Authenticated, RequestClient};
+use crate::presentation::http::openapi::{StatusResponse, UserBatchResponse, UserListResponse};
 use crate::presentation::http::state::HttpContext;
 use axum::{
     Extension, Json,
@@ -35,29 +36,75 @@ use axum::{
 /// # Errors
 ///
 /// Returns an error if authentication fails, the caller lacks permission, the
-/// cursor is invalid, or the user query fails.
+/// cursor or page number is invalid, or the user query fails.
 pub async fn list_users(
     Extension(state): Extension<HttpContext>,
     Authenticated(user): Authenticated,
     Query(params): Query<ListUsersParams>,
 ) -> HttpResult<Json<UserListResponse>> {
-    let page = state
+    let tz = params.display_tz().into_http()?;
+    let mut page = state
         .services
         .user_queries
         .list_users(
             &user,
             ListUsersQuery {
-                limit: params.limit,
+                limit: params.page_size.unwrap_or(params.limit),
                 cursor: params.cursor,
                 q: params.q,
+                direction: params.direction,
+                page: params.page,
             },
         )
         .await
         .into_http()?;
 
+    if tz.is_some() {
+        page.items = page
+            .items
+            .into_iter()
+            .map(|dto| dto.localized(tz))
+            .collect();
+    }
+
     Ok(Json(UserListResponse::from(page)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/batch-get",
+    request_body = BatchGetUsersRequest,
+    responses(
+        (status = 200, description = "Users found and ids that were not.", body = UserBatchResponse),
+        (status = 400, description = "Too many ids requested.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Users"
+)]
+/// Resolve several users by id in one request.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails, the caller lacks permission, too
+/// many ids are requested, or the user query fails.
+pub async fn batch_get_users(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    Json(payload): Json<BatchGetUsersRequest>,
+) -> HttpResult<Json<UserBatchResponse>> {
+    let result = state
+        .services
+        .user_queries
+        .batch_get_users(&user, BatchGetUsersQuery { ids: payload.ids })
+        .await
+        .into_http()?;
+
+    Ok(Json(UserBatchResponse::from(result)))
+}
+
 #[utoipa::path(
     patch,
     path = "/api/v1/users/{id}",
@@ -233,3 +280,42 @@ pub async fn revoke_role(
         .into_http()
         .map(Json)
 }
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{id}/impersonate",
+    params(
+        ("id" = i64, Path, description = "User identifier")
+    ),
+    responses(
+        (status = 200, description = "Short-lived token acting as the user.", body = AuthTokenDto),
+        (status = 400, description = "Invalid input.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 401, description = "Unauthorized.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 403, description = "Forbidden.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 404, description = "User not found.", body = crate::presentation::http::error::ResponsePayload),
+        (status = 500, description = "Unexpected server error.", body = crate::presentation::http::error::ResponsePayload)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Users"
+)]
+/// Issue a short-lived token for acting as a user.
+///
+/// # Errors
+///
+/// Returns an error if authentication fails, the caller lacks
+/// `users:impersonate`, the user cannot be impersonated, or token issuance
+/// fails.
+pub async fn impersonate(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(user): Authenticated,
+    RequestClient(client): RequestClient,
+    Path(id): Path<i64>,
+) -> HttpResult<Json<AuthTokenDto>> {
+    state
+        .services
+        .user_commands
+        .impersonate(&user, id, &client)
+        .await
+        .into_http()
+        .map(Json)
+}
@@ -0,0 +1,266 @@
+// src/presentation/http/controllers/webhooks.rs
+use crate::application::services::CreateWebhookCommand;
+use crate::application::{
+    ArticleWebhookPayload, TOKEN_REUSE_WEBHOOK_EVENT, TokenReuseWebhookPayload, WebhookDeliveryDto,
+    WebhookDto,
+};
+use crate::domain::{WebhookEvent, WebhookUpdate};
+use crate::presentation::http::error::{HttpResult, IntoHttpResult};
+use crate::presentation::http::extractors::Authenticated;
+use crate::presentation::http::state::HttpContext;
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use utoipa::openapi::{Components, ComponentsBuilder};
+
+/// An outgoing webhook event and the component describing its body.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookEventSchema {
+    /// Value of the payload's `event` field.
+    pub event: &'static str,
+    /// `$ref` of the payload schema within `components`.
+    pub schema: &'static str,
+    pub description: &'static str,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WebhookSchemasResponse {
+    pub events: Vec<WebhookEventSchema>,
+    /// JSON schemas for every payload and the types they reference.
+    #[schema(value_type = Object)]
+    pub components: Components,
+}
+
+/// Every webhook event the server can deliver. Register new events here and
+/// add their payload type to [`webhook_components`].
+pub const WEBHOOK_EVENTS: &[WebhookEventSchema] = &[
+    WebhookEventSchema {
+        event: TOKEN_REUSE_WEBHOOK_EVENT,
+        schema: "#/components/schemas/TokenReuseWebhookPayload",
+        description: "A rotated refresh token was presented again for a session.",
+    },
+    WebhookEventSchema {
+        event: WebhookEvent::ArticleCreated.as_str(),
+        schema: "#/components/schemas/ArticleWebhookPayload",
+        description: "An article was created.",
+    },
+    WebhookEventSchema {
+        event: WebhookEvent::ArticleUpdated.as_str(),
+        schema: "#/components/schemas/ArticleWebhookPayload",
+        description: "An article's content or metadata changed, or it was reverted to a revision.",
+    },
+    WebhookEventSchema {
+        event: WebhookEvent::ArticlePublished.as_str(),
+        schema: "#/components/schemas/ArticleWebhookPayload",
+        description: "An article was published, on creation or later.",
+    },
+    WebhookEventSchema {
+        event: WebhookEvent::ArticleUnpublished.as_str(),
+        schema: "#/components/schemas/ArticleWebhookPayload",
+        description: "A published article went back to draft.",
+    },
+    WebhookEventSchema {
+        event: WebhookEvent::ArticleDeleted.as_str(),
+        schema: "#/components/schemas/ArticleWebhookPayload",
+        description: "An article was deleted; the payload shows it as it was.",
+    },
+];
+
+/// Components describing all registered webhook payloads.
+#[must_use]
+pub fn webhook_components() -> Components {
+    let mut referenced = Vec::new();
+    TokenReuseWebhookPayload::schemas(&mut referenced);
+    ArticleWebhookPayload::schemas(&mut referenced);
+
+    ComponentsBuilder::new()
+        .schema_from::<TokenReuseWebhookPayload>()
+        .schema_from::<ArticleWebhookPayload>()
+        .schemas_from_iter(referenced)
+        .build()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks/schemas",
+    responses(
+        (status = 200, description = "JSON schemas of outgoing webhook payloads.", body = WebhookSchemasResponse)
+    ),
+    security([]),
+    tag = "System"
+)]
+/// List outgoing webhook events with the JSON schemas of their payloads.
+pub async fn schemas() -> Json<WebhookSchemasResponse> {
+    Json(WebhookSchemasResponse {
+        events: WEBHOOK_EVENTS.to_vec(),
+        components: webhook_components(),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    /// Absolute `http(s)` URL events are posted to.
+    pub url: String,
+    /// Key deliveries are signed with, 16 to 256 characters; generated and
+    /// returned once when omitted.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Events posted to `url`, such as `article.published`.
+    pub events: Vec<WebhookEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateWebhookRequest {
+    #[serde(default)]
+    pub url: Option<String>,
+    /// New signing key; the old one stops being used at once.
+    #[serde(default)]
+    pub secret: Option<String>,
+    #[serde(default)]
+    pub events: Option<Vec<WebhookEvent>>,
+    #[serde(default)]
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookDeliveriesParams {
+    /// Attempts returned; see `pagination.shared` in
+    /// `GET /api/v1/discovery/limits` for the default and cap.
+    pub limit: Option<u32>,
+}
+
+/// List webhooks, oldest first.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails.
+pub async fn list_webhooks(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+) -> HttpResult<Json<Vec<WebhookDto>>> {
+    state
+        .services
+        .webhooks
+        .list(&actor)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+/// Subscribe a URL to content events. The response carries the signing
+/// secret; later ones do not.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the
+/// webhook is invalid.
+pub async fn create_webhook(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> HttpResult<(StatusCode, Json<WebhookDto>)> {
+    let command = CreateWebhookCommand {
+        url: payload.url,
+        secret: payload.secret,
+        events: payload.events,
+    };
+    let webhook = state
+        .services
+        .webhooks
+        .create(&actor, command)
+        .await
+        .into_http()?;
+    Ok((StatusCode::CREATED, Json(webhook)))
+}
+
+/// Show one webhook.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the
+/// webhook does not exist.
+pub async fn get_webhook(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(id): Path<i64>,
+) -> HttpResult<Json<WebhookDto>> {
+    state
+        .services
+        .webhooks
+        .get(&actor, id)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+/// Change a webhook's URL, secret, events or whether it is active.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, the webhook
+/// does not exist, or a change is invalid.
+pub async fn update_webhook(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(id): Path<i64>,
+    Json(payload): Json<UpdateWebhookRequest>,
+) -> HttpResult<Json<WebhookDto>> {
+    let update = WebhookUpdate {
+        url: payload.url,
+        secret: payload.secret,
+        events: payload.events,
+        active: payload.active,
+    };
+    state
+        .services
+        .webhooks
+        .update(&actor, id, update)
+        .await
+        .into_http()
+        .map(Json)
+}
+
+/// Remove a webhook and its delivery history.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the
+/// webhook does not exist.
+pub async fn delete_webhook(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(id): Path<i64>,
+) -> HttpResult<StatusCode> {
+    state
+        .services
+        .webhooks
+        .delete(&actor, id)
+        .await
+        .into_http()?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List the latest delivery attempts to a webhook, newest first.
+///
+/// # Errors
+///
+/// Returns an error if authentication or authorization fails, or the
+/// webhook does not exist.
+pub async fn list_webhook_deliveries(
+    Extension(state): Extension<HttpContext>,
+    Authenticated(actor): Authenticated,
+    Path(id): Path<i64>,
+    Query(params): Query<WebhookDeliveriesParams>,
+) -> HttpResult<Json<Vec<WebhookDeliveryDto>>> {
+    state
+        .services
+        .webhooks
+        .deliveries(&actor, id, params.limit)
+        .await
+        .into_http()
+        .map(Json)
+}
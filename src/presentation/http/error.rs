@@ -1,45 +1,79 @@
 // src/presentation/http/error.rs
 use crate::application::{AppResult, error::AppError};
+use crate::domain::errors::DomainError;
+use crate::presentation::http::openapi::error_catalog::{self, ErrorCode};
 use axum::{
     Json,
-    http::StatusCode,
+    http::{StatusCode, header::RETRY_AFTER},
     response::{IntoResponse, Response},
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 #[derive(Debug)]
 pub struct Error {
     status: StatusCode,
     message: String,
+    code: &'static str,
+    /// Seconds sent in `Retry-After`.
+    retry_after: Option<u64>,
 }
 
 impl Error {
     #[must_use]
     pub fn from_error(err: AppError) -> Self {
         match err {
-            AppError::Validation(msg) => Self::new(StatusCode::BAD_REQUEST, msg),
-            AppError::NotFound(msg) => Self::new(StatusCode::NOT_FOUND, msg),
-            AppError::Conflict(msg) => Self::new(StatusCode::CONFLICT, msg),
-            AppError::Unauthorized(msg) => Self::new(StatusCode::UNAUTHORIZED, msg),
-            AppError::Forbidden(msg) => Self::new(StatusCode::FORBIDDEN, msg),
+            AppError::Validation(msg) => Self::new(&error_catalog::VALIDATION_FAILED, msg),
+            AppError::NotFound(msg) => Self::new(&error_catalog::NOT_FOUND, msg),
+            AppError::Conflict(msg) => Self::new(&error_catalog::CONFLICT, msg),
+            AppError::Unauthorized(msg) => Self::new(&error_catalog::UNAUTHORIZED, msg),
+            AppError::Forbidden(msg) => Self::new(&error_catalog::FORBIDDEN, msg),
+            AppError::Unavailable(msg) => Self::new(&error_catalog::UNAVAILABLE, msg),
+            AppError::TooManyRequests {
+                message,
+                retry_after_secs,
+            } => Self {
+                retry_after: Some(retry_after_secs),
+                ..Self::new(&error_catalog::TOO_MANY_REQUESTS, message)
+            },
             AppError::Infrastructure(err) => {
                 // Log the detailed internal error for observability, but return a
                 // generic message to the client to avoid leaking internals.
                 tracing::error!(error = %err, "infrastructure error");
                 Self::new(
-                    StatusCode::INTERNAL_SERVER_ERROR,
+                    &error_catalog::INTERNAL_ERROR,
                     "internal server error".to_string(),
                 )
             }
-            AppError::Domain(domain_err) => {
-                Self::new(StatusCode::BAD_REQUEST, domain_err.to_string())
-            }
+            AppError::Domain(domain_err) => Self::from_domain(&domain_err),
         }
     }
 
-    const fn new(status: StatusCode, message: String) -> Self {
-        Self { status, message }
+    /// A request body over the limit of the endpoint it was sent to.
+    #[must_use]
+    pub fn payload_too_large() -> Self {
+        Self::new(
+            &error_catalog::PAYLOAD_TOO_LARGE,
+            "request body is too large".to_string(),
+        )
+    }
+
+    fn from_domain(err: &DomainError) -> Self {
+        let code = match err {
+            DomainError::SlugTaken { .. } => &error_catalog::SLUG_TAKEN,
+            DomainError::UsernameTaken { .. } => &error_catalog::USERNAME_TAKEN,
+            _ => &error_catalog::VALIDATION_FAILED,
+        };
+        Self::new(code, err.to_string())
+    }
+
+    const fn new(code: &ErrorCode, message: String) -> Self {
+        Self {
+            status: code.status,
+            message,
+            code: code.code,
+            retry_after: None,
+        }
     }
 }
 
@@ -52,15 +86,23 @@ impl IntoResponse for Error {
                 .unwrap_or("error")
                 .to_string(),
             message: self.message,
+            code: Some(self.code.to_string()),
         };
-        (self.status, Json(payload)).into_response()
+        let mut response = (self.status, Json(payload)).into_response();
+        if let Some(secs) = self.retry_after {
+            response.headers_mut().insert(RETRY_AFTER, secs.into());
+        }
+        response
     }
 }
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ResponsePayload {
     pub error: String,
     pub message: String,
+    /// Machine-readable reason; `GET /api/v1/errors` lists every value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
 }
 
 pub type HttpResult<T> = Result<T, Error>;
@@ -78,3 +120,59 @@ impl<T> IntoHttpResult<T> for AppResult<T> {
         self.map_err(Error::from_error)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn taken_slug_and_username_are_conflicts_with_codes() {
+        let err = Error::from_error(
+            DomainError::SlugTaken {
+                slug: Some("hello".into()),
+            }
+            .into(),
+        );
+        assert_eq!(err.status, StatusCode::CONFLICT);
+        assert_eq!(err.code, "slug_taken");
+
+        let err = Error::from_error(DomainError::UsernameTaken { username: None }.into());
+        assert_eq!(err.status, StatusCode::CONFLICT);
+        assert_eq!(err.code, "username_taken");
+
+        let err = Error::from_error(DomainError::Validation("bad".into()).into());
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert_eq!(err.code, "validation_failed");
+    }
+
+    #[test]
+    fn every_error_uses_a_catalogued_code_and_status() {
+        let errors = [
+            AppError::validation("bad"),
+            AppError::not_found("gone"),
+            AppError::conflict("stale"),
+            AppError::unauthorized("who"),
+            AppError::forbidden("no"),
+            AppError::unavailable("later"),
+            AppError::too_many_requests("slow down", 1),
+            AppError::infrastructure("boom"),
+            DomainError::NotFound("gone".into()).into(),
+        ];
+        for err in errors {
+            let err = Error::from_error(err);
+            let entry = error_catalog::ERROR_CODES
+                .iter()
+                .find(|entry| entry.code == err.code)
+                .unwrap_or_else(|| panic!("{} is not catalogued", err.code));
+            assert_eq!(entry.status, err.status, "{}", err.code);
+        }
+    }
+
+    #[test]
+    fn too_many_requests_sets_retry_after() {
+        let response =
+            Error::from_error(AppError::too_many_requests("slow down", 42)).into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RETRY_AFTER], "42");
+    }
+}
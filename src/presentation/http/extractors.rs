@@ -1,23 +1,119 @@
 // src/presentation/http/extractors.rs
 use crate::{
-    application::{AuthenticatedUser, error::AppError},
-    presentation::http::state::HttpContext,
+    application::{
+        AuthenticatedUser, error::AppError, ports::security_events::ClientInfo, request_context,
+    },
+    domain::IpRange,
+    infrastructure::rls,
+    presentation::http::{session_cookie, state::HttpContext},
+};
+use axum::{
+    Extension,
+    extract::{ConnectInfo, FromRequestParts},
+    http::{Extensions, HeaderMap, header::USER_AGENT, request::Parts},
+};
+use std::{
+    convert::Infallible,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
 };
-use axum::{Extension, extract::FromRequestParts, http::request::Parts};
-use headers::{Authorization, HeaderMapExt, authorization::Bearer};
 
 use super::error::Error as HttpError;
 
+/// The authenticated caller, from a bearer token or the session cookie.
 #[derive(Debug, Clone)]
 pub struct Authenticated(pub AuthenticatedUser);
 
 #[derive(Debug, Clone)]
 pub struct MaybeAuthenticated(pub Option<AuthenticatedUser>);
 
+/// Client address and user agent of the caller.
+///
+/// See [`client_info`] for how the address is chosen.
+#[derive(Debug, Clone, Default)]
+pub struct RequestClient(pub ClientInfo);
+
+/// Proxies allowed to report the client address, from `TRUSTED_PROXIES`.
+///
+/// Installed as a router extension; without it no forwarding header is
+/// believed.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(pub Arc<[IpRange]>);
+
+impl TrustedProxies {
+    fn contains(&self, ip: IpAddr) -> bool {
+        self.0.iter().any(|range| range.contains(ip))
+    }
+}
+
 fn cached_authenticated_user(parts: &Parts) -> Option<AuthenticatedUser> {
     parts.extensions.get::<AuthenticatedUser>().cloned()
 }
 
+fn header_ip(value: &str) -> Option<IpAddr> {
+    value.trim().parse::<IpAddr>().ok()
+}
+
+/// The right-most `X-Forwarded-For` hop that is not itself a trusted proxy.
+/// Hops left of one that does not parse are not believed.
+fn forwarded_for(headers: &HeaderMap, trusted: &TrustedProxies) -> Option<IpAddr> {
+    let hops = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .map(|value| value.to_str().ok())
+        .collect::<Option<Vec<_>>>()?;
+    for hop in hops.iter().flat_map(|value| value.split(',')).rev() {
+        let ip = header_ip(hop)?;
+        if !trusted.contains(ip) {
+            return Some(ip);
+        }
+    }
+    None
+}
+
+/// The address a trusted proxy reports: from `X-Forwarded-For`, else
+/// `X-Real-IP`.
+fn forwarded_ip(headers: &HeaderMap, trusted: &TrustedProxies) -> Option<IpAddr> {
+    forwarded_for(headers, trusted).or_else(|| {
+        headers
+            .get("x-real-ip")
+            .and_then(|value| value.to_str().ok())
+            .and_then(header_ip)
+    })
+}
+
+/// The peer address, or the one its forwarding headers report when the peer
+/// is a trusted proxy. Headers from anyone else are ignored, so clients
+/// cannot pick the address access rules, rate limits and audit entries see.
+pub(crate) fn client_ip(headers: &HeaderMap, extensions: &Extensions) -> Option<IpAddr> {
+    let peer = extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(peer)| peer.ip())?;
+    match extensions.get::<TrustedProxies>() {
+        Some(trusted) if trusted.contains(peer) => forwarded_ip(headers, trusted).or(Some(peer)),
+        _ => Some(peer),
+    }
+}
+
+/// Client address, as [`client_ip`] chooses it, and user agent.
+pub(crate) fn client_info(headers: &HeaderMap, extensions: &Extensions) -> ClientInfo {
+    ClientInfo {
+        ip_address: client_ip(headers, extensions).map(|ip| ip.to_string()),
+        user_agent: headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(ToString::to_string),
+    }
+}
+
+impl FromRequestParts<()> for RequestClient {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &()) -> Result<Self, Self::Rejection> {
+        Ok(Self(client_info(&parts.headers, &parts.extensions)))
+    }
+}
+
 impl FromRequestParts<()> for Authenticated {
     type Rejection = HttpError;
 
@@ -29,24 +125,24 @@ impl FromRequestParts<()> for Authenticated {
             })?;
 
         if let Some(user) = cached_authenticated_user(parts) {
+            rls::set_current_user(user.id);
+            request_context::set_impersonator(user.impersonator);
             return Ok(Self(user));
         }
 
-        let header = parts
-            .headers
-            .typed_get::<Authorization<Bearer>>()
-            .ok_or_else(|| {
-                HttpError::from_error(AppError::unauthorized("missing Authorization header"))
-            })?;
+        let token = session_cookie::access_token(&parts.headers).ok_or_else(|| {
+            HttpError::from_error(AppError::unauthorized("missing Authorization header"))
+        })?;
 
-        let token = header.token();
         let user = app_state
             .services
             .auth
-            .authenticate(token)
+            .authenticate(&token)
             .await
             .map_err(HttpError::from_error)?;
 
+        rls::set_current_user(user.id);
+        request_context::set_impersonator(user.impersonator);
         parts.extensions.insert(user.clone());
         Ok(Self(user))
     }
@@ -63,17 +159,20 @@ impl FromRequestParts<()> for MaybeAuthenticated {
             })?;
 
         if let Some(user) = cached_authenticated_user(parts) {
+            rls::set_current_user(user.id);
+            request_context::set_impersonator(user.impersonator);
             return Ok(Self(Some(user)));
         }
 
-        if let Some(header) = parts.headers.typed_get::<Authorization<Bearer>>() {
-            let token = header.token();
+        if let Some(token) = session_cookie::access_token(&parts.headers) {
             let user = app_state
                 .services
                 .auth
-                .authenticate(token)
+                .authenticate(&token)
                 .await
                 .map_err(HttpError::from_error)?;
+            rls::set_current_user(user.id);
+            request_context::set_impersonator(user.impersonator);
             parts.extensions.insert(user.clone());
             Ok(Self(Some(user)))
         } else {
@@ -81,3 +180,52 @@ impl FromRequestParts<()> for MaybeAuthenticated {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(peer: &str, forwarded_for: &str, trusted: &[&str]) -> (HeaderMap, Extensions) {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", forwarded_for.parse().unwrap());
+        let mut extensions = Extensions::new();
+        extensions.insert(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 443)));
+        extensions.insert(TrustedProxies(
+            trusted.iter().map(|range| range.parse().unwrap()).collect(),
+        ));
+        (headers, extensions)
+    }
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn forwarding_headers_from_untrusted_peers_are_ignored() {
+        let (headers, extensions) = request("198.51.100.4", "203.0.113.7", &["10.0.0.0/8"]);
+        assert_eq!(client_ip(&headers, &extensions), Some(ip("198.51.100.4")));
+    }
+
+    #[test]
+    fn trusted_proxies_report_the_nearest_untrusted_hop() {
+        let (headers, extensions) = request(
+            "10.0.0.2",
+            "203.0.113.7, 198.51.100.4, 10.0.0.1",
+            &["10.0.0.0/8"],
+        );
+        assert_eq!(client_ip(&headers, &extensions), Some(ip("198.51.100.4")));
+    }
+
+    #[test]
+    fn hops_left_of_garbage_are_not_believed() {
+        let (headers, extensions) = request("10.0.0.2", "203.0.113.7, junk", &["10.0.0.0/8"]);
+        assert_eq!(client_ip(&headers, &extensions), Some(ip("10.0.0.2")));
+    }
+
+    #[test]
+    fn no_peer_means_no_address() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "203.0.113.7".parse().unwrap());
+        assert_eq!(client_ip(&headers, &Extensions::new()), None);
+    }
+}
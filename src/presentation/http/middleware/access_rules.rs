@@ -0,0 +1,48 @@
+// src/presentation/http/middleware/access_rules.rs
+use crate::application::error::AppError;
+use crate::application::ports::security_events::ClientInfo;
+use crate::domain::RouteGroup;
+use crate::presentation::http::error::Error as HttpError;
+use crate::presentation::http::extractors::client_info;
+use crate::presentation::http::state::HttpContext;
+use axum::{
+    body::Body,
+    extract::State,
+    http::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+/// The client as audit entries see it: the peer address, or the one a
+/// trusted proxy reports.
+pub(crate) fn request_client(req: &Request<Body>) -> ClientInfo {
+    client_info(req.headers(), req.extensions())
+}
+
+/// Middleware that applies the access rules for `group` before the request
+/// reaches authentication or the handler.
+///
+/// The client address is the one audit entries see: forwarding headers are
+/// only believed from the proxies listed in `TRUSTED_PROXIES`.
+///
+/// Usage: `axum::middleware::from_fn_with_state(RouteGroup::Admin, enforce_access_rules)`
+pub async fn enforce_access_rules(
+    State(group): State<RouteGroup>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(state) = req.extensions().get::<HttpContext>().cloned() else {
+        return HttpError::from_error(AppError::infrastructure("application state missing"))
+            .into_response();
+    };
+    let client = request_client(&req);
+    match state
+        .services
+        .access_rules
+        .check(group, &client, req.uri().path())
+        .await
+    {
+        Ok(()) => next.run(req).await,
+        Err(err) => HttpError::from_error(err).into_response(),
+    }
+}
@@ -0,0 +1,64 @@
+// src/presentation/http/middleware/body_limit.rs
+use crate::presentation::http::error::Error as HttpError;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header::CONTENT_TYPE},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+/// Middleware giving body limit rejections the usual JSON error body.
+///
+/// Extractors enforce the `DefaultBodyLimit` of their route while reading,
+/// so a handler never buffers more than the limit, but answer with a plain
+/// text 413.
+///
+/// Usage: `axum::middleware::from_fn(explain_oversized_bodies)`
+pub async fn explain_oversized_bodies(req: Request<Body>, next: Next) -> Response {
+    let response = next.run(req).await;
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE && !is_json {
+        return HttpError::payload_too_large().into_response();
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::explain_oversized_bodies;
+    use axum::{
+        Router,
+        body::{Body, Bytes},
+        extract::DefaultBodyLimit,
+        http::{Request, StatusCode},
+        routing::post,
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn oversized_bodies_get_a_json_413() {
+        let app = Router::new()
+            .route(
+                "/",
+                post(|body: Bytes| async move { body.len().to_string() }),
+            )
+            .layer(DefaultBodyLimit::max(8))
+            .layer(axum::middleware::from_fn(explain_oversized_bodies));
+        let call = |body: &'static str| {
+            app.clone()
+                .oneshot(Request::post("/").body(Body::from(body)).unwrap())
+        };
+
+        assert_eq!(call("12345678").await.unwrap().status(), StatusCode::OK);
+
+        let resp = call("123456789").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "payload_too_large");
+    }
+}
@@ -0,0 +1,137 @@
+// src/presentation/http/middleware/cors.rs
+use crate::config::CorsSettings;
+use axum::http::{HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+fn any(values: &[String]) -> bool {
+    values.iter().any(|v| v == "*")
+}
+
+/// Build the CORS layer answering preflights and decorating responses.
+///
+/// Browsers reject `*` in responses to credentialed requests, so with
+/// credentials allowed a wildcard method or header list echoes what the
+/// preflight asked for instead. Credentials are never allowed for any
+/// origin.
+pub fn cors_layer(settings: &CorsSettings) -> CorsLayer {
+    let any_origin = any(&settings.allowed_origins);
+    let credentials = settings.allow_credentials && !any_origin;
+
+    let origins = if any_origin {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(
+            settings
+                .allowed_origins
+                .iter()
+                .filter_map(|origin| origin.parse::<HeaderValue>().ok()),
+        )
+    };
+    let methods = match (any(&settings.allowed_methods), credentials) {
+        (true, true) => AllowMethods::mirror_request(),
+        (true, false) => AllowMethods::any(),
+        (false, _) => AllowMethods::list(
+            settings
+                .allowed_methods
+                .iter()
+                .filter_map(|method| Method::from_bytes(method.as_bytes()).ok()),
+        ),
+    };
+    let headers = match (any(&settings.allowed_headers), credentials) {
+        (true, true) => AllowHeaders::mirror_request(),
+        (true, false) => AllowHeaders::any(),
+        (false, _) => AllowHeaders::list(
+            settings
+                .allowed_headers
+                .iter()
+                .filter_map(|header| HeaderName::from_bytes(header.as_bytes()).ok()),
+        ),
+    };
+
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods(methods)
+        .allow_headers(headers)
+        .allow_credentials(credentials)
+        .max_age(settings.max_age)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cors_layer;
+    use crate::config::CorsSettings;
+    use axum::{
+        Router,
+        body::Body,
+        http::{HeaderMap, Request, StatusCode},
+        routing::get,
+    };
+    use tower::ServiceExt;
+
+    async fn preflight(settings: &CorsSettings, origin: &str, headers: &str) -> HeaderMap {
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(cors_layer(settings));
+        let req = Request::builder()
+            .method("OPTIONS")
+            .uri("/")
+            .header("origin", origin)
+            .header("access-control-request-method", "POST")
+            .header("access-control-request-headers", headers)
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        resp.headers().clone()
+    }
+
+    fn settings(origins: &[&str], headers: &[&str], credentials: bool) -> CorsSettings {
+        CorsSettings {
+            allowed_origins: origins.iter().map(ToString::to_string).collect(),
+            allowed_headers: headers.iter().map(ToString::to_string).collect(),
+            allow_credentials: credentials,
+            ..CorsSettings::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn preflights_from_listed_origins_are_allowed() {
+        let settings = settings(&["https://app.example.com"], &["content-type"], false);
+
+        let headers = preflight(&settings, "https://app.example.com", "content-type").await;
+        assert_eq!(
+            headers["access-control-allow-origin"],
+            "https://app.example.com"
+        );
+        assert!(
+            headers["access-control-allow-methods"]
+                .to_str()
+                .unwrap()
+                .contains("POST")
+        );
+        assert_eq!(headers["access-control-allow-headers"], "content-type");
+        assert_eq!(headers["access-control-max-age"], "3600");
+        assert!(!headers.contains_key("access-control-allow-credentials"));
+
+        let headers = preflight(&settings, "https://evil.example.net", "content-type").await;
+        assert!(!headers.contains_key("access-control-allow-origin"));
+    }
+
+    #[tokio::test]
+    async fn credentialed_preflights_echo_requested_headers() {
+        let settings = settings(&["https://app.example.com"], &["*"], true);
+
+        let headers = preflight(&settings, "https://app.example.com", "x-csrf-token").await;
+        assert_eq!(headers["access-control-allow-credentials"], "true");
+        assert_eq!(headers["access-control-allow-headers"], "x-csrf-token");
+    }
+
+    #[tokio::test]
+    async fn any_origin_never_allows_credentials() {
+        let settings = settings(&["*"], &["*"], true);
+
+        let headers = preflight(&settings, "https://elsewhere.example", "x-token").await;
+        assert_eq!(headers["access-control-allow-origin"], "*");
+        assert!(!headers.contains_key("access-control-allow-credentials"));
+    }
+}
@@ -0,0 +1,202 @@
+// src/presentation/http/middleware/csrf.rs
+use crate::application::error::AppError;
+use crate::presentation::http::error::Error as HttpError;
+use crate::presentation::http::session_cookie::{self, CSRF_HEADER};
+use axum::{
+    body::Body,
+    extract::State,
+    http::{
+        HeaderMap, Request, Uri,
+        header::{HOST, ORIGIN, REFERER},
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+/// Which cookie-authenticated writes the CSRF layer trusts.
+#[derive(Debug, Clone, Default)]
+pub struct CsrfPolicy {
+    trusted_origins: Arc<[String]>,
+    exempt_paths: Arc<[String]>,
+}
+
+impl CsrfPolicy {
+    /// `trusted_origins` are `scheme://host[:port]` values besides the API's
+    /// own host (a `*` entry is ignored); `exempt_paths` are path prefixes
+    /// that skip the checks entirely.
+    #[must_use]
+    pub fn new(trusted_origins: Vec<String>, exempt_paths: Vec<String>) -> Self {
+        Self {
+            trusted_origins: trusted_origins
+                .into_iter()
+                .map(|origin| origin.trim_end_matches('/').to_string())
+                .filter(|origin| origin != "*")
+                .collect(),
+            exempt_paths: exempt_paths.into(),
+        }
+    }
+
+    fn is_exempt(&self, path: &str) -> bool {
+        self.exempt_paths
+            .iter()
+            .any(|prefix| path.starts_with(prefix.as_str()))
+    }
+
+    /// `Origin`, else the origin of `Referer`, must be trusted when sent.
+    /// Requests with neither rely on the token check alone.
+    fn origin_allowed(&self, headers: &HeaderMap) -> bool {
+        let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+        let origin = match (header(ORIGIN), header(REFERER)) {
+            (Some(origin), _) => Some(origin.to_string()),
+            (None, Some(referer)) => Some(origin_of(referer).unwrap_or_default()),
+            (None, None) => None,
+        };
+        origin.is_none_or(|origin| self.is_trusted(&origin, header(HOST)))
+    }
+
+    fn is_trusted(&self, origin: &str, host: Option<&str>) -> bool {
+        let same_host = || {
+            host.zip(origin.parse::<Uri>().ok())
+                .is_some_and(|(host, uri)| uri.authority().is_some_and(|a| a.as_str() == host))
+        };
+        self.trusted_origins.iter().any(|trusted| trusted == origin) || same_host()
+    }
+}
+
+fn origin_of(url: &str) -> Option<String> {
+    let uri = url.parse::<Uri>().ok()?;
+    Some(format!("{}://{}", uri.scheme_str()?, uri.authority()?))
+}
+
+/// Middleware protecting cookie sessions against cross-site request forgery.
+///
+/// Mutating requests that would authenticate with the session cookie must
+/// come from a trusted `Origin`/`Referer` and repeat the CSRF cookie in
+/// `X-CSRF-Token` (double submit); a cross-site form can send the cookies but
+/// cannot read them. Safe methods, bearer-token requests, requests without a
+/// session cookie and exempt paths pass through untouched.
+///
+/// Usage: `axum::middleware::from_fn_with_state(policy, require_csrf_token)`
+pub async fn require_csrf_token(
+    State(policy): State<CsrfPolicy>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let headers = req.headers();
+    if req.method().is_safe()
+        || session_cookie::has_bearer_token(headers)
+        || session_cookie::session_token(headers).is_none()
+        || policy.is_exempt(req.uri().path())
+    {
+        return next.run(req).await;
+    }
+
+    if !policy.origin_allowed(headers) {
+        return HttpError::from_error(AppError::forbidden("cross-origin request rejected"))
+            .into_response();
+    }
+
+    let submitted = headers
+        .get(CSRF_HEADER)
+        .and_then(|value| value.to_str().ok());
+    match (session_cookie::csrf_token(headers), submitted) {
+        (Some(expected), Some(submitted)) if tokens_match(&expected, submitted) => {
+            next.run(req).await
+        }
+        _ => HttpError::from_error(AppError::forbidden("missing or invalid CSRF token"))
+            .into_response(),
+    }
+}
+
+/// Compare without short-circuiting so timing does not reveal the token.
+fn tokens_match(expected: &str, submitted: &str) -> bool {
+    expected.len() == submitted.len()
+        && expected
+            .bytes()
+            .zip(submitted.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CsrfPolicy, require_csrf_token};
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        routing::post,
+    };
+    use tower::ServiceExt;
+
+    const COOKIES: &str = "mokkan_session=tok; mokkan_csrf=abc";
+
+    async fn call(uri: &str, headers: &[(&str, &str)]) -> StatusCode {
+        let policy = CsrfPolicy::new(
+            vec!["https://app.example.com/".into()],
+            vec!["/hooks/".into()],
+        );
+        let app = Router::new()
+            .route("/", post(|| async { "ok" }))
+            .route("/hooks/in", post(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(
+                policy,
+                require_csrf_token,
+            ));
+        let mut req = Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("host", "api.example.com");
+        for (name, value) in headers {
+            req = req.header(*name, *value);
+        }
+        app.oneshot(req.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn cookie_session_needs_matching_header() {
+        let cookie = ("cookie", COOKIES);
+        assert_eq!(call("/", &[cookie]).await, StatusCode::FORBIDDEN);
+        assert_eq!(
+            call("/", &[cookie, ("x-csrf-token", "abd")]).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            call("/", &[cookie, ("x-csrf-token", "abc")]).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn untrusted_origins_are_rejected_even_with_a_token() {
+        let base = [("cookie", COOKIES), ("x-csrf-token", "abc")];
+        let with = |name, value| [base[0], base[1], (name, value)];
+
+        let evil = with("origin", "https://evil.example.net");
+        assert_eq!(call("/", &evil).await, StatusCode::FORBIDDEN);
+        let evil_referer = with("referer", "https://evil.example.net/page");
+        assert_eq!(call("/", &evil_referer).await, StatusCode::FORBIDDEN);
+        let opaque = with("origin", "null");
+        assert_eq!(call("/", &opaque).await, StatusCode::FORBIDDEN);
+
+        let configured = with("origin", "https://app.example.com");
+        assert_eq!(call("/", &configured).await, StatusCode::OK);
+        let same_host = with("referer", "https://api.example.com/admin");
+        assert_eq!(call("/", &same_host).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn bearer_anonymous_and_exempt_requests_pass() {
+        let cookie = ("cookie", COOKIES);
+        assert_eq!(
+            call("/", &[cookie, ("authorization", "Bearer token")]).await,
+            StatusCode::OK
+        );
+        assert_eq!(call("/", &[]).await, StatusCode::OK);
+        assert_eq!(call("/hooks/in", &[cookie]).await, StatusCode::OK);
+    }
+}
@@ -0,0 +1,21 @@
+// src/presentation/http/middleware/ephemeral.rs
+use axum::{
+    body::Body,
+    http::{HeaderName, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+
+/// Header marking responses from an ephemeral instance, whose data is
+/// demo content that is thrown away on restart and reset daily.
+pub const EPHEMERAL_INSTANCE_HEADER: HeaderName = HeaderName::from_static("x-ephemeral-instance");
+
+/// Middleware that adds [`EPHEMERAL_INSTANCE_HEADER`] to every response, so
+/// clients and reviewers can tell a preview deployment from a real one.
+pub async fn watermark(req: Request<Body>, next: Next) -> Response {
+    let mut response = next.run(req).await;
+    response
+        .headers_mut()
+        .insert(EPHEMERAL_INSTANCE_HEADER, HeaderValue::from_static("true"));
+    response
+}
@@ -0,0 +1,165 @@
+// src/presentation/http/middleware/honeypot.rs
+use crate::config::HoneypotSettings;
+use crate::presentation::http::extractors::client_ip;
+use crate::presentation::http::middleware::access_rules::request_client;
+use crate::presentation::http::state::HttpContext;
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// A client is logged, and blocked, at most once per this window.
+const HIT_WINDOW: Duration = Duration::from_mins(1);
+
+/// Clients remembered for `HIT_WINDOW`; older entries are dropped beyond
+/// this.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Responses held back at once. Further hits are answered immediately so
+/// scanners cannot tie up the server with its own tarpit.
+const MAX_TARPITTED: usize = 64;
+
+/// Decoy paths and what to do with clients that request them.
+pub struct Honeypot {
+    paths: HashSet<String>,
+    block_for: Duration,
+    tarpit: Duration,
+    tarpitted: AtomicUsize,
+    recent: Mutex<HashMap<IpAddr, Instant>>,
+}
+
+impl Honeypot {
+    #[must_use]
+    pub fn new(settings: HoneypotSettings) -> Self {
+        Self {
+            paths: settings
+                .paths
+                .into_iter()
+                .map(|path| path.to_ascii_lowercase())
+                .collect(),
+            block_for: settings.block_for,
+            tarpit: settings.tarpit,
+            tarpitted: AtomicUsize::new(0),
+            recent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn is_decoy(&self, path: &str) -> bool {
+        self.paths.contains(&path.to_ascii_lowercase())
+    }
+
+    /// Record a hit from `ip`; `false` if it already hit within the window.
+    fn first_hit(&self, ip: IpAddr) -> bool {
+        let now = Instant::now();
+        let mut recent = self.recent.lock().unwrap_or_else(PoisonError::into_inner);
+        if recent
+            .get(&ip)
+            .is_some_and(|seen| now.duration_since(*seen) < HIT_WINDOW)
+        {
+            return false;
+        }
+        if recent.len() >= MAX_TRACKED_CLIENTS {
+            recent.retain(|_, seen| now.duration_since(*seen) < HIT_WINDOW);
+        }
+        recent.insert(ip, now);
+        true
+    }
+
+    async fn tarpit(&self) {
+        if self.tarpit.is_zero() {
+            return;
+        }
+        if self.tarpitted.fetch_add(1, Ordering::Relaxed) < MAX_TARPITTED {
+            tokio::time::sleep(self.tarpit).await;
+        }
+        self.tarpitted.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Middleware that answers requests for decoy paths with a plain 404.
+///
+/// The client is logged and, when configured, blocked from every route
+/// through a temporary access rule, and the response is held back. The
+/// client is the connecting address unless a trusted proxy reports another,
+/// so a forged `X-Forwarded-For` cannot get someone else blocked.
+///
+/// Usage: `axum::middleware::from_fn_with_state(Arc::new(Honeypot::new(settings)), trap_scanners)`
+pub async fn trap_scanners(
+    State(honeypot): State<Arc<Honeypot>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if !honeypot.is_decoy(req.uri().path()) {
+        return next.run(req).await;
+    }
+    let client = request_client(&req);
+    let ip = client_ip(req.headers(), req.extensions());
+
+    if let Some(ip) = ip
+        && honeypot.first_hit(ip)
+    {
+        let path = req.uri().path();
+        tracing::warn!(
+            %ip,
+            path,
+            user_agent = client.user_agent.as_deref(),
+            "honeypot path requested"
+        );
+        if !honeypot.block_for.is_zero()
+            && let Some(state) = req.extensions().get::<HttpContext>()
+            && let Err(err) = state
+                .services
+                .access_rules
+                .block_temporarily(
+                    ip,
+                    honeypot.block_for,
+                    &format!("honeypot: {path}"),
+                    &client,
+                )
+                .await
+        {
+            tracing::warn!(error = %err, %ip, "failed to block honeypot client");
+        }
+    }
+
+    honeypot.tarpit().await;
+    StatusCode::NOT_FOUND.into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn honeypot() -> Honeypot {
+        Honeypot::new(HoneypotSettings {
+            paths: vec!["/wp-login.php".into(), "/.env".into()],
+            block_for: Duration::from_hours(1),
+            tarpit: Duration::ZERO,
+        })
+    }
+
+    #[test]
+    fn decoys_match_regardless_of_case() {
+        let honeypot = honeypot();
+        assert!(honeypot.is_decoy("/WP-Login.php"));
+        assert!(honeypot.is_decoy("/.env"));
+        assert!(!honeypot.is_decoy("/.env.example"));
+    }
+
+    #[test]
+    fn repeat_hits_within_the_window_are_not_first() {
+        let honeypot = honeypot();
+        let ip: IpAddr = "192.0.2.9".parse().unwrap();
+        assert!(honeypot.first_hit(ip));
+        assert!(!honeypot.first_hit(ip));
+        assert!(honeypot.first_hit("192.0.2.10".parse().unwrap()));
+    }
+}
@@ -1,3 +1,16 @@
 // src/presentation/http/middleware/mod.rs
+pub mod access_rules;
+pub mod body_limit;
+pub mod cors;
+pub mod csrf;
+pub mod ephemeral;
+pub mod honeypot;
 pub mod rate_limit;
+pub mod read_only;
+pub mod request_context;
 pub mod require_capabilities;
+pub mod route_rate_limit;
+pub mod row_level_security;
+pub mod security_headers;
+pub mod tenant;
+pub mod trace_context;
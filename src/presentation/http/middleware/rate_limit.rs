@@ -1,17 +1,31 @@
 // src/presentation/http/middleware/rate_limit.rs
+use crate::presentation::http::extractors::client_ip;
 use ::governor::middleware::NoOpMiddleware;
-use axum::body::Body;
-use std::sync::OnceLock;
+use axum::{body::Body, http::Request};
+use std::{net::IpAddr, sync::OnceLock};
 use tower_governor::{
-    GovernorLayer, governor::GovernorConfigBuilder, key_extractor::SmartIpKeyExtractor,
+    GovernorError, GovernorLayer, governor::GovernorConfigBuilder, key_extractor::KeyExtractor,
 };
 
+/// Keys the governor by client address, resolved as for access rules so
+/// forwarding headers only count when a trusted proxy sent them.
+#[derive(Debug, Clone, Copy)]
+pub struct ClientIpKeyExtractor;
+
+impl KeyExtractor for ClientIpKeyExtractor {
+    type Key = IpAddr;
+
+    fn extract<T>(&self, req: &Request<T>) -> Result<Self::Key, GovernorError> {
+        client_ip(req.headers(), req.extensions()).ok_or(GovernorError::UnableToExtractKey)
+    }
+}
+
 /// Build the shared rate-limiter layer used by the HTTP router.
 ///
 /// # Panics
 /// Panics if the hard-coded governor configuration is invalid.
-pub fn layer() -> GovernorLayer<SmartIpKeyExtractor, NoOpMiddleware, Body> {
-    static RATE_LIMITER: OnceLock<GovernorLayer<SmartIpKeyExtractor, NoOpMiddleware, Body>> =
+pub fn layer() -> GovernorLayer<ClientIpKeyExtractor, NoOpMiddleware, Body> {
+    static RATE_LIMITER: OnceLock<GovernorLayer<ClientIpKeyExtractor, NoOpMiddleware, Body>> =
         OnceLock::new();
 
     RATE_LIMITER
@@ -20,7 +34,7 @@ pub fn layer() -> GovernorLayer<SmartIpKeyExtractor, NoOpMiddleware, Body> {
             builder.per_second(10);
             builder.burst_size(20);
             let config = builder
-                .key_extractor(SmartIpKeyExtractor)
+                .key_extractor(ClientIpKeyExtractor)
                 .finish()
                 .expect("valid rate limit configuration");
 
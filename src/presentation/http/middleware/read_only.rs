@@ -0,0 +1,43 @@
+// src/presentation/http/middleware/read_only.rs
+use crate::application::error::AppError;
+use crate::presentation::http::error::Error as HttpError;
+use crate::presentation::http::state::HttpContext;
+use axum::{
+    body::Body,
+    http::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+/// Paths served in read-only mode despite a mutating method: they only
+/// touch the session store, only read, or switch read-only mode off.
+const ALWAYS_ALLOWED: &[&str] = &[
+    "/api/v1/admin/read-only",
+    "/api/v1/admin/static-export",
+    "/api/v1/articles/batch-get",
+    "/api/v1/auth/introspect",
+    "/api/v1/auth/login",
+    "/api/v1/auth/logout",
+    "/api/v1/auth/refresh",
+    "/api/v1/auth/revoke",
+    "/api/v1/auth/token",
+    "/api/v1/users/batch-get",
+];
+
+/// Middleware that answers mutating requests with 503 while read-only mode
+/// is on. The command services refuse writes as well, so this is the first
+/// of two checks rather than the only one.
+pub async fn refuse_writes(req: Request<Body>, next: Next) -> Response {
+    if req.method().is_safe() || ALWAYS_ALLOWED.contains(&req.uri().path()) {
+        return next.run(req).await;
+    }
+    let Some(state) = req.extensions().get::<HttpContext>() else {
+        return HttpError::from_error(AppError::infrastructure("application state missing"))
+            .into_response();
+    };
+    if state.services.read_only.is_enabled() {
+        return HttpError::from_error(AppError::unavailable("the service is in read-only mode"))
+            .into_response();
+    }
+    next.run(req).await
+}
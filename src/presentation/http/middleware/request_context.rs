@@ -0,0 +1,15 @@
+// src/presentation/http/middleware/request_context.rs
+use crate::application::request_context::{self, RequestContext};
+use crate::presentation::http::extractors::client_info;
+use axum::{body::Body, http::Request, middleware::Next, response::Response};
+
+/// Middleware that runs the request inside a [`request_context::scope`]
+/// carrying the client's address and user agent, for audit logging.
+///
+/// Usage: `axum::middleware::from_fn(capture_request_context)`
+pub async fn capture_request_context(req: Request<Body>, next: Next) -> Response {
+    let context = RequestContext {
+        client: client_info(req.headers(), req.extensions()),
+    };
+    request_context::scope(context, next.run(req)).await
+}
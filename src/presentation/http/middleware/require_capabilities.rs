@@ -1,6 +1,9 @@
 // src/presentation/http/middleware/require_capabilities.rs
 use crate::application::error::AppError;
+use crate::application::request_context;
+use crate::infrastructure::rls;
 use crate::presentation::http::error::Error as HttpError;
+use crate::presentation::http::session_cookie;
 use crate::presentation::http::state::HttpContext;
 use axum::{
     body::Body,
@@ -8,7 +11,6 @@ use axum::{
     middleware::Next,
     response::{IntoResponse, Response},
 };
-use headers::{Authorization, HeaderMapExt, authorization::Bearer};
 
 /// Middleware function that enforces a single capability (resource, action).
 ///
@@ -19,17 +21,17 @@ pub async fn require_capability(
     resource: &'static str,
     action: &'static str,
 ) -> Response {
-    if let Some(header) = req.headers().typed_get::<Authorization<Bearer>>() {
-        let token = header.token();
-
+    if let Some(token) = session_cookie::access_token(req.headers()) {
         if let Some(state) = req.extensions().get::<HttpContext>() {
             match state
                 .services
                 .auth
-                .authenticate_and_authorize(token, resource, action)
+                .authenticate_and_authorize(&token, resource, action)
                 .await
             {
                 Ok(user) => {
+                    rls::set_current_user(user.id);
+                    request_context::set_impersonator(user.impersonator);
                     req.extensions_mut().insert(user);
                     next.run(req).await
                 }
@@ -0,0 +1,151 @@
+// src/presentation/http/middleware/route_rate_limit.rs
+use crate::application::error::AppError;
+use crate::config::RouteRateLimitSettings;
+use crate::presentation::http::error::Error as HttpError;
+use crate::presentation::http::middleware::access_rules::request_client;
+use crate::presentation::http::session_cookie;
+use crate::presentation::http::state::HttpContext;
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Method, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+/// Routes sharing one request budget per client.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitedRoutes {
+    /// Everything under `/api/v1/auth`.
+    Auth,
+    /// Mutating requests to the article routes; reads are not counted.
+    ArticleWrites,
+    /// Everything under `/api/v1/admin`.
+    Admin,
+}
+
+impl LimitedRoutes {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Auth => "auth",
+            Self::ArticleWrites => "article_writes",
+            Self::Admin => "admin",
+        }
+    }
+
+    fn counts(self, method: &Method) -> bool {
+        self != Self::ArticleWrites || !method.is_safe()
+    }
+}
+
+/// The budget for one group of routes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RouteRateLimit {
+    pub routes: LimitedRoutes,
+    pub per_minute: u32,
+}
+
+impl RouteRateLimit {
+    /// The configured budget for `routes`; `None` when its limit is lifted.
+    #[must_use]
+    pub const fn from_settings(
+        routes: LimitedRoutes,
+        settings: &RouteRateLimitSettings,
+    ) -> Option<Self> {
+        let per_minute = match routes {
+            LimitedRoutes::Auth => settings.auth,
+            LimitedRoutes::ArticleWrites => settings.article_writes,
+            LimitedRoutes::Admin => settings.admin,
+        };
+        if per_minute == 0 {
+            None
+        } else {
+            Some(Self { routes, per_minute })
+        }
+    }
+}
+
+/// Middleware that answers with 429 and `Retry-After` once a client has
+/// sent more than its budget for the route group within a minute.
+///
+/// Callers with a valid token are counted per user, others per client
+/// address, resolved as for access rules. Requests with neither are not counted. The counters live behind the
+/// rate-limit port, so they are shared across instances when Redis is
+/// configured; if the store fails the request is let through.
+///
+/// Usage: `axum::middleware::from_fn_with_state(limit, limit_requests)`
+pub async fn limit_requests(
+    State(limit): State<RouteRateLimit>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    if !limit.routes.counts(req.method()) {
+        return next.run(req).await;
+    }
+    let Some(state) = req.extensions().get::<HttpContext>().cloned() else {
+        return HttpError::from_error(AppError::infrastructure("application state missing"))
+            .into_response();
+    };
+    let Some(client) = client_key(&state, &mut req).await else {
+        return next.run(req).await;
+    };
+    let key = format!("route:{}:{client}", limit.routes.as_str());
+    match state
+        .services
+        .request_limits
+        .check(&key, limit.per_minute)
+        .await
+    {
+        Ok(()) => next.run(req).await,
+        Err(err @ AppError::TooManyRequests { .. }) => HttpError::from_error(err).into_response(),
+        Err(err) => {
+            tracing::warn!(error = %err, "rate limit unavailable; letting request through");
+            next.run(req).await
+        }
+    }
+}
+
+/// `user:<id>` for a valid token, else `ip:<address>`. The user is kept on
+/// the request so the handler does not authenticate it again.
+async fn client_key(state: &HttpContext, req: &mut Request<Body>) -> Option<String> {
+    if let Some(token) = session_cookie::access_token(req.headers())
+        && let Ok(user) = state.services.auth.authenticate(&token).await
+    {
+        let key = format!("user:{}", i64::from(user.id));
+        req.extensions_mut().insert(user);
+        return Some(key);
+    }
+    request_client(req).ip_address.map(|ip| format!("ip:{ip}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_writes_count_against_article_limits() {
+        assert!(!LimitedRoutes::ArticleWrites.counts(&Method::GET));
+        assert!(LimitedRoutes::ArticleWrites.counts(&Method::PUT));
+        assert!(LimitedRoutes::Auth.counts(&Method::GET));
+    }
+
+    #[test]
+    fn zero_lifts_a_limit() {
+        let settings = RouteRateLimitSettings {
+            auth: 30,
+            article_writes: 0,
+            admin: 120,
+        };
+        assert_eq!(
+            RouteRateLimit::from_settings(LimitedRoutes::Auth, &settings),
+            Some(RouteRateLimit {
+                routes: LimitedRoutes::Auth,
+                per_minute: 30,
+            })
+        );
+        assert_eq!(
+            RouteRateLimit::from_settings(LimitedRoutes::ArticleWrites, &settings),
+            None
+        );
+    }
+}
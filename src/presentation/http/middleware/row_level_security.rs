@@ -0,0 +1,13 @@
+// src/presentation/http/middleware/row_level_security.rs
+use crate::infrastructure::rls;
+use axum::{body::Body, http::Request, middleware::Next, response::Response};
+
+/// Middleware that opens a row-level security scope for the request.
+///
+/// Authentication fills in the acting user (see
+/// [`rls::set_current_user`]); repositories then forward it to Postgres.
+///
+/// Usage: `axum::middleware::from_fn(row_level_security_context)`
+pub async fn row_level_security_context(req: Request<Body>, next: Next) -> Response {
+    rls::scope(next.run(req)).await
+}
@@ -0,0 +1,126 @@
+// src/presentation/http/middleware/security_headers.rs
+use axum::{
+    body::Body,
+    extract::State,
+    http::{
+        HeaderValue, Request,
+        header::{
+            CONTENT_SECURITY_POLICY, CONTENT_TYPE, REFERRER_POLICY, STRICT_TRANSPORT_SECURITY,
+            X_CONTENT_TYPE_OPTIONS,
+        },
+    },
+    middleware::Next,
+    response::Response,
+};
+
+/// Policy for rendered article HTML: images and inline styles only, so
+/// markup that slipped through rendering cannot run scripts or submit forms.
+pub const HTML_CONTENT_SECURITY_POLICY: &str = "default-src 'none'; img-src 'self' https: data:; \
+     style-src 'unsafe-inline'; base-uri 'none'; form-action 'none'; frame-ancestors 'self'";
+
+/// Headers added to every response that does not set them itself.
+#[derive(Debug, Clone)]
+pub struct SecurityHeaders {
+    hsts: Option<HeaderValue>,
+}
+
+impl SecurityHeaders {
+    /// `hsts_max_age_secs` of zero sends no `Strict-Transport-Security`.
+    #[must_use]
+    pub fn new(hsts_max_age_secs: u64) -> Self {
+        Self {
+            hsts: (hsts_max_age_secs > 0)
+                .then(|| format!("max-age={hsts_max_age_secs}; includeSubDomains"))
+                .and_then(|value| HeaderValue::try_from(value).ok()),
+        }
+    }
+}
+
+/// Middleware adding HSTS, `nosniff`, `Referrer-Policy` and, on HTML
+/// responses, a restrictive `Content-Security-Policy`. Headers a handler
+/// already set, such as the media download's own policy, are kept.
+///
+/// Usage: `axum::middleware::from_fn_with_state(SecurityHeaders::new(..), set_security_headers)`
+pub async fn set_security_headers(
+    State(policy): State<SecurityHeaders>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let mut response = next.run(req).await;
+    let is_html = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"text/html"));
+    let headers = response.headers_mut();
+    headers
+        .entry(X_CONTENT_TYPE_OPTIONS)
+        .or_insert(HeaderValue::from_static("nosniff"));
+    headers
+        .entry(REFERRER_POLICY)
+        .or_insert(HeaderValue::from_static("no-referrer"));
+    if let Some(hsts) = policy.hsts {
+        headers.entry(STRICT_TRANSPORT_SECURITY).or_insert(hsts);
+    }
+    if is_html {
+        headers
+            .entry(CONTENT_SECURITY_POLICY)
+            .or_insert(HeaderValue::from_static(HTML_CONTENT_SECURITY_POLICY));
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HTML_CONTENT_SECURITY_POLICY, SecurityHeaders, set_security_headers};
+    use axum::{
+        Router,
+        body::Body,
+        http::{HeaderMap, Request, header::CONTENT_SECURITY_POLICY},
+        response::Html,
+        routing::get,
+    };
+    use tower::ServiceExt;
+
+    async fn respond(policy: SecurityHeaders, uri: &str) -> HeaderMap {
+        let app = Router::new()
+            .route("/json", get(|| async { "{}" }))
+            .route("/html", get(|| async { Html("<p>hi</p>") }))
+            .route(
+                "/own",
+                get(|| async { ([(CONTENT_SECURITY_POLICY, "sandbox")], Html("<p>hi</p>")) }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                policy,
+                set_security_headers,
+            ));
+        let req = Request::get(uri).body(Body::empty()).unwrap();
+        app.oneshot(req).await.unwrap().headers().clone()
+    }
+
+    #[tokio::test]
+    async fn every_response_gets_the_standard_headers() {
+        let headers = respond(SecurityHeaders::new(600), "/json").await;
+        assert_eq!(headers["x-content-type-options"], "nosniff");
+        assert_eq!(headers["referrer-policy"], "no-referrer");
+        assert_eq!(
+            headers["strict-transport-security"],
+            "max-age=600; includeSubDomains"
+        );
+        assert!(!headers.contains_key("content-security-policy"));
+
+        let without_hsts = respond(SecurityHeaders::new(0), "/json").await;
+        assert!(!without_hsts.contains_key("strict-transport-security"));
+    }
+
+    #[tokio::test]
+    async fn html_responses_get_a_content_security_policy() {
+        let html = respond(SecurityHeaders::new(0), "/html").await;
+        assert_eq!(
+            html["content-security-policy"],
+            HTML_CONTENT_SECURITY_POLICY
+        );
+
+        let own = respond(SecurityHeaders::new(0), "/own").await;
+        assert_eq!(own["content-security-policy"], "sandbox");
+    }
+}
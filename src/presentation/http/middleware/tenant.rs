@@ -0,0 +1,100 @@
+// src/presentation/http/middleware/tenant.rs
+use crate::application::error::AppError;
+use crate::infrastructure::tenancy::{self, TenantSchema};
+use crate::presentation::http::error::Error as HttpError;
+use axum::{
+    body::Body,
+    extract::State,
+    http::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+/// Header carrying the tenant a request is addressed to.
+pub const TENANT_HEADER: &str = "x-tenant-id";
+
+/// Middleware that resolves `X-Tenant-Id` against the configured tenants and
+/// runs the rest of the request inside that tenant's schema scope.
+///
+/// Requests without the header use the default `public` schema; ids that are
+/// not configured are rejected with `404` so tenant names cannot be probed.
+///
+/// Usage: `axum::middleware::from_fn_with_state(tenants, resolve_tenant)`
+pub async fn resolve_tenant(
+    State(tenants): State<Arc<[TenantSchema]>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(value) = req.headers().get(TENANT_HEADER) else {
+        return next.run(req).await;
+    };
+
+    let tenant = value
+        .to_str()
+        .ok()
+        .and_then(|id| tenants.iter().find(|tenant| tenant.id() == id.trim()))
+        .cloned();
+
+    match tenant {
+        Some(tenant) => tenancy::scope(tenant, next.run(req)).await,
+        None => HttpError::from_error(AppError::not_found("unknown tenant")).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TENANT_HEADER, resolve_tenant};
+    use crate::infrastructure::tenancy::{self, TenantSchema};
+    use axum::{Router, body::Body, http::Request, http::StatusCode, routing::get};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn router() -> Router {
+        let tenants: Arc<[TenantSchema]> = Arc::from(vec![TenantSchema::new("acme").unwrap()]);
+        Router::new()
+            .route(
+                "/",
+                get(|| async {
+                    tenancy::current()
+                        .map(|t| t.schema_name())
+                        .unwrap_or_default()
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                tenants,
+                resolve_tenant,
+            ))
+    }
+
+    async fn call(tenant: Option<&str>) -> (StatusCode, String) {
+        let mut req = Request::builder().uri("/");
+        if let Some(tenant) = tenant {
+            req = req.header(TENANT_HEADER, tenant);
+        }
+        let resp = router()
+            .oneshot(req.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = resp.status();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn known_tenant_is_scoped_for_the_handler() {
+        assert_eq!(
+            call(Some("acme")).await,
+            (StatusCode::OK, "tenant_acme".to_string())
+        );
+        assert_eq!(call(None).await, (StatusCode::OK, String::new()));
+    }
+
+    #[tokio::test]
+    async fn unknown_tenant_is_rejected() {
+        let (status, _) = call(Some("globex")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
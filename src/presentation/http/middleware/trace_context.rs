@@ -0,0 +1,53 @@
+// src/presentation/http/middleware/trace_context.rs
+//! Request spans for [`tower_http::trace::TraceLayer`] that continue the
+//! caller's W3C trace context, so spans exported for a request join the
+//! trace our gateway started.
+use axum::extract::MatchedPath;
+use axum::http::{Request, Response};
+use std::time::Duration;
+use tower_http::trace::{DefaultOnResponse, OnResponse};
+use tracing::{Span, field::Empty};
+
+/// Header carrying the caller's trace and span ids.
+pub const TRACEPARENT: &str = "traceparent";
+
+/// Span for one request, named after the matched route.
+///
+/// Usage: `TraceLayer::new_for_http().make_span_with(make_span)`
+pub fn make_span<B>(request: &Request<B>) -> Span {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(MatchedPath::as_str);
+    let name = route.map_or_else(
+        || request.method().to_string(),
+        |route| format!("{} {route}", request.method()),
+    );
+    let traceparent = request
+        .headers()
+        .get(TRACEPARENT)
+        .and_then(|value| value.to_str().ok());
+    tracing::debug_span!(
+        "request",
+        otel.name = name,
+        otel.kind = "server",
+        http.request.method = %request.method(),
+        http.route = route,
+        url.path = request.uri().path(),
+        traceparent,
+        http.response.status_code = Empty,
+        otel.status_code = Empty,
+    )
+}
+
+/// Record the status on the request span, failing it on server errors.
+///
+/// Usage: `TraceLayer::new_for_http().on_response(record_response)`
+pub fn record_response<B>(response: &Response<B>, latency: Duration, span: &Span) {
+    let status = response.status();
+    span.record("http.response.status_code", status.as_u16());
+    if status.is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+    DefaultOnResponse::default().on_response(response, latency, span);
+}
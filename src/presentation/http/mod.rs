@@ -1,8 +1,11 @@
 // src/presentation/http/mod.rs
+pub mod conditional;
 pub mod controllers;
 pub mod error;
 pub mod extractors;
 pub mod middleware;
 pub mod openapi;
+pub mod profiles;
 pub mod routes;
+pub mod session_cookie;
 pub mod state;
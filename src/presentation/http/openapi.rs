@@ -1,5 +1,7 @@
 // src/presentation/http/openapi.rs
 // Minimal OpenAPI helpers used by the HTTP layer and tests.
+pub mod error_catalog;
+pub mod examples;
 pub mod openapi_meta;
 pub mod openapi_mutation;
 use axum::Router;
@@ -31,7 +33,10 @@ pub fn bytes() -> &'static Bytes {
 }
 
 pub mod openapi_types;
-pub use openapi_types::{ArticleListResponse, StatusResponse, UserListResponse};
+pub use openapi_types::{
+    ActivityFeedResponse, ArticleBatchResponse, ArticleListResponse, ModerationCaseListResponse,
+    SearchResponse, SearchResultPage, StatusResponse, UserBatchResponse, UserListResponse,
+};
 /// Return the content length, in bytes, of the `OpenAPI` JSON payload.
 pub fn content_length() -> usize {
     *CONTENT_LENGTH.get_or_init(|| bytes().len())
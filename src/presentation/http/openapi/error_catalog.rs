@@ -0,0 +1,107 @@
+//! Machine-readable catalog of the `code` values error responses carry.
+//!
+//! Every error body the HTTP layer produces takes its status and `code` from
+//! an entry here, so the catalog served at `GET /api/v1/errors` is complete by
+//! construction.
+use axum::http::StatusCode;
+use serde::{Serialize, Serializer};
+use utoipa::ToSchema;
+
+/// One error code, with the status it is sent with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ErrorCode {
+    /// Value of the error body's `code` field.
+    pub code: &'static str,
+    /// HTTP status the code is sent with.
+    #[serde(serialize_with = "status_as_u16")]
+    #[schema(value_type = u16)]
+    pub status: StatusCode,
+    pub description: &'static str,
+}
+
+// serde hands `serialize_with` functions a reference.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn status_as_u16<S: Serializer>(status: &StatusCode, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u16(status.as_u16())
+}
+
+pub const VALIDATION_FAILED: ErrorCode = ErrorCode {
+    code: "validation_failed",
+    status: StatusCode::BAD_REQUEST,
+    description: "The request is malformed or a value is invalid; `message` names the problem.",
+};
+
+pub const UNAUTHORIZED: ErrorCode = ErrorCode {
+    code: "unauthorized",
+    status: StatusCode::UNAUTHORIZED,
+    description: "Credentials are missing, wrong, expired or revoked.",
+};
+
+pub const FORBIDDEN: ErrorCode = ErrorCode {
+    code: "forbidden",
+    status: StatusCode::FORBIDDEN,
+    description: "The caller is authenticated but lacks a capability, or may not act on this resource.",
+};
+
+pub const NOT_FOUND: ErrorCode = ErrorCode {
+    code: "not_found",
+    status: StatusCode::NOT_FOUND,
+    description: "The resource does not exist or is not visible to the caller.",
+};
+
+pub const CONFLICT: ErrorCode = ErrorCode {
+    code: "conflict",
+    status: StatusCode::CONFLICT,
+    description: "The change conflicts with the current state, e.g. the resource changed since it was read.",
+};
+
+pub const SLUG_TAKEN: ErrorCode = ErrorCode {
+    code: "slug_taken",
+    status: StatusCode::CONFLICT,
+    description: "Another article already uses the slug.",
+};
+
+pub const USERNAME_TAKEN: ErrorCode = ErrorCode {
+    code: "username_taken",
+    status: StatusCode::CONFLICT,
+    description: "Another user already has the username.",
+};
+
+pub const PAYLOAD_TOO_LARGE: ErrorCode = ErrorCode {
+    code: "payload_too_large",
+    status: StatusCode::PAYLOAD_TOO_LARGE,
+    description: "The request body is larger than the endpoint accepts; `GET /api/v1/discovery/limits` has the general limit.",
+};
+
+pub const TOO_MANY_REQUESTS: ErrorCode = ErrorCode {
+    code: "too_many_requests",
+    status: StatusCode::TOO_MANY_REQUESTS,
+    description: "Too many requests or failed logins; retry after the `Retry-After` seconds.",
+};
+
+pub const INTERNAL_ERROR: ErrorCode = ErrorCode {
+    code: "internal_error",
+    status: StatusCode::INTERNAL_SERVER_ERROR,
+    description: "Unexpected server error. Details are logged, not returned.",
+};
+
+pub const UNAVAILABLE: ErrorCode = ErrorCode {
+    code: "unavailable",
+    status: StatusCode::SERVICE_UNAVAILABLE,
+    description: "The request is valid but cannot be served now, e.g. a write in read-only mode.",
+};
+
+/// Every code an error response may carry.
+pub const ERROR_CODES: &[ErrorCode] = &[
+    VALIDATION_FAILED,
+    UNAUTHORIZED,
+    FORBIDDEN,
+    NOT_FOUND,
+    CONFLICT,
+    SLUG_TAKEN,
+    USERNAME_TAKEN,
+    PAYLOAD_TOO_LARGE,
+    TOO_MANY_REQUESTS,
+    INTERNAL_ERROR,
+    UNAVAILABLE,
+];
@@ -0,0 +1,595 @@
+//! Example payloads for the bodies documented operations take and return.
+//!
+//! `spec/openapi.json` carries these under `components.examples` and
+//! references them from each operation: DTO examples are keyed by schema
+//! name, bare-array examples by `<item schema>_list` and error examples by
+//! `error_<code>`. A test keeps the snapshot in step
+//! with this module.
+use super::error_catalog::{ERROR_CODES, ErrorCode};
+use crate::application::PaginationPolicy;
+use crate::presentation::http::controllers::{discovery::ServerLimits, webhooks::WEBHOOK_EVENTS};
+use crate::presentation::http::routes::MAX_REQUEST_BODY_BYTES;
+use serde_json::{Value, json};
+
+const CREATED_AT: &str = "2024-05-01T09:30:00Z";
+const UPDATED_AT: &str = "2024-05-02T14:05:00Z";
+const EXPIRES_AT: &str = "2024-05-02T15:05:00Z";
+
+fn user() -> Value {
+    json!({
+        "id": 42,
+        "username": "hanako",
+        "role": "author",
+        "is_active": true,
+        "created_at": CREATED_AT,
+        "timezone": "Asia/Tokyo",
+        "password_reset_required": false,
+        "email": "hanako@example.com",
+        "email_verified": true
+    })
+}
+
+fn article() -> Value {
+    json!({
+        "id": 7,
+        "title": "Hello, Mokkan",
+        "slug": "hello-mokkan",
+        "body": "# Hello\n\nOur first post.",
+        "tags": ["news"],
+        "custom_fields": { "subtitle": "A fresh start" },
+        "published": true,
+        "published_at": UPDATED_AT,
+        "visibility": "public",
+        "author_id": 42,
+        "created_at": CREATED_AT,
+        "updated_at": UPDATED_AT,
+        "view_count": 128
+    })
+}
+
+fn search_result() -> Value {
+    let mut result = article();
+    result["headline"] = json!("# Hello Our first <mark>post</mark>.");
+    result
+}
+
+fn search_facets() -> Value {
+    json!({
+        "published": 21,
+        "drafts": 3,
+        "authors": [{ "author_id": 42, "count": 18 }, { "author_id": 7, "count": 6 }],
+        "tags": [{ "tag": "news", "count": 12 }, { "tag": "release", "count": 4 }]
+    })
+}
+
+fn token() -> Value {
+    json!({
+        "token": "v4.public.eyJzdWIiOiI0MiJ9",
+        "issued_at": UPDATED_AT,
+        "expires_at": EXPIRES_AT,
+        "expires_in": 3600,
+        "session_id": "5f0c6e1e-8d2b-4c4f-9d6a-2f1d3c9b7a10",
+        "refresh_token": "42:5f0c6e1e-8d2b-4c4f-9d6a-2f1d3c9b7a10:Zm9v"
+    })
+}
+
+fn capabilities() -> Value {
+    json!([
+        { "resource": "articles", "action": "create" },
+        { "resource": "articles", "action": "update:own" },
+        { "resource": "articles", "action": "publish" }
+    ])
+}
+
+fn profile() -> Value {
+    json!({
+        "user": user(),
+        "capabilities": capabilities(),
+        "expires_at": EXPIRES_AT,
+        "expires_in": 3600
+    })
+}
+
+fn features() -> Value {
+    json!({ "security_webhook": false, "directory_login": false, "federated_login": true })
+}
+
+fn limits() -> Value {
+    json!(ServerLimits::new(
+        PaginationPolicy::default(),
+        MAX_REQUEST_BODY_BYTES
+    ))
+}
+
+fn moderation_case() -> Value {
+    json!({
+        "id": 3,
+        "article_id": 7,
+        "status": "resolved",
+        "reports": [
+            { "reason": "spam", "details": "Links to a shop.", "reported_at": CREATED_AT }
+        ],
+        "opened_at": CREATED_AT,
+        "resolution": "dismiss",
+        "resolved_by": 1,
+        "resolution_note": "Legitimate announcement.",
+        "resolved_at": UPDATED_AT
+    })
+}
+
+fn field_diff(segments: &Value) -> Value {
+    let count = |op: &str| {
+        segments.as_array().map_or(0, |segments| {
+            segments.iter().filter(|s| s["op"] == op).count()
+        })
+    };
+    let (added, removed) = (count("insert"), count("delete"));
+    json!({
+        "changed": added + removed > 0,
+        "added": added,
+        "removed": removed,
+        "segments": segments
+    })
+}
+
+fn request_examples() -> Vec<(&'static str, Value)> {
+    vec![
+        ("AddContributorRequest", json!({ "user_id": 43 })),
+        ("BatchGetArticlesRequest", json!({ "ids": [7, 8, 404] })),
+        ("BatchGetUsersRequest", json!({ "ids": [42, 404] })),
+        (
+            "BulkArticlesRequest",
+            json!({ "operation": "tag", "ids": [7, 8, 404], "tags": ["release-notes"] }),
+        ),
+        (
+            "ChangeEmailRequest",
+            json!({ "email": "hanako@example.com" }),
+        ),
+        (
+            "ChangePasswordRequest",
+            json!({ "current_password": "Old-Passw0rd!", "new_password": "New-Passw0rd!" }),
+        ),
+        (
+            "CreateArticleRequest",
+            json!({
+                "title": "Hello, Mokkan",
+                "body": "# Hello\n\nOur first post.",
+                "publish": false,
+                "tags": ["news"],
+                "custom_fields": { "subtitle": "A fresh start" }
+            }),
+        ),
+        (
+            "DeleteAccountRequest",
+            json!({ "current_password": "Old-Passw0rd!", "transfer_articles_to": 43 }),
+        ),
+        (
+            "LoginRequest",
+            json!({ "username": "hanako", "password": "Passw0rd-2024!" }),
+        ),
+        (
+            "PasswordResetConfirmRequest",
+            json!({ "token": "bWFpbGVkLXRva2Vu", "new_password": "New-Passw0rd!" }),
+        ),
+        (
+            "PasswordResetRequest",
+            json!({ "email": "hanako@example.com" }),
+        ),
+        ("PublishRequest", json!({ "publish": true })),
+        (
+            "RefreshTokenRequest",
+            json!({ "token": "42:5f0c6e1e-8d2b-4c4f-9d6a-2f1d3c9b7a10:Zm9v" }),
+        ),
+        (
+            "RegisterRequest",
+            json!({ "username": "hanako", "password": "Passw0rd-2024!", "role": "author" }),
+        ),
+        (
+            "ReportRequest",
+            json!({ "article_id": 7, "reason": "spam", "details": "Links to a shop." }),
+        ),
+        (
+            "ResolveCaseRequest",
+            json!({ "action": "dismiss", "note": "Legitimate announcement." }),
+        ),
+        (
+            "RevertArticleRequest",
+            json!({ "expected_updated_at": UPDATED_AT }),
+        ),
+        ("SyndicationRequest", json!({ "enabled": false })),
+        (
+            "UpdateArticleRequest",
+            json!({ "title": "Hello again", "tags": ["news", "release"] }),
+        ),
+        (
+            "UpdatePreferencesRequest",
+            json!({ "timezone": "Asia/Tokyo" }),
+        ),
+        (
+            "UpdateUserRequest",
+            json!({ "is_active": false, "role": null }),
+        ),
+        ("VerifyEmailRequest", json!({ "token": "bWFpbGVkLXRva2Vu" })),
+    ]
+}
+
+fn search_examples() -> Vec<(&'static str, Value)> {
+    vec![
+        ("SearchResultDto", search_result()),
+        (
+            "SearchResultPage",
+            json!({
+                "items": [search_result()],
+                "next_cursor": "MjAyNC0wNS0wMVQwOTozMDowMFp8Nw",
+                "has_more": true,
+                "limit": 20
+            }),
+        ),
+        ("SearchFacetsDto", search_facets()),
+        (
+            "SearchResponse",
+            json!({
+                "items": [search_result()],
+                "next_cursor": "MjAyNC0wNS0wMVQwOTozMDowMFp8Nw",
+                "has_more": true,
+                "limit": 20,
+                "total_estimate": 24,
+                "facets": search_facets()
+            }),
+        ),
+    ]
+}
+
+fn bulk_result() -> Value {
+    json!({
+        "results": [
+            { "id": 7, "status": "updated" },
+            { "id": 8, "status": "forbidden", "error": "insufficient privileges to update article" },
+            { "id": 404, "status": "not_found" }
+        ],
+        "succeeded": 1,
+        "failed": 2
+    })
+}
+
+fn contributor() -> Value {
+    json!({ "article_id": 7, "user_id": 43, "added_by": 42, "added_at": UPDATED_AT })
+}
+
+fn article_examples() -> Vec<(&'static str, Value)> {
+    vec![
+        ("ArticleDto", article()),
+        (
+            "ArticleBatchResponse",
+            json!({ "items": [article()], "missing_ids": [404] }),
+        ),
+        ("BulkResult", bulk_result()),
+        (
+            "ArticleListResponse",
+            json!({
+                "items": [article()],
+                "next_cursor": "MjAyNC0wNS0wMVQwOTozMDowMFp8Nw",
+                "has_more": true,
+                "prev_cursor": null,
+                "limit": 20,
+                "total_estimate": 135,
+                "total_count": null
+            }),
+        ),
+        (
+            "ArticleExportJobDto",
+            json!({
+                "id": "exp_01HX3Q9W6Z",
+                "article_id": 7,
+                "format": "pdf",
+                "status": "completed",
+                "download_url": "/api/v1/downloads/exp_01HX3Q9W6Z?expires=1714662300&sig=3f9a",
+                "error": null,
+                "created_at": UPDATED_AT,
+                "finished_at": UPDATED_AT
+            }),
+        ),
+        (
+            "ArticleImportDto",
+            json!({ "article": article(), "skipped_images": 2 }),
+        ),
+        (
+            "ArticleRevisionDto",
+            json!({
+                "version": 2,
+                "title": "Hello, Mokkan",
+                "slug": "hello-mokkan",
+                "body": "# Hello\n\nOur first post.",
+                "published": true,
+                "published_at": UPDATED_AT,
+                "author_id": 42,
+                "edited_by": 1,
+                "recorded_at": UPDATED_AT
+            }),
+        ),
+        (
+            "ArticleSyndicationDto",
+            json!({ "article_id": 7, "enabled": false, "targets": ["mastodon", "team-slack"] }),
+        ),
+        ("ArticleContributorDto", contributor()),
+        (
+            "ArticleRevisionDiffDto",
+            json!({
+                "article_id": 7,
+                "from_version": 1,
+                "to_version": 2,
+                "granularity": "word",
+                "title": field_diff(&json!([{ "op": "equal", "text": "Hello, Mokkan" }])),
+                "body": field_diff(&json!([
+                    { "op": "equal", "text": "Our " },
+                    { "op": "delete", "text": "draft" },
+                    { "op": "insert", "text": "first" },
+                    { "op": "equal", "text": " post." }
+                ]))
+            }),
+        ),
+        (
+            "CustomFieldDefinitionDto",
+            json!({ "name": "subtitle", "type": "text", "required": false }),
+        ),
+        (
+            "MediaAssetDto",
+            json!({
+                "id": 12,
+                "file_name": "hero.png",
+                "content_type": "image/png",
+                "size_bytes": 48_213,
+                "url": "/api/v1/media/12",
+                "uploaded_by": 42,
+                "created_at": CREATED_AT
+            }),
+        ),
+        ("ModerationCaseDto", moderation_case()),
+        (
+            "ModerationCaseListResponse",
+            json!({
+                "items": [moderation_case()],
+                "next_cursor": null,
+                "has_more": false,
+                "limit": 20
+            }),
+        ),
+    ]
+}
+
+fn user_examples() -> Vec<(&'static str, Value)> {
+    vec![
+        ("UserDto", user()),
+        ("UserProfileDto", profile()),
+        (
+            "UserBatchResponse",
+            json!({ "items": [user()], "missing_ids": [404] }),
+        ),
+        (
+            "UserListResponse",
+            json!({
+                "items": [user()],
+                "next_cursor": null,
+                "has_more": false,
+                "prev_cursor": null,
+                "limit": 20,
+                "total_estimate": 1,
+                "total_count": null
+            }),
+        ),
+        (
+            "AccountDeletionDto",
+            json!({
+                "requested_at": UPDATED_AT,
+                "purge_after": "2024-06-01T14:05:00Z",
+                "transfer_articles_to": 43
+            }),
+        ),
+        (
+            "UserBlockDto",
+            json!({ "user_id": 57, "created_at": UPDATED_AT }),
+        ),
+        (
+            "PermissionsDto",
+            json!({ "role": "author", "permissions": capabilities() }),
+        ),
+        (
+            "SessionInfoDto",
+            json!({
+                "session_id": "5f0c6e1e-8d2b-4c4f-9d6a-2f1d3c9b7a10",
+                "user_agent": "Mozilla/5.0 (Macintosh; Intel Mac OS X 14_4)",
+                "ip_address": "203.0.113.5",
+                "created_at": UPDATED_AT,
+                "revoked": false,
+                "current": true
+            }),
+        ),
+        (
+            "ActivityFeedResponse",
+            json!({
+                "items": [
+                    {
+                        "id": 913,
+                        "category": "sign_in",
+                        "icon": "log-in",
+                        "summary": "Signed in",
+                        "action": "user.login",
+                        "resource_type": "user",
+                        "resource_id": 42,
+                        "by_someone_else": false,
+                        "ip_address": "203.0.113.5",
+                        "user_agent": "Mozilla/5.0 (Macintosh; Intel Mac OS X 14_4)",
+                        "created_at": UPDATED_AT
+                    },
+                    {
+                        "id": 911,
+                        "category": "security",
+                        "icon": "lock",
+                        "summary": "Locked after failed sign-ins",
+                        "action": "user.locked",
+                        "resource_type": "user",
+                        "resource_id": 42,
+                        "by_someone_else": true,
+                        "ip_address": "198.51.100.23",
+                        "user_agent": null,
+                        "created_at": CREATED_AT
+                    }
+                ],
+                "next_cursor": "MjAyNC0wNS0wMVQwOTozMDowMFp8OTEx",
+                "has_more": true,
+                "limit": 2
+            }),
+        ),
+    ]
+}
+
+fn auth_examples() -> Vec<(&'static str, Value)> {
+    vec![
+        ("AuthTokenDto", token()),
+        ("LoginResponse", json!({ "token": token(), "user": user() })),
+        (
+            "CookieLoginResponse",
+            json!({ "user": user(), "expires_in": 3600, "csrf_token": "c3JmLXRva2Vu" }),
+        ),
+        ("CsrfTokenResponse", json!({ "csrf_token": "c3JmLXRva2Vu" })),
+        (
+            "FederatedStartResponse",
+            json!({
+                "authorization_url": "https://accounts.example.com/authorize?client_id=mokkan&state=b3B0",
+                "state": "b3B0"
+            }),
+        ),
+        (
+            "UserInfoResponse",
+            json!({ "sub": "42", "preferred_username": "hanako", "zoneinfo": "Asia/Tokyo" }),
+        ),
+    ]
+}
+
+fn system_examples() -> Vec<(&'static str, Value)> {
+    vec![
+        ("StatusResponse", json!({ "status": "ok" })),
+        (
+            "ServiceStatusDto",
+            json!({
+                "status": "ok",
+                "version": "0.1.0",
+                "git_commit": "b9ba8ef",
+                "build_date": "2024-05-01",
+                "started_at": CREATED_AT,
+                "uptime_seconds": 86_400,
+                "backends": { "storage": "postgres", "session_store": "redis", "redis": true },
+                "features": features()
+            }),
+        ),
+        ("ServerLimits", limits()),
+        (
+            "BootstrapResponse",
+            json!({
+                "profile": profile(),
+                "capabilities": capabilities(),
+                "features": features(),
+                "site": { "version": "0.1.0", "login_providers": ["google"] },
+                "limits": limits()
+            }),
+        ),
+        (
+            "WebhookSchemasResponse",
+            json!({ "events": WEBHOOK_EVENTS, "components": { "schemas": {} } }),
+        ),
+        ("ErrorCatalogResponse", json!({ "errors": ERROR_CODES })),
+    ]
+}
+
+/// `(schema name, example)` for every request and response body schema
+/// documented operations use, other than errors.
+#[must_use]
+pub fn schema_examples() -> Vec<(&'static str, Value)> {
+    let mut examples = request_examples();
+    examples.extend(article_examples());
+    examples.extend(search_examples());
+    examples.extend(user_examples());
+    examples.extend(auth_examples());
+    examples.extend(system_examples());
+    examples
+}
+
+/// `(item schema name, example)` for the operations that return a bare
+/// array of that schema.
+#[must_use]
+pub fn list_examples() -> Vec<(&'static str, Value)> {
+    let examples = schema_examples();
+    let item = |name: &str| {
+        examples
+            .iter()
+            .find(|(schema, _)| *schema == name)
+            .map_or(Value::Null, |(_, value)| value.clone())
+    };
+    vec![
+        (
+            "ArticleRevisionDto",
+            json!([
+                item("ArticleRevisionDto"),
+                {
+                    "version": 1,
+                    "title": "Hello, Mokkan",
+                    "slug": "hello-mokkan",
+                    "body": "# Hello\n\nOur draft post.",
+                    "published": false,
+                    "published_at": null,
+                    "author_id": 42,
+                    "edited_by": 42,
+                    "recorded_at": CREATED_AT
+                }
+            ]),
+        ),
+        (
+            "ArticleContributorDto",
+            json!([item("ArticleContributorDto")]),
+        ),
+        (
+            "CustomFieldDefinitionDto",
+            json!([
+                item("CustomFieldDefinitionDto"),
+                { "name": "reading_minutes", "type": "number", "required": false }
+            ]),
+        ),
+        ("SessionInfoDto", json!([item("SessionInfoDto")])),
+        ("UserBlockDto", json!([item("UserBlockDto")])),
+    ]
+}
+
+/// Name of the list example for `item` under `components.examples`.
+#[must_use]
+pub fn list_example_name(item: &str) -> String {
+    format!("{item}_list")
+}
+
+/// Example error body carrying `code`.
+#[must_use]
+pub fn error_example(code: &ErrorCode) -> Value {
+    let message = match code.code {
+        "validation_failed" => "validation error: title must not be empty",
+        "unauthorized" => "invalid credentials",
+        "forbidden" => "missing capability articles:publish",
+        "not_found" => "article not found",
+        "conflict" => "article has changed since it was last read",
+        "slug_taken" => "conflict: slug already exists",
+        "username_taken" => "conflict: username already exists",
+        "payload_too_large" => "request body is too large",
+        "too_many_requests" => "too many failed logins, try again later",
+        "unavailable" => "the service is in read-only mode",
+        _ => "internal server error",
+    };
+    json!({
+        "error": code.status.canonical_reason().unwrap_or("error"),
+        "message": message,
+        "code": code.code
+    })
+}
+
+/// Name of `code`'s example under `components.examples`.
+#[must_use]
+pub fn error_example_name(code: &ErrorCode) -> String {
+    format!("error_{}", code.code)
+}
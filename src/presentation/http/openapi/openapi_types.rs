@@ -2,7 +2,10 @@
 //!
 //! These are lightweight wrappers around application DTOs to expose stable
 //! response schemas for the `OpenAPI` document.
-use crate::application::{ArticleDto, CursorPage, UserDto};
+use crate::application::{
+    ActivityEntryDto, ArticleDto, BatchResult, CursorPage, ModerationCaseDto, SearchFacetsDto,
+    SearchResultDto, UserDto,
+};
 use serde::{Deserialize, Serialize};
 
 // Simple status response used by health endpoints and docs.
@@ -25,6 +28,16 @@ pub struct UserListResponse {
     pub next_cursor: Option<String>,
     /// True when there are more items available after this page.
     pub has_more: bool,
+    /// An opaque cursor pointing at the first item of this page, present when
+    /// newer users exist before it.
+    pub prev_cursor: Option<String>,
+    /// Effective page size after defaults and clamping.
+    pub limit: Option<u32>,
+    /// Approximate number of users across all pages.
+    pub total_estimate: Option<u64>,
+    /// Exact number of users across all pages, for numbered pages
+    /// (`?page=`).
+    pub total_count: Option<u64>,
 }
 
 impl From<CursorPage<UserDto>> for UserListResponse {
@@ -33,11 +46,15 @@ impl From<CursorPage<UserDto>> for UserListResponse {
             items: page.items,
             next_cursor: page.next_cursor,
             has_more: page.has_more,
+            prev_cursor: page.prev_cursor,
+            limit: page.limit,
+            total_estimate: page.total_estimate,
+            total_count: page.total_count,
         }
     }
 }
 
-#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 /// Paginated list of articles for endpoints that return a cursor-based page.
 pub struct ArticleListResponse {
     /// The list of articles contained in this page.
@@ -46,6 +63,16 @@ pub struct ArticleListResponse {
     pub next_cursor: Option<String>,
     /// True when there are more items available after this page.
     pub has_more: bool,
+    /// An opaque cursor pointing at the first item of this page, present when
+    /// newer articles exist before it.
+    pub prev_cursor: Option<String>,
+    /// Effective page size after defaults and clamping.
+    pub limit: Option<u32>,
+    /// Approximate number of articles across all pages.
+    pub total_estimate: Option<u64>,
+    /// Exact number of articles across all pages, for numbered pages
+    /// (`?page=`).
+    pub total_count: Option<u64>,
 }
 
 impl From<CursorPage<ArticleDto>> for ArticleListResponse {
@@ -54,6 +81,150 @@ impl From<CursorPage<ArticleDto>> for ArticleListResponse {
             items: page.items,
             next_cursor: page.next_cursor,
             has_more: page.has_more,
+            prev_cursor: page.prev_cursor,
+            limit: page.limit,
+            total_estimate: page.total_estimate,
+            total_count: page.total_count,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+/// Paginated article search results, best match first.
+pub struct SearchResultPage {
+    /// The matching articles contained in this page.
+    pub items: Vec<SearchResultDto>,
+    /// An opaque cursor string to retrieve the next page, if any.
+    pub next_cursor: Option<String>,
+    /// True when there are more matches available after this page.
+    pub has_more: bool,
+    /// Effective page size after defaults and clamping.
+    pub limit: Option<u32>,
+}
+
+impl From<CursorPage<SearchResultDto>> for SearchResultPage {
+    fn from(page: CursorPage<SearchResultDto>) -> Self {
+        Self {
+            items: page.items,
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+            limit: page.limit,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+/// A page of search results with counts over every match.
+pub struct SearchResponse {
+    /// The matching articles contained in this page, best match first.
+    pub items: Vec<SearchResultDto>,
+    /// An opaque cursor string to retrieve the next page, if any.
+    pub next_cursor: Option<String>,
+    /// True when there are more matches available after this page.
+    pub has_more: bool,
+    /// Effective page size after defaults and clamping.
+    pub limit: Option<u32>,
+    /// Number of matches across all pages, when the store can count them.
+    pub total_estimate: Option<u64>,
+    /// Matches by publication state, author and tag, when the store can
+    /// count them.
+    pub facets: Option<SearchFacetsDto>,
+}
+
+impl SearchResponse {
+    #[must_use]
+    pub fn new(page: CursorPage<SearchResultDto>, facets: Option<SearchFacetsDto>) -> Self {
+        Self {
+            items: page.items,
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+            limit: page.limit,
+            total_estimate: page.total_estimate,
+            facets,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+/// Paginated list of moderation cases, oldest first.
+pub struct ModerationCaseListResponse {
+    /// The cases contained in this page.
+    pub items: Vec<ModerationCaseDto>,
+    /// An opaque cursor string to retrieve the next page, if any.
+    pub next_cursor: Option<String>,
+    /// True when there are more cases available after this page.
+    pub has_more: bool,
+    /// Effective page size after defaults and clamping.
+    pub limit: Option<u32>,
+}
+
+impl From<CursorPage<ModerationCaseDto>> for ModerationCaseListResponse {
+    fn from(page: CursorPage<ModerationCaseDto>) -> Self {
+        Self {
+            items: page.items,
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+            limit: page.limit,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+/// Paginated account activity of the current user, newest first.
+pub struct ActivityFeedResponse {
+    /// The entries contained in this page.
+    pub items: Vec<ActivityEntryDto>,
+    /// An opaque cursor string to retrieve the next page, if any.
+    pub next_cursor: Option<String>,
+    /// True when there are more entries available after this page.
+    pub has_more: bool,
+    /// Effective page size after defaults and clamping.
+    pub limit: Option<u32>,
+}
+
+impl From<CursorPage<ActivityEntryDto>> for ActivityFeedResponse {
+    fn from(page: CursorPage<ActivityEntryDto>) -> Self {
+        Self {
+            items: page.items,
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+            limit: page.limit,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+/// Users resolved by a batch lookup.
+pub struct UserBatchResponse {
+    /// Users found, in the order their ids were requested.
+    pub items: Vec<UserDto>,
+    /// Requested ids that did not match a user.
+    pub missing_ids: Vec<i64>,
+}
+
+impl From<BatchResult<UserDto>> for UserBatchResponse {
+    fn from(result: BatchResult<UserDto>) -> Self {
+        Self {
+            items: result.items,
+            missing_ids: result.missing_ids,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+/// Articles resolved by a batch lookup.
+pub struct ArticleBatchResponse {
+    /// Articles found, in the order their ids were requested.
+    pub items: Vec<ArticleDto>,
+    /// Requested ids that did not match an article visible to the caller.
+    pub missing_ids: Vec<i64>,
+}
+
+impl From<BatchResult<ArticleDto>> for ArticleBatchResponse {
+    fn from(result: BatchResult<ArticleDto>) -> Self {
+        Self {
+            items: result.items,
+            missing_ids: result.missing_ids,
         }
     }
 }
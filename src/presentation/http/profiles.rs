@@ -0,0 +1,325 @@
+// src/presentation/http/profiles.rs
+//! Opt-in DTO serialization profiles selected with `X-Api-Profile`.
+//!
+//! A profile is a dated snapshot of response shapes. Requests without the
+//! header get the baseline profile, so existing clients keep the shapes they
+//! were built against while new clients opt into renamed or removed fields
+//! ahead of them becoming the default. Profiles are cumulative: selecting a
+//! profile applies its changes and those of every earlier profile.
+use crate::application::error::AppError;
+use crate::presentation::http::conditional::Validators;
+use crate::presentation::http::error::Error as HttpError;
+use axum::{
+    Json,
+    extract::FromRequestParts,
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header, request::Parts},
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Header used to request (and echo back) a serialization profile.
+pub const API_PROFILE_HEADER: &str = "x-api-profile";
+
+/// Request headers that select the representation a conditional response
+/// carries: the profile, and the credentials deciding what the caller sees.
+const VARY: &str = "Accept, Authorization, Cookie, X-Api-Profile";
+
+/// DTO key for the envelope of paginated and batch responses.
+pub const PAGE: &str = "Page";
+
+/// A single field-level difference from the previous profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldChange {
+    /// Drop the field from the serialized object.
+    Remove(&'static str),
+    /// Serialize the field under a new name.
+    Rename {
+        from: &'static str,
+        to: &'static str,
+    },
+}
+
+impl FieldChange {
+    fn apply(self, object: &mut Map<String, Value>) {
+        match self {
+            Self::Remove(field) => {
+                object.remove(field);
+            }
+            Self::Rename { from, to } => {
+                if let Some(field) = object.remove(from) {
+                    object.insert(to.to_string(), field);
+                }
+            }
+        }
+    }
+}
+
+/// A change scoped to one DTO, keyed by its schema name.
+#[derive(Debug, Clone, Copy)]
+pub struct ProfileChange {
+    pub dto: &'static str,
+    pub change: FieldChange,
+}
+
+#[derive(Debug)]
+pub struct ProfileDefinition {
+    pub name: &'static str,
+    pub changes: &'static [ProfileChange],
+}
+
+/// Known profiles, oldest first. The first entry is the baseline.
+pub static PROFILES: &[ProfileDefinition] = &[
+    ProfileDefinition {
+        name: "2024-01",
+        changes: &[],
+    },
+    ProfileDefinition {
+        name: "2024-06",
+        changes: &[
+            // `published` is implied by `published_at`.
+            ProfileChange {
+                dto: "ArticleDto",
+                change: FieldChange::Remove("published"),
+            },
+            // `has_more` is implied by `next_cursor`.
+            ProfileChange {
+                dto: PAGE,
+                change: FieldChange::Remove("has_more"),
+            },
+        ],
+    },
+];
+
+/// The profile a request asked for; an index into [`PROFILES`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApiProfile(usize);
+
+impl ApiProfile {
+    /// Look up a profile by its dated name.
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        PROFILES
+            .iter()
+            .position(|profile| profile.name == name.trim())
+            .map(Self)
+    }
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        PROFILES[self.0].name
+    }
+
+    fn changes_for(self, dto: &'static str) -> impl Iterator<Item = FieldChange> {
+        PROFILES[..=self.0]
+            .iter()
+            .flat_map(|profile| profile.changes)
+            .filter(move |change| change.dto == dto)
+            .map(|change| change.change)
+    }
+
+    /// Rewrite a serialized `dto` object in place for this profile.
+    pub fn apply(self, dto: &'static str, value: &mut Value) {
+        let Some(object) = value.as_object_mut() else {
+            return;
+        };
+        for change in self.changes_for(dto) {
+            change.apply(object);
+        }
+    }
+
+    /// Serialize a single `dto` for this profile.
+    ///
+    /// # Errors
+    ///
+    /// Returns an infrastructure error if the value cannot be serialized.
+    pub fn render<T: Serialize>(self, dto: &'static str, value: &T) -> Result<Profiled, HttpError> {
+        let mut body = to_value(value)?;
+        self.apply(dto, &mut body);
+        Ok(Profiled {
+            profile: self,
+            body,
+        })
+    }
+
+    /// Serialize a page or batch envelope whose `items` are `dto` objects.
+    ///
+    /// # Errors
+    ///
+    /// Returns an infrastructure error if the value cannot be serialized.
+    pub fn render_items<T: Serialize>(
+        self,
+        dto: &'static str,
+        envelope: &T,
+    ) -> Result<Profiled, HttpError> {
+        let mut body = to_value(envelope)?;
+        if let Some(items) = body.get_mut("items").and_then(Value::as_array_mut) {
+            for item in items {
+                self.apply(dto, item);
+            }
+        }
+        self.apply(PAGE, &mut body);
+        Ok(Profiled {
+            profile: self,
+            body,
+        })
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<Value, HttpError> {
+    serde_json::to_value(value).map_err(|err| {
+        HttpError::from_error(AppError::infrastructure(format!(
+            "failed to serialize response: {err}"
+        )))
+    })
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ApiProfile {
+    type Rejection = HttpError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(API_PROFILE_HEADER) else {
+            return Ok(Self::default());
+        };
+
+        value
+            .to_str()
+            .ok()
+            .and_then(Self::parse)
+            .ok_or_else(|| HttpError::from_error(AppError::validation("unknown API profile")))
+    }
+}
+
+/// A JSON body shaped for a profile; echoes the profile in `X-Api-Profile`.
+#[derive(Debug)]
+pub struct Profiled {
+    profile: ApiProfile,
+    body: Value,
+}
+
+impl Profiled {
+    fn profile_header(&self) -> (HeaderName, HeaderValue) {
+        (
+            HeaderName::from_static(API_PROFILE_HEADER),
+            HeaderValue::from_static(self.profile.name()),
+        )
+    }
+
+    /// Answer a conditional GET: the body with `ETag` and `Last-Modified`
+    /// validators, or `304 Not Modified` when `request` shows the client
+    /// already has it (see [`Validators`]). Both carry `Vary`, so shared
+    /// caches keep one copy per profile and caller.
+    ///
+    /// # Errors
+    ///
+    /// Returns an infrastructure error if the body cannot be serialized.
+    pub fn conditional(
+        self,
+        request: &HeaderMap,
+        last_modified: Option<DateTime<Utc>>,
+    ) -> Result<Response, HttpError> {
+        let body = serde_json::to_vec(&self.body).map_err(|err| {
+            HttpError::from_error(AppError::infrastructure(format!(
+                "failed to serialize response: {err}"
+            )))
+        })?;
+        let body = Bytes::from(body);
+        let validators = Validators::new(&body, last_modified);
+        let mut response = if validators.not_modified(request) {
+            ([self.profile_header()], StatusCode::NOT_MODIFIED).into_response()
+        } else {
+            (
+                [
+                    self.profile_header(),
+                    (
+                        header::CONTENT_TYPE,
+                        HeaderValue::from_static("application/json"),
+                    ),
+                ],
+                body,
+            )
+                .into_response()
+        };
+        validators.apply(response.headers_mut());
+        response
+            .headers_mut()
+            .insert(header::VARY, HeaderValue::from_static(VARY));
+        Ok(response)
+    }
+}
+
+impl IntoResponse for Profiled {
+    fn into_response(self) -> Response {
+        ([self.profile_header()], Json(self.body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApiProfile, FieldChange, PAGE, PROFILES};
+    use serde_json::json;
+
+    #[test]
+    fn registry_names_are_unique_and_ordered() {
+        let names: Vec<_> = PROFILES.iter().map(|profile| profile.name).collect();
+        let mut sorted = names.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(names, sorted);
+        assert!(
+            PROFILES[0].changes.is_empty(),
+            "baseline must not change shapes"
+        );
+    }
+
+    #[test]
+    fn baseline_is_the_default_and_leaves_values_untouched() {
+        let profile = ApiProfile::default();
+        assert_eq!(Some(profile), ApiProfile::parse(PROFILES[0].name));
+
+        let mut value = json!({ "published": true, "has_more": false });
+        profile.apply("ArticleDto", &mut value);
+        profile.apply(PAGE, &mut value);
+        assert_eq!(value, json!({ "published": true, "has_more": false }));
+    }
+
+    #[test]
+    fn later_profiles_apply_their_changes() {
+        let profile = ApiProfile::parse("2024-06").expect("registered profile");
+        let rendered = profile
+            .render_items(
+                "ArticleDto",
+                &json!({
+                    "items": [{ "id": 1, "published": true }],
+                    "has_more": false,
+                    "next_cursor": null,
+                }),
+            )
+            .expect("render");
+        assert_eq!(
+            rendered.body,
+            json!({ "items": [{ "id": 1 }], "next_cursor": null })
+        );
+    }
+
+    #[test]
+    fn renames_move_the_value() {
+        let mut object = json!({ "old": 1, "kept": 2 }).as_object().cloned().unwrap();
+        FieldChange::Rename {
+            from: "old",
+            to: "new",
+        }
+        .apply(&mut object);
+        assert_eq!(
+            serde_json::Value::Object(object),
+            json!({ "new": 1, "kept": 2 })
+        );
+    }
+
+    #[test]
+    fn unknown_profiles_are_rejected() {
+        assert!(ApiProfile::parse("1999-01").is_none());
+    }
+}
@@ -1,72 +1,192 @@
 // src/presentation/http/routes.rs
-use crate::presentation::http::controllers::audit;
+use crate::application::MAX_BUNDLE_BYTES;
+use crate::domain::RouteGroup;
+use crate::infrastructure::tenancy::TenantSchema;
+use crate::presentation::http::controllers::{
+    admin_access_rules, admin_custom_fields, admin_inspect, admin_integrity, admin_jobs,
+    admin_read_only, admin_roles, admin_security, admin_static_site, admin_time, admin_users,
+    audit, bundles,
+};
 use crate::presentation::http::state::HttpContext;
 use crate::presentation::http::{
-    controllers::{articles, auth, auth_oidc, auth_sessions, discovery, users},
-    middleware::{rate_limit, require_capabilities},
+    controllers::{
+        account_deletion, articles, auth, auth_blocks, auth_federated, auth_oidc, auth_recovery,
+        auth_sessions, bootstrap, discovery, downloads, errors, events, media, moderation, search,
+        status, users, webhooks,
+    },
+    extractors::TrustedProxies,
+    middleware::{
+        access_rules, body_limit, cors, csrf, ephemeral, honeypot, rate_limit, read_only,
+        request_context, require_capabilities,
+        route_rate_limit::{self, LimitedRoutes, RouteRateLimit},
+        row_level_security,
+        security_headers::{self, SecurityHeaders},
+        tenant, trace_context,
+    },
     openapi::{self, StatusResponse},
 };
 use axum::{
     Extension, Router,
-    http::{Method, header::HeaderValue},
+    extract::DefaultBodyLimit,
     routing::{delete, get, patch, post, put},
 };
-use std::time::Duration;
-use tower_http::cors::AllowOrigin;
-use tower_http::{cors::CorsLayer, trace::TraceLayer};
+use std::sync::Arc;
+
+/// Largest request body accepted by most routes, in bytes, unless
+/// `MAX_REQUEST_BODY_BYTES` overrides it.
+pub const MAX_REQUEST_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Largest authorization code exchange, which is a handful of form fields.
+const MAX_TOKEN_REQUEST_BYTES: usize = 16 * 1024;
+
+/// The general request body limit in effect.
+#[must_use]
+pub fn max_request_body_bytes() -> usize {
+    crate::config::Settings::max_request_body_bytes_from_env().unwrap_or(MAX_REQUEST_BODY_BYTES)
+}
+
+/// Room for the multipart framing around an upload of the largest accepted
+/// size.
+const MULTIPART_OVERHEAD_BYTES: usize = 64 * 1024;
+use tower_http::trace::TraceLayer;
 
 pub fn build_router_with_rate_limiter(state: HttpContext, enable_rate_limiter: bool) -> Router {
-    // prefer reading allowed origins from env directly so tests don't have to provide BISCUIT key
-    let origins = crate::config::Settings::allowed_origins_from_env();
-
-    let cors = if origins.iter().any(|o| o == "*") {
-        CorsLayer::new().allow_origin(tower_http::cors::Any)
-    } else {
-        let origin_list: Vec<_> = origins
-            .iter()
-            .filter_map(|s| s.parse::<HeaderValue>().ok())
-            .collect();
-        CorsLayer::new().allow_origin(AllowOrigin::list(origin_list))
-    }
-    .allow_methods([
-        Method::GET,
-        Method::POST,
-        Method::PUT,
-        Method::PATCH,
-        Method::DELETE,
-        Method::OPTIONS,
-    ])
-    .allow_headers(tower_http::cors::Any)
-    .max_age(Duration::from_secs(3600));
+    // prefer reading CORS settings from env directly so tests don't have to provide BISCUIT key;
+    // `Settings::from_env` already refused invalid values at startup
+    let cors_settings = crate::config::Settings::cors_from_env().unwrap_or_else(|err| {
+        tracing::warn!(error = %err, "invalid CORS configuration, using defaults");
+        crate::config::CorsSettings::default()
+    });
+    let cors = cors::cors_layer(&cors_settings);
+    let origins = cors_settings.allowed_origins;
+
+    // per-client budgets for sensitive route groups, lifted with the governor
+    let limits = enable_rate_limiter.then(crate::config::Settings::route_rate_limits_from_env);
+    let limited = |routes: Router, group: LimitedRoutes| match limits
+        .as_ref()
+        .and_then(|limits| RouteRateLimit::from_settings(group, limits))
+    {
+        Some(limit) => routes.route_layer(axum::middleware::from_fn_with_state(
+            limit,
+            route_rate_limit::limit_requests,
+        )),
+        None => routes,
+    };
 
     let mut router = Router::new()
         .merge(openapi::docs_router())
         .merge(system_routes())
-        .merge(auth_routes())
+        .merge(limited(auth_routes(), LimitedRoutes::Auth))
         .merge(user_routes())
         .merge(audit_routes())
-        .merge(article_routes())
-        .layer(TraceLayer::new_for_http())
+        .merge(limited(admin_routes(), LimitedRoutes::Admin))
+        .merge(limited(article_routes(), LimitedRoutes::ArticleWrites))
+        .merge(moderation_routes())
+        .merge(media_routes(state.services.media.policy().max_bytes))
+        .layer(DefaultBodyLimit::max(max_request_body_bytes()))
+        .layer(axum::middleware::from_fn(
+            body_limit::explain_oversized_bodies,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            csrf::CsrfPolicy::new(
+                origins,
+                crate::config::Settings::csrf_exempt_paths_from_env(),
+            ),
+            csrf::require_csrf_token,
+        ))
+        .layer(axum::middleware::from_fn(read_only::refuse_writes))
+        // client details for the audit entries commands write
+        .layer(axum::middleware::from_fn(
+            request_context::capture_request_context,
+        ))
+        // request spans continue the caller's `traceparent`
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(trace_context::make_span)
+                .on_response(trace_context::record_response),
+        );
+
+    // carry the acting user into Postgres row-level security policies
+    if crate::config::Settings::row_level_security_from_env() {
+        router = router.layer(axum::middleware::from_fn(
+            row_level_security::row_level_security_context,
+        ));
+    }
+
+    // route requests to per-tenant schemas only when tenants are configured
+    let tenants = tenant_schemas();
+    if !tenants.is_empty() {
+        router = router.layer(axum::middleware::from_fn_with_state(
+            tenants,
+            tenant::resolve_tenant,
+        ));
+    }
+
+    // answer decoy paths and block the scanners that request them
+    if let Some(settings) = crate::config::Settings::honeypot_from_env() {
+        router = router.layer(axum::middleware::from_fn_with_state(
+            Arc::new(honeypot::Honeypot::new(settings)),
+            honeypot::trap_scanners,
+        ));
+    }
+
+    // site-wide access rules, including the honeypot's temporary blocks
+    router = router.layer(axum::middleware::from_fn_with_state(
+        RouteGroup::Site,
+        access_rules::enforce_access_rules,
+    ));
+
+    router = router
+        .layer(axum::middleware::from_fn_with_state(
+            SecurityHeaders::new(crate::config::Settings::hsts_max_age_from_env()),
+            security_headers::set_security_headers,
+        ))
         .layer(cors)
         .layer(Extension(state));
 
     // apply rate limiter only when requested. Tests can call the alternative constructor
-    // and pass `false` to avoid the governor dependency on real remote addresses.
+    // and pass `false` to avoid the governor dependency on real remote addresses; this
+    // also lifts the per-route budgets above.
     if enable_rate_limiter {
         router = router.layer(rate_limit::layer());
     }
 
+    // forwarding headers are only believed from these proxies
+    router = router.layer(Extension(TrustedProxies(
+        crate::config::Settings::trusted_proxies_from_env().into(),
+    )));
+
+    // mark every response, including rejections, from a throwaway instance
+    if crate::config::Settings::ephemeral_mode_from_env() {
+        router = router.layer(axum::middleware::from_fn(ephemeral::watermark));
+    }
+
     router
 }
 
 /// Backwards-compatible wrapper that reads the `DISABLE_RATE_LIMIT` env var to decide
 /// whether to enable the governor rate limiter. Production code can continue to call
 /// `build_router(state)`.
+///
+/// The flag also lifts the per-route budgets.
 pub fn build_router(state: HttpContext) -> Router {
     let disable = std::env::var("DISABLE_RATE_LIMIT").as_deref() == Ok("1");
     build_router_with_rate_limiter(state, !disable)
 }
 
+fn tenant_schemas() -> Arc<[TenantSchema]> {
+    crate::config::Settings::tenant_schemas_from_env()
+        .iter()
+        .filter_map(|id| match TenantSchema::new(id) {
+            Ok(tenant) => Some(tenant),
+            Err(err) => {
+                tracing::warn!(error = %err, "ignoring invalid tenant id");
+                None
+            }
+        })
+        .collect()
+}
+
 fn audit_routes() -> Router {
     Router::new()
         .route("/api/v1/audit-logs", get(audit::list_audit_logs))
@@ -80,13 +200,137 @@ fn audit_routes() -> Router {
         )
 }
 
+fn admin_routes() -> Router {
+    Router::new()
+        .route(
+            "/api/v1/admin/security/token-reuse",
+            get(admin_security::list_token_reuse_incidents),
+        )
+        .route(
+            "/api/v1/admin/users/import",
+            post(admin_users::import_users),
+        )
+        .route(
+            "/api/v1/admin/users/import/{id}",
+            get(admin_users::user_import_job),
+        )
+        .route(
+            "/api/v1/admin/inspect/article/{id}",
+            get(admin_inspect::inspect_article),
+        )
+        .route(
+            "/api/v1/admin/inspect/user/{id}",
+            get(admin_inspect::inspect_user),
+        )
+        .route(
+            "/api/v1/admin/inspect/session/{id}",
+            get(admin_inspect::inspect_session),
+        )
+        .route(
+            "/api/v1/admin/custom-fields/{name}",
+            put(admin_custom_fields::put_custom_field)
+                .delete(admin_custom_fields::delete_custom_field),
+        )
+        .route(
+            "/api/v1/admin/custom-fields/{name}/migrations",
+            post(admin_custom_fields::migrate_custom_field),
+        )
+        .route(
+            "/api/v1/admin/custom-fields/migrations/{id}",
+            get(admin_custom_fields::custom_field_migration_job),
+        )
+        .route(
+            "/api/v1/admin/integrity",
+            get(admin_integrity::check_integrity).post(admin_integrity::run_integrity_check),
+        )
+        .route("/api/v1/admin/jobs", get(admin_jobs::list_jobs))
+        .route(
+            "/api/v1/admin/jobs/{name}/runs",
+            get(admin_jobs::list_job_runs),
+        )
+        .route("/api/v1/admin/jobs/{name}/run", post(admin_jobs::run_job))
+        .route(
+            "/api/v1/admin/read-only",
+            get(admin_read_only::read_only).put(admin_read_only::set_read_only),
+        )
+        .route("/api/v1/export", get(bundles::export))
+        .route(
+            "/api/v1/import",
+            post(bundles::import).layer(DefaultBodyLimit::max(MAX_BUNDLE_BYTES)),
+        )
+        .route(
+            "/api/v1/admin/static-export",
+            post(admin_static_site::export_static_site),
+        )
+        .route("/api/v1/admin/time", get(admin_time::simulated_time))
+        .route("/api/v1/admin/time/advance", post(admin_time::advance_time))
+        .route(
+            "/api/v1/admin/access-rules",
+            get(admin_access_rules::list_access_rules).post(admin_access_rules::create_access_rule),
+        )
+        .route(
+            "/api/v1/admin/access-rules/{id}",
+            delete(admin_access_rules::delete_access_rule),
+        )
+        .route(
+            "/api/v1/webhooks",
+            get(webhooks::list_webhooks).post(webhooks::create_webhook),
+        )
+        .route(
+            "/api/v1/webhooks/{id}",
+            get(webhooks::get_webhook)
+                .patch(webhooks::update_webhook)
+                .delete(webhooks::delete_webhook),
+        )
+        .route(
+            "/api/v1/webhooks/{id}/deliveries",
+            get(webhooks::list_webhook_deliveries),
+        )
+        .merge(role_routes())
+        .route_layer(axum::middleware::from_fn_with_state(
+            RouteGroup::Admin,
+            access_rules::enforce_access_rules,
+        ))
+}
+
+/// Custom roles and the capabilities users are given.
+fn role_routes() -> Router {
+    Router::new()
+        .route("/api/v1/admin/roles", get(admin_roles::list_roles))
+        .route(
+            "/api/v1/admin/roles/{name}",
+            put(admin_roles::put_role).delete(admin_roles::delete_role),
+        )
+        .route(
+            "/api/v1/admin/users/{id}/custom-role",
+            put(admin_roles::assign_role),
+        )
+        .route(
+            "/api/v1/admin/users/{id}/capabilities",
+            get(admin_roles::list_capability_grants),
+        )
+        .route(
+            "/api/v1/admin/users/{id}/capabilities/{capability}",
+            put(admin_roles::grant_capability).delete(admin_roles::revoke_capability),
+        )
+}
+
 fn system_routes() -> Router {
     Router::new()
         .route("/health", get(health))
+        .route("/api/v1/status", get(status::status))
+        .route("/api/v1/webhooks/schemas", get(webhooks::schemas))
+        .route("/api/v1/errors", get(errors::catalog))
+        .route("/api/v1/discovery/limits", get(discovery::limits))
+        .route("/api/v1/bootstrap", get(bootstrap::bootstrap))
         .route(
             "/.well-known/openid-configuration",
             get(discovery::openid_configuration),
         )
+        .route(
+            "/.well-known/oauth-authorization-server",
+            get(discovery::oauth_authorization_server),
+        )
         .route(
             "/.well-known/jwks.json",
             get(crate::presentation::http::controllers::auth::keys),
@@ -95,31 +339,107 @@ fn system_routes() -> Router {
 
 fn auth_routes() -> Router {
     Router::new()
-        .route("/api/v1/auth/register", post(auth::register))
+        .route(
+            "/api/v1/auth/register",
+            post(auth::register).layer(axum::middleware::from_fn_with_state(
+                RouteGroup::Registration,
+                access_rules::enforce_access_rules,
+            )),
+        )
         .route("/api/v1/auth/keys", get(auth::keys))
         .route("/api/v1/auth/login", post(auth::login))
         .route("/api/v1/auth/authorize", get(auth_oidc::authorize))
-        .route("/api/v1/auth/introspect", post(auth_oidc::introspect))
-        .route("/api/v1/auth/token", post(auth_oidc::token))
-        .route("/api/v1/auth/revoke", post(auth_oidc::revoke))
+        .route(
+            "/api/v1/auth/token",
+            post(auth_oidc::token).layer(DefaultBodyLimit::max(MAX_TOKEN_REQUEST_BYTES)),
+        )
+        .route("/api/v1/auth/userinfo", get(auth_oidc::userinfo))
+        .route(
+            "/api/v1/auth/oidc/{provider}/start",
+            get(auth_federated::start),
+        )
+        .route(
+            "/api/v1/auth/oidc/{provider}/callback",
+            get(auth_federated::callback),
+        )
+        .route(
+            "/api/v1/auth/password-reset",
+            post(auth_recovery::request_password_reset),
+        )
+        .route(
+            "/api/v1/auth/password-reset/confirm",
+            post(auth_recovery::confirm_password_reset),
+        )
+        .route(
+            "/api/v1/auth/email/verify",
+            post(auth_recovery::verify_email),
+        )
         .route("/api/v1/auth/logout", post(auth::logout))
+        .route("/api/v1/auth/csrf", get(auth::rotate_csrf_token))
         .route("/api/v1/auth/refresh", post(auth::refresh_token))
-        .route("/api/v1/auth/me", get(auth::profile))
+        .route(
+            "/api/v1/auth/me",
+            get(auth::profile).patch(auth::update_preferences),
+        )
+        .route("/api/v1/auth/me/activity", get(audit::list_my_activity))
+        .route("/api/v1/auth/me/email", put(auth_recovery::change_email))
+        .route("/api/v1/auth/me/permissions", get(auth::permissions))
+        .route(
+            "/api/v1/auth/me/deletion",
+            post(account_deletion::delete_account),
+        )
+        .route("/api/v1/auth/me/blocks", get(auth_blocks::list_blocks))
+        .route(
+            "/api/v1/auth/me/blocks/{user_id}",
+            put(auth_blocks::block_user).delete(auth_blocks::unblock_user),
+        )
         .route("/api/v1/auth/sessions", get(auth_sessions::list_sessions))
         .route(
             "/api/v1/auth/sessions/{id}",
             delete(auth_sessions::revoke_session),
         )
+        .merge(token_introspection_routes())
+}
+
+/// RFC 7662 introspection and RFC 7009 revocation, open to anyone only when
+/// `OPEN_TOKEN_INTROSPECTION` is set.
+fn token_introspection_routes() -> Router {
+    let routes = Router::new()
+        .route("/api/v1/auth/introspect", post(auth_oidc::introspect))
+        .route("/api/v1/auth/revoke", post(auth_oidc::revoke));
+    if crate::config::Settings::open_token_introspection_from_env() {
+        return routes;
+    }
+    routes.route_layer(axum::middleware::from_fn(move |req, next| {
+        require_capabilities::require_capability(req, next, "tokens", "introspect")
+    }))
 }
 
 fn user_routes() -> Router {
     Router::new()
         .route("/api/v1/users", get(users::list_users))
+        .route("/api/v1/users/batch-get", post(users::batch_get_users))
         .route("/api/v1/users/{id}", patch(users::update_user))
         .route(
             "/api/v1/users/{id}/change-password",
             post(users::change_password),
         )
+        .route(
+            "/api/v1/users/{id}/sessions",
+            delete(auth_sessions::revoke_user_sessions).layer(axum::middleware::from_fn(
+                move |req, next| {
+                    require_capabilities::require_capability(req, next, "users", "update")
+                },
+            )),
+        )
+        .route(
+            "/api/v1/users/{id}/deletion",
+            delete(account_deletion::cancel_account_deletion).layer(axum::middleware::from_fn(
+                move |req, next| {
+                    require_capabilities::require_capability(req, next, "users", "update")
+                },
+            )),
+        )
         .route(
             "/api/v1/users/{id}/grant-role",
             post(users::grant_role).layer(axum::middleware::from_fn(move |req, next| {
@@ -132,6 +452,12 @@ fn user_routes() -> Router {
                 require_capabilities::require_capability(req, next, "users", "update")
             })),
         )
+        .route(
+            "/api/v1/users/{id}/impersonate",
+            post(users::impersonate).layer(axum::middleware::from_fn(move |req, next| {
+                require_capabilities::require_capability(req, next, "users", "impersonate")
+            })),
+        )
 }
 
 fn article_routes() -> Router {
@@ -143,10 +469,40 @@ fn article_routes() -> Router {
                 require_capabilities::require_capability(req, next, "articles", "create")
             })),
         )
+        .route(
+            "/api/v1/articles/import-document",
+            post(articles::import_document).layer(axum::middleware::from_fn(move |req, next| {
+                require_capabilities::require_capability(req, next, "articles", "create")
+            })),
+        )
+        .route("/api/v1/articles/batch-get", post(articles::batch_get))
+        .route("/api/v1/articles/bulk", post(articles::bulk))
+        .route("/api/v1/articles/search", get(articles::search))
+        .route("/api/v1/search", get(search::search))
+        .route(
+            "/api/v1/events/stream",
+            get(events::stream).layer(axum::middleware::from_fn(move |req, next| {
+                require_capabilities::require_capability(req, next, "articles", "view:drafts")
+            })),
+        )
+        .route(
+            "/api/v1/articles/custom-fields",
+            get(articles::list_custom_fields),
+        )
+        .route("/api/v1/articles/exports/{id}", get(articles::export_job))
+        .route(
+            "/api/v1/articles/exports/{id}/download",
+            get(articles::download_export),
+        )
+        .route("/api/v1/downloads/{token}", get(downloads::download))
         .route(
             "/api/v1/articles/by-slug/{slug}",
             get(articles::get_by_slug),
         )
+        .route(
+            "/api/v1/articles/by-slug/{slug}/html",
+            get(articles::render_html),
+        )
         .route(
             "/api/v1/articles/{id}",
             put(articles::update).layer(axum::middleware::from_fn(move |req, next| {
@@ -159,10 +515,41 @@ fn article_routes() -> Router {
                 require_capabilities::require_capability(req, next, "articles", "delete")
             })),
         )
+        .route("/api/v1/articles/{id}/export", get(articles::export))
         .route(
             "/api/v1/articles/{id}/revisions",
             get(articles::list_revisions),
         )
+        .route(
+            "/api/v1/articles/{id}/revisions/{from}/diff/{to}",
+            get(articles::diff_revisions),
+        )
+        .route(
+            "/api/v1/articles/{id}/revisions/{version}/revert",
+            post(articles::revert_to_revision),
+        )
+        .route(
+            "/api/v1/articles/{id}/contributors",
+            get(articles::list_contributors),
+        )
+        .route(
+            "/api/v1/articles/{id}/contributors",
+            post(articles::add_contributor).layer(axum::middleware::from_fn(move |req, next| {
+                require_capabilities::require_capability(req, next, "articles", "update")
+            })),
+        )
+        .route(
+            "/api/v1/articles/{id}/contributors/{user_id}",
+            delete(articles::remove_contributor).layer(axum::middleware::from_fn(
+                move |req, next| {
+                    require_capabilities::require_capability(req, next, "articles", "update")
+                },
+            )),
+        )
+        .route(
+            "/api/v1/articles/{id}/syndication",
+            get(articles::get_syndication).put(articles::set_syndication),
+        )
         .route(
             "/api/v1/articles/{id}/publish",
             post(articles::set_publish_state).layer(axum::middleware::from_fn(move |req, next| {
@@ -171,6 +558,33 @@ fn article_routes() -> Router {
         )
 }
 
+/// Uploads may exceed [`max_request_body_bytes`], up to the configured
+/// largest file.
+fn media_routes(max_file_bytes: usize) -> Router {
+    Router::new()
+        .route(
+            "/api/v1/media",
+            post(media::upload_media)
+                .layer(DefaultBodyLimit::max(
+                    max_file_bytes.saturating_add(MULTIPART_OVERHEAD_BYTES),
+                ))
+                .layer(axum::middleware::from_fn(move |req, next| {
+                    require_capabilities::require_capability(req, next, "articles", "create")
+                })),
+        )
+        .route("/api/v1/media/{id}", get(media::download_media))
+}
+
+fn moderation_routes() -> Router {
+    Router::new()
+        .route("/api/public/v1/report", post(moderation::report))
+        .route("/api/v1/moderation/cases", get(moderation::list_cases))
+        .route(
+            "/api/v1/moderation/cases/{id}/resolve",
+            post(moderation::resolve_case),
+        )
+}
+
 #[utoipa::path(
     get,
     path = "/health",
@@ -0,0 +1,138 @@
+// src/presentation/http/session_cookie.rs
+//! Cookie credentials for browser frontends (`POST /api/v1/auth/login?mode=cookie`).
+//!
+//! The session cookie holds the signed access token, so it is bound to the
+//! session it was issued for and stops working when that session is revoked.
+//! It is `HttpOnly`; the CSRF cookie is deliberately readable so scripts can
+//! echo it in [`CSRF_HEADER`] (double-submit, see `middleware::csrf`).
+use axum::http::{HeaderMap, HeaderValue};
+use headers::{Authorization, Cookie, HeaderMapExt, authorization::Bearer};
+
+use crate::application::{AppError, AppResult};
+
+pub const SESSION_COOKIE: &str = "mokkan_session";
+pub const CSRF_COOKIE: &str = "mokkan_csrf";
+/// Header that must repeat the CSRF cookie on cookie-authenticated writes.
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+fn cookie_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .typed_get::<Cookie>()?
+        .get(name)
+        .filter(|value| !value.is_empty())
+        .map(ToString::to_string)
+}
+
+/// The access token from the session cookie, if the request has one.
+#[must_use]
+pub fn session_token(headers: &HeaderMap) -> Option<String> {
+    cookie_value(headers, SESSION_COOKIE)
+}
+
+/// Whether the request carries a bearer token, which takes precedence over
+/// the session cookie.
+#[must_use]
+pub fn has_bearer_token(headers: &HeaderMap) -> bool {
+    headers.typed_get::<Authorization<Bearer>>().is_some()
+}
+
+/// The access token the request authenticates with: the bearer token, else
+/// the session cookie.
+#[must_use]
+pub fn access_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .typed_get::<Authorization<Bearer>>()
+        .map(|header| header.token().to_string())
+        .or_else(|| session_token(headers))
+}
+
+/// The CSRF token from the CSRF cookie, if the request has one.
+#[must_use]
+pub fn csrf_token(headers: &HeaderMap) -> Option<String> {
+    cookie_value(headers, CSRF_COOKIE)
+}
+
+fn set_cookie(
+    name: &str,
+    value: &str,
+    max_age: Option<i64>,
+    http_only: bool,
+) -> AppResult<HeaderValue> {
+    let max_age = max_age.map_or_else(String::new, |secs| format!("; Max-Age={secs}"));
+    let http_only = if http_only { "; HttpOnly" } else { "" };
+    HeaderValue::from_str(&format!(
+        "{name}={value}; Path=/{max_age}; Secure; SameSite=Lax{http_only}"
+    ))
+    .map_err(|_| AppError::infrastructure("token is not a valid cookie value"))
+}
+
+/// `Set-Cookie` values starting a cookie session that lasts `max_age` seconds.
+///
+/// # Errors
+///
+/// Returns an error if a token contains characters not allowed in cookies.
+pub fn session_cookies(
+    access_token: &str,
+    csrf_token: &str,
+    max_age: i64,
+) -> AppResult<[HeaderValue; 2]> {
+    Ok([
+        set_cookie(SESSION_COOKIE, access_token, Some(max_age), true)?,
+        set_cookie(CSRF_COOKIE, csrf_token, Some(max_age), false)?,
+    ])
+}
+
+/// `Set-Cookie` value replacing the CSRF token. It lasts for the browser
+/// session; without a session cookie it authorizes nothing.
+///
+/// # Errors
+///
+/// Returns an error if the token contains characters not allowed in cookies.
+pub fn csrf_cookie(csrf_token: &str) -> AppResult<HeaderValue> {
+    set_cookie(CSRF_COOKIE, csrf_token, None, false)
+}
+
+/// `Set-Cookie` values removing both cookies.
+#[must_use]
+pub const fn cleared_cookies() -> [HeaderValue; 2] {
+    [
+        HeaderValue::from_static(
+            "mokkan_session=; Path=/; Max-Age=0; Secure; SameSite=Lax; HttpOnly",
+        ),
+        HeaderValue::from_static("mokkan_csrf=; Path=/; Max-Age=0; Secure; SameSite=Lax"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::header::{AUTHORIZATION, COOKIE};
+
+    #[test]
+    fn reads_cookies_among_others() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            COOKIE,
+            HeaderValue::from_static("theme=dark; mokkan_session=abc.def; mokkan_csrf=xyz"),
+        );
+        assert_eq!(session_token(&headers).as_deref(), Some("abc.def"));
+        assert_eq!(csrf_token(&headers).as_deref(), Some("xyz"));
+    }
+
+    #[test]
+    fn bearer_token_takes_precedence_over_cookie() {
+        let mut headers = HeaderMap::new();
+        headers.insert(COOKIE, HeaderValue::from_static("mokkan_session=cookie"));
+        assert_eq!(access_token(&headers).as_deref(), Some("cookie"));
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer header"));
+        assert_eq!(access_token(&headers).as_deref(), Some("header"));
+    }
+
+    #[test]
+    fn session_cookie_is_http_only_and_csrf_cookie_is_not() {
+        let [session, csrf] = session_cookies("tok", "csrf", 60).unwrap();
+        assert!(session.to_str().unwrap().ends_with("HttpOnly"));
+        assert!(session.to_str().unwrap().contains("Max-Age=60"));
+        assert!(!csrf.to_str().unwrap().contains("HttpOnly"));
+    }
+}
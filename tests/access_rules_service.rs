@@ -0,0 +1,255 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/access_rules_service.rs
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use mokkan_core::application::ports::geoip::AsnLookup;
+use mokkan_core::application::ports::security_events::ClientInfo;
+use mokkan_core::application::services::{
+    AccessRulePorts, AccessRuleService, CreateAccessRuleCommand,
+};
+use mokkan_core::application::{AppError, AuthenticatedUser};
+use mokkan_core::domain::{Role, RouteGroup, RuleAction, UserId};
+
+mod support;
+
+/// Every address in 192.0.2.0/24 belongs to AS 64500.
+struct TestNet;
+
+impl AsnLookup for TestNet {
+    fn asn(&self, ip: IpAddr) -> Option<u32> {
+        ip.to_string().starts_with("192.0.2.").then_some(64500)
+    }
+}
+
+fn service(
+    asn_lookup: Option<Arc<dyn AsnLookup>>,
+) -> (AccessRuleService, support::CapturingAuditRepo) {
+    let audit = support::CapturingAuditRepo::new();
+    let svc = AccessRuleService::new(
+        AccessRulePorts {
+            rule_repo: Arc::new(support::MemoryAccessRules::default()),
+            audit_log_repo: Arc::new(audit.clone()),
+            asn_lookup,
+        },
+        Arc::new(support::DummyClock),
+    );
+    (svc, audit)
+}
+
+fn user(role: Role) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(1).unwrap(),
+        username: "operator".into(),
+        role,
+        capabilities: role.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+fn client(ip: &str) -> ClientInfo {
+    ClientInfo {
+        ip_address: Some(ip.into()),
+        user_agent: None,
+    }
+}
+
+fn cidr_rule(action: RuleAction, cidr: &str, priority: i32) -> CreateAccessRuleCommand {
+    CreateAccessRuleCommand {
+        group: RouteGroup::Admin,
+        action,
+        cidr: Some(cidr.into()),
+        asn: None,
+        priority: Some(priority),
+        note: None,
+    }
+}
+
+async fn check(svc: &AccessRuleService, group: RouteGroup, ip: &str) -> Result<(), AppError> {
+    svc.check(group, &client(ip), "/api/v1/admin/jobs").await
+}
+
+#[tokio::test]
+async fn allowlist_admits_only_listed_networks() {
+    let (svc, audit) = service(None);
+    let admin = user(Role::Admin);
+    let office = svc
+        .create(
+            &admin,
+            &client("198.51.100.1"),
+            cidr_rule(RuleAction::Allow, "198.51.100.0/24", 10),
+        )
+        .await
+        .unwrap();
+    let deny_all = svc
+        .create(
+            &admin,
+            &client("198.51.100.1"),
+            cidr_rule(RuleAction::Deny, "0.0.0.0/0", 100),
+        )
+        .await
+        .unwrap();
+
+    check(&svc, RouteGroup::Admin, "198.51.100.20")
+        .await
+        .unwrap();
+    let err = check(&svc, RouteGroup::Admin, "203.0.113.5")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)), "{err}");
+    // Rules only apply to their own group.
+    check(&svc, RouteGroup::Registration, "203.0.113.5")
+        .await
+        .unwrap();
+
+    let hits: Vec<_> = audit
+        .get_inserted()
+        .into_iter()
+        .filter(|entry| !entry.action.ends_with(".create"))
+        .map(|entry| (entry.action, entry.resource_id, entry.ip_address))
+        .collect();
+    assert_eq!(
+        hits,
+        vec![
+            (
+                "access_rule.allow".into(),
+                Some(office.id),
+                Some("198.51.100.20".into())
+            ),
+            (
+                "access_rule.deny".into(),
+                Some(deny_all.id),
+                Some("203.0.113.5".into())
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn deleting_a_rule_takes_effect_immediately() {
+    let (svc, _) = service(None);
+    let admin = user(Role::Admin);
+    let rule = svc
+        .create(
+            &admin,
+            &client("198.51.100.1"),
+            cidr_rule(RuleAction::Deny, "203.0.113.0/24", 1),
+        )
+        .await
+        .unwrap();
+    assert!(check(&svc, RouteGroup::Admin, "203.0.113.5").await.is_err());
+
+    svc.delete(&admin, &client("198.51.100.1"), rule.id)
+        .await
+        .unwrap();
+    check(&svc, RouteGroup::Admin, "203.0.113.5").await.unwrap();
+
+    let err = svc
+        .delete(&admin, &client("198.51.100.1"), rule.id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)), "{err}");
+}
+
+#[tokio::test]
+async fn asn_rules_need_an_asn_database() {
+    let asn_rule = || CreateAccessRuleCommand {
+        group: RouteGroup::Registration,
+        action: RuleAction::Deny,
+        cidr: None,
+        asn: Some(64500),
+        priority: None,
+        note: Some("hosting provider".into()),
+    };
+    let admin = user(Role::Admin);
+
+    let (without, _) = service(None);
+    let err = without
+        .create(&admin, &client("198.51.100.1"), asn_rule())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)), "{err}");
+
+    let (with, _) = service(Some(Arc::new(TestNet)));
+    let rule = with
+        .create(&admin, &client("198.51.100.1"), asn_rule())
+        .await
+        .unwrap();
+    assert_eq!((rule.asn, rule.cidr), (Some(64500), None));
+    assert!(
+        check(&with, RouteGroup::Registration, "192.0.2.44")
+            .await
+            .is_err()
+    );
+    check(&with, RouteGroup::Registration, "198.51.100.2")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn invalid_rules_and_unprivileged_actors_are_rejected() {
+    let (svc, _) = service(None);
+    let admin = user(Role::Admin);
+    let err = svc
+        .create(
+            &admin,
+            &client("198.51.100.1"),
+            cidr_rule(RuleAction::Deny, "10.0.0.1/8", 1),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("host bits"), "{err}");
+
+    let err = svc.list(&user(Role::Author)).await.unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)), "{err}");
+}
+
+#[tokio::test]
+async fn temporary_blocks_deny_every_route_once() {
+    let (svc, audit) = service(None);
+    let scanner: IpAddr = "203.0.113.9".parse().unwrap();
+    let block_for = std::time::Duration::from_hours(1);
+
+    let rule = svc
+        .block_temporarily(
+            scanner,
+            block_for,
+            "honeypot: /.env",
+            &client("203.0.113.9"),
+        )
+        .await
+        .unwrap()
+        .expect("block created");
+    assert_eq!(rule.group, RouteGroup::Site);
+    assert_eq!(rule.cidr.as_deref(), Some("203.0.113.9/32"));
+    assert_eq!(rule.expires_at, Some(rule.created_at + Duration::hours(1)));
+
+    let again = svc
+        .block_temporarily(
+            scanner,
+            block_for,
+            "honeypot: /.env",
+            &client("203.0.113.9"),
+        )
+        .await
+        .unwrap();
+    assert!(again.is_none());
+
+    assert!(check(&svc, RouteGroup::Site, "203.0.113.9").await.is_err());
+    check(&svc, RouteGroup::Site, "203.0.113.10").await.unwrap();
+
+    // Only placing the block is audited, not the requests it turns away.
+    let actions: Vec<_> = audit
+        .get_inserted()
+        .into_iter()
+        .map(|entry| entry.action)
+        .collect();
+    assert_eq!(actions, vec!["access_rule.create".to_string()]);
+}
@@ -0,0 +1,253 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/article_bulk.rs
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::Utc;
+use mokkan_core::application::commands::articles::{
+    ArticleCommandService, BulkArticleCommand, BulkArticleOperation, CreateArticleCommand,
+};
+use mokkan_core::application::{AppError, AuthenticatedUser, BulkItemStatus, BulkResult};
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::{
+    ArticleId, ArticleReadRepository, ArticleRevisionRepository, Role, Tag, UserId,
+};
+use mokkan_core::infrastructure::repositories::InMemoryStores;
+use mokkan_core::infrastructure::repositories::memory::InMemoryArticleRepository;
+use mokkan_core::infrastructure::time::SimulatedClock;
+
+mod support;
+
+struct Harness {
+    commands: ArticleCommandService,
+    articles: Arc<InMemoryArticleRepository>,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let stores = InMemoryStores::new();
+        let slugs = Arc::new(ArticleSlugService::new(
+            stores.articles.clone(),
+            Arc::new(support::DummySlug),
+        ));
+        let commands = ArticleCommandService::new(
+            stores.articles.clone(),
+            stores.articles.clone(),
+            stores.articles.clone(),
+            slugs,
+            Arc::new(SimulatedClock::new()),
+        );
+        Self {
+            commands,
+            articles: stores.articles,
+        }
+    }
+
+    async fn create(&self, author: &AuthenticatedUser, title: &str, publish: bool) -> i64 {
+        let command = CreateArticleCommand::builder()
+            .title(title)
+            .body("body")
+            .tags(["rust"])
+            .publish(publish)
+            .build()
+            .unwrap();
+        self.commands
+            .create_article(author, command)
+            .await
+            .unwrap()
+            .id
+    }
+
+    async fn bulk(
+        &self,
+        actor: &AuthenticatedUser,
+        operation: BulkArticleOperation,
+        ids: Vec<i64>,
+    ) -> Result<BulkResult, AppError> {
+        self.commands
+            .bulk_articles(actor, BulkArticleCommand { operation, ids })
+            .await
+    }
+
+    async fn revisions(&self, id: i64) -> usize {
+        self.articles
+            .list_by_article(ArticleId::new(id).unwrap())
+            .await
+            .unwrap()
+            .len()
+    }
+}
+
+fn user(id: i64, role: Role) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(id).unwrap(),
+        username: format!("user{id}"),
+        role,
+        capabilities: role.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + chrono::Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+fn statuses(result: &BulkResult) -> Vec<(i64, BulkItemStatus)> {
+    result
+        .results
+        .iter()
+        .map(|item| (item.id, item.status))
+        .collect()
+}
+
+/// 一括公開で下書きが公開され、公開済みは変更なし、存在しない ID は `not_found` として要求順に報告される
+#[tokio::test]
+async fn bulk_publish_reports_each_article() {
+    let harness = Harness::new();
+    let admin = user(1, Role::Admin);
+    let first = harness.create(&admin, "first", false).await;
+    let second = harness.create(&admin, "second", false).await;
+    let live = harness.create(&admin, "live", true).await;
+
+    let result = harness
+        .bulk(
+            &admin,
+            BulkArticleOperation::Publish,
+            vec![second, 404, live, first, second],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        statuses(&result),
+        vec![
+            (second, BulkItemStatus::Updated),
+            (404, BulkItemStatus::NotFound),
+            (live, BulkItemStatus::Unchanged),
+            (first, BulkItemStatus::Updated),
+        ]
+    );
+    assert_eq!((result.succeeded, result.failed), (3, 1));
+    for id in [first, second] {
+        let article = harness
+            .articles
+            .find_by_id(ArticleId::new(id).unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(article.published);
+        assert!(article.published_at.is_some());
+        assert_eq!(harness.revisions(id).await, 2);
+    }
+    assert_eq!(harness.revisions(live).await, 1);
+}
+
+/// 一括削除では自分の記事だけが削除され、他人の記事は forbidden として残る
+#[tokio::test]
+async fn bulk_delete_checks_each_article() {
+    let harness = Harness::new();
+    let admin = user(1, Role::Admin);
+    let author = user(2, Role::Author);
+    let own = harness.create(&author, "own", false).await;
+    let other = harness.create(&admin, "other", false).await;
+
+    let result = harness
+        .bulk(&author, BulkArticleOperation::Delete, vec![own, other])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        statuses(&result),
+        vec![
+            (own, BulkItemStatus::Deleted),
+            (other, BulkItemStatus::Forbidden)
+        ]
+    );
+    assert!(result.results[1].error.is_some());
+    let remaining = harness
+        .articles
+        .find_by_ids(&[ArticleId::new(own).unwrap(), ArticleId::new(other).unwrap()])
+        .await
+        .unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(i64::from(remaining[0].id), other);
+}
+
+/// タグ追加は既存のタグを残して統合し、上限を超える記事は invalid として報告される
+#[tokio::test]
+async fn bulk_tag_merges_and_respects_the_limit() {
+    let harness = Harness::new();
+    let admin = user(1, Role::Admin);
+    let plain = harness.create(&admin, "plain", false).await;
+    let tagged = harness.create(&admin, "tagged", false).await;
+    let full = harness.create(&admin, "full", false).await;
+    let many: Vec<String> = (0..9).map(|n| format!("tag{n}")).collect();
+    let _ = harness
+        .bulk(&admin, BulkArticleOperation::Tag { tags: many }, vec![full])
+        .await
+        .unwrap();
+    let _ = harness
+        .bulk(
+            &admin,
+            BulkArticleOperation::Tag {
+                tags: vec!["news".into()],
+            },
+            vec![tagged],
+        )
+        .await
+        .unwrap();
+
+    let result = harness
+        .bulk(
+            &admin,
+            BulkArticleOperation::Tag {
+                tags: vec!["news".into(), "rust".into()],
+            },
+            vec![plain, tagged, full],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        statuses(&result),
+        vec![
+            (plain, BulkItemStatus::Updated),
+            (tagged, BulkItemStatus::Unchanged),
+            (full, BulkItemStatus::Invalid),
+        ]
+    );
+    let article = harness
+        .articles
+        .find_by_id(ArticleId::new(plain).unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    let tags: HashSet<&str> = article.tags.iter().map(Tag::as_str).collect();
+    assert_eq!(tags, HashSet::from(["news", "rust"]));
+}
+
+/// 公開権限がなければ公開操作全体が拒否され、ID が多すぎる要求は検証エラーになる
+#[tokio::test]
+async fn bulk_rejects_missing_capability_and_oversized_requests() {
+    let harness = Harness::new();
+    let admin = user(1, Role::Admin);
+    let id = harness.create(&admin, "draft", false).await;
+    let mut reader = user(3, Role::Author);
+    reader.capabilities.clear();
+
+    let err = harness
+        .bulk(&reader, BulkArticleOperation::Publish, vec![id])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)), "{err:?}");
+
+    let ids = (1..=101).collect();
+    let err = harness
+        .bulk(&admin, BulkArticleOperation::Delete, ids)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)), "{err:?}");
+    assert_eq!(harness.revisions(id).await, 1);
+}
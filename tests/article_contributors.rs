@@ -0,0 +1,245 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/article_contributors.rs
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use mokkan_core::application::commands::articles::{
+    AddContributorCommand, ArticleCommandService, CreateArticleCommand, RemoveContributorCommand,
+    UpdateArticleCommand,
+};
+use mokkan_core::application::queries::articles::{
+    ArticleQueryService, GetArticleByIdQuery, ListArticleRevisionsQuery, ListContributorsQuery,
+};
+use mokkan_core::application::services::BlockListService;
+use mokkan_core::application::{AppError, ArticleDto, AuthenticatedUser};
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::{ArticleVisibility, BlockList, Role, UserBlock, UserId};
+use mokkan_core::infrastructure::repositories::InMemoryStores;
+use mokkan_core::infrastructure::time::SimulatedClock;
+
+mod support;
+
+struct Harness {
+    commands: ArticleCommandService,
+    queries: ArticleQueryService,
+    blocks: Arc<dyn BlockList>,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let stores = InMemoryStores::new();
+        let clock = Arc::new(SimulatedClock::new());
+        let slugs = Arc::new(ArticleSlugService::new(
+            stores.articles.clone(),
+            Arc::new(support::DummySlug),
+        ));
+        let commands = ArticleCommandService::new(
+            stores.articles.clone(),
+            stores.articles.clone(),
+            stores.articles.clone(),
+            slugs,
+            clock.clone(),
+        )
+        .with_contributors(stores.contributors.clone())
+        .with_blocks(Arc::new(BlockListService::new(
+            stores.blocks.clone(),
+            stores.users.clone(),
+            clock,
+        )));
+        let queries = ArticleQueryService::new(stores.articles.clone(), stores.articles)
+            .with_contributors(stores.contributors);
+        Self {
+            commands,
+            queries,
+            blocks: stores.blocks,
+        }
+    }
+
+    async fn create_private_draft(&self, actor: &AuthenticatedUser) -> ArticleDto {
+        let command = CreateArticleCommand::builder()
+            .title("Shared notes")
+            .body("first draft")
+            .visibility(ArticleVisibility::Private)
+            .build()
+            .unwrap();
+        self.commands.create_article(actor, command).await.unwrap()
+    }
+
+    async fn add(
+        &self,
+        actor: &AuthenticatedUser,
+        article_id: i64,
+        user_id: i64,
+    ) -> Result<(), AppError> {
+        self.commands
+            .add_contributor(
+                actor,
+                AddContributorCommand {
+                    article_id,
+                    user_id,
+                },
+            )
+            .await
+            .map(drop)
+    }
+
+    async fn edit(&self, actor: &AuthenticatedUser, id: i64, body: &str) -> Result<(), AppError> {
+        self.commands
+            .update_article(
+                actor,
+                UpdateArticleCommand {
+                    id,
+                    title: None,
+                    body: Some(body.into()),
+                    publish: None,
+                    tags: None,
+                    custom_fields: None,
+                    visibility: None,
+                },
+            )
+            .await
+            .map(drop)
+    }
+}
+
+fn user(id: i64, role: Role) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(id).unwrap(),
+        username: format!("user{id}"),
+        role,
+        capabilities: role.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+/// 共同編集者は非公開の下書きを読み、編集し、履歴を見られるが、外されると元に戻る
+#[tokio::test]
+async fn contributors_edit_until_removed() {
+    let harness = Harness::new();
+    let author = user(2, Role::Author);
+    let helper = user(3, Role::Author);
+    let article = harness.create_private_draft(&author).await;
+    let by_id = || GetArticleByIdQuery { id: article.id };
+
+    let err = harness
+        .edit(&helper, article.id, "mine now")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)), "{err:?}");
+
+    harness.add(&author, article.id, 3).await.unwrap();
+    harness.add(&author, article.id, 3).await.unwrap();
+    let contributors = harness
+        .queries
+        .list_contributors(
+            &helper,
+            ListContributorsQuery {
+                article_id: article.id,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(contributors.len(), 1);
+    assert_eq!(contributors[0].user_id, 3);
+    assert_eq!(contributors[0].added_by, Some(2));
+
+    harness
+        .edit(&helper, article.id, "second draft")
+        .await
+        .unwrap();
+    let read = harness
+        .queries
+        .get_visible_article_by_id(Some(&helper), by_id())
+        .await
+        .unwrap();
+    assert_eq!(read.body, "second draft");
+    let revisions = harness
+        .queries
+        .list_revisions(
+            &helper,
+            ListArticleRevisionsQuery {
+                article_id: article.id,
+            },
+        )
+        .await
+        .unwrap();
+    assert!(!revisions.is_empty());
+
+    harness
+        .commands
+        .remove_contributor(
+            &author,
+            RemoveContributorCommand {
+                article_id: article.id,
+                user_id: 3,
+            },
+        )
+        .await
+        .unwrap();
+    let err = harness
+        .edit(&helper, article.id, "third draft")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)), "{err:?}");
+    let err = harness
+        .queries
+        .get_visible_article_by_id(Some(&helper), by_id())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)), "{err:?}");
+}
+
+/// 共同編集者を追加・削除できるのは作成者と articles:update:any を持つユーザーだけ
+#[tokio::test]
+async fn only_owners_manage_contributors() {
+    let harness = Harness::new();
+    let author = user(2, Role::Author);
+    let helper = user(3, Role::Author);
+    let admin = user(1, Role::Admin);
+    let article = harness.create_private_draft(&author).await;
+
+    let err = harness.add(&helper, article.id, 3).await.unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)), "{err:?}");
+    harness.add(&admin, article.id, 3).await.unwrap();
+    let err = harness.add(&helper, article.id, 4).await.unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)), "{err:?}");
+
+    let err = harness.add(&author, article.id, 2).await.unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)), "{err:?}");
+
+    let remove = |user_id| RemoveContributorCommand {
+        article_id: article.id,
+        user_id,
+    };
+    let err = harness
+        .commands
+        .remove_contributor(&author, remove(4))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)), "{err:?}");
+    harness
+        .commands
+        .remove_contributor(&author, remove(3))
+        .await
+        .unwrap();
+}
+
+/// 作成者をブロックしている利用者は共同編集者に追加できない
+#[tokio::test]
+async fn users_who_blocked_the_author_cannot_be_added() {
+    let harness = Harness::new();
+    let author = user(2, Role::Author);
+    let article = harness.create_private_draft(&author).await;
+    let block = UserBlock::new(UserId::new(3).unwrap(), author.id, Utc::now()).unwrap();
+    harness.blocks.add(block).await.unwrap();
+
+    let err = harness.add(&author, article.id, 3).await.unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)), "{err:?}");
+    harness.add(&author, article.id, 4).await.unwrap();
+}
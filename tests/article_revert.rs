@@ -0,0 +1,229 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/article_revert.rs
+use std::sync::{Arc, Mutex};
+
+use chrono::Duration;
+use mokkan_core::application::commands::articles::{
+    ArticleCommandService, RevertArticleToRevisionCommand,
+};
+use mokkan_core::application::{AppError, AuthenticatedUser};
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::errors::{DomainError, DomainResult};
+use mokkan_core::domain::{
+    Article, ArticleBody, ArticleId, ArticleListCursor, ArticleReadRepository, ArticleRevision,
+    ArticleRevisionParts, ArticleRevisionRepository, ArticleSlug, ArticleTitle, ArticleUpdate,
+    ArticleWriteRepository, NewArticle, Role, UserId,
+};
+
+mod support;
+
+/// One article and its revision history, with the optimistic `updated_at`
+/// check the Postgres repository applies.
+struct Store {
+    article: Mutex<Article>,
+    revisions: Mutex<Vec<ArticleRevision>>,
+}
+
+impl Store {
+    fn new() -> Self {
+        let article = support::ArticleBuilder::new().title("second-draft").build();
+        let store = Self {
+            article: Mutex::new(article.clone()),
+            revisions: Mutex::new(Vec::new()),
+        };
+        let mut first = article;
+        first.title = ArticleTitle::new("first-draft").unwrap();
+        first.body = ArticleBody::new("first body").unwrap();
+        store.record(&first);
+        store.record(&store.current());
+        store
+    }
+
+    fn current(&self) -> Article {
+        self.article.lock().unwrap().clone()
+    }
+
+    fn record(&self, article: &Article) {
+        let mut revisions = self.revisions.lock().unwrap();
+        let version = i32::try_from(revisions.len()).unwrap() + 1;
+        revisions.push(
+            ArticleRevisionParts {
+                article_id: article.id,
+                version,
+                title: article.title.clone(),
+                slug: article.slug.clone(),
+                body: article.body.clone(),
+                published: article.published,
+                published_at: article.published_at,
+                author_id: article.author_id,
+                edited_by: None,
+                recorded_at: article.updated_at,
+            }
+            .into(),
+        );
+    }
+}
+
+impl ArticleWriteRepository for Store {
+    fn insert(&self, _article: NewArticle) -> BoxFuture<'_, DomainResult<Article>> {
+        boxed(async { Err(DomainError::Validation("not supported".into())) })
+    }
+
+    fn update(&self, update: ArticleUpdate) -> BoxFuture<'_, DomainResult<Article>> {
+        boxed(async move {
+            let mut article_guard = self.article.lock().unwrap();
+            let article = &mut *article_guard;
+            if article.updated_at != update.original_updated_at {
+                return Err(DomainError::Conflict(
+                    "article update conflict, please retry".into(),
+                ));
+            }
+            if let Some(title) = update.title {
+                article.title = title;
+            }
+            if let Some(slug) = update.slug {
+                article.slug = slug;
+            }
+            if let Some(body) = update.body {
+                article.body = body;
+            }
+            article.updated_at = update.updated_at;
+            let article = article.clone();
+            drop(article_guard);
+            Ok(article)
+        })
+    }
+
+    fn delete(&self, _id: ArticleId) -> BoxFuture<'_, DomainResult<()>> {
+        boxed(async { Ok(()) })
+    }
+}
+
+impl ArticleReadRepository for Store {
+    fn find_by_id(&self, id: ArticleId) -> BoxFuture<'_, DomainResult<Option<Article>>> {
+        boxed(async move { Ok(Some(self.current()).filter(|article| article.id == id)) })
+    }
+
+    fn find_by_slug<'a>(
+        &'a self,
+        _slug: &'a ArticleSlug,
+    ) -> BoxFuture<'a, DomainResult<Option<Article>>> {
+        boxed(async { Ok(None) })
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        _include_drafts: bool,
+        _limit: u32,
+        _cursor: Option<ArticleListCursor>,
+        _search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        boxed(async { Ok((vec![], None)) })
+    }
+}
+
+impl ArticleRevisionRepository for Store {
+    fn append<'a>(
+        &'a self,
+        article: &'a Article,
+        _edited_by: Option<UserId>,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        boxed(async move {
+            self.record(article);
+            Ok(())
+        })
+    }
+
+    fn list_by_article(
+        &self,
+        _article_id: ArticleId,
+    ) -> BoxFuture<'_, DomainResult<Vec<ArticleRevision>>> {
+        boxed(async move { Ok(self.revisions.lock().unwrap().clone()) })
+    }
+}
+
+fn service(store: &Arc<Store>) -> ArticleCommandService {
+    ArticleCommandService::new(
+        Arc::clone(store) as Arc<dyn ArticleWriteRepository>,
+        Arc::clone(store) as Arc<dyn ArticleReadRepository>,
+        Arc::clone(store) as Arc<dyn ArticleRevisionRepository>,
+        Arc::new(ArticleSlugService::new(
+            Arc::new(support::DummyArticleRead),
+            Arc::new(support::DummySlug),
+        )),
+        Arc::new(support::DummyClock),
+    )
+}
+
+fn admin() -> AuthenticatedUser {
+    let now = support::fixed_now();
+    AuthenticatedUser {
+        id: UserId::new(1).unwrap(),
+        username: "root".into(),
+        role: Role::Admin,
+        capabilities: Role::Admin.default_capabilities(),
+        issued_at: now,
+        expires_at: now + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+const fn revert(version: i32) -> RevertArticleToRevisionCommand {
+    RevertArticleToRevisionCommand {
+        id: 1,
+        version,
+        expected_updated_at: None,
+    }
+}
+
+#[tokio::test]
+async fn revert_restores_content_as_a_new_revision() {
+    let store = Arc::new(Store::new());
+    let svc = service(&store);
+
+    let article = svc
+        .revert_article_to_revision(&admin(), revert(1))
+        .await
+        .expect("revert succeeds");
+
+    assert_eq!(article.title, "first-draft");
+    assert_eq!(article.slug, "first-draft");
+    assert_eq!(article.body, "first body");
+    let revisions = store.revisions.lock().unwrap().clone();
+    assert_eq!(revisions.len(), 3);
+    assert_eq!(revisions[2].title.as_str(), "first-draft");
+}
+
+#[tokio::test]
+async fn stale_expected_updated_at_is_a_conflict() {
+    let store = Arc::new(Store::new());
+    let svc = service(&store);
+    let command = RevertArticleToRevisionCommand {
+        expected_updated_at: Some(store.current().updated_at - Duration::seconds(1)),
+        ..revert(1)
+    };
+
+    let err = svc
+        .revert_article_to_revision(&admin(), command)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, AppError::Conflict(_)), "{err}");
+    assert_eq!(store.current().title.as_str(), "second-draft");
+    assert_eq!(store.revisions.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn unknown_revision_is_not_found() {
+    let store = Arc::new(Store::new());
+    let err = service(&store)
+        .revert_article_to_revision(&admin(), revert(9))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)), "{err}");
+}
@@ -0,0 +1,151 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/article_revision_diff.rs
+use std::sync::Arc;
+
+use chrono::Duration;
+use mokkan_core::application::queries::articles::{ArticleQueryService, DiffArticleRevisionsQuery};
+use mokkan_core::application::{AppError, AuthenticatedUser, DiffGranularity, DiffOp};
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::errors::DomainResult;
+use mokkan_core::domain::{
+    Article, ArticleBody, ArticleId, ArticleListCursor, ArticleReadRepository, ArticleRevision,
+    ArticleRevisionParts, ArticleRevisionRepository, ArticleSlug, ArticleTitle, Role, UserId,
+};
+
+mod support;
+
+/// One article with two recorded revisions.
+struct Store;
+
+fn revision(version: i32, title: &str, body: &str) -> ArticleRevision {
+    let article = support::ArticleBuilder::new().build();
+    ArticleRevisionParts {
+        article_id: article.id,
+        version,
+        title: ArticleTitle::new(title).unwrap(),
+        slug: article.slug,
+        body: ArticleBody::new(body).unwrap(),
+        published: false,
+        published_at: None,
+        author_id: article.author_id,
+        edited_by: None,
+        recorded_at: article.updated_at,
+    }
+    .into()
+}
+
+impl ArticleReadRepository for Store {
+    fn find_by_id(&self, id: ArticleId) -> BoxFuture<'_, DomainResult<Option<Article>>> {
+        let article = support::ArticleBuilder::new().build();
+        boxed(async move { Ok(Some(article).filter(|article| article.id == id)) })
+    }
+
+    fn find_by_slug<'a>(
+        &'a self,
+        _slug: &'a ArticleSlug,
+    ) -> BoxFuture<'a, DomainResult<Option<Article>>> {
+        boxed(async { Ok(None) })
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        _include_drafts: bool,
+        _limit: u32,
+        _cursor: Option<ArticleListCursor>,
+        _search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        boxed(async { Ok((vec![], None)) })
+    }
+}
+
+impl ArticleRevisionRepository for Store {
+    fn append<'a>(
+        &'a self,
+        _article: &'a Article,
+        _edited_by: Option<UserId>,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        boxed(async { Ok(()) })
+    }
+
+    fn list_by_article(
+        &self,
+        _article_id: ArticleId,
+    ) -> BoxFuture<'_, DomainResult<Vec<ArticleRevision>>> {
+        boxed(async {
+            Ok(vec![
+                revision(1, "Draft title", "intro\nold middle\nend\n"),
+                revision(2, "Final title", "intro\nnew middle\nend\n"),
+            ])
+        })
+    }
+}
+
+fn service() -> ArticleQueryService {
+    let store = Arc::new(Store);
+    ArticleQueryService::new(
+        Arc::clone(&store) as Arc<dyn ArticleReadRepository>,
+        store as Arc<dyn ArticleRevisionRepository>,
+    )
+}
+
+fn user(role: Role) -> AuthenticatedUser {
+    let now = support::fixed_now();
+    AuthenticatedUser {
+        id: UserId::new(99).unwrap(),
+        username: "reviewer".into(),
+        role,
+        capabilities: role.default_capabilities(),
+        issued_at: now,
+        expires_at: now + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+const fn query(from_version: i32, to_version: i32) -> DiffArticleRevisionsQuery {
+    DiffArticleRevisionsQuery {
+        article_id: 1,
+        from_version,
+        to_version,
+        granularity: DiffGranularity::Line,
+    }
+}
+
+#[tokio::test]
+async fn diff_reports_changed_lines_and_title_words() {
+    let diff = service()
+        .diff_revisions(&user(Role::Admin), query(1, 2))
+        .await
+        .expect("diff succeeds");
+
+    assert_eq!((diff.body.added, diff.body.removed), (1, 1));
+    let ops: Vec<_> = diff.body.segments.iter().map(|s| s.op).collect();
+    assert_eq!(
+        ops,
+        vec![DiffOp::Equal, DiffOp::Delete, DiffOp::Insert, DiffOp::Equal]
+    );
+    assert_eq!(diff.body.segments[1].text, "old middle\n");
+    assert_eq!(diff.title.segments[0].text, "Draft");
+    assert_eq!(diff.title.segments[2].text, " title");
+}
+
+#[tokio::test]
+async fn missing_revision_is_not_found() {
+    let err = service()
+        .diff_revisions(&user(Role::Admin), query(1, 7))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)), "{err}");
+}
+
+#[tokio::test]
+async fn other_authors_cannot_diff_revisions() {
+    let err = service()
+        .diff_revisions(&user(Role::Author), query(1, 2))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)), "{err}");
+}
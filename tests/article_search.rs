@@ -0,0 +1,308 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/article_search.rs
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode};
+use chrono::{Duration, Utc};
+use mokkan_core::application::commands::articles::{ArticleCommandService, CreateArticleCommand};
+use mokkan_core::application::queries::articles::{ArticleQueryService, SearchArticlesQuery};
+use mokkan_core::application::{
+    AppError, AppResult, AuthenticatedUser, CursorPage, PageDirection, SearchResultDto,
+};
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::{ArticleVisibility, Role, UserId};
+use mokkan_core::infrastructure::repositories::InMemoryStores;
+use mokkan_core::infrastructure::time::SimulatedClock;
+use tower::util::ServiceExt as _;
+
+mod support;
+
+struct Harness {
+    commands: ArticleCommandService,
+    queries: ArticleQueryService,
+}
+
+impl Harness {
+    async fn with_articles(articles: &[(&str, &str)]) -> Self {
+        let stores = InMemoryStores::new();
+        let slugs = Arc::new(ArticleSlugService::new(
+            stores.articles.clone(),
+            Arc::new(support::DummySlug),
+        ));
+        let commands = ArticleCommandService::new(
+            stores.articles.clone(),
+            stores.articles.clone(),
+            stores.articles.clone(),
+            slugs,
+            Arc::new(SimulatedClock::new()),
+        );
+        let queries = ArticleQueryService::new(stores.articles.clone(), stores.articles);
+        let harness = Self { commands, queries };
+        for (title, body) in articles {
+            let command = CreateArticleCommand::builder()
+                .title(*title)
+                .body(*body)
+                .publish(true)
+                .build()
+                .unwrap();
+            harness
+                .commands
+                .create_article(&admin(), command)
+                .await
+                .unwrap();
+        }
+        harness
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        direction: PageDirection,
+    ) -> AppResult<CursorPage<SearchResultDto>> {
+        self.queries
+            .search_results(
+                None,
+                SearchArticlesQuery {
+                    query: query.into(),
+                    include_drafts: false,
+                    limit: 10,
+                    cursor: None,
+                    direction,
+                    tag: None,
+                },
+            )
+            .await
+    }
+}
+
+fn admin() -> AuthenticatedUser {
+    user(1, Role::Admin)
+}
+
+fn user(id: i64, role: Role) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(id).unwrap(),
+        username: format!("user{id}"),
+        role,
+        capabilities: role.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+fn titles(page: &CursorPage<SearchResultDto>) -> Vec<&str> {
+    page.items
+        .iter()
+        .map(|result| result.article.title.as_str())
+        .collect()
+}
+
+/// 引用符で囲んだフレーズと `-` による除外で検索結果を絞り込める
+#[tokio::test]
+async fn phrases_and_negation_narrow_results() {
+    let harness = Harness::with_articles(&[
+        ("Lunch", "Fish and chips with salt"),
+        ("Dinner", "Fish and chips with vinegar"),
+        ("Breakfast", "Chips, then fish"),
+    ])
+    .await;
+
+    let page = harness
+        .search(r#""fish and chips" -vinegar"#, PageDirection::Forward)
+        .await
+        .unwrap();
+    assert_eq!(titles(&page), vec!["Lunch"]);
+}
+
+/// 検索結果には本文をエスケープし一致語を `<mark>` で囲んだ抜粋が付く
+#[tokio::test]
+async fn results_carry_highlighted_headlines() {
+    let harness = Harness::with_articles(&[("Lunch", "<b>Fish</b> & chips")]).await;
+
+    let page = harness
+        .search("chips", PageDirection::Forward)
+        .await
+        .unwrap();
+    assert_eq!(
+        page.items[0].headline.as_deref(),
+        Some("&lt;b&gt;Fish&lt;/b&gt; &amp; <mark>chips</mark>")
+    );
+    let json = serde_json::to_value(&page.items[0]).unwrap();
+    assert_eq!(json["title"], "Lunch");
+    assert!(json.get("article").is_none());
+}
+
+/// 空の検索語や逆方向のページングは検証エラーになる
+#[tokio::test]
+async fn blank_queries_and_backward_paging_are_rejected() {
+    let harness = Harness::with_articles(&[("Lunch", "Fish and chips")]).await;
+
+    for (query, direction) in [
+        ("   ", PageDirection::Forward),
+        ("fish", PageDirection::Backward),
+    ] {
+        let err = harness.search(query, direction).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)), "{err}");
+    }
+}
+
+/// 検索エンドポイントは結果ページを返し、空の検索語には 400 を返す
+#[tokio::test]
+async fn e2e_search_endpoint_returns_pages_and_rejects_blank_queries() {
+    let app = support::make_test_router().await;
+    let get = |uri: &str| {
+        Request::builder()
+            .method(Method::GET)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let resp = app
+        .clone()
+        .oneshot(get("/api/v1/articles/search?q=fish"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_, json) = to_json_async!(resp).await;
+    assert_eq!(json["items"], serde_json::json!([]));
+    assert_eq!(json["has_more"], false);
+
+    let resp = app
+        .oneshot(get("/api/v1/articles/search?q=%20"))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
+
+/// ファセット付き検索は公開状態・著者・タグごとの件数と総数を返す
+#[tokio::test]
+async fn faceted_search_counts_every_match() {
+    let harness = Harness::with_articles(&[
+        ("Lunch", "Fish and chips"),
+        ("Dinner", "Fish pie"),
+        ("Breakfast", "Toast"),
+    ])
+    .await;
+    let tagged = CreateArticleCommand::builder()
+        .title("Supper")
+        .body("Fish soup")
+        .tags(["soup", "fish"])
+        .build()
+        .unwrap();
+    harness
+        .commands
+        .create_article(&admin(), tagged)
+        .await
+        .unwrap();
+
+    let (page, facets) = harness
+        .queries
+        .faceted_search(
+            Some(&admin()),
+            SearchArticlesQuery {
+                query: "fish".into(),
+                include_drafts: true,
+                limit: 1,
+                cursor: None,
+                direction: PageDirection::Forward,
+                tag: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert!(page.has_more);
+    assert_eq!(page.total_estimate, Some(3));
+
+    let facets = facets.unwrap();
+    assert_eq!((facets.published, facets.drafts), (2, 1));
+    assert_eq!(facets.authors.len(), 1);
+    assert_eq!(
+        (facets.authors[0].author_id, facets.authors[0].count),
+        (1, 3)
+    );
+    let tags: Vec<_> = facets
+        .tags
+        .iter()
+        .map(|facet| (facet.tag.as_str(), facet.count))
+        .collect();
+    assert_eq!(tags, vec![("fish", 1), ("soup", 1)]);
+}
+
+/// ファセットの件数には、呼び出し元が読めない他人の非公開記事を含めない
+#[tokio::test]
+async fn faceted_search_leaves_out_private_articles_of_others() {
+    let harness = Harness::with_articles(&[("Lunch", "Fish and chips")]).await;
+    let author = user(2, Role::Author);
+    for (actor, title) in [(admin(), "Secret fish"), (author.clone(), "My fish")] {
+        let command = CreateArticleCommand::builder()
+            .title(title)
+            .body("Fish")
+            .publish(true)
+            .visibility(ArticleVisibility::Private)
+            .build()
+            .unwrap();
+        harness
+            .commands
+            .create_article(&actor, command)
+            .await
+            .unwrap();
+    }
+    let count = |actor: AuthenticatedUser| {
+        let queries = &harness.queries;
+        async move {
+            let (_, facets) = queries
+                .faceted_search(
+                    Some(&actor),
+                    SearchArticlesQuery {
+                        query: "fish".into(),
+                        include_drafts: true,
+                        limit: 10,
+                        cursor: None,
+                        direction: PageDirection::Forward,
+                        tag: None,
+                    },
+                )
+                .await
+                .unwrap();
+            let facets = facets.unwrap();
+            let authors: Vec<_> = facets
+                .authors
+                .iter()
+                .map(|facet| (facet.author_id, facet.count))
+                .collect();
+            (facets.published, authors)
+        }
+    };
+
+    assert_eq!(count(author).await, (2, vec![(1, 1), (2, 1)]));
+    assert_eq!(count(admin()).await, (3, vec![(1, 2), (2, 1)]));
+}
+
+/// `/api/v1/search` は件数を数えられないストアではファセットを `null` で返す
+#[tokio::test]
+async fn e2e_site_search_returns_results_without_uncountable_facets() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method(Method::GET)
+                .uri("/api/v1/search?q=fish")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_, json) = to_json_async!(resp).await;
+    assert_eq!(json["items"], serde_json::json!([]));
+    assert!(json["facets"].is_null());
+    assert!(json["total_estimate"].is_null());
+}
@@ -0,0 +1,244 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/article_slug_cache.rs
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use chrono::{Duration, Utc};
+use mokkan_core::application::commands::articles::{
+    ArticleCommandService, CreateArticleCommand, DeleteArticleCommand, UpdateArticleCommand,
+};
+use mokkan_core::application::ports::domain_events::{EventHandler, EventPublisher};
+use mokkan_core::application::ports::time::ClockControl;
+use mokkan_core::application::queries::articles::{ArticleQueryService, GetArticleBySlugQuery};
+use mokkan_core::application::services::{
+    DomainEventBus, SlugCache, SlugCachePolicy, SlugCacheStats,
+};
+use mokkan_core::application::{AppError, AppResult, ArticleDto, AuthenticatedUser};
+use mokkan_core::async_support::BoxFuture;
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::errors::DomainResult;
+use mokkan_core::domain::{
+    Article, ArticleId, ArticleListCursor, ArticleReadRepository, ArticleSlug, Role, UserId,
+};
+use mokkan_core::infrastructure::repositories::InMemoryStores;
+use mokkan_core::infrastructure::time::SimulatedClock;
+
+mod support;
+
+/// Counts the slug lookups that reach the store.
+struct CountingReads {
+    inner: Arc<dyn ArticleReadRepository>,
+    slug_lookups: AtomicUsize,
+}
+
+impl ArticleReadRepository for CountingReads {
+    fn find_by_id(&self, id: ArticleId) -> BoxFuture<'_, DomainResult<Option<Article>>> {
+        self.inner.find_by_id(id)
+    }
+
+    fn find_by_slug<'a>(
+        &'a self,
+        slug: &'a ArticleSlug,
+    ) -> BoxFuture<'a, DomainResult<Option<Article>>> {
+        self.slug_lookups.fetch_add(1, Ordering::SeqCst);
+        self.inner.find_by_slug(slug)
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        include_drafts: bool,
+        limit: u32,
+        cursor: Option<ArticleListCursor>,
+        search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        self.inner.list_page(include_drafts, limit, cursor, search)
+    }
+}
+
+struct Harness {
+    commands: ArticleCommandService,
+    queries: ArticleQueryService,
+    reads: Arc<CountingReads>,
+    cache: SlugCache,
+    clock: SimulatedClock,
+}
+
+impl Harness {
+    fn new(policy: SlugCachePolicy) -> Self {
+        let stores = InMemoryStores::new();
+        let clock = SimulatedClock::new();
+        let cache = SlugCache::new(policy, Arc::new(clock.clone()));
+        let reads = Arc::new(CountingReads {
+            inner: stores.articles.clone(),
+            slug_lookups: AtomicUsize::new(0),
+        });
+        let slugs = Arc::new(ArticleSlugService::new(
+            stores.articles.clone(),
+            Arc::new(support::DummySlug),
+        ));
+        let commands = ArticleCommandService::new(
+            stores.articles.clone(),
+            stores.articles.clone(),
+            stores.articles.clone(),
+            slugs,
+            Arc::new(clock.clone()),
+        )
+        .with_event_publisher(Arc::new(
+            DomainEventBus::default()
+                .with_handler(Arc::new(cache.clone()) as Arc<dyn EventHandler>),
+        ) as Arc<dyn EventPublisher>);
+        let queries =
+            ArticleQueryService::new(reads.clone(), stores.articles).with_slug_cache(cache.clone());
+        Self {
+            commands,
+            queries,
+            reads,
+            cache,
+            clock,
+        }
+    }
+
+    async fn create(&self, title: &str, publish: bool) -> ArticleDto {
+        let command = CreateArticleCommand::builder()
+            .title(title)
+            .body("body")
+            .publish(publish)
+            .build()
+            .unwrap();
+        self.commands
+            .create_article(&admin(), command)
+            .await
+            .unwrap()
+    }
+
+    async fn get(&self, actor: Option<&AuthenticatedUser>, slug: &str) -> AppResult<ArticleDto> {
+        self.queries
+            .get_article_by_slug(actor, GetArticleBySlugQuery { slug: slug.into() })
+            .await
+    }
+
+    fn slug_lookups(&self) -> usize {
+        self.reads.slug_lookups.load(Ordering::SeqCst)
+    }
+}
+
+fn admin() -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(1).unwrap(),
+        username: "admin".into(),
+        role: Role::Admin,
+        capabilities: Role::Admin.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+/// 同じスラグの2回目以降はリポジトリを引かず、下書きの閲覧制限はキャッシュ経由でも効く
+#[tokio::test]
+async fn repeated_lookups_are_served_from_memory() {
+    let harness = Harness::new(SlugCachePolicy::default());
+    harness.create("popular", true).await;
+    harness.create("draft", false).await;
+
+    for _ in 0..3 {
+        assert_eq!(harness.get(None, "popular").await.unwrap().slug, "popular");
+    }
+    assert!(harness.get(Some(&admin()), "draft").await.is_ok());
+    let anonymous = harness.get(None, "draft").await;
+    assert!(matches!(anonymous, Err(AppError::NotFound(_))));
+
+    assert_eq!(harness.slug_lookups(), 2);
+    assert_eq!(
+        harness.cache.stats(),
+        SlugCacheStats {
+            hits: 3,
+            negative_hits: 0,
+            misses: 2,
+            entries: 2,
+        }
+    );
+}
+
+/// 存在しないスラグは短時間覚えておき、その記事が作られれば即座に見つかる
+#[tokio::test]
+async fn misses_are_cached_until_the_slug_is_created() {
+    let harness = Harness::new(SlugCachePolicy::default());
+
+    for _ in 0..3 {
+        let missing = harness.get(None, "coming-soon").await;
+        assert!(matches!(missing, Err(AppError::NotFound(_))));
+    }
+    assert_eq!(harness.slug_lookups(), 1);
+    assert_eq!(harness.cache.stats().negative_hits, 2);
+
+    harness.create("coming-soon", true).await;
+    assert!(harness.get(None, "coming-soon").await.is_ok());
+    assert_eq!(harness.slug_lookups(), 2);
+}
+
+/// 記事の更新・削除でキャッシュが無効化され、古いスラグも新しいスラグも正しく引ける
+#[tokio::test]
+async fn writes_invalidate_old_and_new_slugs() {
+    let harness = Harness::new(SlugCachePolicy::default());
+    let created = harness.create("before", true).await;
+    harness.get(None, "before").await.unwrap();
+    assert!(harness.get(None, "after").await.is_err());
+
+    harness
+        .commands
+        .update_article(
+            &admin(),
+            UpdateArticleCommand {
+                id: created.id,
+                title: Some("after".into()),
+                body: None,
+                publish: None,
+                tags: None,
+                custom_fields: None,
+                visibility: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert!(harness.get(None, "before").await.is_err());
+    assert_eq!(harness.get(None, "after").await.unwrap().title, "after");
+
+    harness
+        .commands
+        .delete_article(&admin(), DeleteArticleCommand { id: created.id })
+        .await
+        .unwrap();
+    assert!(harness.get(None, "after").await.is_err());
+}
+
+/// エントリは TTL で期限切れになり、容量を超えると古いものから追い出される
+#[tokio::test]
+async fn entries_expire_and_the_oldest_is_evicted() {
+    let harness = Harness::new(SlugCachePolicy {
+        capacity: 2,
+        ttl: Duration::seconds(60),
+        negative_ttl: Duration::seconds(5),
+    });
+    harness.create("first", true).await;
+
+    harness.get(None, "first").await.unwrap();
+    let _ = harness.get(None, "nothing").await;
+    harness.clock.advance(Duration::seconds(10));
+    harness.get(None, "first").await.unwrap();
+    let _ = harness.get(None, "nothing").await;
+    assert_eq!(harness.slug_lookups(), 3, "only the miss expired");
+
+    let _ = harness.get(None, "other").await;
+    assert_eq!(harness.cache.stats().entries, 2);
+    harness.get(None, "first").await.unwrap();
+    assert_eq!(harness.slug_lookups(), 5, "the oldest entry was evicted");
+
+    harness.clock.advance(Duration::seconds(61));
+    harness.get(None, "first").await.unwrap();
+    assert_eq!(harness.slug_lookups(), 6);
+}
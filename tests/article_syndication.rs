@@ -0,0 +1,296 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/article_syndication.rs
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use mokkan_core::application::commands::articles::{
+    ArticleCommandService, CreateArticleCommand, SetPublishStateCommand, UpdateArticleCommand,
+};
+use mokkan_core::application::ports::domain_events::{EventHandler, EventPublisher};
+use mokkan_core::application::ports::jobs::JobQueue;
+use mokkan_core::application::ports::syndication::{Announcement, SyndicationTarget, Syndicator};
+use mokkan_core::application::services::{DomainEventBus, SyndicationPolicy, SyndicationService};
+use mokkan_core::application::{AppError, AppResult, ArticleDto, AuthenticatedUser};
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::{Role, UserId};
+use mokkan_core::infrastructure::repositories::InMemoryStores;
+use mokkan_core::infrastructure::time::SimulatedClock;
+
+mod support;
+
+/// Holds queued jobs until the test runs them.
+#[derive(Default)]
+struct ManualQueue(Mutex<Vec<BoxFuture<'static, ()>>>);
+
+impl ManualQueue {
+    fn pending(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    /// Run the jobs queued so far; jobs they queue wait for the next call.
+    async fn run_pending(&self) {
+        let jobs = std::mem::take(&mut *self.0.lock().unwrap());
+        for job in jobs {
+            job.await;
+        }
+    }
+}
+
+impl JobQueue for ManualQueue {
+    fn enqueue(&self, _name: &'static str, job: BoxFuture<'static, ()>) {
+        self.0.lock().unwrap().push(job);
+    }
+}
+
+/// Records posts, failing the first `failures` of them.
+#[derive(Default)]
+struct CapturingSyndicator {
+    posts: Mutex<Vec<(String, String)>>,
+    failures: AtomicU32,
+}
+
+impl CapturingSyndicator {
+    fn failing(failures: u32) -> Self {
+        Self {
+            failures: AtomicU32::new(failures),
+            ..Self::default()
+        }
+    }
+
+    fn posts(&self) -> Vec<(String, String)> {
+        self.posts.lock().unwrap().clone()
+    }
+}
+
+impl Syndicator for CapturingSyndicator {
+    fn post<'a>(
+        &'a self,
+        text: &'a str,
+        announcement: &'a Announcement,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        boxed(async move {
+            let failed = self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| {
+                    left.checked_sub(1)
+                })
+                .is_ok();
+            if failed {
+                return Err(AppError::infrastructure("platform unavailable"));
+            }
+            self.posts
+                .lock()
+                .unwrap()
+                .push((text.to_owned(), announcement.url.clone()));
+            Ok(())
+        })
+    }
+}
+
+struct Harness {
+    commands: ArticleCommandService,
+    syndication: Arc<SyndicationService>,
+    queue: Arc<ManualQueue>,
+    mastodon: Arc<CapturingSyndicator>,
+    slack: Arc<CapturingSyndicator>,
+}
+
+impl Harness {
+    fn new(mastodon: CapturingSyndicator, max_attempts: u32) -> Self {
+        let stores = InMemoryStores::new();
+        let clock = SimulatedClock::new();
+        let queue = Arc::new(ManualQueue::default());
+        let mastodon = Arc::new(mastodon);
+        let slack = Arc::new(CapturingSyndicator::default());
+        let targets = vec![
+            SyndicationTarget {
+                name: "mastodon".into(),
+                template: "{title} {url} {tags}".into(),
+                syndicator: mastodon.clone(),
+            },
+            SyndicationTarget {
+                name: "team-slack".into(),
+                template: "New post: {title}".into(),
+                syndicator: slack.clone(),
+            },
+        ];
+        let syndication = Arc::new(SyndicationService::new(
+            targets,
+            stores.articles.clone(),
+            stores.syndication_opt_outs.clone(),
+            queue.clone(),
+            SyndicationPolicy {
+                article_url: "https://blog.example/{slug}".into(),
+                delay: Duration::ZERO,
+                max_attempts,
+                retry_backoff: Duration::ZERO,
+            },
+        ));
+        let slugs = Arc::new(ArticleSlugService::new(
+            stores.articles.clone(),
+            Arc::new(support::DummySlug),
+        ));
+        let commands = ArticleCommandService::new(
+            stores.articles.clone(),
+            stores.articles.clone(),
+            stores.articles,
+            slugs,
+            Arc::new(clock),
+        )
+        .with_event_publisher(Arc::new(
+            DomainEventBus::default().with_handler(syndication.clone() as Arc<dyn EventHandler>),
+        ) as Arc<dyn EventPublisher>);
+        Self {
+            commands,
+            syndication,
+            queue,
+            mastodon,
+            slack,
+        }
+    }
+
+    async fn create(&self, title: &str, publish: bool) -> ArticleDto {
+        let command = CreateArticleCommand::builder()
+            .title(title)
+            .body("body")
+            .tags(["release-notes", "rust"])
+            .publish(publish)
+            .build()
+            .unwrap();
+        self.commands
+            .create_article(&user(1, Role::Admin), command)
+            .await
+            .unwrap()
+    }
+
+    async fn set_published(&self, id: i64, publish: bool) {
+        self.commands
+            .set_publish_state(
+                &user(1, Role::Admin),
+                SetPublishStateCommand { id, publish },
+            )
+            .await
+            .unwrap();
+    }
+}
+
+fn user(id: i64, role: Role) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(id).unwrap(),
+        username: format!("user{id}"),
+        role,
+        capabilities: role.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + chrono::Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+/// 公開時に各ターゲットへテンプレートどおりの告知が一度だけ投稿され、下書きや再編集では投稿されない
+#[tokio::test]
+async fn publishing_announces_once_on_every_target() {
+    let harness = Harness::new(CapturingSyndicator::default(), 3);
+    let draft = harness.create("draft", false).await;
+    harness.create("launch", true).await;
+    assert_eq!(harness.queue.pending(), 2);
+    harness.queue.run_pending().await;
+
+    assert_eq!(
+        harness.mastodon.posts(),
+        vec![(
+            "launch https://blog.example/launch #releasenotes #rust".to_owned(),
+            "https://blog.example/launch".to_owned()
+        )]
+    );
+    assert_eq!(harness.slack.posts()[0].0, "New post: launch");
+
+    harness
+        .commands
+        .update_article(
+            &user(1, Role::Admin),
+            UpdateArticleCommand {
+                id: draft.id,
+                title: None,
+                body: Some("edited".into()),
+                publish: None,
+                tags: None,
+                custom_fields: None,
+                visibility: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(harness.queue.pending(), 0);
+
+    harness.set_published(draft.id, true).await;
+    harness.queue.run_pending().await;
+    assert_eq!(harness.mastodon.posts().len(), 2);
+}
+
+/// オプトアウトした記事や、送信前に非公開へ戻った記事は告知されない
+#[tokio::test]
+async fn opted_out_and_unpublished_articles_are_skipped() {
+    let harness = Harness::new(CapturingSyndicator::default(), 3);
+    let article = harness.create("quiet", false).await;
+    let admin = user(1, Role::Admin);
+
+    let settings = harness
+        .syndication
+        .set_enabled(&admin, article.id, false)
+        .await
+        .unwrap();
+    assert!(!settings.enabled);
+    assert_eq!(settings.targets, ["mastodon", "team-slack"]);
+    harness.set_published(article.id, true).await;
+    harness.queue.run_pending().await;
+    assert!(harness.mastodon.posts().is_empty());
+
+    harness
+        .syndication
+        .set_enabled(&admin, article.id, true)
+        .await
+        .unwrap();
+    harness.set_published(article.id, false).await;
+    harness.set_published(article.id, true).await;
+    harness.set_published(article.id, false).await;
+    harness.queue.run_pending().await;
+    assert!(harness.mastodon.posts().is_empty());
+    assert!(harness.slack.posts().is_empty());
+
+    let other_author = user(2, Role::Author);
+    let denied = harness
+        .syndication
+        .settings(&other_author, article.id)
+        .await;
+    assert!(matches!(denied, Err(AppError::Forbidden(_))));
+}
+
+/// 失敗した投稿はジョブキュー経由で再試行され、上限に達すると諦める
+#[tokio::test]
+async fn failed_posts_are_retried_up_to_the_limit() {
+    let harness = Harness::new(CapturingSyndicator::failing(2), 3);
+    harness.create("flaky", true).await;
+    harness.queue.run_pending().await;
+    assert!(harness.mastodon.posts().is_empty());
+    assert_eq!(harness.slack.posts().len(), 1);
+    assert_eq!(harness.queue.pending(), 1, "only the failed target retries");
+
+    harness.queue.run_pending().await;
+    harness.queue.run_pending().await;
+    assert_eq!(harness.mastodon.posts().len(), 1);
+    assert_eq!(harness.queue.pending(), 0);
+
+    let harness = Harness::new(CapturingSyndicator::failing(5), 2);
+    harness.create("down", true).await;
+    harness.queue.run_pending().await;
+    harness.queue.run_pending().await;
+    assert_eq!(harness.queue.pending(), 0, "gave up after two attempts");
+    assert!(harness.mastodon.posts().is_empty());
+}
@@ -0,0 +1,251 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/article_views.rs
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use mokkan_core::application::commands::articles::{ArticleCommandService, CreateArticleCommand};
+use mokkan_core::application::ports::views::ViewCounter;
+use mokkan_core::application::queries::articles::{
+    ArticleQueryService, GetArticleBySlugQuery, ListArticlesQuery,
+};
+use mokkan_core::application::services::ArticleViewService;
+use mokkan_core::application::{
+    AppError, AppResult, ArticleDto, ArticleSort, AuthenticatedUser, CursorPage, PageDirection,
+};
+use mokkan_core::async_support::BoxFuture;
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::errors::DomainResult;
+use mokkan_core::domain::{
+    Article, ArticleId, ArticleUpdate, ArticleWriteRepository, NewArticle, Role, UserId,
+};
+use mokkan_core::infrastructure::repositories::InMemoryStores;
+use mokkan_core::infrastructure::time::SimulatedClock;
+use mokkan_core::infrastructure::views::InMemoryViewCounter;
+
+mod support;
+
+/// Writes articles but cannot count views, like a store without the column.
+struct NoViewCounting(Arc<dyn ArticleWriteRepository>);
+
+impl ArticleWriteRepository for NoViewCounting {
+    fn insert(&self, article: NewArticle) -> BoxFuture<'_, DomainResult<Article>> {
+        self.0.insert(article)
+    }
+
+    fn update(&self, update: ArticleUpdate) -> BoxFuture<'_, DomainResult<Article>> {
+        self.0.update(update)
+    }
+
+    fn delete(&self, id: ArticleId) -> BoxFuture<'_, DomainResult<()>> {
+        self.0.delete(id)
+    }
+}
+
+struct Harness {
+    commands: ArticleCommandService,
+    queries: ArticleQueryService,
+    counter: Arc<InMemoryViewCounter>,
+    views: ArticleViewService,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let stores = InMemoryStores::new();
+        let clock = Arc::new(SimulatedClock::new());
+        let counter = Arc::new(InMemoryViewCounter::new());
+        let slugs = Arc::new(ArticleSlugService::new(
+            stores.articles.clone(),
+            Arc::new(support::DummySlug),
+        ));
+        let commands = ArticleCommandService::new(
+            stores.articles.clone(),
+            stores.articles.clone(),
+            stores.articles.clone(),
+            slugs,
+            clock,
+        );
+        let queries = ArticleQueryService::new(stores.articles.clone(), stores.articles.clone())
+            .with_view_counter(counter.clone());
+        let views = ArticleViewService::new(counter.clone(), stores.articles);
+        Self {
+            commands,
+            queries,
+            counter,
+            views,
+        }
+    }
+
+    async fn create(&self, title: &str, publish: bool) -> ArticleDto {
+        let command = CreateArticleCommand::builder()
+            .title(title)
+            .body("body")
+            .publish(publish)
+            .build()
+            .unwrap();
+        self.commands
+            .create_article(&admin(), command)
+            .await
+            .unwrap()
+    }
+
+    async fn read(&self, slug: &str, times: usize) {
+        for _ in 0..times {
+            self.queries
+                .get_article_by_slug(Some(&admin()), GetArticleBySlugQuery { slug: slug.into() })
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn popular(
+        &self,
+        limit: u32,
+        cursor: Option<String>,
+        direction: PageDirection,
+    ) -> AppResult<CursorPage<ArticleDto>> {
+        self.queries
+            .list_articles(
+                Some(&admin()),
+                ListArticlesQuery {
+                    include_drafts: true,
+                    limit,
+                    cursor,
+                    direction,
+                    tag: None,
+                    sort: ArticleSort::Popular,
+                    page: None,
+                },
+            )
+            .await
+    }
+}
+
+fn admin() -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(1).unwrap(),
+        username: "admin".into(),
+        role: Role::Admin,
+        capabilities: Role::Admin.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+fn titles(page: &CursorPage<ArticleDto>) -> Vec<(&str, u64)> {
+    page.items
+        .iter()
+        .map(|article| (article.title.as_str(), article.view_count))
+        .collect()
+}
+
+/// 公開記事のスラグ閲覧だけが数えられ、フラッシュ後に `view_count` へ反映される
+#[tokio::test]
+async fn published_reads_are_counted_and_flushed() {
+    let harness = Harness::new();
+    let published = harness.create("hello", true).await;
+    let draft = harness.create("draft", false).await;
+    harness.read(&published.slug, 3).await;
+    harness.read(&draft.slug, 2).await;
+
+    let page = harness
+        .popular(10, None, PageDirection::Forward)
+        .await
+        .unwrap();
+    assert_eq!(titles(&page), vec![("draft", 0), ("hello", 0)]);
+
+    assert_eq!(harness.views.flush().await.unwrap(), 3);
+    assert_eq!(harness.views.flush().await.unwrap(), 0);
+
+    let page = harness
+        .popular(10, None, PageDirection::Forward)
+        .await
+        .unwrap();
+    assert_eq!(titles(&page), vec![("hello", 3), ("draft", 0)]);
+}
+
+/// 人気順は閲覧数の多い順に並び、カーソルで続きのページを取得できる
+#[tokio::test]
+async fn popular_listing_pages_by_view_count() {
+    let harness = Harness::new();
+    for (title, views) in [("first", 1), ("second", 5), ("third", 3)] {
+        let article = harness.create(title, true).await;
+        harness
+            .counter
+            .add(ArticleId::new(article.id).unwrap(), views)
+            .await
+            .unwrap();
+    }
+    harness.views.flush().await.unwrap();
+
+    let page = harness
+        .popular(2, None, PageDirection::Forward)
+        .await
+        .unwrap();
+    assert_eq!(titles(&page), vec![("second", 5), ("third", 3)]);
+    assert!(page.has_more);
+
+    let next = harness
+        .popular(2, page.next_cursor, PageDirection::Forward)
+        .await
+        .unwrap();
+    assert_eq!(titles(&next), vec![("first", 1)]);
+    assert!(!next.has_more);
+}
+
+/// 人気順では逆方向のページングや新着順のカーソルを受け付けない
+#[tokio::test]
+async fn popular_listing_rejects_backward_paging_and_foreign_cursors() {
+    let harness = Harness::new();
+    harness.create("first", true).await;
+    harness.create("second", true).await;
+
+    let err = harness
+        .popular(1, None, PageDirection::Backward)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)), "{err}");
+
+    let newest = harness
+        .queries
+        .list_articles(
+            None,
+            ListArticlesQuery {
+                include_drafts: false,
+                limit: 1,
+                cursor: None,
+                direction: PageDirection::Forward,
+                tag: None,
+                sort: ArticleSort::Newest,
+                page: None,
+            },
+        )
+        .await
+        .unwrap();
+    let err = harness
+        .popular(1, newest.next_cursor, PageDirection::Forward)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)), "{err}");
+}
+
+/// 書き込みに失敗したフラッシュは閲覧数をカウンタへ戻し、次回に持ち越す
+#[tokio::test]
+async fn failed_flushes_keep_pending_views() {
+    let stores = InMemoryStores::new();
+    let counter = Arc::new(InMemoryViewCounter::new());
+    let id = ArticleId::new(7).unwrap();
+    counter.add(id, 4).await.unwrap();
+
+    let failing = ArticleViewService::new(
+        counter.clone(),
+        Arc::new(NoViewCounting(stores.articles.clone())),
+    );
+    assert!(failing.flush().await.is_err());
+
+    assert_eq!(counter.drain().await.unwrap(), vec![(id, 4)]);
+}
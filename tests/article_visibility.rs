@@ -0,0 +1,217 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/article_visibility.rs
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use mokkan_core::application::commands::articles::{
+    ArticleCommandService, CreateArticleCommand, UpdateArticleCommand,
+};
+use mokkan_core::application::queries::articles::{
+    ArticleQueryService, BatchGetArticlesQuery, GetArticleBySlugQuery, ListArticlesQuery,
+    SearchArticlesQuery,
+};
+use mokkan_core::application::{
+    AppError, ArticleDto, ArticleSort, AuthenticatedUser, PageDirection,
+};
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::{ArticleVisibility, Role, UserId};
+use mokkan_core::infrastructure::repositories::InMemoryStores;
+use mokkan_core::infrastructure::time::SimulatedClock;
+
+mod support;
+
+struct Harness {
+    commands: ArticleCommandService,
+    queries: ArticleQueryService,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let stores = InMemoryStores::new();
+        let slugs = Arc::new(ArticleSlugService::new(
+            stores.articles.clone(),
+            Arc::new(support::DummySlug),
+        ));
+        let commands = ArticleCommandService::new(
+            stores.articles.clone(),
+            stores.articles.clone(),
+            stores.articles.clone(),
+            slugs,
+            Arc::new(SimulatedClock::new()),
+        );
+        let queries = ArticleQueryService::new(stores.articles.clone(), stores.articles);
+        Self { commands, queries }
+    }
+
+    async fn create(
+        &self,
+        actor: &AuthenticatedUser,
+        title: &str,
+        visibility: ArticleVisibility,
+    ) -> ArticleDto {
+        let command = CreateArticleCommand::builder()
+            .title(title)
+            .body("body")
+            .publish(true)
+            .visibility(visibility)
+            .build()
+            .unwrap();
+        self.commands.create_article(actor, command).await.unwrap()
+    }
+
+    async fn read(
+        &self,
+        actor: Option<&AuthenticatedUser>,
+        slug: &str,
+    ) -> Result<ArticleDto, AppError> {
+        self.queries
+            .get_article_by_slug(actor, GetArticleBySlugQuery { slug: slug.into() })
+            .await
+    }
+
+    async fn listed(&self, actor: Option<&AuthenticatedUser>, include_drafts: bool) -> Vec<String> {
+        let page = self
+            .queries
+            .list_articles(
+                actor,
+                ListArticlesQuery {
+                    include_drafts,
+                    limit: 20,
+                    cursor: None,
+                    direction: PageDirection::Forward,
+                    tag: None,
+                    sort: ArticleSort::Newest,
+                    page: None,
+                },
+            )
+            .await
+            .unwrap();
+        page.items
+            .into_iter()
+            .map(|article| article.title)
+            .collect()
+    }
+}
+
+fn user(id: i64, role: Role) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(id).unwrap(),
+        username: format!("user{id}"),
+        role,
+        capabilities: role.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+/// 限定公開の記事はスラッグで読めるが、公開の一覧や検索には出ない
+#[tokio::test]
+async fn unlisted_articles_are_readable_by_slug_but_not_listed() {
+    let harness = Harness::new();
+    let author = user(2, Role::Author);
+    harness
+        .create(&author, "Open", ArticleVisibility::Public)
+        .await;
+    let unlisted = harness
+        .create(&author, "Hidden", ArticleVisibility::Unlisted)
+        .await;
+    assert_eq!(unlisted.visibility, ArticleVisibility::Unlisted);
+
+    let read = harness.read(None, &unlisted.slug).await.unwrap();
+    assert_eq!(read.id, unlisted.id);
+    assert_eq!(harness.listed(None, false).await, ["Open"]);
+
+    let found = harness
+        .queries
+        .search_articles(
+            None,
+            SearchArticlesQuery {
+                query: "body".into(),
+                include_drafts: false,
+                limit: 20,
+                cursor: None,
+                direction: PageDirection::Forward,
+                tag: None,
+            },
+        )
+        .await
+        .unwrap();
+    let titles: Vec<_> = found.items.iter().map(|a| a.title.as_str()).collect();
+    assert_eq!(titles, ["Open"]);
+}
+
+/// 非公開の記事は作成者と articles:view:any を持つユーザーにしか見えない
+#[tokio::test]
+async fn private_articles_are_limited_to_author_and_view_any() {
+    let harness = Harness::new();
+    let author = user(2, Role::Author);
+    let other = user(3, Role::Author);
+    let admin = user(1, Role::Admin);
+    harness
+        .create(&author, "Open", ArticleVisibility::Public)
+        .await;
+    let private = harness
+        .create(&author, "Diary", ArticleVisibility::Private)
+        .await;
+
+    for actor in [None, Some(&other)] {
+        let err = harness.read(actor, &private.slug).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)), "{err:?}");
+    }
+    assert!(harness.read(Some(&author), &private.slug).await.is_ok());
+    assert!(harness.read(Some(&admin), &private.slug).await.is_ok());
+
+    assert_eq!(harness.listed(None, false).await, ["Open"]);
+    assert_eq!(harness.listed(Some(&other), true).await, ["Open"]);
+    assert_eq!(harness.listed(Some(&author), true).await, ["Diary", "Open"]);
+    assert_eq!(harness.listed(Some(&admin), true).await, ["Diary", "Open"]);
+
+    let batch = harness
+        .queries
+        .batch_get_articles(
+            Some(&other),
+            BatchGetArticlesQuery {
+                ids: vec![private.id],
+            },
+        )
+        .await
+        .unwrap();
+    assert!(batch.items.is_empty());
+    assert_eq!(batch.missing_ids, [private.id]);
+}
+
+/// 更新で公開範囲を変えると一覧への出方も変わる
+#[tokio::test]
+async fn updating_visibility_changes_who_sees_the_article() {
+    let harness = Harness::new();
+    let author = user(2, Role::Author);
+    let article = harness
+        .create(&author, "Draft plans", ArticleVisibility::Private)
+        .await;
+    assert!(harness.listed(None, false).await.is_empty());
+
+    let updated = harness
+        .commands
+        .update_article(
+            &author,
+            UpdateArticleCommand {
+                id: article.id,
+                title: None,
+                body: None,
+                publish: None,
+                tags: None,
+                custom_fields: None,
+                visibility: Some(ArticleVisibility::Public),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.visibility, ArticleVisibility::Public);
+    assert_eq!(harness.listed(None, false).await, ["Draft plans"]);
+    assert!(harness.read(None, &article.slug).await.is_ok());
+}
@@ -29,6 +29,8 @@ async fn audit_query_service_list_decodes_cursor_and_returns_page() {
         expires_at: chrono::Utc::now(),
         session_id: None,
         token_version: None,
+        custom_role: None,
+        impersonator: None,
     };
 
     let q = ListAuditLogsQuery {
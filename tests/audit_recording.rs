@@ -0,0 +1,244 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/audit_recording.rs
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use mokkan_core::application::commands::articles::{
+    ArticleCommandService, CreateArticleCommand, DeleteArticleCommand, SetPublishStateCommand,
+    UpdateArticleCommand,
+};
+use mokkan_core::application::commands::users::{
+    GrantRoleCommand, LoginUserCommand, RegisterUserCommand, UserCommandService,
+};
+use mokkan_core::application::ports::security_events::ClientInfo;
+use mokkan_core::application::request_context::{self, RequestContext};
+use mokkan_core::application::{AppError, AuthenticatedUser};
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::{Role, UserId};
+use mokkan_core::infrastructure::repositories::InMemoryStores;
+use mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec;
+use mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore;
+
+mod support;
+
+fn admin(id: i64) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(id).unwrap(),
+        username: "admin".into(),
+        role: Role::Admin,
+        capabilities: Role::Admin.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+fn browser() -> RequestContext {
+    RequestContext {
+        client: ClientInfo {
+            ip_address: Some("203.0.113.5".into()),
+            user_agent: Some("Firefox/128.0".into()),
+        },
+    }
+}
+
+fn article_service(
+    stores: &InMemoryStores,
+    audit: &support::CapturingAuditRepo,
+) -> ArticleCommandService {
+    let slugs = Arc::new(ArticleSlugService::new(
+        stores.articles.clone(),
+        Arc::new(support::DummySlug),
+    ));
+    ArticleCommandService::new(
+        stores.articles.clone(),
+        stores.articles.clone(),
+        stores.articles.clone(),
+        slugs,
+        Arc::new(support::DummyClock),
+    )
+    .with_audit(Arc::new(audit.clone()))
+}
+
+fn user_service(
+    stores: &InMemoryStores,
+    audit: &support::CapturingAuditRepo,
+) -> UserCommandService {
+    UserCommandService::new(
+        stores.users.clone(),
+        Arc::new(support::DummyPasswordHasher),
+        Arc::new(support::DummyTokenManager),
+        Arc::new(HmacRefreshTokenCodec::new("test-refresh-secret").unwrap()),
+        Arc::new(InMemorySessionRevocationStore::new()),
+        Arc::new(support::DummyClock),
+    )
+    .with_audit(Arc::new(audit.clone()))
+}
+
+/// 記事の作成・更新・公開・削除が、操作者とリクエスト元とともに監査ログに残る
+#[tokio::test]
+async fn article_writes_are_audited_with_the_request_client() {
+    let stores = InMemoryStores::new();
+    let audit = support::CapturingAuditRepo::new();
+    let svc = article_service(&stores, &audit);
+    let actor = admin(7);
+
+    request_context::scope(browser(), async {
+        let created = svc
+            .create_article(
+                &actor,
+                CreateArticleCommand::builder()
+                    .title("audited")
+                    .body("first body")
+                    .build()
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        svc.update_article(
+            &actor,
+            UpdateArticleCommand {
+                id: created.id,
+                title: None,
+                body: Some("second body".into()),
+                publish: None,
+                tags: None,
+                custom_fields: None,
+                visibility: None,
+            },
+        )
+        .await
+        .unwrap();
+        svc.set_publish_state(
+            &actor,
+            SetPublishStateCommand {
+                id: created.id,
+                publish: true,
+            },
+        )
+        .await
+        .unwrap();
+        svc.delete_article(&actor, DeleteArticleCommand { id: created.id })
+            .await
+            .unwrap();
+    })
+    .await;
+
+    let logged = audit.get_inserted();
+    let actions: Vec<_> = logged.iter().map(|entry| entry.action.as_str()).collect();
+    assert_eq!(
+        actions,
+        [
+            "article.create",
+            "article.update",
+            "article.publish",
+            "article.delete"
+        ]
+    );
+    for entry in &logged {
+        assert_eq!(entry.user_id, Some(actor.id));
+        assert_eq!(entry.resource_type, "article");
+        assert_eq!(entry.resource_id, logged[0].resource_id);
+        assert_eq!(entry.ip_address.as_deref(), Some("203.0.113.5"));
+        assert_eq!(entry.user_agent.as_deref(), Some("Firefox/128.0"));
+    }
+}
+
+/// 失敗したコマンドは監査ログに残らない
+#[tokio::test]
+async fn failed_commands_are_not_audited() {
+    let stores = InMemoryStores::new();
+    let audit = support::CapturingAuditRepo::new();
+    let svc = article_service(&stores, &audit);
+
+    let result = svc
+        .delete_article(&admin(7), DeleteArticleCommand { id: 404 })
+        .await;
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+    assert!(audit.get_inserted().is_empty());
+}
+
+/// 登録・ログイン・ロール変更が監査ログに残り、ログインはクライアント情報を持つ
+#[tokio::test]
+async fn registration_login_and_role_changes_are_audited() {
+    let stores = InMemoryStores::new();
+    let audit = support::CapturingAuditRepo::new();
+    let svc = user_service(&stores, &audit);
+
+    let first = svc
+        .register(
+            None,
+            RegisterUserCommand {
+                username: "owner".into(),
+                password: "Owner-Password-1!".into(),
+                role: None,
+            },
+        )
+        .await
+        .unwrap();
+    let owner = admin(first.id);
+    let writer = svc
+        .register(
+            Some(&owner),
+            RegisterUserCommand {
+                username: "writer".into(),
+                password: "Writer-Password-1!".into(),
+                role: None,
+            },
+        )
+        .await
+        .unwrap();
+    let phone = ClientInfo {
+        ip_address: Some("198.51.100.20".into()),
+        user_agent: Some("mokkan-ios/2.1".into()),
+    };
+    svc.login_from_client(
+        LoginUserCommand {
+            username: "writer".into(),
+            password: "Writer-Password-1!".into(),
+        },
+        &phone,
+    )
+    .await
+    .unwrap();
+    svc.grant_role(
+        &owner,
+        GrantRoleCommand {
+            user_id: writer.id,
+            role: Role::Admin,
+        },
+    )
+    .await
+    .unwrap();
+
+    let logged = audit.get_inserted();
+    let actions: Vec<_> = logged.iter().map(|entry| entry.action.as_str()).collect();
+    assert_eq!(
+        actions,
+        [
+            "user.create",
+            "user.create",
+            "user.login",
+            "user.role_change"
+        ]
+    );
+    assert_eq!(logged[0].user_id, None);
+    assert_eq!(logged[1].user_id, Some(owner.id));
+    assert_eq!(logged[1].resource_id, Some(writer.id));
+
+    let login = &logged[2];
+    assert_eq!(login.user_id.map(i64::from), Some(writer.id));
+    assert_eq!(login.ip_address, phone.ip_address);
+    assert_eq!(login.user_agent, phone.user_agent);
+
+    let role_change = &logged[3];
+    assert_eq!(role_change.resource_id, Some(writer.id));
+    assert_eq!(
+        role_change.details.as_ref().unwrap()["role"],
+        serde_json::json!("admin")
+    );
+}
@@ -0,0 +1,72 @@
+#![allow(clippy::multiple_crate_versions)]
+#![cfg(feature = "client")]
+
+// tests/client_openapi.rs
+use mokkan_core::client::{OPERATIONS, Operation};
+use serde_json::Value;
+
+fn spec() -> Value {
+    serde_json::from_str(include_str!("../spec/openapi.json")).expect("valid OpenAPI snapshot")
+}
+
+fn schema_ref(name: &str) -> Value {
+    Value::String(format!("#/components/schemas/{name}"))
+}
+
+/// `$ref`s a response or request body schema may resolve to.
+fn refs(schema: &Value) -> Vec<Value> {
+    schema.get("oneOf").and_then(Value::as_array).map_or_else(
+        || schema.get("$ref").cloned().into_iter().collect(),
+        |variants| {
+            variants
+                .iter()
+                .filter_map(|v| v.get("$ref").cloned())
+                .collect()
+        },
+    )
+}
+
+fn describe(op: &Operation) -> String {
+    format!("{} {}", op.method.to_uppercase(), op.path)
+}
+
+/// クライアントが呼び出す全操作が `OpenAPI` スナップショットと一致することを確認する
+#[test]
+fn client_operations_match_the_openapi_snapshot() {
+    let spec = spec();
+    for op in OPERATIONS {
+        let documented = &spec["paths"][op.path][op.method];
+        assert!(
+            documented.is_object(),
+            "{} is not in the spec",
+            describe(op)
+        );
+
+        let request = &documented["requestBody"]["content"]["application/json"]["schema"];
+        match op.request {
+            Some(name) => assert!(
+                refs(request).contains(&schema_ref(name)),
+                "{} does not take {name}",
+                describe(op)
+            ),
+            None => assert!(request.is_null(), "{} takes a body", describe(op)),
+        }
+
+        let response = &documented["responses"]["200"]["content"]["application/json"]["schema"];
+        assert!(
+            refs(response).contains(&schema_ref(op.response)),
+            "{} does not return {}",
+            describe(op),
+            op.response
+        );
+    }
+}
+
+/// 同じ操作が重複して登録されていないことを確認する
+#[test]
+fn client_operations_are_unique() {
+    let mut seen: Vec<(&str, &str)> = OPERATIONS.iter().map(|op| (op.method, op.path)).collect();
+    seen.sort_unstable();
+    seen.dedup();
+    assert_eq!(seen.len(), OPERATIONS.len());
+}
@@ -0,0 +1,335 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/command_journal.rs
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::Duration;
+use mokkan_core::application::AuthenticatedUser;
+use mokkan_core::application::commands::articles::ArticleCommandService;
+use mokkan_core::application::commands::users::{
+    GrantRoleCommand, RegisterUserCommand, RevokeRoleCommand, UpdateUserCommand, UserCommandService,
+};
+use mokkan_core::application::ports::command_journal::{
+    CommandJournal, JournalEntry, JournalOutcome, JournaledCommand,
+};
+use mokkan_core::application::ports::time::Clock;
+use mokkan_core::application::services::{JournalReplayer, ReplayClock};
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::UserRepository;
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::errors::{DomainError, DomainResult};
+use mokkan_core::domain::user::entity::{NewUser, User, UserUpdate};
+use mokkan_core::domain::user::value_objects::{Role, UserId, UserListCursor, Username};
+
+mod support;
+
+/// Users keyed by id; ids are handed out from `next_id` so recorded and
+/// replayed ids differ.
+struct InMemoryUserRepo {
+    users: Mutex<HashMap<i64, User>>,
+    next_id: Mutex<i64>,
+}
+
+impl InMemoryUserRepo {
+    fn starting_at(id: i64) -> Self {
+        Self {
+            users: Mutex::new(HashMap::new()),
+            next_id: Mutex::new(id),
+        }
+    }
+
+    fn store(&self, new_user: NewUser) -> DomainResult<User> {
+        let mut next_id = self.next_id.lock().unwrap();
+        let user = User {
+            id: UserId::new(*next_id)?,
+            username: new_user.username,
+            password_hash: new_user.password_hash,
+            role: new_user.role,
+            is_active: new_user.is_active,
+            created_at: new_user.created_at,
+            timezone: None,
+            password_reset_required: new_user.password_reset_required,
+            email: None,
+            email_verified: false,
+        };
+        *next_id += 1;
+        drop(next_id);
+        self.users
+            .lock()
+            .unwrap()
+            .insert(user.id.into(), user.clone());
+        Ok(user)
+    }
+
+    fn snapshot(&self) -> Vec<User> {
+        let mut users: Vec<_> = self.users.lock().unwrap().values().cloned().collect();
+        users.sort_by_key(|user| i64::from(user.id));
+        users
+    }
+}
+
+impl UserRepository for InMemoryUserRepo {
+    fn count(&self) -> BoxFuture<'_, DomainResult<u64>> {
+        boxed(async move { Ok(self.users.lock().unwrap().len() as u64) })
+    }
+
+    fn insert(&self, new_user: NewUser) -> BoxFuture<'_, DomainResult<User>> {
+        boxed(async move { self.store(new_user) })
+    }
+
+    fn insert_bootstrap_admin(
+        &self,
+        new_user: NewUser,
+    ) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        boxed(async move {
+            if self.users.lock().unwrap().is_empty() {
+                self.store(new_user).map(Some)
+            } else {
+                Ok(None)
+            }
+        })
+    }
+
+    fn find_by_username<'a>(
+        &'a self,
+        username: &'a Username,
+    ) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        boxed(async move {
+            let users = self.users.lock().unwrap();
+            Ok(users
+                .values()
+                .find(|user| user.username.eq_ignore_case(username.as_str()))
+                .cloned())
+        })
+    }
+
+    fn find_by_id(&self, id: UserId) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        boxed(async move { Ok(self.users.lock().unwrap().get(&i64::from(id)).cloned()) })
+    }
+
+    fn update(&self, update: UserUpdate) -> BoxFuture<'_, DomainResult<User>> {
+        boxed(async move {
+            let mut users = self.users.lock().unwrap();
+            let user = users
+                .get_mut(&i64::from(update.id))
+                .ok_or_else(|| DomainError::NotFound("user not found".into()))?;
+            if let Some(is_active) = update.is_active {
+                user.is_active = is_active;
+            }
+            if let Some(role) = update.role {
+                user.role = role;
+            }
+            let user = user.clone();
+            drop(users);
+            Ok(user)
+        })
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        _limit: u32,
+        _cursor: Option<UserListCursor>,
+        _search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, Option<UserListCursor>)>> {
+        boxed(async move { Ok((vec![], None)) })
+    }
+}
+
+#[derive(Default)]
+struct MemoryJournal(Mutex<Vec<JournalEntry>>);
+
+impl CommandJournal for MemoryJournal {
+    fn append<'a>(
+        &'a self,
+        entry: &'a JournalEntry,
+    ) -> BoxFuture<'a, mokkan_core::application::AppResult<()>> {
+        boxed(async move {
+            self.0.lock().unwrap().push(entry.clone());
+            Ok(())
+        })
+    }
+}
+
+fn user_commands(repo: Arc<InMemoryUserRepo>, clock: Arc<dyn Clock>) -> UserCommandService {
+    UserCommandService::new(
+        repo,
+        Arc::new(support::DummyPasswordHasher),
+        Arc::new(support::DummyTokenManager),
+        Arc::new(
+            mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec::new(
+                "test-refresh-secret",
+            )
+            .expect("refresh token codec"),
+        ),
+        Arc::new(
+            mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(),
+        ),
+        clock,
+    )
+}
+
+fn article_commands(clock: Arc<dyn Clock>) -> ArticleCommandService {
+    ArticleCommandService::new(
+        Arc::new(support::DummyArticleWrite),
+        Arc::new(support::DummyArticleRead),
+        Arc::new(support::DummyArticleRevision),
+        Arc::new(ArticleSlugService::new(
+            Arc::new(support::DummyArticleRead),
+            Arc::new(support::DummySlug),
+        )),
+        clock,
+    )
+}
+
+fn admin(id: i64) -> AuthenticatedUser {
+    let now = support::fixed_now();
+    AuthenticatedUser {
+        id: UserId::new(id).unwrap(),
+        username: "root".into(),
+        role: Role::Admin,
+        capabilities: Role::Admin.default_capabilities(),
+        issued_at: now,
+        expires_at: now + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+fn register(username: &str, role: Option<Role>) -> RegisterUserCommand {
+    RegisterUserCommand {
+        username: username.into(),
+        password: "Sup3r-secret!".into(),
+        role,
+    }
+}
+
+#[tokio::test]
+async fn journal_replays_into_an_empty_repository() {
+    let recorded_repo = Arc::new(InMemoryUserRepo::starting_at(100));
+    let journal = Arc::new(MemoryJournal::default());
+    let service = user_commands(Arc::clone(&recorded_repo), Arc::new(support::DummyClock))
+        .with_journal(Arc::clone(&journal) as Arc<dyn CommandJournal>);
+
+    let root = service
+        .register(None, register("root", None))
+        .await
+        .unwrap();
+    let actor = admin(root.id);
+    let writer = service
+        .register(Some(&actor), register("writer", None))
+        .await
+        .unwrap();
+    service
+        .grant_role(
+            &actor,
+            GrantRoleCommand {
+                user_id: writer.id,
+                role: Role::Admin,
+            },
+        )
+        .await
+        .unwrap();
+    service
+        .update_user(
+            &actor,
+            UpdateUserCommand {
+                user_id: writer.id,
+                is_active: Some(false),
+                role: None,
+            },
+        )
+        .await
+        .unwrap();
+    service
+        .revoke_role(&actor, RevokeRoleCommand { user_id: 999 })
+        .await
+        .unwrap_err();
+
+    let entries = journal.0.lock().unwrap().clone();
+    assert_eq!(entries.len(), 5);
+    assert!(entries[0].actor.is_none());
+    assert_eq!(
+        entries[1].command,
+        JournaledCommand::RegisterUser {
+            username: "writer".into(),
+            role: None,
+        }
+    );
+    assert!(matches!(entries[4].outcome, JournalOutcome::Failed { .. }));
+    let serialized = serde_json::to_string(&entries).unwrap();
+    assert!(!serialized.contains("Sup3r-secret!"));
+
+    let replayed_repo = Arc::new(InMemoryUserRepo::starting_at(1));
+    let clock = Arc::new(ReplayClock::default());
+    let mut replayer = JournalReplayer::new(
+        Arc::new(user_commands(
+            Arc::clone(&replayed_repo),
+            Arc::clone(&clock) as Arc<dyn Clock>,
+        )),
+        Arc::new(article_commands(Arc::clone(&clock) as Arc<dyn Clock>)),
+        clock,
+    );
+    let summary = replayer.replay(entries).await;
+    assert_eq!(summary.replayed, 4);
+    assert_eq!(summary.skipped, 1);
+    assert!(summary.diverged.is_empty(), "{:?}", summary.diverged);
+
+    let original = recorded_repo.snapshot();
+    let rebuilt = replayed_repo.snapshot();
+    assert_eq!(rebuilt.len(), original.len());
+    for (rebuilt, original) in rebuilt.iter().zip(&original) {
+        assert_eq!(rebuilt.username, original.username);
+        assert_eq!(rebuilt.role, original.role);
+        assert_eq!(rebuilt.is_active, original.is_active);
+        assert_eq!(rebuilt.created_at, original.created_at);
+    }
+    assert_eq!(i64::from(rebuilt[1].id), 2);
+}
+
+#[tokio::test]
+async fn replay_reports_commands_that_no_longer_succeed() {
+    let recorded_repo = Arc::new(InMemoryUserRepo::starting_at(1));
+    let journal = Arc::new(MemoryJournal::default());
+    let service = user_commands(recorded_repo, Arc::new(support::DummyClock))
+        .with_journal(Arc::clone(&journal) as Arc<dyn CommandJournal>);
+    let root = service
+        .register(None, register("root", None))
+        .await
+        .unwrap();
+    service
+        .register(
+            Some(&admin(root.id)),
+            register("writer", Some(Role::Author)),
+        )
+        .await
+        .unwrap();
+    let entries = journal.0.lock().unwrap().clone();
+
+    // The replay target already has a "writer", so the second entry diverges.
+    let replayed_repo = Arc::new(InMemoryUserRepo::starting_at(1));
+    let clock = Arc::new(ReplayClock::default());
+    let users = user_commands(Arc::clone(&replayed_repo), Arc::new(support::DummyClock));
+    let root = users.register(None, register("root", None)).await.unwrap();
+    users
+        .register(Some(&admin(root.id)), register("writer", None))
+        .await
+        .unwrap();
+    let mut replayer = JournalReplayer::new(
+        Arc::new(users),
+        Arc::new(article_commands(Arc::clone(&clock) as Arc<dyn Clock>)),
+        clock,
+    );
+
+    let summary = replayer.replay(entries).await;
+    assert_eq!(summary.replayed, 0);
+    assert_eq!(summary.diverged.len(), 2);
+    assert_eq!(summary.diverged[1].position, 2);
+    assert!(
+        summary.diverged[1]
+            .error
+            .contains("username already exists")
+    );
+}
@@ -0,0 +1,305 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/content_bundle.rs
+use std::sync::Arc;
+
+use chrono::Utc;
+use mokkan_core::application::commands::articles::{
+    ArticleCommandService, CreateArticleCommand, UpdateArticleCommand,
+};
+use mokkan_core::application::services::{
+    ContentBundlePorts, ContentBundleService, ExportBundleQuery, ImportBundleCommand,
+};
+use mokkan_core::application::{
+    AppError, AuthenticatedUser, BundleFormat, BundleImportDto, BundleItemStatus, ConflictStrategy,
+};
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::{
+    Article, ArticleReadRepository, ArticleRevisionRepository, ArticleSlug, NewUser, PasswordHash,
+    Role, UserId, UserRepository, Username,
+};
+use mokkan_core::infrastructure::repositories::InMemoryStores;
+use mokkan_core::infrastructure::time::SimulatedClock;
+use mokkan_core::infrastructure::zip::ZipArchiver;
+
+mod support;
+
+struct Site {
+    stores: InMemoryStores,
+    commands: ArticleCommandService,
+    bundles: ContentBundleService,
+    admin: AuthenticatedUser,
+}
+
+impl Site {
+    /// 管理者 `username` だけがいる空のサイト
+    async fn new(username: &str) -> Self {
+        let stores = InMemoryStores::new();
+        let clock = Arc::new(SimulatedClock::new());
+        let slugs = Arc::new(ArticleSlugService::new(
+            stores.articles.clone(),
+            Arc::new(support::DummySlug),
+        ));
+        let commands = ArticleCommandService::new(
+            stores.articles.clone(),
+            stores.articles.clone(),
+            stores.articles.clone(),
+            slugs,
+            clock.clone(),
+        );
+        let ports = ContentBundlePorts {
+            user_repo: stores.users.clone(),
+            article_read_repo: stores.articles.clone(),
+            article_write_repo: stores.articles.clone(),
+            article_revision_repo: stores.articles.clone(),
+            audit_log_repo: stores.audit_logs.clone(),
+            password_hasher: Arc::new(support::DummyPasswordHasher),
+            archiver: Arc::new(ZipArchiver),
+        };
+        let bundles = ContentBundleService::new(ports, clock);
+
+        let user = stores
+            .users
+            .insert(
+                NewUser::new(
+                    Username::new(username).unwrap(),
+                    PasswordHash::new("hash").unwrap(),
+                    Role::Admin,
+                    Utc::now(),
+                )
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        let admin = actor(user.id.into(), Role::Admin);
+        Self {
+            stores,
+            commands,
+            bundles,
+            admin,
+        }
+    }
+
+    async fn create(&self, title: &str, body: &str, publish: bool) -> i64 {
+        let command = CreateArticleCommand::builder()
+            .title(title)
+            .body(body)
+            .tags(["rust"])
+            .publish(publish)
+            .build()
+            .unwrap();
+        self.commands
+            .create_article(&self.admin, command)
+            .await
+            .unwrap()
+            .id
+    }
+
+    async fn export(&self, format: BundleFormat) -> Vec<u8> {
+        let query = ExportBundleQuery {
+            format,
+            include_revisions: true,
+            include_users: true,
+        };
+        let mut export = self.bundles.export(&self.admin, query).await.unwrap();
+        if format == BundleFormat::Zip {
+            return export.into_archive().await.unwrap();
+        }
+        let mut bundle = Vec::new();
+        while let Some(chunk) = export.next_chunk().await.unwrap() {
+            bundle.extend(chunk);
+        }
+        bundle
+    }
+
+    async fn import(&self, bundle: &[u8], on_conflict: ConflictStrategy) -> BundleImportDto {
+        self.bundles
+            .import(
+                &self.admin,
+                ImportBundleCommand {
+                    bundle,
+                    on_conflict,
+                },
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn article(&self, slug: &str) -> Article {
+        self.stores
+            .articles
+            .find_by_slug(&ArticleSlug::new(slug).unwrap())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    async fn revisions(&self, slug: &str) -> usize {
+        let article = self.article(slug).await;
+        self.stores
+            .articles
+            .list_by_article(article.id)
+            .await
+            .unwrap()
+            .len()
+    }
+}
+
+fn actor(id: i64, role: Role) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(id).unwrap(),
+        username: format!("user{id}"),
+        role,
+        capabilities: role.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + chrono::Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+fn statuses(report: &BundleImportDto) -> Vec<(&str, BundleItemStatus)> {
+    report
+        .articles
+        .iter()
+        .map(|item| (item.slug.as_str(), item.status))
+        .collect()
+}
+
+/// NDJSON で書き出した記事・履歴・利用者が別の環境にそのまま取り込まれる
+#[tokio::test]
+async fn ndjson_bundles_move_articles_revisions_and_authors() {
+    let source = Site::new("alice").await;
+    let draft = source.create("draft", "first body", false).await;
+    source.create("live", "live body", true).await;
+    source
+        .commands
+        .update_article(
+            &source.admin,
+            UpdateArticleCommand {
+                id: draft,
+                title: None,
+                body: Some("second body".into()),
+                publish: None,
+                tags: None,
+                custom_fields: None,
+                visibility: None,
+            },
+        )
+        .await
+        .unwrap();
+    let bundle = source.export(BundleFormat::Ndjson).await;
+    let first_line = bundle.split(|byte| *byte == b'\n').next().unwrap();
+    let manifest: serde_json::Value = serde_json::from_slice(first_line).unwrap();
+    assert_eq!(manifest["type"], "manifest");
+    assert_eq!(manifest["format"], "mokkan-bundle");
+
+    let target = Site::new("bob").await;
+    let report = target.import(&bundle, ConflictStrategy::Skip).await;
+
+    assert_eq!(report.users_created, 1);
+    assert_eq!(report.revisions_imported, 3);
+    assert_eq!(
+        statuses(&report),
+        vec![
+            ("live", BundleItemStatus::Created),
+            ("draft", BundleItemStatus::Created)
+        ]
+    );
+    let alice = target
+        .stores
+        .users
+        .find_by_username(&Username::new("alice").unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(alice.password_reset_required);
+    let imported = target.article("draft").await;
+    assert_eq!(imported.body.as_str(), "second body");
+    assert_eq!(imported.author_id, alice.id);
+    assert!(!imported.published);
+    assert_eq!(target.revisions("draft").await, 2);
+    let live = target.article("live").await;
+    assert!(live.published);
+    assert_eq!(live.published_at, source.article("live").await.published_at);
+}
+
+/// zip バンドルでは衝突時に skip・new-slug・overwrite がそれぞれ適用される
+#[tokio::test]
+async fn zip_bundles_resolve_slug_conflicts() {
+    let source = Site::new("alice").await;
+    source.create("news", "from source", true).await;
+    let bundle = source.export(BundleFormat::Zip).await;
+    assert!(bundle.starts_with(b"PK"));
+
+    let target = Site::new("alice").await;
+    target.create("news", "from target", false).await;
+
+    let report = target.import(&bundle, ConflictStrategy::Skip).await;
+    assert_eq!(statuses(&report), vec![("news", BundleItemStatus::Skipped)]);
+    assert_eq!(report.users_created, 0);
+    assert_eq!(target.article("news").await.body.as_str(), "from target");
+
+    let report = target.import(&bundle, ConflictStrategy::NewSlug).await;
+    assert_eq!(statuses(&report), vec![("news", BundleItemStatus::Renamed)]);
+    assert_eq!(report.articles[0].imported_slug.as_deref(), Some("news-1"));
+    assert_eq!(target.article("news-1").await.body.as_str(), "from source");
+
+    let report = target.import(&bundle, ConflictStrategy::Overwrite).await;
+    assert_eq!(
+        statuses(&report),
+        vec![("news", BundleItemStatus::Overwritten)]
+    );
+    let overwritten = target.article("news").await;
+    assert_eq!(overwritten.body.as_str(), "from source");
+    assert!(overwritten.published);
+    assert_eq!(target.revisions("news").await, 2);
+}
+
+/// 保守権限のない利用者は書き出しも取り込みもできず、壊れたバンドルは検証エラーになる
+#[tokio::test]
+async fn bundles_require_maintenance_and_a_valid_manifest() {
+    let site = Site::new("alice").await;
+    let author = actor(2, Role::Author);
+
+    let err = site
+        .bundles
+        .export(&author, ExportBundleQuery::default())
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(err, AppError::Forbidden(_)), "{err:?}");
+    let err = site
+        .bundles
+        .import(
+            &author,
+            ImportBundleCommand {
+                bundle: b"",
+                on_conflict: ConflictStrategy::Skip,
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)), "{err:?}");
+
+    for bundle in [
+        &b"{\"type\":\"user\",\"username\":\"x\"}\n"[..],
+        b"{\"type\":\"manifest\",\"format\":\"other\",\"version\":1,\"exported_at\":\"2024-01-01T00:00:00Z\",\"includes_revisions\":false,\"includes_users\":false}\n",
+        b"PK\x03\x04 truncated",
+    ] {
+        let err = site
+            .bundles
+            .import(
+                &site.admin,
+                ImportBundleCommand {
+                    bundle,
+                    on_conflict: ConflictStrategy::Skip,
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)), "{err:?}");
+    }
+}
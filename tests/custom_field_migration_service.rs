@@ -0,0 +1,302 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/custom_field_migration_service.rs
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, Utc};
+use mokkan_core::application::services::CustomFieldMigrationService;
+use mokkan_core::application::{
+    AuthenticatedUser, CustomFieldMigration, CustomFieldMigrationJobDto, MigrationJobStatus,
+};
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::article::entity::{Article, ArticleUpdate, NewArticle};
+use mokkan_core::domain::article::value_objects::{ArticleId, ArticleListCursor, ArticleSlug};
+use mokkan_core::domain::errors::{DomainError, DomainResult};
+use mokkan_core::domain::{
+    ArticleReadRepository, ArticleWriteRepository, CustomFieldDefinition, CustomFieldRepository,
+    CustomFieldType, Role, UserId,
+};
+use mokkan_core::infrastructure::jobs::TokioJobQueue;
+use serde_json::{Value, json};
+
+mod support;
+
+/// In-memory articles that enforce the optimistic `updated_at` check.
+#[derive(Default)]
+struct InMemoryArticles {
+    articles: Mutex<Vec<Article>>,
+}
+
+impl InMemoryArticles {
+    fn with_fields(values: &[Value]) -> Self {
+        let repo = Self::default();
+        for (id, value) in (1..).zip(values) {
+            let mut article = support::ArticleBuilder::new().id(id).build();
+            article.custom_fields = value.as_object().cloned().unwrap();
+            repo.articles.lock().unwrap().push(article);
+        }
+        repo
+    }
+
+    fn fields(&self) -> Vec<Value> {
+        self.articles
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|article| Value::Object(article.custom_fields.clone()))
+            .collect()
+    }
+}
+
+impl ArticleWriteRepository for InMemoryArticles {
+    fn insert(&self, _article: NewArticle) -> BoxFuture<'_, DomainResult<Article>> {
+        boxed(async { Err(DomainError::Validation("not supported".into())) })
+    }
+
+    fn update(&self, update: ArticleUpdate) -> BoxFuture<'_, DomainResult<Article>> {
+        boxed(async move {
+            let mut articles = self.articles.lock().unwrap();
+            let article = articles
+                .iter_mut()
+                .find(|article| article.id == update.id)
+                .ok_or_else(|| DomainError::NotFound("article not found".into()))?;
+            if article.updated_at != update.original_updated_at {
+                return Err(DomainError::Conflict("article was modified".into()));
+            }
+            if let Some(custom_fields) = update.custom_fields {
+                article.set_custom_fields(custom_fields, update.updated_at);
+            }
+            let article = article.clone();
+            drop(articles);
+            Ok(article)
+        })
+    }
+
+    fn delete(&self, _id: ArticleId) -> BoxFuture<'_, DomainResult<()>> {
+        boxed(async { Ok(()) })
+    }
+}
+
+impl ArticleReadRepository for InMemoryArticles {
+    fn find_by_id(&self, _id: ArticleId) -> BoxFuture<'_, DomainResult<Option<Article>>> {
+        boxed(async { Ok(None) })
+    }
+
+    fn find_by_slug<'a>(
+        &'a self,
+        _slug: &'a ArticleSlug,
+    ) -> BoxFuture<'a, DomainResult<Option<Article>>> {
+        boxed(async { Ok(None) })
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        _include_drafts: bool,
+        _limit: u32,
+        _cursor: Option<ArticleListCursor>,
+        _search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        boxed(async move { Ok((self.articles.lock().unwrap().clone(), None)) })
+    }
+}
+
+fn admin() -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(1).unwrap(),
+        username: "admin".into(),
+        role: Role::Admin,
+        capabilities: Role::Admin.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+async fn setup(
+    definition: (&str, CustomFieldType),
+    values: &[Value],
+) -> (
+    CustomFieldMigrationService,
+    Arc<support::MemoryCustomFields>,
+    Arc<InMemoryArticles>,
+) {
+    let fields = Arc::new(support::MemoryCustomFields::default());
+    fields
+        .upsert(CustomFieldDefinition::new(definition.0, definition.1, false).unwrap())
+        .await
+        .unwrap();
+    let articles = Arc::new(InMemoryArticles::with_fields(values));
+    let svc = CustomFieldMigrationService::new(
+        Arc::clone(&fields) as Arc<dyn CustomFieldRepository>,
+        Arc::clone(&articles) as Arc<dyn ArticleReadRepository>,
+        Arc::clone(&articles) as Arc<dyn ArticleWriteRepository>,
+        Arc::new(support::DummyClock),
+        Arc::new(TokioJobQueue),
+    );
+    (svc, fields, articles)
+}
+
+async fn run(
+    svc: &CustomFieldMigrationService,
+    field: &str,
+    migration: CustomFieldMigration,
+) -> CustomFieldMigrationJobDto {
+    let queued = svc
+        .submit(&admin(), field, migration)
+        .await
+        .expect("migration is accepted");
+    for _ in 0..200 {
+        let job = svc.job(&admin(), &queued.id).expect("job exists");
+        if job.status != MigrationJobStatus::Queued && job.status != MigrationJobStatus::Running {
+            return job;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+    panic!("migration job {} did not finish", queued.id);
+}
+
+#[tokio::test]
+async fn rename_moves_values_and_the_definition() {
+    let (svc, fields, articles) = setup(
+        ("isbn", CustomFieldType::Text),
+        &[json!({"isbn": "978-0"}), json!({})],
+    )
+    .await;
+
+    let job = run(
+        &svc,
+        "isbn",
+        CustomFieldMigration::Rename {
+            to: "isbn13".into(),
+        },
+    )
+    .await;
+
+    assert_eq!(job.status, MigrationJobStatus::Completed);
+    assert_eq!(
+        (
+            job.total_articles,
+            job.processed_articles,
+            job.updated_articles
+        ),
+        (2, 2, 1)
+    );
+    assert_eq!(
+        articles.fields(),
+        vec![json!({"isbn13": "978-0"}), json!({})]
+    );
+    let names: Vec<_> = fields
+        .list()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|definition| definition.name)
+        .collect();
+    assert_eq!(names, vec!["isbn13".to_owned()]);
+}
+
+#[tokio::test]
+async fn change_type_converts_values_and_reports_the_rest() {
+    let (svc, fields, articles) = setup(
+        ("pages", CustomFieldType::Text),
+        &[json!({"pages": "320"}), json!({"pages": "many"})],
+    )
+    .await;
+
+    let job = run(
+        &svc,
+        "pages",
+        CustomFieldMigration::ChangeType {
+            to: CustomFieldType::Number,
+            drop_unconvertible: false,
+        },
+    )
+    .await;
+
+    assert_eq!(job.status, MigrationJobStatus::Completed);
+    assert_eq!(job.updated_articles, 1);
+    assert_eq!(job.failures.len(), 1);
+    assert_eq!(job.failures[0].article_id, 2);
+    assert_eq!(
+        articles.fields(),
+        vec![json!({"pages": 320}), json!({"pages": "many"})]
+    );
+    assert_eq!(
+        fields.list().await.unwrap()[0].field_type,
+        CustomFieldType::Number
+    );
+
+    let job = run(
+        &svc,
+        "pages",
+        CustomFieldMigration::ChangeType {
+            to: CustomFieldType::Text,
+            drop_unconvertible: true,
+        },
+    )
+    .await;
+    assert!(job.failures.is_empty());
+    assert_eq!(
+        articles.fields(),
+        vec![json!({"pages": "320"}), json!({"pages": "many"})]
+    );
+}
+
+#[tokio::test]
+async fn backfill_only_fills_missing_values() {
+    let (svc, _fields, articles) = setup(
+        ("signed", CustomFieldType::Boolean),
+        &[json!({"signed": true}), json!({})],
+    )
+    .await;
+
+    let job = run(
+        &svc,
+        "signed",
+        CustomFieldMigration::Backfill {
+            value: json!(false),
+        },
+    )
+    .await;
+
+    assert_eq!(job.updated_articles, 1);
+    assert_eq!(
+        articles.fields(),
+        vec![json!({"signed": true}), json!({"signed": false})]
+    );
+}
+
+#[tokio::test]
+async fn migrations_that_do_not_fit_the_field_are_rejected() {
+    let (svc, _fields, _articles) = setup(("pages", CustomFieldType::Number), &[]).await;
+    for (field, migration) in [
+        (
+            "pages",
+            CustomFieldMigration::Backfill {
+                value: json!("many"),
+            },
+        ),
+        (
+            "pages",
+            CustomFieldMigration::ChangeType {
+                to: CustomFieldType::Number,
+                drop_unconvertible: false,
+            },
+        ),
+        (
+            "pages",
+            CustomFieldMigration::Rename {
+                to: "Page-Count".into(),
+            },
+        ),
+        (
+            "missing",
+            CustomFieldMigration::Rename { to: "other".into() },
+        ),
+    ] {
+        assert!(svc.submit(&admin(), field, migration).await.is_err());
+    }
+}
@@ -0,0 +1,158 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/domain_events.rs
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use mokkan_core::application::AuthenticatedUser;
+use mokkan_core::application::commands::articles::{
+    ArticleCommandService, CreateArticleCommand, DeleteArticleCommand, SetPublishStateCommand,
+};
+use mokkan_core::application::commands::users::{
+    GrantRoleCommand, RegisterUserCommand, UserCommandService,
+};
+use mokkan_core::application::ports::domain_events::EventPublisher;
+use mokkan_core::application::services::DomainEventBus;
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::{ArticleId, DomainEvent, EventKind, Role, UserId};
+use mokkan_core::infrastructure::repositories::InMemoryStores;
+use mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec;
+use mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore;
+use tokio::sync::broadcast;
+
+mod support;
+
+fn admin(id: i64) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(id).unwrap(),
+        username: "admin".into(),
+        role: Role::Admin,
+        capabilities: Role::Admin.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+fn drain(events: &mut broadcast::Receiver<DomainEvent>) -> Vec<EventKind> {
+    std::iter::from_fn(|| events.try_recv().ok())
+        .map(|event| event.kind)
+        .collect()
+}
+
+/// 記事の作成・公開・削除がドメインイベントとして購読者に届く
+#[tokio::test]
+async fn article_writes_reach_subscribers() {
+    let stores = InMemoryStores::new();
+    let bus = Arc::new(DomainEventBus::default());
+    let mut events = bus.subscribe();
+    let svc = ArticleCommandService::new(
+        stores.articles.clone(),
+        stores.articles.clone(),
+        stores.articles.clone(),
+        Arc::new(ArticleSlugService::new(
+            stores.articles.clone(),
+            Arc::new(support::DummySlug),
+        )),
+        Arc::new(support::DummyClock),
+    )
+    .with_event_publisher(bus.clone() as Arc<dyn EventPublisher>);
+    let actor = admin(7);
+
+    let created = svc
+        .create_article(
+            &actor,
+            CreateArticleCommand::builder()
+                .title("evented")
+                .body("body")
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    svc.set_publish_state(
+        &actor,
+        SetPublishStateCommand {
+            id: created.id,
+            publish: true,
+        },
+    )
+    .await
+    .unwrap();
+    svc.delete_article(&actor, DeleteArticleCommand { id: created.id })
+        .await
+        .unwrap();
+
+    let id = ArticleId::new(created.id).unwrap();
+    assert_eq!(
+        drain(&mut events),
+        [
+            EventKind::ArticleCreated(id),
+            EventKind::ArticlePublished(id),
+            EventKind::ArticleDeleted(id),
+        ]
+    );
+}
+
+/// ユーザーの登録とロール変更がドメインイベントとして購読者に届く
+#[tokio::test]
+async fn account_changes_reach_subscribers() {
+    let stores = InMemoryStores::new();
+    let bus = Arc::new(DomainEventBus::default());
+    let mut events = bus.subscribe();
+    let svc = UserCommandService::new(
+        stores.users.clone(),
+        Arc::new(support::DummyPasswordHasher),
+        Arc::new(support::DummyTokenManager),
+        Arc::new(HmacRefreshTokenCodec::new("test-refresh-secret").unwrap()),
+        Arc::new(InMemorySessionRevocationStore::new()),
+        Arc::new(support::DummyClock),
+    )
+    .with_event_publisher(bus.clone() as Arc<dyn EventPublisher>);
+
+    let owner = svc
+        .register(
+            None,
+            RegisterUserCommand {
+                username: "owner".into(),
+                password: "Owner-Password-1!".into(),
+                role: None,
+            },
+        )
+        .await
+        .unwrap();
+    let writer = svc
+        .register(
+            Some(&admin(owner.id)),
+            RegisterUserCommand {
+                username: "writer".into(),
+                password: "Writer-Password-1!".into(),
+                role: None,
+            },
+        )
+        .await
+        .unwrap();
+    svc.grant_role(
+        &admin(owner.id),
+        GrantRoleCommand {
+            user_id: writer.id,
+            role: Role::Admin,
+        },
+    )
+    .await
+    .unwrap();
+
+    let writer_id = UserId::new(writer.id).unwrap();
+    assert_eq!(
+        drain(&mut events),
+        [
+            EventKind::UserRegistered(UserId::new(owner.id).unwrap()),
+            EventKind::UserRegistered(writer_id),
+            EventKind::UserUpdated(writer_id),
+        ]
+    );
+    assert_eq!(EventKind::UserUpdated(writer_id).name(), "user.updated");
+}
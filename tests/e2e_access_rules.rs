@@ -0,0 +1,116 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_access_rules.rs
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{Method, Request, StatusCode, header::AUTHORIZATION};
+use std::net::{IpAddr, SocketAddr};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn request(method: Method, uri: &str, client_ip: &str, body: Option<&str>) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .extension(ConnectInfo(SocketAddr::new(
+            client_ip.parse::<IpAddr>().unwrap(),
+            40_000,
+        )))
+        .header(AUTHORIZATION, format!("Bearer {}", support::TEST_TOKEN))
+        .body(body.map_or_else(Body::empty, |body| Body::from(body.to_owned())))
+        .unwrap()
+}
+
+/// 拒否ルールに一致するクライアントは管理 API に到達できないことを確認する
+#[tokio::test]
+async fn e2e_deny_rule_blocks_admin_routes() {
+    let app = support::make_test_router().await;
+    let rule = serde_json::json!({ "group": "admin", "action": "deny", "cidr": "203.0.113.0/24" });
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::POST,
+            "/api/v1/admin/access-rules",
+            "198.51.100.1",
+            Some(&rule.to_string()),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let (_, json) = to_json_async!(resp).await;
+    assert_eq!(json["cidr"], "203.0.113.0/24");
+
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::GET,
+            "/api/v1/admin/access-rules",
+            "203.0.113.9",
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+
+    let resp = app
+        .oneshot(request(
+            Method::GET,
+            "/api/v1/admin/access-rules",
+            "198.51.100.1",
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+/// 登録ルートの拒否は認証より前に適用されることを確認する
+#[tokio::test]
+async fn e2e_deny_rule_blocks_registration() {
+    let app = support::make_test_router().await;
+    let rule = serde_json::json!({ "group": "registration", "action": "deny", "cidr": "::/0" });
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::POST,
+            "/api/v1/admin/access-rules",
+            "198.51.100.1",
+            Some(&rule.to_string()),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body = serde_json::json!({ "username": "newcomer", "password": "Secret123!" });
+    let resp = app
+        .oneshot(request(
+            Method::POST,
+            "/api/v1/auth/register",
+            "2001:db8::1",
+            Some(&body.to_string()),
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+}
+
+/// CIDR と ASN を両方指定したルールは拒否されることを確認する
+#[tokio::test]
+async fn e2e_rule_with_cidr_and_asn_is_rejected() {
+    let app = support::make_test_router().await;
+    let rule = serde_json::json!({
+        "group": "admin", "action": "deny", "cidr": "10.0.0.0/8", "asn": 64500
+    });
+    let resp = app
+        .oneshot(request(
+            Method::POST,
+            "/api/v1/admin/access-rules",
+            "198.51.100.1",
+            Some(&rule.to_string()),
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
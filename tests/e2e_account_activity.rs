@@ -0,0 +1,99 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_account_activity.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header::AUTHORIZATION};
+use chrono::Duration;
+use mokkan_core::domain::audit::entity::AuditLog;
+use mokkan_core::domain::user::value_objects::UserId;
+use tower::util::ServiceExt as _;
+
+mod support;
+
+const ACTIVITY: &str = "/api/v1/auth/me/activity";
+
+/// ユーザー `subject` についての、`minutes_ago` 分前の監査ログ
+fn entry(id: i64, user_id: Option<i64>, action: &str, subject: i64, minutes_ago: i64) -> AuditLog {
+    let mut log = support::sample(support::fixed_now() - Duration::minutes(minutes_ago));
+    log.id = id;
+    log.user_id = user_id.map(|id| UserId::new(id).unwrap());
+    log.action = action.into();
+    log.resource_type = "user".into();
+    log.resource_id = Some(subject);
+    log
+}
+
+/// `NO_AUDIT_TOKEN` のユーザー (id 2) 本人の操作と、本人のアカウントに対する操作
+fn entries() -> Vec<AuditLog> {
+    vec![
+        entry(6, Some(2), "user.login", 2, 1),
+        entry(5, None, "user.locked", 2, 10),
+        entry(4, Some(1), "user.login", 1, 20),
+        entry(3, Some(2), "test", 2, 30),
+        entry(2, Some(1), "user.role_change", 2, 40),
+        entry(1, Some(1), "user.role_change", 3, 50),
+    ]
+}
+
+async fn get(uri: &str) -> axum::response::Response {
+    let repo = support::MockRepo::with_items(entries());
+    let app = support::make_test_router_with_audit_repo(std::sync::Arc::new(repo)).await;
+    let req = Request::builder()
+        .method("GET")
+        .uri(uri)
+        .header(AUTHORIZATION, format!("Bearer {}", support::NO_AUDIT_TOKEN))
+        .body(Body::empty())
+        .unwrap();
+    app.oneshot(req).await.unwrap()
+}
+
+/// 監査権限がなくても自分に関わる既知の操作だけが、カテゴリとアイコン付きで返る
+#[tokio::test]
+async fn lists_only_activity_involving_the_caller() {
+    let resp = get(ACTIVITY).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let (_, json) = to_json_async!(resp).await;
+    let items = json["items"].as_array().expect("items array");
+    let ids: Vec<_> = items
+        .iter()
+        .map(|item| item["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, [6, 5, 2]);
+
+    assert_eq!(items[0]["category"], "sign_in");
+    assert_eq!(items[0]["icon"], "log-in");
+    assert_eq!(items[0]["summary"], "Signed in");
+    assert_eq!(items[0]["by_someone_else"], false);
+    assert_eq!(items[1]["category"], "security");
+    assert_eq!(items[1]["icon"], "lock");
+    assert_eq!(items[1]["by_someone_else"], true);
+    assert_eq!(items[2]["category"], "administration");
+    assert_eq!(items[2]["summary"], "Role changed");
+}
+
+/// カテゴリと期間で絞り込め、不正なカテゴリは 400 になる
+#[tokio::test]
+async fn activity_can_be_narrowed_by_category_and_time() {
+    let resp = get(&format!("{ACTIVITY}?category=security")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_, json) = to_json_async!(resp).await;
+    assert_eq!(json["items"].as_array().unwrap().len(), 1);
+    assert_eq!(json["items"][0]["action"], "user.locked");
+
+    let since = (support::fixed_now() - Duration::minutes(15)).to_rfc3339();
+    let uri = format!("{ACTIVITY}?since={}", since.replace('+', "%2B"));
+    let resp = get(&uri).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_, json) = to_json_async!(resp).await;
+    let ids: Vec<_> = json["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, [6, 5]);
+
+    let resp = get(&format!("{ACTIVITY}?category=billing")).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
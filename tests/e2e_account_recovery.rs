@@ -0,0 +1,61 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_account_recovery.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header::CONTENT_TYPE};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn post_json(uri: &str, body: &'static str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+/// 未登録のアドレスでも 202 を返し、登録の有無を漏らさない
+#[tokio::test]
+async fn password_reset_for_unknown_address_is_accepted() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(post_json(
+            "/api/v1/auth/password-reset",
+            r#"{"email":"nobody@example.com"}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    let (_headers, json) = to_json_async!(resp).await;
+    assert_eq!(json["status"], "accepted");
+}
+
+/// 形式が不正なアドレスは 400
+#[tokio::test]
+async fn password_reset_rejects_malformed_address() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(post_json(
+            "/api/v1/auth/password-reset",
+            r#"{"email":"not-an-address"}"#,
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
+
+/// 未知のトークンではパスワードを変更できない
+#[tokio::test]
+async fn password_reset_confirm_rejects_unknown_token() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(post_json(
+            "/api/v1/auth/password-reset/confirm",
+            r#"{"token":"made-up","new_password":"N3w!Password-long"}"#,
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
@@ -0,0 +1,111 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_admin_inspect.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header::AUTHORIZATION};
+use chrono::Duration;
+use mokkan_core::application::AuthenticatedUser;
+use mokkan_core::application::ports::session_revocation::{
+    Ports, RefreshNonceStore, RefreshTokenFamilyStore, Revocation, SessionMetadataStore,
+};
+use mokkan_core::application::queries::inspect::{InspectQueryService, InspectSessionQuery};
+use mokkan_core::domain::{Role, UserId};
+use mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore;
+use std::sync::Arc;
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn bearer(tok: &str) -> String {
+    format!("Bearer {tok}")
+}
+
+async fn get(path: &str, token: &str) -> axum::response::Response {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method("GET")
+        .uri(path)
+        .header(AUTHORIZATION, bearer(token))
+        .body(Body::empty())
+        .unwrap();
+    app.oneshot(req).await.unwrap()
+}
+
+#[tokio::test]
+async fn inspect_missing_article_returns_not_found() {
+    let resp = get("/api/v1/admin/inspect/article/42", support::TEST_TOKEN).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn inspect_requires_admin_capability() {
+    for path in [
+        "/api/v1/admin/inspect/article/1",
+        "/api/v1/admin/inspect/user/1",
+        "/api/v1/admin/inspect/session/sess-1",
+    ] {
+        let resp = get(path, support::NO_AUDIT_TOKEN).await;
+        assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+    }
+}
+
+#[tokio::test]
+async fn inspect_session_reports_revoked_state_and_family() {
+    let store = Arc::new(InMemorySessionRevocationStore::new());
+    store
+        .set_session_metadata(
+            5,
+            "sess-1",
+            Some("curl/8.0"),
+            Some("192.0.2.1"),
+            1_700_000_000,
+        )
+        .await
+        .unwrap();
+    store
+        .add_session_to_family("fam-1", "sess-1")
+        .await
+        .unwrap();
+    store
+        .set_session_refresh_nonce("sess-1", "nonce-1")
+        .await
+        .unwrap();
+    store.revoke("sess-1").await.unwrap();
+
+    let service = InspectQueryService::new(
+        Arc::new(support::DummyRepo),
+        Arc::new(support::DummyArticleRead),
+        Arc::new(support::DummyArticleRevision),
+        Ports::from_store(store),
+    );
+    let now = support::fixed_now();
+    let admin = AuthenticatedUser {
+        id: UserId::new(1).unwrap(),
+        username: "admin".into(),
+        role: Role::Admin,
+        capabilities: Role::Admin.default_capabilities(),
+        issued_at: now,
+        expires_at: now + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    };
+
+    let dto = service
+        .inspect_session(
+            &admin,
+            InspectSessionQuery {
+                session_id: "sess-1".into(),
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(dto.user_id, 5);
+    assert!(dto.revoked);
+    assert_eq!(dto.family_id.as_deref(), Some("fam-1"));
+    assert_eq!(dto.ip_address.as_deref(), Some("192.0.2.1"));
+    assert_eq!(dto.created_at_unix, 1_700_000_000);
+    assert!(dto.created_at.is_some());
+}
@@ -0,0 +1,67 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_admin_jobs.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header::AUTHORIZATION};
+use serde_json::json;
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn get(token: &str) -> Request<Body> {
+    Request::builder()
+        .uri("/api/v1/admin/jobs")
+        .header(AUTHORIZATION, format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn admin_sees_an_empty_list_without_a_scheduler() {
+    let app = support::make_test_router().await;
+    let resp = app.oneshot(get(support::TEST_TOKEN)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, json) = to_json_async!(resp).await;
+    assert_eq!(json, json!([]));
+}
+
+#[tokio::test]
+async fn jobs_require_admin_inspect() {
+    let app = support::make_test_router().await;
+    let resp = app.oneshot(get(support::NO_AUDIT_TOKEN)).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+}
+
+#[tokio::test]
+async fn running_a_job_requires_jobs_run() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/admin/jobs/session_cleanup/run")
+                .header(AUTHORIZATION, format!("Bearer {}", support::NO_AUDIT_TOKEN))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+}
+
+#[tokio::test]
+async fn unknown_jobs_are_not_found() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/admin/jobs/session_cleanup/run")
+                .header(AUTHORIZATION, format!("Bearer {}", support::TEST_TOKEN))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
@@ -0,0 +1,42 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_api_profiles.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+async fn list_articles(profile: Option<&str>) -> axum::response::Response {
+    let app = support::make_test_router().await;
+    let mut req = Request::builder().method("GET").uri("/api/v1/articles");
+    if let Some(profile) = profile {
+        req = req.header("x-api-profile", profile);
+    }
+    app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+}
+
+#[tokio::test]
+async fn requests_without_profile_get_baseline_shapes() {
+    let resp = list_articles(None).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (headers, json) = to_json_async!(resp).await;
+    assert_eq!(headers["x-api-profile"], "2024-01");
+    assert_eq!(json["has_more"], false);
+}
+
+#[tokio::test]
+async fn newer_profile_drops_retired_fields() {
+    let resp = list_articles(Some("2024-06")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (headers, json) = to_json_async!(resp).await;
+    assert_eq!(headers["x-api-profile"], "2024-06");
+    assert!(json.get("has_more").is_none());
+    assert!(json.get("items").is_some());
+}
+
+#[tokio::test]
+async fn unknown_profile_returns_400() {
+    let resp = list_articles(Some("1999-01")).await;
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
@@ -0,0 +1,51 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_article_export.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+async fn get(uri: &str) -> axum::response::Response {
+    let app = support::make_test_router().await;
+    app.oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn export_rejects_unknown_formats() {
+    let resp = get("/api/v1/articles/1/export?format=docx").await;
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
+
+#[tokio::test]
+async fn export_requires_a_format() {
+    let resp = get("/api/v1/articles/1/export").await;
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
+
+#[tokio::test]
+async fn export_of_missing_article_is_not_found() {
+    let resp = get("/api/v1/articles/999/export?format=pdf").await;
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
+
+#[tokio::test]
+async fn unknown_export_jobs_are_not_found() {
+    let resp = get("/api/v1/articles/exports/no-such-job").await;
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+
+    let resp = get("/api/v1/articles/exports/no-such-job/download").await;
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
+
+#[tokio::test]
+async fn invalid_download_links_are_not_found() {
+    let resp = get("/api/v1/downloads/dl1.not-a-payload.not-a-signature").await;
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+
+    let resp = get("/api/v1/downloads/garbage").await;
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
@@ -0,0 +1,32 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_article_html.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+async fn get(uri: &str) -> axum::response::Response {
+    let app = support::make_test_router().await;
+    app.oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn render_rejects_unknown_profiles() {
+    let resp = get("/api/v1/articles/by-slug/hello/html?profile=amp").await;
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
+
+#[tokio::test]
+async fn render_of_missing_article_is_not_found() {
+    for uri in [
+        "/api/v1/articles/by-slug/hello/html",
+        "/api/v1/articles/by-slug/hello/html?profile=print",
+    ] {
+        let resp = get(uri).await;
+        assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+    }
+}
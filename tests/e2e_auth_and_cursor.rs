@@ -75,9 +75,11 @@ async fn invalid_cursor_returns_400() {
 #[tokio::test]
 async fn next_cursor_propagates_in_response() {
     // Use MockRepo that returns next_cursor
+    let next_cursor =
+        mokkan_core::domain::audit::cursor::AuditLogCursor::new(support::fixed_now(), 123);
     let repo = support::MockRepo {
         items: vec![],
-        next_cursor: Some("next-123".into()),
+        next_cursor: Some(next_cursor.clone()),
     };
     let audit_repo = std::sync::Arc::new(repo);
     let app = support::make_test_router_with_audit_repo(audit_repo).await;
@@ -99,6 +101,90 @@ async fn next_cursor_propagates_in_response() {
         json.get("next_cursor")
             .and_then(|v| v.as_str())
             .unwrap_or(""),
-        "next-123"
+        next_cursor.encode()
     );
 }
+
+#[tokio::test]
+async fn article_list_reports_effective_limit() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method("GET")
+        .uri("/api/v1/articles?limit=500")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, json) = to_json_async!(resp).await;
+    assert_eq!(
+        json.get("limit").and_then(serde_json::Value::as_u64),
+        Some(100)
+    );
+    assert!(json["prev_cursor"].is_null());
+}
+
+#[tokio::test]
+async fn backward_direction_without_cursor_returns_400() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method("GET")
+        .uri("/api/v1/articles?direction=backward")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
+
+#[tokio::test]
+async fn backward_direction_is_rejected_for_search() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method("GET")
+        .uri("/api/v1/articles?q=rust&direction=backward")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
+
+#[tokio::test]
+async fn tag_filtered_list_reports_limit_without_prev_cursor() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method("GET")
+        .uri("/api/v1/articles?tag=Rust&limit=5")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, json) = to_json_async!(resp).await;
+    assert_eq!(
+        json.get("limit").and_then(serde_json::Value::as_u64),
+        Some(5)
+    );
+    assert!(json["prev_cursor"].is_null());
+}
+
+#[tokio::test]
+async fn invalid_tag_filter_returns_400() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method("GET")
+        .uri("/api/v1/articles?tag=not%20a%20tag")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
+
+#[tokio::test]
+async fn backward_direction_is_rejected_for_tag_filter() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method("GET")
+        .uri("/api/v1/articles?tag=rust&direction=backward")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
@@ -13,6 +13,7 @@ use mokkan_core::{
         services::{Dependencies, Registry, RuntimeDependencies},
     },
     async_support::{BoxFuture, boxed},
+    infrastructure::repositories::memory,
     presentation::http::{
         extractors::Authenticated, middleware::require_capabilities, state::HttpContext,
     },
@@ -61,6 +62,8 @@ impl TokenManager for CountingTokenManager {
                 expires_at: now + chrono::Duration::hours(1),
                 session_id: None,
                 token_version: None,
+                custom_role: None,
+                impersonator: None,
             })
         })
     }
@@ -87,7 +90,23 @@ fn test_state(token_manager: Arc<dyn TokenManager>) -> HttpContext {
         article_write_repo: Arc::new(support::mocks::DummyArticleWrite),
         article_read_repo: Arc::new(support::mocks::DummyArticleRead),
         article_revision_repo: Arc::new(support::mocks::DummyArticleRevision),
+        custom_field_repo: Arc::new(support::mocks::MemoryCustomFields::default()),
+        contributor_repo: Arc::new(memory::InMemoryContributorRepository::new()),
+        moderation_repo: Arc::new(support::mocks::MemoryModeration::default()),
+        media_repo: Arc::new(memory::InMemoryMediaRepository::new()),
+        block_list: Arc::new(support::mocks::MemoryBlockList::default()),
+        password_history: Arc::new(memory::InMemoryPasswordHistory::new()),
+        account_deletions: Arc::new(memory::InMemoryAccountDeletions::new()),
+        role_store: Arc::new(memory::InMemoryRoleStore::new()),
+        access_rule_repo: Arc::new(support::mocks::MemoryAccessRules::default()),
         audit_log_repo: Arc::new(support::mocks::MockAuditRepo),
+        user_tokens: Arc::new(
+            mokkan_core::infrastructure::security::user_tokens::InMemoryUserTokenStore::new(),
+        ),
+        syndication_opt_outs: Arc::new(memory::InMemorySyndicationOptOutStore::new()),
+        integrity_checker: None,
+        unit_of_work: None,
+        webhook_repo: Arc::new(memory::InMemoryWebhookRepository::new()),
     };
 
     let services = Arc::new(Registry::new(
@@ -95,6 +114,9 @@ fn test_state(token_manager: Arc<dyn TokenManager>) -> HttpContext {
         RuntimeDependencies {
             password_hasher: Arc::new(support::mocks::DummyPasswordHasher)
                 as Arc<dyn PasswordHasher>,
+            password_breach_checker: Arc::new(
+                mokkan_core::infrastructure::security::breach::OfflineBreachChecker,
+            ),
             token_manager,
             refresh_token_codec: Arc::new(
                 mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec::new(
@@ -106,12 +128,53 @@ fn test_state(token_manager: Arc<dyn TokenManager>) -> HttpContext {
                 mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(
                 ),
             ),
+            session_backend: mokkan_core::application::ports::session_revocation::Backend::InMemory,
             authorization_code_store: Arc::new(
                 mokkan_core::infrastructure::security::authorization_code_store::InMemoryStore::new(
                 ),
             ),
             clock: Arc::new(support::mocks::DummyClock),
+            clock_control: None,
             slugger: Arc::new(support::mocks::DummySlug),
+            security_webhook: None,
+            job_queue: std::sync::Arc::new(mokkan_core::infrastructure::jobs::TokioJobQueue),
+            external_authenticator: None,
+            group_roles: mokkan_core::application::ports::external_auth::GroupRoleMapping::default(),
+            oidc_providers: Vec::new(),
+            command_journal: None,
+            job_control: None,
+            document_converter: std::sync::Arc::new(mokkan_core::infrastructure::documents::DocxConverter),
+            article_renderer: None,
+            archiver: std::sync::Arc::new(mokkan_core::infrastructure::zip::ZipArchiver),
+            site_writer: std::sync::Arc::new(support::mocks::MemorySiteWriter::default()),
+            download_links: std::sync::Arc::new(
+                mokkan_core::infrastructure::security::download_links::HmacDownloadLinkSigner::new(
+                    "test-secret",
+                    std::time::Duration::from_mins(15),
+                )
+                .unwrap(),
+            ),
+            asn_lookup: None,
+            email_sender: Arc::new(mokkan_core::infrastructure::notification::LogEmailSender),
+            read_only: mokkan_core::application::ReadOnlySwitch::default(),
+            rate_limiter: Arc::new(
+                mokkan_core::infrastructure::security::rate_limiter::InMemoryRateLimiter::new(),
+            ),
+            login_throttle:
+                mokkan_core::application::ports::rate_limit::LoginThrottlePolicy::default(),
+            session_policy:
+                mokkan_core::application::ports::session_revocation::SessionPolicy::default(),
+            password_history_depth: 0,
+            account_deletion_grace: chrono::Duration::days(30),
+            slug_cache: mokkan_core::application::services::SlugCachePolicy::default(),
+            syndication_targets: Vec::new(),
+            syndication: mokkan_core::application::services::SyndicationPolicy::default(),
+            webhook_sender: None,
+            webhooks: mokkan_core::application::services::WebhookPolicy::default(),
+            pagination: mokkan_core::application::PaginationPolicy::default(),
+            blob_storage: Arc::new(support::mocks::MemoryBlobStorage::default()),
+            media: mokkan_core::application::services::MediaPolicy::default(),
+            view_counter: Arc::new(mokkan_core::infrastructure::views::InMemoryViewCounter::new()),
         },
     ));
 
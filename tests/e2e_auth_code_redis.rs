@@ -0,0 +1,82 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_auth_code_redis.rs
+use std::collections::HashSet;
+use std::env;
+
+use chrono::Utc;
+use mokkan_core::application::TokenSubject;
+use mokkan_core::application::ports::authorization_code::{Code, CodeStore};
+use mokkan_core::domain::{Role, UserId};
+use mokkan_core::infrastructure::security::redis_authorization_code_store::RedisAuthorizationCodeStore;
+use tokio::time::Duration;
+
+mod support;
+
+async fn redis_available(url: &str) -> bool {
+    let host_port = {
+        let mut s = url;
+        if let Some(i) = s.find("://") {
+            s = &s[i + 3..];
+        }
+        if let Some(i) = s.rfind('/') {
+            s = &s[..i];
+        }
+        if let Some(i) = s.rfind('@') {
+            s = &s[i + 1..];
+        }
+        s.to_string()
+    };
+
+    matches!(
+        tokio::time::timeout(
+            Duration::from_secs(2),
+            tokio::net::TcpStream::connect(host_port.clone()),
+        )
+        .await,
+        Ok(Ok(_))
+    )
+}
+
+/// Redis に保存した認可コードは一度だけ交換できることを確認する
+#[tokio::test]
+async fn redis_codes_are_consumed_exactly_once() {
+    let url = env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".into());
+    if !redis_available(&url).await {
+        eprintln!("Skipping Redis integration test because Redis is not reachable");
+        return;
+    }
+
+    let store = RedisAuthorizationCodeStore::from_url(&url).expect("create store");
+    let now = Utc::now();
+    let code = format!("it-{}", now.timestamp_nanos_opt().unwrap_or_default());
+    store
+        .create_code(Code {
+            code: code.clone(),
+            client_id: Some("cli".into()),
+            redirect_uri: None,
+            subject: TokenSubject {
+                user_id: UserId::new(1).unwrap(),
+                username: "alice".into(),
+                role: Role::Author,
+                capabilities: HashSet::new(),
+                session_id: None,
+                token_version: None,
+                custom_role: None,
+                impersonator: None,
+            },
+            scope: None,
+            code_challenge: None,
+            code_challenge_method: None,
+            nonce: None,
+            created_at: now,
+            expires_at: now + chrono::Duration::minutes(5),
+        })
+        .await
+        .expect("create code");
+
+    let found = store.get_code(&code).await.expect("get code");
+    assert_eq!(found.map(|c| c.subject.username), Some("alice".into()));
+    assert!(store.consume_code(&code).await.expect("consume").is_some());
+    assert!(store.consume_code(&code).await.expect("consume").is_none());
+}
@@ -0,0 +1,94 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_batch_get.rs
+use axum::body::Body;
+use axum::http::{
+    Request, StatusCode,
+    header::{AUTHORIZATION, CONTENT_TYPE},
+};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+async fn post(
+    path: &str,
+    token: Option<&str>,
+    body: &serde_json::Value,
+) -> axum::response::Response {
+    let app = support::make_test_router().await;
+    let mut req = Request::builder()
+        .method("POST")
+        .uri(path)
+        .header(CONTENT_TYPE, "application/json");
+    if let Some(token) = token {
+        req = req.header(AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let req = req.body(Body::from(body.to_string())).unwrap();
+    app.oneshot(req).await.unwrap()
+}
+
+#[tokio::test]
+async fn article_batch_get_reports_unknown_ids_as_missing() {
+    let body = serde_json::json!({ "ids": [3, 1, 3, 0] });
+    let resp = post("/api/v1/articles/batch-get", None, &body).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, json) = to_json_async!(resp).await;
+    assert_eq!(json["items"], serde_json::json!([]));
+    assert_eq!(json["missing_ids"], serde_json::json!([3, 1, 0]));
+}
+
+#[tokio::test]
+async fn article_batch_get_rejects_too_many_ids() {
+    let ids: Vec<i64> = (1..=101).collect();
+    let resp = post(
+        "/api/v1/articles/batch-get",
+        None,
+        &serde_json::json!({ "ids": ids }),
+    )
+    .await;
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
+
+#[tokio::test]
+async fn user_batch_get_requires_users_read() {
+    let body = serde_json::json!({ "ids": [1] });
+    let resp = post(
+        "/api/v1/users/batch-get",
+        Some(support::NO_AUDIT_TOKEN),
+        &body,
+    )
+    .await;
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+
+    let resp = post("/api/v1/users/batch-get", Some(support::TEST_TOKEN), &body).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, json) = to_json_async!(resp).await;
+    assert_eq!(json["missing_ids"], serde_json::json!([1]));
+}
+
+#[tokio::test]
+async fn article_bulk_reports_unknown_ids_as_not_found() {
+    let body = serde_json::json!({ "operation": "publish", "ids": [5, 0] });
+    let resp = post("/api/v1/articles/bulk", Some(support::TEST_TOKEN), &body).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, json) = to_json_async!(resp).await;
+    assert_eq!(
+        json["results"],
+        serde_json::json!([
+            { "id": 5, "status": "not_found" },
+            { "id": 0, "status": "not_found" }
+        ])
+    );
+    assert_eq!(json["failed"], 2);
+}
+
+#[tokio::test]
+async fn article_bulk_rejects_tags_outside_the_tag_operation() {
+    let body = serde_json::json!({ "operation": "delete", "ids": [5], "tags": ["news"] });
+    let resp = post("/api/v1/articles/bulk", Some(support::TEST_TOKEN), &body).await;
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+
+    let body = serde_json::json!({ "operation": "delete", "ids": [5] });
+    let resp = post("/api/v1/articles/bulk", None, &body).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,41 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_bootstrap.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header::AUTHORIZATION};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+#[tokio::test]
+async fn anonymous_bootstrap_has_settings_but_no_profile() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .uri("/api/v1/bootstrap")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let (_headers, json) = to_json_async!(resp).await;
+    assert!(json["profile"].is_null());
+    assert_eq!(json["capabilities"], serde_json::json!([]));
+    assert_eq!(json["features"]["security_webhook"], false);
+    assert_eq!(json["features"]["directory_login"], false);
+    assert_eq!(json["features"]["federated_login"], false);
+    assert_eq!(json["site"]["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(json["site"]["login_providers"], serde_json::json!([]));
+    assert!(json["limits"]["pagination"]["max_limit"].is_u64());
+}
+
+#[tokio::test]
+async fn invalid_credentials_are_not_treated_as_anonymous() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .uri("/api/v1/bootstrap")
+        .header(AUTHORIZATION, "Bearer not-a-token")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::UNAUTHORIZED, "Unauthorized").await;
+}
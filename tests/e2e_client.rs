@@ -0,0 +1,56 @@
+#![allow(clippy::multiple_crate_versions)]
+#![cfg(feature = "client")]
+
+// tests/e2e_client.rs
+use axum::http::StatusCode;
+use mokkan_core::client::{ApiClient, ArticleListParams};
+use std::net::SocketAddr;
+
+mod support;
+
+async fn serve() -> ApiClient {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let service = support::make_test_router()
+        .await
+        .into_make_service_with_connect_info::<SocketAddr>();
+    tokio::spawn(async move { axum::serve(listener, service).await.unwrap() });
+    ApiClient::new(&format!("http://{address}")).unwrap()
+}
+
+/// クライアントがステータスを取得できることを確認する
+#[tokio::test]
+async fn e2e_client_reads_status() {
+    let status = serve().await.status().await.expect("status succeeds");
+    assert_eq!(status.status, "ok");
+}
+
+/// トークンなしでは 401 になり、トークンを付けると認証を通過することを確認する
+#[tokio::test]
+async fn e2e_client_sends_bearer_tokens() {
+    let client = serve().await;
+    let err = client.me().await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::UNAUTHORIZED), "{err}");
+
+    // テスト用リポジトリには利用者がいないため、認証を通過した後で 404 になる
+    let err = client
+        .with_token(support::TEST_TOKEN)
+        .me()
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::NOT_FOUND), "{err}");
+}
+
+/// 記事一覧と存在しない記事の取得がサーバーの応答どおりに返ることを確認する
+#[tokio::test]
+async fn e2e_client_lists_articles_and_reports_missing_ones() {
+    let client = serve().await;
+    let page = client
+        .list_articles(&ArticleListParams::default())
+        .await
+        .expect("list succeeds");
+    assert_eq!(page.limit, Some(20));
+
+    let err = client.article_by_slug("no such slug").await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::NOT_FOUND), "{err}");
+}
@@ -0,0 +1,63 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_conditional_get.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+async fn get(uri: &str, headers: &[(header::HeaderName, &str)]) -> axum::response::Response {
+    let app = support::make_test_router().await;
+    let mut req = Request::builder().method("GET").uri(uri);
+    for (name, value) in headers {
+        req = req.header(name, *value);
+    }
+    app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+}
+
+const VARY: &str = "Accept, Authorization, Cookie, X-Api-Profile";
+
+/// 一覧は `ETag` を返し、同じ `ETag` を `If-None-Match` で送ると本文なしの 304 になる
+#[tokio::test]
+async fn article_list_answers_if_none_match_with_304() {
+    let resp = get("/api/v1/articles", &[]).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let etag = resp.headers()[header::ETAG].to_str().unwrap().to_owned();
+    assert_eq!(resp.headers()[header::VARY], VARY);
+    assert!(
+        resp.headers().get(header::LAST_MODIFIED).is_none(),
+        "pages are validated by ETag only"
+    );
+
+    let resp = get("/api/v1/articles", &[(header::IF_NONE_MATCH, &etag)]).await;
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(resp.headers()[header::ETAG], etag.as_str());
+    assert_eq!(resp.headers()[header::VARY], VARY);
+    assert_eq!(resp.headers()["x-api-profile"], "2024-01");
+    let body = axum::body::to_bytes(resp.into_body(), 1024).await.unwrap();
+    assert!(body.is_empty());
+
+    let resp = get(
+        "/api/v1/articles",
+        &[(header::IF_NONE_MATCH, "\"something-else\"")],
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+/// プロファイルが違えば本文が違うので `ETag` も一致せず、本文が返る
+#[tokio::test]
+async fn etags_differ_between_api_profiles() {
+    let resp = get("/api/v1/articles", &[]).await;
+    let etag = resp.headers()[header::ETAG].to_str().unwrap().to_owned();
+
+    let profile = header::HeaderName::from_static("x-api-profile");
+    let resp = get(
+        "/api/v1/articles",
+        &[(header::IF_NONE_MATCH, &etag), (profile, "2024-06")],
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_ne!(resp.headers()[header::ETAG], etag.as_str());
+}
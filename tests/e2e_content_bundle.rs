@@ -0,0 +1,83 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_content_bundle.rs
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode, header::AUTHORIZATION};
+use http_body_util::BodyExt as _;
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn request(method: Method, uri: &str, token: &str, body: &'static str) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(AUTHORIZATION, format!("Bearer {token}"))
+        .body(Body::from(body))
+        .unwrap()
+}
+
+/// 書き出しは NDJSON を添付ファイルとして返し、先頭行がマニフェストになる
+#[tokio::test]
+async fn e2e_export_streams_an_ndjson_bundle() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(request(
+            Method::GET,
+            "/api/v1/export?include_revisions=true",
+            support::TEST_TOKEN,
+            "",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "application/x-ndjson");
+    let disposition = resp.headers()["content-disposition"].to_str().unwrap();
+    assert!(
+        disposition.starts_with("attachment; filename=\"mokkan-bundle-"),
+        "{disposition}"
+    );
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let first_line = body.split(|byte| *byte == b'\n').next().unwrap();
+    let manifest: serde_json::Value = serde_json::from_slice(first_line).unwrap();
+    assert_eq!(manifest["type"], "manifest");
+    assert_eq!(manifest["version"], 1);
+    assert_eq!(manifest["includes_revisions"], true);
+    assert_eq!(manifest["includes_users"], false);
+}
+
+/// 権限のない利用者には 403、不明な形式や壊れたバンドルには 400 を返す
+#[tokio::test]
+async fn e2e_bundles_reject_forbidden_and_malformed_requests() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .clone()
+        .oneshot(request(Method::GET, "/api/v1/export", "no-audit", ""))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::GET,
+            "/api/v1/export?format=tar",
+            support::TEST_TOKEN,
+            "",
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+
+    let resp = app
+        .oneshot(request(
+            Method::POST,
+            "/api/v1/import?on_conflict=new-slug",
+            support::TEST_TOKEN,
+            "not a bundle",
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
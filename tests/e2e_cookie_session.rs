@@ -0,0 +1,136 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_cookie_session.rs
+use axum::body::Body;
+use axum::http::{
+    Method, Request, StatusCode,
+    header::{AUTHORIZATION, CONTENT_TYPE, COOKIE, SET_COOKIE},
+};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+use support::mocks::SESSION_TOKEN;
+
+fn create_article(cookie: &str, csrf: Option<&str>) -> Request<Body> {
+    let mut req = Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/articles")
+        .header(COOKIE, cookie)
+        .header(CONTENT_TYPE, "application/json");
+    if let Some(csrf) = csrf {
+        req = req.header("x-csrf-token", csrf);
+    }
+    let body = serde_json::json!({ "title": "t", "body": "b", "publish": false }).to_string();
+    req.body(Body::from(body)).unwrap()
+}
+
+#[tokio::test]
+async fn session_cookie_authenticates_reads() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .uri("/api/v1/auth/sessions")
+        .header(COOKIE, format!("mokkan_session={SESSION_TOKEN}"))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn cookie_writes_require_the_csrf_header() {
+    let app = support::make_test_router().await;
+    let cookie = format!("mokkan_session={SESSION_TOKEN}; mokkan_csrf=c1");
+
+    let resp = app
+        .clone()
+        .oneshot(create_article(&cookie, None))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+
+    let resp = app
+        .clone()
+        .oneshot(create_article(&cookie, Some("other")))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+
+    // The dummy article repo fails later; reaching it means auth and CSRF passed.
+    let resp = app
+        .oneshot(create_article(&cookie, Some("c1")))
+        .await
+        .unwrap();
+    assert!(resp.status() != StatusCode::UNAUTHORIZED && resp.status() != StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn bearer_requests_skip_the_csrf_check() {
+    let app = support::make_test_router().await;
+    let mut req = create_article("mokkan_session=stale; mokkan_csrf=c1", None);
+    req.headers_mut().insert(
+        AUTHORIZATION,
+        format!("Bearer {SESSION_TOKEN}").parse().unwrap(),
+    );
+    let resp = app.oneshot(req).await.unwrap();
+    assert!(resp.status() != StatusCode::UNAUTHORIZED && resp.status() != StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn logout_clears_the_cookies() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/auth/logout")
+        .header(
+            COOKIE,
+            format!("mokkan_session={SESSION_TOKEN}; mokkan_csrf=c1"),
+        )
+        .header("x-csrf-token", "c1")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let cleared: Vec<_> = resp
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .map(|value| value.to_str().unwrap().to_string())
+        .collect();
+    assert_eq!(cleared.len(), 2);
+    assert!(cleared.iter().all(|cookie| cookie.contains("Max-Age=0")));
+}
+
+#[tokio::test]
+async fn cross_origin_cookie_writes_are_rejected() {
+    let app = support::make_test_router().await;
+    let mut req = create_article(
+        &format!("mokkan_session={SESSION_TOKEN}; mokkan_csrf=c1"),
+        Some("c1"),
+    );
+    req.headers_mut()
+        .insert("origin", "https://evil.example.net".parse().unwrap());
+    let resp = app.oneshot(req).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+}
+
+#[tokio::test]
+async fn csrf_token_can_be_rotated() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .uri("/api/v1/auth/csrf")
+        .header(
+            COOKIE,
+            format!("mokkan_session={SESSION_TOKEN}; mokkan_csrf=c1"),
+        )
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let cookie = resp.headers()[SET_COOKIE].to_str().unwrap().to_string();
+    let (_headers, json) = to_json_async!(resp).await;
+    let token = json["csrf_token"].as_str().unwrap();
+    assert_ne!(token, "c1");
+    assert!(cookie.starts_with(&format!("mokkan_csrf={token};")));
+}
@@ -0,0 +1,47 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_cors.rs
+use axum::body::Body;
+use axum::http::{HeaderMap, Method, Request, StatusCode};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+async fn preflight(origin: &str) -> (StatusCode, HeaderMap) {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method(Method::OPTIONS)
+        .uri("/api/v1/articles")
+        .header("origin", origin)
+        .header("access-control-request-method", "POST")
+        .header(
+            "access-control-request-headers",
+            "authorization,content-type",
+        )
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    (resp.status(), resp.headers().clone())
+}
+
+/// 既定で許可された開発用オリジンからのプリフライトは認証なしで許可される
+#[tokio::test]
+async fn e2e_preflight_from_allowed_origin_is_answered() {
+    let (status, headers) = preflight("http://localhost:3000").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        headers["access-control-allow-origin"],
+        "http://localhost:3000"
+    );
+    let methods = headers["access-control-allow-methods"].to_str().unwrap();
+    assert!(methods.contains("POST"), "{methods}");
+    assert!(headers.contains_key("access-control-allow-headers"));
+    assert_eq!(headers["access-control-max-age"], "3600");
+}
+
+/// 許可されていないオリジンには CORS ヘッダーを返さない
+#[tokio::test]
+async fn e2e_preflight_from_other_origin_gets_no_cors_headers() {
+    let (_, headers) = preflight("https://evil.example.net").await;
+    assert!(!headers.contains_key("access-control-allow-origin"));
+}
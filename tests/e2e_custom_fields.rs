@@ -0,0 +1,217 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_custom_fields.rs
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode, header::AUTHORIZATION};
+use serde_json::{Value, json};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn request(method: Method, uri: &str, token: &str, body: Option<Value>) -> Request<Body> {
+    let builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(AUTHORIZATION, format!("Bearer {token}"));
+    match body {
+        Some(body) => builder
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+        None => builder.body(Body::empty()).unwrap(),
+    }
+}
+
+fn put_field(token: &str, name: &str, body: Value) -> Request<Body> {
+    request(
+        Method::PUT,
+        &format!("/api/v1/admin/custom-fields/{name}"),
+        token,
+        Some(body),
+    )
+}
+
+fn list_fields() -> Request<Body> {
+    Request::get("/api/v1/articles/custom-fields")
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn admins_define_and_remove_custom_fields() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .clone()
+        .oneshot(put_field(
+            support::TEST_TOKEN,
+            "isbn",
+            json!({"type": "text", "required": true}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app.clone().oneshot(list_fields()).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, json) = to_json_async!(resp).await;
+    assert_eq!(
+        json,
+        json!([{"name": "isbn", "type": "text", "required": true}])
+    );
+
+    let delete = || {
+        request(
+            Method::DELETE,
+            "/api/v1/admin/custom-fields/isbn",
+            support::TEST_TOKEN,
+            None,
+        )
+    };
+    let resp = app.clone().oneshot(delete()).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let resp = app.oneshot(delete()).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
+
+#[tokio::test]
+async fn defining_custom_fields_requires_the_capability() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(put_field(
+            support::NO_AUDIT_TOKEN,
+            "isbn",
+            json!({"type": "text"}),
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+}
+
+#[tokio::test]
+async fn invalid_definitions_are_rejected() {
+    let app = support::make_test_router().await;
+    for (name, body) in [
+        ("isbn", json!({"type": "color"})),
+        ("Not-A-Name", json!({"type": "text"})),
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(put_field(support::TEST_TOKEN, name, body))
+            .await
+            .unwrap();
+        assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+    }
+}
+
+#[tokio::test]
+async fn articles_must_match_the_custom_field_definitions() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .clone()
+        .oneshot(put_field(
+            support::TEST_TOKEN,
+            "pages",
+            json!({"type": "number", "required": true}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    for custom_fields in [
+        json!({}),
+        json!({"pages": "many"}),
+        json!({"pages": 3, "colour": "red"}),
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(request(
+                Method::POST,
+                "/api/v1/articles",
+                support::TEST_TOKEN,
+                Some(json!({"title": "T", "body": "B", "custom_fields": custom_fields})),
+            ))
+            .await
+            .unwrap();
+        assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+    }
+}
+
+fn migrate(token: &str, name: &str, body: Value) -> Request<Body> {
+    request(
+        Method::POST,
+        &format!("/api/v1/admin/custom-fields/{name}/migrations"),
+        token,
+        Some(body),
+    )
+}
+
+#[tokio::test]
+async fn migrations_are_queued_and_reported() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .clone()
+        .oneshot(put_field(
+            support::TEST_TOKEN,
+            "pages",
+            json!({"type": "text"}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app
+        .clone()
+        .oneshot(migrate(
+            support::TEST_TOKEN,
+            "pages",
+            json!({"kind": "change_type", "to": "number"}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    let (_headers, json) = to_json_async!(resp).await;
+    assert_eq!(json["field"], "pages");
+    assert_eq!(json["migration"]["kind"], "change_type");
+    let id = json["id"].as_str().unwrap().to_owned();
+
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::GET,
+            &format!("/api/v1/admin/custom-fields/migrations/{id}"),
+            support::TEST_TOKEN,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app
+        .oneshot(request(
+            Method::GET,
+            "/api/v1/admin/custom-fields/migrations/does-not-exist",
+            support::TEST_TOKEN,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
+
+#[tokio::test]
+async fn migrations_require_the_capability_and_a_known_field() {
+    let app = support::make_test_router().await;
+    let body = json!({"kind": "rename", "to": "page_count"});
+    let resp = app
+        .clone()
+        .oneshot(migrate(support::NO_AUDIT_TOKEN, "pages", body.clone()))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+
+    let resp = app
+        .oneshot(migrate(support::TEST_TOKEN, "pages", body))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
@@ -0,0 +1,208 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_custom_roles.rs
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode, header::AUTHORIZATION};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn request(method: Method, uri: &str, token: &str, body: Option<&str>) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .header(AUTHORIZATION, format!("Bearer {token}"))
+        .body(body.map_or_else(Body::empty, |body| Body::from(body.to_owned())))
+        .unwrap()
+}
+
+/// カスタムロールの定義・再定義・一覧・削除が管理 API から行えることを確認する
+#[tokio::test]
+async fn e2e_custom_role_lifecycle() {
+    let app = support::make_test_router().await;
+    let payload = serde_json::json!({
+        "description": "Reviews drafts",
+        "capabilities": [
+            { "resource": "articles", "action": "update:any" },
+            { "resource": "articles", "action": "view:drafts" },
+            { "resource": "articles", "action": "update:any" },
+        ],
+    });
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::PUT,
+            "/api/v1/admin/roles/reviewer",
+            support::TEST_TOKEN,
+            Some(&payload.to_string()),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_, created) = to_json_async!(resp).await;
+    assert_eq!(created["name"], "reviewer");
+    assert_eq!(created["capabilities"].as_array().map(Vec::len), Some(2));
+
+    let payload = serde_json::json!({
+        "capabilities": [{ "resource": "articles", "action": "view:drafts" }],
+    });
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::PUT,
+            "/api/v1/admin/roles/reviewer",
+            support::TEST_TOKEN,
+            Some(&payload.to_string()),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::GET,
+            "/api/v1/admin/roles",
+            support::TEST_TOKEN,
+            None,
+        ))
+        .await
+        .unwrap();
+    let (_, list) = to_json_async!(resp).await;
+    let roles = list.as_array().unwrap();
+    assert_eq!(roles.len(), 1);
+    assert!(roles[0].get("description").is_none());
+    assert_eq!(
+        roles[0]["capabilities"],
+        serde_json::json!([{ "resource": "articles", "action": "view:drafts" }])
+    );
+
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::DELETE,
+            "/api/v1/admin/roles/reviewer",
+            support::TEST_TOKEN,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let resp = app
+        .oneshot(request(
+            Method::DELETE,
+            "/api/v1/admin/roles/reviewer",
+            support::TEST_TOKEN,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
+
+/// 予約名や未知の権限、存在しないユーザーへの割り当ては拒否される
+#[tokio::test]
+async fn e2e_custom_role_rejects_invalid_requests() {
+    let app = support::make_test_router().await;
+    let unknown = serde_json::json!({
+        "capabilities": [{ "resource": "articles", "action": "launch" }],
+    });
+    let empty = serde_json::json!({ "capabilities": [] });
+    for (uri, payload) in [
+        ("/api/v1/admin/roles/admin", &empty),
+        ("/api/v1/admin/roles/Editor", &empty),
+        ("/api/v1/admin/roles/editor", &unknown),
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(request(
+                Method::PUT,
+                uri,
+                support::TEST_TOKEN,
+                Some(&payload.to_string()),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{uri}");
+    }
+
+    let resp = app
+        .oneshot(request(
+            Method::PUT,
+            "/api/v1/admin/users/42/custom-role",
+            support::TEST_TOKEN,
+            Some(r#"{"role":"editor"}"#),
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
+
+/// roles:manage を持たないユーザーはロールを扱えない
+#[tokio::test]
+async fn e2e_custom_role_requires_capability() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::GET,
+            "/api/v1/admin/roles",
+            "no-audit",
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+
+    let resp = app
+        .oneshot(request(
+            Method::PUT,
+            "/api/v1/admin/roles/editor",
+            "no-audit",
+            Some(r#"{"capabilities":[]}"#),
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+}
+
+/// 個別権限の付与は roles:manage が必要で、存在しないユーザーには行えない
+#[tokio::test]
+async fn e2e_capability_grants_are_checked() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::PUT,
+            "/api/v1/admin/users/42/capabilities/audit:read",
+            "no-audit",
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::PUT,
+            "/api/v1/admin/users/42/capabilities/audit:read",
+            support::TEST_TOKEN,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+
+    let resp = app
+        .oneshot(request(
+            Method::PUT,
+            "/api/v1/admin/users/42/capabilities/launch",
+            support::TEST_TOKEN,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
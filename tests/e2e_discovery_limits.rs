@@ -0,0 +1,59 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_discovery_limits.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use mokkan_core::domain::{ArticleBody, ArticleTitle};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+#[tokio::test]
+async fn limits_match_server_validation() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method("GET")
+        .uri("/api/v1/discovery/limits")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, json) = to_json_async!(resp).await;
+
+    let max_title = json["article"]["max_title_chars"].as_u64().unwrap();
+    let max_body = json["article"]["max_body_chars"].as_u64().unwrap();
+    let max_title = usize::try_from(max_title).unwrap();
+    let max_body = usize::try_from(max_body).unwrap();
+    assert!(ArticleTitle::new("t".repeat(max_title)).is_ok());
+    assert!(ArticleTitle::new("t".repeat(max_title + 1)).is_err());
+    assert!(ArticleBody::new("b".repeat(max_body)).is_ok());
+    assert!(ArticleBody::new("b".repeat(max_body + 1)).is_err());
+
+    assert_eq!(json["pagination"]["max_limit"], 100);
+    for endpoint in ["articles", "users", "audit_logs", "moderation", "jobs"] {
+        let limits = &json["pagination"]["endpoints"][endpoint];
+        assert_eq!(limits["default_limit"], 20, "{endpoint}");
+        assert_eq!(limits["max_limit"], 100, "{endpoint}");
+    }
+    assert_eq!(json["pagination"]["max_batch_ids"], 100);
+    assert_eq!(json["password"]["min_length"], 12);
+    assert!(json["max_request_body_bytes"].as_u64().unwrap() > 0);
+}
+
+#[tokio::test]
+async fn oversized_title_is_rejected_with_400() {
+    let app = support::make_test_router().await;
+    let body = serde_json::json!({
+        "title": "t".repeat(ArticleTitle::MAX_CHARS + 1),
+        "body": "body",
+    });
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/v1/articles")
+        .header("authorization", format!("Bearer {}", support::TEST_TOKEN))
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
@@ -0,0 +1,51 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_document_import.rs
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode, header::AUTHORIZATION};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+const DOCX: &str = "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+
+fn import_request(token: Option<&str>, body: &'static [u8]) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/articles/import-document")
+        .header("content-type", DOCX);
+    if let Some(token) = token {
+        builder = builder.header(AUTHORIZATION, format!("Bearer {token}"));
+    }
+    builder.body(Body::from(body)).unwrap()
+}
+
+#[tokio::test]
+async fn import_requires_authentication() {
+    let app = support::make_test_router().await;
+    let resp = app.oneshot(import_request(None, b"PK")).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::UNAUTHORIZED, "Unauthorized").await;
+}
+
+#[tokio::test]
+async fn import_requires_create_capability() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(import_request(Some(support::NO_AUDIT_TOKEN), b"PK"))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+}
+
+#[tokio::test]
+async fn import_rejects_files_that_are_not_docx() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(import_request(
+            Some(support::TEST_TOKEN),
+            b"%PDF-1.7 not a word file",
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
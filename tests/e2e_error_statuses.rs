@@ -41,3 +41,40 @@ async fn e2e_create_article_forbidden_returns_403() {
     let resp = app.oneshot(req).await.unwrap();
     assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
 }
+
+fn revert_request(token: &str) -> Request<Body> {
+    Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/articles/1/revisions/1/revert")
+        .header(AUTHORIZATION, format!("Bearer {token}"))
+        .header("content-type", "application/json")
+        .body(Body::from("{}"))
+        .unwrap()
+}
+
+/// 存在しない記事のリビジョン復元で 404 Not Found を返すことを確認する
+#[tokio::test]
+async fn e2e_revert_missing_article_returns_404() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(revert_request(support::TEST_TOKEN))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
+
+/// 存在しない記事のリビジョン差分で 404 Not Found を返すことを確認する
+#[tokio::test]
+async fn e2e_diff_missing_article_returns_404() {
+    let app = support::make_test_router().await;
+
+    let req = Request::builder()
+        .method(Method::GET)
+        .uri("/api/v1/articles/1/revisions/1/diff/2?granularity=word")
+        .header(AUTHORIZATION, "Bearer test-token")
+        .body(Body::empty())
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
@@ -0,0 +1,146 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_events_stream.rs
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode, header::AUTHORIZATION};
+use chrono::Utc;
+use http_body_util::BodyExt as _;
+use mokkan_core::application::ports::domain_events::EventPublisher;
+use mokkan_core::domain::{
+    ArticleId, ArticleSnapshot, ArticleVisibility, DomainEvent, EventKind, UserId,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn stream_request(token: &str) -> Request<Body> {
+    Request::builder()
+        .method(Method::GET)
+        .uri("/api/v1/events/stream")
+        .header(AUTHORIZATION, format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// 記事の作成がイベントストリームに `article.created` として届き、記事以外のイベントは流れないことを確認する
+#[tokio::test]
+async fn e2e_events_stream_pushes_article_changes() {
+    let state = support::build_test_state().await;
+    let events = Arc::clone(&state.services.domain_events);
+    let app = mokkan_core::presentation::http::routes::build_router_with_rate_limiter(state, false);
+    let resp = app
+        .oneshot(stream_request(support::TEST_TOKEN))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "text/event-stream");
+    assert_eq!(events.subscribers(), 1);
+    let mut body = resp.into_body();
+
+    let user_event = DomainEvent::new(
+        EventKind::UserRegistered(UserId::new(3).unwrap()),
+        Utc::now(),
+    );
+    events.publish(&user_event).await.unwrap();
+    let article_event = DomainEvent::new(
+        EventKind::ArticleCreated(ArticleId::new(7).unwrap()),
+        Utc::now(),
+    )
+    .with_article(ArticleSnapshot {
+        slug: "live".into(),
+        previous_slug: None,
+        title: "Live".into(),
+        published: false,
+        published_at: None,
+        visibility: ArticleVisibility::Public,
+        author_id: UserId::new(1).unwrap(),
+    });
+    events.publish(&article_event).await.unwrap();
+
+    let frame = tokio::time::timeout(Duration::from_secs(5), body.frame())
+        .await
+        .expect("an event before the timeout")
+        .expect("an open stream")
+        .unwrap();
+    let text = String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap();
+    assert!(text.starts_with("event: article.created\n"), "{text}");
+    let data = text
+        .lines()
+        .find_map(|line| line.strip_prefix("data: "))
+        .unwrap();
+    let payload: serde_json::Value = serde_json::from_str(data).unwrap();
+    assert_eq!(payload["event"], "article.created");
+    assert_eq!(payload["article"]["id"], 7);
+    assert_eq!(payload["article"]["title"], "Live");
+    assert_eq!(payload["article"]["published"], false);
+}
+
+fn article_event(id: i64, visibility: ArticleVisibility) -> DomainEvent {
+    DomainEvent::new(
+        EventKind::ArticleUpdated(ArticleId::new(id).unwrap()),
+        Utc::now(),
+    )
+    .with_article(ArticleSnapshot {
+        slug: format!("article-{id}"),
+        previous_slug: None,
+        title: format!("Article {id}"),
+        published: true,
+        published_at: Some(Utc::now()),
+        visibility,
+        author_id: UserId::new(1).unwrap(),
+    })
+}
+
+/// 他人の非公開記事のイベントは、それを読めない利用者のストリームには流れないことを確認する
+#[tokio::test]
+async fn e2e_events_stream_skips_private_articles_of_others() {
+    let state = support::build_test_state().await;
+    let events = Arc::clone(&state.services.domain_events);
+    let app = mokkan_core::presentation::http::routes::build_router_with_rate_limiter(state, false);
+    let resp = app
+        .oneshot(stream_request(support::AUTHOR_TOKEN))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let mut body = resp.into_body();
+
+    events
+        .publish(&article_event(8, ArticleVisibility::Private))
+        .await
+        .unwrap();
+    events
+        .publish(&article_event(9, ArticleVisibility::Public))
+        .await
+        .unwrap();
+
+    let frame = tokio::time::timeout(Duration::from_secs(5), body.frame())
+        .await
+        .expect("an event before the timeout")
+        .expect("an open stream")
+        .unwrap();
+    let text = String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap();
+    assert!(text.starts_with("event: article.updated\n"), "{text}");
+    assert!(text.contains(r#""id":9"#), "{text}");
+}
+
+/// トークンなしでは 401、権限のない利用者には 403 を返すことを確認する
+#[tokio::test]
+async fn e2e_events_stream_requires_capability() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/v1/events/stream")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let resp = app.oneshot(stream_request("no-audit")).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+}
@@ -0,0 +1,48 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_federated_login.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn get(uri: &str) -> Request<Body> {
+    Request::builder()
+        .method("GET")
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn unknown_provider_is_not_found() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(get("/api/v1/auth/oidc/nope/start"))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
+
+#[tokio::test]
+async fn provider_error_is_reported_as_unauthorized() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(get(
+            "/api/v1/auth/oidc/corp/callback?error=access_denied&state=abc",
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::UNAUTHORIZED, "Unauthorized").await;
+}
+
+#[tokio::test]
+async fn callback_without_code_is_rejected() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(get("/api/v1/auth/oidc/corp/callback?state=abc"))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
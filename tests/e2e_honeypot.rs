@@ -0,0 +1,103 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_honeypot.rs
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{Request, StatusCode};
+use axum::{Extension, Router, routing::get};
+use mokkan_core::config::HoneypotSettings;
+use mokkan_core::domain::RouteGroup;
+use mokkan_core::presentation::http::extractors::TrustedProxies;
+use mokkan_core::presentation::http::middleware::{
+    access_rules::enforce_access_rules,
+    honeypot::{Honeypot, trap_scanners},
+};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tower::util::ServiceExt as _;
+
+mod support;
+
+const PROXY: &str = "192.0.2.1";
+const SPOOFED: &str = "203.0.113.7";
+
+async fn trapped_router(trusted: &[&str]) -> Router {
+    let state = support::build_test_state().await;
+    let honeypot = Honeypot::new(HoneypotSettings {
+        paths: vec!["/wp-login.php".into()],
+        block_for: Duration::from_hours(1),
+        tarpit: Duration::ZERO,
+    });
+    Router::new()
+        .route("/", get(|| async { StatusCode::NO_CONTENT }))
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::new(honeypot),
+            trap_scanners,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            RouteGroup::Site,
+            enforce_access_rules,
+        ))
+        .layer(Extension(state))
+        .layer(Extension(TrustedProxies(
+            trusted.iter().map(|range| range.parse().unwrap()).collect(),
+        )))
+}
+
+fn request(uri: &str, peer: &str, forwarded_for: Option<&str>) -> Request<Body> {
+    let mut req = Request::builder().uri(uri);
+    if let Some(forwarded_for) = forwarded_for {
+        req = req.header("x-forwarded-for", forwarded_for);
+    }
+    req.extension(ConnectInfo(SocketAddr::new(
+        peer.parse::<IpAddr>().unwrap(),
+        40_000,
+    )))
+    .body(Body::empty())
+    .unwrap()
+}
+
+/// 信頼していない接続元が偽装した X-Forwarded-For のアドレスはブロックされず、接続元がブロックされることを確認する
+#[tokio::test]
+async fn e2e_spoofed_forwarded_for_does_not_block_the_spoofed_ip() {
+    let app = trapped_router(&[]).await;
+    let resp = app
+        .clone()
+        .oneshot(request("/wp-login.php", PROXY, Some(SPOOFED)))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = app
+        .clone()
+        .oneshot(request("/", SPOOFED, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = app.oneshot(request("/", PROXY, None)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+/// 信頼するプロキシ経由では転送元のクライアントがブロックされ、プロキシ自体はブロックされないことを確認する
+#[tokio::test]
+async fn e2e_trusted_proxy_reports_the_client_to_block() {
+    let app = trapped_router(&[PROXY]).await;
+    let resp = app
+        .clone()
+        .oneshot(request("/wp-login.php", PROXY, Some(SPOOFED)))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = app
+        .clone()
+        .oneshot(request("/", SPOOFED, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = app.oneshot(request("/", PROXY, None)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+}
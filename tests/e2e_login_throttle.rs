@@ -0,0 +1,37 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_login_throttle.rs
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode, header::RETRY_AFTER};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn login(username: &str) -> Request<Body> {
+    let body = serde_json::json!({ "username": username, "password": "wrong-password" });
+    Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/auth/login")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+/// 同じユーザー名で失敗を繰り返すとロックされ、429 と Retry-After が返る
+#[tokio::test]
+async fn repeated_failed_logins_lock_the_account() {
+    let app = support::make_test_router().await;
+
+    for _ in 0..5 {
+        let resp = app.clone().oneshot(login("mallory")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let resp = app.clone().oneshot(login("Mallory")).await.unwrap();
+    assert_eq!(resp.headers()[RETRY_AFTER], "900");
+    assert_error_response_async!(resp, StatusCode::TOO_MANY_REQUESTS, "Too Many Requests").await;
+
+    // Other accounts are unaffected.
+    let resp = app.oneshot(login("trent")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,129 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_media.rs
+use axum::body::Body;
+use axum::http::{
+    Method, Request, StatusCode,
+    header::{AUTHORIZATION, CONTENT_TYPE, LOCATION, X_CONTENT_TYPE_OPTIONS},
+};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+const BOUNDARY: &str = "mokkan-test-boundary";
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+/// `name` パートに `content_type` のファイルを1つ載せたマルチパート本文
+fn multipart_body(name: &str, content_type: &str, content: &[u8]) -> Vec<u8> {
+    let mut body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"; filename=\"cat.png\"\r\nContent-Type: {content_type}\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+    body
+}
+
+fn upload_request(token: Option<&str>, body: Vec<u8>) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/media")
+        .header(
+            CONTENT_TYPE,
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        );
+    if let Some(token) = token {
+        builder = builder.header(AUTHORIZATION, format!("Bearer {token}"));
+    }
+    builder.body(Body::from(body)).unwrap()
+}
+
+fn get(uri: &str) -> Request<Body> {
+    Request::builder()
+        .method(Method::GET)
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// 未認証のアップロードは 401 Unauthorized を返すことを確認する
+#[tokio::test]
+async fn e2e_upload_requires_authentication() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(upload_request(
+            None,
+            multipart_body("file", "image/png", PNG),
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::UNAUTHORIZED, "Unauthorized").await;
+}
+
+/// 記事作成権限のない利用者はアップロードできないことを確認する
+#[tokio::test]
+async fn e2e_upload_requires_article_create_capability() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(upload_request(
+            Some(support::NO_AUDIT_TOKEN),
+            multipart_body("file", "image/png", PNG),
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+}
+
+/// アップロードした画像を同じ内容・種類で取得できることを確認する
+#[tokio::test]
+async fn e2e_uploaded_images_are_served_back() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .clone()
+        .oneshot(upload_request(
+            Some(support::TEST_TOKEN),
+            multipart_body("file", "image/png", PNG),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let location = resp.headers()[LOCATION].to_str().unwrap().to_string();
+    let (_, json) = to_json_async!(resp).await;
+    assert_eq!(json["url"], location.as_str());
+    assert_eq!(json["content_type"], "image/png");
+    assert_eq!(json["file_name"], "cat.png");
+    assert_eq!(json["size_bytes"], PNG.len());
+
+    let resp = app.oneshot(get(&location)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[CONTENT_TYPE], "image/png");
+    assert_eq!(resp.headers()[X_CONTENT_TYPE_OPTIONS], "nosniff");
+    let bytes = axum::body::to_bytes(resp.into_body(), 1024).await.unwrap();
+    assert_eq!(&bytes[..], PNG);
+}
+
+/// 許可されていない種類や中身と食い違う種類のファイルは拒否されることを確認する
+#[tokio::test]
+async fn e2e_upload_rejects_unaccepted_or_mismatched_files() {
+    let app = support::make_test_router().await;
+    for body in [
+        multipart_body("file", "text/plain", b"hello"),
+        multipart_body("file", "image/png", b"<svg onload=alert(1)>"),
+        multipart_body("attachment", "image/png", PNG),
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(upload_request(Some(support::TEST_TOKEN), body))
+            .await
+            .unwrap();
+        assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+    }
+}
+
+/// 存在しないメディアの取得は 404 Not Found を返すことを確認する
+#[tokio::test]
+async fn e2e_missing_media_returns_404() {
+    let app = support::make_test_router().await;
+    let resp = app.oneshot(get("/api/v1/media/42")).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
@@ -0,0 +1,101 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_moderation.rs
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode, header::AUTHORIZATION};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn json_request(method: Method, uri: &str, token: Option<&str>, body: &str) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(AUTHORIZATION, format!("Bearer {token}"));
+    }
+    builder.body(Body::from(body.to_owned())).unwrap()
+}
+
+/// 存在しない記事の通報は 404 Not Found を返すことを確認する
+#[tokio::test]
+async fn e2e_report_missing_article_returns_404() {
+    let app = support::make_test_router().await;
+    let body = serde_json::json!({ "article_id": 1, "reason": "spam" }).to_string();
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            "/api/public/v1/report",
+            None,
+            &body,
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
+
+/// 未知の通報理由はリクエストとして受け付けないことを確認する
+#[tokio::test]
+async fn e2e_report_unknown_reason_is_rejected() {
+    let app = support::make_test_router().await;
+    let body = serde_json::json!({ "article_id": 1, "reason": "boring" }).to_string();
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            "/api/public/v1/report",
+            None,
+            &body,
+        ))
+        .await
+        .unwrap();
+    assert!(resp.status().is_client_error(), "{}", resp.status());
+}
+
+/// モデレーター権限のない利用者はキューを閲覧できないことを確認する
+#[tokio::test]
+async fn e2e_list_cases_requires_moderation_review() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method(Method::GET)
+        .uri("/api/v1/moderation/cases")
+        .header(AUTHORIZATION, format!("Bearer {}", support::NO_AUDIT_TOKEN))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+}
+
+/// 管理者は空のキューを取得できることを確認する
+#[tokio::test]
+async fn e2e_list_cases_returns_an_empty_page() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method(Method::GET)
+        .uri("/api/v1/moderation/cases?status=resolved")
+        .header(AUTHORIZATION, format!("Bearer {}", support::TEST_TOKEN))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_, json) = to_json_async!(resp).await;
+    assert_eq!(json["items"], serde_json::json!([]));
+    assert_eq!(json["has_more"], false);
+}
+
+/// 存在しないケースの解決は 404 Not Found を返すことを確認する
+#[tokio::test]
+async fn e2e_resolve_missing_case_returns_404() {
+    let app = support::make_test_router().await;
+    let body = serde_json::json!({ "action": "dismiss" }).to_string();
+    let resp = app
+        .oneshot(json_request(
+            Method::POST,
+            "/api/v1/moderation/cases/42/resolve",
+            Some(support::TEST_TOKEN),
+            &body,
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
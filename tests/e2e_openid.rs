@@ -43,8 +43,8 @@ async fn openid_discovery_returns_document() {
         .and_then(|v| v.as_str())
         .expect("userinfo present");
     assert!(
-        userinfo.ends_with("/api/v1/auth/me"),
-        "userinfo endpoint should point to /api/v1/auth/me"
+        userinfo.ends_with("/api/v1/auth/userinfo"),
+        "userinfo endpoint should point to /api/v1/auth/userinfo"
     );
 
     // Ensure claims_supported contains 'sub'
@@ -70,6 +70,49 @@ async fn openid_discovery_returns_document() {
     );
 }
 
+#[tokio::test]
+async fn oauth_authorization_server_metadata_matches_openid_discovery() {
+    let app = support::make_test_router().await;
+    let fetch = |uri: &'static str| {
+        let app = app.clone();
+        async move {
+            let req = Request::builder()
+                .method(Method::GET)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap();
+            let resp = app.oneshot(req).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            to_json_async!(resp).await.1
+        }
+    };
+
+    let metadata = fetch("/.well-known/oauth-authorization-server").await;
+    let openid = fetch("/.well-known/openid-configuration").await;
+
+    for field in [
+        "issuer",
+        "authorization_endpoint",
+        "token_endpoint",
+        "jwks_uri",
+        "revocation_endpoint",
+        "introspection_endpoint",
+        "grant_types_supported",
+        "code_challenge_methods_supported",
+    ] {
+        assert_eq!(metadata[field], openid[field], "{field} differs");
+    }
+    assert!(
+        metadata["token_endpoint"]
+            .as_str()
+            .is_some_and(|uri| uri.ends_with("/api/v1/auth/token"))
+    );
+    assert_eq!(
+        metadata["code_challenge_methods_supported"],
+        serde_json::json!(["S256", "plain"])
+    );
+}
+
 #[tokio::test]
 async fn introspect_and_revoke_endpoints_behave() {
     let app = support::make_test_router().await;
@@ -80,6 +123,7 @@ async fn introspect_and_revoke_endpoints_behave() {
         .method(Method::POST)
         .uri("/api/v1/auth/introspect")
         .header("content-type", "application/json")
+        .header("authorization", format!("Bearer {}", support::TEST_TOKEN))
         .body(Body::from(body))
         .unwrap();
 
@@ -98,9 +142,51 @@ async fn introspect_and_revoke_endpoints_behave() {
         .method(Method::POST)
         .uri("/api/v1/auth/revoke")
         .header("content-type", "application/json")
+        .header("authorization", format!("Bearer {}", support::TEST_TOKEN))
         .body(Body::from(body))
         .unwrap();
 
     let resp = app.oneshot(req).await.unwrap();
     assert_eq!(resp.status(), StatusCode::OK);
 }
+
+#[tokio::test]
+async fn introspect_and_revoke_require_the_introspect_capability() {
+    let app = support::make_test_router().await;
+
+    for uri in ["/api/v1/auth/introspect", "/api/v1/auth/revoke"] {
+        for (token, status) in [
+            (None, StatusCode::UNAUTHORIZED),
+            (Some(support::NO_AUDIT_TOKEN), StatusCode::FORBIDDEN),
+        ] {
+            let mut req = Request::builder()
+                .method(Method::POST)
+                .uri(uri)
+                .header("content-type", "application/json");
+            if let Some(token) = token {
+                req = req.header("authorization", format!("Bearer {token}"));
+            }
+            let body = serde_json::json!({ "token": "invalid" }).to_string();
+            let resp = app
+                .clone()
+                .oneshot(req.body(Body::from(body)).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), status, "{uri} with {token:?}");
+        }
+    }
+}
+
+#[tokio::test]
+async fn userinfo_requires_a_bearer_token() {
+    let app = support::make_test_router().await;
+
+    let req = Request::builder()
+        .method(Method::GET)
+        .uri("/api/v1/auth/userinfo")
+        .body(Body::empty())
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,68 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_permissions.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header::AUTHORIZATION};
+use serde_json::{Value, json};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn get(token: Option<&str>) -> Request<Body> {
+    let mut req = Request::builder().uri("/api/v1/auth/me/permissions");
+    if let Some(token) = token {
+        req = req.header(AUTHORIZATION, format!("Bearer {token}"));
+    }
+    req.body(Body::empty()).unwrap()
+}
+
+fn pairs(json: &Value) -> Vec<String> {
+    json["permissions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| {
+            format!(
+                "{}:{}",
+                p["resource"].as_str().unwrap(),
+                p["action"].as_str().unwrap()
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn lists_the_tokens_permissions_sorted() {
+    let app = support::make_test_router().await;
+    let resp = app.oneshot(get(Some(support::TEST_TOKEN))).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let (_headers, json) = to_json_async!(resp).await;
+    assert_eq!(json["role"], "admin");
+    let pairs = pairs(&json);
+    assert!(pairs.contains(&"audit:read".to_string()));
+    assert!(pairs.contains(&"users:update".to_string()));
+    assert!(!pairs.contains(&"articles:update:own".to_string()));
+    let mut sorted = pairs.clone();
+    sorted.sort();
+    assert_eq!(pairs, sorted);
+}
+
+#[tokio::test]
+async fn token_without_capabilities_gets_an_empty_list() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(get(Some(support::NO_AUDIT_TOKEN)))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, json) = to_json_async!(resp).await;
+    assert_eq!(json["permissions"], json!([]));
+}
+
+#[tokio::test]
+async fn requires_authentication() {
+    let app = support::make_test_router().await;
+    let resp = app.oneshot(get(None)).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::UNAUTHORIZED, "Unauthorized").await;
+}
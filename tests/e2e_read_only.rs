@@ -0,0 +1,99 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_read_only.rs
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode, header::AUTHORIZATION};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn request(
+    method: Method,
+    path: &str,
+    token: &str,
+    body: Option<&serde_json::Value>,
+) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(path)
+        .header(AUTHORIZATION, format!("Bearer {token}"))
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+        .unwrap()
+}
+
+fn grant_role() -> Request<Body> {
+    request(
+        Method::POST,
+        "/api/v1/users/1/grant-role",
+        support::TEST_TOKEN,
+        Some(&serde_json::json!({ "role": "admin" })),
+    )
+}
+
+fn set_read_only(enabled: bool, token: &str) -> Request<Body> {
+    request(
+        Method::PUT,
+        "/api/v1/admin/read-only",
+        token,
+        Some(&serde_json::json!({ "enabled": enabled })),
+    )
+}
+
+/// 読み取り専用モード中は書き込み系リクエストが 503 になり、解除すると元に戻る
+#[tokio::test]
+async fn read_only_mode_refuses_writes_until_switched_off() {
+    let app = support::make_test_router().await;
+
+    let resp = app
+        .clone()
+        .oneshot(set_read_only(true, support::TEST_TOKEN))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_, body) = to_json_async!(resp).await;
+    assert_eq!(body["enabled"], true);
+
+    let resp = app.clone().oneshot(grant_role()).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::SERVICE_UNAVAILABLE, "Service Unavailable")
+        .await;
+
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::GET,
+            "/api/v1/admin/read-only",
+            support::TEST_TOKEN,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_, body) = to_json_async!(resp).await;
+    assert_eq!(body["enabled"], true);
+
+    let resp = app
+        .clone()
+        .oneshot(set_read_only(false, support::TEST_TOKEN))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app.oneshot(grant_role()).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
+
+/// 読み取り専用モードの切り替えには管理者権限が必要
+#[tokio::test]
+async fn switching_read_only_mode_requires_admin() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .clone()
+        .oneshot(set_read_only(true, support::NO_AUDIT_TOKEN))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+
+    let resp = app.oneshot(grant_role()).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
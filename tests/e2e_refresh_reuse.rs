@@ -14,8 +14,10 @@ use mokkan_core::application::commands::users::{
 };
 use mokkan_core::application::ports::{
     refresh_token::Codec,
-    session_revocation::{OpaqueRefreshTokenStore, Revocation},
+    security_events::{ClientInfo, SecurityEventSink, TOKEN_REUSE_AUDIT_ACTION},
+    session_revocation::{OpaqueRefreshTokenStore, RefreshTokenFamilyStore, Revocation},
 };
+use mokkan_core::application::services::SecurityEventService;
 use mokkan_core::domain::user::entity::User;
 use mokkan_core::domain::user::value_objects::{PasswordHash, Role, UserId, Username};
 
@@ -103,6 +105,16 @@ impl mokkan_core::domain::UserRepository for InMemoryUserRepo {
         })
     }
 
+    fn insert_bootstrap_admin(
+        &self,
+        _new_user: mokkan_core::domain::user::entity::NewUser,
+    ) -> BoxFuture<
+        '_,
+        mokkan_core::domain::errors::DomainResult<Option<mokkan_core::domain::user::entity::User>>,
+    > {
+        boxed(async move { Ok(None) })
+    }
+
     fn find_by_username<'a>(
         &'a self,
         username: &'a mokkan_core::domain::user::value_objects::Username,
@@ -183,8 +195,17 @@ impl mokkan_core::domain::UserRepository for InMemoryUserRepo {
     }
 }
 
-#[tokio::test]
-async fn refresh_token_reuse_triggers_revocation_in_memory() {
+type InMemoryStore =
+    mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore;
+type HmacCodec = mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec;
+
+fn build_service() -> (Arc<UserCommandService>, Arc<InMemoryStore>, Arc<HmacCodec>) {
+    build_service_with_events(None)
+}
+
+fn build_service_with_events(
+    security_events: Option<Arc<dyn SecurityEventSink>>,
+) -> (Arc<UserCommandService>, Arc<InMemoryStore>, Arc<HmacCodec>) {
     // prepare a user
     let user = User {
         id: UserId::new(300).unwrap(),
@@ -193,33 +214,51 @@ async fn refresh_token_reuse_triggers_revocation_in_memory() {
         role: Role::Author,
         is_active: true,
         created_at: chrono::Utc::now(),
+        timezone: None,
+        password_reset_required: false,
+        email: None,
+        email_verified: false,
     };
 
     let mut users = HashMap::new();
-    users.insert(300, user.clone());
+    users.insert(300, user);
 
     let repo = Arc::new(InMemoryUserRepo::new(users));
     let password_hasher = Arc::new(support::DummyPasswordHasher);
     let token_manager = Arc::new(FakeTokenManager);
     let clock = Arc::new(support::DummyClock);
-    let session_store = Arc::new(
-        mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(),
-    );
-    let refresh_token_codec = Arc::new(
-        mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec::new(
-            "test-refresh-secret",
-        )
-        .expect("refresh token codec"),
-    );
+    let session_store = Arc::new(InMemoryStore::new());
+    let refresh_token_codec =
+        Arc::new(HmacCodec::new("test-refresh-secret").expect("refresh token codec"));
 
-    let svc = Arc::new(UserCommandService::new(
+    let mut svc = UserCommandService::new(
         repo,
         password_hasher,
         token_manager,
         refresh_token_codec.clone(),
         session_store.clone(),
         clock,
-    ));
+    );
+    if let Some(sink) = security_events {
+        svc = svc.with_security_events(sink);
+    }
+
+    (Arc::new(svc), session_store, refresh_token_codec)
+}
+
+async fn login_tokens(svc: &UserCommandService) -> mokkan_core::application::AuthTokenDto {
+    svc.login(LoginUserCommand {
+        username: "reuse_user".into(),
+        password: "pwd".into(),
+    })
+    .await
+    .expect("login")
+    .token
+}
+
+#[tokio::test]
+async fn refresh_token_reuse_triggers_revocation_in_memory() {
+    let (svc, session_store, refresh_token_codec) = build_service();
 
     // login to get a refresh token
     let login = svc
@@ -297,3 +336,141 @@ async fn refresh_token_reuse_triggers_revocation_in_memory() {
         "revoking the session should clean up stored opaque refresh handles"
     );
 }
+
+#[tokio::test]
+async fn refresh_token_reuse_revokes_only_the_affected_family() {
+    let (svc, session_store, _) = build_service();
+
+    let first = login_tokens(&svc).await;
+    let first_session = first.session_id.expect("first session id");
+    let first_refresh = first.refresh_token.expect("first refresh token");
+
+    let second = login_tokens(&svc).await;
+    let second_session = second.session_id.expect("second session id");
+    let second_refresh = second.refresh_token.expect("second refresh token");
+
+    let first_family = session_store
+        .family_for_session(&first_session)
+        .await
+        .expect("load first family")
+        .expect("login should start a refresh family");
+    let second_family = session_store
+        .family_for_session(&second_session)
+        .await
+        .expect("load second family")
+        .expect("login should start a refresh family");
+    assert_ne!(first_family, second_family);
+
+    // rotate once, then replay the original token to trigger reuse detection
+    let rotated = svc
+        .refresh_token(RefreshTokenCommand {
+            token: first_refresh.clone(),
+        })
+        .await
+        .expect("first refresh")
+        .refresh_token
+        .expect("rotated refresh token");
+    let reused = svc
+        .refresh_token(RefreshTokenCommand {
+            token: first_refresh,
+        })
+        .await;
+    assert!(reused.is_err(), "reusing refresh token should fail");
+
+    assert!(
+        session_store
+            .is_revoked(&first_session)
+            .await
+            .expect("check first revoked"),
+        "reused family should be revoked"
+    );
+    let rotated_after_reuse = svc
+        .refresh_token(RefreshTokenCommand { token: rotated })
+        .await;
+    assert!(
+        rotated_after_reuse.is_err(),
+        "later tokens in the reused family should be rejected"
+    );
+
+    // the other device's family is untouched
+    assert!(
+        !session_store
+            .is_revoked(&second_session)
+            .await
+            .expect("check second revoked"),
+        "sessions outside the reused family should stay active"
+    );
+    let other = svc
+        .refresh_token(RefreshTokenCommand {
+            token: second_refresh,
+        })
+        .await;
+    assert!(
+        other.is_ok(),
+        "refresh tokens from other families should keep working"
+    );
+}
+
+#[tokio::test]
+async fn refresh_token_reuse_reports_incident_with_both_clients() {
+    let audit_repo = support::CapturingAuditRepo::new();
+    let events = Arc::new(SecurityEventService::new(
+        Arc::new(audit_repo.clone()),
+        None,
+    ));
+    let (svc, _, _) = build_service_with_events(Some(events.clone()));
+
+    let device = ClientInfo {
+        ip_address: Some("192.0.2.10".into()),
+        user_agent: Some("phone-app/1.0".into()),
+    };
+    let attacker = ClientInfo {
+        ip_address: Some("198.51.100.7".into()),
+        user_agent: Some("curl/8.0".into()),
+    };
+
+    let login = svc
+        .login_from_client(
+            LoginUserCommand {
+                username: "reuse_user".into(),
+                password: "pwd".into(),
+            },
+            &device,
+        )
+        .await
+        .expect("login");
+    let refresh_token = login.token.refresh_token.expect("refresh token");
+    let session_id = login.token.session_id.expect("session id");
+
+    svc.refresh_token_from_client(
+        RefreshTokenCommand {
+            token: refresh_token.clone(),
+        },
+        &device,
+    )
+    .await
+    .expect("first refresh");
+    let reused = svc
+        .refresh_token_from_client(
+            RefreshTokenCommand {
+                token: refresh_token,
+            },
+            &attacker,
+        )
+        .await;
+    assert!(reused.is_err(), "reusing refresh token should fail");
+    assert_eq!(events.token_reuse_total(), 1);
+
+    let inserted = audit_repo.get_inserted();
+    assert_eq!(inserted.len(), 1, "reuse should write one audit entry");
+    let entry = &inserted[0];
+    assert_eq!(entry.action, TOKEN_REUSE_AUDIT_ACTION);
+    assert_eq!(entry.ip_address.as_deref(), Some("198.51.100.7"));
+
+    let details = entry.details.as_ref().expect("incident details");
+    assert_eq!(details["session_id"], session_id.as_str());
+    assert!(details["family_id"].is_string());
+    assert_eq!(details["legitimate"]["user_agent"], "phone-app/1.0");
+    assert_eq!(details["legitimate"]["ip_address"], "192.0.2.10");
+    assert_eq!(details["suspect"]["user_agent"], "curl/8.0");
+}
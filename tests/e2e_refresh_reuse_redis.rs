@@ -98,6 +98,16 @@ impl mokkan_core::domain::UserRepository for InMemoryUserRepo {
         })
     }
 
+    fn insert_bootstrap_admin(
+        &self,
+        _new_user: mokkan_core::domain::user::entity::NewUser,
+    ) -> BoxFuture<
+        '_,
+        mokkan_core::domain::errors::DomainResult<Option<mokkan_core::domain::user::entity::User>>,
+    > {
+        boxed(async move { Ok(None) })
+    }
+
     fn find_by_username<'a>(
         &'a self,
         username: &'a mokkan_core::domain::user::value_objects::Username,
@@ -226,6 +236,10 @@ fn build_user_command_service(
         role: Role::Author,
         is_active: true,
         created_at: chrono::Utc::now(),
+        timezone: None,
+        password_reset_required: false,
+        email: None,
+        email_verified: false,
     };
 
     let mut users = HashMap::new();
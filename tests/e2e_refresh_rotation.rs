@@ -50,6 +50,13 @@ impl UserRepository for InMemoryUserRepo {
         })
     }
 
+    fn insert_bootstrap_admin(
+        &self,
+        _new_user: NewUser,
+    ) -> BoxFuture<'_, mokkan_core::domain::errors::DomainResult<Option<User>>> {
+        boxed(async move { Ok(None) })
+    }
+
     fn find_by_username<'a>(
         &'a self,
         username: &'a Username,
@@ -175,6 +182,10 @@ async fn refresh_token_single_use_and_concurrent_rotation() {
         role: Role::Author,
         is_active: true,
         created_at: Utc::now(),
+        timezone: None,
+        password_reset_required: false,
+        email: None,
+        email_verified: false,
     };
 
     let mut users = HashMap::new();
@@ -0,0 +1,87 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_route_rate_limit.rs
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{Method, Request, StatusCode, header::RETRY_AFTER};
+use axum::{Extension, Router, routing::post};
+use mokkan_core::presentation::http::middleware::route_rate_limit::{
+    LimitedRoutes, RouteRateLimit, limit_requests,
+};
+use std::net::{IpAddr, SocketAddr};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+async fn limited_router(per_minute: u32) -> Router {
+    let state = support::build_test_state().await;
+    let limit = RouteRateLimit {
+        routes: LimitedRoutes::Auth,
+        per_minute,
+    };
+    Router::new()
+        .route("/probe", post(|| async { StatusCode::NO_CONTENT }))
+        .route_layer(axum::middleware::from_fn_with_state(limit, limit_requests))
+        .layer(Extension(state))
+}
+
+fn probe(ip: &str, token: Option<&str>) -> Request<Body> {
+    let mut req = Request::builder()
+        .method(Method::POST)
+        .uri("/probe")
+        .extension(ConnectInfo(SocketAddr::new(
+            ip.parse::<IpAddr>().unwrap(),
+            40_000,
+        )));
+    if let Some(token) = token {
+        req = req.header("authorization", format!("Bearer {token}"));
+    }
+    req.body(Body::empty()).unwrap()
+}
+
+/// 上限を超えたクライアントアドレスには 429 と Retry-After が返る
+#[tokio::test]
+async fn clients_over_budget_get_too_many_requests() {
+    let app = limited_router(2).await;
+
+    for _ in 0..2 {
+        let resp = app
+            .clone()
+            .oneshot(probe("203.0.113.7", None))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    }
+
+    let resp = app
+        .clone()
+        .oneshot(probe("203.0.113.7", None))
+        .await
+        .unwrap();
+    assert_eq!(resp.headers()[RETRY_AFTER], "60");
+    assert_error_response_async!(resp, StatusCode::TOO_MANY_REQUESTS, "Too Many Requests").await;
+
+    // Other addresses have their own budget.
+    let resp = app.oneshot(probe("203.0.113.8", None)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+}
+
+/// 認証済みの呼び出しはアドレスではなくユーザー単位で数える
+#[tokio::test]
+async fn authenticated_callers_are_counted_per_user() {
+    let app = limited_router(2).await;
+
+    for ip in ["203.0.113.7", "198.51.100.1"] {
+        let req = probe(ip, Some(support::TEST_TOKEN));
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    }
+
+    let req = probe("192.0.2.1", Some(support::TEST_TOKEN));
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // The addresses themselves were not charged.
+    let resp = app.oneshot(probe("203.0.113.7", None)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+}
@@ -0,0 +1,49 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_security_headers.rs
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode, header::CONTENT_TYPE};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+/// 通常の応答にも標準のセキュリティヘッダーが付く
+#[tokio::test]
+async fn e2e_responses_carry_security_headers() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .uri("/api/v1/discovery/limits")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let headers = resp.headers();
+    assert_eq!(headers["x-content-type-options"], "nosniff");
+    assert_eq!(headers["referrer-policy"], "no-referrer");
+    assert!(
+        headers["strict-transport-security"]
+            .to_str()
+            .unwrap()
+            .starts_with("max-age=")
+    );
+    assert!(!headers.contains_key("content-security-policy"));
+}
+
+/// トークン交換は小さな本文しか受け付けず、超過分は JSON の 413 で拒否する
+#[tokio::test]
+async fn e2e_oversized_token_requests_are_rejected() {
+    let app = support::make_test_router().await;
+    let body = format!(
+        "grant_type=authorization_code&code={}",
+        "x".repeat(32 * 1024)
+    );
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/auth/token")
+        .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(Body::from(body))
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::PAYLOAD_TOO_LARGE, "Payload Too Large").await;
+}
@@ -0,0 +1,163 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_session_meta_redis.rs
+use std::env;
+use std::time::Instant;
+
+use mokkan_core::application::ports::session_revocation::{
+    RefreshTokenFamilyStore, Revocation, SessionMetadataStore,
+};
+use mokkan_core::infrastructure::security::redis_session_store::RedisSessionRevocationStore;
+use tokio::time::{Duration, sleep};
+
+mod support;
+
+const SESSIONS: usize = 200;
+
+// Helper to check whether Redis is reachable at the given URL.
+async fn redis_available(url: &str) -> bool {
+    let host_port = {
+        let mut s = url;
+        if let Some(i) = s.find("://") {
+            s = &s[i + 3..];
+        }
+        if let Some(i) = s.rfind('/') {
+            s = &s[..i];
+        }
+        if let Some(i) = s.rfind('@') {
+            s = &s[i + 1..];
+        }
+        s.to_string()
+    };
+
+    matches!(
+        tokio::time::timeout(
+            Duration::from_secs(2),
+            tokio::net::TcpStream::connect(host_port),
+        )
+        .await,
+        Ok(Ok(_))
+    )
+}
+
+/// 多数のセッションを持つユーザーの一覧取得が往復回数一定で済むことを確認する
+#[tokio::test]
+async fn listing_many_sessions_takes_a_fixed_number_of_round_trips() {
+    let url = env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".into());
+    sleep(Duration::from_millis(200)).await;
+    if !redis_available(&url).await {
+        eprintln!("Skipping Redis session metadata test because Redis is not reachable");
+        return;
+    }
+
+    let store = RedisSessionRevocationStore::from_url_with_options(&url, 60, false)
+        .expect("create redis store");
+    let user_id = 9_000_000 + i64::from(chrono::Utc::now().timestamp_subsec_micros());
+    let prefix = format!("meta-bench-{user_id}");
+    for (i, created_at) in (0..SESSIONS).zip(1_000_i64..) {
+        let sid = format!("{prefix}-{i}");
+        store
+            .set_session_metadata(user_id, &sid, Some("bench"), Some("192.0.2.1"), created_at)
+            .await
+            .unwrap();
+    }
+    store.revoke(&format!("{prefix}-0")).await.unwrap();
+
+    let before = store.metadata_round_trips();
+    let started = Instant::now();
+    let sessions = store
+        .list_sessions_for_user_with_meta(user_id)
+        .await
+        .unwrap();
+    let elapsed = started.elapsed();
+    let round_trips = store.metadata_round_trips() - before;
+    eprintln!(
+        "listed {SESSIONS} sessions in {round_trips} round trips ({elapsed:?}); \
+         field-by-field reads took {}",
+        1 + SESSIONS * 7
+    );
+
+    assert_eq!(round_trips, 2);
+    assert_eq!(sessions.len(), SESSIONS);
+    assert!(
+        sessions
+            .iter()
+            .all(|s| s.user_agent.as_deref() == Some("bench"))
+    );
+    assert_eq!(sessions.iter().filter(|s| s.revoked).count(), 1);
+
+    let one = store
+        .get_session_metadata(&format!("{prefix}-1"))
+        .await
+        .unwrap()
+        .expect("stored session");
+    assert_eq!(one.created_at_unix, 1_001);
+    assert!(
+        store
+            .get_session_metadata("meta-bench-missing")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    store.revoke_sessions_for_user(user_id).await.unwrap();
+    for i in 0..SESSIONS {
+        store
+            .delete_session_metadata(&format!("{prefix}-{i}"))
+            .await
+            .unwrap();
+    }
+}
+
+/// リフレッシュトークンファミリーの対応付けは有効期限付きで保存され、ファミリーの失効で消える
+#[tokio::test]
+async fn refresh_family_links_expire_and_go_with_the_family() {
+    let url = env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".into());
+    sleep(Duration::from_millis(200)).await;
+    if !redis_available(&url).await {
+        eprintln!("Skipping Redis refresh family test because Redis is not reachable");
+        return;
+    }
+
+    let store = RedisSessionRevocationStore::from_url_with_options(&url, 60, false)
+        .expect("create redis store")
+        .with_refresh_family_ttl(Duration::from_mins(10));
+    let family = format!(
+        "family-ttl-{}",
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    );
+    let sessions = [format!("{family}-a"), format!("{family}-b")];
+    for sid in &sessions {
+        store.add_session_to_family(&family, sid).await.unwrap();
+    }
+    let keys = [
+        format!("refresh_family:sessions:{family}"),
+        format!("session_refresh_family:{}", sessions[0]),
+        format!("session_refresh_family:{}", sessions[1]),
+    ];
+    let mut conn = redis::Client::open(url.as_str())
+        .unwrap()
+        .get_multiplexed_async_connection()
+        .await
+        .unwrap();
+    for key in &keys {
+        let ttl: i64 = redis::cmd("TTL")
+            .arg(key)
+            .query_async(&mut conn)
+            .await
+            .unwrap();
+        assert!((1..=600).contains(&ttl), "{key}: {ttl}");
+    }
+
+    store.revoke_family(&family).await.unwrap();
+    let remaining: usize = redis::cmd("EXISTS")
+        .arg(&keys)
+        .query_async(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 0);
+    for sid in &sessions {
+        assert!(store.is_revoked(sid).await.unwrap());
+        assert!(store.family_for_session(sid).await.unwrap().is_none());
+    }
+}
@@ -56,6 +56,12 @@ async fn e2e_list_and_revoke_sessions() {
 
     assert!(ids.contains(&"sid-1".to_string()));
     assert!(ids.contains(&"sid-2".to_string()));
+    let current: Vec<&str> = arr
+        .iter()
+        .filter(|v| v["current"] == true)
+        .filter_map(|v| v["session_id"].as_str())
+        .collect();
+    assert_eq!(current, ["sid-1"]);
 
     // Revoke sid-2 as owner
     let req = Request::builder()
@@ -108,3 +114,53 @@ async fn e2e_revoke_other_users_session_requires_permission() {
     let resp = app.oneshot(req).await.unwrap();
     assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
 }
+
+#[tokio::test]
+async fn e2e_revoke_all_sessions_of_a_user_bumps_token_version() {
+    let state = support::build_test_state().await;
+    let store = state.services.session_revocation_store();
+    let now = Utc::now().timestamp();
+
+    for sid in ["sid-1", "sid-2"] {
+        store
+            .set_session_metadata(4, sid, Some("ua"), Some("10.0.0.1"), now)
+            .await
+            .expect("set meta");
+    }
+
+    let app = mokkan_core::presentation::http::routes::build_router_with_rate_limiter(
+        state.clone(),
+        false,
+    );
+    let revoke_all = |token: &str| {
+        Request::builder()
+            .method("DELETE")
+            .uri("/api/v1/users/4/sessions")
+            .header(AUTHORIZATION, format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let resp = app
+        .clone()
+        .oneshot(revoke_all(support::mocks::NO_AUDIT_TOKEN))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+    assert_eq!(store.get_min_token_version(4).await.expect("min"), None);
+
+    let resp = app
+        .clone()
+        .oneshot(revoke_all(support::TEST_TOKEN))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    for sid in ["sid-1", "sid-2"] {
+        assert!(store.is_revoked(sid).await.expect("is_revoked"), "{sid}");
+    }
+    assert_eq!(store.get_min_token_version(4).await.expect("min"), Some(2));
+
+    let resp = app.oneshot(revoke_all(support::TEST_TOKEN)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(store.get_min_token_version(4).await.expect("min"), Some(3));
+}
@@ -0,0 +1,226 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_simulated_time.rs
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header::AUTHORIZATION},
+};
+use mokkan_core::{
+    application::{
+        TokenSubject,
+        ports::security::TokenManager,
+        services::{Dependencies, Registry, RuntimeDependencies},
+    },
+    domain::{Role, UserId},
+    infrastructure::{
+        repositories::memory, security::token::BiscuitTokenManager, time::SimulatedClock,
+    },
+    presentation::http::{routes::build_router_with_rate_limiter, state::HttpContext},
+};
+use serde_json::json;
+use std::{sync::Arc, time::Duration};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+const PRIVATE_KEY: &str = "6937d945f8dbe222ae559a9d341a9c70071ef4565367dcf02bf7d5b03a46df1f";
+
+fn router(token_manager: Arc<dyn TokenManager>, clock: &SimulatedClock) -> axum::Router {
+    let deps = Dependencies {
+        user_repo: Arc::new(support::mocks::DummyRepo),
+        article_write_repo: Arc::new(support::mocks::DummyArticleWrite),
+        article_read_repo: Arc::new(support::mocks::DummyArticleRead),
+        article_revision_repo: Arc::new(support::mocks::DummyArticleRevision),
+        custom_field_repo: Arc::new(support::mocks::MemoryCustomFields::default()),
+        contributor_repo: Arc::new(memory::InMemoryContributorRepository::new()),
+        moderation_repo: Arc::new(support::mocks::MemoryModeration::default()),
+        media_repo: Arc::new(memory::InMemoryMediaRepository::new()),
+        block_list: Arc::new(support::mocks::MemoryBlockList::default()),
+        password_history: Arc::new(memory::InMemoryPasswordHistory::new()),
+        account_deletions: Arc::new(memory::InMemoryAccountDeletions::new()),
+        role_store: Arc::new(memory::InMemoryRoleStore::new()),
+        access_rule_repo: Arc::new(support::mocks::MemoryAccessRules::default()),
+        audit_log_repo: Arc::new(support::mocks::MockAuditRepo),
+        user_tokens: Arc::new(
+            mokkan_core::infrastructure::security::user_tokens::InMemoryUserTokenStore::new(),
+        ),
+        syndication_opt_outs: Arc::new(memory::InMemorySyndicationOptOutStore::new()),
+        integrity_checker: None,
+        unit_of_work: None,
+        webhook_repo: Arc::new(memory::InMemoryWebhookRepository::new()),
+    };
+    let services = Arc::new(Registry::new(
+        deps,
+        RuntimeDependencies {
+            password_hasher: Arc::new(support::mocks::DummyPasswordHasher),
+            password_breach_checker: Arc::new(
+                mokkan_core::infrastructure::security::breach::OfflineBreachChecker,
+            ),
+            token_manager,
+            refresh_token_codec: Arc::new(
+                mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec::new(
+                    "test-refresh-secret",
+                )
+                .expect("refresh token codec"),
+            ),
+            session_revocation_store: Arc::new(
+                mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(),
+            ),
+            session_backend: mokkan_core::application::ports::session_revocation::Backend::InMemory,
+            authorization_code_store: Arc::new(
+                mokkan_core::infrastructure::security::authorization_code_store::InMemoryStore::new(),
+            ),
+            clock: Arc::new(clock.clone()),
+            clock_control: Some(Arc::new(clock.clone())),
+            slugger: Arc::new(support::mocks::DummySlug),
+            security_webhook: None,
+            job_queue: Arc::new(mokkan_core::infrastructure::jobs::TokioJobQueue),
+            external_authenticator: None,
+            group_roles: mokkan_core::application::ports::external_auth::GroupRoleMapping::default(),
+            oidc_providers: Vec::new(),
+            command_journal: None,
+            job_control: None,
+            document_converter: std::sync::Arc::new(mokkan_core::infrastructure::documents::DocxConverter),
+            article_renderer: None,
+            archiver: std::sync::Arc::new(mokkan_core::infrastructure::zip::ZipArchiver),
+            site_writer: std::sync::Arc::new(support::mocks::MemorySiteWriter::default()),
+            download_links: std::sync::Arc::new(
+                mokkan_core::infrastructure::security::download_links::HmacDownloadLinkSigner::new(
+                    "test-secret",
+                    std::time::Duration::from_mins(15),
+                )
+                .unwrap(),
+            ),
+            asn_lookup: None,
+            email_sender: Arc::new(mokkan_core::infrastructure::notification::LogEmailSender),
+            read_only: mokkan_core::application::ReadOnlySwitch::default(),
+            rate_limiter: Arc::new(
+                mokkan_core::infrastructure::security::rate_limiter::InMemoryRateLimiter::new(),
+            ),
+            login_throttle:
+                mokkan_core::application::ports::rate_limit::LoginThrottlePolicy::default(),
+            session_policy:
+                mokkan_core::application::ports::session_revocation::SessionPolicy::default(),
+            password_history_depth: 0,
+            account_deletion_grace: chrono::Duration::days(30),
+            slug_cache: mokkan_core::application::services::SlugCachePolicy::default(),
+            syndication_targets: Vec::new(),
+            syndication: mokkan_core::application::services::SyndicationPolicy::default(),
+            webhook_sender: None,
+            webhooks: mokkan_core::application::services::WebhookPolicy::default(),
+            pagination: mokkan_core::application::PaginationPolicy::default(),
+            blob_storage: Arc::new(support::mocks::MemoryBlobStorage::default()),
+            media: mokkan_core::application::services::MediaPolicy::default(),
+            view_counter: Arc::new(mokkan_core::infrastructure::views::InMemoryViewCounter::new()),
+        },
+    ));
+    let db_pool = sqlx::postgres::PgPoolOptions::new()
+        .connect_lazy("postgres://localhost/postgres")
+        .expect("connect_lazy");
+    build_router_with_rate_limiter(HttpContext { services, db_pool }, false)
+}
+
+fn request(method: &str, uri: &str, token: &str, body: Option<serde_json::Value>) -> Request<Body> {
+    let builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(AUTHORIZATION, format!("Bearer {token}"));
+    match body {
+        Some(body) => builder
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+        None => builder.body(Body::empty()).unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn advancing_the_clock_expires_tokens() {
+    let clock = SimulatedClock::new();
+    let token_manager = BiscuitTokenManager::new(PRIVATE_KEY, Duration::from_mins(10))
+        .unwrap()
+        .with_clock(Arc::new(clock.clone()));
+    let token = token_manager
+        .issue(TokenSubject {
+            user_id: UserId::new(1).unwrap(),
+            username: "admin".into(),
+            role: Role::Admin,
+            capabilities: Role::Admin.default_capabilities(),
+            session_id: None,
+            token_version: None,
+            custom_role: None,
+            impersonator: None,
+        })
+        .await
+        .unwrap()
+        .token;
+    let app = router(Arc::new(token_manager), &clock);
+
+    let resp = app
+        .clone()
+        .oneshot(request("GET", "/api/v1/admin/time", &token, None))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, body) = to_json_async!(resp).await;
+    assert_eq!(body["offset_seconds"], 0);
+
+    let resp = app
+        .clone()
+        .oneshot(request(
+            "POST",
+            "/api/v1/admin/time/advance",
+            &token,
+            Some(json!({ "seconds": 300 })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, body) = to_json_async!(resp).await;
+    assert_eq!(body["offset_seconds"], 300);
+
+    let resp = app
+        .clone()
+        .oneshot(request(
+            "POST",
+            "/api/v1/admin/time/advance",
+            &token,
+            Some(json!({ "seconds": 301 })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app
+        .oneshot(request("GET", "/api/v1/admin/time", &token, None))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::UNAUTHORIZED, "Unauthorized").await;
+}
+
+#[tokio::test]
+async fn endpoints_are_hidden_unless_enabled() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .clone()
+        .oneshot(request(
+            "POST",
+            "/api/v1/admin/time/advance",
+            support::TEST_TOKEN,
+            Some(json!({ "seconds": 60 })),
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+
+    let resp = app
+        .oneshot(request(
+            "GET",
+            "/api/v1/admin/time",
+            support::NO_AUDIT_TOKEN,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+}
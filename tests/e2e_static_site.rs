@@ -0,0 +1,41 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_static_site.rs
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode, header::AUTHORIZATION};
+use http_body_util::BodyExt as _;
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn request(token: &str) -> Request<Body> {
+    Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/admin/static-export")
+        .header(AUTHORIZATION, format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// 管理者は静的サイトを書き出せ、書き出した件数が返る
+#[tokio::test]
+async fn e2e_static_export_reports_what_was_written() {
+    let app = support::make_test_router().await;
+    let resp = app.oneshot(request(support::TEST_TOKEN)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let report: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(report["location"], "memory");
+    let articles = report["articles"].as_u64().unwrap();
+    assert_eq!(report["files_written"].as_u64().unwrap(), articles + 1);
+    assert!(report["generated_at"].is_string());
+}
+
+/// 権限のない利用者には 403 を返す
+#[tokio::test]
+async fn e2e_static_export_is_forbidden_without_maintenance() {
+    let app = support::make_test_router().await;
+    let resp = app.oneshot(request("no-audit")).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+}
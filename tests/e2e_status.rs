@@ -0,0 +1,37 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_status.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+#[tokio::test]
+async fn status_reports_version_build_and_backends() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method("GET")
+        .uri("/api/v1/status")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let (_, json) = to_json_async!(resp).await;
+    assert_eq!(json["status"], "ok");
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    assert!(
+        json["git_commit"]
+            .as_str()
+            .is_some_and(|commit| !commit.is_empty()),
+        "git commit should be embedded at build time"
+    );
+    assert_eq!(json["uptime_seconds"], 0);
+    assert_eq!(json["backends"]["storage"], "postgres");
+    assert_eq!(json["backends"]["session_store"], "in_memory");
+    assert_eq!(json["backends"]["redis"], false);
+    assert_eq!(json["features"]["security_webhook"], false);
+    assert_eq!(json["features"]["directory_login"], false);
+    assert_eq!(json["features"]["federated_login"], false);
+}
@@ -0,0 +1,77 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_timezones.rs
+use axum::body::Body;
+use axum::http::{
+    Request, StatusCode,
+    header::{AUTHORIZATION, CONTENT_TYPE},
+};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn get(uri: &str) -> Request<Body> {
+    Request::builder()
+        .method("GET")
+        .uri(uri)
+        .header(AUTHORIZATION, format!("Bearer {}", support::TEST_TOKEN))
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn audit_list_localizes_timestamps_when_tz_is_given() {
+    let audit_repo = std::sync::Arc::new(support::MockRepo {
+        items: vec![support::sample(support::fixed_now())],
+        next_cursor: None,
+    });
+    let app = support::make_test_router_with_audit_repo(audit_repo).await;
+    let resp = app
+        .oneshot(get("/api/v1/audit-logs?tz=Asia/Tokyo"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, json) = to_json_async!(resp).await;
+    let item = &json["items"][0];
+    assert_eq!(item["created_at"], "2024-01-01T00:00:00+00:00");
+    assert_eq!(item["created_at_local"], "2024-01-01T09:00:00+09:00");
+}
+
+#[tokio::test]
+async fn audit_list_omits_local_timestamps_without_tz() {
+    let audit_repo = std::sync::Arc::new(support::MockRepo {
+        items: vec![support::sample(support::fixed_now())],
+        next_cursor: None,
+    });
+    let app = support::make_test_router_with_audit_repo(audit_repo).await;
+    let resp = app.oneshot(get("/api/v1/audit-logs")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, json) = to_json_async!(resp).await;
+    assert!(json["items"][0].get("created_at_local").is_none());
+}
+
+#[tokio::test]
+async fn unknown_tz_hint_returns_400() {
+    for uri in [
+        "/api/v1/audit-logs?tz=Nowhere/Special",
+        "/api/v1/users?tz=Nowhere/Special",
+    ] {
+        let app = support::make_test_router().await;
+        let resp = app.oneshot(get(uri)).await.unwrap();
+        assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+    }
+}
+
+#[tokio::test]
+async fn updating_preferences_rejects_unknown_timezone() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method("PATCH")
+        .uri("/api/v1/auth/me")
+        .header(AUTHORIZATION, format!("Bearer {}", support::TEST_TOKEN))
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(r#"{"timezone":"Nowhere/Special"}"#))
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
@@ -0,0 +1,69 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_token_reuse_incidents.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header::AUTHORIZATION};
+use mokkan_core::application::ports::security_events::TOKEN_REUSE_AUDIT_ACTION;
+use tower::util::ServiceExt as _;
+
+mod support;
+
+const TOKEN_REUSE: &str = "/api/v1/admin/security/token-reuse";
+
+fn bearer(tok: &str) -> String {
+    format!("Bearer {tok}")
+}
+
+fn reuse_entry() -> mokkan_core::domain::audit::entity::AuditLog {
+    let mut log = support::sample(support::fixed_now());
+    log.id = 7;
+    log.action = TOKEN_REUSE_AUDIT_ACTION.into();
+    log.resource_type = "session".into();
+    log.resource_id = None;
+    log.details = Some(serde_json::json!({
+        "user_id": 1,
+        "session_id": "sess-1",
+        "family_id": "fam-1",
+        "legitimate": { "ip_address": "192.0.2.10", "user_agent": "phone-app/1.0" },
+        "suspect": { "ip_address": "198.51.100.7", "user_agent": "curl/8.0" },
+        "detected_at": "2024-01-01T00:00:00Z",
+    }));
+    log
+}
+
+#[tokio::test]
+async fn lists_only_token_reuse_incidents() {
+    let repo =
+        support::MockRepo::with_items(vec![reuse_entry(), support::sample(support::fixed_now())]);
+    let app = support::make_test_router_with_audit_repo(std::sync::Arc::new(repo)).await;
+    let req = Request::builder()
+        .method("GET")
+        .uri(TOKEN_REUSE)
+        .header(AUTHORIZATION, bearer(support::TEST_TOKEN))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let (_, json) = to_json_async!(resp).await;
+    let items = json["items"].as_array().expect("items array");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], 7);
+    assert_eq!(items[0]["session_id"], "sess-1");
+    assert_eq!(items[0]["family_id"], "fam-1");
+    assert_eq!(items[0]["legitimate"]["user_agent"], "phone-app/1.0");
+    assert_eq!(items[0]["suspect"]["ip_address"], "198.51.100.7");
+}
+
+#[tokio::test]
+async fn token_reuse_incidents_require_audit_capability() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method("GET")
+        .uri(TOKEN_REUSE)
+        .header(AUTHORIZATION, bearer(support::NO_AUDIT_TOKEN))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+}
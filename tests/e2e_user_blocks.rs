@@ -0,0 +1,82 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_user_blocks.rs
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode, header::AUTHORIZATION};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn request(method: Method, uri: &str, token: Option<&str>, body: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(AUTHORIZATION, format!("Bearer {token}"));
+    }
+    builder
+        .body(body.map_or_else(Body::empty, |body| Body::from(body.to_owned())))
+        .unwrap()
+}
+
+/// ブロックしていない利用者は空の一覧を取得できることを確認する
+#[tokio::test]
+async fn e2e_list_blocks_starts_empty() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(request(
+            Method::GET,
+            "/api/v1/auth/me/blocks",
+            Some(support::TEST_TOKEN),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_, json) = to_json_async!(resp).await;
+    assert_eq!(json, serde_json::json!([]));
+}
+
+/// 未認証ではブロック一覧を取得できないことを確認する
+#[tokio::test]
+async fn e2e_list_blocks_requires_authentication() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(request(Method::GET, "/api/v1/auth/me/blocks", None, None))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::UNAUTHORIZED, "Unauthorized").await;
+}
+
+/// 存在しない利用者のブロックは 404 Not Found を返すことを確認する
+#[tokio::test]
+async fn e2e_block_missing_user_returns_404() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(request(
+            Method::PUT,
+            "/api/v1/auth/me/blocks/42",
+            Some(support::TEST_TOKEN),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
+
+/// ブロックしていない利用者のブロック解除は 404 Not Found を返すことを確認する
+#[tokio::test]
+async fn e2e_unblock_without_block_returns_404() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(request(
+            Method::DELETE,
+            "/api/v1/auth/me/blocks/42",
+            Some(support::TEST_TOKEN),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
@@ -0,0 +1,74 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_user_import.rs
+use axum::body::Body;
+use axum::http::{
+    Request, StatusCode,
+    header::{AUTHORIZATION, CONTENT_TYPE},
+};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn import(token: &str, csv: &'static str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/api/v1/admin/users/import?on_duplicate=skip")
+        .header(AUTHORIZATION, format!("Bearer {token}"))
+        .header(CONTENT_TYPE, "text/csv")
+        .body(Body::from(csv))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn import_requires_user_creation_capability() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(import(
+            support::NO_AUDIT_TOKEN,
+            "username,role\nalice,author\n",
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::FORBIDDEN, "Forbidden").await;
+}
+
+#[tokio::test]
+async fn malformed_csv_is_rejected_before_queueing() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(import(
+            support::TEST_TOKEN,
+            "username,shoe_size\nalice,42\n",
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::BAD_REQUEST, "Bad Request").await;
+}
+
+#[tokio::test]
+async fn valid_csv_is_accepted_as_a_job() {
+    let app = support::make_test_router().await;
+    let resp = app
+        .oneshot(import(support::TEST_TOKEN, "username,role\nalice,author\n"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    let (_headers, json) = to_json_async!(resp).await;
+    assert_eq!(json["total_rows"], 1);
+    assert_eq!(json["on_duplicate"], "skip");
+    assert!(json["id"].is_string());
+}
+
+#[tokio::test]
+async fn unknown_import_job_returns_404() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method("GET")
+        .uri("/api/v1/admin/users/import/does-not-exist")
+        .header(AUTHORIZATION, format!("Bearer {}", support::TEST_TOKEN))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
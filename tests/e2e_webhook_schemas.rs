@@ -0,0 +1,45 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_webhook_schemas.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+const SPEC: &str = include_str!("../spec/openapi.json");
+
+#[tokio::test]
+async fn webhook_schemas_resolve_and_match_the_openapi_document() {
+    let app = support::make_test_router().await;
+    let req = Request::builder()
+        .method("GET")
+        .uri("/api/v1/webhooks/schemas")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, json) = to_json_async!(resp).await;
+
+    let events = json["events"].as_array().expect("events array");
+    assert!(
+        events
+            .iter()
+            .any(|event| event["event"] == "refresh_token_reuse")
+    );
+
+    let schemas = &json["components"]["schemas"];
+    for event in events {
+        let reference = event["schema"].as_str().unwrap();
+        let name = reference.trim_start_matches("#/components/schemas/");
+        assert!(schemas.get(name).is_some(), "{reference} is not defined");
+    }
+
+    let spec: serde_json::Value = serde_json::from_str(SPEC).unwrap();
+    for (name, schema) in schemas.as_object().unwrap() {
+        assert_eq!(
+            &spec["components"]["schemas"][name], schema,
+            "spec/openapi.json is out of date for {name}"
+        );
+    }
+}
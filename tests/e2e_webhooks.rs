@@ -0,0 +1,132 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/e2e_webhooks.rs
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode, header::AUTHORIZATION};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn request(method: Method, uri: &str, body: Option<&str>) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .header(AUTHORIZATION, format!("Bearer {}", support::TEST_TOKEN))
+        .body(body.map_or_else(Body::empty, |body| Body::from(body.to_owned())))
+        .unwrap()
+}
+
+/// Webhook の作成・取得・変更・削除が API から行えることを確認する
+#[tokio::test]
+async fn e2e_webhook_crud() {
+    let app = support::make_test_router().await;
+    let payload = serde_json::json!({
+        "url": "https://hooks.example/cms",
+        "secret": "0123456789abcdef",
+        "events": ["article.published", "article.deleted"],
+    });
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::POST,
+            "/api/v1/webhooks",
+            Some(&payload.to_string()),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let (_, created) = to_json_async!(resp).await;
+    assert_eq!(created["secret"], "0123456789abcdef");
+    assert_eq!(created["active"], true);
+    let id = created["id"].as_i64().unwrap();
+
+    let patch = serde_json::json!({ "active": false, "events": ["article.created"] });
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::PATCH,
+            &format!("/api/v1/webhooks/{id}"),
+            Some(&patch.to_string()),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_, updated) = to_json_async!(resp).await;
+    assert_eq!(updated["active"], false);
+    assert_eq!(updated["events"], serde_json::json!(["article.created"]));
+    assert!(updated.get("secret").is_none());
+
+    let resp = app
+        .clone()
+        .oneshot(request(Method::GET, "/api/v1/webhooks", None))
+        .await
+        .unwrap();
+    let (_, list) = to_json_async!(resp).await;
+    assert_eq!(list.as_array().map(Vec::len), Some(1));
+
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::GET,
+            &format!("/api/v1/webhooks/{id}/deliveries"),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_, deliveries) = to_json_async!(resp).await;
+    assert_eq!(deliveries, serde_json::json!([]));
+
+    let resp = app
+        .clone()
+        .oneshot(request(
+            Method::DELETE,
+            &format!("/api/v1/webhooks/{id}"),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let resp = app
+        .oneshot(request(
+            Method::GET,
+            &format!("/api/v1/webhooks/{id}"),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_error_response_async!(resp, StatusCode::NOT_FOUND, "Not Found").await;
+}
+
+/// 不明なイベントや不正な URL は拒否される
+#[tokio::test]
+async fn e2e_webhook_rejects_invalid_subscriptions() {
+    let app = support::make_test_router().await;
+    for payload in [
+        serde_json::json!({ "url": "ftp://hooks.example/", "events": ["article.created"] }),
+        serde_json::json!({ "url": "https://hooks.example/", "events": [] }),
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(request(
+                Method::POST,
+                "/api/v1/webhooks",
+                Some(&payload.to_string()),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{payload}");
+    }
+    let unknown =
+        serde_json::json!({ "url": "https://hooks.example/", "events": ["article.viewed"] });
+    let resp = app
+        .oneshot(request(
+            Method::POST,
+            "/api/v1/webhooks",
+            Some(&unknown.to_string()),
+        ))
+        .await
+        .unwrap();
+    assert!(resp.status().is_client_error());
+}
@@ -0,0 +1,97 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/ephemeral_mode.rs
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::{Router, routing::get};
+use mokkan_core::application::services::SeedService;
+use mokkan_core::domain::{ArticleReadRepository, ArticleSlug, UserRepository, Username};
+use mokkan_core::infrastructure::repositories::InMemoryStores;
+use mokkan_core::infrastructure::seed;
+use mokkan_core::presentation::http::middleware::ephemeral::{
+    EPHEMERAL_INSTANCE_HEADER, watermark,
+};
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn seeder(stores: &InMemoryStores) -> SeedService {
+    SeedService::new(
+        stores.users.clone(),
+        Arc::new(support::DummyPasswordHasher),
+        stores.audit_logs.clone(),
+        Arc::new(support::DummyClock),
+    )
+    .with_pages(
+        stores.articles.clone(),
+        stores.articles.clone(),
+        stores.articles.clone(),
+    )
+    .with_custom_fields(stores.custom_fields.clone())
+}
+
+/// リセット後にデモデータを入れ直すと、同じ内容が新しい ID で復元される
+#[tokio::test]
+async fn reset_restores_the_demo_content_under_new_ids() {
+    let stores = InMemoryStores::new();
+    let seeder = seeder(&stores);
+    let demo = seed::demo();
+
+    let summary = seeder.apply(&demo).await.unwrap();
+    assert_eq!(summary.users_created, demo.users.len());
+    assert_eq!(summary.pages_created, demo.pages.len());
+    let admin = Username::new("demo-admin").unwrap();
+    let before = stores
+        .users
+        .find_by_username(&admin)
+        .await
+        .unwrap()
+        .unwrap();
+
+    stores.reset();
+    assert_eq!(stores.users.count().await.unwrap(), 0);
+
+    seeder.apply(&demo).await.unwrap();
+    let after = stores
+        .users
+        .find_by_username(&admin)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(i64::from(after.id) > i64::from(before.id));
+    assert!(stores.users.find_by_id(before.id).await.unwrap().is_none());
+    let welcome = ArticleSlug::new("welcome").unwrap();
+    assert!(
+        stores
+            .articles
+            .find_by_slug(&welcome)
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
+
+/// エフェメラルインスタンスのレスポンスには目印のヘッダーが付く
+#[tokio::test]
+async fn responses_are_watermarked() {
+    let app = Router::new()
+        .route("/probe", get(|| async { StatusCode::NO_CONTENT }))
+        .layer(axum::middleware::from_fn(watermark));
+
+    let req = Request::builder()
+        .uri("/missing")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(resp.headers()[EPHEMERAL_INSTANCE_HEADER], "true");
+
+    let req = Request::builder()
+        .uri("/probe")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.headers()[EPHEMERAL_INSTANCE_HEADER], "true");
+}
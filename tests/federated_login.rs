@@ -0,0 +1,361 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/federated_login.rs
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::{Json, Router, extract::State, routing::get, routing::post};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::Utc;
+use mokkan_core::application::commands::users::UserCommandService;
+use mokkan_core::application::error::AppError;
+use mokkan_core::application::ports::oidc_login::UpstreamProvider;
+use mokkan_core::application::ports::security_events::ClientInfo;
+use mokkan_core::application::services::{FederatedCallback, FederatedLoginService};
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::UserRepository;
+use mokkan_core::domain::errors::{DomainError, DomainResult};
+use mokkan_core::domain::user::entity::{NewUser, User, UserUpdate};
+use mokkan_core::domain::user::value_objects::{
+    PasswordHash, Role, UserId, UserListCursor, Username,
+};
+use mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec;
+use mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore;
+use mokkan_core::infrastructure::security::upstream_oidc::UpstreamOidcClient;
+use rsa::RsaPrivateKey;
+use rsa::pkcs1::DecodeRsaPrivateKey as _;
+use rsa::pkcs1v15::SigningKey;
+use rsa::sha2::Sha256;
+use rsa::signature::{SignatureEncoding as _, Signer as _};
+use rsa::traits::PublicKeyParts as _;
+use serde_json::{Value, json};
+
+mod support;
+
+const CLIENT_ID: &str = "mokkan";
+const KEY_PEM: &str = include_str!("support/oidc_test_key.pem");
+
+/// What the fake provider puts in the next `id_token` it issues.
+#[derive(Clone)]
+struct FakeIdp {
+    issuer: String,
+    key: Arc<RsaPrivateKey>,
+    nonce: Arc<Mutex<String>>,
+}
+
+impl FakeIdp {
+    fn id_token(&self) -> String {
+        let header = URL_SAFE_NO_PAD.encode(json!({"alg": "RS256", "kid": "k1"}).to_string());
+        let claims = json!({
+            "iss": self.issuer,
+            "aud": CLIENT_ID,
+            "sub": "upstream-1",
+            "exp": Utc::now().timestamp() + 300,
+            "nonce": *self.nonce.lock().unwrap(),
+            "preferred_username": "upstream-user",
+        });
+        let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+        let signing_input = format!("{header}.{payload}");
+        let signature = SigningKey::<Sha256>::new((*self.key).clone())
+            .sign(signing_input.as_bytes())
+            .to_vec();
+        format!("{signing_input}.{}", URL_SAFE_NO_PAD.encode(signature))
+    }
+}
+
+async fn discovery(State(idp): State<FakeIdp>) -> Json<Value> {
+    Json(json!({
+        "issuer": idp.issuer,
+        "authorization_endpoint": format!("{}/authorize", idp.issuer),
+        "token_endpoint": format!("{}/token", idp.issuer),
+        "jwks_uri": format!("{}/jwks", idp.issuer),
+    }))
+}
+
+async fn jwks(State(idp): State<FakeIdp>) -> Json<Value> {
+    let public = idp.key.to_public_key();
+    Json(json!({"keys": [{
+        "kty": "RSA",
+        "kid": "k1",
+        "n": URL_SAFE_NO_PAD.encode(public.n().to_bytes_be()),
+        "e": URL_SAFE_NO_PAD.encode(public.e().to_bytes_be()),
+    }]}))
+}
+
+async fn token(State(idp): State<FakeIdp>) -> Json<Value> {
+    Json(json!({"access_token": "opaque", "token_type": "Bearer", "id_token": idp.id_token()}))
+}
+
+async fn start_fake_idp() -> FakeIdp {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let idp = FakeIdp {
+        issuer: format!("http://{}", listener.local_addr().unwrap()),
+        key: Arc::new(RsaPrivateKey::from_pkcs1_pem(KEY_PEM).unwrap()),
+        nonce: Arc::default(),
+    };
+    let app = Router::new()
+        .route("/.well-known/openid-configuration", get(discovery))
+        .route("/jwks", get(jwks))
+        .route("/token", post(token))
+        .with_state(idp.clone());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    idp
+}
+
+#[derive(Default)]
+struct InMemoryUserRepo {
+    users: Mutex<Vec<User>>,
+    links: Mutex<HashMap<(String, String), UserId>>,
+}
+
+impl UserRepository for InMemoryUserRepo {
+    fn count(&self) -> BoxFuture<'_, DomainResult<u64>> {
+        boxed(async move { Ok(self.users.lock().unwrap().len() as u64) })
+    }
+
+    fn insert(&self, new_user: NewUser) -> BoxFuture<'_, DomainResult<User>> {
+        boxed(async move {
+            let mut users = self.users.lock().unwrap();
+            let user = User {
+                id: UserId::new(i64::try_from(users.len()).unwrap() + 1)?,
+                username: new_user.username,
+                password_hash: new_user.password_hash,
+                role: new_user.role,
+                is_active: new_user.is_active,
+                created_at: new_user.created_at,
+                timezone: None,
+                password_reset_required: new_user.password_reset_required,
+                email: None,
+                email_verified: false,
+            };
+            users.push(user.clone());
+            drop(users);
+            Ok(user)
+        })
+    }
+
+    fn insert_bootstrap_admin(
+        &self,
+        _new_user: NewUser,
+    ) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        boxed(async move { Ok(None) })
+    }
+
+    fn find_by_username<'a>(
+        &'a self,
+        username: &'a Username,
+    ) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        boxed(async move {
+            Ok(self
+                .users
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|user| user.username.eq_ignore_case(username.as_str()))
+                .cloned())
+        })
+    }
+
+    fn find_by_id(&self, id: UserId) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        boxed(async move {
+            Ok(self
+                .users
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|user| user.id == id)
+                .cloned())
+        })
+    }
+
+    fn update(&self, update: UserUpdate) -> BoxFuture<'_, DomainResult<User>> {
+        boxed(async move {
+            self.find_by_id(update.id)
+                .await?
+                .ok_or_else(|| DomainError::NotFound("user not found".into()))
+        })
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        _limit: u32,
+        _cursor: Option<UserListCursor>,
+        _search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, Option<UserListCursor>)>> {
+        boxed(async move { Ok((vec![], None)) })
+    }
+
+    fn find_by_federated_identity<'a>(
+        &'a self,
+        provider: &'a str,
+        subject: &'a str,
+    ) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        boxed(async move {
+            let linked = self
+                .links
+                .lock()
+                .unwrap()
+                .get(&(provider.to_string(), subject.to_string()))
+                .copied();
+            match linked {
+                Some(id) => self.find_by_id(id).await,
+                None => Ok(None),
+            }
+        })
+    }
+
+    fn link_federated_identity<'a>(
+        &'a self,
+        provider: &'a str,
+        subject: &'a str,
+        user_id: UserId,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        boxed(async move {
+            let mut links = self.links.lock().unwrap();
+            let key = (provider.to_string(), subject.to_string());
+            if links.contains_key(&key) {
+                return Err(DomainError::Conflict("identity already linked".into()));
+            }
+            links.insert(key, user_id);
+            drop(links);
+            Ok(())
+        })
+    }
+}
+
+fn service(idp: &FakeIdp, repo: &Arc<InMemoryUserRepo>) -> FederatedLoginService {
+    let repo = Arc::clone(repo) as Arc<dyn UserRepository>;
+    let clock = Arc::new(support::DummyClock);
+    let user_commands = Arc::new(UserCommandService::new(
+        Arc::clone(&repo),
+        Arc::new(support::DummyPasswordHasher),
+        Arc::new(support::DummyTokenManager),
+        Arc::new(HmacRefreshTokenCodec::new("test-refresh-secret").unwrap()),
+        Arc::new(InMemorySessionRevocationStore::new()),
+        clock.clone(),
+    ));
+    let client = UpstreamOidcClient::new(
+        idp.issuer.clone(),
+        CLIENT_ID,
+        "secret",
+        "http://localhost/api/v1/auth/oidc/corp/callback",
+    )
+    .unwrap();
+    FederatedLoginService::new(
+        vec![UpstreamProvider {
+            name: "corp".into(),
+            client: Arc::new(client),
+            default_role: Role::Author,
+        }],
+        repo,
+        Arc::new(support::DummyPasswordHasher),
+        clock,
+        user_commands,
+    )
+}
+
+/// Start a login and have the fake provider expect its nonce.
+async fn start(svc: &FederatedLoginService, idp: &FakeIdp) -> String {
+    let started = svc.start("corp", None).await.expect("start login");
+    let query = started.authorization_url.split_once('?').unwrap().1;
+    let params: HashMap<String, String> = serde_urlencoded::from_str(query).unwrap();
+    assert_eq!(params["state"], started.state);
+    assert_eq!(params["code_challenge_method"], "S256");
+    idp.nonce.lock().unwrap().clone_from(&params["nonce"]);
+    started.state
+}
+
+fn callback(state: &str) -> FederatedCallback {
+    FederatedCallback {
+        provider: "corp".into(),
+        state: state.into(),
+        code: "code".into(),
+    }
+}
+
+#[tokio::test]
+async fn first_login_provisions_and_later_logins_reuse_the_link() {
+    let idp = start_fake_idp().await;
+    let repo = Arc::new(InMemoryUserRepo::default());
+    let svc = service(&idp, &repo);
+
+    let state = start(&svc, &idp).await;
+    let first = svc
+        .callback(callback(&state), &ClientInfo::default())
+        .await
+        .expect("first login");
+    assert_eq!(first.user.username, "upstream-user");
+    assert_eq!(first.user.role, Role::Author);
+
+    let state = start(&svc, &idp).await;
+    let second = svc
+        .callback(callback(&state), &ClientInfo::default())
+        .await
+        .expect("second login");
+    assert_eq!(second.user.id, first.user.id);
+    assert_eq!(repo.users.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn existing_username_is_not_taken_over() {
+    let idp = start_fake_idp().await;
+    let repo = Arc::new(InMemoryUserRepo::default());
+    repo.users.lock().unwrap().push(User {
+        id: UserId::new(1).unwrap(),
+        username: Username::new("upstream-user").unwrap(),
+        password_hash: PasswordHash::new("hash").unwrap(),
+        role: Role::Admin,
+        is_active: true,
+        created_at: Utc::now(),
+        timezone: None,
+        password_reset_required: false,
+        email: None,
+        email_verified: false,
+    });
+    let svc = service(&idp, &repo);
+
+    let state = start(&svc, &idp).await;
+    let result = svc
+        .callback(callback(&state), &ClientInfo::default())
+        .await
+        .expect("login");
+    assert_ne!(result.user.id, 1);
+    assert!(result.user.username.starts_with("upstream-user-"));
+    assert_eq!(result.user.role, Role::Author);
+}
+
+#[tokio::test]
+async fn state_is_single_use() {
+    let idp = start_fake_idp().await;
+    let repo = Arc::new(InMemoryUserRepo::default());
+    let svc = service(&idp, &repo);
+
+    let state = start(&svc, &idp).await;
+    svc.callback(callback(&state), &ClientInfo::default())
+        .await
+        .expect("first use");
+    let replay = svc.callback(callback(&state), &ClientInfo::default()).await;
+    assert!(matches!(replay, Err(AppError::Unauthorized(_))));
+}
+
+#[tokio::test]
+async fn id_token_for_another_login_is_rejected() {
+    let idp = start_fake_idp().await;
+    let repo = Arc::new(InMemoryUserRepo::default());
+    let svc = service(&idp, &repo);
+
+    let state = start(&svc, &idp).await;
+    *idp.nonce.lock().unwrap() = "someone-elses-nonce".into();
+    let result = svc.callback(callback(&state), &ClientInfo::default()).await;
+    assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    assert!(repo.users.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn unknown_provider_is_not_found() {
+    let idp = start_fake_idp().await;
+    let svc = service(&idp, &Arc::new(InMemoryUserRepo::default()));
+
+    let result = svc.start("nope", None).await;
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
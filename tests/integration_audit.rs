@@ -30,6 +30,7 @@ async fn integration_audit_write_and_read() {
     for i in 0..5i64 {
         let log = mokkan_core::domain::audit::entity::NewAuditLog {
             user_id: Some(mokkan_core::domain::user::value_objects::UserId::new(1).unwrap()),
+            impersonator_id: None,
             action: format!("test-integration-{i}"),
             resource_type: "article".to_string(),
             resource_id: Some(100 + i),
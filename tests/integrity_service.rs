@@ -0,0 +1,113 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/integrity_service.rs
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, Utc};
+use mokkan_core::application::ports::integrity::IntegrityChecker;
+use mokkan_core::application::services::IntegrityService;
+use mokkan_core::application::{
+    AnomalyKind, AppError, AppResult, AuthenticatedUser, IntegrityAnomalyDto,
+};
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::{Role, UserId};
+
+mod support;
+
+/// Holds a fixed set of anomalies; a repair drops the repairable ones.
+struct FakeChecker {
+    anomalies: Mutex<Vec<IntegrityAnomalyDto>>,
+}
+
+impl IntegrityChecker for FakeChecker {
+    fn scan(&self) -> BoxFuture<'_, AppResult<Vec<IntegrityAnomalyDto>>> {
+        boxed(async move { Ok(self.anomalies.lock().unwrap().clone()) })
+    }
+
+    fn repair(&self) -> BoxFuture<'_, AppResult<u64>> {
+        boxed(async move {
+            let mut anomalies = self.anomalies.lock().unwrap();
+            let before = anomalies.len();
+            anomalies.retain(|anomaly| !anomaly.kind.repairable());
+            Ok((before - anomalies.len()) as u64)
+        })
+    }
+}
+
+fn anomaly(kind: AnomalyKind, resource_type: &str, resource_id: i64) -> IntegrityAnomalyDto {
+    IntegrityAnomalyDto {
+        kind,
+        resource_type: resource_type.into(),
+        resource_id,
+        detail: String::new(),
+    }
+}
+
+fn service() -> (IntegrityService, support::CapturingAuditRepo) {
+    let audit = support::CapturingAuditRepo::new();
+    let checker = FakeChecker {
+        anomalies: Mutex::new(vec![
+            anomaly(AnomalyKind::OrphanedRevision, "article_revision", 7),
+            anomaly(AnomalyKind::UndefinedCustomField, "article", 3),
+            anomaly(AnomalyKind::DanglingAuditResource, "audit_log", 11),
+        ]),
+    };
+    let svc = IntegrityService::new(
+        Some(Arc::new(checker)),
+        Arc::new(audit.clone()),
+        Arc::new(support::DummyClock),
+    );
+    (svc, audit)
+}
+
+fn user(role: Role) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(1).unwrap(),
+        username: "operator".into(),
+        role,
+        capabilities: role.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+/// 修復は修復可能な異常のみを対象とし、監査ログに記録される
+#[tokio::test]
+async fn repair_fixes_repairable_anomalies_and_is_audited() {
+    let (svc, audit) = service();
+    let admin = user(Role::Admin);
+
+    let report = svc.check(&admin, false).await.unwrap();
+    assert_eq!(report.repaired, 0);
+    assert_eq!(report.anomalies.len(), 3);
+    assert!(audit.get_inserted().is_empty());
+
+    let report = svc.check(&admin, true).await.unwrap();
+    assert_eq!(report.repaired, 2);
+    assert_eq!(
+        report.anomalies,
+        vec![anomaly(AnomalyKind::DanglingAuditResource, "audit_log", 11)]
+    );
+    let logged = audit.get_inserted();
+    assert_eq!(logged.len(), 1);
+    assert_eq!(logged[0].action, "integrity.repair");
+    assert_eq!(
+        logged[0].details,
+        Some(serde_json::json!({ "repaired": 2 }))
+    );
+}
+
+/// 管理者以外は検査も修復もできない
+#[tokio::test]
+async fn integrity_checks_are_admin_only() {
+    let (svc, audit) = service();
+    for repair in [false, true] {
+        let result = svc.check(&user(Role::Author), repair).await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+    assert!(audit.get_inserted().is_empty());
+}
@@ -0,0 +1,368 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/moderation_service.rs
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, Utc};
+use mokkan_core::application::ports::security_events::ClientInfo;
+use mokkan_core::application::services::{
+    ListModerationCasesQuery, ModerationPorts, ModerationService, ReportArticleCommand,
+    ResolveModerationCaseCommand,
+};
+use mokkan_core::application::{AppError, AuthenticatedUser};
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::errors::{DomainError, DomainResult};
+use mokkan_core::domain::user::entity::{NewUser, User, UserUpdate};
+use mokkan_core::domain::user::value_objects::{PasswordHash, UserListCursor, Username};
+use mokkan_core::domain::{
+    Article, ArticleId, ArticleListCursor, ArticleReadRepository, ArticleRevision,
+    ArticleRevisionRepository, ArticleSlug, ArticleUpdate, ArticleWriteRepository, CaseStatus,
+    NewArticle, ReportReason, Resolution, Role, UserId, UserRepository,
+};
+
+mod support;
+
+const AUTHOR: i64 = 2;
+const MODERATOR: i64 = 9;
+
+/// One published article by `AUTHOR`, and the author's account.
+struct Site {
+    article: Mutex<Article>,
+    revisions: Mutex<usize>,
+    author: Mutex<User>,
+}
+
+impl Site {
+    fn new() -> Self {
+        Self {
+            article: Mutex::new(
+                support::ArticleBuilder::new()
+                    .author(AUTHOR)
+                    .published()
+                    .build(),
+            ),
+            revisions: Mutex::new(0),
+            author: Mutex::new(User {
+                id: UserId::new(AUTHOR).unwrap(),
+                username: Username::new("author").unwrap(),
+                password_hash: PasswordHash::new("hash".to_string()).unwrap(),
+                role: Role::Author,
+                is_active: true,
+                created_at: Utc::now(),
+                timezone: None,
+                password_reset_required: false,
+                email: None,
+                email_verified: false,
+            }),
+        }
+    }
+
+    fn article(&self) -> Article {
+        self.article.lock().unwrap().clone()
+    }
+}
+
+impl ArticleWriteRepository for Site {
+    fn insert(&self, _article: NewArticle) -> BoxFuture<'_, DomainResult<Article>> {
+        boxed(async { Err(DomainError::Validation("not supported".into())) })
+    }
+
+    fn update(&self, update: ArticleUpdate) -> BoxFuture<'_, DomainResult<Article>> {
+        boxed(async move {
+            let mut article_guard = self.article.lock().unwrap();
+            let article = &mut *article_guard;
+            if article.updated_at != update.original_updated_at {
+                return Err(DomainError::Conflict("article was modified".into()));
+            }
+            if let Some(state) = update.publish_state {
+                article.published = state.published;
+                article.published_at = state.published_at;
+            }
+            article.updated_at = update.updated_at;
+            let article = article.clone();
+            drop(article_guard);
+            Ok(article)
+        })
+    }
+
+    fn delete(&self, _id: ArticleId) -> BoxFuture<'_, DomainResult<()>> {
+        boxed(async { Ok(()) })
+    }
+}
+
+impl ArticleReadRepository for Site {
+    fn find_by_id(&self, id: ArticleId) -> BoxFuture<'_, DomainResult<Option<Article>>> {
+        boxed(async move { Ok(Some(self.article()).filter(|article| article.id == id)) })
+    }
+
+    fn find_by_slug<'a>(
+        &'a self,
+        _slug: &'a ArticleSlug,
+    ) -> BoxFuture<'a, DomainResult<Option<Article>>> {
+        boxed(async { Ok(None) })
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        _include_drafts: bool,
+        _limit: u32,
+        _cursor: Option<ArticleListCursor>,
+        _search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        boxed(async { Ok((vec![], None)) })
+    }
+}
+
+impl ArticleRevisionRepository for Site {
+    fn append<'a>(
+        &'a self,
+        _article: &'a Article,
+        _edited_by: Option<UserId>,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        boxed(async move {
+            *self.revisions.lock().unwrap() += 1;
+            Ok(())
+        })
+    }
+
+    fn list_by_article(
+        &self,
+        _article_id: ArticleId,
+    ) -> BoxFuture<'_, DomainResult<Vec<ArticleRevision>>> {
+        boxed(async { Ok(vec![]) })
+    }
+}
+
+impl UserRepository for Site {
+    fn count(&self) -> BoxFuture<'_, DomainResult<u64>> {
+        boxed(async { Ok(1) })
+    }
+
+    fn insert(&self, _new_user: NewUser) -> BoxFuture<'_, DomainResult<User>> {
+        boxed(async { Err(DomainError::Validation("not supported".into())) })
+    }
+
+    fn insert_bootstrap_admin(
+        &self,
+        _new_user: NewUser,
+    ) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        boxed(async { Ok(None) })
+    }
+
+    fn find_by_username<'a>(
+        &'a self,
+        _username: &'a Username,
+    ) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        boxed(async { Ok(None) })
+    }
+
+    fn find_by_id(&self, id: UserId) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        let user = self.author.lock().unwrap().clone();
+        boxed(async move { Ok(Some(user).filter(|user| user.id == id)) })
+    }
+
+    fn update(&self, update: UserUpdate) -> BoxFuture<'_, DomainResult<User>> {
+        boxed(async move {
+            let mut author = self.author.lock().unwrap();
+            if author.id != update.id {
+                return Err(DomainError::NotFound("user not found".into()));
+            }
+            if let Some(is_active) = update.is_active {
+                author.is_active = is_active;
+            }
+            let user = author.clone();
+            drop(author);
+            Ok(user)
+        })
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        _limit: u32,
+        _cursor: Option<UserListCursor>,
+        _search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, Option<UserListCursor>)>> {
+        boxed(async { Ok((vec![], None)) })
+    }
+}
+
+struct Fixture {
+    site: Arc<Site>,
+    cases: Arc<support::MemoryModeration>,
+    audit: support::CapturingAuditRepo,
+    svc: ModerationService,
+}
+
+fn fixture() -> Fixture {
+    let site = Arc::new(Site::new());
+    let cases = Arc::new(support::MemoryModeration::default());
+    let audit = support::CapturingAuditRepo::new();
+    let svc = ModerationService::new(
+        ModerationPorts {
+            moderation_repo: Arc::clone(&cases) as _,
+            article_read_repo: Arc::clone(&site) as _,
+            article_write_repo: Arc::clone(&site) as _,
+            article_revision_repo: Arc::clone(&site) as _,
+            user_repo: Arc::clone(&site) as _,
+            audit_log_repo: Arc::new(audit.clone()),
+        },
+        Arc::new(support::DummyClock),
+    );
+    Fixture {
+        site,
+        cases,
+        audit,
+        svc,
+    }
+}
+
+fn user(id: i64, role: Role) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(id).unwrap(),
+        username: format!("user-{id}"),
+        role,
+        capabilities: role.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+fn client() -> ClientInfo {
+    ClientInfo {
+        ip_address: Some("203.0.113.7".into()),
+        user_agent: Some("reader".into()),
+    }
+}
+
+async fn report(fixture: &Fixture, reason: ReportReason) -> Result<(), AppError> {
+    fixture
+        .svc
+        .report(
+            None,
+            &client(),
+            ReportArticleCommand {
+                article_id: 1,
+                reason,
+                details: None,
+            },
+        )
+        .await
+}
+
+async fn resolve(fixture: &Fixture, resolution: Resolution) -> Result<CaseStatus, AppError> {
+    fixture
+        .svc
+        .resolve(
+            &user(MODERATOR, Role::Admin),
+            &client(),
+            ResolveModerationCaseCommand {
+                case_id: 1,
+                resolution,
+                note: Some("checked".into()),
+            },
+        )
+        .await
+        .map(|case| case.status)
+}
+
+#[tokio::test]
+async fn reports_about_one_article_share_an_open_case() {
+    let fixture = fixture();
+    report(&fixture, ReportReason::Spam).await.unwrap();
+    report(&fixture, ReportReason::Other).await.unwrap();
+
+    let page = fixture
+        .svc
+        .list_cases(
+            &user(MODERATOR, Role::Admin),
+            ListModerationCasesQuery {
+                status: CaseStatus::Open,
+                limit: 0,
+                cursor: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].reports.len(), 2);
+    let audited = fixture.audit.get_inserted();
+    assert_eq!(audited.len(), 2);
+    assert_eq!(audited[0].action, "moderation.report");
+    assert_eq!(audited[0].ip_address.as_deref(), Some("203.0.113.7"));
+}
+
+#[tokio::test]
+async fn drafts_cannot_be_reported() {
+    let fixture = fixture();
+    fixture.site.article.lock().unwrap().published = false;
+    let err = report(&fixture, ReportReason::Spam).await.unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)), "{err}");
+    assert!(fixture.cases.cases().is_empty());
+}
+
+#[tokio::test]
+async fn unpublish_takes_the_article_down_once() {
+    let fixture = fixture();
+    report(&fixture, ReportReason::Illegal).await.unwrap();
+
+    let status = resolve(&fixture, Resolution::Unpublish).await.unwrap();
+
+    assert_eq!(status, CaseStatus::Resolved);
+    assert!(!fixture.site.article().published);
+    assert_eq!(*fixture.site.revisions.lock().unwrap(), 1);
+    assert_eq!(
+        fixture.audit.get_inserted().last().unwrap().action,
+        "moderation.unpublish"
+    );
+    let err = resolve(&fixture, Resolution::Dismiss).await.unwrap_err();
+    assert!(matches!(err, AppError::Conflict(_)), "{err}");
+}
+
+#[tokio::test]
+async fn ban_author_deactivates_the_author() {
+    let fixture = fixture();
+    report(&fixture, ReportReason::Harassment).await.unwrap();
+
+    resolve(&fixture, Resolution::BanAuthor).await.unwrap();
+
+    assert!(!fixture.site.article().published);
+    assert!(!fixture.site.author.lock().unwrap().is_active);
+}
+
+#[tokio::test]
+async fn dismiss_leaves_the_article_alone() {
+    let fixture = fixture();
+    report(&fixture, ReportReason::Misinformation)
+        .await
+        .unwrap();
+
+    resolve(&fixture, Resolution::Dismiss).await.unwrap();
+
+    assert!(fixture.site.article().published);
+    assert!(fixture.cases.cases()[0].decision.is_some());
+}
+
+#[tokio::test]
+async fn authors_cannot_review_cases() {
+    let fixture = fixture();
+    report(&fixture, ReportReason::Spam).await.unwrap();
+    let err = fixture
+        .svc
+        .resolve(
+            &user(AUTHOR, Role::Author),
+            &client(),
+            ResolveModerationCaseCommand {
+                case_id: 1,
+                resolution: Resolution::Dismiss,
+                note: None,
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)), "{err}");
+    assert!(fixture.cases.cases()[0].decision.is_none());
+}
@@ -0,0 +1,187 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/numbered_pages.rs
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use mokkan_core::application::commands::articles::{ArticleCommandService, CreateArticleCommand};
+use mokkan_core::application::queries::articles::{ArticleQueryService, ListArticlesQuery};
+use mokkan_core::application::queries::users::{ListUsersQuery, UserQueryService};
+use mokkan_core::application::{
+    AppError, AppResult, ArticleDto, ArticleSort, AuthenticatedUser, CursorPage, PageDirection,
+    UserDto,
+};
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::{NewUser, PasswordHash, Role, UserId, UserRepository, Username};
+use mokkan_core::infrastructure::repositories::InMemoryStores;
+use mokkan_core::infrastructure::time::SimulatedClock;
+
+mod support;
+
+fn admin() -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(1).unwrap(),
+        username: "admin".into(),
+        role: Role::Admin,
+        capabilities: Role::Admin.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+const fn article_page(page: u32, limit: u32) -> ListArticlesQuery {
+    ListArticlesQuery {
+        include_drafts: false,
+        limit,
+        cursor: None,
+        direction: PageDirection::Forward,
+        tag: None,
+        sort: ArticleSort::Newest,
+        page: Some(page),
+    }
+}
+
+async fn articles(titles: &[&str]) -> ArticleQueryService {
+    let stores = InMemoryStores::new();
+    let slugs = Arc::new(ArticleSlugService::new(
+        stores.articles.clone(),
+        Arc::new(support::DummySlug),
+    ));
+    let commands = ArticleCommandService::new(
+        stores.articles.clone(),
+        stores.articles.clone(),
+        stores.articles.clone(),
+        slugs,
+        Arc::new(SimulatedClock::new()),
+    );
+    for title in titles {
+        let command = CreateArticleCommand::builder()
+            .title(*title)
+            .body("body")
+            .publish(true)
+            .build()
+            .unwrap();
+        commands.create_article(&admin(), command).await.unwrap();
+    }
+    ArticleQueryService::new(stores.articles.clone(), stores.articles)
+}
+
+fn titles(page: &CursorPage<ArticleDto>) -> Vec<&str> {
+    page.items
+        .iter()
+        .map(|article| article.title.as_str())
+        .collect()
+}
+
+/// 番号付きページは新着順に区切られ、総件数と続きの有無を返す
+#[tokio::test]
+async fn numbered_article_pages_report_the_total_count() {
+    let queries = articles(&["one", "two", "three"]).await;
+
+    let first = queries
+        .list_articles(None, article_page(1, 2))
+        .await
+        .unwrap();
+    assert_eq!(titles(&first), vec!["three", "two"]);
+    assert_eq!(first.total_count, Some(3));
+    assert!(first.has_more);
+    assert!(first.next_cursor.is_none());
+
+    let second = queries
+        .list_articles(None, article_page(2, 2))
+        .await
+        .unwrap();
+    assert_eq!(titles(&second), vec!["one"]);
+    assert!(!second.has_more);
+
+    let beyond = queries
+        .list_articles(None, article_page(5, 2))
+        .await
+        .unwrap();
+    assert!(beyond.items.is_empty());
+    assert_eq!(beyond.total_count, Some(3));
+}
+
+/// ページ番号 0 やカーソル・人気順との併用は検証エラーになる
+#[tokio::test]
+async fn numbered_article_pages_reject_zero_and_cursor_options() {
+    let queries = articles(&["one", "two"]).await;
+    let cursor = queries
+        .list_articles(
+            None,
+            ListArticlesQuery {
+                page: None,
+                ..article_page(1, 1)
+            },
+        )
+        .await
+        .unwrap()
+        .next_cursor;
+
+    for query in [
+        article_page(0, 1),
+        ListArticlesQuery {
+            cursor,
+            ..article_page(1, 1)
+        },
+        ListArticlesQuery {
+            direction: PageDirection::Backward,
+            ..article_page(1, 1)
+        },
+        ListArticlesQuery {
+            sort: ArticleSort::Popular,
+            ..article_page(1, 1)
+        },
+    ] {
+        let err = queries.list_articles(None, query).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)), "{err}");
+    }
+}
+
+async fn list_users(
+    queries: &UserQueryService,
+    page: u32,
+    q: Option<&str>,
+) -> AppResult<CursorPage<UserDto>> {
+    queries
+        .list_users(
+            &admin(),
+            ListUsersQuery {
+                limit: 1,
+                cursor: None,
+                q: q.map(Into::into),
+                direction: PageDirection::Forward,
+                page: Some(page),
+            },
+        )
+        .await
+}
+
+/// 利用者一覧も番号付きページで検索条件に合う総件数を返す
+#[tokio::test]
+async fn numbered_user_pages_count_matching_users() {
+    let stores = InMemoryStores::new();
+    for name in ["alice", "alfred", "bob"] {
+        let user = NewUser::new(
+            Username::new(name).unwrap(),
+            PasswordHash::new("$argon2id$v=19$m=19456,t=2,p=1$c2FsdA$aGFzaA").unwrap(),
+            Role::Author,
+            Utc::now(),
+        )
+        .unwrap();
+        stores.users.insert(user).await.unwrap();
+    }
+    let queries = UserQueryService::new(stores.users, Arc::new(SimulatedClock::new()));
+
+    let page = list_users(&queries, 2, Some("al")).await.unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.total_count, Some(2));
+    assert!(!page.has_more);
+
+    let err = list_users(&queries, 0, None).await.unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)), "{err}");
+}
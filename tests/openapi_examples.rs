@@ -0,0 +1,206 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/openapi_examples.rs
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use mokkan_core::presentation::http::openapi::{error_catalog::ERROR_CODES, examples};
+use serde_json::Value;
+use tower::util::ServiceExt as _;
+
+mod support;
+
+fn spec() -> Value {
+    serde_json::from_str(include_str!("../spec/openapi.json")).expect("valid OpenAPI snapshot")
+}
+
+fn resolve<'a>(spec: &'a Value, schema: &'a Value) -> &'a Value {
+    schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .map_or(schema, |reference| {
+            let name = reference.trim_start_matches("#/components/schemas/");
+            &spec["components"]["schemas"][name]
+        })
+}
+
+fn type_matches(ty: &str, value: &Value) -> bool {
+    match ty {
+        "null" => value.is_null(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => false,
+    }
+}
+
+/// Problems with `value` against `schema`, following `$ref`s into the spec.
+/// Covers the keywords the snapshot uses: `type`, `enum`, `oneOf`,
+/// `required`, `properties` and `items`.
+fn check(spec: &Value, schema: &Value, value: &Value, at: &str, problems: &mut Vec<String>) {
+    let schema = resolve(spec, schema);
+    if let Some(variants) = schema.get("oneOf").and_then(Value::as_array) {
+        let fits = variants.iter().any(|variant| {
+            let mut inner = Vec::new();
+            check(spec, variant, value, at, &mut inner);
+            inner.is_empty()
+        });
+        if !fits {
+            problems.push(format!("{at}: matches no oneOf variant"));
+        }
+        return;
+    }
+    if let Some(ty) = schema.get("type") {
+        let types: Vec<&str> = match ty {
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            other => other.as_str().into_iter().collect(),
+        };
+        if !types.iter().any(|ty| type_matches(ty, value)) {
+            problems.push(format!("{at}: expected {types:?}, got {value}"));
+            return;
+        }
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        problems.push(format!("{at}: {value} is not one of {allowed:?}"));
+    }
+    if let Some(object) = value.as_object() {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for name in schema["required"].as_array().into_iter().flatten() {
+            let name = name.as_str().unwrap();
+            if !object.contains_key(name) {
+                problems.push(format!("{at}: missing required {name}"));
+            }
+        }
+        if let Some(properties) = properties {
+            for (name, field) in object {
+                match properties.get(name) {
+                    Some(property) => {
+                        check(spec, property, field, &format!("{at}.{name}"), problems);
+                    }
+                    None => problems.push(format!("{at}: undocumented property {name}")),
+                }
+            }
+        }
+    }
+    if let (Some(items), Some(values)) = (schema.get("items"), value.as_array()) {
+        for (index, item) in values.iter().enumerate() {
+            check(spec, items, item, &format!("{at}[{index}]"), problems);
+        }
+    }
+}
+
+/// スナップショットの例がコード上の例と一致し、スキーマにも適合している
+#[test]
+fn snapshot_examples_match_the_module_and_their_schemas() {
+    let spec = spec();
+    let documented = &spec["components"]["examples"];
+    let mut problems = Vec::new();
+
+    for (name, value) in examples::schema_examples() {
+        assert_eq!(
+            documented[name]["value"], value,
+            "spec/openapi.json is out of date for the {name} example"
+        );
+        let schema = serde_json::json!({ "$ref": format!("#/components/schemas/{name}") });
+        check(&spec, &schema, &value, name, &mut problems);
+    }
+    for (item, value) in examples::list_examples() {
+        let name = examples::list_example_name(item);
+        assert_eq!(
+            documented[&name]["value"], value,
+            "spec/openapi.json is out of date for the {name} example"
+        );
+        let schema = serde_json::json!({
+            "type": "array",
+            "items": { "$ref": format!("#/components/schemas/{item}") }
+        });
+        check(&spec, &schema, &value, &name, &mut problems);
+    }
+    let error_schema = serde_json::json!({ "$ref": "#/components/schemas/ErrorResponse" });
+    for code in ERROR_CODES {
+        let name = examples::error_example_name(code);
+        let value = examples::error_example(code);
+        assert_eq!(
+            documented[&name]["value"], value,
+            "spec/openapi.json is out of date for the {name} example"
+        );
+        check(&spec, &error_schema, &value, &name, &mut problems);
+    }
+    assert!(problems.is_empty(), "{problems:#?}");
+}
+
+/// 文書化された全操作のリクエスト・レスポンスに例があり、エラー例はステータスと一致する
+#[test]
+fn every_documented_body_references_examples() {
+    let spec = spec();
+    for (path, operations) in spec["paths"].as_object().unwrap() {
+        for (method, operation) in operations.as_object().unwrap() {
+            let responses = operation["responses"].as_object().into_iter().flatten();
+            let bodies = std::iter::once((None, &operation["requestBody"]))
+                .chain(responses.map(|(status, body)| (Some(status.as_str()), body)));
+            for (status, body) in bodies {
+                let media = &body["content"]["application/json"];
+                if media["schema"].is_null() {
+                    continue;
+                }
+                let at = format!(
+                    "{} {path} {}",
+                    method.to_uppercase(),
+                    status.unwrap_or("request")
+                );
+                let referenced = media["examples"]
+                    .as_object()
+                    .unwrap_or_else(|| panic!("{at} has no examples"));
+                assert!(!referenced.is_empty(), "{at} has no examples");
+                for (name, example) in referenced {
+                    let target = example["$ref"].as_str().unwrap();
+                    let target = target.trim_start_matches("#/components/examples/");
+                    let value = &spec["components"]["examples"][target]["value"];
+                    assert!(!value.is_null(), "{at} references missing example {target}");
+                    if let Some(code) = value.get("code").and_then(Value::as_str)
+                        && name.starts_with("error_")
+                    {
+                        let entry = ERROR_CODES.iter().find(|entry| entry.code == code).unwrap();
+                        assert_eq!(
+                            Some(entry.status.as_str()),
+                            status,
+                            "{at} shows the {code} error"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// エラーカタログを取得でき、実際のエラー応答もカタログのコードを返す
+#[tokio::test]
+async fn error_catalog_is_served_and_errors_carry_its_codes() {
+    let app = support::make_test_router().await;
+
+    let req = Request::builder()
+        .uri("/api/v1/errors")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let (_headers, json) = to_json_async!(resp).await;
+    let served = json["errors"].as_array().unwrap();
+    assert_eq!(served.len(), ERROR_CODES.len());
+    assert!(served.iter().any(|entry| {
+        entry["code"] == "slug_taken" && entry["status"] == 409 && entry["description"].is_string()
+    }));
+
+    let req = Request::builder()
+        .uri("/api/v1/auth/me")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let (_headers, json) = to_json_async!(resp).await;
+    assert_eq!(json["code"], "unauthorized");
+}
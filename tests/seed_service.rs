@@ -0,0 +1,330 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/seed_service.rs
+use std::sync::{Arc, Mutex};
+
+use mokkan_core::application::AppError;
+use mokkan_core::application::services::{SeedService, SeedSummary};
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::errors::{DomainError, DomainResult};
+use mokkan_core::domain::{
+    Article, ArticleId, ArticleListCursor, ArticleReadRepository, ArticleRevision,
+    ArticleRevisionRepository, ArticleSlug, ArticleWriteRepository, NewArticle, NewUser, Role,
+    User, UserId, UserListCursor, UserRepository, UserUpdate, Username,
+};
+use mokkan_core::infrastructure::seed;
+
+mod support;
+
+const SEED: &str = "
+users:
+  - username: admin
+    role: admin
+    email: Admin@Example.com
+    password: Str0ng!Password
+  - username: writer
+custom_fields:
+  - name: subtitle
+    type: text
+pages:
+  - slug: about
+    title: About
+    body: Who we are.
+    author: admin
+    published: true
+";
+
+#[derive(Default)]
+struct Users(Mutex<Vec<User>>);
+
+impl UserRepository for Users {
+    fn count(&self) -> BoxFuture<'_, DomainResult<u64>> {
+        boxed(async move { Ok(self.0.lock().unwrap().len() as u64) })
+    }
+
+    fn insert(&self, new_user: NewUser) -> BoxFuture<'_, DomainResult<User>> {
+        boxed(async move {
+            let mut users = self.0.lock().unwrap();
+            let user = User {
+                id: UserId::new(i64::try_from(users.len()).unwrap() + 1)?,
+                username: new_user.username,
+                password_hash: new_user.password_hash,
+                role: new_user.role,
+                is_active: new_user.is_active,
+                created_at: new_user.created_at,
+                timezone: None,
+                password_reset_required: new_user.password_reset_required,
+                email: None,
+                email_verified: false,
+            };
+            users.push(user.clone());
+            drop(users);
+            Ok(user)
+        })
+    }
+
+    fn insert_bootstrap_admin(
+        &self,
+        _new_user: NewUser,
+    ) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        boxed(async { Ok(None) })
+    }
+
+    fn find_by_username<'a>(
+        &'a self,
+        username: &'a Username,
+    ) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        boxed(async move {
+            let users = self.0.lock().unwrap();
+            Ok(users
+                .iter()
+                .find(|user| user.username.eq_ignore_case(username.as_str()))
+                .cloned())
+        })
+    }
+
+    fn find_by_id(&self, id: UserId) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        boxed(async move {
+            let users = self.0.lock().unwrap();
+            Ok(users.iter().find(|user| user.id == id).cloned())
+        })
+    }
+
+    fn update(&self, update: UserUpdate) -> BoxFuture<'_, DomainResult<User>> {
+        boxed(async move {
+            let mut users = self.0.lock().unwrap();
+            let user = users
+                .iter_mut()
+                .find(|user| user.id == update.id)
+                .ok_or_else(|| DomainError::NotFound("user not found".into()))?;
+            if let Some(role) = update.role {
+                user.role = role;
+            }
+            if let Some(email) = update.email {
+                user.email = email;
+            }
+            if let Some(verified) = update.email_verified {
+                user.email_verified = verified;
+            }
+            let user = user.clone();
+            drop(users);
+            Ok(user)
+        })
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        _limit: u32,
+        _cursor: Option<UserListCursor>,
+        _search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, Option<UserListCursor>)>> {
+        boxed(async { Ok((vec![], None)) })
+    }
+}
+
+#[derive(Default)]
+struct Pages {
+    articles: Mutex<Vec<Article>>,
+    revisions: Mutex<Vec<(ArticleId, Option<UserId>)>>,
+}
+
+impl ArticleWriteRepository for Pages {
+    fn insert(&self, article: NewArticle) -> BoxFuture<'_, DomainResult<Article>> {
+        boxed(async move {
+            let mut articles = self.articles.lock().unwrap();
+            let created = Article {
+                id: ArticleId::new(i64::try_from(articles.len()).unwrap() + 1)?,
+                title: article.title,
+                slug: article.slug,
+                body: article.body,
+                tags: article.tags,
+                custom_fields: article.custom_fields,
+                published: article.published,
+                published_at: article.published_at,
+                visibility: article.visibility,
+                author_id: article.author_id,
+                created_at: article.created_at,
+                updated_at: article.updated_at,
+                view_count: 0,
+            };
+            articles.push(created.clone());
+            drop(articles);
+            Ok(created)
+        })
+    }
+
+    fn update(
+        &self,
+        _update: mokkan_core::domain::ArticleUpdate,
+    ) -> BoxFuture<'_, DomainResult<Article>> {
+        boxed(async { Err(DomainError::Validation("not supported".into())) })
+    }
+
+    fn delete(&self, _id: ArticleId) -> BoxFuture<'_, DomainResult<()>> {
+        boxed(async { Ok(()) })
+    }
+}
+
+impl ArticleReadRepository for Pages {
+    fn find_by_id(&self, id: ArticleId) -> BoxFuture<'_, DomainResult<Option<Article>>> {
+        boxed(async move {
+            let articles = self.articles.lock().unwrap();
+            Ok(articles.iter().find(|article| article.id == id).cloned())
+        })
+    }
+
+    fn find_by_slug<'a>(
+        &'a self,
+        slug: &'a ArticleSlug,
+    ) -> BoxFuture<'a, DomainResult<Option<Article>>> {
+        boxed(async move {
+            let articles = self.articles.lock().unwrap();
+            Ok(articles
+                .iter()
+                .find(|article| article.slug.as_str() == slug.as_str())
+                .cloned())
+        })
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        _include_drafts: bool,
+        _limit: u32,
+        _cursor: Option<ArticleListCursor>,
+        _search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<Article>, Option<ArticleListCursor>)>> {
+        boxed(async { Ok((vec![], None)) })
+    }
+}
+
+impl ArticleRevisionRepository for Pages {
+    fn append<'a>(
+        &'a self,
+        article: &'a Article,
+        edited_by: Option<UserId>,
+    ) -> BoxFuture<'a, DomainResult<()>> {
+        boxed(async move {
+            self.revisions.lock().unwrap().push((article.id, edited_by));
+            Ok(())
+        })
+    }
+
+    fn list_by_article(
+        &self,
+        _article_id: ArticleId,
+    ) -> BoxFuture<'_, DomainResult<Vec<ArticleRevision>>> {
+        boxed(async { Ok(vec![]) })
+    }
+}
+
+fn service(
+    users: &Arc<Users>,
+    pages: &Arc<Pages>,
+    audit: &support::CapturingAuditRepo,
+) -> SeedService {
+    SeedService::new(
+        Arc::clone(users) as Arc<dyn UserRepository>,
+        Arc::new(support::DummyPasswordHasher),
+        Arc::new(audit.clone()),
+        Arc::new(support::DummyClock),
+    )
+    .with_pages(
+        Arc::clone(pages) as Arc<dyn ArticleWriteRepository>,
+        Arc::clone(pages) as Arc<dyn ArticleReadRepository>,
+        Arc::clone(pages) as Arc<dyn ArticleRevisionRepository>,
+    )
+    .with_custom_fields(Arc::new(support::MemoryCustomFields::default()))
+}
+
+/// シードは不足分だけを作成し、再適用しても何も変わらない
+#[tokio::test]
+async fn seeding_twice_changes_nothing_the_second_time() {
+    let (users, pages) = (Arc::new(Users::default()), Arc::new(Pages::default()));
+    let audit = support::CapturingAuditRepo::new();
+    let svc = service(&users, &pages, &audit);
+    let document = seed::parse(SEED).unwrap();
+
+    let first = svc.apply(&document).await.unwrap();
+    assert_eq!(
+        first,
+        SeedSummary {
+            users_created: 2,
+            users_updated: 0,
+            custom_fields_saved: 1,
+            pages_created: 1,
+        }
+    );
+    let seeded = users.0.lock().unwrap().clone();
+    assert_eq!(seeded[0].role, Role::Admin);
+    assert_eq!(
+        seeded[0].email.as_ref().unwrap().as_str(),
+        "admin@example.com"
+    );
+    assert!(seeded[0].email_verified);
+    assert!(!seeded[0].password_reset_required);
+    assert_eq!(seeded[1].role, Role::Author);
+    assert!(seeded[1].password_reset_required, "no password was given");
+    let page = pages.articles.lock().unwrap()[0].clone();
+    assert_eq!(page.author_id, seeded[0].id);
+    assert!(page.published);
+    assert_eq!(
+        pages.revisions.lock().unwrap().clone(),
+        [(page.id, Some(seeded[0].id))]
+    );
+
+    let second = svc.apply(&document).await.unwrap();
+    assert_eq!(second, SeedSummary::default());
+    assert_eq!(users.0.lock().unwrap().len(), 2);
+    assert_eq!(pages.articles.lock().unwrap().len(), 1);
+    let actions: Vec<_> = audit.get_inserted().into_iter().map(|e| e.action).collect();
+    assert_eq!(actions, ["seed.apply"]);
+}
+
+/// 既存ユーザーはロールのみ宣言に合わせ、既存ページは上書きしない
+#[tokio::test]
+async fn seeding_aligns_roles_and_keeps_existing_pages() {
+    let (users, pages) = (Arc::new(Users::default()), Arc::new(Pages::default()));
+    let audit = support::CapturingAuditRepo::new();
+    let svc = service(&users, &pages, &audit);
+    svc.apply(&seed::parse(SEED).unwrap()).await.unwrap();
+
+    let changed = seed::parse(
+        "
+users:
+  - username: WRITER
+    role: admin
+pages:
+  - slug: about
+    title: Rewritten
+    body: Replaced.
+    author: writer
+",
+    )
+    .unwrap();
+    let summary = svc.apply(&changed).await.unwrap();
+    assert_eq!(summary.users_updated, 1);
+    assert_eq!(summary.pages_created, 0);
+    assert_eq!(users.0.lock().unwrap()[1].role, Role::Admin);
+    assert_eq!(pages.articles.lock().unwrap()[0].title.as_str(), "About");
+}
+
+/// 存在しない著者や弱いパスワードは検証エラーになる
+#[tokio::test]
+async fn invalid_seeds_are_rejected() {
+    let (users, pages) = (Arc::new(Users::default()), Arc::new(Pages::default()));
+    let svc = service(&users, &pages, &support::CapturingAuditRepo::new());
+
+    let orphan = seed::parse(
+        "pages:\n  - slug: about\n    title: About\n    body: Hi.\n    author: ghost\n",
+    )
+    .unwrap();
+    let result = svc.apply(&orphan).await;
+    assert!(matches!(result, Err(AppError::Validation(msg)) if msg.contains("ghost")));
+
+    let weak = seed::parse("users:\n  - username: admin\n    password: short\n").unwrap();
+    assert!(matches!(
+        svc.apply(&weak).await,
+        Err(AppError::Validation(_))
+    ));
+    assert!(users.0.lock().unwrap().is_empty());
+}
@@ -0,0 +1,270 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/sqlite_backend.rs
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use mokkan_core::application::commands::articles::{
+    ArticleCommandService, CreateArticleCommand, UpdateArticleCommand,
+};
+use mokkan_core::application::ports::unit_of_work::UnitOfWork;
+use mokkan_core::application::queries::articles::{ArticleQueryService, SearchArticlesQuery};
+use mokkan_core::application::{AuthenticatedUser, PageDirection};
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::{
+    ArticleBody, ArticleId, ArticleReadRepository, ArticleRevisionRepository, ArticleSlug,
+    ArticleTitle, ArticleVisibility, CustomFields, NewArticle, NewUser, PasswordHash, Role,
+    UserRepository, Username,
+};
+use mokkan_core::infrastructure::database::{init_sqlite_pool, run_sqlite_migrations};
+use mokkan_core::infrastructure::repositories::SqliteStores;
+use mokkan_core::infrastructure::time::SimulatedClock;
+
+mod support;
+
+struct Harness {
+    admin: AuthenticatedUser,
+    commands: ArticleCommandService,
+    queries: ArticleQueryService,
+    stores: SqliteStores,
+}
+
+impl Harness {
+    async fn new() -> Self {
+        let pool = init_sqlite_pool("sqlite::memory:").await.unwrap();
+        run_sqlite_migrations(&pool).await.unwrap();
+        let stores = SqliteStores::new(&pool);
+
+        let user = stores
+            .users
+            .insert(
+                NewUser::new(
+                    Username::new("admin").unwrap(),
+                    PasswordHash::new("$argon2id$v=19$m=19456,t=2,p=1$c2FsdA$aGFzaA").unwrap(),
+                    Role::Admin,
+                    Utc::now(),
+                )
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        let admin = AuthenticatedUser {
+            id: user.id,
+            username: "admin".into(),
+            role: Role::Admin,
+            capabilities: Role::Admin.default_capabilities(),
+            issued_at: Utc::now(),
+            expires_at: Utc::now() + Duration::hours(1),
+            session_id: None,
+            token_version: None,
+            custom_role: None,
+            impersonator: None,
+        };
+
+        let slugs = Arc::new(ArticleSlugService::new(
+            stores.articles.clone(),
+            Arc::new(support::DummySlug),
+        ));
+        let commands = ArticleCommandService::new(
+            stores.articles.clone(),
+            stores.articles.clone(),
+            stores.articles.clone(),
+            slugs,
+            Arc::new(SimulatedClock::new()),
+        )
+        .with_unit_of_work(stores.unit_of_work.clone());
+        let queries = ArticleQueryService::new(stores.articles.clone(), stores.articles.clone());
+        Self {
+            admin,
+            commands,
+            queries,
+            stores,
+        }
+    }
+
+    async fn create(&self, title: &str, body: &str, tags: &[&str], publish: bool) -> i64 {
+        let command = CreateArticleCommand::builder()
+            .title(title)
+            .body(body)
+            .tags(tags.iter().copied())
+            .publish(publish)
+            .build()
+            .unwrap();
+        self.commands
+            .create_article(&self.admin, command)
+            .await
+            .unwrap()
+            .id
+    }
+
+    fn search(query: &str) -> SearchArticlesQuery {
+        SearchArticlesQuery {
+            query: query.into(),
+            include_drafts: true,
+            limit: 10,
+            cursor: None,
+            direction: PageDirection::Forward,
+            tag: None,
+        }
+    }
+}
+
+/// `SQLite` バックエンドでもフレーズ・除外検索と抜粋の強調ができる
+#[tokio::test]
+async fn search_matches_phrases_and_highlights_headlines() {
+    let harness = Harness::new().await;
+    harness
+        .create("Lunch", "Fish and chips with salt", &[], true)
+        .await;
+    harness
+        .create("Dinner", "Fish and chips with vinegar", &[], true)
+        .await;
+    harness
+        .create("Breakfast", "Chips, then fish", &[], true)
+        .await;
+
+    let page = harness
+        .queries
+        .search_results(
+            Some(&harness.admin),
+            Harness::search(r#""fish and chips" -vinegar"#),
+        )
+        .await
+        .unwrap();
+    let titles: Vec<_> = page
+        .items
+        .iter()
+        .map(|result| result.article.title.as_str())
+        .collect();
+    assert_eq!(titles, vec!["Lunch"]);
+    assert_eq!(
+        page.items[0].headline.as_deref(),
+        Some("<mark>Fish</mark> <mark>and</mark> <mark>chips</mark> with salt")
+    );
+}
+
+/// ファセットは公開状態・著者・タグごとの件数を数え、更新したタグも反映される
+#[tokio::test]
+async fn facets_count_matches_and_follow_updates() {
+    let harness = Harness::new().await;
+    harness
+        .create("Lunch", "Fish and chips", &["fish"], true)
+        .await;
+    harness.create("Toast", "Bread", &["bread"], true).await;
+    let supper = harness
+        .create("Supper", "Fish soup", &["soup"], false)
+        .await;
+
+    harness
+        .commands
+        .update_article(
+            &harness.admin,
+            UpdateArticleCommand {
+                id: supper,
+                title: None,
+                body: None,
+                publish: None,
+                tags: Some(vec!["soup".into(), "fish".into()]),
+                custom_fields: None,
+                visibility: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let (page, facets) = harness
+        .queries
+        .faceted_search(Some(&harness.admin), Harness::search("fish"))
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.total_estimate, Some(2));
+
+    let facets = facets.unwrap();
+    assert_eq!((facets.published, facets.drafts), (1, 1));
+    assert_eq!(
+        (facets.authors[0].author_id, facets.authors[0].count),
+        (i64::from(harness.admin.id), 2)
+    );
+    let tags: Vec<_> = facets
+        .tags
+        .iter()
+        .map(|facet| (facet.tag.as_str(), facet.count))
+        .collect();
+    assert_eq!(tags, vec![("fish", 2), ("soup", 1)]);
+}
+
+/// 作成と更新は記事とリビジョンを同じトランザクションで書き込む
+#[tokio::test]
+async fn unit_of_work_records_a_revision_for_each_write() {
+    let harness = Harness::new().await;
+    let id = harness.create("Lunch", "Fish", &[], false).await;
+    harness
+        .commands
+        .update_article(
+            &harness.admin,
+            UpdateArticleCommand {
+                id,
+                title: None,
+                body: Some("Fish and chips".into()),
+                publish: None,
+                tags: None,
+                custom_fields: None,
+                visibility: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let revisions = harness
+        .stores
+        .articles
+        .list_by_article(ArticleId::new(id).unwrap())
+        .await
+        .unwrap();
+    let bodies: Vec<_> = revisions
+        .iter()
+        .map(|revision| (revision.version, revision.body.as_str()))
+        .collect();
+    assert_eq!(bodies, vec![(2, "Fish and chips"), (1, "Fish")]);
+}
+
+/// コミットせずに破棄した作業の書き込みは残らない
+#[tokio::test]
+async fn dropped_work_leaves_nothing_behind() {
+    let harness = Harness::new().await;
+    let now = Utc::now();
+    let slug = ArticleSlug::new("abandoned").unwrap();
+
+    let mut work = harness.stores.unit_of_work.begin().await.unwrap();
+    let article = work
+        .insert_article(NewArticle {
+            title: ArticleTitle::new("Abandoned").unwrap(),
+            slug: slug.clone(),
+            body: ArticleBody::new("Never committed").unwrap(),
+            tags: Vec::new(),
+            custom_fields: CustomFields::default(),
+            published: false,
+            published_at: None,
+            visibility: ArticleVisibility::Public,
+            author_id: harness.admin.id,
+            created_at: now,
+            updated_at: now,
+        })
+        .await
+        .unwrap();
+    work.append_revision(&article, Some(harness.admin.id))
+        .await
+        .unwrap();
+    drop(work);
+
+    let found = harness.stores.articles.find_by_slug(&slug).await.unwrap();
+    assert!(found.is_none());
+    let revisions = harness
+        .stores
+        .articles
+        .list_by_article(article.id)
+        .await
+        .unwrap();
+    assert!(revisions.is_empty());
+}
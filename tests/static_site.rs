@@ -0,0 +1,153 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/static_site.rs
+use std::sync::Arc;
+
+use chrono::Utc;
+use mokkan_core::application::commands::articles::{ArticleCommandService, CreateArticleCommand};
+use mokkan_core::application::services::{INDEX_PAGE, StaticSiteService};
+use mokkan_core::application::{AppError, AuthenticatedUser};
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::audit::repository::AuditLogRepository;
+use mokkan_core::domain::{Role, UserId};
+use mokkan_core::infrastructure::repositories::InMemoryStores;
+use mokkan_core::infrastructure::time::SimulatedClock;
+
+mod support;
+
+struct Site {
+    stores: InMemoryStores,
+    commands: ArticleCommandService,
+    writer: Arc<support::MemorySiteWriter>,
+    exporter: StaticSiteService,
+}
+
+impl Site {
+    fn new() -> Self {
+        let stores = InMemoryStores::new();
+        let clock = Arc::new(SimulatedClock::new());
+        let slugs = Arc::new(ArticleSlugService::new(
+            stores.articles.clone(),
+            Arc::new(support::DummySlug),
+        ));
+        let commands = ArticleCommandService::new(
+            stores.articles.clone(),
+            stores.articles.clone(),
+            stores.articles.clone(),
+            slugs,
+            clock.clone(),
+        );
+        let writer = Arc::new(support::MemorySiteWriter::default());
+        let exporter = StaticSiteService::new(
+            stores.articles.clone(),
+            stores.audit_logs.clone(),
+            writer.clone(),
+            clock,
+        );
+        Self {
+            stores,
+            commands,
+            writer,
+            exporter,
+        }
+    }
+
+    async fn create(&self, title: &str, body: &str, publish: bool) -> i64 {
+        let command = CreateArticleCommand::builder()
+            .title(title)
+            .body(body)
+            .publish(publish)
+            .build()
+            .unwrap();
+        self.commands
+            .create_article(&actor(Role::Admin), command)
+            .await
+            .unwrap()
+            .id
+    }
+}
+
+fn actor(role: Role) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(1).unwrap(),
+        username: "admin".into(),
+        role,
+        capabilities: role.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + chrono::Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+/// 公開記事だけが Markdown を HTML にした個別ページになり、索引から新しい順に辿れる
+#[tokio::test]
+async fn published_articles_become_pages_linked_from_the_index() {
+    let site = Site::new();
+    site.create("first", "# Intro\n\n*hello*", true).await;
+    site.create("draft", "secret", false).await;
+    let odd = site
+        .create("Fish & Chips", "<script>x</script>", true)
+        .await;
+
+    let report = site.exporter.write_site().await.unwrap();
+
+    assert_eq!(report.articles, 2);
+    assert_eq!(report.files_written, 3);
+    assert_eq!(report.location, "memory");
+    let odd_page = format!("articles/article-{odd}.html");
+    assert_eq!(
+        site.writer.paths(),
+        vec![
+            odd_page.clone(),
+            "articles/first.html".into(),
+            INDEX_PAGE.into()
+        ]
+    );
+
+    let first = site.writer.page("articles/first.html").unwrap();
+    assert!(first.starts_with("<!DOCTYPE html>"), "{first}");
+    assert!(first.contains("<h1>first</h1>"), "{first}");
+    assert!(first.contains("<h2>Intro</h2>"), "{first}");
+    assert!(first.contains("<em>hello</em>"), "{first}");
+    assert!(first.contains("href=\"../index.html\""), "{first}");
+    let odd = site.writer.page(&odd_page).unwrap();
+    assert!(odd.contains("<title>Fish &amp; Chips</title>"), "{odd}");
+    assert!(!odd.contains("<script>"), "{odd}");
+
+    let index = site.writer.page(INDEX_PAGE).unwrap();
+    assert!(!index.contains("draft"), "{index}");
+    let newest = index.find(&odd_page).unwrap();
+    assert!(
+        newest < index.find("articles/first.html").unwrap(),
+        "{index}"
+    );
+}
+
+/// 管理者の書き出しは監査され、保守権限のない利用者は拒否される
+#[tokio::test]
+async fn exports_require_maintenance_and_are_audited() {
+    let site = Site::new();
+    site.create("news", "body", true).await;
+
+    let err = site
+        .exporter
+        .export(&actor(Role::Author))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)), "{err:?}");
+    assert!(site.writer.paths().is_empty());
+
+    let report = site.exporter.export(&actor(Role::Admin)).await.unwrap();
+    assert_eq!(report.articles, 1);
+    let (entries, _) = site
+        .stores
+        .audit_logs
+        .find_by_action("content.static_export", 10, None)
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].details.as_ref().unwrap()["articles"], 1);
+}
@@ -1,7 +1,18 @@
 // tests/support/builders.rs
-use chrono::Utc;
+use std::sync::Arc;
 
-use mokkan_core::domain::{Article, ArticleBody, ArticleId, ArticleSlug, ArticleTitle, UserId};
+use chrono::{Duration, Utc};
+
+use mokkan_core::application::AuthenticatedUser;
+use mokkan_core::application::commands::users::UserCommandService;
+use mokkan_core::application::ports::security::TokenManager;
+use mokkan_core::application::ports::session_revocation::Store;
+use mokkan_core::application::ports::time::Clock;
+use mokkan_core::domain::{
+    Article, ArticleBody, ArticleId, ArticleSlug, ArticleTitle, ArticleVisibility, Email,
+    PasswordHash, Role, User, UserId, UserRepository, Username,
+};
+use mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec;
 
 #[must_use]
 pub struct ArticleBuilder {
@@ -35,6 +46,11 @@ impl ArticleBuilder {
         self
     }
 
+    pub const fn author(mut self, author_id: i64) -> Self {
+        self.author_id = author_id;
+        self
+    }
+
     pub fn title(mut self, title: impl Into<String>) -> Self {
         self.title = title.into();
         self
@@ -47,15 +63,68 @@ impl ArticleBuilder {
             title: ArticleTitle::new(self.title).unwrap(),
             slug: ArticleSlug::new(self.slug).unwrap(),
             body: ArticleBody::new(self.body).unwrap(),
+            tags: Vec::new(),
+            custom_fields: serde_json::Map::new(),
             published: self.published,
             published_at: if self.published {
                 Some(Utc::now())
             } else {
                 None
             },
+            visibility: ArticleVisibility::Public,
             author_id: UserId::new(self.author_id).unwrap(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            view_count: 0,
         }
     }
 }
+
+/// `name@example.com` を確認済みの有効なユーザー `name` を作成（パスワードは `password`）
+pub fn member(id: i64, name: &str, role: Role, password: &str) -> User {
+    User {
+        id: UserId::new(id).unwrap(),
+        username: Username::new(name).unwrap(),
+        password_hash: PasswordHash::new(format!("hash::{password}")).unwrap(),
+        role,
+        is_active: true,
+        created_at: Utc::now(),
+        timezone: None,
+        password_reset_required: false,
+        email: Some(Email::new(&format!("{name}@example.com")).unwrap()),
+        email_verified: true,
+    }
+}
+
+/// `user` がロールの既定権限でログインした状態を作成
+pub fn actor_for(user: &User) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: user.id,
+        username: user.username.to_string(),
+        role: user.role,
+        capabilities: user.role.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+/// [`member`] のパスワードを検証する `UserCommandService` を構築
+pub fn user_command_service(
+    users: Arc<dyn UserRepository>,
+    tokens: Arc<dyn TokenManager>,
+    sessions: Arc<dyn Store>,
+    clock: Arc<dyn Clock>,
+) -> UserCommandService {
+    UserCommandService::new(
+        users,
+        Arc::new(super::StrictPasswordHasher),
+        tokens,
+        Arc::new(HmacRefreshTokenCodec::new("test-refresh-secret").expect("refresh token codec")),
+        sessions,
+        clock,
+    )
+}
@@ -1,5 +1,8 @@
 // tests/support/helpers.rs
 use super::mocks;
+use mokkan_core::infrastructure::repositories::memory;
+use mokkan_core::infrastructure::security::breach::OfflineBreachChecker;
+use mokkan_core::infrastructure::security::user_tokens::InMemoryUserTokenStore;
 use std::{
     future::{Ready, ready},
     sync::Arc,
@@ -124,13 +127,28 @@ fn make_services(audit_repo: Arc<AuditRepo>) -> Arc<mokkan_core::application::se
         article_write_repo: article_write,
         article_read_repo: article_read,
         article_revision_repo: article_rev,
+        custom_field_repo: Arc::new(mocks::MemoryCustomFields::default()),
+        contributor_repo: Arc::new(memory::InMemoryContributorRepository::new()),
+        moderation_repo: Arc::new(mocks::MemoryModeration::default()),
+        media_repo: Arc::new(memory::InMemoryMediaRepository::new()),
+        block_list: Arc::new(mocks::MemoryBlockList::default()),
+        password_history: Arc::new(memory::InMemoryPasswordHistory::new()),
+        account_deletions: Arc::new(memory::InMemoryAccountDeletions::new()),
+        role_store: Arc::new(memory::InMemoryRoleStore::new()),
+        access_rule_repo: Arc::new(mocks::MemoryAccessRules::default()),
         audit_log_repo: audit_repo,
+        user_tokens: Arc::new(InMemoryUserTokenStore::new()),
+        syndication_opt_outs: Arc::new(memory::InMemorySyndicationOptOutStore::new()),
+        integrity_checker: None,
+        unit_of_work: None,
+        webhook_repo: Arc::new(memory::InMemoryWebhookRepository::new()),
     };
 
     Arc::new(mokkan_core::application::services::Registry::new(
         deps,
         mokkan_core::application::services::RuntimeDependencies {
             password_hasher,
+            password_breach_checker: Arc::new(OfflineBreachChecker),
             token_manager,
             refresh_token_codec: Arc::new(
                 mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec::new(
@@ -141,11 +159,57 @@ fn make_services(audit_repo: Arc<AuditRepo>) -> Arc<mokkan_core::application::se
             session_revocation_store: Arc::new(
                 mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(),
             ),
+            session_backend: mokkan_core::application::ports::session_revocation::Backend::InMemory,
             authorization_code_store: Arc::new(
                 mokkan_core::infrastructure::security::authorization_code_store::InMemoryStore::new(),
             ),
             clock,
+            clock_control: None,
             slugger,
+            security_webhook: None,
+            job_queue: std::sync::Arc::new(mokkan_core::infrastructure::jobs::TokioJobQueue),
+            external_authenticator: None,
+            group_roles: mokkan_core::application::ports::external_auth::GroupRoleMapping::default(),
+            oidc_providers: Vec::new(),
+            command_journal: None,
+            job_control: None,
+            document_converter: std::sync::Arc::new(mokkan_core::infrastructure::documents::DocxConverter),
+            #[cfg(feature = "article-export")]
+            article_renderer: Some(std::sync::Arc::new(
+                mokkan_core::infrastructure::exports::BuiltinArticleRenderer,
+            )),
+            #[cfg(not(feature = "article-export"))]
+            article_renderer: None,
+            archiver: std::sync::Arc::new(mokkan_core::infrastructure::zip::ZipArchiver),
+            site_writer: std::sync::Arc::new(mocks::MemorySiteWriter::default()),
+            download_links: std::sync::Arc::new(
+                mokkan_core::infrastructure::security::download_links::HmacDownloadLinkSigner::new(
+                    "test-secret",
+                    std::time::Duration::from_mins(15),
+                )
+                .unwrap(),
+            ),
+            asn_lookup: None,
+            email_sender: Arc::new(mokkan_core::infrastructure::notification::LogEmailSender),
+            read_only: mokkan_core::application::ReadOnlySwitch::default(),
+            rate_limiter: Arc::new(
+                mokkan_core::infrastructure::security::rate_limiter::InMemoryRateLimiter::new(),
+            ),
+            login_throttle:
+                mokkan_core::application::ports::rate_limit::LoginThrottlePolicy::default(),
+            session_policy:
+                mokkan_core::application::ports::session_revocation::SessionPolicy::default(),
+            password_history_depth: 0,
+            account_deletion_grace: chrono::Duration::days(30),
+            slug_cache: mokkan_core::application::services::SlugCachePolicy::default(),
+            syndication_targets: Vec::new(),
+            syndication: mokkan_core::application::services::SyndicationPolicy::default(),
+            webhook_sender: None,
+            webhooks: mokkan_core::application::services::WebhookPolicy::default(),
+            pagination: mokkan_core::application::PaginationPolicy::default(),
+            blob_storage: Arc::new(mocks::MemoryBlobStorage::default()),
+            media: mokkan_core::application::services::MediaPolicy::default(),
+            view_counter: Arc::new(mokkan_core::infrastructure::views::InMemoryViewCounter::new()),
         },
     ))
 }
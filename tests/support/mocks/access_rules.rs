@@ -0,0 +1,61 @@
+// tests/support/mocks/access_rules.rs
+//! メモリ上のアクセスルールリポジトリ
+use chrono::{DateTime, Utc};
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::errors::DomainResult;
+use mokkan_core::domain::{AccessRule, AccessRuleRepository, NewAccessRule};
+use std::sync::Mutex;
+
+/// 追加順に ID を採番するメモリ上のアクセスルール一覧
+#[derive(Default)]
+pub struct MemoryAccessRules(Mutex<Vec<AccessRule>>);
+
+impl AccessRuleRepository for MemoryAccessRules {
+    fn list(&self) -> BoxFuture<'_, DomainResult<Vec<AccessRule>>> {
+        let mut rules = self.0.lock().unwrap().clone();
+        rules.sort_by_key(|rule| (rule.priority, rule.id));
+        boxed(async move { Ok(rules) })
+    }
+
+    fn insert(&self, rule: NewAccessRule) -> BoxFuture<'_, DomainResult<AccessRule>> {
+        let stored = {
+            let mut rules = self.0.lock().unwrap();
+            let id = rules.iter().map(|rule| rule.id).max().unwrap_or(0) + 1;
+            let stored = AccessRule {
+                id,
+                group: rule.group,
+                action: rule.action,
+                target: rule.target,
+                priority: rule.priority,
+                note: rule.note,
+                created_by: rule.created_by,
+                created_at: rule.created_at,
+                expires_at: rule.expires_at,
+            };
+            rules.push(stored.clone());
+            drop(rules);
+            stored
+        };
+        boxed(async move { Ok(stored) })
+    }
+
+    fn delete(&self, id: i64) -> BoxFuture<'_, DomainResult<bool>> {
+        let removed = {
+            let mut rules = self.0.lock().unwrap();
+            let before = rules.len();
+            rules.retain(|rule| rule.id != id);
+            before != rules.len()
+        };
+        boxed(async move { Ok(removed) })
+    }
+
+    fn delete_expired(&self, now: DateTime<Utc>) -> BoxFuture<'_, DomainResult<u64>> {
+        let removed = {
+            let mut rules = self.0.lock().unwrap();
+            let before = rules.len();
+            rules.retain(|rule| !rule.is_expired(now));
+            (before - rules.len()) as u64
+        };
+        boxed(async move { Ok(removed) })
+    }
+}
@@ -88,6 +88,19 @@ impl mokkan_core::domain::ArticleReadRepository for DummyArticleRead {
     > {
         boxed(async move { Ok((vec![], None)) })
     }
+
+    fn list(
+        &self,
+        _query: mokkan_core::domain::article::repository::ArticleQuery,
+    ) -> BoxFuture<
+        '_,
+        mokkan_core::domain::errors::DomainResult<(
+            Vec<mokkan_core::domain::article::entity::Article>,
+            Option<mokkan_core::domain::article::value_objects::ArticleListCursor>,
+        )>,
+    > {
+        boxed(async move { Ok((vec![], None)) })
+    }
 }
 
 /* -------------------------------- ArticleRevisionRepository -------------------------------- */
@@ -114,3 +127,50 @@ impl mokkan_core::domain::ArticleRevisionRepository for DummyArticleRevision {
         boxed(async move { Ok(vec![]) })
     }
 }
+
+/* -------------------------------- CustomFieldRepository -------------------------------- */
+
+/// メモリ上のカスタムフィールド定義リポジトリ
+#[derive(Default)]
+pub struct MemoryCustomFields(std::sync::Mutex<Vec<mokkan_core::domain::CustomFieldDefinition>>);
+
+impl mokkan_core::domain::CustomFieldRepository for MemoryCustomFields {
+    fn list(
+        &self,
+    ) -> BoxFuture<
+        '_,
+        mokkan_core::domain::errors::DomainResult<Vec<mokkan_core::domain::CustomFieldDefinition>>,
+    > {
+        let mut definitions = self.0.lock().unwrap().clone();
+        definitions.sort_by(|a, b| a.name.cmp(&b.name));
+        boxed(async move { Ok(definitions) })
+    }
+
+    fn upsert(
+        &self,
+        definition: mokkan_core::domain::CustomFieldDefinition,
+    ) -> BoxFuture<
+        '_,
+        mokkan_core::domain::errors::DomainResult<mokkan_core::domain::CustomFieldDefinition>,
+    > {
+        {
+            let mut definitions = self.0.lock().unwrap();
+            definitions.retain(|existing| existing.name != definition.name);
+            definitions.push(definition.clone());
+        }
+        boxed(async move { Ok(definition) })
+    }
+
+    fn delete<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> BoxFuture<'a, mokkan_core::domain::errors::DomainResult<bool>> {
+        let removed = {
+            let mut definitions = self.0.lock().unwrap();
+            let before = definitions.len();
+            definitions.retain(|existing| existing.name != name);
+            definitions.len() < before
+        };
+        boxed(async move { Ok(removed) })
+    }
+}
@@ -6,6 +6,7 @@ pub fn sample(created_at: DateTime<Utc>) -> mokkan_core::domain::audit::entity::
     mokkan_core::domain::audit::entity::AuditLog {
         id: 1,
         user_id: Some(mokkan_core::domain::user::value_objects::UserId::new(1).unwrap()),
+        impersonator_id: None,
         action: "test".into(),
         resource_type: "article".into(),
         resource_id: Some(100),
@@ -25,6 +26,7 @@ pub fn sample_audit_with(
     mokkan_core::domain::audit::entity::AuditLog {
         id,
         user_id: Some(mokkan_core::domain::user::value_objects::UserId::new(1).unwrap()),
+        impersonator_id: None,
         action: "test".into(),
         resource_type: "article".into(),
         resource_id: Some(resource_id),
@@ -0,0 +1,67 @@
+// tests/support/mocks/blocks.rs
+//! メモリ上のブロックリスト
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::errors::DomainResult;
+use mokkan_core::domain::{BlockList, UserBlock, UserId};
+use std::sync::Mutex;
+
+/// 追加順にブロックを保持するメモリ上のブロックリスト
+#[derive(Default)]
+pub struct MemoryBlockList(Mutex<Vec<UserBlock>>);
+
+impl BlockList for MemoryBlockList {
+    fn add(&self, block: UserBlock) -> BoxFuture<'_, DomainResult<UserBlock>> {
+        let stored = {
+            let mut blocks = self.0.lock().unwrap();
+            let existing = blocks
+                .iter()
+                .find(|b| b.owner == block.owner && b.target == block.target)
+                .cloned();
+            let stored = existing.unwrap_or_else(|| {
+                blocks.push(block.clone());
+                block
+            });
+            drop(blocks);
+            stored
+        };
+        boxed(async move { Ok(stored) })
+    }
+
+    fn remove(&self, owner: UserId, target: UserId) -> BoxFuture<'_, DomainResult<bool>> {
+        let removed = {
+            let mut blocks = self.0.lock().unwrap();
+            let before = blocks.len();
+            blocks.retain(|b| !(b.owner == owner && b.target == target));
+            before != blocks.len()
+        };
+        boxed(async move { Ok(removed) })
+    }
+
+    fn list(&self, owner: UserId) -> BoxFuture<'_, DomainResult<Vec<UserBlock>>> {
+        let mut found: Vec<_> = self
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|b| b.owner == owner)
+            .cloned()
+            .collect();
+        found.reverse();
+        boxed(async move { Ok(found) })
+    }
+
+    fn find(
+        &self,
+        owner: UserId,
+        target: UserId,
+    ) -> BoxFuture<'_, DomainResult<Option<UserBlock>>> {
+        let found = self
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|b| b.owner == owner && b.target == target)
+            .cloned();
+        boxed(async move { Ok(found) })
+    }
+}
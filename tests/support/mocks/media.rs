@@ -0,0 +1,71 @@
+// tests/support/mocks/media.rs
+//! メモリ上のブロブストレージと静的サイトの書き出し先
+use bytes::Bytes;
+use mokkan_core::application::AppResult;
+use mokkan_core::application::ports::blob_storage::{BlobRead, BlobStorage};
+use mokkan_core::application::ports::static_site::SiteWriter;
+use mokkan_core::async_support::{BoxFuture, boxed};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// キーごとにバイト列を保持し、そのまま返すストレージ
+#[derive(Default)]
+pub struct MemoryBlobStorage(Mutex<HashMap<String, Bytes>>);
+
+impl MemoryBlobStorage {
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+}
+
+impl BlobStorage for MemoryBlobStorage {
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        _content_type: &'a str,
+        bytes: Bytes,
+    ) -> BoxFuture<'a, AppResult<()>> {
+        self.0.lock().unwrap().insert(key.to_string(), bytes);
+        boxed(async move { Ok(()) })
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, AppResult<Option<BlobRead>>> {
+        let bytes = self.0.lock().unwrap().get(key).cloned();
+        boxed(async move { Ok(bytes.map(BlobRead::Bytes)) })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, AppResult<()>> {
+        self.0.lock().unwrap().remove(key);
+        boxed(async move { Ok(()) })
+    }
+}
+
+/// 書き出されたファイルをパスごとに保持する静的サイトの書き出し先
+#[derive(Default)]
+pub struct MemorySiteWriter(Mutex<HashMap<String, Vec<u8>>>);
+
+impl MemorySiteWriter {
+    pub fn page(&self, path: &str) -> Option<String> {
+        let files = self.0.lock().unwrap();
+        files
+            .get(path)
+            .map(|contents| String::from_utf8_lossy(contents).into_owned())
+    }
+
+    pub fn paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = self.0.lock().unwrap().keys().cloned().collect();
+        paths.sort();
+        paths
+    }
+}
+
+impl SiteWriter for MemorySiteWriter {
+    fn write<'a>(&'a self, path: &'a str, contents: Vec<u8>) -> BoxFuture<'a, AppResult<()>> {
+        self.0.lock().unwrap().insert(path.to_string(), contents);
+        boxed(async move { Ok(()) })
+    }
+
+    fn location(&self) -> String {
+        "memory".into()
+    }
+}
@@ -2,8 +2,12 @@
 //! テストサポートモック再エクスポートモジュール
 #![cfg(test)]
 
+pub mod access_rules;
 pub mod article_repos;
 pub mod audit;
+pub mod blocks;
+pub mod media;
+pub mod moderation;
 pub mod repos;
 pub mod security;
 pub mod time;
@@ -17,8 +21,8 @@ pub use time::fixed_now;
 
 // セキュリティ関連
 pub use security::{
-    DummyPasswordHasher, DummyTokenManager, EXPIRED_TOKEN, NO_AUDIT_TOKEN, SESSION_TOKEN,
-    StrictPasswordHasher, TEST_TOKEN,
+    AUTHOR_TOKEN, DummyPasswordHasher, DummyTokenManager, EXPIRED_TOKEN, NO_AUDIT_TOKEN,
+    SESSION_TOKEN, StrictPasswordHasher, TEST_TOKEN,
 };
 
 // 監査ログ関連
@@ -34,4 +38,18 @@ pub use util::{DummyClock, DummySlug};
 pub use user_repo::DummyRepo;
 
 // 記事リポジトリ
-pub use article_repos::{DummyArticleRead, DummyArticleRevision, DummyArticleWrite};
+pub use article_repos::{
+    DummyArticleRead, DummyArticleRevision, DummyArticleWrite, MemoryCustomFields,
+};
+
+// メディア
+pub use media::{MemoryBlobStorage, MemorySiteWriter};
+
+// モデレーション
+pub use moderation::MemoryModeration;
+
+// ブロックリスト
+pub use blocks::MemoryBlockList;
+
+// アクセスルール
+pub use access_rules::MemoryAccessRules;
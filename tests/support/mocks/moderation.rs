@@ -0,0 +1,87 @@
+// tests/support/mocks/moderation.rs
+//! メモリ上のモデレーションリポジトリ
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::errors::DomainResult;
+use mokkan_core::domain::{CaseStatus, ModerationCase, ModerationRepository, NewReport};
+use std::sync::Mutex;
+
+/// 記事ごとに未解決ケースを1件だけ持つ、メモリ上のモデレーションキュー
+#[derive(Default)]
+pub struct MemoryModeration(Mutex<Vec<ModerationCase>>);
+
+impl MemoryModeration {
+    pub fn cases(&self) -> Vec<ModerationCase> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl ModerationRepository for MemoryModeration {
+    fn report(&self, report: NewReport) -> BoxFuture<'_, DomainResult<ModerationCase>> {
+        let case = {
+            let mut cases = self.0.lock().unwrap();
+            let open = cases.iter().position(|case| {
+                case.article_id == report.article_id && case.status() == CaseStatus::Open
+            });
+            let index = open.unwrap_or_else(|| {
+                let id = i64::try_from(cases.len()).unwrap() + 1;
+                cases.push(ModerationCase {
+                    id,
+                    article_id: report.article_id,
+                    reports: Vec::new(),
+                    opened_at: report.report.reported_at,
+                    decision: None,
+                });
+                cases.len() - 1
+            });
+            cases[index].reports.push(report.report);
+            cases[index].clone()
+        };
+        boxed(async move { Ok(case) })
+    }
+
+    fn find_by_id(&self, id: i64) -> BoxFuture<'_, DomainResult<Option<ModerationCase>>> {
+        let case = self
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|case| case.id == id)
+            .cloned();
+        boxed(async move { Ok(case) })
+    }
+
+    fn list(
+        &self,
+        status: CaseStatus,
+        limit: u32,
+        after: Option<i64>,
+    ) -> BoxFuture<'_, DomainResult<Vec<ModerationCase>>> {
+        let cases = self
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|case| case.status() == status && after.is_none_or(|after| case.id > after))
+            .take(limit as usize)
+            .cloned()
+            .collect();
+        boxed(async move { Ok(cases) })
+    }
+
+    fn save_decision<'a>(&'a self, case: &'a ModerationCase) -> BoxFuture<'a, DomainResult<bool>> {
+        let saved = {
+            let mut cases = self.0.lock().unwrap();
+            match cases
+                .iter_mut()
+                .find(|stored| stored.id == case.id && stored.decision.is_none())
+            {
+                Some(stored) => {
+                    stored.decision.clone_from(&case.decision);
+                    true
+                }
+                None => false,
+            }
+        };
+        boxed(async move { Ok(saved) })
+    }
+}
@@ -9,7 +9,7 @@ use mokkan_core::async_support::{BoxFuture, boxed};
 #[must_use]
 pub struct MockRepo {
     pub items: Vec<mokkan_core::domain::audit::entity::AuditLog>,
-    pub next_cursor: Option<String>,
+    pub next_cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
 }
 
 impl MockRepo {
@@ -26,7 +26,7 @@ impl MockRepo {
 
     pub fn with(
         items: Vec<mokkan_core::domain::audit::entity::AuditLog>,
-        next_cursor: Option<String>,
+        next_cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
     ) -> Self {
         Self { items, next_cursor }
     }
@@ -43,12 +43,12 @@ impl mokkan_core::domain::audit::repository::AuditLogRepository for MockRepo {
     fn list(
         &self,
         _limit: u32,
-        _cursor: Option<mokkan_core::domain::audit::cursor::Cursor>,
+        _cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
     ) -> BoxFuture<
         '_,
         mokkan_core::domain::errors::DomainResult<(
             Vec<mokkan_core::domain::audit::entity::AuditLog>,
-            Option<String>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
         )>,
     > {
         boxed(async move { Ok((self.items.clone(), self.next_cursor.clone())) })
@@ -58,12 +58,12 @@ impl mokkan_core::domain::audit::repository::AuditLogRepository for MockRepo {
         &self,
         _user_id: i64,
         _limit: u32,
-        _cursor: Option<mokkan_core::domain::audit::cursor::Cursor>,
+        _cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
     ) -> BoxFuture<
         '_,
         mokkan_core::domain::errors::DomainResult<(
             Vec<mokkan_core::domain::audit::entity::AuditLog>,
-            Option<String>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
         )>,
     > {
         boxed(async move { Ok((self.items.clone(), self.next_cursor.clone())) })
@@ -74,16 +74,62 @@ impl mokkan_core::domain::audit::repository::AuditLogRepository for MockRepo {
         _resource_type: &str,
         _resource_id: i64,
         _limit: u32,
-        _cursor: Option<mokkan_core::domain::audit::cursor::Cursor>,
+        _cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
     ) -> BoxFuture<
         'a,
         mokkan_core::domain::errors::DomainResult<(
             Vec<mokkan_core::domain::audit::entity::AuditLog>,
-            Option<String>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
         )>,
     > {
         boxed(async move { Ok((self.items.clone(), self.next_cursor.clone())) })
     }
+
+    fn find_by_action<'a>(
+        &'a self,
+        action: &'a str,
+        _limit: u32,
+        _cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
+    ) -> BoxFuture<
+        'a,
+        mokkan_core::domain::errors::DomainResult<(
+            Vec<mokkan_core::domain::audit::entity::AuditLog>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
+        )>,
+    > {
+        boxed(async move {
+            let items = self
+                .items
+                .iter()
+                .filter(|log| log.action == action)
+                .cloned()
+                .collect();
+            Ok((items, self.next_cursor.clone()))
+        })
+    }
+
+    fn search<'a>(
+        &'a self,
+        filter: &'a mokkan_core::domain::audit::filter::AuditLogFilter,
+        _limit: u32,
+        _cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
+    ) -> BoxFuture<
+        'a,
+        mokkan_core::domain::errors::DomainResult<(
+            Vec<mokkan_core::domain::audit::entity::AuditLog>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
+        )>,
+    > {
+        boxed(async move {
+            let items = self
+                .items
+                .iter()
+                .filter(|log| filter.matches(log))
+                .cloned()
+                .collect();
+            Ok((items, self.next_cursor.clone()))
+        })
+    }
 }
 
 /* -------------------------------- MockAuditRepo -------------------------------- */
@@ -103,12 +149,12 @@ impl mokkan_core::domain::audit::repository::AuditLogRepository for MockAuditRep
     fn list(
         &self,
         _limit: u32,
-        _cursor: Option<mokkan_core::domain::audit::cursor::Cursor>,
+        _cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
     ) -> BoxFuture<
         '_,
         mokkan_core::domain::errors::DomainResult<(
             Vec<mokkan_core::domain::audit::entity::AuditLog>,
-            Option<String>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
         )>,
     > {
         boxed(async move {
@@ -120,32 +166,62 @@ impl mokkan_core::domain::audit::repository::AuditLogRepository for MockAuditRep
     fn find_by_user(
         &self,
         _user_id: i64,
-        _limit: u32,
-        _cursor: Option<mokkan_core::domain::audit::cursor::Cursor>,
+        limit: u32,
+        cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
     ) -> BoxFuture<
         '_,
         mokkan_core::domain::errors::DomainResult<(
             Vec<mokkan_core::domain::audit::entity::AuditLog>,
-            Option<String>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
         )>,
     > {
-        boxed(async move { self.list(_limit, _cursor).await })
+        boxed(async move { self.list(limit, cursor).await })
     }
 
     fn find_by_resource<'a>(
         &'a self,
         _resource_type: &str,
         _resource_id: i64,
-        _limit: u32,
-        _cursor: Option<mokkan_core::domain::audit::cursor::Cursor>,
+        limit: u32,
+        cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
+    ) -> BoxFuture<
+        'a,
+        mokkan_core::domain::errors::DomainResult<(
+            Vec<mokkan_core::domain::audit::entity::AuditLog>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
+        )>,
+    > {
+        boxed(async move { self.list(limit, cursor).await })
+    }
+
+    fn find_by_action<'a>(
+        &'a self,
+        _action: &'a str,
+        limit: u32,
+        cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
     ) -> BoxFuture<
         'a,
         mokkan_core::domain::errors::DomainResult<(
             Vec<mokkan_core::domain::audit::entity::AuditLog>,
-            Option<String>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
         )>,
     > {
-        boxed(async move { self.list(_limit, _cursor).await })
+        boxed(async move { self.list(limit, cursor).await })
+    }
+
+    fn search<'a>(
+        &'a self,
+        _filter: &'a mokkan_core::domain::audit::filter::AuditLogFilter,
+        limit: u32,
+        cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
+    ) -> BoxFuture<
+        'a,
+        mokkan_core::domain::errors::DomainResult<(
+            Vec<mokkan_core::domain::audit::entity::AuditLog>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
+        )>,
+    > {
+        boxed(async move { self.list(limit, cursor).await })
     }
 }
 
@@ -157,7 +233,7 @@ impl mokkan_core::domain::audit::repository::AuditLogRepository for MockAuditRep
 #[must_use]
 pub struct CapturingAuditRepo {
     pub items: Vec<mokkan_core::domain::audit::entity::AuditLog>,
-    pub next_cursor: Option<String>,
+    pub next_cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
     pub inserted:
         std::sync::Arc<std::sync::Mutex<Vec<mokkan_core::domain::audit::entity::NewAuditLog>>>,
 }
@@ -193,12 +269,12 @@ impl mokkan_core::domain::audit::repository::AuditLogRepository for CapturingAud
     fn list(
         &self,
         _limit: u32,
-        _cursor: Option<mokkan_core::domain::audit::cursor::Cursor>,
+        _cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
     ) -> BoxFuture<
         '_,
         mokkan_core::domain::errors::DomainResult<(
             Vec<mokkan_core::domain::audit::entity::AuditLog>,
-            Option<String>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
         )>,
     > {
         boxed(async move { Ok((self.items.clone(), self.next_cursor.clone())) })
@@ -208,12 +284,12 @@ impl mokkan_core::domain::audit::repository::AuditLogRepository for CapturingAud
         &self,
         _user_id: i64,
         _limit: u32,
-        _cursor: Option<mokkan_core::domain::audit::cursor::Cursor>,
+        _cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
     ) -> BoxFuture<
         '_,
         mokkan_core::domain::errors::DomainResult<(
             Vec<mokkan_core::domain::audit::entity::AuditLog>,
-            Option<String>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
         )>,
     > {
         boxed(async move { Ok((self.items.clone(), self.next_cursor.clone())) })
@@ -224,14 +300,60 @@ impl mokkan_core::domain::audit::repository::AuditLogRepository for CapturingAud
         _resource_type: &str,
         _resource_id: i64,
         _limit: u32,
-        _cursor: Option<mokkan_core::domain::audit::cursor::Cursor>,
+        _cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
     ) -> BoxFuture<
         'a,
         mokkan_core::domain::errors::DomainResult<(
             Vec<mokkan_core::domain::audit::entity::AuditLog>,
-            Option<String>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
         )>,
     > {
         boxed(async move { Ok((self.items.clone(), self.next_cursor.clone())) })
     }
+
+    fn find_by_action<'a>(
+        &'a self,
+        action: &'a str,
+        _limit: u32,
+        _cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
+    ) -> BoxFuture<
+        'a,
+        mokkan_core::domain::errors::DomainResult<(
+            Vec<mokkan_core::domain::audit::entity::AuditLog>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
+        )>,
+    > {
+        boxed(async move {
+            let items = self
+                .items
+                .iter()
+                .filter(|log| log.action == action)
+                .cloned()
+                .collect();
+            Ok((items, self.next_cursor.clone()))
+        })
+    }
+
+    fn search<'a>(
+        &'a self,
+        filter: &'a mokkan_core::domain::audit::filter::AuditLogFilter,
+        _limit: u32,
+        _cursor: Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
+    ) -> BoxFuture<
+        'a,
+        mokkan_core::domain::errors::DomainResult<(
+            Vec<mokkan_core::domain::audit::entity::AuditLog>,
+            Option<mokkan_core::domain::audit::cursor::AuditLogCursor>,
+        )>,
+    > {
+        boxed(async move {
+            let items = self
+                .items
+                .iter()
+                .filter(|log| filter.matches(log))
+                .cloned()
+                .collect();
+            Ok((items, self.next_cursor.clone()))
+        })
+    }
 }
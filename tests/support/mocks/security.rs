@@ -8,6 +8,7 @@ pub const TEST_TOKEN: &str = "test-token";
 pub const NO_AUDIT_TOKEN: &str = "no-audit";
 pub const EXPIRED_TOKEN: &str = "expired-token";
 pub const SESSION_TOKEN: &str = "session-token";
+pub const AUTHOR_TOKEN: &str = "author-token";
 
 /* -------------------------------- TokenManager -------------------------------- */
 
@@ -17,7 +18,7 @@ pub struct DummyTokenManager;
 impl mokkan_core::application::ports::security::TokenManager for DummyTokenManager {
     fn issue(
         &self,
-        _subject: mokkan_core::application::TokenSubject,
+        subject: mokkan_core::application::TokenSubject,
     ) -> BoxFuture<'_, mokkan_core::application::AppResult<mokkan_core::application::AuthTokenDto>>
     {
         boxed(async move {
@@ -25,11 +26,11 @@ impl mokkan_core::application::ports::security::TokenManager for DummyTokenManag
             let now = super::time::fixed_now();
             let expires_at = now + chrono::Duration::hours(1);
             Ok(mokkan_core::application::AuthTokenDto {
-                token: format!("issued-{}", i64::from(_subject.user_id)),
+                token: format!("issued-{}", i64::from(subject.user_id)),
                 issued_at: now,
                 expires_at,
                 expires_in: expires_at.signed_duration_since(now).num_seconds(),
-                session_id: _subject.session_id,
+                session_id: subject.session_id,
                 refresh_token: None,
             })
         })
@@ -48,6 +49,7 @@ impl mokkan_core::application::ports::security::TokenManager for DummyTokenManag
                 TEST_TOKEN => Ok(admin_audit_user(now)),
                 SESSION_TOKEN => Ok(session_user(now)),
                 NO_AUDIT_TOKEN => Ok(author_user(now)),
+                AUTHOR_TOKEN => Ok(default_author_user(now)),
                 // Expired tokens should be rejected at authentication time
                 EXPIRED_TOKEN => Err(mokkan_core::application::error::AppError::unauthorized(
                     "expired token",
@@ -70,11 +72,16 @@ impl mokkan_core::application::ports::security::TokenManager for DummyTokenManag
 
 fn admin_audit_user(now: DateTime<Utc>) -> mokkan_core::application::AuthenticatedUser {
     // For tests, treat the admin test-token as having the Admin role's default capabilities
-    // plus the audit:read capability so it can access audit endpoints used in tests.
+    // plus the audit:read and tokens:introspect capabilities so it can access the audit
+    // and token introspection endpoints used in tests.
     let mut caps = mokkan_core::domain::user::value_objects::Role::Admin.default_capabilities();
     caps.insert(mokkan_core::domain::user::value_objects::Capability::new(
         "audit", "read",
     ));
+    caps.insert(mokkan_core::domain::user::value_objects::Capability::new(
+        "tokens",
+        "introspect",
+    ));
 
     mokkan_core::application::AuthenticatedUser {
         id: mokkan_core::domain::user::value_objects::UserId::new(1).expect("invalid user id"),
@@ -85,6 +92,8 @@ fn admin_audit_user(now: DateTime<Utc>) -> mokkan_core::application::Authenticat
         expires_at: now + Duration::hours(1),
         session_id: None,
         token_version: None,
+        custom_role: None,
+        impersonator: None,
     }
 }
 
@@ -98,6 +107,24 @@ fn author_user(now: DateTime<Utc>) -> mokkan_core::application::AuthenticatedUse
         expires_at: now + Duration::hours(1),
         session_id: None,
         token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+fn default_author_user(now: DateTime<Utc>) -> mokkan_core::application::AuthenticatedUser {
+    let role = mokkan_core::domain::user::value_objects::Role::Author;
+    mokkan_core::application::AuthenticatedUser {
+        id: mokkan_core::domain::user::value_objects::UserId::new(5).expect("invalid user id"),
+        username: "author".into(),
+        role,
+        capabilities: role.default_capabilities(),
+        issued_at: now,
+        expires_at: now + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
     }
 }
 
@@ -113,6 +140,8 @@ fn session_user(now: DateTime<Utc>) -> mokkan_core::application::AuthenticatedUs
         expires_at: now + Duration::hours(1),
         session_id: Some("sid-1".into()),
         token_version: Some(1),
+        custom_role: None,
+        impersonator: None,
     }
 }
 
@@ -129,6 +158,8 @@ fn expired_admin_audit_user(now: DateTime<Utc>) -> mokkan_core::application::Aut
         expires_at: now - Duration::hours(1),
         session_id: None,
         token_version: None,
+        custom_role: None,
+        impersonator: None,
     }
 }
 
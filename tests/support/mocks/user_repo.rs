@@ -23,6 +23,16 @@ impl mokkan_core::domain::UserRepository for DummyRepo {
         })
     }
 
+    fn insert_bootstrap_admin(
+        &self,
+        _new_user: mokkan_core::domain::user::entity::NewUser,
+    ) -> BoxFuture<
+        '_,
+        mokkan_core::domain::errors::DomainResult<Option<mokkan_core::domain::user::entity::User>>,
+    > {
+        boxed(async move { Ok(None) })
+    }
+
     fn find_by_username<'a>(
         &'a self,
         _username: &mokkan_core::domain::user::value_objects::Username,
@@ -0,0 +1,142 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/user_blocks.rs
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use mokkan_core::application::services::BlockListService;
+use mokkan_core::application::{AppError, AuthenticatedUser};
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::errors::{DomainError, DomainResult};
+use mokkan_core::domain::user::entity::{NewUser, User, UserUpdate};
+use mokkan_core::domain::user::value_objects::{PasswordHash, UserListCursor, Username};
+use mokkan_core::domain::{Role, UserId, UserRepository};
+
+mod support;
+
+const ALICE: i64 = 1;
+const BOB: i64 = 2;
+
+/// Users 1 through 3 exist.
+struct Users;
+
+impl UserRepository for Users {
+    fn count(&self) -> BoxFuture<'_, DomainResult<u64>> {
+        boxed(async { Ok(3) })
+    }
+
+    fn insert(&self, _new_user: NewUser) -> BoxFuture<'_, DomainResult<User>> {
+        boxed(async { Err(DomainError::Validation("not supported".into())) })
+    }
+
+    fn insert_bootstrap_admin(
+        &self,
+        _new_user: NewUser,
+    ) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        boxed(async { Ok(None) })
+    }
+
+    fn find_by_username<'a>(
+        &'a self,
+        _username: &'a Username,
+    ) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        boxed(async { Ok(None) })
+    }
+
+    fn find_by_id(&self, id: UserId) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        let user = (i64::from(id) <= 3).then(|| User {
+            id,
+            username: Username::new(format!("user{}", i64::from(id))).unwrap(),
+            password_hash: PasswordHash::new("hash".to_string()).unwrap(),
+            role: Role::Author,
+            is_active: true,
+            created_at: Utc::now(),
+            timezone: None,
+            password_reset_required: false,
+            email: None,
+            email_verified: false,
+        });
+        boxed(async move { Ok(user) })
+    }
+
+    fn update(&self, _update: UserUpdate) -> BoxFuture<'_, DomainResult<User>> {
+        boxed(async { Err(DomainError::Validation("not supported".into())) })
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        _limit: u32,
+        _cursor: Option<UserListCursor>,
+        _search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, Option<UserListCursor>)>> {
+        boxed(async { Ok((vec![], None)) })
+    }
+}
+
+fn service() -> BlockListService {
+    BlockListService::new(
+        Arc::new(support::MemoryBlockList::default()),
+        Arc::new(Users),
+        Arc::new(support::DummyClock),
+    )
+}
+
+fn user(id: i64) -> AuthenticatedUser {
+    let now = support::fixed_now();
+    AuthenticatedUser {
+        id: UserId::new(id).unwrap(),
+        username: format!("user{id}"),
+        role: Role::Author,
+        capabilities: Role::Author.default_capabilities(),
+        issued_at: now,
+        expires_at: now + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+fn id(value: i64) -> UserId {
+    UserId::new(value).unwrap()
+}
+
+#[tokio::test]
+async fn blocked_users_cannot_interact() {
+    let svc = service();
+    svc.ensure_can_interact(id(BOB), id(ALICE)).await.unwrap();
+
+    let first = svc.block(&user(ALICE), BOB).await.unwrap();
+    let again = svc.block(&user(ALICE), BOB).await.unwrap();
+    assert_eq!((again.user_id, again.created_at), (BOB, first.created_at));
+    let err = svc
+        .ensure_can_interact(id(BOB), id(ALICE))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)), "{err}");
+
+    // Blocks are one-way.
+    svc.ensure_can_interact(id(ALICE), id(BOB)).await.unwrap();
+    assert_eq!(svc.list(&user(ALICE)).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn unblocking_restores_interaction() {
+    let svc = service();
+    svc.block(&user(ALICE), BOB).await.unwrap();
+    svc.unblock(&user(ALICE), BOB).await.unwrap();
+
+    svc.ensure_can_interact(id(BOB), id(ALICE)).await.unwrap();
+    let err = svc.unblock(&user(ALICE), BOB).await.unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)), "{err}");
+}
+
+#[tokio::test]
+async fn blocking_yourself_or_unknown_users_is_rejected() {
+    let svc = service();
+    let err = svc.block(&user(ALICE), ALICE).await.unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)), "{err}");
+
+    let err = svc.block(&user(ALICE), 42).await.unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)), "{err}");
+}
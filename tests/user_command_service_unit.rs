@@ -8,16 +8,34 @@ use mokkan_core::async_support::{BoxFuture, boxed};
 
 mod support;
 
-use mokkan_core::application::AuthenticatedUser;
+use support::{actor_for, member, user_command_service};
+
 use mokkan_core::application::commands::users::{
-    GrantRoleCommand, RevokeRoleCommand, UserCommandService,
+    ChangeEmailCommand, ChangePasswordCommand, DeleteAccountCommand, GrantRoleCommand,
+    LoginUserCommand, RefreshTokenCommand, RegisterUserCommand, RequestPasswordResetCommand,
+    ResetPasswordCommand, RevokeRoleCommand, UpdatePreferencesCommand, UserCommandService,
+    VerifyEmailCommand,
+};
+use mokkan_core::application::error::AppError;
+use mokkan_core::application::ports::external_auth::{
+    ExternalAuthenticator, ExternalIdentity, GroupRoleMapping,
 };
-use mokkan_core::domain::UserRepository;
+use mokkan_core::application::ports::notification::{EmailMessage, EmailSender};
+use mokkan_core::application::ports::rate_limit::LoginThrottlePolicy;
+use mokkan_core::application::ports::security_events::ClientInfo;
+use mokkan_core::application::ports::session_revocation::{
+    RefreshBinding, RefreshBindingMode, SessionPolicy,
+};
+use mokkan_core::application::ports::time::{Clock, ClockControl};
+use mokkan_core::application::{AuthenticatedUser, ReadOnlySwitch};
 use mokkan_core::domain::errors::DomainResult;
 use mokkan_core::domain::user::entity::{NewUser, User, UserUpdate};
 use mokkan_core::domain::user::value_objects::{
     PasswordHash, Role, UserId, UserListCursor, Username,
 };
+use mokkan_core::domain::{Email, UserRepository};
+use mokkan_core::infrastructure::security::rate_limiter::InMemoryRateLimiter;
+use mokkan_core::infrastructure::time::SimulatedClock;
 
 #[must_use]
 struct InMemoryUserRepo {
@@ -40,11 +58,53 @@ impl UserRepository for InMemoryUserRepo {
         })
     }
 
-    fn insert(&self, _new_user: NewUser) -> BoxFuture<'_, DomainResult<User>> {
+    fn insert(&self, new_user: NewUser) -> BoxFuture<'_, DomainResult<User>> {
         boxed(async move {
-            Err(mokkan_core::domain::errors::DomainError::NotFound(
-                "not implemented".into(),
-            ))
+            let mut map = self.inner.lock().unwrap();
+            let id = map.keys().max().copied().unwrap_or_default() + 1;
+            let user = User {
+                id: UserId::new(id)?,
+                username: new_user.username,
+                password_hash: new_user.password_hash,
+                role: new_user.role,
+                is_active: new_user.is_active,
+                created_at: new_user.created_at,
+                timezone: None,
+                password_reset_required: new_user.password_reset_required,
+                email: None,
+                email_verified: false,
+            };
+            map.insert(id, user.clone());
+            drop(map);
+            Ok(user)
+        })
+    }
+
+    fn insert_bootstrap_admin(
+        &self,
+        new_user: NewUser,
+    ) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        boxed(async move {
+            let mut map = self.inner.lock().unwrap();
+            if !map.is_empty() {
+                return Ok(None);
+            }
+
+            let user = User {
+                id: UserId::new(1)?,
+                username: new_user.username,
+                password_hash: new_user.password_hash,
+                role: new_user.role,
+                is_active: new_user.is_active,
+                created_at: new_user.created_at,
+                timezone: None,
+                password_reset_required: false,
+                email: None,
+                email_verified: false,
+            };
+            map.insert(1, user.clone());
+            drop(map);
+            Ok(Some(user))
         })
     }
 
@@ -56,13 +116,23 @@ impl UserRepository for InMemoryUserRepo {
             let found = {
                 let map = self.inner.lock().unwrap();
                 map.values()
-                    .find(|u| u.username.as_str() == username.as_str())
+                    .find(|u| u.username.eq_ignore_case(username.as_str()))
                     .cloned()
             };
             Ok(found)
         })
     }
 
+    fn find_by_email<'a>(&'a self, email: &'a Email) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        boxed(async move {
+            let map = self.inner.lock().unwrap();
+            Ok(map
+                .values()
+                .find(|u| u.email.as_ref() == Some(email))
+                .cloned())
+        })
+    }
+
     fn find_by_id(&self, id: UserId) -> BoxFuture<'_, DomainResult<Option<User>>> {
         boxed(async move {
             let map = self.inner.lock().unwrap();
@@ -77,6 +147,9 @@ impl UserRepository for InMemoryUserRepo {
                 let id = i64::from(update.id);
                 match map.get_mut(&id) {
                     Some(user) => {
+                        if let Some(username) = update.username {
+                            user.username = username;
+                        }
                         if let Some(is_active) = update.is_active {
                             user.is_active = is_active;
                         }
@@ -86,6 +159,15 @@ impl UserRepository for InMemoryUserRepo {
                         if let Some(password_hash) = update.password_hash {
                             user.password_hash = password_hash;
                         }
+                        if let Some(timezone) = update.timezone {
+                            user.timezone = timezone;
+                        }
+                        if let Some(email) = update.email {
+                            user.email = email;
+                        }
+                        if let Some(verified) = update.email_verified {
+                            user.email_verified = verified;
+                        }
 
                         Ok(user.clone())
                     }
@@ -117,6 +199,10 @@ async fn grant_and_revoke_role_service_flow() {
         role: Role::Admin,
         is_active: true,
         created_at: Utc::now(),
+        timezone: None,
+        password_reset_required: false,
+        email: None,
+        email_verified: false,
     };
 
     let target = User {
@@ -126,6 +212,10 @@ async fn grant_and_revoke_role_service_flow() {
         role: Role::Author,
         is_active: true,
         created_at: Utc::now(),
+        timezone: None,
+        password_reset_required: false,
+        email: None,
+        email_verified: false,
     };
 
     let mut users = HashMap::new();
@@ -165,6 +255,8 @@ async fn grant_and_revoke_role_service_flow() {
         expires_at: Utc::now() + Duration::hours(1),
         session_id: None,
         token_version: None,
+        custom_role: None,
+        impersonator: None,
     };
 
     // grant admin role to target
@@ -186,3 +278,1410 @@ async fn grant_and_revoke_role_service_flow() {
         .expect("revoke_role failed");
     assert_eq!(updated2.role, Role::Author);
 }
+
+/// Password hasher that yields once so concurrent registrations interleave
+/// between the user count check and the insert.
+struct YieldingPasswordHasher;
+
+impl mokkan_core::application::ports::security::PasswordHasher for YieldingPasswordHasher {
+    fn hash<'a>(
+        &'a self,
+        _password: &'a str,
+    ) -> BoxFuture<'a, mokkan_core::application::AppResult<String>> {
+        boxed(async move {
+            tokio::task::yield_now().await;
+            Ok("hash".into())
+        })
+    }
+
+    fn verify<'a>(
+        &'a self,
+        _password: &'a str,
+        _expected_hash: &'a str,
+    ) -> BoxFuture<'a, mokkan_core::application::AppResult<()>> {
+        boxed(async move { Ok(()) })
+    }
+}
+
+#[tokio::test]
+async fn concurrent_first_registrations_bootstrap_single_admin() {
+    let repo = Arc::new(InMemoryUserRepo::new(HashMap::new()));
+    let svc = UserCommandService::new(
+        repo.clone(),
+        Arc::new(YieldingPasswordHasher),
+        Arc::new(support::DummyTokenManager),
+        Arc::new(
+            mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec::new(
+                "test-refresh-secret",
+            )
+            .expect("refresh token codec"),
+        ),
+        Arc::new(
+            mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(),
+        ),
+        Arc::new(support::DummyClock),
+    );
+
+    let register = |username: &str| RegisterUserCommand {
+        username: username.into(),
+        password: "Str0ng!Password".into(),
+        role: None,
+    };
+
+    // Both registrations observe an empty user table before either inserts.
+    let (first, second) = tokio::join!(
+        svc.register(None, register("first")),
+        svc.register(None, register("second")),
+    );
+
+    let (winner, loser) = if first.is_ok() {
+        (first, second)
+    } else {
+        (second, first)
+    };
+    let admin = winner.expect("one registration bootstraps the admin");
+    assert_eq!(admin.role, Role::Admin);
+    assert!(
+        matches!(loser, Err(AppError::Forbidden(_))),
+        "losing registration must not be promoted to admin"
+    );
+    assert_eq!(repo.count().await.expect("count"), 1);
+}
+
+#[tokio::test]
+async fn register_rejects_username_differing_only_by_case() {
+    let admin = User {
+        id: UserId::new(1).unwrap(),
+        username: Username::new("alice").unwrap(),
+        password_hash: PasswordHash::new("hash".to_string()).unwrap(),
+        role: Role::Admin,
+        is_active: true,
+        created_at: Utc::now(),
+        timezone: None,
+        password_reset_required: false,
+        email: None,
+        email_verified: false,
+    };
+    let repo = Arc::new(InMemoryUserRepo::new(HashMap::from([(1, admin)])));
+    let svc = UserCommandService::new(
+        repo,
+        Arc::new(support::DummyPasswordHasher),
+        Arc::new(support::DummyTokenManager),
+        Arc::new(
+            mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec::new(
+                "test-refresh-secret",
+            )
+            .expect("refresh token codec"),
+        ),
+        Arc::new(
+            mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(),
+        ),
+        Arc::new(support::DummyClock),
+    );
+
+    let actor = AuthenticatedUser {
+        id: UserId::new(1).unwrap(),
+        username: "alice".into(),
+        role: Role::Admin,
+        capabilities: Role::Admin.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    };
+
+    let err = svc
+        .register(
+            Some(&actor),
+            RegisterUserCommand {
+                username: "ALICE".into(),
+                password: "Str0ng!Password".into(),
+                role: None,
+            },
+        )
+        .await
+        .expect_err("case-only duplicate must be rejected");
+
+    assert!(matches!(err, AppError::Conflict(_)));
+}
+
+/// 読み取り専用モード中はコマンドサービスも書き込みを拒否する
+#[tokio::test]
+async fn read_only_mode_refuses_registration() {
+    let repo = Arc::new(InMemoryUserRepo::new(HashMap::new()));
+    let switch = ReadOnlySwitch::new(true);
+    let svc = directory_service(repo.clone(), &[]).with_read_only(switch.clone());
+    let command = || RegisterUserCommand {
+        username: "erin".into(),
+        password: "Str0ng!Password".into(),
+        role: None,
+    };
+
+    let result = svc.register(None, command()).await;
+    assert!(matches!(result, Err(AppError::Unavailable(_))));
+    assert_eq!(repo.count().await.unwrap(), 0);
+
+    assert!(switch.set(false));
+    svc.register(None, command()).await.expect("writable again");
+    assert_eq!(repo.count().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn update_preferences_sets_and_clears_timezone() {
+    let author = User {
+        id: UserId::new(3).unwrap(),
+        username: Username::new("writer").unwrap(),
+        password_hash: PasswordHash::new("hash".to_string()).unwrap(),
+        role: Role::Author,
+        is_active: true,
+        created_at: Utc::now(),
+        timezone: None,
+        password_reset_required: false,
+        email: None,
+        email_verified: false,
+    };
+    let repo = Arc::new(InMemoryUserRepo::new(HashMap::from([(3, author)])));
+    let svc = UserCommandService::new(
+        repo.clone(),
+        Arc::new(support::DummyPasswordHasher),
+        Arc::new(support::DummyTokenManager),
+        Arc::new(
+            mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec::new(
+                "test-refresh-secret",
+            )
+            .expect("refresh token codec"),
+        ),
+        Arc::new(
+            mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(),
+        ),
+        Arc::new(support::DummyClock),
+    );
+    let actor = AuthenticatedUser {
+        id: UserId::new(3).unwrap(),
+        username: "writer".into(),
+        role: Role::Author,
+        capabilities: Role::Author.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    };
+
+    let profile = svc
+        .update_preferences(
+            &actor,
+            UpdatePreferencesCommand {
+                timezone: Some("Asia/Tokyo".into()),
+            },
+        )
+        .await
+        .expect("valid timezone");
+    assert_eq!(profile.user.timezone.as_deref(), Some("Asia/Tokyo"));
+
+    let err = svc
+        .update_preferences(
+            &actor,
+            UpdatePreferencesCommand {
+                timezone: Some("Mars/Olympus_Mons".into()),
+            },
+        )
+        .await
+        .expect_err("unknown timezone");
+    assert!(matches!(
+        err,
+        AppError::Domain(mokkan_core::domain::errors::DomainError::Validation(_))
+    ));
+    let stored = repo.find_by_id(actor.id).await.unwrap().unwrap();
+    assert_eq!(stored.timezone.map(|tz| tz.as_str()), Some("Asia/Tokyo"));
+
+    let profile = svc
+        .update_preferences(&actor, UpdatePreferencesCommand { timezone: None })
+        .await
+        .expect("clearing is allowed");
+    assert!(profile.user.timezone.is_none());
+}
+
+/// Directory that accepts a single password and reports fixed groups.
+struct StaticDirectory {
+    password: &'static str,
+    groups: Vec<String>,
+}
+
+impl ExternalAuthenticator for StaticDirectory {
+    fn authenticate<'a>(
+        &'a self,
+        username: &'a str,
+        password: &'a str,
+    ) -> BoxFuture<'a, mokkan_core::application::AppResult<Option<ExternalIdentity>>> {
+        boxed(async move {
+            Ok((password == self.password).then(|| ExternalIdentity {
+                subject: format!("uid={username},dc=example,dc=com"),
+                groups: self.groups.clone(),
+            }))
+        })
+    }
+}
+
+/// Password hasher whose verification always fails, so a successful login
+/// proves the local password path was skipped.
+struct RejectingPasswordHasher;
+
+impl mokkan_core::application::ports::security::PasswordHasher for RejectingPasswordHasher {
+    fn hash<'a>(
+        &'a self,
+        _password: &'a str,
+    ) -> BoxFuture<'a, mokkan_core::application::AppResult<String>> {
+        boxed(async move { Ok("hash".into()) })
+    }
+
+    fn verify<'a>(
+        &'a self,
+        _password: &'a str,
+        _expected_hash: &'a str,
+    ) -> BoxFuture<'a, mokkan_core::application::AppResult<()>> {
+        boxed(async move { Err(AppError::unauthorized("invalid credentials")) })
+    }
+}
+
+fn directory_service(repo: Arc<InMemoryUserRepo>, groups: &[&str]) -> UserCommandService {
+    directory_service_at(repo, groups, Arc::new(support::DummyClock))
+}
+
+fn directory_service_at(
+    repo: Arc<InMemoryUserRepo>,
+    groups: &[&str],
+    clock: Arc<dyn Clock>,
+) -> UserCommandService {
+    UserCommandService::new(
+        repo,
+        Arc::new(RejectingPasswordHasher),
+        Arc::new(support::DummyTokenManager),
+        Arc::new(
+            mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec::new(
+                "test-refresh-secret",
+            )
+            .expect("refresh token codec"),
+        ),
+        Arc::new(
+            mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(),
+        ),
+        clock,
+    )
+    .with_external_authenticator(
+        Arc::new(StaticDirectory {
+            password: "directory-secret",
+            groups: groups.iter().map(ToString::to_string).collect(),
+        }),
+        GroupRoleMapping::new(
+            vec![("cn=cms-admins,dc=example,dc=com".into(), Role::Admin)],
+            None,
+        ),
+    )
+}
+
+fn login(password: &str) -> LoginUserCommand {
+    LoginUserCommand {
+        username: "dana".into(),
+        password: password.into(),
+    }
+}
+
+#[tokio::test]
+async fn external_login_provisions_user_with_mapped_role() {
+    let repo = Arc::new(InMemoryUserRepo::new(HashMap::new()));
+    let svc = directory_service(repo.clone(), &["CN=CMS-Admins,DC=example,DC=com"]);
+
+    let first = svc
+        .login(login("directory-secret"))
+        .await
+        .expect("directory accepts the password");
+    assert_eq!(first.user.role, Role::Admin);
+    assert_eq!(repo.count().await.unwrap(), 1);
+
+    // Later logins reuse the provisioned account.
+    let second = svc.login(login("directory-secret")).await.unwrap();
+    assert_eq!(second.user.id, first.user.id);
+    assert_eq!(repo.count().await.unwrap(), 1);
+
+    // A directory rejection falls through to the local password check.
+    let result = svc.login(login("wrong")).await;
+    assert!(matches!(result, Err(AppError::Unauthorized(_))));
+}
+
+#[tokio::test]
+async fn external_login_without_a_mapped_role_is_refused() {
+    let repo = Arc::new(InMemoryUserRepo::new(HashMap::new()));
+    let svc = directory_service(repo.clone(), &["cn=visitors,dc=example,dc=com"]);
+
+    let result = svc.login(login("directory-secret")).await;
+    assert!(
+        matches!(result, Err(AppError::Forbidden(_))),
+        "no group maps to a role"
+    );
+    assert_eq!(repo.count().await.unwrap(), 0);
+}
+
+fn throttled_service(
+    repo: Arc<InMemoryUserRepo>,
+    audit: &support::CapturingAuditRepo,
+) -> UserCommandService {
+    directory_service(repo, &["cn=cms-admins,dc=example,dc=com"]).with_login_throttle(
+        Arc::new(InMemoryRateLimiter::new()),
+        LoginThrottlePolicy {
+            max_failures_per_user: 3,
+            max_failures_per_client: 5,
+            window: Duration::minutes(15),
+            lockout: Duration::minutes(10),
+        },
+        Arc::new(audit.clone()),
+    )
+}
+
+fn client(ip: &str) -> ClientInfo {
+    ClientInfo {
+        ip_address: Some(ip.into()),
+        user_agent: None,
+    }
+}
+
+/// 失敗回数の上限でアカウントがロックされ、成功するとカウントがリセットされる
+#[tokio::test]
+async fn failed_logins_lock_the_account_until_the_lockout_ends() {
+    let repo = Arc::new(InMemoryUserRepo::new(HashMap::new()));
+    let audit = support::CapturingAuditRepo::new();
+    let svc = throttled_service(repo, &audit);
+    let from = client("192.0.2.1");
+
+    for _ in 0..2 {
+        let result = svc.login_from_client(login("wrong"), &from).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+    // A success resets the account's count.
+    svc.login_from_client(login("directory-secret"), &from)
+        .await
+        .expect("not locked yet");
+    for _ in 0..3 {
+        let result = svc
+            .login_from_client(login("wrong"), &client("192.0.2.2"))
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    // Locked: even the right password is refused.
+    let result = svc
+        .login_from_client(login("directory-secret"), &from)
+        .await;
+    assert!(matches!(
+        result,
+        Err(AppError::TooManyRequests {
+            retry_after_secs: 600,
+            ..
+        })
+    ));
+    let logged = audit.get_inserted();
+    assert_eq!(logged.len(), 1);
+    assert_eq!(logged[0].action, "user.locked");
+    assert_eq!(logged[0].resource_id, Some(1));
+    assert_eq!(logged[0].ip_address.as_deref(), Some("192.0.2.2"));
+}
+
+/// 多数のユーザー名を試すクライアントはアドレス単位で締め出される
+#[tokio::test]
+async fn failed_logins_across_usernames_lock_out_the_client() {
+    let repo = Arc::new(InMemoryUserRepo::new(HashMap::new()));
+    let audit = support::CapturingAuditRepo::new();
+    let svc = throttled_service(repo, &audit);
+    let attacker = client("198.51.100.9");
+
+    for name in ["amy", "bob", "cat", "dan", "eve"] {
+        let command = LoginUserCommand {
+            username: name.into(),
+            password: "guess".into(),
+        };
+        let result = svc.login_from_client(command, &attacker).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    let result = svc
+        .login_from_client(login("directory-secret"), &attacker)
+        .await;
+    assert!(matches!(result, Err(AppError::TooManyRequests { .. })));
+    svc.login_from_client(login("directory-secret"), &client("203.0.113.4"))
+        .await
+        .expect("other clients are unaffected");
+    let actions: Vec<_> = audit.get_inserted().into_iter().map(|e| e.action).collect();
+    assert_eq!(actions, ["login.client_locked"]);
+}
+
+/// ユーザー名でも確認済みのメールアドレスでもログインでき、`@` を含むユーザー名は登録できない
+#[tokio::test]
+async fn login_accepts_a_username_or_a_verified_email() {
+    let mut unverified = member(2, "bob", Role::Author, "secret");
+    unverified.email_verified = false;
+    let repo = Arc::new(InMemoryUserRepo::new(HashMap::from([
+        (1, member(1, "alice", Role::Author, "secret")),
+        (2, unverified),
+    ])));
+    let svc = recovery_service(repo, Arc::new(CapturingMailer::default()));
+    let login_as = |identifier: &str| LoginUserCommand {
+        username: identifier.into(),
+        password: "secret".into(),
+    };
+
+    let by_name = svc.login(login_as("alice")).await.unwrap();
+    let by_email = svc.login(login_as(" Alice@Example.com ")).await.unwrap();
+    assert_eq!(by_name.user.id, by_email.user.id);
+    svc.login(login_as("bob")).await.unwrap();
+    for identifier in ["bob@example.com", "nobody@example.com"] {
+        let result = svc.login(login_as(identifier)).await;
+        assert!(
+            matches!(result, Err(AppError::Unauthorized(_))),
+            "{identifier}"
+        );
+    }
+
+    let registered = svc
+        .register(
+            None,
+            RegisterUserCommand {
+                username: "carol@example.com".into(),
+                password: "Str0ng!Password".into(),
+                role: None,
+            },
+        )
+        .await;
+    assert!(
+        matches!(
+            registered,
+            Err(AppError::Domain(
+                mokkan_core::domain::errors::DomainError::Validation(_)
+            ))
+        ),
+        "{registered:?}"
+    );
+}
+
+fn session_service(clock: &SimulatedClock, policy: SessionPolicy) -> UserCommandService {
+    let repo = Arc::new(InMemoryUserRepo::new(HashMap::new()));
+    directory_service_at(
+        repo,
+        &["cn=cms-admins,dc=example,dc=com"],
+        Arc::new(clock.clone()),
+    )
+    .with_session_policy(policy)
+}
+
+/// Refresh the session `token` belongs to after `idle`, returning the
+/// rotated refresh token.
+async fn refresh_after(
+    svc: &UserCommandService,
+    clock: &SimulatedClock,
+    idle: Duration,
+    token: String,
+) -> Result<String, AppError> {
+    clock.advance(idle);
+    let refreshed = svc.refresh_token(RefreshTokenCommand { token }).await?;
+    Ok(refreshed.refresh_token.expect("rotated refresh token"))
+}
+
+/// 更新のたびにアイドル期限が延び、放置されたセッションは更新できなくなる
+#[tokio::test]
+async fn refresh_keeps_sessions_alive_until_they_sit_idle() {
+    let clock = SimulatedClock::new();
+    let svc = session_service(
+        &clock,
+        SessionPolicy {
+            max_age: Some(Duration::days(1)),
+            idle_timeout: Some(Duration::hours(1)),
+            ..SessionPolicy::default()
+        },
+    );
+    let session = svc.login(login("directory-secret")).await.unwrap();
+    let mut token = session.token.refresh_token.unwrap();
+
+    for _ in 0..3 {
+        token = refresh_after(&svc, &clock, Duration::minutes(50), token)
+            .await
+            .expect("refreshed within the idle timeout");
+    }
+    let result = refresh_after(&svc, &clock, Duration::minutes(61), token).await;
+    assert!(
+        matches!(result, Err(AppError::Unauthorized(_))),
+        "{result:?}"
+    );
+}
+
+/// 更新を続けても最大有効期間を過ぎたセッションは延長されない
+#[tokio::test]
+async fn refresh_does_not_extend_sessions_past_their_max_age() {
+    let clock = SimulatedClock::new();
+    let svc = session_service(
+        &clock,
+        SessionPolicy {
+            max_age: Some(Duration::hours(2)),
+            idle_timeout: None,
+            ..SessionPolicy::default()
+        },
+    );
+    let session = svc.login(login("directory-secret")).await.unwrap();
+    let mut token = session.token.refresh_token.unwrap();
+
+    for _ in 0..2 {
+        token = refresh_after(&svc, &clock, Duration::minutes(50), token)
+            .await
+            .expect("refreshed before the max age");
+    }
+    let result = refresh_after(&svc, &clock, Duration::minutes(50), token).await;
+    assert!(
+        matches!(result, Err(AppError::Unauthorized(_))),
+        "{result:?}"
+    );
+}
+
+fn browser(ip: &str, user_agent: &str) -> ClientInfo {
+    ClientInfo {
+        ip_address: Some(ip.into()),
+        user_agent: Some(user_agent.into()),
+    }
+}
+
+fn bound_session_service(
+    clock: &SimulatedClock,
+    mode: RefreshBindingMode,
+    audit: &support::CapturingAuditRepo,
+) -> UserCommandService {
+    session_service(
+        clock,
+        SessionPolicy {
+            binding: RefreshBinding {
+                mode,
+                user_agent: true,
+                ip_subnet: true,
+            },
+            ..SessionPolicy::default()
+        },
+    )
+    .with_audit(Arc::new(audit.clone()))
+}
+
+/// 強制モードではログインしたクライアント以外からのリフレッシュを拒否し監査に残す
+#[tokio::test]
+async fn enforced_binding_refuses_refreshes_from_other_clients() {
+    let clock = SimulatedClock::new();
+    let audit = support::CapturingAuditRepo::new();
+    let svc = bound_session_service(&clock, RefreshBindingMode::Enforce, &audit);
+    let laptop = browser("192.0.2.10", "Firefox");
+    let session = svc
+        .login_from_client(login("directory-secret"), &laptop)
+        .await
+        .unwrap();
+    let token = session.token.refresh_token.unwrap();
+
+    for thief in [
+        browser("192.0.2.10", "curl"),
+        browser("198.51.100.7", "Firefox"),
+    ] {
+        let result = svc
+            .refresh_token_from_client(
+                RefreshTokenCommand {
+                    token: token.clone(),
+                },
+                &thief,
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))), "{result:?}");
+    }
+    // The refused attempts did not rotate the token, and a new address in
+    // the same network still counts as the same client.
+    svc.refresh_token_from_client(
+        RefreshTokenCommand { token },
+        &browser("192.0.2.77", "Firefox"),
+    )
+    .await
+    .expect("same client, same network");
+
+    let mismatches: Vec<_> = audit
+        .get_inserted()
+        .into_iter()
+        .filter(|entry| entry.action == "auth.refresh_client_mismatch")
+        .collect();
+    assert_eq!(mismatches.len(), 2);
+    assert_eq!(mismatches[0].user_agent.as_deref(), Some("curl"));
+    assert_eq!(mismatches[0].details.as_ref().unwrap()["refused"], true);
+}
+
+/// 監査モードでは不一致を記録するだけでリフレッシュは通る
+#[tokio::test]
+async fn audited_binding_records_mismatches_without_refusing() {
+    let clock = SimulatedClock::new();
+    let audit = support::CapturingAuditRepo::new();
+    let svc = bound_session_service(&clock, RefreshBindingMode::Audit, &audit);
+    let session = svc
+        .login_from_client(login("directory-secret"), &browser("192.0.2.10", "Firefox"))
+        .await
+        .unwrap();
+
+    svc.refresh_token_from_client(
+        RefreshTokenCommand {
+            token: session.token.refresh_token.unwrap(),
+        },
+        &browser("203.0.113.5", "Firefox"),
+    )
+    .await
+    .expect("audit mode lets the refresh through");
+
+    let mismatch = audit
+        .get_inserted()
+        .into_iter()
+        .find(|entry| entry.action == "auth.refresh_client_mismatch")
+        .expect("mismatch audited");
+    assert_eq!(mismatch.ip_address.as_deref(), Some("203.0.113.5"));
+    assert_eq!(mismatch.details.unwrap()["refused"], false);
+}
+
+/// Mailer that keeps every message so tests can read the mailed tokens.
+#[derive(Default)]
+struct CapturingMailer {
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+impl CapturingMailer {
+    /// The token on the last line of the most recent message to `to`.
+    fn last_token(&self, to: &str) -> String {
+        let body = self
+            .sent
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|message| message.to == to)
+            .expect("a message was mailed")
+            .body
+            .clone();
+        body.lines()
+            .find(|line| line.len() == 43 && !line.contains(' '))
+            .expect("message carries a token")
+            .to_owned()
+    }
+}
+
+impl EmailSender for CapturingMailer {
+    fn send(
+        &self,
+        message: EmailMessage,
+    ) -> BoxFuture<'_, mokkan_core::application::AppResult<()>> {
+        boxed(async move {
+            self.sent.lock().unwrap().push(message);
+            Ok(())
+        })
+    }
+}
+
+fn recovery_service(
+    repo: Arc<InMemoryUserRepo>,
+    mailer: Arc<CapturingMailer>,
+) -> UserCommandService {
+    UserCommandService::new(
+        repo,
+        Arc::new(support::DummyPasswordHasher),
+        Arc::new(support::DummyTokenManager),
+        Arc::new(
+            mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec::new(
+                "test-refresh-secret",
+            )
+            .expect("refresh token codec"),
+        ),
+        Arc::new(
+            mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(),
+        ),
+        Arc::new(support::DummyClock),
+    )
+    .with_account_recovery(
+        Arc::new(mokkan_core::infrastructure::security::user_tokens::InMemoryUserTokenStore::new()),
+        mailer,
+    )
+}
+
+#[tokio::test]
+async fn email_verification_gates_password_resets() {
+    let writer = User {
+        id: UserId::new(4).unwrap(),
+        username: Username::new("erin").unwrap(),
+        password_hash: PasswordHash::new("old-hash".to_string()).unwrap(),
+        role: Role::Author,
+        is_active: true,
+        created_at: Utc::now(),
+        timezone: None,
+        password_reset_required: false,
+        email: None,
+        email_verified: false,
+    };
+    let repo = Arc::new(InMemoryUserRepo::new(HashMap::from([(4, writer)])));
+    let mailer = Arc::new(CapturingMailer::default());
+    let svc = recovery_service(repo.clone(), mailer.clone());
+    let actor = AuthenticatedUser {
+        id: UserId::new(4).unwrap(),
+        username: "erin".into(),
+        role: Role::Author,
+        capabilities: Role::Author.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    };
+    let reset = |email: &str| RequestPasswordResetCommand {
+        email: email.into(),
+    };
+
+    let profile = svc
+        .change_email(
+            &actor,
+            ChangeEmailCommand {
+                email: Some(" Erin@Example.COM ".into()),
+            },
+        )
+        .await
+        .expect("address is accepted");
+    assert_eq!(profile.user.email.as_deref(), Some("erin@example.com"));
+    assert!(!profile.user.email_verified);
+
+    // Unverified addresses receive no reset mail.
+    svc.request_password_reset(reset("erin@example.com"))
+        .await
+        .unwrap();
+    assert_eq!(mailer.sent.lock().unwrap().len(), 1);
+
+    let verification = mailer.last_token("erin@example.com");
+    svc.verify_email(VerifyEmailCommand {
+        token: verification.clone(),
+    })
+    .await
+    .expect("mailed token verifies");
+    assert!(
+        repo.find_by_id(actor.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .email_verified
+    );
+    let reused = svc
+        .verify_email(VerifyEmailCommand {
+            token: verification,
+        })
+        .await;
+    assert!(reused.is_err(), "tokens are single-use");
+
+    // Unknown addresses look the same to the caller.
+    svc.request_password_reset(reset("nobody@example.com"))
+        .await
+        .unwrap();
+    svc.request_password_reset(reset("ERIN@example.com"))
+        .await
+        .unwrap();
+    assert_eq!(mailer.sent.lock().unwrap().len(), 2);
+
+    let token = mailer.last_token("erin@example.com");
+    let weak = svc
+        .reset_password(ResetPasswordCommand {
+            token: token.clone(),
+            new_password: "short".into(),
+        })
+        .await;
+    assert!(weak.is_err());
+    svc.reset_password(ResetPasswordCommand {
+        token: token.clone(),
+        new_password: "N3w!Password-long".into(),
+    })
+    .await
+    .expect("a weak password does not spend the token");
+    let stored = repo.find_by_id(actor.id).await.unwrap().unwrap();
+    assert_eq!(stored.password_hash.as_str(), "hash");
+
+    let replay = svc
+        .reset_password(ResetPasswordCommand {
+            token,
+            new_password: "N3w!Password-long".into(),
+        })
+        .await;
+    assert!(matches!(replay, Err(AppError::Validation(_))));
+}
+
+/// 古いパラメータのハッシュはログイン成功時に現在のパラメータで再ハッシュされる
+#[tokio::test]
+async fn login_rehashes_passwords_hashed_with_old_parameters() {
+    use mokkan_core::application::ports::security::PasswordHasher as _;
+    use mokkan_core::infrastructure::security::password::Argon2PasswordHasher;
+
+    let old = Argon2PasswordHasher::new(8 * 1024, 1, 1).unwrap();
+    let current = Arc::new(Argon2PasswordHasher::new(16 * 1024, 2, 1).unwrap());
+    let old_hash = old.hash("s3cret-passphrase").await.unwrap();
+    let user = User {
+        id: UserId::new(5).unwrap(),
+        username: Username::new("dana").unwrap(),
+        password_hash: PasswordHash::new(old_hash.clone()).unwrap(),
+        role: Role::Author,
+        is_active: true,
+        created_at: Utc::now(),
+        timezone: None,
+        password_reset_required: false,
+        email: None,
+        email_verified: false,
+    };
+    let repo = Arc::new(InMemoryUserRepo::new(HashMap::from([(5, user)])));
+    let svc = UserCommandService::new(
+        repo.clone(),
+        current.clone(),
+        Arc::new(support::DummyTokenManager),
+        Arc::new(
+            mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec::new(
+                "test-refresh-secret",
+            )
+            .expect("refresh token codec"),
+        ),
+        Arc::new(
+            mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(),
+        ),
+        Arc::new(support::DummyClock),
+    );
+
+    svc.login(login("s3cret-passphrase")).await.unwrap();
+    let stored = repo.inner.lock().unwrap()[&5].password_hash.clone();
+    let stored = stored.as_str().to_owned();
+    assert_ne!(stored, old_hash);
+    assert!(!current.needs_rehash(&stored));
+
+    svc.login(login("s3cret-passphrase"))
+        .await
+        .expect("the upgraded hash verifies");
+}
+
+/// 直近の履歴に含まれるパスワードへの変更は拒否され、履歴から外れると再利用できる
+#[tokio::test]
+async fn password_changes_refuse_recent_passwords() {
+    let user = User {
+        id: UserId::new(6).unwrap(),
+        username: Username::new("gale").unwrap(),
+        password_hash: PasswordHash::new("hash::Passphrase-one-1!".to_string()).unwrap(),
+        role: Role::Author,
+        is_active: true,
+        created_at: Utc::now(),
+        timezone: None,
+        password_reset_required: false,
+        email: None,
+        email_verified: false,
+    };
+    let repo = Arc::new(InMemoryUserRepo::new(HashMap::from([(6, user)])));
+    let svc = user_command_service(
+        repo,
+        Arc::new(support::DummyTokenManager),
+        Arc::new(
+            mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(),
+        ),
+        Arc::new(support::DummyClock),
+    )
+    .with_password_history(
+        Arc::new(mokkan_core::infrastructure::repositories::memory::InMemoryPasswordHistory::new()),
+        3,
+    );
+    let actor = AuthenticatedUser {
+        id: UserId::new(6).unwrap(),
+        username: "gale".into(),
+        role: Role::Author,
+        capabilities: Role::Author.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    };
+    let change = |from: &str, to: &str| ChangePasswordCommand {
+        user_id: 6,
+        current_password: Some(from.into()),
+        new_password: to.into(),
+    };
+
+    let same = svc
+        .change_password(&actor, change("Passphrase-one-1!", "Passphrase-one-1!"))
+        .await;
+    assert!(matches!(same, Err(AppError::Validation(_))), "{same:?}");
+    svc.change_password(&actor, change("Passphrase-one-1!", "Passphrase-two-2!"))
+        .await
+        .unwrap();
+    svc.change_password(&actor, change("Passphrase-two-2!", "Passphrase-three-3!"))
+        .await
+        .unwrap();
+    let reused = svc
+        .change_password(&actor, change("Passphrase-three-3!", "Passphrase-one-1!"))
+        .await;
+    assert!(matches!(reused, Err(AppError::Validation(_))), "{reused:?}");
+
+    // A fourth password pushes the first one out of the last three.
+    svc.change_password(&actor, change("Passphrase-three-3!", "Passphrase-four-4!"))
+        .await
+        .unwrap();
+    svc.change_password(&actor, change("Passphrase-four-4!", "Passphrase-one-1!"))
+        .await
+        .expect("passwords older than the history depth may be reused");
+}
+
+/// Breach checker that knows one breached password and fails lookups of
+/// another.
+struct KnownBreaches;
+
+impl mokkan_core::application::ports::security::PasswordBreachChecker for KnownBreaches {
+    fn breach_count<'a>(
+        &'a self,
+        password: &'a str,
+    ) -> BoxFuture<'a, mokkan_core::application::AppResult<u64>> {
+        boxed(async move {
+            match password {
+                "Breached-Passw0rd!" => Ok(42),
+                "Lookup-Fails-0!" => Err(AppError::infrastructure("breach service unreachable")),
+                _ => Ok(0),
+            }
+        })
+    }
+}
+
+/// 漏洩済みのパスワードは登録・変更時に拒否され、照会の失敗では拒否しない
+#[tokio::test]
+async fn breached_passwords_are_refused() {
+    let admin = User {
+        id: UserId::new(1).unwrap(),
+        username: Username::new("alice").unwrap(),
+        password_hash: PasswordHash::new("hash".to_string()).unwrap(),
+        role: Role::Admin,
+        is_active: true,
+        created_at: Utc::now(),
+        timezone: None,
+        password_reset_required: false,
+        email: None,
+        email_verified: false,
+    };
+    let repo = Arc::new(InMemoryUserRepo::new(HashMap::from([(1, admin)])));
+    let svc = UserCommandService::new(
+        repo,
+        Arc::new(support::DummyPasswordHasher),
+        Arc::new(support::DummyTokenManager),
+        Arc::new(
+            mokkan_core::infrastructure::security::refresh_token::HmacRefreshTokenCodec::new(
+                "test-refresh-secret",
+            )
+            .expect("refresh token codec"),
+        ),
+        Arc::new(
+            mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(),
+        ),
+        Arc::new(support::DummyClock),
+    )
+    .with_breach_checker(Arc::new(KnownBreaches));
+    let actor = AuthenticatedUser {
+        id: UserId::new(1).unwrap(),
+        username: "alice".into(),
+        role: Role::Admin,
+        capabilities: Role::Admin.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    };
+    let register = |username: &str, password: &str| RegisterUserCommand {
+        username: username.into(),
+        password: password.into(),
+        role: None,
+    };
+
+    let breached = svc
+        .register(Some(&actor), register("bob", "Breached-Passw0rd!"))
+        .await;
+    assert!(
+        matches!(breached, Err(AppError::Validation(_))),
+        "{breached:?}"
+    );
+    svc.register(Some(&actor), register("bob", "Str0ng!Password"))
+        .await
+        .expect("passwords not in any breach are accepted");
+    svc.register(Some(&actor), register("cleo", "Lookup-Fails-0!"))
+        .await
+        .expect("failed lookups do not block registration");
+
+    let changed = svc
+        .change_password(
+            &actor,
+            ChangePasswordCommand {
+                user_id: 1,
+                current_password: Some("whatever".into()),
+                new_password: "Breached-Passw0rd!".into(),
+            },
+        )
+        .await;
+    assert!(
+        matches!(changed, Err(AppError::Validation(_))),
+        "{changed:?}"
+    );
+}
+
+/// 退会すると即座に無効化され、猶予期間中は管理者が取り消せ、期間後に匿名化される
+#[tokio::test]
+async fn account_deletion_disables_then_anonymizes_the_account() {
+    use mokkan_core::domain::{
+        ArticleBody, ArticleReadRepository, ArticleSlug, ArticleTitle, ArticleVisibility,
+        ArticleWriteRepository, CustomFields, NewArticle,
+    };
+    use mokkan_core::infrastructure::repositories::memory;
+
+    let users = [
+        member(1, "alice", Role::Admin, "Admin-Passw0rd!"),
+        member(2, "bob", Role::Author, "Bobs-Passw0rd!"),
+        member(3, "cleo", Role::Author, "Cleos-Passw0rd!"),
+    ];
+    let (admin, bob) = (actor_for(&users[0]), actor_for(&users[1]));
+    let repo = Arc::new(InMemoryUserRepo::new(
+        users.map(|user| (i64::from(user.id), user)).into(),
+    ));
+    let articles = Arc::new(memory::InMemoryArticleRepository::new());
+    let article = articles
+        .insert(NewArticle {
+            title: ArticleTitle::new("Farewell").unwrap(),
+            slug: ArticleSlug::new("farewell").unwrap(),
+            body: ArticleBody::new("So long").unwrap(),
+            tags: Vec::new(),
+            custom_fields: CustomFields::default(),
+            published: false,
+            published_at: None,
+            visibility: ArticleVisibility::Public,
+            author_id: bob.id,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+    let clock = SimulatedClock::new();
+    let svc = user_command_service(
+        Arc::clone(&repo) as Arc<dyn UserRepository>,
+        Arc::new(support::DummyTokenManager),
+        Arc::new(
+            mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(),
+        ),
+        Arc::new(clock.clone()),
+    )
+    .with_account_deletion(
+        Arc::new(memory::InMemoryAccountDeletions::new()),
+        Arc::clone(&articles) as Arc<dyn ArticleWriteRepository>,
+        Duration::days(30),
+    );
+    let delete = |password: &str, transfer_articles_to| DeleteAccountCommand {
+        current_password: password.into(),
+        transfer_articles_to,
+    };
+    let bob_user = || async { repo.find_by_id(bob.id).await.unwrap().unwrap() };
+
+    let wrong = svc.delete_account(&bob, delete("guess", None)).await;
+    assert!(matches!(wrong, Err(AppError::Unauthorized(_))), "{wrong:?}");
+    let to_self = svc
+        .delete_account(&bob, delete("Bobs-Passw0rd!", Some(2)))
+        .await;
+    assert!(
+        matches!(to_self, Err(AppError::Validation(_))),
+        "{to_self:?}"
+    );
+
+    svc.delete_account(&bob, delete("Bobs-Passw0rd!", Some(3)))
+        .await
+        .unwrap();
+    assert!(!bob_user().await.is_active);
+    let by_author = svc.cancel_account_deletion(&bob, 2).await;
+    assert!(
+        matches!(by_author, Err(AppError::Forbidden(_))),
+        "{by_author:?}"
+    );
+    svc.cancel_account_deletion(&admin, 2).await.unwrap();
+    assert!(bob_user().await.is_active);
+    let again = svc.cancel_account_deletion(&admin, 2).await;
+    assert!(matches!(again, Err(AppError::NotFound(_))), "{again:?}");
+
+    let scheduled = svc
+        .delete_account(&bob, delete("Bobs-Passw0rd!", Some(3)))
+        .await
+        .unwrap();
+    assert_eq!(
+        scheduled.purge_after - scheduled.requested_at,
+        Duration::days(30)
+    );
+    clock.advance(Duration::days(29));
+    assert_eq!(svc.purge_due_account_deletions().await.unwrap(), 0);
+    clock.advance(Duration::days(2));
+    assert_eq!(svc.purge_due_account_deletions().await.unwrap(), 1);
+
+    let purged = bob_user().await;
+    assert_eq!(purged.username.as_str(), "deleted-user-2");
+    assert!(purged.email.is_none());
+    assert!(!purged.is_active);
+    assert_ne!(purged.password_hash.as_str(), "hash::Bobs-Passw0rd!");
+    let moved = articles.find_by_id(article.id).await.unwrap().unwrap();
+    assert_eq!(moved.author_id, UserId::new(3).unwrap());
+    assert_eq!(svc.purge_due_account_deletions().await.unwrap(), 0);
+}
+
+struct ImpersonationFixture {
+    svc: UserCommandService,
+    tokens: Arc<mokkan_core::infrastructure::security::token::BiscuitTokenManager>,
+    audit: Arc<mokkan_core::infrastructure::repositories::memory::InMemoryAuditLogRepository>,
+    sessions:
+        Arc<mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore>,
+    roles: Arc<mokkan_core::infrastructure::repositories::memory::InMemoryRoleStore>,
+    /// alice, an admin who may impersonate.
+    admin: AuthenticatedUser,
+}
+
+/// alice (admin), bob (author) and dana (admin), with custom roles enabled.
+fn impersonation_fixture() -> ImpersonationFixture {
+    use mokkan_core::application::ports::security::TokenManager;
+    use mokkan_core::application::ports::session_revocation::Store;
+    use mokkan_core::domain::audit::repository::AuditLogRepository;
+    use mokkan_core::domain::{Capability, RoleStore};
+    use mokkan_core::infrastructure::repositories::memory::{
+        InMemoryAuditLogRepository, InMemoryRoleStore,
+    };
+    use mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore;
+    use mokkan_core::infrastructure::security::token::BiscuitTokenManager;
+
+    let users = [
+        member(1, "alice", Role::Admin, "Admin-Passw0rd!"),
+        member(2, "bob", Role::Author, "Bobs-Passw0rd!"),
+        member(3, "dana", Role::Admin, "Danas-Passw0rd!"),
+    ];
+    let mut admin = actor_for(&users[0]);
+    admin
+        .capabilities
+        .insert(Capability::new("users", "impersonate"));
+    let repo = Arc::new(InMemoryUserRepo::new(
+        users.map(|user| (i64::from(user.id), user)).into(),
+    ));
+    let tokens = Arc::new(
+        BiscuitTokenManager::new(&"a".repeat(64), std::time::Duration::from_hours(1)).unwrap(),
+    );
+    let audit = Arc::new(InMemoryAuditLogRepository::new());
+    let sessions = Arc::new(InMemorySessionRevocationStore::new());
+    let roles = Arc::new(InMemoryRoleStore::new());
+    let svc = user_command_service(
+        Arc::clone(&repo) as Arc<dyn UserRepository>,
+        Arc::clone(&tokens) as Arc<dyn TokenManager>,
+        Arc::clone(&sessions) as Arc<dyn Store>,
+        Arc::new(SimulatedClock::new()),
+    )
+    .with_audit(Arc::clone(&audit) as Arc<dyn AuditLogRepository>)
+    .with_custom_roles(Arc::clone(&roles) as Arc<dyn RoleStore>);
+    ImpersonationFixture {
+        svc,
+        tokens,
+        audit,
+        sessions,
+        roles,
+        admin,
+    }
+}
+
+/// なりすましトークンは短命で管理者を記録し、その間の監査ログは両者を残す
+#[tokio::test]
+async fn impersonation_tokens_record_the_admin_behind_them() {
+    use mokkan_core::application::ports::security::TokenManager;
+    use mokkan_core::application::ports::session_revocation::SessionMetadataStore;
+    use mokkan_core::application::request_context::{self, RequestContext};
+    use mokkan_core::domain::Capability;
+    use mokkan_core::domain::audit::repository::AuditLogRepository;
+
+    let fx = impersonation_fixture();
+    let from = client("198.51.100.4");
+    let issued = fx.svc.impersonate(&fx.admin, 2, &from).await.unwrap();
+    assert!(issued.refresh_token.is_none());
+    assert_eq!(issued.expires_in, 15 * 60);
+    let mut bob = fx.tokens.authenticate(&issued.token).await.unwrap();
+    assert_eq!(i64::from(bob.id), 2);
+    assert_eq!(bob.impersonator, Some(fx.admin.id));
+    let session_id = bob.session_id.clone().unwrap();
+    assert_eq!(
+        fx.sessions.list_sessions_for_user(2).await.unwrap(),
+        std::slice::from_ref(&session_id)
+    );
+    let session = fx
+        .sessions
+        .get_session_metadata(&session_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(session.ip_address.as_deref(), Some("198.51.100.4"));
+
+    bob.capabilities
+        .insert(Capability::new("users", "impersonate"));
+    let chained = fx.svc.impersonate(&bob, 3, &from).await;
+    assert!(
+        matches!(chained, Err(AppError::Forbidden(_))),
+        "{chained:?}"
+    );
+
+    request_context::scope(RequestContext::default(), async {
+        request_context::set_impersonator(bob.impersonator);
+        fx.svc
+            .update_preferences(
+                &bob,
+                UpdatePreferencesCommand {
+                    timezone: Some("Asia/Tokyo".into()),
+                },
+            )
+            .await
+            .unwrap();
+    })
+    .await;
+
+    let (logs, _) = fx.audit.list(10, None).await.unwrap();
+    let entries: Vec<_> = logs
+        .iter()
+        .map(|log| {
+            (
+                log.action.as_str(),
+                log.user_id.map(i64::from),
+                log.impersonator_id.map(i64::from),
+            )
+        })
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            ("user.preferences_update", Some(2), Some(1)),
+            ("user.impersonate", Some(1), None),
+        ]
+    );
+}
+
+/// 自分にない権限を持つ利用者や、なりすまし権限を持つ利用者にはなりすませない
+#[tokio::test]
+async fn impersonation_never_reaches_beyond_the_impersonator() {
+    use mokkan_core::domain::{Capability, RoleStore};
+
+    let fx = impersonation_fixture();
+    let from = client("198.51.100.4");
+    let mut author = fx.admin.clone();
+    author.capabilities = Role::Author.default_capabilities();
+    let without = fx.svc.impersonate(&author, 2, &from).await;
+    assert!(
+        matches!(without, Err(AppError::Forbidden(_))),
+        "{without:?}"
+    );
+    author
+        .capabilities
+        .insert(Capability::new("users", "impersonate"));
+    let wider = fx.svc.impersonate(&author, 3, &from).await;
+    assert!(matches!(wider, Err(AppError::Forbidden(_))), "{wider:?}");
+
+    fx.svc.impersonate(&fx.admin, 3, &from).await.unwrap();
+    fx.roles
+        .grant_capability(
+            UserId::new(3).unwrap(),
+            &Capability::new("users", "impersonate"),
+        )
+        .await
+        .unwrap();
+    let impersonator = fx.svc.impersonate(&fx.admin, 3, &from).await;
+    assert!(
+        matches!(impersonator, Err(AppError::Forbidden(_))),
+        "{impersonator:?}"
+    );
+}
+
+/// 個別に付与した権限は次のログインからロールの既定権限に加わり、取り消すと外れる
+#[tokio::test]
+async fn granted_capabilities_join_the_role_defaults_at_login() {
+    use mokkan_core::application::ports::security::TokenManager;
+    use mokkan_core::application::services::{
+        GrantCapabilityCommand, RevokeCapabilityCommand, RoleService,
+    };
+    use mokkan_core::domain::RoleStore;
+    use mokkan_core::infrastructure::repositories::memory::InMemoryRoleStore;
+    use mokkan_core::infrastructure::security::token::BiscuitTokenManager;
+
+    let users = [
+        member(1, "alice", Role::Admin, "Admin-Passw0rd!"),
+        member(2, "bob", Role::Author, "Bobs-Passw0rd!"),
+    ];
+    let (admin, bob) = (actor_for(&users[0]), actor_for(&users[1]));
+    let repo = Arc::new(InMemoryUserRepo::new(
+        users.map(|user| (i64::from(user.id), user)).into(),
+    ));
+    let roles = Arc::new(InMemoryRoleStore::new());
+    let tokens = Arc::new(
+        BiscuitTokenManager::new(&"a".repeat(64), std::time::Duration::from_hours(1)).unwrap(),
+    );
+    let clock = Arc::new(SimulatedClock::new());
+    let role_svc = RoleService::new(
+        Arc::clone(&roles) as Arc<dyn RoleStore>,
+        Arc::clone(&repo) as Arc<dyn UserRepository>,
+        Arc::clone(&clock) as Arc<dyn Clock>,
+    );
+    let svc = user_command_service(
+        Arc::clone(&repo) as Arc<dyn UserRepository>,
+        Arc::clone(&tokens) as Arc<dyn TokenManager>,
+        Arc::new(
+            mokkan_core::infrastructure::security::session_store::InMemorySessionRevocationStore::new(),
+        ),
+        clock,
+    )
+    .with_custom_roles(roles);
+    let grant = |capability: &str| GrantCapabilityCommand {
+        user_id: 2,
+        capability: capability.into(),
+    };
+    let revoke = || RevokeCapabilityCommand {
+        user_id: 2,
+        capability: "audit:read".into(),
+    };
+    let login_as_bob = || async {
+        let token = svc
+            .login(LoginUserCommand {
+                username: "bob".into(),
+                password: "Bobs-Passw0rd!".into(),
+            })
+            .await
+            .unwrap()
+            .token
+            .token;
+        tokens.authenticate(&token).await.unwrap()
+    };
+
+    let by_author = role_svc.grant_capability(&bob, grant("audit:read")).await;
+    assert!(
+        matches!(by_author, Err(AppError::Forbidden(_))),
+        "{by_author:?}"
+    );
+    let unknown = role_svc
+        .grant_capability(&admin, grant("articles:launch"))
+        .await;
+    assert!(
+        matches!(unknown, Err(AppError::Validation(_))),
+        "{unknown:?}"
+    );
+
+    let granted = role_svc
+        .grant_capability(&admin, grant("audit:read"))
+        .await
+        .unwrap();
+    assert_eq!(granted.capabilities.len(), 1);
+    let user = login_as_bob().await;
+    assert!(user.has_capability("audit", "read"));
+    assert!(user.has_capability("articles", "create"));
+
+    let revoked = role_svc.revoke_capability(&admin, revoke()).await.unwrap();
+    assert!(revoked.capabilities.is_empty());
+    assert!(!login_as_bob().await.has_capability("audit", "read"));
+    let again = role_svc.revoke_capability(&admin, revoke()).await;
+    assert!(matches!(again, Err(AppError::NotFound(_))), "{again:?}");
+}
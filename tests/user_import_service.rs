@@ -0,0 +1,262 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/user_import_service.rs
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, Utc};
+use mokkan_core::application::services::{UserImportCommand, UserImportService};
+use mokkan_core::application::{
+    AuthenticatedUser, DuplicateStrategy, ImportJobStatus, ImportRowStatus, UserImportJobDto,
+};
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::UserRepository;
+use mokkan_core::domain::errors::{DomainError, DomainResult};
+use mokkan_core::domain::user::entity::{NewUser, User, UserUpdate};
+use mokkan_core::domain::user::value_objects::{
+    PasswordHash, Role, UserId, UserListCursor, Username,
+};
+use mokkan_core::infrastructure::jobs::TokioJobQueue;
+
+mod support;
+
+/// In-memory user repo that assigns sequential ids on insert.
+#[derive(Default)]
+struct InMemoryUserRepo {
+    users: Mutex<Vec<User>>,
+}
+
+impl InMemoryUserRepo {
+    fn with_user(username: &str, role: Role) -> Self {
+        let repo = Self::default();
+        repo.users.lock().unwrap().push(User {
+            id: UserId::new(1).unwrap(),
+            username: Username::new(username).unwrap(),
+            password_hash: PasswordHash::new("existing").unwrap(),
+            role,
+            is_active: true,
+            created_at: Utc::now(),
+            timezone: None,
+            password_reset_required: false,
+            email: None,
+            email_verified: false,
+        });
+        repo
+    }
+
+    fn get(&self, username: &str) -> Option<User> {
+        self.users
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|user| user.username.as_str() == username)
+            .cloned()
+    }
+}
+
+impl UserRepository for InMemoryUserRepo {
+    fn count(&self) -> BoxFuture<'_, DomainResult<u64>> {
+        boxed(async move { Ok(self.users.lock().unwrap().len() as u64) })
+    }
+
+    fn insert(&self, new_user: NewUser) -> BoxFuture<'_, DomainResult<User>> {
+        boxed(async move {
+            let mut users = self.users.lock().unwrap();
+            let id = i64::try_from(users.len()).unwrap() + 1;
+            let user = User {
+                id: UserId::new(id)?,
+                username: new_user.username,
+                password_hash: new_user.password_hash,
+                role: new_user.role,
+                is_active: new_user.is_active,
+                created_at: new_user.created_at,
+                timezone: None,
+                password_reset_required: new_user.password_reset_required,
+                email: None,
+                email_verified: false,
+            };
+            users.push(user.clone());
+            drop(users);
+            Ok(user)
+        })
+    }
+
+    fn insert_bootstrap_admin(
+        &self,
+        _new_user: NewUser,
+    ) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        boxed(async move { Ok(None) })
+    }
+
+    fn find_by_username<'a>(
+        &'a self,
+        username: &'a Username,
+    ) -> BoxFuture<'a, DomainResult<Option<User>>> {
+        boxed(async move { Ok(self.get(username.as_str())) })
+    }
+
+    fn find_by_id(&self, id: UserId) -> BoxFuture<'_, DomainResult<Option<User>>> {
+        boxed(async move {
+            Ok(self
+                .users
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|user| user.id == id)
+                .cloned())
+        })
+    }
+
+    fn update(&self, update: UserUpdate) -> BoxFuture<'_, DomainResult<User>> {
+        boxed(async move {
+            let mut users = self.users.lock().unwrap();
+            let user = users
+                .iter_mut()
+                .find(|user| user.id == update.id)
+                .ok_or_else(|| DomainError::NotFound("user not found".into()))?;
+            if let Some(role) = update.role {
+                user.role = role;
+            }
+            if let Some(password_hash) = update.password_hash {
+                user.password_hash = password_hash;
+            }
+            if let Some(required) = update.password_reset_required {
+                user.password_reset_required = required;
+            }
+            let user = user.clone();
+            drop(users);
+            Ok(user)
+        })
+    }
+
+    fn list_page<'a>(
+        &'a self,
+        _limit: u32,
+        _cursor: Option<UserListCursor>,
+        _search: Option<&'a str>,
+    ) -> BoxFuture<'a, DomainResult<(Vec<User>, Option<UserListCursor>)>> {
+        boxed(async move { Ok((vec![], None)) })
+    }
+}
+
+fn actor(role: Role) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(1).unwrap(),
+        username: "admin".into(),
+        role,
+        capabilities: role.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+fn service(repo: &Arc<InMemoryUserRepo>) -> UserImportService {
+    UserImportService::new(
+        Arc::clone(repo) as Arc<dyn UserRepository>,
+        Arc::new(support::DummyPasswordHasher),
+        Arc::new(support::DummyClock),
+        Arc::new(TokioJobQueue),
+    )
+}
+
+fn command(csv: &str, on_duplicate: DuplicateStrategy) -> UserImportCommand {
+    UserImportCommand {
+        csv: csv.into(),
+        on_duplicate,
+        force_password_reset: false,
+    }
+}
+
+async fn wait_for(svc: &UserImportService, id: &str) -> UserImportJobDto {
+    let admin = actor(Role::Admin);
+    for _ in 0..200 {
+        let job = svc.job(&admin, id).expect("job exists");
+        if job.status == ImportJobStatus::Completed {
+            return job;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+    panic!("import job {id} did not complete");
+}
+
+const CSV: &str = "username,role,password_hash\n\
+                   alice,author,\n\
+                   existing,admin,$argon2id$v=19$migrated\n\
+                   bob,editor,\n\
+                   carol,author,md5:abc\n";
+
+#[tokio::test]
+async fn import_reports_per_row_results() {
+    let repo = Arc::new(InMemoryUserRepo::with_user("existing", Role::Author));
+    let svc = service(&repo);
+
+    let queued = svc
+        .submit(&actor(Role::Admin), &command(CSV, DuplicateStrategy::Skip))
+        .expect("valid CSV is accepted");
+    assert_eq!(queued.total_rows, 4);
+
+    let job = wait_for(&svc, &queued.id).await;
+    let statuses: Vec<_> = job.results.iter().map(|row| row.status).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ImportRowStatus::Created,
+            ImportRowStatus::Skipped,
+            ImportRowStatus::Failed,
+            ImportRowStatus::Failed,
+        ]
+    );
+    assert_eq!(job.processed_rows, 4);
+    assert_eq!(job.results[2].line, 4);
+    assert!(job.finished_at.is_some());
+
+    let alice = repo.get("alice").expect("alice imported");
+    assert!(
+        alice.password_reset_required,
+        "users without a migrated hash must reset"
+    );
+    assert_eq!(repo.get("existing").unwrap().role, Role::Author);
+}
+
+#[tokio::test]
+async fn duplicate_strategies_fail_or_update_existing_users() {
+    let repo = Arc::new(InMemoryUserRepo::with_user("existing", Role::Author));
+    let svc = service(&repo);
+    let csv = "username,role,password_hash\nexisting,admin,$argon2id$v=19$migrated\n";
+
+    let failed = svc
+        .submit(&actor(Role::Admin), &command(csv, DuplicateStrategy::Fail))
+        .unwrap();
+    let failed = wait_for(&svc, &failed.id).await;
+    assert_eq!(failed.results[0].status, ImportRowStatus::Failed);
+    assert_eq!(repo.get("existing").unwrap().role, Role::Author);
+
+    let updated = svc
+        .submit(
+            &actor(Role::Admin),
+            &command(csv, DuplicateStrategy::Update),
+        )
+        .unwrap();
+    let updated = wait_for(&svc, &updated.id).await;
+    assert_eq!(updated.results[0].status, ImportRowStatus::Updated);
+    let existing = repo.get("existing").unwrap();
+    assert_eq!(existing.role, Role::Admin);
+    assert_eq!(existing.password_hash.as_str(), "$argon2id$v=19$migrated");
+    assert!(!existing.password_reset_required);
+}
+
+#[tokio::test]
+async fn import_requires_user_creation_capability() {
+    let repo = Arc::new(InMemoryUserRepo::default());
+    let svc = service(&repo);
+    let err = svc
+        .submit(&actor(Role::Author), &command(CSV, DuplicateStrategy::Skip))
+        .expect_err("authors cannot import users");
+    assert!(matches!(
+        err,
+        mokkan_core::application::AppError::Forbidden(_)
+    ));
+}
@@ -0,0 +1,372 @@
+#![allow(clippy::multiple_crate_versions)]
+
+// tests/webhook_delivery.rs
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use mokkan_core::application::commands::articles::{
+    ArticleCommandService, CreateArticleCommand, SetPublishStateCommand,
+};
+use mokkan_core::application::ports::domain_events::{EventHandler, EventPublisher};
+use mokkan_core::application::ports::jobs::JobQueue;
+use mokkan_core::application::ports::webhooks::{WebhookRequest, WebhookSender};
+use mokkan_core::application::services::{
+    CreateWebhookCommand, DomainEventBus, WebhookPolicy, WebhookService,
+};
+use mokkan_core::application::{AppError, AppResult, ArticleDto, AuthenticatedUser, WebhookDto};
+use mokkan_core::async_support::{BoxFuture, boxed};
+use mokkan_core::domain::article::services::ArticleSlugService;
+use mokkan_core::domain::{ArticleVisibility, Role, UserId, WebhookEvent, WebhookUpdate};
+use mokkan_core::infrastructure::repositories::InMemoryStores;
+use mokkan_core::infrastructure::time::SimulatedClock;
+
+mod support;
+
+/// Holds queued jobs until the test runs them.
+#[derive(Default)]
+struct ManualQueue(Mutex<Vec<BoxFuture<'static, ()>>>);
+
+impl ManualQueue {
+    fn pending(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    /// Run the jobs queued so far; jobs they queue wait for the next call.
+    async fn run_pending(&self) {
+        let jobs = std::mem::take(&mut *self.0.lock().unwrap());
+        for job in jobs {
+            job.await;
+        }
+    }
+}
+
+impl JobQueue for ManualQueue {
+    fn enqueue(&self, _name: &'static str, job: BoxFuture<'static, ()>) {
+        self.0.lock().unwrap().push(job);
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Sent {
+    url: String,
+    event: WebhookEvent,
+    delivery_id: String,
+    body: serde_json::Value,
+}
+
+/// Records requests and answers with `responses` in order, then 200.
+#[derive(Default)]
+struct CapturingSender {
+    sent: Mutex<Vec<Sent>>,
+    responses: Mutex<Vec<AppResult<u16>>>,
+}
+
+impl CapturingSender {
+    fn answering(responses: Vec<AppResult<u16>>) -> Self {
+        Self {
+            responses: Mutex::new(responses),
+            ..Self::default()
+        }
+    }
+
+    fn sent(&self) -> Vec<Sent> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+impl WebhookSender for CapturingSender {
+    fn send<'a>(&'a self, request: &'a WebhookRequest<'a>) -> BoxFuture<'a, AppResult<u16>> {
+        boxed(async move {
+            self.sent.lock().unwrap().push(Sent {
+                url: request.url.to_owned(),
+                event: request.event,
+                delivery_id: request.delivery_id.to_owned(),
+                body: serde_json::from_slice(request.body).unwrap(),
+            });
+            let mut responses = self.responses.lock().unwrap();
+            if responses.is_empty() {
+                Ok(200)
+            } else {
+                responses.remove(0)
+            }
+        })
+    }
+}
+
+struct Harness {
+    commands: ArticleCommandService,
+    webhooks: Arc<WebhookService>,
+    queue: Arc<ManualQueue>,
+    sender: Arc<CapturingSender>,
+}
+
+impl Harness {
+    fn new(sender: CapturingSender, max_attempts: u32) -> Self {
+        let stores = InMemoryStores::new();
+        let clock = Arc::new(SimulatedClock::new());
+        let queue = Arc::new(ManualQueue::default());
+        let sender = Arc::new(sender);
+        let webhooks = Arc::new(WebhookService::new(
+            stores.webhooks.clone(),
+            Some(sender.clone()),
+            queue.clone(),
+            clock.clone(),
+            WebhookPolicy {
+                max_attempts,
+                retry_backoff: Duration::ZERO,
+            },
+        ));
+        let slugs = Arc::new(ArticleSlugService::new(
+            stores.articles.clone(),
+            Arc::new(support::DummySlug),
+        ));
+        let commands = ArticleCommandService::new(
+            stores.articles.clone(),
+            stores.articles.clone(),
+            stores.articles,
+            slugs,
+            clock,
+        )
+        .with_event_publisher(Arc::new(
+            DomainEventBus::default().with_handler(webhooks.clone() as Arc<dyn EventHandler>),
+        ) as Arc<dyn EventPublisher>);
+        Self {
+            commands,
+            webhooks,
+            queue,
+            sender,
+        }
+    }
+
+    async fn subscribe(&self, url: &str, events: Vec<WebhookEvent>) -> WebhookDto {
+        self.webhooks
+            .create(
+                &user(1, Role::Admin),
+                CreateWebhookCommand {
+                    url: url.into(),
+                    secret: None,
+                    events,
+                },
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn create(&self, title: &str, publish: bool) -> ArticleDto {
+        let command = CreateArticleCommand::builder()
+            .title(title)
+            .body("body")
+            .publish(publish)
+            .build()
+            .unwrap();
+        self.commands
+            .create_article(&user(1, Role::Admin), command)
+            .await
+            .unwrap()
+    }
+}
+
+fn user(id: i64, role: Role) -> AuthenticatedUser {
+    AuthenticatedUser {
+        id: UserId::new(id).unwrap(),
+        username: format!("user{id}"),
+        role,
+        capabilities: role.default_capabilities(),
+        issued_at: Utc::now(),
+        expires_at: Utc::now() + chrono::Duration::hours(1),
+        session_id: None,
+        token_version: None,
+        custom_role: None,
+        impersonator: None,
+    }
+}
+
+/// 購読しているイベントだけが、記事の内容を載せて各 Webhook に送られる
+#[tokio::test]
+async fn subscribed_events_are_delivered_with_the_article() {
+    let harness = Harness::new(CapturingSender::default(), 3);
+    harness
+        .subscribe(
+            "https://a.example/hook",
+            vec![WebhookEvent::ArticlePublished],
+        )
+        .await;
+    harness
+        .subscribe(
+            "https://b.example/hook",
+            vec![WebhookEvent::ArticleCreated, WebhookEvent::ArticleDeleted],
+        )
+        .await;
+
+    let article = harness.create("launch", true).await;
+    assert_eq!(harness.queue.pending(), 2);
+    harness.queue.run_pending().await;
+    let sent = harness.sender.sent();
+    let events: Vec<_> = sent.iter().map(|s| (s.url.as_str(), s.event)).collect();
+    assert_eq!(
+        events,
+        vec![
+            ("https://b.example/hook", WebhookEvent::ArticleCreated),
+            ("https://a.example/hook", WebhookEvent::ArticlePublished),
+        ]
+    );
+    assert_eq!(sent[1].body["event"], "article.published");
+    assert_eq!(sent[1].body["article"]["id"], article.id);
+    assert_eq!(sent[1].body["article"]["slug"], "launch");
+    assert_eq!(sent[1].body["article"]["published"], true);
+    assert_ne!(sent[0].delivery_id, sent[1].delivery_id);
+
+    harness
+        .commands
+        .set_publish_state(
+            &user(1, Role::Admin),
+            SetPublishStateCommand {
+                id: article.id,
+                publish: false,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(harness.queue.pending(), 0, "nobody follows unpublishing");
+}
+
+/// 非公開記事のイベントは購読していても Webhook に送られない
+#[tokio::test]
+async fn private_articles_are_never_delivered() {
+    let harness = Harness::new(CapturingSender::default(), 3);
+    harness
+        .subscribe(
+            "https://a.example/hook",
+            vec![WebhookEvent::ArticleCreated, WebhookEvent::ArticlePublished],
+        )
+        .await;
+
+    let command = CreateArticleCommand::builder()
+        .title("secret")
+        .body("body")
+        .publish(true)
+        .visibility(ArticleVisibility::Private)
+        .build()
+        .unwrap();
+    harness
+        .commands
+        .create_article(&user(1, Role::Admin), command)
+        .await
+        .unwrap();
+    assert_eq!(harness.queue.pending(), 0);
+
+    harness.create("open", true).await;
+    harness.queue.run_pending().await;
+    assert_eq!(harness.sender.sent().len(), 2);
+}
+
+/// 失敗した配信は同じ delivery id で再試行され、すべての試行が履歴に残る
+#[tokio::test]
+async fn failed_deliveries_are_retried_and_recorded() {
+    let sender = CapturingSender::answering(vec![
+        Ok(503),
+        Err(AppError::infrastructure("connection refused")),
+    ]);
+    let harness = Harness::new(sender, 3);
+    let webhook = harness
+        .subscribe("https://a.example/hook", vec![WebhookEvent::ArticleCreated])
+        .await;
+    harness.create("flaky", false).await;
+    for _ in 0..3 {
+        harness.queue.run_pending().await;
+    }
+    assert_eq!(harness.queue.pending(), 0);
+
+    let sent = harness.sender.sent();
+    assert_eq!(sent.len(), 3);
+    assert!(sent.iter().all(|s| s.delivery_id == sent[0].delivery_id));
+
+    let history = harness
+        .webhooks
+        .deliveries(&user(1, Role::Admin), webhook.id, None)
+        .await
+        .unwrap();
+    let tries: Vec<_> = history
+        .iter()
+        .map(|a| (a.attempt, a.status_code, a.succeeded))
+        .collect();
+    assert_eq!(
+        tries,
+        vec![
+            (3, Some(200), true),
+            (2, None, false),
+            (1, Some(503), false)
+        ]
+    );
+    assert_eq!(
+        history[1].error.as_deref(),
+        Some("infrastructure failure: connection refused")
+    );
+}
+
+/// 上限に達した配信は諦め、無効化された Webhook にはそれ以上送らない
+#[tokio::test]
+async fn deliveries_stop_at_the_limit_or_when_deactivated() {
+    let sender = CapturingSender::answering(vec![Ok(500), Ok(500), Ok(500)]);
+    let harness = Harness::new(sender, 2);
+    harness
+        .subscribe("https://a.example/hook", vec![WebhookEvent::ArticleCreated])
+        .await;
+    harness.create("down", false).await;
+    harness.queue.run_pending().await;
+    harness.queue.run_pending().await;
+    assert_eq!(harness.queue.pending(), 0, "gave up after two attempts");
+    assert_eq!(harness.sender.sent().len(), 2);
+
+    let harness = Harness::new(CapturingSender::default(), 3);
+    let webhook = harness
+        .subscribe("https://a.example/hook", vec![WebhookEvent::ArticleCreated])
+        .await;
+    harness.create("quiet", false).await;
+    harness
+        .webhooks
+        .update(
+            &user(1, Role::Admin),
+            webhook.id,
+            WebhookUpdate {
+                active: Some(false),
+                ..WebhookUpdate::default()
+            },
+        )
+        .await
+        .unwrap();
+    harness.queue.run_pending().await;
+    assert!(harness.sender.sent().is_empty());
+}
+
+/// シークレットは作成時だけ返り、管理には webhooks:manage が必要
+#[tokio::test]
+async fn secrets_are_shown_once_and_management_needs_the_capability() {
+    let harness = Harness::new(CapturingSender::default(), 3);
+    let created = harness
+        .subscribe("https://a.example/hook", vec![WebhookEvent::ArticleUpdated])
+        .await;
+    assert!(created.secret.as_deref().is_some_and(|s| s.len() >= 16));
+    let admin = user(1, Role::Admin);
+    let fetched = harness.webhooks.get(&admin, created.id).await.unwrap();
+    assert!(fetched.secret.is_none());
+
+    let author = user(2, Role::Author);
+    assert!(matches!(
+        harness.webhooks.list(&author).await,
+        Err(AppError::Forbidden(_))
+    ));
+    let invalid = harness
+        .webhooks
+        .create(
+            &admin,
+            CreateWebhookCommand {
+                url: "https://a.example/hook".into(),
+                secret: Some("short".into()),
+                events: vec![WebhookEvent::ArticleUpdated],
+            },
+        )
+        .await;
+    assert!(invalid.is_err());
+}